@@ -0,0 +1,24 @@
+//! Criterion benchmarks for the orchestration hot paths.
+//!
+//! Runs the workloads from `crewai::bench` (shared with `crewai bench`):
+//!
+//! ```bash
+//! cargo bench --bench hot_paths
+//! # compare against a saved criterion baseline:
+//! cargo bench --bench hot_paths -- --save-baseline main
+//! cargo bench --bench hot_paths -- --baseline main
+//! ```
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn hot_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_paths");
+    for workload in crewai::bench::workloads() {
+        let mut routine = workload.routine();
+        group.bench_function(workload.name, |b| b.iter(&mut routine));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
@@ -9,12 +9,19 @@
 //! - HTTP Basic authentication
 //! - HTTP Digest authentication
 //! - mTLS (mutual TLS) client certificate authentication
+//!
+//! [`server`] holds the matching check for incoming requests to the A2A
+//! server endpoint.
 
 use std::collections::HashMap;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+pub mod server;
+
+pub use server::ServerAuth;
+
 // ---------------------------------------------------------------------------
 // TLS config
 // ---------------------------------------------------------------------------
@@ -0,0 +1,113 @@
+//! Server-side authentication for the A2A endpoint.
+//!
+//! The counterpart of the client schemes: [`ServerAuth`] checks the
+//! credentials on incoming JSON-RPC requests and describes itself as an
+//! OpenAPI security scheme for the agent card.
+
+use axum::http::HeaderMap;
+use serde_json::Value;
+
+/// Environment variable holding an accepted bearer token.
+pub const A2A_TOKEN_ENV: &str = "CREWAI_A2A_TOKEN";
+/// Environment variable holding an accepted API key (sent as `X-API-Key`).
+pub const A2A_API_KEY_ENV: &str = "CREWAI_A2A_API_KEY";
+
+/// Credentials accepted by the A2A server.
+#[derive(Clone)]
+pub enum ServerAuth {
+    /// `Authorization: Bearer <token>` with one of the tokens.
+    Bearer { tokens: Vec<String> },
+    /// One of the keys in the header `name`.
+    ApiKey { name: String, keys: Vec<String> },
+}
+
+impl std::fmt::Debug for ServerAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bearer { tokens } => f
+                .debug_struct("Bearer")
+                .field("tokens", &format!("<{} redacted>", tokens.len()))
+                .finish(),
+            Self::ApiKey { name, keys } => f
+                .debug_struct("ApiKey")
+                .field("name", name)
+                .field("keys", &format!("<{} redacted>", keys.len()))
+                .finish(),
+        }
+    }
+}
+
+impl ServerAuth {
+    /// Accept a single bearer token.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::Bearer {
+            tokens: vec![token.into()],
+        }
+    }
+
+    /// Accept a single API key in header `name`.
+    pub fn api_key(name: impl Into<String>, key: impl Into<String>) -> Self {
+        Self::ApiKey {
+            name: name.into(),
+            keys: vec![key.into()],
+        }
+    }
+
+    /// Configure from [`A2A_TOKEN_ENV`] or [`A2A_API_KEY_ENV`]; `None` when
+    /// neither is set (the endpoint is then unauthenticated).
+    pub fn from_env() -> Option<Self> {
+        let non_empty = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+        if let Some(token) = non_empty(A2A_TOKEN_ENV) {
+            return Some(Self::bearer(token));
+        }
+        non_empty(A2A_API_KEY_ENV).map(|key| Self::api_key("X-API-Key", key))
+    }
+
+    /// Whether the request headers carry accepted credentials.
+    pub fn verify(&self, headers: &HeaderMap) -> bool {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        match self {
+            Self::Bearer { tokens } => header("authorization")
+                .and_then(|v| {
+                    v.split_once(' ')
+                        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+                })
+                .is_some_and(|(_, token)| tokens.iter().any(|t| constant_time_eq(t, token.trim()))),
+            Self::ApiKey { name, keys } => {
+                header(name).is_some_and(|value| keys.iter().any(|k| constant_time_eq(k, value)))
+            }
+        }
+    }
+
+    /// `WWW-Authenticate` challenge for rejected requests.
+    pub fn challenge(&self) -> String {
+        match self {
+            Self::Bearer { .. } => "Bearer realm=\"a2a\"".to_string(),
+            Self::ApiKey { name, .. } => format!("ApiKey header=\"{}\"", name),
+        }
+    }
+
+    /// OpenAPI security scheme advertised in the agent card.
+    pub fn security_scheme(&self) -> Value {
+        match self {
+            Self::Bearer { .. } => serde_json::json!({
+                "type": "http",
+                "scheme": "bearer",
+            }),
+            Self::ApiKey { name, .. } => serde_json::json!({
+                "type": "apiKey",
+                "in": "header",
+                "name": name,
+            }),
+        }
+    }
+}
+
+/// Compare secrets without short-circuiting on the first differing byte.
+fn constant_time_eq(expected: &str, actual: &str) -> bool {
+    let (a, b) = (expected.as_bytes(), actual.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
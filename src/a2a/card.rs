@@ -0,0 +1,267 @@
+//! Agent cards generated from runtime configuration.
+//!
+//! Derives A2A [`AgentSkill`]s from what agents and crews are configured
+//! with: an agent offers one skill per tool and knowledge collection, and a
+//! crew one skill summarizing its agents' tools and knowledge, the input
+//! modes it accepts, the output modes its tasks produce and its rate limit.
+//! The server rebuilds its card from these on every discovery request, so
+//! the card follows configuration changes; [`AgentCard::to_json`] exports a
+//! card for external catalogs.
+
+use std::collections::BTreeSet;
+
+use super::client::{AgentCapabilities, AgentCard, AgentSkill, RateLimits};
+use crate::agent::Agent;
+use crate::crew::Crew;
+use crate::tools::registry::ToolRegistry;
+
+/// Plain text input or output.
+pub const TEXT_MODE: &str = "text/plain";
+/// Structured JSON input or output.
+pub const JSON_MODE: &str = "application/json";
+/// Image input, accepted by multimodal agents.
+pub const IMAGE_MODE: &str = "image/*";
+
+impl AgentCard {
+    /// The card as pretty-printed JSON, as served on the well-known
+    /// endpoint.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// Input modes `agent` accepts: text and JSON, plus images when it is
+/// multimodal.
+pub fn agent_input_modes(agent: &Agent) -> Vec<String> {
+    let mut modes = vec![TEXT_MODE.to_string(), JSON_MODE.to_string()];
+    if agent.multimodal {
+        modes.push(IMAGE_MODE.to_string());
+    }
+    modes
+}
+
+/// Names of the knowledge collections `agent` searches: its own
+/// collection, when it has knowledge sources.
+pub fn knowledge_collections(agent: &Agent) -> Vec<String> {
+    agent.knowledge_collection_name().into_iter().collect()
+}
+
+/// Advertised limit for a `max_rpm` setting (unset or non-positive means
+/// unlimited).
+fn rate_limits(max_rpm: Option<i32>) -> Option<RateLimits> {
+    let rpm = u32::try_from(max_rpm?).ok().filter(|&rpm| rpm > 0)?;
+    Some(RateLimits {
+        requests_per_minute: Some(rpm),
+    })
+}
+
+/// Skills of `agent`: one per tool (described from the agent's tool
+/// registry) and one per knowledge collection.
+pub fn agent_skills(agent: &Agent) -> Vec<AgentSkill> {
+    let registry = agent
+        .tool_registry
+        .as_ref()
+        .unwrap_or_else(|| ToolRegistry::global());
+    let tools = agent.tools.iter().map(|tool| AgentSkill {
+        id: format!("tool.{}", tool),
+        name: tool.clone(),
+        description: registry.description(tool),
+        input_modes: vec![JSON_MODE.to_string()],
+        output_modes: vec![TEXT_MODE.to_string()],
+        tags: vec!["tool".to_string()],
+        rate_limits: rate_limits(agent.max_rpm),
+    });
+    let knowledge = knowledge_collections(agent)
+        .into_iter()
+        .map(|collection| AgentSkill {
+            id: format!("knowledge.{}", collection),
+            description: Some(format!(
+                "Answer from the '{}' knowledge collection",
+                collection
+            )),
+            name: collection,
+            input_modes: vec![TEXT_MODE.to_string()],
+            output_modes: vec![TEXT_MODE.to_string()],
+            tags: vec!["knowledge".to_string()],
+            rate_limits: rate_limits(agent.max_rpm),
+        });
+    tools.chain(knowledge).collect()
+}
+
+/// Card describing `agent` alone, reachable at `url`.
+pub fn agent_card(agent: &Agent, url: impl Into<String>) -> AgentCard {
+    AgentCard {
+        name: agent.role.clone(),
+        description: Some(agent.goal.clone()),
+        url: url.into(),
+        version: Some(crate::VERSION.to_string()),
+        capabilities: AgentCapabilities {
+            streaming: false,
+            push_notifications: false,
+            multi_turn: true,
+        },
+        skills: agent_skills(agent),
+        provider: None,
+        default_input_modes: agent_input_modes(agent),
+        default_output_modes: vec![TEXT_MODE.to_string()],
+        security_schemes: Vec::new(),
+        extensions: Vec::new(),
+    }
+}
+
+/// Skill delegating to `crew`, registered as `name`.
+///
+/// Tags list the tools (`tool:<name>`) and knowledge collections
+/// (`knowledge:<name>`) of its agents and tasks. The crew's `max_rpm`, or
+/// else the lowest `max_rpm` of its agents, becomes the skill's rate limit.
+pub fn crew_skill(name: &str, crew: &Crew, description: Option<String>) -> AgentSkill {
+    let mut tools = BTreeSet::new();
+    let mut knowledge = BTreeSet::new();
+    let mut multimodal = false;
+    let mut agent_rpm: Option<i32> = None;
+    for role in &crew.agents {
+        let Some(agent) = crew.get_agent(role) else {
+            continue;
+        };
+        let Ok(agent) = agent.read() else {
+            continue;
+        };
+        tools.extend(agent.tools.iter().cloned());
+        knowledge.extend(knowledge_collections(&agent));
+        multimodal |= agent.multimodal;
+        agent_rpm = agent_rpm.into_iter().chain(agent.max_rpm).min();
+    }
+    tools.extend(
+        crew.tasks
+            .iter()
+            .flat_map(|task| task.tools.iter().cloned()),
+    );
+    knowledge.extend(crew.knowledge_collection_name());
+
+    let mut tags = vec!["crew".to_string()];
+    tags.extend(tools.iter().map(|tool| format!("tool:{}", tool)));
+    tags.extend(knowledge.iter().map(|c| format!("knowledge:{}", c)));
+
+    // Text is the `message` input and data parts become further inputs.
+    let mut input_modes = vec![TEXT_MODE.to_string(), JSON_MODE.to_string()];
+    if multimodal {
+        input_modes.push(IMAGE_MODE.to_string());
+    }
+    let mut output_modes = vec![TEXT_MODE.to_string()];
+    if crew
+        .tasks
+        .iter()
+        .any(|task| task.output_json.is_some() || task.output_pydantic.is_some())
+    {
+        output_modes.push(JSON_MODE.to_string());
+    }
+
+    AgentSkill {
+        id: name.to_string(),
+        name: name.to_string(),
+        description: description.or_else(|| {
+            let roles = crew.agents.join(", ");
+            Some(if roles.is_empty() {
+                format!("Delegate to the '{}' crew", name)
+            } else {
+                format!("Delegate to the '{}' crew ({})", name, roles)
+            })
+        }),
+        input_modes,
+        output_modes,
+        tags,
+        rate_limits: rate_limits(crew.max_rpm.or(agent_rpm)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knowledge::source::StringKnowledgeSource;
+    use crate::task::Task;
+    use crate::tools::base_tool::Tool;
+    use serde_json::Value;
+    use std::sync::Arc;
+
+    fn researcher() -> Agent {
+        let registry = ToolRegistry::new();
+        registry.register(Tool::new(
+            "search",
+            "Search the web",
+            Arc::new(|_| Ok(Value::Null)),
+        ));
+        let mut agent = Agent::new(
+            "Senior Researcher".into(),
+            "Find facts".into(),
+            "Curious".into(),
+        );
+        agent.tools = vec!["search".to_string()];
+        agent.tool_registry = Some(registry);
+        agent.max_rpm = Some(30);
+        agent.knowledge_sources = vec![Arc::new(StringKnowledgeSource::new(
+            "Transformers use attention.".into(),
+        ))];
+        agent
+    }
+
+    #[test]
+    fn test_agent_card_from_tools_and_knowledge() {
+        let mut agent = researcher();
+        agent.multimodal = true;
+        let card = agent_card(&agent, "https://agents.local/a2a");
+        let ids: Vec<&str> = card.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["tool.search", "knowledge.agent_Senior_Researcher"]);
+        assert_eq!(
+            card.skills[0].description.as_deref(),
+            Some("Search the web")
+        );
+        assert_eq!(
+            card.skills[0].rate_limits,
+            Some(RateLimits {
+                requests_per_minute: Some(30)
+            })
+        );
+        assert_eq!(card.default_input_modes.last().unwrap(), IMAGE_MODE);
+
+        let exported: Value = serde_json::from_str(&card.to_json()).unwrap();
+        assert_eq!(
+            exported["skills"][0]["rate_limits"]["requests_per_minute"],
+            30
+        );
+        assert_eq!(
+            exported["default_output_modes"],
+            serde_json::json!([TEXT_MODE])
+        );
+    }
+
+    #[test]
+    fn test_crew_skill_follows_configuration() {
+        let mut task = Task::new("Research {topic}".into(), "Report".into());
+        task.tools = vec!["calculator".to_string()];
+        let mut crew = Crew::with_agents(vec![task], vec![researcher()]);
+
+        let skill = crew_skill("research", &crew, None);
+        assert_eq!(
+            skill.description.as_deref(),
+            Some("Delegate to the 'research' crew (Senior Researcher)")
+        );
+        assert_eq!(
+            skill.tags,
+            [
+                "crew",
+                "tool:calculator",
+                "tool:search",
+                "knowledge:agent_Senior_Researcher"
+            ]
+        );
+        assert_eq!(skill.output_modes, [TEXT_MODE]);
+        assert_eq!(skill.rate_limits.unwrap().requests_per_minute, Some(30));
+
+        crew.max_rpm = Some(10);
+        crew.tasks[0].output_json = Some("Report".to_string());
+        let skill = crew_skill("research", &crew, Some("Researches".to_string()));
+        assert_eq!(skill.description.as_deref(), Some("Researches"));
+        assert_eq!(skill.output_modes, [TEXT_MODE, JSON_MODE]);
+        assert_eq!(skill.rate_limits.unwrap().requests_per_minute, Some(10));
+    }
+}
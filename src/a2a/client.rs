@@ -9,6 +9,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -38,6 +39,17 @@ pub struct AgentSkill {
     /// Tags for categorization.
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Rate limits applied when the skill is invoked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limits: Option<RateLimits>,
+}
+
+/// Rate limits advertised for a skill.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimits {
+    /// Maximum LLM requests per minute.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
 }
 
 /// Capabilities advertised by an A2A agent.
@@ -436,4 +448,133 @@ impl A2AClient {
 
         Ok(())
     }
+
+    /// Call a JSON-RPC method on the agent and return its `result`.
+    ///
+    /// JSON-RPC errors are returned as errors carrying the remote code and
+    /// message.
+    pub async fn call(&self, method: &str, params: Value) -> Result<Value, anyhow::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(self.timeout))
+            .build()?;
+        let resp = self
+            .rpc_request(&client, method, params)
+            .await?
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("A2A {} failed: HTTP {} — {}", method, status, body);
+        }
+        rpc_result(resp.json().await?)
+    }
+
+    /// Fetch a task's current state via JSON-RPC `tasks/get`.
+    pub async fn get_task(&self, task_id: &str) -> Result<A2ATask, anyhow::Error> {
+        let result = self
+            .call("tasks/get", serde_json::json!({ "task_id": task_id }))
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Send JSON-RPC `message/stream` and return the streamed results (the
+    /// task, then `status-update` / `artifact-update` events).
+    ///
+    /// Only the connection is bounded by [`timeout`](Self::timeout); the
+    /// stream itself stays open until the agent closes it.
+    pub async fn stream_message(
+        &self,
+        params: Value,
+    ) -> Result<impl Stream<Item = Result<Value, anyhow::Error>> + Send, anyhow::Error> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(self.timeout))
+            .build()?;
+        let resp = self
+            .rpc_request(&client, "message/stream", params)
+            .await?
+            .header("Accept", "text/event-stream")
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            anyhow::bail!("A2A message/stream failed: HTTP {} — {}", status, body);
+        }
+
+        // Split the body into SSE events and decode each `data:` payload.
+        let bytes = resp.bytes_stream();
+        Ok(futures::stream::unfold(
+            (bytes, String::new()),
+            |(mut bytes, mut buffer)| async move {
+                loop {
+                    if let Some(end) = buffer.find("\n\n") {
+                        let event: String = buffer.drain(..end + 2).collect();
+                        let data = event
+                            .lines()
+                            .filter_map(|l| l.strip_prefix("data:"))
+                            .map(str::trim_start)
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        if data.is_empty() {
+                            continue;
+                        }
+                        let item = serde_json::from_str(&data)
+                            .map_err(anyhow::Error::from)
+                            .and_then(rpc_result);
+                        return Some((item, (bytes, buffer)));
+                    }
+                    match bytes.next().await? {
+                        Ok(chunk) => {
+                            buffer.push_str(&String::from_utf8_lossy(&chunk).replace('\r', ""))
+                        }
+                        Err(e) => return Some((Err(e.into()), (bytes, String::new()))),
+                    }
+                }
+            },
+        ))
+    }
+
+    /// Build an authenticated JSON-RPC POST to the agent endpoint.
+    async fn rpc_request(
+        &self,
+        client: &reqwest::Client,
+        method: &str,
+        params: Value,
+    ) -> Result<reqwest::RequestBuilder, anyhow::Error> {
+        let url = format!("{}/a2a", self.endpoint.trim_end_matches('/'));
+        log::debug!("Calling A2A {} at: {}", method, url);
+        let rpc_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "id": uuid::Uuid::new_v4().to_string(),
+            "params": params,
+        });
+        let mut req = client
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .json(&rpc_body);
+        if let Some(ref auth) = self.auth {
+            let mut headers = HashMap::new();
+            auth.apply_auth(&mut headers)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            for (k, v) in &headers {
+                req = req.header(k.as_str(), v.as_str());
+            }
+        }
+        Ok(req)
+    }
+}
+
+/// The `result` of a JSON-RPC response, or its `error` as an error.
+fn rpc_result(response: Value) -> Result<Value, anyhow::Error> {
+    if let Some(error) = response.get("error") {
+        anyhow::bail!(
+            "A2A error {}: {}",
+            error["code"],
+            error["message"].as_str().unwrap_or("unknown error")
+        );
+    }
+    Ok(response.get("result").cloned().unwrap_or_default())
 }
@@ -0,0 +1,390 @@
+//! Delegating contract steps to remote A2A agents.
+//!
+//! [`StepDelegator`] is the client-side counterpart of `POST /execute`: it
+//! takes a [`StepDelegationRequest`], sends the step to a remote agent as an
+//! A2A message, follows the resulting task (streaming when the agent card
+//! advertises it, polling `tasks/get` otherwise) and maps the outcome back
+//! into a [`StepDelegationResponse`].
+//!
+//! The step input is sent as a `data` part, so a crew behind the remote
+//! agent receives its keys as kickoff inputs. The skill is taken from the
+//! step input's `skill_id`, or matched against the card's skills by the
+//! step type's sub-type (`crew.summarizer` → `summarizer`).
+//!
+//! Remote failures, timeouts and cancellation end up in the returned step's
+//! status; only transport and protocol errors are returned as errors.
+
+use std::cell::OnceCell;
+use std::future::Future;
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde_json::Value;
+
+use super::client::{A2AClient, A2ATask, A2ATaskState, A2ATaskStatus, AgentCard};
+use crate::contract::envelope::{from_crew_callback, to_task_input};
+use crate::contract::router::StepDomain;
+use crate::contract::types::{
+    DataEnvelope, StepDelegationRequest, StepDelegationResponse, StepStatus, UnifiedStep,
+};
+
+/// How a delegated task is followed until it finishes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DelegationMode {
+    /// Stream if the agent card advertises streaming, otherwise poll.
+    #[default]
+    Auto,
+    /// Use `message/stream`.
+    Stream,
+    /// Use non-blocking `message/send` and poll `tasks/get`.
+    Poll,
+}
+
+/// Delegates [`UnifiedStep`]s to a remote A2A agent.
+#[derive(Debug)]
+pub struct StepDelegator {
+    /// Client for the remote agent (its card is cached after discovery).
+    pub client: A2AClient,
+    /// How tasks are followed.
+    pub mode: DelegationMode,
+    /// Maximum time a delegated step may take before it is cancelled.
+    pub timeout: Duration,
+    /// Interval between `tasks/get` polls.
+    pub poll_interval: Duration,
+}
+
+/// How following a task ended.
+enum Outcome {
+    Finished(Box<A2ATask>),
+    TimedOut,
+    Cancelled,
+}
+
+impl StepDelegator {
+    /// Create a delegator with a 5 minute timeout and 1 second polling.
+    pub fn new(client: A2AClient) -> Self {
+        Self {
+            client,
+            mode: DelegationMode::Auto,
+            timeout: Duration::from_secs(300),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Builder: set how tasks are followed.
+    pub fn with_mode(mut self, mode: DelegationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Builder: set the per-step timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builder: set the polling interval.
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// The remote agent card, fetched on first use.
+    pub async fn discover(&mut self) -> Result<AgentCard, anyhow::Error> {
+        match &self.client.agent_card {
+            Some(card) => Ok(card.clone()),
+            None => self.client.get_agent_card().await,
+        }
+    }
+
+    /// Delegate a step and wait for its outcome.
+    pub async fn delegate(
+        &mut self,
+        request: StepDelegationRequest,
+    ) -> Result<StepDelegationResponse, anyhow::Error> {
+        self.delegate_until(request, std::future::pending()).await
+    }
+
+    /// Delegate a step, cancelling it (locally and on the remote agent) when
+    /// `cancel` resolves first.
+    pub async fn delegate_until(
+        &mut self,
+        request: StepDelegationRequest,
+        cancel: impl Future<Output = ()>,
+    ) -> Result<StepDelegationResponse, anyhow::Error> {
+        let card = self.discover().await?;
+        let mut step = request.step;
+        if step.status() != StepStatus::Running {
+            step.mark_running()?;
+        }
+        let params = message_params(&step, &request.input, &card);
+        let streaming = match self.mode {
+            DelegationMode::Auto => card.capabilities.streaming,
+            DelegationMode::Stream => true,
+            DelegationMode::Poll => false,
+        };
+
+        let task_id = OnceCell::new();
+        let follow = async {
+            if streaming {
+                self.follow_stream(params, &task_id).await
+            } else {
+                self.follow_polling(params, &task_id).await
+            }
+        };
+        let outcome = tokio::select! {
+            result = tokio::time::timeout(self.timeout, follow) => match result {
+                Ok(task) => Outcome::Finished(Box::new(task?)),
+                Err(_) => Outcome::TimedOut,
+            },
+            _ = cancel => Outcome::Cancelled,
+        };
+
+        if let (Outcome::TimedOut | Outcome::Cancelled, Some(id)) = (&outcome, task_id.get()) {
+            if let Err(e) = self.client.cancel_task(id).await {
+                log::warn!("Failed to cancel remote A2A task {}: {}", id, e);
+            }
+        }
+        Ok(self.response(step, outcome, task_id.get()))
+    }
+
+    /// Send with `message/stream` and fold the events into the task.
+    async fn follow_stream(
+        &self,
+        params: Value,
+        task_id: &OnceCell<String>,
+    ) -> Result<A2ATask, anyhow::Error> {
+        let mut events = Box::pin(self.client.stream_message(params).await?);
+        let mut task: Option<A2ATask> = None;
+        while let Some(event) = events.next().await {
+            let event = event?;
+            match (event["kind"].as_str(), task.as_mut()) {
+                (Some("task"), _) => {
+                    let started: A2ATask = serde_json::from_value(event)?;
+                    let _ = task_id.set(started.id.clone());
+                    task = Some(started);
+                }
+                (Some("status-update"), Some(task)) => {
+                    task.status = serde_json::from_value::<A2ATaskStatus>(event["status"].clone())?;
+                    if event["final"].as_bool() == Some(true) {
+                        break;
+                    }
+                }
+                (Some("artifact-update"), Some(task)) => {
+                    task.artifacts.push(event["artifact"].clone());
+                }
+                _ => {}
+            }
+        }
+        match task {
+            Some(task) if is_finished(&task.status.state) => Ok(task),
+            // The stream ended early; continue by polling.
+            Some(task) => self.poll(task).await,
+            None => anyhow::bail!("A2A stream ended before the task was created"),
+        }
+    }
+
+    /// Send with non-blocking `message/send`, then poll the task.
+    async fn follow_polling(
+        &self,
+        mut params: Value,
+        task_id: &OnceCell<String>,
+    ) -> Result<A2ATask, anyhow::Error> {
+        params["configuration"] = serde_json::json!({ "blocking": false });
+        let task: A2ATask =
+            serde_json::from_value(self.client.call("message/send", params).await?)?;
+        let _ = task_id.set(task.id.clone());
+        self.poll(task).await
+    }
+
+    async fn poll(&self, mut task: A2ATask) -> Result<A2ATask, anyhow::Error> {
+        while !is_finished(&task.status.state) {
+            tokio::time::sleep(self.poll_interval).await;
+            task = self.client.get_task(&task.id).await?;
+        }
+        Ok(task)
+    }
+
+    /// Update the step from the outcome and wrap it in a response.
+    fn response(
+        &self,
+        mut step: UnifiedStep,
+        outcome: Outcome,
+        task_id: Option<&String>,
+    ) -> StepDelegationResponse {
+        let error = match outcome {
+            Outcome::Finished(task) if task.status.state == A2ATaskState::Completed => {
+                let output = serde_json::json!({
+                    "result": result_text(&task),
+                    "task_id": task.id,
+                });
+                if let Err(e) = step.mark_completed(output.clone()) {
+                    log::warn!("delegate: {}", e);
+                }
+                return StepDelegationResponse {
+                    output: from_crew_callback(output, &step.step_id, 1.0),
+                    step: Some(step),
+                };
+            }
+            Outcome::Finished(task) if task.status.state == A2ATaskState::Canceled => {
+                "Remote agent canceled the task".to_string()
+            }
+            Outcome::Finished(task) if task.status.state == A2ATaskState::InputRequired => {
+                "Remote agent requires input, which delegated steps cannot provide".to_string()
+            }
+            Outcome::Finished(task) => {
+                let message = status_text(&task.status);
+                format!("Remote agent failed: {}", message.unwrap_or("no details"))
+            }
+            Outcome::TimedOut => format!(
+                "Remote agent did not finish within {}s",
+                self.timeout.as_secs_f64()
+            ),
+            Outcome::Cancelled => {
+                if let Err(e) = step.mark_cancelled() {
+                    log::warn!("delegate: {}", e);
+                }
+                "Delegation cancelled".to_string()
+            }
+        };
+        if step.status() == StepStatus::Running {
+            if let Err(e) = step.mark_failed(&error) {
+                log::warn!("delegate: {}", e);
+            }
+        }
+        let data = serde_json::json!({ "error": error, "task_id": task_id });
+        StepDelegationResponse {
+            output: from_crew_callback(data, &step.step_id, 0.0),
+            step: Some(step),
+        }
+    }
+}
+
+/// JSON-RPC params for the step's message.
+fn message_params(step: &UnifiedStep, input: &DataEnvelope, card: &AgentCard) -> Value {
+    let text = match to_task_input(input) {
+        text if text.is_empty() => step.name.clone(),
+        text => text,
+    };
+    let mut parts = vec![serde_json::json!({ "kind": "text", "text": text })];
+    if step.input.is_object() {
+        parts.push(serde_json::json!({ "kind": "data", "data": step.input }));
+    }
+
+    let sub_type = StepDomain::sub_type(&step.step_type);
+    let skill_id = step.input["skill_id"].as_str().or_else(|| {
+        card.skills
+            .iter()
+            .find(|s| s.id == sub_type || s.name == sub_type)
+            .map(|s| s.id.as_str())
+    });
+    serde_json::json!({
+        "message": {
+            "role": "user",
+            "parts": parts,
+            "metadata": {
+                "step_id": step.step_id,
+                "execution_id": step.execution_id,
+                "step_type": step.step_type,
+            },
+        },
+        "metadata": { "skill_id": skill_id },
+    })
+}
+
+fn is_finished(state: &A2ATaskState) -> bool {
+    !matches!(state, A2ATaskState::Submitted | A2ATaskState::Working)
+}
+
+/// Text of the task's artifacts, falling back to its status message.
+fn result_text(task: &A2ATask) -> String {
+    let text: Vec<&str> = task
+        .artifacts
+        .iter()
+        .filter_map(|a| a["parts"].as_array())
+        .flatten()
+        .filter_map(|p| p["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        status_text(&task.status).unwrap_or_default().to_string()
+    } else {
+        text.join("\n")
+    }
+}
+
+fn status_text(status: &A2ATaskStatus) -> Option<&str> {
+    status
+        .message
+        .as_ref()
+        .and_then(|m| m.parts.first())
+        .map(|p| p.text.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::a2a::auth::{BearerTokenAuth, ServerAuth};
+    use crate::crew::Crew;
+    use crate::server::a2a_routes::{a2a_router, A2AState};
+    use crate::task::Task;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delegate_step_to_remote_crew() {
+        let state = A2AState::new().with_auth(ServerAuth::bearer("s3cret"));
+        state.crews.register("summarizer", || {
+            let mut task = Task::new("Summarize: {message} ({lang})".into(), "Summary".into());
+            task.agent = Some("writer".to_string());
+            task.set_agent_executor(|prompt: &str, _: Option<&str>, _: &[String]| {
+                if prompt.contains("slowly") {
+                    std::thread::sleep(Duration::from_secs(2));
+                }
+                Ok((format!("summary of [{}]", prompt), Vec::new()))
+            });
+            Crew::new(vec![task], vec!["writer".to_string()])
+        });
+        state
+            .crews
+            .register("translator", || Crew::new(vec![], vec![]));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, a2a_router(state)).await });
+
+        let request = |text: &str| {
+            let mut step = UnifiedStep::new("exec-1", "crew.summarizer", "Summarize", 0);
+            step.input = serde_json::json!({ "lang": "en" });
+            StepDelegationRequest {
+                step,
+                input: DataEnvelope::new(serde_json::json!(text), "previous"),
+            }
+        };
+        let auth = Arc::new(BearerTokenAuth {
+            token: "s3cret".to_string(),
+            tls: None,
+        });
+        for mode in [DelegationMode::Stream, DelegationMode::Poll] {
+            let client = A2AClient::new(&endpoint, Some(auth.clone()), None);
+            let mut delegator = StepDelegator::new(client)
+                .with_mode(mode)
+                .with_poll_interval(Duration::from_millis(50));
+            let response = delegator.delegate(request("long text")).await.unwrap();
+            let step = response.step.unwrap();
+            assert_eq!(step.status(), StepStatus::Completed, "{:?}", mode);
+            assert!(response.output.data["result"]
+                .as_str()
+                .unwrap()
+                .contains("Summarize: long text (en)"));
+        }
+
+        let client = A2AClient::new(&endpoint, Some(auth), None);
+        let mut delegator = StepDelegator::new(client).with_timeout(Duration::from_millis(300));
+        let response = delegator.delegate(request("slowly")).await.unwrap();
+        let step = response.step.unwrap();
+        assert_eq!(step.status(), StepStatus::Failed);
+        assert!(step.error.unwrap().contains("did not finish"));
+        let task_id = response.output.data["task_id"].as_str().unwrap();
+        let task = delegator.client.get_task(task_id).await.unwrap();
+        assert_eq!(task.status.state, A2ATaskState::Canceled);
+    }
+}
@@ -4,11 +4,15 @@
 //!
 //! Provides configuration, type definitions, error codes, wrapper logic,
 //! authentication schemes, extensions, update mechanisms, and utilities
-//! for the A2A protocol integration.
+//! for the A2A protocol integration. [`delegation`] hands contract steps to
+//! remote agents; [`card`] derives agent cards from agent and crew
+//! configuration.
 
 pub mod auth;
+pub mod card;
 pub mod client;
 pub mod config;
+pub mod delegation;
 pub mod errors;
 pub mod extensions;
 pub mod types;
@@ -10,15 +10,63 @@
 use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use super::repository::RepositoryError;
+use crate::agents::cache::CacheHandler;
+use crate::agents::context_allocator::{
+    ContextAllocator, ContextPlan, ContextRatios, ContextSources, TaskProfile,
+};
 use crate::agents::crew_agent_executor::CrewAgentExecutor;
+use crate::agents::exploration::ExplorationBudget;
 use crate::agents::tools_handler::ToolsHandler;
+use crate::events::base_event::BaseEventData;
+use crate::events::types::agent_events::{
+    AgentExecutionCompletedEvent, AgentExecutionErrorEvent, AgentExecutionStartedEvent,
+};
+use crate::events::types::knowledge_events::{
+    KnowledgeQueryCompletedEvent, KnowledgeQueryFailedEvent, KnowledgeQueryStartedEvent,
+    KnowledgeRetrievalCompletedEvent, KnowledgeRetrievalStartedEvent,
+};
+use crate::events::types::llm_events::{
+    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent, LLMCallThrottledEvent,
+    LLMCallType,
+};
+use crate::events::types::tool_events::{
+    ToolUsageFinishedEvent, ToolUsageLimitReachedEvent, ToolUsageStartedEvent,
+};
+use crate::events::{BaseEvent, CREWAI_EVENT_BUS};
+use crate::hooks::{LLMCallHookContext, LLMCallHooks};
+use crate::knowledge::{format_knowledge_results, BaseKnowledgeSource, Knowledge, KnowledgeConfig};
+use crate::lite_agent::LiteAgentOutput;
 use crate::llms::base_llm::{BaseLLM, LLMMessage};
+use crate::llms::coalescing::{self, Coalesced};
 use crate::llms::providers::anthropic::AnthropicCompletion;
 use crate::llms::providers::openai::OpenAICompletion;
 use crate::llms::providers::xai::XAICompletion;
+use crate::mcp::config::MCPServerHTTP;
+use crate::mcp::toolset::MCPToolset;
+use crate::policy::PolicyEngine;
+use crate::rag::embeddings::EmbedderConfig;
+use crate::security::fingerprint::Fingerprint;
 use crate::security::security_config::SecurityConfig;
+use crate::tools::agent_tools::add_image_tool::{AddImageTool, ADD_IMAGE_TOOL_NAME};
+use crate::tools::agent_tools::scratchpad_tool::{Scratchpad, ScratchpadTool};
+use crate::tools::base_tool::BaseTool;
+use crate::tools::builtin::code_interpreter::{
+    self, CodeInterpreterTool, CodeSandbox, CODE_INTERPRETER_ACTION,
+};
+use crate::tools::registry::ToolRegistry;
+use crate::types::usage_metrics::{UsageMetrics, UsageScope};
+use crate::utilities::cancellation::{self, CancellationScope, CancellationToken};
+use crate::utilities::journal;
+use crate::utilities::prompt_templates::{self, PromptTemplate, PromptTemplates};
+use crate::utilities::rpm_controller::RPMController;
+use crate::utilities::run_log::{self, LogEntryKind};
+use crate::utilities::string_utils::interpolate_only;
+use crate::utilities::training_handler::TRAINED_AGENTS_DATA_FILE;
 
 /// MCP connection timeout in seconds.
 pub const MCP_CONNECTION_TIMEOUT: u64 = 10;
@@ -31,8 +79,7 @@ pub const MCP_MAX_RETRIES: u32 = 3;
 /// MCP schema cache TTL in seconds (5 minutes).
 pub const MCP_CACHE_TTL: u64 = 300;
 
-/// Type alias for a step callback function.
-pub type StepCallback = Box<dyn Fn(&str) + Send + Sync>;
+pub use crate::agents::crew_agent_executor::{AgentStep, StepCallback};
 
 /// Code execution mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -84,6 +131,9 @@ pub struct Agent {
     pub allow_delegation: bool,
     /// Tools at agents' disposal (stored as tool names).
     pub tools: Vec<String>,
+    /// Maximum uses of the named tools within one task execution, on top
+    /// of each tool's own `max_usage_count`.
+    pub tool_usage_limits: Option<HashMap<String, u32>>,
     /// Maximum iterations for an agent to execute a task.
     pub max_iter: i32,
     /// Language model identifier that will run the agent.
@@ -104,40 +154,69 @@ pub struct Agent {
     pub knowledge_config: Option<HashMap<String, serde_json::Value>>,
     /// Platform apps the agent can access through CrewAI AMP Tools.
     pub apps: Option<Vec<String>>,
-    /// MCP server references for tool integration.
+    /// MCP server references for tool integration: `https://` server URLs,
+    /// optionally with `#tool_name` to use a single tool.
     pub mcps: Option<Vec<String>>,
 
     // ---- Agent-specific fields (from Agent, extending BaseAgent) ----
     /// Maximum execution time for an agent to execute a task (seconds).
     pub max_execution_time: Option<i64>,
 
+    /// Wall-clock budget for exploration; once spent, the agent synthesizes
+    /// a best-effort final answer from what it gathered.
+    pub exploration_budget: Option<ExplorationBudget>,
+
     /// Callback to be executed after each step of the agent execution.
     #[serde(skip)]
     pub step_callback: Option<StepCallback>,
+    /// Hooks run around each of the agent's LLM calls, after the global
+    /// ones; they may change the messages, veto the call or replace the
+    /// response.
+    #[serde(skip)]
+    pub llm_call_hooks: LLMCallHooks,
 
-    /// Use system prompt for the agent.
+    /// Use system prompt for the agent; when unset, the system and user
+    /// prompts are sent as a single user message.
     pub use_system_prompt: bool,
 
-    /// Language model that will handle tool calling for this agent.
+    /// Language model that will handle tool calling for this agent,
+    /// overriding the crew's `function_calling_llm`.
     pub function_calling_llm: Option<String>,
 
-    /// System format for the agent.
+    /// System prompt template replacing the default one (see
+    /// [`Agent::build_prompt`]).
     pub system_template: Option<String>,
-    /// Prompt format for the agent.
+    /// User prompt template replacing the default one (see
+    /// [`Agent::build_prompt`]).
     pub prompt_template: Option<String>,
-    /// Response format for the agent.
+    /// Response template; the part before `{{ .Response }}` ends the user
+    /// prompt.
     pub response_template: Option<String>,
-
-    /// Enable code execution for the agent.
+    /// Language of the agent's prompts (see [`PromptTemplates`]); English
+    /// when unset.
+    pub language: Option<String>,
+    /// Prompt templates the agent's language is selected from; the
+    /// embedded defaults when unset.
+    pub prompt_templates: Option<PromptTemplates>,
+
+    /// Enable code execution for the agent: it is given the
+    /// `code_interpreter` tool (see [`Agent::code_interpreter_tool`]).
     pub allow_code_execution: bool,
 
     /// Keep messages under the context window size by summarizing content.
     pub respect_context_window: bool,
+    /// Share one provider call among identical LLM requests that are in
+    /// flight at the same time (see [`coalescing`]).
+    pub coalesce_llm_calls: bool,
+    /// How the context window is split between history, knowledge,
+    /// memory and tools when `respect_context_window` is set.
+    pub context_ratios: Option<ContextRatios>,
 
     /// Maximum number of retries for an agent when an error occurs.
     pub max_retry_limit: i32,
 
-    /// Whether the agent is multimodal (deprecated, will be removed in v2.0).
+    /// Whether the agent is multimodal: it is given the `add_image` tool
+    /// (see [`AddImageTool`]) and its model must support images.
     pub multimodal: bool,
 
     /// Whether to automatically inject the current date into tasks.
@@ -145,7 +224,8 @@ pub struct Agent {
     /// Format string for date when inject_date is enabled.
     pub date_format: String,
 
-    /// Mode for code execution: 'safe' (using Docker) or 'unsafe' (direct execution).
+    /// Mode for code execution: 'safe' (using Docker) or 'unsafe' (firejail
+    /// when installed, else direct execution).
     pub code_execution_mode: CodeExecutionMode,
 
     /// Whether the agent should reflect and create a plan before executing a task.
@@ -153,8 +233,15 @@ pub struct Agent {
     /// Maximum number of reasoning attempts before executing the task.
     pub max_reasoning_attempts: Option<i32>,
 
+    /// Training data file of the crew training the agent. While set, the
+    /// human feedback recorded in it is added to the agent's task prompts.
+    pub training_file: Option<String>,
+    /// File of the suggestions distilled from training that the agent
+    /// follows; [`TRAINED_AGENTS_DATA_FILE`] when unset.
+    pub trained_agents_file: Option<String>,
+
     /// Embedder configuration for the agent.
-    pub embedder: Option<HashMap<String, serde_json::Value>>,
+    pub embedder: Option<EmbedderConfig>,
 
     /// Agent knowledge context (injected before task execution).
     pub agent_knowledge_context: Option<String>,
@@ -163,7 +250,8 @@ pub struct Agent {
     /// Knowledge search query dynamically generated by the agent.
     pub knowledge_search_query: Option<String>,
 
-    /// The Agent's role to be loaded from a repository.
+    /// Name of the repository definition the agent was loaded from (see
+    /// [`Agent::from_repository`]).
     pub from_repository: Option<String>,
 
     /// Guardrail description or callable for validating agent output.
@@ -177,12 +265,19 @@ pub struct Agent {
     /// Executor class name (for custom agent executors).
     pub executor_class: Option<String>,
 
-    /// Knowledge sources for the agent (stored as config maps).
-    pub knowledge_sources: Option<Vec<HashMap<String, serde_json::Value>>>,
+    /// Knowledge sources owned by the agent. They are stored in the
+    /// agent's own collection, apart from the crew's knowledge, and
+    /// embedded with the agent's `embedder`.
+    #[serde(skip)]
+    pub knowledge_sources: Vec<Arc<dyn BaseKnowledgeSource>>,
     /// Knowledge storage configuration.
     pub knowledge_storage: Option<serde_json::Value>,
-    /// Knowledge instance for the agent.
-    pub knowledge: Option<serde_json::Value>,
+    /// The agent's knowledge, built from `knowledge_sources` on first use.
+    #[serde(skip)]
+    pub knowledge: Option<Arc<Knowledge>>,
+    /// The knowledge of the agent's crew, queried alongside its own.
+    #[serde(skip)]
+    pub crew_knowledge: Option<Arc<Knowledge>>,
 
     /// Crew reference (not serialized).
     #[serde(skip)]
@@ -204,9 +299,33 @@ pub struct Agent {
     /// Last messages from the agent's LLM interaction.
     #[serde(skip)]
     pub last_messages: Vec<HashMap<String, String>>,
-    /// MCP client references for cleanup.
+    /// MCP toolsets connected for the current task, for cleanup.
     #[serde(skip)]
-    mcp_clients: Vec<serde_json::Value>,
+    mcp_clients: Vec<MCPToolset>,
+    /// Private scratchpad backing the built-in `scratchpad` tool, kept for
+    /// the agent's lifetime (see [`Agent::enable_scratchpad`]).
+    #[serde(skip)]
+    pub scratchpad: Option<Scratchpad>,
+    /// Registry resolving the agent's tool names and transforming their
+    /// results; the [global](ToolRegistry::global) one when `None`.
+    #[serde(skip)]
+    pub tool_registry: Option<ToolRegistry>,
+    /// Tool result cache, shared with the other agents of the crew; used
+    /// when `cache` is set.
+    #[serde(skip)]
+    pub cache_handler: Option<CacheHandler>,
+    /// Enforces `max_rpm` across the agent's LLM calls; created on first
+    /// execution.
+    #[serde(skip)]
+    pub rpm_controller: Option<RPMController>,
+    /// Crew-wide request budget shared with the other agents of the crew,
+    /// enforced in addition to the agent's own `max_rpm`.
+    #[serde(skip)]
+    pub crew_rpm_controller: Option<RPMController>,
+    /// Policy every tool call is checked against; the agent's role is its
+    /// id in policy requests and RBAC.
+    #[serde(skip)]
+    pub policy: Option<Arc<Mutex<PolicyEngine>>>,
 }
 
 impl std::fmt::Debug for Agent {
@@ -234,6 +353,7 @@ impl Clone for Agent {
             max_rpm: self.max_rpm,
             allow_delegation: self.allow_delegation,
             tools: self.tools.clone(),
+            tool_usage_limits: self.tool_usage_limits.clone(),
             max_iter: self.max_iter,
             llm: self.llm.clone(),
             max_tokens: self.max_tokens,
@@ -244,14 +364,20 @@ impl Clone for Agent {
             apps: self.apps.clone(),
             mcps: self.mcps.clone(),
             max_execution_time: self.max_execution_time,
-            step_callback: None, // Can't clone closures
+            exploration_budget: self.exploration_budget.clone(),
+            step_callback: self.step_callback.clone(),
+            llm_call_hooks: self.llm_call_hooks.clone(),
             use_system_prompt: self.use_system_prompt,
             function_calling_llm: self.function_calling_llm.clone(),
             system_template: self.system_template.clone(),
             prompt_template: self.prompt_template.clone(),
             response_template: self.response_template.clone(),
+            language: self.language.clone(),
+            prompt_templates: self.prompt_templates.clone(),
             allow_code_execution: self.allow_code_execution,
             respect_context_window: self.respect_context_window,
+            coalesce_llm_calls: self.coalesce_llm_calls,
+            context_ratios: self.context_ratios.clone(),
             max_retry_limit: self.max_retry_limit,
             multimodal: self.multimodal,
             inject_date: self.inject_date,
@@ -259,6 +385,8 @@ impl Clone for Agent {
             code_execution_mode: self.code_execution_mode,
             reasoning: self.reasoning,
             max_reasoning_attempts: self.max_reasoning_attempts,
+            training_file: self.training_file.clone(),
+            trained_agents_file: self.trained_agents_file.clone(),
             embedder: self.embedder.clone(),
             agent_knowledge_context: self.agent_knowledge_context.clone(),
             crew_knowledge_context: self.crew_knowledge_context.clone(),
@@ -271,12 +399,19 @@ impl Clone for Agent {
             knowledge_sources: self.knowledge_sources.clone(),
             knowledge_storage: self.knowledge_storage.clone(),
             knowledge: self.knowledge.clone(),
+            crew_knowledge: self.crew_knowledge.clone(),
             crew: self.crew.clone(),
             times_executed: 0,
             original_role: self.original_role.clone(),
             original_goal: self.original_goal.clone(),
             original_backstory: self.original_backstory.clone(),
             last_messages: Vec::new(),
+            scratchpad: self.scratchpad.as_ref().map(|_| Scratchpad::new()),
+            tool_registry: self.tool_registry.clone(),
+            cache_handler: self.cache_handler.clone(),
+            rpm_controller: None,
+            crew_rpm_controller: self.crew_rpm_controller.clone(),
+            policy: self.policy.clone(),
             mcp_clients: Vec::new(),
         }
     }
@@ -285,7 +420,7 @@ impl Clone for Agent {
 impl Agent {
     /// Create a new Agent with required fields.
     pub fn new(role: String, goal: String, backstory: String) -> Self {
-        Self {
+        let mut agent = Self {
             id: Uuid::new_v4(),
             role,
             goal,
@@ -296,6 +431,7 @@ impl Agent {
             max_rpm: None,
             allow_delegation: false,
             tools: Vec::new(),
+            tool_usage_limits: None,
             max_iter: 25,
             llm: None,
             max_tokens: None,
@@ -306,14 +442,20 @@ impl Agent {
             apps: None,
             mcps: None,
             max_execution_time: None,
+            exploration_budget: None,
             step_callback: None,
+            llm_call_hooks: LLMCallHooks::default(),
             use_system_prompt: true,
             function_calling_llm: None,
             system_template: None,
             prompt_template: None,
             response_template: None,
+            language: None,
+            prompt_templates: None,
             allow_code_execution: false,
             respect_context_window: true,
+            coalesce_llm_calls: false,
+            context_ratios: None,
             max_retry_limit: 2,
             multimodal: false,
             inject_date: false,
@@ -321,6 +463,8 @@ impl Agent {
             code_execution_mode: CodeExecutionMode::default(),
             reasoning: false,
             max_reasoning_attempts: None,
+            training_file: None,
+            trained_agents_file: None,
             embedder: None,
             agent_knowledge_context: None,
             crew_knowledge_context: None,
@@ -330,30 +474,310 @@ impl Agent {
             guardrail_max_retries: 3,
             a2a: None,
             executor_class: None,
-            knowledge_sources: None,
+            knowledge_sources: Vec::new(),
             knowledge_storage: None,
             knowledge: None,
+            crew_knowledge: None,
             crew: None,
             times_executed: 0,
             original_role: None,
             original_goal: None,
             original_backstory: None,
             last_messages: Vec::new(),
+            scratchpad: None,
+            tool_registry: None,
+            cache_handler: None,
+            rpm_controller: None,
+            crew_rpm_controller: None,
+            policy: None,
             mcp_clients: Vec::new(),
+        };
+        agent.security_config = SecurityConfig::with_fingerprint(agent.definition_fingerprint());
+        agent
+    }
+
+    /// Select the language of the agent's prompts, e.g. `es` or `pt-BR`.
+    pub fn i18n(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Use `templates` instead of the embedded prompt templates.
+    pub fn with_prompt_templates(mut self, templates: PromptTemplates) -> Self {
+        self.prompt_templates = Some(templates);
+        self
+    }
+
+    /// The prompt templates of the agent's language.
+    pub fn prompt_template(&self) -> &PromptTemplate {
+        let templates = match &self.prompt_templates {
+            Some(templates) => templates,
+            None => prompt_templates::default_prompt_templates(),
+        };
+        templates.get(
+            self.language
+                .as_deref()
+                .unwrap_or(prompt_templates::DEFAULT_LANGUAGE),
+        )
+    }
+
+    /// The prompts the agent's executor starts from: `system` and `user`,
+    /// or a single `prompt` when `use_system_prompt` is unset.
+    ///
+    /// `system_template` and `prompt_template` override the default prompts
+    /// of the agent's language, for models expecting their own instruction
+    /// format. Both may use `{role}`, `{goal}`, `{backstory}`, `{tools}`
+    /// and `{task}`; `{{ .System }}` and `{{ .Prompt }}` insert the default
+    /// system prompt (with the tool-use format the executor parses) and
+    /// user prompt. The part of `response_template` before
+    /// `{{ .Response }}` is appended to the user prompt. Placeholders are
+    /// filled in one pass, so placeholders in the values are kept as text.
+    pub fn build_prompt(&self, task_prompt: &str, tools: &[String]) -> HashMap<String, String> {
+        let template = self.prompt_template();
+        let default_system = template.system_prompt(&self.role, &self.goal, &self.backstory, tools);
+        let default_user = template.user_prompt(task_prompt);
+        let tools = tools.join(", ");
+        let value = |placeholder: &str| match placeholder {
+            "{role}" => Some(self.role.as_str()),
+            "{goal}" => Some(self.goal.as_str()),
+            "{backstory}" => Some(self.backstory.as_str()),
+            "{tools}" => Some(tools.as_str()),
+            "{task}" => Some(task_prompt),
+            "{{ .System }}" => Some(default_system.as_str()),
+            "{{ .Prompt }}" => Some(default_user.as_str()),
+            _ => None,
+        };
+
+        let system = match &self.system_template {
+            Some(system) => fill_placeholders(system, value),
+            None => default_system.clone(),
+        };
+        let mut user = match &self.prompt_template {
+            Some(user) => fill_placeholders(user, value),
+            None => default_user.clone(),
+        };
+        if let Some(response) = &self.response_template {
+            let response = response.split("{{ .Response }}").next().unwrap_or_default();
+            if !response.is_empty() {
+                user = format!("{}\n{}", user, response);
+            }
+        }
+
+        let mut prompt = HashMap::new();
+        if self.use_system_prompt {
+            prompt.insert("system".to_string(), system);
+            prompt.insert("user".to_string(), user);
+        } else {
+            prompt.insert("prompt".to_string(), format!("{}\n{}", system, user));
+        }
+        prompt
+    }
+
+    /// Load agent `name` from the repository named by
+    /// `$CREWAI_AGENT_REPOSITORY` (see [`super::repository`]).
+    ///
+    /// Definitions are cached, so repeated loads don't reach the repository
+    /// again.
+    pub fn from_repository(name: &str) -> Result<Self, RepositoryError> {
+        super::repository::global_repository()?.load_agent(name)
+    }
+
+    /// Use `cache_handler` for tool results.
+    ///
+    /// Corresponds to `BaseAgent.set_cache_handler()` in Python.
+    pub fn set_cache_handler(&mut self, cache_handler: CacheHandler) {
+        self.cache_handler = Some(cache_handler);
+    }
+
+    /// Share the crew's request budget with the agent.
+    ///
+    /// Corresponds to `BaseAgent.set_rpm_controller()` in Python; unlike
+    /// there, an agent with its own `max_rpm` honours both limits.
+    pub fn set_rpm_controller(&mut self, rpm_controller: RPMController) {
+        self.crew_rpm_controller = Some(rpm_controller);
+    }
+
+    /// The limits LLM calls wait on, with their scope (`"agent"` or
+    /// `"crew"`).
+    fn rpm_controllers(&mut self) -> Vec<(&'static str, RPMController)> {
+        let max_rpm = self.max_rpm.filter(|&rpm| rpm > 0);
+        if self.rpm_controller.as_ref().map(|c| c.max_rpm) != Some(max_rpm) {
+            self.rpm_controller = max_rpm.map(|rpm| RPMController::new(Some(rpm)));
         }
+        let own = self.rpm_controller.clone().map(|c| ("agent", c));
+        let crew = self
+            .crew_rpm_controller
+            .clone()
+            .filter(|c| c.max_rpm.is_some())
+            .map(|c| ("crew", c));
+        own.into_iter().chain(crew).collect()
+    }
+
+    /// Give the agent a private scratchpad and the `scratchpad` tool to use
+    /// it. Notes persist across the agent's tasks until the scratchpad is
+    /// cleared.
+    pub fn enable_scratchpad(&mut self) -> Scratchpad {
+        let pad = self.scratchpad.get_or_insert_with(Scratchpad::new).clone();
+        if !self.tools.iter().any(|t| t == "scratchpad") {
+            self.tools.push("scratchpad".to_string());
+        }
+        pad
     }
 
     /// Set knowledge for the agent with optional crew embedder configuration.
     ///
     /// Corresponds to `Agent.set_knowledge()` in Python.
-    pub fn set_knowledge(&mut self, crew_embedder: Option<&HashMap<String, serde_json::Value>>) {
+    ///
+    /// The agent's embedder falls back to the crew's. When the agent has
+    /// knowledge sources, they are ingested into a collection named after
+    /// the agent (`agent_<role>`).
+    pub fn set_knowledge(&mut self, crew_embedder: Option<&EmbedderConfig>) -> Result<(), String> {
         if self.embedder.is_none() {
             if let Some(embedder) = crew_embedder {
                 self.embedder = Some(embedder.clone());
             }
         }
-        // TODO: Initialize Knowledge instance from knowledge_sources if present.
-        log::debug!("set_knowledge called for agent '{}'", self.role);
+        if self.knowledge_sources.is_empty() {
+            return Ok(());
+        }
+
+        let collection = self.knowledge_collection_name();
+        let sources = self
+            .knowledge_sources
+            .iter()
+            .map(|s| Box::new(s.clone()) as Box<dyn BaseKnowledgeSource>)
+            .collect();
+        let knowledge = Knowledge::new(sources, self.embedder.clone(), collection, None);
+        knowledge
+            .add_sources()
+            .map_err(|e| format!("Failed to load knowledge for '{}': {}", self.role, e))?;
+        self.knowledge = Some(Arc::new(knowledge));
+        Ok(())
+    }
+
+    /// Name of the collection holding the agent's own knowledge, if it has
+    /// any: `agent_<role>` with whitespace in the role replaced by `_`.
+    pub fn knowledge_collection_name(&self) -> Option<String> {
+        if let Some(knowledge) = &self.knowledge {
+            return knowledge.collection_name.clone();
+        }
+        if self.knowledge_sources.is_empty() {
+            return None;
+        }
+        let role: Vec<&str> = self.role.split_whitespace().collect();
+        Some(format!("agent_{}", role.join("_")))
+    }
+
+    /// Query the agent's own knowledge and its crew's for `query`, storing
+    /// the matches, with their sources, in `agent_knowledge_context` and
+    /// `crew_knowledge_context` (empty when nothing matches).
+    ///
+    /// Knowledge is loaded on first use. Failures are logged and leave the
+    /// task to run without knowledge. The retrieval is bracketed by
+    /// knowledge retrieval events, the last carrying the combined matches,
+    /// and each search by knowledge query events carrying the chunks
+    /// returned, their scores and the latency.
+    pub fn retrieve_knowledge(&mut self, query: &str) {
+        if self.knowledge.is_none() && !self.knowledge_sources.is_empty() {
+            if let Err(e) = self.set_knowledge(None) {
+                log::warn!("{}", e);
+            }
+        }
+        if self.knowledge.is_none() && self.crew_knowledge.is_none() {
+            return;
+        }
+        let config: KnowledgeConfig = self
+            .knowledge_config
+            .as_ref()
+            .and_then(|c| serde_json::to_value(c).ok())
+            .and_then(|c| serde_json::from_value(c).ok())
+            .unwrap_or_default();
+        self.knowledge_search_query = Some(query.to_string());
+        let agent_id = self.id.to_string();
+        let mut started = KnowledgeRetrievalStartedEvent::new();
+        self.tag_event(&mut started.base);
+        emit_event(
+            &agent_id,
+            self.security_config.fingerprint.stamp(&mut started),
+        );
+
+        self.agent_knowledge_context = self
+            .knowledge
+            .as_ref()
+            .and_then(|knowledge| self.query_knowledge(knowledge, query, &config));
+        self.crew_knowledge_context = self
+            .crew_knowledge
+            .as_ref()
+            .and_then(|knowledge| self.query_knowledge(knowledge, query, &config));
+
+        let retrieved = super::utils::combine_knowledge_context(
+            self.agent_knowledge_context.as_deref(),
+            self.crew_knowledge_context.as_deref(),
+        );
+        let mut completed = KnowledgeRetrievalCompletedEvent::new(query.to_string(), retrieved);
+        self.tag_event(&mut completed.base);
+        emit_event(
+            &agent_id,
+            self.security_config.fingerprint.stamp(&mut completed),
+        );
+    }
+
+    /// Search `knowledge` for `query`, emitting the knowledge query events.
+    fn query_knowledge(
+        &self,
+        knowledge: &Knowledge,
+        query: &str,
+        config: &KnowledgeConfig,
+    ) -> Option<String> {
+        let agent_id = self.id.to_string();
+        let mut started = KnowledgeQueryStartedEvent::new(query.to_string());
+        self.tag_event(&mut started.base);
+        emit_event(
+            &agent_id,
+            self.security_config.fingerprint.stamp(&mut started),
+        );
+
+        let clock = Instant::now();
+        let results = knowledge.query(
+            query,
+            Some(config.results_limit),
+            Some(config.score_threshold),
+        );
+        let latency_ms = clock.elapsed().as_millis() as u64;
+        match results {
+            Ok(results) => {
+                let scores = results.iter().filter_map(|r| r["score"].as_f64()).collect();
+                let mut completed = KnowledgeQueryCompletedEvent::new(
+                    query.to_string(),
+                    results.len(),
+                    scores,
+                    latency_ms,
+                );
+                self.tag_event(&mut completed.base);
+                emit_event(
+                    &agent_id,
+                    self.security_config.fingerprint.stamp(&mut completed),
+                );
+                format_knowledge_results(&results)
+            }
+            Err(e) => {
+                log::warn!("Knowledge query for '{}' failed: {}", self.role, e);
+                let mut failed = KnowledgeQueryFailedEvent::new(e.to_string(), latency_ms);
+                self.tag_event(&mut failed.base);
+                emit_event(
+                    &agent_id,
+                    self.security_config.fingerprint.stamp(&mut failed),
+                );
+                None
+            }
+        }
+    }
+
+    /// Attribute a knowledge event to this agent.
+    fn tag_event(&self, base: &mut BaseEventData) {
+        base.agent_id = Some(self.id.to_string());
+        base.agent_role = Some(self.role.clone());
+        base.source_type = Some("agent".to_string());
     }
 
     /// Check if any memory is available through the crew.
@@ -369,6 +793,24 @@ impl Agent {
         !tools.is_empty() && self.llm.is_some()
     }
 
+    /// Usage limits of the agent's tools for one task execution: the
+    /// agent's own [`tool_usage_limits`](Self::tool_usage_limits), capped by
+    /// the uses each tool has left in `registry`. Unlimited tools are absent.
+    pub fn effective_tool_usage_limits(&self, registry: &ToolRegistry) -> HashMap<String, u32> {
+        self.tools
+            .iter()
+            .filter_map(|tool| {
+                let own = self
+                    .tool_usage_limits
+                    .as_ref()
+                    .and_then(|limits| limits.get(tool))
+                    .copied();
+                let limit = own.into_iter().chain(registry.remaining_uses(tool)).min()?;
+                Some((tool.clone(), limit))
+            })
+            .collect()
+    }
+
     /// Execute a task with the agent.
     ///
     /// # Arguments
@@ -388,40 +830,97 @@ impl Agent {
     ) -> Result<String, String> {
         log::debug!("Agent '{}' executing task: {}", self.role, task_description);
 
-        // Handle reasoning if enabled
-        if self.reasoning {
-            let _ = super::utils::handle_reasoning(
-                &self.role,
-                task_description,
-                self.max_reasoning_attempts.unwrap_or(3),
-            );
-        }
+        // Plan the task first if reasoning is enabled
+        let task_description = if self.reasoning {
+            super::utils::handle_reasoning(self, task_description)
+        } else {
+            task_description.to_string()
+        };
 
         // Inject date if enabled
         let task_desc = if self.inject_date {
-            self.inject_date_to_description(task_description)
+            self.inject_date_to_description(&task_description)
         } else {
-            task_description.to_string()
+            task_description
         };
 
         // Build task prompt with schema
         let task_prompt = super::utils::build_task_prompt_with_schema(&task_desc, None);
 
         // Format with context
-        let task_prompt = if let Some(ctx) = context {
+        let mut task_prompt = if let Some(ctx) = context {
             format!("{}\n\nContext:\n{}", task_prompt, ctx)
         } else {
             task_prompt
         };
 
+        // Look up the agent's and the crew's knowledge. With a context window
+        // plan it is budgeted alongside history; otherwise it goes into the
+        // prompt.
+        self.retrieve_knowledge(&task_desc);
+        if !self.respect_context_window {
+            let knowledge = super::utils::combine_knowledge_context(
+                self.agent_knowledge_context.as_deref(),
+                self.crew_knowledge_context.as_deref(),
+            );
+            if !knowledge.is_empty() {
+                task_prompt = format!("{}\n\nRelevant knowledge:\n{}", task_prompt, knowledge);
+            }
+        }
+
+        // Follow the human feedback while training, the suggestions
+        // distilled from it afterwards
+        let task_prompt = super::utils::apply_training_data(
+            &self.role,
+            &task_prompt,
+            self.training_file.as_deref(),
+            self.trained_agents_file(),
+        );
+
         // Validate max execution time
         super::utils::validate_max_execution_time(self.max_execution_time)?;
 
+        // Connect the agent's MCP servers and add their tools for this task
+        if let Some(mcps) = self.mcps.clone() {
+            let mcp_tools = self.get_mcp_tools(&mcps);
+            self.tools.extend(mcp_tools);
+        }
+
+        let agent_id = self.id.to_string();
+        emit_event(
+            &agent_id,
+            self.security_config
+                .fingerprint
+                .stamp(&mut AgentExecutionStartedEvent::new(
+                    self.role.clone(),
+                    agent_id.clone(),
+                    task_prompt.clone(),
+                    Some(self.tools.clone()),
+                )),
+        );
+
         // Execute (with or without timeout)
         let result = if let Some(timeout) = self.max_execution_time {
-            self.execute_with_timeout(&task_prompt, timeout)?
+            self.execute_with_timeout(&task_prompt, timeout)
         } else {
-            self.execute_without_timeout(&task_prompt)?
+            self.execute_without_timeout(&task_prompt)
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                self.cleanup_mcp_clients();
+                emit_event(
+                    &agent_id,
+                    self.security_config
+                        .fingerprint
+                        .stamp(&mut AgentExecutionErrorEvent::new(
+                            self.role.clone(),
+                            agent_id.clone(),
+                            e.clone(),
+                        )),
+                );
+                return Err(e);
+            }
         };
 
         // Process tool results
@@ -433,6 +932,17 @@ impl Agent {
         // Cleanup MCP clients
         self.cleanup_mcp_clients();
 
+        emit_event(
+            &agent_id,
+            self.security_config
+                .fingerprint
+                .stamp(&mut AgentExecutionCompletedEvent::new(
+                    self.role.clone(),
+                    agent_id.clone(),
+                    result.clone(),
+                )),
+        );
+
         Ok(result)
     }
 
@@ -449,9 +959,15 @@ impl Agent {
     }
 
     /// Execute a task with a timeout.
+    ///
+    /// LLM and tool calls still running when the timeout passes are
+    /// abandoned (see [`cancellation`]).
     fn execute_with_timeout(&mut self, task_prompt: &str, timeout: i64) -> Result<String, String> {
-        // TODO: Implement actual timeout using tokio::time::timeout or threads.
         log::debug!("Executing with timeout: {}s", timeout);
+        let _scope = CancellationScope::enter(
+            CancellationToken::new(),
+            Some(Duration::from_secs(timeout.max(0) as u64)),
+        );
         self.execute_without_timeout(task_prompt)
     }
 
@@ -465,35 +981,63 @@ impl Agent {
         let llm = self
             .create_llm_instance()
             .map_err(|e| format!("Failed to create LLM instance: {}", e))?;
+        self.validate_docker_installation()?;
+        if self.multimodal && !llm.supports_multimodal() {
+            return Err(format!(
+                "Model '{}' does not support images, which multimodal agents need",
+                llm.model()
+            ));
+        }
 
-        // 2. Build system + user prompt
-        let system_prompt = format!(
-            "You are {}.\n{}\n\nYour goal: {}\n\nAvailable tools: {}\n\n\
-             You MUST use the following format:\n\n\
-             Thought: you should always think about what to do\n\
-             Action: the action to take, one of [{}]\n\
-             Action Input: the input to the action\n\
-             Observation: the result of the action\n\
-             ... (this Thought/Action/Action Input/Observation can repeat N times)\n\
-             Thought: I now know the final answer\n\
-             Final Answer: the final answer to the original input question",
-            self.role,
-            self.backstory,
-            self.goal,
-            self.tools.join(", "),
-            self.tools.join(", "),
-        );
-
-        let mut prompt = HashMap::new();
-        prompt.insert("system".to_string(), system_prompt);
-        prompt.insert("user".to_string(), task_prompt.to_string());
+        // 2. Build system + user prompt, leaving out tools with no uses left
+        //    and giving agents the image and code execution tools they
+        //    are enabled for
+        let registry = self
+            .tool_registry
+            .clone()
+            .unwrap_or_else(|| ToolRegistry::global().clone());
+        let usage_limits = self.effective_tool_usage_limits(&registry);
+        let mut tools: Vec<String> = self
+            .tools
+            .iter()
+            .filter(|t| usage_limits.get(*t) != Some(&0))
+            .cloned()
+            .collect();
+        let mut builtin_tools = self.get_code_execution_tools();
+        if self.multimodal {
+            builtin_tools.extend(Self::get_multimodal_tools());
+        }
+        for tool in builtin_tools {
+            if !tools.contains(&tool) {
+                tools.push(tool);
+            }
+        }
+        let template = self.prompt_template().clone();
+        let prompt = self.build_prompt(task_prompt, &tools);
 
         // 3. Build the executor
-        let tools_names = self.tools.join(", ");
-        let tools_description = self
-            .tools
+        let cache_registry = registry.clone();
+        let tools_names = tools.join(", ");
+        let tools_description = tools
             .iter()
-            .map(|t| format!("- {}: A tool named {}", t, t))
+            .map(|t| {
+                let description = registry.description(t).unwrap_or_else(|| {
+                    if self.multimodal && t == ADD_IMAGE_TOOL_NAME {
+                        let tool = AddImageTool::new();
+                        format!(
+                            "{} Input: {}",
+                            tool.description,
+                            AddImageTool::args_schema()
+                        )
+                    } else if self.allow_code_execution && t == CODE_INTERPRETER_ACTION {
+                        let tool = CodeInterpreterTool::new();
+                        format!("{} Input: {}", tool.description(), tool.args_schema())
+                    } else {
+                        format!("A tool named {}", t)
+                    }
+                });
+                format!("- {}: {}", t, description)
+            })
             .collect::<Vec<_>>()
             .join("\n");
 
@@ -508,12 +1052,84 @@ impl Agent {
             tools_names.clone(),
             vec!["Observation:".to_string()], // stop words
             tools_description,
-            ToolsHandler::new(None),
+            ToolsHandler::new(self.cache_handler.clone().filter(|_| self.cache)),
         );
+        executor.set_exploration_budget(self.exploration_budget.clone());
+        if self.multimodal {
+            executor.set_image_tool(ADD_IMAGE_TOOL_NAME);
+        }
+        if let Some(callback) = &self.step_callback {
+            executor.set_step_callback(callback.clone());
+        }
+        executor.set_llm_call_hooks(
+            self.llm_call_hooks.clone(),
+            LLMCallHookContext {
+                agent: Some(self.role.clone()),
+                task: Some(task_prompt.to_string()),
+                llm: Some(llm.model().to_string()),
+                ..LLMCallHookContext::new()
+            },
+        );
+        let rpm_controllers = self.rpm_controllers();
+        if !rpm_controllers.is_empty() {
+            let rpm_agent_id = self.id.to_string();
+            let rpm_model = llm.model().to_string();
+            executor.set_request_within_rpm_limit(move || {
+                for (scope, controller) in &rpm_controllers {
+                    controller.acquire(|wait| {
+                        let mut event = LLMCallThrottledEvent::new(
+                            Some(rpm_model.clone()),
+                            controller.max_rpm.unwrap_or_default(),
+                            scope.to_string(),
+                            wait.as_millis() as u64,
+                        );
+                        emit_event(&rpm_agent_id, &mut event);
+                    });
+                }
+                true
+            });
+        }
+        if let Some(policy) = &self.policy {
+            executor.set_policy(policy.clone(), self.role.clone());
+        }
+        executor.set_tool_usage_limits(usage_limits);
+        let limit_agent_id = self.id.to_string();
+        let limit_role = self.role.clone();
+        executor.set_on_tool_limit_reached(move |tool_name, limit| {
+            let mut event = ToolUsageLimitReachedEvent::new(tool_name.to_string(), limit);
+            event.agent_key = Some(limit_role.clone());
+            emit_event(&limit_agent_id, &mut event);
+        });
+        // Cache what the tool allows, except failures (reported as output).
+        let cache_template = template.clone();
+        executor.set_cache_function(move |tool_name, args, result| {
+            !cache_template.is_tool_error(tool_name, result)
+                && cache_registry.should_cache(
+                    tool_name,
+                    args,
+                    &serde_json::Value::String(result.to_string()),
+                )
+        });
+        if self.respect_context_window {
+            let knowledge = super::utils::combine_knowledge_context(
+                self.agent_knowledge_context.as_deref(),
+                self.crew_knowledge_context.as_deref(),
+            );
+            executor.set_context_plan(Some(ContextPlan {
+                allocator: ContextAllocator::for_context_window(llm.get_context_window_size())
+                    .with_ratios(self.context_ratios.clone().unwrap_or_default()),
+                profile: TaskProfile::from_task(task_prompt),
+                sources: ContextSources::default().with_knowledge_text(&knowledge),
+            }));
+        }
 
         // 4. Set the LLM call callback using the real LLM instance
         let llm_arc: std::sync::Arc<dyn BaseLLM> = std::sync::Arc::from(llm);
         let llm_for_call = llm_arc.clone();
+        let agent_id = self.id.to_string();
+        let llm_agent_id = agent_id.clone();
+        let llm_role = self.role.clone();
+        let coalesce = self.coalesce_llm_calls;
         executor.set_llm_call(
             move |messages: &[crate::agents::crew_agent_executor::LLMMessage],
                   tools: Option<&[serde_json::Value]>| {
@@ -522,9 +1138,94 @@ impl Agent {
                     .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
                     .collect();
 
+                let call_type = if tools.is_some() {
+                    LLMCallType::ToolCall
+                } else {
+                    LLMCallType::LlmCall
+                };
                 let tools_vec = tools.map(|t| t.to_vec());
 
-                let result = llm_for_call.call(msgs, tools_vec, None)?;
+                let call_id = Uuid::new_v4().to_string();
+                let model = Some(llm_for_call.model().to_string());
+                emit_event(
+                    &llm_agent_id,
+                    &mut LLMCallStartedEvent::new(call_id.clone(), model.clone()),
+                );
+                run_log::record(
+                    LogEntryKind::LlmRequest,
+                    serde_json::json!({ "call_id": call_id, "model": model, "messages": msgs }),
+                );
+                let usage_before = llm_for_call.get_token_usage_summary();
+                let journal = journal::current();
+                let journal_key = journal.as_ref().map(|_| {
+                    coalescing::content_key(&*llm_for_call, &msgs, tools_vec.as_deref())
+                });
+                let key = coalesce
+                    .then(|| coalescing::request_key(&*llm_for_call, &msgs, tools_vec.as_deref()))
+                    .flatten();
+                let provider_call = || {
+                    let llm = llm_for_call.clone();
+                    let live_call = || {
+                        cancellation::run_interruptible(move || {
+                            llm.call(msgs, tools_vec, None).map_err(|e| e.to_string())
+                        })
+                        .map_err(|e| e.to_string())?
+                    };
+                    match (&journal, &journal_key) {
+                        (Some(journal), Some(key)) => {
+                            journal.llm_call(key, &llm_role, model.as_deref(), live_call)
+                        }
+                        _ => live_call(),
+                    }
+                };
+                let Coalesced {
+                    result,
+                    shared_from,
+                } = match &key {
+                    Some(key) => coalescing::global().run(key, &call_id, provider_call),
+                    None => Coalesced {
+                        result: provider_call(),
+                        shared_from: None,
+                    },
+                };
+                let result = match result {
+                    Ok(result) => {
+                        run_log::record(
+                            LogEntryKind::LlmResponse,
+                            serde_json::json!({ "call_id": call_id, "model": model, "response": result }),
+                        );
+                        let mut completed =
+                            LLMCallCompletedEvent::new(call_id, model, result.clone(), call_type);
+                        let usage = llm_for_call.get_token_usage_summary();
+                        if let Some(shared_from) = shared_from {
+                            completed = completed.with_shared_from(shared_from);
+                        } else if usage.total_tokens > usage_before.total_tokens {
+                            completed = completed.with_usage(UsageMetrics {
+                                total_tokens: usage.total_tokens - usage_before.total_tokens,
+                                prompt_tokens: usage.prompt_tokens - usage_before.prompt_tokens,
+                                cached_prompt_tokens: usage.cached_prompt_tokens
+                                    - usage_before.cached_prompt_tokens,
+                                completion_tokens: usage.completion_tokens
+                                    - usage_before.completion_tokens,
+                                successful_requests: usage.successful_requests
+                                    - usage_before.successful_requests,
+                            });
+                        }
+                        emit_event(&llm_agent_id, &mut completed);
+                        result
+                    }
+                    Err(e) => {
+                        run_log::record(
+                            LogEntryKind::LlmResponse,
+                            serde_json::json!({ "call_id": call_id, "model": model, "error": e.to_string() }),
+                        );
+                        emit_event(
+                            &llm_agent_id,
+                            &mut LLMCallFailedEvent::new(call_id, model, e.clone()),
+                        );
+                        return Err(e.into());
+                    }
+                };
 
                 // Extract text from the LLM Value response
                 match result {
@@ -534,16 +1235,119 @@ impl Agent {
             },
         );
 
-        // 5. Set a basic tool executor (logs tool calls, returns stub for now)
-        executor.set_tool_executor(|tool_name: &str, tool_input: &str| {
+        // 5. Let the function calling LLM, if any, extract tool calls the
+        //    main LLM got wrong
+        if let Some(function_calling_llm) = self
+            .create_function_calling_llm_instance()
+            .map_err(|e| format!("Failed to create function calling LLM instance: {}", e))?
+        {
+            executor.set_function_calling_llm_call(move |messages| {
+                let msgs: Vec<LLMMessage> = messages
+                    .iter()
+                    .map(|m| m.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+                    .collect();
+                match function_calling_llm.call(msgs, None, None)? {
+                    serde_json::Value::String(s) => Ok(s),
+                    other => Ok(other.to_string()),
+                }
+            });
+        }
+
+        // 6. Set the tool executor: the scratchpad and registered tools are
+        //    executed (their output passed through the registry's result
+        //    transformers), unknown tools return a stub
+        let scratchpad = self.scratchpad.clone();
+        let role = self.role.clone();
+        let multimodal = self.multimodal;
+        let code_interpreter = self
+            .allow_code_execution
+            .then(|| self.code_interpreter_tool());
+        executor.set_tool_executor(move |tool_name: &str, tool_input: &str| {
             log::info!("Tool call: {}({})", tool_name, tool_input);
-            Ok(format!(
-                "Tool '{}' executed with input: {}",
-                tool_name, tool_input
-            ))
+            let tool_args = serde_json::from_str(tool_input)
+                .unwrap_or_else(|_| serde_json::Value::String(tool_input.to_string()));
+            let started_at = chrono::Utc::now();
+            emit_event(
+                &agent_id,
+                &mut ToolUsageStartedEvent::new(tool_name.to_string(), tool_args.clone(), 1),
+            );
+            let run_tool = || -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+                Ok(match &scratchpad {
+                    Some(pad) if tool_name == "scratchpad" => {
+                        let args = match &tool_args {
+                            serde_json::Value::Object(map) => map.clone().into_iter().collect(),
+                            _ => HashMap::new(),
+                        };
+                        let output = ScratchpadTool::with_scratchpad(pad.clone())
+                            .execute(&args)
+                            .unwrap_or_else(|e| e);
+                        registry.transform(Some(&role), tool_name, output)
+                    }
+                    _ if multimodal && tool_name == ADD_IMAGE_TOOL_NAME => {
+                        let location = tool_args
+                            .get("image_url")
+                            .or(Some(&tool_args))
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default();
+                        let action = tool_args.get("action").and_then(|v| v.as_str());
+                        match AddImageTool::new().load(location, action) {
+                            Ok(message) => message.to_string(),
+                            Err(e) => template.tool_error(tool_name, &e),
+                        }
+                    }
+                    _ if code_interpreter.is_some() && tool_name == CODE_INTERPRETER_ACTION => {
+                        let code = tool_args
+                            .get("code")
+                            .or(Some(&tool_args))
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string();
+                        let tool = code_interpreter.clone().expect("checked by the guard");
+                        match cancellation::run_interruptible(move || tool.execute(&code))? {
+                            Ok(output) => output,
+                            Err(e) => template.tool_error(tool_name, &e.to_string()),
+                        }
+                    }
+                    _ => {
+                        let (registry, role) = (registry.clone(), role.clone());
+                        let (name, args) = (tool_name.to_string(), tool_args.clone());
+                        let executed = cancellation::run_interruptible(move || {
+                            registry.execute(Some(&role), &name, &args)
+                        })?;
+                        match executed {
+                            Some(result) => result
+                                .unwrap_or_else(|e| template.tool_error(tool_name, &e.to_string())),
+                            None => {
+                                format!("Tool '{}' executed with input: {}", tool_name, tool_input)
+                            }
+                        }
+                    }
+                })
+            };
+            let output = match journal::current() {
+                Some(journal) => journal.tool_call(&role, tool_name, &tool_args, run_tool)?,
+                None => run_tool()?,
+            };
+            run_log::record(
+                LogEntryKind::ToolCall,
+                serde_json::json!({ "tool": tool_name, "input": tool_args, "output": output }),
+            );
+            emit_event(
+                &agent_id,
+                &mut ToolUsageFinishedEvent::new(
+                    tool_name.to_string(),
+                    tool_args,
+                    1,
+                    started_at,
+                    chrono::Utc::now(),
+                    false,
+                    serde_json::Value::String(output.clone()),
+                ),
+            );
+            Ok(output)
         });
 
-        // 6. Run the executor
+        // 7. Run the executor
         let mut inputs = HashMap::new();
         inputs.insert("input".to_string(), task_prompt.to_string());
         inputs.insert("tool_names".to_string(), tools_names);
@@ -552,7 +1356,7 @@ impl Agent {
             .invoke(inputs)
             .map_err(|e| format!("Agent execution failed: {}", e))?;
 
-        // 7. Extract the output
+        // 8. Extract the output
         let output = result
             .get("output")
             .and_then(|v| v.as_str())
@@ -569,7 +1373,22 @@ impl Agent {
     ///
     /// Corresponds to `Agent._create_llm` in Python.
     pub fn create_llm_instance(&self) -> Result<Box<dyn BaseLLM>, String> {
-        let llm_str = self.llm.as_deref().unwrap_or("openai/gpt-4o-mini");
+        Self::llm_instance(self.llm.as_deref().unwrap_or("openai/gpt-4o-mini"))
+    }
+
+    /// Create the LLM instance for `function_calling_llm`, if one is set.
+    ///
+    /// The crew's `function_calling_llm` is used for agents without one of
+    /// their own (see `Crew::kickoff`).
+    pub fn create_function_calling_llm_instance(&self) -> Result<Option<Box<dyn BaseLLM>>, String> {
+        self.function_calling_llm
+            .as_deref()
+            .map(Self::llm_instance)
+            .transpose()
+    }
+
+    /// Create an LLM instance from a configuration string.
+    pub(crate) fn llm_instance(llm_str: &str) -> Result<Box<dyn BaseLLM>, String> {
         let (provider, model) = if let Some(idx) = llm_str.find('/') {
             (&llm_str[..idx], &llm_str[idx + 1..])
         } else {
@@ -650,7 +1469,13 @@ impl Agent {
     /// # Arguments
     ///
     /// * `mcps` - List of MCP server reference strings.
-    pub fn get_mcp_tools(&self, mcps: &[String]) -> Vec<String> {
+    ///
+    /// # Returns
+    ///
+    /// Names of the tools registered in the agent's tool registry. They stay
+    /// registered until [`cleanup_mcp_clients`](Self::cleanup_mcp_clients)
+    /// runs at the end of the task.
+    pub fn get_mcp_tools(&mut self, mcps: &[String]) -> Vec<String> {
         let mut all_tools = Vec::new();
         for mcp_ref in mcps {
             if mcp_ref.starts_with("crewai-amp:") {
@@ -665,9 +1490,35 @@ impl Agent {
     }
 
     /// Get tools from external HTTPS MCP server.
-    fn get_external_mcp_tools(&self, _mcp_ref: &str) -> Vec<String> {
-        // TODO: Implement MCP tool discovery via HTTP/SSE transport.
-        Vec::new()
+    fn get_external_mcp_tools(&mut self, mcp_ref: &str) -> Vec<String> {
+        let (server_url, tool_name) = match mcp_ref.split_once('#') {
+            Some((url, tool)) => (url, Some(tool.to_string())),
+            None => (mcp_ref, None),
+        };
+        let mut toolset = MCPToolset::from_config(
+            Self::extract_server_name(server_url),
+            &MCPServerHTTP::new(server_url).into(),
+        );
+        if let Some(tool_name) = tool_name {
+            toolset = toolset.with_tool_filter(std::sync::Arc::new(move |tool| {
+                tool.get("name").and_then(|n| n.as_str()) == Some(tool_name.as_str())
+            }));
+        }
+
+        let registry = self
+            .tool_registry
+            .clone()
+            .unwrap_or_else(|| ToolRegistry::global().clone());
+        match toolset.register_blocking(&registry) {
+            Ok(names) => {
+                self.mcp_clients.push(toolset);
+                names
+            }
+            Err(e) => {
+                log::warn!("Failed to get MCP tools from {}: {}", server_url, e);
+                Vec::new()
+            }
+        }
     }
 
     /// Get tools from CrewAI AMP MCP marketplace.
@@ -678,7 +1529,17 @@ impl Agent {
 
     /// Cleanup MCP client connections after task execution.
     fn cleanup_mcp_clients(&mut self) {
-        self.mcp_clients.clear();
+        for toolset in std::mem::take(&mut self.mcp_clients) {
+            let names = toolset.tool_names();
+            self.tools.retain(|t| !names.contains(t));
+            if let Err(e) = toolset.disconnect_blocking() {
+                log::warn!(
+                    "Failed to disconnect MCP server '{}': {}",
+                    toolset.server_name,
+                    e
+                );
+            }
+        }
     }
 
     /// Extract server name from URL for tool prefixing.
@@ -692,18 +1553,41 @@ impl Agent {
             .to_string()
     }
 
+    /// File of the suggestions distilled from training that the agent
+    /// follows.
+    pub fn trained_agents_file(&self) -> &str {
+        self.trained_agents_file
+            .as_deref()
+            .unwrap_or(TRAINED_AGENTS_DATA_FILE)
+    }
+
     /// Get code execution tools.
     pub fn get_code_execution_tools(&self) -> Vec<String> {
         if !self.allow_code_execution {
             return Vec::new();
         }
-        // TODO: Integrate with CodeInterpreterTool.
-        vec!["code_interpreter".to_string()]
+        vec![CODE_INTERPRETER_ACTION.to_string()]
+    }
+
+    /// The code interpreter run by the agent's `code_interpreter` tool:
+    /// sandboxed per `code_execution_mode` and checked against the agent's
+    /// policy engine.
+    pub fn code_interpreter_tool(&self) -> CodeInterpreterTool {
+        let tool = CodeInterpreterTool::new();
+        let mut tool = match self.code_execution_mode {
+            CodeExecutionMode::Safe => tool.with_sandbox(CodeSandbox::docker()),
+            CodeExecutionMode::Unsafe => tool.with_unsafe_sandbox(CodeSandbox::unsafe_default()),
+        }
+        .with_agent(self.id.to_string(), vec![self.role.clone()]);
+        if let Some(policy) = &self.policy {
+            tool = tool.with_policy(policy.clone());
+        }
+        tool
     }
 
     /// Get multimodal tools.
     pub fn get_multimodal_tools() -> Vec<String> {
-        vec!["add_image".to_string()]
+        vec![ADD_IMAGE_TOOL_NAME.to_string()]
     }
 
     /// Compute the key property (MD5 hash of role|goal|backstory).
@@ -722,7 +1606,10 @@ impl Agent {
     }
 
     /// Interpolate inputs into the agent role, goal, and backstory.
-    pub fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) {
+    ///
+    /// # Errors
+    /// Returns an error naming the first placeholder missing from `inputs`.
+    pub fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) -> Result<(), String> {
         if self.original_role.is_none() {
             self.original_role = Some(self.role.clone());
         }
@@ -734,46 +1621,64 @@ impl Agent {
         }
 
         if inputs.is_empty() {
-            return;
+            return Ok(());
         }
 
-        if let Some(ref orig) = self.original_role {
-            self.role = interpolate_string(orig, inputs);
-        }
-        if let Some(ref orig) = self.original_goal {
-            self.goal = interpolate_string(orig, inputs);
-        }
-        if let Some(ref orig) = self.original_backstory {
-            self.backstory = interpolate_string(orig, inputs);
-        }
+        let field = |name: &str, template: &Option<String>| {
+            interpolate_only(template.as_deref(), inputs)
+                .map_err(|e| format!("Agent {}: {}", name, e))
+        };
+        let role = field("role", &self.original_role)?;
+        let goal = field("goal", &self.original_goal)?;
+        let backstory = field("backstory", &self.original_backstory)?;
+        self.role = role;
+        self.goal = goal;
+        self.backstory = backstory;
+        Ok(())
     }
 
     /// Simple kickoff for standalone agent execution.
     ///
-    /// Executes the agent with the given messages without requiring a Crew.
-    /// Supports tools, response formatting, guardrails, and file inputs.
-    ///
-    /// # Arguments
+    /// Executes the agent on `messages` as an implicit single task, with
+    /// its tools and knowledge, without requiring a Crew or Task. System
+    /// messages are passed as the task's context; the other messages form
+    /// the task description.
     ///
-    /// * `query` - The query or messages string to execute.
-    pub fn kickoff(&mut self, query: &str) -> Result<String, String> {
-        log::debug!("Agent '{}' kickoff with query: {}", self.role, query);
-
-        // TODO: Implement full standalone execution:
-        // 1. Process platform apps and MCP tools
-        // 2. Parse tools
-        // 3. Build prompts
-        // 4. Create AgentExecutor
-        // 5. Execute and build output
-        // 6. Process guardrails
-        // 7. Return LiteAgentOutput
-
-        Ok(format!("[Agent '{}' response to: {}]", self.role, query))
+    /// Corresponds to `Agent.kickoff` in Python.
+    pub fn kickoff(
+        &mut self,
+        messages: Vec<crate::utilities::types::LLMMessage>,
+    ) -> Result<LiteAgentOutput, String> {
+        let (context, request) = crate::lite_agent::split_messages(&messages);
+        log::debug!("Agent '{}' kickoff with request: {}", self.role, request);
+
+        let usage = UsageScope::enter();
+        let raw = self.execute_task(&request, context.as_deref(), None)?;
+        let mut output =
+            LiteAgentOutput::new(raw, self.role.clone()).with_usage_metrics(&usage.usage());
+        output.messages = messages;
+        Ok(output)
     }
 
     /// Async version of kickoff.
-    pub async fn kickoff_async(&mut self, query: &str) -> Result<String, String> {
-        self.kickoff(query)
+    ///
+    /// The agent's LLM calls block, so the kickoff runs on tokio's blocking
+    /// thread pool. The agent moves there for the run; should the future
+    /// be dropped before completing, this agent is left as a new agent with
+    /// the same role, goal and backstory.
+    pub async fn kickoff_async(
+        &mut self,
+        messages: Vec<crate::utilities::types::LLMMessage>,
+    ) -> Result<LiteAgentOutput, String> {
+        let placeholder = Agent::new(self.role.clone(), self.goal.clone(), self.backstory.clone());
+        let mut agent = std::mem::replace(self, placeholder);
+        let (agent, output) = cancellation::spawn_blocking(move || {
+            let output = agent.kickoff(messages);
+            (agent, output)
+        })
+        .await;
+        *self = agent;
+        output
     }
 
     /// Inject the current date into a task description if inject_date is enabled.
@@ -810,11 +1715,41 @@ impl Agent {
         self.security_config = security_config;
     }
 
+    /// Fingerprint of the agent's definition: its role, goal and backstory
+    /// (before input interpolation) and model.
+    pub fn definition_fingerprint(&self) -> Fingerprint {
+        Fingerprint::of_definition(
+            "agent",
+            &serde_json::json!({
+                "role": self.original_role.as_ref().unwrap_or(&self.role),
+                "goal": self.original_goal.as_ref().unwrap_or(&self.goal),
+                "backstory": self.original_backstory.as_ref().unwrap_or(&self.backstory),
+                "llm": self.llm,
+            }),
+        )
+    }
+
+    /// Bring a definition fingerprint up to date with the agent's
+    /// definition (see [`Fingerprint::update_definition`]).
+    pub fn refresh_fingerprint(&mut self) {
+        let fingerprint = self.definition_fingerprint();
+        self.security_config
+            .fingerprint
+            .update_definition(fingerprint);
+    }
+
     /// Validate Docker installation for code execution.
     fn validate_docker_installation(&self) -> Result<(), String> {
-        // TODO: Check if Docker is installed and running.
-        if self.allow_code_execution {
-            log::debug!("Validating Docker installation for agent '{}'", self.role);
+        if !self.allow_code_execution || self.code_execution_mode != CodeExecutionMode::Safe {
+            return Ok(());
+        }
+        log::debug!("Validating Docker installation for agent '{}'", self.role);
+        if !code_interpreter::docker_available() {
+            return Err(format!(
+                "Docker is not installed or not running, which agent '{}' needs to execute \
+                 code in safe mode. Install Docker or set code_execution_mode to Unsafe.",
+                self.role
+            ));
         }
         Ok(())
     }
@@ -830,12 +1765,94 @@ impl std::fmt::Display for Agent {
     }
 }
 
-/// Simple string interpolation: replace `{key}` with corresponding value.
-fn interpolate_string(template: &str, inputs: &HashMap<String, String>) -> String {
-    let mut result = template.to_string();
-    for (key, value) in inputs {
-        let pattern = format!("{{{}}}", key);
-        result = result.replace(&pattern, value);
+/// Emit an event on the global event bus, if it has been initialised.
+fn emit_event<E: BaseEvent + 'static>(agent_id: &str, event: &mut E) {
+    if let Some(bus) = CREWAI_EVENT_BUS.get() {
+        bus.emit(std::sync::Arc::new(agent_id.to_string()), event);
+    }
+}
+
+/// Replace each `{name}` and `{{ .Name }}` placeholder in `template` with its
+/// `value`, scanning the template once. Placeholders without a value are
+/// kept as they are.
+fn fill_placeholders<'a>(template: &str, value: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = if rest.starts_with("{{") {
+            rest.find("}}").map(|end| end + 2)
+        } else {
+            rest.find('}').map(|end| end + 1)
+        };
+        match end.and_then(|end| Some((end, value(&rest[..end])?))) {
+            Some((end, value)) => {
+                filled.push_str(value);
+                rest = &rest[end..];
+            }
+            None => {
+                filled.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::journal::{ExecutionJournal, JournalScope};
+
+    #[tokio::test]
+    async fn test_kickoff_async_inside_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let entries = [
+            serde_json::json!({"type": "seed", "seed": 7}),
+            serde_json::json!({
+                "type": "llm_call",
+                "request_key": "recorded",
+                "agent": "Researcher",
+                "response": "Thought: I know this.\nFinal Answer: 2015",
+            }),
+        ];
+        let lines: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let mut agent = Agent::new(
+            "Researcher".to_string(),
+            "Find release dates".to_string(),
+            "A historian of programming languages".to_string(),
+        );
+        agent.verbose = true;
+        // No provider is reachable: the LLM call must come from the journal.
+        let _journal = JournalScope::enter(ExecutionJournal::replay(&path).unwrap());
+        let messages = vec![HashMap::from([
+            ("role".to_string(), "user".to_string()),
+            ("content".to_string(), "When was Rust released?".to_string()),
+        ])];
+        let output = agent.kickoff_async(messages).await.unwrap();
+        assert_eq!(output.raw, "2015");
+        assert_eq!(output.agent_role, "Researcher");
+        assert!(agent.verbose);
+    }
+
+    #[test]
+    fn test_build_prompt_fills_placeholders_once() {
+        let mut agent = Agent::new(
+            "Analyst".to_string(),
+            "Explain {task} and {{ .System }}".to_string(),
+            "".to_string(),
+        );
+        agent.system_template = Some("{{ .System }}\nGoal: {goal} {unknown}".to_string());
+        agent.prompt_template = Some("{\"task\": \"{task}\"}".to_string());
+
+        let prompt = agent.build_prompt("Summarize {goal}", &[]);
+        assert!(prompt["system"].starts_with("You are Analyst."));
+        assert!(prompt["system"].ends_with("Goal: Explain {task} and {{ .System }} {unknown}"));
+        assert_eq!(prompt["user"], "{\"task\": \"Summarize {goal}\"}");
     }
-    result
 }
@@ -8,6 +8,7 @@
 
 pub mod core;
 pub mod internal;
+pub mod repository;
 pub mod utils;
 
 // Re-export the main Agent type.
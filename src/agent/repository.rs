@@ -0,0 +1,462 @@
+//! Shareable agent definitions.
+//!
+//! Python's `Agent(from_repository="name")` pulls an agent published to the
+//! CrewAI platform. Here a repository is either a local registry (a
+//! directory of definition files, or one YAML file in the format of
+//! `agents.yaml`) or an HTTP registry serving definitions as JSON. A
+//! definition is an [`AgentConfig`]: role, goal, backstory, tool names,
+//! model and the optional agent settings.
+//!
+//! [`Agent::from_repository`] loads from the repository named by
+//! `$CREWAI_AGENT_REPOSITORY`. Definitions are cached for the life of the
+//! process, and remote ones also on disk, so a registry that goes offline
+//! keeps serving the last definition it returned.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use once_cell::sync::OnceCell;
+use thiserror::Error;
+
+use super::core::Agent;
+use crate::project::config::{build_agent, entries};
+use crate::project::{AgentConfig, ConfigError};
+
+/// Location of the repository [`Agent::from_repository`] loads from: an
+/// `http(s)://` registry URL, or a local directory or YAML file.
+pub const AGENT_REPOSITORY_ENV: &str = "CREWAI_AGENT_REPOSITORY";
+
+/// Bearer token sent to a remote agent registry.
+pub const AGENT_REPOSITORY_TOKEN_ENV: &str = "CREWAI_AGENT_REPOSITORY_TOKEN";
+
+/// How long a remote definition cached on disk is used without asking the
+/// registry again.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Errors raised while loading an agent from a repository.
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    /// No repository is configured.
+    #[error("no agent repository configured; set {AGENT_REPOSITORY_ENV}")]
+    NotConfigured,
+    /// The name cannot be an agent name.
+    #[error("invalid agent name '{0}': use letters, digits, '-' and '_'")]
+    InvalidName(String),
+    /// The repository has no agent of that name.
+    #[error("agent '{name}' not found in {repository}")]
+    NotFound { name: String, repository: String },
+    /// A definition file could not be read.
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A definition is not a valid agent config.
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    /// The registry could not be reached.
+    #[error("agent registry request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    /// The registry answered with an error status.
+    #[error("agent registry returned {status} for '{name}': {body}")]
+    Http {
+        name: String,
+        status: u16,
+        body: String,
+    },
+}
+
+/// Where a repository's definitions come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepositorySource {
+    /// A directory of `<name>.yaml`, `<name>.yml` or `<name>.json` files,
+    /// or one YAML file mapping names to definitions.
+    Local(PathBuf),
+    /// An HTTP registry serving `GET {url}/agents/{name}` as JSON.
+    Remote { url: String, token: Option<String> },
+}
+
+impl RepositorySource {
+    /// A remote source for `http(s)://` URLs, a local one otherwise.
+    pub fn parse(location: &str) -> Self {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            Self::Remote {
+                url: location.trim_end_matches('/').to_string(),
+                token: None,
+            }
+        } else {
+            Self::Local(PathBuf::from(location))
+        }
+    }
+}
+
+impl std::fmt::Display for RepositorySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local(path) => write!(f, "{}", path.display()),
+            Self::Remote { url, .. } => f.write_str(url),
+        }
+    }
+}
+
+/// Loads agent definitions from a [`RepositorySource`], caching them.
+#[derive(Debug)]
+pub struct AgentRepository {
+    source: RepositorySource,
+    cache_dir: Option<PathBuf>,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<String, AgentConfig>>,
+}
+
+impl AgentRepository {
+    /// A repository reading from `source`, caching definitions in memory.
+    pub fn new(source: RepositorySource) -> Self {
+        Self {
+            source,
+            cache_dir: None,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The repository named by `$CREWAI_AGENT_REPOSITORY`, authenticated
+    /// with `$CREWAI_AGENT_REPOSITORY_TOKEN`. Remote definitions are cached
+    /// on disk under the CrewAI data directory.
+    pub fn from_env() -> Result<Self, RepositoryError> {
+        let location = std::env::var(AGENT_REPOSITORY_ENV)
+            .ok()
+            .filter(|location| !location.trim().is_empty())
+            .ok_or(RepositoryError::NotConfigured)?;
+        let mut source = RepositorySource::parse(location.trim());
+        if let RepositorySource::Remote { token, .. } = &mut source {
+            *token = std::env::var(AGENT_REPOSITORY_TOKEN_ENV).ok();
+        }
+        let cache_dir = crate::utilities::paths::cache_dir().join("agent_repository");
+        Ok(Self::new(source).with_cache_dir(cache_dir))
+    }
+
+    /// Also cache remote definitions as JSON files in `dir`.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(dir.into());
+        self
+    }
+
+    /// How long a remote definition cached on disk is used without asking
+    /// the registry again.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Where the definitions come from.
+    pub fn source(&self) -> &RepositorySource {
+        &self.source
+    }
+
+    /// The definition of agent `name`.
+    pub fn definition(&self, name: &str) -> Result<AgentConfig, RepositoryError> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(RepositoryError::InvalidName(name.to_string()));
+        }
+        if let Some(config) = self.cache.lock().unwrap().get(name) {
+            return Ok(config.clone());
+        }
+        let config = match &self.source {
+            RepositorySource::Local(path) => self.read_local(path, name)?,
+            RepositorySource::Remote { url, token } => {
+                self.fetch_remote(url, token.as_deref(), name)?
+            }
+        };
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), config.clone());
+        Ok(config)
+    }
+
+    /// Agent `name`, built from its definition.
+    pub fn load_agent(&self, name: &str) -> Result<Agent, RepositoryError> {
+        let mut agent = build_agent(&self.definition(name)?);
+        agent.from_repository = Some(name.to_string());
+        Ok(agent)
+    }
+
+    /// Forget the definitions cached in memory, so they are loaded again.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn read_local(&self, path: &Path, name: &str) -> Result<AgentConfig, RepositoryError> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path).map_err(|source| RepositoryError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+        let not_found = || RepositoryError::NotFound {
+            name: name.to_string(),
+            repository: self.source.to_string(),
+        };
+        if path.is_file() {
+            let file = path.display().to_string();
+            return entries::<AgentConfig>(&file, &read(path)?)?
+                .into_iter()
+                .find_map(|(entry, config)| (entry == name).then_some(config))
+                .ok_or_else(not_found);
+        }
+        for extension in ["yaml", "yml", "json"] {
+            let file = path.join(format!("{}.{}", name, extension));
+            if file.is_file() {
+                return parse_definition(&file.display().to_string(), name, &read(&file)?);
+            }
+        }
+        Err(not_found())
+    }
+
+    fn fetch_remote(
+        &self,
+        url: &str,
+        token: Option<&str>,
+        name: &str,
+    ) -> Result<AgentConfig, RepositoryError> {
+        let cached = self
+            .cache_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.json", name)));
+        let cached_definition = |max_age: Option<Duration>| {
+            let path = cached.as_ref()?;
+            let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+            let age = SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default();
+            if max_age.is_some_and(|max_age| age > max_age) {
+                return None;
+            }
+            let json = std::fs::read_to_string(path).ok()?;
+            parse_definition(&path.display().to_string(), name, &json).ok()
+        };
+        if let Some(config) = cached_definition(Some(self.cache_ttl)) {
+            return Ok(config);
+        }
+
+        let request = async {
+            let mut request = reqwest::Client::new().get(format!("{}/agents/{}", url, name));
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            let response = request.send().await?;
+            let status = response.status().as_u16();
+            let body = response.text().await?;
+            Ok::<_, reqwest::Error>((status, body))
+        };
+        let (status, body) = match block_on(request) {
+            Ok(response) => response,
+            Err(e) => {
+                let config = cached_definition(None).ok_or(RepositoryError::Request(e))?;
+                log::warn!("Agent registry unreachable, using cached '{}'", name);
+                return Ok(config);
+            }
+        };
+        match status {
+            200..=299 => {}
+            404 => {
+                return Err(RepositoryError::NotFound {
+                    name: name.to_string(),
+                    repository: url.to_string(),
+                })
+            }
+            _ => {
+                return Err(RepositoryError::Http {
+                    name: name.to_string(),
+                    status,
+                    body,
+                })
+            }
+        }
+        let config = parse_definition(&format!("{}/agents/{}", url, name), name, &body)?;
+        if let (Some(path), Some(dir)) = (&cached, &self.cache_dir) {
+            let written = std::fs::create_dir_all(dir).and_then(|()| {
+                std::fs::write(
+                    path,
+                    serde_json::to_string_pretty(&config).unwrap_or_default(),
+                )
+            });
+            if let Err(e) = written {
+                log::warn!(
+                    "Failed to cache agent '{}' in {}: {}",
+                    name,
+                    dir.display(),
+                    e
+                );
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// The repository named by `$CREWAI_AGENT_REPOSITORY`, shared by the
+/// process so each definition is loaded once.
+pub fn global_repository() -> Result<&'static AgentRepository, RepositoryError> {
+    static REPOSITORY: OnceCell<AgentRepository> = OnceCell::new();
+    REPOSITORY.get_or_try_init(AgentRepository::from_env)
+}
+
+/// A single definition from YAML or JSON (JSON is valid YAML).
+fn parse_definition(file: &str, name: &str, text: &str) -> Result<AgentConfig, RepositoryError> {
+    serde_yaml::from_str(text).map_err(|e| {
+        ConfigError::InvalidEntry {
+            file: file.to_string(),
+            name: name.to_string(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+/// Run `future` to completion on a thread of its own, so it may be called
+/// from inside a runtime.
+fn block_on<F>(future: F) -> F::Output
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("failed to build runtime")
+                    .block_on(future)
+            })
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const RESEARCHER: &str = "role: Researcher\ngoal: Find facts\nbackstory: Thorough\ntools: [search]\nllm: gpt-4o-mini\n";
+
+    #[test]
+    fn test_local_repository_loads_files_and_registries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("researcher.yaml"), RESEARCHER).unwrap();
+        std::fs::write(
+            dir.path().join("writer.json"),
+            r#"{"role": "Writer", "goal": "Write", "backstory": "Concise", "max_iter": 3}"#,
+        )
+        .unwrap();
+        let repository = AgentRepository::new(RepositorySource::Local(dir.path().into()));
+
+        let agent = repository.load_agent("researcher").unwrap();
+        assert_eq!(agent.role, "Researcher");
+        assert_eq!(agent.tools, ["search"]);
+        assert_eq!(agent.llm.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(agent.from_repository.as_deref(), Some("researcher"));
+        assert_eq!(repository.load_agent("writer").unwrap().max_iter, 3);
+
+        // Served from memory once loaded.
+        std::fs::remove_file(dir.path().join("researcher.yaml")).unwrap();
+        assert!(repository.definition("researcher").is_ok());
+        repository.clear_cache();
+        assert!(matches!(
+            repository.definition("researcher"),
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert!(matches!(
+            repository.definition("../writer"),
+            Err(RepositoryError::InvalidName(_))
+        ));
+
+        let registry = dir.path().join("agents.yaml");
+        std::fs::write(
+            &registry,
+            format!("researcher:\n  {}", RESEARCHER.replace('\n', "\n  ")),
+        )
+        .unwrap();
+        let repository = AgentRepository::new(RepositorySource::parse(&registry.to_string_lossy()));
+        assert_eq!(
+            repository.definition("researcher").unwrap().goal,
+            "Find facts"
+        );
+        assert!(repository.definition("writer").is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_repository_caches_definitions() {
+        use axum::extract::Path;
+        use axum::http::{HeaderMap, StatusCode};
+        use axum::routing::get;
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let router = axum::Router::new().route(
+            "/agents/:name",
+            get(
+                move |Path(name): Path<String>, headers: HeaderMap| async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    assert_eq!(headers["authorization"], "Bearer secret");
+                    match name.as_str() {
+                        "researcher" => (
+                            StatusCode::OK,
+                            serde_json::to_string(
+                                &serde_yaml::from_str::<serde_json::Value>(RESEARCHER).unwrap(),
+                            )
+                            .unwrap(),
+                        ),
+                        _ => (StatusCode::NOT_FOUND, String::new()),
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let cache = tempfile::tempdir().unwrap();
+        let remote = |url: &str| {
+            AgentRepository::new(RepositorySource::Remote {
+                url: url.to_string(),
+                token: Some("secret".to_string()),
+            })
+            .with_cache_dir(cache.path())
+        };
+        let repository = remote(&url);
+        assert_eq!(
+            repository.load_agent("researcher").unwrap().role,
+            "Researcher"
+        );
+        assert_eq!(
+            repository.load_agent("researcher").unwrap().role,
+            "Researcher"
+        );
+        assert!(matches!(
+            repository.definition("ghost"),
+            Err(RepositoryError::NotFound { .. })
+        ));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // A fresh disk cache is used without a request; a stale one only
+        // when the registry is unreachable.
+        assert!(remote(&url).definition("researcher").is_ok());
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        let offline = remote("http://127.0.0.1:1").with_cache_ttl(Duration::ZERO);
+        assert_eq!(offline.definition("researcher").unwrap().role, "Researcher");
+        assert!(matches!(
+            offline.definition("writer"),
+            Err(RepositoryError::Request(_))
+        ));
+    }
+}
@@ -9,40 +9,58 @@
 
 use std::collections::HashMap;
 
+use super::core::Agent;
+use crate::utilities::reasoning_handler::AgentReasoning;
+use crate::utilities::training_handler::CrewTrainingHandler;
+
 /// Handle reasoning/chain-of-thought for an agent's task execution.
 ///
-/// If the agent has reasoning enabled, this generates a plan using the
-/// agent's LLM before actual task execution begins.
+/// With `reasoning` enabled, the agent plans the task with its LLM (see
+/// [`AgentReasoning`]) before executing it, and the plan is appended to
+/// the task prompt. When planning fails the prompt is returned unchanged.
 ///
 /// # Arguments
 ///
-/// * `agent_role` - The role of the agent performing reasoning.
-/// * `task_description` - The task to reason about.
-/// * `max_attempts` - Maximum number of reasoning iterations.
+/// * `agent` - The agent performing reasoning.
+/// * `task_prompt` - The task to reason about.
 ///
 /// # Returns
 ///
-/// The reasoning result string.
-pub fn handle_reasoning(
-    agent_role: &str,
-    task_description: &str,
-    max_attempts: i32,
-) -> Result<String, String> {
-    log::debug!(
-        "handle_reasoning: agent='{}', task='{}', max_attempts={}",
-        agent_role,
-        task_description,
-        max_attempts
+/// The task prompt, with the reasoning plan if one was made.
+pub fn handle_reasoning(agent: &Agent, task_prompt: &str) -> String {
+    // A task's prompt ends with its expected output (see `Task::prompt`).
+    let (description, expected_output) = task_prompt
+        .rsplit_once("\nExpected Output: ")
+        .unwrap_or((task_prompt, ""));
+    let mut reasoning = AgentReasoning::new(
+        agent.role.clone(),
+        agent.goal.clone(),
+        agent.backstory.clone(),
+        description,
+        expected_output,
     );
-    // TODO: Implement iterative reasoning with LLM via AgentReasoning handler.
-    // In Python this delegates to:
-    //   reasoning_handler = AgentReasoning(task=task, agent=agent)
-    //   reasoning_output = reasoning_handler.handle_agent_reasoning()
-    //   task.description += f"\n\nReasoning Plan:\n{reasoning_output.plan.plan}"
-    Ok(format!(
-        "[Reasoning result for '{}' on task: {}]",
-        agent_role, task_description
-    ))
+    reasoning.tools = agent.tools.clone();
+    if let Some(max_attempts) = agent.max_reasoning_attempts {
+        reasoning.max_attempts = max_attempts.max(1) as usize;
+    }
+    reasoning.agent_id = Some(agent.id.to_string());
+
+    let planned = agent.create_llm_instance().and_then(|llm| {
+        reasoning.handle_agent_reasoning(&mut |messages| {
+            let response = llm.call(messages, None, None).map_err(|e| e.to_string())?;
+            Ok(match response {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            })
+        })
+    });
+    match planned {
+        Ok(output) => output.apply_to(task_prompt),
+        Err(e) => {
+            log::warn!("Reasoning for agent '{}' failed: {}", agent.role, e);
+            task_prompt.to_string()
+        }
+    }
 }
 
 /// Build the task prompt with schema information for structured output.
@@ -154,28 +172,43 @@ pub async fn ahandle_knowledge_retrieval(
 
 /// Apply training data to the task prompt.
 ///
-/// If the crew is in training mode, applies training handler data.
-/// Otherwise, applies previously trained agent data.
+/// While the agent is being trained, the human feedback recorded for it in
+/// `training_file` is appended as instructions. Otherwise the suggestions
+/// distilled for it into `trained_agents_file` are.
 ///
 /// # Arguments
 ///
 /// * `agent_role` - The role of the agent.
 /// * `task_prompt` - The current task prompt.
-/// * `is_training` - Whether the crew is in training mode.
+/// * `training_file` - Training data file, when the agent is being trained.
+/// * `trained_agents_file` - File of the trained agents' suggestions.
 ///
 /// # Returns
 ///
 /// The task prompt with training data applied.
-pub fn apply_training_data(agent_role: &str, task_prompt: &str, is_training: bool) -> String {
+pub fn apply_training_data(
+    agent_role: &str,
+    task_prompt: &str,
+    training_file: Option<&str>,
+    trained_agents_file: &str,
+) -> String {
     log::debug!(
         "apply_training_data: agent='{}', training={}",
         agent_role,
-        is_training
+        training_file.is_some()
     );
-    // TODO: Implement training data application:
-    //   - If training: load from TRAINING_DATA_FILE, append human_feedbacks
-    //   - If not training: load from TRAINED_AGENTS_DATA_FILE, append suggestions
-    task_prompt.to_string()
+    let instructions = match training_file {
+        Some(file) => CrewTrainingHandler::for_file(file).human_feedbacks(agent_role),
+        None => CrewTrainingHandler::for_file(trained_agents_file).suggestions(agent_role),
+    };
+    if instructions.is_empty() {
+        return task_prompt.to_string();
+    }
+    format!(
+        "{}\n\nYou MUST follow these instructions: \n - {}",
+        task_prompt,
+        instructions.join("\n - ")
+    )
 }
 
 /// Process tool results after execution.
@@ -289,7 +322,7 @@ pub fn combine_knowledge_context(
     let agent_ctx = agent_context.unwrap_or("");
     let crew_ctx = crew_context.unwrap_or("");
     let separator = if !agent_ctx.is_empty() && !crew_ctx.is_empty() {
-        "\n"
+        "\n\n"
     } else {
         ""
     };
@@ -0,0 +1,476 @@
+//! Adaptive context allocation.
+//!
+//! A [`ContextAllocator`] splits the token budget left after the system and
+//! task prompts between conversation history, retrieved knowledge,
+//! memories and tool schemas. Each section gets a share proportional to its
+//! configured [`ContextRatios`], adjusted by a [`TaskProfile`] derived from
+//! the task; sections needing less than their share pass the surplus on to
+//! the others, so no budget is reserved for empty sections and no section
+//! overflows its grant.
+//!
+//! The executor re-allocates before every LLM call (history grows as the
+//! agent works) and logs each [`ContextAllocation`] under the
+//! `crewai::context` target.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::llm::CONTEXT_WINDOW_USAGE_RATIO;
+
+/// A section of the prompt competing for context budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextSection {
+    /// Messages exchanged while working on the task.
+    History,
+    /// Retrieved knowledge snippets.
+    Knowledge,
+    /// Recalled memories.
+    Memory,
+    /// Tool descriptions or schemas.
+    Tools,
+}
+
+impl ContextSection {
+    /// Snake-case name used in logs.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::History => "history",
+            Self::Knowledge => "knowledge",
+            Self::Memory => "memory",
+            Self::Tools => "tools",
+        }
+    }
+}
+
+impl fmt::Display for ContextSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Relative weights of the context sections. Only their proportions
+/// matter; a weight of zero excludes the section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextRatios {
+    /// Weight of conversation history.
+    pub history: f64,
+    /// Weight of retrieved knowledge.
+    pub knowledge: f64,
+    /// Weight of memories.
+    pub memory: f64,
+    /// Weight of tool schemas.
+    pub tools: f64,
+}
+
+impl Default for ContextRatios {
+    fn default() -> Self {
+        Self {
+            history: 0.35,
+            knowledge: 0.3,
+            memory: 0.2,
+            tools: 0.15,
+        }
+    }
+}
+
+impl ContextRatios {
+    /// Weight of `section`.
+    pub fn weight(&self, section: ContextSection) -> f64 {
+        let weight = match section {
+            ContextSection::History => self.history,
+            ContextSection::Knowledge => self.knowledge,
+            ContextSection::Memory => self.memory,
+            ContextSection::Tools => self.tools,
+        };
+        weight.max(0.0)
+    }
+}
+
+/// Words suggesting a task leans on retrieved knowledge.
+const RETRIEVAL_HINTS: &[&str] = &[
+    "research",
+    "according to",
+    "document",
+    "knowledge",
+    "source",
+    "cite",
+    "look up",
+    "find",
+];
+
+/// Words suggesting a task builds on earlier exchanges.
+const CONVERSATION_HINTS: &[&str] = &[
+    "conversation",
+    "follow up",
+    "follow-up",
+    "previous",
+    "earlier",
+    "continue",
+    "chat",
+];
+
+/// Characteristics of a task that shift the context split.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskProfile {
+    /// The task relies on retrieved knowledge and memories.
+    pub retrieval_heavy: bool,
+    /// The task builds on earlier conversation.
+    pub conversational: bool,
+}
+
+impl TaskProfile {
+    /// Profile a task from its description.
+    pub fn from_task(description: &str) -> Self {
+        let text = description.to_lowercase();
+        let mentions = |hints: &[&str]| hints.iter().any(|hint| text.contains(hint));
+        Self {
+            retrieval_heavy: mentions(RETRIEVAL_HINTS),
+            conversational: mentions(CONVERSATION_HINTS),
+        }
+    }
+
+    /// Weight of `section` under `ratios` for this task.
+    pub fn weight(&self, ratios: &ContextRatios, section: ContextSection) -> f64 {
+        let boost = match section {
+            ContextSection::Knowledge if self.retrieval_heavy => 1.5,
+            ContextSection::Memory if self.retrieval_heavy => 1.25,
+            ContextSection::History if self.conversational => 1.5,
+            _ => 1.0,
+        };
+        ratios.weight(section) * boost
+    }
+}
+
+/// Tokens requested by and granted to one section.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectionAllocation {
+    /// The section.
+    pub section: ContextSection,
+    /// Tokens the section's content needs.
+    pub requested: usize,
+    /// Tokens the section may use.
+    pub granted: usize,
+}
+
+/// One allocation decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextAllocation {
+    /// Total budget in tokens.
+    pub budget: usize,
+    /// Tokens taken by fixed prompt parts (system and task prompts).
+    pub fixed: usize,
+    /// Per-section grants.
+    pub sections: Vec<SectionAllocation>,
+}
+
+impl ContextAllocation {
+    /// Tokens granted to `section` (0 when it requested none).
+    pub fn granted(&self, section: ContextSection) -> usize {
+        self.sections
+            .iter()
+            .find(|s| s.section == section)
+            .map_or(0, |s| s.granted)
+    }
+
+    /// Tokens used by fixed parts and all grants.
+    pub fn total(&self) -> usize {
+        self.fixed + self.sections.iter().map(|s| s.granted).sum::<usize>()
+    }
+}
+
+impl fmt::Display for ContextAllocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "context budget {} tokens: fixed {}",
+            self.budget, self.fixed
+        )?;
+        for s in &self.sections {
+            write!(f, ", {} {}/{}", s.section, s.granted, s.requested)?;
+        }
+        Ok(())
+    }
+}
+
+/// Splits a token budget between context sections.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextAllocator {
+    /// Token budget for the whole prompt.
+    pub budget: usize,
+    /// Section weights.
+    pub ratios: ContextRatios,
+}
+
+impl ContextAllocator {
+    /// An allocator for a prompt of at most `budget` tokens.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            ratios: ContextRatios::default(),
+        }
+    }
+
+    /// An allocator using the usable part of a model's context window.
+    pub fn for_context_window(context_window: usize) -> Self {
+        Self::new((context_window as f64 * CONTEXT_WINDOW_USAGE_RATIO) as usize)
+    }
+
+    /// Builder: use `ratios`.
+    pub fn with_ratios(mut self, ratios: ContextRatios) -> Self {
+        self.ratios = ratios;
+        self
+    }
+
+    /// Split the budget left after `fixed` tokens between the `requested`
+    /// sections.
+    pub fn allocate(
+        &self,
+        profile: &TaskProfile,
+        fixed: usize,
+        requested: &[(ContextSection, usize)],
+    ) -> ContextAllocation {
+        let mut sections: Vec<SectionAllocation> = requested
+            .iter()
+            .map(|&(section, requested)| SectionAllocation {
+                section,
+                requested,
+                granted: 0,
+            })
+            .collect();
+        let weight = |s: &SectionAllocation| profile.weight(&self.ratios, s.section);
+
+        let mut remaining = self.budget.saturating_sub(fixed);
+        let mut open: Vec<usize> = (0..sections.len())
+            .filter(|&i| sections[i].requested > 0 && weight(&sections[i]) > 0.0)
+            .collect();
+        // Sections whose need fits their share are granted in full and
+        // leave the loop; the rest split what is left by weight.
+        while !open.is_empty() && remaining > 0 {
+            let total: f64 = open.iter().map(|&i| weight(&sections[i])).sum();
+            let share = |i: usize| (remaining as f64 * weight(&sections[i]) / total) as usize;
+            let satisfied: Vec<usize> = open
+                .iter()
+                .copied()
+                .filter(|&i| sections[i].requested <= share(i))
+                .collect();
+            if satisfied.is_empty() {
+                let shares: Vec<(usize, usize)> = open.iter().map(|&i| (i, share(i))).collect();
+                for (i, share) in shares {
+                    sections[i].granted = share;
+                }
+                break;
+            }
+            for i in satisfied {
+                sections[i].granted = sections[i].requested;
+                remaining -= sections[i].requested;
+                open.retain(|&j| j != i);
+            }
+        }
+
+        ContextAllocation {
+            budget: self.budget,
+            fixed,
+            sections,
+        }
+    }
+}
+
+/// Knowledge and memories available to an executor, best first.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextSources {
+    /// Retrieved knowledge snippets.
+    pub knowledge: Vec<String>,
+    /// Recalled memories.
+    pub memories: Vec<String>,
+}
+
+impl ContextSources {
+    /// Split a knowledge context into paragraph snippets.
+    pub fn with_knowledge_text(mut self, text: &str) -> Self {
+        self.knowledge.extend(
+            text.split("\n\n")
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string),
+        );
+        self
+    }
+}
+
+/// How an executor manages its context: the allocator, the task's profile
+/// and the content competing with history and tools.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextPlan {
+    /// Budget splitter.
+    pub allocator: ContextAllocator,
+    /// Profile of the task being executed.
+    pub profile: TaskProfile,
+    /// Knowledge and memories.
+    pub sources: ContextSources,
+}
+
+/// Rough token count of `text` (four characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Keep items, in order, while they fit in `budget` tokens; items that do
+/// not fit are skipped. Returns the kept items.
+pub fn fit_items(items: &[String], budget: usize) -> Vec<&str> {
+    let mut used = 0;
+    items
+        .iter()
+        .filter(|item| {
+            let cost = estimate_tokens(item) + 1;
+            if used + cost <= budget {
+                used += cost;
+                true
+            } else {
+                false
+            }
+        })
+        .map(String::as_str)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocation_redistributes_unused_share() {
+        let allocator = ContextAllocator::new(1_100);
+        let profile = TaskProfile::default();
+        let allocation = allocator.allocate(
+            &profile,
+            100,
+            &[
+                (ContextSection::History, 5_000),
+                (ContextSection::Knowledge, 50),
+                (ContextSection::Memory, 0),
+                (ContextSection::Tools, 5_000),
+            ],
+        );
+        // Knowledge needs less than its share; memory nothing. The rest
+        // goes to history and tools in their 35:15 proportion.
+        assert_eq!(allocation.granted(ContextSection::Knowledge), 50);
+        assert_eq!(allocation.granted(ContextSection::Memory), 0);
+        assert_eq!(allocation.granted(ContextSection::History), 665);
+        assert_eq!(allocation.granted(ContextSection::Tools), 285);
+        assert!(allocation.total() <= 1_100);
+        assert_eq!(
+            allocation.to_string(),
+            "context budget 1100 tokens: fixed 100, history 665/5000, knowledge 50/50, \
+             memory 0/0, tools 285/5000"
+        );
+
+        // Everything fits: every section gets what it asked for.
+        let allocation = allocator.allocate(
+            &profile,
+            0,
+            &[(ContextSection::History, 300), (ContextSection::Tools, 200)],
+        );
+        assert_eq!(allocation.granted(ContextSection::History), 300);
+        assert_eq!(allocation.granted(ContextSection::Tools), 200);
+    }
+
+    #[test]
+    fn test_task_profile_shifts_weights() {
+        let profile = TaskProfile::from_task("Research the sources and cite them");
+        assert!(profile.retrieval_heavy && !profile.conversational);
+
+        let allocator = ContextAllocator::new(1_000).with_ratios(ContextRatios {
+            history: 1.0,
+            knowledge: 1.0,
+            memory: 0.0,
+            tools: 1.0,
+        });
+        let requested = [
+            (ContextSection::History, 1_000),
+            (ContextSection::Knowledge, 1_000),
+            (ContextSection::Memory, 1_000),
+        ];
+        let neutral = allocator.allocate(&TaskProfile::default(), 0, &requested);
+        let boosted = allocator.allocate(&profile, 0, &requested);
+        assert_eq!(neutral.granted(ContextSection::Knowledge), 500);
+        assert_eq!(boosted.granted(ContextSection::Knowledge), 600);
+        assert_eq!(boosted.granted(ContextSection::Memory), 0);
+
+        let items: Vec<String> = ["a".repeat(40), "b".repeat(400), "c".repeat(8)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            fit_items(&items, 20),
+            vec![items[0].as_str(), items[2].as_str()]
+        );
+    }
+
+    #[test]
+    fn test_executor_fits_every_call_to_budget() {
+        use crate::agents::{CrewAgentExecutor, ToolsHandler};
+        use std::collections::HashMap;
+        use std::sync::{Arc, Mutex};
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([
+                ("system".to_string(), "You are a researcher.".to_string()),
+                ("user".to_string(), "{input}".to_string()),
+            ]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            "- search: Search the web".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |messages, _| {
+            let mut calls = recorded.lock().unwrap();
+            calls.push(messages.to_vec());
+            Ok(if calls.len() < 6 {
+                "Thought: look\nAction: search\nAction Input: {}".to_string()
+            } else {
+                "Thought: done\nFinal Answer: summary".to_string()
+            })
+        });
+        executor.set_tool_executor(|_, _| Ok("finding ".repeat(50)));
+        executor.set_context_plan(Some(ContextPlan {
+            allocator: ContextAllocator::new(300),
+            profile: TaskProfile::from_task("Summarize the findings"),
+            sources: ContextSources::default()
+                .with_knowledge_text("Rust 1.93 is out.\n\nCrews run tasks in order."),
+        }));
+
+        let inputs = HashMap::from([("input".to_string(), "Summarize the findings".to_string())]);
+        let output = executor.invoke(inputs).unwrap();
+        assert_eq!(output["output"], "summary");
+
+        let calls = calls.lock().unwrap();
+        let content = |msg: &HashMap<String, serde_json::Value>| {
+            msg["content"].as_str().unwrap_or_default().to_string()
+        };
+        assert_eq!(
+            content(&calls[0][0]),
+            "You are a researcher.\n\nTool descriptions:\n- search: Search the web"
+        );
+        assert_eq!(
+            content(&calls[0][1]),
+            "Summarize the findings\n\nRelevant knowledge:\nRust 1.93 is out.\nCrews run tasks in order."
+        );
+        // Late calls drop the oldest steps instead of overflowing.
+        let last = calls.last().unwrap();
+        assert!(content(&last[2]).ends_with("omitted to fit the context window]"));
+        assert!(last.len() < executor.messages.len());
+        let allocation = executor.last_context_allocation.as_ref().unwrap();
+        assert!(allocation.total() <= 300);
+        assert_eq!(allocation.granted(ContextSection::Knowledge), 14);
+    }
+}
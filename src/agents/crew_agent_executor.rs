@@ -10,13 +10,26 @@
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use serde_json::Value;
 
-use super::parser::{AgentFinish, ParseResult};
+use super::context_allocator::{
+    estimate_tokens, fit_items, ContextAllocation, ContextPlan, ContextSection,
+};
+use super::exploration::{self, ExplorationBudget};
+use super::parser::{AgentAction, AgentFinish, ParseResult};
 use super::tools_handler::ToolsHandler;
+use crate::hooks::{self, LLMCallHookContext, LLMCallHooks};
+use crate::policy::{PolicyEngine, PolicyViolation};
+use crate::tasks::execution_trace::{self, ExecutionStep, ToolCallTrace};
 use crate::tools::structured_tool::CrewStructuredTool;
 use crate::tools::tool_calling::ToolCalling;
+use crate::tools::tool_types::ToolResult;
+use crate::types::usage_metrics::{UsageMetrics, UsageScope};
+use crate::utilities::cancellation;
+use crate::utilities::run_log::{self, LogEntryKind};
 
 // ---------------------------------------------------------------------------
 // LLM Message type alias (re-export from base_llm for convenience)
@@ -25,6 +38,58 @@ use crate::tools::tool_calling::ToolCalling;
 /// A single message in an LLM conversation.
 pub type LLMMessage = HashMap<String, Value>;
 
+/// Messages and tool schemas of a context-fitted LLM call.
+type FittedCall = (Vec<LLMMessage>, Option<Vec<Value>>);
+
+/// A step of an agent, passed to its step callback.
+#[derive(Debug, Clone)]
+pub enum AgentStep {
+    /// A tool call, with the tool's result.
+    Action(AgentAction),
+    /// The result of a tool call, before the action it belongs to.
+    ToolResult(ToolResult),
+    /// The final answer.
+    Finish(AgentFinish),
+}
+
+/// Callback invoked with every step of an agent.
+pub type StepCallback = Arc<dyn Fn(&AgentStep) + Send + Sync>;
+
+/// Predicate deciding whether a tool result (tool name, arguments, result)
+/// may be cached.
+pub type ToolCacheFn = Box<dyn Fn(&str, &Value, &str) -> bool + Send + Sync>;
+
+/// Callback receiving a tool's name and usage limit once its uses run out.
+pub type ToolLimitReachedFn = Box<dyn Fn(&str, u32) + Send + Sync>;
+
+/// Callback sending messages to the function calling LLM and returning its
+/// reply.
+pub type FunctionCallingLLMFn = Box<
+    dyn Fn(&[LLMMessage]) -> Result<String, Box<dyn std::error::Error + Send + Sync>> + Send + Sync,
+>;
+
+/// How often a tool may still be used within an execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolUsageLimit {
+    /// Uses allowed.
+    pub limit: u32,
+    /// Uses left.
+    pub remaining: u32,
+}
+
+/// Estimated tokens of a message's content and tool calls.
+fn message_tokens(msg: &LLMMessage) -> usize {
+    let content = match msg.get("content") {
+        Some(Value::String(s)) => estimate_tokens(s),
+        Some(Value::Null) | None => 0,
+        Some(other) => estimate_tokens(&other.to_string()),
+    };
+    let tool_calls = msg
+        .get("tool_calls")
+        .map_or(0, |calls| estimate_tokens(&calls.to_string()));
+    content + tool_calls + 4
+}
+
 // ---------------------------------------------------------------------------
 // CrewAgentExecutor
 // ---------------------------------------------------------------------------
@@ -60,11 +125,13 @@ pub struct CrewAgentExecutor {
     /// Original BaseTool objects (before conversion to structured tools).
     pub original_tools: Vec<Box<dyn Any + Send + Sync>>,
     /// Optional step callback function.
-    pub step_callback: Option<Box<dyn Fn(&dyn Any) + Send + Sync>>,
+    pub step_callback: Option<StepCallback>,
     /// Tool descriptions string.
     pub tools_description: String,
-    /// Optional function calling LLM (type-erased).
-    pub function_calling_llm: Option<Box<dyn Any + Send + Sync>>,
+    /// Callback to the function calling LLM, which extracts tool calls the
+    /// main LLM did not format properly (see
+    /// [`set_function_calling_llm_call`](Self::set_function_calling_llm_call)).
+    pub function_calling_llm: Option<FunctionCallingLLMFn>,
     /// Whether to respect context window limits.
     pub respect_context_window: bool,
     /// Optional RPM limit check function.
@@ -100,8 +167,38 @@ pub struct CrewAgentExecutor {
                 + Sync,
         >,
     >,
+    /// Decides whether a tool result may be cached, given the tool name,
+    /// arguments and result; results are always cacheable when `None`.
+    pub cache_function: Option<ToolCacheFn>,
+    /// Tool whose results are image messages (see
+    /// [`AddImageTool`](crate::tools::agent_tools::add_image_tool::AddImageTool)),
+    /// added to the conversation as they are instead of as observations.
+    pub image_tool: Option<String>,
+    /// Usage limits of tools, by name. A tool whose uses run out is removed
+    /// from the available tools.
+    pub tool_usage_limits: HashMap<String, ToolUsageLimit>,
+    /// Called with a tool's name and limit when its uses run out.
+    pub on_tool_limit_reached: Option<ToolLimitReachedFn>,
+    /// Policy every tool call is checked against, and the agent id the
+    /// calls are made as.
+    pub policy: Option<(Arc<Mutex<PolicyEngine>>, String)>,
     /// Whether the LLM supports native function calling.
     pub supports_function_calling: bool,
+    /// Optional wall-clock budget for exploration before forcing synthesis.
+    pub exploration_budget: Option<ExplorationBudget>,
+    /// When the current invocation started exploring.
+    exploration_started: Option<Instant>,
+    /// Adaptive context allocation; every LLM call then gets a prompt
+    /// fitted to the token budget.
+    pub context_plan: Option<ContextPlan>,
+    /// The allocation made for the most recent LLM call.
+    pub last_context_allocation: Option<ContextAllocation>,
+    /// Hooks run around every LLM call, after the global ones.
+    pub llm_call_hooks: LLMCallHooks,
+    /// Agent, task, crew and model given to the LLM call hooks.
+    pub llm_hook_context: LLMCallHookContext,
+    /// Tokens used by the most recent LLM call, not yet recorded in a step.
+    step_usage: UsageMetrics,
 }
 
 impl fmt::Debug for CrewAgentExecutor {
@@ -173,7 +270,19 @@ impl CrewAgentExecutor {
             log_error_after: 3,
             llm_call: None,
             tool_executor: None,
+            cache_function: None,
+            image_tool: None,
+            tool_usage_limits: HashMap::new(),
+            on_tool_limit_reached: None,
+            policy: None,
             supports_function_calling: false,
+            exploration_budget: None,
+            exploration_started: None,
+            context_plan: None,
+            last_context_allocation: None,
+            llm_call_hooks: LLMCallHooks::default(),
+            llm_hook_context: LLMCallHookContext::new(),
+            step_usage: UsageMetrics::default(),
         }
     }
 
@@ -191,6 +300,22 @@ impl CrewAgentExecutor {
         self.llm_call = Some(Box::new(callback));
     }
 
+    /// Set the function calling LLM callback.
+    ///
+    /// When set, ReAct responses that cannot be parsed and actions whose
+    /// input is not JSON are handed to this (typically cheaper) LLM to
+    /// extract the tool call as `{"tool": ..., "arguments": {...}}`, instead
+    /// of asking the main LLM to try again.
+    pub fn set_function_calling_llm_call<F>(&mut self, callback: F)
+    where
+        F: Fn(&[LLMMessage]) -> Result<String, Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.function_calling_llm = Some(Box::new(callback));
+    }
+
     /// Set the tool executor callback.
     pub fn set_tool_executor<F>(&mut self, callback: F)
     where
@@ -202,6 +327,92 @@ impl CrewAgentExecutor {
         self.tool_executor = Some(Box::new(callback));
     }
 
+    /// Add the results of `tool_name` to the conversation as messages; see
+    /// [`image_tool`](Self::image_tool).
+    pub fn set_image_tool(&mut self, tool_name: impl Into<String>) {
+        self.image_tool = Some(tool_name.into());
+    }
+
+    /// Run `hooks` around every LLM call, after the global hooks, giving
+    /// them the agent, task, crew and model of `context`.
+    pub fn set_llm_call_hooks(&mut self, hooks: LLMCallHooks, context: LLMCallHookContext) {
+        self.llm_call_hooks = hooks;
+        self.llm_hook_context = context;
+    }
+
+    /// Set the step callback.
+    pub fn set_step_callback(&mut self, callback: StepCallback) {
+        self.step_callback = Some(callback);
+    }
+
+    /// Set the exploration budget. Once it is spent, the executor stops
+    /// calling tools and synthesizes a best-effort final answer.
+    pub fn set_exploration_budget(&mut self, budget: Option<ExplorationBudget>) {
+        self.exploration_budget = budget;
+    }
+
+    /// Manage the prompt with `plan`; see [`ContextPlan`].
+    pub fn set_context_plan(&mut self, plan: Option<ContextPlan>) {
+        self.context_plan = plan;
+    }
+
+    /// Set the predicate deciding whether a tool result may be cached.
+    pub fn set_cache_function<F>(&mut self, callback: F)
+    where
+        F: Fn(&str, &Value, &str) -> bool + Send + Sync + 'static,
+    {
+        self.cache_function = Some(Box::new(callback));
+    }
+
+    /// Limit how often each tool in `limits` may be used. Tools with a
+    /// limit of zero are removed right away.
+    pub fn set_tool_usage_limits(&mut self, limits: HashMap<String, u32>) {
+        for (tool, limit) in limits {
+            if limit == 0 {
+                self.remove_tool(&tool);
+            }
+            self.tool_usage_limits.insert(
+                tool,
+                ToolUsageLimit {
+                    limit,
+                    remaining: limit,
+                },
+            );
+        }
+    }
+
+    /// Set the callback invoked when a tool's uses run out.
+    pub fn set_on_tool_limit_reached<F>(&mut self, callback: F)
+    where
+        F: Fn(&str, u32) + Send + Sync + 'static,
+    {
+        self.on_tool_limit_reached = Some(Box::new(callback));
+    }
+
+    /// Check every tool call against `engine`, as agent `agent_id`.
+    pub fn set_policy(&mut self, engine: Arc<Mutex<PolicyEngine>>, agent_id: impl Into<String>) {
+        self.policy = Some((engine, agent_id.into()));
+    }
+
+    /// Set the check run before every LLM call; the call waits while it
+    /// returns `false`.
+    pub fn set_request_within_rpm_limit<F>(&mut self, check: F)
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.request_within_rpm_limit = Some(Box::new(check));
+    }
+
+    /// Wait until the RPM limit, if configured, allows another LLM call.
+    fn wait_for_rpm_limit(&self) {
+        if let Some(ref check_rpm) = self.request_within_rpm_limit {
+            while !check_rpm() {
+                log::debug!("Waiting for RPM limit...");
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+        }
+    }
+
     /// Set whether the LLM supports native function calling.
     pub fn set_supports_function_calling(&mut self, supports: bool) {
         self.supports_function_calling = supports;
@@ -231,6 +442,7 @@ impl CrewAgentExecutor {
             .get("ask_for_human_input")
             .map(|v| v == "true")
             .unwrap_or(false);
+        self.exploration_started = Some(Instant::now());
 
         let formatted_answer = self.invoke_loop()?;
 
@@ -308,6 +520,9 @@ impl CrewAgentExecutor {
         &mut self,
     ) -> Result<AgentFinish, Box<dyn std::error::Error + Send + Sync>> {
         loop {
+            // Stop if the crew was cancelled or the task timed out
+            cancellation::check()?;
+
             // Check iteration limit
             if self.iterations >= self.max_iter {
                 log::warn!(
@@ -323,22 +538,15 @@ impl CrewAgentExecutor {
                 });
             }
 
-            // Enforce RPM limit if configured
-            if let Some(ref check_rpm) = self.request_within_rpm_limit {
-                while !check_rpm() {
-                    log::debug!("Waiting for RPM limit...");
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                }
+            if self.exploration_expired() {
+                return self.synthesize();
             }
 
-            // Get LLM callback
-            let llm_call = self
-                .llm_call
-                .as_ref()
-                .ok_or("LLM call callback not configured")?;
+            // Enforce RPM limit if configured
+            self.wait_for_rpm_limit();
 
             // Call LLM with current messages (no tools for ReAct - tools are in prompt)
-            let response = llm_call(&self.messages, None)?;
+            let response = self.call_llm(None)?;
 
             log::debug!(
                 "LLM response (iteration {}): {}",
@@ -348,30 +556,38 @@ impl CrewAgentExecutor {
 
             // Parse the response
             let parse_result = match super::parser::parse(&response) {
+                Ok(ParseResult::Action(action)) => {
+                    ParseResult::Action(self.repair_tool_input(action))
+                }
                 Ok(result) => result,
-                Err(e) => {
-                    // Log parse error and append error message to conversation
-                    if self.iterations >= self.log_error_after {
-                        log::warn!("Parse error (iteration {}): {}", self.iterations, e);
+                Err(e) => match self.extract_tool_call(&response) {
+                    Some(action) => ParseResult::Action(action),
+                    None => {
+                        // Log parse error and append error message to conversation
+                        if self.iterations >= self.log_error_after {
+                            log::warn!("Parse error (iteration {}): {}", self.iterations, e);
+                        }
+                        let error_msg = format!(
+                            "I encountered an error parsing your response: {}. \
+                             Please format your response correctly with either:\n\
+                             - Action: [tool_name]\\nAction Input: [input]\n\
+                             - Final Answer: [your answer]",
+                            e
+                        );
+                        self.append_message(&response, "assistant");
+                        self.append_message(&error_msg, "user");
+                        self.record_step(&response, Vec::new(), None);
+                        self.iterations += 1;
+                        continue;
                     }
-                    let error_msg = format!(
-                        "I encountered an error parsing your response: {}. \
-                         Please format your response correctly with either:\n\
-                         - Action: [tool_name]\\nAction Input: [input]\n\
-                         - Final Answer: [your answer]",
-                        e
-                    );
-                    self.append_message(&response, "assistant");
-                    self.append_message(&error_msg, "user");
-                    self.iterations += 1;
-                    continue;
-                }
+                },
             };
 
             match parse_result {
                 ParseResult::Finish(finish) => {
                     log::debug!("Agent finished with output: {:?}", finish.output);
-                    self.invoke_step_callback(&finish);
+                    self.record_step(&finish.thought, Vec::new(), Some(&finish.output));
+                    self.invoke_step_callback(AgentStep::Finish(finish.clone()));
                     return Ok(finish);
                 }
                 ParseResult::Action(mut action) => {
@@ -381,26 +597,34 @@ impl CrewAgentExecutor {
                         action.tool_input
                     );
 
-                    // Execute the tool
-                    let tool_result = self.execute_tool(&action.tool, &action.tool_input)?;
+                    // Execute the tool (or serve it from the cache)
+                    let tool_result = self.use_tool(&action.tool, &action.tool_input)?;
                     action.result = Some(tool_result.clone());
-
-                    // Record tool use for caching
-                    let calling = ToolCalling::new(
-                        action.tool.clone(),
-                        serde_json::from_str(&action.tool_input).ok(),
-                    );
-                    self.tools_handler.on_tool_use(&calling, &tool_result, true);
+                    let call = ToolCallTrace {
+                        tool: action.tool.clone(),
+                        arguments: action.tool_input.clone(),
+                        observation: tool_result.clone(),
+                    };
+                    self.record_step(&action.thought, vec![call], None);
 
                     // Invoke step callback
-                    self.invoke_step_callback(&action);
+                    self.invoke_step_callback(AgentStep::ToolResult(ToolResult::new(
+                        tool_result.clone(),
+                    )));
+                    self.invoke_step_callback(AgentStep::Action(action.clone()));
 
                     // Append the action and result to conversation
                     self.append_message(&action.text, "assistant");
 
-                    // Format tool result as observation
-                    let observation = format!("Observation: {}", tool_result);
-                    self.append_message(&observation, "user");
+                    // Format tool result as observation, unless it is an
+                    // image for the conversation
+                    match self.image_message(&action.tool, &tool_result) {
+                        Some(message) => self.messages.push(message),
+                        None => {
+                            let observation = format!("Observation: {}", tool_result);
+                            self.append_message(&observation, "user");
+                        }
+                    }
 
                     self.iterations += 1;
                 }
@@ -416,7 +640,7 @@ impl CrewAgentExecutor {
         &mut self,
     ) -> Result<AgentFinish, Box<dyn std::error::Error + Send + Sync>> {
         // Build tool schemas for the LLM
-        let tool_schemas: Vec<Value> = self
+        let mut tool_schemas: Vec<Value> = self
             .tools
             .iter()
             .map(|t| {
@@ -437,6 +661,9 @@ impl CrewAgentExecutor {
             .collect();
 
         loop {
+            // Stop if the crew was cancelled or the task timed out
+            cancellation::check()?;
+
             // Check iteration limit
             if self.iterations >= self.max_iter {
                 log::warn!(
@@ -452,14 +679,14 @@ impl CrewAgentExecutor {
                 });
             }
 
-            // Get LLM callback
-            let llm_call = self
-                .llm_call
-                .as_ref()
-                .ok_or("LLM call callback not configured")?;
+            if self.exploration_expired() {
+                return self.synthesize();
+            }
+
+            self.wait_for_rpm_limit();
 
             // Call LLM with tools
-            let response = llm_call(&self.messages, Some(&tool_schemas))?;
+            let response = self.call_llm(Some(&tool_schemas))?;
 
             // Try to parse as JSON (native tool calling returns structured response)
             let response_json: Value = serde_json::from_str(&response).unwrap_or_else(|_| {
@@ -479,7 +706,9 @@ impl CrewAgentExecutor {
                         .insert("tool_calls".to_string(), Value::Array(tool_calls.clone()));
                     self.messages.push(assistant_msg);
 
-                    // Execute each tool call
+                    // Execute each tool call; images follow the tool results
+                    let mut images = Vec::new();
+                    let mut calls = Vec::new();
                     for tool_call in tool_calls {
                         let function = tool_call
                             .get("function")
@@ -499,15 +728,20 @@ impl CrewAgentExecutor {
 
                         log::debug!("Native tool call: {}({})", tool_name, tool_args);
 
-                        // Execute the tool
-                        let tool_result = self.execute_tool(tool_name, tool_args)?;
-
-                        // Record tool use
-                        let calling = ToolCalling::new(
-                            tool_name.to_string(),
-                            serde_json::from_str(tool_args).ok(),
-                        );
-                        self.tools_handler.on_tool_use(&calling, &tool_result, true);
+                        // Execute the tool (or serve it from the cache)
+                        let mut tool_result = self.use_tool(tool_name, tool_args)?;
+                        self.invoke_step_callback(AgentStep::ToolResult(ToolResult::new(
+                            tool_result.clone(),
+                        )));
+                        calls.push(ToolCallTrace {
+                            tool: tool_name.to_string(),
+                            arguments: tool_args.to_string(),
+                            observation: tool_result.clone(),
+                        });
+                        if let Some(message) = self.image_message(tool_name, &tool_result) {
+                            images.push(message);
+                            tool_result = "Image added to the conversation.".to_string();
+                        }
 
                         // Append tool result message
                         let mut tool_msg = HashMap::new();
@@ -519,7 +753,16 @@ impl CrewAgentExecutor {
                         tool_msg.insert("content".to_string(), Value::String(tool_result));
                         self.messages.push(tool_msg);
                     }
+                    self.messages.extend(images);
+                    let thought = response_json["content"].as_str().unwrap_or_default();
+                    self.record_step(thought, calls, None);
 
+                    // Stop offering tools whose uses ran out.
+                    tool_schemas.retain(|schema| {
+                        schema["function"]["name"]
+                            .as_str()
+                            .is_none_or(|name| !self.tool_exhausted(name))
+                    });
                     self.iterations += 1;
                     continue;
                 }
@@ -538,12 +781,443 @@ impl CrewAgentExecutor {
                     }
                 });
 
-            return Ok(AgentFinish {
+            let finish = AgentFinish {
                 thought: "".to_string(),
                 output: Value::String(content),
                 text: response,
-            });
+            };
+            self.record_step(&finish.thought, Vec::new(), Some(&finish.output));
+            self.invoke_step_callback(AgentStep::Finish(finish.clone()));
+            return Ok(finish);
+        }
+    }
+
+    /// Whether the exploration budget of the current invocation is spent.
+    fn exploration_expired(&self) -> bool {
+        match (&self.exploration_budget, self.exploration_started) {
+            (Some(budget), Some(started)) => started.elapsed() >= budget.duration,
+            _ => false,
+        }
+    }
+
+    /// Force the synthesis phase: ask the LLM, without tools, to combine what
+    /// was gathered into a final answer with caveats.
+    ///
+    /// If the synthesis call fails, the gathered observations are returned
+    /// unprocessed rather than failing the task.
+    fn synthesize(&mut self) -> Result<AgentFinish, Box<dyn std::error::Error + Send + Sync>> {
+        let budget = self
+            .exploration_budget
+            .clone()
+            .ok_or("No exploration budget configured")?;
+        let elapsed = self
+            .exploration_started
+            .map(|started| started.elapsed())
+            .unwrap_or_default();
+        log::warn!(
+            "Exploration budget ({:?}) spent after {} step(s), synthesizing final answer",
+            budget.duration,
+            self.iterations
+        );
+
+        let observations = self.gathered_observations();
+        let message = budget.synthesis_message(elapsed, self.iterations);
+        self.append_message(&message, "user");
+
+        self.wait_for_rpm_limit();
+        let (output, text) = match self.call_llm(None) {
+            Ok(response) => {
+                let answer = match super::parser::parse(&response) {
+                    Ok(ParseResult::Finish(finish)) => match finish.output {
+                        Value::String(s) => s,
+                        other => other.to_string(),
+                    },
+                    _ => response.clone(),
+                };
+                (exploration::ensure_caveats(&answer, &budget), response)
+            }
+            Err(e) => {
+                log::warn!("Synthesis call failed: {}", e);
+                (
+                    exploration::fallback_answer(&observations, &budget, &e.to_string()),
+                    String::new(),
+                )
+            }
+        };
+
+        let finish = AgentFinish {
+            thought: "Exploration budget spent; synthesized a best-effort answer".to_string(),
+            output: Value::String(output),
+            text,
+        };
+        self.record_step(&finish.thought, Vec::new(), Some(&finish.output));
+        self.invoke_step_callback(AgentStep::Finish(finish.clone()));
+        Ok(finish)
+    }
+
+    /// Call the LLM with the conversation, fitted to the context plan if
+    /// any, and `tool_schemas`.
+    ///
+    /// The before-LLM-call hooks may change the conversation or block the
+    /// call; the after-LLM-call hooks may replace the response.
+    fn call_llm(
+        &mut self,
+        tool_schemas: Option<&[Value]>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let hooked = !self.llm_call_hooks.is_empty()
+            || hooks::before_llm_call_hook_count() > 0
+            || hooks::after_llm_call_hook_count() > 0;
+        let mut ctx = None;
+        if hooked {
+            let mut hook_ctx = LLMCallHookContext {
+                messages: LLMCallHookContext::with_messages(&self.messages).messages,
+                iterations: self.iterations as usize,
+                ..self.llm_hook_context.clone()
+            };
+            if !self.llm_call_hooks.run_before(&mut hook_ctx) {
+                return Err("LLM call blocked by before_llm_call hook".into());
+            }
+            hook_ctx.apply_messages(&mut self.messages);
+            ctx = Some(hook_ctx);
         }
+
+        let fitted = self.fit_context(tool_schemas);
+        let llm_call = self
+            .llm_call
+            .as_ref()
+            .ok_or("LLM call callback not configured")?;
+        let usage = UsageScope::enter();
+        let response = match &fitted {
+            Some((messages, schemas)) => llm_call(messages, schemas.as_deref())?,
+            None => llm_call(&self.messages, tool_schemas)?,
+        };
+        self.step_usage = usage.usage();
+
+        let Some(mut ctx) = ctx else {
+            return Ok(response);
+        };
+        ctx.response = Some(response);
+        self.llm_call_hooks.run_after(&mut ctx);
+        Ok(ctx.response.unwrap_or_default())
+    }
+
+    /// Record a step of the current iteration, with the tokens of the LLM
+    /// call that made it, in the trace of the running task.
+    fn record_step(
+        &mut self,
+        thought: &str,
+        tool_calls: Vec<ToolCallTrace>,
+        final_answer: Option<&Value>,
+    ) {
+        let thought = thought.trim();
+        execution_trace::record(ExecutionStep {
+            iteration: self.iterations as usize,
+            thought: thought
+                .strip_prefix("Thought:")
+                .unwrap_or(thought)
+                .trim_start()
+                .to_string(),
+            tool_calls,
+            final_answer: final_answer.map(|output| match output {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            }),
+            token_usage: std::mem::take(&mut self.step_usage),
+        });
+    }
+
+    /// Fit the next LLM call's prompt to the context plan, if any.
+    ///
+    /// The system and task messages are kept as they are. Tool descriptions
+    /// (ReAct) or `tool_schemas` (native calling), knowledge, memories and
+    /// history then share the rest of the budget; history is kept from the
+    /// most recent turn backwards, a turn being an assistant message and
+    /// the tool results that follow it. Returns the messages and tool
+    /// schemas to send.
+    fn fit_context(&mut self, tool_schemas: Option<&[Value]>) -> Option<FittedCall> {
+        let plan = self.context_plan.as_ref()?;
+        let head = self.messages.len().min(2);
+        let fixed: usize = self.messages[..head].iter().map(message_tokens).sum();
+
+        let tool_items: Vec<String> = match tool_schemas {
+            Some(schemas) => schemas.iter().map(Value::to_string).collect(),
+            None => self
+                .tools_description
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        let mut turns: Vec<(usize, usize)> = Vec::new();
+        for (i, msg) in self.messages.iter().enumerate().skip(head) {
+            let starts_turn = msg.get("role").and_then(Value::as_str) == Some("assistant");
+            match turns.last_mut() {
+                Some((_, cost)) if !starts_turn => *cost += message_tokens(msg),
+                _ => turns.push((i, message_tokens(msg))),
+            }
+        }
+        let total = |items: &[String]| items.iter().map(|i| estimate_tokens(i) + 1).sum();
+
+        let allocation = plan.allocator.allocate(
+            &plan.profile,
+            fixed,
+            &[
+                (ContextSection::History, turns.iter().map(|t| t.1).sum()),
+                (ContextSection::Knowledge, total(&plan.sources.knowledge)),
+                (ContextSection::Memory, total(&plan.sources.memories)),
+                (ContextSection::Tools, total(&tool_items)),
+            ],
+        );
+        log::info!(target: "crewai::context", "{}", allocation);
+
+        let knowledge = fit_items(
+            &plan.sources.knowledge,
+            allocation.granted(ContextSection::Knowledge),
+        );
+        let memories = fit_items(
+            &plan.sources.memories,
+            allocation.granted(ContextSection::Memory),
+        );
+        let tools = fit_items(&tool_items, allocation.granted(ContextSection::Tools));
+        let history_budget = allocation.granted(ContextSection::History);
+        let mut used = 0;
+        let kept_turns = turns
+            .iter()
+            .rev()
+            .take_while(|(_, cost)| {
+                used += cost;
+                used <= history_budget
+            })
+            .count();
+        let history_start = match kept_turns {
+            0 => self.messages.len(),
+            n => turns[turns.len() - n].0,
+        };
+
+        let mut messages = self.messages[..head].to_vec();
+        let mut extend = |index: usize, heading: &str, items: &[&str]| {
+            if items.is_empty() || index >= messages.len() {
+                return;
+            }
+            if let Some(Value::String(content)) = messages[index].get_mut("content") {
+                content.push_str(&format!("\n\n{}\n{}", heading, items.join("\n")));
+            }
+        };
+        if tool_schemas.is_none() {
+            extend(0, "Tool descriptions:", &tools);
+        }
+        let task = head.saturating_sub(1);
+        extend(task, "Relevant knowledge:", &knowledge);
+        extend(task, "Relevant memories:", &memories);
+        let omitted = turns.len() - kept_turns;
+        if omitted > 0 {
+            messages.push(HashMap::from([
+                ("role".to_string(), Value::String("user".to_string())),
+                (
+                    "content".to_string(),
+                    Value::String(format!(
+                        "[{} earlier step(s) omitted to fit the context window]",
+                        omitted
+                    )),
+                ),
+            ]));
+        }
+        messages.extend_from_slice(&self.messages[history_start..]);
+        let schemas = tool_schemas.map(|_| {
+            tools
+                .iter()
+                .filter_map(|t| serde_json::from_str(t).ok())
+                .collect()
+        });
+
+        self.last_context_allocation = Some(allocation);
+        Some((messages, schemas))
+    }
+
+    /// Tool results gathered so far in the conversation.
+    fn gathered_observations(&self) -> Vec<String> {
+        self.messages
+            .iter()
+            .filter_map(|msg| {
+                let content = msg.get("content")?.as_str()?;
+                match msg.get("role")?.as_str()? {
+                    "tool" => Some(content.to_string()),
+                    "user" => content.strip_prefix("Observation: ").map(str::to_string),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Ask the function calling LLM for the tool call in `response`, which
+    /// the ReAct parser could not make sense of.
+    ///
+    /// Returns `None` without a function calling LLM, when the call fails,
+    /// or when the reply is not a call to one of the available tools.
+    fn extract_tool_call(&self, response: &str) -> Option<AgentAction> {
+        let function_calling_llm = self.function_calling_llm.as_ref()?;
+        let instructions = format!(
+            "Extract the tool call from the AI agent response you are given. \
+             The available tools are:\n{}\n\n\
+             Reply with only a JSON object {{\"tool\": \"<tool name>\", \"arguments\": {{...}}}}, \
+             or with null if the response does not call one of these tools.",
+            self.tools_description
+        );
+        let messages: Vec<LLMMessage> = [("system", instructions.as_str()), ("user", response)]
+            .into_iter()
+            .map(|(role, content)| {
+                HashMap::from([
+                    ("role".to_string(), Value::String(role.to_string())),
+                    ("content".to_string(), Value::String(content.to_string())),
+                ])
+            })
+            .collect();
+        let reply = match function_calling_llm(&messages) {
+            Ok(reply) => reply,
+            Err(e) => {
+                log::warn!("Function calling LLM failed: {}", e);
+                return None;
+            }
+        };
+        let json = &reply[reply.find('{')?..=reply.rfind('}')?];
+        let call: Value = serde_json::from_str(json).ok()?;
+        let tool = call["tool"].as_str()?;
+        if !self.tools_names.split(", ").any(|name| name == tool) {
+            log::debug!("Function calling LLM chose unknown tool '{}'", tool);
+            return None;
+        }
+        let arguments = match &call["arguments"] {
+            Value::Null => Value::Object(Default::default()),
+            arguments => arguments.clone(),
+        };
+        Some(AgentAction {
+            thought: String::new(),
+            tool: tool.to_string(),
+            tool_input: arguments.to_string(),
+            text: response.to_string(),
+            result: None,
+        })
+    }
+
+    /// Have the function calling LLM turn an action input that is not JSON
+    /// into JSON arguments; the action is returned as is otherwise.
+    fn repair_tool_input(&self, action: AgentAction) -> AgentAction {
+        if self.function_calling_llm.is_none()
+            || serde_json::from_str::<Value>(&action.tool_input).is_ok()
+        {
+            return action;
+        }
+        match self.extract_tool_call(&action.text) {
+            Some(extracted) if extracted.tool == action.tool => AgentAction {
+                tool_input: extracted.tool_input,
+                ..action
+            },
+            _ => action,
+        }
+    }
+
+    /// Run a tool call, serving identical earlier calls from the tools
+    /// handler's cache and caching results the cache function accepts.
+    /// Calls to a used-up tool are refused; cache hits do not count as uses.
+    fn use_tool(
+        &mut self,
+        tool_name: &str,
+        tool_input: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if self.tool_exhausted(tool_name) {
+            let limit = self.tool_usage_limits[tool_name].limit;
+            log::warn!(
+                "Refused call to '{}': usage limit of {} reached",
+                tool_name,
+                limit
+            );
+            return Ok(format!(
+                "Tool '{}' has reached its maximum usage limit of {}. You should not use the {} tool again.",
+                tool_name, limit, tool_name
+            ));
+        }
+        if let Some((engine, agent_id)) = &self.policy {
+            let args = serde_json::from_str(tool_input)
+                .unwrap_or_else(|_| Value::String(tool_input.to_string()));
+            let checked = engine
+                .lock()
+                .map_err(|_| "policy engine lock poisoned")?
+                .check_tool_call(agent_id, tool_name, &args);
+            match checked {
+                Ok(_) => {}
+                Err(blocked @ PolicyViolation::Blocked { .. }) => {
+                    log::warn!("{}", blocked);
+                    return Ok(format!(
+                        "{}. Do not call this tool with these arguments again.",
+                        blocked
+                    ));
+                }
+                Err(escalated) => return Err(Box::new(escalated)),
+            }
+        }
+        let calling = ToolCalling::new(tool_name, serde_json::from_str(tool_input).ok());
+        if let Some(cached) = self.tools_handler.read_cache(&calling) {
+            log::debug!("Tool '{}' served from cache", tool_name);
+            self.tools_handler.last_used_tool = Some(calling);
+            return Ok(cached);
+        }
+
+        let result = self.execute_tool(tool_name, tool_input)?;
+        self.count_tool_use(tool_name);
+        let should_cache = self.cache_function.as_ref().is_none_or(|should_cache| {
+            let args = serde_json::to_value(&calling.arguments).unwrap_or_default();
+            should_cache(tool_name, &args, &result)
+        });
+        self.tools_handler
+            .on_tool_use(&calling, &result, should_cache);
+        Ok(result)
+    }
+
+    /// Whether `tool_name` has no uses left.
+    fn tool_exhausted(&self, tool_name: &str) -> bool {
+        self.tool_usage_limits
+            .get(tool_name)
+            .is_some_and(|usage| usage.remaining == 0)
+    }
+
+    /// Count a use of `tool_name` against its limit; when the last use is
+    /// spent, remove the tool and report it.
+    fn count_tool_use(&mut self, tool_name: &str) {
+        let Some(usage) = self.tool_usage_limits.get_mut(tool_name) else {
+            return;
+        };
+        usage.remaining = usage.remaining.saturating_sub(1);
+        if usage.remaining > 0 {
+            return;
+        }
+        let limit = usage.limit;
+        log::info!(
+            "Tool '{}' reached its usage limit of {} and is no longer available",
+            tool_name,
+            limit
+        );
+        self.remove_tool(tool_name);
+        if let Some(callback) = &self.on_tool_limit_reached {
+            callback(tool_name, limit);
+        }
+    }
+
+    /// Remove `tool_name` from the available tools and their descriptions.
+    fn remove_tool(&mut self, tool_name: &str) {
+        self.tools.retain(|t| t.name != tool_name);
+        self.tools_names = self
+            .tools_names
+            .split(", ")
+            .filter(|name| *name != tool_name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let entry = format!("- {}:", tool_name);
+        self.tools_description = self
+            .tools_description
+            .lines()
+            .filter(|line| !line.starts_with(&entry))
+            .collect::<Vec<_>>()
+            .join("\n");
     }
 
     /// Execute a tool by name with the given input.
@@ -579,6 +1253,20 @@ impl CrewAgentExecutor {
         Err(format!("Tool '{}' has no executable function", tool_name).into())
     }
 
+    /// The message in `result` if `tool_name` is the
+    /// [`image_tool`](Self::image_tool) and the call succeeded.
+    fn image_message(&self, tool_name: &str, result: &str) -> Option<LLMMessage> {
+        if self.image_tool.as_deref() != Some(tool_name) {
+            return None;
+        }
+        match serde_json::from_str(result).ok()? {
+            Value::Object(message) if message.get("content").is_some_and(Value::is_array) => {
+                Some(message.into_iter().collect())
+            }
+            _ => None,
+        }
+    }
+
     /// Append a message to the conversation history.
     fn append_message(&mut self, text: &str, role: &str) {
         let mut msg = HashMap::new();
@@ -602,10 +1290,382 @@ impl CrewAgentExecutor {
         result
     }
 
-    /// Invoke the optional step callback.
-    fn invoke_step_callback(&self, _answer: &dyn Any) {
-        if let Some(ref _callback) = self.step_callback {
-            // _callback(_answer);  // Would need proper type handling
+    /// Record the step in the current run log and invoke the optional step
+    /// callback.
+    fn invoke_step_callback(&self, step: AgentStep) {
+        if let AgentStep::Action(action) = &step {
+            run_log::record(
+                LogEntryKind::AgentStep,
+                serde_json::json!({
+                    "thought": action.thought,
+                    "tool": action.tool,
+                    "tool_input": action.tool_input,
+                    "result": action.result,
+                }),
+            );
+        } else if let AgentStep::Finish(finish) = &step {
+            run_log::record(
+                LogEntryKind::AgentStep,
+                serde_json::json!({ "thought": finish.thought, "finalanswer": finish.output }),
+            );
+        }
+        if let Some(callback) = &self.step_callback {
+            callback(&step);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_tool_removed_when_usage_limit_reached() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let reached = Arc::new(Mutex::new(Vec::new()));
+        let reported = reached.clone();
+        let calls = AtomicUsize::new(0);
+
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search, read".to_string(),
+            Vec::new(),
+            "- search: Search the web\n- read: Read a page".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |_, _| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            Ok(if call < 3 {
+                format!(
+                    "Thought: again\nAction: search\nAction Input: {{\"q\": {}}}",
+                    call
+                )
+            } else {
+                "Thought: done\nFinal Answer: ok".to_string()
+            })
+        });
+        executor.set_tool_executor(move |_, _| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok("result".to_string())
+        });
+        executor.set_tool_usage_limits(HashMap::from([("search".to_string(), 2)]));
+        executor.set_on_tool_limit_reached(move |tool, limit| {
+            reported.lock().unwrap().push((tool.to_string(), limit));
+        });
+
+        let inputs = HashMap::from([("input".to_string(), "look around".to_string())]);
+        executor.invoke(inputs).unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+        assert_eq!(*reached.lock().unwrap(), [("search".to_string(), 2)]);
+        assert_eq!(executor.tools_names, "read");
+        assert_eq!(executor.tools_description, "- read: Read a page");
+        let refusal = executor.messages[6]["content"].as_str().unwrap();
+        assert_eq!(
+            refusal,
+            "Observation: Tool 'search' has reached its maximum usage limit of 2. \
+             You should not use the search tool again."
+        );
+    }
+    #[test]
+    fn test_llm_calls_wait_for_shared_rpm_budget() {
+        let controller = crate::utilities::rpm_controller::RPMController::new(Some(1200));
+        while controller.try_acquire().is_ok() {}
+        let calls = AtomicUsize::new(0);
+
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            "- search: Search the web".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |_, _| {
+            Ok(if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                "Thought: look\nAction: search\nAction Input: {}".to_string()
+            } else {
+                "Thought: done\nFinal Answer: ok".to_string()
+            })
+        });
+        executor.set_tool_executor(|_, _| Ok("result".to_string()));
+        let budget = controller.clone();
+        executor.set_request_within_rpm_limit(move || budget.check_or_wait());
+
+        let used = controller.current_rpm();
+        let inputs = HashMap::from([("input".to_string(), "look around".to_string())]);
+        executor.invoke(inputs).unwrap();
+        // Both LLM calls drew on the drained budget and had to wait.
+        assert_eq!(controller.current_rpm(), used + 2);
+        assert_eq!(controller.throttled(), 2);
+    }
+    #[test]
+    fn test_policy_blocks_or_escalates_denied_tool_calls() {
+        use crate::policy::{
+            ConditionOperator, EnforcementMode, PolicyAction, PolicyCondition, PolicyEffect,
+            PolicyPrincipal, PolicyResource, PolicyRule,
+        };
+
+        let rule = PolicyRule {
+            name: "no_rm".to_string(),
+            description: "No recursive deletes".to_string(),
+            effect: PolicyEffect::Deny,
+            principal: PolicyPrincipal::Role("intern".to_string()),
+            action: PolicyAction::ToolCall("shell".to_string()),
+            resource: PolicyResource::Any,
+            conditions: vec![PolicyCondition {
+                key: "command".to_string(),
+                operator: ConditionOperator::StartsWith,
+                value: Value::String("rm -rf".to_string()),
+            }],
+            priority: 10,
+        };
+        let run = |mode: EnforcementMode| {
+            let mut engine = PolicyEngine::with_rules(vec![rule.clone()], mode);
+            engine.rbac.assign_role("Operator", "intern");
+            let engine = Arc::new(Mutex::new(engine));
+            let runs = Arc::new(AtomicUsize::new(0));
+            let counter = runs.clone();
+            let calls = AtomicUsize::new(0);
+            let mut executor = CrewAgentExecutor::new(
+                Box::new(()),
+                Box::new(()),
+                Box::new(()),
+                Box::new(()),
+                HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+                10,
+                Vec::new(),
+                "shell".to_string(),
+                Vec::new(),
+                "- shell: Run a command".to_string(),
+                ToolsHandler::new(None),
+            );
+            executor.set_llm_call(move |_, _| {
+                Ok(if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    "Thought: clean\nAction: shell\nAction Input: {\"command\": \"rm -rf /\"}"
+                        .to_string()
+                } else {
+                    "Thought: done\nFinal Answer: ok".to_string()
+                })
+            });
+            executor.set_tool_executor(move |_, _| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok("removed".to_string())
+            });
+            executor.set_policy(engine, "Operator");
+            let inputs = HashMap::from([("input".to_string(), "tidy up".to_string())]);
+            let result = executor.invoke(inputs);
+            (result, executor.messages, runs.load(Ordering::SeqCst))
+        };
+
+        let (result, messages, runs) = run(EnforcementMode::Strict);
+        assert!(result.is_ok());
+        assert_eq!(runs, 0);
+        let observation = messages[2]["content"].as_str().unwrap();
+        assert!(
+            observation.starts_with("Observation: Tool 'shell' was blocked by policy"),
+            "{}",
+            observation
+        );
+
+        let (result, _, runs) = run(EnforcementMode::Escalate);
+        assert_eq!(runs, 0);
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("denied by policy and escalated"));
+
+        let (result, _, runs) = run(EnforcementMode::AuditOnly);
+        assert!(result.is_ok());
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn test_function_calling_llm_extracts_malformed_tool_calls() {
+        let inputs_seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = inputs_seen.clone();
+        let calls = AtomicUsize::new(0);
+        let extractions = Arc::new(AtomicUsize::new(0));
+        let extraction_counter = extractions.clone();
+
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            "- search: Search the web".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |_, _| {
+            Ok(match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => "I should search the web for rust async runtimes".to_string(),
+                1 => "Thought: more\nAction: search\nAction Input: tokio vs smol".to_string(),
+                _ => "Thought: done\nFinal Answer: tokio".to_string(),
+            })
+        });
+        executor.set_function_calling_llm_call(move |messages| {
+            extraction_counter.fetch_add(1, Ordering::SeqCst);
+            let response = messages[1]["content"].as_str().unwrap();
+            Ok(if response.contains("tokio vs smol") {
+                r#"{"tool": "search", "arguments": {"q": "tokio vs smol"}}"#.to_string()
+            } else {
+                "```json\n{\"tool\": \"search\", \"arguments\": {\"q\": \"rust async\"}}\n```"
+                    .to_string()
+            })
+        });
+        executor.set_tool_executor(move |name, input| {
+            recorder
+                .lock()
+                .unwrap()
+                .push((name.to_string(), input.to_string()));
+            Ok("result".to_string())
+        });
+
+        let inputs = HashMap::from([("input".to_string(), "pick a runtime".to_string())]);
+        let output = executor.invoke(inputs).unwrap();
+        assert_eq!(output["output"], "tokio");
+        assert_eq!(extractions.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            *inputs_seen.lock().unwrap(),
+            [
+                ("search".to_string(), r#"{"q":"rust async"}"#.to_string()),
+                ("search".to_string(), r#"{"q":"tokio vs smol"}"#.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_image_tool_results_join_the_conversation() {
+        let calls = AtomicUsize::new(0);
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "add_image".to_string(),
+            Vec::new(),
+            "- add_image: Add an image".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |_, _| {
+            Ok(match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => "Thought: look\nAction: add_image\nAction Input: {\"image_url\": \"a.png\"}",
+                1 => "Thought: look\nAction: add_image\nAction Input: {\"image_url\": \"b.txt\"}",
+                _ => "Thought: done\nFinal Answer: a cat",
+            }
+            .to_string())
+        });
+        executor.set_tool_executor(|_, input| {
+            Ok(match input.contains("a.png") {
+                true => crate::tools::agent_tools::add_image_tool::AddImageTool::new()
+                    .run("data:image/png;base64,iVBO", Some("What is it?"))
+                    .to_string(),
+                false => "Tool error: not an image".to_string(),
+            })
+        });
+        executor.set_image_tool("add_image");
+
+        let inputs = HashMap::from([("input".to_string(), "describe".to_string())]);
+        executor.invoke(inputs).unwrap();
+        let image = &executor.messages[2];
+        assert_eq!(image["role"], "user");
+        assert_eq!(
+            image["content"][1]["image_url"]["url"],
+            "data:image/png;base64,iVBO"
+        );
+        assert_eq!(
+            executor.messages[4]["content"],
+            "Observation: Tool error: not an image"
+        );
+    }
+
+    #[test]
+    fn test_step_callback_and_llm_call_hooks() {
+        let calls = AtomicUsize::new(0);
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            "- search: Search the web".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |messages, _| {
+            assert!(messages[0]["content"]
+                .as_str()
+                .unwrap()
+                .ends_with("Be brief."));
+            Ok(if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                "Thought: look\nAction: search\nAction Input: {}".to_string()
+            } else {
+                "Thought: done\nFinal Answer: ok".to_string()
+            })
+        });
+        executor.set_tool_executor(|_, _| Ok("result".to_string()));
+        let steps = Arc::new(Mutex::new(Vec::new()));
+        let recorded = steps.clone();
+        executor.set_step_callback(Arc::new(move |step: &AgentStep| {
+            let step = match step {
+                AgentStep::Action(action) => format!("action {}", action.tool),
+                AgentStep::ToolResult(result) => format!("result {}", result.result),
+                AgentStep::Finish(finish) => format!("finish {}", finish.output.as_str().unwrap()),
+            };
+            recorded.lock().unwrap().push(step);
+        }));
+        let mut hooks = LLMCallHooks::default();
+        hooks.add_before(|ctx| {
+            assert_eq!(ctx.agent.as_deref(), Some("Researcher"));
+            if !ctx.messages[0]["content"].ends_with("Be brief.") {
+                ctx.messages[0]
+                    .get_mut("content")
+                    .unwrap()
+                    .push_str("\nBe brief.");
+            }
+            None
+        });
+        hooks.add_after(|ctx| ctx.response.as_ref().map(|r| r.replace("ok", "OK")));
+        let context = LLMCallHookContext {
+            agent: Some("Researcher".to_string()),
+            ..LLMCallHookContext::new()
+        };
+        executor.set_llm_call_hooks(hooks.clone(), context.clone());
+
+        let inputs = HashMap::from([("input".to_string(), "look around".to_string())]);
+        let output = executor.invoke(inputs.clone()).unwrap();
+        assert_eq!(output["output"], "OK");
+        assert_eq!(
+            *steps.lock().unwrap(),
+            ["result result", "action search", "finish OK"]
+        );
+
+        hooks.add_before(|_| Some(false));
+        executor.set_llm_call_hooks(hooks, context);
+        let error = executor.invoke(inputs).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("blocked by before_llm_call hook"));
+    }
+}
@@ -0,0 +1,208 @@
+//! Time-boxed exploration.
+//!
+//! An [`ExplorationBudget`] gives an agent a wall-clock allowance for
+//! exploration (reasoning steps and tool calls). Once it is spent, the
+//! executor stops exploring and forces a synthesis phase: the LLM is asked to
+//! combine whatever was gathered into a final answer with explicit caveats.
+//! Open-ended research tasks then return a best-effort answer instead of
+//! failing or overrunning.
+//!
+//! ```ignore
+//! use std::time::Duration;
+//!
+//! let crew = Crew::new(tasks, agents).exploration_budget(Duration::from_secs(120));
+//! ```
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Heading the synthesized answer's caveats are listed under.
+pub const CAVEATS_HEADING: &str = "Caveats:";
+
+/// Default instructions for the synthesis phase.
+const DEFAULT_SYNTHESIS_PROMPT: &str = "Your time for exploration is up. Do not use any more \
+tools. Combine everything you have gathered so far into the best possible answer to the \
+original task. After the answer, add a section starting with \"Caveats:\" listing what you \
+could not verify or complete, open questions, and how confident you are.";
+
+/// Wall-clock budget for exploration before a forced synthesis phase.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExplorationBudget {
+    /// Time allotted for exploration.
+    pub duration: Duration,
+    /// Instructions given to the LLM for the synthesis phase. Defaults to
+    /// asking for a best-effort answer followed by a `Caveats:` section.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synthesis_prompt: Option<String>,
+}
+
+impl ExplorationBudget {
+    /// A budget of `duration` with the default synthesis instructions.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            synthesis_prompt: None,
+        }
+    }
+
+    /// Builder: replace the synthesis instructions.
+    pub fn with_synthesis_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.synthesis_prompt = Some(prompt.into());
+        self
+    }
+
+    /// The message sent to the LLM to start the synthesis phase.
+    pub fn synthesis_message(&self, elapsed: Duration, steps: u32) -> String {
+        let instructions = self
+            .synthesis_prompt
+            .as_deref()
+            .unwrap_or(DEFAULT_SYNTHESIS_PROMPT);
+        format!(
+            "{}\n\n(Exploration ran for {} over {} step(s).)\n\n\
+             You MUST respond in the following format:\n\n\
+             Thought: I now need to give my best answer\n\
+             Final Answer: the best-effort answer, followed by its caveats",
+            instructions,
+            format_duration(elapsed),
+            steps
+        )
+    }
+}
+
+impl From<Duration> for ExplorationBudget {
+    fn from(duration: Duration) -> Self {
+        Self::new(duration)
+    }
+}
+
+/// Make sure a synthesized answer carries caveats, appending a generic
+/// section when the LLM left it out.
+pub fn ensure_caveats(answer: &str, budget: &ExplorationBudget) -> String {
+    if answer
+        .to_lowercase()
+        .contains(&CAVEATS_HEADING.to_lowercase())
+    {
+        return answer.to_string();
+    }
+    format!(
+        "{}\n\n{}\n- This is a best-effort answer: exploration stopped after its {} time \
+         budget, so the research may be incomplete.",
+        answer.trim_end(),
+        CAVEATS_HEADING,
+        format_duration(budget.duration)
+    )
+}
+
+/// Fallback answer when the synthesis call itself fails: the gathered
+/// observations, verbatim, with caveats.
+pub fn fallback_answer(observations: &[String], budget: &ExplorationBudget, error: &str) -> String {
+    let findings = if observations.is_empty() {
+        "No findings were gathered before the exploration budget ran out.".to_string()
+    } else {
+        let items: Vec<String> = observations
+            .iter()
+            .map(|o| format!("- {}", o.trim()))
+            .collect();
+        format!(
+            "Findings gathered during exploration:\n{}",
+            items.join("\n")
+        )
+    };
+    format!(
+        "{}\n\n{}\n- Exploration stopped after its {} time budget.\n\
+         - The findings could not be synthesized ({}); they are listed unprocessed.",
+        findings,
+        CAVEATS_HEADING,
+        format_duration(budget.duration),
+        error
+    )
+}
+
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs < 60.0 {
+        format!("{:.1}s", secs)
+    } else {
+        format!(
+            "{}m{:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caveats_and_fallback() {
+        let budget = ExplorationBudget::new(Duration::from_secs(90));
+        assert!(budget
+            .synthesis_message(Duration::from_millis(1500), 3)
+            .contains("ran for 1.5s over 3 step(s)"));
+
+        let kept = "Answer.\n\nCaveats:\n- unverified";
+        assert_eq!(ensure_caveats(kept, &budget), kept);
+        assert_eq!(
+            ensure_caveats("Answer.\n", &budget),
+            "Answer.\n\nCaveats:\n- This is a best-effort answer: exploration stopped after \
+             its 1m30s time budget, so the research may be incomplete."
+        );
+
+        let fallback = fallback_answer(&["rust 1.93 released".into()], &budget, "timeout");
+        assert!(fallback.starts_with("Findings gathered during exploration:\n- rust 1.93 released"));
+        assert!(fallback.contains("could not be synthesized (timeout)"));
+    }
+
+    #[test]
+    fn test_executor_synthesizes_when_budget_spent() {
+        use crate::agents::{CrewAgentExecutor, ToolsHandler};
+        use std::collections::HashMap;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            String::new(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |messages, _| {
+            // Explore until told to synthesize.
+            counter.fetch_add(1, Ordering::SeqCst);
+            let last = messages
+                .last()
+                .and_then(|m| m["content"].as_str())
+                .unwrap_or("");
+            if last.contains("Your time for exploration is up") {
+                Ok("Thought: done\nFinal Answer: Partial answer".to_string())
+            } else {
+                std::thread::sleep(Duration::from_millis(30));
+                Ok("Thought: look\nAction: search\nAction Input: {}".to_string())
+            }
+        });
+        executor.set_tool_executor(|_, _| Ok("a finding".to_string()));
+        executor.set_exploration_budget(Some(ExplorationBudget::new(Duration::from_millis(50))));
+
+        let inputs = HashMap::from([("input".to_string(), "research".to_string())]);
+        let output = executor.invoke(inputs).unwrap();
+        let answer = output["output"].as_str().unwrap();
+        assert!(answer.starts_with("Partial answer\n\nCaveats:\n- This is a best-effort answer"));
+        assert!(executor.iterations >= 1 && executor.iterations < 10);
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            executor.iterations as usize + 1
+        );
+    }
+}
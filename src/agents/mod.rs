@@ -3,14 +3,16 @@
 //! Corresponds to `crewai/agents/` Python package.
 //!
 //! This module provides the agent infrastructure including the base agent
-//! trait, agent builder, executor, parser, tools handler, cache, and
-//! agent adapters for different frameworks.
+//! trait, agent builder, executor, context allocation, parser, tools
+//! handler, cache, and agent adapters for different frameworks.
 
 pub mod agent_adapters;
 pub mod agent_builder;
 pub mod base_agent;
 pub mod cache;
+pub mod context_allocator;
 pub mod crew_agent_executor;
+pub mod exploration;
 pub mod parser;
 pub mod tools_handler;
 
@@ -18,6 +20,8 @@ pub mod tools_handler;
 pub use agent_builder::base_agent_trait::{BaseAgent, PlatformApp};
 pub use base_agent::BaseAgentData;
 pub use cache::cache_handler::CacheHandler;
+pub use context_allocator::{ContextAllocator, ContextRatios};
 pub use crew_agent_executor::CrewAgentExecutor;
+pub use exploration::ExplorationBudget;
 pub use parser::{AgentAction, AgentFinish, OutputParserError};
 pub use tools_handler::ToolsHandler;
@@ -37,6 +37,29 @@ impl ToolsHandler {
         }
     }
 
+    /// Cache input of a tool call: its arguments as JSON with sorted keys,
+    /// so identical calls share a cache entry.
+    pub fn cache_input(calling: &ToolCalling) -> String {
+        match &calling.arguments {
+            Some(args) => serde_json::to_value(args)
+                .map(|args| args.to_string())
+                .unwrap_or_default(),
+            None => String::new(),
+        }
+    }
+
+    /// Output of an identical earlier call, if cached.
+    pub fn read_cache(&self, calling: &ToolCalling) -> Option<String> {
+        let cached = self
+            .cache
+            .as_ref()?
+            .read(&calling.tool_name, &Self::cache_input(calling))?;
+        Some(match cached {
+            Value::String(s) => s,
+            other => other.to_string(),
+        })
+    }
+
     /// Handle a tool use event.
     ///
     /// Records the tool calling instance and optionally caches the output.
@@ -52,17 +75,90 @@ impl ToolsHandler {
         if let Some(ref mut cache) = self.cache {
             // Don't cache the cache tool itself
             if should_cache && calling.tool_name != "CacheTools" {
-                let input_str = match &calling.arguments {
-                    Some(args) => serde_json::to_string(args).unwrap_or_default(),
-                    None => String::new(),
-                };
-
                 cache.add(
                     &calling.tool_name,
-                    &input_str,
+                    &Self::cache_input(calling),
                     Value::String(output.to_string()),
                 );
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::CrewAgentExecutor;
+    use crate::tools::base_tool::Tool;
+    use crate::tools::ToolRegistry;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_executor_serves_repeated_calls_from_cache() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        let registry = ToolRegistry::new();
+        registry.register(
+            Tool::new(
+                "lookup",
+                "Look a term up",
+                Arc::new(move |args| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(Value::String(format!("definition of {}", args["term"])))
+                }),
+            )
+            // Never cache lookups of "now": the answer changes.
+            .with_cache_function(|args, _| args["term"] != "now"),
+        );
+
+        let cache = CacheHandler::new();
+        let steps = Mutex::new(
+            [
+                r#"{"term": "crew", "lang": "en"}"#,
+                r#"{"lang": "en", "term": "crew"}"#,
+                r#"{"term": "now"}"#,
+                r#"{"term": "now"}"#,
+            ]
+            .into_iter(),
+        );
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "lookup".to_string(),
+            Vec::new(),
+            String::new(),
+            ToolsHandler::new(Some(cache.clone())),
+        );
+        executor.set_llm_call(move |_, _| {
+            Ok(match steps.lock().unwrap().next() {
+                Some(input) => format!("Thought: check\nAction: lookup\nAction Input: {}", input),
+                None => "Thought: done\nFinal Answer: ok".to_string(),
+            })
+        });
+        let tools = registry.clone();
+        executor.set_tool_executor(move |name, input| {
+            let args: Value = serde_json::from_str(input)?;
+            tools.execute(None, name, &args).ok_or("unknown tool")?
+        });
+        executor.set_cache_function(move |name, args, result| {
+            registry.should_cache(name, args, &Value::String(result.to_string()))
+        });
+
+        let inputs = HashMap::from([("input".to_string(), "define".to_string())]);
+        executor.invoke(inputs).unwrap();
+        // The reordered "crew" call is a cache hit; both "now" calls run.
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.read("lookup", r#"{"lang":"en","term":"crew"}"#),
+            Some(Value::String("definition of \"crew\"".to_string()))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+//! Speech-to-text and text-to-speech.
+//!
+//! - [`transcription`]: the [`Transcriber`] trait with an OpenAI Whisper API
+//!   backend and a local whisper.cpp backend, plus [`TranscriptionTool`] so
+//!   agents can transcribe audio files. Transcripts can also be ingested as
+//!   knowledge with
+//!   [`AudioKnowledgeSource`](crate::knowledge::source::audio::AudioKnowledgeSource).
+//! - [`speech`]: the [`SpeechSynthesizer`] trait with OpenAI TTS and local
+//!   Piper backends, plus [`TtsOutputProcessor`] which renders final task
+//!   outputs to audio files.
+//!
+//! ```ignore
+//! use crewai::audio::{OpenAITts, TtsOutputProcessor};
+//!
+//! let tts = TtsOutputProcessor::new(OpenAITts::new(None), "audio_out");
+//! task.callback = Some(tts.task_callback());
+//! ```
+
+pub mod speech;
+pub mod transcription;
+
+pub use speech::{AudioFormat, OpenAITts, PiperTts, SpeechSynthesizer, TtsOutputProcessor};
+pub use transcription::{
+    OpenAIWhisper, Transcriber, Transcript, TranscriptSegment, TranscriptionTool, WhisperCpp,
+};
+
+use thiserror::Error;
+
+/// Errors from transcription and speech synthesis backends.
+#[derive(Debug, Error)]
+pub enum AudioError {
+    /// Reading or writing an audio file failed.
+    #[error("audio I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The HTTP request failed.
+    #[error("audio request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    /// The API returned an error status.
+    #[error("audio API returned {status}: {body}")]
+    Api {
+        /// HTTP status code.
+        status: u16,
+        /// Response body.
+        body: String,
+    },
+    /// No API key was configured.
+    #[error("{0} API key not set")]
+    MissingApiKey(&'static str),
+    /// A local backend failed or produced unexpected output.
+    #[error("{backend} failed: {message}")]
+    Backend {
+        /// Backend name.
+        backend: &'static str,
+        /// What went wrong.
+        message: String,
+    },
+}
+
+/// A temporary directory for a local backend's output, removed on drop.
+struct ScratchDir(std::path::PathBuf);
+
+impl ScratchDir {
+    fn new(prefix: &str) -> std::io::Result<Self> {
+        let dir = std::env::temp_dir().join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self(dir))
+    }
+
+    fn path(&self) -> &std::path::Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run a request future from synchronous code, also when the caller is
+/// already on an async runtime (`kickoff_async`, the server).
+fn block_on<F>(future: F) -> Result<F::Output, AudioError>
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    crate::tools::builtin::block_on(future).map_err(|e| AudioError::Io(std::io::Error::other(e)))
+}
+
+/// Turn a non-success response into [`AudioError::Api`].
+async fn check_status(response: reqwest::Response) -> Result<reqwest::Response, AudioError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    Err(AudioError::Api {
+        status: status.as_u16(),
+        body: response.text().await.unwrap_or_default(),
+    })
+}
@@ -0,0 +1,560 @@
+//! Text-to-speech backends and the task output processor.
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::{block_on, check_status, AudioError, ScratchDir};
+use crate::task::TaskCallback;
+use crate::tasks::task_output::TaskOutput;
+
+/// Encoding of synthesized audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// MP3 (default).
+    #[default]
+    Mp3,
+    /// Uncompressed WAV.
+    Wav,
+    /// Opus in an Ogg container.
+    Opus,
+    /// AAC.
+    Aac,
+    /// FLAC.
+    Flac,
+}
+
+impl AudioFormat {
+    /// File extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mp3 => "mp3",
+            Self::Wav => "wav",
+            Self::Opus => "opus",
+            Self::Aac => "aac",
+            Self::Flac => "flac",
+        }
+    }
+}
+
+/// A text-to-speech backend.
+pub trait SpeechSynthesizer: Send + Sync + fmt::Debug {
+    /// Backend name (for logging).
+    fn name(&self) -> &str;
+
+    /// Encoding of the audio returned by [`synthesize`](Self::synthesize).
+    fn format(&self) -> AudioFormat;
+
+    /// Longest text accepted in one [`synthesize`](Self::synthesize) call.
+    fn max_input_chars(&self) -> usize {
+        4096
+    }
+
+    /// Render `text` to audio.
+    fn synthesize(&self, text: &str) -> Result<Vec<u8>, AudioError>;
+}
+
+// ---------------------------------------------------------------------------
+// OpenAI TTS
+// ---------------------------------------------------------------------------
+
+/// Speech synthesis through the OpenAI `audio/speech` endpoint.
+#[derive(Clone)]
+pub struct OpenAITts {
+    /// API key (defaults to `OPENAI_API_KEY`).
+    pub api_key: Option<String>,
+    /// API base URL.
+    pub base_url: String,
+    /// Speech model.
+    pub model: String,
+    /// Voice name.
+    pub voice: String,
+    /// Output encoding.
+    pub format: AudioFormat,
+    /// Playback speed (0.25 to 4.0).
+    pub speed: Option<f32>,
+    /// Request timeout.
+    pub timeout: Duration,
+}
+
+impl OpenAITts {
+    /// Create a backend using `tts-1` with the `alloy` voice.
+    ///
+    /// `api_key` defaults to the `OPENAI_API_KEY` environment variable.
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            api_key: api_key.or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: "tts-1".to_string(),
+            voice: "alloy".to_string(),
+            format: AudioFormat::Mp3,
+            speed: None,
+            timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Builder: set the model.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Builder: set the voice.
+    pub fn with_voice(mut self, voice: impl Into<String>) -> Self {
+        self.voice = voice.into();
+        self
+    }
+
+    /// Builder: set the output encoding.
+    pub fn with_format(mut self, format: AudioFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Builder: set the playback speed.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    /// Builder: set the API base URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    async fn request(&self, text: &str) -> Result<Vec<u8>, AudioError> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or(AudioError::MissingApiKey("OpenAI"))?;
+        let mut body = json!({
+            "model": self.model,
+            "voice": self.voice,
+            "input": text,
+            "response_format": self.format.extension(),
+        });
+        if let Some(speed) = self.speed {
+            body["speed"] = json!(speed);
+        }
+        let response = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()?
+            .post(format!(
+                "{}/audio/speech",
+                self.base_url.trim_end_matches('/')
+            ))
+            .bearer_auth(api_key)
+            .json(&body)
+            .send()
+            .await?;
+        Ok(check_status(response).await?.bytes().await?.to_vec())
+    }
+}
+
+impl fmt::Debug for OpenAITts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAITts")
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .field("voice", &self.voice)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl SpeechSynthesizer for OpenAITts {
+    fn name(&self) -> &str {
+        "openai_tts"
+    }
+
+    fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    fn synthesize(&self, text: &str) -> Result<Vec<u8>, AudioError> {
+        block_on(self.request(text))?
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Piper
+// ---------------------------------------------------------------------------
+
+/// Local speech synthesis with the Piper command line tool.
+///
+/// Runs `piper --model <model> --output_file <file>` with the text on stdin
+/// and returns the WAV it writes.
+#[derive(Debug, Clone)]
+pub struct PiperTts {
+    /// Path to the piper binary.
+    pub binary: PathBuf,
+    /// Path to the `.onnx` voice model.
+    pub model_path: PathBuf,
+    /// Speaker ID for multi-speaker models.
+    pub speaker: Option<u32>,
+}
+
+impl PiperTts {
+    /// Create a backend using `piper` from `PATH` and the given voice model.
+    pub fn new(model_path: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: PathBuf::from("piper"),
+            model_path: model_path.into(),
+            speaker: None,
+        }
+    }
+
+    /// Builder: set the binary path.
+    pub fn with_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = binary.into();
+        self
+    }
+
+    /// Builder: set the speaker ID.
+    pub fn with_speaker(mut self, speaker: u32) -> Self {
+        self.speaker = Some(speaker);
+        self
+    }
+}
+
+impl SpeechSynthesizer for PiperTts {
+    fn name(&self) -> &str {
+        "piper"
+    }
+
+    fn format(&self) -> AudioFormat {
+        AudioFormat::Wav
+    }
+
+    fn max_input_chars(&self) -> usize {
+        usize::MAX
+    }
+
+    fn synthesize(&self, text: &str) -> Result<Vec<u8>, AudioError> {
+        let out_dir = ScratchDir::new("crewai-piper")?;
+        let out_file = out_dir.path().join("speech.wav");
+        let mut command = Command::new(&self.binary);
+        command
+            .arg("--model")
+            .arg(&self.model_path)
+            .arg("--output_file")
+            .arg(&out_file)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        if let Some(speaker) = self.speaker {
+            command.arg("--speaker").arg(speaker.to_string());
+        }
+        let mut child = command.spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(AudioError::Backend {
+                backend: "piper",
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(std::fs::read(out_file)?)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Output processor
+// ---------------------------------------------------------------------------
+
+/// Renders task outputs to audio files.
+///
+/// Markdown markup is stripped before synthesis. Outputs longer than the
+/// backend's input limit are split at sentence boundaries and written as
+/// numbered parts (`report-01.mp3`, `report-02.mp3`, ...).
+#[derive(Debug, Clone)]
+pub struct TtsOutputProcessor {
+    synthesizer: Arc<dyn SpeechSynthesizer>,
+    /// Directory audio files are written to.
+    pub output_dir: PathBuf,
+}
+
+impl TtsOutputProcessor {
+    /// Create a processor writing into `output_dir`.
+    pub fn new(
+        synthesizer: impl SpeechSynthesizer + 'static,
+        output_dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            synthesizer: Arc::new(synthesizer),
+            output_dir: output_dir.into(),
+        }
+    }
+
+    /// Render `text` to `<output_dir>/<stem>.<ext>` (or numbered parts).
+    pub fn render(&self, text: &str, stem: &str) -> Result<Vec<PathBuf>, AudioError> {
+        let spoken = spoken_text(text);
+        if spoken.is_empty() {
+            return Ok(Vec::new());
+        }
+        std::fs::create_dir_all(&self.output_dir)?;
+        let parts = split_for_speech(&spoken, self.synthesizer.max_input_chars());
+        let extension = self.synthesizer.format().extension();
+        let mut paths = Vec::with_capacity(parts.len());
+        for (i, part) in parts.iter().enumerate() {
+            let file_name = if parts.len() == 1 {
+                format!("{}.{}", stem, extension)
+            } else {
+                format!("{}-{:02}.{}", stem, i + 1, extension)
+            };
+            let path = self.output_dir.join(file_name);
+            std::fs::write(&path, self.synthesizer.synthesize(part)?)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Render a task's final output, named after the task.
+    pub fn process(&self, output: &TaskOutput) -> Result<Vec<PathBuf>, AudioError> {
+        let name = output.name.as_deref().unwrap_or(&output.description);
+        self.render(&output.raw, &file_stem(name))
+    }
+
+    /// A task callback that renders the task's output, logging failures.
+    pub fn task_callback(self) -> TaskCallback {
+        Box::new(move |output: &TaskOutput| match self.process(output) {
+            Ok(paths) => {
+                for path in paths {
+                    log::info!("Wrote task audio to {}", path.display());
+                }
+            }
+            Err(e) => log::warn!("Text-to-speech for task output failed: {}", e),
+        })
+    }
+}
+
+/// A filesystem-safe stem from a task name.
+fn file_stem(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            stem.extend(c.to_lowercase());
+        } else if !stem.ends_with('-') && !stem.is_empty() {
+            stem.push('-');
+        }
+        if stem.chars().count() >= 60 {
+            break;
+        }
+    }
+    let stem = stem.trim_end_matches('-');
+    if stem.is_empty() {
+        "output".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+/// Text with Markdown markup and code blocks removed, for reading aloud.
+fn spoken_text(text: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        let without_marker = trimmed
+            .trim_start_matches('#')
+            .trim_start_matches("- ")
+            .trim_start_matches("* ")
+            .trim_start_matches('>');
+        let cleaned: String = without_marker
+            .chars()
+            .filter(|c| !matches!(c, '*' | '`'))
+            .collect();
+        lines.push(cleaned.trim().to_string());
+    }
+    lines
+        .split(|l| l.is_empty())
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| paragraph.join(" "))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Split text into pieces of at most `max_chars` characters, preferring
+/// paragraph and sentence boundaries.
+fn split_for_speech(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    fn push(current: &mut String, parts: &mut Vec<String>) {
+        let piece = current.trim();
+        if !piece.is_empty() {
+            parts.push(piece.to_string());
+        }
+        current.clear();
+    }
+
+    for sentence in sentences(text) {
+        if current.chars().count() + sentence.chars().count() > max_chars {
+            push(&mut current, &mut parts);
+        }
+        if sentence.chars().count() > max_chars {
+            let chars: Vec<char> = sentence.chars().collect();
+            for chunk in chars.chunks(max_chars) {
+                current.extend(chunk);
+                push(&mut current, &mut parts);
+            }
+            continue;
+        }
+        current.push_str(sentence);
+    }
+    push(&mut current, &mut parts);
+    parts
+}
+
+/// Sentences of `text`, each including its trailing punctuation and space.
+fn sentences(text: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while let Some((i, c)) = chars.next() {
+            let boundary = match c {
+                '\n' => true,
+                '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+                _ => false,
+            };
+            if boundary {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, next)) = chars.peek() {
+                    if !next.is_whitespace() {
+                        break;
+                    }
+                    end = j + next.len_utf8();
+                    chars.next();
+                }
+                let sentence = &text[start..end];
+                start = end;
+                return Some(sentence);
+            }
+        }
+        if start < text.len() {
+            let rest = &text[start..];
+            start = text.len();
+            return Some(rest);
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::output_format::OutputFormat;
+    use std::sync::Mutex;
+
+    /// Records what it was asked to say and returns the text as "audio".
+    #[derive(Debug, Default)]
+    struct EchoSynth {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl SpeechSynthesizer for Arc<EchoSynth> {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn format(&self) -> AudioFormat {
+            AudioFormat::Wav
+        }
+
+        fn max_input_chars(&self) -> usize {
+            50
+        }
+
+        fn synthesize(&self, text: &str) -> Result<Vec<u8>, AudioError> {
+            self.calls.lock().unwrap().push(text.to_string());
+            Ok(text.as_bytes().to_vec())
+        }
+    }
+
+    #[test]
+    fn test_split_for_speech() {
+        let text = "First sentence here. Second one! Third?\nA very long sentence without any stop";
+        let parts = split_for_speech(text, 25);
+        assert_eq!(
+            parts,
+            vec![
+                "First sentence here.",
+                "Second one! Third?",
+                "A very long sentence with",
+                "out any stop"
+            ]
+        );
+        assert!(parts.iter().all(|p| p.chars().count() <= 25));
+        assert_eq!(split_for_speech("v1.2 is out.", 100), vec!["v1.2 is out."]);
+    }
+
+    #[test]
+    fn test_processor_writes_task_audio() {
+        let dir = tempfile::tempdir().unwrap();
+        let synth = Arc::new(EchoSynth::default());
+        let processor = TtsOutputProcessor::new(synth.clone(), dir.path());
+
+        let mut output = TaskOutput::new(
+            "Write the Q3 report".into(),
+            "writer".into(),
+            String::new(),
+            OutputFormat::Raw,
+        );
+        output.raw =
+            "# Summary\n\nRevenue **grew** by 12%.\n\n```\ncode\n```\n- Costs fell.".into();
+        let paths = processor.process(&output).unwrap();
+        assert_eq!(paths, vec![dir.path().join("write-the-q3-report.wav")]);
+        assert_eq!(
+            std::fs::read_to_string(&paths[0]).unwrap(),
+            "Summary\n\nRevenue grew by 12%.\n\nCosts fell."
+        );
+
+        output.raw = "One two three four five six. Seven eight nine ten eleven twelve.".into();
+        let paths = processor.render(&output.raw, "long").unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                dir.path().join("long-01.wav"),
+                dir.path().join("long-02.wav")
+            ]
+        );
+        assert_eq!(synth.calls.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_openai_tts_inside_runtime() {
+        use axum::routing::post;
+
+        let router = axum::Router::new().route(
+            "/audio/speech",
+            post(|body: axum::Json<serde_json::Value>| async move {
+                format!("audio of {}", body["input"].as_str().unwrap_or_default())
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        // A synchronous call from async code, as under `kickoff_async`.
+        let tts = OpenAITts::new(Some("key".to_string())).with_base_url(url);
+        assert_eq!(tts.synthesize("hello").unwrap(), b"audio of hello");
+    }
+}
@@ -0,0 +1,593 @@
+//! Speech-to-text backends and the transcription tool.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{block_on, check_status, AudioError, ScratchDir};
+use crate::tools::base_tool::BaseTool;
+
+/// A timed piece of a transcript.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    /// Start offset in seconds.
+    pub start: f64,
+    /// End offset in seconds.
+    pub end: f64,
+    /// Text spoken in this segment.
+    pub text: String,
+}
+
+/// The result of transcribing an audio file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    /// Full transcript text.
+    pub text: String,
+    /// Detected or requested language, if reported.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Audio duration in seconds, if reported.
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Timed segments, if the backend provides them.
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// The transcript with a `[mm:ss.s - mm:ss.s]` prefix on each segment.
+    ///
+    /// Falls back to the plain text when there are no segments.
+    pub fn timestamped_text(&self) -> String {
+        if self.segments.is_empty() {
+            return self.text.clone();
+        }
+        self.segments
+            .iter()
+            .map(|s| {
+                format!(
+                    "[{} - {}] {}",
+                    format_offset(s.start),
+                    format_offset(s.end),
+                    s.text.trim()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn format_offset(seconds: f64) -> String {
+    let seconds = seconds.max(0.0);
+    let minutes = (seconds / 60.0).floor();
+    format!("{:02}:{:04.1}", minutes as u64, seconds - minutes * 60.0)
+}
+
+/// A speech-to-text backend.
+pub trait Transcriber: Send + Sync + fmt::Debug {
+    /// Backend name (for logging and metadata).
+    fn name(&self) -> &str;
+
+    /// Transcribe an audio file.
+    fn transcribe(&self, path: &Path) -> Result<Transcript, AudioError>;
+}
+
+// ---------------------------------------------------------------------------
+// OpenAI Whisper API
+// ---------------------------------------------------------------------------
+
+/// Transcription through the OpenAI `audio/transcriptions` endpoint.
+///
+/// Works with any OpenAI-compatible server (set `base_url`).
+#[derive(Clone)]
+pub struct OpenAIWhisper {
+    /// API key (defaults to `OPENAI_API_KEY`).
+    pub api_key: Option<String>,
+    /// API base URL.
+    pub base_url: String,
+    /// Transcription model.
+    pub model: String,
+    /// ISO-639-1 language hint.
+    pub language: Option<String>,
+    /// Prompt to guide spelling and style.
+    pub prompt: Option<String>,
+    /// Request timeout.
+    pub timeout: Duration,
+}
+
+impl OpenAIWhisper {
+    /// Create a backend using `whisper-1`.
+    ///
+    /// `api_key` defaults to the `OPENAI_API_KEY` environment variable.
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            api_key: api_key.or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+            base_url: "https://api.openai.com/v1".to_string(),
+            model: "whisper-1".to_string(),
+            language: None,
+            prompt: None,
+            timeout: Duration::from_secs(300),
+        }
+    }
+
+    /// Builder: set the model.
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Builder: set the API base URL.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Builder: set the language hint.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Builder: set the prompt.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    async fn request(&self, path: &Path) -> Result<Transcript, AudioError> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or(AudioError::MissingApiKey("OpenAI"))?;
+        let audio = tokio::fs::read(path).await?;
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "audio".to_string());
+
+        let mut form = Multipart::new();
+        form.text("model", &self.model);
+        form.text("response_format", "verbose_json");
+        form.text("timestamp_granularities[]", "segment");
+        if let Some(language) = &self.language {
+            form.text("language", language);
+        }
+        if let Some(prompt) = &self.prompt {
+            form.text("prompt", prompt);
+        }
+        form.file("file", &file_name, mime_type(path), &audio);
+        let (content_type, body) = form.finish();
+
+        let response = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()?
+            .post(format!(
+                "{}/audio/transcriptions",
+                self.base_url.trim_end_matches('/')
+            ))
+            .bearer_auth(api_key)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(body)
+            .send()
+            .await?;
+        let body: Value = check_status(response).await?.json().await?;
+        Ok(parse_verbose_json(&body))
+    }
+}
+
+impl fmt::Debug for OpenAIWhisper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenAIWhisper")
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("base_url", &self.base_url)
+            .field("model", &self.model)
+            .field("language", &self.language)
+            .finish()
+    }
+}
+
+impl Transcriber for OpenAIWhisper {
+    fn name(&self) -> &str {
+        "openai_whisper"
+    }
+
+    fn transcribe(&self, path: &Path) -> Result<Transcript, AudioError> {
+        block_on(self.request(path))?
+    }
+}
+
+/// Parse a `verbose_json` (or plain `json`) transcription response.
+fn parse_verbose_json(body: &Value) -> Transcript {
+    let segments = body
+        .get("segments")
+        .and_then(Value::as_array)
+        .map(|segments| {
+            segments
+                .iter()
+                .map(|s| TranscriptSegment {
+                    start: s.get("start").and_then(Value::as_f64).unwrap_or_default(),
+                    end: s.get("end").and_then(Value::as_f64).unwrap_or_default(),
+                    text: s
+                        .get("text")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Transcript {
+        text: body
+            .get("text")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        language: body
+            .get("language")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        duration: body.get("duration").and_then(Value::as_f64),
+        segments,
+    }
+}
+
+/// Content type for an audio file, by extension.
+fn mime_type(path: &Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("mp3" | "mpga" | "mpeg") => "audio/mpeg",
+        Some("m4a" | "mp4") => "audio/mp4",
+        Some("wav") => "audio/wav",
+        Some("webm") => "audio/webm",
+        Some("ogg" | "oga") => "audio/ogg",
+        Some("flac") => "audio/flac",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Minimal `multipart/form-data` encoder.
+struct Multipart {
+    boundary: String,
+    body: Vec<u8>,
+}
+
+impl Multipart {
+    fn new() -> Self {
+        Self {
+            boundary: format!("crewai-{}", uuid::Uuid::new_v4().simple()),
+            body: Vec::new(),
+        }
+    }
+
+    fn text(&mut self, name: &str, value: &str) {
+        self.body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                self.boundary, name, value
+            )
+            .as_bytes(),
+        );
+    }
+
+    fn file(&mut self, name: &str, file_name: &str, content_type: &str, data: &[u8]) {
+        self.body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                 Content-Type: {}\r\n\r\n",
+                self.boundary,
+                name,
+                file_name.replace('"', "_"),
+                content_type
+            )
+            .as_bytes(),
+        );
+        self.body.extend_from_slice(data);
+        self.body.extend_from_slice(b"\r\n");
+    }
+
+    /// The `Content-Type` header value and the encoded body.
+    fn finish(mut self) -> (String, Vec<u8>) {
+        self.body
+            .extend_from_slice(format!("--{}--\r\n", self.boundary).as_bytes());
+        (
+            format!("multipart/form-data; boundary={}", self.boundary),
+            self.body,
+        )
+    }
+}
+
+// ---------------------------------------------------------------------------
+// whisper.cpp
+// ---------------------------------------------------------------------------
+
+/// Local transcription with a whisper.cpp command line binary.
+///
+/// Runs `whisper-cli -m <model> -f <file> -oj` and reads the JSON output.
+/// whisper.cpp expects 16 kHz WAV input unless it was built with ffmpeg
+/// support.
+#[derive(Debug, Clone)]
+pub struct WhisperCpp {
+    /// Path to the whisper.cpp binary.
+    pub binary: PathBuf,
+    /// Path to the ggml model file.
+    pub model_path: PathBuf,
+    /// Language code, or `None` for auto-detection.
+    pub language: Option<String>,
+    /// Number of threads, or `None` for the binary's default.
+    pub threads: Option<u32>,
+}
+
+impl WhisperCpp {
+    /// Create a backend using `whisper-cli` from `PATH` and the given model.
+    pub fn new(model_path: impl Into<PathBuf>) -> Self {
+        Self {
+            binary: PathBuf::from("whisper-cli"),
+            model_path: model_path.into(),
+            language: None,
+            threads: None,
+        }
+    }
+
+    /// Builder: set the binary path.
+    pub fn with_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.binary = binary.into();
+        self
+    }
+
+    /// Builder: set the language.
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Builder: set the thread count.
+    pub fn with_threads(mut self, threads: u32) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+}
+
+impl Transcriber for WhisperCpp {
+    fn name(&self) -> &str {
+        "whisper_cpp"
+    }
+
+    fn transcribe(&self, path: &Path) -> Result<Transcript, AudioError> {
+        let out_dir = ScratchDir::new("crewai-whisper")?;
+        let out_base = out_dir.path().join("transcript");
+        let mut command = Command::new(&self.binary);
+        command
+            .arg("-m")
+            .arg(&self.model_path)
+            .arg("-f")
+            .arg(path)
+            .arg("-oj")
+            .arg("-of")
+            .arg(&out_base)
+            .arg("-np")
+            .arg("-l")
+            .arg(self.language.as_deref().unwrap_or("auto"));
+        if let Some(threads) = self.threads {
+            command.arg("-t").arg(threads.to_string());
+        }
+        let output = command.output()?;
+        if !output.status.success() {
+            return Err(AudioError::Backend {
+                backend: "whisper.cpp",
+                message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        let json = std::fs::read_to_string(out_base.with_extension("json"))?;
+        let body: Value = serde_json::from_str(&json).map_err(|e| AudioError::Backend {
+            backend: "whisper.cpp",
+            message: format!("invalid JSON output: {}", e),
+        })?;
+        Ok(parse_whisper_cpp_json(&body))
+    }
+}
+
+/// Parse the `-oj` output of whisper.cpp.
+fn parse_whisper_cpp_json(body: &Value) -> Transcript {
+    let segments: Vec<TranscriptSegment> = body
+        .get("transcription")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| {
+                    let offset = |key: &str| {
+                        item.pointer(&format!("/offsets/{}", key))
+                            .and_then(Value::as_f64)
+                            .unwrap_or_default()
+                            / 1000.0
+                    };
+                    TranscriptSegment {
+                        start: offset("from"),
+                        end: offset("to"),
+                        text: item
+                            .get("text")
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string(),
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Transcript {
+        text: segments
+            .iter()
+            .map(|s| s.text.as_str())
+            .filter(|t| !t.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+        language: body
+            .pointer("/result/language")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        duration: segments.last().map(|s| s.end),
+        segments,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tool
+// ---------------------------------------------------------------------------
+
+/// Agent tool that transcribes an audio file.
+///
+/// Arguments: `file_path` (required) and `timestamps` (optional, prefixes
+/// each segment with its time range).
+#[derive(Debug, Clone)]
+pub struct TranscriptionTool {
+    transcriber: Arc<dyn Transcriber>,
+    usage_count: u32,
+}
+
+impl TranscriptionTool {
+    /// Create a tool backed by `transcriber`.
+    pub fn new(transcriber: impl Transcriber + 'static) -> Self {
+        Self::from_arc(Arc::new(transcriber))
+    }
+
+    /// Create a tool sharing an existing transcriber.
+    pub fn from_arc(transcriber: Arc<dyn Transcriber>) -> Self {
+        Self {
+            transcriber,
+            usage_count: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTool for TranscriptionTool {
+    fn name(&self) -> &str {
+        "transcribe_audio"
+    }
+
+    fn description(&self) -> &str {
+        "Transcribe speech in an audio file (mp3, wav, m4a, ...) to text."
+    }
+
+    fn args_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Path to the audio file"
+                },
+                "timestamps": {
+                    "type": "boolean",
+                    "description": "Prefix each segment with its time range"
+                }
+            },
+            "required": ["file_path"]
+        })
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(
+        &mut self,
+        args: HashMap<String, Value>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let path = args
+            .get("file_path")
+            .and_then(Value::as_str)
+            .ok_or("transcribe_audio requires a `file_path` argument")?;
+        let timestamps = args
+            .get("timestamps")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let transcript = self.transcriber.transcribe(Path::new(path))?;
+        self.usage_count += 1;
+        Ok(Value::String(if timestamps {
+            transcript.timestamped_text()
+        } else {
+            transcript.text
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_backend_output() {
+        let api = parse_verbose_json(&json!({
+            "text": " Hello there. General Kenobi. ",
+            "language": "english",
+            "duration": 4.5,
+            "segments": [
+                {"start": 0.0, "end": 1.8, "text": " Hello there."},
+                {"start": 1.8, "end": 64.3, "text": " General Kenobi."}
+            ]
+        }));
+        assert_eq!(api.text, "Hello there. General Kenobi.");
+        assert_eq!(api.language.as_deref(), Some("english"));
+        assert_eq!(
+            api.timestamped_text(),
+            "[00:00.0 - 00:01.8] Hello there.\n[00:01.8 - 01:04.3] General Kenobi."
+        );
+
+        let local = parse_whisper_cpp_json(&json!({
+            "result": {"language": "en"},
+            "transcription": [
+                {"offsets": {"from": 0, "to": 1500}, "text": " Hello"},
+                {"offsets": {"from": 1500, "to": 3000}, "text": " world"}
+            ]
+        }));
+        assert_eq!(local.text, "Hello world");
+        assert_eq!(local.duration, Some(3.0));
+        assert_eq!(local.language.as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn test_multipart_encoding() {
+        let mut form = Multipart::new();
+        let boundary = form.boundary.clone();
+        form.text("model", "whisper-1");
+        form.file("file", "a\"b.wav", "audio/wav", b"RIFF");
+        let (content_type, body) = form.finish();
+        assert_eq!(
+            content_type,
+            format!("multipart/form-data; boundary={}", boundary)
+        );
+        let body = String::from_utf8(body).unwrap();
+        assert_eq!(
+            body,
+            format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\nwhisper-1\r\n\
+                 --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a_b.wav\"\r\n\
+                 Content-Type: audio/wav\r\n\r\nRIFF\r\n--{b}--\r\n",
+                b = boundary
+            )
+        );
+    }
+}
@@ -0,0 +1,474 @@
+//! Benchmark workloads for the orchestration hot paths.
+//!
+//! The same [`workloads`] back the criterion suite in `benches/hot_paths.rs`
+//! and the `crewai bench` command. The command runs them with a small
+//! built-in harness ([`run`]), writes a JSON [`BenchReport`] and compares it
+//! against a saved baseline ([`compare`]) so regressions fail before a
+//! release. Reference baselines live in `benches/baselines/`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::base_event::BaseEventData;
+use crate::events::CrewAIEventsBus;
+use crate::impl_base_event;
+use crate::knowledge::source::{BaseKnowledgeSource, StringKnowledgeSource};
+use crate::memory::storage::interface::Storage;
+use crate::memory::storage::rag_storage::RAGStorage;
+use crate::policy::{
+    EnforcementMode, PolicyAction, PolicyEffect, PolicyEngine, PolicyPrincipal, PolicyRequest,
+    PolicyResource, PolicyRule,
+};
+use crate::utilities::prompts::{AgentInfo, PromptResult, Prompts};
+use crate::utilities::string_utils::interpolate_only;
+
+/// Number of rules in the policy evaluation workload.
+pub const POLICY_RULES: usize = 10_000;
+
+/// Number of entries searched by the vector search workload.
+pub const MEMORY_ENTRIES: usize = 5_000;
+
+/// A benchmarked hot path.
+pub struct Workload {
+    /// Benchmark name, also the key in reports and baselines.
+    pub name: &'static str,
+    /// What one iteration does.
+    pub description: &'static str,
+    setup: fn() -> Box<dyn FnMut()>,
+}
+
+impl Workload {
+    /// Build the workload's fixtures and return one iteration.
+    pub fn routine(&self) -> Box<dyn FnMut()> {
+        (self.setup)()
+    }
+}
+
+impl std::fmt::Debug for Workload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Workload")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .finish()
+    }
+}
+
+/// All hot-path workloads.
+pub fn workloads() -> Vec<Workload> {
+    vec![
+        Workload {
+            name: "message_formatting",
+            description: "Build an agent's task prompt and interpolate task inputs",
+            setup: message_formatting,
+        },
+        Workload {
+            name: "chunking",
+            description: "Chunk a 256 KiB knowledge source (4000/200)",
+            setup: chunking,
+        },
+        Workload {
+            name: "vector_search",
+            description: "Search 5000 memory entries",
+            setup: vector_search,
+        },
+        Workload {
+            name: "event_dispatch",
+            description: "Emit an event to two handlers on the global bus",
+            setup: event_dispatch,
+        },
+        Workload {
+            name: "policy_evaluation",
+            description: "Evaluate a tool call against 10000 policy rules",
+            setup: policy_evaluation,
+        },
+    ]
+}
+
+fn message_formatting() -> Box<dyn FnMut()> {
+    let prompts = Prompts {
+        has_tools: true,
+        ..Default::default()
+    };
+    let agent = AgentInfo {
+        role: "Senior {topic} Researcher".to_string(),
+        goal: "Uncover developments in {topic} for {year}".to_string(),
+        backstory: "A veteran analyst of {topic} who writes for {audience}.".to_string(),
+    };
+    let inputs: HashMap<String, String> = [
+        ("topic", "AI agents"),
+        ("year", "2025"),
+        ("audience", "engineers"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+    Box::new(move || {
+        let prompt = prompts.task_execution(&agent);
+        let text = match prompt {
+            PromptResult::Standard(p) => p.prompt,
+            PromptResult::System(p) => p.prompt,
+        };
+        black_box(interpolate_only(Some(&text), &inputs).ok());
+    })
+}
+
+fn chunking() -> Box<dyn FnMut()> {
+    let paragraph = "Crews coordinate role-playing agents that share tasks, tools and \
+                     memory to solve problems no single prompt can. ";
+    let text = paragraph.repeat(256 * 1024 / paragraph.len());
+    let source = StringKnowledgeSource::new(String::new());
+    Box::new(move || {
+        black_box(source.chunk_text(&text, Some(4000), Some(200)));
+    })
+}
+
+fn vector_search() -> Box<dyn FnMut()> {
+    let storage = RAGStorage::new("short_term", true, None, None, None);
+    let topics = [
+        "rust", "python", "agents", "memory", "policy", "events", "tools", "flows",
+    ];
+    for i in 0..MEMORY_ENTRIES {
+        let value = format!(
+            "Observation {} about {} and {} from task {}",
+            i,
+            topics[i % topics.len()],
+            topics[(i / 3) % topics.len()],
+            i % 97
+        );
+        storage
+            .save(&value, &HashMap::new())
+            .expect("in-memory save");
+    }
+    Box::new(move || {
+        black_box(storage.search("agents memory observation", 5, 0.3).ok());
+    })
+}
+
+/// Event emitted by the event dispatch workload.
+#[derive(Debug, Clone, Serialize)]
+struct BenchEvent {
+    #[serde(flatten)]
+    base: BaseEventData,
+}
+
+impl_base_event!(BenchEvent);
+
+fn event_dispatch() -> Box<dyn FnMut()> {
+    static HANDLERS: OnceLock<()> = OnceLock::new();
+    let bus = CrewAIEventsBus::global();
+    HANDLERS.get_or_init(|| {
+        bus.on::<BenchEvent>("bench_counter", |_, _| {}, None);
+        bus.on::<BenchEvent>(
+            "bench_inspector",
+            |_, e| {
+                black_box(e.event_type());
+            },
+            None,
+        );
+    });
+    let source: Arc<dyn std::any::Any + Send + Sync> = Arc::new(());
+    let mut emitted = 0u32;
+    Box::new(move || {
+        let mut event = BenchEvent {
+            base: BaseEventData::new("bench_event"),
+        };
+        bus.emit(source.clone(), &mut event);
+        // Handlers run on the bus runtime; drain them regularly so the
+        // pending handles stay bounded.
+        emitted += 1;
+        if emitted.is_multiple_of(1024) {
+            bus.flush();
+        }
+    })
+}
+
+fn policy_evaluation() -> Box<dyn FnMut()> {
+    let rules = (0..POLICY_RULES)
+        .map(|i| PolicyRule {
+            name: format!("rule_{}", i),
+            description: String::new(),
+            effect: if i % 4 == 0 {
+                PolicyEffect::Allow
+            } else {
+                PolicyEffect::Deny
+            },
+            principal: PolicyPrincipal::Role(format!("role_{}", i % 50)),
+            action: PolicyAction::ToolCall(format!("tool_{}", i)),
+            resource: PolicyResource::Any,
+            conditions: Vec::new(),
+            priority: (i % 200) as u32,
+        })
+        .collect();
+    let mut engine = PolicyEngine::with_rules(rules, EnforcementMode::Strict);
+    // Matches no rule, so every rule is checked.
+    let request = PolicyRequest {
+        agent_slot: 1,
+        agent_id: "researcher".to_string(),
+        agent_roles: vec!["role_7".to_string()],
+        action: PolicyAction::ToolCall("web_search".to_string()),
+        resource: PolicyResource::Tool("web_search".to_string()),
+        context: HashMap::new(),
+    };
+    Box::new(move || {
+        black_box(engine.evaluate(&request));
+    })
+}
+
+/// Harness settings for [`run`].
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Only run workloads whose name contains this string.
+    pub filter: Option<String>,
+    /// Measurement time per workload (after a warm-up of a tenth of it).
+    pub measurement_time: Duration,
+    /// Number of samples the measurement time is split into.
+    pub samples: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            filter: None,
+            measurement_time: Duration::from_secs(2),
+            samples: 50,
+        }
+    }
+}
+
+/// Timings of one workload, in nanoseconds per iteration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    /// Workload name.
+    pub name: String,
+    /// Iterations measured.
+    pub iterations: u64,
+    /// Mean over all samples.
+    pub mean_ns: f64,
+    /// Median sample.
+    pub median_ns: f64,
+    /// 95th percentile sample.
+    pub p95_ns: f64,
+}
+
+/// Results of a benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// crewAI version that produced the report.
+    pub version: String,
+    /// When the run finished.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Per-workload timings.
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    /// Load a report (or baseline) from a JSON file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Cannot read {}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the report as pretty JSON.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), anyhow::Error> {
+        std::fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// The result for workload `name`.
+    pub fn result(&self, name: &str) -> Option<&BenchResult> {
+        self.results.iter().find(|r| r.name == name)
+    }
+}
+
+/// Run the workloads selected by `options`.
+///
+/// Workloads run on a dedicated thread: some block (draining the event
+/// bus), which would panic on an async runtime's thread.
+pub fn run(options: &BenchOptions) -> BenchReport {
+    let results = std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                workloads()
+                    .iter()
+                    .filter(|w| {
+                        options
+                            .filter
+                            .as_deref()
+                            .is_none_or(|filter| w.name.contains(filter))
+                    })
+                    .map(|w| measure(w, options))
+                    .collect()
+            })
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    });
+    BenchReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now(),
+        results,
+    }
+}
+
+fn measure(workload: &Workload, options: &BenchOptions) -> BenchResult {
+    let mut routine = workload.routine();
+    let samples = options.samples.max(1);
+
+    // Warm up and estimate the cost of one iteration.
+    let warm_up = options.measurement_time / 10;
+    let started = Instant::now();
+    let mut warm_iterations = 0u64;
+    while warm_iterations == 0 || started.elapsed() < warm_up {
+        routine();
+        warm_iterations += 1;
+    }
+    let estimate = started.elapsed().as_nanos() as f64 / warm_iterations as f64;
+    let per_sample = options.measurement_time.as_nanos() as f64 / samples as f64;
+    let batch = ((per_sample / estimate.max(1.0)) as u64).max(1);
+
+    let mut times: Vec<f64> = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..batch {
+                routine();
+            }
+            start.elapsed().as_nanos() as f64 / batch as f64
+        })
+        .collect();
+    times.sort_by(f64::total_cmp);
+    let percentile = |p: f64| times[((times.len() - 1) as f64 * p).round() as usize];
+    BenchResult {
+        name: workload.name.to_string(),
+        iterations: batch * samples as u64,
+        mean_ns: times.iter().sum::<f64>() / times.len() as f64,
+        median_ns: percentile(0.5),
+        p95_ns: percentile(0.95),
+    }
+}
+
+/// A workload slower than its baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    /// Workload name.
+    pub name: String,
+    /// Baseline median, in nanoseconds.
+    pub baseline_ns: f64,
+    /// Current median, in nanoseconds.
+    pub current_ns: f64,
+}
+
+impl Regression {
+    /// Relative slowdown (0.25 = 25% slower).
+    pub fn change(&self) -> f64 {
+        self.current_ns / self.baseline_ns - 1.0
+    }
+}
+
+/// Workloads whose median grew by more than `threshold` (0.1 = 10%) over
+/// `baseline`. Workloads missing from the baseline are skipped.
+pub fn compare(report: &BenchReport, baseline: &BenchReport, threshold: f64) -> Vec<Regression> {
+    report
+        .results
+        .iter()
+        .filter_map(|current| {
+            let base = baseline.result(&current.name)?;
+            (base.median_ns > 0.0 && current.median_ns > base.median_ns * (1.0 + threshold)).then(
+                || Regression {
+                    name: current.name.clone(),
+                    baseline_ns: base.median_ns,
+                    current_ns: current.median_ns,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Render `report` as a table, with the change against `baseline` if given.
+pub fn format_report(report: &BenchReport, baseline: Option<&BenchReport>) -> String {
+    let mut out = format!(
+        "{:<20} {:>14} {:>14} {:>14} {:>10}\n",
+        "benchmark", "median", "mean", "p95", "change"
+    );
+    for r in &report.results {
+        let change = baseline
+            .and_then(|b| b.result(&r.name))
+            .filter(|b| b.median_ns > 0.0)
+            .map(|b| format!("{:+.1}%", (r.median_ns / b.median_ns - 1.0) * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<20} {:>14} {:>14} {:>14} {:>10}\n",
+            r.name,
+            format_ns(r.median_ns),
+            format_ns(r.mean_ns),
+            format_ns(r.p95_ns),
+            change
+        ));
+    }
+    out
+}
+
+fn format_ns(ns: f64) -> String {
+    if ns >= 1e9 {
+        format!("{:.2} s", ns / 1e9)
+    } else if ns >= 1e6 {
+        format!("{:.2} ms", ns / 1e6)
+    } else if ns >= 1e3 {
+        format!("{:.2} µs", ns / 1e3)
+    } else {
+        format!("{:.1} ns", ns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_and_compare_against_baseline() {
+        let options = BenchOptions {
+            filter: None,
+            measurement_time: Duration::from_millis(20),
+            samples: 4,
+        };
+        let report = run(&options);
+        let names: Vec<&str> = report.results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "message_formatting",
+                "chunking",
+                "vector_search",
+                "event_dispatch",
+                "policy_evaluation"
+            ]
+        );
+        assert!(report
+            .results
+            .iter()
+            .all(|r| r.iterations > 0 && r.median_ns > 0.0));
+
+        let mut baseline = report.clone();
+        baseline.results[0].median_ns = report.results[0].median_ns / 2.0;
+        baseline.results.truncate(2);
+        let regressions = compare(&report, &baseline, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].name, "message_formatting");
+        assert!((regressions[0].change() - 1.0).abs() < 1e-9);
+        assert!(compare(&report, &report, 0.0).is_empty());
+
+        let table = format_report(&report, Some(&baseline));
+        assert!(table.contains("+100.0%"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        report.save(&path).unwrap();
+        let loaded = BenchReport::load(&path).unwrap();
+        assert_eq!(loaded.results.len(), report.results.len());
+        assert!((loaded.results[1].median_ns - report.results[1].median_ns).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,44 @@
+//! crewai-rust playground binary.
+//!
+//! Serves a local web UI for composing an agent and task, running it against
+//! a configured provider, watching the transcript and events live, and
+//! exporting the result as `agents.yaml` / `tasks.yaml`.
+//!
+//! # Environment Variables
+//!
+//! - `PORT` — HTTP port (default: 3030)
+//! - `OPENAI_API_KEY`, `ANTHROPIC_API_KEY`, `XAI_API_KEY` — provider credentials
+//! - `RUST_LOG` — Tracing filter (default: "info")
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --bin crewai-playground --features playground
+//! # then open http://127.0.0.1:3030
+//! ```
+
+use crewai::server::playground::{playground_router, PlaygroundState};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "info,crewai=debug".into()),
+        )
+        .init();
+
+    let port = std::env::var("PORT").unwrap_or_else(|_| "3030".to_string());
+    // Local tool: bind to loopback only.
+    let bind_addr = format!("127.0.0.1:{}", port);
+
+    let app = playground_router(PlaygroundState::default());
+
+    tracing::info!("crewai playground running at http://{}", bind_addr);
+
+    let listener = tokio::net::TcpListener::bind(&bind_addr)
+        .await
+        .expect("Failed to bind");
+
+    axum::serve(listener, app).await.expect("Server failed");
+}
@@ -9,6 +9,21 @@
 //! - `CREWAI_STORE` — Storage backend: "memory" (default) or "postgres"
 //! - `DATABASE_URL` — PostgreSQL connection string (required if CREWAI_STORE=postgres)
 //! - `RUST_LOG` — Tracing filter (default: "info")
+//! - `CREWAI_A2A_TOKEN` / `CREWAI_A2A_API_KEY` — Require a bearer token or
+//!   `X-API-Key` on the A2A JSON-RPC endpoint
+//! - `CREWAI_A2A_URL` — Public A2A endpoint URL advertised in the agent card
+//! - `CREWAI_QUOTAS` — YAML/JSON file with per-tenant execution quotas (API
+//!   keys, concurrency, daily token/cost budgets, allowed crews)
+//! - `CREWAI_PROFILES` — YAML/JSON file with run profiles (per-environment
+//!   models, budgets, verbosity, telemetry endpoint, storage locations)
+//! - `CREWAI_PROFILE` — Active profile (or pass `--profile <name>`); defaults
+//!   to the file's `default`
+//! - `SHUTDOWN_TIMEOUT_SECS` — How long to wait for running crew executions
+//!   on shutdown (default: 30)
+//! - `CREWAI_FLIGHT_RECORDER` — Execution history database (or `off`); see
+//!   [`crewai::telemetry::flight_recorder`]
+//! - `CREWAI_POLICY_AUDIT` — Persistent policy audit log (`.jsonl` file or
+//!   SQLite database); see [`crewai::policy::audit`]
 //!
 //! # Usage
 //!
@@ -16,12 +31,166 @@
 //! cargo run --bin server
 //! # or with postgres:
 //! cargo run --bin server --features postgres
+//! # run with the prod settings from profiles.yaml:
+//! CREWAI_PROFILES=profiles.yaml server --profile prod
+//! # write a systemd unit (or WinSW config with --target windows):
+//! server serve install --name crewai --port 8080 --output /etc/systemd/system
+//! # scaffold a new crew (or flow) project in the current directory:
+//! server create crew research
+//! # run the crew in src/config/ of the current project, score its outputs
+//! # over three runs, or collect human feedback on five runs as training data:
+//! server run --input topic=Rust
+//! server test -n 3 -m gpt-4o-mini -o evaluation.json --input topic=Rust
+//! server train -n 5 -f training_data.json --input topic=Rust
+//! # chat with the crew, which runs once the chat has gathered its inputs:
+//! server chat -m gpt-4o
+//! # benchmark hot paths against the reference baseline (build with --release):
+//! server bench --baseline benches/baselines/hot_paths.json
+//! # serve global tools and registered crews over MCP on stdin/stdout:
+//! server mcp
+//! # export the A2A agent card for external catalogs:
+//! server agent-card --output agent.json
+//! # failed executions of the last day, and aggregate stats:
+//! server history --status failed --since 24h
+//! server history --crew research --stats
+//! # the last 50 policy denials of one agent:
+//! server policy-audit --agent analyst -n 50
 //! ```
+//!
+//! Under systemd the server signals readiness and shutdown via `sd_notify`
+//! and pings the watchdog (see [`crewai::server::service`]).
+
+use std::time::Duration;
 
-use crewai::server::{app_router, AppState};
+use crewai::server::{app_router, service, shutdown_signal, AppState};
 
 #[tokio::main]
 async fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(profile) = crewai::utilities::profiles::take_profile_arg(&mut args) {
+        std::env::set_var(crewai::utilities::profiles::PROFILE_ENV, profile);
+    }
+    if let ["serve", "install", rest @ ..] | ["install", rest @ ..] = args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        let rest: Vec<String> = rest.iter().map(|s| s.to_string()).collect();
+        match crewai::cli::serve_install(&rest) {
+            Ok(path) => println!("Wrote {}", path),
+            Err(e) => {
+                eprintln!("serve install: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("create") {
+        match crewai::cli::create(&args[1..]) {
+            Ok(path) => println!("Created {}", path),
+            Err(e) => {
+                eprintln!("create: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if let Some(command @ ("run" | "test" | "train" | "chat")) = args.first().map(String::as_str) {
+        // Crews drive their own runtime, so they run off this one.
+        let command = command.to_string();
+        let result = tokio::task::block_in_place(|| {
+            crewai::cli::local_crew(".").and_then(|mut crew| match command.as_str() {
+                "run" => crewai::cli::run_crew(&mut crew, &args[1..]),
+                "test" => crewai::cli::test_crew(&mut crew, &args[1..]),
+                "chat" => crewai::cli::chat_crew(
+                    &mut crew,
+                    &args[1..],
+                    &mut std::io::stdin().lock(),
+                    &mut std::io::stdout(),
+                )
+                .map(|()| String::new()),
+                _ => crewai::cli::train_crew(&mut crew, &args[1..], &mut crewai::cli::ask_feedback)
+                    .map(|file| format!("Training data saved to {}", file)),
+            })
+        });
+        match result {
+            Ok(report) if report.is_empty() => {}
+            Ok(report) => println!("{}", report),
+            Err(e) => {
+                eprintln!("{}: {}", command, e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("bench") {
+        match crewai::cli::bench(&args[1..]) {
+            Ok(table) => print!("{}", table),
+            Err(e) => {
+                eprintln!("bench: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("agent-card") {
+        match crewai::cli::agent_card(&AppState::new().a2a, &args[1..]) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("agent-card: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("history") {
+        match crewai::cli::history(&args[1..]) {
+            Ok(output) => print!("{}", output),
+            Err(e) => {
+                eprintln!("history: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("policy-audit") {
+        match crewai::cli::policy_audit(&args[1..]) {
+            Ok(output) => print!("{}", output),
+            Err(e) => {
+                eprintln!("policy-audit: {}", e);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if args.first().map(String::as_str) == Some("mcp") {
+        // stdout carries the protocol, so logs go to stderr.
+        tracing_subscriber::fmt()
+            .with_env_filter(
+                tracing_subscriber::EnvFilter::try_from_default_env()
+                    .unwrap_or_else(|_| "warn".into()),
+            )
+            .with_writer(std::io::stderr)
+            .init();
+        let state = AppState::new();
+        let server = crewai::mcp::MCPServer::new("crewai")
+            .with_tools(crewai::tools::registry::ToolRegistry::global().clone())
+            .with_crews(state.crews.crews.clone(), state.crews.executions.clone());
+        if let Err(e) = server.serve_stdio().await {
+            eprintln!("mcp: {}", e);
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Initialize tracing
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -61,17 +230,52 @@ async fn main() {
         }
     }
 
+    let executions = state.crews.executions.clone();
+    let quotas_enabled = state.crews.quotas.is_some();
     let app = app_router(state);
 
     tracing::info!("crewai-rust server starting on {}", bind_addr);
     tracing::info!("Endpoints:");
     tracing::info!("  GET  /health  — liveness probe");
+    tracing::info!("  GET  /ready   — readiness probe");
     tracing::info!("  POST /execute — crew.* step delegation");
     tracing::info!("  POST /chat    — substrate-driven chat (holy grail pipeline)");
+    tracing::info!("  POST /flows/:name/resume/:flow_id — resume a paused flow");
+    tracing::info!("  POST /crews/:name/kickoff — start a registered crew");
+    tracing::info!("  GET  /executions/:id[/events|/ws] — crew run status / live events");
+    tracing::info!("  POST /mcp     — MCP endpoint for tools and crews");
+    if quotas_enabled {
+        tracing::info!("  GET  /quota   — caller's tenant quota and usage");
+    }
 
     let listener = tokio::net::TcpListener::bind(&bind_addr)
         .await
         .expect("Failed to bind");
 
-    axum::serve(listener, app).await.expect("Server failed");
+    let timeout = std::env::var("SHUTDOWN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(30));
+
+    service::notify_ready(&format!("Serving on {}", bind_addr));
+    service::spawn_watchdog();
+
+    // On shutdown, refuse new kickoffs but keep serving status and event
+    // requests until running crew executions finish (or the timeout hits).
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            shutdown_signal().await;
+            let running = executions.active_count();
+            tracing::info!(
+                "Shutting down; waiting for {} running crew execution(s)",
+                running
+            );
+            service::notify_stopping(&format!("Draining {} running crew execution(s)", running));
+            if !executions.shutdown(timeout).await {
+                tracing::warn!("Shutdown timeout reached with crew executions still running");
+            }
+        })
+        .await
+        .expect("Server failed");
 }
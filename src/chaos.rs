@@ -0,0 +1,343 @@
+//! Fault injection for resilience testing.
+//!
+//! With the `chaos` feature, [`enable`] makes LLM providers and registered
+//! tools fail or slow down at random, with the configured probabilities:
+//!
+//! - provider requests are answered with an injected 429 or 5xx before
+//!   reaching the network, exercising the providers' retry and backoff;
+//! - provider requests and tool runs are delayed (slow responses);
+//! - tool runs time out, failing after the configured delay.
+//!
+//! Faults can be limited to some providers or tools, and a seed makes a run
+//! reproducible. Every injected fault is logged under the `crewai::chaos`
+//! target and counted in [`stats`]. Without the feature no fault code is
+//! compiled in.
+//!
+//! ```yaml
+//! # CREWAI_CHAOS=chaos.yaml
+//! seed: 42
+//! rate_limit: 0.2
+//! server_error: 0.1
+//! slow_response: 0.3
+//! slow_response_ms: 2000
+//! tool_timeout: 0.1
+//! tools: [search]
+//! ```
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utilities::journal::{self, splitmix64};
+
+/// Environment variable naming a chaos configuration file (YAML or JSON)
+/// that is enabled on first use.
+pub const CHAOS_ENV: &str = "CREWAI_CHAOS";
+
+/// What to inject and how often. Probabilities are per request or tool run,
+/// between 0 and 1.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Seed of the fault sequence. When unset, journaled runs draw from the
+    /// journal's random stream and other runs from a random seed.
+    pub seed: Option<u64>,
+    /// Probability of answering a provider request with a 429.
+    pub rate_limit: f64,
+    /// Probability of answering a provider request with a 500.
+    pub server_error: f64,
+    /// Probability of delaying a provider request or tool run.
+    pub slow_response: f64,
+    /// Delay of a slow response, in milliseconds.
+    pub slow_response_ms: u64,
+    /// Probability of a tool run timing out.
+    pub tool_timeout: f64,
+    /// How long a timing-out tool hangs before failing, in milliseconds.
+    pub tool_timeout_ms: u64,
+    /// Providers to inject into (e.g. `openai`); all when empty.
+    pub providers: Vec<String>,
+    /// Tools to inject into; all when empty.
+    pub tools: Vec<String>,
+}
+
+impl ChaosConfig {
+    /// Load from a YAML or JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_yaml::from_str(&text).map_err(|e| format!("Invalid chaos config: {}", e))
+    }
+
+    fn targets_provider(&self, provider: &str) -> bool {
+        self.providers.is_empty() || self.providers.iter().any(|p| p == provider)
+    }
+
+    fn targets_tool(&self, tool: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|t| t == tool)
+    }
+}
+
+/// A fault injected into a provider request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ProviderFault {
+    /// The provider answered 429 Too Many Requests.
+    #[error("Rate limited (429) [injected by chaos mode]")]
+    RateLimited,
+    /// The provider answered 500 Internal Server Error.
+    #[error("Server error: 500 Internal Server Error [injected by chaos mode]")]
+    ServerError,
+}
+
+/// A tool run that timed out by injection.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Tool '{tool}' timed out after {elapsed:?} [injected by chaos mode]")]
+pub struct ToolTimeout {
+    /// Name of the tool.
+    pub tool: String,
+    /// How long the run hung.
+    pub elapsed: Duration,
+}
+
+/// Number of faults injected so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChaosStats {
+    /// Injected 429s.
+    pub rate_limits: u64,
+    /// Injected 5xx errors.
+    pub server_errors: u64,
+    /// Delayed provider requests and tool runs.
+    pub slow_responses: u64,
+    /// Timed-out tool runs.
+    pub tool_timeouts: u64,
+}
+
+struct Chaos {
+    config: ChaosConfig,
+    rng: Mutex<u64>,
+}
+
+impl Chaos {
+    /// Whether an event of `probability` happens (splitmix64 draw).
+    fn roll(&self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        // Without a seed of its own, a journaled run's stream makes the
+        // faults reproducible.
+        let journaled = match self.config.seed {
+            Some(_) => None,
+            None => journal::next_random(),
+        };
+        let z = journaled.unwrap_or_else(|| {
+            let mut state = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+            splitmix64(&mut state)
+        });
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn slow_delay(&self) -> Option<Duration> {
+        self.roll(self.config.slow_response).then(|| {
+            SLOW_RESPONSES.fetch_add(1, Ordering::Relaxed);
+            Duration::from_millis(self.config.slow_response_ms)
+        })
+    }
+}
+
+static CHAOS: RwLock<Option<Chaos>> = RwLock::new(None);
+static FROM_ENV: OnceLock<()> = OnceLock::new();
+static RATE_LIMITS: AtomicU64 = AtomicU64::new(0);
+static SERVER_ERRORS: AtomicU64 = AtomicU64::new(0);
+static SLOW_RESPONSES: AtomicU64 = AtomicU64::new(0);
+static TOOL_TIMEOUTS: AtomicU64 = AtomicU64::new(0);
+
+/// Start injecting faults as described by `config`, replacing any earlier
+/// configuration.
+pub fn enable(config: ChaosConfig) {
+    FROM_ENV.get_or_init(|| ());
+    install(config);
+}
+
+fn install(config: ChaosConfig) {
+    let seed = config.seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    log::warn!(target: "crewai::chaos", "Chaos mode enabled: {:?}", config);
+    *CHAOS.write().unwrap_or_else(|e| e.into_inner()) = Some(Chaos {
+        config,
+        rng: Mutex::new(seed),
+    });
+}
+
+/// Stop injecting faults, overriding `CREWAI_CHAOS`.
+pub fn disable() {
+    FROM_ENV.get_or_init(|| ());
+    *CHAOS.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Whether faults are being injected.
+pub fn is_enabled() -> bool {
+    with_chaos(|_| ()).is_some()
+}
+
+/// Faults injected since the process started or [`reset_stats`].
+pub fn stats() -> ChaosStats {
+    ChaosStats {
+        rate_limits: RATE_LIMITS.load(Ordering::Relaxed),
+        server_errors: SERVER_ERRORS.load(Ordering::Relaxed),
+        slow_responses: SLOW_RESPONSES.load(Ordering::Relaxed),
+        tool_timeouts: TOOL_TIMEOUTS.load(Ordering::Relaxed),
+    }
+}
+
+/// Zero the fault counters.
+pub fn reset_stats() {
+    for counter in [
+        &RATE_LIMITS,
+        &SERVER_ERRORS,
+        &SLOW_RESPONSES,
+        &TOOL_TIMEOUTS,
+    ] {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
+
+fn with_chaos<T>(f: impl FnOnce(&Chaos) -> T) -> Option<T> {
+    FROM_ENV.get_or_init(|| {
+        let Ok(path) = std::env::var(CHAOS_ENV) else {
+            return;
+        };
+        match ChaosConfig::from_file(&path) {
+            Ok(config) => install(config),
+            Err(e) => log::error!(target: "crewai::chaos", "{}: {}", CHAOS_ENV, e),
+        }
+    });
+    CHAOS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(f)
+}
+
+/// Decide the fault for the next request to `provider`: sleeps first when
+/// the response is to be slow, then returns the error to report instead of
+/// sending the request, if any.
+pub async fn provider_fault(provider: &str) -> Option<ProviderFault> {
+    let (delay, fault) = with_chaos(|chaos| {
+        if !chaos.config.targets_provider(provider) {
+            return (None, None);
+        }
+        let fault = if chaos.roll(chaos.config.rate_limit) {
+            RATE_LIMITS.fetch_add(1, Ordering::Relaxed);
+            Some(ProviderFault::RateLimited)
+        } else if chaos.roll(chaos.config.server_error) {
+            SERVER_ERRORS.fetch_add(1, Ordering::Relaxed);
+            Some(ProviderFault::ServerError)
+        } else {
+            None
+        };
+        (chaos.slow_delay(), fault)
+    })?;
+    if let Some(delay) = delay {
+        log::warn!(target: "crewai::chaos", "Delaying {} request by {:?}", provider, delay);
+        tokio::time::sleep(delay).await;
+    }
+    if let Some(fault) = fault {
+        log::warn!(target: "crewai::chaos", "Injecting into {} request: {}", provider, fault);
+    }
+    fault
+}
+
+/// Apply faults to a run of `tool`: sleeps when the run is to be slow, and
+/// hangs then fails when it is to time out.
+pub fn tool_fault(tool: &str) -> Result<(), ToolTimeout> {
+    let Some((delay, timeout)) = with_chaos(|chaos| {
+        if !chaos.config.targets_tool(tool) {
+            return (None, None);
+        }
+        let timeout = chaos.roll(chaos.config.tool_timeout).then(|| {
+            TOOL_TIMEOUTS.fetch_add(1, Ordering::Relaxed);
+            Duration::from_millis(chaos.config.tool_timeout_ms)
+        });
+        (chaos.slow_delay(), timeout)
+    }) else {
+        return Ok(());
+    };
+    if let Some(delay) = delay {
+        log::warn!(target: "crewai::chaos", "Delaying tool '{}' by {:?}", tool, delay);
+        std::thread::sleep(delay);
+    }
+    match timeout {
+        Some(elapsed) => {
+            std::thread::sleep(elapsed);
+            let timeout = ToolTimeout {
+                tool: tool.to_string(),
+                elapsed,
+            };
+            log::warn!(target: "crewai::chaos", "{}", timeout);
+            Err(timeout)
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faults_follow_probabilities_and_targets() {
+        // Chaos state is global: target names no other test uses.
+        enable(ChaosConfig {
+            seed: Some(7),
+            tool_timeout: 1.0,
+            tool_timeout_ms: 5,
+            rate_limit: 1.0,
+            providers: vec!["chaos-test-provider".to_string()],
+            tools: vec!["chaos-test-tool".to_string()],
+            ..Default::default()
+        });
+        let before = stats();
+
+        let err = tool_fault("chaos-test-tool").unwrap_err();
+        assert_eq!(err.elapsed, Duration::from_millis(5));
+        assert!(err
+            .to_string()
+            .starts_with("Tool 'chaos-test-tool' timed out"));
+        assert!(tool_fault("other-tool").is_ok());
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        assert_eq!(
+            runtime.block_on(provider_fault("chaos-test-provider")),
+            Some(ProviderFault::RateLimited)
+        );
+        assert_eq!(runtime.block_on(provider_fault("openai")), None);
+
+        let after = stats();
+        assert!(after.tool_timeouts > before.tool_timeouts);
+        assert!(after.rate_limits > before.rate_limits);
+        disable();
+        assert!(tool_fault("chaos-test-tool").is_ok());
+
+        // The same seed gives the same fault sequence.
+        let draws = |seed| {
+            let chaos = Chaos {
+                config: ChaosConfig::default(),
+                rng: Mutex::new(seed),
+            };
+            (0..64).map(|_| chaos.roll(0.3)).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(1), draws(1));
+        assert_ne!(draws(1), draws(2));
+        let hits = draws(3).iter().filter(|&&hit| hit).count();
+        assert!((5..=35).contains(&hits), "{}", hits);
+    }
+}
@@ -566,8 +566,9 @@ impl AwarenessSession {
     ) -> Result<Value, String> {
         let api_key = provider
             .state
-            .api_key
-            .as_ref()
+            .resolve_api_key("XAI_API_KEY")
+            .await
+            .map_err(|e| e.to_string())?
             .ok_or("xAI API key not set")?;
         let base_url = provider.api_base_url();
         let endpoint = format!("{}/chat/completions", base_url);
@@ -588,7 +589,10 @@ impl AwarenessSession {
                 .http
                 .post(&endpoint)
                 .header("Content-Type", "application/json")
-                .header("Authorization", format!("Bearer {}", api_key))
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", api_key.expose_secret()),
+                )
                 .json(&body)
                 .send()
                 .await
@@ -0,0 +1,387 @@
+//! Interactive chat with a crew for `crewai chat`.
+//!
+//! Corresponds to `crewai/cli/crew_chat.py`. A chat LLM talks with the user
+//! and is offered the crew as a tool whose parameters are the crew's inputs
+//! (the `{placeholders}` of its tasks and agents). Once it has gathered
+//! them it calls the tool: the crew is kicked off with those inputs, each
+//! task's output is streamed back as it completes, and the crew's final
+//! output becomes the assistant's reply.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::crew::Crew;
+use crate::llm::LLM;
+use crate::types::crew_chat::{ChatInputField, ChatInputs};
+use crate::utilities::string_utils::{sanitize_tool_name, template_variables};
+
+/// How often finished task outputs are streamed during a kickoff.
+const STREAM_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A chat session with a crew.
+pub struct CrewChat<'a> {
+    crew: &'a mut Crew,
+    llm: LLM,
+    inputs: ChatInputs,
+    messages: Vec<HashMap<String, String>>,
+}
+
+impl<'a> CrewChat<'a> {
+    /// Start a session in which `llm` talks with the user about `crew`.
+    pub fn new(crew: &'a mut Crew, llm: LLM) -> Self {
+        let inputs = chat_inputs(crew);
+        let system = format!(
+            "You are a helpful assistant for the crew '{}', which {}.\n\
+             Help the user with what the crew can do. You may answer general \
+             questions, but guide the user back to the crew's purpose. When you \
+             know {}, call the '{}' tool to run the crew. Never invent inputs the \
+             user did not give or clearly imply; ask for what is missing.",
+            inputs.crew_name,
+            inputs.crew_description,
+            match inputs.inputs.len() {
+                0 => "what the user wants".to_string(),
+                _ => format!(
+                    "the crew's inputs ({})",
+                    inputs
+                        .inputs
+                        .iter()
+                        .map(|input| input.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            },
+            tool_name(&inputs),
+        );
+        Self {
+            crew,
+            llm,
+            inputs,
+            messages: vec![message("system", system)],
+        }
+    }
+
+    /// The crew's name, description and inputs.
+    pub fn inputs(&self) -> &ChatInputs {
+        &self.inputs
+    }
+
+    /// The conversation so far, system prompt first.
+    pub fn messages(&self) -> &[HashMap<String, String>] {
+        &self.messages
+    }
+
+    /// Introduction shown when the session starts.
+    pub fn greeting(&self) -> String {
+        let mut greeting = format!(
+            "Chatting with {}, which {}.",
+            self.inputs.crew_name, self.inputs.crew_description
+        );
+        if !self.inputs.inputs.is_empty() {
+            greeting.push_str("\nIt needs:");
+            for input in &self.inputs.inputs {
+                greeting.push_str(&format!("\n  - {}: {}", input.name, input.description));
+            }
+        }
+        greeting
+    }
+
+    /// Answer a user message, kicking off the crew when the chat LLM asks
+    /// for it. Progress of a kickoff is written to `out` as it happens.
+    pub fn respond(&mut self, text: &str, out: &mut dyn Write) -> Result<String, anyhow::Error> {
+        self.messages.push(message("user", text.to_string()));
+        let tool = self.tool_schema();
+        let result = self
+            .llm
+            .call_v2(&self.messages, Some(std::slice::from_ref(&tool)))
+            .map_err(anyhow::Error::msg)?;
+
+        let name = tool_name(&self.inputs);
+        let call = result
+            .tool_calls
+            .iter()
+            .find(|call| call["function"]["name"] == name.as_str());
+        let reply = match call {
+            Some(call) => {
+                let inputs = call_arguments(&call["function"]["arguments"])?;
+                self.kickoff(inputs, out)?
+            }
+            None => result.message,
+        };
+        self.messages.push(message("assistant", reply.clone()));
+        Ok(reply)
+    }
+
+    /// Run the crew with `inputs`, streaming each task's output to `out`,
+    /// and return its final output.
+    fn kickoff(
+        &mut self,
+        inputs: HashMap<String, String>,
+        out: &mut dyn Write,
+    ) -> Result<String, anyhow::Error> {
+        let mut shown: Vec<_> = inputs.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        shown.sort();
+        writeln!(
+            out,
+            "Running {} with {}",
+            self.inputs.crew_name,
+            shown.join(", ")
+        )?;
+
+        let (tx, rx) = mpsc::channel();
+        let previous = self.crew.task_callback.replace(Box::new(move |output| {
+            let _ = tx.send(output.clone());
+        }));
+        let crew = &mut *self.crew;
+        let result = std::thread::scope(|scope| {
+            let kickoff = scope.spawn(move || crew.kickoff((!inputs.is_empty()).then_some(inputs)));
+            // The sender lives in the crew's callback, so poll until the
+            // kickoff is done rather than waiting for the channel to close.
+            loop {
+                let finished = kickoff.is_finished();
+                for output in rx.try_iter() {
+                    let _ = writeln!(
+                        out,
+                        "\n## {} ({})\n{}",
+                        output.description, output.agent, output.raw
+                    );
+                    let _ = out.flush();
+                }
+                if finished {
+                    break;
+                }
+                std::thread::sleep(STREAM_POLL_INTERVAL);
+            }
+            kickoff.join()
+        });
+        self.crew.task_callback = previous;
+
+        let output = match result {
+            Ok(output) => output.map_err(anyhow::Error::msg)?,
+            Err(panic) => std::panic::resume_unwind(panic),
+        };
+        writeln!(out)?;
+        Ok(output.raw)
+    }
+
+    /// The crew as an OpenAI-style function tool.
+    fn tool_schema(&self) -> Value {
+        let properties: serde_json::Map<String, Value> = self
+            .inputs
+            .inputs
+            .iter()
+            .map(|input| {
+                (
+                    input.name.clone(),
+                    serde_json::json!({"type": "string", "description": input.description}),
+                )
+            })
+            .collect();
+        let required: Vec<&str> = self.inputs.inputs.iter().map(|i| i.name.as_str()).collect();
+        serde_json::json!({
+            "type": "function",
+            "function": {
+                "name": tool_name(&self.inputs),
+                "description": format!("Run the crew, which {}.", self.inputs.crew_description),
+                "parameters": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                },
+            },
+        })
+    }
+}
+
+/// The name, a description and the inputs of `crew`, derived from its
+/// tasks and agents.
+pub fn chat_inputs(crew: &Crew) -> ChatInputs {
+    let name = crew.name.clone().unwrap_or_else(|| "crew".to_string());
+    let steps: Vec<String> = crew
+        .tasks
+        .iter()
+        .map(|task| {
+            task.name
+                .clone()
+                .unwrap_or_else(|| task.description.clone())
+        })
+        .collect();
+    let description = match steps.len() {
+        0 => "has no tasks".to_string(),
+        _ => format!("runs these tasks in turn: {}", steps.join("; ")),
+    };
+    let mut chat_inputs = ChatInputs::new(name, description);
+
+    let mut add = |text: &str, used_by: &str| {
+        for variable in template_variables(text) {
+            if !chat_inputs
+                .inputs
+                .iter()
+                .any(|input| input.name == variable)
+            {
+                chat_inputs.inputs.push(ChatInputField {
+                    description: format!("Input used by {}", used_by),
+                    name: variable,
+                });
+            }
+        }
+    };
+    for task in &crew.tasks {
+        let used_by = format!(
+            "the task '{}'",
+            task.name.as_deref().unwrap_or(&task.description)
+        );
+        add(&task.description, &used_by);
+        add(&task.expected_output, &used_by);
+    }
+    for role in &crew.agents {
+        if let Some(Ok(agent)) = crew.get_agent(role).as_deref().map(|agent| agent.read()) {
+            let used_by = format!("the agent '{}'", agent.role);
+            for text in [&agent.role, &agent.goal, &agent.backstory] {
+                add(text, &used_by);
+            }
+        }
+    }
+    chat_inputs
+}
+
+/// Name of the tool that runs the crew.
+fn tool_name(inputs: &ChatInputs) -> String {
+    match sanitize_tool_name(&inputs.crew_name, None) {
+        name if name.is_empty() => "crew".to_string(),
+        name => name,
+    }
+}
+
+/// Kickoff inputs from the arguments of a tool call: a JSON object, or a
+/// string holding one.
+fn call_arguments(arguments: &Value) -> Result<HashMap<String, String>, anyhow::Error> {
+    let arguments = match arguments {
+        Value::String(text) if text.trim().is_empty() => Value::Object(Default::default()),
+        Value::String(text) => serde_json::from_str(text)?,
+        other => other.clone(),
+    };
+    let Value::Object(map) = arguments else {
+        return Err(anyhow::anyhow!("Crew inputs must be a JSON object"));
+    };
+    Ok(map
+        .into_iter()
+        .map(|(key, value)| match value {
+            Value::String(text) => (key, text),
+            other => (key, other.to_string()),
+        })
+        .collect())
+}
+
+fn message(role: &str, content: String) -> HashMap<String, String> {
+    HashMap::from([
+        ("role".to_string(), role.to_string()),
+        ("content".to_string(), content),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task;
+    use axum::{routing::post, Json};
+    use std::sync::{Arc, Mutex};
+
+    /// A chat completions server answering with `replies` in turn.
+    fn serve(replies: Vec<Value>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let router = axum::Router::new().route(
+            "/v1/chat/completions",
+            post(move |Json(body): Json<Value>| {
+                let turn = {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(body);
+                    seen.len() - 1
+                };
+                let reply = replies[turn.min(replies.len() - 1)].clone();
+                async move {
+                    Json(serde_json::json!({
+                        "model": "gpt-4o-mini",
+                        "choices": [{"message": reply, "finish_reason": "stop"}],
+                        "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+                    }))
+                }
+            }),
+        );
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                tx.send(format!("http://{}/v1", listener.local_addr().unwrap()))
+                    .unwrap();
+                axum::serve(listener, router).await.unwrap();
+            });
+        });
+        (rx.recv().unwrap(), requests)
+    }
+
+    #[test]
+    fn test_chat_kicks_off_crew_with_gathered_inputs() {
+        let (url, requests) = serve(vec![
+            serde_json::json!({"role": "assistant", "content": "Which topic?"}),
+            serde_json::json!({"role": "assistant", "content": null, "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "blog", "arguments": "{\"topic\": \"Rust\"}"}
+            }]}),
+        ]);
+        let mut task = Task::new("Write about {topic}".to_string(), "A post".to_string());
+        task.agent = Some("writer".to_string());
+        task.set_agent_executor(|prompt: &str, _: Option<&str>, _: &[String]| {
+            Ok((format!("Post: {}", prompt.contains("Rust")), Vec::new()))
+        });
+        let mut crew = Crew::new(vec![task], vec!["writer".to_string()]);
+        crew.name = Some("Blog".to_string());
+
+        let mut llm = LLM::new("gpt-4o-mini").api_key("sk-test");
+        llm.api_base = Some(url);
+        let mut chat = CrewChat::new(&mut crew, llm);
+        assert_eq!(chat.inputs().inputs[0].name, "topic");
+        assert!(chat.greeting().contains("- topic: Input used by the task"));
+
+        let mut out = Vec::new();
+        assert_eq!(
+            chat.respond("Write a post", &mut out).unwrap(),
+            "Which topic?"
+        );
+        assert_eq!(
+            chat.respond("Rust, please", &mut out).unwrap(),
+            "Post: true"
+        );
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("Running Blog with topic=Rust"));
+        assert!(out.contains("## Write about Rust (writer)\nPost: true"));
+        assert_eq!(chat.messages().len(), 5);
+
+        let requests = requests.lock().unwrap();
+        let tool = &requests[1]["tools"][0]["function"];
+        assert_eq!(tool["name"], "blog");
+        assert_eq!(tool["parameters"]["required"], serde_json::json!(["topic"]));
+        assert_eq!(requests[1]["messages"][2]["content"], "Which topic?");
+        drop(requests);
+        assert!(crew.task_callback.is_none());
+
+        let mut out = Vec::new();
+        let input = "hello\nexit\n".as_bytes();
+        let args = [
+            "--model".to_string(),
+            "gpt-4o-mini".to_string(),
+            "-x".to_string(),
+        ];
+        assert!(super::super::chat_crew(&mut crew, &args, &mut &input[..], &mut out).is_err());
+        assert_eq!(
+            super::super::parse_command("chat"),
+            Some(super::super::CliCommand::Chat)
+        );
+    }
+}
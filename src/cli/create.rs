@@ -0,0 +1,412 @@
+//! Project scaffolding for `crewai create crew|flow <name>`.
+//!
+//! Corresponds to the cookiecutter-style templates in
+//! `crewai/cli/templates/`. A crew project defines its agents and tasks in
+//! `src/config/agents.yaml` and `src/config/tasks.yaml`, which `src/main.rs`
+//! loads with [`CrewConfig`](crate::project::CrewConfig); a flow project
+//! wires a typed-state [`Flow`](crate::flow::Flow) whose last step kicks
+//! off such a crew.
+
+use std::path::{Path, PathBuf};
+
+/// Kind of project to scaffold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectKind {
+    /// A crew configured from YAML.
+    Crew,
+    /// A flow that orchestrates a crew.
+    Flow,
+}
+
+impl std::str::FromStr for ProjectKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "crew" => Ok(Self::Crew),
+            "flow" => Ok(Self::Flow),
+            other => Err(anyhow::anyhow!(
+                "Unknown project type '{}' (expected crew or flow)",
+                other
+            )),
+        }
+    }
+}
+
+/// Names derived from the name given on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProjectNames {
+    /// Directory and package name (`my_project`).
+    folder: String,
+    /// Type name prefix (`MyProject`).
+    class: String,
+}
+
+impl ProjectNames {
+    /// Like the Python CLI: lowercase with spaces and dashes as `_` for the
+    /// folder, title case without separators for types.
+    fn new(name: &str) -> Result<Self, anyhow::Error> {
+        let words: Vec<&str> = name
+            .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+            .filter(|w| !w.is_empty())
+            .collect();
+        let valid = |w: &&str| w.chars().all(|c| c.is_ascii_alphanumeric());
+        if words.is_empty()
+            || !words.iter().all(valid)
+            || words[0].starts_with(|c: char| c.is_ascii_digit())
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid project name '{}': use letters, digits, spaces, '-' or '_', \
+                 starting with a letter",
+                name
+            ));
+        }
+        let folder = words.join("_").to_lowercase();
+        let class = words
+            .iter()
+            .map(|w| {
+                let mut chars = w.chars();
+                let first = chars.next().map(|c| c.to_ascii_uppercase());
+                first
+                    .into_iter()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect::<String>()
+            })
+            .collect();
+        Ok(Self { folder, class })
+    }
+
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{crew_builder}}", CREW_BUILDER)
+            .replace("{{folder_name}}", &self.folder)
+            .replace("{{class_name}}", &self.class)
+            .replace("{{crewai_version}}", env!("CARGO_PKG_VERSION"))
+    }
+}
+
+/// Scaffold a crew project named `name` inside `parent`.
+///
+/// Returns the project directory. Fails if it already exists.
+pub fn create_crew(name: &str, parent: &Path) -> Result<PathBuf, anyhow::Error> {
+    create_project(ProjectKind::Crew, name, parent)
+}
+
+/// Scaffold a flow project named `name` inside `parent`.
+///
+/// Returns the project directory. Fails if it already exists.
+pub fn create_flow(name: &str, parent: &Path) -> Result<PathBuf, anyhow::Error> {
+    create_project(ProjectKind::Flow, name, parent)
+}
+
+/// Scaffold a project of the given kind named `name` inside `parent`.
+pub fn create_project(
+    kind: ProjectKind,
+    name: &str,
+    parent: &Path,
+) -> Result<PathBuf, anyhow::Error> {
+    let names = ProjectNames::new(name)?;
+    let root = parent.join(&names.folder);
+    if root.exists() {
+        return Err(anyhow::anyhow!(
+            "Folder {} already exists",
+            root.to_string_lossy()
+        ));
+    }
+
+    let mut manifest = CARGO_TOML.to_string();
+    let mut files = vec![
+        (".gitignore", GITIGNORE),
+        (".env", ENV),
+        ("src/config/agents.yaml", AGENTS_YAML),
+        ("src/config/tasks.yaml", TASKS_YAML),
+    ];
+    match kind {
+        ProjectKind::Crew => {
+            files.push(("README.md", CREW_README));
+            files.push(("src/main.rs", CREW_MAIN));
+        }
+        ProjectKind::Flow => {
+            manifest.push_str(FLOW_DEPENDENCIES);
+            files.push(("README.md", FLOW_README));
+            files.push(("src/main.rs", FLOW_MAIN));
+        }
+    }
+    files.push(("Cargo.toml", &manifest));
+    for (path, template) in files {
+        let path = root.join(path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, names.render(template))?;
+    }
+    Ok(root)
+}
+
+const CARGO_TOML: &str = r#"[package]
+name = "{{folder_name}}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+crewai = "{{crewai_version}}"
+anyhow = "1"
+"#;
+
+/// Flow state is serialized, and the crew runs on a blocking thread of the
+/// flow's runtime.
+const FLOW_DEPENDENCIES: &str = r#"serde = { version = "1", features = ["derive"] }
+serde_json = "1"
+tokio = { version = "1", features = ["rt"] }
+"#;
+
+const GITIGNORE: &str = "/target\n.env\n";
+
+const ENV: &str = "MODEL=gpt-4o-mini\nOPENAI_API_KEY=\n";
+
+const AGENTS_YAML: &str = r#"researcher:
+  role: >
+    Senior Data Researcher
+  goal: >
+    Uncover cutting-edge developments in {topic}
+  backstory: >
+    You're a seasoned researcher with a knack for uncovering the latest
+    developments in {topic}. Known for your ability to find the most relevant
+    information and present it in a clear and concise manner.
+
+reporting_analyst:
+  role: >
+    Reporting Analyst
+  goal: >
+    Create detailed reports based on {topic} data analysis and research findings
+  backstory: >
+    You're a meticulous analyst with a keen eye for detail. You're known for
+    your ability to turn complex data into clear and concise reports, making
+    it easy for others to understand and act on the information you provide.
+"#;
+
+const TASKS_YAML: &str = r#"research_task:
+  description: >
+    Conduct a thorough research about {topic}.
+    Make sure you find any interesting and relevant information given
+    the current year is {current_year}.
+  expected_output: >
+    A list with 10 bullet points of the most relevant information about {topic}
+  agent: researcher
+
+reporting_task:
+  description: >
+    Review the context you got and expand each topic into a full section for a report.
+    Make sure the report is detailed and contains any and all relevant information.
+  expected_output: >
+    A fully fledged report with the main topics, each with a full section of information.
+    Formatted as markdown without code fences.
+  agent: reporting_analyst
+  output_file: report.md
+"#;
+
+/// Crew construction shared by both templates.
+const CREW_BUILDER: &str = r#"/// Build the {{class_name}} crew from `config/agents.yaml` and
+/// `config/tasks.yaml`; agents without an `llm` use `$MODEL`.
+fn {{folder_name}}_crew() -> anyhow::Result<Crew> {
+    let model = std::env::var("MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+    let mut config = CrewConfig::from_yaml(
+        include_str!("config/agents.yaml"),
+        include_str!("config/tasks.yaml"),
+    )?;
+    for (_, agent) in &mut config.agents {
+        agent.llm.get_or_insert_with(|| model.clone());
+    }
+    let mut crew = config.build_crew(None)?;
+    crew.name = Some("{{folder_name}}".to_string());
+    Ok(crew)
+}
+"#;
+
+const CREW_MAIN: &str = r#"//! {{class_name}} crew.
+//!
+//! Agents and tasks are defined in `src/config/`; placeholders such as
+//! `{topic}` are filled from the kickoff inputs.
+//!
+//! `cargo run -- [run]` runs the crew, `cargo run -- test -n 3 -m MODEL`
+//! scores its outputs and `cargo run -- train -n 5 -f FILE` collects your
+//! feedback on them. All take `--input key=value` to override an input.
+
+use crewai::prelude::*;
+use crewai::project::CrewConfig;
+
+{{crew_builder}}
+fn main() -> anyhow::Result<()> {
+    let mut crew = {{folder_name}}_crew()?;
+    crew.before_kickoff_callbacks.push(Box::new(|inputs| {
+        let mut inputs = inputs.unwrap_or_default();
+        inputs
+            .entry("topic".to_string())
+            .or_insert_with(|| "AI LLMs".to_string());
+        inputs
+            .entry("current_year".to_string())
+            .or_insert_with(|| "2025".to_string());
+        Some(inputs)
+    }));
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let report = match args.first().map(String::as_str) {
+        Some("run") => crewai::cli::run_crew(&mut crew, &args[1..])?,
+        Some("test") => crewai::cli::test_crew(&mut crew, &args[1..])?,
+        Some("train") => {
+            let file = crewai::cli::train_crew(&mut crew, &args[1..], &mut crewai::cli::ask_feedback)?;
+            format!("Training data saved to {}", file)
+        }
+        _ => crewai::cli::run_crew(&mut crew, &args)?,
+    };
+    println!("{}", report);
+    Ok(())
+}
+"#;
+
+const FLOW_MAIN: &str = r#"//! {{class_name}} flow.
+//!
+//! `pick_topic` starts the flow and `write_report` runs the crew defined in
+//! `src/config/` on the topic it picked.
+
+use std::collections::HashMap;
+
+use crewai::flow::flow_wrappers::{listen_method_meta, start_method_meta};
+use crewai::flow::{Flow, FlowConditionType, FlowMethodName};
+use crewai::prelude::*;
+use crewai::project::CrewConfig;
+use serde::{Deserialize, Serialize};
+
+/// State shared by the flow's steps.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct {{class_name}}State {
+    id: String,
+    topic: String,
+    report: String,
+}
+
+{{crew_builder}}
+fn {{folder_name}}_flow() -> Flow<{{class_name}}State> {
+    let mut flow = Flow::<{{class_name}}State>::default();
+    flow.name = Some("{{class_name}}Flow".to_string());
+    flow.register_method_meta("pick_topic", &start_method_meta());
+    flow.register_method_meta(
+        "write_report",
+        &listen_method_meta(vec![FlowMethodName::new("pick_topic")], FlowConditionType::OR),
+    );
+
+    flow.register_callback(
+        "pick_topic",
+        Box::new(|state, _| {
+            Box::pin(async move {
+                state.topic = "AI LLMs".to_string();
+                Ok(serde_json::json!(state.topic))
+            })
+        }),
+    );
+    flow.register_callback(
+        "write_report",
+        Box::new(|state, _| {
+            Box::pin(async move {
+                let inputs = HashMap::from([
+                    ("topic".to_string(), state.topic.clone()),
+                    ("current_year".to_string(), "2025".to_string()),
+                ]);
+                let mut crew = {{folder_name}}_crew()?;
+                let output = tokio::task::spawn_blocking(move || crew.kickoff(Some(inputs)))
+                    .await?
+                    .map_err(anyhow::Error::msg)?;
+                state.report = output.raw;
+                Ok(serde_json::json!(state.report))
+            })
+        }),
+    );
+    flow
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut flow = {{folder_name}}_flow();
+    flow.kickoff()?;
+    println!("{}", flow.state.report);
+    Ok(())
+}
+"#;
+
+const CREW_README: &str = r#"# {{class_name}} Crew
+
+A multi-agent crew built with [crewai](https://crates.io/crates/crewai).
+
+## Running
+
+Fill in your model and API key in `.env`, then:
+
+```bash
+set -a; . ./.env; set +a
+cargo run
+```
+
+`cargo run -- test -n 3 -m gpt-4o-mini` runs the crew three times and scores
+each task's output; `cargo run -- train -n 5 -f training_data.json` asks for
+your feedback on every output and saves it as training data.
+
+## Customizing
+
+- `src/config/agents.yaml` defines the agents.
+- `src/config/tasks.yaml` defines the tasks and which agent runs each one.
+- `src/main.rs` builds the crew and sets the default inputs interpolated into
+  `{placeholders}`.
+"#;
+
+const FLOW_README: &str = r#"# {{class_name}} Flow
+
+An event-driven flow built with [crewai](https://crates.io/crates/crewai).
+
+## Running
+
+Fill in your model and API key in `.env`, then:
+
+```bash
+set -a; . ./.env; set +a
+cargo run
+```
+
+## Customizing
+
+- `src/main.rs` registers the flow's steps and its state.
+- `src/config/agents.yaml` and `src/config/tasks.yaml` define the crew the
+  flow runs.
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_names() {
+        let names = ProjectNames::new("Latest AI-development").unwrap();
+        assert_eq!(names.folder, "latest_ai_development");
+        assert_eq!(names.class, "LatestAiDevelopment");
+        assert!(ProjectNames::new("9lives").is_err());
+        assert!(ProjectNames::new("bad/name").is_err());
+    }
+
+    #[test]
+    fn test_create_crew_and_flow_projects() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = create_crew("research crew", dir.path()).unwrap();
+        assert_eq!(root, dir.path().join("research_crew"));
+        let manifest = std::fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"research_crew\""));
+        assert!(manifest.contains(&format!("crewai = \"{}\"", env!("CARGO_PKG_VERSION"))));
+        let main = std::fs::read_to_string(root.join("src/main.rs")).unwrap();
+        assert!(main.contains("fn research_crew_crew()"));
+        assert!(!main.contains("{{"));
+        assert!(root.join("src/config/agents.yaml").exists());
+        assert!(create_crew("research crew", dir.path()).is_err());
+
+        let root = create_flow("poem", dir.path()).unwrap();
+        let main = std::fs::read_to_string(root.join("src/main.rs")).unwrap();
+        assert!(main.contains("struct PoemState"));
+        assert!(main.contains("Flow::<PoemState>::default()"));
+    }
+}
@@ -5,6 +5,30 @@
 //! Provides command-line interface commands for creating, running,
 //! training, and managing CrewAI projects.
 
+mod chat;
+mod create;
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::bench::{BenchOptions, BenchReport};
+use crate::crew::Crew;
+use crate::flow::{Flow, FlowStateModel, PlotFormat};
+use crate::policy::audit::{self, AuditFilter, AuditRecord, POLICY_AUDIT_ENV};
+use crate::policy::PolicyEffect;
+use crate::project::CrewBase;
+use crate::server::a2a_routes::{self, A2AState};
+use crate::server::service::{ServiceDefinition, ServiceTarget};
+use crate::tasks::task_output::TaskOutput;
+use crate::telemetry::flight_recorder::{
+    ExecutionRecord, FlightRecorder, HistoryFilter, HistoryStats, DEFAULT_MAX_EXECUTIONS,
+};
+
+pub use chat::{chat_inputs, CrewChat};
+pub use create::{create_crew, create_flow, create_project, ProjectKind};
+
 /// Available CLI commands.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CliCommand {
@@ -22,6 +46,20 @@ pub enum CliCommand {
     ResetMemories,
     /// Show version information.
     Version,
+    /// Plot a flow (`crewai flow plot`).
+    FlowPlot,
+    /// Generate a service definition for the server (`crewai serve install`).
+    ServeInstall,
+    /// Benchmark the orchestration hot paths (`crewai bench`).
+    Bench,
+    /// Export the A2A agent card as JSON (`crewai agent-card`).
+    AgentCard,
+    /// Query the flight recorder's execution history (`crewai history`).
+    History,
+    /// Inspect the persistent policy audit log (`crewai policy-audit`).
+    PolicyAudit,
+    /// Chat with the crew interactively (`crewai chat`).
+    Chat,
 }
 
 impl std::fmt::Display for CliCommand {
@@ -34,6 +72,13 @@ impl std::fmt::Display for CliCommand {
             Self::Replay => write!(f, "replay"),
             Self::ResetMemories => write!(f, "reset-memories"),
             Self::Version => write!(f, "version"),
+            Self::FlowPlot => write!(f, "flow plot"),
+            Self::ServeInstall => write!(f, "serve install"),
+            Self::Bench => write!(f, "bench"),
+            Self::AgentCard => write!(f, "agent-card"),
+            Self::History => write!(f, "history"),
+            Self::PolicyAudit => write!(f, "policy-audit"),
+            Self::Chat => write!(f, "chat"),
         }
     }
 }
@@ -48,28 +93,251 @@ pub fn parse_command(cmd: &str) -> Option<CliCommand> {
         "replay" => Some(CliCommand::Replay),
         "reset-memories" | "reset_memories" => Some(CliCommand::ResetMemories),
         "version" | "--version" | "-v" => Some(CliCommand::Version),
+        "flow plot" | "flow-plot" => Some(CliCommand::FlowPlot),
+        "serve install" | "serve-install" => Some(CliCommand::ServeInstall),
+        "bench" => Some(CliCommand::Bench),
+        "agent-card" | "agent_card" => Some(CliCommand::AgentCard),
+        "history" => Some(CliCommand::History),
+        "policy-audit" | "policy_audit" => Some(CliCommand::PolicyAudit),
+        "chat" => Some(CliCommand::Chat),
         _ => None,
     }
 }
 
-/// CLI command to create a new CrewAI project.
-pub fn create_crew(_name: &str) {
-    // Stub: project scaffolding
+/// CLI command to scaffold a project:
+/// `crewai create crew|flow <name> [--output DIR]`.
+///
+/// Creates the project directory inside `--output` (default: the current
+/// directory) and returns its path.
+pub fn create(args: &[String]) -> Result<String, anyhow::Error> {
+    let mut output = std::path::PathBuf::from(".");
+    let mut positional = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--output" | "-o" => output = value()?.into(),
+            other if other.starts_with('-') => {
+                return Err(anyhow::anyhow!("Unknown argument for create: {}", other))
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+    let [kind, name @ ..] = positional.as_slice() else {
+        return Err(anyhow::anyhow!("Usage: create crew|flow <name>"));
+    };
+    if name.is_empty() {
+        return Err(anyhow::anyhow!("Usage: create crew|flow <name>"));
+    }
+    let root = create_project(kind.parse()?, &name.join(" "), &output)?;
+    Ok(root.to_string_lossy().to_string())
+}
+
+/// The crew of the project at `root`, built from `src/config/agents.yaml`
+/// and `src/config/tasks.yaml`; agents without an `llm` use `$MODEL`.
+pub fn local_crew(root: impl AsRef<Path>) -> Result<Crew, anyhow::Error> {
+    let mut config = CrewBase::new().load_config(root.as_ref().join("src"))?;
+    if let Ok(model) = std::env::var("MODEL") {
+        for (_, agent) in &mut config.agents {
+            agent.llm.get_or_insert_with(|| model.clone());
+        }
+    }
+    let mut crew = config.build_crew(None)?;
+    if let Some(name) = std::fs::canonicalize(root.as_ref()).ok().and_then(|root| {
+        root.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    }) {
+        crew.name = Some(name);
+    }
+    Ok(crew)
+}
+
+/// CLI command to run a crew: `crewai run [--input key=value]...`.
+///
+/// Returns the crew's final output.
+pub fn run_crew(crew: &mut Crew, args: &[String]) -> Result<String, anyhow::Error> {
+    let mut inputs = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || flag_value(flag, inline.clone(), &mut args);
+        match flag {
+            "--input" | "-i" => insert_input(&mut inputs, &value()?)?,
+            other => return Err(anyhow::anyhow!("Unknown argument for run: {}", other)),
+        }
+    }
+    let output = crew
+        .kickoff((!inputs.is_empty()).then_some(inputs))
+        .map_err(anyhow::Error::msg)?;
+    Ok(output.raw)
+}
+
+/// CLI command to test a crew:
+/// `crewai test [-n ITERATIONS] [-m MODEL] [-o REPORT] [--input key=value]...`.
+///
+/// Runs the crew `-n` times (default 3) and returns a table of the scores
+/// `-m` (default `gpt-4o-mini`) gave each task's output. `-o` also saves the
+/// report as JSON.
+pub fn test_crew(crew: &mut Crew, args: &[String]) -> Result<String, anyhow::Error> {
+    let mut n_iterations = 3;
+    let mut model = "gpt-4o-mini".to_string();
+    let mut report_path = None;
+    let mut inputs = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || flag_value(flag, inline.clone(), &mut args);
+        match flag {
+            "--n-iterations" | "-n" => n_iterations = value()?.parse()?,
+            "--model" | "-m" => model = value()?,
+            "--output" | "-o" => report_path = Some(value()?),
+            "--input" | "-i" => insert_input(&mut inputs, &value()?)?,
+            other => return Err(anyhow::anyhow!("Unknown argument for test: {}", other)),
+        }
+    }
+    let report = crew
+        .test(n_iterations, &model, (!inputs.is_empty()).then_some(inputs))
+        .map_err(anyhow::Error::msg)?;
+    if let Some(path) = &report_path {
+        std::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+    }
+    Ok(report.to_string())
+}
+
+/// CLI command to train a crew:
+/// `crewai train [-n ITERATIONS] [-f FILE] [--input key=value]...`.
+///
+/// Runs the crew `-n` times (default 5), asks `feedback` for human feedback
+/// on every task output and appends both to the training data in `-f`
+/// (default `training_data.json`), whose path is returned.
+pub fn train_crew(
+    crew: &mut Crew,
+    args: &[String],
+    feedback: &mut dyn FnMut(&TaskOutput) -> String,
+) -> Result<String, anyhow::Error> {
+    let mut n_iterations = 5;
+    let mut filename = "training_data.json".to_string();
+    let mut inputs = HashMap::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || flag_value(flag, inline.clone(), &mut args);
+        match flag {
+            "--n-iterations" | "-n" => n_iterations = value()?.parse()?,
+            "--filename" | "-f" => filename = value()?,
+            "--input" | "-i" => insert_input(&mut inputs, &value()?)?,
+            other => return Err(anyhow::anyhow!("Unknown argument for train: {}", other)),
+        }
+    }
+    if !filename.ends_with(".json") {
+        return Err(anyhow::anyhow!(
+            "Training data file must be a .json file: {}",
+            filename
+        ));
+    }
+    crew.train(
+        n_iterations,
+        &filename,
+        (!inputs.is_empty()).then_some(inputs),
+        feedback,
+    )
+    .map_err(anyhow::Error::msg)?;
+    Ok(filename)
+}
+
+/// Show a task output on stdout and read the human feedback on it from
+/// stdin, for [`train_crew`].
+pub fn ask_feedback(output: &TaskOutput) -> String {
+    println!(
+        "\n## {} ({})\n{}\n",
+        output.description, output.agent, output.raw
+    );
+    print!("Feedback on this output (empty to accept): ");
+    let _ = std::io::stdout().flush();
+    let mut line = String::new();
+    let _ = std::io::stdin().lock().read_line(&mut line);
+    line.trim().to_string()
+}
+
+/// CLI command to chat with a crew: `crewai chat [-m MODEL]`.
+///
+/// Reads user messages from `input` until end of input or `exit`, and
+/// writes the replies of a chat LLM (`-m`, default `$MODEL` or
+/// `gpt-4o-mini`) to `output`. The LLM kicks off the crew once it has
+/// gathered the crew's inputs; see [`CrewChat`].
+pub fn chat_crew(
+    crew: &mut Crew,
+    args: &[String],
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), anyhow::Error> {
+    let mut model = std::env::var("MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = split_flag(arg);
+        let mut value = || flag_value(flag, inline.clone(), &mut args);
+        match flag {
+            "--model" | "-m" => model = value()?,
+            other => return Err(anyhow::anyhow!("Unknown argument for chat: {}", other)),
+        }
+    }
+
+    let mut chat = CrewChat::new(crew, crate::llm::LLM::new(model));
+    writeln!(output, "{}\nType 'exit' to end the chat.", chat.greeting())?;
+    loop {
+        write!(output, "\n> ")?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            break;
+        }
+        match line.trim() {
+            "" => continue,
+            "exit" | "quit" => break,
+            message => match chat.respond(message, output) {
+                Ok(reply) => writeln!(output, "{}", reply)?,
+                Err(e) => writeln!(output, "Error: {}", e)?,
+            },
+        }
+    }
+    Ok(())
 }
 
-/// CLI command to run a CrewAI project.
-pub fn run_crew() {
-    // Stub: crew execution from CLI
+/// A `--flag=value` argument split into the flag and its inline value.
+fn split_flag(arg: &str) -> (&str, Option<String>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with('-') => (flag, Some(value.to_string())),
+        _ => (arg, None),
+    }
 }
 
-/// CLI command to train a crew.
-pub fn train_crew(_iterations: u32) {
-    // Stub: training mode
+/// The inline value of `flag`, or else the next argument.
+fn flag_value<'a>(
+    flag: &str,
+    inline: Option<String>,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<String, anyhow::Error> {
+    inline
+        .or_else(|| args.next().cloned())
+        .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
 }
 
-/// CLI command to test a crew.
-pub fn test_crew(_iterations: u32) {
-    // Stub: testing mode
+/// Add a `key=value` kickoff input.
+fn insert_input(inputs: &mut HashMap<String, String>, input: &str) -> Result<(), anyhow::Error> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Inputs are key=value: {}", input))?;
+    inputs.insert(key.trim().to_string(), value.to_string());
+    Ok(())
 }
 
 /// CLI command to replay a task from a specific kickoff.
@@ -81,3 +349,652 @@ pub fn replay_task(_task_id: &str) {
 pub fn reset_memories(_all: bool) {
     // Stub: memory reset
 }
+
+/// CLI command to plot a flow: `crewai flow plot [--format html|mermaid|dot] [--output NAME]`.
+///
+/// Flows are compiled into the project binary, so the project's `main`
+/// passes its flow and the remaining arguments here. Returns the path of
+/// the written file.
+pub fn plot_flow<S: FlowStateModel>(
+    flow: &Flow<S>,
+    args: &[String],
+) -> Result<String, anyhow::Error> {
+    let mut format = PlotFormat::default();
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--format" | "-f" => format = value()?.parse()?,
+            "--output" | "-o" => output = Some(value()?),
+            other => return Err(anyhow::anyhow!("Unknown argument for flow plot: {}", other)),
+        }
+    }
+    flow.plot_as(output.as_deref(), format)
+}
+
+/// CLI command to generate a service definition for the server binary:
+/// `crewai serve install [--target systemd|windows] [--name NAME]
+/// [--output DIR] [--exec PATH] [--port PORT] [--user USER]
+/// [--shutdown-timeout SECS]`.
+///
+/// Writes a systemd unit or a WinSW configuration (defaulting to the
+/// current platform) for the running executable and returns its path; it
+/// does not register the service, which needs `systemctl enable` or
+/// `WinSW install` with administrator rights.
+pub fn serve_install(args: &[String]) -> Result<String, anyhow::Error> {
+    let mut target = ServiceTarget::native();
+    let mut definition = ServiceDefinition::new("crewai")?;
+    let mut output = std::path::PathBuf::from(".");
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--target" | "-t" => target = value()?.parse().map_err(anyhow::Error::msg)?,
+            "--name" | "-n" => definition.name = value()?,
+            "--output" | "-o" => output = value()?.into(),
+            "--exec" => definition.executable = value()?.into(),
+            "--port" | "-p" => definition.port = value()?.parse()?,
+            "--user" | "-u" => definition.user = Some(value()?),
+            "--shutdown-timeout" => {
+                definition.shutdown_timeout = Duration::from_secs(value()?.parse()?)
+            }
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Unknown argument for serve install: {}",
+                    other
+                ))
+            }
+        }
+    }
+    let path = output.join(definition.file_name(target));
+    std::fs::write(&path, definition.render(target))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// CLI command to benchmark the orchestration hot paths:
+/// `crewai bench [--filter NAME] [--time SECS] [--baseline PATH]
+/// [--threshold PCT] [--save PATH]`.
+///
+/// Runs the [`crate::bench`] workloads and returns a results table. With
+/// `--baseline`, a workload whose median is more than `--threshold` percent
+/// (default 10) slower than the baseline makes the command fail. `--save`
+/// writes the report as JSON, e.g. to refresh `benches/baselines/`.
+pub fn bench(args: &[String]) -> Result<String, anyhow::Error> {
+    let mut options = BenchOptions::default();
+    let mut baseline = None;
+    let mut save = None;
+    let mut threshold = 10.0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--filter" | "-f" => options.filter = Some(value()?),
+            "--time" | "-t" => {
+                options.measurement_time = Duration::from_secs_f64(value()?.parse()?)
+            }
+            "--baseline" | "-b" => baseline = Some(BenchReport::load(value()?)?),
+            "--threshold" => threshold = value()?.parse()?,
+            "--save" | "-o" => save = Some(value()?),
+            other => return Err(anyhow::anyhow!("Unknown argument for bench: {}", other)),
+        }
+    }
+
+    let report = crate::bench::run(&options);
+    if let Some(path) = &save {
+        report.save(path)?;
+    }
+    let table = crate::bench::format_report(&report, baseline.as_ref());
+    let regressions = baseline
+        .as_ref()
+        .map(|b| crate::bench::compare(&report, b, threshold / 100.0))
+        .unwrap_or_default();
+    if regressions.is_empty() {
+        return Ok(table);
+    }
+    let names: Vec<String> = regressions
+        .iter()
+        .map(|r| format!("{} ({:+.1}%)", r.name, r.change() * 100.0))
+        .collect();
+    Err(anyhow::anyhow!(
+        "{}\n{} benchmark(s) regressed beyond {}%: {}",
+        table,
+        regressions.len(),
+        threshold,
+        names.join(", ")
+    ))
+}
+
+/// CLI command to export the A2A agent card served by `state`:
+/// `crewai agent-card [--url URL] [--output PATH]`.
+///
+/// Returns the card as JSON, with skills derived from the crews registered
+/// in `state`; `--url` overrides the advertised endpoint and `--output`
+/// also writes the card to a file for external catalogs.
+pub fn agent_card(state: &A2AState, args: &[String]) -> Result<String, anyhow::Error> {
+    let mut state = state.clone();
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--url" | "-u" => state.url = value()?,
+            "--output" | "-o" => output = Some(value()?),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Unknown argument for agent-card: {}",
+                    other
+                ))
+            }
+        }
+    }
+    let json = a2a_routes::agent_card(&state).to_json();
+    if let Some(path) = output {
+        std::fs::write(path, &json)?;
+    }
+    Ok(json)
+}
+
+/// CLI command to query the flight recorder:
+/// `crewai history [--db PATH] [--crew NAME] [--fingerprint FP] [--status STATUS]
+/// [--model MODEL] [--since TIME] [--limit N] [--stats] [--json]`.
+///
+/// Lists the most recent executions (20 unless `--limit` says otherwise),
+/// newest first, or with `--stats` aggregates over all matching ones:
+/// success rate, mean and p95 duration, tokens, cost, models and the most
+/// frequent errors. `--since` takes an RFC 3339 time or an age such as
+/// `30m`, `24h` or `7d`. `--db` defaults to the recorder's configured
+/// database.
+pub fn history(args: &[String]) -> Result<String, anyhow::Error> {
+    let mut db = None;
+    let mut filter = HistoryFilter {
+        limit: Some(20),
+        ..Default::default()
+    };
+    let mut stats = false;
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--db" => db = Some(value()?),
+            "--crew" | "-c" => filter.crew = Some(value()?),
+            "--fingerprint" | "-f" => filter.fingerprint = Some(value()?),
+            "--status" | "-s" => filter.status = Some(value()?.parse()?),
+            "--model" | "-m" => filter.model = Some(value()?),
+            "--since" => filter.since = Some(parse_since(&value()?)?),
+            "--limit" | "-n" => filter.limit = Some(value()?.parse()?),
+            "--stats" => stats = true,
+            "--json" => json = true,
+            other => return Err(anyhow::anyhow!("Unknown argument for history: {}", other)),
+        }
+    }
+
+    let recorder = match db {
+        Some(path) => FlightRecorder::open(path, DEFAULT_MAX_EXECUTIONS)?,
+        None => FlightRecorder::from_env()?.ok_or_else(|| {
+            anyhow::anyhow!("The flight recorder is disabled; pass --db to read a database")
+        })?,
+    };
+    if stats {
+        filter.limit = None;
+        let stats = recorder.stats(&filter)?;
+        return Ok(if json {
+            serde_json::to_string_pretty(&stats)?
+        } else {
+            format_history_stats(&stats)
+        });
+    }
+    let records = recorder.query(&filter)?;
+    Ok(if json {
+        serde_json::to_string_pretty(&records)?
+    } else {
+        format_history(&records)
+    })
+}
+
+/// CLI command to inspect the policy audit log:
+/// `crewai policy-audit [--log PATH] [--agent ID] [--rule NAME] [--since TIME]
+/// [--limit N] [--all] [--json]`.
+///
+/// Shows the most recent denials (20 unless `--limit` says otherwise),
+/// oldest first like `tail`; `--all` includes allowed requests. `--log`
+/// defaults to `CREWAI_POLICY_AUDIT` and is read as JSON Lines for
+/// `.jsonl`/`.json` files and as SQLite otherwise.
+pub fn policy_audit(args: &[String]) -> Result<String, anyhow::Error> {
+    let mut log = None;
+    let mut filter = AuditFilter {
+        effect: Some(PolicyEffect::Deny),
+        limit: Some(20),
+        ..Default::default()
+    };
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag, Some(value.to_string())),
+            None => (arg.as_str(), None),
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next().cloned())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", flag))
+        };
+        match flag {
+            "--log" => log = Some(value()?),
+            "--agent" | "-a" => filter.agent_id = Some(value()?),
+            "--rule" | "-r" => filter.rule_name = Some(value()?),
+            "--since" => filter.since = Some(parse_since(&value()?)?),
+            "--limit" | "-n" => filter.limit = Some(value()?.parse()?),
+            "--all" => filter.effect = None,
+            "--json" => json = true,
+            other => {
+                return Err(anyhow::anyhow!(
+                    "Unknown argument for policy-audit: {}",
+                    other
+                ))
+            }
+        }
+    }
+
+    let path = log
+        .or_else(|| std::env::var(POLICY_AUDIT_ENV).ok())
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No audit log configured; set {} or pass --log",
+                POLICY_AUDIT_ENV
+            )
+        })?;
+    let mut records = audit::open_sink(&path)?.query(&filter)?;
+    records.reverse();
+    Ok(if json {
+        serde_json::to_string_pretty(&records)?
+    } else {
+        format_audit(&records)
+    })
+}
+
+fn format_audit(records: &[AuditRecord]) -> String {
+    if records.is_empty() {
+        return "No matching policy decisions.\n".to_string();
+    }
+    let mut out = String::new();
+    for record in records {
+        let effect = match (&record.effect, record.enforced) {
+            (PolicyEffect::Allow, _) => "ALLOW",
+            (PolicyEffect::Deny, true) => "DENY",
+            (PolicyEffect::Deny, false) => "DENY (audit only)",
+        };
+        out.push_str(&format!(
+            "{} {} agent={} action={} resource={} rule={}\n    {}\n",
+            record.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            effect,
+            record.agent_id,
+            record.action,
+            record.resource,
+            record.rule_name.as_deref().unwrap_or("-"),
+            record.reason,
+        ));
+    }
+    out
+}
+
+/// An RFC 3339 time, or an age (`30m`, `24h`, `7d`) before now.
+fn parse_since(value: &str) -> Result<chrono::DateTime<chrono::Utc>, anyhow::Error> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&chrono::Utc));
+    }
+    let split = value.len().saturating_sub(1);
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid --since: {}", value))?;
+    let age = match unit {
+        "s" => chrono::Duration::seconds(amount),
+        "m" => chrono::Duration::minutes(amount),
+        "h" => chrono::Duration::hours(amount),
+        "d" => chrono::Duration::days(amount),
+        _ => return Err(anyhow::anyhow!("Invalid --since: {}", value)),
+    };
+    Ok(chrono::Utc::now() - age)
+}
+
+fn format_history(records: &[ExecutionRecord]) -> String {
+    if records.is_empty() {
+        return "No executions recorded.\n".to_string();
+    }
+    let mut out = format!(
+        "{:<20} {:<20} {:<9} {:>9} {:>8} {:>9}  {}\n",
+        "STARTED", "CREW", "STATUS", "DURATION", "TOKENS", "COST", "MODELS"
+    );
+    for record in records {
+        out.push_str(&format!(
+            "{:<20} {:<20} {:<9} {:>8.1}s {:>8} {:>9}  {}\n",
+            record.started_at.format("%Y-%m-%d %H:%M:%S"),
+            record.crew.as_deref().unwrap_or("-"),
+            record.status,
+            record.duration_ms as f64 / 1000.0,
+            record.usage.total_tokens,
+            record.cost.map_or("-".to_string(), |c| format!("{:.4}", c)),
+            record.models.join(","),
+        ));
+        if let Some(error) = &record.error {
+            out.push_str(&format!("    error: {}\n", error));
+        }
+    }
+    out
+}
+
+fn format_history_stats(stats: &HistoryStats) -> String {
+    let rate = if stats.executions == 0 {
+        0.0
+    } else {
+        stats.completed as f64 * 100.0 / stats.executions as f64
+    };
+    let mut out = format!(
+        "Executions: {} ({} completed, {} failed, {:.1}% success)\n\
+         Duration:   mean {:.1}s, p95 {:.1}s\n\
+         Tokens:     {}\n\
+         Cost:       {:.4}\n",
+        stats.executions,
+        stats.completed,
+        stats.failed,
+        rate,
+        stats.avg_duration_ms as f64 / 1000.0,
+        stats.p95_duration_ms as f64 / 1000.0,
+        stats.total_tokens,
+        stats.total_cost,
+    );
+    if !stats.models.is_empty() {
+        out.push_str("Models:\n");
+        for (model, count) in &stats.models {
+            out.push_str(&format!("  {:<30} {}\n", model, count));
+        }
+    }
+    if !stats.top_errors.is_empty() {
+        out.push_str("Top errors:\n");
+        for (error, count) in &stats.top_errors {
+            out.push_str(&format!("  {:>4}  {}\n", count, error));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task;
+
+    fn echo_crew() -> Crew {
+        let mut task = Task::new("Summarize {topic}".to_string(), "A summary".to_string());
+        task.agent = Some("writer".to_string());
+        task.set_agent_executor(|prompt: &str, _: Option<&str>, _: &[String]| {
+            let topic = if prompt.contains("Rust") { "Rust" } else { "?" };
+            Ok((format!("All about {}", topic), Vec::new()))
+        });
+        Crew::new(vec![task], vec!["writer".to_string()])
+    }
+
+    #[test]
+    fn test_run_and_train_crew() {
+        let mut crew = echo_crew();
+        let args: Vec<String> = vec!["--input=topic=Rust".into()];
+        assert_eq!(run_crew(&mut crew, &args).unwrap(), "All about Rust");
+        assert!(run_crew(&mut crew, &["--input".to_string(), "topic".to_string()]).is_err());
+        assert!(test_crew(&mut crew, &["-n".to_string(), "many".to_string()]).is_err());
+        let args = vec!["-f".to_string(), "notes.txt".to_string()];
+        assert!(train_crew(&mut crew, &args, &mut |_: &TaskOutput| String::new()).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir
+            .path()
+            .join("feedback.json")
+            .to_string_lossy()
+            .to_string();
+        let args: Vec<String> = ["-n", "2", "-f", &file, "-i", "topic=Rust"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let mut asked = 0;
+        let mut feedback = |output: &TaskOutput| {
+            asked += 1;
+            format!("Longer than '{}'", output.raw)
+        };
+        assert_eq!(train_crew(&mut crew, &args, &mut feedback).unwrap(), file);
+        assert_eq!(asked, 2);
+
+        let data: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        let entries = data["writer"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1]["iteration"], 1);
+        assert_eq!(entries[1]["initial_output"], "All about Rust");
+        assert_eq!(entries[1]["human_feedback"], "Longer than 'All about Rust'");
+    }
+
+    #[test]
+    fn test_plot_flow_writes_requested_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let output = dir.path().join("plot").to_string_lossy().to_string();
+        let mut flow = Flow::with_name("PlotFlow");
+        flow.register_method_meta("begin", &crate::flow::flow_wrappers::start_method_meta());
+
+        let args: Vec<String> = vec!["--format=mermaid".into(), "-o".into(), output.clone()];
+        let path = plot_flow(&flow, &args).unwrap();
+        assert_eq!(path, format!("{}.mmd", output));
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("title: PlotFlow"));
+
+        assert!(plot_flow(&flow, &["--format".to_string()]).is_err());
+        assert!(plot_flow(&flow, &["--format".to_string(), "svg".to_string()]).is_err());
+        assert_eq!(parse_command("flow plot"), Some(CliCommand::FlowPlot));
+    }
+
+    #[test]
+    fn test_serve_install_writes_systemd_unit() {
+        let dir = tempfile::tempdir().unwrap();
+        let args: Vec<String> = [
+            "--target=systemd",
+            "--name",
+            "crews",
+            "--port=9090",
+            "-o",
+            dir.path().to_str().unwrap(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let path = serve_install(&args).unwrap();
+        assert!(path.ends_with("crews.service"));
+        let unit = std::fs::read_to_string(path).unwrap();
+        assert!(unit.contains("Environment=PORT=9090"));
+        assert!(serve_install(&["--port".to_string()]).is_err());
+        assert_eq!(
+            parse_command("serve install"),
+            Some(CliCommand::ServeInstall)
+        );
+    }
+
+    #[test]
+    fn test_agent_card_exports_registered_crews() {
+        let state = A2AState::new();
+        state.crews.register("review", || {
+            let mut task = crate::task::Task::new("Review {message}".into(), "Notes".into());
+            task.tools = vec!["linter".to_string()];
+            crate::crew::Crew::new(vec![task], vec!["reviewer".to_string()])
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("card.json");
+        let args: Vec<String> = vec![
+            "--url=https://agents.example/a2a".into(),
+            "-o".into(),
+            path.to_string_lossy().to_string(),
+        ];
+        let json = agent_card(&state, &args).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), json);
+
+        let card: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(card["url"], "https://agents.example/a2a");
+        let review = card["skills"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["id"] == "review")
+            .unwrap();
+        assert_eq!(
+            review["description"],
+            "Delegate to the 'review' crew (reviewer)"
+        );
+        assert_eq!(review["tags"], serde_json::json!(["crew", "tool:linter"]));
+        assert!(agent_card(&state, &["--bogus".to_string()]).is_err());
+        assert_eq!(parse_command("agent-card"), Some(CliCommand::AgentCard));
+    }
+
+    #[test]
+    fn test_history_lists_and_aggregates_recorded_crews() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("history.db");
+        let recorder = FlightRecorder::open(&db, 10).unwrap();
+        crate::telemetry::flight_recorder::install(Some(recorder));
+
+        let mut task = crate::task::Task::new("Summarize".into(), "Summary".into());
+        task.agent = Some("writer".to_string());
+        task.set_agent_executor(|_: &str, _: Option<&str>, _: &[String]| {
+            Ok(("Summary".to_string(), Vec::new()))
+        });
+        let mut ok = crate::crew::Crew::new(vec![task], vec!["writer".to_string()]);
+        ok.name = Some("history-cli-ok".to_string());
+        ok.kickoff(None).unwrap();
+        let mut failing = crate::crew::Crew::new(vec![], vec![]);
+        failing.name = Some("history-cli-failing".to_string());
+        assert!(failing.kickoff(None).is_err());
+        crate::telemetry::flight_recorder::install(None);
+
+        let db = format!("--db={}", db.display());
+        let table = history(std::slice::from_ref(&db)).unwrap();
+        assert!(table.starts_with("STARTED"));
+        assert!(table.contains("history-cli-ok"));
+        assert!(table.contains("    error: "));
+
+        // Crews of concurrently running tests may be recorded too.
+        let args = [
+            db.clone(),
+            "--status".into(),
+            "failed".into(),
+            "--crew=history-cli-failing".into(),
+            "--json".into(),
+        ];
+        let failed: serde_json::Value = serde_json::from_str(&history(&args).unwrap()).unwrap();
+        assert_eq!(failed.as_array().unwrap().len(), 1);
+        assert_eq!(failed[0]["tasks"], serde_json::json!([]));
+
+        let args = [
+            db.clone(),
+            "--crew=history-cli-ok".into(),
+            "--since=1h".into(),
+            "--stats".into(),
+        ];
+        let stats = history(&args).unwrap();
+        assert!(stats.starts_with("Executions: 1 (1 completed, 0 failed, 100.0% success)"));
+        let args = [db.clone(), "--crew=history-cli-ok".into(), "--json".into()];
+        let ok: serde_json::Value = serde_json::from_str(&history(&args).unwrap()).unwrap();
+        assert_eq!(ok[0]["tasks"][0]["agent"], "writer");
+        assert_eq!(ok[0]["tasks"][0]["status"], "completed");
+        assert!(history(&[db, "--since=soon".into()]).is_err());
+        assert_eq!(parse_command("history"), Some(CliCommand::History));
+    }
+
+    #[test]
+    fn test_policy_audit_tails_denials() {
+        use crate::policy::{
+            PolicyAction, PolicyEngine, PolicyPrincipal, PolicyResource, PolicyRule,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.db");
+        let mut engine = PolicyEngine::new();
+        engine.add_audit_sink(audit::open_sink(&path).unwrap());
+        engine.add_rule(PolicyRule {
+            name: "no_shell".to_string(),
+            description: "Agents may not run shell commands".to_string(),
+            effect: PolicyEffect::Deny,
+            principal: PolicyPrincipal::All,
+            action: PolicyAction::ToolCall("shell".to_string()),
+            resource: PolicyResource::Any,
+            conditions: vec![],
+            priority: 10,
+        });
+        for tool in ["search", "shell", "shell"] {
+            let _ = engine.check_tool_call("analyst", tool, &serde_json::json!({}));
+        }
+
+        let log = format!("--log={}", path.display());
+        let tail = policy_audit(std::slice::from_ref(&log)).unwrap();
+        assert_eq!(tail.matches("DENY agent=analyst").count(), 2, "{}", tail);
+        assert!(tail.contains("rule=no_shell"));
+        assert!(!tail.contains("ALLOW"));
+
+        let args = [
+            log.clone(),
+            "--all".into(),
+            "-n".into(),
+            "2".into(),
+            "--json".into(),
+        ];
+        let records: serde_json::Value =
+            serde_json::from_str(&policy_audit(&args).unwrap()).unwrap();
+        assert_eq!(records.as_array().unwrap().len(), 2);
+        assert_eq!(records[1]["effect"], "deny");
+        assert!(policy_audit(&[log, "--rule=other".into()])
+            .unwrap()
+            .starts_with("No matching"));
+        assert_eq!(parse_command("policy-audit"), Some(CliCommand::PolicyAudit));
+    }
+}
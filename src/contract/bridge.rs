@@ -27,6 +27,9 @@ impl From<StepStatus> for V1StepStatus {
             StepStatus::Completed => V1StepStatus::Completed,
             StepStatus::Failed => V1StepStatus::Failed,
             StepStatus::Skipped => V1StepStatus::Skipped,
+            // V1 predates cancellation and retries.
+            StepStatus::Cancelled => V1StepStatus::Failed,
+            StepStatus::Retrying => V1StepStatus::Running,
         }
     }
 }
@@ -85,6 +88,7 @@ impl From<V1UnifiedStep> for UnifiedStep {
             reasoning: v.reasoning,
             confidence: v.confidence,
             alternatives: v.alternatives,
+            attempts: 0,
         }
     }
 }
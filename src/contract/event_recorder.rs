@@ -75,7 +75,9 @@ impl ContractRecorder {
         };
 
         let mut step = UnifiedStep::new(&execution_id, &step_type, task_name, sequence);
-        step.mark_running();
+        if let Err(e) = step.mark_running() {
+            log::warn!("ContractRecorder: {}", e);
+        }
 
         let step_id = step.step_id.clone();
         self.task_to_step
@@ -103,7 +105,9 @@ impl ContractRecorder {
     ) {
         if let Some(step_id) = self.task_to_step.get(task_id).cloned() {
             if let Some(step) = self.steps.get_mut(&step_id) {
-                step.mark_completed(output.clone());
+                if let Err(e) = step.mark_completed(output.clone()) {
+                    log::warn!("ContractRecorder: {}", e);
+                }
                 step.reasoning = reasoning;
                 step.confidence = confidence;
                 step.alternatives = alternatives;
@@ -123,7 +127,9 @@ impl ContractRecorder {
     pub fn on_task_failed(&mut self, task_id: &str, error: &str) {
         if let Some(step_id) = self.task_to_step.get(task_id).cloned() {
             if let Some(step) = self.steps.get_mut(&step_id) {
-                step.mark_failed(error);
+                if let Err(e) = step.mark_failed(error) {
+                    log::warn!("ContractRecorder: {}", e);
+                }
 
                 let exec_id = step.execution_id.clone();
                 if let Some(exec) = self.executions.get_mut(&exec_id) {
@@ -196,6 +202,7 @@ pub fn shared_recorder() -> Arc<RwLock<ContractRecorder>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::contract::types::StepStatus;
 
     #[test]
     fn test_recorder_crew_lifecycle() {
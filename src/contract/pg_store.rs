@@ -162,7 +162,7 @@ mod inner {
             status: StepStatus,
         ) -> Result<(), PgStoreError> {
             let now = chrono::Utc::now();
-            let finished = matches!(status, StepStatus::Completed | StepStatus::Failed);
+            let finished = status.is_terminal();
 
             sqlx::query(
                 r#"
@@ -190,6 +190,8 @@ mod inner {
             StepStatus::Completed => "completed",
             StepStatus::Failed => "failed",
             StepStatus::Skipped => "skipped",
+            StepStatus::Cancelled => "cancelled",
+            StepStatus::Retrying => "retrying",
         }
     }
 }
@@ -36,14 +36,13 @@ use crate::hooks::lifecycle::HookRegistry;
 /// struct EchoHandler;
 /// impl StepHandler for EchoHandler {
 ///     fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-///         step.mark_running();
 ///         bb.put_typed(
 ///             format!("out:{}", step.sequence),
 ///             step.input.clone(),
 ///             "echo",
 ///             &step.step_type,
 ///         );
-///         step.mark_completed(step.input.clone());
+///         step.mark_completed(step.input.clone())?;
 ///         Ok(())
 ///     }
 ///     fn domain(&self) -> StepDomain { StepDomain::Crew }
@@ -116,7 +115,7 @@ impl Pipeline {
 
         for i in 0..execution.steps.len() {
             let step = &execution.steps[i];
-            if step.status != StepStatus::Pending {
+            if !matches!(step.status(), StepStatus::Pending | StepStatus::Retrying) {
                 continue;
             }
 
@@ -195,6 +194,8 @@ impl std::fmt::Debug for Pipeline {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::contract::router::StepDomain;
+    use crate::contract::types::UnifiedStep;
     use serde_json::Value;
 
     // Simple handler that writes typed data to blackboard
@@ -202,8 +203,6 @@ mod tests {
 
     impl StepHandler for TypedWriteHandler {
         fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-            step.mark_running();
-
             // Read input from blackboard if available
             let input_key = format!("in:{}", step.sequence);
             let context = bb.get_typed::<String>(&input_key).cloned();
@@ -213,7 +212,7 @@ mod tests {
             let output = format!("Processed by {} (context: {:?})", step.name, context,);
             bb.put_typed(output_key, output.clone(), &step.step_type, &step.step_type);
 
-            step.mark_completed(serde_json::json!({"output": output}));
+            step.mark_completed(serde_json::json!({"output": output}))?;
             Ok(())
         }
 
@@ -226,7 +225,6 @@ mod tests {
 
     impl StepHandler for OcHandler {
         fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-            step.mark_running();
             let key = format!("oc:{}", step.sequence);
             bb.put_typed(
                 key,
@@ -234,7 +232,7 @@ mod tests {
                 "oc",
                 &step.step_type,
             );
-            step.mark_completed(Value::String("done".into()));
+            step.mark_completed(Value::String("done".into()))?;
             Ok(())
         }
 
@@ -349,8 +347,7 @@ mod tests {
         struct FailingHandler;
         impl StepHandler for FailingHandler {
             fn handle(&self, step: &mut UnifiedStep, _bb: &mut Blackboard) -> StepResult {
-                step.mark_running();
-                step.mark_failed("boom");
+                step.mark_failed("boom")?;
                 Err("boom".into())
             }
             fn domain(&self) -> StepDomain {
@@ -386,7 +383,9 @@ mod tests {
 
         let mut exec = UnifiedExecution::new("skip-test");
         let mut done = UnifiedStep::new("e1", "crew.agent", "AlreadyDone", 0);
-        done.mark_completed(serde_json::json!({"pre": true}));
+        done.mark_running().unwrap();
+        done.mark_completed(serde_json::json!({"pre": true}))
+            .unwrap();
         exec.steps.push(done);
         exec.steps
             .push(UnifiedStep::new("e1", "crew.agent", "RunMe", 1));
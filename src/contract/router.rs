@@ -23,7 +23,7 @@
 //!
 //! impl StepHandler for MyCrewHandler {
 //!     fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-//!         step.mark_completed(serde_json::json!({"done": true}));
+//!         step.mark_completed(serde_json::json!({"done": true}))?;
 //!         Ok(())
 //!     }
 //!     fn domain(&self) -> StepDomain { StepDomain::Crew }
@@ -36,7 +36,7 @@
 
 use std::collections::HashMap;
 
-use super::types::UnifiedStep;
+use super::types::{StepStatus, UnifiedStep};
 use crate::blackboard::Blackboard;
 
 // ---------------------------------------------------------------------------
@@ -122,8 +122,15 @@ pub type StepResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
 ///
 /// # Contract
 ///
-/// - The handler MUST update `step.status` (mark_running → mark_completed or mark_failed).
+/// - The router moves the step to `Running` before calling the handler.
+/// - The handler MUST finish the step (mark_completed, mark_failed, or
+///   mark_retrying). Illegal transitions return a [`StepTransitionError`]
+///   which handlers propagate with `?`.
+/// - If the handler returns an error without finishing the step, the router
+///   marks it failed.
 /// - The handler SHOULD write its output to the blackboard, not just to `step.output`.
+///
+/// [`StepTransitionError`]: super::types::StepTransitionError
 /// - The handler MAY read inputs from the blackboard (written by previous phases).
 pub trait StepHandler: Send + Sync {
     /// Execute a step within the given blackboard context.
@@ -178,6 +185,7 @@ impl StepRouter {
     /// Dispatch a step to the appropriate handler.
     ///
     /// Returns an error if no handler is registered for the step's domain,
+    /// if the step is not in a runnable status (`Pending` or `Retrying`),
     /// or if the handler itself returns an error.
     pub fn dispatch(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
         let domain = StepDomain::from_step_type(&step.step_type).ok_or_else(|| {
@@ -201,7 +209,14 @@ impl StepRouter {
             handler.name(),
         );
 
-        handler.handle(step, bb)
+        step.mark_running()?;
+        let result = handler.handle(step, bb);
+        if let Err(ref e) = result {
+            if !step.status().is_terminal() && step.status() != StepStatus::Retrying {
+                step.mark_failed(e.to_string())?;
+            }
+        }
+        result
     }
 
     /// Dispatch all steps in an execution sequentially.
@@ -210,7 +225,7 @@ impl StepRouter {
     /// blackboard trace.
     pub fn dispatch_all(&self, steps: &mut [UnifiedStep], bb: &mut Blackboard) -> StepResult {
         for step in steps.iter_mut() {
-            if step.status != super::types::StepStatus::Pending {
+            if !matches!(step.status(), StepStatus::Pending | StepStatus::Retrying) {
                 continue; // Skip already-processed steps
             }
             self.dispatch(step, bb)?;
@@ -253,7 +268,6 @@ mod tests {
 
     impl StepHandler for TestHandler {
         fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-            step.mark_running();
             // Write output to blackboard (zero-serde)
             let key = format!("{}:{}", step.step_type, step.sequence);
             bb.put_typed(
@@ -262,7 +276,7 @@ mod tests {
                 &step.step_type,
                 &step.step_type,
             );
-            step.mark_completed(serde_json::json!({"handled": true}));
+            step.mark_completed(serde_json::json!({"handled": true}))?;
             Ok(())
         }
 
@@ -275,9 +289,7 @@ mod tests {
     struct FailHandler;
 
     impl StepHandler for FailHandler {
-        fn handle(&self, step: &mut UnifiedStep, _bb: &mut Blackboard) -> StepResult {
-            step.mark_running();
-            step.mark_failed("Intentional failure");
+        fn handle(&self, _step: &mut UnifiedStep, _bb: &mut Blackboard) -> StepResult {
             Err("Intentional failure".into())
         }
 
@@ -415,8 +427,9 @@ mod tests {
         let result = router.dispatch_all(&mut steps, &mut bb);
         assert!(result.is_err());
 
-        // First step failed
+        // First step was marked failed by the router
         assert_eq!(steps[0].status, super::super::types::StepStatus::Failed);
+        assert_eq!(steps[0].error.as_deref(), Some("Intentional failure"));
         // Second step never ran
         assert_eq!(steps[1].status, super::super::types::StepStatus::Pending);
     }
@@ -430,9 +443,12 @@ mod tests {
 
         let mut bb = Blackboard::new();
         let mut already_done = UnifiedStep::new("e1", "crew.agent", "Done", 0);
-        already_done.mark_completed(serde_json::json!({"already": true}));
+        already_done.mark_running().unwrap();
+        already_done
+            .mark_completed(serde_json::json!({"already": true}))
+            .unwrap();
 
-        let mut pending = UnifiedStep::new("e1", "crew.agent", "New", 1);
+        let pending = UnifiedStep::new("e1", "crew.agent", "New", 1);
         let mut steps = vec![already_done, pending];
 
         router.dispatch_all(&mut steps, &mut bb).unwrap();
@@ -442,4 +458,20 @@ mod tests {
         // Second step was processed
         assert_eq!(steps[1].status, super::super::types::StepStatus::Completed);
     }
+
+    #[test]
+    fn test_router_rejects_finished_step() {
+        let mut router = StepRouter::new();
+        router.register(Box::new(TestHandler {
+            domain: StepDomain::Crew,
+        }));
+
+        let mut bb = Blackboard::new();
+        let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
+        router.dispatch(&mut step, &mut bb).unwrap();
+
+        let err = router.dispatch(&mut step, &mut bb).unwrap_err();
+        assert!(err.to_string().contains("completed -> running"));
+        assert_eq!(step.status, super::super::types::StepStatus::Completed);
+    }
 }
@@ -223,14 +223,13 @@ mod tests {
             struct Handler;
             impl StepHandler for Handler {
                 fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-                    step.mark_running();
                     bb.put_typed(
                         format!("crew:{}", step.sequence),
                         "crew-result".to_string(),
                         "crew",
                         &step.step_type,
                     );
-                    step.mark_completed(serde_json::json!({"crew": true}));
+                    step.mark_completed(serde_json::json!({"crew": true}))?;
                     Ok(())
                 }
                 fn domain(&self) -> StepDomain {
@@ -275,14 +274,13 @@ mod tests {
             struct Handler;
             impl StepHandler for Handler {
                 fn handle(&self, step: &mut UnifiedStep, bb: &mut Blackboard) -> StepResult {
-                    step.mark_running();
                     bb.put_typed(
                         format!("oc:{}", step.sequence),
                         "oc-result".to_string(),
                         "oc",
                         &step.step_type,
                     );
-                    step.mark_completed(serde_json::json!({"oc": true}));
+                    step.mark_completed(serde_json::json!({"oc": true}))?;
                     Ok(())
                 }
                 fn domain(&self) -> StepDomain {
@@ -6,12 +6,23 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use thiserror::Error;
 
 // ============================================================================
 // StepStatus
 // ============================================================================
 
 /// Status of a single step within a unified execution.
+///
+/// Steps move through a fixed state machine (see [`StepStatus::can_transition_to`]):
+///
+/// ```text
+/// Pending ──> Running ──> Completed | Failed | Cancelled
+///    │          │  ^
+///    │          v  │
+///    │        Retrying ──> Failed | Cancelled
+///    └──> Skipped | Cancelled
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 #[derive(Default)]
@@ -22,6 +33,62 @@ pub enum StepStatus {
     Completed,
     Failed,
     Skipped,
+    /// Stopped before finishing (by the caller, a timeout, or a policy).
+    Cancelled,
+    /// A failed attempt is waiting to be run again.
+    Retrying,
+}
+
+impl StepStatus {
+    /// Whether the state machine allows moving from `self` to `next`.
+    pub fn can_transition_to(self, next: StepStatus) -> bool {
+        use StepStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Running | Skipped | Cancelled)
+                | (Running, Completed | Failed | Cancelled | Retrying)
+                | (Retrying, Running | Failed | Cancelled)
+        )
+    }
+
+    /// Whether no further transitions are possible from this status.
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            StepStatus::Completed
+                | StepStatus::Failed
+                | StepStatus::Skipped
+                | StepStatus::Cancelled
+        )
+    }
+}
+
+impl std::fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            StepStatus::Pending => "pending",
+            StepStatus::Running => "running",
+            StepStatus::Completed => "completed",
+            StepStatus::Failed => "failed",
+            StepStatus::Skipped => "skipped",
+            StepStatus::Cancelled => "cancelled",
+            StepStatus::Retrying => "retrying",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Error returned when a step is asked to make a transition the state
+/// machine does not allow (e.g. `completed -> running`).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("illegal step transition for step '{step_id}': {from} -> {to}")]
+pub struct StepTransitionError {
+    /// The step that rejected the transition.
+    pub step_id: String,
+    /// Status the step was in.
+    pub from: StepStatus,
+    /// Status that was requested.
+    pub to: StepStatus,
 }
 
 
@@ -50,9 +117,15 @@ pub struct UnifiedStep {
     /// Human-readable step name (maps to n8n node name).
     pub name: String,
 
-    /// Current status.
+    /// Current status. Read with [`UnifiedStep::status`]; change only through
+    /// [`UnifiedStep::transition`] (or the `mark_*` helpers) so the state
+    /// machine is enforced.
     #[serde(default)]
-    pub status: StepStatus,
+    pub(crate) status: StepStatus,
+
+    /// Number of times this step has entered `Retrying`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub attempts: u32,
 
     /// Ordering within the execution (0-based).
     pub sequence: i32,
@@ -105,6 +178,7 @@ impl UnifiedStep {
             step_type: step_type.into(),
             name: name.into(),
             status: StepStatus::Pending,
+            attempts: 0,
             sequence,
             input: Value::Null,
             output: Value::Null,
@@ -117,24 +191,79 @@ impl UnifiedStep {
         }
     }
 
+    /// Current status.
+    pub fn status(&self) -> StepStatus {
+        self.status
+    }
+
+    /// Move this step to `to`, enforcing the [`StepStatus`] state machine.
+    ///
+    /// Updates timestamps and the retry counter, and emits a
+    /// [`StepStatusChangedEvent`](crate::events::types::contract_events::StepStatusChangedEvent)
+    /// on the event bus (if it has been initialised).
+    pub fn transition(&mut self, to: StepStatus) -> Result<(), StepTransitionError> {
+        let from = self.status;
+        if !from.can_transition_to(to) {
+            return Err(StepTransitionError {
+                step_id: self.step_id.clone(),
+                from,
+                to,
+            });
+        }
+
+        let now = Utc::now();
+        match to {
+            StepStatus::Running if self.started_at.is_none() => self.started_at = Some(now),
+            StepStatus::Retrying => self.attempts += 1,
+            _ => {}
+        }
+        if to.is_terminal() {
+            self.finished_at = Some(now);
+        }
+        self.status = to;
+
+        if let Some(bus) = crate::events::CREWAI_EVENT_BUS.get() {
+            let mut event = crate::events::types::contract_events::StepStatusChangedEvent::new(
+                self.step_id.clone(),
+                self.execution_id.clone(),
+                self.step_type.clone(),
+                from,
+                to,
+            );
+            bus.emit(std::sync::Arc::new(self.step_id.clone()), &mut event);
+        }
+        Ok(())
+    }
+
     /// Mark this step as running.
-    pub fn mark_running(&mut self) {
-        self.status = StepStatus::Running;
-        self.started_at = Some(Utc::now());
+    pub fn mark_running(&mut self) -> Result<(), StepTransitionError> {
+        self.transition(StepStatus::Running)
     }
 
     /// Mark this step as completed with output.
-    pub fn mark_completed(&mut self, output: Value) {
-        self.status = StepStatus::Completed;
+    pub fn mark_completed(&mut self, output: Value) -> Result<(), StepTransitionError> {
+        self.transition(StepStatus::Completed)?;
         self.output = output;
-        self.finished_at = Some(Utc::now());
+        Ok(())
     }
 
     /// Mark this step as failed.
-    pub fn mark_failed(&mut self, error: impl Into<String>) {
-        self.status = StepStatus::Failed;
+    pub fn mark_failed(&mut self, error: impl Into<String>) -> Result<(), StepTransitionError> {
+        self.transition(StepStatus::Failed)?;
         self.error = Some(error.into());
-        self.finished_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Mark this step as cancelled.
+    pub fn mark_cancelled(&mut self) -> Result<(), StepTransitionError> {
+        self.transition(StepStatus::Cancelled)
+    }
+
+    /// Record a failed attempt that will be retried.
+    pub fn mark_retrying(&mut self, error: impl Into<String>) -> Result<(), StepTransitionError> {
+        self.transition(StepStatus::Retrying)?;
+        self.error = Some(error.into());
+        Ok(())
     }
 
     /// Returns true if this step should be routed to crewai-rust.
@@ -175,6 +304,10 @@ impl UnifiedStep {
     }
 }
 
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 // ============================================================================
 // UnifiedExecution
 // ============================================================================
@@ -378,6 +511,8 @@ mod tests {
             (StepStatus::Completed, "\"completed\""),
             (StepStatus::Failed, "\"failed\""),
             (StepStatus::Skipped, "\"skipped\""),
+            (StepStatus::Cancelled, "\"cancelled\""),
+            (StepStatus::Retrying, "\"retrying\""),
         ] {
             let json = serde_json::to_string(&status).unwrap();
             assert_eq!(json, expected);
@@ -473,11 +608,12 @@ mod tests {
         assert_eq!(step.status, StepStatus::Pending);
         assert!(step.started_at.is_none());
 
-        step.mark_running();
+        step.mark_running().unwrap();
         assert_eq!(step.status, StepStatus::Running);
         assert!(step.started_at.is_some());
 
-        step.mark_completed(serde_json::json!({"result": "done"}));
+        step.mark_completed(serde_json::json!({"result": "done"}))
+            .unwrap();
         assert_eq!(step.status, StepStatus::Completed);
         assert!(step.finished_at.is_some());
     }
@@ -485,12 +621,65 @@ mod tests {
     #[test]
     fn test_step_failure() {
         let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
-        step.mark_running();
-        step.mark_failed("Agent timeout");
+        step.mark_running().unwrap();
+        step.mark_failed("Agent timeout").unwrap();
         assert_eq!(step.status, StepStatus::Failed);
         assert_eq!(step.error.as_deref(), Some("Agent timeout"));
     }
 
+    #[test]
+    fn test_step_retry_loop() {
+        let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
+        step.mark_running().unwrap();
+        let started_at = step.started_at;
+
+        step.mark_retrying("rate limited").unwrap();
+        step.mark_running().unwrap();
+        step.mark_retrying("rate limited").unwrap();
+        step.mark_running().unwrap();
+        step.mark_completed(serde_json::json!("ok")).unwrap();
+
+        assert_eq!(step.status(), StepStatus::Completed);
+        assert_eq!(step.attempts, 2);
+        assert_eq!(step.started_at, started_at);
+    }
+
+    #[test]
+    fn test_illegal_transitions_rejected() {
+        let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
+        let err = step.mark_completed(serde_json::json!("early")).unwrap_err();
+        assert_eq!(err.from, StepStatus::Pending);
+        assert_eq!(err.to, StepStatus::Completed);
+        assert_eq!(step.status(), StepStatus::Pending);
+        assert_eq!(step.output, Value::Null);
+
+        step.mark_running().unwrap();
+        assert!(step.mark_running().is_err());
+        step.mark_cancelled().unwrap();
+        for next in [
+            StepStatus::Pending,
+            StepStatus::Running,
+            StepStatus::Completed,
+            StepStatus::Retrying,
+        ] {
+            assert!(step.transition(next).is_err());
+        }
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "illegal step transition for step '{}': pending -> completed",
+                step.step_id
+            )
+        );
+    }
+
+    #[test]
+    fn test_attempts_omitted_when_zero() {
+        let step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
+        let json = serde_json::to_value(&step).unwrap();
+        assert!(json.get("attempts").is_none());
+    }
+
     #[test]
     fn test_fork_execution() {
         let exec = UnifiedExecution::fork("parent-123", "forked-workflow");
@@ -536,7 +725,9 @@ mod tests {
     fn test_delegation_response_serde() {
         let output = DataEnvelope::new(serde_json::json!({"result": "found"}), "agent-1");
         let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
-        step.mark_completed(serde_json::json!({"result": "found"}));
+        step.mark_running().unwrap();
+        step.mark_completed(serde_json::json!({"result": "found"}))
+            .unwrap();
         step.reasoning = Some("Searched 3 sources".into());
         step.confidence = Some(0.88);
 
@@ -90,11 +90,17 @@ pub fn emit(response: &CogPacket, original_step: &UnifiedStep) -> StepDelegation
     let sat = response.satisfaction_array();
 
     let mut step = original_step.clone();
-    step.status = if response.is_error() {
+    if step.status() == StepStatus::Pending {
+        let _ = step.mark_running();
+    }
+    let to = if response.is_error() {
         StepStatus::Failed
     } else {
         StepStatus::Completed
     };
+    if let Err(e) = step.transition(to) {
+        log::warn!("wire_bridge::emit: {}", e);
+    }
     step.confidence = Some(tv.confidence as f64);
 
     let metadata = EnvelopeMetadata {
@@ -234,6 +240,7 @@ mod tests {
                 reasoning: None,
                 confidence: Some(0.9),
                 alternatives: None,
+                attempts: 0,
             },
             input: DataEnvelope {
                 data: serde_json::json!({"query": "test"}),
@@ -293,6 +300,7 @@ mod tests {
             reasoning: None,
             confidence: None,
             alternatives: None,
+            attempts: 0,
         };
 
         let delegation_response = emit(&response, &step);
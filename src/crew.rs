@@ -2,19 +2,49 @@
 //!
 //! Corresponds to `crewai/crew.py`.
 
+use chrono::{DateTime, Utc};
 use md5::{Digest, Md5};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use crate::agent::core::Agent;
+use crate::agent::core::{Agent, StepCallback};
+use crate::agents::cache::CacheHandler;
+use crate::agents::exploration::ExplorationBudget;
 use crate::crews::crew_output::CrewOutput;
+use crate::events::types::crew_events::{
+    CrewKickoffCompletedEvent, CrewKickoffFailedEvent, CrewKickoffStartedEvent,
+    CrewTestCompletedEvent, CrewTestFailedEvent, CrewTestResultEvent, CrewTestStartedEvent,
+    CrewTrainCompletedEvent, CrewTrainFailedEvent, CrewTrainStartedEvent,
+};
+use crate::events::{BaseEvent, ExecutionState, CREWAI_EVENT_BUS};
+use crate::hooks::LLMCallHooks;
+use crate::knowledge::{BaseKnowledgeSource, Knowledge};
+use crate::memory::shared::{CrewSharedMemory, SharedMemory};
+use crate::policy::PolicyEngine;
 use crate::process::Process;
+use crate::rag::embeddings::EmbedderConfig;
+use crate::security::fingerprint::Fingerprint;
+use crate::security::provenance::{ProvenanceConfig, ProvenanceManifest};
 use crate::security::security_config::SecurityConfig;
 use crate::task::Task;
 use crate::tasks::task_output::{LLMMessage, TaskOutput};
-use crate::types::usage_metrics::UsageMetrics;
+use crate::telemetry;
+use crate::telemetry::flight_recorder::{self, ExecutionRecord, RecordStatus, TaskRecord};
+use crate::telemetry::run_stats::{self, RunKind, RunStatistics};
+use crate::types::usage_metrics::{self, UsageMetrics, UsageScope};
+use crate::utilities::cancellation::{CancellationScope, CancellationToken, Interrupted};
+use crate::utilities::evaluators::{evaluate_training_data, CrewEvaluator, EvaluationReport};
+use crate::utilities::feature_flags::{FeatureFlags, FlagScope};
+use crate::utilities::journal::{self, ExecutionJournal, JournalScope};
+use crate::utilities::rpm_controller::RPMController;
+use crate::utilities::run_log::{RunLog, RunLogScope};
+use crate::utilities::training_handler::CrewTrainingHandler;
+
+/// Hook run when a kickoff is cancelled or a task times out.
+pub type CleanupHook = Box<dyn Fn() + Send + Sync>;
 
 /// Represents a group of agents, defining how they should collaborate and the
 /// tasks they should perform.
@@ -65,7 +95,7 @@ pub struct Crew {
 
     // ---- Embedder ----
     /// Configuration for the embedder to be used for the crew.
-    pub embedder: Option<HashMap<String, serde_json::Value>>,
+    pub embedder: Option<EmbedderConfig>,
 
     // ---- Usage metrics ----
     /// Metrics for the LLM usage during all tasks execution.
@@ -78,7 +108,9 @@ pub struct Crew {
     pub manager_agent: Option<String>,
 
     // ---- Function calling LLM ----
-    /// Language model for tool calling for all agents.
+    /// Language model that extracts tool calls for agents without a
+    /// `function_calling_llm` of their own, so a cheap model can handle tool
+    /// call formatting while the agents' own LLMs do the reasoning.
     pub function_calling_llm: Option<String>,
 
     // ---- Config ----
@@ -91,8 +123,13 @@ pub struct Crew {
 
     // ---- Callbacks (not serialized) ----
     /// Callback to be executed after each step for all agents execution.
+    /// Agents with their own `step_callback` keep it.
     #[serde(skip)]
-    pub step_callback: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    pub step_callback: Option<StepCallback>,
+    /// Hooks run around every LLM call of the crew's agents, after the
+    /// agents' own.
+    #[serde(skip)]
+    pub llm_call_hooks: LLMCallHooks,
     /// Callback to be executed after each task for all agents execution.
     #[serde(skip)]
     pub task_callback: Option<Box<dyn Fn(&TaskOutput) + Send + Sync>>,
@@ -108,6 +145,13 @@ pub struct Crew {
     /// List of callbacks to be executed after crew kickoff.
     #[serde(skip)]
     pub after_kickoff_callbacks: Vec<Box<dyn Fn(CrewOutput) -> CrewOutput + Send + Sync>>,
+    /// Hooks run when a kickoff is cancelled or a task times out, to release
+    /// what the interrupted work held.
+    #[serde(skip)]
+    pub cleanup_hooks: Vec<CleanupHook>,
+    /// Token cancelling the current kickoff (see [`Crew::kickoff_with_cancel`]).
+    #[serde(skip)]
+    pub cancellation: Option<CancellationToken>,
 
     // ---- Streaming ----
     /// Whether to stream output from the crew execution.
@@ -128,15 +172,24 @@ pub struct Crew {
     pub execution_logs: Vec<HashMap<String, serde_json::Value>>,
 
     // ---- Knowledge ----
-    /// Knowledge sources for the crew (stored as config maps).
-    pub knowledge_sources: Option<Vec<HashMap<String, serde_json::Value>>>,
-    /// Knowledge for the crew.
-    pub knowledge: Option<HashMap<String, serde_json::Value>>,
+    /// Knowledge sources for the crew. They are stored in the "crew"
+    /// collection, embedded with the crew's `embedder`, and every agent
+    /// queries them with its task.
+    #[serde(skip)]
+    pub knowledge_sources: Vec<Arc<dyn BaseKnowledgeSource>>,
+    /// The crew's knowledge, built from `knowledge_sources` at kickoff.
+    #[serde(skip)]
+    pub knowledge: Option<Arc<Knowledge>>,
 
     // ---- Security ----
     /// Security configuration for the crew, including fingerprinting.
     pub security_config: SecurityConfig,
 
+    // ---- Provenance ----
+    /// Attach a provenance manifest to the output (and task output files).
+    #[serde(default)]
+    pub provenance: Option<ProvenanceConfig>,
+
     // ---- Token usage ----
     /// Metrics for the LLM usage during all tasks execution.
     pub token_usage: Option<UsageMetrics>,
@@ -150,13 +203,42 @@ pub struct Crew {
     pub prompt_file: Option<String>,
 
     // ---- Output log file ----
-    /// Path to the log file to be saved.
+    /// Path to the log file to be saved. `.json` paths get structured JSON
+    /// entries; other paths get one text line per entry.
     pub output_log_file: Option<String>,
+    /// Directory receiving one structured JSON log file per task.
+    pub task_log_dir: Option<String>,
+    /// Path of the execution journal recording the run's LLM responses,
+    /// tool outputs and random seed.
+    pub journal_file: Option<String>,
+    /// Journal replayed instead of making live LLM and tool calls.
+    pub replay_journal: Option<String>,
+
+    // ---- Exploration ----
+    /// Wall-clock exploration budget applied to agents without their own.
+    pub exploration_budget: Option<ExplorationBudget>,
+
+    // ---- Shared memory ----
+    /// Memory namespaces shared with other crews, accessed under the crew's
+    /// name (see [`Crew::shared_memory`]).
+    #[serde(skip)]
+    pub shared_memory: Option<SharedMemory>,
 
     // ---- Chat LLM ----
     /// LLM used to handle chatting with the crew.
     pub chat_llm: Option<String>,
 
+    // ---- Profile ----
+    /// Name of the run profile applied to the crew, reported in its output
+    /// (see [`ActiveProfile::apply`](crate::utilities::profiles::ActiveProfile::apply)).
+    #[serde(default)]
+    pub profile: Option<String>,
+
+    // ---- Feature flags ----
+    /// Feature flags made current while the crew executes.
+    #[serde(skip)]
+    pub feature_flags: FeatureFlags,
+
     // ---- Private state (not serialized) ----
     /// Inputs provided during kickoff.
     #[serde(skip)]
@@ -170,6 +252,20 @@ pub struct Crew {
     /// Manager agent for hierarchical process.
     #[serde(skip)]
     pub manager_agent_instance: Option<Arc<std::sync::RwLock<Agent>>>,
+
+    /// Tool result cache shared by the crew's agents when `cache` is set.
+    #[serde(skip)]
+    pub cache_handler: CacheHandler,
+    /// Request budget shared by the crew's agents when `max_rpm` is set.
+    #[serde(skip)]
+    pub rpm_controller: Option<RPMController>,
+    /// Policy checked on the tool calls of agents without their own.
+    #[serde(skip)]
+    pub policy: Option<Arc<Mutex<PolicyEngine>>>,
+    /// State of an earlier run whose completed tasks the next kickoff
+    /// restores instead of executing.
+    #[serde(skip)]
+    resume_state: Option<ExecutionState>,
 }
 
 impl std::fmt::Debug for Crew {
@@ -191,7 +287,7 @@ impl std::fmt::Debug for Crew {
 impl Crew {
     /// Create a new Crew with required fields.
     pub fn new(tasks: Vec<Task>, agents: Vec<String>) -> Self {
-        Self {
+        let mut crew = Self {
             name: Some("crew".to_string()),
             id: Uuid::new_v4(),
             cache: true,
@@ -212,26 +308,43 @@ impl Crew {
             config: None,
             share_crew: false,
             step_callback: None,
+            llm_call_hooks: LLMCallHooks::default(),
             task_callback: None,
             before_kickoff_callbacks: Vec::new(),
             after_kickoff_callbacks: Vec::new(),
+            cleanup_hooks: Vec::new(),
+            cancellation: None,
             stream: false,
             max_rpm: None,
             planning: false,
             planning_llm: None,
             execution_logs: Vec::new(),
-            knowledge_sources: None,
+            knowledge_sources: Vec::new(),
             knowledge: None,
             security_config: SecurityConfig::default(),
+            provenance: None,
             token_usage: None,
             tracing: None,
             prompt_file: None,
             output_log_file: None,
+            task_log_dir: None,
+            journal_file: None,
+            replay_journal: None,
+            exploration_budget: None,
+            shared_memory: None,
             chat_llm: None,
+            profile: None,
+            feature_flags: FeatureFlags::default(),
             _inputs: None,
             agent_objects: HashMap::new(),
             manager_agent_instance: None,
-        }
+            cache_handler: CacheHandler::new(),
+            rpm_controller: None,
+            policy: None,
+            resume_state: None,
+        };
+        crew.security_config = SecurityConfig::with_fingerprint(crew.definition_fingerprint());
+        crew
     }
 
     /// Create a Crew with actual Agent objects.
@@ -244,7 +357,7 @@ impl Crew {
         for agent in agents {
             agent_objects.insert(agent.role.clone(), Arc::new(std::sync::RwLock::new(agent)));
         }
-        Self {
+        let mut crew = Self {
             name: Some("crew".to_string()),
             id: Uuid::new_v4(),
             cache: true,
@@ -265,26 +378,103 @@ impl Crew {
             config: None,
             share_crew: false,
             step_callback: None,
+            llm_call_hooks: LLMCallHooks::default(),
             task_callback: None,
             before_kickoff_callbacks: Vec::new(),
             after_kickoff_callbacks: Vec::new(),
+            cleanup_hooks: Vec::new(),
+            cancellation: None,
             stream: false,
             max_rpm: None,
             planning: false,
             planning_llm: None,
             execution_logs: Vec::new(),
-            knowledge_sources: None,
+            knowledge_sources: Vec::new(),
             knowledge: None,
             security_config: SecurityConfig::default(),
+            provenance: None,
             token_usage: None,
             tracing: None,
             prompt_file: None,
             output_log_file: None,
+            task_log_dir: None,
+            journal_file: None,
+            replay_journal: None,
+            exploration_budget: None,
+            shared_memory: None,
             chat_llm: None,
+            profile: None,
+            feature_flags: FeatureFlags::default(),
             _inputs: None,
             agent_objects,
             manager_agent_instance: None,
-        }
+            cache_handler: CacheHandler::new(),
+            rpm_controller: None,
+            policy: None,
+            resume_state: None,
+        };
+        crew.security_config = SecurityConfig::with_fingerprint(crew.definition_fingerprint());
+        crew
+    }
+
+    /// Builder: save the run log (task lifecycle, agent steps, tool calls
+    /// and LLM prompts/responses) to `path`.
+    pub fn output_log_file(mut self, path: impl Into<String>) -> Self {
+        self.output_log_file = Some(path.into());
+        self
+    }
+
+    /// Builder: also save each task's log entries to a JSON file in `dir`.
+    pub fn task_log_dir(mut self, dir: impl Into<String>) -> Self {
+        self.task_log_dir = Some(dir.into());
+        self
+    }
+
+    /// Builder: record every LLM response, tool output and the random seed
+    /// of each run to the execution journal at `path`.
+    pub fn journal_file(mut self, path: impl Into<String>) -> Self {
+        self.journal_file = Some(path.into());
+        self
+    }
+
+    /// Builder: replay the execution journal at `path` on kickoff, serving
+    /// journaled LLM responses and tool outputs instead of live calls.
+    pub fn replay_journal(mut self, path: impl Into<String>) -> Self {
+        self.replay_journal = Some(path.into());
+        self
+    }
+
+    /// Builder: embed the crew's knowledge, and that of agents without an
+    /// embedder of their own, with `embedder`.
+    pub fn embedder(mut self, embedder: impl Into<EmbedderConfig>) -> Self {
+        self.embedder = Some(embedder.into());
+        self
+    }
+
+    /// Builder: add a knowledge source queried by every agent of the crew.
+    pub fn knowledge_source(mut self, source: impl BaseKnowledgeSource + 'static) -> Self {
+        self.knowledge_sources.push(Arc::new(source));
+        self
+    }
+
+    /// Builder: time-box exploration. Once the budget is spent, agents stop
+    /// calling tools and synthesize a best-effort answer with caveats.
+    /// Agents with their own `exploration_budget` keep it.
+    pub fn exploration_budget(mut self, budget: impl Into<ExplorationBudget>) -> Self {
+        self.exploration_budget = Some(budget.into());
+        self
+    }
+
+    /// Builder: join the shared memory `memory`.
+    pub fn with_shared_memory(mut self, memory: SharedMemory) -> Self {
+        self.shared_memory = Some(memory);
+        self
+    }
+
+    /// The shared memory, acting as this crew (by name, or id when unnamed).
+    pub fn shared_memory(&self) -> Option<CrewSharedMemory> {
+        let crew = self.name.clone().unwrap_or_else(|| self.id.to_string());
+        self.shared_memory.as_ref().map(|m| m.for_crew(crew))
     }
 
     /// Register an agent with the crew.
@@ -329,6 +519,15 @@ impl Crew {
         &mut self,
         inputs: Option<HashMap<String, String>>,
     ) -> Result<CrewOutput, String> {
+        // Make this execution's flags current; they are recorded in the output.
+        let flags = self.feature_flags.for_execution();
+        let _flag_scope = FlagScope::enter(flags.clone());
+        let _log_scope = self.run_log().map(RunLogScope::enter);
+        let _journal_scope = self.journal()?.map(JournalScope::enter);
+        let started_at = Utc::now();
+        let clock = Instant::now();
+        self.refresh_fingerprints();
+
         // Run before_kickoff callbacks
         let mut current_inputs = inputs;
         for callback in &self.before_kickoff_callbacks {
@@ -338,19 +537,37 @@ impl Crew {
         // Store inputs
         self._inputs = current_inputs.clone();
 
-        // Interpolate inputs into tasks
+        // Interpolate inputs into tasks and agents
         if let Some(ref inp) = current_inputs {
-            self.interpolate_inputs(inp);
+            self.interpolate_inputs(inp)?;
         }
 
+        let mut started =
+            CrewKickoffStartedEvent::new(self.name.clone(), json_inputs(current_inputs.as_ref()));
+        self.emit_event(&mut started);
+
         // Execute based on process
         let result = match self.process {
-            Process::Sequential => self.run_sequential_process()?,
-            Process::Hierarchical => self.run_hierarchical_process()?,
+            Process::Sequential => self.run_sequential_process(),
+            Process::Hierarchical => self.run_hierarchical_process(),
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                self.emit_event(&mut CrewKickoffFailedEvent::new(
+                    self.name.clone(),
+                    e.clone(),
+                ));
+                self.record_flight(started_at, clock.elapsed(), Err(&e));
+                self.record_run_statistics(clock.elapsed(), false);
+                return Err(e);
+            }
         };
 
         // Run after_kickoff callbacks
         let mut final_result = result;
+        final_result.feature_flags = flags.evaluated();
+        final_result.profile = self.profile.clone();
         for callback in &self.after_kickoff_callbacks {
             final_result = callback(final_result);
         }
@@ -358,9 +575,243 @@ impl Crew {
         // Calculate usage metrics
         self.usage_metrics = Some(self.calculate_usage_metrics());
 
+        if let Some(ref config) = self.provenance {
+            final_result.provenance = Some(self.attach_provenance(config, &final_result.raw));
+        }
+
+        let total_tokens = self
+            .usage_metrics
+            .as_ref()
+            .map_or(0, |metrics| metrics.total_tokens);
+        self.emit_event(&mut CrewKickoffCompletedEvent::new(
+            self.name.clone(),
+            serde_json::Value::String(final_result.raw.clone()),
+            total_tokens,
+        ));
+        self.record_flight(started_at, clock.elapsed(), Ok(&final_result));
+        self.record_run_statistics(clock.elapsed(), true);
+
         Ok(final_result)
     }
 
+    /// Execute the crew's workflow until `cancellation` is cancelled.
+    ///
+    /// Cancellation is cooperative: the running task stops at its next
+    /// agent iteration, in-flight LLM and tool calls are abandoned, the
+    /// cleanup hooks run and the kickoff fails with
+    /// [`Interrupted::Cancelled`].
+    pub fn kickoff_with_cancel(
+        &mut self,
+        inputs: Option<HashMap<String, String>>,
+        cancellation: CancellationToken,
+    ) -> Result<CrewOutput, String> {
+        let previous = self.cancellation.replace(cancellation);
+        let result = self.kickoff(inputs);
+        self.cancellation = previous;
+        result
+    }
+
+    /// Add a hook run when a kickoff is cancelled or a task times out.
+    pub fn add_cleanup_hook(&mut self, hook: impl Fn() + Send + Sync + 'static) {
+        self.cleanup_hooks.push(Box::new(hook));
+    }
+
+    /// Report anonymized statistics of this execution to telemetry.
+    fn record_run_statistics(&self, duration: Duration, success: bool) {
+        let mut stats = RunStatistics::new(RunKind::Crew, success, duration);
+        stats.process = Some(self.process.to_string());
+        stats.agent_count = self.agents.len();
+        stats.task_count = self.tasks.len();
+        stats.tasks_completed = self.tasks.iter().filter(|t| t.output.is_some()).count();
+        stats.model_families = self
+            .agents
+            .iter()
+            .filter_map(|role| self.get_agent(role)?.read().ok()?.llm.clone())
+            .map(|model| run_stats::model_family(&model))
+            .collect();
+        stats.total_tokens = self.calculate_usage_metrics().total_tokens;
+        if let Ok(telemetry) = telemetry::telemetry().lock() {
+            telemetry.record_run(&stats);
+        }
+    }
+
+    /// Record this execution in the flight recorder.
+    fn record_flight(
+        &self,
+        started_at: DateTime<Utc>,
+        duration: Duration,
+        result: Result<&CrewOutput, &str>,
+    ) {
+        let model = |role: &str| {
+            self.get_agent(role)
+                .and_then(|agent| agent.read().ok().and_then(|a| a.llm.clone()))
+        };
+        let tasks: Vec<TaskRecord> = self
+            .tasks
+            .iter()
+            .map(|task| {
+                let status = if task.output.is_some() {
+                    RecordStatus::Completed
+                } else if task.start_time.is_some() && result.is_err() {
+                    RecordStatus::Failed
+                } else {
+                    RecordStatus::Skipped
+                };
+                TaskRecord {
+                    name: task
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| task.description.clone()),
+                    agent: task.agent.clone(),
+                    model: task.agent.as_deref().and_then(model),
+                    duration_ms: task
+                        .start_time
+                        .zip(task.end_time)
+                        .map(|(start, end)| (end - start).num_milliseconds().max(0) as u64),
+                    status,
+                }
+            })
+            .collect();
+        let models: BTreeSet<String> = self.agents.iter().filter_map(|role| model(role)).collect();
+        flight_recorder::record(&ExecutionRecord {
+            id: self.id.to_string(),
+            crew: self.name.clone(),
+            fingerprint: Some(self.security_config.fingerprint.uuid_str().to_string()),
+            profile: self.profile.clone(),
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            status: if result.is_ok() {
+                RecordStatus::Completed
+            } else {
+                RecordStatus::Failed
+            },
+            error: result.err().map(str::to_string),
+            models: models.into_iter().collect(),
+            usage: match result {
+                Ok(output) => output.token_usage.clone(),
+                Err(_) => self.calculate_usage_metrics(),
+            },
+            cost: None,
+            tasks,
+        });
+    }
+
+    /// Emit an event on the global event bus, if it has been initialised.
+    fn emit_event<E: BaseEvent + 'static>(&self, event: &mut E) {
+        if let Some(bus) = CREWAI_EVENT_BUS.get() {
+            self.security_config.fingerprint.stamp(event);
+            bus.emit(Arc::new(self.id.to_string()), event);
+        }
+    }
+
+    /// Fingerprint of the crew's definition: its name, process and the
+    /// definition hashes of its agents and tasks.
+    pub fn definition_fingerprint(&self) -> Fingerprint {
+        let hash = |fingerprint: &Fingerprint| {
+            fingerprint
+                .hash()
+                .unwrap_or(fingerprint.uuid_str())
+                .to_string()
+        };
+        let mut agents: Vec<String> = if self.agent_objects.is_empty() {
+            self.agents.clone()
+        } else {
+            self.agent_objects
+                .values()
+                .filter_map(|agent| {
+                    agent
+                        .read()
+                        .ok()
+                        .map(|a| hash(&a.security_config.fingerprint))
+                })
+                .collect()
+        };
+        agents.sort();
+        let tasks: Vec<String> = self
+            .tasks
+            .iter()
+            .map(|task| hash(&task.security_config.fingerprint))
+            .collect();
+        Fingerprint::of_definition(
+            "crew",
+            &serde_json::json!({
+                "name": self.name,
+                "process": self.process,
+                "agents": agents,
+                "tasks": tasks,
+            }),
+        )
+    }
+
+    /// Bring the definition fingerprints of the crew, its agents and its
+    /// tasks up to date (see [`Fingerprint::update_definition`]).
+    pub fn refresh_fingerprints(&mut self) {
+        for agent in self.agent_objects.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent.refresh_fingerprint();
+            }
+        }
+        for task in &mut self.tasks {
+            task.refresh_fingerprint();
+        }
+        let fingerprint = self.definition_fingerprint();
+        self.security_config
+            .fingerprint
+            .update_definition(fingerprint);
+    }
+
+    /// Run log for this execution, when `output_log_file` or `task_log_dir`
+    /// is set.
+    fn run_log(&self) -> Option<RunLog> {
+        if self.output_log_file.is_none() && self.task_log_dir.is_none() {
+            return None;
+        }
+        let mut log = RunLog::new();
+        if let Some(path) = &self.output_log_file {
+            log = log.with_output_file(path);
+        }
+        if let Some(dir) = &self.task_log_dir {
+            log = log.with_task_dir(dir);
+        }
+        Some(log)
+    }
+
+    /// Execution journal for this execution: replaying `replay_journal`, or
+    /// recording to `journal_file`.
+    fn journal(&self) -> Result<Option<ExecutionJournal>, String> {
+        if let Some(path) = &self.replay_journal {
+            return ExecutionJournal::replay(path)
+                .map(Some)
+                .map_err(|e| format!("Failed to load execution journal {}: {}", path, e));
+        }
+        match &self.journal_file {
+            Some(path) => ExecutionJournal::record(path)
+                .map(Some)
+                .map_err(|e| format!("Failed to create execution journal {}: {}", path, e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Builder: restore the tasks `state` records as completed on the next
+    /// kickoff instead of executing them again.
+    ///
+    /// Tasks are matched by ID and then by name, so the state of a run of an
+    /// identically built crew applies as well.
+    pub fn resume_from(mut self, state: ExecutionState) -> Self {
+        self.resume_state = Some(state);
+        self
+    }
+
+    /// Resume a failed or interrupted run from its reconstructed `state`
+    /// (see [`crate::events::state`]): kick off with the run's inputs,
+    /// restoring the outputs of the tasks it completed and executing the
+    /// rest.
+    pub fn resume(&mut self, state: ExecutionState) -> Result<CrewOutput, String> {
+        let inputs = state.kickoff_inputs();
+        self.resume_state = Some(state);
+        self.kickoff(inputs)
+    }
+
     /// Async version of kickoff.
     pub async fn kickoff_async(
         &mut self,
@@ -371,6 +822,161 @@ impl Crew {
         self.kickoff(inputs)
     }
 
+    /// Kick the crew off `n_iterations` times and have `eval_llm` score
+    /// every task output.
+    ///
+    /// Returns the scores aggregated per task, per agent and per iteration.
+    pub fn test(
+        &mut self,
+        n_iterations: u32,
+        eval_llm: &str,
+        inputs: Option<HashMap<String, String>>,
+    ) -> Result<EvaluationReport, String> {
+        let mut started = CrewTestStartedEvent::new(
+            self.name.clone(),
+            n_iterations.into(),
+            Some(eval_llm.to_string()),
+            json_inputs(inputs.as_ref()),
+        );
+        self.emit_event(&mut started);
+
+        let mut evaluator = CrewEvaluator::new(eval_llm);
+        for _ in 0..n_iterations {
+            let clock = Instant::now();
+            let run = self.kickoff(inputs.clone()).and_then(|output| {
+                evaluator.evaluate(&self.tasks, &output.tasks_output, clock.elapsed())
+            });
+            let run = match run {
+                Ok(run) => run,
+                Err(e) => {
+                    let mut failed = CrewTestFailedEvent::new(self.name.clone(), e.clone());
+                    self.emit_event(&mut failed);
+                    return Err(e);
+                }
+            };
+            let mut result = CrewTestResultEvent::new(
+                self.name.clone(),
+                run.quality(),
+                run.execution_time,
+                eval_llm.to_string(),
+            );
+            self.emit_event(&mut result);
+        }
+
+        let mut completed = CrewTestCompletedEvent::new(self.name.clone());
+        self.emit_event(&mut completed);
+        Ok(evaluator.report())
+    }
+
+    /// Kick the crew off `n_iterations` times, asking `feedback` for human
+    /// feedback on every task output.
+    ///
+    /// Each output and its feedback are appended to the training data in
+    /// `filename` under the role of the agent that produced it, and the
+    /// feedback recorded so far is added to the agents' task prompts. After
+    /// the last iteration each agent's LLM distills its feedback into
+    /// suggestions, saved in the agent's trained agents file for its future
+    /// tasks.
+    pub fn train(
+        &mut self,
+        n_iterations: u32,
+        filename: &str,
+        inputs: Option<HashMap<String, String>>,
+        feedback: &mut dyn FnMut(&TaskOutput) -> String,
+    ) -> Result<(), String> {
+        let mut started = CrewTrainStartedEvent::new(
+            self.name.clone(),
+            n_iterations.into(),
+            filename.to_string(),
+            json_inputs(inputs.as_ref()),
+        );
+        self.emit_event(&mut started);
+
+        let handler = CrewTrainingHandler::for_file(filename);
+        self.set_training_file(Some(filename));
+        let mut result = Ok(());
+        for iteration in 0..n_iterations {
+            result = self.kickoff(inputs.clone()).and_then(|output| {
+                output.tasks_output.iter().try_for_each(|task_output| {
+                    let human_feedback = feedback(task_output);
+                    handler
+                        .append(
+                            &task_output.agent,
+                            serde_json::json!({
+                                "iteration": iteration,
+                                "task": task_output.description,
+                                "initial_output": task_output.raw,
+                                "human_feedback": human_feedback,
+                            }),
+                        )
+                        .map_err(|e| {
+                            format!("Failed to write training data to {}: {}", filename, e)
+                        })
+                })
+            });
+            if result.is_err() {
+                break;
+            }
+        }
+        self.set_training_file(None);
+        if let Err(e) = result.and_then(|()| self.save_trained_data(&handler)) {
+            let mut failed = CrewTrainFailedEvent::new(self.name.clone(), e.clone());
+            self.emit_event(&mut failed);
+            return Err(e);
+        }
+
+        let mut completed = CrewTrainCompletedEvent::new(
+            self.name.clone(),
+            n_iterations.into(),
+            filename.to_string(),
+        );
+        self.emit_event(&mut completed);
+        Ok(())
+    }
+
+    /// Mark the crew's agents as trained with the data in `filename`.
+    fn set_training_file(&self, filename: Option<&str>) {
+        for agent in self.agent_objects.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent.training_file = filename.map(str::to_string);
+            }
+        }
+    }
+
+    /// Distill the training data of each agent in `handler` into
+    /// suggestions with the agent's LLM and save them in its trained agents
+    /// file.
+    fn save_trained_data(&self, handler: &CrewTrainingHandler) -> Result<(), String> {
+        for agent in self.agent_objects.values() {
+            let agent = agent
+                .read()
+                .map_err(|e| format!("Failed to lock agent: {}", e))?;
+            let entries = handler.entries(&agent.role);
+            if entries.is_empty() {
+                continue;
+            }
+            let llm = agent.create_llm_instance()?;
+            let evaluation = evaluate_training_data(&agent.role, &entries, &mut |messages| {
+                let response = llm.call(messages, None, None).map_err(|e| e.to_string())?;
+                Ok(match response {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                })
+            })?;
+            let trained_data = serde_json::to_value(&evaluation).map_err(|e| e.to_string())?;
+            CrewTrainingHandler::for_file(agent.trained_agents_file())
+                .save_trained_data(&agent.role, trained_data)
+                .map_err(|e| {
+                    format!(
+                        "Failed to write trained data to {}: {}",
+                        agent.trained_agents_file(),
+                        e
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
     /// Creates a deep copy of the Crew instance.
     pub fn copy(&self) -> Crew {
         Crew {
@@ -393,10 +999,13 @@ impl Crew {
             function_calling_llm: self.function_calling_llm.clone(),
             config: self.config.clone(),
             share_crew: self.share_crew,
-            step_callback: None, // Callbacks can't be cloned
+            step_callback: self.step_callback.clone(),
+            llm_call_hooks: self.llm_call_hooks.clone(),
             task_callback: None,
             before_kickoff_callbacks: Vec::new(),
             after_kickoff_callbacks: Vec::new(),
+            cleanup_hooks: Vec::new(),
+            cancellation: None,
             stream: self.stream,
             max_rpm: self.max_rpm,
             planning: self.planning,
@@ -405,14 +1014,26 @@ impl Crew {
             knowledge_sources: self.knowledge_sources.clone(),
             knowledge: self.knowledge.clone(),
             security_config: self.security_config.clone(),
+            provenance: self.provenance.clone(),
             token_usage: None,
             tracing: self.tracing,
             prompt_file: self.prompt_file.clone(),
             output_log_file: self.output_log_file.clone(),
+            task_log_dir: self.task_log_dir.clone(),
+            journal_file: self.journal_file.clone(),
+            replay_journal: self.replay_journal.clone(),
+            exploration_budget: self.exploration_budget.clone(),
+            shared_memory: self.shared_memory.clone(),
             chat_llm: self.chat_llm.clone(),
+            profile: self.profile.clone(),
+            feature_flags: self.feature_flags.for_execution(),
             _inputs: None,
             agent_objects: HashMap::new(), // Don't clone agent locks, start fresh
             manager_agent_instance: None,
+            cache_handler: CacheHandler::new(),
+            rpm_controller: None,
+            policy: None,
+            resume_state: None,
         }
     }
 
@@ -483,23 +1104,34 @@ impl Crew {
     }
 
     /// Calculate and return usage metrics.
+    ///
+    /// Sums the token usage recorded on each executed task's output.
     pub fn calculate_usage_metrics(&self) -> UsageMetrics {
-        // TODO: Iterate over agents' LLM usage summaries and aggregate.
-        // For now, return the existing usage metrics or default.
-        self.usage_metrics.clone().unwrap_or_default()
+        let mut total = UsageMetrics::default();
+        for usage in self
+            .tasks
+            .iter()
+            .filter_map(|task| task.output.as_ref()?.token_usage.as_ref())
+        {
+            total.add_usage_metrics(usage);
+        }
+        total
     }
 
     /// Interpolate inputs into tasks and agents.
-    fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) {
+    ///
+    /// Fails on the first placeholder without a matching input.
+    fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) -> Result<(), String> {
         for task in &mut self.tasks {
-            task.interpolate_inputs(inputs);
+            task.interpolate_inputs(inputs)?;
         }
         // Interpolate inputs into registered agents
         for agent_lock in self.agent_objects.values() {
             if let Ok(mut agent) = agent_lock.write() {
-                agent.interpolate_inputs(inputs);
+                agent.interpolate_inputs(inputs)?;
             }
         }
+        Ok(())
     }
 
     /// Execute tasks sequentially and return the final output.
@@ -563,39 +1195,12 @@ impl Crew {
         // First wire up all agent executors to avoid borrow conflicts
         self.wire_all_task_executors_hierarchical();
 
-        let mut task_outputs: Vec<TaskOutput> = Vec::new();
-
-        for task in &mut self.tasks {
-            let context = if !task_outputs.is_empty() {
-                Some(
-                    task_outputs
-                        .iter()
-                        .map(|o| o.raw.clone())
-                        .collect::<Vec<String>>()
-                        .join("\n\n---\n\n"),
-                )
-            } else {
-                None
-            };
-
-            // In hierarchical mode, unassigned tasks go to the manager
-            let agent_role = task.agent.clone().or_else(|| {
-                self.manager_agent
-                    .clone()
-                    .or_else(|| Some("Crew Manager".to_string()))
-            });
-
-            let task_output = task.execute_sync(agent_role.as_deref(), context.as_deref(), None)?;
-
-            // Invoke task callback if set
-            if let Some(ref callback) = self.task_callback {
-                callback(&task_output);
-            }
-
-            task_outputs.push(task_output);
-        }
-
-        self.create_crew_output(task_outputs)
+        // In hierarchical mode, unassigned tasks go to the manager
+        let manager_role = self
+            .manager_agent
+            .clone()
+            .unwrap_or_else(|| "Crew Manager".to_string());
+        self.run_tasks(|task| Some(task.agent.clone().unwrap_or_else(|| manager_role.clone())))
     }
 
     /// Wire up agent executors for hierarchical mode.
@@ -608,10 +1213,18 @@ impl Crew {
         // Collect role -> agent_lock mappings first
         let agent_locks: HashMap<String, Arc<std::sync::RwLock<Agent>>> =
             self.agent_objects.clone();
+        self.share_rpm_controller(&agent_locks);
+        self.share_policy(&agent_locks);
+        self.share_step_callback(&agent_locks);
+        self.share_function_calling_llm(&agent_locks);
+        self.share_embedder(&agent_locks);
+        self.share_knowledge(&agent_locks);
+        let budget = self.exploration_budget.clone();
+        let hooks = self.llm_call_hooks.clone();
 
         for task in &mut self.tasks {
             let role = task.agent.clone().unwrap_or_else(|| manager_role.clone());
-            Self::wire_task_executor_static(task, &role, &agent_locks);
+            Self::wire_task_executor_static(task, &role, &agent_locks, budget.as_ref(), &hooks);
         }
     }
 
@@ -620,34 +1233,263 @@ impl Crew {
         // First wire up all agent executors to avoid borrow conflicts
         self.wire_all_task_executors();
 
-        let mut task_outputs: Vec<TaskOutput> = Vec::new();
+        self.run_tasks(|task| task.agent.clone())
+    }
 
-        for task in &mut self.tasks {
-            let context = if !task_outputs.is_empty() {
-                Some(
-                    task_outputs
-                        .iter()
-                        .map(|o| o.raw.clone())
-                        .collect::<Vec<String>>()
-                        .join("\n\n---\n\n"),
-                )
-            } else {
-                None
-            };
+    /// Execute the crew's tasks in order, each by the agent `agent_role`
+    /// picks for it, and return the crew output.
+    ///
+    /// A task's context is the output of the tasks its `context` names or,
+    /// without one, of all tasks before it. Tasks with `async_execution`
+    /// run on their own thread while the crew moves on; a task waits for
+    /// those it takes context from, and a synchronous task for all of them.
+    /// A task whose `condition` rejects the previous task's output is
+    /// skipped with an empty output.
+    fn run_tasks(
+        &mut self,
+        agent_role: impl Fn(&Task) -> Option<String>,
+    ) -> Result<CrewOutput, String> {
+        validate_task_context(&self.tasks)?;
+        let resumed = self.resume_state.take();
+        let ids: Vec<Uuid> = self.tasks.iter().map(|task| task.id).collect();
+        let cancellation = self.cancellation.clone();
+        let (tasks, cleanup_hooks) = (&mut self.tasks, &self.cleanup_hooks);
+        let task_callback = &self.task_callback;
+        let record = |output: TaskOutput, outputs: &mut [Option<TaskOutput>], i: usize| {
+            if let Some(callback) = task_callback {
+                callback(&output);
+            }
+            outputs[i] = Some(output);
+        };
 
-            let agent_role = task.agent.clone();
+        let task_outputs = std::thread::scope(|scope| {
+            let mut outputs: Vec<Option<TaskOutput>> = ids.iter().map(|_| None).collect();
+            let mut pending = Vec::new();
+            for (i, task) in tasks.iter_mut().enumerate() {
+                let (awaited, running): (Vec<_>, Vec<_>) =
+                    pending.into_iter().partition(|(j, _)| {
+                        // A condition reads the previous task's output
+                        !task.async_execution
+                            || task.condition.is_some()
+                            || task.context.as_ref().is_some_and(|c| c.contains(&ids[*j]))
+                    });
+                pending = running;
+                for (j, handle) in awaited {
+                    record(join_task(handle)?, &mut outputs, j);
+                }
+
+                let context = task_context(task, &ids, &outputs);
+                let agent_role = agent_role(task);
+                if let Some(raw) = resumed_output(resumed.as_ref(), task) {
+                    outputs[i] = Some(task.restore_output(raw));
+                    continue;
+                }
+                if let Some(condition) = &task.condition {
+                    let previous = outputs[..i].iter().rev().flatten().next();
+                    if previous.is_some_and(|output| !condition(output)) {
+                        outputs[i] = Some(task.skipped_output());
+                        continue;
+                    }
+                }
+                if task.async_execution {
+                    let cancellation = cancellation.clone();
+                    let journal = journal::current();
+                    let handle = scope.spawn(move || {
+                        let _journal_scope = journal.map(JournalScope::enter);
+                        let usage = UsageScope::enter();
+                        let output = Self::execute_task_interruptible(
+                            task,
+                            agent_role.as_deref(),
+                            context.as_deref(),
+                            cancellation.as_ref(),
+                            cleanup_hooks,
+                        );
+                        (output, usage.usage())
+                    });
+                    pending.push((i, handle));
+                    continue;
+                }
+                let task_output = Self::execute_task_interruptible(
+                    task,
+                    agent_role.as_deref(),
+                    context.as_deref(),
+                    cancellation.as_ref(),
+                    cleanup_hooks,
+                )?;
+                record(task_output, &mut outputs, i);
+            }
+            for (j, handle) in pending {
+                record(join_task(handle)?, &mut outputs, j);
+            }
+            Ok::<_, String>(outputs.into_iter().flatten().collect())
+        })?;
+
+        self.create_crew_output(task_outputs)
+    }
+
+    /// Execute `task`, stopping it when the kickoff is cancelled or the task
+    /// runs past its `max_execution_time`. A timed-out task yields an output
+    /// marked `timed_out` and the crew moves on; a cancelled one fails the
+    /// kickoff. Both run the cleanup hooks.
+    fn execute_task_interruptible(
+        task: &mut Task,
+        agent_role: Option<&str>,
+        context: Option<&str>,
+        cancellation: Option<&CancellationToken>,
+        cleanup_hooks: &[CleanupHook],
+    ) -> Result<TaskOutput, String> {
+        let run_cleanup_hooks = || cleanup_hooks.iter().for_each(|hook| hook());
+        if cancellation.is_some_and(CancellationToken::is_cancelled) {
+            run_cleanup_hooks();
+            return Err(Interrupted::Cancelled.to_string());
+        }
+        let timeout = task.max_execution_time.map(Duration::from_secs);
+        if cancellation.is_none() && timeout.is_none() {
+            return task.execute_sync(agent_role, context, None);
+        }
 
-            let task_output = task.execute_sync(agent_role.as_deref(), context.as_deref(), None)?;
+        let scope = CancellationScope::enter(cancellation.cloned().unwrap_or_default(), timeout);
+        let result = task.execute_sync(agent_role, context, None);
+        match (result, scope.interrupted()) {
+            (Ok(output), _) => Ok(output),
+            (Err(e), None) => Err(e),
+            (Err(_), Some(Interrupted::TimedOut(timeout))) => {
+                log::warn!(
+                    "Task '{}' timed out after {}s",
+                    task.name.as_deref().unwrap_or(&task.description),
+                    timeout.as_secs()
+                );
+                run_cleanup_hooks();
+                Ok(task.timed_out_output())
+            }
+            (Err(_), Some(Interrupted::Cancelled)) => {
+                run_cleanup_hooks();
+                Err(Interrupted::Cancelled.to_string())
+            }
+        }
+    }
 
-            // Invoke task callback if set
-            if let Some(ref callback) = self.task_callback {
-                callback(&task_output);
+    /// Give every agent the crew's request budget when `max_rpm` is set,
+    /// so all of them draw on one limit.
+    fn share_rpm_controller(&mut self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        let Some(max_rpm) = self.max_rpm.filter(|&rpm| rpm > 0) else {
+            return;
+        };
+        let controller = match &self.rpm_controller {
+            Some(controller) if controller.max_rpm == Some(max_rpm) => controller.clone(),
+            _ => self
+                .rpm_controller
+                .insert(RPMController::new(Some(max_rpm)))
+                .clone(),
+        };
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent.set_rpm_controller(controller.clone());
             }
+        }
+    }
 
-            task_outputs.push(task_output);
+    /// Give agents without a policy of their own the crew's.
+    fn share_policy(&self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        let Some(policy) = &self.policy else {
+            return;
+        };
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent.policy.get_or_insert_with(|| policy.clone());
+            }
         }
+    }
 
-        self.create_crew_output(task_outputs)
+    /// Give agents without a step callback of their own the crew's.
+    fn share_step_callback(&self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        let Some(step_callback) = &self.step_callback else {
+            return;
+        };
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent
+                    .step_callback
+                    .get_or_insert_with(|| step_callback.clone());
+            }
+        }
+    }
+
+    /// Give agents without a function calling LLM of their own the crew's.
+    fn share_function_calling_llm(&self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        let Some(function_calling_llm) = &self.function_calling_llm else {
+            return;
+        };
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent
+                    .function_calling_llm
+                    .get_or_insert_with(|| function_calling_llm.clone());
+            }
+        }
+    }
+
+    /// Load each agent's own knowledge, using the crew's embedder for agents
+    /// without one.
+    fn share_embedder(&self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                if agent.knowledge.is_some() {
+                    continue;
+                }
+                if let Err(e) = agent.set_knowledge(self.embedder.as_ref()) {
+                    log::warn!("{}", e);
+                }
+            }
+        }
+    }
+
+    /// Name of the collection holding the crew's knowledge, if it has any.
+    pub fn knowledge_collection_name(&self) -> Option<String> {
+        if let Some(knowledge) = &self.knowledge {
+            return knowledge.collection_name.clone();
+        }
+        (!self.knowledge_sources.is_empty()).then(|| "crew".to_string())
+    }
+
+    /// Load the crew's knowledge, if not loaded yet, and give it to the
+    /// agents to query alongside their own.
+    fn share_knowledge(&mut self, agents: &HashMap<String, Arc<std::sync::RwLock<Agent>>>) {
+        if let Err(e) = self.set_knowledge() {
+            log::warn!("{}", e);
+        }
+        for agent in agents.values() {
+            if let Ok(mut agent) = agent.write() {
+                agent.crew_knowledge = self.knowledge.clone();
+            }
+        }
+    }
+
+    /// Ingest the crew's knowledge sources into the "crew" collection,
+    /// embedded with the crew's `embedder`.
+    ///
+    /// Corresponds to the knowledge set up by `Crew.create_crew_knowledge()`
+    /// in Python.
+    pub fn set_knowledge(&mut self) -> Result<(), String> {
+        if self.knowledge.is_some() || self.knowledge_sources.is_empty() {
+            return Ok(());
+        }
+        let sources = self
+            .knowledge_sources
+            .iter()
+            .map(|s| Box::new(s.clone()) as Box<dyn BaseKnowledgeSource>)
+            .collect();
+        let knowledge = Knowledge::new(
+            sources,
+            self.embedder.clone(),
+            Some("crew".to_string()),
+            None,
+        );
+        knowledge
+            .add_sources()
+            .map_err(|e| format!("Failed to load the crew's knowledge: {}", e))?;
+        self.knowledge = Some(Arc::new(knowledge));
+        Ok(())
     }
 
     /// Wire up agent executors for all tasks.
@@ -655,11 +1497,25 @@ impl Crew {
         // Clone the agent_objects map to avoid borrow conflicts
         let agent_locks: HashMap<String, Arc<std::sync::RwLock<Agent>>> =
             self.agent_objects.clone();
+        if self.cache {
+            for agent in agent_locks.values() {
+                if let Ok(mut agent) = agent.write() {
+                    agent.set_cache_handler(self.cache_handler.clone());
+                }
+            }
+        }
+        self.share_rpm_controller(&agent_locks);
+        self.share_policy(&agent_locks);
+        self.share_step_callback(&agent_locks);
+        self.share_embedder(&agent_locks);
+        self.share_knowledge(&agent_locks);
+        let budget = self.exploration_budget.clone();
+        let hooks = self.llm_call_hooks.clone();
 
         for task in &mut self.tasks {
             // Clone the role to avoid borrowing task immutably while passing it mutably
             if let Some(role) = task.agent.clone() {
-                Self::wire_task_executor_static(task, &role, &agent_locks);
+                Self::wire_task_executor_static(task, &role, &agent_locks, budget.as_ref(), &hooks);
             }
         }
     }
@@ -672,10 +1528,14 @@ impl Crew {
         task: &mut Task,
         role: &str,
         agent_objects: &HashMap<String, Arc<std::sync::RwLock<Agent>>>,
+        exploration_budget: Option<&ExplorationBudget>,
+        llm_call_hooks: &LLMCallHooks,
     ) {
         // Look up the agent in the registry
         if let Some(agent_lock) = agent_objects.get(role) {
             let agent_clone = agent_lock.clone();
+            let exploration_budget = exploration_budget.cloned();
+            let llm_call_hooks = llm_call_hooks.clone();
 
             // Create the executor callback
             task.set_agent_executor(
@@ -684,12 +1544,24 @@ impl Crew {
                         .write()
                         .map_err(|e| format!("Failed to lock agent: {}", e))?;
 
+                    // Apply the crew's exploration budget for this task only
+                    let agent_budget = agent.exploration_budget.clone();
+                    if agent_budget.is_none() {
+                        agent.exploration_budget = exploration_budget.clone();
+                    }
+                    // Run the crew's LLM call hooks after the agent's own
+                    let agent_hooks = agent.llm_call_hooks.clone();
+                    agent.llm_call_hooks.extend(&llm_call_hooks);
+
                     // Execute the task through the agent
                     let result = agent.execute_task(
                         prompt,
                         context,
                         if tools.is_empty() { None } else { Some(tools) },
-                    )?;
+                    );
+                    agent.exploration_budget = agent_budget;
+                    agent.llm_call_hooks = agent_hooks;
+                    let result = result?;
 
                     // Convert agent's last_messages to LLMMessage structs
                     let messages: Vec<LLMMessage> = agent
@@ -714,6 +1586,21 @@ impl Crew {
         }
     }
 
+    /// Build the provenance manifest for `output` and attach it to task output files.
+    fn attach_provenance(&self, config: &ProvenanceConfig, output: &str) -> ProvenanceManifest {
+        let manifest = ProvenanceManifest::for_crew(self, self._inputs.as_ref(), output);
+        for output_file in self.tasks.iter().filter_map(|t| t.output_file.as_ref()) {
+            let path = std::path::Path::new(output_file);
+            if !path.exists() {
+                continue;
+            }
+            if let Err(e) = manifest.attach_to_file(path, config) {
+                log::warn!("Failed to attach provenance to {}: {}", output_file, e);
+            }
+        }
+        manifest
+    }
+
     /// Create CrewOutput from task outputs.
     fn create_crew_output(&mut self, task_outputs: Vec<TaskOutput>) -> Result<CrewOutput, String> {
         if task_outputs.is_empty() {
@@ -722,11 +1609,14 @@ impl Crew {
 
         let valid_outputs: Vec<&TaskOutput> =
             task_outputs.iter().filter(|t| !t.raw.is_empty()).collect();
-        if valid_outputs.is_empty() {
-            return Err("No valid task outputs available to create crew output.".to_string());
-        }
+        // Tasks that timed out have no output but are still reported.
+        let final_task_output = match valid_outputs.last() {
+            Some(output) => *output,
+            None => task_outputs.iter().rfind(|t| t.timed_out).ok_or_else(|| {
+                "No valid task outputs available to create crew output.".to_string()
+            })?,
+        };
 
-        let final_task_output = valid_outputs.last().unwrap();
         let token_usage = self.calculate_usage_metrics();
         self.token_usage = Some(token_usage.clone());
 
@@ -736,10 +1626,82 @@ impl Crew {
             json_dict: final_task_output.json_dict.clone(),
             tasks_output: task_outputs,
             token_usage,
+            feature_flags: Default::default(),
+            profile: None,
+            provenance: None,
         })
     }
 }
 
+/// Kickoff inputs as the JSON values events carry.
+fn json_inputs(
+    inputs: Option<&HashMap<String, String>>,
+) -> Option<HashMap<String, serde_json::Value>> {
+    inputs.map(|inputs| {
+        inputs
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+            .collect()
+    })
+}
+
+/// Fail if a task's `context` names a task that does not run before it.
+fn validate_task_context(tasks: &[Task]) -> Result<(), String> {
+    for (i, task) in tasks.iter().enumerate() {
+        let mut context = task.context.iter().flatten();
+        if let Some(id) = context.find(|id| !tasks[..i].iter().any(|t| t.id == **id)) {
+            return Err(format!(
+                "Task '{}' has task {} in its context, which does not run before it",
+                task.name.as_deref().unwrap_or(&task.description),
+                id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The context of `task`, from the `outputs` of the tasks with `ids`: the
+/// outputs of the tasks its `context` names, each under a header, or else
+/// of all tasks so far. Tasks that timed out are left out.
+fn task_context(task: &Task, ids: &[Uuid], outputs: &[Option<TaskOutput>]) -> Option<String> {
+    let sections: Vec<String> = match &task.context {
+        Some(context) => context
+            .iter()
+            .filter_map(|id| outputs[ids.iter().position(|i| i == id)?].as_ref())
+            .filter(|output| !output.timed_out)
+            .map(|output| {
+                let name = output.name.as_deref().unwrap_or(&output.description);
+                format!("Output of task '{}':\n{}", name, output.raw)
+            })
+            .collect(),
+        None => outputs
+            .iter()
+            .flatten()
+            .filter(|output| !output.timed_out)
+            .map(|output| output.raw.clone())
+            .collect(),
+    };
+    (!sections.is_empty()).then(|| sections.join("\n\n---\n\n"))
+}
+
+/// Wait for a task run by [`Crew::run_tasks`] on its own thread, adding its
+/// usage to this thread's.
+fn join_task(
+    handle: std::thread::ScopedJoinHandle<'_, (Result<TaskOutput, String>, UsageMetrics)>,
+) -> Result<TaskOutput, String> {
+    let (output, usage) = handle
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+    usage_metrics::record(&usage);
+    output
+}
+
+/// Output `state` records for `task`, if it completed in the resumed run.
+fn resumed_output<'a>(state: Option<&'a ExecutionState>, task: &Task) -> Option<&'a str> {
+    let name = task.name.as_deref().unwrap_or(&task.description);
+    state?.completed_output(&task.id.to_string(), name)
+}
+
 impl std::fmt::Display for Crew {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -752,3 +1714,125 @@ impl std::fmt::Display for Crew {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{TaskState, TaskStatus};
+    use crate::tasks::output_format::OutputFormat;
+    use std::sync::Mutex;
+
+    /// A task answering `answer` after `delay_ms`, recording its context.
+    fn task(
+        name: &str,
+        answer: &'static str,
+        delay_ms: u64,
+        seen: &Arc<Mutex<Vec<String>>>,
+    ) -> Task {
+        let mut task = Task::new(format!("{} task", name), "An answer".into());
+        task.name = Some(name.to_string());
+        task.agent = Some("Researcher".to_string());
+        let seen = seen.clone();
+        let name = name.to_string();
+        task.set_agent_executor(move |_, context, _| {
+            std::thread::sleep(Duration::from_millis(delay_ms));
+            let context = context.unwrap_or("none").replace('\n', " ");
+            seen.lock().unwrap().push(format!("{}: {}", name, context));
+            Ok((answer.to_string(), Vec::new()))
+        });
+        task
+    }
+
+    #[test]
+    fn test_task_context_chaining() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut research = task("research", "facts", 100, &seen);
+        research.async_execution = true;
+        let mut figures = task("figures", "numbers", 0, &seen);
+        figures.async_execution = true;
+        let mut summary = task("summary", "summed up", 0, &seen);
+        summary.context = Some(vec![research.id]);
+        let review = task("review", "approved", 0, &seen);
+        let (research_id, summary_id) = (research.id, summary.id);
+
+        let mut crew = Crew::new(vec![research, figures, summary, review], Vec::new());
+        let output = crew.kickoff(None).unwrap();
+        let raws: Vec<&str> = output.tasks_output.iter().map(|o| o.raw.as_str()).collect();
+        assert_eq!(raws, ["facts", "numbers", "summed up", "approved"]);
+        let seen = seen.lock().unwrap();
+        // The slow research ran alongside the figures.
+        assert_eq!(seen[..2], ["figures: none", "research: none"]);
+        assert_eq!(seen[2], "summary: Output of task 'research': facts");
+        assert_eq!(seen[3], "review: facts  ---  numbers  ---  summed up");
+
+        crew.tasks[0].context = Some(vec![summary_id]);
+        let err = crew.kickoff(None).unwrap_err();
+        assert!(err.contains("does not run before it"), "{}", err);
+        crew.tasks[0].context = None;
+        crew.tasks[2].context = Some(vec![research_id, Uuid::new_v4()]);
+        assert!(crew.kickoff(None).is_err());
+    }
+
+    #[test]
+    fn test_resume_restores_structured_output() {
+        let mut rate = Task::new("Rate the draft".into(), "A score".into());
+        rate.name = Some("rate".to_string());
+        rate.agent = Some("Reviewer".to_string());
+        rate.output_json = Some("Score".to_string());
+        rate.set_agent_executor(|_, _, _| Err("should not run again".to_string()));
+        let state = ExecutionState {
+            tasks: vec![TaskState {
+                task_id: None,
+                name: Some("rate".to_string()),
+                agent_role: Some("Reviewer".to_string()),
+                status: TaskStatus::Completed,
+                output: Some(r#"{"score": 7}"#.to_string()),
+                error: None,
+                started_at: None,
+                finished_at: None,
+                sequence: None,
+            }],
+            ..Default::default()
+        };
+
+        let mut crew = Crew::new(vec![rate], Vec::new());
+        let output = crew.resume(state).unwrap();
+        let restored = &output.tasks_output[0];
+        assert_eq!(restored.output_format, OutputFormat::JSON);
+        assert_eq!(restored.json_dict.as_ref().unwrap()["score"], 7);
+    }
+
+    #[test]
+    fn test_task_condition_and_guardrail_retry() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let research = task("research", "short", 0, &seen);
+        let mut follow_up = task("follow_up", "more", 0, &seen);
+        follow_up.condition = Some(Box::new(|output| output.raw.len() > 10));
+        let mut summary = Task::new("summary task".into(), "An answer".into());
+        summary.agent = Some("Researcher".to_string());
+        summary.set_agent_executor(|_, context, _| {
+            let retried = context.is_some_and(|c| c.contains("failed validation: Too long"));
+            let answer = if retried { "ok" } else { "far too long" };
+            Ok((answer.to_string(), Vec::new()))
+        });
+        summary.guardrail_fn = Some(Box::new(|output| {
+            (output.raw.len() < 5, "Too long".to_string())
+        }));
+
+        let mut crew = Crew::new(vec![research, follow_up, summary], Vec::new());
+        let output = crew.kickoff(None).unwrap();
+        let raws: Vec<&str> = output.tasks_output.iter().map(|o| o.raw.as_str()).collect();
+        assert_eq!(raws, ["short", "", "ok"]);
+        // The follow-up was skipped without running.
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert_eq!(crew.tasks[2].retry_count, 1);
+
+        crew.tasks[2].guardrail_fn = Some(Box::new(|_| (false, "Never good".to_string())));
+        let err = crew.kickoff(None).unwrap_err();
+        assert!(
+            err.contains("after 3 retries. Last error: Never good"),
+            "{}",
+            err
+        );
+    }
+}
@@ -6,10 +6,12 @@
 //! structured (Pydantic/JSON) output, individual task outputs, and
 //! token usage metrics.
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
+use crate::security::provenance::ProvenanceManifest;
 use crate::tasks::output_format::OutputFormat;
 use crate::tasks::task_output::TaskOutput;
 use crate::types::usage_metrics::UsageMetrics;
@@ -25,6 +27,9 @@ use crate::types::usage_metrics::UsageMetrics;
 /// * `json_dict` - JSON dict output of Crew.
 /// * `tasks_output` - Output of each task in execution order.
 /// * `token_usage` - Processed token summary across all tasks.
+/// * `feature_flags` - Feature flags checked during the execution and their states.
+/// * `profile` - Run profile the crew executed under, if any.
+/// * `provenance` - Provenance manifest, when the crew has provenance enabled.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrewOutput {
     /// Raw output of crew.
@@ -37,6 +42,15 @@ pub struct CrewOutput {
     pub tasks_output: Vec<TaskOutput>,
     /// Processed token summary.
     pub token_usage: UsageMetrics,
+    /// Feature flags checked during the execution and their states.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub feature_flags: BTreeMap<String, bool>,
+    /// Run profile the crew executed under, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Provenance manifest, when the crew has provenance enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ProvenanceManifest>,
 }
 
 impl Default for CrewOutput {
@@ -47,6 +61,9 @@ impl Default for CrewOutput {
             json_dict: None,
             tasks_output: Vec::new(),
             token_usage: UsageMetrics::new(),
+            feature_flags: BTreeMap::new(),
+            profile: None,
+            provenance: None,
         }
     }
 }
@@ -60,6 +77,9 @@ impl CrewOutput {
             json_dict: None,
             tasks_output,
             token_usage,
+            feature_flags: BTreeMap::new(),
+            profile: None,
+            provenance: None,
         }
     }
 
@@ -123,6 +143,75 @@ impl CrewOutput {
         }
         Err(format!("Key '{}' not found in CrewOutput.", key))
     }
+
+    /// Serialize the whole output, task outputs and token usage included,
+    /// as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Render a Markdown report: the final output, then each task's output
+    /// attributed to the agent that produced it, then token usage.
+    pub fn to_markdown_report(&self) -> String {
+        let mut report = String::from("# Crew Report\n\n## Final Output\n\n");
+        report.push_str(self.raw.trim());
+        report.push_str("\n\n## Tasks\n");
+
+        for (i, task) in self.tasks_output.iter().enumerate() {
+            let title = task
+                .name
+                .as_deref()
+                .or(task.summary.as_deref())
+                .unwrap_or(&task.description);
+            report.push_str(&format!("\n### {}. {}\n\n", i + 1, title));
+            report.push_str(&format!("**Agent:** {}\n", task.agent));
+            if task.timed_out {
+                report.push_str("**Status:** timed out\n");
+            }
+            report.push('\n');
+            report.push_str(task.raw.trim());
+            report.push('\n');
+        }
+
+        let usage = &self.token_usage;
+        report.push_str(&format!(
+            "\n## Token Usage\n\n\
+             - Total tokens: {}\n\
+             - Prompt tokens: {}\n\
+             - Completion tokens: {}\n\
+             - Successful requests: {}\n",
+            usage.total_tokens,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+            usage.successful_requests
+        ));
+        report
+    }
+
+    /// Merge the structured (JSON or Pydantic) outputs of all tasks into one
+    /// object, in task order; later tasks win on conflicting keys.
+    pub fn merged_dict(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut merged = serde_json::Map::new();
+        for task in &self.tasks_output {
+            merged.extend(task.to_dict());
+        }
+        merged
+    }
+
+    /// Deserialize the merged structured outputs of all tasks (see
+    /// [`merged_dict`](Self::merged_dict)) into a typed struct.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no task produced structured output or the merged
+    /// object does not match `T`.
+    pub fn merge_into<T: DeserializeOwned>(&self) -> Result<T, String> {
+        let merged = self.merged_dict();
+        if merged.is_empty() {
+            return Err("No structured task output found in CrewOutput.".to_string());
+        }
+        serde_json::from_value(serde_json::Value::Object(merged)).map_err(|e| e.to_string())
+    }
 }
 
 impl fmt::Display for CrewOutput {
@@ -136,3 +225,67 @@ impl fmt::Display for CrewOutput {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn structured_output(agent: &str, raw: &str, dict: serde_json::Value) -> TaskOutput {
+        let mut output = TaskOutput::new(
+            format!("Task done by {}", agent),
+            agent.to_string(),
+            raw.to_string(),
+            OutputFormat::JSON,
+        );
+        output.json_dict = serde_json::from_value(dict).ok();
+        output
+    }
+
+    #[test]
+    fn test_export_helpers() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Report {
+            topic: String,
+            sources: u32,
+            verdict: String,
+        }
+
+        let mut research = structured_output(
+            "Researcher",
+            "Found 3 sources",
+            serde_json::json!({"topic": "Rust", "sources": 3, "verdict": "draft"}),
+        );
+        research.name = Some("research".to_string());
+        let review = structured_output(
+            "Reviewer",
+            "Approved",
+            serde_json::json!({"verdict": "approved"}),
+        );
+        let usage = UsageMetrics {
+            total_tokens: 120,
+            ..Default::default()
+        };
+        let output = CrewOutput::new("Approved".to_string(), vec![research, review], usage);
+
+        let report: Report = output.merge_into().unwrap();
+        assert_eq!(
+            report,
+            Report {
+                topic: "Rust".to_string(),
+                sources: 3,
+                verdict: "approved".to_string(),
+            }
+        );
+
+        let markdown = output.to_markdown_report();
+        assert!(markdown.contains("### 1. research\n\n**Agent:** Researcher"));
+        assert!(markdown.contains("**Agent:** Reviewer\n\nApproved"));
+        assert!(markdown.contains("- Total tokens: 120"));
+
+        let json: serde_json::Value = serde_json::from_str(&output.to_json().unwrap()).unwrap();
+        assert_eq!(json["tasks_output"][1]["agent"], "Reviewer");
+
+        let plain = CrewOutput::new("text".to_string(), Vec::new(), UsageMetrics::new());
+        assert!(plain.merge_into::<Report>().is_err());
+    }
+}
@@ -9,6 +9,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::rag::embeddings::EmbedderConfig;
 use crate::tasks::task_output::TaskOutput;
 
 /// Data container for prepared task execution information.
@@ -138,7 +139,7 @@ pub fn enable_agent_streaming(agent_roles: &[String]) {
 /// * `function_calling_llm` - Default function calling LLM for agents.
 pub fn setup_agents(
     agents: &[String],
-    _crew_embedder: Option<&HashMap<String, serde_json::Value>>,
+    _crew_embedder: Option<&EmbedderConfig>,
     _function_calling_llm: Option<&str>,
 ) {
     for role in agents {
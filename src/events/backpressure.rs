@@ -0,0 +1,378 @@
+//! Bounded, isolated event subscribers with overflow policies.
+//!
+//! Handlers registered with [`CrewAIEventsBus::on_bounded`] do not run on the
+//! shared event runtime. Each gets its own queue of fixed capacity, drained
+//! in order by a dedicated worker thread, so a slow subscriber (e.g. a
+//! webhook) only ever backs up its own queue. When the queue is full the
+//! subscriber's [`OverflowPolicy`] decides what happens to new events, and
+//! [`SubscriberMetrics`] reports queue depth, drops and lag.
+//!
+//! [`CrewAIEventsBus::on_bounded`]: crate::events::event_bus::CrewAIEventsBus::on_bounded
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::base_event::BaseEventData;
+use crate::events::event_bus::{HandlerId, SyncHandler};
+
+/// What a bounded subscriber does with a new event when its queue is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Evict the oldest queued event to make room.
+    DropOldest,
+    /// Discard the new event.
+    DropNewest,
+    /// Block the emitter until there is room, or until `timeout_ms` elapses
+    /// (then the new event is discarded).
+    ///
+    /// Blocking lets a slow subscriber stall execution; emitting from inside
+    /// the same subscriber's handler with a full queue deadlocks without a
+    /// timeout.
+    Block {
+        /// Maximum time to wait for room, in milliseconds (`None` = forever).
+        timeout_ms: Option<u64>,
+    },
+    /// Enqueue only every `every`-th event; on a full queue, discard it.
+    Sample {
+        /// Sampling interval (1 = every event).
+        every: u32,
+    },
+}
+
+/// Queue configuration for a bounded subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberConfig {
+    /// Maximum number of queued events.
+    pub capacity: usize,
+    /// Behaviour when the queue is full.
+    pub overflow: OverflowPolicy,
+}
+
+impl SubscriberConfig {
+    /// Create a config with the given capacity and overflow policy.
+    pub fn new(capacity: usize, overflow: OverflowPolicy) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            overflow,
+        }
+    }
+}
+
+impl Default for SubscriberConfig {
+    fn default() -> Self {
+        Self::new(1024, OverflowPolicy::DropOldest)
+    }
+}
+
+/// Point-in-time statistics for a bounded subscriber.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubscriberMetrics {
+    /// Handler name.
+    pub name: String,
+    /// Queue capacity.
+    pub capacity: usize,
+    /// Events currently queued.
+    pub queued: usize,
+    /// Events delivered to the handler.
+    pub delivered: u64,
+    /// Events discarded or evicted because the queue was full.
+    pub dropped: u64,
+    /// Events skipped by the sampling policy.
+    pub sampled_out: u64,
+    /// Queue wait of the most recently delivered event.
+    pub last_lag: Duration,
+    /// Longest queue wait of any delivered event.
+    pub max_lag: Duration,
+}
+
+struct QueuedEvent {
+    source: Arc<dyn Any + Send + Sync>,
+    event: Arc<BaseEventData>,
+    enqueued_at: Instant,
+}
+
+#[derive(Default)]
+struct QueueState {
+    items: VecDeque<QueuedEvent>,
+    /// The worker is running the handler.
+    busy: bool,
+    closed: bool,
+    /// Events offered so far (for sampling).
+    offered: u64,
+    metrics: SubscriberMetrics,
+}
+
+/// A handler with its own bounded queue and worker thread.
+pub(crate) struct BoundedSubscriber {
+    pub(crate) id: HandlerId,
+    config: SubscriberConfig,
+    handler: SyncHandler,
+    state: Mutex<QueueState>,
+    /// Signalled on every queue change (push, pop, delivery, close).
+    changed: Condvar,
+}
+
+impl BoundedSubscriber {
+    /// Create a subscriber and start its worker thread.
+    pub(crate) fn spawn(
+        id: HandlerId,
+        handler: SyncHandler,
+        config: SubscriberConfig,
+    ) -> std::io::Result<Arc<Self>> {
+        let subscriber = Arc::new(Self::new(id, handler, config));
+        let worker = subscriber.clone();
+        std::thread::Builder::new()
+            .name(format!("crewai-events-{}", subscriber.id.name))
+            .spawn(move || worker.run())?;
+        Ok(subscriber)
+    }
+
+    fn new(id: HandlerId, handler: SyncHandler, config: SubscriberConfig) -> Self {
+        let metrics = SubscriberMetrics {
+            name: id.name.clone(),
+            capacity: config.capacity,
+            ..Default::default()
+        };
+        Self {
+            id,
+            config,
+            handler,
+            state: Mutex::new(QueueState {
+                metrics,
+                ..Default::default()
+            }),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Offer an event to the queue, applying the overflow policy.
+    pub(crate) fn offer(&self, source: Arc<dyn Any + Send + Sync>, event: Arc<BaseEventData>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.closed {
+            return;
+        }
+
+        if let OverflowPolicy::Sample { every } = self.config.overflow {
+            state.offered += 1;
+            if !(state.offered - 1).is_multiple_of(u64::from(every.max(1))) {
+                state.metrics.sampled_out += 1;
+                return;
+            }
+        }
+
+        let deadline = match self.config.overflow {
+            OverflowPolicy::Block {
+                timeout_ms: Some(ms),
+            } => Some(Instant::now() + Duration::from_millis(ms)),
+            _ => None,
+        };
+        while state.items.len() >= self.config.capacity {
+            match self.config.overflow {
+                OverflowPolicy::DropOldest => {
+                    state.items.pop_front();
+                    state.metrics.dropped += 1;
+                }
+                OverflowPolicy::DropNewest | OverflowPolicy::Sample { .. } => {
+                    state.metrics.dropped += 1;
+                    return;
+                }
+                OverflowPolicy::Block { .. } => {
+                    state = match deadline {
+                        None => self.changed.wait(state).unwrap_or_else(|e| e.into_inner()),
+                        Some(deadline) => {
+                            let remaining = deadline.saturating_duration_since(Instant::now());
+                            if remaining.is_zero() {
+                                state.metrics.dropped += 1;
+                                log::warn!(
+                                    "[CrewAIEventsBus] Subscriber {} still full after {:?}; dropping event",
+                                    self.id.name,
+                                    self.config.overflow
+                                );
+                                return;
+                            }
+                            self.changed
+                                .wait_timeout(state, remaining)
+                                .unwrap_or_else(|e| e.into_inner())
+                                .0
+                        }
+                    };
+                    if state.closed {
+                        return;
+                    }
+                }
+            }
+        }
+
+        state.items.push_back(QueuedEvent {
+            source,
+            event,
+            enqueued_at: Instant::now(),
+        });
+        self.changed.notify_all();
+    }
+
+    /// Worker loop: deliver queued events in order until closed.
+    fn run(&self) {
+        loop {
+            let item = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                loop {
+                    if state.closed {
+                        return;
+                    }
+                    if let Some(item) = state.items.pop_front() {
+                        state.busy = true;
+                        break item;
+                    }
+                    state = self.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+                }
+            };
+            // Room was made; wake blocked emitters.
+            self.changed.notify_all();
+
+            let lag = item.enqueued_at.elapsed();
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                (self.handler)(item.source.as_ref(), item.event.as_ref());
+            }));
+            if let Err(e) = result {
+                log::error!("[CrewAIEventsBus] Handler {} panic: {:?}", self.id.name, e);
+            }
+
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.busy = false;
+            state.metrics.delivered += 1;
+            state.metrics.last_lag = lag;
+            state.metrics.max_lag = state.metrics.max_lag.max(lag);
+            self.changed.notify_all();
+        }
+    }
+
+    /// Block until the queue is empty and the handler is idle.
+    pub(crate) fn wait_idle(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        while !state.closed && (state.busy || !state.items.is_empty()) {
+            state = self.changed.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Stop the worker, discarding queued events.
+    pub(crate) fn close(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.closed = true;
+        state.items.clear();
+        self.changed.notify_all();
+    }
+
+    /// Current statistics.
+    pub(crate) fn metrics(&self) -> SubscriberMetrics {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        SubscriberMetrics {
+            queued: state.items.len(),
+            ..state.metrics.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn event(n: u64) -> Arc<BaseEventData> {
+        let mut data = BaseEventData::new("test_event");
+        data.emission_sequence = Some(n);
+        Arc::new(data)
+    }
+
+    fn queued_sequences(subscriber: &BoundedSubscriber) -> Vec<u64> {
+        let state = subscriber.state.lock().unwrap();
+        state
+            .items
+            .iter()
+            .filter_map(|q| q.event.emission_sequence)
+            .collect()
+    }
+
+    /// A subscriber without a worker, so offers just fill the queue.
+    fn idle_subscriber(config: SubscriberConfig) -> BoundedSubscriber {
+        BoundedSubscriber::new(HandlerId::new("test"), Arc::new(|_, _| {}), config)
+    }
+
+    fn offer_all(subscriber: &BoundedSubscriber, n: u64) {
+        for i in 1..=n {
+            subscriber.offer(Arc::new(()), event(i));
+        }
+    }
+
+    #[test]
+    fn test_drop_policies() {
+        let oldest = idle_subscriber(SubscriberConfig::new(2, OverflowPolicy::DropOldest));
+        offer_all(&oldest, 4);
+        assert_eq!(queued_sequences(&oldest), vec![3, 4]);
+        assert_eq!(oldest.metrics().dropped, 2);
+
+        let newest = idle_subscriber(SubscriberConfig::new(2, OverflowPolicy::DropNewest));
+        offer_all(&newest, 4);
+        assert_eq!(queued_sequences(&newest), vec![1, 2]);
+        assert_eq!(newest.metrics().dropped, 2);
+
+        let sample = idle_subscriber(SubscriberConfig::new(
+            10,
+            OverflowPolicy::Sample { every: 3 },
+        ));
+        offer_all(&sample, 7);
+        assert_eq!(queued_sequences(&sample), vec![1, 4, 7]);
+        assert_eq!(sample.metrics().sampled_out, 4);
+    }
+
+    #[test]
+    fn test_block_times_out() {
+        let blocking = idle_subscriber(SubscriberConfig::new(
+            1,
+            OverflowPolicy::Block {
+                timeout_ms: Some(20),
+            },
+        ));
+        let started = Instant::now();
+        offer_all(&blocking, 2);
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(queued_sequences(&blocking), vec![1]);
+        assert_eq!(blocking.metrics().dropped, 1);
+    }
+
+    #[test]
+    fn test_worker_delivers_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (seen_h, calls_h) = (seen.clone(), calls.clone());
+        let subscriber = BoundedSubscriber::spawn(
+            HandlerId::new("worker"),
+            Arc::new(move |_, event: &dyn crate::events::BaseEvent| {
+                calls_h.fetch_add(1, Ordering::SeqCst);
+                seen_h
+                    .lock()
+                    .unwrap()
+                    .push(event.emission_sequence().unwrap());
+            }),
+            SubscriberConfig::new(8, OverflowPolicy::Block { timeout_ms: None }),
+        )
+        .unwrap();
+
+        offer_all(&subscriber, 20);
+        subscriber.wait_idle();
+        assert_eq!(*seen.lock().unwrap(), (1..=20).collect::<Vec<_>>());
+        let metrics = subscriber.metrics();
+        assert_eq!(metrics.delivered, 20);
+        assert_eq!(metrics.dropped, 0);
+        assert_eq!(metrics.queued, 0);
+
+        subscriber.close();
+        offer_all(&subscriber, 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 20);
+    }
+}
@@ -26,6 +26,17 @@ pub fn reset_emission_counter() {
     EMISSION_COUNTER.with(|c| c.store(1, Ordering::Relaxed));
 }
 
+/// The emission sequence number the current thread will use next.
+pub fn peek_emission_sequence() -> u64 {
+    EMISSION_COUNTER.with(|c| c.load(Ordering::Relaxed))
+}
+
+/// Continue the current thread's emission sequence at `next`, e.g. on a
+/// worker thread emitting on behalf of another.
+pub fn set_emission_counter(next: u64) {
+    EMISSION_COUNTER.with(|c| c.store(next, Ordering::Relaxed));
+}
+
 // ---------------------------------------------------------------------------
 // BaseEvent trait
 // ---------------------------------------------------------------------------
@@ -54,6 +65,14 @@ pub trait BaseEvent: Send + Sync + std::fmt::Debug {
     /// Arbitrary fingerprint metadata.
     fn fingerprint_metadata(&self) -> Option<&HashMap<String, serde_json::Value>>;
 
+    /// Set the source entity fingerprint and its metadata.
+    fn set_source_fingerprint(
+        &mut self,
+        _fingerprint: Option<String>,
+        _metadata: Option<HashMap<String, serde_json::Value>>,
+    ) {
+    }
+
     /// Task ID associated with this event, if any.
     fn task_id(&self) -> Option<&str>;
 
@@ -89,6 +108,11 @@ pub trait BaseEvent: Send + Sync + std::fmt::Debug {
 
     /// Set the emission sequence number.
     fn set_emission_sequence(&mut self, seq: Option<u64>);
+
+    /// The full event (including type-specific fields) as JSON, if available.
+    fn to_json(&self) -> Option<serde_json::Value> {
+        None
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -143,6 +167,11 @@ pub struct BaseEventData {
 
     /// Emission sequence number.
     pub emission_sequence: Option<u64>,
+
+    /// The full originating event as JSON, attached when the bus hands a
+    /// copy of the event to handlers.
+    #[serde(skip)]
+    pub payload: Option<serde_json::Value>,
 }
 
 impl BaseEventData {
@@ -165,6 +194,7 @@ impl BaseEventData {
             previous_event_id: None,
             triggered_by_event_id: None,
             emission_sequence: None,
+            payload: None,
         }
     }
 }
@@ -188,6 +218,14 @@ impl BaseEvent for BaseEventData {
     fn fingerprint_metadata(&self) -> Option<&HashMap<String, serde_json::Value>> {
         self.fingerprint_metadata.as_ref()
     }
+    fn set_source_fingerprint(
+        &mut self,
+        fingerprint: Option<String>,
+        metadata: Option<HashMap<String, serde_json::Value>>,
+    ) {
+        self.source_fingerprint = fingerprint;
+        self.fingerprint_metadata = metadata;
+    }
     fn task_id(&self) -> Option<&str> {
         self.task_id.as_deref()
     }
@@ -224,6 +262,9 @@ impl BaseEvent for BaseEventData {
     fn set_emission_sequence(&mut self, seq: Option<u64>) {
         self.emission_sequence = seq;
     }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        self.payload.clone()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -260,6 +301,14 @@ macro_rules! impl_base_event {
             ) -> Option<&std::collections::HashMap<String, serde_json::Value>> {
                 self.base.fingerprint_metadata.as_ref()
             }
+            fn set_source_fingerprint(
+                &mut self,
+                fingerprint: Option<String>,
+                metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+            ) {
+                self.base.source_fingerprint = fingerprint;
+                self.base.fingerprint_metadata = metadata;
+            }
             fn task_id(&self) -> Option<&str> {
                 self.base.task_id.as_deref()
             }
@@ -296,6 +345,9 @@ macro_rules! impl_base_event {
             fn set_emission_sequence(&mut self, seq: Option<u64>) {
                 self.base.emission_sequence = seq;
             }
+            fn to_json(&self) -> Option<serde_json::Value> {
+                serde_json::to_value(self).ok()
+            }
         }
     };
 }
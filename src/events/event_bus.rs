@@ -6,6 +6,10 @@
 //! handling of events throughout the CrewAI system. Handlers are dispatched
 //! via a background Tokio runtime. Dependency-aware execution ordering is
 //! supported through [`Depends`] and the [`handler_graph`] module.
+//!
+//! Handlers registered with [`CrewAIEventsBus::on_bounded`] instead get an
+//! isolated bounded queue with an overflow policy; see the
+//! [`backpressure`](crate::events::backpressure) module.
 
 use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
@@ -15,6 +19,7 @@ use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use tokio::runtime::{Builder, Runtime};
 use tokio::task::JoinHandle;
 
+use crate::events::backpressure::{BoundedSubscriber, SubscriberConfig, SubscriberMetrics};
 use crate::events::base_event::{get_next_emission_sequence, BaseEvent};
 use crate::events::event_context::{
     get_current_parent_id, get_enclosing_parent_id, get_last_event_id, get_triggering_event_id,
@@ -22,6 +27,7 @@ use crate::events::event_context::{
     SCOPE_ENDING_EVENTS, SCOPE_STARTING_EVENTS, VALID_EVENT_PAIRS,
 };
 use crate::events::handler_graph::build_execution_plan;
+use crate::utilities::perf::{self, HotPath};
 
 // ---------------------------------------------------------------------------
 // Global singleton
@@ -146,6 +152,9 @@ pub struct CrewAIEventsBus {
     /// Cached execution plans keyed by event `TypeId`.
     execution_plan_cache: RwLock<HashMap<TypeId, ExecutionPlan>>,
 
+    /// Bounded, isolated subscribers keyed by event `TypeId`.
+    bounded: RwLock<HashMap<TypeId, Vec<Arc<BoundedSubscriber>>>>,
+
     /// Background Tokio runtime for async handler dispatch.
     runtime: Runtime,
 
@@ -175,6 +184,7 @@ impl CrewAIEventsBus {
             CrewAIEventsBus {
                 handlers: RwLock::new(HashMap::new()),
                 execution_plan_cache: RwLock::new(HashMap::new()),
+                bounded: RwLock::new(HashMap::new()),
                 runtime,
                 pending: Mutex::new(Vec::new()),
                 shutting_down: RwLock::new(false),
@@ -241,9 +251,61 @@ impl CrewAIEventsBus {
         id
     }
 
+    /// Register a handler with its own bounded queue for event type `E`.
+    ///
+    /// The handler runs on a dedicated worker thread, in emission order, so
+    /// a slow handler only backs up its own queue; `config` decides what
+    /// happens to new events when that queue is full. Bounded handlers do
+    /// not take part in [`Depends`] ordering.
+    pub fn on_bounded<E: BaseEvent + 'static>(
+        &self,
+        name: impl Into<String>,
+        handler: impl Fn(&dyn Any, &dyn BaseEvent) + Send + Sync + 'static,
+        config: SubscriberConfig,
+    ) -> HandlerId {
+        let id = HandlerId::new(name);
+        let handler: SyncHandler = Arc::new(handler);
+        match BoundedSubscriber::spawn(id.clone(), handler.clone(), config) {
+            Ok(subscriber) => {
+                let mut map = self.bounded.write().unwrap();
+                map.entry(TypeId::of::<E>()).or_default().push(subscriber);
+                id
+            }
+            Err(e) => {
+                log::error!(
+                    "[CrewAIEventsBus] Failed to start worker for {}: {e}; registering unbounded",
+                    id.name
+                );
+                let name = id.name.clone();
+                self.register_handler::<E>(name, handler)
+            }
+        }
+    }
+
+    /// Queue statistics for every bounded handler.
+    pub fn subscriber_metrics(&self) -> Vec<SubscriberMetrics> {
+        let map = self.bounded.read().unwrap();
+        map.values().flatten().map(|s| s.metrics()).collect()
+    }
+
     /// Unregister a handler by its [`HandlerId`].
     pub fn off<E: BaseEvent + 'static>(&self, handler_id: &HandlerId) {
         let type_id = TypeId::of::<E>();
+        {
+            let mut map = self.bounded.write().unwrap();
+            if let Some(subscribers) = map.get_mut(&type_id) {
+                subscribers.retain(|s| {
+                    let keep = s.id != *handler_id;
+                    if !keep {
+                        s.close();
+                    }
+                    keep
+                });
+                if subscribers.is_empty() {
+                    map.remove(&type_id);
+                }
+            }
+        }
         {
             let mut map = self.handlers.write().unwrap();
             if let Some(entries) = map.get_mut(&type_id) {
@@ -269,6 +331,7 @@ impl CrewAIEventsBus {
     /// the Python implementation, then dispatches handlers on the background
     /// runtime.
     pub fn emit<E: BaseEvent + 'static>(&self, source: Arc<dyn Any + Send + Sync>, event: &mut E) {
+        let _span = perf::span(HotPath::EventDispatch);
         // -- chain tracking ------------------------------------------------
         event.set_previous_event_id(get_last_event_id());
         event.set_triggered_by_event_id(get_triggering_event_id());
@@ -312,11 +375,19 @@ impl CrewAIEventsBus {
 
         let entries: Vec<HandlerEntry> = {
             let map = self.handlers.read().unwrap();
-            match map.get(&type_id) {
-                Some(v) => v.clone(),
-                None => return,
-            }
+            map.get(&type_id).cloned().unwrap_or_default()
         };
+        let bounded: Vec<Arc<BoundedSubscriber>> = {
+            let map = self.bounded.read().unwrap();
+            map.get(&type_id).cloned().unwrap_or_default()
+        };
+
+        if !bounded.is_empty() {
+            let event_data = serialize_event(event);
+            for subscriber in &bounded {
+                subscriber.offer(source.clone(), event_data.clone());
+            }
+        }
 
         if entries.is_empty() {
             return;
@@ -416,8 +487,18 @@ impl CrewAIEventsBus {
 
     /// Block until all pending event handlers complete.
     ///
+    /// Also waits for every bounded handler's queue to drain.
+    ///
     /// Returns `true` if all handlers completed, `false` if errors occurred.
     pub fn flush(&self) -> bool {
+        let bounded: Vec<Arc<BoundedSubscriber>> = {
+            let map = self.bounded.read().unwrap();
+            map.values().flatten().cloned().collect()
+        };
+        for subscriber in bounded {
+            subscriber.wait_idle();
+        }
+
         let handles: Vec<JoinHandle<()>> = {
             let mut pending = self.pending.lock().unwrap();
             std::mem::take(&mut *pending)
@@ -457,6 +538,13 @@ impl CrewAIEventsBus {
             let mut map = self.handlers.write().unwrap();
             map.clear();
         }
+        {
+            let mut map = self.bounded.write().unwrap();
+            for subscriber in map.values().flatten() {
+                subscriber.close();
+            }
+            map.clear();
+        }
         {
             let mut cache = self.execution_plan_cache.write().unwrap();
             cache.clear();
@@ -494,7 +582,7 @@ impl CrewAIEventsBus {
 use crate::events::base_event::BaseEventData;
 
 /// Serialize a `&dyn BaseEvent` into a sendable `Arc<BaseEventData>`.
-fn serialize_event(event: &dyn BaseEvent) -> Arc<BaseEventData> {
+pub(crate) fn serialize_event(event: &dyn BaseEvent) -> Arc<BaseEventData> {
     Arc::new(BaseEventData {
         event_id: event.event_id().to_string(),
         timestamp: event.timestamp(),
@@ -510,5 +598,6 @@ fn serialize_event(event: &dyn BaseEvent) -> Arc<BaseEventData> {
         previous_event_id: event.previous_event_id().map(|s| s.to_string()),
         triggered_by_event_id: event.triggered_by_event_id().map(|s| s.to_string()),
         emission_sequence: event.emission_sequence(),
+        payload: event.to_json(),
     })
 }
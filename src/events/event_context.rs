@@ -222,6 +222,38 @@ pub fn set_triggering_event_id(event_id: Option<String>) {
     TRIGGERING_EVENT_ID.with(|cell| *cell.borrow_mut() = event_id);
 }
 
+// ---------------------------------------------------------------------------
+// Public API – carrying the context to another thread
+// ---------------------------------------------------------------------------
+
+/// The event context of a thread, for a worker thread to continue it.
+#[derive(Debug, Clone, Default)]
+pub struct EventContextSnapshot {
+    stack: Vec<(String, String)>,
+    config: Option<EventContextConfig>,
+    last_event_id: Option<String>,
+    triggering_event_id: Option<String>,
+}
+
+/// The current thread's event context.
+pub fn snapshot_context() -> EventContextSnapshot {
+    EventContextSnapshot {
+        stack: EVENT_ID_STACK.with(|stack| stack.borrow().clone()),
+        config: EVENT_CONTEXT_CONFIG.with(|cell| cell.borrow().clone()),
+        last_event_id: get_last_event_id(),
+        triggering_event_id: get_triggering_event_id(),
+    }
+}
+
+/// Make `snapshot` the current thread's event context, so events emitted
+/// here get the parents they would have had on the snapshot's thread.
+pub fn restore_context(snapshot: EventContextSnapshot) {
+    EVENT_ID_STACK.with(|stack| *stack.borrow_mut() = snapshot.stack);
+    EVENT_CONTEXT_CONFIG.with(|cell| *cell.borrow_mut() = snapshot.config);
+    LAST_EVENT_ID.with(|cell| *cell.borrow_mut() = snapshot.last_event_id);
+    set_triggering_event_id(snapshot.triggering_event_id);
+}
+
 // ---------------------------------------------------------------------------
 // Event scope guard (RAII equivalent of Python contextmanager)
 // ---------------------------------------------------------------------------
@@ -22,12 +22,18 @@ pub mod base_event_listener;
 /// Singleton event bus implementation.
 pub mod event_bus;
 
+/// Bounded, isolated subscriber queues with overflow policies.
+pub mod backpressure;
+
 /// Event context management for parent-child relationship tracking.
 pub mod event_context;
 
 /// Dependency graph resolution for handler execution ordering.
 pub mod handler_graph;
 
+/// Execution state reconstructed by folding a run's events.
+pub mod state;
+
 // ---------------------------------------------------------------------------
 // Facade / convenience modules
 // ---------------------------------------------------------------------------
@@ -59,6 +65,7 @@ pub use base_event_listener::BaseEventListener;
 pub use event_bus::{CrewAIEventsBus, Depends, HandlerId, CREWAI_EVENT_BUS};
 pub use event_listener::{CrewAIBaseEvent, Listener};
 pub use handler_graph::CircularDependencyError;
+pub use state::{ExecutionState, RunStatus, TaskState, TaskStatus};
 
 // Agent events
 pub use types::agent_events::{
@@ -82,13 +89,14 @@ pub use types::task_events::{
 // Tool events
 pub use types::tool_events::{
     ToolExecutionErrorEvent, ToolSelectionErrorEvent, ToolUsageErrorEvent, ToolUsageEvent,
-    ToolUsageFinishedEvent, ToolUsageStartedEvent, ToolValidateInputErrorEvent,
+    ToolUsageFinishedEvent, ToolUsageLimitReachedEvent, ToolUsageStartedEvent,
+    ToolValidateInputErrorEvent,
 };
 
 // LLM events
 pub use types::llm_events::{
-    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent, LLMCallType,
-    LLMStreamChunkEvent,
+    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent, LLMCallThrottledEvent,
+    LLMCallType, LLMStreamChunkEvent,
 };
 
 // Flow events
@@ -0,0 +1,372 @@
+//! Execution state reconstructed from the event stream.
+//!
+//! [`ExecutionState`] is a snapshot of a crew run — its status, the state of
+//! each task, the final output and activity counters — derived purely by
+//! folding the run's events, in order, through [`ExecutionState::apply`].
+//! Anything that has the events of a run (the server's execution store, a
+//! persisted event log, a test capturing the bus) can rebuild the same
+//! snapshot without access to the crew itself, and a failed run can be
+//! resumed from it with [`Crew::resume`](crate::crew::Crew::resume).
+//!
+//! Events are consumed in their serialized form (a JSON object with a
+//! `type` field), so stored and live events go through the same reducer.
+//! The event bus delivers events concurrently, so they may arrive out of
+//! order: a late `*_started` event never moves a finished run or task back
+//! to running, and tasks are ordered by emission sequence rather than by
+//! arrival.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::event_bus::serialize_event;
+use super::BaseEvent;
+
+/// Status of a run as seen in its events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// No kickoff event seen yet.
+    #[default]
+    Pending,
+    /// Kicked off and not finished.
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Finished with an error.
+    Failed,
+}
+
+/// Status of a task within a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// Started and not finished.
+    Running,
+    /// Finished with an output.
+    Completed,
+    /// Finished with an error.
+    Failed,
+}
+
+/// State of one task, in the order tasks were first seen.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskState {
+    /// Task ID.
+    pub task_id: Option<String>,
+    /// Task name (its description when unnamed).
+    pub name: Option<String>,
+    /// Role of the agent that worked on the task.
+    pub agent_role: Option<String>,
+    /// Current status.
+    pub status: TaskStatus,
+    /// Raw output, once completed.
+    pub output: Option<String>,
+    /// Error message, if failed.
+    pub error: Option<String>,
+    /// When the task started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the task finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Emission sequence of the task's earliest event.
+    pub sequence: Option<u64>,
+}
+
+impl TaskState {
+    /// Whether the task has finished.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, TaskStatus::Completed | TaskStatus::Failed)
+    }
+}
+
+/// Snapshot of a crew run, folded from its events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionState {
+    /// Run status.
+    pub status: RunStatus,
+    /// Crew name.
+    pub crew_name: Option<String>,
+    /// Kickoff inputs.
+    pub inputs: BTreeMap<String, Value>,
+    /// Tasks, in the order they were emitted.
+    pub tasks: Vec<TaskState>,
+    /// Final crew output, once completed.
+    pub output: Option<Value>,
+    /// Error message, if failed.
+    pub error: Option<String>,
+    /// When the run started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the run finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Completed LLM calls.
+    pub llm_calls: u64,
+    /// Failed LLM calls.
+    pub llm_errors: u64,
+    /// Finished tool calls.
+    pub tool_calls: u64,
+    /// Failed tool calls.
+    pub tool_errors: u64,
+    /// Tokens reported by LLM calls (or the crew's total, once completed).
+    pub total_tokens: i64,
+    /// Number of events applied.
+    pub event_count: usize,
+    /// ID of the last event applied.
+    pub last_event_id: Option<String>,
+}
+
+impl ExecutionState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold serialized events, in order, into a snapshot.
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a Value>) -> Self {
+        let mut state = Self::new();
+        for event in events {
+            state.apply(event);
+        }
+        state
+    }
+
+    /// Apply an event as emitted on the bus.
+    pub fn apply_event(&mut self, event: &dyn BaseEvent) {
+        let data = serialize_event(event);
+        let mut value = data
+            .payload
+            .clone()
+            .unwrap_or_else(|| serde_json::to_value(&*data).unwrap_or(Value::Null));
+        if let Value::Object(map) = &mut value {
+            map.insert("type".to_string(), Value::String(data.event_type.clone()));
+        }
+        self.apply(&value);
+    }
+
+    /// Apply a serialized event. Events of unknown types only advance the
+    /// event count.
+    pub fn apply(&mut self, event: &Value) {
+        let str_field = |name: &str| event[name].as_str().map(str::to_string);
+        let timestamp = event
+            .get("timestamp")
+            .and_then(|t| serde_json::from_value::<DateTime<Utc>>(t.clone()).ok());
+        self.event_count += 1;
+        if let Some(id) = str_field("event_id") {
+            self.last_event_id = Some(id);
+        }
+
+        match event["type"].as_str().unwrap_or_default() {
+            "crew_kickoff_started" => {
+                if !self.is_finished() {
+                    self.status = RunStatus::Running;
+                }
+                self.crew_name = str_field("crew_name");
+                self.inputs = event["inputs"]
+                    .as_object()
+                    .map(|inputs| inputs.clone().into_iter().collect())
+                    .unwrap_or_default();
+                self.started_at = timestamp;
+            }
+            "crew_kickoff_completed" => {
+                self.status = RunStatus::Completed;
+                self.output = event.get("output").cloned();
+                if let Some(total) = event["total_tokens"].as_i64().filter(|&t| t > 0) {
+                    self.total_tokens = total;
+                }
+                self.finished_at = timestamp;
+            }
+            "crew_kickoff_failed" => {
+                self.status = RunStatus::Failed;
+                self.error = str_field("error");
+                self.finished_at = timestamp;
+            }
+            kind @ ("task_started" | "task_completed" | "task_failed") => {
+                let sequence = event["emission_sequence"].as_u64();
+                let task = self.task_entry(str_field("task_id"), str_field("task_name"));
+                task.sequence = match (task.sequence, sequence) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                match kind {
+                    "task_started" => {
+                        task.started_at = timestamp;
+                    }
+                    "task_completed" => {
+                        task.status = TaskStatus::Completed;
+                        task.output = Some(match &event["output"] {
+                            Value::String(raw) => raw.clone(),
+                            other => other.to_string(),
+                        });
+                        task.finished_at = timestamp;
+                    }
+                    _ => {
+                        task.status = TaskStatus::Failed;
+                        task.error = str_field("error");
+                        task.finished_at = timestamp;
+                    }
+                }
+                self.tasks.sort_by_key(|t| t.sequence.unwrap_or(u64::MAX));
+            }
+            "agent_execution_started" => {
+                let role = str_field("agent_role");
+                if let Some(task) = self
+                    .tasks
+                    .iter_mut()
+                    .rev()
+                    .find(|t| t.status == TaskStatus::Running && t.agent_role.is_none())
+                {
+                    task.agent_role = role;
+                }
+            }
+            "llm_call_completed" => {
+                self.llm_calls += 1;
+                if self.status != RunStatus::Completed {
+                    self.total_tokens += event["usage"]["total_tokens"].as_i64().unwrap_or(0);
+                }
+            }
+            "llm_call_failed" => self.llm_errors += 1,
+            "tool_usage_finished" => self.tool_calls += 1,
+            "tool_usage_error" => self.tool_errors += 1,
+            _ => {}
+        }
+    }
+
+    /// The task with `task_id`, or failing that the last one named `name`,
+    /// created when unknown.
+    fn task_entry(&mut self, task_id: Option<String>, name: Option<String>) -> &mut TaskState {
+        let index = match task_id.as_deref() {
+            Some(id) => self
+                .tasks
+                .iter()
+                .position(|t| t.task_id.as_deref() == Some(id)),
+            None => self
+                .tasks
+                .iter()
+                .rposition(|t| t.name.is_some() && t.name == name),
+        };
+        let index = index.unwrap_or_else(|| {
+            self.tasks.push(TaskState {
+                task_id,
+                name,
+                agent_role: None,
+                status: TaskStatus::Running,
+                output: None,
+                error: None,
+                started_at: None,
+                finished_at: None,
+                sequence: None,
+            });
+            self.tasks.len() - 1
+        });
+        &mut self.tasks[index]
+    }
+
+    /// Whether the run has finished.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, RunStatus::Completed | RunStatus::Failed)
+    }
+
+    /// Output of a completed task, looked up by ID and then by name (task
+    /// IDs change when a crew is rebuilt).
+    pub fn completed_output(&self, task_id: &str, name: &str) -> Option<&str> {
+        let completed = || {
+            self.tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Completed)
+        };
+        completed()
+            .find(|t| t.task_id.as_deref() == Some(task_id))
+            .or_else(|| completed().find(|t| t.name.as_deref() == Some(name)))
+            .and_then(|t| t.output.as_deref())
+    }
+
+    /// Kickoff inputs as the string map [`Crew::kickoff`] takes.
+    ///
+    /// [`Crew::kickoff`]: crate::crew::Crew::kickoff
+    pub fn kickoff_inputs(&self) -> Option<std::collections::HashMap<String, String>> {
+        (!self.inputs.is_empty()).then(|| {
+            self.inputs
+                .iter()
+                .map(|(k, v)| match v {
+                    Value::String(s) => (k.clone(), s.clone()),
+                    other => (k.clone(), other.to_string()),
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::types::crew_events::{
+        CrewKickoffCompletedEvent, CrewKickoffFailedEvent, CrewKickoffStartedEvent,
+    };
+    use crate::events::types::task_events::{
+        TaskCompletedEvent, TaskFailedEvent, TaskStartedEvent,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_fold_events_into_state() {
+        let id = |s: &str| Some(s.to_string());
+        let mut state = ExecutionState::new();
+        state.apply_event(&CrewKickoffStartedEvent::new(
+            id("research"),
+            Some(HashMap::from([("topic".to_string(), Value::from("AI"))])),
+        ));
+        state.apply_event(&TaskStartedEvent::new(id("t1"), id("Research"), None));
+        state.apply_event(&TaskCompletedEvent::new(
+            id("t1"),
+            id("Research"),
+            Value::from("notes"),
+        ));
+        state.apply_event(&TaskStartedEvent::new(id("t2"), id("Write"), None));
+        assert_eq!(state.status, RunStatus::Running);
+        assert_eq!(state.tasks[0].status, TaskStatus::Completed);
+        assert_eq!(state.tasks[1].status, TaskStatus::Running);
+
+        state.apply_event(&TaskFailedEvent::new(id("t2"), id("Write"), "boom".into()));
+        state.apply_event(&CrewKickoffFailedEvent::new(id("research"), "boom".into()));
+        assert_eq!(state.status, RunStatus::Failed);
+        assert_eq!(state.error.as_deref(), Some("boom"));
+        assert_eq!(state.event_count, 6);
+        assert_eq!(
+            state.completed_output("other-id", "Research"),
+            Some("notes")
+        );
+        assert_eq!(state.completed_output("t2", "Write"), None);
+        assert_eq!(
+            state.kickoff_inputs(),
+            Some(HashMap::from([("topic".to_string(), "AI".to_string())]))
+        );
+
+        // Snapshots round-trip through JSON.
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(
+            serde_json::from_value::<ExecutionState>(json).unwrap(),
+            state
+        );
+
+        state.apply_event(&CrewKickoffCompletedEvent::new(
+            id("research"),
+            Value::from("done"),
+            42,
+        ));
+        assert!(state.is_finished());
+        assert_eq!(state.total_tokens, 42);
+
+        // Events delivered late or out of order do not rewind the state.
+        let mut started = TaskStartedEvent::new(id("t0"), id("Plan"), None);
+        started.base.emission_sequence = Some(0);
+        let mut completed = TaskCompletedEvent::new(id("t0"), id("Plan"), Value::from("plan"));
+        completed.base.emission_sequence = Some(1);
+        state.apply_event(&completed);
+        state.apply_event(&started);
+        state.apply_event(&CrewKickoffStartedEvent::new(id("research"), None));
+        assert_eq!(state.status, RunStatus::Completed);
+        assert_eq!(state.tasks[0].name.as_deref(), Some("Plan"));
+        assert_eq!(state.tasks[0].status, TaskStatus::Completed);
+        assert!(state.tasks[0].started_at.is_some());
+    }
+}
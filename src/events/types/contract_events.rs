@@ -0,0 +1,52 @@
+//! Unified execution contract event types.
+//!
+//! Contains events emitted by the contract runtime when a
+//! [`UnifiedStep`](crate::contract::types::UnifiedStep) changes status.
+
+use serde::{Deserialize, Serialize};
+
+use crate::contract::types::StepStatus;
+use crate::events::base_event::BaseEventData;
+use crate::impl_base_event;
+
+// ---------------------------------------------------------------------------
+// StepStatusChangedEvent
+// ---------------------------------------------------------------------------
+
+/// Event emitted after a unified step makes a legal status transition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepStatusChangedEvent {
+    #[serde(flatten)]
+    pub base: BaseEventData,
+    /// Step that changed status.
+    pub step_id: String,
+    /// Execution the step belongs to.
+    pub execution_id: String,
+    /// Step type with routing prefix (e.g. `crew.agent`).
+    pub step_type: String,
+    /// Status before the transition.
+    pub from_status: StepStatus,
+    /// Status after the transition.
+    pub to_status: StepStatus,
+}
+
+impl StepStatusChangedEvent {
+    pub fn new(
+        step_id: String,
+        execution_id: String,
+        step_type: String,
+        from_status: StepStatus,
+        to_status: StepStatus,
+    ) -> Self {
+        Self {
+            base: BaseEventData::new("step_status_changed"),
+            step_id,
+            execution_id,
+            step_type,
+            from_status,
+            to_status,
+        }
+    }
+}
+
+impl_base_event!(StepStatusChangedEvent);
@@ -106,13 +106,17 @@ pub struct KnowledgeQueryFailedEvent {
     pub base: BaseEventData,
     /// Error message.
     pub error: String,
+    /// Time until the search failed, in milliseconds.
+    #[serde(default)]
+    pub latency_ms: u64,
 }
 
 impl KnowledgeQueryFailedEvent {
-    pub fn new(error: String) -> Self {
+    pub fn new(error: String, latency_ms: u64) -> Self {
         Self {
             base: BaseEventData::new("knowledge_query_failed"),
             error,
+            latency_ms,
         }
     }
 }
@@ -132,13 +136,25 @@ pub struct KnowledgeQueryCompletedEvent {
     pub base: BaseEventData,
     /// The query that was executed.
     pub query: String,
+    /// Number of chunks returned.
+    #[serde(default)]
+    pub chunks: usize,
+    /// Similarity score of each returned chunk, best first.
+    #[serde(default)]
+    pub scores: Vec<f64>,
+    /// Time the search took, in milliseconds.
+    #[serde(default)]
+    pub latency_ms: u64,
 }
 
 impl KnowledgeQueryCompletedEvent {
-    pub fn new(query: String) -> Self {
+    pub fn new(query: String, chunks: usize, scores: Vec<f64>, latency_ms: u64) -> Self {
         Self {
             base: BaseEventData::new("knowledge_query_completed"),
             query,
+            chunks,
+            scores,
+            latency_ms,
         }
     }
 }
@@ -13,6 +13,7 @@ use serde_json::Value;
 
 use crate::events::base_event::BaseEventData;
 use crate::impl_base_event;
+use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
 // LLMCallType
@@ -132,6 +133,13 @@ pub struct LLMCallCompletedEvent {
     pub response: Value,
     /// Type of LLM call that completed.
     pub call_type: LLMCallType,
+    /// Token usage of the call, when the provider reported it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<UsageMetrics>,
+    /// Call ID of the identical in-flight call whose response this call
+    /// shared; its usage is reported by that call only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shared_from: Option<String>,
 }
 
 impl LLMCallCompletedEvent {
@@ -148,8 +156,22 @@ impl LLMCallCompletedEvent {
             messages: None,
             response,
             call_type,
+            usage: None,
+            shared_from: None,
         }
     }
+
+    /// Builder: attach token usage.
+    pub fn with_usage(mut self, usage: UsageMetrics) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Builder: mark the response as shared from call `call_id`.
+    pub fn with_shared_from(mut self, call_id: String) -> Self {
+        self.shared_from = Some(call_id);
+        self
+    }
 }
 
 impl_base_event!(LLMCallCompletedEvent);
@@ -226,3 +248,37 @@ impl LLMStreamChunkEvent {
 }
 
 impl_base_event!(LLMStreamChunkEvent);
+
+// ---------------------------------------------------------------------------
+// LLMCallThrottledEvent
+// ---------------------------------------------------------------------------
+
+/// Event emitted when a LLM call waits because the agent's or crew's
+/// `max_rpm` budget is exhausted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LLMCallThrottledEvent {
+    #[serde(flatten)]
+    pub base: BaseEventData,
+    /// LLM model name.
+    pub model: Option<String>,
+    /// The limit that was reached, in requests per minute.
+    pub max_rpm: i32,
+    /// Whose limit was reached: `"agent"` or `"crew"`.
+    pub scope: String,
+    /// Expected wait before the call, in milliseconds.
+    pub wait_ms: u64,
+}
+
+impl LLMCallThrottledEvent {
+    pub fn new(model: Option<String>, max_rpm: i32, scope: String, wait_ms: u64) -> Self {
+        Self {
+            base: BaseEventData::new("llm_call_throttled"),
+            model,
+            max_rpm,
+            scope,
+            wait_ms,
+        }
+    }
+}
+
+impl_base_event!(LLMCallThrottledEvent);
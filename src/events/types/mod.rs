@@ -51,6 +51,12 @@ pub mod system_events;
 /// A2A (Agent-to-Agent) delegation events.
 pub mod a2a_events;
 
+/// Unified execution contract events (step status transitions).
+pub mod contract_events;
+
+/// Policy engine events (denials).
+pub mod policy_events;
+
 /// Tool usage events under their original module name (backward-compat alias).
 ///
 /// New code should prefer [`tool_events`].
@@ -0,0 +1,55 @@
+//! Policy engine event types.
+//!
+//! Contains events emitted by the [`PolicyEngine`](crate::policy::PolicyEngine)
+//! when it evaluates a request.
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::base_event::BaseEventData;
+use crate::impl_base_event;
+
+// ---------------------------------------------------------------------------
+// PolicyDeniedEvent
+// ---------------------------------------------------------------------------
+
+/// Event emitted when a deny rule matches a policy request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyDeniedEvent {
+    #[serde(flatten)]
+    pub base: BaseEventData,
+    /// Action that was denied (e.g. `tool_call`).
+    pub action: String,
+    /// Resource the action targeted.
+    pub resource: String,
+    /// Name of the matching deny rule.
+    pub rule_name: Option<String>,
+    /// Human-readable reason.
+    pub reason: String,
+    /// Whether the denial was enforced (false in audit-only mode).
+    pub enforced: bool,
+}
+
+impl PolicyDeniedEvent {
+    pub fn new(
+        agent_id: String,
+        action: String,
+        resource: String,
+        rule_name: Option<String>,
+        reason: String,
+        enforced: bool,
+    ) -> Self {
+        let mut evt = Self {
+            base: BaseEventData::new("policy_denied"),
+            action,
+            resource,
+            rule_name,
+            reason,
+            enforced,
+        };
+        evt.base.agent_id = Some(agent_id);
+        evt.base.source_type = Some("policy".to_string());
+        evt
+    }
+}
+
+impl_base_event!(PolicyDeniedEvent);
@@ -2,8 +2,8 @@
 //!
 //! Corresponds to `crewai/events/types/tool_usage_events.py`.
 //!
-//! Contains events for tool usage lifecycle: started, finished, and
-//! various error conditions (validation, selection, execution).
+//! Contains events for tool usage lifecycle: started, finished, usage
+//! limits, and various error conditions (validation, selection, execution).
 
 use std::collections::HashMap;
 
@@ -314,3 +314,34 @@ impl ToolExecutionErrorEvent {
 }
 
 impl_base_event!(ToolExecutionErrorEvent);
+
+// ---------------------------------------------------------------------------
+// ToolUsageLimitReachedEvent
+// ---------------------------------------------------------------------------
+
+/// Event emitted when an agent has used a tool as often as it may; the tool
+/// is then removed from the agent's available tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolUsageLimitReachedEvent {
+    #[serde(flatten)]
+    pub base: BaseEventData,
+    /// Key identifying the agent that used up the tool.
+    pub agent_key: Option<String>,
+    /// Name of the tool.
+    pub tool_name: String,
+    /// Number of uses the agent was allowed.
+    pub usage_limit: u32,
+}
+
+impl ToolUsageLimitReachedEvent {
+    pub fn new(tool_name: String, usage_limit: u32) -> Self {
+        Self {
+            base: BaseEventData::new("tool_usage_limit_reached"),
+            agent_key: None,
+            tool_name,
+            usage_limit,
+        }
+    }
+}
+
+impl_base_event!(ToolUsageLimitReachedEvent);
@@ -239,6 +239,57 @@ impl HumanFeedbackPending {
             message: msg,
         }
     }
+
+    /// Create a pause signal from inside a flow method callback.
+    ///
+    /// Callbacks return this (converted into an `anyhow::Error`) to suspend
+    /// the flow. The flow engine fills in the flow id, class and method name
+    /// when it intercepts the signal, so the callback only describes what
+    /// the human should see.
+    pub fn request(message: impl Into<String>, method_output: serde_json::Value) -> Self {
+        let message = message.into();
+        Self {
+            context: PendingFeedbackContext::new(
+                String::new(),
+                String::new(),
+                String::new(),
+                method_output,
+                message.clone(),
+            ),
+            callback_info: HashMap::new(),
+            message,
+        }
+    }
+
+    /// Builder: set emit options for routing the human response.
+    pub fn with_emit(mut self, emit: Vec<String>) -> Self {
+        self.context.emit = Some(emit);
+        self
+    }
+
+    /// Builder: set the outcome used when the human response is empty.
+    pub fn with_default_outcome(mut self, outcome: String) -> Self {
+        self.context.default_outcome = Some(outcome);
+        self
+    }
+
+    /// Builder: set callback info for external systems.
+    pub fn with_callback_info(mut self, callback_info: HashMap<String, serde_json::Value>) -> Self {
+        self.callback_info = callback_info;
+        self
+    }
+
+    /// Serialize the pause signal into the value returned by `kickoff()`.
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::json!({
+            "status": "pending_human_input",
+            "flow_id": self.context.flow_id,
+            "method_name": self.context.method_name,
+            "message": self.message,
+            "emit": self.context.emit,
+            "callback_info": self.callback_info,
+        })
+    }
 }
 
 impl std::fmt::Display for HumanFeedbackPending {
@@ -394,6 +445,18 @@ mod tests {
         assert!(pending.callback_info.is_empty());
     }
 
+    #[test]
+    fn test_human_feedback_pending_request() {
+        let pending = HumanFeedbackPending::request("Approve?", serde_json::json!("draft"))
+            .with_emit(vec!["approve".to_string(), "reject".to_string()]);
+        assert!(pending.context.flow_id.is_empty());
+        assert_eq!(pending.context.message, "Approve?");
+
+        let value = pending.to_value();
+        assert_eq!(value["status"], "pending_human_input");
+        assert_eq!(value["emit"][1], "reject");
+    }
+
     #[test]
     fn test_human_feedback_pending_display() {
         let ctx = PendingFeedbackContext::new(
@@ -10,17 +10,25 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use thiserror::Error;
 use uuid::Uuid;
 
-use super::async_feedback::PendingFeedbackContext;
+use super::async_feedback::{HumanFeedbackPending, PendingFeedbackContext};
 use super::flow_wrappers::{
-    FlowCondition, FlowConditionItem, FlowConditionType, FlowMethodMeta, FlowMethodName,
-    SimpleFlowCondition,
+    FlowCondition, FlowConditionType, FlowMethodMeta, FlowMethodName, SimpleFlowCondition,
 };
 use super::human_feedback::HumanFeedbackResult;
 use super::persistence::FlowPersistence;
+use super::visualization::ExecutionTrace;
+use crate::events::types::flow_events::{
+    MethodExecutionFailedEvent, MethodExecutionFinishedEvent, MethodExecutionStartedEvent,
+};
+use crate::events::CREWAI_EVENT_BUS;
+use crate::telemetry::run_stats::{RunKind, RunStatistics};
 
 /// Constant for OR condition type (matches Python `OR_CONDITION`).
 pub const OR_CONDITION: &str = "OR";
@@ -103,6 +111,100 @@ impl FlowState {
     }
 }
 
+/// A type usable as flow state.
+///
+/// Untyped flows use [`FlowState`]; typed flows use any serde struct with a
+/// `Default`, which gives methods compile-time access to state fields. If
+/// the struct has an `id: String` field, the flow injects its ID there, in
+/// the same way [`FlowState::id`] is populated. Persisted state always
+/// carries an `"id"` key.
+pub trait FlowStateModel:
+    Serialize + DeserializeOwned + Default + Clone + Send + Sync + 'static
+{
+}
+
+impl<T> FlowStateModel for T where
+    T: Serialize + DeserializeOwned + Default + Clone + Send + Sync + 'static
+{
+}
+
+/// Errors raised when flow state does not fit the flow's state schema.
+#[derive(Debug, Error)]
+pub enum FlowStateError {
+    /// Inputs or persisted data could not be deserialized into the state type.
+    #[error("state for flow '{flow_id}' does not match schema {schema}: {source}")]
+    SchemaMismatch {
+        /// The flow whose state was being built.
+        flow_id: String,
+        /// Rust type name of the expected state.
+        schema: &'static str,
+        /// The underlying deserialization error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// The state type does not serialize to a JSON object.
+    #[error("flow state {schema} must serialize to a JSON object")]
+    NotAnObject {
+        /// Rust type name of the state.
+        schema: &'static str,
+    },
+}
+
+/// Serialize a state to its JSON object form.
+fn state_to_map<S: FlowStateModel>(
+    state: &S,
+) -> Result<serde_json::Map<String, Value>, FlowStateError> {
+    match serde_json::to_value(state) {
+        Ok(Value::Object(map)) => Ok(map),
+        _ => Err(FlowStateError::NotAnObject {
+            schema: std::any::type_name::<S>(),
+        }),
+    }
+}
+
+/// Build a state from a JSON object, reporting schema mismatches.
+fn state_from_map<S: FlowStateModel>(
+    flow_id: &str,
+    map: serde_json::Map<String, Value>,
+) -> Result<S, FlowStateError> {
+    serde_json::from_value(Value::Object(map)).map_err(|source| FlowStateError::SchemaMismatch {
+        flow_id: flow_id.to_string(),
+        schema: std::any::type_name::<S>(),
+        source,
+    })
+}
+
+/// Read a non-empty `id` field from a state, if it has one.
+fn state_id<S: FlowStateModel>(state: &S) -> Option<String> {
+    state_to_map(state)
+        .ok()?
+        .get("id")
+        .and_then(|v| v.as_str())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+/// Whether the state type has an `id` field the flow ID is injected into.
+fn has_id_field<S: FlowStateModel>() -> bool {
+    state_to_map(&S::default())
+        .map(|map| map.contains_key("id"))
+        .unwrap_or(false)
+}
+
+/// Write `id` into the state's `id` field. States without one are unchanged.
+fn inject_id<S: FlowStateModel>(state: &mut S, id: &str) {
+    let Ok(mut map) = state_to_map(state) else {
+        return;
+    };
+    if !map.contains_key("id") {
+        return;
+    }
+    map.insert("id".to_string(), Value::String(id.to_string()));
+    if let Ok(updated) = state_from_map(id, map) {
+        *state = updated;
+    }
+}
+
 /// Method execution type marker (analogous to Python decorators).
 ///
 /// Represents the role a method plays in a flow:
@@ -134,11 +236,8 @@ impl std::fmt::Display for FlowMethodType {
 /// In Rust, we represent flow methods as boxed async closures that take
 /// a mutable reference to the flow state, an optional trigger result,
 /// and return a `Value` result.
-pub type FlowMethodFn = Box<
-    dyn Fn(
-            &mut FlowState,
-            Option<Value>,
-        ) -> futures::future::BoxFuture<'_, Result<Value, anyhow::Error>>
+pub type FlowMethodFn<S = FlowState> = Box<
+    dyn Fn(&mut S, Option<Value>) -> futures::future::BoxFuture<'_, Result<Value, anyhow::Error>>
         + Send
         + Sync,
 >;
@@ -273,16 +372,29 @@ pub enum ListenerCondition {
 
 /// Main Flow struct for orchestrating event-driven workflows.
 ///
-/// In the Python implementation, `Flow` is generic over a state type T
-/// (`dict[str, Any]` or `BaseModel`). Here, we use `FlowState` as the
-/// state container, with a `HashMap<String, Value>` for arbitrary data.
+/// Like the Python implementation, `Flow` is generic over its state type.
+/// The default, [`FlowState`], holds arbitrary JSON data; any
+/// [`FlowStateModel`] struct can be used instead for typed access:
+///
+/// ```ignore
+/// #[derive(Clone, Default, Serialize, Deserialize)]
+/// struct Counter { id: String, count: u32 }
+///
+/// let mut flow = Flow::<Counter>::default();
+/// flow.register_callback("bump", Box::new(|state: &mut Counter, _| {
+///     Box::pin(async move {
+///         state.count += 1;
+///         Ok(serde_json::json!(state.count))
+///     })
+/// }));
+/// ```
 ///
 /// Corresponds to `crewai.flow.flow.Flow`.
-pub struct Flow {
+pub struct Flow<S: FlowStateModel = FlowState> {
     /// The flow's mutable state.
-    pub state: FlowState,
+    pub state: S,
     /// The initial state (preserved for reset).
-    initial_state: FlowState,
+    initial_state: S,
     /// Unique flow identifier (from state.id).
     flow_id: String,
     /// Human-readable name of the flow.
@@ -315,6 +427,8 @@ pub struct Flow {
     pub method_outputs: Vec<Value>,
     /// Method results keyed by method name.
     method_results: HashMap<String, Value>,
+    /// Method start/finish timeline of the current run.
+    execution_trace: ExecutionTrace,
 
     // --- Human feedback ---
     /// Human feedback history.
@@ -338,15 +452,59 @@ pub struct Flow {
     /// Request ID for tracing.
     pub request_id: Option<String>,
     /// Registered method callbacks (not serialized).
-    method_callbacks: HashMap<FlowMethodName, Arc<FlowMethodFn>>,
+    method_callbacks: HashMap<FlowMethodName, Arc<FlowMethodFn<S>>>,
+    /// Maximum number of independent methods run at once (1 = sequential).
+    max_concurrency: usize,
     /// Thread-safe state lock.
     state_lock: Arc<Mutex<()>>,
 }
 
-impl Default for Flow {
+impl<S: FlowStateModel> Default for Flow<S> {
     fn default() -> Self {
-        let state = FlowState::new();
-        let flow_id = state.id.clone();
+        Self::from_state(S::default())
+    }
+}
+
+impl Flow {
+    /// Create a new Flow with default state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new Flow with a specific name.
+    pub fn with_name(name: &str) -> Self {
+        let mut flow = Self::default();
+        flow.name = Some(name.to_string());
+        flow
+    }
+
+    /// Create a new Flow with specific initial state.
+    pub fn with_state(state: FlowState) -> Self {
+        Self::from_state(state)
+    }
+
+    /// Create a Flow instance from a pending feedback state.
+    ///
+    /// Corresponds to `Flow.from_pending()` classmethod in Python.
+    pub fn from_pending(
+        flow_id: &str,
+        persistence: Box<dyn FlowPersistence>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut flow = Self::default();
+        flow.persistence = Some(persistence);
+        flow.load_pending(flow_id)?;
+        Ok(flow)
+    }
+}
+
+impl<S: FlowStateModel> Flow<S> {
+    /// Create a flow around an initial state of any [`FlowStateModel`].
+    ///
+    /// The state's `id` is used as the flow ID when present; otherwise a
+    /// new UUID is generated and injected into the state.
+    pub fn from_state(mut state: S) -> Self {
+        let flow_id = state_id(&state).unwrap_or_else(|| Uuid::new_v4().to_string());
+        inject_id(&mut state, &flow_id);
         Self {
             state: state.clone(),
             initial_state: state,
@@ -367,6 +525,7 @@ impl Default for Flow {
             fired_or_listeners: HashSet::new(),
             method_outputs: Vec::new(),
             method_results: HashMap::new(),
+            execution_trace: ExecutionTrace::default(),
             human_feedback_history: Vec::new(),
             last_human_feedback: None,
             pending_feedback_context: None,
@@ -377,38 +536,10 @@ impl Default for Flow {
             tracing: None,
             request_id: None,
             method_callbacks: HashMap::new(),
+            max_concurrency: 1,
             state_lock: Arc::new(Mutex::new(())),
         }
     }
-}
-
-impl Flow {
-    /// Create a new Flow with default state.
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Create a new Flow with a specific name.
-    pub fn with_name(name: &str) -> Self {
-        let mut flow = Self::default();
-        flow.name = Some(name.to_string());
-        flow
-    }
-
-    /// Create a new Flow with specific initial state.
-    pub fn with_state(state: FlowState) -> Self {
-        let flow_id = state.id.clone();
-        Self {
-            state: state.clone(),
-            initial_state: state,
-            flow_id: flow_id.clone(),
-            execution_data: FlowExecutionData {
-                id: flow_id,
-                ..Default::default()
-            },
-            ..Default::default()
-        }
-    }
 
     /// Builder: set persistence backend.
     pub fn with_persistence(mut self, persistence: Box<dyn FlowPersistence>) -> Self {
@@ -422,6 +553,20 @@ impl Flow {
         self
     }
 
+    /// Builder: run independent methods concurrently, at most `limit` at a time.
+    ///
+    /// Methods triggered by the same event (all start methods, or all
+    /// listeners of one completed method) run as tokio tasks on clones of
+    /// the state. Once they all finish, their state changes are merged back
+    /// in registration order (later methods win conflicting keys) and each
+    /// result is propagated in that same order, so AND conditions resolve
+    /// identically on every run. A limit of 1 (the default) keeps the
+    /// sequential, depth-first execution.
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        self.max_concurrency = limit.max(1);
+        self
+    }
+
     /// Get the flow's unique identifier.
     pub fn flow_id(&self) -> &str {
         &self.flow_id
@@ -437,6 +582,16 @@ impl Flow {
         self.pending_feedback_context.as_ref()
     }
 
+    /// Method start/finish timeline of the current (or last) run.
+    pub fn execution_trace(&self) -> &ExecutionTrace {
+        &self.execution_trace
+    }
+
+    /// Whether the flow is suspended waiting for human input.
+    pub fn is_paused(&self) -> bool {
+        self.pending_feedback_context.is_some()
+    }
+
     // -----------------------------------------------------------------------
     // Method registration (equivalent to FlowMeta metaclass processing)
     // -----------------------------------------------------------------------
@@ -506,7 +661,7 @@ impl Flow {
     ///
     /// In Rust, since we cannot use Python-style decorators, callers register
     /// async functions that will be called during flow execution.
-    pub fn register_callback(&mut self, name: &str, callback: FlowMethodFn) {
+    pub fn register_callback(&mut self, name: &str, callback: FlowMethodFn<S>) {
         self.method_callbacks
             .insert(FlowMethodName::new(name), Arc::new(callback));
     }
@@ -537,26 +692,76 @@ impl Flow {
 
     /// Initialize or update the flow state with new inputs.
     ///
+    /// Inputs are merged over the current state. For typed states, inputs
+    /// that do not fit the schema are rejected and the state is left
+    /// unchanged.
+    ///
     /// Corresponds to `Flow._initialize_state()` in Python.
-    pub fn initialize_state(&mut self, inputs: HashMap<String, Value>) {
-        let current_id = self.state.id.clone();
-        let inputs_has_id = inputs.contains_key("id");
-
-        for (k, v) in inputs {
-            self.state.set(k, v);
+    pub fn initialize_state(
+        &mut self,
+        inputs: HashMap<String, Value>,
+    ) -> Result<(), FlowStateError> {
+        let mut map = state_to_map(&self.state)?;
+        let input_id = inputs
+            .get("id")
+            .and_then(|v| v.as_str())
+            .filter(|id| !id.is_empty())
+            .map(str::to_string);
+
+        map.extend(inputs);
+        // Preserve existing ID unless inputs explicitly provided one.
+        let flow_id = input_id.unwrap_or_else(|| self.flow_id.clone());
+        if has_id_field::<S>() {
+            map.insert("id".to_string(), Value::String(flow_id.clone()));
+        } else {
+            map.remove("id");
         }
 
-        // Preserve existing ID unless inputs explicitly provided one.
-        if !inputs_has_id {
-            self.state.id = current_id;
+        self.state = state_from_map(&flow_id, map)?;
+        self.flow_id = flow_id;
+        Ok(())
+    }
+
+    /// Replace the state with persisted data, validating it against the schema.
+    fn restore_state_value(&mut self, flow_id: &str, data: Value) -> Result<(), FlowStateError> {
+        let mut map = match data {
+            Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        if has_id_field::<S>() {
+            map.insert("id".to_string(), Value::String(flow_id.to_string()));
+        } else {
+            map.remove("id");
         }
+        self.state = state_from_map(flow_id, map)?;
+        self.flow_id = flow_id.to_string();
+        Ok(())
+    }
 
-        self.flow_id = self.state.id.clone();
+    /// Restore the latest persisted state for `flow_id`.
+    ///
+    /// Fails with [`FlowStateError::SchemaMismatch`] if the stored data does
+    /// not fit this flow's state type.
+    pub fn restore_state(&mut self, flow_id: &str) -> Result<(), anyhow::Error> {
+        let persistence = self.persistence.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Cannot restore state: no persistence backend configured")
+        })?;
+        let data = persistence
+            .load_state(flow_id)?
+            .ok_or_else(|| anyhow::anyhow!("No persisted state found for flow_id: {}", flow_id))?;
+        self.restore_state_value(flow_id, data)?;
+        Ok(())
     }
 
-    /// Create a serializable copy of the current state.
+    /// Create a serializable copy of the current state, always including its ID.
     fn copy_and_serialize_state(&self) -> Value {
-        serde_json::to_value(&self.state).unwrap_or(Value::Null)
+        match state_to_map(&self.state) {
+            Ok(mut map) => {
+                map.insert("id".to_string(), Value::String(self.flow_id.clone()));
+                Value::Object(map)
+            }
+            Err(_) => Value::Null,
+        }
     }
 
     // -----------------------------------------------------------------------
@@ -592,6 +797,19 @@ impl Flow {
     ///
     /// Corresponds to `Flow.kickoff_async()` in Python.
     pub async fn kickoff_async(&mut self) -> Result<Value, anyhow::Error> {
+        let clock = std::time::Instant::now();
+        let result = self.run_start_methods().await;
+
+        let mut stats = RunStatistics::new(RunKind::Flow, result.is_ok(), clock.elapsed());
+        stats.method_count = self.execution_trace.spans.len();
+        if let Ok(telemetry) = crate::telemetry::telemetry().lock() {
+            telemetry.record_run(&stats);
+        }
+        result
+    }
+
+    /// Run the start methods and everything they trigger.
+    async fn run_start_methods(&mut self) -> Result<Value, anyhow::Error> {
         log::debug!("Flow::kickoff_async starting for flow_id={}", self.flow_id);
 
         // Emit flow started event.
@@ -612,6 +830,31 @@ impl Flow {
             ));
         }
 
+        // Trigger tracking is per run.
+        self.clear_or_listeners();
+        self.pending_and_listeners.clear();
+        self.execution_trace = ExecutionTrace::new(self.flow_name());
+
+        // Independent start methods run concurrently when enabled.
+        if self.max_concurrency > 1 && start_methods.len() > 1 {
+            let names: Vec<FlowMethodName> = start_methods.iter().map(|m| m.name.clone()).collect();
+            let completed = match self.execute_methods_concurrently(&names).await {
+                Ok(completed) => completed,
+                Err(e) => return paused_or_err(e),
+            };
+            for (method_name, result) in &completed {
+                self.record_completion(method_name, result.clone());
+            }
+            let mut last_result = Value::Null;
+            for (method_name, result) in completed {
+                if let Err(e) = self.propagate(&method_name, &result).await {
+                    return paused_or_err(e);
+                }
+                last_result = result;
+            }
+            return Ok(last_result);
+        }
+
         // Execute all start methods.
         let mut last_result = Value::Null;
 
@@ -621,25 +864,19 @@ impl Flow {
 
             match self.execute_method(&method_name).await {
                 Ok(result) => {
-                    self.method_outputs.push(result.clone());
-                    self.method_results
-                        .insert(method_name.0.clone(), result.clone());
-                    self.completed_methods.insert(method_name.clone());
-
-                    // Propagate to listeners.
-                    if let Err(e) = self.execute_listeners(&method_name, &result).await {
-                        // Check if it's a HumanFeedbackPending pause.
-                        let err_str = format!("{}", e);
-                        if err_str.contains("HumanFeedbackPending") {
-                            log::info!("Flow paused for human feedback at method {}", method_name);
-                            return Ok(Value::String(err_str));
-                        }
-                        return Err(e);
+                    self.record_completion(&method_name, result.clone());
+
+                    // Propagate to listeners (or the chosen route).
+                    if let Err(e) = self.propagate(&method_name, &result).await {
+                        return paused_or_err(e);
                     }
 
                     last_result = result;
                 }
                 Err(e) => {
+                    if e.is::<HumanFeedbackPending>() {
+                        return paused_or_err(e);
+                    }
                     log::error!("Start method {} failed: {}", method_name, e);
                     return Err(e);
                 }
@@ -672,6 +909,64 @@ impl Flow {
         }
     }
 
+    /// Resume a persisted paused flow by ID (synchronous wrapper).
+    ///
+    /// See [`Flow::resume_pending_async`].
+    pub fn resume_pending(
+        &mut self,
+        flow_id: &str,
+        feedback: &str,
+    ) -> Result<Value, anyhow::Error> {
+        let rt = tokio::runtime::Handle::try_current();
+        match rt {
+            Ok(_) => Err(anyhow::anyhow!(
+                "resume_pending() cannot be called from within an async context. \
+                 Use 'flow.resume_pending_async(flow_id, feedback).await' instead."
+            )),
+            Err(_) => {
+                let rt = tokio::runtime::Runtime::new()?;
+                rt.block_on(self.resume_pending_async(flow_id, feedback))
+            }
+        }
+    }
+
+    /// Resume a persisted paused flow by ID (async).
+    ///
+    /// Unlike [`Flow::from_pending`], this is called on a flow that already
+    /// has its methods and callbacks registered, so execution can continue
+    /// past the paused method. Requires a persistence backend.
+    pub async fn resume_pending_async(
+        &mut self,
+        flow_id: &str,
+        feedback: &str,
+    ) -> Result<Value, anyhow::Error> {
+        self.load_pending(flow_id)?;
+        self.resume_async(feedback).await
+    }
+
+    /// Restore a paused flow's state and pending context from persistence.
+    ///
+    /// Corresponds to the state-restoring half of `Flow.from_pending()` in Python.
+    pub fn load_pending(&mut self, flow_id: &str) -> Result<(), anyhow::Error> {
+        let persistence = self.persistence.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Cannot load pending feedback: no persistence backend configured")
+        })?;
+        let (state_data, pending_context) = persistence
+            .load_pending_feedback(flow_id)?
+            .ok_or_else(|| anyhow::anyhow!("No pending feedback found for flow_id: {}", flow_id))?;
+
+        // Restore state from persisted data.
+        self.restore_state_value(flow_id, state_data)?;
+
+        // Store pending context for resume.
+        self.completed_methods
+            .insert(FlowMethodName::new(pending_context.method_name.as_str()));
+        self.pending_feedback_context = Some(pending_context);
+        self.is_execution_resuming = true;
+
+        Ok(())
+    }
+
     /// Resume flow execution with human feedback (async).
     ///
     /// Corresponds to `Flow.resume_async()` in Python.
@@ -684,25 +979,7 @@ impl Flow {
         })?;
 
         let emit = context.emit.clone();
-        let default_outcome = context.default_outcome.clone();
-        let _llm = context.llm.clone();
-
-        // Determine outcome.
-        let collapsed_outcome: Option<String> = if feedback.trim().is_empty() {
-            if let Some(ref default) = default_outcome {
-                Some(default.clone())
-            } else if let Some(ref emit_opts) = emit {
-                emit_opts.first().cloned()
-            } else {
-                None
-            }
-        } else if let Some(ref emit_opts) = emit {
-            // In a full implementation, we would use the LLM to collapse feedback
-            // to one of the emit options. For now, use the first option.
-            emit_opts.first().cloned()
-        } else {
-            None
-        };
+        let collapsed_outcome = collapse_outcome(&context, feedback);
 
         // Create result.
         let result = HumanFeedbackResult {
@@ -727,52 +1004,23 @@ impl Flow {
         self.is_execution_resuming = false;
 
         // Trigger downstream listeners.
+        let result_value = serde_json::to_value(&result).unwrap_or(Value::Null);
         let trigger_name =
             if let (Some(_emit_opts), Some(ref outcome)) = (&emit, &collapsed_outcome) {
                 self.method_outputs.push(Value::String(outcome.clone()));
                 FlowMethodName::new(outcome.as_str())
             } else {
+                self.method_outputs.push(result_value.clone());
                 FlowMethodName::new(context.method_name.as_str())
             };
+        self.method_results
+            .insert(context.method_name.clone(), result_value.clone());
 
-        let result_value = serde_json::to_value(&result).unwrap_or(Value::Null);
-        self.execute_listeners(&trigger_name, &result_value).await?;
-
-        Ok(result_value)
-    }
-
-    /// Create a Flow instance from a pending feedback state.
-    ///
-    /// Corresponds to `Flow.from_pending()` classmethod in Python.
-    pub fn from_pending(
-        flow_id: &str,
-        persistence: Box<dyn FlowPersistence>,
-    ) -> Result<Self, anyhow::Error> {
-        let loaded = persistence
-            .load_pending_feedback(flow_id)?
-            .ok_or_else(|| anyhow::anyhow!("No pending feedback found for flow_id: {}", flow_id))?;
-
-        let (state_data, pending_context) = loaded;
-
-        let mut flow = Self::default();
-        flow.persistence = Some(persistence);
-
-        // Restore state from persisted data.
-        if let Some(state_map) = state_data.as_object() {
-            let map: HashMap<String, Value> = state_map
-                .iter()
-                .map(|(k, v)| (k.clone(), v.clone()))
-                .collect();
-            flow.initialize_state(map);
+        if let Err(e) = self.execute_listeners(&trigger_name, &result_value).await {
+            return paused_or_err(e);
         }
 
-        // Store pending context for resume.
-        flow.pending_feedback_context = Some(pending_context.clone());
-        flow.is_execution_resuming = true;
-        flow.completed_methods
-            .insert(FlowMethodName::new(pending_context.method_name.as_str()));
-
-        Ok(flow)
+        Ok(result_value)
     }
 
     // -----------------------------------------------------------------------
@@ -786,24 +1034,25 @@ impl Flow {
     ) -> Result<Value, anyhow::Error> {
         log::debug!("Executing method: {}", method_name);
 
-        // Look up the callback.
-        let callback = self
-            .method_callbacks
-            .get(method_name)
-            .cloned()
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No callback registered for method '{}'. \
-                     Register callbacks with flow.register_callback().",
-                    method_name
-                )
-            })?;
+        let callback = self.callback_for(method_name)?;
 
         // Get the last result from the triggering method.
         let trigger_result = self.method_outputs.last().cloned();
 
-        // Execute the method callback.
-        let result = callback(&mut self.state, trigger_result).await?;
+        // Execute the method callback. A `HumanFeedbackPending` error suspends
+        // the flow instead of failing it.
+        self.trace_started(method_name, Utc::now());
+        let result = match callback(&mut self.state, trigger_result).await {
+            Ok(result) => result,
+            Err(e) => match e.downcast::<HumanFeedbackPending>() {
+                Ok(pending) => return Err(self.suspend(method_name, pending)),
+                Err(e) => {
+                    self.trace_failed(method_name, Utc::now(), &e);
+                    return Err(e);
+                }
+            },
+        };
+        self.trace_finished(method_name, Utc::now(), &result);
 
         // Track execution count.
         let count = self
@@ -815,6 +1064,211 @@ impl Flow {
         Ok(result)
     }
 
+    /// Look up the callback registered for a method.
+    fn callback_for(
+        &self,
+        method_name: &FlowMethodName,
+    ) -> Result<Arc<FlowMethodFn<S>>, anyhow::Error> {
+        self.method_callbacks
+            .get(method_name)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No callback registered for method '{}'. \
+                 Register callbacks with flow.register_callback().",
+                    method_name
+                )
+            })
+    }
+
+    /// Execute independent methods as concurrent tokio tasks.
+    ///
+    /// Each method runs on its own clone of the current state, with at most
+    /// `max_concurrency` tasks in flight. State changes are merged back in
+    /// the order of `methods`. Returns the successful results in that order;
+    /// if any method failed, the first failure (in order) is returned after
+    /// the successful changes have been merged.
+    async fn execute_methods_concurrently(
+        &mut self,
+        methods: &[FlowMethodName],
+    ) -> Result<Vec<(FlowMethodName, Value)>, anyhow::Error> {
+        log::debug!(
+            "Executing {} methods concurrently (limit {}): {:?}",
+            methods.len(),
+            self.max_concurrency,
+            methods
+        );
+
+        let trigger_result = self.method_outputs.last().cloned();
+        let permits = Arc::new(tokio::sync::Semaphore::new(self.max_concurrency));
+        let mut handles = Vec::with_capacity(methods.len());
+        for method_name in methods {
+            let callback = self.callback_for(method_name)?;
+            let mut state = self.state.clone();
+            let input = trigger_result.clone();
+            let permits = permits.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let started_at = Utc::now();
+                let result = callback(&mut state, input).await;
+                (state, result, started_at, Utc::now())
+            }));
+        }
+        let mut joined = Vec::with_capacity(handles.len());
+        for handle in handles {
+            joined.push(handle.await);
+        }
+
+        let base = state_to_map(&self.state)?;
+        let mut merged = base.clone();
+        let mut completed = Vec::new();
+        let mut failure = None;
+        for (method_name, joined) in methods.iter().zip(joined) {
+            let (state, result, started_at, finished_at) =
+                joined.map_err(|e| anyhow::anyhow!("Method {} panicked: {}", method_name, e))?;
+            self.trace_started(method_name, started_at);
+            match result {
+                Ok(value) => {
+                    self.trace_finished(method_name, finished_at, &value);
+                    merge_state_changes(&base, state_to_map(&state)?, &mut merged);
+                    *self
+                        .method_execution_counts
+                        .entry(method_name.clone())
+                        .or_insert(0) += 1;
+                    completed.push((method_name.clone(), value));
+                }
+                Err(e) => {
+                    if !e.is::<HumanFeedbackPending>() {
+                        self.trace_failed(method_name, finished_at, &e);
+                    }
+                    if failure.is_none() {
+                        failure = Some((method_name, e));
+                    }
+                }
+            }
+        }
+        self.state = state_from_map(&self.flow_id, merged)?;
+
+        match failure {
+            None => Ok(completed),
+            Some((method_name, e)) => {
+                for (name, value) in completed {
+                    self.record_completion(&name, value);
+                }
+                match e.downcast::<HumanFeedbackPending>() {
+                    Ok(pending) => Err(self.suspend(method_name, pending)),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+
+    /// Record a method start in the execution trace and on the event bus.
+    fn trace_started(&mut self, method_name: &FlowMethodName, at: DateTime<Utc>) {
+        let mut event = MethodExecutionStartedEvent::new(
+            self.flow_name().to_string(),
+            method_name.0.clone(),
+            self.copy_and_serialize_state(),
+            None,
+        );
+        event.base.timestamp = at;
+        self.execution_trace.on_started(&event);
+        self.emit_flow_event(&mut event);
+    }
+
+    /// Record a method completion in the execution trace and on the event bus.
+    fn trace_finished(&mut self, method_name: &FlowMethodName, at: DateTime<Utc>, result: &Value) {
+        let mut event = MethodExecutionFinishedEvent::new(
+            self.flow_name().to_string(),
+            method_name.0.clone(),
+            Some(result.clone()),
+            self.copy_and_serialize_state(),
+        );
+        event.base.timestamp = at;
+        self.execution_trace.on_finished(&event);
+        self.emit_flow_event(&mut event);
+    }
+
+    /// Record a method failure in the execution trace and on the event bus.
+    fn trace_failed(
+        &mut self,
+        method_name: &FlowMethodName,
+        at: DateTime<Utc>,
+        error: &anyhow::Error,
+    ) {
+        let mut event = MethodExecutionFailedEvent::new(
+            self.flow_name().to_string(),
+            method_name.0.clone(),
+            error.to_string(),
+        );
+        event.base.timestamp = at;
+        self.execution_trace.on_failed(&event);
+        self.emit_flow_event(&mut event);
+    }
+
+    /// Emit a flow event on the global bus, if one is running and flow
+    /// events are not suppressed.
+    fn emit_flow_event<E: crate::events::BaseEvent + 'static>(&self, event: &mut E) {
+        if self.suppress_flow_events {
+            return;
+        }
+        if let Some(bus) = CREWAI_EVENT_BUS.get() {
+            bus.emit(Arc::new(self.flow_id.clone()), event);
+        }
+    }
+
+    /// Record a method's result as completed.
+    fn record_completion(&mut self, method_name: &FlowMethodName, result: Value) {
+        self.method_outputs.push(result.clone());
+        self.method_results.insert(method_name.0.clone(), result);
+        self.completed_methods.insert(method_name.clone());
+    }
+
+    /// Suspend the flow at `method_name` and persist its pending context.
+    ///
+    /// Fills in the identity fields of the pause signal, saves state and
+    /// context through the persistence backend (if any), and records the
+    /// context so `resume_async()` can continue in-process. Returns the
+    /// pause signal as the error to unwind the kickoff with, or the
+    /// persistence error when the pending context could not be saved, since
+    /// the flow could then never be resumed by ID.
+    fn suspend(
+        &mut self,
+        method_name: &FlowMethodName,
+        mut pending: HumanFeedbackPending,
+    ) -> anyhow::Error {
+        pending.context.flow_id = self.flow_id.clone();
+        if pending.context.flow_class.is_empty() {
+            pending.context.flow_class = self.flow_name().to_string();
+        }
+        pending.context.method_name = method_name.0.clone();
+
+        if let Some(ref persistence) = self.persistence {
+            let state_data = self.copy_and_serialize_state();
+            if let Err(e) =
+                persistence.save_pending_feedback(&self.flow_id, &pending.context, &state_data)
+            {
+                return e.context(format!(
+                    "failed to persist pending feedback for flow {}",
+                    self.flow_id
+                ));
+            }
+        } else {
+            log::debug!(
+                "Flow {} paused without persistence; resume is only possible in-process",
+                self.flow_id
+            );
+        }
+
+        log::info!(
+            "Flow {} paused for human feedback at method {}",
+            self.flow_id,
+            method_name
+        );
+        self.pending_feedback_context = Some(pending.context.clone());
+        pending.into()
+    }
+
     /// Execute all listeners triggered by a method's completion.
     ///
     /// Corresponds to `Flow._execute_listeners()` in Python.
@@ -823,12 +1277,18 @@ impl Flow {
         completed_method: &FlowMethodName,
         _result: &Value,
     ) -> Result<(), anyhow::Error> {
-        // Collect listeners that should be triggered.
+        // Collect listeners that should be triggered, in registration order.
         // We collect keys first to avoid borrowing self immutably while calling should_trigger.
+        let mut seen = HashSet::new();
         let listener_keys: Vec<(FlowMethodName, ListenerCondition)> = self
-            .listeners
+            .methods
             .iter()
-            .map(|(k, v)| (k.clone(), v.clone()))
+            .filter(|m| seen.insert(m.name.clone()))
+            .filter_map(|m| {
+                self.listeners
+                    .get(&m.name)
+                    .map(|condition| (m.name.clone(), condition.clone()))
+            })
             .collect();
 
         let mut triggered: Vec<FlowMethodName> = Vec::new();
@@ -848,41 +1308,38 @@ impl Flow {
             triggered
         );
 
-        // Execute triggered listeners.
-        for listener_name in &triggered {
-            // Skip if already resuming and method was completed before pause.
-            if self.is_execution_resuming && self.completed_methods.contains(listener_name) {
-                continue;
+        // Skip listeners already completed before a pause we are resuming from.
+        if self.is_execution_resuming {
+            triggered.retain(|name| !self.completed_methods.contains(name));
+        }
+
+        // Independent listeners run concurrently when enabled.
+        if self.max_concurrency > 1 && triggered.len() > 1 {
+            let completed = self.execute_methods_concurrently(&triggered).await?;
+            for (listener_name, listener_result) in &completed {
+                self.record_completion(listener_name, listener_result.clone());
+                self.persist_method_state(listener_name);
+            }
+            for (listener_name, listener_result) in &completed {
+                Box::pin(self.propagate(listener_name, listener_result)).await?;
             }
+            return Ok(());
+        }
 
+        // Execute triggered listeners.
+        for listener_name in &triggered {
             match self.execute_method(listener_name).await {
                 Ok(listener_result) => {
-                    self.method_outputs.push(listener_result.clone());
-                    self.method_results
-                        .insert(listener_name.0.clone(), listener_result.clone());
-                    self.completed_methods.insert(listener_name.clone());
-
-                    // Persist state after method completion if persistence is configured.
-                    if let Some(ref persistence) = self.persistence {
-                        let state_data = self.copy_and_serialize_state();
-                        let _ =
-                            persistence.save_state(&self.flow_id, &listener_name.0, &state_data);
-                    }
+                    self.record_completion(listener_name, listener_result.clone());
+                    self.persist_method_state(listener_name);
 
-                    // If the listener is a router, route based on its return value.
-                    if self.routers.contains(listener_name) {
-                        if let Some(route_str) = listener_result.as_str() {
-                            let route_name = FlowMethodName::new(route_str);
-                            // Recursively trigger listeners for the route value.
-                            Box::pin(self.execute_listeners(&route_name, &listener_result)).await?;
-                        }
-                    } else {
-                        // Recursively trigger downstream listeners.
-                        Box::pin(self.execute_listeners(listener_name, &listener_result)).await?;
-                    }
+                    // Recursively trigger downstream listeners.
+                    Box::pin(self.propagate(listener_name, &listener_result)).await?;
                 }
                 Err(e) => {
-                    log::error!("Listener {} failed: {}", listener_name, e);
+                    if !e.is::<HumanFeedbackPending>() {
+                        log::error!("Listener {} failed: {}", listener_name, e);
+                    }
                     return Err(e);
                 }
             }
@@ -891,6 +1348,48 @@ impl Flow {
         Ok(())
     }
 
+    /// Persist state after a method completes, if persistence is configured.
+    fn persist_method_state(&self, method_name: &FlowMethodName) {
+        if let Some(ref persistence) = self.persistence {
+            let state_data = self.copy_and_serialize_state();
+            let _ = persistence.save_state(&self.flow_id, &method_name.0, &state_data);
+        }
+    }
+
+    /// Propagate a completed method to its listeners.
+    ///
+    /// Routers propagate the route label they returned instead of their own
+    /// name, so `listen("approved")` fires when a router returns `"approved"`.
+    async fn propagate(
+        &mut self,
+        method_name: &FlowMethodName,
+        result: &Value,
+    ) -> Result<(), anyhow::Error> {
+        if !self.routers.contains(method_name) {
+            return self.execute_listeners(method_name, result).await;
+        }
+
+        let Some(route) = result.as_str() else {
+            log::warn!(
+                "Router {} returned a non-string value; no route taken",
+                method_name
+            );
+            return Ok(());
+        };
+        let route = FlowMethodName::new(route);
+        if let Some(paths) = self.router_paths.get(method_name) {
+            if !paths.is_empty() && !paths.contains(&route) {
+                log::warn!(
+                    "Router {} returned undeclared route '{}' (declared: {:?})",
+                    method_name,
+                    route,
+                    paths
+                );
+            }
+        }
+        self.execute_listeners(&route, result).await
+    }
+
     /// Determine if a listener should be triggered by a completed method.
     fn should_trigger(
         &mut self,
@@ -943,66 +1442,32 @@ impl Flow {
     }
 
     /// Evaluate a compound (nested) flow condition.
+    ///
+    /// Every trigger the listener depends on is recorded as it fires; the
+    /// listener triggers once the recorded set satisfies the whole condition
+    /// tree, after which the record is reset. A top-level OR fires at most
+    /// once per run.
     fn evaluate_compound_condition(
         &mut self,
         listener_name: &FlowMethodName,
         condition: &FlowCondition,
         completed_method: &FlowMethodName,
     ) -> bool {
-        // Check if the completed method is mentioned anywhere in this condition.
-        let all_methods = extract_all_methods_from_condition(condition);
-        if !all_methods.contains(&completed_method.0) {
+        if !condition.method_names().contains(completed_method) {
             return false;
         }
 
+        let key = format!("{}:compound", listener_name);
+        let fired = self.pending_and_listeners.entry(key.clone()).or_default();
+        fired.insert(completed_method.clone());
+        if !condition.is_satisfied(fired) {
+            return false;
+        }
+
+        self.pending_and_listeners.remove(&key);
         match condition.condition_type {
-            FlowConditionType::OR => {
-                // OR: any sub-condition being satisfied triggers.
-                for item in &condition.conditions {
-                    match item {
-                        FlowConditionItem::MethodName(m) => {
-                            if m == completed_method {
-                                return self.mark_or_listener_fired(listener_name);
-                            }
-                        }
-                        FlowConditionItem::Condition(sub) => {
-                            if self.evaluate_compound_condition(
-                                listener_name,
-                                sub,
-                                completed_method,
-                            ) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-                // Also check direct methods list.
-                if condition.methods.contains(completed_method) {
-                    return self.mark_or_listener_fired(listener_name);
-                }
-                false
-            }
-            FlowConditionType::AND => {
-                // AND: all sub-conditions must be satisfied.
-                let key = format!("{}:compound", listener_name);
-                let pending = self
-                    .pending_and_listeners
-                    .entry(key.clone())
-                    .or_default();
-                pending.insert(completed_method.clone());
-
-                let all_required: HashSet<FlowMethodName> = all_methods
-                    .into_iter()
-                    .map(FlowMethodName::new)
-                    .collect();
-
-                if pending.is_superset(&all_required) {
-                    self.pending_and_listeners.remove(&key);
-                    true
-                } else {
-                    false
-                }
-            }
+            FlowConditionType::OR => self.mark_or_listener_fired(listener_name),
+            FlowConditionType::AND => true,
         }
     }
 
@@ -1014,11 +1479,38 @@ impl Flow {
     ///
     /// Corresponds to `Flow.plot()` in Python.
     pub fn plot(&self, filename: Option<&str>) -> Result<String, anyhow::Error> {
+        self.plot_as(filename, super::visualization::PlotFormat::Html)
+    }
+
+    /// Plot the flow structure as HTML, Mermaid markdown, or Graphviz DOT.
+    ///
+    /// Writes `{filename}.{html,mmd,dot}` and returns its path.
+    pub fn plot_as(
+        &self,
+        filename: Option<&str>,
+        format: super::visualization::PlotFormat,
+    ) -> Result<String, anyhow::Error> {
         let filename = filename.unwrap_or("flow_plot");
-        log::debug!("Flow::plot for flow_id={}", self.flow_id);
+        log::debug!("Flow::plot_as({}) for flow_id={}", format, self.flow_id);
 
-        let structure = super::visualization::build_flow_structure(&self.methods);
-        super::visualization::render_interactive(&structure, filename)
+        let mut structure = super::visualization::build_flow_structure(&self.methods);
+        structure.flow_name = self.flow_name().to_string();
+        super::visualization::render(&structure, format, filename)
+    }
+
+    /// Plot the flow as interactive HTML with the last run's execution
+    /// trace overlaid: the path taken, per-method durations, and a timeline.
+    ///
+    /// Writes `{filename}.html` and returns its path.
+    pub fn plot_with_trace(&self, filename: Option<&str>) -> Result<String, anyhow::Error> {
+        let filename = filename.unwrap_or("flow_plot");
+        let mut structure = super::visualization::build_flow_structure(&self.methods);
+        structure.flow_name = self.flow_name().to_string();
+        super::visualization::render_interactive_with_trace(
+            &structure,
+            Some(&self.execution_trace),
+            filename,
+        )
     }
 
     // -----------------------------------------------------------------------
@@ -1028,7 +1520,9 @@ impl Flow {
     /// Reset the flow state to initial.
     pub fn reset(&mut self) {
         self.state = self.initial_state.clone();
-        self.flow_id = self.state.id.clone();
+        if let Some(id) = state_id(&self.state) {
+            self.flow_id = id;
+        }
         self.execution_data = FlowExecutionData {
             id: self.flow_id.clone(),
             ..Default::default()
@@ -1039,6 +1533,7 @@ impl Flow {
         self.fired_or_listeners.clear();
         self.method_outputs.clear();
         self.method_results.clear();
+        self.execution_trace = ExecutionTrace::default();
         self.human_feedback_history.clear();
         self.last_human_feedback = None;
         self.pending_feedback_context = None;
@@ -1046,7 +1541,7 @@ impl Flow {
     }
 }
 
-impl std::fmt::Debug for Flow {
+impl<S: FlowStateModel> std::fmt::Debug for Flow<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Flow")
             .field("flow_id", &self.flow_id)
@@ -1058,7 +1553,7 @@ impl std::fmt::Debug for Flow {
     }
 }
 
-impl std::fmt::Display for Flow {
+impl<S: FlowStateModel> std::fmt::Display for Flow<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -1075,23 +1570,59 @@ impl std::fmt::Display for Flow {
 // Helper functions
 // ---------------------------------------------------------------------------
 
-/// Extract all method names from a FlowCondition recursively.
-fn extract_all_methods_from_condition(condition: &FlowCondition) -> HashSet<String> {
-    let mut result = HashSet::new();
-    for item in &condition.conditions {
-        match item {
-            FlowConditionItem::MethodName(m) => {
-                result.insert(m.0.clone());
-            }
-            FlowConditionItem::Condition(sub) => {
-                result.extend(extract_all_methods_from_condition(sub));
-            }
+/// Convert a `HumanFeedbackPending` pause into the kickoff/resume result.
+///
+/// Any other error is passed through unchanged.
+fn paused_or_err(e: anyhow::Error) -> Result<Value, anyhow::Error> {
+    match e.downcast_ref::<HumanFeedbackPending>() {
+        Some(pending) => Ok(pending.to_value()),
+        None => Err(e),
+    }
+}
+
+/// Apply the keys a branch changed (relative to `base`) onto `merged`.
+///
+/// Keys the branch removed are removed from `merged`; keys it left
+/// untouched keep whatever an earlier branch merged.
+fn merge_state_changes(
+    base: &serde_json::Map<String, Value>,
+    branch: serde_json::Map<String, Value>,
+    merged: &mut serde_json::Map<String, Value>,
+) {
+    for key in base.keys() {
+        if !branch.contains_key(key) {
+            merged.remove(key);
         }
     }
-    for m in &condition.methods {
-        result.insert(m.0.clone());
+    for (key, value) in branch {
+        if base.get(&key) != Some(&value) {
+            merged.insert(key, value);
+        }
     }
-    result
+}
+
+/// Collapse free-form human feedback to one of the context's emit options.
+///
+/// An empty response falls back to the default outcome (or the first emit
+/// option). A response naming an emit option (case-insensitive) selects it;
+/// anything else falls back the same way as an empty response.
+fn collapse_outcome(context: &PendingFeedbackContext, feedback: &str) -> Option<String> {
+    let emit = context.emit.as_ref()?;
+    let fallback = || {
+        context
+            .default_outcome
+            .clone()
+            .or_else(|| emit.first().cloned())
+    };
+
+    let feedback = feedback.trim();
+    if feedback.is_empty() {
+        return fallback();
+    }
+    emit.iter()
+        .find(|option| option.eq_ignore_ascii_case(feedback))
+        .cloned()
+        .or_else(fallback)
 }
 
 #[cfg(test)]
@@ -1206,7 +1737,7 @@ mod tests {
             "counter".to_string(),
             Value::Number(serde_json::Number::from(42)),
         );
-        flow.initialize_state(inputs);
+        flow.initialize_state(inputs).unwrap();
 
         // ID should be preserved.
         assert_eq!(flow.flow_id(), original_id);
@@ -1216,6 +1747,528 @@ mod tests {
         );
     }
 
+    fn callback<F>(f: F) -> FlowMethodFn
+    where
+        F: for<'a> Fn(
+                &'a mut FlowState,
+                Option<Value>,
+            ) -> futures::future::BoxFuture<'a, Result<Value, anyhow::Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Box::new(f)
+    }
+
+    /// draft (start) -> review (pauses for approval) -> publish (on "approve").
+    fn approval_flow(persistence: Option<Box<dyn FlowPersistence>>) -> Flow {
+        let mut flow = Flow::with_name("ApprovalFlow");
+        if let Some(persistence) = persistence {
+            flow = flow.with_persistence(persistence);
+        }
+        flow.register_method_meta(
+            "draft",
+            &super::super::flow_wrappers::FlowMethodMeta {
+                is_start_method: true,
+                ..Default::default()
+            },
+        );
+        flow.register_method_meta(
+            "review",
+            &super::super::flow_wrappers::FlowMethodMeta {
+                trigger_methods: Some(vec![FlowMethodName::new("draft")]),
+                ..Default::default()
+            },
+        );
+        flow.register_method_meta(
+            "publish",
+            &super::super::flow_wrappers::FlowMethodMeta {
+                trigger_methods: Some(vec![FlowMethodName::new("approve")]),
+                ..Default::default()
+            },
+        );
+        flow.register_callback(
+            "draft",
+            callback(|state, _| {
+                Box::pin(async move {
+                    state.set("draft".to_string(), Value::String("v1".to_string()));
+                    Ok(Value::String("v1".to_string()))
+                })
+            }),
+        );
+        flow.register_callback(
+            "review",
+            callback(|_, input| {
+                Box::pin(async move {
+                    Err(
+                        HumanFeedbackPending::request("Approve?", input.unwrap_or_default())
+                            .with_emit(vec!["approve".to_string(), "reject".to_string()])
+                            .into(),
+                    )
+                })
+            }),
+        );
+        flow.register_callback(
+            "publish",
+            callback(|state, _| {
+                Box::pin(async move {
+                    state.set("published".to_string(), Value::Bool(true));
+                    Ok(Value::String("published".to_string()))
+                })
+            }),
+        );
+        flow
+    }
+
+    #[tokio::test]
+    async fn test_flow_pauses_for_human_input_in_process() {
+        let mut flow = approval_flow(None);
+        let result = flow.kickoff_async().await.unwrap();
+        assert_eq!(result["status"], "pending_human_input");
+        assert_eq!(result["method_name"], "review");
+        assert!(flow.is_paused());
+        assert_eq!(flow.pending_feedback().unwrap().flow_id, flow.flow_id());
+
+        let resumed = flow.resume_async("approve").await.unwrap();
+        assert_eq!(resumed["outcome"], "approve");
+        assert!(!flow.is_paused());
+        assert_eq!(flow.state.get("published"), Some(&Value::Bool(true)));
+    }
+
+    #[tokio::test]
+    async fn test_flow_resume_pending_from_persistence() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let path = tmp.path().to_string_lossy().to_string();
+
+        let mut flow = approval_flow(Some(Box::new(
+            super::super::persistence::SQLiteFlowPersistence::new(Some(path.clone())),
+        )));
+        let result = flow.kickoff_async().await.unwrap();
+        assert_eq!(result["status"], "pending_human_input");
+        let flow_id = flow.flow_id().to_string();
+        drop(flow);
+
+        // A fresh instance (e.g. another process hours later) resumes by ID.
+        let mut flow = approval_flow(Some(Box::new(
+            super::super::persistence::SQLiteFlowPersistence::new(Some(path)),
+        )));
+        let resumed = flow.resume_pending_async(&flow_id, "reject").await.unwrap();
+        assert_eq!(resumed["outcome"], "reject");
+        assert_eq!(flow.flow_id(), flow_id);
+        assert_eq!(
+            flow.state.get("draft"),
+            Some(&Value::String("v1".to_string()))
+        );
+        assert!(flow.state.get("published").is_none());
+
+        // The pending marker is cleared after resume.
+        assert!(flow
+            .resume_pending_async(&flow_id, "approve")
+            .await
+            .is_err());
+    }
+
+    /// Persistence that stores state but cannot save pending feedback.
+    #[derive(Debug)]
+    struct NoPendingPersistence;
+
+    impl FlowPersistence for NoPendingPersistence {
+        fn init_db(&self) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+
+        fn save_state(&self, _: &str, _: &str, _: &Value) -> Result<(), anyhow::Error> {
+            Ok(())
+        }
+
+        fn load_state(&self, _: &str) -> Result<Option<Value>, anyhow::Error> {
+            Ok(None)
+        }
+
+        fn save_pending_feedback(
+            &self,
+            _: &str,
+            _: &PendingFeedbackContext,
+            _: &Value,
+        ) -> Result<(), anyhow::Error> {
+            Err(anyhow::anyhow!("disk full"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flow_pause_fails_when_pending_feedback_is_not_saved() {
+        let mut flow = approval_flow(Some(Box::new(NoPendingPersistence)));
+        let err = flow.kickoff_async().await.unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("failed to persist pending feedback for flow"),
+            "{}",
+            err
+        );
+        assert!(err.root_cause().to_string().contains("disk full"));
+        assert!(!flow.is_paused());
+    }
+
+    /// Register `name` with a callback that appends it to `state["log"]`
+    /// and returns `output`.
+    fn register_logged(flow: &mut Flow, name: &str, meta: FlowMethodMeta, output: &str) {
+        flow.register_method_meta(name, &meta);
+        let (name, output) = (name.to_string(), output.to_string());
+        flow.register_callback(
+            &name.clone(),
+            callback(move |state, _| {
+                let (name, output) = (name.clone(), output.clone());
+                Box::pin(async move {
+                    let mut log = state.get("log").cloned().unwrap_or(Value::Array(vec![]));
+                    log.as_array_mut().unwrap().push(Value::String(name));
+                    state.set("log".to_string(), log);
+                    Ok(Value::String(output))
+                })
+            }),
+        );
+    }
+
+    fn log_of(flow: &Flow) -> Vec<String> {
+        flow.state
+            .get("log")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_and_condition_waits_for_all_triggers() {
+        use super::super::flow_wrappers::{and_, listen_condition_meta, start_method_meta};
+
+        let mut flow = Flow::new();
+        register_logged(&mut flow, "a", start_method_meta(), "a");
+        register_logged(&mut flow, "b", start_method_meta(), "b");
+        register_logged(
+            &mut flow,
+            "joined",
+            listen_condition_meta(and_(["a", "b"])),
+            "done",
+        );
+
+        flow.kickoff_async().await.unwrap();
+        assert_eq!(log_of(&flow), vec!["a", "b", "joined"]);
+    }
+
+    #[tokio::test]
+    async fn test_nested_condition_and_or_listener_fires_once() {
+        use super::super::flow_wrappers::{
+            and_, listen_condition_meta, or_, start_method_meta, FlowConditionItem,
+        };
+
+        let mut flow = Flow::new();
+        register_logged(&mut flow, "a", start_method_meta(), "a");
+        register_logged(&mut flow, "b", start_method_meta(), "b");
+        register_logged(&mut flow, "c", start_method_meta(), "c");
+        // a AND (b OR c): satisfied once b fires, not re-triggered by c.
+        register_logged(
+            &mut flow,
+            "nested",
+            listen_condition_meta(and_([FlowConditionItem::from("a"), or_(["b", "c"]).into()])),
+            "nested",
+        );
+        register_logged(
+            &mut flow,
+            "any",
+            listen_condition_meta(or_(["a", "c"])),
+            "any",
+        );
+
+        flow.kickoff_async().await.unwrap();
+        assert_eq!(log_of(&flow), vec!["a", "any", "b", "nested", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_router_label_triggers_listeners() {
+        use super::super::flow_wrappers::{listen_method_meta, router_method_meta};
+
+        let mut flow = Flow::new();
+        let mut start_router = router_method_meta(
+            vec![],
+            FlowConditionType::OR,
+            Some(vec!["approved".to_string(), "rejected".to_string()]),
+        );
+        start_router.is_start_method = true;
+        register_logged(&mut flow, "classify", start_router, "approved");
+        register_logged(
+            &mut flow,
+            "on_approved",
+            listen_method_meta(vec!["approved".into()], FlowConditionType::OR),
+            "ok",
+        );
+        register_logged(
+            &mut flow,
+            "on_rejected",
+            listen_method_meta(vec!["rejected".into()], FlowConditionType::OR),
+            "no",
+        );
+        // Listening on the router's own name does not fire; only its label does.
+        register_logged(
+            &mut flow,
+            "on_classify",
+            listen_method_meta(vec!["classify".into()], FlowConditionType::OR),
+            "never",
+        );
+
+        flow.kickoff_async().await.unwrap();
+        assert_eq!(log_of(&flow), vec!["classify", "on_approved"]);
+    }
+
+    #[tokio::test]
+    async fn test_execution_trace_overlay() {
+        use super::super::flow_wrappers::{listen_method_meta, router_method_meta};
+        use super::super::visualization::SpanStatus;
+
+        let mut flow = Flow::with_name("TracedFlow");
+        let mut start_router = router_method_meta(
+            vec![],
+            FlowConditionType::OR,
+            Some(vec!["approved".to_string(), "rejected".to_string()]),
+        );
+        start_router.is_start_method = true;
+        register_logged(&mut flow, "classify", start_router, "approved");
+        register_logged(
+            &mut flow,
+            "on_approved",
+            listen_method_meta(vec!["approved".into()], FlowConditionType::OR),
+            "ok",
+        );
+        flow.register_method_meta(
+            "on_rejected",
+            &listen_method_meta(vec!["rejected".into()], FlowConditionType::OR),
+        );
+        flow.register_method_meta(
+            "audit",
+            &listen_method_meta(vec!["on_approved".into()], FlowConditionType::OR),
+        );
+        flow.register_callback(
+            "audit",
+            callback(|_, _| Box::pin(async { Err(anyhow::anyhow!("audit log unavailable")) })),
+        );
+
+        assert!(flow.kickoff_async().await.is_err());
+        let trace = flow.execution_trace();
+        assert_eq!(trace.flow_name, "TracedFlow");
+        assert_eq!(
+            trace.executed_methods(),
+            vec!["classify", "on_approved", "audit"]
+        );
+        assert_eq!(trace.spans[0].output, Some(Value::from("approved")));
+        assert_eq!(trace.spans[2].status(), SpanStatus::Failed);
+        assert_eq!(
+            trace.spans[2].error.as_deref(),
+            Some("audit log unavailable")
+        );
+
+        let mut structure = super::super::visualization::build_flow_structure(&flow.methods);
+        structure.flow_name = flow.flow_name().to_string();
+        let taken: Vec<(&str, &str)> = trace
+            .taken_edges(&structure)
+            .into_iter()
+            .map(|i| {
+                let e = &structure.edges[i];
+                (e.source.as_str(), e.target.as_str())
+            })
+            .collect();
+        assert_eq!(
+            taken,
+            vec![
+                ("classify", "approved"),
+                ("approved", "on_approved"),
+                ("on_approved", "audit"),
+            ]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = flow
+            .plot_with_trace(Some(dir.path().join("traced").to_str().unwrap()))
+            .unwrap();
+        let html = std::fs::read_to_string(path).unwrap();
+        assert!(html.contains("Execution timeline"));
+        assert!(html.contains("audit log unavailable"));
+
+        // A new run starts a new trace.
+        flow.reset();
+        assert!(flow.execution_trace().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_listeners_bounded_and_merged_in_order() {
+        use super::super::flow_wrappers::{
+            and_, listen_condition_meta, listen_method_meta, start_method_meta,
+        };
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let mut flow = Flow::new().with_max_concurrency(2);
+        register_logged(&mut flow, "fetch", start_method_meta(), "data");
+        for branch in ["b1", "b2", "b3"] {
+            flow.register_method_meta(
+                branch,
+                &listen_method_meta(vec!["fetch".into()], FlowConditionType::OR),
+            );
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            flow.register_callback(
+                branch,
+                callback(move |state, input| {
+                    let (in_flight, peak) = (in_flight.clone(), peak.clone());
+                    Box::pin(async move {
+                        let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                        assert_eq!(input, Some(Value::String("data".to_string())));
+                        state.set(branch.to_string(), Value::Bool(true));
+                        state.set("winner".to_string(), Value::String(branch.to_string()));
+                        Ok(Value::String(branch.to_string()))
+                    })
+                }),
+            );
+        }
+        register_logged(
+            &mut flow,
+            "join",
+            listen_condition_meta(and_(["b1", "b2", "b3"])),
+            "joined",
+        );
+
+        flow.kickoff_async().await.unwrap();
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        for branch in ["b1", "b2", "b3"] {
+            assert_eq!(flow.state.get(branch), Some(&Value::Bool(true)));
+        }
+        // Conflicting writes resolve in registration order.
+        assert_eq!(
+            flow.state.get("winner"),
+            Some(&Value::String("b3".to_string()))
+        );
+        assert_eq!(log_of(&flow), vec!["fetch", "join"]);
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    struct CounterState {
+        id: String,
+        count: u32,
+        label: Option<String>,
+    }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct OtherState {
+        items: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn test_typed_flow_state() {
+        let mut flow = Flow::<CounterState>::default();
+        assert!(!flow.state.id.is_empty());
+        assert_eq!(flow.state.id, flow.flow_id());
+
+        flow.register_method_meta(
+            "bump",
+            &super::super::flow_wrappers::FlowMethodMeta {
+                is_start_method: true,
+                ..Default::default()
+            },
+        );
+        flow.register_callback(
+            "bump",
+            Box::new(|state: &mut CounterState, _| {
+                Box::pin(async move {
+                    state.count += 1;
+                    Ok(serde_json::json!(state.count))
+                })
+            }),
+        );
+
+        let mut inputs = HashMap::new();
+        inputs.insert("count".to_string(), serde_json::json!(41));
+        flow.initialize_state(inputs).unwrap();
+        flow.kickoff_async().await.unwrap();
+        assert_eq!(flow.state.count, 42);
+    }
+
+    #[test]
+    fn test_typed_flow_rejects_mismatched_inputs() {
+        let mut flow = Flow::<CounterState>::default();
+        let mut inputs = HashMap::new();
+        inputs.insert("count".to_string(), serde_json::json!("not a number"));
+
+        let err = flow.initialize_state(inputs).unwrap_err();
+        assert!(matches!(err, FlowStateError::SchemaMismatch { .. }));
+        assert_eq!(flow.state.count, 0);
+    }
+
+    #[test]
+    fn test_typed_flow_without_id_field() {
+        let mut flow = Flow::<OtherState>::default();
+        let mut inputs = HashMap::new();
+        inputs.insert("items".to_string(), serde_json::json!(["a"]));
+        inputs.insert("id".to_string(), serde_json::json!("explicit-id"));
+        flow.initialize_state(inputs).unwrap();
+
+        assert_eq!(flow.flow_id(), "explicit-id");
+        assert_eq!(flow.state.items, vec!["a".to_string()]);
+        assert_eq!(flow.copy_and_serialize_state()["id"], "explicit-id");
+    }
+
+    #[test]
+    fn test_restore_state_validates_schema() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let path = tmp.path().to_string_lossy().to_string();
+        let persistence = super::super::persistence::SQLiteFlowPersistence::new(Some(path.clone()));
+        persistence
+            .save_state(
+                "flow-1",
+                "bump",
+                &serde_json::json!({"id": "flow-1", "count": 7}),
+            )
+            .unwrap();
+        persistence
+            .save_state(
+                "flow-2",
+                "bump",
+                &serde_json::json!({"id": "flow-2", "count": "seven"}),
+            )
+            .unwrap();
+
+        let mut flow = Flow::<CounterState>::default().with_persistence(Box::new(
+            super::super::persistence::SQLiteFlowPersistence::new(Some(path)),
+        ));
+        flow.restore_state("flow-1").unwrap();
+        assert_eq!(flow.flow_id(), "flow-1");
+        assert_eq!(flow.state.id, "flow-1");
+        assert_eq!(flow.state.count, 7);
+
+        let err = flow.restore_state("flow-2").unwrap_err();
+        assert!(err.downcast_ref::<FlowStateError>().is_some());
+        assert_eq!(flow.state.count, 7);
+    }
+
+    #[test]
+    fn test_collapse_outcome() {
+        let ctx = PendingFeedbackContext::new(
+            "f".to_string(),
+            "Flow".to_string(),
+            "review".to_string(),
+            Value::Null,
+            "msg".to_string(),
+        )
+        .with_emit(vec!["approve".to_string(), "reject".to_string()])
+        .with_default_outcome("reject".to_string());
+
+        assert_eq!(
+            collapse_outcome(&ctx, "Approve").as_deref(),
+            Some("approve")
+        );
+        assert_eq!(collapse_outcome(&ctx, "").as_deref(), Some("reject"));
+        assert_eq!(collapse_outcome(&ctx, "maybe").as_deref(), Some("reject"));
+    }
+
     #[test]
     fn test_flow_display() {
         let flow = Flow::with_name("TestFlow");
@@ -2,6 +2,8 @@
 //!
 //! Corresponds to `crewai/flow/flow_wrappers.py`.
 
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 
 /// A type-safe method name for flow methods.
@@ -82,6 +84,57 @@ pub struct FlowCondition {
     pub methods: Vec<FlowMethodName>,
 }
 
+impl FlowCondition {
+    /// All method names (and router labels) referenced anywhere in the condition.
+    pub fn method_names(&self) -> Vec<FlowMethodName> {
+        let mut names = Vec::new();
+        self.collect_method_names(&mut names);
+        names
+    }
+
+    fn collect_method_names(&self, names: &mut Vec<FlowMethodName>) {
+        let direct =
+            self.methods
+                .iter()
+                .chain(self.conditions.iter().filter_map(|item| match item {
+                    FlowConditionItem::MethodName(m) => Some(m),
+                    FlowConditionItem::Condition(_) => None,
+                }));
+        for m in direct {
+            if !names.contains(m) {
+                names.push(m.clone());
+            }
+        }
+        for item in &self.conditions {
+            if let FlowConditionItem::Condition(sub) = item {
+                sub.collect_method_names(names);
+            }
+        }
+    }
+
+    /// Whether the condition holds given the triggers that have fired.
+    ///
+    /// OR needs any item satisfied, AND needs every item satisfied. An
+    /// empty condition is never satisfied.
+    pub fn is_satisfied(&self, fired: &HashSet<FlowMethodName>) -> bool {
+        if self.methods.is_empty() && self.conditions.is_empty() {
+            return false;
+        }
+        let mut results =
+            self.methods
+                .iter()
+                .map(|m| fired.contains(m))
+                .chain(self.conditions.iter().map(|item| match item {
+                    FlowConditionItem::MethodName(m) => fired.contains(m),
+                    FlowConditionItem::Condition(sub) => sub.is_satisfied(fired),
+                }));
+        match self.condition_type {
+            FlowConditionType::OR => results.any(|r| r),
+            FlowConditionType::AND => results.all(|r| r),
+        }
+    }
+}
+
 /// An item in a FlowCondition's conditions list.
 ///
 /// Can be either a method name or a nested FlowCondition.
@@ -94,6 +147,30 @@ pub enum FlowConditionItem {
     Condition(FlowCondition),
 }
 
+impl From<FlowMethodName> for FlowConditionItem {
+    fn from(m: FlowMethodName) -> Self {
+        Self::MethodName(m)
+    }
+}
+
+impl From<&str> for FlowConditionItem {
+    fn from(s: &str) -> Self {
+        Self::MethodName(FlowMethodName::new(s))
+    }
+}
+
+impl From<String> for FlowConditionItem {
+    fn from(s: String) -> Self {
+        Self::MethodName(FlowMethodName(s))
+    }
+}
+
+impl From<FlowCondition> for FlowConditionItem {
+    fn from(c: FlowCondition) -> Self {
+        Self::Condition(c)
+    }
+}
+
 /// Metadata for a flow method registration.
 ///
 /// Corresponds to the attributes set by FlowMethod, StartMethod, ListenMethod,
@@ -153,20 +230,99 @@ pub fn router_method_meta(
     }
 }
 
-/// Helper to create an OR condition.
-pub fn or_(methods: Vec<FlowMethodName>) -> FlowCondition {
+/// ListenMethod metadata for a compound condition built with [`and_`] / [`or_`].
+pub fn listen_condition_meta(condition: FlowCondition) -> FlowMethodMeta {
+    FlowMethodMeta {
+        trigger_methods: Some(condition.method_names()),
+        condition_type: Some(condition.condition_type),
+        trigger_condition: Some(condition),
+        ..Default::default()
+    }
+}
+
+/// RouterMethod metadata for a compound condition built with [`and_`] / [`or_`].
+pub fn router_condition_meta(
+    condition: FlowCondition,
+    router_paths: Option<Vec<String>>,
+) -> FlowMethodMeta {
+    FlowMethodMeta {
+        is_router: true,
+        router_paths,
+        ..listen_condition_meta(condition)
+    }
+}
+
+fn condition<I, T>(condition_type: FlowConditionType, items: I) -> FlowCondition
+where
+    I: IntoIterator<Item = T>,
+    T: Into<FlowConditionItem>,
+{
+    let mut methods = Vec::new();
+    let mut conditions = Vec::new();
+    for item in items {
+        match item.into() {
+            FlowConditionItem::MethodName(m) => methods.push(m),
+            nested => conditions.push(nested),
+        }
+    }
     FlowCondition {
-        condition_type: FlowConditionType::OR,
-        conditions: Vec::new(),
+        condition_type,
+        conditions,
         methods,
     }
 }
 
+/// Helper to create an OR condition.
+///
+/// Items may be method names, router labels, or nested conditions:
+/// `or_(["a".into(), and_(["b", "c"]).into()])`.
+pub fn or_<I, T>(items: I) -> FlowCondition
+where
+    I: IntoIterator<Item = T>,
+    T: Into<FlowConditionItem>,
+{
+    condition(FlowConditionType::OR, items)
+}
+
 /// Helper to create an AND condition.
-pub fn and_(methods: Vec<FlowMethodName>) -> FlowCondition {
-    FlowCondition {
-        condition_type: FlowConditionType::AND,
-        conditions: Vec::new(),
-        methods,
+///
+/// Items may be method names, router labels, or nested conditions.
+pub fn and_<I, T>(items: I) -> FlowCondition
+where
+    I: IntoIterator<Item = T>,
+    T: Into<FlowConditionItem>,
+{
+    condition(FlowConditionType::AND, items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fired(names: &[&str]) -> HashSet<FlowMethodName> {
+        names.iter().map(|n| FlowMethodName::new(*n)).collect()
+    }
+
+    #[test]
+    fn test_nested_condition_satisfaction() {
+        // a AND (b OR c)
+        let cond = and_([FlowConditionItem::from("a"), or_(["b", "c"]).into()]);
+        assert_eq!(
+            cond.method_names(),
+            vec![
+                FlowMethodName::new("a"),
+                FlowMethodName::new("b"),
+                FlowMethodName::new("c")
+            ]
+        );
+        assert!(!cond.is_satisfied(&fired(&["a"])));
+        assert!(!cond.is_satisfied(&fired(&["b", "c"])));
+        assert!(cond.is_satisfied(&fired(&["a", "c"])));
+    }
+
+    #[test]
+    fn test_empty_condition_never_satisfied() {
+        let cond = or_(Vec::<FlowMethodName>::new());
+        assert!(!cond.is_satisfied(&fired(&["a"])));
     }
 }
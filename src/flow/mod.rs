@@ -19,7 +19,7 @@ pub mod utils;
 pub mod visualization;
 
 // Re-export the main Flow type and FlowState.
-pub use self::flow::{Flow, FlowState};
+pub use self::flow::{Flow, FlowState, FlowStateError, FlowStateModel};
 
 // Re-export decorator-style helpers.
 pub use self::flow_wrappers::{
@@ -31,4 +31,7 @@ pub use self::flow_wrappers::{
 pub use self::flow_events::FlowEvent;
 
 // Re-export visualization entry points.
-pub use self::visualization::{build_flow_structure, render_interactive, FlowStructure};
+pub use self::visualization::{
+    build_flow_structure, render, render_dot, render_interactive, render_interactive_with_trace,
+    render_mermaid, ExecutionTrace, FlowStructure, MethodSpan, PlotFormat, SpanStatus,
+};
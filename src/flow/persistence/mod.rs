@@ -128,9 +128,15 @@ impl SQLiteFlowPersistence {
     /// # Arguments
     ///
     /// * `db_path` - Optional path to the SQLite database file.
-    ///   If None, uses `flow_states.db` in the current directory.
+    ///   If None, uses `flow_states.db` in the
+    ///   [checkpoints directory](crate::utilities::paths::checkpoints_dir).
     pub fn new(db_path: Option<String>) -> Self {
-        let path = db_path.unwrap_or_else(|| "flow_states.db".to_string());
+        let path = db_path.unwrap_or_else(|| {
+            crate::utilities::paths::checkpoints_dir()
+                .join("flow_states.db")
+                .to_string_lossy()
+                .to_string()
+        });
 
         // Ensure parent directory exists.
         if let Some(parent) = Path::new(&path).parent() {
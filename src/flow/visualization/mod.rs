@@ -4,15 +4,23 @@
 //!
 //! Provides types and functions for building a structural representation
 //! of a Flow's method graph and rendering it as an interactive HTML
-//! visualization using inline JavaScript/CSS.
+//! visualization using inline JavaScript/CSS, Mermaid markdown, or
+//! Graphviz DOT. An [`ExecutionTrace`] of a run can be overlaid on the
+//! HTML output as a timeline.
+
+pub mod trace;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 
 use std::collections::VecDeque;
 
 use super::flow::FlowMethodRegistration;
 
+pub use self::trace::{ExecutionTrace, MethodSpan, SpanStatus};
+
 /// Simple BFS-based node level calculation for visualization.
 fn calculate_node_levels(
     start_nodes: &[String],
@@ -321,6 +329,20 @@ pub fn calculate_execution_paths(structure: &FlowStructure) -> Vec<Vec<String>>
 pub fn render_interactive(
     structure: &FlowStructure,
     filename: &str,
+) -> Result<String, anyhow::Error> {
+    render_interactive_with_trace(structure, None, filename)
+}
+
+/// Render the interactive HTML visualization with an execution trace overlaid.
+///
+/// Methods that ran and the edges the run went through are highlighted,
+/// failed methods are marked, each executed node shows its duration, and a
+/// timeline below the graph shows when each method ran. With `trace` set to
+/// `None` this is [`render_interactive`].
+pub fn render_interactive_with_trace(
+    structure: &FlowStructure,
+    trace: Option<&ExecutionTrace>,
+    filename: &str,
 ) -> Result<String, anyhow::Error> {
     let output_path = format!("{}.html", filename);
 
@@ -330,6 +352,10 @@ pub fn render_interactive(
 
     let nodes_json = serde_json::to_string_pretty(&sorted_nodes)?;
     let edges_json = serde_json::to_string_pretty(&structure.edges)?;
+    let trace_json = match trace {
+        Some(trace) => trace_overlay_json(structure, trace)?,
+        None => "null".to_string(),
+    };
     let flow_name = if structure.flow_name.is_empty() {
         "Flow"
     } else {
@@ -415,6 +441,64 @@ pub fn render_interactive(
             margin-right: 4px;
             vertical-align: middle;
         }}
+        .node.executed {{
+            box-shadow: 0 0 0 3px #222;
+        }}
+        .node.failed {{
+            box-shadow: 0 0 0 3px #E53935;
+        }}
+        .node.skipped {{
+            opacity: 0.35;
+        }}
+        .node .duration {{
+            font-size: 11px;
+            font-weight: 400;
+            margin-left: 6px;
+        }}
+        .edge.taken {{
+            color: #222;
+            font-weight: 600;
+        }}
+        .timeline {{
+            margin-top: 20px;
+            background: white;
+            border-radius: 8px;
+            padding: 20px;
+            box-shadow: 0 2px 4px rgba(0,0,0,0.1);
+        }}
+        .timeline-row {{
+            display: flex;
+            align-items: center;
+            margin: 4px 0;
+            font-size: 12px;
+        }}
+        .timeline-label {{
+            width: 180px;
+            flex-shrink: 0;
+            overflow: hidden;
+            text-overflow: ellipsis;
+        }}
+        .timeline-track {{
+            position: relative;
+            flex-grow: 1;
+            height: 16px;
+            background: #f0f0f0;
+            border-radius: 3px;
+        }}
+        .timeline-bar {{
+            position: absolute;
+            height: 100%;
+            min-width: 2px;
+            border-radius: 3px;
+            background: #2196F3;
+        }}
+        .timeline-bar.failed {{ background: #E53935; }}
+        .timeline-bar.running {{ background: #9E9E9E; }}
+        .timeline-duration {{
+            width: 90px;
+            text-align: right;
+            flex-shrink: 0;
+        }}
         .tooltip {{
             display: none;
             position: absolute;
@@ -438,14 +522,28 @@ pub fn render_interactive(
         <span class="legend-item"><span class="legend-color" style="background:#2196F3"></span> Listen</span>
         <span class="legend-item"><span class="legend-color" style="background:#FF9800"></span> Router</span>
     </div>
+    <div class="timeline" id="timeline" style="display:none"></div>
     <div class="tooltip" id="tooltip"></div>
     <script>
         const nodes = {nodes_json};
         const edges = {edges_json};
+        const trace = {trace_json};
 
         const container = document.getElementById('graph');
         const tooltip = document.getElementById('tooltip');
 
+        const formatMs = ms => ms == null ? 'running' : (ms < 1000 ? ms + ' ms' : (ms / 1000).toFixed(2) + ' s');
+        const summarize = v => {{
+            const text = typeof v === 'string' ? v : JSON.stringify(v);
+            return text.length > 120 ? text.slice(0, 117) + '...' : text;
+        }};
+        const spansByMethod = {{}};
+        if (trace) {{
+            trace.spans.forEach(span => {{
+                (spansByMethod[span.method] = spansByMethod[span.method] || []).push(span);
+            }});
+        }}
+
         // Group nodes by level.
         const levels = {{}};
         nodes.forEach(n => {{
@@ -465,6 +563,20 @@ pub fn render_interactive(
                 el.className = 'node node-' + (node.is_start ? 'start' : nodeType);
                 el.textContent = node.label || node.id;
 
+                const spans = spansByMethod[node.id] || [];
+                if (trace) {{
+                    if (spans.some(s => s.status === 'failed')) el.classList.add('failed');
+                    else if (spans.length) el.classList.add('executed');
+                    else el.classList.add('skipped');
+                    if (spans.length) {{
+                        const total = spans.reduce((sum, s) => sum + (s.duration_ms || 0), 0);
+                        const dur = document.createElement('span');
+                        dur.className = 'duration';
+                        dur.textContent = formatMs(total) + (spans.length > 1 ? ' \u00d7' + spans.length : '');
+                        el.appendChild(dur);
+                    }}
+                }}
+
                 // Tooltip on hover.
                 el.addEventListener('mouseenter', (e) => {{
                     const info = [];
@@ -472,6 +584,10 @@ pub fn render_interactive(
                     if (node.condition_type) info.push('Condition: ' + node.condition_type);
                     if (node.trigger_methods) info.push('Triggers: ' + node.trigger_methods.join(', '));
                     if (node.router_paths && node.router_paths.length) info.push('Paths: ' + node.router_paths.join(', '));
+                    spans.forEach(s => {{
+                        if (s.error) info.push('Error: ' + summarize(s.error));
+                        else if (s.output !== undefined && s.output !== null) info.push('Output: ' + summarize(s.output));
+                    }});
                     tooltip.textContent = info.join(' | ');
                     tooltip.style.display = 'block';
                     tooltip.style.left = e.pageX + 10 + 'px';
@@ -492,9 +608,10 @@ pub fn render_interactive(
             const edgeDiv = document.createElement('div');
             edgeDiv.className = 'edges-section';
             edgeDiv.innerHTML = '<h3>Connections</h3>';
-            edges.forEach(edge => {{
+            edges.forEach((edge, index) => {{
                 const el = document.createElement('div');
                 el.className = 'edge' + (edge.is_router_path ? ' router-path' : '');
+                if (trace && trace.taken_edges.includes(index)) el.classList.add('taken');
                 const label = edge.router_path_label || edge.label || '';
                 el.textContent = edge.source + ' \u2192 ' + edge.target +
                     (label ? ' [' + label + ']' : '') +
@@ -503,12 +620,42 @@ pub fn render_interactive(
             }});
             container.appendChild(edgeDiv);
         }}
+
+        // Render the execution timeline.
+        if (trace && trace.spans.length > 0) {{
+            const timeline = document.getElementById('timeline');
+            timeline.style.display = 'block';
+            timeline.innerHTML = '<h3>Execution timeline (' + formatMs(trace.total_ms) + ')</h3>';
+            const span = Math.max(trace.total_ms || 0, 1);
+            trace.spans.forEach(s => {{
+                const row = document.createElement('div');
+                row.className = 'timeline-row';
+                const label = document.createElement('span');
+                label.className = 'timeline-label';
+                label.textContent = s.method;
+                const track = document.createElement('span');
+                track.className = 'timeline-track';
+                const bar = document.createElement('span');
+                bar.className = 'timeline-bar ' + s.status;
+                const width = s.duration_ms == null ? span - s.offset_ms : s.duration_ms;
+                bar.style.left = (100 * s.offset_ms / span) + '%';
+                bar.style.width = (100 * width / span) + '%';
+                bar.title = s.error ? 'Error: ' + s.error : summarize(s.output ?? '');
+                track.appendChild(bar);
+                const dur = document.createElement('span');
+                dur.className = 'timeline-duration';
+                dur.textContent = formatMs(s.duration_ms);
+                row.append(label, track, dur);
+                timeline.appendChild(row);
+            }});
+        }}
     </script>
 </body>
 </html>"#,
         flow_name = flow_name,
         nodes_json = nodes_json,
         edges_json = edges_json,
+        trace_json = trace_json,
     );
 
     std::fs::write(&output_path, &html)?;
@@ -518,6 +665,257 @@ pub fn render_interactive(
     Ok(output_path)
 }
 
+/// Trace data embedded in the HTML page: spans with offsets and durations
+/// in milliseconds, plus the indexes of the edges that were taken.
+fn trace_overlay_json(
+    structure: &FlowStructure,
+    trace: &ExecutionTrace,
+) -> Result<String, serde_json::Error> {
+    let origin = trace.spans.iter().map(|s| s.started_at).min();
+    let spans: Vec<Value> = trace
+        .spans
+        .iter()
+        .map(|s| {
+            let offset = origin
+                .map(|o| (s.started_at - o).num_milliseconds())
+                .unwrap_or(0);
+            serde_json::json!({
+                "method": s.method,
+                "status": s.status(),
+                "offset_ms": offset,
+                "duration_ms": s.duration().map(|d| d.as_millis() as u64),
+                "output": s.output,
+                "error": s.error,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&serde_json::json!({
+        "spans": spans,
+        "total_ms": trace.total_duration().map(|d| d.as_millis() as u64),
+        "taken_edges": trace.taken_edges(structure),
+    }))
+}
+
+/// Output format for flow plots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlotFormat {
+    /// Standalone interactive HTML page.
+    #[default]
+    Html,
+    /// Mermaid flowchart markdown.
+    Mermaid,
+    /// Graphviz DOT.
+    Dot,
+}
+
+impl PlotFormat {
+    /// File extension used when writing this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Mermaid => "mmd",
+            Self::Dot => "dot",
+        }
+    }
+}
+
+impl std::fmt::Display for PlotFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Html => write!(f, "html"),
+            Self::Mermaid => write!(f, "mermaid"),
+            Self::Dot => write!(f, "dot"),
+        }
+    }
+}
+
+impl std::str::FromStr for PlotFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "html" => Ok(Self::Html),
+            "mermaid" | "mmd" => Ok(Self::Mermaid),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            other => Err(anyhow::anyhow!(
+                "Unknown plot format '{}'. Expected one of: html, mermaid, dot",
+                other
+            )),
+        }
+    }
+}
+
+/// Render the flow structure in `format` and write it to `filename`.
+///
+/// The format's extension is appended to `filename`.
+///
+/// # Returns
+///
+/// The path to the generated file, or an error.
+pub fn render(
+    structure: &FlowStructure,
+    format: PlotFormat,
+    filename: &str,
+) -> Result<String, anyhow::Error> {
+    let contents = match format {
+        PlotFormat::Html => return render_interactive(structure, filename),
+        PlotFormat::Mermaid => render_mermaid(structure),
+        PlotFormat::Dot => render_dot(structure),
+    };
+    let output_path = format!("{}.{}", filename, format.extension());
+    std::fs::write(&output_path, contents)?;
+
+    log::info!("Flow {} diagram written to {}", format, output_path);
+
+    Ok(output_path)
+}
+
+/// Graph nodes in a stable order: method nodes by (level, name), then
+/// route labels (edge endpoints that are not methods) by name.
+fn ordered_node_ids(structure: &FlowStructure) -> Vec<(String, Option<&NodeMetadata>)> {
+    let mut methods: Vec<&NodeMetadata> = structure.nodes.values().collect();
+    methods.sort_by(|a, b| (a.level, &a.id).cmp(&(b.level, &b.id)));
+
+    let mut labels: Vec<String> = structure
+        .edges
+        .iter()
+        .flat_map(|e| [&e.source, &e.target])
+        .filter(|id| !structure.nodes.contains_key(*id))
+        .cloned()
+        .collect();
+    labels.sort();
+    labels.dedup();
+
+    methods
+        .into_iter()
+        .map(|n| (n.id.clone(), Some(n)))
+        .chain(labels.into_iter().map(|id| (id, None)))
+        .collect()
+}
+
+/// Edge label shown in static diagrams: the router path, or AND for
+/// AND-conditioned listeners (OR is the default and left unlabelled).
+fn static_edge_label(edge: &StructureEdge) -> Option<String> {
+    if edge.is_router_path == Some(true) {
+        return edge
+            .router_path_label
+            .clone()
+            .or_else(|| edge.label.clone());
+    }
+    edge.condition_type.clone().filter(|c| c == "AND")
+}
+
+fn flow_title(structure: &FlowStructure) -> &str {
+    if structure.flow_name.is_empty() {
+        "Flow"
+    } else {
+        &structure.flow_name
+    }
+}
+
+/// Render the flow structure as a Mermaid flowchart.
+///
+/// Start methods are drawn as stadiums, routers as diamonds, listeners as
+/// rectangles, and route labels as parallelograms; router paths are dashed.
+pub fn render_mermaid(structure: &FlowStructure) -> String {
+    fn escape(label: &str) -> String {
+        label.replace('"', "#quot;")
+    }
+
+    let nodes = ordered_node_ids(structure);
+    let ids: HashMap<&str, String> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| (name.as_str(), format!("n{}", i)))
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "---\ntitle: {}\n---", escape(flow_title(structure)));
+    out.push_str("flowchart TD\n");
+    for (name, meta) in &nodes {
+        let id = &ids[name.as_str()];
+        let label = escape(name);
+        let (shape, class) = match meta {
+            Some(m) if m.is_start => (format!("([\"{}\"])", label), "start"),
+            Some(m) if m.is_router == Some(true) => (format!("{{\"{}\"}}", label), "router"),
+            Some(_) => (format!("[\"{}\"]", label), "listen"),
+            None => (format!("[/\"{}\"/]", label), "route"),
+        };
+        let _ = writeln!(out, "    {}{}:::{}", id, shape, class);
+    }
+    for edge in &structure.edges {
+        let arrow = if edge.is_router_path == Some(true) {
+            "-.->"
+        } else {
+            "-->"
+        };
+        let label = static_edge_label(edge)
+            .map(|l| format!("|\"{}\"|", escape(&l)))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "    {} {}{} {}",
+            ids[edge.source.as_str()],
+            arrow,
+            label,
+            ids[edge.target.as_str()]
+        );
+    }
+    out.push_str("    classDef start fill:#4CAF50,color:#fff\n");
+    out.push_str("    classDef listen fill:#2196F3,color:#fff\n");
+    out.push_str("    classDef router fill:#FF9800,color:#fff\n");
+    out.push_str("    classDef route fill:#fff,stroke:#FF9800\n");
+    out
+}
+
+/// Render the flow structure as a Graphviz DOT digraph.
+///
+/// Uses the same shapes and colours as [`render_mermaid`].
+pub fn render_dot(structure: &FlowStructure) -> String {
+    fn quote(s: &str) -> String {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(out, "digraph {} {{", quote(flow_title(structure)));
+    out.push_str("    rankdir=TB;\n");
+    out.push_str("    node [fontname=\"Helvetica\", style=filled, fontcolor=white];\n");
+    for (name, meta) in ordered_node_ids(structure) {
+        let attrs = match meta {
+            Some(m) if m.is_start => "shape=box, style=\"rounded,filled\", fillcolor=\"#4CAF50\"",
+            Some(m) if m.is_router == Some(true) => "shape=diamond, fillcolor=\"#FF9800\"",
+            Some(_) => "shape=box, fillcolor=\"#2196F3\"",
+            None => "shape=parallelogram, style=solid, color=\"#FF9800\", fontcolor=black",
+        };
+        let _ = writeln!(out, "    {} [{}];", quote(&name), attrs);
+    }
+    for edge in &structure.edges {
+        let mut attrs = Vec::new();
+        if let Some(label) = static_edge_label(edge) {
+            attrs.push(format!("label={}", quote(&label)));
+        }
+        if edge.is_router_path == Some(true) {
+            attrs.push("style=dashed".to_string());
+            attrs.push("color=\"#FF9800\"".to_string());
+        }
+        let attrs = if attrs.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", attrs.join(", "))
+        };
+        let _ = writeln!(
+            out,
+            "    {} -> {}{};",
+            quote(&edge.source),
+            quote(&edge.target),
+            attrs
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -651,4 +1049,89 @@ mod tests {
         assert_eq!(deserialized.id, "test");
         assert!(deserialized.is_start);
     }
+
+    fn router_flow() -> FlowStructure {
+        let methods = vec![
+            FlowMethodRegistration {
+                name: FlowMethodName::new("begin"),
+                method_type: FlowMethodType::Start,
+                is_start_method: true,
+                trigger_methods: None,
+                condition_type: None,
+                trigger_condition: None,
+                is_router: false,
+                router_paths: None,
+            },
+            FlowMethodRegistration {
+                name: FlowMethodName::new("decide"),
+                method_type: FlowMethodType::Router,
+                is_start_method: false,
+                trigger_methods: Some(vec![FlowMethodName::new("begin")]),
+                condition_type: Some(FlowConditionType::OR),
+                trigger_condition: None,
+                is_router: true,
+                router_paths: Some(vec!["approved".to_string()]),
+            },
+            FlowMethodRegistration {
+                name: FlowMethodName::new("publish"),
+                method_type: FlowMethodType::Listen,
+                is_start_method: false,
+                trigger_methods: Some(vec![
+                    FlowMethodName::new("approved"),
+                    FlowMethodName::new("begin"),
+                ]),
+                condition_type: Some(FlowConditionType::AND),
+                trigger_condition: None,
+                is_router: false,
+                router_paths: None,
+            },
+        ];
+        let mut structure = build_flow_structure(&methods);
+        structure.flow_name = "Review \"Flow\"".to_string();
+        structure
+    }
+
+    #[test]
+    fn test_render_mermaid() {
+        let mermaid = render_mermaid(&router_flow());
+        let expected = r#"---
+title: Review #quot;Flow#quot;
+---
+flowchart TD
+    n0(["begin"]):::start
+    n1{"decide"}:::router
+    n2["publish"]:::listen
+    n3[/"approved"/]:::route
+    n0 --> n1
+    n1 -.->|"approved"| n3
+    n3 -->|"AND"| n2
+    n0 -->|"AND"| n2
+"#;
+        assert!(mermaid.starts_with(expected), "{}", mermaid);
+        assert!(mermaid.contains("classDef router"));
+    }
+
+    #[test]
+    fn test_render_dot() {
+        let dot = render_dot(&router_flow());
+        assert!(dot.starts_with("digraph \"Review \\\"Flow\\\"\" {\n"));
+        assert!(dot.contains("    \"decide\" [shape=diamond"));
+        assert!(dot.contains("    \"approved\" [shape=parallelogram"));
+        assert!(dot.contains(
+            "    \"decide\" -> \"approved\" [label=\"approved\", style=dashed, color=\"#FF9800\"];"
+        ));
+        assert!(dot.contains("    \"begin\" -> \"decide\";"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_plot_format_parse() {
+        assert_eq!(
+            "Mermaid".parse::<PlotFormat>().unwrap(),
+            PlotFormat::Mermaid
+        );
+        assert_eq!("dot".parse::<PlotFormat>().unwrap(), PlotFormat::Dot);
+        assert_eq!("html".parse::<PlotFormat>().unwrap(), PlotFormat::Html);
+        assert!("svg".parse::<PlotFormat>().is_err());
+    }
 }
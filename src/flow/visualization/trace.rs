@@ -0,0 +1,247 @@
+//! Execution traces for overlaying a flow run on its visualization.
+//!
+//! An [`ExecutionTrace`] records when each flow method started and finished,
+//! what it returned, and whether it failed. Flows build one from the
+//! `MethodExecution*` events they emit on the event bus; it can be passed to
+//! [`render_interactive_with_trace`](super::render_interactive_with_trace)
+//! to highlight the path that was taken and show a timeline of the run.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::FlowStructure;
+use crate::events::types::flow_events::{
+    MethodExecutionFailedEvent, MethodExecutionFinishedEvent, MethodExecutionStartedEvent,
+};
+
+/// Outcome of a traced method execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanStatus {
+    /// Started but not finished (still running, or paused for feedback).
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Finished with an error.
+    Failed,
+}
+
+/// One execution of a flow method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MethodSpan {
+    /// Name of the method.
+    pub method: String,
+    /// When the method started.
+    pub started_at: DateTime<Utc>,
+    /// When the method finished, if it has.
+    #[serde(default)]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// The method's return value, if it completed.
+    #[serde(default)]
+    pub output: Option<Value>,
+    /// The error message, if it failed.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl MethodSpan {
+    /// Status of this execution.
+    pub fn status(&self) -> SpanStatus {
+        match (&self.finished_at, &self.error) {
+            (None, _) => SpanStatus::Running,
+            (Some(_), None) => SpanStatus::Completed,
+            (Some(_), Some(_)) => SpanStatus::Failed,
+        }
+    }
+
+    /// Wall-clock duration, once finished.
+    pub fn duration(&self) -> Option<std::time::Duration> {
+        self.finished_at
+            .and_then(|end| (end - self.started_at).to_std().ok())
+    }
+}
+
+/// Method executions of a single flow run, in the order they were recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    /// Name of the traced flow.
+    pub flow_name: String,
+    /// Method executions in the order they were recorded.
+    pub spans: Vec<MethodSpan>,
+}
+
+impl ExecutionTrace {
+    /// Create an empty trace for a flow.
+    pub fn new(flow_name: impl Into<String>) -> Self {
+        Self {
+            flow_name: flow_name.into(),
+            spans: Vec::new(),
+        }
+    }
+
+    /// Record that `method` started at `at`.
+    pub fn start(&mut self, method: impl Into<String>, at: DateTime<Utc>) {
+        self.spans.push(MethodSpan {
+            method: method.into(),
+            started_at: at,
+            finished_at: None,
+            output: None,
+            error: None,
+        });
+    }
+
+    /// Record that the latest open execution of `method` completed.
+    pub fn finish(&mut self, method: &str, at: DateTime<Utc>, output: Option<Value>) {
+        if let Some(span) = self.open_span(method, at) {
+            span.finished_at = Some(at);
+            span.output = output;
+        }
+    }
+
+    /// Record that the latest open execution of `method` failed.
+    pub fn fail(&mut self, method: &str, at: DateTime<Utc>, error: impl Into<String>) {
+        if let Some(span) = self.open_span(method, at) {
+            span.finished_at = Some(at);
+            span.error = Some(error.into());
+        }
+    }
+
+    /// The latest unfinished span of `method`, opening one at `at` if a
+    /// finish arrives without a start (e.g. a trace joined mid-run).
+    fn open_span(&mut self, method: &str, at: DateTime<Utc>) -> Option<&mut MethodSpan> {
+        let index = match self
+            .spans
+            .iter()
+            .rposition(|s| s.method == method && s.finished_at.is_none())
+        {
+            Some(index) => index,
+            None => {
+                self.start(method, at);
+                self.spans.len() - 1
+            }
+        };
+        self.spans.get_mut(index)
+    }
+
+    /// Record a `method_execution_started` event.
+    pub fn on_started(&mut self, event: &MethodExecutionStartedEvent) {
+        self.start(event.method_name.clone(), event.base.timestamp);
+    }
+
+    /// Record a `method_execution_finished` event.
+    pub fn on_finished(&mut self, event: &MethodExecutionFinishedEvent) {
+        self.finish(
+            &event.method_name,
+            event.base.timestamp,
+            event.result.clone(),
+        );
+    }
+
+    /// Record a `method_execution_failed` event.
+    pub fn on_failed(&mut self, event: &MethodExecutionFailedEvent) {
+        self.fail(
+            &event.method_name,
+            event.base.timestamp,
+            event.error.clone(),
+        );
+    }
+
+    /// Whether nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Names of the methods that ran, in first-start order.
+    pub fn executed_methods(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.spans
+            .iter()
+            .map(|s| s.method.as_str())
+            .filter(|m| seen.insert(*m))
+            .collect()
+    }
+
+    /// Time from the first start to the last finish.
+    pub fn total_duration(&self) -> Option<std::time::Duration> {
+        let start = self.spans.iter().map(|s| s.started_at).min()?;
+        let end = self.spans.iter().filter_map(|s| s.finished_at).max()?;
+        (end - start).to_std().ok()
+    }
+
+    /// Nodes reached during the run: executed methods plus the labels
+    /// returned by router methods.
+    pub fn reached_nodes(&self, structure: &FlowStructure) -> HashSet<String> {
+        let mut reached: HashSet<String> = self.spans.iter().map(|s| s.method.clone()).collect();
+        for span in &self.spans {
+            if !structure.router_methods.contains(&span.method) {
+                continue;
+            }
+            if let Some(Value::String(label)) = &span.output {
+                reached.insert(label.clone());
+            }
+        }
+        reached
+    }
+
+    /// Indexes into `structure.edges` of the edges the run went through.
+    pub fn taken_edges(&self, structure: &FlowStructure) -> Vec<usize> {
+        let reached = self.reached_nodes(structure);
+        structure
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| reached.contains(&e.source) && reached.contains(&e.target))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_spans_from_events() {
+        let t0 = Utc::now();
+        let mut trace = ExecutionTrace::new("demo");
+        let mut started =
+            MethodExecutionStartedEvent::new("demo".into(), "a".into(), Value::Null, None);
+        started.base.timestamp = t0;
+        trace.on_started(&started);
+        trace.start("a", t0 + Duration::milliseconds(5));
+
+        let mut finished = MethodExecutionFinishedEvent::new(
+            "demo".into(),
+            "a".into(),
+            Some(Value::from(1)),
+            Value::Null,
+        );
+        finished.base.timestamp = t0 + Duration::milliseconds(30);
+        trace.on_finished(&finished);
+        trace.fail("b", t0 + Duration::milliseconds(40), "boom");
+
+        let statuses: Vec<_> = trace.spans.iter().map(|s| s.status()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                SpanStatus::Running,
+                SpanStatus::Completed,
+                SpanStatus::Failed
+            ]
+        );
+        assert_eq!(
+            trace.spans[1].duration(),
+            Some(std::time::Duration::from_millis(25))
+        );
+        assert_eq!(trace.spans[2].duration(), Some(std::time::Duration::ZERO));
+        assert_eq!(trace.executed_methods(), vec!["a", "b"]);
+        assert_eq!(
+            trace.total_duration(),
+            Some(std::time::Duration::from_millis(40))
+        );
+    }
+}
@@ -3,7 +3,12 @@
 //! Corresponds to `crewai/hooks/`.
 //!
 //! Provides context structs and global hook registries for before/after
-//! interception of LLM calls and tool invocations.
+//! interception of LLM calls and tool invocations. Agents and crews also
+//! carry their own [`LLMCallHooks`], which agent executors run after the
+//! global ones around every LLM call.
+//!
+//! Hooks can branch on the current execution's feature flags with
+//! [`crate::utilities::feature_flags::enabled`].
 //!
 //! # Lifecycle Hooks
 //!
@@ -14,7 +19,8 @@
 pub mod lifecycle;
 
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 
 use once_cell::sync::Lazy;
 use serde_json::Value;
@@ -66,6 +72,58 @@ impl Default for LLMCallHookContext {
     }
 }
 
+impl LLMCallHookContext {
+    /// A context for a call with `messages`, whose non-string values are
+    /// given to hooks as JSON.
+    pub fn with_messages(messages: &[HashMap<String, Value>]) -> Self {
+        let messages = messages
+            .iter()
+            .map(|message| {
+                message
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect()
+            })
+            .collect();
+        Self {
+            messages,
+            ..Self::new()
+        }
+    }
+
+    /// Apply the hooks' changes to the context's messages to `messages`,
+    /// the messages it was created [`with`](Self::with_messages).
+    ///
+    /// Values the hooks left alone keep their type; changed ones become
+    /// strings. Messages the hooks added are appended, and removed ones
+    /// dropped from the end.
+    pub fn apply_messages(&self, messages: &mut Vec<HashMap<String, Value>>) {
+        let original = Self::with_messages(messages).messages;
+        if original == self.messages {
+            return;
+        }
+        messages.truncate(self.messages.len());
+        for (i, changed) in self.messages.iter().enumerate() {
+            if i == messages.len() {
+                messages.push(HashMap::new());
+            }
+            let message = &mut messages[i];
+            message.retain(|key, _| changed.contains_key(key));
+            for (key, value) in changed {
+                if original.get(i).and_then(|m| m.get(key)) != Some(value) {
+                    message.insert(key.clone(), Value::String(value.clone()));
+                }
+            }
+        }
+    }
+}
+
 /// Context object passed to tool call hooks.
 ///
 /// Provides hooks with access to the tool being called, its input,
@@ -116,6 +174,75 @@ pub type BeforeToolCallHook = Box<dyn Fn(&mut ToolCallHookContext) -> Option<boo
 /// After-tool-call hook: receives context, returns replacement result or `None`.
 pub type AfterToolCallHook = Box<dyn Fn(&mut ToolCallHookContext) -> Option<String> + Send + Sync>;
 
+// ---------------------------------------------------------------------------
+// Agent and crew hooks
+// ---------------------------------------------------------------------------
+
+/// LLM call hooks of an agent or crew, run after the global ones.
+#[derive(Clone, Default)]
+pub struct LLMCallHooks {
+    before: Vec<Arc<BeforeLLMCallHook>>,
+    after: Vec<Arc<AfterLLMCallHook>>,
+}
+
+impl fmt::Debug for LLMCallHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LLMCallHooks")
+            .field("before", &self.before.len())
+            .field("after", &self.after.len())
+            .finish()
+    }
+}
+
+impl LLMCallHooks {
+    /// Add a before-LLM-call hook, which may return `Some(false)` to block
+    /// the call.
+    pub fn add_before(
+        &mut self,
+        hook: impl Fn(&mut LLMCallHookContext) -> Option<bool> + Send + Sync + 'static,
+    ) {
+        self.before.push(Arc::new(Box::new(hook)));
+    }
+
+    /// Add an after-LLM-call hook, which may return a replacement response.
+    pub fn add_after(
+        &mut self,
+        hook: impl Fn(&mut LLMCallHookContext) -> Option<String> + Send + Sync + 'static,
+    ) {
+        self.after.push(Arc::new(Box::new(hook)));
+    }
+
+    /// Add the hooks of `other` after these.
+    pub fn extend(&mut self, other: &LLMCallHooks) {
+        self.before.extend(other.before.iter().cloned());
+        self.after.extend(other.after.iter().cloned());
+    }
+
+    /// Whether no hooks were added.
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+
+    /// Run the global before-LLM-call hooks, then these.
+    ///
+    /// Returns `false` if any hook blocks the call.
+    pub fn run_before(&self, ctx: &mut LLMCallHookContext) -> bool {
+        run_before_llm_call_hooks(ctx) && self.before.iter().all(|hook| hook(ctx) != Some(false))
+    }
+
+    /// Run the global after-LLM-call hooks, then these.
+    ///
+    /// A hook's replacement response updates `ctx.response`.
+    pub fn run_after(&self, ctx: &mut LLMCallHookContext) {
+        run_after_llm_call_hooks(ctx);
+        for hook in &self.after {
+            if let Some(replacement) = hook(ctx) {
+                ctx.response = Some(replacement);
+            }
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Global hook registries
 // ---------------------------------------------------------------------------
@@ -12,6 +12,7 @@ use serde_json::Value;
 
 use super::source::BaseKnowledgeSource;
 use super::storage::{BaseKnowledgeStorage, KnowledgeStorage};
+use crate::rag::embeddings::EmbedderConfig;
 
 /// Knowledge manages a collection of knowledge sources and provides
 /// query and ingestion capabilities.
@@ -38,8 +39,8 @@ pub struct Knowledge {
     pub sources: Vec<Box<dyn BaseKnowledgeSource>>,
     /// The knowledge storage backend.
     pub storage: Arc<KnowledgeStorage>,
-    /// Optional embedder configuration (provider-specific).
-    pub embedder_config: Option<Value>,
+    /// Optional embedder configuration.
+    pub embedder_config: Option<EmbedderConfig>,
     /// Optional collection name override.
     pub collection_name: Option<String>,
 }
@@ -59,7 +60,7 @@ impl Knowledge {
     ///   provided embedder_config and collection_name.
     pub fn new(
         sources: Vec<Box<dyn BaseKnowledgeSource>>,
-        embedder_config: Option<Value>,
+        embedder_config: Option<EmbedderConfig>,
         collection_name: Option<String>,
         storage: Option<KnowledgeStorage>,
     ) -> Self {
@@ -140,10 +141,41 @@ impl Knowledge {
     /// Reset all knowledge by clearing the storage.
     ///
     /// This removes all stored documents, embeddings, and collections
-    /// from the storage backend.
+    /// from the storage backend. Sources can be re-ingested afterwards
+    /// with `add_sources()`.
     pub fn reset(&self) -> Result<(), anyhow::Error> {
         self.storage.reset()
     }
+
+    /// Names of the collections holding knowledge, sorted.
+    ///
+    /// Sources with a `collection_name` are stored in their own
+    /// "knowledge_{name}" collection; the rest share the storage's.
+    pub fn list_collections(&self) -> Result<Vec<String>, anyhow::Error> {
+        self.storage.list_collections()
+    }
+
+    /// Delete a collection, by its listed name or a source's
+    /// `collection_name`. Returns whether the collection existed.
+    pub fn delete_collection(&self, name: &str) -> Result<bool, anyhow::Error> {
+        self.storage.delete_collection(name)
+    }
+}
+
+/// Query `results` as prompt text: the content of each result, followed
+/// by its `source` metadata when it has one. `None` without results.
+pub fn format_knowledge_results(results: &[Value]) -> Option<String> {
+    let snippets: Vec<String> = results
+        .iter()
+        .filter_map(|result| {
+            let content = result["content"].as_str()?;
+            Some(match result["metadata"]["source"].as_str() {
+                Some(source) => format!("{}\n[Source: {}]", content, source),
+                None => content.to_string(),
+            })
+        })
+        .collect();
+    (!snippets.is_empty()).then(|| snippets.join("\n\n"))
 }
 
 #[cfg(test)]
@@ -174,4 +206,111 @@ mod tests {
             Some("test_collection")
         );
     }
+
+    #[test]
+    fn test_sources_are_routed_to_their_collections() {
+        let faq = StringKnowledgeSource::new(
+            "Refunds are issued within five days after a return is received.".to_string(),
+        )
+        .with_collection_name("faq".to_string());
+        let policy = StringKnowledgeSource::new(
+            "Returns are accepted within thirty days of delivery.".to_string(),
+        );
+        let knowledge = Knowledge::new(
+            vec![Box::new(faq), Box::new(policy)],
+            None,
+            Some("support".to_string()),
+            None,
+        );
+        knowledge.add_sources().unwrap();
+        assert_eq!(
+            knowledge.list_collections().unwrap(),
+            vec!["knowledge_faq", "knowledge_support"]
+        );
+
+        let results = knowledge
+            .query("refund issued after return", None, Some(0.2))
+            .unwrap();
+        assert_eq!(results[0]["collection"], "knowledge_faq");
+        let results = knowledge
+            .storage
+            .search_collection("support", "refund issued after return", 3, 0.2)
+            .unwrap();
+        assert!(results
+            .iter()
+            .all(|r| r["collection"] == "knowledge_support"));
+
+        assert!(knowledge.delete_collection("faq").unwrap());
+        assert!(!knowledge.delete_collection("faq").unwrap());
+        assert_eq!(
+            knowledge.list_collections().unwrap(),
+            vec!["knowledge_support"]
+        );
+
+        knowledge.reset().unwrap();
+        assert!(knowledge.list_collections().unwrap().is_empty());
+        assert!(knowledge.storage.is_empty());
+    }
+
+    #[test]
+    fn test_agent_knowledge_is_queried_from_its_own_collection() {
+        use crate::agent::Agent;
+        use std::collections::HashMap;
+
+        let mut agent = Agent::new(
+            "Support Agent".into(),
+            "Answer customers".into(),
+            "Helpful".into(),
+        );
+        agent.knowledge_sources = vec![Arc::new(StringKnowledgeSource::new(
+            "Refunds are issued within five days after a return is received.".to_string(),
+        ))];
+        let crew_embedder = EmbedderConfig::new("custom");
+        agent.set_knowledge(Some(&crew_embedder)).unwrap();
+        assert_eq!(agent.embedder.as_ref(), Some(&crew_embedder));
+        assert_eq!(
+            agent.knowledge_collection_name().as_deref(),
+            Some("agent_Support_Agent")
+        );
+
+        agent.retrieve_knowledge("When is a refund issued after a return?");
+        assert!(agent
+            .agent_knowledge_context
+            .as_deref()
+            .unwrap()
+            .contains("five days"));
+        agent.retrieve_knowledge("quarterly revenue forecast");
+        assert!(agent.agent_knowledge_context.is_none());
+    }
+
+    #[test]
+    fn test_crew_knowledge_is_retrieved_with_sources() {
+        use crate::agent::Agent;
+        use crate::crew::Crew;
+        use crate::knowledge::source::TextFileKnowledgeSource;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("returns.txt");
+        std::fs::write(
+            &path,
+            "Returns are accepted within thirty days of delivery.",
+        )
+        .unwrap();
+        let mut crew = Crew::new(Vec::new(), Vec::new())
+            .knowledge_source(TextFileKnowledgeSource::new(vec![path.clone()]));
+        assert_eq!(crew.knowledge_collection_name().as_deref(), Some("crew"));
+        crew.set_knowledge().unwrap();
+
+        let mut agent = Agent::new("Support Agent".into(), "Answer".into(), "".into());
+        agent.crew_knowledge = crew.knowledge.clone();
+        agent.retrieve_knowledge("How many days are returns accepted after delivery?");
+        assert!(agent.agent_knowledge_context.is_none());
+        assert_eq!(
+            agent.crew_knowledge_context.unwrap(),
+            format!(
+                "Returns are accepted within thirty days of delivery.\n[Source: {}]",
+                path.display()
+            )
+        );
+    }
 }
@@ -14,7 +14,7 @@ pub mod source;
 pub mod storage;
 
 // Re-export main types.
-pub use self::knowledge::Knowledge;
+pub use self::knowledge::{format_knowledge_results, Knowledge};
 pub use self::knowledge_config::KnowledgeConfig;
 pub use self::source::{BaseFileKnowledgeSource, BaseKnowledgeSource, StringKnowledgeSource};
 pub use self::storage::{BaseKnowledgeStorage, KnowledgeStorage};
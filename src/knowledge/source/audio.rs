@@ -0,0 +1,212 @@
+//! Knowledge source for audio recordings.
+//!
+//! Transcribes each file with a [`Transcriber`] (OpenAI Whisper API or a
+//! local whisper.cpp binary) and ingests the transcript. Chunks carry the
+//! file path, backend, language and duration as metadata.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{BaseFileKnowledgeSource, BaseKnowledgeSource};
+use crate::audio::{Transcriber, Transcript};
+use crate::knowledge::storage::KnowledgeStorage;
+
+/// Knowledge source that transcribes audio files.
+#[derive(Debug, Clone)]
+pub struct AudioKnowledgeSource {
+    /// Paths to the audio files.
+    pub file_paths: Vec<PathBuf>,
+    /// Backend used to transcribe the files.
+    pub transcriber: Arc<dyn Transcriber>,
+    /// Include `[mm:ss.s - mm:ss.s]` segment timestamps in the ingested text.
+    pub timestamps: bool,
+    /// Optional chunk size override.
+    pub chunk_size: Option<usize>,
+    /// Optional chunk overlap override.
+    pub chunk_overlap: Option<usize>,
+    /// Metadata attached to every chunk.
+    pub metadata: HashMap<String, Value>,
+    /// Optional collection name override.
+    pub collection_name: Option<String>,
+}
+
+impl AudioKnowledgeSource {
+    /// Create a source transcribing `file_paths` with `transcriber`.
+    pub fn new(file_paths: Vec<PathBuf>, transcriber: impl Transcriber + 'static) -> Self {
+        Self {
+            file_paths,
+            transcriber: Arc::new(transcriber),
+            timestamps: false,
+            chunk_size: None,
+            chunk_overlap: None,
+            metadata: HashMap::new(),
+            collection_name: None,
+        }
+    }
+
+    /// Builder: include segment timestamps in the ingested text.
+    pub fn with_timestamps(mut self, timestamps: bool) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Builder: set metadata.
+    pub fn with_metadata(mut self, metadata: HashMap<String, Value>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Builder: set chunk parameters.
+    pub fn with_chunking(mut self, chunk_size: usize, chunk_overlap: usize) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self.chunk_overlap = Some(chunk_overlap);
+        self
+    }
+
+    /// Transcribe every file, in order.
+    pub fn transcribe_all(&self) -> Result<Vec<(PathBuf, Transcript)>, anyhow::Error> {
+        self.file_paths
+            .iter()
+            .map(|path| {
+                let transcript = self.transcriber.transcribe(path).map_err(|e| {
+                    anyhow::anyhow!("Failed to transcribe {}: {}", path.display(), e)
+                })?;
+                Ok((path.clone(), transcript))
+            })
+            .collect()
+    }
+
+    fn transcript_text(&self, transcript: &Transcript) -> String {
+        if self.timestamps {
+            transcript.timestamped_text()
+        } else {
+            transcript.text.clone()
+        }
+    }
+
+    fn transcript_metadata(&self, path: &Path, transcript: &Transcript) -> HashMap<String, Value> {
+        let mut metadata = self.metadata.clone();
+        metadata.insert(
+            "source".to_string(),
+            Value::String(path.display().to_string()),
+        );
+        metadata.insert(
+            "transcriber".to_string(),
+            Value::String(self.transcriber.name().to_string()),
+        );
+        if let Some(language) = &transcript.language {
+            metadata.insert("language".to_string(), Value::String(language.clone()));
+        }
+        if let Some(duration) = transcript.duration {
+            metadata.insert("duration_seconds".to_string(), Value::from(duration));
+        }
+        metadata
+    }
+}
+
+#[async_trait]
+impl BaseKnowledgeSource for AudioKnowledgeSource {
+    fn source_name(&self) -> &str {
+        "AudioKnowledgeSource"
+    }
+
+    fn validate_content(&self) -> Result<(), anyhow::Error> {
+        self.validate_paths()
+    }
+
+    fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
+        let mut chunks = Vec::new();
+        for (_, transcript) in self.transcribe_all()? {
+            chunks.extend(self.chunk_text(
+                &self.transcript_text(&transcript),
+                self.chunk_size,
+                self.chunk_overlap,
+            ));
+        }
+        Ok(chunks)
+    }
+
+    fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
+        self.validate_content()?;
+        for (path, transcript) in self.transcribe_all()? {
+            let text = self.transcript_text(&transcript);
+            if text.trim().is_empty() {
+                continue;
+            }
+            let chunks = self.chunk_text(&text, self.chunk_size, self.chunk_overlap);
+            storage.save_chunks_to(
+                self.collection_name.as_deref(),
+                &chunks,
+                &self.transcript_metadata(&path, &transcript),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn metadata(&self) -> HashMap<String, Value> {
+        self.metadata.clone()
+    }
+}
+
+#[async_trait]
+impl BaseFileKnowledgeSource for AudioKnowledgeSource {
+    fn file_paths(&self) -> &[PathBuf] {
+        &self.file_paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{AudioError, TranscriptSegment};
+
+    #[derive(Debug)]
+    struct FixedTranscriber;
+
+    impl Transcriber for FixedTranscriber {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        fn transcribe(&self, path: &Path) -> Result<Transcript, AudioError> {
+            Ok(Transcript {
+                text: format!("Recording {}", path.display()),
+                language: Some("en".into()),
+                duration: Some(2.0),
+                segments: vec![TranscriptSegment {
+                    start: 0.0,
+                    end: 2.0,
+                    text: "Recording".into(),
+                }],
+            })
+        }
+    }
+
+    #[test]
+    fn test_transcripts_as_chunks() {
+        let source = AudioKnowledgeSource::new(
+            vec![PathBuf::from("a.mp3"), PathBuf::from("b.mp3")],
+            FixedTranscriber,
+        );
+        assert_eq!(
+            source.load_content().unwrap(),
+            vec!["Recording a.mp3", "Recording b.mp3"]
+        );
+        assert!(source.validate_content().is_err());
+
+        let source = source.with_timestamps(true);
+        assert_eq!(
+            source.load_content().unwrap()[0],
+            "[00:00.0 - 00:02.0] Recording"
+        );
+        let (path, transcript) = source.transcribe_all().unwrap().remove(1);
+        let metadata = source.transcript_metadata(&path, &transcript);
+        assert_eq!(metadata["source"], "b.mp3");
+        assert_eq!(metadata["transcriber"], "fixed");
+        assert_eq!(metadata["duration_seconds"], 2.0);
+    }
+}
@@ -0,0 +1,1124 @@
+//! Knowledge source for email inboxes over IMAP.
+//!
+//! [`EmailKnowledgeSource`] fetches messages from one or more IMAP folders
+//! (optionally restricted to a date range), converts each message body to
+//! plain text, and hands attachments to the matching file knowledge source
+//! (text, CSV, JSON, PDF, Excel). Every chunk is saved with the message's
+//! sender, recipients, date, subject and folder as metadata.
+//!
+//! Fetching is behind the [`MailFetcher`] trait; [`ImapFetcher`] is a
+//! minimal, read-only IMAP4rev1 client (LOGIN, EXAMINE, UID SEARCH, UID
+//! FETCH) over TLS or plain TCP.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{
+    BaseKnowledgeSource, CSVKnowledgeSource, ExcelKnowledgeSource, JSONKnowledgeSource,
+    PDFKnowledgeSource, TextFileKnowledgeSource,
+};
+use crate::knowledge::storage::KnowledgeStorage;
+
+// ---------------------------------------------------------------------------
+// Fetching
+// ---------------------------------------------------------------------------
+
+/// A source of raw RFC 5322 messages.
+pub trait MailFetcher: Send + Sync {
+    /// Fetch the raw messages in `folder` received on or after `since` and
+    /// before `before` (both optional, day granularity).
+    fn fetch(
+        &self,
+        folder: &str,
+        since: Option<NaiveDate>,
+        before: Option<NaiveDate>,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error>;
+}
+
+/// Connection settings for an IMAP server.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ImapConfig {
+    /// Server host name.
+    pub host: String,
+    /// Server port (993 for IMAPS, 143 for plain IMAP).
+    #[serde(default = "default_imap_port")]
+    pub port: u16,
+    /// Login user name.
+    pub username: String,
+    /// Login password or app password. Never serialized.
+    #[serde(default, skip_serializing)]
+    pub password: String,
+    /// Connect with implicit TLS.
+    #[serde(default = "default_true")]
+    pub use_tls: bool,
+    /// Socket read/write timeout in seconds.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_imap_port() -> u16 {
+    993
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+impl ImapConfig {
+    /// Settings for an IMAPS server on port 993.
+    pub fn new(
+        host: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        Self {
+            host: host.into(),
+            port: default_imap_port(),
+            username: username.into(),
+            password: password.into(),
+            use_tls: true,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ImapConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImapConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .field("use_tls", &self.use_tls)
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+/// [`MailFetcher`] speaking IMAP4rev1 to a server.
+#[derive(Debug, Clone)]
+pub struct ImapFetcher {
+    config: ImapConfig,
+}
+
+impl ImapFetcher {
+    /// Create a fetcher for the given server.
+    pub fn new(config: ImapConfig) -> Self {
+        Self { config }
+    }
+
+    fn connect(&self) -> Result<ImapSession<Box<dyn ReadWrite>>, anyhow::Error> {
+        let address = (self.config.host.as_str(), self.config.port);
+        let tcp = TcpStream::connect(address).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to connect to IMAP server {}:{}: {}",
+                self.config.host,
+                self.config.port,
+                e
+            )
+        })?;
+        let timeout = Some(Duration::from_secs(self.config.timeout_secs));
+        tcp.set_read_timeout(timeout)?;
+        tcp.set_write_timeout(timeout)?;
+
+        let stream: Box<dyn ReadWrite> = if self.config.use_tls {
+            let connector = native_tls::TlsConnector::new()?;
+            Box::new(
+                connector
+                    .connect(&self.config.host, tcp)
+                    .map_err(|e| anyhow::anyhow!("IMAP TLS handshake failed: {}", e))?,
+            )
+        } else {
+            Box::new(tcp)
+        };
+        ImapSession::open(stream)
+    }
+}
+
+impl MailFetcher for ImapFetcher {
+    fn fetch(
+        &self,
+        folder: &str,
+        since: Option<NaiveDate>,
+        before: Option<NaiveDate>,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+        let mut session = self.connect()?;
+        session.login(&self.config.username, &self.config.password)?;
+        let result = session.fetch_folder(folder, since, before);
+        // Best effort; the messages are already read.
+        let _ = session.logout();
+        result
+    }
+}
+
+/// Byte stream an IMAP session runs over.
+trait ReadWrite: Read + Write + Send {}
+
+impl<T: Read + Write + Send> ReadWrite for T {}
+
+/// One untagged response line, with any literals it carried.
+struct UntaggedResponse {
+    line: String,
+    literals: Vec<Vec<u8>>,
+}
+
+/// A minimal synchronous IMAP client session.
+struct ImapSession<T: Read + Write> {
+    stream: BufReader<T>,
+    next_tag: u32,
+}
+
+impl<T: Read + Write> ImapSession<T> {
+    /// Wrap a connected stream and read the server greeting.
+    fn open(stream: T) -> Result<Self, anyhow::Error> {
+        let mut session = Self {
+            stream: BufReader::new(stream),
+            next_tag: 1,
+        };
+        let greeting = session.read_line()?;
+        if !greeting.starts_with("* OK") && !greeting.starts_with("* PREAUTH") {
+            return Err(anyhow::anyhow!(
+                "Unexpected IMAP greeting: {}",
+                greeting.trim_end()
+            ));
+        }
+        Ok(session)
+    }
+
+    fn login(&mut self, username: &str, password: &str) -> Result<(), anyhow::Error> {
+        self.command(&format!("LOGIN {} {}", quote(username), quote(password)))
+            .map_err(|e| anyhow::anyhow!("IMAP login failed for {}: {}", username, e))?;
+        Ok(())
+    }
+
+    fn fetch_folder(
+        &mut self,
+        folder: &str,
+        since: Option<NaiveDate>,
+        before: Option<NaiveDate>,
+    ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+        self.command(&format!("EXAMINE {}", quote(folder)))?;
+
+        let mut criteria = Vec::new();
+        if let Some(since) = since {
+            criteria.push(format!("SINCE {}", since.format("%d-%b-%Y")));
+        }
+        if let Some(before) = before {
+            criteria.push(format!("BEFORE {}", before.format("%d-%b-%Y")));
+        }
+        if criteria.is_empty() {
+            criteria.push("ALL".to_string());
+        }
+        let uids: Vec<String> = self
+            .command(&format!("UID SEARCH {}", criteria.join(" ")))?
+            .iter()
+            .filter_map(|r| r.line.strip_prefix("* SEARCH"))
+            .flat_map(|rest| rest.split_whitespace().map(str::to_string))
+            .collect();
+        if uids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let responses = self.command(&format!("UID FETCH {} BODY.PEEK[]", uids.join(",")))?;
+        Ok(responses
+            .into_iter()
+            .filter(|r| r.line.contains("FETCH"))
+            .filter_map(|r| r.literals.into_iter().next())
+            .collect())
+    }
+
+    fn logout(&mut self) -> Result<(), anyhow::Error> {
+        self.command("LOGOUT").map(|_| ())
+    }
+
+    /// Send a tagged command and collect its untagged responses.
+    fn command(&mut self, command: &str) -> Result<Vec<UntaggedResponse>, anyhow::Error> {
+        let tag = format!("A{:04}", self.next_tag);
+        self.next_tag += 1;
+        let stream = self.stream.get_mut();
+        stream.write_all(format!("{} {}\r\n", tag, command).as_bytes())?;
+        stream.flush()?;
+
+        let mut responses = Vec::new();
+        loop {
+            let mut line = self.read_line()?;
+            if let Some(status) = line.strip_prefix(&format!("{} ", tag)) {
+                if status.starts_with("OK") {
+                    return Ok(responses);
+                }
+                return Err(anyhow::anyhow!("{}", status.trim_end()));
+            }
+
+            let mut literals = Vec::new();
+            while let Some(size) = literal_size(&line) {
+                let mut literal = vec![0u8; size];
+                self.stream.read_exact(&mut literal)?;
+                literals.push(literal);
+                line.push_str(&self.read_line()?);
+            }
+            responses.push(UntaggedResponse { line, literals });
+        }
+    }
+
+    fn read_line(&mut self) -> Result<String, anyhow::Error> {
+        let mut buf = Vec::new();
+        if self.stream.read_until(b'\n', &mut buf)? == 0 {
+            return Err(anyhow::anyhow!("IMAP connection closed unexpectedly"));
+        }
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+}
+
+/// Size of the literal announced at the end of a response line (`{123}`).
+fn literal_size(line: &str) -> Option<usize> {
+    let line = line.trim_end();
+    let open = line.rfind('{')?;
+    line.strip_suffix('}')?[open + 1..].parse().ok()
+}
+
+/// IMAP quoted string.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// ---------------------------------------------------------------------------
+// Message parsing
+// ---------------------------------------------------------------------------
+
+/// A file attached to an email.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailAttachment {
+    /// File name from the MIME headers.
+    pub filename: String,
+    /// MIME type, e.g. `text/csv`.
+    pub content_type: String,
+    /// Decoded content.
+    pub data: Vec<u8>,
+}
+
+/// A parsed email message.
+#[derive(Debug, Clone, Default)]
+pub struct EmailMessage {
+    /// Folder the message was fetched from.
+    pub folder: String,
+    /// `Message-ID` header.
+    pub message_id: Option<String>,
+    /// `From` header.
+    pub from: Option<String>,
+    /// `To` header.
+    pub to: Option<String>,
+    /// `Subject` header.
+    pub subject: Option<String>,
+    /// `Date` header.
+    pub date: Option<DateTime<FixedOffset>>,
+    /// Plain-text body (HTML bodies are converted to text).
+    pub body: String,
+    /// Attached files.
+    pub attachments: Vec<EmailAttachment>,
+}
+
+impl EmailMessage {
+    /// Parse a raw RFC 5322 / MIME message.
+    pub fn parse(raw: &[u8], folder: &str) -> Self {
+        let part = MimePart::parse(raw);
+        let header = |name: &str| part.header(name).map(decode_encoded_words);
+
+        let mut message = Self {
+            folder: folder.to_string(),
+            message_id: header("message-id"),
+            from: header("from"),
+            to: header("to"),
+            subject: header("subject"),
+            date: part.header("date").and_then(parse_date),
+            ..Default::default()
+        };
+        let mut html = None;
+        message.collect(&part, &mut html);
+        if message.body.trim().is_empty() {
+            if let Some(html) = html {
+                message.body = html_to_text(&html);
+            }
+        }
+        message.body = message.body.trim().to_string();
+        message
+    }
+
+    fn collect(&mut self, part: &MimePart, html: &mut Option<String>) {
+        let (mime, params) = part.content_type();
+        if let Some(filename) = part.filename() {
+            self.attachments.push(EmailAttachment {
+                filename,
+                content_type: mime,
+                data: part.decoded_body(),
+            });
+            return;
+        }
+        if mime.starts_with("multipart/") {
+            if let Some(boundary) = params.get("boundary") {
+                for child in part.split_multipart(boundary) {
+                    self.collect(&child, html);
+                }
+            }
+        } else if mime == "message/rfc822" {
+            let nested = Self::parse(&part.decoded_body(), &self.folder);
+            self.body.push_str(&format!(
+                "\n\n--- Forwarded message from {} ---\n{}",
+                nested.from.as_deref().unwrap_or("unknown sender"),
+                nested.body
+            ));
+            self.attachments.extend(nested.attachments);
+        } else if mime == "text/html" {
+            if html.is_none() {
+                *html = Some(part.text());
+            }
+        } else if mime == "text/plain" {
+            if !self.body.is_empty() {
+                self.body.push_str("\n\n");
+            }
+            self.body.push_str(&part.text());
+        }
+    }
+
+    /// The message as a text document for ingestion.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (label, value) in [
+            ("From", &self.from),
+            ("To", &self.to),
+            ("Subject", &self.subject),
+        ] {
+            if let Some(value) = value {
+                text.push_str(&format!("{}: {}\n", label, value));
+            }
+        }
+        if let Some(date) = &self.date {
+            text.push_str(&format!("Date: {}\n", date.to_rfc2822()));
+        }
+        text.push('\n');
+        text.push_str(&self.body);
+        text
+    }
+
+    /// Sender, recipients, date, subject and folder as chunk metadata.
+    pub fn metadata(&self) -> HashMap<String, Value> {
+        let mut metadata = HashMap::new();
+        metadata.insert("source".to_string(), Value::from("email"));
+        metadata.insert("folder".to_string(), Value::from(self.folder.clone()));
+        let fields = [
+            ("message_id", &self.message_id),
+            ("from", &self.from),
+            ("to", &self.to),
+            ("subject", &self.subject),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                metadata.insert(key.to_string(), Value::from(value.clone()));
+            }
+        }
+        if let Some(date) = &self.date {
+            metadata.insert("date".to_string(), Value::from(date.to_rfc3339()));
+        }
+        metadata
+    }
+}
+
+/// A MIME entity: headers plus raw body.
+struct MimePart {
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MimePart {
+    fn parse(raw: &[u8]) -> Self {
+        let (head, body) = match find(raw, b"\r\n\r\n") {
+            Some(i) => (&raw[..i], &raw[i + 4..]),
+            None => match find(raw, b"\n\n") {
+                Some(i) => (&raw[..i], &raw[i + 2..]),
+                None => (raw, &[][..]),
+            },
+        };
+
+        let mut headers: Vec<(String, String)> = Vec::new();
+        for line in String::from_utf8_lossy(head).lines() {
+            if line.starts_with([' ', '\t']) {
+                if let Some((_, value)) = headers.last_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+            } else if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+            }
+        }
+        Self {
+            headers,
+            body: body.to_vec(),
+        }
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Lower-cased MIME type and its parameters (`text/plain` by default).
+    fn content_type(&self) -> (String, HashMap<String, String>) {
+        match self.header("content-type") {
+            Some(value) => {
+                let (mime, params) = split_params(value);
+                (mime.to_ascii_lowercase(), params)
+            }
+            None => ("text/plain".to_string(), HashMap::new()),
+        }
+    }
+
+    /// File name, if the part is an attachment.
+    fn filename(&self) -> Option<String> {
+        let (disposition, disposition_params) = self
+            .header("content-disposition")
+            .map(split_params)
+            .unwrap_or_default();
+        let (_, type_params) = self.content_type();
+        let name = disposition_params
+            .get("filename")
+            .or_else(|| type_params.get("name"))
+            .map(|n| decode_encoded_words(n));
+        match name {
+            Some(name) => Some(name),
+            None if disposition.eq_ignore_ascii_case("attachment") => {
+                Some("attachment".to_string())
+            }
+            None => None,
+        }
+    }
+
+    fn decoded_body(&self) -> Vec<u8> {
+        let encoding = self
+            .header("content-transfer-encoding")
+            .unwrap_or("7bit")
+            .to_ascii_lowercase();
+        match encoding.as_str() {
+            "base64" => {
+                let compact: Vec<u8> = self
+                    .body
+                    .iter()
+                    .copied()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect();
+                base64::engine::general_purpose::STANDARD
+                    .decode(compact)
+                    .unwrap_or_else(|_| self.body.clone())
+            }
+            "quoted-printable" => decode_quoted_printable(&self.body, false),
+            _ => self.body.clone(),
+        }
+    }
+
+    /// Decoded body as text in the part's charset.
+    fn text(&self) -> String {
+        let (_, params) = self.content_type();
+        decode_charset(
+            &self.decoded_body(),
+            params.get("charset").map(String::as_str),
+        )
+    }
+
+    fn split_multipart(&self, boundary: &str) -> Vec<MimePart> {
+        let delimiter = format!("--{}", boundary);
+        let body = String::from_utf8_lossy(&self.body);
+        let mut parts = Vec::new();
+        let mut current: Option<Vec<&str>> = None;
+        for line in body.split('\n') {
+            let trimmed = line.trim_end_matches('\r');
+            if trimmed.starts_with(&delimiter) {
+                if let Some(lines) = current.take() {
+                    parts.push(MimePart::parse(lines.join("\n").as_bytes()));
+                }
+                if trimmed[delimiter.len()..].starts_with("--") {
+                    break;
+                }
+                current = Some(Vec::new());
+            } else if let Some(lines) = current.as_mut() {
+                lines.push(trimmed);
+            }
+        }
+        parts
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Split `value; key=val; key2="val 2"` into the value and its parameters.
+fn split_params(value: &str) -> (String, HashMap<String, String>) {
+    let mut pieces = value.split(';');
+    let main = pieces.next().unwrap_or_default().trim().to_string();
+    let params = pieces
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().trim_end_matches('*').to_ascii_lowercase(),
+                v.trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    (main, params)
+}
+
+fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    // Drop trailing comments such as "(UTC)".
+    let value = value.split('(').next().unwrap_or(value).trim();
+    DateTime::parse_from_rfc2822(value).ok()
+}
+
+fn decode_charset(bytes: &[u8], charset: Option<&str>) -> String {
+    let encoding = charset
+        .and_then(|c| encoding_rs::Encoding::for_label(c.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8);
+    encoding.decode(bytes).0.into_owned()
+}
+
+/// Decode quoted-printable; `header` also maps `_` to space (RFC 2047 Q).
+fn decode_quoted_printable(input: &[u8], header: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'=' if input[i + 1..].starts_with(b"\r\n") => i += 3,
+            b'=' if input[i + 1..].starts_with(b"\n") => i += 2,
+            b'=' if i + 2 < input.len() => {
+                match std::str::from_utf8(&input[i + 1..i + 3])
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 3;
+                    }
+                    None => {
+                        out.push(b'=');
+                        i += 1;
+                    }
+                }
+            }
+            b'_' if header => {
+                out.push(b' ');
+                i += 1;
+            }
+            byte => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Decode RFC 2047 encoded words (`=?utf-8?B?...?=`) in a header value.
+fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut after_word = false;
+    while let Some(start) = rest.find("=?") {
+        let decoded = rest[start + 2..].splitn(3, '?').collect::<Vec<_>>();
+        let word = match decoded.as_slice() {
+            [charset, encoding, tail] => tail.find("?=").map(|end| {
+                let text = &tail[..end];
+                let bytes = if encoding.eq_ignore_ascii_case("b") {
+                    base64::engine::general_purpose::STANDARD
+                        .decode(text)
+                        .unwrap_or_default()
+                } else {
+                    decode_quoted_printable(text.as_bytes(), true)
+                };
+                let consumed = start + 2 + charset.len() + encoding.len() + 2 + end + 2;
+                (decode_charset(&bytes, Some(charset)), consumed)
+            }),
+            _ => None,
+        };
+        let Some((text, consumed)) = word else {
+            break;
+        };
+        // Whitespace between adjacent encoded words is not significant.
+        let between = &rest[..start];
+        if !(after_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&text);
+        rest = &rest[consumed..];
+        after_word = true;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Convert an HTML body to readable plain text.
+fn html_to_text(html: &str) -> String {
+    let hidden = regex::Regex::new(r"(?is)<(script|style|head)\b.*?</(script|style|head)>")
+        .expect("valid regex");
+    let breaks =
+        regex::Regex::new(r"(?i)<(br|/p|/div|/li|/tr|/h[1-6])\b[^>]*>").expect("valid regex");
+    let tags = regex::Regex::new(r"(?s)<[^>]+>").expect("valid regex");
+    let blank_lines = regex::Regex::new(r"\n\s*\n\s*\n+").expect("valid regex");
+
+    let text = hidden.replace_all(html, "");
+    let text = breaks.replace_all(&text, "\n");
+    let text = tags.replace_all(&text, "");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let text: Vec<&str> = text.lines().map(str::trim).collect();
+    blank_lines
+        .replace_all(&text.join("\n"), "\n\n")
+        .trim()
+        .to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Knowledge source
+// ---------------------------------------------------------------------------
+
+/// Knowledge source for messages in an IMAP mailbox.
+///
+/// Each message becomes a document with its headers and body; attachments
+/// are ingested through the file knowledge source for their type. Chunks
+/// carry the message's sender, date, subject and folder as metadata.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EmailKnowledgeSource {
+    /// IMAP server settings.
+    pub imap: ImapConfig,
+    /// Folders to read.
+    #[serde(default = "default_folders")]
+    pub folders: Vec<String>,
+    /// Only messages received on or after this date.
+    #[serde(default)]
+    pub since: Option<NaiveDate>,
+    /// Only messages received before this date.
+    #[serde(default)]
+    pub before: Option<NaiveDate>,
+    /// Maximum number of messages per folder (most recent kept).
+    #[serde(default)]
+    pub max_messages: Option<usize>,
+    /// Ingest attachments through the matching file sources.
+    #[serde(default = "default_true")]
+    pub include_attachments: bool,
+    /// Optional chunk size override.
+    pub chunk_size: Option<usize>,
+    /// Optional chunk overlap override.
+    pub chunk_overlap: Option<usize>,
+    /// Optional metadata to attach to chunks.
+    #[serde(default)]
+    pub metadata: HashMap<String, Value>,
+    /// Optional collection name override.
+    pub collection_name: Option<String>,
+    /// Fetcher override (defaults to [`ImapFetcher`]).
+    #[serde(skip)]
+    fetcher: Option<Arc<dyn MailFetcher>>,
+}
+
+fn default_folders() -> Vec<String> {
+    vec!["INBOX".to_string()]
+}
+
+impl EmailKnowledgeSource {
+    /// Create a source reading `INBOX` on the given server.
+    pub fn new(imap: ImapConfig) -> Self {
+        Self {
+            imap,
+            folders: default_folders(),
+            since: None,
+            before: None,
+            max_messages: None,
+            include_attachments: true,
+            chunk_size: None,
+            chunk_overlap: None,
+            metadata: HashMap::new(),
+            collection_name: None,
+            fetcher: None,
+        }
+    }
+
+    /// Builder: set the folders to read.
+    pub fn with_folders(mut self, folders: Vec<String>) -> Self {
+        self.folders = folders;
+        self
+    }
+
+    /// Builder: restrict to messages received in `[since, before)`.
+    pub fn with_date_range(mut self, since: Option<NaiveDate>, before: Option<NaiveDate>) -> Self {
+        self.since = since;
+        self.before = before;
+        self
+    }
+
+    /// Builder: cap the number of messages per folder.
+    pub fn with_max_messages(mut self, max: usize) -> Self {
+        self.max_messages = Some(max);
+        self
+    }
+
+    /// Builder: fetch messages through a custom [`MailFetcher`].
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn MailFetcher>) -> Self {
+        self.fetcher = Some(fetcher);
+        self
+    }
+
+    /// Fetch and parse the messages matching the folder and date filters.
+    pub fn fetch_messages(&self) -> Result<Vec<EmailMessage>, anyhow::Error> {
+        let fetcher = match &self.fetcher {
+            Some(fetcher) => fetcher.clone(),
+            None => Arc::new(ImapFetcher::new(self.imap.clone())),
+        };
+        let mut messages = Vec::new();
+        for folder in &self.folders {
+            let mut folder_messages: Vec<EmailMessage> = fetcher
+                .fetch(folder, self.since, self.before)?
+                .iter()
+                .map(|raw| EmailMessage::parse(raw, folder))
+                .filter(|m| self.in_date_range(m))
+                .collect();
+            if let Some(max) = self.max_messages {
+                let skip = folder_messages.len().saturating_sub(max);
+                folder_messages.drain(..skip);
+            }
+            log::debug!(
+                "EmailKnowledgeSource: {} message(s) from {}",
+                folder_messages.len(),
+                folder
+            );
+            messages.extend(folder_messages);
+        }
+        Ok(messages)
+    }
+
+    /// Servers filter by internal date; re-check the `Date` header.
+    fn in_date_range(&self, message: &EmailMessage) -> bool {
+        let Some(date) = message.date.map(|d| d.date_naive()) else {
+            return true;
+        };
+        self.since.is_none_or(|since| date >= since)
+            && self.before.is_none_or(|before| date < before)
+    }
+
+    /// Text chunks for a message and its attachments.
+    fn message_chunks(&self, message: &EmailMessage) -> Vec<(String, HashMap<String, Value>)> {
+        let mut metadata = self.metadata.clone();
+        metadata.extend(message.metadata());
+
+        let mut chunks: Vec<_> = self
+            .chunk_text(&message.to_text(), self.chunk_size, self.chunk_overlap)
+            .into_iter()
+            .map(|c| (c, metadata.clone()))
+            .collect();
+
+        if self.include_attachments {
+            for attachment in &message.attachments {
+                match self.attachment_chunks(attachment) {
+                    Ok(attachment_chunks) => {
+                        let mut attachment_metadata = metadata.clone();
+                        attachment_metadata.insert(
+                            "attachment".to_string(),
+                            Value::from(attachment.filename.clone()),
+                        );
+                        chunks.extend(
+                            attachment_chunks
+                                .into_iter()
+                                .map(|c| (c, attachment_metadata.clone())),
+                        );
+                    }
+                    Err(e) => log::warn!(
+                        "EmailKnowledgeSource: skipping attachment {}: {}",
+                        attachment.filename,
+                        e
+                    ),
+                }
+            }
+        }
+        chunks
+    }
+
+    /// Ingest an attachment through the file source matching its type.
+    fn attachment_chunks(
+        &self,
+        attachment: &EmailAttachment,
+    ) -> Result<Vec<String>, anyhow::Error> {
+        let extension = Path::new(&attachment.filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .unwrap_or_default();
+
+        let dir = std::env::temp_dir().join(format!("crewai-email-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        let file_name = Path::new(&attachment.filename)
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("attachment"));
+        let path = dir.join(file_name);
+        std::fs::write(&path, &attachment.data)?;
+
+        let paths = vec![path];
+        let result = match extension.as_str() {
+            "csv" => CSVKnowledgeSource::new(paths).load_content(),
+            "json" => JSONKnowledgeSource::new(paths).load_content(),
+            "pdf" => PDFKnowledgeSource::new(paths).load_content(),
+            "xlsx" | "xls" => ExcelKnowledgeSource::new(paths).load_content(),
+            _ if matches!(extension.as_str(), "txt" | "md" | "log" | "text")
+                || attachment.content_type.starts_with("text/") =>
+            {
+                let mut source = TextFileKnowledgeSource::new(paths);
+                source.chunk_size = self.chunk_size;
+                source.chunk_overlap = self.chunk_overlap;
+                source.load_content()
+            }
+            _ => Err(anyhow::anyhow!(
+                "no knowledge source for {}",
+                attachment.content_type
+            )),
+        };
+        let _ = std::fs::remove_dir_all(&dir);
+        result
+    }
+}
+
+impl std::fmt::Debug for EmailKnowledgeSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmailKnowledgeSource")
+            .field("imap", &self.imap)
+            .field("folders", &self.folders)
+            .field("since", &self.since)
+            .field("before", &self.before)
+            .field("max_messages", &self.max_messages)
+            .field("include_attachments", &self.include_attachments)
+            .field("collection_name", &self.collection_name)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BaseKnowledgeSource for EmailKnowledgeSource {
+    fn source_name(&self) -> &str {
+        "EmailKnowledgeSource"
+    }
+
+    fn validate_content(&self) -> Result<(), anyhow::Error> {
+        if self.fetcher.is_none() && self.imap.host.is_empty() {
+            return Err(anyhow::anyhow!(
+                "EmailKnowledgeSource requires an IMAP host"
+            ));
+        }
+        if self.folders.is_empty() {
+            return Err(anyhow::anyhow!(
+                "EmailKnowledgeSource requires at least one folder"
+            ));
+        }
+        Ok(())
+    }
+
+    fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
+        self.validate_content()?;
+        Ok(self
+            .fetch_messages()?
+            .iter()
+            .flat_map(|m| self.message_chunks(m))
+            .map(|(chunk, _)| chunk)
+            .collect())
+    }
+
+    fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
+        self.validate_content()?;
+        for message in self.fetch_messages()? {
+            for (chunk, metadata) in self.message_chunks(&message) {
+                storage.save_chunks_to(self.collection_name.as_deref(), &[chunk], &metadata)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn metadata(&self) -> HashMap<String, Value> {
+        self.metadata.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const MULTIPART: &str = "From: =?utf-8?B?SsO2cmc=?= <jorg@example.com>\r\n\
+To: support@example.com\r\n\
+Subject: =?utf-8?Q?Invoice_r=C3=A9sum=C3=A9?=\r\n\
+\t=?utf-8?Q?_attached?=\r\n\
+Date: Tue, 03 Mar 2026 10:15:00 +0100 (CET)\r\n\
+Message-ID: <abc@example.com>\r\n\
+MIME-Version: 1.0\r\n\
+Content-Type: multipart/mixed; boundary=\"outer\"\r\n\
+\r\n\
+--outer\r\n\
+Content-Type: multipart/alternative; boundary=inner\r\n\
+\r\n\
+--inner\r\n\
+Content-Type: text/plain; charset=utf-8\r\n\
+Content-Transfer-Encoding: quoted-printable\r\n\
+\r\n\
+The totals are in the sheet, caf=C3=A9 receipts incl=\r\n\
+uded.\r\n\
+--inner\r\n\
+Content-Type: text/html\r\n\
+\r\n\
+<p>ignored</p>\r\n\
+--inner--\r\n\
+--outer\r\n\
+Content-Type: text/csv; name=\"totals.csv\"\r\n\
+Content-Disposition: attachment; filename=\"totals.csv\"\r\n\
+Content-Transfer-Encoding: base64\r\n\
+\r\n\
+aXRlbSxhbW91bnQKY29mZmVlLDMuNTAK\r\n\
+--outer--\r\n";
+
+    #[test]
+    fn test_parse_multipart_message() {
+        let message = EmailMessage::parse(MULTIPART.as_bytes(), "INBOX");
+        assert_eq!(message.from.as_deref(), Some("Jörg <jorg@example.com>"));
+        assert_eq!(message.subject.as_deref(), Some("Invoice résumé attached"));
+        assert_eq!(
+            message.date.unwrap().to_rfc3339(),
+            "2026-03-03T10:15:00+01:00"
+        );
+        assert_eq!(
+            message.body,
+            "The totals are in the sheet, café receipts included."
+        );
+        assert_eq!(message.attachments.len(), 1);
+        assert_eq!(message.attachments[0].filename, "totals.csv");
+        assert_eq!(
+            message.attachments[0].data,
+            b"item,amount\ncoffee,3.50\n".to_vec()
+        );
+        assert_eq!(
+            message.metadata()["from"],
+            Value::from("Jörg <jorg@example.com>")
+        );
+    }
+
+    #[test]
+    fn test_html_only_body_is_converted() {
+        let raw = "Subject: hi\nContent-Type: text/html\n\n<html><head><style>p{}</style></head>\
+<body><p>Hello &amp; welcome</p><p>Line two<br>three</p></body></html>";
+        let message = EmailMessage::parse(raw.as_bytes(), "INBOX");
+        assert_eq!(message.body, "Hello & welcome\nLine two\nthree");
+    }
+
+    struct ScriptedStream {
+        input: Cursor<Vec<u8>>,
+        output: Vec<u8>,
+    }
+
+    impl Read for ScriptedStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for ScriptedStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_imap_session_fetches_literals() {
+        let message = "Subject: a\r\n\r\nbody\r\n";
+        let server = format!(
+            "* OK ready\r\n\
+A0001 OK logged in\r\n\
+* 2 EXISTS\r\nA0002 OK [READ-ONLY] done\r\n\
+* SEARCH 7 9\r\nA0003 OK done\r\n\
+* 1 FETCH (UID 7 BODY[] {{{len}}}\r\n{message})\r\n\
+* 2 FETCH (UID 9 BODY[] {{{len}}}\r\n{message})\r\n\
+A0004 OK done\r\n",
+            len = message.len(),
+            message = message
+        );
+        let stream = ScriptedStream {
+            input: Cursor::new(server.into_bytes()),
+            output: Vec::new(),
+        };
+        let mut session = ImapSession::open(stream).unwrap();
+        session.login("me", "p\"w").unwrap();
+        let since = NaiveDate::from_ymd_opt(2026, 3, 1);
+        let messages = session.fetch_folder("INBOX", since, None).unwrap();
+        assert_eq!(messages, vec![message.as_bytes().to_vec(); 2]);
+
+        let sent = String::from_utf8(session.stream.get_ref().output.clone()).unwrap();
+        assert_eq!(
+            sent,
+            "A0001 LOGIN \"me\" \"p\\\"w\"\r\n\
+A0002 EXAMINE \"INBOX\"\r\n\
+A0003 UID SEARCH SINCE 01-Mar-2026\r\n\
+A0004 UID FETCH 7,9 BODY.PEEK[]\r\n"
+        );
+    }
+
+    struct StaticFetcher(Vec<Vec<u8>>);
+
+    impl MailFetcher for StaticFetcher {
+        fn fetch(
+            &self,
+            _folder: &str,
+            _since: Option<NaiveDate>,
+            _before: Option<NaiveDate>,
+        ) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_source_filters_dates_and_dispatches_attachments() {
+        let old = "From: a@example.com\nDate: Sun, 01 Feb 2026 09:00:00 +0000\n\nold news";
+        let source = EmailKnowledgeSource::new(ImapConfig::new("imap.example.com", "me", "pw"))
+            .with_date_range(NaiveDate::from_ymd_opt(2026, 3, 1), None)
+            .with_fetcher(Arc::new(StaticFetcher(vec![
+                old.as_bytes().to_vec(),
+                MULTIPART.as_bytes().to_vec(),
+            ])));
+
+        let messages = source.fetch_messages().unwrap();
+        assert_eq!(messages.len(), 1);
+        let chunks = source.message_chunks(&messages[0]);
+        let texts: Vec<&str> = chunks.iter().map(|(c, _)| c.as_str()).collect();
+        assert!(texts[0].starts_with("From: Jörg <jorg@example.com>\n"));
+        assert!(texts[0].ends_with("café receipts included."));
+        assert_eq!(&texts[1..], &["item,amount", "coffee,3.50"]);
+        assert_eq!(chunks[1].1["attachment"], Value::from("totals.csv"));
+        assert_eq!(
+            chunks[1].1["subject"],
+            Value::from("Invoice résumé attached")
+        );
+    }
+}
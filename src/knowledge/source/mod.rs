@@ -4,17 +4,22 @@
 //!
 //! Provides the `BaseKnowledgeSource` and `BaseFileKnowledgeSource` traits
 //! along with concrete implementations for strings, text files, CSV, PDF,
-//! JSON, and Excel sources.
+//! JSON, and Excel sources, plus email inboxes over IMAP ([`email`]) and
+//! transcribed audio recordings ([`audio`]).
+
+pub mod audio;
+pub mod email;
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::knowledge::storage::{BaseKnowledgeStorage, KnowledgeStorage};
+use crate::knowledge::storage::KnowledgeStorage;
+use crate::utilities::perf::{self, HotPath};
 
 // ---------------------------------------------------------------------------
 // Base traits
@@ -83,6 +88,7 @@ pub trait BaseKnowledgeSource: Send + Sync {
         chunk_size: Option<usize>,
         chunk_overlap: Option<usize>,
     ) -> Vec<String> {
+        let _span = perf::span(HotPath::Chunking);
         let chunk_size = chunk_size.unwrap_or(4000);
         let chunk_overlap = chunk_overlap.unwrap_or(200);
 
@@ -106,6 +112,39 @@ pub trait BaseKnowledgeSource: Send + Sync {
     }
 }
 
+/// Shared sources (e.g. an agent's, which are cloned with the agent) can
+/// be handed to a [`Knowledge`](crate::knowledge::Knowledge) as is.
+#[async_trait]
+impl<T: BaseKnowledgeSource + ?Sized> BaseKnowledgeSource for Arc<T> {
+    fn source_name(&self) -> &str {
+        (**self).source_name()
+    }
+
+    fn validate_content(&self) -> Result<(), anyhow::Error> {
+        (**self).validate_content()
+    }
+
+    fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
+        (**self).load_content()
+    }
+
+    fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
+        (**self).add(storage)
+    }
+
+    async fn aadd(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
+        (**self).aadd(storage).await
+    }
+
+    fn metadata(&self) -> HashMap<String, Value> {
+        (**self).metadata()
+    }
+
+    fn get_embeddings(&self) -> Vec<Vec<f32>> {
+        (**self).get_embeddings()
+    }
+}
+
 /// Base trait for file-based knowledge sources.
 ///
 /// Extends `BaseKnowledgeSource` with file path support and validation.
@@ -125,6 +164,17 @@ pub trait BaseFileKnowledgeSource: BaseKnowledgeSource {
         }
         Ok(())
     }
+
+    /// Metadata for the chunks of the file at `path`: the source's, with
+    /// the path as `source`.
+    fn file_metadata(&self, path: &Path) -> HashMap<String, Value> {
+        let mut metadata = self.metadata();
+        metadata.insert(
+            "source".to_string(),
+            Value::String(path.display().to_string()),
+        );
+        metadata
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -195,7 +245,7 @@ impl BaseKnowledgeSource for StringKnowledgeSource {
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
         let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        storage.save_chunks_to(self.collection_name.as_deref(), &chunks, &self.metadata)
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
@@ -234,6 +284,13 @@ impl TextFileKnowledgeSource {
             collection_name: None,
         }
     }
+
+    /// Chunks of the text file at `path`.
+    fn file_chunks(&self, path: &Path) -> Result<Vec<String>, anyhow::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Ok(self.chunk_text(&content, self.chunk_size, self.chunk_overlap))
+    }
 }
 
 #[async_trait]
@@ -245,17 +302,21 @@ impl BaseKnowledgeSource for TextFileKnowledgeSource {
     fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
         let mut all_chunks = Vec::new();
         for path in &self.file_paths {
-            let content = std::fs::read_to_string(path)
-                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
-            let chunks = self.chunk_text(&content, self.chunk_size, self.chunk_overlap);
-            all_chunks.extend(chunks);
+            all_chunks.extend(self.file_chunks(path)?);
         }
         Ok(all_chunks)
     }
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
-        let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        for path in &self.file_paths {
+            let chunks = self.file_chunks(path)?;
+            storage.save_chunks_to(
+                self.collection_name.as_deref(),
+                &chunks,
+                &self.file_metadata(path),
+            )?;
+        }
+        Ok(())
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
@@ -301,6 +362,17 @@ impl CSVKnowledgeSource {
             collection_name: None,
         }
     }
+
+    /// Chunks of the CSV file at `path`: each row becomes a chunk.
+    fn file_chunks(&self, path: &Path) -> Result<Vec<String>, anyhow::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
 }
 
 #[async_trait]
@@ -312,21 +384,21 @@ impl BaseKnowledgeSource for CSVKnowledgeSource {
     fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
         let mut all_chunks = Vec::new();
         for path in &self.file_paths {
-            let content = std::fs::read_to_string(path)
-                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
-            // Each row of a CSV becomes a chunk.
-            for line in content.lines() {
-                if !line.trim().is_empty() {
-                    all_chunks.push(line.to_string());
-                }
-            }
+            all_chunks.extend(self.file_chunks(path)?);
         }
         Ok(all_chunks)
     }
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
-        let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        for path in &self.file_paths {
+            let chunks = self.file_chunks(path)?;
+            storage.save_chunks_to(
+                self.collection_name.as_deref(),
+                &chunks,
+                &self.file_metadata(path),
+            )?;
+        }
+        Ok(())
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
@@ -393,7 +465,7 @@ impl BaseKnowledgeSource for PDFKnowledgeSource {
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
         let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        storage.save_chunks_to(self.collection_name.as_deref(), &chunks, &self.metadata)
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
@@ -441,6 +513,16 @@ impl JSONKnowledgeSource {
         }
     }
 
+    /// Chunks of the JSON file at `path`, as text.
+    fn file_chunks(&self, path: &Path) -> Result<Vec<String>, anyhow::Error> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let parsed: Value = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse JSON {}: {}", path.display(), e))?;
+        let text = Self::json_to_text(&parsed);
+        Ok(self.chunk_text(&text, self.chunk_size, self.chunk_overlap))
+    }
+
     /// Recursively convert a JSON value to a readable text representation.
     fn json_to_text(value: &Value) -> String {
         match value {
@@ -471,20 +553,21 @@ impl BaseKnowledgeSource for JSONKnowledgeSource {
     fn load_content(&self) -> Result<Vec<String>, anyhow::Error> {
         let mut all_chunks = Vec::new();
         for path in &self.file_paths {
-            let content = std::fs::read_to_string(path)
-                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
-            let parsed: Value = serde_json::from_str(&content)
-                .map_err(|e| anyhow::anyhow!("Failed to parse JSON {}: {}", path.display(), e))?;
-            let text = Self::json_to_text(&parsed);
-            let chunks = self.chunk_text(&text, self.chunk_size, self.chunk_overlap);
-            all_chunks.extend(chunks);
+            all_chunks.extend(self.file_chunks(path)?);
         }
         Ok(all_chunks)
     }
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
-        let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        for path in &self.file_paths {
+            let chunks = self.file_chunks(path)?;
+            storage.save_chunks_to(
+                self.collection_name.as_deref(),
+                &chunks,
+                &self.file_metadata(path),
+            )?;
+        }
+        Ok(())
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
@@ -551,7 +634,7 @@ impl BaseKnowledgeSource for ExcelKnowledgeSource {
 
     fn add(&self, storage: &Arc<KnowledgeStorage>) -> Result<(), anyhow::Error> {
         let chunks = self.load_content()?;
-        storage.save_chunks(&chunks, &self.metadata)
+        storage.save_chunks_to(self.collection_name.as_deref(), &chunks, &self.metadata)
     }
 
     fn metadata(&self) -> HashMap<String, Value> {
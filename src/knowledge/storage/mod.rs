@@ -6,11 +6,16 @@
 //! implementation that delegates to a configurable RAG client (e.g., ChromaDB)
 //! for vector similarity search and document storage.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
 
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::rag::core::EmbeddingFunctionTrait;
+use crate::rag::embeddings::EmbedderConfig;
+use crate::rag::pipeline::cosine_similarity;
+
 // ---------------------------------------------------------------------------
 // Base trait
 // ---------------------------------------------------------------------------
@@ -95,7 +100,7 @@ pub trait BaseKnowledgeStorage: Send + Sync {
         self.save_chunks(chunks, metadata)
     }
 
-    /// Reset the storage by removing all data in the collection.
+    /// Reset the storage by removing all data in its collections.
     fn reset(&self) -> Result<(), anyhow::Error>;
 
     /// Reset the storage asynchronously.
@@ -110,14 +115,19 @@ pub trait BaseKnowledgeStorage: Send + Sync {
 // Concrete implementation
 // ---------------------------------------------------------------------------
 
-/// Default KnowledgeStorage backed by a RAG client.
+/// Default KnowledgeStorage, an in-memory collection of chunks.
 ///
-/// Extends the base storage with embedding support for knowledge entries,
-/// improving search efficiency through vector similarity.
+/// With an embedder (built from `embedder_config`, or set with
+/// [`KnowledgeStorage::with_embedder`]) chunks are embedded on save and
+/// searched by cosine similarity. Without one, or when the configured
+/// provider cannot be built, search falls back to keyword overlap.
 ///
-/// In the Python implementation, this integrates with ChromaDB or other
-/// vector databases. The Rust implementation provides the interface and
-/// delegates to the configured RAG client.
+/// Each instance is its own store, so an agent's knowledge and the crew's
+/// never mix. Within a store, chunks live in named collections: the
+/// storage's own collection, or the one a source names in its
+/// `collection_name` (see [`KnowledgeStorage::save_chunks_to`]). Searches
+/// cover every collection unless narrowed with
+/// [`KnowledgeStorage::search_collection`].
 ///
 /// Corresponds to `crewai.knowledge.storage.knowledge_storage.KnowledgeStorage`.
 ///
@@ -129,8 +139,8 @@ pub trait BaseKnowledgeStorage: Send + Sync {
 /// let storage = KnowledgeStorage::new(None, Some("my_knowledge".to_string()));
 /// ```
 pub struct KnowledgeStorage {
-    /// Embedder configuration (provider-specific, e.g., model name, dimensions).
-    pub embedder_config: Option<Value>,
+    /// Embedder configuration (provider, model, dimensions, ...).
+    pub embedder_config: Option<EmbedderConfig>,
     /// Collection name in the vector store.
     /// Prefixed with "knowledge_" when accessing the backend.
     pub collection_name: Option<String>,
@@ -138,6 +148,17 @@ pub struct KnowledgeStorage {
     pub default_limit: usize,
     /// Default score threshold for queries.
     pub default_score_threshold: f64,
+    /// Embedding function; `None` means keyword search.
+    embedder: Option<Arc<dyn EmbeddingFunctionTrait>>,
+    /// Saved chunks, by fully-qualified collection name.
+    collections: RwLock<BTreeMap<String, Vec<StoredChunk>>>,
+}
+
+/// A saved chunk with its metadata and embedding.
+struct StoredChunk {
+    content: String,
+    metadata: HashMap<String, Value>,
+    embedding: Option<Vec<f32>>,
 }
 
 impl KnowledgeStorage {
@@ -145,17 +166,50 @@ impl KnowledgeStorage {
     ///
     /// # Arguments
     ///
-    /// * `embedder_config` - Optional embedder configuration (provider spec).
+    /// * `embedder_config` - Optional embedder configuration.
     /// * `collection_name` - Optional collection name override.
-    pub fn new(embedder_config: Option<Value>, collection_name: Option<String>) -> Self {
+    pub fn new(embedder_config: Option<EmbedderConfig>, collection_name: Option<String>) -> Self {
+        let embedder = embedder_config
+            .as_ref()
+            .and_then(|config| match config.build() {
+                Ok(embedder) => Some(embedder),
+                Err(e) => {
+                    log::warn!(
+                        "Knowledge embedder unavailable, using keyword search: {}",
+                        e
+                    );
+                    None
+                }
+            });
         Self {
             embedder_config,
             collection_name,
             default_limit: 5,
             default_score_threshold: 0.6,
+            embedder,
+            collections: RwLock::new(BTreeMap::new()),
         }
     }
 
+    /// Builder: embed chunks and queries with `embedder`.
+    pub fn with_embedder(mut self, embedder: Arc<dyn EmbeddingFunctionTrait>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Number of saved chunks, across all collections.
+    pub fn len(&self) -> usize {
+        self.collections
+            .read()
+            .map(|c| c.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Whether nothing has been saved.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Get the fully-qualified collection name for the backend.
     ///
     /// Returns "knowledge_{name}" if a collection name is set,
@@ -166,80 +220,213 @@ impl KnowledgeStorage {
             None => "knowledge".to_string(),
         }
     }
-}
 
-#[async_trait]
-impl BaseKnowledgeStorage for KnowledgeStorage {
-    fn search(
+    /// Fully-qualified name of a source's collection: "knowledge_{name}",
+    /// or the storage's own collection when the source names none.
+    fn qualified_collection_name(&self, collection: Option<&str>) -> String {
+        match collection {
+            Some(name) => format!("knowledge_{}", name),
+            None => self.effective_collection_name(),
+        }
+    }
+
+    /// Fully-qualified names of the collections holding chunks, sorted.
+    pub fn list_collections(&self) -> Result<Vec<String>, anyhow::Error> {
+        let collections = self
+            .collections
+            .read()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?;
+        Ok(collections.keys().cloned().collect())
+    }
+
+    /// Delete a collection and its chunks.
+    ///
+    /// `name` is either a fully-qualified name, as returned by
+    /// [`list_collections`](Self::list_collections), or a source's
+    /// `collection_name`. Returns whether the collection existed.
+    pub fn delete_collection(&self, name: &str) -> Result<bool, anyhow::Error> {
+        let mut collections = self
+            .collections
+            .write()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?;
+        let deleted = collections.remove(name).is_some()
+            || collections
+                .remove(&self.qualified_collection_name(Some(name)))
+                .is_some();
+        log::debug!(
+            "KnowledgeStorage::delete_collection: name='{}', deleted={}",
+            name,
+            deleted
+        );
+        Ok(deleted)
+    }
+
+    /// Save chunks with metadata to a source's collection (`None` for the
+    /// storage's own collection).
+    pub fn save_chunks_to(
         &self,
-        query: &str,
-        limit: usize,
-        score_threshold: f64,
-    ) -> Result<Vec<Value>, anyhow::Error> {
-        if query.is_empty() {
-            return Err(anyhow::anyhow!("Query cannot be empty"));
+        collection: Option<&str>,
+        chunks: &[String],
+        metadata: &HashMap<String, Value>,
+    ) -> Result<(), anyhow::Error> {
+        if chunks.is_empty() {
+            return Ok(());
         }
 
-        let collection = self.effective_collection_name();
+        let collection = self.qualified_collection_name(collection);
         log::debug!(
-            "KnowledgeStorage::search: collection='{}', query='{}', limit={}, threshold={}",
+            "KnowledgeStorage::save_chunks: collection='{}', num_chunks={}, metadata_keys={:?}",
             collection,
-            query,
-            limit,
-            score_threshold
+            chunks.len(),
+            metadata.keys().collect::<Vec<_>>()
         );
 
-        // Delegate to RAG client when integrated:
-        // let client = self.get_client();
-        // client.search(collection, query, limit, None, score_threshold)
-        //
-        // For now, return empty results. Integration point for RAG backend.
-        Ok(Vec::new())
+        let embeddings = match &self.embedder {
+            Some(embedder) => embedder.call(chunks)?.into_iter().map(Some).collect(),
+            None => vec![None; chunks.len()],
+        };
+        let mut collections = self
+            .collections
+            .write()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?;
+        collections
+            .entry(collection)
+            .or_default()
+            .extend(
+                chunks
+                    .iter()
+                    .zip(embeddings)
+                    .map(|(content, embedding)| StoredChunk {
+                        content: content.clone(),
+                        metadata: metadata.clone(),
+                        embedding,
+                    }),
+            );
+        Ok(())
     }
 
-    async fn asearch(
+    /// Search a single collection, named as for
+    /// [`delete_collection`](Self::delete_collection).
+    pub fn search_collection(
         &self,
+        name: &str,
         query: &str,
-        _limit: usize,
-        _score_threshold: f64,
+        limit: usize,
+        score_threshold: f64,
+    ) -> Result<Vec<Value>, anyhow::Error> {
+        let qualified = self.qualified_collection_name(Some(name));
+        self.search_in(
+            |collection| collection == name || collection == qualified,
+            query,
+            limit,
+            score_threshold,
+        )
+    }
+
+    /// Search the collections whose name passes `filter`.
+    fn search_in(
+        &self,
+        filter: impl Fn(&str) -> bool,
+        query: &str,
+        limit: usize,
+        score_threshold: f64,
     ) -> Result<Vec<Value>, anyhow::Error> {
         if query.is_empty() {
             return Err(anyhow::anyhow!("Query cannot be empty"));
         }
 
-        let collection = self.effective_collection_name();
         log::debug!(
-            "KnowledgeStorage::asearch: collection='{}', query='{}'",
-            collection,
+            "KnowledgeStorage::search: collection='{}', query='{}', limit={}, threshold={}",
+            self.effective_collection_name(),
             query,
+            limit,
+            score_threshold
         );
 
-        // Delegate to RAG client async search when integrated:
-        // let client = self.get_client();
-        // client.asearch(collection, query, limit, None, score_threshold).await
-        Ok(Vec::new())
+        let collections = self
+            .collections
+            .read()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?;
+        let entries: Vec<(&String, &StoredChunk)> = collections
+            .iter()
+            .filter(|(name, _)| filter(name))
+            .flat_map(|(name, chunks)| chunks.iter().map(move |chunk| (name, chunk)))
+            .collect();
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query_embedding = match &self.embedder {
+            Some(embedder) => Some(embedder.embed_query(query)?),
+            None => None,
+        };
+        let query_keywords = keywords(query);
+
+        let mut scored: Vec<(f64, &String, &StoredChunk)> = entries
+            .into_iter()
+            .map(|(collection, entry)| {
+                let score = match (&query_embedding, &entry.embedding) {
+                    (Some(q), Some(e)) => cosine_similarity(q, e),
+                    _ => keyword_score(&query_keywords, &entry.content),
+                };
+                (score, collection, entry)
+            })
+            .filter(|(score, _, _)| *score >= score_threshold)
+            .collect();
+        scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(score, collection, entry)| {
+                serde_json::json!({
+                    "content": entry.content,
+                    "metadata": entry.metadata,
+                    "score": score,
+                    "collection": collection,
+                })
+            })
+            .collect())
     }
+}
 
-    fn save(&self, documents: &[String]) -> Result<(), anyhow::Error> {
-        if documents.is_empty() {
-            return Ok(());
-        }
+/// Words ignored by keyword search.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on",
+    "or", "that", "the", "this", "to", "was", "what", "with", "your", "you",
+];
+
+pub(crate) fn keywords(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 2 && !STOP_WORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
 
-        let collection = self.effective_collection_name();
-        log::debug!(
-            "KnowledgeStorage::save: collection='{}', num_documents={}",
-            collection,
-            documents.len()
-        );
+/// Shared keywords over the size of the smaller keyword set, so a long
+/// task prompt can still fully match a short chunk.
+fn keyword_score(query: &HashSet<String>, content: &str) -> f64 {
+    let content = keywords(content);
+    let smaller = query.len().min(content.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    query.intersection(&content).count() as f64 / smaller as f64
+}
 
-        // Delegate to RAG client when integrated:
-        // let client = self.get_client();
-        // client.get_or_create_collection(&collection);
-        // let rag_docs: Vec<_> = documents.iter()
-        //     .map(|doc| json!({"content": doc}))
-        //     .collect();
-        // client.add_documents(&collection, &rag_docs);
-        Ok(())
+#[async_trait]
+impl BaseKnowledgeStorage for KnowledgeStorage {
+    fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        score_threshold: f64,
+    ) -> Result<Vec<Value>, anyhow::Error> {
+        self.search_in(|_| true, query, limit, score_threshold)
+    }
+
+    fn save(&self, documents: &[String]) -> Result<(), anyhow::Error> {
+        self.save_chunks(documents, &HashMap::new())
     }
 
     fn save_chunks(
@@ -247,39 +434,17 @@ impl BaseKnowledgeStorage for KnowledgeStorage {
         chunks: &[String],
         metadata: &HashMap<String, Value>,
     ) -> Result<(), anyhow::Error> {
-        if chunks.is_empty() {
-            return Ok(());
-        }
-
-        let collection = self.effective_collection_name();
-        log::debug!(
-            "KnowledgeStorage::save_chunks: collection='{}', num_chunks={}, metadata_keys={:?}",
-            collection,
-            chunks.len(),
-            metadata.keys().collect::<Vec<_>>()
-        );
-
-        // Delegate to RAG client when integrated:
-        // let client = self.get_client();
-        // client.get_or_create_collection(&collection);
-        // let rag_docs: Vec<_> = chunks.iter()
-        //     .map(|chunk| {
-        //         let mut doc = json!({"content": chunk});
-        //         for (k, v) in metadata { doc[k] = v.clone(); }
-        //         doc
-        //     })
-        //     .collect();
-        // client.add_documents(&collection, &rag_docs);
-        Ok(())
+        self.save_chunks_to(None, chunks, metadata)
     }
 
     fn reset(&self) -> Result<(), anyhow::Error> {
         let collection = self.effective_collection_name();
         log::debug!("KnowledgeStorage::reset: collection='{}'", collection);
 
-        // Delegate to RAG client when integrated:
-        // let client = self.get_client();
-        // client.delete_collection(&collection);
+        self.collections
+            .write()
+            .map_err(|e| anyhow::anyhow!("Lock poisoned: {}", e))?
+            .clear();
         Ok(())
     }
 }
@@ -312,8 +477,34 @@ mod tests {
     fn test_knowledge_storage_search() {
         let storage = KnowledgeStorage::new(None, None);
         let results = storage.search("test query", 5, 0.6).unwrap();
-        // Returns empty until RAG backend is integrated.
         assert!(results.is_empty());
+
+        let chunks = vec![
+            "Refunds are issued within 14 days of a return.".to_string(),
+            "Shipping to Canada takes five business days.".to_string(),
+        ];
+        storage.save_chunks(&chunks, &HashMap::new()).unwrap();
+        let results = storage
+            .search("When is a refund issued after a return?", 5, 0.3)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["content"], chunks[0]);
+
+        let embedded = KnowledgeStorage::new(None, None).with_embedder(Arc::new(
+            crate::rag::embeddings::providers::custom::CustomEmbedding::new(
+                |texts: &[String]| {
+                    Ok(texts
+                        .iter()
+                        .map(|t| vec![t.contains("Canada") as u8 as f32, 1.0])
+                        .collect())
+                },
+            ),
+        ));
+        embedded.save_chunks(&chunks, &HashMap::new()).unwrap();
+        let results = embedded.search("Canada", 1, 0.0).unwrap();
+        assert_eq!(results[0]["content"], chunks[1]);
+        embedded.reset().unwrap();
+        assert!(embedded.is_empty());
     }
 
     #[test]
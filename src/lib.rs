@@ -7,10 +7,16 @@
 //! LLM provider support, memory management, RAG capabilities, and event-driven
 //! architecture for both crews (autonomous multi-agent systems) and flows
 //! (deterministic event-driven workflows).
+//!
+//! New users can start from [`prelude`], which re-exports the common types
+//! along with one-liners such as [`quickstart::run_single_agent`] and
+//! [`quickstart::quick_crew`].
 
 pub mod a2a;
 pub mod agent;
 pub mod agents;
+pub mod audio;
+pub mod bench;
 pub mod blackboard;
 pub mod capabilities;
 pub mod chat;
@@ -36,8 +42,10 @@ pub mod meta_agents;
 pub mod modules;
 pub mod persona;
 pub mod policy;
+pub mod prelude;
 pub mod process;
 pub mod project;
+pub mod quickstart;
 pub mod rag;
 pub mod security;
 pub mod server;
@@ -49,10 +57,24 @@ pub mod translations;
 pub mod types;
 pub mod utilities;
 
+// Embedded Rhai scripting for guardrails and conditions
+#[cfg(feature = "scripting")]
+pub mod scripting;
+
+// Fault injection for resilience testing
+#[cfg(feature = "chaos")]
+pub mod chaos;
+
 // xAI gRPC provider — typed protobuf client for Grok models
 #[cfg(feature = "xai-grpc")]
 pub mod xai_grpc;
 
+#[doc(hidden)]
+pub mod __private {
+    //! Re-exports used by exported macros.
+    pub use serde_json;
+}
+
 // Re-exports matching Python's __init__.py __all__
 pub use agent::Agent;
 pub use crew::Crew;
@@ -5,13 +5,44 @@
 //! `LiteAgent` provides a simpler agent interface than the full `Agent`,
 //! suitable for direct LLM conversations without the overhead of crew
 //! orchestration, task management, or complex tool handling.
+//!
+//! ```ignore
+//! use crewai::lite_agent::LiteAgent;
+//! use crewai::utilities::types::llm_message;
+//!
+//! crewai::tool_args! {
+//!     pub struct Summary {
+//!         /// One-line summary.
+//!         pub headline: String,
+//!     }
+//! }
+//!
+//! let output = LiteAgent::new("Editor", "Summarize articles", "openai/gpt-4o-mini")
+//!     .with_tools(["scrape_website"])
+//!     .with_response_model::<Summary>()
+//!     .kickoff(vec![llm_message("user", "Summarize https://example.com")])?;
+//! let summary: Summary = output.model()?;
+//! ```
 
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::agent::Agent;
+use crate::agents::crew_agent_executor::CrewAgentExecutor;
+use crate::agents::tools_handler::ToolsHandler;
+use crate::events::types::agent_events::{
+    LiteAgentExecutionCompletedEvent, LiteAgentExecutionErrorEvent, LiteAgentExecutionStartedEvent,
+};
+use crate::events::{BaseEvent, CREWAI_EVENT_BUS};
+use crate::llms::base_llm::BaseLLM;
+use crate::tools::registry::ToolRegistry;
+use crate::tools::typed_tool::ToolArgs;
+use crate::types::usage_metrics::{UsageMetrics, UsageScope};
+use crate::utilities::cancellation;
 use crate::utilities::types::LLMMessage;
 
 // ---------------------------------------------------------------------------
@@ -54,6 +85,25 @@ impl LiteAgentOutput {
             .clone()
             .unwrap_or(Value::Object(Default::default()))
     }
+
+    /// Set the token usage of the execution.
+    pub fn with_usage_metrics(mut self, usage: &UsageMetrics) -> Self {
+        self.usage_metrics = serde_json::to_value(usage)
+            .ok()
+            .and_then(|v| serde_json::from_value(v).ok());
+        self
+    }
+
+    /// The structured output as the model set with
+    /// [`LiteAgent::with_response_model`].
+    pub fn model<T: ToolArgs>(&self) -> Result<T, String> {
+        match &self.pydantic {
+            Some(Value::Object(map)) => {
+                T::from_args(map.clone().into_iter().collect()).map_err(|e| e.to_string())
+            }
+            _ => Err("LiteAgent output has no structured result".to_string()),
+        }
+    }
 }
 
 impl fmt::Display for LiteAgentOutput {
@@ -97,6 +147,18 @@ pub struct LiteAgent {
     pub allow_delegation: bool,
     /// System message to prepend to conversations.
     pub system_message: Option<String>,
+    /// Names of the tools the agent may use, looked up in the tool registry.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// JSON Schema the final answer must match; the parsed answer is the
+    /// output's `pydantic` value.
+    pub response_format: Option<Value>,
+    /// LLM instance to use instead of creating one from `llm`.
+    #[serde(skip)]
+    pub llm_instance: Option<Arc<dyn BaseLLM>>,
+    /// Registry the tools are looked up in (defaults to the global one).
+    #[serde(skip)]
+    pub tool_registry: Option<ToolRegistry>,
     /// Messages accumulated during the current execution.
     #[serde(skip)]
     pub messages: Vec<LLMMessage>,
@@ -125,6 +187,10 @@ impl Default for LiteAgent {
             max_rpm: None,
             allow_delegation: false,
             system_message: None,
+            tools: Vec::new(),
+            response_format: None,
+            llm_instance: None,
+            tool_registry: None,
             messages: Vec::new(),
             iterations: 0,
         }
@@ -132,9 +198,11 @@ impl Default for LiteAgent {
 }
 
 impl LiteAgent {
-    /// Create a new `LiteAgent` with the specified LLM model.
-    pub fn new(llm: impl Into<String>) -> Self {
+    /// Create a new `LiteAgent` with the given role, goal and LLM model.
+    pub fn new(role: impl Into<String>, goal: impl Into<String>, llm: impl Into<String>) -> Self {
         Self {
+            role: role.into(),
+            goal: goal.into(),
             llm: llm.into(),
             ..Default::default()
         }
@@ -143,20 +211,108 @@ impl LiteAgent {
     /// Create a builder for configuring a `LiteAgent`.
     pub fn builder(llm: impl Into<String>) -> LiteAgentBuilder {
         LiteAgentBuilder {
-            agent: Self::new(llm),
+            agent: Self {
+                llm: llm.into(),
+                ..Default::default()
+            },
         }
     }
 
-    /// Execute the agent with the given messages (placeholder).
+    /// Let the agent use the tools registered under `tools`.
+    pub fn with_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tools.extend(tools.into_iter().map(Into::into));
+        self
+    }
+
+    /// Look tools up in `registry` instead of the global registry.
+    pub fn with_tool_registry(mut self, registry: ToolRegistry) -> Self {
+        self.tool_registry = Some(registry);
+        self
+    }
+
+    /// Require the final answer to be JSON matching `schema`.
+    pub fn with_response_format(mut self, schema: Value) -> Self {
+        self.response_format = Some(schema);
+        self
+    }
+
+    /// Require the final answer to be a `T` (declared with
+    /// [`tool_args!`](crate::tool_args)), read back with
+    /// [`LiteAgentOutput::model`].
+    pub fn with_response_model<T: ToolArgs>(self) -> Self {
+        self.with_response_format(T::args_schema())
+    }
+
+    /// Use `llm` instead of creating an LLM from the `llm` identifier.
+    pub fn with_llm_instance(mut self, llm: Arc<dyn BaseLLM>) -> Self {
+        self.llm_instance = Some(llm);
+        self
+    }
+
+    /// Execute the agent with the given messages.
     ///
-    /// In the full implementation, this drives the LLM conversation
-    /// loop with tool calling, guardrails, and hooks.
+    /// Runs the ReAct loop with the agent's tools until the LLM gives a
+    /// final answer or `max_iter` is reached. System messages are added to
+    /// the agent's system prompt; the other messages form the request.
     pub fn kickoff(&mut self, messages: Vec<LLMMessage>) -> Result<LiteAgentOutput, String> {
         self.messages = messages;
         self.iterations = 0;
 
-        // Stub: the full implementation calls the LLM, handles tools, etc.
-        Err("LiteAgent.kickoff() not yet implemented".to_string())
+        let agent_info = self.agent_info();
+        self.emit(&mut LiteAgentExecutionStartedEvent::new(
+            agent_info.clone(),
+            (!self.tools.is_empty()).then(|| self.tools.clone()),
+            serde_json::to_value(&self.messages).unwrap_or_default(),
+        ));
+        let usage = UsageScope::enter();
+        match self.run() {
+            Ok(raw) => {
+                let mut output = LiteAgentOutput::new(raw, self.role.clone());
+                if self.response_format.is_some() {
+                    output.pydantic = parse_json(&output.raw);
+                    if output.pydantic.is_none() {
+                        log::warn!("LiteAgent '{}' did not answer in JSON", self.role);
+                    }
+                }
+                output = output.with_usage_metrics(&usage.usage());
+                output.messages = self.messages.clone();
+                self.emit(&mut LiteAgentExecutionCompletedEvent::new(
+                    agent_info,
+                    output.raw.clone(),
+                ));
+                Ok(output)
+            }
+            Err(e) => {
+                self.emit(&mut LiteAgentExecutionErrorEvent::new(
+                    agent_info,
+                    e.clone(),
+                ));
+                Err(e)
+            }
+        }
+    }
+
+    /// Async version of [`kickoff`](Self::kickoff).
+    ///
+    /// The LLM calls block, so the kickoff runs on tokio's blocking thread
+    /// pool. The agent moves there for the run; should the future be
+    /// dropped before completing, this agent is left as a default one.
+    pub async fn kickoff_async(
+        &mut self,
+        messages: Vec<LLMMessage>,
+    ) -> Result<LiteAgentOutput, String> {
+        let mut agent = std::mem::take(self);
+        let (agent, output) = cancellation::spawn_blocking(move || {
+            let output = agent.kickoff(messages);
+            (agent, output)
+        })
+        .await;
+        *self = agent;
+        output
     }
 
     /// Reset the agent's execution state.
@@ -164,6 +320,176 @@ impl LiteAgent {
         self.messages.clear();
         self.iterations = 0;
     }
+
+    /// Run the executor over `messages` and return the final answer.
+    fn run(&mut self) -> Result<String, String> {
+        let llm: Arc<dyn BaseLLM> = match &self.llm_instance {
+            Some(llm) => llm.clone(),
+            None => Arc::from(Agent::llm_instance(&self.llm)?),
+        };
+        let registry = self
+            .tool_registry
+            .clone()
+            .unwrap_or_else(|| ToolRegistry::global().clone());
+        let tools_description = self
+            .tools
+            .iter()
+            .map(|t| {
+                let description = registry
+                    .description(t)
+                    .unwrap_or_else(|| format!("A tool named {}", t));
+                format!("- {}: {}", t, description)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            self.prompt(&tools_description),
+            self.max_iter as u32,
+            Vec::new(),
+            self.tools.join(", "),
+            vec!["Observation:".to_string()],
+            tools_description,
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |messages, tools| {
+            match llm.call(messages.to_vec(), tools.map(<[Value]>::to_vec), None)? {
+                Value::String(s) => Ok(s),
+                other => Ok(other.to_string()),
+            }
+        });
+        let role = self.role.clone();
+        executor.set_tool_executor(move |tool_name, tool_input| {
+            let args = serde_json::from_str(tool_input)
+                .unwrap_or_else(|_| Value::String(tool_input.to_string()));
+            Ok(match registry.execute(Some(&role), tool_name, &args) {
+                Some(Ok(output)) => output,
+                Some(Err(e)) => format!("Tool error: {}", e),
+                None => format!("Tool '{}' does not exist", tool_name),
+            })
+        });
+
+        let result = executor.invoke(HashMap::new());
+        self.iterations = executor.iterations as usize;
+        self.messages = executor
+            .messages
+            .iter()
+            .map(|m| {
+                m.iter()
+                    .map(|(k, v)| match v {
+                        Value::String(s) => (k.clone(), s.clone()),
+                        other => (k.clone(), other.to_string()),
+                    })
+                    .collect()
+            })
+            .collect();
+        let output = result.map_err(|e| e.to_string())?;
+        Ok(match &output["output"] {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    }
+
+    /// The system and user prompts for the current messages.
+    fn prompt(&self, tools_description: &str) -> HashMap<String, String> {
+        let mut system = format!(
+            "You are {}. {}\nYour personal goal is: {}",
+            self.role, self.backstory, self.goal
+        );
+        if !self.tools.is_empty() {
+            system.push_str(&format!(
+                "\n\nYou have access to the following tools:\n{}\n\n\
+                 To use a tool, reply with:\n\n\
+                 Thought: you should always think about what to do\n\
+                 Action: the action to take, one of [{}]\n\
+                 Action Input: the input to the action, as a JSON object\n\n\
+                 You will then get an Observation with the tool's result.",
+                tools_description,
+                self.tools.join(", ")
+            ));
+        }
+        system.push_str(
+            "\n\nWhen you have the answer, reply with:\n\n\
+             Thought: I now know the final answer\n\
+             Final Answer: the final answer",
+        );
+        if let Some(schema) = &self.response_format {
+            system.push_str(&format!(
+                "\n\nThe final answer must be a JSON object matching this schema:\n{}",
+                schema
+            ));
+        }
+        if let Some(message) = &self.system_message {
+            system.push_str(&format!("\n\n{}", message));
+        }
+
+        let (instructions, user) = split_messages(&self.messages);
+        if let Some(instructions) = instructions {
+            system.push_str(&format!("\n\n{}", instructions));
+        }
+
+        HashMap::from([("system".to_string(), system), ("user".to_string(), user)])
+    }
+
+    /// Role, goal and backstory for LiteAgent events.
+    fn agent_info(&self) -> HashMap<String, Value> {
+        HashMap::from([
+            ("role".to_string(), Value::String(self.role.clone())),
+            ("goal".to_string(), Value::String(self.goal.clone())),
+            (
+                "backstory".to_string(),
+                Value::String(self.backstory.clone()),
+            ),
+        ])
+    }
+
+    fn emit<E: BaseEvent + 'static>(&self, event: &mut E) {
+        if let Some(bus) = CREWAI_EVENT_BUS.get() {
+            bus.emit(Arc::new(self.role.clone()), event);
+        }
+    }
+}
+
+/// Split `messages` into the content of the system messages, if any, and
+/// the rest of the conversation as a single request: the content of a lone
+/// message, or a `role: content` transcript.
+pub(crate) fn split_messages(messages: &[LLMMessage]) -> (Option<String>, String) {
+    let (system, conversation): (Vec<_>, Vec<_>) = messages
+        .iter()
+        .partition(|m| m.get("role").map(String::as_str) == Some("system"));
+    let system = (!system.is_empty()).then(|| {
+        system
+            .iter()
+            .map(|m| m.get("content").map_or("", String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    });
+    let request = match conversation.as_slice() {
+        [message] => message.get("content").cloned().unwrap_or_default(),
+        messages => messages
+            .iter()
+            .map(|m| {
+                format!(
+                    "{}: {}",
+                    m.get("role").map_or("user", String::as_str),
+                    m.get("content").map_or("", String::as_str)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    };
+    (system, request)
+}
+
+/// The JSON value in `answer`, which may be wrapped in a code fence.
+fn parse_json(answer: &str) -> Option<Value> {
+    let start = answer.find(['{', '['])?;
+    let end = answer.rfind(['}', ']'])?;
+    serde_json::from_str(answer.get(start..=end)?).ok()
 }
 
 /// Builder for configuring a `LiteAgent`.
@@ -207,7 +533,176 @@ impl LiteAgentBuilder {
         self
     }
 
+    pub fn tools(mut self, tools: Vec<String>) -> Self {
+        self.agent.tools = tools;
+        self
+    }
+
+    pub fn response_format(mut self, schema: Value) -> Self {
+        self.agent.response_format = Some(schema);
+        self
+    }
+
     pub fn build(self) -> LiteAgent {
         self.agent
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llms::providers::openai::OpenAICompletion;
+    use crate::tools::base_tool::Tool;
+    use crate::utilities::types::llm_message;
+    use axum::{routing::post, Json};
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    crate::tool_args! {
+        struct City {
+            /// City to look up.
+            city: String,
+        }
+    }
+
+    crate::tool_args! {
+        struct Forecast {
+            /// One-line forecast.
+            headline: String,
+        }
+    }
+
+    /// Serve `replies` as consecutive chat completions; returns the base URL
+    /// and the request bodies received.
+    fn serve(replies: Vec<&'static str>) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let router = axum::Router::new().route(
+            "/v1/chat/completions",
+            post(move |Json(body): Json<Value>| {
+                let turn = {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(body);
+                    seen.len() - 1
+                };
+                let reply = replies[turn.min(replies.len() - 1)];
+                async move {
+                    Json(json!({
+                        "model": "gpt-4o-mini",
+                        "choices": [{
+                            "message": {"role": "assistant", "content": reply},
+                            "finish_reason": "stop"
+                        }],
+                        "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+                    }))
+                }
+            }),
+        );
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Runtime::new().unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+                tx.send(format!("http://{}/v1", listener.local_addr().unwrap()))
+                    .unwrap();
+                axum::serve(listener, router).await.unwrap();
+            });
+        });
+        (rx.recv().unwrap(), requests)
+    }
+
+    #[test]
+    fn test_kickoff_uses_tools_and_response_model() {
+        let (url, requests) = serve(vec![
+            "Thought: check the weather\nAction: weather\nAction Input: {\"city\": \"Paris\"}",
+            "Thought: I now know the final answer\n\
+             Final Answer: ```json\n{\"headline\": \"Sunny in Paris\"}\n```",
+        ]);
+        let registry = ToolRegistry::new();
+        registry.register(Tool::typed(
+            "weather",
+            "Current weather in a city",
+            |args: City| Ok::<_, String>(format!("{}: 24C, clear sky", args.city)),
+        ));
+        let llm = OpenAICompletion::new("gpt-4o-mini", Some("sk-test".into()), Some(url));
+
+        let mut agent = LiteAgent::new("Forecaster", "Report the weather", "openai/gpt-4o-mini")
+            .with_tools(["weather"])
+            .with_tool_registry(registry)
+            .with_response_model::<Forecast>()
+            .with_llm_instance(Arc::new(llm));
+        let output = agent
+            .kickoff(vec![llm_message("user", "What's the weather in Paris?")])
+            .unwrap();
+
+        let forecast: Forecast = output.model().unwrap();
+        assert_eq!(forecast.headline, "Sunny in Paris");
+        assert_eq!(output.agent_role, "Forecaster");
+        assert_eq!(output.usage_metrics.unwrap()["total_tokens"], 30);
+        assert_eq!(agent.iterations, 1);
+
+        let requests = requests.lock().unwrap();
+        let system = requests[0]["messages"][0]["content"].as_str().unwrap();
+        assert!(system.contains("- weather: Current weather in a city"));
+        assert!(system.contains("\"headline\""));
+        assert_eq!(
+            requests[0]["messages"][1]["content"],
+            "What's the weather in Paris?"
+        );
+        let observation = requests[1]["messages"][3]["content"].as_str().unwrap();
+        assert_eq!(observation, "Observation: Paris: 24C, clear sky");
+    }
+
+    #[test]
+    fn test_kickoff_plain_answer() {
+        let (url, _) = serve(vec![
+            "Thought: I now know the final answer\nFinal Answer: 42",
+        ]);
+        let llm = OpenAICompletion::new("gpt-4o-mini", Some("sk-test".into()), Some(url));
+        let mut agent = LiteAgent::new("Oracle", "Answer", "openai/gpt-4o-mini")
+            .with_llm_instance(Arc::new(llm));
+        let output = agent
+            .kickoff(vec![
+                llm_message("system", "Be terse."),
+                llm_message("user", "What is the answer?"),
+            ])
+            .unwrap();
+        assert_eq!(output.raw, "42");
+        assert!(output.pydantic.is_none());
+        assert!(output.model::<Forecast>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_kickoff_async_inside_runtime() {
+        let (url, _) = serve(vec![
+            "Thought: I now know the final answer\nFinal Answer: 42",
+        ]);
+        let llm = OpenAICompletion::new("gpt-4o-mini", Some("sk-test".into()), Some(url));
+        let mut agent = LiteAgent::new("Oracle", "Answer", "openai/gpt-4o-mini")
+            .with_llm_instance(Arc::new(llm));
+        let output = agent
+            .kickoff_async(vec![llm_message("user", "What is the answer?")])
+            .await
+            .unwrap();
+        assert_eq!(output.raw, "42");
+        assert_eq!(output.agent_role, "Oracle");
+        assert_eq!(agent.role, "Oracle");
+        assert!(agent.llm_instance.is_some());
+    }
+
+    #[test]
+    fn test_split_messages() {
+        let (system, request) = split_messages(&[llm_message("user", "Hi")]);
+        assert_eq!(system, None);
+        assert_eq!(request, "Hi");
+
+        let (system, request) = split_messages(&[
+            llm_message("system", "Be terse."),
+            llm_message("user", "Hi"),
+            llm_message("assistant", "Hello"),
+            llm_message("user", "Bye"),
+        ]);
+        assert_eq!(system.as_deref(), Some("Be terse."));
+        assert_eq!(request, "user: Hi\n\nassistant: Hello\n\nuser: Bye");
+    }
+}
@@ -11,10 +11,20 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use crate::llms::base_llm::BaseLLM;
+use crate::llms::base_llm::{BaseLLM, BaseLLMState, TokenUsageCounter};
+use crate::llms::http::HttpConfig;
 use crate::llms::providers::openai::OpenAICompletion;
 use crate::llms::providers::xai::XAICompletion;
+use crate::llms::retry::RetryPolicy;
+use crate::llms::third_party::{Gateway, GatewayCompletion};
+use crate::security::secrets::{SecretString, SecretsProvider};
+use crate::types::usage_metrics::UsageMetrics;
+
+mod result;
+
+pub use result::LLMResult;
 
 /// Minimum context window size.
 pub const MIN_CONTEXT: i64 = 1024;
@@ -249,7 +259,10 @@ pub struct LLM {
     pub api_version: Option<String>,
     /// API key for authentication.
     #[serde(skip_serializing)]
-    pub api_key: Option<String>,
+    pub api_key: Option<SecretString>,
+    /// Resolves the API key at call time when `api_key` is unset.
+    #[serde(skip)]
+    pub secrets_provider: Option<Arc<dyn SecretsProvider>>,
     /// Callbacks to be executed during LLM calls.
     #[serde(skip)]
     pub callbacks: Vec<Box<dyn std::any::Any + Send + Sync>>,
@@ -265,12 +278,24 @@ pub struct LLM {
     pub additional_params: HashMap<String, Value>,
     /// Whether this model is an Anthropic model.
     pub is_anthropic: bool,
-    /// Whether this LLM uses LiteLLM as a backend.
+    /// Whether this LLM calls a LiteLLM proxy (at `base_url`, or
+    /// `$LITELLM_PROXY_API_BASE`) with the model name passed through.
     pub is_litellm: bool,
     /// Explicit provider override (e.g., "openai", "anthropic").
     pub provider: Option<String>,
+    /// Retry policy for the provider's requests; the provider's default
+    /// (two retries, 1s then 2s apart) when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Proxy and TLS settings of the provider's HTTP client; taken from
+    /// the environment when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_config: Option<HttpConfig>,
     /// Completion cost from the last call.
     pub completion_cost: Option<f64>,
+    /// Token usage of all calls made through this LLM.
+    #[serde(default)]
+    pub token_usage: TokenUsageCounter,
 }
 
 impl Clone for LLM {
@@ -295,6 +320,7 @@ impl Clone for LLM {
             api_base: self.api_base.clone(),
             api_version: self.api_version.clone(),
             api_key: self.api_key.clone(),
+            secrets_provider: self.secrets_provider.clone(),
             callbacks: Vec::new(), // Callbacks cannot be cloned
             reasoning_effort: self.reasoning_effort.clone(),
             stream: self.stream,
@@ -304,7 +330,10 @@ impl Clone for LLM {
             is_anthropic: self.is_anthropic,
             is_litellm: self.is_litellm,
             provider: self.provider.clone(),
+            retry_policy: self.retry_policy.clone(),
+            http_config: self.http_config.clone(),
             completion_cost: self.completion_cost,
+            token_usage: self.token_usage.clone(),
         }
     }
 }
@@ -352,7 +381,13 @@ impl LLM {
 
     /// Set the API key.
     pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
-        self.api_key = Some(api_key.into());
+        self.api_key = Some(SecretString::new(api_key));
+        self
+    }
+
+    /// Resolve the API key from a secrets provider at call time.
+    pub fn secrets_provider(mut self, provider: Arc<dyn SecretsProvider>) -> Self {
+        self.secrets_provider = Some(provider);
         self
     }
 
@@ -392,6 +427,35 @@ impl LLM {
         self
     }
 
+    /// Set the retry policy.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Set the proxy and TLS settings.
+    pub fn http_config(mut self, config: HttpConfig) -> Self {
+        self.http_config = Some(config);
+        self
+    }
+
+    /// Send requests through `proxy`, e.g. `http://proxy.corp:3128`.
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.http_config
+            .get_or_insert_with(HttpConfig::from_env)
+            .proxy = Some(proxy.into());
+        self
+    }
+
+    /// Trust the root certificates in the PEM file at `path`.
+    pub fn ca_cert(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.http_config
+            .get_or_insert_with(HttpConfig::from_env)
+            .ca_certs
+            .push(path.into());
+        self
+    }
+
     // --- Anthropic detection ---
 
     /// Check if a model name is an Anthropic model.
@@ -442,10 +506,16 @@ impl LLM {
         if let Some(ref provider) = self.provider {
             return provider.clone();
         }
+        if self.is_litellm {
+            return Gateway::LiteLLM.name().to_string();
+        }
 
         let model_lower = self.model.to_lowercase();
 
-        // Check prefix (e.g., "openai/gpt-4")
+        // Check prefix (e.g., "openai/gpt-4", "openrouter/meta-llama/...")
+        if let Some((gateway, _)) = Gateway::split_model(&model_lower) {
+            return gateway.name().to_string();
+        }
         if let Some((prefix, _)) = model_lower.split_once('/') {
             match prefix {
                 "openai" => return "openai".to_string(),
@@ -499,12 +569,36 @@ impl LLM {
     ///
     /// # Returns
     ///
-    /// The LLM response string.
+    /// The LLM response string. Use [`call_v2`](Self::call_v2) for the
+    /// tool calls, usage and finish reason as separate fields.
     pub fn call(
         &self,
         messages: &[HashMap<String, String>],
         tools: Option<&[Value]>,
     ) -> Result<String, String> {
+        let (response, _) = self.call_provider(messages, tools)?;
+
+        // Extract text content from provider response Value
+        Self::extract_text_from_response(&response)
+    }
+
+    /// Call the LLM and return the structured [`LLMResult`] (synchronous).
+    pub fn call_v2(
+        &self,
+        messages: &[HashMap<String, String>],
+        tools: Option<&[Value]>,
+    ) -> Result<LLMResult, String> {
+        let (response, state) = self.call_provider(messages, tools)?;
+        Ok(self.llm_result(&response, &state))
+    }
+
+    /// Send `messages` to the provider, returning its parsed response and
+    /// the state of the client that made the call.
+    fn call_provider(
+        &self,
+        messages: &[HashMap<String, String>],
+        tools: Option<&[Value]>,
+    ) -> Result<(Value, BaseLLMState), String> {
         let provider = self.infer_provider();
         log::debug!(
             "LLM.call: model={}, provider={}, {} messages, {} tools",
@@ -514,43 +608,86 @@ impl LLM {
             tools.map_or(0, |t| t.len())
         );
 
-        // Convert HashMap<String, String> → Vec<LLMMessage> (HashMap<String, Value>)
-        let llm_messages: Vec<HashMap<String, Value>> = messages
+        let llm_messages = Self::to_llm_messages(messages);
+        let tools_vec = tools.map(|t| t.to_vec());
+
+        let (result, state) = match provider.as_str() {
+            "openai" => {
+                let mut completion = OpenAICompletion::from_llm_config(self);
+                self.configure_state(&mut completion.state);
+                let result = completion.call(llm_messages, tools_vec, None);
+                (result, completion.state)
+            }
+            "xai" => {
+                let mut completion = XAICompletion::from_llm_config(self);
+                self.configure_state(&mut completion.state);
+                let result = completion.call(llm_messages, tools_vec, None);
+                (result, completion.state)
+            }
+            other => match Gateway::from_name(other) {
+                Some(gateway) => {
+                    let completion = self.gateway_completion(gateway);
+                    let result = completion.call(llm_messages, tools_vec, None);
+                    (result, completion.completion.state)
+                }
+                None => return Err(unwired_provider(other)),
+            },
+        };
+        self.token_usage.add(&state.token_usage.get().into());
+        let response = result.map_err(|e| e.to_string())?;
+        Ok((response, state))
+    }
+
+    /// Convert HashMap<String, String> → Vec<LLMMessage> (HashMap<String, Value>)
+    fn to_llm_messages(messages: &[HashMap<String, String>]) -> Vec<HashMap<String, Value>> {
+        messages
             .iter()
             .map(|m| {
                 m.iter()
                     .map(|(k, v)| (k.clone(), Value::String(v.clone())))
                     .collect()
             })
-            .collect();
+            .collect()
+    }
 
-        let tools_vec = tools.map(|t| t.to_vec());
+    /// The [`LLMResult`] of a provider `response`, with the usage and raw
+    /// body kept by the client's `state`.
+    fn llm_result(&self, response: &Value, state: &BaseLLMState) -> LLMResult {
+        LLMResult::from_response(
+            response,
+            state.token_usage.get().into(),
+            state.last_response.get(),
+            &self.model,
+        )
+    }
 
-        let result = match provider.as_str() {
-            "openai" => {
-                let completion =
-                    OpenAICompletion::new(&self.model, self.api_key.clone(), self.api_base.clone());
-                completion
-                    .call(llm_messages, tools_vec, None)
-                    .map_err(|e| e.to_string())
-            }
-            "xai" => {
-                let completion =
-                    XAICompletion::new(&self.model, self.api_key.clone(), self.api_base.clone());
-                completion
-                    .call(llm_messages, tools_vec, None)
-                    .map_err(|e| e.to_string())
-            }
-            other => {
-                return Err(format!(
-                    "Provider '{}' not yet wired. Supported: openai, xai",
-                    other
-                ));
-            }
-        }?;
+    /// A client for `gateway`, at this LLM's base URL (or the gateway's
+    /// default). A model prefixed with the gateway has the prefix dropped;
+    /// the rest of the name goes to the gateway as is.
+    fn gateway_completion(&self, gateway: Gateway) -> GatewayCompletion {
+        let model = match Gateway::split_model(&self.model) {
+            Some((prefix, model)) if prefix == gateway => model,
+            _ => &self.model,
+        };
+        let base_url = self.base_url.clone().or_else(|| self.api_base.clone());
+        let mut completion = GatewayCompletion::new(gateway, model, base_url).with_llm_config(self);
+        self.configure_state(&mut completion.completion.state);
+        completion
+    }
 
-        // Extract text content from provider response Value
-        Self::extract_text_from_response(&result)
+    /// Pass this LLM's API key, secrets provider, retry policy and HTTP
+    /// settings on to a provider's state.
+    fn configure_state(&self, state: &mut BaseLLMState) {
+        if let Some(key) = &self.api_key {
+            state.api_key = Some(key.clone());
+        }
+        state.secrets_provider = self.secrets_provider.clone();
+        if self.retry_policy.is_some() {
+            state.retry_policy = self.retry_policy.clone();
+        }
+        if self.http_config.is_some() {
+            state.http = self.http_config.clone();
+        }
     }
 
     /// Async version of call.
@@ -561,6 +698,27 @@ impl LLM {
         messages: &[HashMap<String, String>],
         tools: Option<&[Value]>,
     ) -> Result<String, String> {
+        let (response, _) = self.acall_provider(messages, tools).await?;
+
+        Self::extract_text_from_response(&response)
+    }
+
+    /// Async version of [`call_v2`](Self::call_v2).
+    pub async fn acall_v2(
+        &self,
+        messages: &[HashMap<String, String>],
+        tools: Option<&[Value]>,
+    ) -> Result<LLMResult, String> {
+        let (response, state) = self.acall_provider(messages, tools).await?;
+        Ok(self.llm_result(&response, &state))
+    }
+
+    /// Async version of [`call_provider`](Self::call_provider).
+    async fn acall_provider(
+        &self,
+        messages: &[HashMap<String, String>],
+        tools: Option<&[Value]>,
+    ) -> Result<(Value, BaseLLMState), String> {
         let provider = self.infer_provider();
         log::debug!(
             "LLM.acall: model={}, provider={}, {} messages, {} tools",
@@ -570,44 +728,34 @@ impl LLM {
             tools.map_or(0, |t| t.len())
         );
 
-        // Convert HashMap<String, String> → Vec<LLMMessage> (HashMap<String, Value>)
-        let llm_messages: Vec<HashMap<String, Value>> = messages
-            .iter()
-            .map(|m| {
-                m.iter()
-                    .map(|(k, v)| (k.clone(), Value::String(v.clone())))
-                    .collect()
-            })
-            .collect();
-
+        let llm_messages = Self::to_llm_messages(messages);
         let tools_vec = tools.map(|t| t.to_vec());
 
-        let result = match provider.as_str() {
+        let (result, state) = match provider.as_str() {
             "openai" => {
-                let completion =
-                    OpenAICompletion::new(&self.model, self.api_key.clone(), self.api_base.clone());
-                completion
-                    .acall(llm_messages, tools_vec, None)
-                    .await
-                    .map_err(|e| e.to_string())
+                let mut completion = OpenAICompletion::from_llm_config(self);
+                self.configure_state(&mut completion.state);
+                let result = completion.acall(llm_messages, tools_vec, None).await;
+                (result, completion.state)
             }
             "xai" => {
-                let completion =
-                    XAICompletion::new(&self.model, self.api_key.clone(), self.api_base.clone());
-                completion
-                    .acall(llm_messages, tools_vec, None)
-                    .await
-                    .map_err(|e| e.to_string())
-            }
-            other => {
-                return Err(format!(
-                    "Provider '{}' not yet wired. Supported: openai, xai",
-                    other
-                ));
+                let mut completion = XAICompletion::from_llm_config(self);
+                self.configure_state(&mut completion.state);
+                let result = completion.acall(llm_messages, tools_vec, None).await;
+                (result, completion.state)
             }
-        }?;
-
-        Self::extract_text_from_response(&result)
+            other => match Gateway::from_name(other) {
+                Some(gateway) => {
+                    let completion = self.gateway_completion(gateway);
+                    let result = completion.acall(llm_messages, tools_vec, None).await;
+                    (result, completion.completion.state)
+                }
+                None => return Err(unwired_provider(other)),
+            },
+        };
+        self.token_usage.add(&state.token_usage.get().into());
+        let response = result.map_err(|e| e.to_string())?;
+        Ok((response, state))
     }
 
     /// Extract the text content from a provider response Value.
@@ -642,6 +790,13 @@ impl LLM {
         Ok(response.to_string())
     }
 
+    /// Token usage of all calls made through this LLM.
+    ///
+    /// Corresponds to `LLM.get_token_usage_summary` in Python.
+    pub fn get_token_usage_summary(&self) -> UsageMetrics {
+        self.token_usage.get().into()
+    }
+
     // --- Capability queries ---
 
     /// Check if the model supports function calling (tool use).
@@ -779,7 +934,10 @@ impl LLM {
             params.insert("timeout".to_string(), serde_json::json!(timeout));
         }
         if let Some(ref api_key) = self.api_key {
-            params.insert("api_key".to_string(), serde_json::json!(api_key));
+            params.insert(
+                "api_key".to_string(),
+                serde_json::json!(api_key.expose_secret()),
+            );
         }
         if let Some(ref base_url) = self.base_url.as_ref().or(self.api_base.as_ref()) {
             params.insert("api_base".to_string(), serde_json::json!(base_url));
@@ -871,6 +1029,15 @@ impl std::fmt::Display for LLM {
 // Tests
 // ---------------------------------------------------------------------------
 
+/// Error for a provider [`LLM::call`] cannot route to.
+fn unwired_provider(provider: &str) -> String {
+    format!(
+        "Provider '{}' not yet wired. Supported: openai, xai, {}",
+        provider,
+        Gateway::ALL.map(Gateway::name).join(", ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -906,7 +1073,10 @@ mod tests {
         assert_eq!(llm.temperature, Some(0.7));
         assert_eq!(llm.max_tokens, Some(1000));
         assert!(llm.stream);
-        assert_eq!(llm.api_key, Some("test-key".to_string()));
+        assert_eq!(
+            llm.api_key.as_ref().map(|k| k.expose_secret()),
+            Some("test-key")
+        );
     }
 
     #[test]
@@ -962,6 +1132,30 @@ mod tests {
         assert_eq!(llm.infer_provider(), "mistral");
     }
 
+    #[test]
+    fn test_infer_provider_gateway() {
+        let llm = LLM::new("openrouter/anthropic/claude-3.5-sonnet");
+        assert_eq!(llm.infer_provider(), "openrouter");
+        let completion = llm.gateway_completion(Gateway::OpenRouter);
+        assert_eq!(completion.model(), "anthropic/claude-3.5-sonnet");
+
+        let mut llm = LLM::new("gpt-4o");
+        llm.is_litellm = true;
+        llm.base_url = Some("http://proxy:4000".into());
+        assert_eq!(llm.infer_provider(), "litellm");
+        let completion = llm.gateway_completion(Gateway::LiteLLM);
+        assert_eq!(completion.model(), "gpt-4o");
+        assert_eq!(
+            completion.completion.state.base_url.as_deref(),
+            Some("http://proxy:4000")
+        );
+
+        let err = LLM::with_provider("command-r", "cohere")
+            .call(&[], None)
+            .unwrap_err();
+        assert!(err.contains("openrouter, together_ai"));
+    }
+
     #[test]
     fn test_matches_provider_pattern() {
         assert!(LLM::matches_provider_pattern("gpt-4o", "openai"));
@@ -1045,6 +1239,18 @@ mod tests {
         assert_eq!(params["stream"], serde_json::json!(true));
     }
 
+    #[test]
+    fn test_stop_reaches_provider_request() {
+        let llm = LLM::new("openrouter/meta-llama/llama-3-70b").stop(vec!["END".to_string()]);
+        let completion = llm.gateway_completion(Gateway::OpenRouter);
+        let body = completion.completion.build_request_body(&[], None);
+        assert_eq!(body["stop"], serde_json::json!(["END"]));
+
+        let completion = XAICompletion::from_llm_config(&llm);
+        let body = completion.build_request_body(&[], None);
+        assert_eq!(body["stop"], serde_json::json!(["END"]));
+    }
+
     #[test]
     fn test_display() {
         let llm = LLM::new("gpt-4o");
@@ -0,0 +1,137 @@
+//! Structured result of an LLM call.
+//!
+//! [`LLM::call`](super::LLM::call) flattens a response to a string, with
+//! tool calls serialized into it. [`LLMResult`], returned by
+//! [`LLM::call_v2`](super::LLM::call_v2), keeps the pieces apart.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::types::usage_metrics::UsageMetrics;
+
+/// The structured result of a single LLM call.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LLMResult {
+    /// Text content of the reply; empty when the model only called tools.
+    pub message: String,
+    /// Tool calls requested by the model, in the OpenAI
+    /// `{"id", "type": "function", "function": {"name", "arguments"}}` shape.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<Value>,
+    /// Tokens used by the call.
+    pub usage: UsageMetrics,
+    /// Why the model stopped (e.g. `stop`, `length`, `tool_calls`), as the
+    /// provider reported it.
+    pub finish_reason: Option<String>,
+    /// The model that served the call.
+    pub model: String,
+    /// The provider's response body, when the provider exposes it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Value>,
+}
+
+impl LLMResult {
+    /// Build a result from a provider's parsed `response`, the `usage` of
+    /// the call and the `raw` response body; `model` is used when the body
+    /// does not name one.
+    pub fn from_response(
+        response: &Value,
+        usage: UsageMetrics,
+        raw: Option<Value>,
+        model: &str,
+    ) -> Self {
+        let tool_calls = response
+            .get("tool_calls")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        let message = match response {
+            Value::String(text) => text.clone(),
+            _ => response
+                .get("content")
+                .or_else(|| response.get("text"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        };
+        let finish_reason = raw
+            .as_ref()
+            .and_then(finish_reason)
+            .or_else(|| (!tool_calls.is_empty()).then(|| "tool_calls".to_string()));
+        let model = raw
+            .as_ref()
+            .and_then(|raw| raw.get("model").or_else(|| raw.get("modelVersion")))
+            .and_then(Value::as_str)
+            .unwrap_or(model)
+            .to_string();
+        Self {
+            message,
+            tool_calls,
+            usage,
+            finish_reason,
+            model,
+            raw,
+        }
+    }
+
+    /// Whether the model asked for tools to be called.
+    pub fn has_tool_calls(&self) -> bool {
+        !self.tool_calls.is_empty()
+    }
+}
+
+/// The stop reason in a Chat Completions, Responses, Anthropic, Gemini or
+/// Bedrock Converse response body.
+fn finish_reason(raw: &Value) -> Option<String> {
+    let reason = raw["choices"][0]
+        .get("finish_reason")
+        .or_else(|| raw["incomplete_details"].get("reason"))
+        .or_else(|| raw.get("stop_reason"))
+        .or_else(|| raw["candidates"][0].get("finishReason"))
+        .or_else(|| raw.get("stopReason"))?;
+    reason.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_completions_tool_call() {
+        let raw = json!({
+            "model": "gpt-4o-2024-08-06",
+            "choices": [{
+                "message": {"role": "assistant", "content": null, "tool_calls": [
+                    {"id": "call_1", "type": "function",
+                     "function": {"name": "search", "arguments": "{}"}}
+                ]},
+                "finish_reason": "tool_calls"
+            }]
+        });
+        let parsed = raw["choices"][0]["message"].clone();
+        let usage = UsageMetrics {
+            total_tokens: 12,
+            successful_requests: 1,
+            ..Default::default()
+        };
+        let result = LLMResult::from_response(&parsed, usage, Some(raw), "gpt-4o");
+
+        assert_eq!(result.message, "");
+        assert_eq!(result.tool_calls[0]["function"]["name"], "search");
+        assert_eq!(result.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(result.model, "gpt-4o-2024-08-06");
+        assert_eq!(result.usage.total_tokens, 12);
+        assert!(result.has_tool_calls());
+    }
+
+    #[test]
+    fn test_from_text_without_raw() {
+        let result =
+            LLMResult::from_response(&json!("Hello"), UsageMetrics::default(), None, "grok-3");
+        assert_eq!(result.message, "Hello");
+        assert_eq!(result.finish_reason, None);
+        assert_eq!(result.model, "grok-3");
+        assert!(!result.has_tool_calls());
+    }
+}
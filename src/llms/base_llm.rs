@@ -11,13 +11,18 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::http::{HttpConfig, HttpConfigError};
+use super::retry::RetryPolicy;
+use crate::security::secrets::{SecretString, SecretsError, SecretsProvider};
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -205,6 +210,20 @@ pub trait BaseLLM: Send + Sync + fmt::Debug {
     fn convert_tools_for_inference(&self, tools: Vec<Value>) -> Vec<Value> {
         tools
     }
+
+    // --- Request identity ---
+
+    /// What identifies the request [`call`](Self::call) sends for
+    /// `messages` and `tools`: its endpoint, a hash of its credential and
+    /// the serialized body. Requests with the same identity get the same
+    /// answer from the provider, so concurrent ones may share a call.
+    ///
+    /// `None` (the default) when the implementation cannot tell; its
+    /// requests are never shared.
+    fn request_identity(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Option<Value> {
+        let _ = (messages, tools);
+        None
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -224,8 +243,15 @@ pub struct BaseLLMState {
     pub model: String,
     /// Optional temperature setting for response generation.
     pub temperature: Option<f64>,
-    /// Optional API key.
-    pub api_key: Option<String>,
+    /// Optional API key. Redacted in `Debug` and serialized output.
+    pub api_key: Option<SecretString>,
+    /// Resolves the API key at call time when `api_key` is unset.
+    #[serde(skip)]
+    pub secrets_provider: Option<Arc<dyn SecretsProvider>>,
+    /// Secret name to look up in `secrets_provider` instead of the
+    /// provider's default (e.g. `OPENAI_API_KEY`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_secret: Option<String>,
     /// Optional base URL for the API.
     pub base_url: Option<String>,
     /// Stop sequences that the LLM should use to stop generation.
@@ -236,8 +262,20 @@ pub struct BaseLLMState {
     pub prefer_upload: bool,
     /// Additional provider-specific parameters.
     pub additional_params: HashMap<String, Value>,
-    /// Internal token usage tracking.
-    pub token_usage: TokenUsage,
+    /// Retry policy for the provider's requests; the provider's default
+    /// when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Proxy and TLS settings of the provider's HTTP client; taken from
+    /// the environment when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<HttpConfig>,
+    /// Internal token usage tracking, updated by calls through `&self`.
+    pub token_usage: TokenUsageCounter,
+    /// Body of the last successful response, as the provider's API
+    /// returned it.
+    #[serde(skip)]
+    pub last_response: LastResponse,
 }
 
 /// Internal token usage counters.
@@ -252,6 +290,86 @@ pub struct TokenUsage {
     pub cached_prompt_tokens: i64,
 }
 
+/// [`TokenUsage`] counters that a provider updates from `acall(&self)`.
+///
+/// Clones take a snapshot rather than sharing the counters; serializes as
+/// the [`TokenUsage`] it holds.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(from = "TokenUsage", into = "TokenUsage")]
+pub struct TokenUsageCounter(std::sync::Mutex<TokenUsage>);
+
+impl TokenUsageCounter {
+    /// The current counts.
+    pub fn get(&self) -> TokenUsage {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Add `usage` to the counts.
+    pub fn add(&self, usage: &UsageMetrics) {
+        let mut counts = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        counts.total_tokens += usage.total_tokens;
+        counts.prompt_tokens += usage.prompt_tokens;
+        counts.completion_tokens += usage.completion_tokens;
+        counts.successful_requests += usage.successful_requests;
+        counts.cached_prompt_tokens += usage.cached_prompt_tokens;
+    }
+}
+
+impl Clone for TokenUsageCounter {
+    fn clone(&self) -> Self {
+        Self::from(self.get())
+    }
+}
+
+impl From<TokenUsage> for TokenUsageCounter {
+    fn from(usage: TokenUsage) -> Self {
+        Self(std::sync::Mutex::new(usage))
+    }
+}
+
+/// The raw body of a provider's last successful response, set from
+/// `acall(&self)`.
+///
+/// Clones take a snapshot, like [`TokenUsageCounter`].
+#[derive(Debug, Default)]
+pub struct LastResponse(std::sync::Mutex<Option<Value>>);
+
+impl LastResponse {
+    /// The last response, if any call has succeeded.
+    pub fn get(&self) -> Option<Value> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Replace the last response with `response`.
+    pub fn set(&self, response: &Value) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(response.clone());
+    }
+}
+
+impl Clone for LastResponse {
+    fn clone(&self) -> Self {
+        Self(std::sync::Mutex::new(self.get()))
+    }
+}
+
+impl From<TokenUsage> for UsageMetrics {
+    fn from(usage: TokenUsage) -> Self {
+        Self {
+            total_tokens: usage.total_tokens,
+            prompt_tokens: usage.prompt_tokens,
+            cached_prompt_tokens: usage.cached_prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            successful_requests: usage.successful_requests,
+        }
+    }
+}
+
+impl From<TokenUsageCounter> for TokenUsage {
+    fn from(counter: TokenUsageCounter) -> Self {
+        counter.0.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 impl BaseLLMState {
     /// Create a new `BaseLLMState` with the given model name.
     ///
@@ -269,12 +387,17 @@ impl BaseLLMState {
             model,
             temperature: None,
             api_key: None,
+            secrets_provider: None,
+            api_key_secret: None,
             base_url: None,
             stop: Vec::new(),
             provider: "openai".to_string(),
             prefer_upload: false,
             additional_params: HashMap::new(),
-            token_usage: TokenUsage::default(),
+            retry_policy: None,
+            http: None,
+            token_usage: TokenUsageCounter::default(),
+            last_response: LastResponse::default(),
         }
     }
 
@@ -296,16 +419,78 @@ impl BaseLLMState {
         Self {
             model,
             temperature,
-            api_key,
+            api_key: api_key.map(SecretString::from),
+            secrets_provider: None,
+            api_key_secret: None,
             base_url,
             stop: Vec::new(),
             provider: provider.unwrap_or_else(|| "openai".to_string()),
             prefer_upload,
             additional_params: HashMap::new(),
-            token_usage: TokenUsage::default(),
+            retry_policy: None,
+            http: None,
+            token_usage: TokenUsageCounter::default(),
+            last_response: LastResponse::default(),
         }
     }
 
+    /// The retry policy for a request: `retry_policy` if set, otherwise
+    /// the default policy with `max_retries` retries.
+    pub fn effective_retry_policy(&self, max_retries: u32) -> RetryPolicy {
+        self.retry_policy
+            .clone()
+            .unwrap_or_else(|| RetryPolicy::default().with_max_retries(max_retries))
+    }
+
+    /// An HTTP client with the `http` settings (or the environment's) and
+    /// the request `timeout`.
+    pub fn http_client(
+        &self,
+        timeout: std::time::Duration,
+    ) -> Result<reqwest::Client, HttpConfigError> {
+        match self.http {
+            Some(ref config) => config.client(Some(timeout)),
+            None => HttpConfig::from_env().client(Some(timeout)),
+        }
+    }
+
+    // --- Credentials ---
+
+    /// The API key to use for a call: the configured key if set, otherwise
+    /// `api_key_secret` (or `default_secret`) looked up in the secrets
+    /// provider.
+    pub async fn resolve_api_key(
+        &self,
+        default_secret: &str,
+    ) -> Result<Option<SecretString>, SecretsError> {
+        if let Some(key) = &self.api_key {
+            return Ok(Some(key.clone()));
+        }
+        match &self.secrets_provider {
+            Some(provider) => {
+                let name = self.api_key_secret.as_deref().unwrap_or(default_secret);
+                provider.get_secret(name).await
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// A hash of the credential calls authenticate with: the configured
+    /// API key or, without one, the secrets provider instance and the
+    /// secret name looked up in it.
+    pub fn credential_hash(&self, default_secret: &str) -> String {
+        let credential = match (&self.api_key, &self.secrets_provider) {
+            (Some(key), _) => format!("key:{}", key.expose_secret()),
+            (None, Some(provider)) => format!(
+                "secret:{:p}:{}",
+                Arc::as_ptr(provider),
+                self.api_key_secret.as_deref().unwrap_or(default_secret)
+            ),
+            (None, None) => "none".to_string(),
+        };
+        hex::encode(Sha256::digest(credential.as_bytes()))
+    }
+
     // --- Stop word handling ---
 
     /// Apply stop words to truncate response content.
@@ -383,10 +568,12 @@ impl BaseLLMState {
     /// Track token usage from API response data.
     ///
     /// Extracts tokens in a provider-agnostic way, supporting OpenAI,
-    /// Anthropic, Gemini, and Bedrock field names.
+    /// Anthropic, Gemini, and Bedrock field names. Counts one successful
+    /// request, and [records](crate::types::usage_metrics::record) the
+    /// usage for the task running on this thread.
     ///
     /// Corresponds to `BaseLLM._track_token_usage_internal`.
-    pub fn track_token_usage_internal(&mut self, usage_data: &HashMap<String, Value>) {
+    pub fn track_token_usage_internal(&self, usage_data: &HashMap<String, Value>) {
         let prompt_tokens = usage_data
             .get("prompt_tokens")
             .or_else(|| usage_data.get("prompt_token_count"))
@@ -407,22 +594,20 @@ impl BaseLLMState {
             .and_then(|v| v.as_i64())
             .unwrap_or(0);
 
-        self.token_usage.prompt_tokens += prompt_tokens;
-        self.token_usage.completion_tokens += completion_tokens;
-        self.token_usage.total_tokens += prompt_tokens + completion_tokens;
-        self.token_usage.successful_requests += 1;
-        self.token_usage.cached_prompt_tokens += cached_tokens;
+        let usage = UsageMetrics {
+            total_tokens: prompt_tokens + completion_tokens,
+            prompt_tokens,
+            cached_prompt_tokens: cached_tokens,
+            completion_tokens,
+            successful_requests: 1,
+        };
+        self.token_usage.add(&usage);
+        crate::types::usage_metrics::record(&usage);
     }
 
     /// Get summary of token usage as `UsageMetrics`.
     pub fn get_token_usage_summary(&self) -> UsageMetrics {
-        UsageMetrics {
-            total_tokens: self.token_usage.total_tokens,
-            prompt_tokens: self.token_usage.prompt_tokens,
-            cached_prompt_tokens: self.token_usage.cached_prompt_tokens,
-            completion_tokens: self.token_usage.completion_tokens,
-            successful_requests: self.token_usage.successful_requests,
-        }
+        self.token_usage.get().into()
     }
 
     // --- Provider utilities ---
@@ -520,29 +705,33 @@ pub fn emit_stream_chunk_event(chunk: &str, call_id: &str) {
 }
 
 // ---------------------------------------------------------------------------
-// Hook invocation stubs
+// Hook invocation
 // ---------------------------------------------------------------------------
 
-/// Stub: invoke before-LLM-call hooks.
+/// Invoke the global before-LLM-call hooks.
 ///
 /// Returns `true` if the call should proceed, `false` if blocked by a hook.
 /// Corresponds to `BaseLLM._invoke_before_llm_call_hooks`.
-pub fn invoke_before_llm_call_hooks(_messages: &[LLMMessage], _model: &str) -> bool {
-    // Stub: always allow
-    true
+pub fn invoke_before_llm_call_hooks(messages: &[LLMMessage], model: &str) -> bool {
+    let mut ctx = crate::hooks::LLMCallHookContext::with_messages(messages);
+    ctx.llm = Some(model.to_string());
+    crate::hooks::run_before_llm_call_hooks(&mut ctx)
 }
 
-/// Stub: invoke after-LLM-call hooks.
+/// Invoke the global after-LLM-call hooks.
 ///
 /// Returns the (potentially modified) response string.
 /// Corresponds to `BaseLLM._invoke_after_llm_call_hooks`.
 pub fn invoke_after_llm_call_hooks(
-    _messages: &[LLMMessage],
+    messages: &[LLMMessage],
     response: String,
-    _model: &str,
+    model: &str,
 ) -> String {
-    // Stub: return response unchanged
-    response
+    let mut ctx = crate::hooks::LLMCallHookContext::with_messages(messages);
+    ctx.llm = Some(model.to_string());
+    ctx.response = Some(response);
+    crate::hooks::run_after_llm_call_hooks(&mut ctx);
+    ctx.response.unwrap_or_default()
 }
 
 // ---------------------------------------------------------------------------
@@ -649,24 +838,33 @@ mod tests {
 
     #[test]
     fn test_token_usage_tracking() {
-        let mut state = BaseLLMState::new("test");
+        let state = BaseLLMState::new("test");
         let mut usage = HashMap::new();
         usage.insert("prompt_tokens".to_string(), serde_json::json!(100));
         usage.insert("completion_tokens".to_string(), serde_json::json!(50));
         usage.insert("cached_tokens".to_string(), serde_json::json!(10));
 
+        let scope = crate::types::usage_metrics::UsageScope::enter();
         state.track_token_usage_internal(&usage);
 
-        assert_eq!(state.token_usage.prompt_tokens, 100);
-        assert_eq!(state.token_usage.completion_tokens, 50);
-        assert_eq!(state.token_usage.total_tokens, 150);
-        assert_eq!(state.token_usage.successful_requests, 1);
-        assert_eq!(state.token_usage.cached_prompt_tokens, 10);
+        let counts = state.token_usage.get();
+        assert_eq!(counts.prompt_tokens, 100);
+        assert_eq!(counts.completion_tokens, 50);
+        assert_eq!(counts.total_tokens, 150);
+        assert_eq!(counts.successful_requests, 1);
+        assert_eq!(counts.cached_prompt_tokens, 10);
 
         // Track again to test accumulation
         state.track_token_usage_internal(&usage);
-        assert_eq!(state.token_usage.total_tokens, 300);
-        assert_eq!(state.token_usage.successful_requests, 2);
+        assert_eq!(state.token_usage.get().total_tokens, 300);
+        assert_eq!(state.get_token_usage_summary().successful_requests, 2);
+        assert_eq!(scope.usage().total_tokens, 300);
+
+        // Clones and round trips keep the counts
+        let json = serde_json::to_value(&state).unwrap();
+        assert_eq!(json["token_usage"]["total_tokens"], 300);
+        let restored: BaseLLMState = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.clone().token_usage.get().total_tokens, 300);
     }
 
     #[test]
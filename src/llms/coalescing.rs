@@ -0,0 +1,272 @@
+//! Coalescing of identical concurrent LLM requests.
+//!
+//! Fan-out workloads such as `kickoff_for_each` often send byte-identical
+//! requests (a shared preamble step, the same planning prompt) from several
+//! agents at once. With coalescing enabled, the first of a set of identical
+//! in-flight requests goes to the provider and the others wait for and share
+//! its result instead of paying for their own call.
+//!
+//! Only requests that are in flight at the same time are shared; nothing is
+//! cached once the provider call returns. The provider's token usage is
+//! attributed to the call that made the request: waiters report no usage
+//! and name the shared call in [`Coalesced::shared_from`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::base_llm::{BaseLLM, LLMMessage};
+
+/// Result of a coalesced call.
+#[derive(Debug, Clone)]
+pub struct Coalesced {
+    /// The provider's response, or its error message.
+    pub result: Result<Value, String>,
+    /// Call ID of the provider call whose result was shared; `None` for the
+    /// call that made the request.
+    pub shared_from: Option<String>,
+}
+
+/// A provider call in flight and the callers waiting for it.
+#[derive(Debug)]
+struct InFlight {
+    call_id: String,
+    result: Mutex<Option<Result<Value, String>>>,
+    ready: Condvar,
+}
+
+/// Shares the results of identical in-flight requests.
+#[derive(Debug, Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<String, Arc<InFlight>>>,
+}
+
+impl RequestCoalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    /// Run `call` (identified by `call_id`) for the request `key`, unless an
+    /// identical request is already in flight, in which case wait for and
+    /// share its result.
+    pub fn run<F>(&self, key: &str, call_id: &str, call: F) -> Coalesced
+    where
+        F: FnOnce() -> Result<Value, String>,
+    {
+        let (flight, leader) = {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            match in_flight.get(key) {
+                Some(flight) => (flight.clone(), false),
+                None => {
+                    let flight = Arc::new(InFlight {
+                        call_id: call_id.to_string(),
+                        result: Mutex::new(None),
+                        ready: Condvar::new(),
+                    });
+                    in_flight.insert(key.to_string(), flight.clone());
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leader {
+            let mut result = flight.result.lock().unwrap_or_else(|e| e.into_inner());
+            while result.is_none() {
+                result = flight.ready.wait(result).unwrap_or_else(|e| e.into_inner());
+            }
+            return Coalesced {
+                result: result
+                    .clone()
+                    .unwrap_or_else(|| Err("no result".to_string())),
+                shared_from: Some(flight.call_id.clone()),
+            };
+        }
+
+        // Waiters must be released even if the call panics.
+        struct Finish<'a> {
+            coalescer: &'a RequestCoalescer,
+            key: &'a str,
+            flight: Arc<InFlight>,
+            result: Option<Result<Value, String>>,
+        }
+        impl Drop for Finish<'_> {
+            fn drop(&mut self) {
+                self.coalescer
+                    .in_flight
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .remove(self.key);
+                let result = self
+                    .result
+                    .take()
+                    .unwrap_or_else(|| Err("The shared LLM call panicked".to_string()));
+                *self.flight.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                self.flight.ready.notify_all();
+            }
+        }
+        let mut finish = Finish {
+            coalescer: self,
+            key,
+            flight,
+            result: None,
+        };
+        let result = call();
+        finish.result = Some(result.clone());
+        drop(finish);
+        Coalesced {
+            result,
+            shared_from: None,
+        }
+    }
+}
+
+/// Key identifying a request: a hash of its
+/// [identity](BaseLLM::request_identity), i.e. the endpoint, the credential
+/// and the body sent. Requests with the same key go to the same endpoint,
+/// for the same account, with the same body.
+///
+/// `None` when `llm` cannot identify its requests; they are not coalesced.
+pub fn request_key(
+    llm: &dyn BaseLLM,
+    messages: &[LLMMessage],
+    tools: Option<&[Value]>,
+) -> Option<String> {
+    let identity = llm.request_identity(messages, tools)?;
+    Some(hex::encode(Sha256::digest(identity.to_string().as_bytes())))
+}
+
+/// Key identifying the content of a request: a hash of the model settings,
+/// messages and tools, leaving out the endpoint and credential so a
+/// journal recorded with one account replays with another. Not suitable
+/// for sharing responses; see [`request_key`].
+pub fn content_key(llm: &dyn BaseLLM, messages: &[LLMMessage], tools: Option<&[Value]>) -> String {
+    let request = serde_json::json!({
+        "provider": llm.provider(),
+        "model": llm.model(),
+        "temperature": llm.temperature(),
+        "stop": llm.stop(),
+        "messages": messages,
+        "tools": tools,
+    });
+    hex::encode(Sha256::digest(request.to_string().as_bytes()))
+}
+
+/// The process-wide coalescer shared by all agents.
+pub fn global() -> &'static RequestCoalescer {
+    static COALESCER: OnceLock<RequestCoalescer> = OnceLock::new();
+    COALESCER.get_or_init(RequestCoalescer::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+    use std::time::Duration;
+
+    #[test]
+    fn test_identical_concurrent_requests_share_one_call() {
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(4));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let (coalescer, calls, barrier) =
+                    (coalescer.clone(), calls.clone(), barrier.clone());
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    // Stagger the waiters so the first call is in flight.
+                    std::thread::sleep(Duration::from_millis(if i == 0 { 0 } else { 20 }));
+                    coalescer.run("same", &format!("call-{}", i), || {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(150));
+                        Ok(Value::from("answer"))
+                    })
+                })
+            })
+            .collect();
+        let results: Vec<Coalesced> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(results
+            .iter()
+            .all(|r| r.result == Ok(Value::from("answer"))));
+        let leaders: Vec<usize> = (0..4)
+            .filter(|&i| results[i].shared_from.is_none())
+            .collect();
+        assert_eq!(leaders.len(), 1);
+        let leader_call = format!("call-{}", leaders[0]);
+        assert!(results
+            .iter()
+            .filter(|r| r.shared_from.is_some())
+            .all(|r| r.shared_from.as_deref() == Some(leader_call.as_str())));
+        assert_eq!(coalescer.in_flight(), 0);
+
+        // Finished requests are not cached, and errors are shared as well.
+        let again = coalescer.run("same", "call-4", || Err("rate limited".to_string()));
+        assert_eq!(again.result, Err("rate limited".to_string()));
+        assert!(again.shared_from.is_none());
+    }
+
+    #[test]
+    fn test_requests_for_other_accounts_or_settings_are_not_shared() {
+        use crate::llms::providers::openai::OpenAICompletion;
+
+        let llm = |api_key: &str| {
+            let mut llm = OpenAICompletion::new("gpt-4o-mini", Some(api_key.to_string()), None);
+            llm.max_tokens = Some(100);
+            llm
+        };
+        let messages = vec![HashMap::from([
+            ("role".to_string(), Value::from("user")),
+            ("content".to_string(), Value::from("Hello")),
+        ])];
+        let key = |llm: &OpenAICompletion| request_key(llm, &messages, None).unwrap();
+
+        let tenant_a = llm("key-a");
+        assert_eq!(key(&tenant_a), key(&llm("key-a")));
+        let tenant_b = llm("key-b");
+        let mut longer = llm("key-a");
+        longer.max_tokens = Some(2000);
+        let mut other_endpoint = llm("key-a");
+        other_endpoint.state.base_url = Some("https://proxy.example.com/v1".to_string());
+        for other in [&tenant_b, &longer, &other_endpoint] {
+            assert_ne!(key(&tenant_a), key(other));
+        }
+
+        // Concurrent requests for the two tenants each reach the provider.
+        let coalescer = Arc::new(RequestCoalescer::new());
+        let both_running = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = [key(&tenant_a), key(&tenant_b)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let (coalescer, both_running) = (coalescer.clone(), both_running.clone());
+                std::thread::spawn(move || {
+                    coalescer.run(&key, &format!("call-{}", i), || {
+                        both_running.wait();
+                        Ok(Value::from(format!("answer for tenant {}", i)))
+                    })
+                })
+            })
+            .collect();
+        for (i, handle) in handles.into_iter().enumerate() {
+            let result = handle.join().unwrap();
+            assert_eq!(
+                result.result,
+                Ok(Value::from(format!("answer for tenant {}", i)))
+            );
+            assert!(result.shared_from.is_none());
+        }
+    }
+}
@@ -50,8 +50,10 @@
 //! ```
 
 use std::fmt;
+use std::sync::Arc;
 
 use async_trait::async_trait;
+use reqwest::header::{HeaderName, HeaderValue};
 
 // ---------------------------------------------------------------------------
 // BaseInterceptor trait
@@ -215,3 +217,59 @@ where
         message
     }
 }
+
+// ---------------------------------------------------------------------------
+// Header injection
+// ---------------------------------------------------------------------------
+
+/// Computes the headers to add to a request.
+pub type HeaderHook = dyn Fn(&reqwest::Request) -> Vec<(String, String)> + Send + Sync;
+
+/// An interceptor that adds headers computed for each outbound HTTP request.
+///
+/// Covers provider-specific headers such as OpenRouter's attribution
+/// headers or a per-request trace ID. Headers with an invalid name or value
+/// are skipped with a warning.
+#[derive(Clone)]
+pub struct HeaderInjector {
+    hook: Arc<HeaderHook>,
+}
+
+impl HeaderInjector {
+    /// Create an injector adding the headers `hook` returns.
+    pub fn new(
+        hook: impl Fn(&reqwest::Request) -> Vec<(String, String)> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            hook: Arc::new(hook),
+        }
+    }
+}
+
+impl fmt::Debug for HeaderInjector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HeaderInjector").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl BaseInterceptor<reqwest::Request, reqwest::Response> for HeaderInjector {
+    fn on_outbound(&self, mut request: reqwest::Request) -> reqwest::Request {
+        for (name, value) in (self.hook)(&request) {
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                (Ok(name), Ok(value)) => {
+                    request.headers_mut().insert(name, value);
+                }
+                _ => log::warn!("Skipping invalid header '{}'", name),
+            }
+        }
+        request
+    }
+
+    fn on_inbound(&self, response: reqwest::Response) -> reqwest::Response {
+        response
+    }
+}
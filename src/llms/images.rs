@@ -0,0 +1,123 @@
+//! Images in LLM messages.
+//!
+//! Messages carry images as OpenAI-style content blocks,
+//! `{"type": "image_url", "image_url": {"url": ...}}`, whose URL is either
+//! an `http(s)` URL or a base64 `data:` URL. Providers with another image
+//! format convert these blocks through [`ImageSource`].
+
+use base64::Engine as _;
+use serde_json::{json, Value};
+
+use crate::utilities::artifacts::mime_type_for;
+
+/// Where an image's content comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSource {
+    /// A URL the provider fetches.
+    Url(String),
+    /// Inline base64 data.
+    Base64 {
+        /// MIME type, e.g. `image/png`.
+        media_type: String,
+        /// Base64 encoded bytes.
+        data: String,
+    },
+}
+
+impl ImageSource {
+    /// The source of the image at `url`; `data:` URLs are inline data.
+    pub fn parse(url: &str) -> Self {
+        let inline = url.strip_prefix("data:").and_then(|rest| {
+            let (header, data) = rest.split_once(',')?;
+            let media_type = header.strip_suffix(";base64")?;
+            Some(Self::Base64 {
+                media_type: media_type.to_string(),
+                data: data.to_string(),
+            })
+        });
+        inline.unwrap_or_else(|| Self::Url(url.to_string()))
+    }
+
+    /// The source of an `image_url` content block, if `block` is one.
+    pub fn from_block(block: &Value) -> Option<Self> {
+        if block.get("type").and_then(Value::as_str) != Some("image_url") {
+            return None;
+        }
+        let url = &block["image_url"];
+        let url = url.get("url").unwrap_or(url).as_str()?;
+        Some(Self::parse(url))
+    }
+
+    /// MIME type of the image, guessed from the URL's extension for URLs.
+    pub fn media_type(&self) -> &str {
+        match self {
+            Self::Url(url) => {
+                let path = url.split(['?', '#']).next().unwrap_or(url);
+                match mime_type_for(path) {
+                    mime if mime.starts_with("image/") => mime,
+                    _ => "image/jpeg",
+                }
+            }
+            Self::Base64 { media_type, .. } => media_type,
+        }
+    }
+}
+
+/// A URL for the image at `location`: `http(s)` and `data:` URLs as they
+/// are, local files read into a `data:` URL.
+pub fn image_url(location: &str) -> Result<String, String> {
+    let location = location.trim();
+    if ["http://", "https://", "data:"]
+        .iter()
+        .any(|scheme| location.starts_with(scheme))
+    {
+        return Ok(location.to_string());
+    }
+    let path = location.strip_prefix("file://").unwrap_or(location);
+    let media_type = mime_type_for(path);
+    if !media_type.starts_with("image/") {
+        return Err(format!("'{}' is not a supported image file", location));
+    }
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read image '{}': {}", path, e))?;
+    let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(format!("data:{};base64,{}", media_type, data))
+}
+
+/// An `image_url` content block for `url`.
+pub fn image_block(url: &str) -> Value {
+    json!({"type": "image_url", "image_url": {"url": url}})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_sources() {
+        let block = image_block("data:image/png;base64,iVBORw0K");
+        assert_eq!(
+            ImageSource::from_block(&block),
+            Some(ImageSource::Base64 {
+                media_type: "image/png".to_string(),
+                data: "iVBORw0K".to_string(),
+            })
+        );
+        let url = ImageSource::parse("https://example.com/chart.webp?size=2");
+        assert_eq!(url.media_type(), "image/webp");
+        assert!(ImageSource::from_block(&json!({"type": "text", "text": "hi"})).is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dot.gif");
+        std::fs::write(&path, b"GIF89a").unwrap();
+        assert_eq!(
+            image_url(path.to_str().unwrap()).unwrap(),
+            "data:image/gif;base64,R0lGODlh"
+        );
+        assert_eq!(
+            image_url(" https://example.com/a.png").unwrap(),
+            "https://example.com/a.png"
+        );
+        assert!(image_url(dir.path().join("notes.txt").to_str().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,927 @@
+//! Incremental JSON parsing for streamed structured outputs.
+//!
+//! [`StreamingJsonParser`] consumes a JSON document as text deltas arrive
+//! and validates the partial document against an optional JSON schema as it
+//! goes. Callers get:
+//!
+//! - **Early abort** — a generation that diverges from the schema (wrong
+//!   type, unknown property, value outside an `enum`, too many items) fails
+//!   on the offending token instead of after the full response.
+//! - **Progressive rendering** — every completed field and list item is
+//!   reported as a [`JsonStreamEvent`], and [`partial`](StreamingJsonParser::partial)
+//!   returns a best-effort snapshot of the document so far.
+//!
+//! Text before the first `{` or `[` (prose, a Markdown code fence) and
+//! anything after the root value is ignored.
+//!
+//! ```ignore
+//! let mut parser = StreamingJsonParser::new().with_schema(schema);
+//! let output = parse_structured_stream(&mut receiver, &mut parser, |event| {
+//!     if let JsonStreamEvent::Item { pointer, value, .. } = event {
+//!         render(pointer, value);
+//!     }
+//! })
+//! .await?;
+//! ```
+
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use super::streaming::{StreamChunk, StreamReceiver};
+use crate::utilities::pydantic_schema_utils::resolve_refs;
+
+/// Errors from [`StreamingJsonParser`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum JsonStreamError {
+    /// The text is not valid JSON.
+    #[error("invalid JSON at offset {offset}: {message}")]
+    Syntax { offset: usize, message: String },
+    /// The document diverges from the schema.
+    #[error("output diverges from schema at '{pointer}': {message}")]
+    Schema { pointer: String, message: String },
+    /// The input ended before the document was complete.
+    #[error("JSON document is incomplete")]
+    Incomplete,
+    /// The underlying stream reported an error.
+    #[error("stream error: {0}")]
+    Stream(String),
+}
+
+/// Progress reported while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonStreamEvent {
+    /// An object member finished parsing.
+    Field {
+        /// JSON pointer of the member (e.g. `/author/name`).
+        pointer: String,
+        /// The member's key.
+        key: String,
+        /// The member's value.
+        value: Value,
+    },
+    /// An array element finished parsing.
+    Item {
+        /// JSON pointer of the element (e.g. `/items/2`).
+        pointer: String,
+        /// Position within the array.
+        index: usize,
+        /// The element's value.
+        value: Value,
+    },
+    /// The root value is complete.
+    Done(Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ObjectExpect {
+    KeyOrEnd,
+    Key,
+    Colon,
+    Value,
+    CommaOrEnd,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArrayExpect {
+    ValueOrEnd,
+    Value,
+    CommaOrEnd,
+}
+
+#[derive(Debug)]
+enum Frame {
+    Object {
+        map: Map<String, Value>,
+        key: Option<String>,
+        expect: ObjectExpect,
+    },
+    Array {
+        items: Vec<Value>,
+        expect: ArrayExpect,
+    },
+}
+
+#[derive(Debug)]
+enum Lexeme {
+    None,
+    Str {
+        buf: String,
+        is_key: bool,
+        escape: Escape,
+        high_surrogate: Option<u32>,
+    },
+    Number(String),
+    Literal(String),
+}
+
+#[derive(Debug)]
+enum Escape {
+    None,
+    Backslash,
+    Unicode(String),
+}
+
+/// Incremental JSON parser with optional schema validation.
+#[derive(Debug)]
+pub struct StreamingJsonParser {
+    schema: Option<Value>,
+    stack: Vec<Frame>,
+    lexeme: Lexeme,
+    root: Option<Value>,
+    started: bool,
+    offset: usize,
+    error: Option<JsonStreamError>,
+    events: Vec<JsonStreamEvent>,
+}
+
+impl Default for StreamingJsonParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingJsonParser {
+    /// A parser that checks syntax only.
+    pub fn new() -> Self {
+        Self {
+            schema: None,
+            stack: Vec::new(),
+            lexeme: Lexeme::None,
+            root: None,
+            started: false,
+            offset: 0,
+            error: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Builder: validate against a JSON schema (`$ref`s into `$defs` are
+    /// resolved up front).
+    pub fn with_schema(mut self, schema: Value) -> Self {
+        let definitions = schema
+            .get("$defs")
+            .or_else(|| schema.get("definitions"))
+            .and_then(|d| d.as_object())
+            .cloned()
+            .unwrap_or_default();
+        self.schema = Some(resolve_refs(&schema, &definitions));
+        self
+    }
+
+    /// Number of bytes received so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether the root value is complete.
+    pub fn is_complete(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// Feed the next text delta, returning the values it completed.
+    ///
+    /// After an error the parser stays failed and returns the same error.
+    pub fn push(&mut self, text: &str) -> Result<Vec<JsonStreamEvent>, JsonStreamError> {
+        if let Some(e) = &self.error {
+            return Err(e.clone());
+        }
+        for c in text.chars() {
+            // Once the root is complete the rest is skipped but still counted.
+            if self.root.is_none() {
+                if let Err(e) = self.consume(c) {
+                    self.error = Some(e.clone());
+                    return Err(e);
+                }
+            }
+            self.offset += c.len_utf8();
+        }
+        Ok(std::mem::take(&mut self.events))
+    }
+
+    /// Feed a stream chunk; only text deltas carry document text.
+    pub fn push_chunk(
+        &mut self,
+        chunk: &StreamChunk,
+    ) -> Result<Vec<JsonStreamEvent>, JsonStreamError> {
+        match chunk {
+            StreamChunk::TextDelta { text } => self.push(text),
+            StreamChunk::Error { message } => Err(JsonStreamError::Stream(message.clone())),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// The complete document, or [`JsonStreamError::Incomplete`].
+    pub fn finish(&self) -> Result<Value, JsonStreamError> {
+        if let Some(e) = &self.error {
+            return Err(e.clone());
+        }
+        self.root.clone().ok_or(JsonStreamError::Incomplete)
+    }
+
+    /// Best-effort snapshot of the document parsed so far: open containers
+    /// are closed and an in-progress string is included as is.
+    pub fn partial(&self) -> Option<Value> {
+        if let Some(root) = &self.root {
+            return Some(root.clone());
+        }
+        let mut child = match &self.lexeme {
+            Lexeme::Str {
+                buf, is_key: false, ..
+            } => Some(Value::String(buf.clone())),
+            Lexeme::Number(n) => serde_json::from_str(n).ok(),
+            _ => None,
+        };
+        for frame in self.stack.iter().rev() {
+            child = Some(match frame {
+                Frame::Object { map, key, .. } => {
+                    let mut map = map.clone();
+                    if let (Some(key), Some(value)) = (key, child) {
+                        map.insert(key.clone(), value);
+                    }
+                    Value::Object(map)
+                }
+                Frame::Array { items, .. } => {
+                    let mut items = items.clone();
+                    items.extend(child);
+                    Value::Array(items)
+                }
+            });
+        }
+        child
+    }
+
+    // ------------------------------------------------------------------
+    // Lexing
+    // ------------------------------------------------------------------
+
+    fn consume(&mut self, c: char) -> Result<(), JsonStreamError> {
+        if !self.started {
+            if c == '{' || c == '[' {
+                self.started = true;
+                return self.start_value(c);
+            }
+            return Ok(());
+        }
+        match &mut self.lexeme {
+            Lexeme::Str { .. } => return self.consume_string(c),
+            Lexeme::Number(buf) => {
+                if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+                    buf.push(c);
+                    return Ok(());
+                }
+                let buf = std::mem::take(buf);
+                self.lexeme = Lexeme::None;
+                let value: Value = serde_json::from_str(&buf)
+                    .map_err(|_| self.syntax(format!("invalid number '{}'", buf)))?;
+                self.complete_value(value)?;
+            }
+            Lexeme::Literal(buf) => {
+                buf.push(c);
+                let value = match buf.as_str() {
+                    "true" => Some(Value::Bool(true)),
+                    "false" => Some(Value::Bool(false)),
+                    "null" => Some(Value::Null),
+                    _ => None,
+                };
+                if let Some(value) = value {
+                    self.lexeme = Lexeme::None;
+                    return self.complete_value(value);
+                }
+                let buf = buf.clone();
+                if !["true", "false", "null"]
+                    .iter()
+                    .any(|l| l.starts_with(&buf))
+                {
+                    return Err(self.syntax(format!("invalid literal '{}'", buf)));
+                }
+                return Ok(());
+            }
+            Lexeme::None => {}
+        }
+        self.structural(c)
+    }
+
+    fn consume_string(&mut self, c: char) -> Result<(), JsonStreamError> {
+        let Lexeme::Str {
+            buf,
+            is_key,
+            escape,
+            high_surrogate,
+        } = &mut self.lexeme
+        else {
+            return Ok(());
+        };
+        match escape {
+            Escape::None => match c {
+                '"' => {
+                    let (buf, is_key) = (std::mem::take(buf), *is_key);
+                    self.lexeme = Lexeme::None;
+                    return if is_key {
+                        self.complete_key(buf)
+                    } else {
+                        self.complete_value(Value::String(buf))
+                    };
+                }
+                '\\' => *escape = Escape::Backslash,
+                c if (c as u32) < 0x20 => {
+                    return Err(self.syntax("control character in string".to_string()))
+                }
+                c => buf.push(c),
+            },
+            Escape::Backslash => {
+                let decoded = match c {
+                    '"' => '"',
+                    '\\' => '\\',
+                    '/' => '/',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => {
+                        *escape = Escape::Unicode(String::new());
+                        return Ok(());
+                    }
+                    other => return Err(self.syntax(format!("invalid escape '\\{}'", other))),
+                };
+                buf.push(decoded);
+                *escape = Escape::None;
+            }
+            Escape::Unicode(hex) => {
+                if !c.is_ascii_hexdigit() {
+                    return Err(self.syntax("invalid unicode escape".to_string()));
+                }
+                hex.push(c);
+                if hex.len() < 4 {
+                    return Ok(());
+                }
+                let code = u32::from_str_radix(hex, 16).unwrap_or(0xFFFD);
+                *escape = Escape::None;
+                if (0xD800..0xDC00).contains(&code) {
+                    *high_surrogate = Some(code);
+                    return Ok(());
+                }
+                let code = match high_surrogate.take() {
+                    Some(high) if (0xDC00..0xE000).contains(&code) => {
+                        0x10000 + ((high - 0xD800) << 10) + (code - 0xDC00)
+                    }
+                    _ => code,
+                };
+                buf.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+            }
+        }
+        if !*is_key {
+            let prefix = buf.clone();
+            self.check_string_prefix(&prefix)?;
+        }
+        Ok(())
+    }
+
+    fn structural(&mut self, c: char) -> Result<(), JsonStreamError> {
+        if c.is_whitespace() {
+            return Ok(());
+        }
+        let expects_value = match self.stack.last() {
+            Some(Frame::Object { expect, .. }) => *expect == ObjectExpect::Value,
+            Some(Frame::Array { expect, .. }) => {
+                matches!(expect, ArrayExpect::Value | ArrayExpect::ValueOrEnd)
+            }
+            None => false,
+        };
+        if expects_value && c != ']' {
+            return self.start_value(c);
+        }
+        match (self.stack.last_mut(), c) {
+            (
+                Some(Frame::Object {
+                    expect: ObjectExpect::KeyOrEnd | ObjectExpect::Key,
+                    ..
+                }),
+                '"',
+            ) => {
+                self.lexeme = Lexeme::Str {
+                    buf: String::new(),
+                    is_key: true,
+                    escape: Escape::None,
+                    high_surrogate: None,
+                };
+                Ok(())
+            }
+            (Some(Frame::Object { expect, .. }), ':') if *expect == ObjectExpect::Colon => {
+                *expect = ObjectExpect::Value;
+                Ok(())
+            }
+            (Some(Frame::Object { expect, .. }), ',') if *expect == ObjectExpect::CommaOrEnd => {
+                *expect = ObjectExpect::Key;
+                Ok(())
+            }
+            (Some(Frame::Array { expect, .. }), ',') if *expect == ArrayExpect::CommaOrEnd => {
+                *expect = ArrayExpect::Value;
+                Ok(())
+            }
+            (
+                Some(Frame::Object {
+                    expect: ObjectExpect::KeyOrEnd | ObjectExpect::CommaOrEnd,
+                    ..
+                }),
+                '}',
+            )
+            | (
+                Some(Frame::Array {
+                    expect: ArrayExpect::ValueOrEnd | ArrayExpect::CommaOrEnd,
+                    ..
+                }),
+                ']',
+            ) => self.close_container(),
+            _ => Err(self.syntax(format!("unexpected '{}'", c))),
+        }
+    }
+
+    fn start_value(&mut self, c: char) -> Result<(), JsonStreamError> {
+        let kind = match c {
+            '{' => "object",
+            '[' => "array",
+            '"' => "string",
+            '-' | '0'..='9' => "number",
+            't' | 'f' => "boolean",
+            'n' => "null",
+            other => return Err(self.syntax(format!("unexpected '{}'", other))),
+        };
+        if let Some(schema) = self.schema_at(self.stack.len()) {
+            if !type_allows(schema, kind) {
+                return Err(self.schema_error(format!(
+                    "expected {}, got {}",
+                    describe_types(schema),
+                    kind
+                )));
+            }
+        }
+        match c {
+            '{' => self.stack.push(Frame::Object {
+                map: Map::new(),
+                key: None,
+                expect: ObjectExpect::KeyOrEnd,
+            }),
+            '[' => self.stack.push(Frame::Array {
+                items: Vec::new(),
+                expect: ArrayExpect::ValueOrEnd,
+            }),
+            '"' => {
+                self.lexeme = Lexeme::Str {
+                    buf: String::new(),
+                    is_key: false,
+                    escape: Escape::None,
+                    high_surrogate: None,
+                }
+            }
+            't' | 'f' | 'n' => self.lexeme = Lexeme::Literal(c.to_string()),
+            _ => self.lexeme = Lexeme::Number(c.to_string()),
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Tree building
+    // ------------------------------------------------------------------
+
+    fn complete_key(&mut self, key: String) -> Result<(), JsonStreamError> {
+        let container = self.stack.len() - 1;
+        if let Some(schema) = self.schema_at(container).map(effective) {
+            let known = schema
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .is_some_and(|p| p.contains_key(&key));
+            if !known && schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+                return Err(JsonStreamError::Schema {
+                    pointer: self.pointer(container),
+                    message: format!("unexpected property '{}'", key),
+                });
+            }
+        }
+        if let Some(Frame::Object {
+            map,
+            key: slot,
+            expect,
+        }) = self.stack.last_mut()
+        {
+            if map.contains_key(&key) {
+                let message = format!("duplicate key '{}'", key);
+                return Err(self.syntax(message));
+            }
+            *slot = Some(key);
+            *expect = ObjectExpect::Colon;
+        }
+        Ok(())
+    }
+
+    fn close_container(&mut self) -> Result<(), JsonStreamError> {
+        let depth = self.stack.len() - 1;
+        let value = match self.stack.pop() {
+            Some(Frame::Object { map, .. }) => Value::Object(map),
+            Some(Frame::Array { items, .. }) => Value::Array(items),
+            None => return Err(self.syntax("unbalanced brackets".to_string())),
+        };
+        if let (Some(schema), Value::Object(map)) = (self.schema_at(depth).map(effective), &value) {
+            let missing: Vec<&str> = schema
+                .get("required")
+                .and_then(|r| r.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|k| k.as_str())
+                .filter(|k| !map.contains_key(*k))
+                .collect();
+            if !missing.is_empty() {
+                return Err(self.schema_error(format!(
+                    "missing required properties: {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+        self.complete_value(value)
+    }
+
+    fn complete_value(&mut self, value: Value) -> Result<(), JsonStreamError> {
+        let depth = self.stack.len();
+        if let Some(schema) = self.schema_at(depth) {
+            if let Some(message) = check_complete(schema, &value) {
+                return Err(self.schema_error(message));
+            }
+        }
+        let pointer = self.pointer(depth);
+        let max_items = match depth.checked_sub(1) {
+            Some(parent) => self
+                .schema_at(parent)
+                .map(effective)
+                .and_then(|s| s.get("maxItems"))
+                .and_then(|m| m.as_u64()),
+            None => None,
+        };
+        match self.stack.last_mut() {
+            None => {
+                self.root = Some(value.clone());
+                self.events.push(JsonStreamEvent::Done(value));
+            }
+            Some(Frame::Object { map, key, expect }) => {
+                let key = key.take().unwrap_or_default();
+                map.insert(key.clone(), value.clone());
+                *expect = ObjectExpect::CommaOrEnd;
+                self.events.push(JsonStreamEvent::Field {
+                    pointer,
+                    key,
+                    value,
+                });
+            }
+            Some(Frame::Array { items, expect }) => {
+                let index = items.len();
+                if max_items.is_some_and(|max| index as u64 >= max) {
+                    return Err(JsonStreamError::Schema {
+                        pointer: self.pointer(depth - 1),
+                        message: format!("more than {} items", max_items.unwrap_or(0)),
+                    });
+                }
+                items.push(value.clone());
+                *expect = ArrayExpect::CommaOrEnd;
+                self.events.push(JsonStreamEvent::Item {
+                    pointer,
+                    index,
+                    value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // Schema helpers
+    // ------------------------------------------------------------------
+
+    /// Schema for the value at `depth` (the number of enclosing frames).
+    fn schema_at(&self, depth: usize) -> Option<&Value> {
+        let mut schema = self.schema.as_ref()?;
+        for frame in &self.stack[..depth] {
+            let current = effective(schema);
+            schema = match frame {
+                Frame::Object { key: Some(key), .. } => current
+                    .get("properties")
+                    .and_then(|p| p.get(key))
+                    .or_else(|| {
+                        current
+                            .get("additionalProperties")
+                            .filter(|a| a.is_object())
+                    })?,
+                Frame::Object { key: None, .. } => return None,
+                Frame::Array { .. } => current.get("items").filter(|i| i.is_object())?,
+            };
+        }
+        Some(schema)
+    }
+
+    /// Check a string's progress against the schema's `enum`/`const`.
+    fn check_string_prefix(&self, prefix: &str) -> Result<(), JsonStreamError> {
+        let Some(schema) = self.schema_at(self.stack.len()) else {
+            return Ok(());
+        };
+        let allowed: Vec<&str> = match (schema.get("enum"), schema.get("const")) {
+            (Some(Value::Array(values)), _) => values.iter().filter_map(|v| v.as_str()).collect(),
+            (_, Some(Value::String(c))) => vec![c.as_str()],
+            _ => return Ok(()),
+        };
+        if allowed.iter().any(|a| a.starts_with(prefix)) {
+            return Ok(());
+        }
+        Err(self.schema_error(format!(
+            "'{}' does not match any allowed value ({})",
+            prefix,
+            allowed.join(", ")
+        )))
+    }
+
+    /// JSON pointer of the value at `depth`.
+    fn pointer(&self, depth: usize) -> String {
+        let mut pointer = String::new();
+        for frame in &self.stack[..depth] {
+            pointer.push('/');
+            match frame {
+                Frame::Object { key, .. } => pointer.push_str(
+                    &key.as_deref()
+                        .unwrap_or_default()
+                        .replace('~', "~0")
+                        .replace('/', "~1"),
+                ),
+                Frame::Array { items, .. } => pointer.push_str(&items.len().to_string()),
+            }
+        }
+        pointer
+    }
+
+    fn syntax(&self, message: String) -> JsonStreamError {
+        JsonStreamError::Syntax {
+            offset: self.offset,
+            message,
+        }
+    }
+
+    fn schema_error(&self, message: String) -> JsonStreamError {
+        JsonStreamError::Schema {
+            pointer: self.pointer(self.stack.len()),
+            message,
+        }
+    }
+}
+
+/// Unwrap `anyOf`/`oneOf` when exactly one non-null alternative remains
+/// (the shape optional fields take in generated schemas).
+fn effective(schema: &Value) -> &Value {
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(alternatives)) = schema.get(keyword) {
+            let mut non_null = alternatives
+                .iter()
+                .filter(|a| a.get("type") != Some(&Value::String("null".into())));
+            if let (Some(only), None) = (non_null.next(), non_null.next()) {
+                return only;
+            }
+        }
+    }
+    schema
+}
+
+fn type_allows(schema: &Value, kind: &str) -> bool {
+    if kind == "null" && schema.get("nullable") == Some(&Value::Bool(true)) {
+        return true;
+    }
+    let matches = |t: &str| t == kind || (t == "integer" && kind == "number");
+    match schema.get("type") {
+        Some(Value::String(t)) => matches(t),
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).any(matches),
+        _ => ["anyOf", "oneOf"]
+            .iter()
+            .find_map(|k| schema.get(*k).and_then(|a| a.as_array()))
+            .is_none_or(|alternatives| alternatives.iter().any(|a| type_allows(a, kind))),
+    }
+}
+
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn describe_types(schema: &Value) -> String {
+    let types = schema_types(schema);
+    if types.is_empty() {
+        "one of the allowed types".to_string()
+    } else {
+        types.join(" or ")
+    }
+}
+
+/// Checks that need the whole value: integers, `enum` and `const`.
+fn check_complete(schema: &Value, value: &Value) -> Option<String> {
+    if let Value::Number(n) = value {
+        let types = schema_types(schema);
+        if types.contains(&"integer") && !types.contains(&"number") && n.is_f64() {
+            return Some(format!("expected integer, got {}", n));
+        }
+    }
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            return Some(format!("{} is not one of the allowed values", value));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            return Some(format!("expected {}", expected));
+        }
+    }
+    None
+}
+
+/// Drive `parser` from a stream, reporting progress to `on_event`.
+///
+/// Stops reading as soon as the document diverges from the schema, so the
+/// caller can abort the generation. Providers that only send a final
+/// [`StreamChunk::Done`] are handled by parsing its content.
+pub async fn parse_structured_stream<F>(
+    receiver: &mut dyn StreamReceiver,
+    parser: &mut StreamingJsonParser,
+    mut on_event: F,
+) -> Result<Value, JsonStreamError>
+where
+    F: FnMut(&JsonStreamEvent),
+{
+    while let Some(chunk) = receiver.next().await {
+        let events = match &chunk {
+            StreamChunk::Done { content, .. } => {
+                let rest = content.get(parser.offset()..).unwrap_or_default();
+                let events = parser.push(rest)?;
+                events.iter().for_each(&mut on_event);
+                break;
+            }
+            chunk => parser.push_chunk(chunk)?,
+        };
+        events.iter().for_each(&mut on_event);
+        if parser.is_complete() {
+            break;
+        }
+    }
+    parser.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": {"type": "string"},
+                "status": {"type": "string", "enum": ["draft", "final"]},
+                "tags": {"type": "array", "items": {"type": "string"}, "maxItems": 3},
+                "author": {"$ref": "#/$defs/Author"}
+            },
+            "required": ["title"],
+            "additionalProperties": false,
+            "$defs": {
+                "Author": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}, "age": {"type": "integer"}}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_incremental_parse_and_events() {
+        let mut parser = StreamingJsonParser::new().with_schema(schema());
+        let doc = "```json\n{\"title\": \"Caf\\u00e9 \\ud83d\\ude00\", \"tags\": [\"a\", \"b\"], \
+                   \"author\": {\"name\": \"Ada\", \"age\": 36}, \"status\": \"final\"}\n```";
+        let mut events = Vec::new();
+        let mut snapshot = None;
+        for (i, chunk) in doc.as_bytes().chunks(5).enumerate() {
+            events.extend(parser.push(std::str::from_utf8(chunk).unwrap()).unwrap());
+            if i == 10 {
+                snapshot = parser.partial();
+            }
+        }
+        assert_eq!(snapshot, Some(json!({"title": "Café 😀", "tags": ["a"]})));
+
+        let items: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                JsonStreamEvent::Item { pointer, value, .. } => Some((pointer.as_str(), value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![("/tags/0", &json!("a")), ("/tags/1", &json!("b"))]
+        );
+        assert!(events.contains(&JsonStreamEvent::Field {
+            pointer: "/author/name".into(),
+            key: "name".into(),
+            value: json!("Ada"),
+        }));
+        let done = parser.finish().unwrap();
+        assert_eq!(events.last(), Some(&JsonStreamEvent::Done(done.clone())));
+        assert_eq!(done["author"]["age"], 36);
+    }
+
+    #[test]
+    fn test_early_abort_on_divergence() {
+        let cases = [
+            ("{\"title\": 5", "/title", "expected string, got number"),
+            ("{\"status\": \"dra", "", ""),
+            (
+                "{\"status\": \"pub",
+                "/status",
+                "does not match any allowed value",
+            ),
+            (
+                "{\"title\": \"x\", \"extra\"",
+                "",
+                "unexpected property 'extra'",
+            ),
+            (
+                "{\"author\": {\"age\": 1.5}",
+                "/author/age",
+                "expected integer",
+            ),
+            (
+                "{\"tags\": [\"a\", \"b\", \"c\", \"d\"",
+                "/tags",
+                "more than 3 items",
+            ),
+            ("{\"tags\": []}", "", "missing required properties: title"),
+        ];
+        for (input, pointer, message) in cases {
+            let mut parser = StreamingJsonParser::new().with_schema(schema());
+            match parser.push(input) {
+                Err(JsonStreamError::Schema {
+                    pointer: p,
+                    message: m,
+                }) => {
+                    assert_eq!(p, pointer, "{}", input);
+                    assert!(m.contains(message), "{}: {}", input, m);
+                }
+                Ok(_) => assert!(message.is_empty(), "{} should fail", input),
+                Err(other) => panic!("{}: {:?}", input, other),
+            }
+        }
+
+        let mut parser = StreamingJsonParser::new();
+        assert!(matches!(
+            parser.push("[1, 2,]"),
+            Err(JsonStreamError::Syntax { offset: 6, .. })
+        ));
+        assert_eq!(parser.finish().unwrap_err(), parser.push("]").unwrap_err());
+        assert_eq!(
+            StreamingJsonParser::new()
+                .push("{\"a\": tru")
+                .map(|e| e.len()),
+            Ok(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_structured_stream() {
+        use crate::llms::streaming::ChannelStreamReceiver;
+
+        let (tx, mut rx) = ChannelStreamReceiver::pair(8);
+        for text in ["{\"title\": ", "\"Report\", \"status\": \"draft\"}"] {
+            tx.send(StreamChunk::TextDelta { text: text.into() })
+                .await
+                .unwrap();
+        }
+        drop(tx);
+        let mut parser = StreamingJsonParser::new().with_schema(schema());
+        let mut fields = Vec::new();
+        let value = parse_structured_stream(&mut rx, &mut parser, |e| {
+            if let JsonStreamEvent::Field { key, .. } = e {
+                fields.push(key.clone());
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(value, json!({"title": "Report", "status": "draft"}));
+        assert_eq!(fields, vec!["title", "status"]);
+
+        // Done-only providers: the final content is parsed in one go.
+        let (tx, mut rx) = ChannelStreamReceiver::pair(8);
+        tx.send(StreamChunk::Done {
+            content: "{\"title\": \"T\"}".into(),
+            tool_calls: None,
+            usage: None,
+        })
+        .await
+        .unwrap();
+        let mut parser = StreamingJsonParser::new();
+        let value = parse_structured_stream(&mut rx, &mut parser, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(value["title"], "T");
+    }
+}
@@ -5,17 +5,32 @@
 //! This module provides the LLM infrastructure including:
 //!
 //! - [`base_llm`] - The abstract base trait for all LLM implementations
+//! - [`coalescing`] - Sharing one provider call among identical concurrent requests
 //! - [`hooks`] - Transport-level interceptors for request/response modification
+//! - [`http`] - Proxy and TLS settings of the providers' HTTP clients
+//! - [`images`] - Image content blocks and their provider formats
+//! - [`json_stream`] - Incremental, schema-checked parsing of streamed structured outputs
 //! - [`providers`] - Native SDK provider implementations (OpenAI, Anthropic, etc.)
+//! - [`retry`] - Retry and backoff policy for provider requests
 //! - [`third_party`] - Third-party LLM integrations (LiteLLM bridge)
 
 pub mod base_llm;
+pub mod coalescing;
 pub mod hooks;
+pub mod http;
+pub mod images;
+pub mod json_stream;
 pub mod providers;
+pub mod retry;
 pub mod streaming;
 pub mod third_party;
 
 // Re-exports for convenience
-pub use base_llm::{BaseLLM, BaseLLMState, LLMCallType, LLMMessage, TokenUsage};
+pub use base_llm::{
+    BaseLLM, BaseLLMState, LLMCallType, LLMMessage, LastResponse, TokenUsage, TokenUsageCounter,
+};
 pub use hooks::BaseInterceptor;
+pub use http::{HttpConfig, HttpConfigError};
+pub use json_stream::{JsonStreamError, JsonStreamEvent, StreamingJsonParser};
+pub use retry::{RetryOn, RetryPolicy};
 pub use streaming::{StreamAccumulator, StreamChunk, StreamReceiver, StreamingLLM};
@@ -9,7 +9,7 @@
 //! # Features
 //!
 //! - Anthropic Messages API with real HTTP calls via `reqwest`
-//! - Retry with exponential backoff on 429/5xx
+//! - Retries on 429/5xx as the [`RetryPolicy`](crate::llms::retry::RetryPolicy) allows
 //! - Native tool use (function calling)
 //! - Extended thinking / chain-of-thought (budget_tokens)
 //! - System message extraction from message list
@@ -24,6 +24,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::llms::images::ImageSource;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -120,7 +123,7 @@ pub struct AnthropicCompletion {
 
     /// Request timeout in seconds.
     pub timeout: Option<f64>,
-    /// Maximum number of retries.
+    /// Maximum number of retries, unless `state.retry_policy` is set.
     pub max_retries: u32,
     /// Maximum tokens in response (required for Anthropic).
     pub max_tokens: u32,
@@ -156,7 +159,7 @@ impl AnthropicCompletion {
         let api_key = api_key.or_else(|| std::env::var("ANTHROPIC_API_KEY").ok());
 
         let mut state = BaseLLMState::new(model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.base_url = base_url;
         state.provider = "anthropic".to_string();
 
@@ -175,6 +178,12 @@ impl AnthropicCompletion {
         }
     }
 
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
     /// Get the API base URL.
     pub fn api_base_url(&self) -> String {
         self.state
@@ -284,7 +293,7 @@ impl AnthropicCompletion {
                 // Standard message passthrough
                 formatted.push(serde_json::json!({
                     "role": role,
-                    "content": content,
+                    "content": format_content(content),
                 }));
             }
         }
@@ -555,9 +564,13 @@ impl BaseLLM for AnthropicCompletion {
         );
 
         // Validate API key
-        let api_key = self.state.api_key.as_ref().ok_or_else(|| {
-            "Anthropic API key not set. Set ANTHROPIC_API_KEY environment variable or pass api_key to constructor."
-        })?;
+        let api_key = self
+            .state
+            .resolve_api_key("ANTHROPIC_API_KEY")
+            .await?
+            .ok_or(
+                "Anthropic API key not set. Set ANTHROPIC_API_KEY environment variable or pass api_key to constructor.",
+            )?;
 
         // Build request body
         let tools_slice = tools.as_deref();
@@ -569,131 +582,86 @@ impl BaseLLM for AnthropicCompletion {
 
         // Build HTTP client with timeout
         let timeout_secs = self.timeout.unwrap_or(120.0);
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs_f64(timeout_secs))
-            .build()?;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs_f64(timeout_secs))?;
 
         // Collect beta headers
         let betas = self.beta_headers();
 
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                log::warn!(
-                    "Anthropic API retry attempt {} after {:?}",
-                    attempt,
-                    retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2; // Exponential backoff
-            }
-
-            // Build request with Anthropic-specific headers
-            let mut request = client
-                .post(&endpoint)
-                .header("content-type", "application/json")
-                .header("x-api-key", api_key.as_str())
-                .header("anthropic-version", &self.anthropic_version);
+        // Send with retries as the policy allows (429, 529 and 5xx by
+        // default, after any Retry-After the API asks for)
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("Anthropic", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("anthropic").await {
+                    return Err(fault.into());
+                }
 
-            // Add beta headers if needed
-            if !betas.is_empty() {
-                request = request.header("anthropic-beta", betas.join(","));
-            }
+                // Build request with Anthropic-specific headers
+                let mut request = client
+                    .post(&endpoint)
+                    .header("content-type", "application/json")
+                    .header("x-api-key", api_key.expose_secret())
+                    .header("anthropic-version", &self.anthropic_version);
 
-            // Send request
-            let response = match request.json(&body).send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+                // Add beta headers if needed
+                if !betas.is_empty() {
+                    request = request.header("anthropic-beta", betas.join(","));
                 }
-            };
-
-            let status = response.status();
-
-            // Handle rate limiting (429)
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                // Check for Retry-After header
-                if let Some(retry_after) = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|v| v.parse::<u64>().ok())
-                {
-                    retry_delay = std::time::Duration::from_secs(retry_after);
-                }
-                last_error = Some("Rate limited by Anthropic API (429)".into());
-                continue;
-            }
 
-            // Handle overloaded (529)
-            if status.as_u16() == 529 {
-                last_error = Some("Anthropic API overloaded (529)".into());
-                continue;
-            }
+                Ok(request.json(&body).send().await?)
+            })
+            .await?;
 
-            // Handle server errors (5xx)
-            if status.is_server_error() {
-                last_error = Some(format!("Anthropic API server error: {}", status).into());
-                continue;
-            }
+        let status = response.status();
 
-            // Parse response body
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
-                }
-            };
+        // Parse response body
+        let response_text = response.text().await?;
 
-            // Handle client errors (4xx) — don't retry
-            if status.is_client_error() {
-                return Err(format!("Anthropic API error ({}): {}", status, response_text).into());
-            }
+        // Handle errors that were not retried
+        if !status.is_success() {
+            return Err(format!("Anthropic API error ({}): {}", status, response_text).into());
+        }
 
-            // Parse JSON response
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse Anthropic response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
-
-            // Check for API-level error in the response body
-            if let Some(err_type) = response_json.get("type").and_then(|t| t.as_str()) {
-                if err_type == "error" {
-                    let err_msg = response_json
-                        .get("error")
-                        .and_then(|e| e.get("message"))
-                        .and_then(|m| m.as_str())
-                        .unwrap_or("Unknown Anthropic API error");
-                    return Err(format!("Anthropic API error: {}", err_msg).into());
-                }
+        // Parse JSON response
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse Anthropic response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
 
-            // Log token usage
-            let usage = Self::extract_token_usage(&response_json);
-            if !usage.is_empty() {
-                log::debug!("Anthropic usage tracked: {:?}", usage);
+        // Check for API-level error in the response body
+        if let Some(err_type) = response_json.get("type").and_then(|t| t.as_str()) {
+            if err_type == "error" {
+                let err_msg = response_json
+                    .get("error")
+                    .and_then(|e| e.get("message"))
+                    .and_then(|m| m.as_str())
+                    .unwrap_or("Unknown Anthropic API error");
+                return Err(format!("Anthropic API error: {}", err_msg).into());
             }
+        }
 
-            // Parse the response content
-            let result = self.parse_response(&response_json)?;
-
-            return Ok(result);
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("Anthropic usage tracked: {:?}", usage);
         }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
+
+        // Parse the response content
+        let result = self.parse_response(&response_json)?;
 
-        // All retries exhausted
-        Err(last_error.unwrap_or_else(|| "Anthropic API call failed after all retries".into()))
+        Ok(result)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
@@ -703,12 +671,42 @@ impl BaseLLM for AnthropicCompletion {
     fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
         self.state.track_token_usage_internal(usage_data);
     }
+
+    fn request_identity(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Option<Value> {
+        Some(serde_json::json!({
+            "endpoint": format!("{}/v1/messages", self.api_base_url()),
+            "betas": self.beta_headers(),
+            "credential": self.state.credential_hash("ANTHROPIC_API_KEY"),
+            "body": self.build_request_body(messages, tools),
+        }))
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
 
+/// `content` with its `image_url` blocks converted to Anthropic image
+/// blocks.
+fn format_content(content: Value) -> Value {
+    let Value::Array(blocks) = content else {
+        return content;
+    };
+    let blocks = blocks.into_iter().map(|block| {
+        let image = match ImageSource::from_block(&block) {
+            Some(ImageSource::Url(url)) => serde_json::json!({"type": "url", "url": url}),
+            Some(ImageSource::Base64 { media_type, data }) => serde_json::json!({
+                "type": "base64",
+                "media_type": media_type,
+                "data": data,
+            }),
+            None => return block,
+        };
+        serde_json::json!({"type": "image", "source": image})
+    });
+    Value::Array(blocks.collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -778,6 +776,31 @@ mod tests {
         assert_eq!(formatted[0]["role"], "user");
     }
 
+    #[test]
+    fn test_image_blocks_are_converted() {
+        let provider = AnthropicCompletion::new("claude-opus-4-5-20251101", None, None);
+        let message = HashMap::from([
+            ("role".to_string(), Value::String("user".to_string())),
+            (
+                "content".to_string(),
+                serde_json::json!([
+                    {"type": "text", "text": "What is this?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBO"}},
+                    {"type": "image_url", "image_url": {"url": "https://example.com/a.jpg"}},
+                ]),
+            ),
+        ]);
+
+        let (_, formatted) = provider.extract_system_and_messages(&[message]);
+        let content = &formatted[0]["content"];
+        assert_eq!(content[0]["text"], "What is this?");
+        assert_eq!(
+            content[1]["source"],
+            serde_json::json!({"type": "base64", "media_type": "image/png", "data": "iVBO"})
+        );
+        assert_eq!(content[2]["source"]["url"], "https://example.com/a.jpg");
+    }
+
     #[test]
     fn test_extract_system_multiple() {
         let provider = AnthropicCompletion::new("claude-opus-4-5-20251101", None, None);
@@ -27,6 +27,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::llms::providers::regions::RegionSelector;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -64,7 +67,7 @@ pub struct AzureCompletion {
     pub api_version: Option<String>,
     /// Request timeout in seconds.
     pub timeout: Option<f64>,
-    /// Maximum number of retries.
+    /// Maximum number of retries, unless `state.retry_policy` is set.
     pub max_retries: u32,
     /// Nucleus sampling parameter.
     pub top_p: Option<f64>,
@@ -78,6 +81,10 @@ pub struct AzureCompletion {
     pub stream: bool,
     /// Response format for structured output.
     pub response_format: Option<Value>,
+
+    /// Optional multi-region selector; overrides `endpoint` when set.
+    #[serde(skip)]
+    pub region_selector: Option<RegionSelector>,
 }
 
 impl AzureCompletion {
@@ -98,7 +105,7 @@ impl AzureCompletion {
         let api_version = std::env::var("AZURE_API_VERSION").ok();
 
         let mut state = BaseLLMState::new(model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.base_url = endpoint.clone();
         state.provider = "azure".to_string();
 
@@ -114,14 +121,32 @@ impl AzureCompletion {
             max_tokens: None,
             stream: false,
             response_format: None,
+            region_selector: None,
         }
     }
 
+    /// Route calls through a multi-region selector of Azure resources.
+    pub fn with_region_selector(mut self, selector: RegionSelector) -> Self {
+        self.region_selector = Some(selector);
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
     /// Get the full API URL for chat completions.
     pub fn api_url(&self) -> String {
-        let ep = self
-            .endpoint
+        let selected = self
+            .region_selector
+            .as_ref()
+            .and_then(|s| s.select())
+            .map(|e| e.url);
+        let ep = selected
             .as_deref()
+            .or(self.endpoint.as_deref())
             .or(self.state.base_url.as_deref())
             .unwrap_or("https://YOUR_RESOURCE.openai.azure.com");
         let version = self.api_version.as_deref().unwrap_or("2024-02-01");
@@ -305,9 +330,9 @@ impl BaseLLM for AzureCompletion {
 
         let api_key = self
             .state
-            .api_key
-            .as_ref()
-            .ok_or_else(|| "Azure API key not set. Set AZURE_API_KEY environment variable.")?;
+            .resolve_api_key("AZURE_API_KEY")
+            .await?
+            .ok_or("Azure API key not set. Set AZURE_API_KEY environment variable.")?;
 
         let tools_slice = tools.as_deref();
         let body = self.build_request_body(&messages, tools_slice);
@@ -315,95 +340,66 @@ impl BaseLLM for AzureCompletion {
         let url = self.api_url();
 
         let timeout_secs = self.timeout.unwrap_or(120.0) as u64;
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_secs))
-            .build()?;
-
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                log::warn!(
-                    "Azure API retry attempt {} after {:?}",
-                    attempt,
-                    retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2;
-            }
-
-            let response = match client
-                .post(&url)
-                .header("api-key", api_key.as_str())
-                .header("content-type", "application/json")
-                .json(&body)
-                .send()
-                .await
-            {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
-                }
-            };
-
-            let status = response.status();
-
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some("Rate limited by Azure API (429)".into());
-                continue;
-            }
-
-            if status.is_server_error() {
-                last_error = Some(format!("Azure API server error: {}", status).into());
-                continue;
-            }
-
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs(timeout_secs))?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("Azure", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("azure").await {
+                    return Err(fault.into());
                 }
-            };
 
-            if status.is_client_error() {
-                return Err(format!("Azure API error ({}): {}", status, response_text).into());
-            }
-
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse Azure response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
-
-            // Check for API error
-            if let Some(error) = response_json.get("error") {
-                let msg = error
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("Unknown Azure API error");
-                return Err(format!("Azure API error: {}", msg).into());
-            }
+                Ok(client
+                    .post(&url)
+                    .header("api-key", api_key.expose_secret())
+                    .header("content-type", "application/json")
+                    .json(&body)
+                    .send()
+                    .await?)
+            })
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(format!("Azure API error ({}): {}", status, response_text).into());
+        }
 
-            // Extract token usage
-            let usage = Self::extract_token_usage(&response_json);
-            if !usage.is_empty() {
-                log::debug!("Azure usage: {:?}", usage);
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse Azure response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
+
+        // Check for API error
+        if let Some(error) = response_json.get("error") {
+            let msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown Azure API error");
+            return Err(format!("Azure API error: {}", msg).into());
+        }
 
-            return self.parse_response(&response_json);
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("Azure usage: {:?}", usage);
         }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
 
-        Err(last_error.unwrap_or_else(|| "Azure API call failed after all retries".into()))
+        self.parse_response(&response_json)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
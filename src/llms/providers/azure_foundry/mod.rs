@@ -0,0 +1,517 @@
+//! Azure AI Foundry completion provider.
+//!
+//! Calls models deployed in Azure AI Foundry: serverless API endpoints
+//! (`https://<name>.<region>.models.ai.azure.com`) that serve one model,
+//! and the model inference endpoint of an AI services resource
+//! (`https://<resource>.services.ai.azure.com`) that serves every model
+//! deployed to it. Both speak the Azure AI Model Inference chat
+//! completions API.
+//!
+//! # Authentication
+//!
+//! - **Serverless endpoints**: the endpoint key as a Bearer token
+//!   (`AZURE_AI_API_KEY`).
+//! - **AI services resources**: the resource key in the `api-key` header.
+//! - **Microsoft Entra ID**: an access token for
+//!   `https://cognitiveservices.azure.com/.default`, see
+//!   [`AzureFoundryCompletion::with_entra_token`]; it takes precedence over
+//!   keys.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::security::secrets::SecretString;
+use crate::types::usage_metrics::UsageMetrics;
+
+/// API version of the model inference endpoint of AI services resources.
+pub const DEFAULT_API_VERSION: &str = "2024-05-01-preview";
+
+// ---------------------------------------------------------------------------
+// AzureFoundryCompletion provider
+// ---------------------------------------------------------------------------
+
+/// Azure AI Foundry completion implementation.
+///
+/// # Example
+///
+/// ```ignore
+/// let provider = AzureFoundryCompletion::new(
+///     "Meta-Llama-3.1-70B-Instruct",
+///     None,   // api_key from AZURE_AI_API_KEY env var
+///     None,   // endpoint from AZURE_AI_ENDPOINT env var
+/// );
+/// let messages = vec![/* ... */];
+/// let response = provider.call(messages, None, None)?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AzureFoundryCompletion {
+    /// Shared base LLM state.
+    #[serde(flatten)]
+    pub state: BaseLLMState,
+
+    /// Serverless or AI services endpoint URL.
+    pub endpoint: Option<String>,
+    /// API version (AI services resources only).
+    pub api_version: Option<String>,
+    /// Microsoft Entra ID access token, used instead of the key.
+    #[serde(skip_serializing)]
+    pub entra_token: Option<SecretString>,
+    /// How the endpoint treats parameters it does not know: `pass-through`,
+    /// `drop` or `error` (the service default).
+    pub extra_parameters: Option<String>,
+
+    /// Request timeout in seconds.
+    pub timeout: Option<f64>,
+    /// Maximum number of retries, unless `state.retry_policy` is set.
+    pub max_retries: u32,
+    /// Nucleus sampling parameter.
+    pub top_p: Option<f64>,
+    /// Frequency penalty (-2 to 2).
+    pub frequency_penalty: Option<f64>,
+    /// Presence penalty (-2 to 2).
+    pub presence_penalty: Option<f64>,
+    /// Maximum tokens in response.
+    pub max_tokens: Option<u32>,
+    /// Response format for structured output.
+    pub response_format: Option<Value>,
+}
+
+impl AzureFoundryCompletion {
+    /// Create a new Azure AI Foundry completion provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - Model or deployment name.
+    /// * `api_key` - Optional key (defaults to AZURE_AI_API_KEY env var).
+    /// * `endpoint` - Optional endpoint URL (defaults to AZURE_AI_ENDPOINT env var).
+    pub fn new(
+        model: impl Into<String>,
+        api_key: Option<String>,
+        endpoint: Option<String>,
+    ) -> Self {
+        let api_key = api_key.or_else(|| std::env::var("AZURE_AI_API_KEY").ok());
+        let endpoint = endpoint.or_else(|| std::env::var("AZURE_AI_ENDPOINT").ok());
+
+        let mut state = BaseLLMState::new(model);
+        state.api_key = api_key.map(SecretString::from);
+        state.base_url = endpoint.clone();
+        state.provider = "azure_foundry".to_string();
+
+        Self {
+            state,
+            endpoint,
+            api_version: None,
+            entra_token: None,
+            extra_parameters: None,
+            timeout: None,
+            max_retries: 2,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            max_tokens: None,
+            response_format: None,
+        }
+    }
+
+    /// Authenticate with a Microsoft Entra ID access token instead of a key.
+    pub fn with_entra_token(mut self, token: impl Into<String>) -> Self {
+        self.entra_token = Some(SecretString::new(token));
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
+    fn base_endpoint(&self) -> &str {
+        self.endpoint
+            .as_deref()
+            .or(self.state.base_url.as_deref())
+            .unwrap_or("https://YOUR_ENDPOINT.models.ai.azure.com")
+            .trim_end_matches('/')
+    }
+
+    /// Whether the endpoint is an AI services resource serving several
+    /// models (rather than a single-model serverless endpoint).
+    pub fn is_services_endpoint(&self) -> bool {
+        self.base_endpoint().contains(".services.ai.azure.com")
+    }
+
+    /// Get the full API URL for chat completions.
+    pub fn api_url(&self) -> String {
+        let base = self.base_endpoint();
+        if self.is_services_endpoint() {
+            let base = base.trim_end_matches("/models");
+            let version = self.api_version.as_deref().unwrap_or(DEFAULT_API_VERSION);
+            format!("{}/models/chat/completions?api-version={}", base, version)
+        } else {
+            format!("{}/chat/completions", base)
+        }
+    }
+
+    /// Build the chat completions request body.
+    fn build_request_body(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Value {
+        let mut body = serde_json::json!({
+            "messages": messages,
+        });
+
+        // Serverless endpoints serve a single model
+        if self.is_services_endpoint() {
+            body["model"] = serde_json::json!(self.state.model);
+        }
+        if let Some(temp) = self.state.temperature {
+            body["temperature"] = serde_json::json!(temp);
+        }
+        if let Some(max) = self.max_tokens {
+            body["max_tokens"] = serde_json::json!(max);
+        }
+        if let Some(top_p) = self.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if let Some(fp) = self.frequency_penalty {
+            body["frequency_penalty"] = serde_json::json!(fp);
+        }
+        if let Some(pp) = self.presence_penalty {
+            body["presence_penalty"] = serde_json::json!(pp);
+        }
+        if !self.state.stop.is_empty() {
+            body["stop"] = serde_json::json!(self.state.stop);
+        }
+
+        if let Some(tools) = tools {
+            if !tools.is_empty() {
+                body["tools"] = Value::Array(tools.to_vec());
+            }
+        }
+
+        if let Some(ref fmt) = self.response_format {
+            body["response_format"] = fmt.clone();
+        }
+
+        body
+    }
+
+    /// Authentication header for `key` (or the Entra ID token).
+    fn auth_header(&self, key: Option<&SecretString>) -> Option<(&'static str, String)> {
+        if let Some(ref token) = self.entra_token {
+            return Some(("authorization", format!("Bearer {}", token.expose_secret())));
+        }
+        let key = key?.expose_secret();
+        if self.is_services_endpoint() {
+            Some(("api-key", key.to_string()))
+        } else {
+            Some(("authorization", format!("Bearer {}", key)))
+        }
+    }
+
+    /// Parse a chat completions response.
+    fn parse_response(
+        &self,
+        response: &Value,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let choices = response
+            .get("choices")
+            .and_then(|c| c.as_array())
+            .ok_or("No choices in Azure AI Foundry response")?;
+
+        if choices.is_empty() {
+            return Err("Empty choices array in Azure AI Foundry response".into());
+        }
+
+        let message = choices[0]
+            .get("message")
+            .ok_or("No message in Azure AI Foundry response choice")?;
+
+        // Check for tool calls
+        if let Some(tool_calls) = message.get("tool_calls") {
+            if tool_calls.as_array().is_some_and(|a| !a.is_empty()) {
+                return Ok(serde_json::json!({
+                    "role": "assistant",
+                    "content": message.get("content").cloned().unwrap_or(Value::Null),
+                    "tool_calls": tool_calls,
+                }));
+            }
+        }
+
+        // Plain text response
+        let content = message
+            .get("content")
+            .and_then(|c| c.as_str())
+            .unwrap_or("");
+        let final_content = self.state.apply_stop_words(content);
+        Ok(Value::String(final_content))
+    }
+
+    /// Extract token usage from a chat completions response.
+    fn extract_token_usage(response: &Value) -> HashMap<String, Value> {
+        let mut usage = HashMap::new();
+        if let Some(usage_obj) = response.get("usage") {
+            let prompt = usage_obj
+                .get("prompt_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let completion = usage_obj
+                .get("completion_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let total = usage_obj
+                .get("total_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(prompt + completion);
+
+            usage.insert("prompt_tokens".to_string(), serde_json::json!(prompt));
+            usage.insert(
+                "completion_tokens".to_string(),
+                serde_json::json!(completion),
+            );
+            usage.insert("total_tokens".to_string(), serde_json::json!(total));
+        }
+        usage
+    }
+}
+
+#[async_trait]
+impl BaseLLM for AzureFoundryCompletion {
+    fn model(&self) -> &str {
+        &self.state.model
+    }
+
+    fn temperature(&self) -> Option<f64> {
+        self.state.temperature
+    }
+
+    fn stop(&self) -> &[String] {
+        &self.state.stop
+    }
+
+    fn set_stop(&mut self, stop: Vec<String>) {
+        self.state.stop = stop;
+    }
+
+    fn provider(&self) -> &str {
+        "azure_foundry"
+    }
+
+    fn supports_function_calling(&self) -> bool {
+        true
+    }
+
+    fn supports_stop_words(&self) -> bool {
+        self.state.has_stop_words()
+    }
+
+    fn call(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        log::debug!(
+            "AzureFoundryCompletion.call: model={}, endpoint={:?}, messages={}, tools={:?}",
+            self.state.model,
+            self.endpoint,
+            messages.len(),
+            tools.as_ref().map(|t| t.len()),
+        );
+
+        let rt = tokio::runtime::Runtime::new()?;
+        rt.block_on(self.acall(messages, tools, available_functions))
+    }
+
+    async fn acall(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        _available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        log::debug!(
+            "AzureFoundryCompletion.acall: model={}, messages={}",
+            self.state.model,
+            messages.len(),
+        );
+
+        let api_key = match self.entra_token {
+            Some(_) => None,
+            None => self.state.resolve_api_key("AZURE_AI_API_KEY").await?,
+        };
+        let (auth_name, auth_value) = self.auth_header(api_key.as_ref()).ok_or(
+            "Azure AI Foundry key not set. Set AZURE_AI_API_KEY or use an Entra ID token.",
+        )?;
+
+        let tools_slice = tools.as_deref();
+        let body = self.build_request_body(&messages, tools_slice);
+
+        let url = self.api_url();
+
+        let timeout_secs = self.timeout.unwrap_or(120.0) as u64;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs(timeout_secs))?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("Azure AI Foundry", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("azure_foundry").await {
+                    return Err(fault.into());
+                }
+
+                let mut request = client
+                    .post(&url)
+                    .header(auth_name, auth_value.as_str())
+                    .header("content-type", "application/json");
+                if let Some(ref extra) = self.extra_parameters {
+                    request = request.header("extra-parameters", extra.as_str());
+                }
+
+                Ok(request.json(&body).send().await?)
+            })
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(
+                format!("Azure AI Foundry API error ({}): {}", status, response_text).into(),
+            );
+        }
+
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse Azure AI Foundry response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
+            }
+        };
+
+        // Check for API error
+        if let Some(error) = response_json.get("error") {
+            let msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown Azure AI Foundry API error");
+            return Err(format!("Azure AI Foundry API error: {}", msg).into());
+        }
+
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("Azure AI Foundry usage: {:?}", usage);
+        }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
+
+        self.parse_response(&response_json)
+    }
+
+    fn get_token_usage_summary(&self) -> UsageMetrics {
+        self.state.get_token_usage_summary()
+    }
+
+    fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
+        self.state.track_token_usage_internal(usage_data);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(pairs: &[(&str, Value)]) -> LLMMessage {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_serverless_endpoint() {
+        let provider = AzureFoundryCompletion::new(
+            "Meta-Llama-3.1-70B-Instruct",
+            Some("key".to_string()),
+            Some("https://llama-70b.eastus2.models.ai.azure.com/".to_string()),
+        );
+        assert_eq!(provider.provider(), "azure_foundry");
+        assert!(!provider.is_services_endpoint());
+        assert_eq!(
+            provider.api_url(),
+            "https://llama-70b.eastus2.models.ai.azure.com/chat/completions"
+        );
+        let key = provider.state.api_key.clone();
+        assert_eq!(
+            provider.auth_header(key.as_ref()),
+            Some(("authorization", "Bearer key".to_string()))
+        );
+
+        let messages = vec![msg(&[
+            ("role", serde_json::json!("user")),
+            ("content", serde_json::json!("Hello")),
+        ])];
+        assert!(provider
+            .build_request_body(&messages, None)
+            .get("model")
+            .is_none());
+    }
+
+    #[test]
+    fn test_services_endpoint() {
+        let provider = AzureFoundryCompletion::new(
+            "Mistral-large-2407",
+            Some("key".to_string()),
+            Some("https://my-hub.services.ai.azure.com/models".to_string()),
+        );
+        assert!(provider.is_services_endpoint());
+        assert_eq!(
+            provider.api_url(),
+            "https://my-hub.services.ai.azure.com/models/chat/completions?api-version=2024-05-01-preview"
+        );
+        let key = provider.state.api_key.clone();
+        assert_eq!(
+            provider.auth_header(key.as_ref()),
+            Some(("api-key", "key".to_string()))
+        );
+        let body = provider.build_request_body(&[], None);
+        assert_eq!(body["model"], "Mistral-large-2407");
+
+        let provider = provider.with_entra_token("eyJ0");
+        assert_eq!(
+            provider.auth_header(key.as_ref()),
+            Some(("authorization", "Bearer eyJ0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_response_and_usage() {
+        let provider = AzureFoundryCompletion::new("phi-4", None, None);
+        let response = serde_json::json!({
+            "choices": [{
+                "message": {"role": "assistant", "content": "Hello there!"},
+                "finish_reason": "stop"
+            }],
+            "usage": {"prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15}
+        });
+
+        let result = provider.parse_response(&response).unwrap();
+        assert_eq!(result.as_str().unwrap(), "Hello there!");
+        let usage = AzureFoundryCompletion::extract_token_usage(&response);
+        assert_eq!(usage["total_tokens"], 15);
+    }
+}
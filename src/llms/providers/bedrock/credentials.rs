@@ -0,0 +1,1009 @@
+//! AWS credential resolution for Bedrock.
+//!
+//! Mirrors the default credential chain of the AWS SDKs, so the provider
+//! runs on EC2, ECS, EKS or with a configured profile without exporting
+//! keys. [`CredentialSource::default_chain`] tries, in order:
+//!
+//! 1. `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`;
+//! 2. web identity (`AWS_ROLE_ARN` with `AWS_WEB_IDENTITY_TOKEN_FILE`, as
+//!    set up by EKS service accounts);
+//! 3. the profile in `~/.aws/credentials` and `~/.aws/config` (a
+//!    `role_arn` with `source_profile` or `credential_source` is assumed
+//!    through STS);
+//! 4. ECS container credentials (`AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`
+//!    or `AWS_CONTAINER_CREDENTIALS_FULL_URI`);
+//! 5. EC2 instance metadata (IMDSv2), unless `AWS_EC2_METADATA_DISABLED`.
+//!
+//! An [`AwsCredentialsProvider`] caches what it resolved and resolves again
+//! shortly before temporary credentials expire.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::future::{BoxFuture, FutureExt};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::sigv4;
+use crate::security::secrets::SecretString;
+
+/// How long before expiry cached credentials are refreshed.
+pub const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Default EC2 instance metadata endpoint.
+pub const IMDS_ENDPOINT: &str = "http://169.254.169.254";
+
+/// Host serving ECS container credentials for a relative URI.
+pub const ECS_CREDENTIALS_HOST: &str = "http://169.254.170.2";
+
+/// Longest `source_profile` chain followed before giving up.
+const MAX_PROFILE_DEPTH: usize = 8;
+
+/// A set of AWS credentials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AwsCredentials {
+    /// Access key ID.
+    pub access_key_id: String,
+    /// Secret access key.
+    pub secret_access_key: SecretString,
+    /// Session token of temporary credentials.
+    pub session_token: Option<SecretString>,
+    /// When temporary credentials expire.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl AwsCredentials {
+    /// Long-term credentials.
+    pub fn new(access_key_id: impl Into<String>, secret_access_key: impl Into<String>) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: SecretString::new(secret_access_key),
+            session_token: None,
+            expires_at: None,
+        }
+    }
+
+    /// Add a session token.
+    pub fn with_session_token(mut self, token: impl Into<String>) -> Self {
+        self.session_token = Some(SecretString::new(token));
+        self
+    }
+
+    /// Whether the credentials expire within `margin`.
+    pub fn expires_within(&self, margin: Duration) -> bool {
+        let margin = chrono::Duration::from_std(margin).unwrap_or(chrono::Duration::zero());
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= Utc::now() + margin)
+    }
+
+    /// Credentials from the `AWS_*` environment variables.
+    pub fn from_env() -> Option<Self> {
+        let credentials = Self::new(env("AWS_ACCESS_KEY_ID")?, env("AWS_SECRET_ACCESS_KEY")?);
+        Some(match env("AWS_SESSION_TOKEN") {
+            Some(token) => credentials.with_session_token(token),
+            None => credentials,
+        })
+    }
+
+    /// Credentials in the JSON format of the container and instance
+    /// metadata endpoints.
+    fn from_json(value: &Value, service: &'static str) -> Result<Self, CredentialsError> {
+        let field = |name: &str| value.get(name).and_then(Value::as_str);
+        let (Some(access_key_id), Some(secret)) = (field("AccessKeyId"), field("SecretAccessKey"))
+        else {
+            return Err(CredentialsError::Response {
+                service,
+                message: "missing AccessKeyId or SecretAccessKey".to_string(),
+            });
+        };
+        let mut credentials = Self::new(access_key_id, secret);
+        credentials.session_token = field("Token").map(SecretString::from);
+        credentials.expires_at = field("Expiration").and_then(parse_time);
+        Ok(credentials)
+    }
+}
+
+/// Errors resolving AWS credentials.
+#[derive(Debug, Error)]
+pub enum CredentialsError {
+    /// No source in the chain had credentials.
+    #[error("No AWS credentials found (tried {0})")]
+    NotFound(String),
+    /// A profile is missing or incomplete.
+    #[error("AWS profile '{profile}': {message}")]
+    Profile { profile: String, message: String },
+    /// A credentials, config or token file could not be read.
+    #[error("Failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A credentials endpoint could not be reached.
+    #[error("AWS credentials request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    /// A credentials endpoint returned an error.
+    #[error("{service} returned {status}: {message}")]
+    Service {
+        service: &'static str,
+        status: u16,
+        message: String,
+    },
+    /// A credentials endpoint returned something unexpected.
+    #[error("Invalid response from {service}: {message}")]
+    Response {
+        service: &'static str,
+        message: String,
+    },
+}
+
+/// Where credentials come from.
+#[derive(Debug, Clone)]
+pub enum CredentialSource {
+    /// Fixed credentials.
+    Static(AwsCredentials),
+    /// The `AWS_*` environment variables.
+    Environment,
+    /// A named profile of the shared credentials and config files.
+    Profile(String),
+    /// STS `AssumeRoleWithWebIdentity` with the token in `token_file`.
+    WebIdentity {
+        role_arn: String,
+        token_file: PathBuf,
+        session_name: Option<String>,
+    },
+    /// The ECS container credentials endpoint at `uri`, with the
+    /// `Authorization` header from `authorization` or read from
+    /// `authorization_file`.
+    Container {
+        uri: String,
+        authorization: Option<String>,
+        authorization_file: Option<PathBuf>,
+    },
+    /// The EC2 instance metadata service at `endpoint`.
+    InstanceMetadata { endpoint: String },
+    /// STS `AssumeRole`, signed with the credentials of another source.
+    AssumeRole(Box<AssumeRole>),
+    /// The first source that has credentials.
+    Chain(Vec<CredentialSource>),
+}
+
+impl CredentialSource {
+    /// The AWS SDK default chain, with `profile` (or `AWS_PROFILE`, or
+    /// `default`) as the profile.
+    pub fn default_chain(profile: Option<&str>) -> Self {
+        let profile = profile
+            .map(str::to_string)
+            .or_else(|| env("AWS_PROFILE"))
+            .unwrap_or_else(|| "default".to_string());
+        let mut chain = vec![Self::Environment];
+        if let Some(source) = Self::web_identity_from_env() {
+            chain.push(source);
+        }
+        chain.push(Self::Profile(profile));
+        if let Some(source) = Self::container_from_env() {
+            chain.push(source);
+        }
+        if !env("AWS_EC2_METADATA_DISABLED").is_some_and(|v| v.eq_ignore_ascii_case("true")) {
+            chain.push(Self::instance_metadata_from_env());
+        }
+        Self::Chain(chain)
+    }
+
+    /// Web identity from `AWS_ROLE_ARN` and `AWS_WEB_IDENTITY_TOKEN_FILE`.
+    pub fn web_identity_from_env() -> Option<Self> {
+        Some(Self::WebIdentity {
+            role_arn: env("AWS_ROLE_ARN")?,
+            token_file: env("AWS_WEB_IDENTITY_TOKEN_FILE")?.into(),
+            session_name: env("AWS_ROLE_SESSION_NAME"),
+        })
+    }
+
+    /// Container credentials from the `AWS_CONTAINER_*` variables.
+    pub fn container_from_env() -> Option<Self> {
+        let uri = match env("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI") {
+            Some(path) => format!("{}{}", ECS_CREDENTIALS_HOST, path),
+            None => env("AWS_CONTAINER_CREDENTIALS_FULL_URI")?,
+        };
+        Some(Self::Container {
+            uri,
+            authorization: env("AWS_CONTAINER_AUTHORIZATION_TOKEN"),
+            authorization_file: env("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE").map(PathBuf::from),
+        })
+    }
+
+    /// Instance metadata at `AWS_EC2_METADATA_SERVICE_ENDPOINT` or the
+    /// default endpoint.
+    pub fn instance_metadata_from_env() -> Self {
+        Self::InstanceMetadata {
+            endpoint: env("AWS_EC2_METADATA_SERVICE_ENDPOINT")
+                .unwrap_or_else(|| IMDS_ENDPOINT.to_string()),
+        }
+    }
+
+    /// Short description for error messages.
+    fn describe(&self) -> String {
+        match self {
+            Self::Static(_) => "static credentials".to_string(),
+            Self::Environment => "environment".to_string(),
+            Self::Profile(name) => format!("profile '{}'", name),
+            Self::WebIdentity { .. } => "web identity".to_string(),
+            Self::Container { .. } => "container credentials".to_string(),
+            Self::InstanceMetadata { .. } => "instance metadata".to_string(),
+            Self::AssumeRole(role) => format!("assume role {}", role.role_arn),
+            Self::Chain(sources) => sources
+                .iter()
+                .map(Self::describe)
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+}
+
+/// STS `AssumeRole` parameters.
+#[derive(Debug, Clone)]
+pub struct AssumeRole {
+    /// ARN of the role to assume.
+    pub role_arn: String,
+    /// Session name (defaults to `crewai-<timestamp>`).
+    pub session_name: Option<String>,
+    /// External ID required by the role's trust policy.
+    pub external_id: Option<String>,
+    /// Session duration (defaults to one hour).
+    pub duration: Option<Duration>,
+    /// Credentials the `AssumeRole` call is signed with.
+    pub source: CredentialSource,
+}
+
+impl AssumeRole {
+    /// Assume `role_arn` with the credentials of `source`.
+    pub fn new(role_arn: impl Into<String>, source: CredentialSource) -> Self {
+        Self {
+            role_arn: role_arn.into(),
+            session_name: None,
+            external_id: None,
+            duration: None,
+            source,
+        }
+    }
+
+    /// Set the session name.
+    pub fn with_session_name(mut self, name: impl Into<String>) -> Self {
+        self.session_name = Some(name.into());
+        self
+    }
+
+    /// Set the external ID.
+    pub fn with_external_id(mut self, external_id: impl Into<String>) -> Self {
+        self.external_id = Some(external_id.into());
+        self
+    }
+
+    /// Set the session duration.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+/// Resolves credentials from a [`CredentialSource`] and caches them until
+/// shortly before they expire.
+///
+/// ```no_run
+/// use crewai::llms::providers::bedrock::credentials::{
+///     AssumeRole, AwsCredentialsProvider, CredentialSource,
+/// };
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let role = AssumeRole::new(
+///     "arn:aws:iam::123456789012:role/bedrock",
+///     CredentialSource::default_chain(None),
+/// );
+/// let provider =
+///     AwsCredentialsProvider::new(CredentialSource::AssumeRole(Box::new(role)), "us-east-1");
+/// let credentials = provider.credentials().await?;
+/// # drop(credentials);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AwsCredentialsProvider {
+    source: CredentialSource,
+    region: String,
+    sts_endpoint: Option<String>,
+    credentials_file: Option<PathBuf>,
+    config_file: Option<PathBuf>,
+    cached: tokio::sync::Mutex<Option<AwsCredentials>>,
+}
+
+impl AwsCredentialsProvider {
+    /// A provider for `source`; STS calls go to `region`.
+    pub fn new(source: CredentialSource, region: impl Into<String>) -> Self {
+        Self {
+            source,
+            region: region.into(),
+            sts_endpoint: None,
+            credentials_file: None,
+            config_file: None,
+            cached: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// A provider for the default chain with `profile`.
+    pub fn default_chain(profile: Option<&str>, region: impl Into<String>) -> Self {
+        Self::new(CredentialSource::default_chain(profile), region)
+    }
+
+    /// Send STS calls to `endpoint` instead of `https://sts.<region>.amazonaws.com`.
+    pub fn with_sts_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.sts_endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Read profiles from these files instead of `AWS_SHARED_CREDENTIALS_FILE`
+    /// / `AWS_CONFIG_FILE` or `~/.aws`.
+    pub fn with_profile_files(
+        mut self,
+        credentials: impl Into<PathBuf>,
+        config: impl Into<PathBuf>,
+    ) -> Self {
+        self.credentials_file = Some(credentials.into());
+        self.config_file = Some(config.into());
+        self
+    }
+
+    /// The source credentials are resolved from.
+    pub fn source(&self) -> &CredentialSource {
+        &self.source
+    }
+
+    /// Current credentials, resolved again when the cached ones are about to
+    /// expire.
+    pub async fn credentials(&self) -> Result<AwsCredentials, CredentialsError> {
+        let mut cached = self.cached.lock().await;
+        if let Some(credentials) = cached.as_ref() {
+            if !credentials.expires_within(REFRESH_MARGIN) {
+                return Ok(credentials.clone());
+            }
+            log::debug!("Refreshing AWS credentials");
+        }
+        let credentials = self
+            .resolve(&self.source, 0)
+            .await?
+            .ok_or_else(|| CredentialsError::NotFound(self.source.describe()))?;
+        *cached = Some(credentials.clone());
+        Ok(credentials)
+    }
+
+    /// Drop the cached credentials.
+    pub async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    /// Credentials of `source`; `None` if it is not configured here (and a
+    /// chain moves on to the next source).
+    fn resolve<'a>(
+        &'a self,
+        source: &'a CredentialSource,
+        depth: usize,
+    ) -> BoxFuture<'a, Result<Option<AwsCredentials>, CredentialsError>> {
+        async move {
+            match source {
+                CredentialSource::Static(credentials) => Ok(Some(credentials.clone())),
+                CredentialSource::Environment => Ok(AwsCredentials::from_env()),
+                CredentialSource::Profile(name) => self.profile(name, depth).await,
+                CredentialSource::WebIdentity {
+                    role_arn,
+                    token_file,
+                    session_name,
+                } => {
+                    let token = read_file(token_file)?;
+                    self.assume_role_with_web_identity(
+                        role_arn,
+                        session_name.as_deref(),
+                        token.trim(),
+                    )
+                    .await
+                    .map(Some)
+                }
+                CredentialSource::Container {
+                    uri,
+                    authorization,
+                    authorization_file,
+                } => {
+                    let authorization = match authorization_file {
+                        Some(path) => Some(read_file(path)?.trim().to_string()),
+                        None => authorization.clone(),
+                    };
+                    container_credentials(uri, authorization.as_deref())
+                        .await
+                        .map(Some)
+                }
+                CredentialSource::InstanceMetadata { endpoint } => {
+                    instance_metadata_credentials(endpoint).await
+                }
+                CredentialSource::AssumeRole(role) => {
+                    let Some(credentials) = self.resolve(&role.source, depth + 1).await? else {
+                        return Err(CredentialsError::NotFound(role.source.describe()));
+                    };
+                    self.assume_role(role, &credentials).await.map(Some)
+                }
+                CredentialSource::Chain(sources) => {
+                    for source in sources {
+                        if let Some(credentials) = self.resolve(source, depth).await? {
+                            return Ok(Some(credentials));
+                        }
+                    }
+                    Ok(None)
+                }
+            }
+        }
+        .boxed()
+    }
+
+    /// Credentials of profile `name`: its keys, or the role it assumes.
+    async fn profile(
+        &self,
+        name: &str,
+        depth: usize,
+    ) -> Result<Option<AwsCredentials>, CredentialsError> {
+        let profile_error = |message: String| CredentialsError::Profile {
+            profile: name.to_string(),
+            message,
+        };
+        if depth > MAX_PROFILE_DEPTH {
+            return Err(profile_error(
+                "source_profile chain is too long".to_string(),
+            ));
+        }
+        let Some(settings) = self.profile_settings(name)? else {
+            return Ok(None);
+        };
+        let keys = || {
+            let credentials = AwsCredentials::new(
+                settings.get("aws_access_key_id")?,
+                settings.get("aws_secret_access_key")?.as_str(),
+            );
+            Some(match settings.get("aws_session_token") {
+                Some(token) => credentials.with_session_token(token.as_str()),
+                None => credentials,
+            })
+        };
+
+        let Some(role_arn) = settings.get("role_arn") else {
+            return Ok(keys());
+        };
+        let source = match (
+            settings.get("source_profile"),
+            settings.get("credential_source"),
+        ) {
+            (Some(source), _) if source == name => match keys() {
+                Some(credentials) => CredentialSource::Static(credentials),
+                None => return Err(profile_error("source_profile has no keys".to_string())),
+            },
+            (Some(source), _) => CredentialSource::Profile(source.clone()),
+            (None, Some(source)) => match source.as_str() {
+                "Environment" => CredentialSource::Environment,
+                "Ec2InstanceMetadata" => CredentialSource::instance_metadata_from_env(),
+                "EcsContainer" => CredentialSource::container_from_env().ok_or_else(|| {
+                    profile_error("no container credentials in the environment".to_string())
+                })?,
+                other => {
+                    return Err(profile_error(format!(
+                        "unsupported credential_source '{}'",
+                        other
+                    )))
+                }
+            },
+            (None, None) => {
+                return Err(profile_error(
+                    "role_arn needs source_profile or credential_source".to_string(),
+                ))
+            }
+        };
+        let role = AssumeRole {
+            role_arn: role_arn.clone(),
+            session_name: settings.get("role_session_name").cloned(),
+            external_id: settings.get("external_id").cloned(),
+            duration: settings
+                .get("duration_seconds")
+                .and_then(|v| v.parse().ok())
+                .map(Duration::from_secs),
+            source,
+        };
+        self.resolve(&CredentialSource::AssumeRole(Box::new(role)), depth + 1)
+            .await
+    }
+
+    /// Settings of profile `name`, from the config file overlaid with the
+    /// credentials file; `None` if neither has the profile.
+    fn profile_settings(
+        &self,
+        name: &str,
+    ) -> Result<Option<HashMap<String, String>>, CredentialsError> {
+        let credentials_file = self.credentials_file.clone().or_else(|| {
+            env("AWS_SHARED_CREDENTIALS_FILE")
+                .map(PathBuf::from)
+                .or_else(|| aws_dir().map(|dir| dir.join("credentials")))
+        });
+        let config_file = self.config_file.clone().or_else(|| {
+            env("AWS_CONFIG_FILE")
+                .map(PathBuf::from)
+                .or_else(|| aws_dir().map(|dir| dir.join("config")))
+        });
+
+        let mut settings = None;
+        if let Some(mut config) = config_file.as_deref().map(read_ini).transpose()? {
+            let section = if name == "default" {
+                "default".to_string()
+            } else {
+                format!("profile {}", name)
+            };
+            settings = config.remove(&section).or_else(|| config.remove(name));
+        }
+        if let Some(mut credentials) = credentials_file.as_deref().map(read_ini).transpose()? {
+            if let Some(section) = credentials.remove(name) {
+                settings.get_or_insert_with(HashMap::new).extend(section);
+            }
+        }
+        Ok(settings)
+    }
+
+    fn sts_endpoint(&self) -> String {
+        self.sts_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://sts.{}.amazonaws.com", self.region))
+    }
+
+    /// STS `AssumeRole`, signed with `credentials`.
+    async fn assume_role(
+        &self,
+        role: &AssumeRole,
+        credentials: &AwsCredentials,
+    ) -> Result<AwsCredentials, CredentialsError> {
+        let mut params = vec![
+            ("Action", "AssumeRole".to_string()),
+            ("RoleArn", role.role_arn.clone()),
+            (
+                "RoleSessionName",
+                session_name(role.session_name.as_deref()),
+            ),
+            ("Version", "2011-06-15".to_string()),
+        ];
+        if let Some(ref external_id) = role.external_id {
+            params.push(("ExternalId", external_id.clone()));
+        }
+        if let Some(duration) = role.duration {
+            params.push(("DurationSeconds", duration.as_secs().to_string()));
+        }
+        let body = form_body(&params);
+
+        let endpoint = self.sts_endpoint();
+        let headers = vec![
+            (
+                "content-type".to_string(),
+                "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+            ),
+            ("host".to_string(), host_of(&endpoint).to_string()),
+        ];
+        let headers = sigv4::sign(
+            &credentials.access_key_id,
+            credentials.secret_access_key.expose_secret(),
+            credentials
+                .session_token
+                .as_ref()
+                .map(SecretString::expose_secret),
+            &self.region,
+            "sts",
+            "POST",
+            "/",
+            headers,
+            body.as_bytes(),
+        );
+        log::debug!("Assuming AWS role {}", role.role_arn);
+        self.sts_request(&endpoint, headers, body).await
+    }
+
+    /// STS `AssumeRoleWithWebIdentity` (unsigned).
+    async fn assume_role_with_web_identity(
+        &self,
+        role_arn: &str,
+        session: Option<&str>,
+        token: &str,
+    ) -> Result<AwsCredentials, CredentialsError> {
+        let body = form_body(&[
+            ("Action", "AssumeRoleWithWebIdentity".to_string()),
+            ("RoleArn", role_arn.to_string()),
+            ("RoleSessionName", session_name(session)),
+            ("Version", "2011-06-15".to_string()),
+            ("WebIdentityToken", token.to_string()),
+        ]);
+        let headers = vec![(
+            "content-type".to_string(),
+            "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+        )];
+        log::debug!("Assuming AWS role {} with web identity", role_arn);
+        self.sts_request(&self.sts_endpoint(), headers, body).await
+    }
+
+    async fn sts_request(
+        &self,
+        endpoint: &str,
+        headers: Vec<(String, String)>,
+        body: String,
+    ) -> Result<AwsCredentials, CredentialsError> {
+        const STS: &str = "STS";
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+        let mut request = client.post(format!("{}/", endpoint.trim_end_matches('/')));
+        for (name, value) in &headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let response = request.body(body).send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(CredentialsError::Service {
+                service: STS,
+                status: status.as_u16(),
+                message: xml_tag(&text, "Message").unwrap_or(text),
+            });
+        }
+
+        let field = |tag: &str| {
+            xml_tag(&text, tag).ok_or_else(|| CredentialsError::Response {
+                service: STS,
+                message: format!("missing {}", tag),
+            })
+        };
+        let mut credentials = AwsCredentials::new(field("AccessKeyId")?, field("SecretAccessKey")?)
+            .with_session_token(field("SessionToken")?);
+        credentials.expires_at = xml_tag(&text, "Expiration").as_deref().and_then(parse_time);
+        Ok(credentials)
+    }
+}
+
+/// Credentials from the ECS container endpoint.
+async fn container_credentials(
+    uri: &str,
+    authorization: Option<&str>,
+) -> Result<AwsCredentials, CredentialsError> {
+    const SERVICE: &str = "container credentials endpoint";
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()?;
+    let mut request = client.get(uri);
+    if let Some(authorization) = authorization {
+        request = request.header("Authorization", authorization);
+    }
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(CredentialsError::Service {
+            service: SERVICE,
+            status: status.as_u16(),
+            message: response.text().await.unwrap_or_default(),
+        });
+    }
+    AwsCredentials::from_json(&response.json().await?, SERVICE)
+}
+
+/// Credentials of the instance role, from IMDSv2. `None` when the metadata
+/// service does not answer (i.e. not on EC2).
+async fn instance_metadata_credentials(
+    endpoint: &str,
+) -> Result<Option<AwsCredentials>, CredentialsError> {
+    const SERVICE: &str = "instance metadata service";
+    const PATH: &str = "latest/meta-data/iam/security-credentials";
+    let endpoint = endpoint.trim_end_matches('/');
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(1))
+        .timeout(Duration::from_secs(2))
+        .build()?;
+
+    let token = match client
+        .put(format!("{}/latest/api/token", endpoint))
+        .header("X-aws-ec2-metadata-token-ttl-seconds", "21600")
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => response.text().await?,
+        Ok(response) => {
+            return Err(CredentialsError::Service {
+                service: SERVICE,
+                status: response.status().as_u16(),
+                message: "failed to get a session token".to_string(),
+            })
+        }
+        Err(e) if e.is_connect() || e.is_timeout() => {
+            log::debug!("No instance metadata service at {}: {}", endpoint, e);
+            return Ok(None);
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let get = |path: String| {
+        client
+            .get(format!("{}/{}", endpoint, path))
+            .header("X-aws-ec2-metadata-token", token.as_str())
+            .send()
+    };
+    let response = get(format!("{}/", PATH)).await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        // The instance has no role attached.
+        return Ok(None);
+    }
+    let roles = response.error_for_status()?.text().await?;
+    let Some(role) = roles.lines().map(str::trim).find(|l| !l.is_empty()) else {
+        return Ok(None);
+    };
+    let response = get(format!("{}/{}", PATH, role)).await?;
+    let value: Value = response.error_for_status()?.json().await?;
+    AwsCredentials::from_json(&value, SERVICE).map(Some)
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn aws_dir() -> Option<PathBuf> {
+    env("HOME")
+        .or_else(|| env("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".aws"))
+}
+
+fn read_file(path: &Path) -> Result<String, CredentialsError> {
+    std::fs::read_to_string(path).map_err(|source| CredentialsError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Sections of an INI file; a missing file has none.
+fn read_ini(path: &Path) -> Result<HashMap<String, HashMap<String, String>>, CredentialsError> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    Ok(parse_ini(&read_file(path)?))
+}
+
+/// Parse the INI format of the AWS shared config files. Keys are
+/// lowercased; nested (indented) settings are skipped.
+fn parse_ini(text: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = None;
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+    sections
+}
+
+fn session_name(name: Option<&str>) -> String {
+    name.map(str::to_string)
+        .unwrap_or_else(|| format!("crewai-{}", Utc::now().timestamp()))
+}
+
+/// `application/x-www-form-urlencoded` body of `params`.
+fn form_body(params: &[(&str, String)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Percent-encode everything but the RFC 3986 unreserved characters.
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn host_of(endpoint: &str) -> &str {
+    let rest = endpoint
+        .split_once("://")
+        .map_or(endpoint, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Text of the first `<tag>` element of an XML document.
+fn xml_tag(xml: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&format!("</{}>", tag))?;
+    Some(xml[start..end].trim().to_string())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn serve(router: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        url
+    }
+
+    fn sts_response(access_key_id: &str, expires_at: DateTime<Utc>) -> String {
+        format!(
+            "<AssumeRoleResponse><AssumeRoleResult><Credentials>\
+             <AccessKeyId>{}</AccessKeyId><SecretAccessKey>secret</SecretAccessKey>\
+             <SessionToken>token</SessionToken><Expiration>{}</Expiration>\
+             </Credentials></AssumeRoleResult></AssumeRoleResponse>",
+            access_key_id,
+            expires_at.to_rfc3339(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_profile_assumes_role_through_sts() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = bodies.clone();
+        let sts = serve(axum::Router::new().route(
+            "/",
+            axum::routing::post(
+                move |headers: axum::http::HeaderMap, body: String| async move {
+                    let authorization = headers["authorization"].to_str().unwrap().to_string();
+                    seen.lock().unwrap().push((authorization, body));
+                    sts_response("ASIAROLE", Utc::now() + chrono::Duration::hours(1))
+                },
+            ),
+        ))
+        .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let credentials = dir.path().join("credentials");
+        let config = dir.path().join("config");
+        std::fs::write(
+            &credentials,
+            "[base]\naws_access_key_id = AKIABASE\naws_secret_access_key = base-secret\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &config,
+            "[default]\nregion = us-east-1\n\n[profile bedrock]\n\
+             role_arn = arn:aws:iam::123456789012:role/bedrock\n\
+             source_profile = base\nexternal_id = ext-1\n",
+        )
+        .unwrap();
+
+        let provider = |name: &str| {
+            AwsCredentialsProvider::new(CredentialSource::Profile(name.into()), "us-east-1")
+                .with_profile_files(&credentials, &config)
+                .with_sts_endpoint(&sts)
+        };
+        let base = provider("base").credentials().await.unwrap();
+        assert_eq!(base.access_key_id, "AKIABASE");
+        assert!(base.expires_at.is_none());
+
+        let role = provider("bedrock").credentials().await.unwrap();
+        assert_eq!(role.access_key_id, "ASIAROLE");
+        assert_eq!(role.session_token.unwrap().expose_secret(), "token");
+        let (authorization, body) = bodies.lock().unwrap()[0].clone();
+        assert!(authorization.contains("Credential=AKIABASE/"));
+        assert!(authorization.contains("/us-east-1/sts/aws4_request"));
+        assert!(body.contains("RoleArn=arn%3Aaws%3Aiam%3A%3A123456789012%3Arole%2Fbedrock"));
+        assert!(body.contains("ExternalId=ext-1"));
+
+        // The default profile has only a region: not configured here.
+        let err = provider("default").credentials().await.unwrap_err();
+        assert!(matches!(err, CredentialsError::NotFound(_)));
+
+        std::fs::write(
+            &config,
+            "[profile a]\nrole_arn = arn:a\nsource_profile = b\n\
+             [profile b]\nrole_arn = arn:b\nsource_profile = a\n",
+        )
+        .unwrap();
+        let err = provider("a").credentials().await.unwrap_err();
+        assert!(err.to_string().contains("too long"));
+    }
+
+    #[tokio::test]
+    async fn test_metadata_credentials_refresh_before_expiry() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let count = requests.clone();
+        let container = serve(axum::Router::new().route(
+            "/creds",
+            axum::routing::get(move |headers: axum::http::HeaderMap| async move {
+                assert_eq!(headers["authorization"], "secret-token");
+                let n = count.fetch_add(1, Ordering::SeqCst);
+                // The first credentials are about to expire.
+                let expires_at =
+                    Utc::now() + chrono::Duration::minutes(if n == 0 { 1 } else { 60 });
+                axum::Json(serde_json::json!({
+                    "AccessKeyId": format!("ASIA{}", n),
+                    "SecretAccessKey": "secret",
+                    "Token": "token",
+                    "Expiration": expires_at.to_rfc3339(),
+                }))
+            }),
+        ))
+        .await;
+
+        let provider = AwsCredentialsProvider::new(
+            CredentialSource::Container {
+                uri: format!("{}/creds", container),
+                authorization: Some("secret-token".into()),
+                authorization_file: None,
+            },
+            "us-east-1",
+        );
+        assert_eq!(provider.credentials().await.unwrap().access_key_id, "ASIA0");
+        assert_eq!(provider.credentials().await.unwrap().access_key_id, "ASIA1");
+        assert_eq!(provider.credentials().await.unwrap().access_key_id, "ASIA1");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        let imds = serve(
+            axum::Router::new()
+                .route(
+                    "/latest/api/token",
+                    axum::routing::put(|| async { "imds-token" }),
+                )
+                .route(
+                    "/latest/meta-data/iam/security-credentials/",
+                    axum::routing::get(|| async { "instance-role\n" }),
+                )
+                .route(
+                    "/latest/meta-data/iam/security-credentials/instance-role",
+                    axum::routing::get(|headers: axum::http::HeaderMap| async move {
+                        assert_eq!(headers["x-aws-ec2-metadata-token"], "imds-token");
+                        axum::Json(serde_json::json!({
+                            "Code": "Success",
+                            "AccessKeyId": "ASIAEC2",
+                            "SecretAccessKey": "secret",
+                            "Token": "token",
+                        }))
+                    }),
+                ),
+        )
+        .await;
+        let provider = AwsCredentialsProvider::new(
+            CredentialSource::Chain(vec![
+                CredentialSource::InstanceMetadata {
+                    endpoint: "http://127.0.0.1:1".into(),
+                },
+                CredentialSource::InstanceMetadata { endpoint: imds },
+            ]),
+            "us-east-1",
+        );
+        assert_eq!(
+            provider.credentials().await.unwrap().access_key_id,
+            "ASIAEC2"
+        );
+    }
+}
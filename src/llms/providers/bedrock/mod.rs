@@ -12,7 +12,9 @@
 //! - Streaming support via ConverseStream
 //! - Native tool use (function calling)
 //! - Structured output via tool-based approach
-//! - AWS credential chain authentication (env vars, profiles, IAM roles)
+//! - AWS credential chain authentication (env vars, web identity, profiles
+//!   with assumed roles, ECS container and EC2 instance credentials); see
+//!   [`credentials`]
 //! - Guardrail support
 //! - Cross-region inference
 //! - Token usage tracking
@@ -31,14 +33,24 @@
 //! HTTP interceptors are not supported for the Bedrock provider as it uses
 //! the AWS SDK (boto3 equivalent) rather than direct HTTP calls.
 
+pub mod credentials;
+
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::llms::images::ImageSource;
+use crate::llms::providers::bedrock::credentials::{
+    AssumeRole, AwsCredentials, AwsCredentialsProvider, CredentialSource,
+};
+use crate::llms::providers::regions::RegionSelector;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -54,7 +66,7 @@ const SERVICE: &str = "bedrock";
 // AWS SigV4 signing
 // ---------------------------------------------------------------------------
 
-mod sigv4 {
+pub(crate) mod sigv4 {
     use hmac::{Hmac, Mac};
     use sha2::{Digest, Sha256};
 
@@ -132,6 +144,59 @@ mod sigv4 {
             access_key, credential_scope, signed_headers, signature,
         )
     }
+
+    /// Sign a request: `headers` (lowercase names, `host` among them) plus
+    /// `x-amz-date`, `x-amz-security-token` with a session token, and
+    /// `authorization`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign(
+        access_key: &str,
+        secret_key: &str,
+        session_token: Option<&str>,
+        region: &str,
+        service: &str,
+        method: &str,
+        uri: &str,
+        mut headers: Vec<(String, String)>,
+        payload: &[u8],
+    ) -> Vec<(String, String)> {
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let credential_scope = format!("{}/{}/{}/aws4_request", date_stamp, region, service);
+
+        headers.push(("x-amz-date".to_string(), amz_date.clone()));
+        if let Some(token) = session_token {
+            headers.push(("x-amz-security-token".to_string(), token.to_string()));
+        }
+        headers.sort_by(|a, b| a.0.cmp(&b.0));
+        let signed_headers: String = headers
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical = canonical_request(
+            method,
+            uri,
+            "",
+            &headers,
+            &signed_headers,
+            &sha256_hex(payload),
+        );
+        let sts = string_to_sign(
+            &amz_date,
+            &credential_scope,
+            &sha256_hex(canonical.as_bytes()),
+        );
+        let key = signing_key(secret_key, &date_stamp, region, service);
+        let signature = sign_hex(&key, &sts);
+        headers.push((
+            "authorization".to_string(),
+            authorization_header(access_key, &credential_scope, &signed_headers, &signature),
+        ));
+        headers
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -170,14 +235,14 @@ pub struct BedrockCompletion {
     pub aws_access_key_id: Option<String>,
     /// AWS secret access key.
     #[serde(skip_serializing)]
-    pub aws_secret_access_key: Option<String>,
+    pub aws_secret_access_key: Option<SecretString>,
     /// AWS session token (for temporary credentials).
     #[serde(skip_serializing)]
-    pub aws_session_token: Option<String>,
+    pub aws_session_token: Option<SecretString>,
 
     /// Request timeout in seconds.
     pub timeout: Option<f64>,
-    /// Maximum number of retries.
+    /// Maximum number of retries, unless `state.retry_policy` is set.
     pub max_retries: u32,
     /// Maximum tokens in response.
     pub max_tokens: Option<u32>,
@@ -198,6 +263,14 @@ pub struct BedrockCompletion {
     pub guardrail_id: Option<String>,
     /// Guardrail version.
     pub guardrail_version: Option<String>,
+
+    /// Optional multi-region selector; overrides `region_name` when set.
+    #[serde(skip)]
+    pub region_selector: Option<RegionSelector>,
+    /// Where credentials come from when the key fields are not set
+    /// (defaults to the AWS SDK credential chain for `profile_name`).
+    #[serde(skip)]
+    pub credentials_provider: Option<Arc<AwsCredentialsProvider>>,
 }
 
 impl BedrockCompletion {
@@ -219,6 +292,11 @@ impl BedrockCompletion {
             .or_else(|| Some("us-east-1".to_string()));
         let profile_name = profile_name.or_else(|| std::env::var("AWS_PROFILE").ok());
 
+        let credentials_provider = Arc::new(AwsCredentialsProvider::default_chain(
+            profile_name.as_deref(),
+            region_name.as_deref().unwrap_or("us-east-1"),
+        ));
+
         let mut state = BaseLLMState::new(model);
         state.provider = "bedrock".to_string();
 
@@ -227,8 +305,12 @@ impl BedrockCompletion {
             region_name,
             profile_name,
             aws_access_key_id: std::env::var("AWS_ACCESS_KEY_ID").ok(),
-            aws_secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY").ok(),
-            aws_session_token: std::env::var("AWS_SESSION_TOKEN").ok(),
+            aws_secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY")
+                .ok()
+                .map(SecretString::from),
+            aws_session_token: std::env::var("AWS_SESSION_TOKEN")
+                .ok()
+                .map(SecretString::from),
             timeout: None,
             max_retries: 2,
             max_tokens: None,
@@ -240,19 +322,77 @@ impl BedrockCompletion {
             response_format: None,
             guardrail_id: None,
             guardrail_version: None,
+            region_selector: None,
+            credentials_provider: Some(credentials_provider),
         }
     }
 
+    /// Resolve credentials with `provider` (shared by clones, which then
+    /// share its cache) instead of the default chain.
+    pub fn with_credentials_provider(mut self, provider: Arc<AwsCredentialsProvider>) -> Self {
+        self.credentials_provider = Some(provider);
+        self
+    }
+
+    /// Assume `role_arn` through STS, signed with the configured keys or
+    /// the default chain, and refresh the role credentials before they
+    /// expire.
+    pub fn with_assume_role(self, role_arn: impl Into<String>) -> Self {
+        let source = match (&self.aws_access_key_id, &self.aws_secret_access_key) {
+            (Some(access_key), Some(secret_key)) => CredentialSource::Static(AwsCredentials {
+                access_key_id: access_key.clone(),
+                secret_access_key: secret_key.clone(),
+                session_token: self.aws_session_token.clone(),
+                expires_at: None,
+            }),
+            _ => CredentialSource::default_chain(self.profile_name.as_deref()),
+        };
+        let role = AssumeRole::new(role_arn, source);
+        let provider = AwsCredentialsProvider::new(
+            CredentialSource::AssumeRole(Box::new(role)),
+            self.effective_region(),
+        );
+        Self {
+            aws_access_key_id: None,
+            aws_secret_access_key: None,
+            aws_session_token: None,
+            ..self
+        }
+        .with_credentials_provider(Arc::new(provider))
+    }
+
+    /// Route calls through a multi-region selector.
+    pub fn with_region_selector(mut self, selector: RegionSelector) -> Self {
+        self.region_selector = Some(selector);
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
+    /// Region calls are sent to (and signed for).
+    pub fn effective_region(&self) -> String {
+        self.region_selector
+            .as_ref()
+            .and_then(|s| s.selected_region())
+            .or_else(|| self.region_name.clone())
+            .unwrap_or_else(|| "us-east-1".to_string())
+    }
+
     /// Get the Bedrock endpoint URL.
     pub fn endpoint_url(&self) -> String {
-        let region = self.region_name.as_deref().unwrap_or("us-east-1");
-        format!("https://bedrock-runtime.{}.amazonaws.com", region)
+        format!(
+            "https://bedrock-runtime.{}.amazonaws.com",
+            self.effective_region()
+        )
     }
 
     /// Get the host header value.
     fn host(&self) -> String {
-        let region = self.region_name.as_deref().unwrap_or("us-east-1");
-        format!("bedrock-runtime.{}.amazonaws.com", region)
+        format!("bedrock-runtime.{}.amazonaws.com", self.effective_region())
     }
 
     /// Build the Converse API URI path.
@@ -338,10 +478,13 @@ impl BedrockCompletion {
                 }));
             } else {
                 // user role
-                let text = content.as_str().unwrap_or("").to_string();
+                let parts = match content {
+                    Value::Array(blocks) => blocks.iter().map(user_part).collect(),
+                    other => vec![serde_json::json!({ "text": other.as_str().unwrap_or("") })],
+                };
                 converse_messages.push(serde_json::json!({
                     "role": "user",
-                    "content": [{ "text": text }],
+                    "content": parts,
                 }));
             }
         }
@@ -515,81 +658,55 @@ impl BedrockCompletion {
         usage
     }
 
+    /// Credentials for the next request: the explicit keys if set, else
+    /// those of `credentials_provider`.
+    async fn resolve_credentials(
+        &self,
+    ) -> Result<AwsCredentials, Box<dyn std::error::Error + Send + Sync>> {
+        if let (Some(access_key), Some(secret_key)) =
+            (&self.aws_access_key_id, &self.aws_secret_access_key)
+        {
+            return Ok(AwsCredentials {
+                access_key_id: access_key.clone(),
+                secret_access_key: secret_key.clone(),
+                session_token: self.aws_session_token.clone(),
+                expires_at: None,
+            });
+        }
+        match self.credentials_provider {
+            Some(ref provider) => Ok(provider.credentials().await?),
+            None => {
+                Err("AWS credentials not set (AWS_ACCESS_KEY_ID / AWS_SECRET_ACCESS_KEY)".into())
+            }
+        }
+    }
+
     /// Sign a request using AWS SigV4 and return headers.
     fn sign_request(
         &self,
+        credentials: &AwsCredentials,
         method: &str,
         uri: &str,
         payload: &[u8],
-    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error + Send + Sync>> {
-        let access_key = self
-            .aws_access_key_id
-            .as_ref()
-            .ok_or("AWS_ACCESS_KEY_ID not set")?;
-        let secret_key = self
-            .aws_secret_access_key
-            .as_ref()
-            .ok_or("AWS_SECRET_ACCESS_KEY not set")?;
-        let region = self.region_name.as_deref().unwrap_or("us-east-1");
-
-        let now = chrono::Utc::now();
-        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
-        let date_stamp = now.format("%Y%m%d").to_string();
-        let credential_scope = format!("{}/{}/{}/aws4_request", date_stamp, region, SERVICE);
-
-        let host = self.host();
-        let payload_hash = sigv4::sha256_hex(payload);
-
-        // Build canonical headers (must be sorted by lowercase key)
-        let mut headers: Vec<(String, String)> = vec![
+    ) -> Vec<(String, String)> {
+        let headers = vec![
             ("content-type".to_string(), "application/json".to_string()),
-            ("host".to_string(), host.clone()),
-            ("x-amz-date".to_string(), amz_date.clone()),
+            ("host".to_string(), self.host()),
         ];
-
-        if let Some(ref token) = self.aws_session_token {
-            headers.push(("x-amz-security-token".to_string(), token.clone()));
-        }
-
-        headers.sort_by(|a, b| a.0.cmp(&b.0));
-
-        let signed_headers: String = headers
-            .iter()
-            .map(|(k, _)| k.as_str())
-            .collect::<Vec<_>>()
-            .join(";");
-
-        let canonical = sigv4::canonical_request(
+        sigv4::sign(
+            &credentials.access_key_id,
+            credentials.secret_access_key.expose_secret(),
+            credentials
+                .session_token
+                .as_ref()
+                .map(SecretString::expose_secret),
+            &self.effective_region(),
+            SERVICE,
             method,
             uri,
-            "", // no query string for Converse
-            &headers,
-            &signed_headers,
-            &payload_hash,
-        );
-
-        let canonical_hash = sigv4::sha256_hex(canonical.as_bytes());
-        let sts = sigv4::string_to_sign(&amz_date, &credential_scope, &canonical_hash);
-
-        let signing_key = sigv4::signing_key(secret_key, &date_stamp, region, SERVICE);
-        let signature = sigv4::sign_hex(&signing_key, &sts);
-
-        let auth_header =
-            sigv4::authorization_header(access_key, &credential_scope, &signed_headers, &signature);
-
-        // Return all the headers needed for the request
-        let mut result_headers = vec![
-            ("Content-Type".to_string(), "application/json".to_string()),
-            ("Host".to_string(), host),
-            ("X-Amz-Date".to_string(), amz_date),
-            ("Authorization".to_string(), auth_header),
-        ];
-
-        if let Some(ref token) = self.aws_session_token {
-            result_headers.push(("X-Amz-Security-Token".to_string(), token.clone()));
-        }
-
-        Ok(result_headers)
+            headers,
+            payload,
+        )
     }
 }
 
@@ -659,9 +776,9 @@ impl BaseLLM for BedrockCompletion {
         available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         log::debug!(
-            "BedrockCompletion.call: model={}, region={:?}, messages={}, tools={:?}",
+            "BedrockCompletion.call: model={}, region={}, messages={}, tools={:?}",
             self.state.model,
-            self.region_name,
+            self.effective_region(),
             messages.len(),
             tools.as_ref().map(|t| t.len()),
         );
@@ -690,90 +807,61 @@ impl BaseLLM for BedrockCompletion {
         let endpoint = format!("{}{}", self.endpoint_url(), uri);
 
         let timeout_secs = self.timeout.unwrap_or(120.0) as u64;
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_secs))
-            .build()?;
-
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                log::warn!(
-                    "Bedrock API retry attempt {} after {:?}",
-                    attempt,
-                    retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2;
-            }
-
-            // Sign the request (must re-sign each attempt for fresh timestamp)
-            let headers = match self.sign_request("POST", &uri, &payload) {
-                Ok(h) => h,
-                Err(e) => return Err(e),
-            };
-
-            let mut request = client.post(&endpoint);
-            for (k, v) in &headers {
-                request = request.header(k.as_str(), v.as_str());
-            }
-
-            let response = match request.body(payload.clone()).send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs(timeout_secs))?;
+
+        let credentials = self.resolve_credentials().await?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("Bedrock", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("bedrock").await {
+                    return Err(fault.into());
                 }
-            };
-
-            let status = response.status();
 
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some("Rate limited by Bedrock API (429)".into());
-                continue;
-            }
-
-            if status.is_server_error() {
-                last_error = Some(format!("Bedrock API server error: {}", status).into());
-                continue;
-            }
+                // Sign the request (must re-sign each attempt for fresh timestamp)
+                let headers = self.sign_request(&credentials, "POST", &uri, &payload);
 
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+                let mut request = client.post(&endpoint);
+                for (k, v) in &headers {
+                    request = request.header(k.as_str(), v.as_str());
                 }
-            };
 
-            if status.is_client_error() {
-                return Err(format!("Bedrock API error ({}): {}", status, response_text).into());
-            }
+                Ok(request.body(payload.clone()).send().await?)
+            })
+            .await?;
 
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse Bedrock response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(format!("Bedrock API error ({}): {}", status, response_text).into());
+        }
 
-            // Extract token usage
-            let usage = Self::extract_token_usage(&response_json);
-            if !usage.is_empty() {
-                log::debug!("Bedrock usage: {:?}", usage);
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse Bedrock response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
 
-            return self.parse_response(&response_json);
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("Bedrock usage: {:?}", usage);
         }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
 
-        Err(last_error.unwrap_or_else(|| "Bedrock API call failed after all retries".into()))
+        self.parse_response(&response_json)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
@@ -789,6 +877,23 @@ impl BaseLLM for BedrockCompletion {
 // Tests
 // ---------------------------------------------------------------------------
 
+/// A Converse content block for a block of user content. Converse only
+/// takes inline images, so image URLs are passed on as text.
+fn user_part(block: &Value) -> Value {
+    match ImageSource::from_block(block) {
+        Some(ImageSource::Base64 { media_type, data }) => serde_json::json!({
+            "image": {
+                "format": media_type.strip_prefix("image/").unwrap_or(&media_type),
+                "source": { "bytes": data },
+            }
+        }),
+        Some(ImageSource::Url(url)) => serde_json::json!({ "text": format!("Image: {}", url) }),
+        None => serde_json::json!({
+            "text": block.get("text").and_then(|t| t.as_str()).unwrap_or_default()
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -820,6 +925,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bedrock_region_selector_pin() {
+        let selector = RegionSelector::bedrock(&["us-east-1", "us-west-2"]);
+        let provider = BedrockCompletion::new(
+            "anthropic.claude-3-5-sonnet-20241022-v2:0",
+            Some("eu-west-1".to_string()),
+            None,
+        )
+        .with_region_selector(selector.clone());
+        assert_eq!(provider.effective_region(), "us-east-1");
+
+        selector.pin("us-west-2").unwrap();
+        assert_eq!(
+            provider.endpoint_url(),
+            "https://bedrock-runtime.us-west-2.amazonaws.com"
+        );
+    }
+
     #[test]
     fn test_converse_uri_encodes_colons() {
         let provider =
@@ -860,6 +983,29 @@ mod tests {
         assert_eq!(converse[0]["role"], "user");
     }
 
+    #[test]
+    fn test_format_messages_with_images() {
+        let provider = BedrockCompletion::new("test-model", None, None);
+        let messages: Vec<LLMMessage> = vec![msg(&[
+            ("role", serde_json::json!("user")),
+            (
+                "content",
+                serde_json::json!([
+                    {"type": "text", "text": "Compare these"},
+                    {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/"}},
+                    {"type": "image_url", "image_url": {"url": "https://example.com/b.png"}},
+                ]),
+            ),
+        ])];
+
+        let (_, converse) = provider.format_messages(&messages);
+        let content = &converse[0]["content"];
+        assert_eq!(content[0]["text"], "Compare these");
+        assert_eq!(content[1]["image"]["format"], "jpeg");
+        assert_eq!(content[1]["image"]["source"]["bytes"], "/9j/");
+        assert_eq!(content[2]["text"], "Image: https://example.com/b.png");
+    }
+
     #[test]
     fn test_format_messages_with_tool_calls() {
         let provider = BedrockCompletion::new("test-model", None, None);
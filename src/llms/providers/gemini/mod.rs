@@ -21,21 +21,31 @@
 //!
 //! - **Gemini API**: Uses GOOGLE_API_KEY or GEMINI_API_KEY env var.
 //! - **Vertex AI**: Uses Application Default Credentials (ADC) with project ID.
-//!   Run `gcloud auth application-default login` for local development.
-//! - **Vertex AI Express**: API key with api_version="v1" auto-configured.
+//!   Run `gcloud auth application-default login` for local development; see
+//!   [`adc`] for how credentials are found and tokens refreshed.
+//! - **Vertex AI Express**: API key with api_version="v1" auto-configured,
+//!   used when no project is set.
 //!
 //! # Note
 //!
 //! HTTP interceptors are not yet supported for the Gemini provider.
 
+pub mod adc;
+
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::llms::images::ImageSource;
+use crate::llms::providers::gemini::adc::{GoogleCredentials, GoogleTokenProvider};
+use crate::llms::providers::regions::RegionSelector;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -93,6 +103,14 @@ pub struct GeminiCompletion {
     pub use_vertexai: bool,
     /// Response format for structured output.
     pub response_format: Option<Value>,
+
+    /// Optional multi-region selector for Vertex AI; overrides `location` when set.
+    #[serde(skip)]
+    pub region_selector: Option<RegionSelector>,
+    /// Access tokens for Vertex AI (defaults to the application default
+    /// credentials).
+    #[serde(skip)]
+    pub token_provider: Option<Arc<GoogleTokenProvider>>,
 }
 
 impl GeminiCompletion {
@@ -116,7 +134,7 @@ impl GeminiCompletion {
             .unwrap_or(false);
 
         let mut state = BaseLLMState::new(model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.provider = "gemini".to_string();
 
         Self {
@@ -132,18 +150,62 @@ impl GeminiCompletion {
             client_params: None,
             use_vertexai,
             response_format: None,
+            region_selector: None,
+            token_provider: Some(Arc::new(GoogleTokenProvider::application_default())),
         }
     }
 
-    /// Get the API endpoint URL.
-    fn api_endpoint(&self) -> String {
+    /// Authenticate Vertex AI calls with `credentials` instead of the
+    /// application default credentials.
+    pub fn with_credentials(self, credentials: GoogleCredentials) -> Self {
+        self.with_token_provider(Arc::new(GoogleTokenProvider::new(credentials)))
+    }
+
+    /// Take Vertex AI access tokens from `provider` (shared by clones,
+    /// which then share its cache).
+    pub fn with_token_provider(mut self, provider: Arc<GoogleTokenProvider>) -> Self {
+        self.token_provider = Some(provider);
+        self
+    }
+
+    /// Route Vertex AI calls through a multi-region selector.
+    pub fn with_region_selector(mut self, selector: RegionSelector) -> Self {
+        self.region_selector = Some(selector);
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
+    /// Vertex AI location calls are sent to.
+    pub fn effective_location(&self) -> String {
+        self.region_selector
+            .as_ref()
+            .and_then(|s| s.selected_region())
+            .or_else(|| self.location.clone())
+            .unwrap_or_else(|| "us-central1".to_string())
+    }
+
+    /// Get the API endpoint URL. Vertex AI without a `project` is
+    /// Express mode.
+    fn api_endpoint(&self, project: Option<&str>) -> String {
         if self.use_vertexai {
-            let project = self.project.as_deref().unwrap_or("default");
-            let location = self.location.as_deref().unwrap_or("us-central1");
-            format!(
-                "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/models/{}:generateContent",
-                location, project, location, self.state.model
-            )
+            match project {
+                Some(project) => {
+                    let location = self.effective_location();
+                    format!(
+                        "https://{}-aiplatform.googleapis.com/v1/projects/{}/locations/{}/publishers/google/models/{}:generateContent",
+                        location, project, location, self.state.model
+                    )
+                }
+                None => format!(
+                    "https://aiplatform.googleapis.com/v1/publishers/google/models/{}:generateContent",
+                    self.state.model
+                ),
+            }
         } else {
             format!(
                 "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
@@ -230,6 +292,8 @@ impl GeminiCompletion {
                             .map(|block| {
                                 if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
                                     serde_json::json!({ "text": text })
+                                } else if let Some(image) = ImageSource::from_block(block) {
+                                    image_part(&image)
                                 } else {
                                     block.clone()
                                 }
@@ -494,110 +558,104 @@ impl BaseLLM for GeminiCompletion {
             messages.len(),
         );
 
-        let api_key = self.state.api_key.as_ref().ok_or_else(|| {
-            "Gemini API key not set. Set GOOGLE_API_KEY or GEMINI_API_KEY environment variable."
-        })?;
+        let api_key = self.state.resolve_api_key("GOOGLE_API_KEY").await?;
+
+        // Vertex AI with a project authenticates with an access token;
+        // without one it is Express mode and takes the API key.
+        let mut project = self.project.clone();
+        let mut access_token = None;
+        if self.use_vertexai {
+            if let Some(ref provider) = self.token_provider {
+                if project.is_none() && api_key.is_none() {
+                    project = provider.project_id().await;
+                }
+                if project.is_some() {
+                    access_token = Some(provider.token().await?);
+                }
+            }
+        }
+        let api_key = match access_token {
+            Some(_) => None,
+            None => Some(api_key.ok_or(if self.use_vertexai {
+                "Vertex AI credentials not set. Set GOOGLE_CLOUD_PROJECT and configure \
+                 Application Default Credentials, or set GOOGLE_API_KEY for Express mode."
+            } else {
+                "Gemini API key not set. Set GOOGLE_API_KEY or GEMINI_API_KEY environment variable."
+            })?),
+        };
 
         let tools_slice = tools.as_deref();
         let body = self.build_request_body(&messages, tools_slice);
 
-        let endpoint = self.api_endpoint();
-
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(120))
-            .build()?;
-
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-        let max_retries = 2u32;
-
-        for attempt in 0..=max_retries {
-            if attempt > 0 {
-                log::warn!(
-                    "Gemini API retry attempt {} after {:?}",
-                    attempt,
-                    retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2;
-            }
+        let endpoint = self.api_endpoint(project.as_deref());
 
-            let mut request = client
-                .post(&endpoint)
-                .header("content-type", "application/json");
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs(120))?;
 
-            if self.use_vertexai {
-                // Vertex AI uses Bearer token auth (ADC)
-                request = request.header("authorization", format!("Bearer {}", api_key));
-            } else {
-                // Gemini API uses query parameter
-                request = request.query(&[("key", api_key.as_str())]);
-            }
-
-            let response = match request.json(&body).send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(2);
+        let response = policy
+            .send("Gemini", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("gemini").await {
+                    return Err(fault.into());
                 }
-            };
-
-            let status = response.status();
 
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some("Rate limited by Gemini API (429)".into());
-                continue;
-            }
+                let mut request = client
+                    .post(&endpoint)
+                    .header("content-type", "application/json");
+
+                if let Some(ref token) = access_token {
+                    // Vertex AI uses Bearer token auth (ADC)
+                    request = request
+                        .header("authorization", format!("Bearer {}", token.expose_secret()));
+                } else if let Some(ref api_key) = api_key {
+                    // Gemini API and Vertex AI Express use query parameter
+                    request = request.query(&[("key", api_key.expose_secret())]);
+                }
 
-            if status.is_server_error() {
-                last_error = Some(format!("Gemini API server error: {}", status).into());
-                continue;
-            }
+                Ok(request.json(&body).send().await?)
+            })
+            .await?;
 
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
-                }
-            };
+        let status = response.status();
+        let response_text = response.text().await?;
 
-            if status.is_client_error() {
-                return Err(format!("Gemini API error ({}): {}", status, response_text).into());
-            }
+        if !status.is_success() {
+            return Err(format!("Gemini API error ({}): {}", status, response_text).into());
+        }
 
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse Gemini response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
-
-            // Check for API error
-            if let Some(error) = response_json.get("error") {
-                let msg = error
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("Unknown Gemini API error");
-                return Err(format!("Gemini API error: {}", msg).into());
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse Gemini response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
 
-            // Extract token usage
-            let usage = Self::extract_token_usage(&response_json);
-            if !usage.is_empty() {
-                log::debug!("Gemini usage: {:?}", usage);
-            }
+        // Check for API error
+        if let Some(error) = response_json.get("error") {
+            let msg = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown Gemini API error");
+            return Err(format!("Gemini API error: {}", msg).into());
+        }
 
-            return self.parse_response(&response_json);
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("Gemini usage: {:?}", usage);
         }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
 
-        Err(last_error.unwrap_or_else(|| "Gemini API call failed after all retries".into()))
+        self.parse_response(&response_json)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
@@ -608,3 +666,15 @@ impl BaseLLM for GeminiCompletion {
         self.state.track_token_usage_internal(usage_data);
     }
 }
+
+/// A Gemini part holding `image`: inline data, or a file URI.
+fn image_part(image: &ImageSource) -> Value {
+    match image {
+        ImageSource::Base64 { media_type, data } => serde_json::json!({
+            "inlineData": {"mimeType": media_type, "data": data}
+        }),
+        ImageSource::Url(url) => serde_json::json!({
+            "fileData": {"mimeType": image.media_type(), "fileUri": url}
+        }),
+    }
+}
@@ -16,8 +16,16 @@
 //! | Anthropic | [`anthropic`] | `crewai.llms.providers.anthropic.completion` |
 //! | xAI / Grok | [`xai`] | — (new in Rust port) |
 //! | Azure | [`azure`] | `crewai.llms.providers.azure.completion` |
+//! | Azure AI Foundry | [`azure_foundry`] | — (new in Rust port) |
 //! | Bedrock | [`bedrock`] | `crewai.llms.providers.bedrock.completion` |
 //! | Gemini | [`gemini`] | `crewai.llms.providers.gemini.completion` |
+//! | SageMaker | [`sagemaker`] | — (new in Rust port) |
+//!
+//! # Regional Endpoints
+//!
+//! Azure, Bedrock and Gemini (Vertex AI) accept a
+//! [`RegionSelector`](regions::RegionSelector) that probes several regional
+//! endpoints and routes calls to the fastest healthy one, or a pinned region.
 //!
 //! # Shared Utilities
 //!
@@ -27,8 +35,11 @@
 
 pub mod anthropic;
 pub mod azure;
+pub mod azure_foundry;
 pub mod bedrock;
 pub mod gemini;
 pub mod openai;
+pub mod regions;
+pub mod sagemaker;
 pub mod utils;
 pub mod xai;
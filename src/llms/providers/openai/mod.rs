@@ -19,15 +19,23 @@
 //! - Auto-chaining for multi-turn conversations
 //! - Token usage tracking
 
+mod responses;
+
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::llm::LLM;
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::hooks::BaseInterceptor;
+use crate::llms::http::HttpConfig;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
+use responses::ResponseChain;
 
 // ---------------------------------------------------------------------------
 // OpenAI API mode
@@ -45,7 +53,6 @@ pub enum OpenAIApiMode {
     Responses,
 }
 
-
 // ---------------------------------------------------------------------------
 // Responses API result types
 // ---------------------------------------------------------------------------
@@ -133,7 +140,7 @@ pub struct OpenAICompletion {
     pub project: Option<String>,
     /// Request timeout in seconds.
     pub timeout: Option<f64>,
-    /// Maximum number of retries.
+    /// Maximum number of retries, unless `state.retry_policy` is set.
     pub max_retries: u32,
     /// Default headers to include in requests.
     pub default_headers: Option<HashMap<String, String>>,
@@ -141,6 +148,14 @@ pub struct OpenAICompletion {
     pub default_query: Option<HashMap<String, Value>>,
     /// Additional client parameters.
     pub client_params: Option<HashMap<String, Value>>,
+    /// Sees (and may modify) every request before it is sent and every
+    /// response before it is parsed.
+    #[serde(skip)]
+    pub interceptor: Option<Arc<dyn BaseInterceptor<reqwest::Request, reqwest::Response>>>,
+    /// Whether calls fail without an API key. OpenAI-compatible servers
+    /// such as a local LiteLLM proxy may not need one.
+    #[serde(default = "default_true")]
+    pub api_key_required: bool,
 
     // --- Generation parameters ---
     /// Nucleus sampling parameter.
@@ -163,6 +178,9 @@ pub struct OpenAICompletion {
     pub logprobs: Option<bool>,
     /// Number of top log probabilities to return.
     pub top_logprobs: Option<i32>,
+    /// Bias added to the logits of the given token IDs (-100 to 100).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<i64, f64>>,
     /// Reasoning effort level for reasoning models.
     pub reasoning_effort: Option<String>,
 
@@ -177,14 +195,20 @@ pub struct OpenAICompletion {
     pub previous_response_id: Option<String>,
     /// Additional data to include in response (Responses API only).
     pub include: Option<Vec<String>>,
-    /// List of OpenAI built-in tools to enable (Responses API only).
+    /// OpenAI built-in tools to enable (Responses API only): `web_search`,
+    /// `file_search`, `code_interpreter`, `computer_use`, or any other
+    /// Responses tool type.
     pub builtin_tools: Option<Vec<String>>,
     /// Whether to return structured ResponsesAPIResult (Responses API only).
     pub parse_tool_outputs: bool,
-    /// Automatically track response IDs for multi-turn (Responses API only).
+    /// Continue each call from the previous response, sending only the new
+    /// messages (Responses API only).
     pub auto_chain: bool,
     /// Automatically track reasoning items for ZDR (Responses API only).
     pub auto_chain_reasoning: bool,
+    /// The last response, for `auto_chain` and `auto_chain_reasoning`.
+    #[serde(skip)]
+    chain: Arc<Mutex<ResponseChain>>,
 }
 
 impl OpenAICompletion {
@@ -203,7 +227,7 @@ impl OpenAICompletion {
         let api_key = api_key.or_else(|| std::env::var("OPENAI_API_KEY").ok());
 
         let mut state = BaseLLMState::new(model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.base_url = base_url;
         state.provider = "openai".to_string();
 
@@ -216,6 +240,8 @@ impl OpenAICompletion {
             default_headers: None,
             default_query: None,
             client_params: None,
+            interceptor: None,
+            api_key_required: true,
             top_p: None,
             frequency_penalty: None,
             presence_penalty: None,
@@ -226,6 +252,7 @@ impl OpenAICompletion {
             response_format: None,
             logprobs: None,
             top_logprobs: None,
+            logit_bias: None,
             reasoning_effort: None,
             api: OpenAIApiMode::default(),
             instructions: None,
@@ -236,7 +263,61 @@ impl OpenAICompletion {
             parse_tool_outputs: false,
             auto_chain: false,
             auto_chain_reasoning: false,
+            chain: Arc::default(),
+        }
+    }
+
+    /// A provider for `llm`'s model and base URL, with its generation
+    /// parameters.
+    pub fn from_llm_config(llm: &LLM) -> Self {
+        Self::new(&llm.model, None, llm.api_base.clone()).with_llm_config(llm)
+    }
+
+    /// Take the generation parameters set on `llm`: temperature, sampling,
+    /// penalties, token limits, stop sequences, seed, response format, log
+    /// probabilities, reasoning effort, timeout and additional parameters.
+    ///
+    /// `n` and `stream` are not taken; only the first choice of a complete
+    /// response is parsed.
+    pub fn with_llm_config(mut self, llm: &LLM) -> Self {
+        self.state.temperature = llm.temperature.or(self.state.temperature);
+        if !llm.stop.is_empty() {
+            self.state.stop = llm.stop.clone();
+        }
+        self.state
+            .additional_params
+            .extend(llm.additional_params.clone());
+        self.timeout = llm.timeout.or(self.timeout);
+        self.top_p = llm.top_p.or(self.top_p);
+        self.frequency_penalty = llm.frequency_penalty.or(self.frequency_penalty);
+        self.presence_penalty = llm.presence_penalty.or(self.presence_penalty);
+        if let Some(max_tokens) = llm.max_tokens {
+            self.max_tokens = u32::try_from(max_tokens).ok();
+        }
+        if let Some(max_completion_tokens) = llm.max_completion_tokens {
+            self.max_completion_tokens = u32::try_from(max_completion_tokens).ok();
+        }
+        self.seed = llm.seed.or(self.seed);
+        if llm.response_format.is_some() {
+            self.response_format = llm.response_format.clone();
+        }
+        if let Some(logprobs) = llm.logprobs {
+            self.logprobs = Some(logprobs > 0);
+        }
+        self.top_logprobs = llm.top_logprobs.or(self.top_logprobs);
+        if llm.logit_bias.is_some() {
+            self.logit_bias = llm.logit_bias.clone();
         }
+        if let Some(ref effort) = llm.reasoning_effort {
+            self.reasoning_effort = Some(effort.to_string());
+        }
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
     }
 
     /// Get the API base URL.
@@ -247,6 +328,72 @@ impl OpenAICompletion {
             .unwrap_or_else(|| "https://api.openai.com/v1".to_string())
     }
 
+    /// ID of the last response, as `auto_chain` continues from it.
+    pub fn last_response_id(&self) -> Option<String> {
+        self.lock_chain().response_id.clone()
+    }
+
+    /// Forget the last response: the next call starts a new conversation.
+    pub fn reset_chain(&self) {
+        *self.lock_chain() = ResponseChain::default();
+    }
+
+    fn lock_chain(&self) -> std::sync::MutexGuard<'_, ResponseChain> {
+        self.chain.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Responses API input for `messages`, and the response it continues.
+    ///
+    /// With `auto_chain`, messages that extend the conversation of the last
+    /// response are sent as the new items only, continuing from that
+    /// response; its output (the assistant message after the answered
+    /// messages) is already stored with it. With `auto_chain_reasoning`
+    /// and `store: false`, nothing is stored, so the whole conversation is
+    /// sent with the last response's reasoning items put back in place.
+    fn responses_input(&self, messages: &[LLMMessage]) -> (Vec<Value>, Option<String>) {
+        let chain = self.lock_chain();
+        let extends = !chain.messages.is_empty() && messages.starts_with(&chain.messages);
+        if extends {
+            let new = &messages[chain.messages.len()..];
+            if self.auto_chain && self.store != Some(false) {
+                if let Some(ref id) = chain.response_id {
+                    let answered = new
+                        .iter()
+                        .take_while(|m| m.get("role").and_then(Value::as_str) == Some("assistant"))
+                        .count();
+                    return (responses::input_items(&new[answered..]), Some(id.clone()));
+                }
+            }
+            if self.auto_chain_reasoning {
+                let mut input = responses::input_items(&chain.messages);
+                input.extend(chain.reasoning.iter().cloned());
+                input.extend(responses::input_items(new));
+                return (input, self.previous_response_id.clone());
+            }
+        }
+        (
+            responses::input_items(messages),
+            self.previous_response_id.clone(),
+        )
+    }
+
+    /// Remember the response to `messages` for the next call.
+    fn record_response(&self, messages: &[LLMMessage], response: &Value) {
+        if !self.auto_chain && !self.auto_chain_reasoning {
+            return;
+        }
+        let mut chain = self.lock_chain();
+        chain.response_id = response["id"].as_str().map(String::from);
+        chain.messages = messages.to_vec();
+        chain.reasoning = response["output"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| item["type"] == "reasoning")
+            .cloned()
+            .collect();
+    }
+
     /// Parse a Chat Completions API response.
     fn parse_completions_response(
         &self,
@@ -299,93 +446,174 @@ impl OpenAICompletion {
         Ok(Value::String(final_content))
     }
 
+    /// Token usage of a Completions or Responses API response, with the
+    /// cached prompt tokens taken out of the token details.
+    fn extract_token_usage(response: &Value) -> HashMap<String, Value> {
+        let Some(usage) = response.get("usage").and_then(Value::as_object) else {
+            return HashMap::new();
+        };
+        let mut usage: HashMap<String, Value> = usage.clone().into_iter().collect();
+        let cached = usage
+            .get("prompt_tokens_details")
+            .or_else(|| usage.get("input_tokens_details"))
+            .and_then(|details| details.get("cached_tokens"))
+            .cloned();
+        if let Some(cached) = cached {
+            usage.insert("cached_tokens".to_string(), cached);
+        }
+        usage
+    }
+
     /// Parse a Responses API response.
+    ///
+    /// Function calls come back as a chat assistant message with
+    /// `tool_calls` (as from the Completions API), unless
+    /// `parse_tool_outputs` asks for a [`ResponsesApiResult`].
     fn parse_responses_response(
         &self,
         response: &Value,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        // Responses API returns different structure
-        // Extract output items
-        let output = response.get("output").unwrap_or(response);
+        if let Some(error) = response.get("error").filter(|e| !e.is_null()) {
+            let message = error["message"].as_str().unwrap_or("unknown error");
+            return Err(format!("OpenAI Responses API error: {}", message).into());
+        }
+        if response["status"] == "incomplete" {
+            log::warn!(
+                "OpenAI response incomplete: {}",
+                response["incomplete_details"]["reason"]
+                    .as_str()
+                    .unwrap_or("unknown reason")
+            );
+        }
+        let output = response["output"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let text = self.state.apply_stop_words(&responses::output_text(output));
+
+        if let Some(usage) = response.get("usage") {
+            log::debug!(
+                "OpenAI token usage: input={}, output={}, total={}",
+                usage["input_tokens"].as_i64().unwrap_or(0),
+                usage["output_tokens"].as_i64().unwrap_or(0),
+                usage["total_tokens"].as_i64().unwrap_or(0),
+            );
+        }
 
         if self.parse_tool_outputs {
-            // Build ResponsesApiResult
-            let mut result = ResponsesApiResult::default();
-
-            // Get response ID for multi-turn
-            result.response_id = response
-                .get("id")
-                .and_then(|v| v.as_str())
-                .map(String::from);
-
-            // Parse output items
-            if let Some(items) = output.as_array() {
-                for item in items {
-                    let item_type = item.get("type").and_then(|t| t.as_str()).unwrap_or("");
-                    match item_type {
-                        "message" => {
-                            if let Some(content) = item.get("content") {
-                                if let Some(text) = content
-                                    .get(0)
-                                    .and_then(|c| c.get("text"))
-                                    .and_then(|t| t.as_str())
-                                {
-                                    result.text = self.state.apply_stop_words(text);
-                                }
-                            }
-                        }
-                        "web_search_call" => {
-                            result.web_search_results.push(item.clone());
-                        }
-                        "file_search_call" => {
-                            result.file_search_results.push(item.clone());
-                        }
-                        "code_interpreter_call" => {
-                            result.code_interpreter_results.push(item.clone());
-                        }
-                        "computer_call" => {
-                            result.computer_use_results.push(item.clone());
-                        }
-                        "reasoning" => {
-                            result.reasoning_summaries.push(item.clone());
-                        }
-                        "function_call" => {
-                            result.function_calls.push(item.clone());
-                        }
-                        _ => {}
-                    }
-                }
+            let mut result = ResponsesApiResult {
+                text,
+                response_id: response["id"].as_str().map(String::from),
+                ..Default::default()
+            };
+            for item in output {
+                let results = match item["type"].as_str().unwrap_or("") {
+                    "web_search_call" => &mut result.web_search_results,
+                    "file_search_call" => &mut result.file_search_results,
+                    "code_interpreter_call" => &mut result.code_interpreter_results,
+                    "computer_call" => &mut result.computer_use_results,
+                    "reasoning" => &mut result.reasoning_summaries,
+                    "function_call" => &mut result.function_calls,
+                    _ => continue,
+                };
+                results.push(item.clone());
             }
-
             return Ok(serde_json::to_value(result)?);
         }
 
-        // Simple text extraction
-        let text = if let Some(items) = output.as_array() {
-            items
+        if let Some(message) = responses::tool_calls_message(output, &text) {
+            return Ok(message);
+        }
+        Ok(Value::String(text))
+    }
+
+    /// Build the request body for the Responses API from input items.
+    ///
+    /// Built-in tools come first, then the function tools (given in chat
+    /// format, as for [`build_request_body`](Self::build_request_body)).
+    pub fn build_responses_body(
+        &self,
+        input: Vec<Value>,
+        tools: Option<&[Value]>,
+        previous_response_id: Option<&str>,
+    ) -> Value {
+        let mut body = serde_json::json!({
+            "model": self.state.model,
+            "input": input,
+        });
+
+        if let Some(ref instructions) = self.instructions {
+            body["instructions"] = serde_json::json!(instructions);
+        }
+        if let Some(id) = previous_response_id {
+            body["previous_response_id"] = serde_json::json!(id);
+        }
+        if let Some(store) = self.store {
+            body["store"] = serde_json::json!(store);
+        }
+        let mut include = self.include.clone().unwrap_or_default();
+        if self.auto_chain_reasoning && self.store == Some(false) {
+            let encrypted = "reasoning.encrypted_content".to_string();
+            if !include.contains(&encrypted) {
+                include.push(encrypted);
+            }
+        }
+        if !include.is_empty() {
+            body["include"] = serde_json::json!(include);
+        }
+        if let Some(temp) = self.state.temperature {
+            body["temperature"] = serde_json::json!(temp);
+        }
+        if let Some(max_tokens) = self.max_completion_tokens.or(self.max_tokens) {
+            body["max_output_tokens"] = serde_json::json!(max_tokens);
+        }
+        if let Some(top_p) = self.top_p {
+            body["top_p"] = serde_json::json!(top_p);
+        }
+        if let Some(ref effort) = self.reasoning_effort {
+            body["reasoning"] = serde_json::json!({"effort": effort});
+        }
+        if let Some(ref format) = self.response_format {
+            body["text"] = serde_json::json!({"format": responses::text_format(format)});
+        }
+
+        let mut all_tools: Vec<Value> = self
+            .builtin_tools
+            .iter()
+            .flatten()
+            .map(|name| serde_json::json!({"type": builtin_tool_type(name).unwrap_or(name)}))
+            .collect();
+        all_tools.extend(
+            tools
+                .unwrap_or_default()
                 .iter()
-                .filter_map(|item| {
-                    if item.get("type").and_then(|t| t.as_str()) == Some("message") {
-                        item.get("content")
-                            .and_then(|c| c.get(0))
-                            .and_then(|c| c.get("text"))
-                            .and_then(|t| t.as_str())
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join("")
-        } else {
-            output
-                .get("content")
-                .and_then(|c| c.as_str())
-                .unwrap_or("")
-                .to_string()
-        };
+                .map(responses::response_tool),
+        );
+        if !all_tools.is_empty() {
+            body["tools"] = Value::Array(all_tools);
+            body["tool_choice"] = serde_json::json!("auto");
+        }
 
-        let final_text = self.state.apply_stop_words(&text);
-        Ok(Value::String(final_text))
+        body
+    }
+
+    /// The body of the request for `messages` and `tools` in the configured
+    /// API mode, and the endpoint it is posted to.
+    fn request(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> (Value, String) {
+        let base_url = self.api_base_url();
+        match self.api {
+            OpenAIApiMode::Completions => (
+                self.build_request_body(messages, tools),
+                format!("{}/chat/completions", base_url),
+            ),
+            OpenAIApiMode::Responses => {
+                let (input, previous) = self.responses_input(messages);
+                (
+                    self.build_responses_body(input, tools, previous.as_deref()),
+                    format!("{}/responses", base_url),
+                )
+            }
+        }
     }
 
     /// Build the request body for the Chat Completions API.
@@ -419,6 +647,15 @@ impl OpenAICompletion {
         if let Some(seed) = self.seed {
             body["seed"] = serde_json::json!(seed);
         }
+        if let Some(logprobs) = self.logprobs {
+            body["logprobs"] = serde_json::json!(logprobs);
+        }
+        if let Some(top_logprobs) = self.top_logprobs {
+            body["top_logprobs"] = serde_json::json!(top_logprobs);
+        }
+        if let Some(ref logit_bias) = self.logit_bias {
+            body["logit_bias"] = serde_json::json!(logit_bias);
+        }
         if let Some(ref effort) = self.reasoning_effort {
             body["reasoning_effort"] = serde_json::json!(effort);
         }
@@ -431,11 +668,21 @@ impl OpenAICompletion {
                 body["tool_choice"] = serde_json::json!("auto");
             }
         }
+        // Additional parameters never override the ones set above
+        for (key, value) in &self.state.additional_params {
+            if body.get(key).is_none() {
+                body[key.as_str()] = value.clone();
+            }
+        }
 
         body
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
 #[async_trait]
 impl BaseLLM for OpenAICompletion {
     fn model(&self) -> &str {
@@ -529,126 +776,112 @@ impl BaseLLM for OpenAICompletion {
         );
 
         // Validate API key
-        let api_key = self.state.api_key.as_ref().ok_or_else(|| {
-            "OpenAI API key not set. Set OPENAI_API_KEY environment variable or pass api_key to constructor."
-        })?;
-
-        // Build request body
-        let tools_slice = tools.as_deref();
-        let body = self.build_request_body(&messages, tools_slice);
+        let api_key = self.state.resolve_api_key("OPENAI_API_KEY").await?;
+        if api_key.is_none() && self.api_key_required {
+            return Err(
+                "OpenAI API key not set. Set OPENAI_API_KEY environment variable or pass api_key to constructor."
+                    .into(),
+            );
+        }
 
-        // Determine endpoint
-        let base_url = self.api_base_url();
-        let endpoint = match self.api {
-            OpenAIApiMode::Completions => format!("{}/chat/completions", base_url),
-            OpenAIApiMode::Responses => format!("{}/chat/responses", base_url),
-        };
+        // Build request body and determine endpoint
+        let (body, endpoint) = self.request(&messages, tools.as_deref());
 
         // Build HTTP client with timeout
         let timeout_secs = self.timeout.unwrap_or(120.0);
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs_f64(timeout_secs))
-            .build()?;
-
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                log::warn!(
-                    "OpenAI API retry attempt {} after {:?}",
-                    attempt,
-                    retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2; // Exponential backoff
-            }
-
-            // Build request
-            let mut request = client
-                .post(&endpoint)
-                .header("Content-Type", "application/json")
-                .header("Authorization", format!("Bearer {}", api_key));
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs_f64(timeout_secs))?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("OpenAI", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("openai").await {
+                    return Err(fault.into());
+                }
 
-            // Add organization header if set
-            if let Some(ref org) = self.organization {
-                request = request.header("OpenAI-Organization", org);
-            }
+                // Build request
+                let mut request = client
+                    .post(&endpoint)
+                    .header("Content-Type", "application/json");
+                if let Some(ref api_key) = api_key {
+                    request = request.header(
+                        "Authorization",
+                        format!("Bearer {}", api_key.expose_secret()),
+                    );
+                }
 
-            // Add project header if set
-            if let Some(ref proj) = self.project {
-                request = request.header("OpenAI-Project-Id", proj);
-            }
+                // Add organization header if set
+                if let Some(ref org) = self.organization {
+                    request = request.header("OpenAI-Organization", org);
+                }
 
-            // Add default headers
-            if let Some(ref headers) = self.default_headers {
-                for (k, v) in headers {
-                    request = request.header(k, v);
+                // Add project header if set
+                if let Some(ref proj) = self.project {
+                    request = request.header("OpenAI-Project-Id", proj);
                 }
-            }
 
-            // Send request
-            let response = match request.json(&body).send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+                // Add default headers
+                if let Some(ref headers) = self.default_headers {
+                    for (k, v) in headers {
+                        request = request.header(k, v);
+                    }
                 }
-            };
 
-            let status = response.status();
+                // Send request through the interceptor, if any
+                let mut request = request.json(&body).build()?;
+                if let Some(ref interceptor) = self.interceptor {
+                    request = interceptor.aon_outbound(request).await;
+                }
+                let response = client.execute(request).await?;
+                Ok(match self.interceptor {
+                    Some(ref interceptor) => interceptor.aon_inbound(response).await,
+                    None => response,
+                })
+            })
+            .await?;
 
-            // Handle rate limiting
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some("Rate limited by OpenAI API (429)".into());
-                continue;
-            }
+        let status = response.status();
 
-            // Handle server errors (5xx)
-            if status.is_server_error() {
-                last_error = Some(format!("OpenAI API server error: {}", status).into());
-                continue;
-            }
+        // Parse response body
+        let response_text = response.text().await?;
 
-            // Parse response body
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
-                }
-            };
+        // Handle errors that were not retried
+        if !status.is_success() {
+            return Err(format!("OpenAI API error ({}): {}", status, response_text).into());
+        }
 
-            // Handle client errors (4xx)
-            if status.is_client_error() {
-                return Err(format!("OpenAI API error ({}): {}", status, response_text).into());
+        // Parse JSON response
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse OpenAI response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
 
-            // Parse JSON response
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse OpenAI response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
-
-            // Extract content based on API mode
-            let result = match self.api {
-                OpenAIApiMode::Completions => self.parse_completions_response(&response_json)?,
-                OpenAIApiMode::Responses => self.parse_responses_response(&response_json)?,
-            };
+        // Extract content based on API mode
+        let result = match self.api {
+            OpenAIApiMode::Completions => self.parse_completions_response(&response_json)?,
+            OpenAIApiMode::Responses => {
+                let result = self.parse_responses_response(&response_json)?;
+                self.record_response(&messages, &response_json);
+                result
+            }
+        };
 
-            return Ok(result);
-        }
+        // Record token usage
+        self.state
+            .track_token_usage_internal(&Self::extract_token_usage(&response_json));
+        self.state.last_response.set(&response_json);
 
-        // All retries exhausted
-        Err(last_error.unwrap_or_else(|| "OpenAI API call failed after all retries".into()))
+        Ok(result)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
@@ -658,4 +891,134 @@ impl BaseLLM for OpenAICompletion {
     fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
         self.state.track_token_usage_internal(usage_data);
     }
+
+    fn request_identity(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Option<Value> {
+        let (body, endpoint) = self.request(messages, tools);
+        Some(serde_json::json!({
+            "endpoint": endpoint,
+            "query": self.default_query,
+            "headers": self.default_headers,
+            "organization": self.organization,
+            "project": self.project,
+            "credential": self.state.credential_hash("OPENAI_API_KEY"),
+            "body": body,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::post;
+    use axum::Json;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_llm_config_passes_generation_params() {
+        let mut llm = LLM::new("gpt-4o")
+            .temperature(0.2)
+            .max_tokens(256)
+            .stop(vec!["Observation:".to_string()]);
+        llm.top_p = Some(0.9);
+        llm.seed = Some(7);
+        llm.logprobs = Some(1);
+        llm.response_format = Some(json!({"type": "json_object"}));
+        llm.additional_params
+            .insert("user".to_string(), json!("crew-1"));
+        llm.additional_params.insert("seed".to_string(), json!(99));
+
+        let body = OpenAICompletion::from_llm_config(&llm).build_request_body(&[], None);
+        assert_eq!(body["temperature"], json!(0.2));
+        assert_eq!(body["max_tokens"], json!(256));
+        assert_eq!(body["top_p"], json!(0.9));
+        assert_eq!(body["stop"], json!(["Observation:"]));
+        assert_eq!(body["seed"], json!(7));
+        assert_eq!(body["logprobs"], json!(true));
+        assert_eq!(body["response_format"]["type"], "json_object");
+        assert_eq!(body["user"], "crew-1");
+    }
+
+    #[tokio::test]
+    async fn test_responses_tool_round_trip_chains_responses() {
+        let requests = Arc::new(Mutex::new(Vec::<Value>::new()));
+        let seen = requests.clone();
+        let router = axum::Router::new().route(
+            "/v1/responses",
+            post(move |Json(body): Json<Value>| {
+                let turn = {
+                    let mut seen = seen.lock().unwrap();
+                    seen.push(body);
+                    seen.len()
+                };
+                async move {
+                    Json(match turn {
+                        1 => json!({"id": "resp_1", "output": [
+                            {"type": "web_search_call", "id": "ws_1", "status": "completed"},
+                            {"type": "function_call", "call_id": "call_1", "name": "lookup",
+                             "arguments": "{\"q\":\"answer\"}"},
+                        ]}),
+                        _ => json!({"id": "resp_2", "output": [
+                            {"type": "message", "role": "assistant", "content": [
+                                {"type": "output_text", "text": "The answer is 42"},
+                            ]},
+                        ]}),
+                    })
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let mut llm = OpenAICompletion::new("gpt-4.1", Some("sk-test".into()), Some(url));
+        llm.api = OpenAIApiMode::Responses;
+        llm.builtin_tools = Some(vec!["web_search".into()]);
+        llm.auto_chain = true;
+        let tools = vec![json!({"type": "function", "function": {
+            "name": "lookup", "parameters": {"type": "object"}
+        }})];
+        let mut messages: Vec<LLMMessage> = vec![
+            serde_json::from_value(json!({"role": "system", "content": "Be brief."})).unwrap(),
+            serde_json::from_value(json!({"role": "user", "content": "What is the answer?"}))
+                .unwrap(),
+        ];
+
+        let reply = llm
+            .acall(messages.clone(), Some(tools.clone()), None)
+            .await
+            .unwrap();
+        let tool_calls = reply["tool_calls"].as_array().unwrap();
+        assert_eq!(tool_calls[0]["id"], "call_1");
+        assert_eq!(tool_calls[0]["function"]["name"], "lookup");
+
+        messages.push(
+            serde_json::from_value(
+                json!({"role": "assistant", "content": null, "tool_calls": tool_calls}),
+            )
+            .unwrap(),
+        );
+        messages.push(
+            serde_json::from_value(
+                json!({"role": "tool", "tool_call_id": "call_1", "content": "42"}),
+            )
+            .unwrap(),
+        );
+        let reply = llm.acall(messages, Some(tools), None).await.unwrap();
+        assert_eq!(reply, "The answer is 42");
+        assert_eq!(llm.last_response_id().as_deref(), Some("resp_2"));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0]["input"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            requests[0]["tools"][0],
+            json!({"type": "web_search_preview"})
+        );
+        assert_eq!(requests[0]["tools"][1]["name"], "lookup");
+        assert!(requests[0].get("previous_response_id").is_none());
+        assert_eq!(requests[1]["previous_response_id"], "resp_1");
+        assert_eq!(
+            requests[1]["input"],
+            json!([{"type": "function_call_output", "call_id": "call_1", "output": "42"}])
+        );
+    }
 }
@@ -0,0 +1,211 @@
+//! Responses API request and response mapping.
+//!
+//! The agent executor speaks the Chat Completions message format: an
+//! assistant message carries `tool_calls`, and each result comes back as a
+//! `tool` message with the `tool_call_id`. The Responses API wants input
+//! items instead (`function_call`, `function_call_output`) and returns
+//! function calls as output items. These helpers translate between the two,
+//! so a provider in Responses mode runs the same tool round trips as one in
+//! Completions mode.
+
+use serde_json::{json, Value};
+
+use crate::llms::base_llm::LLMMessage;
+
+/// What a provider with `auto_chain` remembers of its last response.
+#[derive(Debug, Clone, Default)]
+pub(super) struct ResponseChain {
+    /// ID of the last response.
+    pub response_id: Option<String>,
+    /// Messages the last response answered.
+    pub messages: Vec<LLMMessage>,
+    /// Reasoning items of the last response, with their encrypted content,
+    /// for `auto_chain_reasoning` without server-side storage.
+    pub reasoning: Vec<Value>,
+}
+
+/// Responses API input items for chat-format `messages`.
+pub(super) fn input_items(messages: &[LLMMessage]) -> Vec<Value> {
+    let mut items = Vec::new();
+    for message in messages {
+        let role = message
+            .get("role")
+            .and_then(Value::as_str)
+            .unwrap_or("user");
+        let content = message.get("content").cloned().unwrap_or(Value::Null);
+        match role {
+            "tool" => items.push(json!({
+                "type": "function_call_output",
+                "call_id": message.get("tool_call_id").cloned().unwrap_or(Value::Null),
+                "output": match content {
+                    Value::String(text) => text,
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                },
+            })),
+            _ => {
+                if !is_empty_content(&content) {
+                    items.push(json!({"role": role, "content": input_content(content)}));
+                }
+                let tool_calls = message.get("tool_calls").and_then(Value::as_array);
+                for call in tool_calls.into_iter().flatten() {
+                    items.push(json!({
+                        "type": "function_call",
+                        "call_id": call["id"],
+                        "name": call["function"]["name"],
+                        "arguments": call["function"]["arguments"],
+                    }));
+                }
+            }
+        }
+    }
+    items
+}
+
+/// Chat content parts as Responses input content (`input_text`,
+/// `input_image`); plain text stays as it is.
+fn input_content(content: Value) -> Value {
+    let Value::Array(parts) = content else {
+        return content;
+    };
+    Value::Array(
+        parts
+            .into_iter()
+            .map(|part| match part["type"].as_str() {
+                Some("text") => json!({"type": "input_text", "text": part["text"]}),
+                Some("image_url") => {
+                    let url = part["image_url"]
+                        .get("url")
+                        .unwrap_or(&part["image_url"])
+                        .clone();
+                    json!({"type": "input_image", "image_url": url})
+                }
+                _ => part,
+            })
+            .collect(),
+    )
+}
+
+fn is_empty_content(content: &Value) -> bool {
+    match content {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        Value::Array(parts) => parts.is_empty(),
+        _ => false,
+    }
+}
+
+/// A chat-format tool schema as a Responses function tool; other tools
+/// (already in Responses format) pass through.
+pub(super) fn response_tool(tool: &Value) -> Value {
+    match tool.get("function") {
+        Some(function) if tool["type"] == "function" => {
+            let mut converted = json!({"type": "function", "name": function["name"]});
+            for key in ["description", "parameters", "strict"] {
+                if let Some(value) = function.get(key) {
+                    converted[key] = value.clone();
+                }
+            }
+            converted
+        }
+        _ => tool.clone(),
+    }
+}
+
+/// A chat `response_format` as the Responses `text.format`.
+pub(super) fn text_format(response_format: &Value) -> Value {
+    match response_format.get("json_schema") {
+        Some(schema) if response_format["type"] == "json_schema" => {
+            let mut format = json!({"type": "json_schema"});
+            if let Some(fields) = schema.as_object() {
+                for (key, value) in fields {
+                    format[key] = value.clone();
+                }
+            }
+            format
+        }
+        _ => response_format.clone(),
+    }
+}
+
+/// Text of the `message` items in a response's output.
+pub(super) fn output_text(output: &[Value]) -> String {
+    output
+        .iter()
+        .filter(|item| item["type"] == "message")
+        .flat_map(|item| item["content"].as_array().into_iter().flatten())
+        .filter_map(|part| part["text"].as_str())
+        .collect()
+}
+
+/// The `function_call` output items as a chat assistant message with
+/// `tool_calls`, for the executor to run; `None` without function calls.
+pub(super) fn tool_calls_message(output: &[Value], text: &str) -> Option<Value> {
+    let tool_calls: Vec<Value> = output
+        .iter()
+        .filter(|item| item["type"] == "function_call")
+        .map(|call| {
+            json!({
+                "id": call["call_id"],
+                "type": "function",
+                "function": {"name": call["name"], "arguments": call["arguments"]},
+            })
+        })
+        .collect();
+    if tool_calls.is_empty() {
+        return None;
+    }
+    Some(json!({
+        "role": "assistant",
+        "content": if text.is_empty() { Value::Null } else { Value::from(text) },
+        "tool_calls": tool_calls,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(fields: Value) -> LLMMessage {
+        serde_json::from_value(fields).unwrap()
+    }
+
+    #[test]
+    fn test_chat_messages_become_input_items() {
+        let messages = [
+            message(json!({"role": "user", "content": [
+                {"type": "text", "text": "What is this?"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/a.png"}},
+            ]})),
+            message(json!({"role": "assistant", "content": null, "tool_calls": [
+                {"id": "call_1", "type": "function",
+                 "function": {"name": "lookup", "arguments": "{\"q\":\"a.png\"}"}},
+            ]})),
+            message(json!({"role": "tool", "tool_call_id": "call_1", "content": "a cat"})),
+        ];
+        let items = input_items(&messages);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0]["content"][0]["type"], "input_text");
+        assert_eq!(
+            items[0]["content"][1]["image_url"],
+            "https://example.com/a.png"
+        );
+        assert_eq!(items[1]["type"], "function_call");
+        assert_eq!(items[1]["name"], "lookup");
+        assert_eq!(
+            items[2],
+            json!({"type": "function_call_output", "call_id": "call_1", "output": "a cat"})
+        );
+
+        let tool = response_tool(&json!({"type": "function", "function": {
+            "name": "lookup", "description": "Look up", "parameters": {"type": "object"}
+        }}));
+        assert_eq!(tool["name"], "lookup");
+        assert_eq!(tool["parameters"]["type"], "object");
+        let format = text_format(&json!({"type": "json_schema", "json_schema": {
+            "name": "Answer", "schema": {"type": "object"}, "strict": true
+        }}));
+        assert_eq!(format["name"], "Answer");
+        assert_eq!(format["strict"], true);
+    }
+}
@@ -0,0 +1,398 @@
+//! Multi-region endpoint selection with latency probing.
+//!
+//! Cloud providers expose the same models from several regional endpoints
+//! (Azure OpenAI resources per region, Bedrock runtime per AWS region,
+//! Vertex AI per Google Cloud location). A [`RegionSelector`] holds the
+//! configured endpoints for one provider, probes them for latency, and
+//! answers which endpoint calls should go to:
+//!
+//! 1. A manually pinned region always wins.
+//! 2. Otherwise the fastest healthy endpoint from the last probe.
+//! 3. Otherwise (never probed, or nothing healthy) the first configured one.
+//!
+//! Probing is optional. Run [`RegionSelector::probe`] once at startup, or
+//! [`RegionSelector::spawn_periodic_probe`] to refresh on a schedule. Each
+//! probe result is reported to [`telemetry`](crate::telemetry) as a
+//! `region_probe` span.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let selector = RegionSelector::bedrock(&["us-east-1", "us-west-2", "eu-central-1"]);
+//! selector.probe().await;
+//!
+//! let provider = BedrockCompletion::new("anthropic.claude-3-5-sonnet-20241022-v2:0", None, None)
+//!     .with_region_selector(selector.clone());
+//!
+//! // Force a region regardless of probe results:
+//! selector.pin("eu-central-1")?;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default per-endpoint probe timeout.
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// ---------------------------------------------------------------------------
+// RegionalEndpoint / ProbeResult
+// ---------------------------------------------------------------------------
+
+/// A provider endpoint in a specific region.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionalEndpoint {
+    /// Region identifier (e.g. "us-east-1", "westeurope", "us-central1").
+    pub region: String,
+    /// Base URL of the endpoint in that region.
+    pub url: String,
+}
+
+impl RegionalEndpoint {
+    /// Create a new regional endpoint.
+    pub fn new(region: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            region: region.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// Outcome of probing a single regional endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProbeResult {
+    /// Region that was probed.
+    pub region: String,
+    /// URL that was probed.
+    pub url: String,
+    /// Round-trip latency in milliseconds, if a response arrived.
+    pub latency_ms: Option<u64>,
+    /// Whether the endpoint answered without a server error.
+    pub healthy: bool,
+    /// Error message for unreachable or failing endpoints.
+    pub error: Option<String>,
+    /// When the probe ran.
+    pub probed_at: DateTime<Utc>,
+}
+
+// ---------------------------------------------------------------------------
+// RegionSelector
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default)]
+struct SelectorState {
+    pinned: Option<String>,
+    results: HashMap<String, ProbeResult>,
+}
+
+/// Chooses the regional endpoint for one provider.
+///
+/// Cloning is cheap and clones share pin and probe state, so the same
+/// selector can be handed to several provider instances and to a
+/// background probe task.
+#[derive(Debug, Clone)]
+pub struct RegionSelector {
+    provider: String,
+    endpoints: Arc<Vec<RegionalEndpoint>>,
+    probe_timeout: Duration,
+    state: Arc<RwLock<SelectorState>>,
+}
+
+impl RegionSelector {
+    /// Create a selector for `provider` over the given endpoints.
+    pub fn new(provider: impl Into<String>, endpoints: Vec<RegionalEndpoint>) -> Self {
+        Self {
+            provider: provider.into(),
+            endpoints: Arc::new(endpoints),
+            probe_timeout: DEFAULT_PROBE_TIMEOUT,
+            state: Arc::new(RwLock::new(SelectorState::default())),
+        }
+    }
+
+    /// Selector over Azure OpenAI resources, given as `(region, endpoint)` pairs.
+    pub fn azure<R, U>(resources: impl IntoIterator<Item = (R, U)>) -> Self
+    where
+        R: Into<String>,
+        U: Into<String>,
+    {
+        let endpoints = resources
+            .into_iter()
+            .map(|(region, url)| RegionalEndpoint::new(region, url))
+            .collect();
+        Self::new("azure", endpoints)
+    }
+
+    /// Selector over Bedrock runtime endpoints in the given AWS regions.
+    pub fn bedrock(regions: &[&str]) -> Self {
+        let endpoints = regions
+            .iter()
+            .map(|r| {
+                RegionalEndpoint::new(*r, format!("https://bedrock-runtime.{}.amazonaws.com", r))
+            })
+            .collect();
+        Self::new("bedrock", endpoints)
+    }
+
+    /// Selector over Vertex AI endpoints in the given Google Cloud locations.
+    pub fn gemini(locations: &[&str]) -> Self {
+        let endpoints = locations
+            .iter()
+            .map(|l| RegionalEndpoint::new(*l, format!("https://{}-aiplatform.googleapis.com", l)))
+            .collect();
+        Self::new("gemini", endpoints)
+    }
+
+    /// Set the per-endpoint probe timeout.
+    pub fn with_probe_timeout(mut self, timeout: Duration) -> Self {
+        self.probe_timeout = timeout;
+        self
+    }
+
+    /// Provider name this selector serves.
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    /// All configured endpoints, in configuration order.
+    pub fn endpoints(&self) -> &[RegionalEndpoint] {
+        &self.endpoints
+    }
+
+    /// Pin calls to `region`, bypassing probe results.
+    ///
+    /// Returns an error if the region is not configured.
+    pub fn pin(&self, region: &str) -> Result<(), String> {
+        if !self.endpoints.iter().any(|e| e.region == region) {
+            return Err(format!(
+                "{} region '{}' is not configured",
+                self.provider, region
+            ));
+        }
+        if let Ok(mut state) = self.state.write() {
+            state.pinned = Some(region.to_string());
+        }
+        Ok(())
+    }
+
+    /// Remove a manual pin and go back to latency-based selection.
+    pub fn unpin(&self) {
+        if let Ok(mut state) = self.state.write() {
+            state.pinned = None;
+        }
+    }
+
+    /// Currently pinned region, if any.
+    pub fn pinned(&self) -> Option<String> {
+        self.state.read().ok().and_then(|s| s.pinned.clone())
+    }
+
+    /// Latest probe results, in configuration order.
+    pub fn probe_results(&self) -> Vec<ProbeResult> {
+        let Ok(state) = self.state.read() else {
+            return Vec::new();
+        };
+        self.endpoints
+            .iter()
+            .filter_map(|e| state.results.get(&e.region).cloned())
+            .collect()
+    }
+
+    /// The endpoint calls should currently be routed to.
+    pub fn select(&self) -> Option<RegionalEndpoint> {
+        let state = self.state.read().ok()?;
+
+        if let Some(pinned) = &state.pinned {
+            if let Some(ep) = self.endpoints.iter().find(|e| &e.region == pinned) {
+                return Some(ep.clone());
+            }
+        }
+
+        self.endpoints
+            .iter()
+            .filter_map(|e| {
+                let result = state.results.get(&e.region)?;
+                match (result.healthy, result.latency_ms) {
+                    (true, Some(ms)) => Some((ms, e)),
+                    _ => None,
+                }
+            })
+            .min_by_key(|(ms, _)| *ms)
+            .map(|(_, e)| e.clone())
+            .or_else(|| self.endpoints.first().cloned())
+    }
+
+    /// Region of the selected endpoint.
+    pub fn selected_region(&self) -> Option<String> {
+        self.select().map(|e| e.region)
+    }
+
+    /// Probe every endpoint concurrently and record the results.
+    ///
+    /// Any HTTP response below 500 counts as healthy: unauthenticated
+    /// requests are expected to be rejected, and the round trip is what
+    /// is being measured.
+    pub async fn probe(&self) -> Vec<ProbeResult> {
+        let client = match reqwest::Client::builder()
+            .timeout(self.probe_timeout)
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("RegionSelector: failed to build probe client: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let probes = self.endpoints.iter().map(|endpoint| {
+            let client = client.clone();
+            async move {
+                let start = Instant::now();
+                let response = client.get(&endpoint.url).send().await;
+                let elapsed = start.elapsed().as_millis() as u64;
+                let (latency_ms, healthy, error) = match response {
+                    Ok(resp) if resp.status().is_server_error() => (
+                        Some(elapsed),
+                        false,
+                        Some(format!("HTTP {}", resp.status())),
+                    ),
+                    Ok(_) => (Some(elapsed), true, None),
+                    Err(e) => (None, false, Some(e.to_string())),
+                };
+                ProbeResult {
+                    region: endpoint.region.clone(),
+                    url: endpoint.url.clone(),
+                    latency_ms,
+                    healthy,
+                    error,
+                    probed_at: Utc::now(),
+                }
+            }
+        });
+
+        let results = futures::future::join_all(probes).await;
+        self.record(results.clone());
+        results
+    }
+
+    /// Store probe results and report them to telemetry.
+    ///
+    /// Exposed so results from an external health checker can be fed in.
+    pub fn record(&self, results: Vec<ProbeResult>) {
+        if let Ok(telemetry) = crate::telemetry::telemetry().lock() {
+            for result in &results {
+                telemetry.region_probe(&self.provider, result).end();
+            }
+        }
+        if let Ok(mut state) = self.state.write() {
+            for result in results {
+                state.results.insert(result.region.clone(), result);
+            }
+        }
+        log::debug!(
+            "RegionSelector: {} now routes to {:?}",
+            self.provider,
+            self.selected_region()
+        );
+    }
+
+    /// Re-probe every `interval` on the current Tokio runtime.
+    ///
+    /// The first probe runs immediately. Abort the returned handle to stop.
+    pub fn spawn_periodic_probe(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let selector = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                selector.probe().await;
+            }
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(region: &str, latency_ms: Option<u64>, healthy: bool) -> ProbeResult {
+        ProbeResult {
+            region: region.to_string(),
+            url: format!("https://{}.example.com", region),
+            latency_ms,
+            healthy,
+            error: None,
+            probed_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_select_defaults_to_first_endpoint() {
+        let selector = RegionSelector::bedrock(&["us-east-1", "us-west-2"]);
+        assert_eq!(selector.selected_region().as_deref(), Some("us-east-1"));
+        assert_eq!(
+            selector.select().unwrap().url,
+            "https://bedrock-runtime.us-east-1.amazonaws.com"
+        );
+    }
+
+    #[test]
+    fn test_select_fastest_healthy() {
+        let selector = RegionSelector::gemini(&["us-central1", "europe-west4", "asia-east1"]);
+        selector.record(vec![
+            result("us-central1", Some(180), true),
+            result("europe-west4", Some(40), true),
+            result("asia-east1", Some(10), false),
+        ]);
+        assert_eq!(selector.selected_region().as_deref(), Some("europe-west4"));
+        assert_eq!(selector.probe_results().len(), 3);
+    }
+
+    #[test]
+    fn test_pin_overrides_probe() {
+        let selector = RegionSelector::azure([
+            ("eastus", "https://eastus.openai.azure.com"),
+            ("westeurope", "https://weu.openai.azure.com"),
+        ]);
+        selector.record(vec![
+            result("eastus", Some(20), true),
+            result("westeurope", Some(90), true),
+        ]);
+        selector.pin("westeurope").unwrap();
+        assert_eq!(selector.selected_region().as_deref(), Some("westeurope"));
+
+        selector.unpin();
+        assert_eq!(selector.selected_region().as_deref(), Some("eastus"));
+
+        assert!(selector.pin("mars-north-1").is_err());
+    }
+
+    #[test]
+    fn test_all_unhealthy_falls_back_to_first() {
+        let selector = RegionSelector::bedrock(&["us-east-1", "us-west-2"]);
+        selector.record(vec![
+            result("us-east-1", None, false),
+            result("us-west-2", None, false),
+        ]);
+        assert_eq!(selector.selected_region().as_deref(), Some("us-east-1"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_unreachable_endpoint() {
+        let selector = RegionSelector::new(
+            "test",
+            vec![RegionalEndpoint::new("local", "http://127.0.0.1:1")],
+        )
+        .with_probe_timeout(Duration::from_millis(500));
+        let results = selector.probe().await;
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].healthy);
+        assert!(results[0].error.is_some());
+    }
+}
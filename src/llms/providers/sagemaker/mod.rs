@@ -0,0 +1,601 @@
+//! AWS SageMaker endpoint completion provider.
+//!
+//! Calls a model deployed to a SageMaker real-time inference endpoint
+//! through the `InvokeEndpoint` runtime API, signed with AWS SigV4 like
+//! [`bedrock`](super::bedrock).
+//!
+//! # Features
+//!
+//! - SageMaker `InvokeEndpoint` runtime API
+//! - Chat completions payloads (LMI, TGI and vLLM containers with the
+//!   Messages API) and the raw text-generation payload of older TGI
+//!   containers; see [`SageMakerPayload`]
+//! - Inference components and production variants
+//! - AWS credential chain authentication; see
+//!   [`credentials`](super::bedrock::credentials)
+//! - Token usage tracking (chat completions payloads)
+//!
+//! The model name is the endpoint name, e.g. `my-llama-endpoint`.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::llms::providers::bedrock::credentials::{AwsCredentials, AwsCredentialsProvider};
+use crate::llms::providers::bedrock::sigv4;
+use crate::security::secrets::SecretString;
+use crate::types::usage_metrics::UsageMetrics;
+
+const SERVICE: &str = "sagemaker";
+
+// ---------------------------------------------------------------------------
+// Payload formats
+// ---------------------------------------------------------------------------
+
+/// Request and response format the endpoint's serving container speaks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SageMakerPayload {
+    /// OpenAI-style `{"messages": [...]}` in, `choices` out.
+    #[default]
+    Messages,
+    /// `{"inputs": "...", "parameters": {...}}` in,
+    /// `[{"generated_text": "..."}]` out. Messages are flattened into a
+    /// single prompt.
+    TextGeneration,
+}
+
+// ---------------------------------------------------------------------------
+// SageMakerCompletion provider
+// ---------------------------------------------------------------------------
+
+/// AWS SageMaker endpoint completion implementation.
+///
+/// # Example
+///
+/// ```ignore
+/// let provider = SageMakerCompletion::new(
+///     "my-llama-endpoint",
+///     None,   // region defaults to AWS_DEFAULT_REGION or us-east-1
+///     None,   // profile from AWS_PROFILE env var
+/// );
+/// let messages = vec![/* ... */];
+/// let response = provider.call(messages, None, None)?;
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SageMakerCompletion {
+    /// Shared base LLM state; `model` is the endpoint name.
+    #[serde(flatten)]
+    pub state: BaseLLMState,
+
+    /// AWS region name.
+    pub region_name: Option<String>,
+    /// AWS profile name.
+    pub profile_name: Option<String>,
+    /// Inference component to route to, on endpoints hosting several.
+    pub inference_component: Option<String>,
+    /// Production variant to route to.
+    pub target_variant: Option<String>,
+    /// Request and response format of the serving container.
+    pub payload: SageMakerPayload,
+
+    /// Request timeout in seconds.
+    pub timeout: Option<f64>,
+    /// Maximum number of retries, unless `state.retry_policy` is set.
+    pub max_retries: u32,
+    /// Maximum tokens in response.
+    pub max_tokens: Option<u32>,
+    /// Nucleus sampling parameter.
+    pub top_p: Option<f64>,
+
+    /// Where credentials come from (defaults to the AWS SDK credential
+    /// chain for `profile_name`).
+    #[serde(skip)]
+    pub credentials_provider: Option<Arc<AwsCredentialsProvider>>,
+}
+
+impl SageMakerCompletion {
+    /// Create a new SageMaker completion provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_name` - SageMaker endpoint name.
+    /// * `region_name` - Optional AWS region (defaults to AWS_DEFAULT_REGION or us-east-1).
+    /// * `profile_name` - Optional AWS profile (defaults to AWS_PROFILE env var).
+    pub fn new(
+        endpoint_name: impl Into<String>,
+        region_name: Option<String>,
+        profile_name: Option<String>,
+    ) -> Self {
+        let region_name = region_name
+            .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+            .or_else(|| std::env::var("AWS_REGION").ok())
+            .or_else(|| Some("us-east-1".to_string()));
+        let profile_name = profile_name.or_else(|| std::env::var("AWS_PROFILE").ok());
+
+        let credentials_provider = Arc::new(AwsCredentialsProvider::default_chain(
+            profile_name.as_deref(),
+            region_name.as_deref().unwrap_or("us-east-1"),
+        ));
+
+        let mut state = BaseLLMState::new(endpoint_name);
+        state.provider = "sagemaker".to_string();
+
+        Self {
+            state,
+            region_name,
+            profile_name,
+            inference_component: None,
+            target_variant: None,
+            payload: SageMakerPayload::default(),
+            timeout: None,
+            max_retries: 2,
+            max_tokens: None,
+            top_p: None,
+            credentials_provider: Some(credentials_provider),
+        }
+    }
+
+    /// Resolve credentials with `provider` (shared by clones, which then
+    /// share its cache) instead of the default chain.
+    pub fn with_credentials_provider(mut self, provider: Arc<AwsCredentialsProvider>) -> Self {
+        self.credentials_provider = Some(provider);
+        self
+    }
+
+    /// Speak `payload` to the serving container.
+    pub fn with_payload(mut self, payload: SageMakerPayload) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Route calls to an inference component of the endpoint.
+    pub fn with_inference_component(mut self, component: impl Into<String>) -> Self {
+        self.inference_component = Some(component.into());
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
+    /// AWS region calls are sent to.
+    pub fn effective_region(&self) -> String {
+        self.region_name
+            .clone()
+            .unwrap_or_else(|| "us-east-1".to_string())
+    }
+
+    /// Get the SageMaker runtime endpoint URL.
+    pub fn endpoint_url(&self) -> String {
+        format!("https://{}", self.host())
+    }
+
+    fn host(&self) -> String {
+        format!(
+            "runtime.sagemaker.{}.amazonaws.com",
+            self.effective_region()
+        )
+    }
+
+    /// Build the `InvokeEndpoint` URI path.
+    fn invocations_uri(&self) -> String {
+        format!("/endpoints/{}/invocations", self.state.model)
+    }
+
+    /// Build the request body in the configured payload format.
+    fn build_request_body(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Value {
+        match self.payload {
+            SageMakerPayload::Messages => {
+                let mut body = serde_json::json!({ "messages": messages });
+                if let Some(temp) = self.state.temperature {
+                    body["temperature"] = serde_json::json!(temp);
+                }
+                if let Some(max) = self.max_tokens {
+                    body["max_tokens"] = serde_json::json!(max);
+                }
+                if let Some(top_p) = self.top_p {
+                    body["top_p"] = serde_json::json!(top_p);
+                }
+                if !self.state.stop.is_empty() {
+                    body["stop"] = serde_json::json!(self.state.stop);
+                }
+                if let Some(tools) = tools {
+                    if !tools.is_empty() {
+                        body["tools"] = Value::Array(tools.to_vec());
+                    }
+                }
+                body
+            }
+            SageMakerPayload::TextGeneration => {
+                let mut parameters = serde_json::json!({ "return_full_text": false });
+                if let Some(temp) = self.state.temperature {
+                    parameters["temperature"] = serde_json::json!(temp);
+                }
+                if let Some(max) = self.max_tokens {
+                    parameters["max_new_tokens"] = serde_json::json!(max);
+                }
+                if let Some(top_p) = self.top_p {
+                    parameters["top_p"] = serde_json::json!(top_p);
+                }
+                if !self.state.stop.is_empty() {
+                    parameters["stop"] = serde_json::json!(self.state.stop);
+                }
+                serde_json::json!({
+                    "inputs": Self::format_prompt(messages),
+                    "parameters": parameters,
+                })
+            }
+        }
+    }
+
+    /// Flatten messages into a single prompt for text-generation payloads.
+    fn format_prompt(messages: &[LLMMessage]) -> String {
+        let mut prompt = String::new();
+        for msg in messages {
+            let role = msg.get("role").and_then(|v| v.as_str()).unwrap_or("user");
+            let content = msg.get("content").and_then(|v| v.as_str()).unwrap_or("");
+            prompt.push_str(&format!("{}: {}\n\n", role, content));
+        }
+        prompt.push_str("assistant:");
+        prompt
+    }
+
+    /// Parse an `InvokeEndpoint` response in the configured payload format.
+    fn parse_response(
+        &self,
+        response: &Value,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        let content = match self.payload {
+            SageMakerPayload::Messages => {
+                let message = response
+                    .get("choices")
+                    .and_then(|c| c.get(0))
+                    .and_then(|c| c.get("message"))
+                    .ok_or("No choices in SageMaker response")?;
+
+                if let Some(tool_calls) = message.get("tool_calls") {
+                    if tool_calls.as_array().is_some_and(|a| !a.is_empty()) {
+                        return Ok(serde_json::json!({
+                            "role": "assistant",
+                            "content": message.get("content").cloned().unwrap_or(Value::Null),
+                            "tool_calls": tool_calls,
+                        }));
+                    }
+                }
+                message.get("content").and_then(|c| c.as_str())
+            }
+            SageMakerPayload::TextGeneration => {
+                // A list of generations, or a single one
+                let generation = match response.as_array() {
+                    Some(items) => items.first(),
+                    None => Some(response),
+                };
+                generation.and_then(|g| g.get("generated_text").and_then(|t| t.as_str()))
+            }
+        }
+        .ok_or("No generated text in SageMaker response")?;
+
+        Ok(Value::String(self.state.apply_stop_words(content)))
+    }
+
+    /// Extract token usage from a chat completions response.
+    fn extract_token_usage(response: &Value) -> HashMap<String, Value> {
+        let mut usage = HashMap::new();
+        if let Some(usage_obj) = response.get("usage") {
+            let prompt = usage_obj
+                .get("prompt_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let completion = usage_obj
+                .get("completion_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(0);
+            let total = usage_obj
+                .get("total_tokens")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(prompt + completion);
+
+            usage.insert("prompt_tokens".to_string(), serde_json::json!(prompt));
+            usage.insert(
+                "completion_tokens".to_string(),
+                serde_json::json!(completion),
+            );
+            usage.insert("total_tokens".to_string(), serde_json::json!(total));
+        }
+        usage
+    }
+
+    /// Credentials for the next request.
+    async fn resolve_credentials(
+        &self,
+    ) -> Result<AwsCredentials, Box<dyn std::error::Error + Send + Sync>> {
+        match self.credentials_provider {
+            Some(ref provider) => Ok(provider.credentials().await?),
+            None => {
+                Err("AWS credentials not set (AWS_ACCESS_KEY_ID / AWS_SECRET_ACCESS_KEY)".into())
+            }
+        }
+    }
+
+    /// Sign a request using AWS SigV4 and return headers.
+    fn sign_request(
+        &self,
+        credentials: &AwsCredentials,
+        uri: &str,
+        payload: &[u8],
+    ) -> Vec<(String, String)> {
+        let mut headers = vec![
+            ("content-type".to_string(), "application/json".to_string()),
+            ("host".to_string(), self.host()),
+        ];
+        if let Some(ref component) = self.inference_component {
+            headers.push((
+                "x-amzn-sagemaker-inference-component".to_string(),
+                component.clone(),
+            ));
+        }
+        if let Some(ref variant) = self.target_variant {
+            headers.push((
+                "x-amzn-sagemaker-target-variant".to_string(),
+                variant.clone(),
+            ));
+        }
+        sigv4::sign(
+            &credentials.access_key_id,
+            credentials.secret_access_key.expose_secret(),
+            credentials
+                .session_token
+                .as_ref()
+                .map(SecretString::expose_secret),
+            &self.effective_region(),
+            SERVICE,
+            "POST",
+            uri,
+            headers,
+            payload,
+        )
+    }
+}
+
+#[async_trait]
+impl BaseLLM for SageMakerCompletion {
+    fn model(&self) -> &str {
+        &self.state.model
+    }
+
+    fn temperature(&self) -> Option<f64> {
+        self.state.temperature
+    }
+
+    fn stop(&self) -> &[String] {
+        &self.state.stop
+    }
+
+    fn set_stop(&mut self, stop: Vec<String>) {
+        self.state.stop = stop;
+    }
+
+    fn provider(&self) -> &str {
+        "sagemaker"
+    }
+
+    fn supports_function_calling(&self) -> bool {
+        // Depends on the serving container; only the Messages API has tools
+        self.payload == SageMakerPayload::Messages
+    }
+
+    fn supports_stop_words(&self) -> bool {
+        self.state.has_stop_words()
+    }
+
+    fn call(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        log::debug!(
+            "SageMakerCompletion.call: endpoint={}, region={}, messages={}, tools={:?}",
+            self.state.model,
+            self.effective_region(),
+            messages.len(),
+            tools.as_ref().map(|t| t.len()),
+        );
+
+        let rt = tokio::runtime::Runtime::new()?;
+        rt.block_on(self.acall(messages, tools, available_functions))
+    }
+
+    async fn acall(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        _available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        log::debug!(
+            "SageMakerCompletion.acall: endpoint={}, messages={}",
+            self.state.model,
+            messages.len(),
+        );
+
+        let tools_slice = tools.as_deref();
+        let body = self.build_request_body(&messages, tools_slice);
+        let payload = serde_json::to_vec(&body)?;
+
+        let uri = self.invocations_uri();
+        let endpoint = format!("{}{}", self.endpoint_url(), uri);
+
+        let timeout_secs = self.timeout.unwrap_or(120.0) as u64;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs(timeout_secs))?;
+
+        let credentials = self.resolve_credentials().await?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("SageMaker", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("sagemaker").await {
+                    return Err(fault.into());
+                }
+
+                // Sign the request (must re-sign each attempt for fresh timestamp)
+                let headers = self.sign_request(&credentials, &uri, &payload);
+
+                let mut request = client.post(&endpoint);
+                for (k, v) in &headers {
+                    request = request.header(k.as_str(), v.as_str());
+                }
+
+                Ok(request.body(payload.clone()).send().await?)
+            })
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(format!("SageMaker API error ({}): {}", status, response_text).into());
+        }
+
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse SageMaker response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
+            }
+        };
+
+        // Record token usage
+        let usage = Self::extract_token_usage(&response_json);
+        if !usage.is_empty() {
+            log::debug!("SageMaker usage: {:?}", usage);
+        }
+        self.state.track_token_usage_internal(&usage);
+        self.state.last_response.set(&response_json);
+
+        self.parse_response(&response_json)
+    }
+
+    fn get_token_usage_summary(&self) -> UsageMetrics {
+        self.state.get_token_usage_summary()
+    }
+
+    fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
+        self.state.track_token_usage_internal(usage_data);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(pairs: &[(&str, Value)]) -> LLMMessage {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sagemaker_endpoint() {
+        let provider =
+            SageMakerCompletion::new("my-llama-endpoint", Some("eu-west-1".to_string()), None);
+        assert_eq!(provider.provider(), "sagemaker");
+        assert_eq!(
+            provider.endpoint_url(),
+            "https://runtime.sagemaker.eu-west-1.amazonaws.com"
+        );
+        assert_eq!(
+            provider.invocations_uri(),
+            "/endpoints/my-llama-endpoint/invocations"
+        );
+    }
+
+    #[test]
+    fn test_sign_request_includes_routing_headers() {
+        let provider = SageMakerCompletion::new("ep", Some("us-west-2".to_string()), None)
+            .with_inference_component("llama-ic");
+        let credentials = AwsCredentials::new("AKIDEXAMPLE", "secret");
+        let headers = provider.sign_request(&credentials, "/endpoints/ep/invocations", b"{}");
+
+        let auth = &headers
+            .iter()
+            .find(|(k, _)| k == "authorization")
+            .unwrap()
+            .1;
+        assert!(auth.contains("/us-west-2/sagemaker/aws4_request"));
+        assert!(auth.contains("x-amzn-sagemaker-inference-component"));
+        assert!(headers
+            .iter()
+            .any(|(k, v)| k == "x-amzn-sagemaker-inference-component" && v == "llama-ic"));
+    }
+
+    #[test]
+    fn test_messages_payload_round_trip() {
+        let mut provider = SageMakerCompletion::new("ep", None, None);
+        provider.max_tokens = Some(256);
+        let messages = vec![msg(&[
+            ("role", serde_json::json!("user")),
+            ("content", serde_json::json!("Hello")),
+        ])];
+
+        let body = provider.build_request_body(&messages, None);
+        assert_eq!(body["messages"][0]["content"], "Hello");
+        assert_eq!(body["max_tokens"], 256);
+
+        let response = serde_json::json!({
+            "choices": [{"message": {"role": "assistant", "content": "Hi!"}}],
+            "usage": {"prompt_tokens": 3, "completion_tokens": 2}
+        });
+        assert_eq!(provider.parse_response(&response).unwrap(), "Hi!");
+        let usage = SageMakerCompletion::extract_token_usage(&response);
+        assert_eq!(usage["total_tokens"], 5);
+    }
+
+    #[test]
+    fn test_text_generation_payload_round_trip() {
+        let mut provider = SageMakerCompletion::new("ep", None, None)
+            .with_payload(SageMakerPayload::TextGeneration);
+        provider.max_tokens = Some(128);
+        let messages = vec![
+            msg(&[
+                ("role", serde_json::json!("system")),
+                ("content", serde_json::json!("Be brief.")),
+            ]),
+            msg(&[
+                ("role", serde_json::json!("user")),
+                ("content", serde_json::json!("Hello")),
+            ]),
+        ];
+
+        let body = provider.build_request_body(&messages, None);
+        let inputs = body["inputs"].as_str().unwrap();
+        assert!(inputs.starts_with("system: Be brief."));
+        assert!(inputs.ends_with("assistant:"));
+        assert_eq!(body["parameters"]["max_new_tokens"], 128);
+        assert!(!provider.supports_function_calling());
+
+        let response = serde_json::json!([{"generated_text": " Hi there."}]);
+        assert_eq!(provider.parse_response(&response).unwrap(), " Hi there.");
+    }
+}
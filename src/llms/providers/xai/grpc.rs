@@ -209,6 +209,7 @@ impl BaseLLM for XAIGrpcCompletion {
             messages: proto_messages,
             max_tokens: self.max_tokens,
             temperature,
+            stop: self.state.stop.clone(),
             ..Default::default()
         };
 
@@ -232,7 +233,8 @@ impl BaseLLM for XAIGrpcCompletion {
 
         let final_content = self.state.apply_stop_words(&content);
 
-        // Log token usage
+        // Log and record token usage
+        let mut usage_data = HashMap::new();
         if let Some(ref usage) = inner.usage {
             log::debug!(
                 "xAI gRPC token usage: prompt={}, completion={}, total={}",
@@ -240,7 +242,16 @@ impl BaseLLM for XAIGrpcCompletion {
                 usage.completion_tokens,
                 usage.total_tokens,
             );
+            usage_data.insert(
+                "prompt_tokens".to_string(),
+                serde_json::json!(usage.prompt_tokens),
+            );
+            usage_data.insert(
+                "completion_tokens".to_string(),
+                serde_json::json!(usage.completion_tokens),
+            );
         }
+        self.state.track_token_usage_internal(&usage_data);
 
         Ok(Value::String(final_content))
     }
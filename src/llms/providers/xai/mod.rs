@@ -8,7 +8,7 @@
 //! # Features
 //!
 //! - OpenAI-compatible Chat Completions API via `reqwest`
-//! - Retry with exponential backoff on 429/5xx
+//! - Retries on 429/5xx as the [`RetryPolicy`](crate::llms::retry::RetryPolicy) allows
 //! - Native tool use (function calling)
 //! - Live search grounding (xAI-specific)
 //! - Deferred reasoning support (grok-3)
@@ -36,7 +36,10 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::llm::LLM;
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::llms::http::HttpConfig;
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
 // ---------------------------------------------------------------------------
@@ -79,7 +82,7 @@ pub struct XAICompletion {
 
     /// Request timeout in seconds.
     pub timeout: Option<f64>,
-    /// Maximum number of retries.
+    /// Maximum number of retries, unless `state.retry_policy` is set.
     pub max_retries: u32,
     /// Nucleus sampling parameter.
     pub top_p: Option<f64>,
@@ -117,7 +120,7 @@ impl XAICompletion {
         let api_key = api_key.or_else(|| std::env::var("XAI_API_KEY").ok());
 
         let mut state = BaseLLMState::new(model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.base_url = base_url;
         state.provider = "xai".to_string();
 
@@ -137,6 +140,47 @@ impl XAICompletion {
         }
     }
 
+    /// A provider for `llm`'s model and base URL, with its generation
+    /// parameters.
+    pub fn from_llm_config(llm: &LLM) -> Self {
+        Self::new(&llm.model, None, llm.api_base.clone()).with_llm_config(llm)
+    }
+
+    /// Take the generation parameters set on `llm`: temperature, sampling,
+    /// penalties, token limit (`max_tokens`, else `max_completion_tokens`),
+    /// stop sequences, seed, response format, reasoning effort, timeout and
+    /// additional parameters.
+    pub fn with_llm_config(mut self, llm: &LLM) -> Self {
+        self.state.temperature = llm.temperature.or(self.state.temperature);
+        if !llm.stop.is_empty() {
+            self.state.stop = llm.stop.clone();
+        }
+        self.state
+            .additional_params
+            .extend(llm.additional_params.clone());
+        self.timeout = llm.timeout.or(self.timeout);
+        self.top_p = llm.top_p.or(self.top_p);
+        self.frequency_penalty = llm.frequency_penalty.or(self.frequency_penalty);
+        self.presence_penalty = llm.presence_penalty.or(self.presence_penalty);
+        if let Some(max_tokens) = llm.max_tokens.or(llm.max_completion_tokens) {
+            self.max_tokens = u32::try_from(max_tokens).ok();
+        }
+        self.seed = llm.seed.or(self.seed);
+        if llm.response_format.is_some() {
+            self.response_format = llm.response_format.clone();
+        }
+        if let Some(ref effort) = llm.reasoning_effort {
+            self.reasoning_effort = Some(effort.to_string());
+        }
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.state.http = Some(config);
+        self
+    }
+
     /// Get the API base URL.
     pub fn api_base_url(&self) -> String {
         self.state
@@ -221,9 +265,34 @@ impl XAICompletion {
             }
         }
 
+        // Additional parameters never override the ones set above
+        for (key, value) in &self.state.additional_params {
+            if body.get(key).is_none() {
+                body[key.as_str()] = value.clone();
+            }
+        }
+
         body
     }
 
+    /// Token usage of a response, with the prompt tokens served from
+    /// xAI's prefix cache as `cached_tokens`.
+    fn extract_token_usage(response: &Value) -> HashMap<String, Value> {
+        let Some(usage) = response.get("usage").and_then(Value::as_object) else {
+            return HashMap::new();
+        };
+        let mut usage: HashMap<String, Value> = usage.clone().into_iter().collect();
+        let cached = usage
+            .get("prompt_tokens_details")
+            .and_then(|d| d.get("cached_tokens"))
+            .or_else(|| usage.get("cached_prompt_text_tokens"))
+            .cloned();
+        if let Some(cached) = cached {
+            usage.insert("cached_tokens".to_string(), cached);
+        }
+        usage
+    }
+
     /// Parse a Chat Completions API response (OpenAI-compatible format).
     fn parse_response(
         &self,
@@ -380,9 +449,13 @@ impl BaseLLM for XAICompletion {
         );
 
         // Validate API key
-        let api_key = self.state.api_key.as_ref().ok_or_else(|| {
-            "xAI API key not set. Set XAI_API_KEY environment variable or pass api_key to constructor."
-        })?;
+        let api_key = self
+            .state
+            .resolve_api_key("XAI_API_KEY")
+            .await?
+            .ok_or(
+                "xAI API key not set. Set XAI_API_KEY environment variable or pass api_key to constructor.",
+            )?;
 
         // Build request body
         let tools_slice = tools.as_deref();
@@ -394,88 +467,68 @@ impl BaseLLM for XAICompletion {
 
         // Build HTTP client
         let timeout_secs = self.timeout.unwrap_or(120.0);
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs_f64(timeout_secs))
-            .build()?;
-
-        // Retry loop with exponential backoff
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
-        let mut retry_delay = std::time::Duration::from_secs(1);
-
-        for attempt in 0..=self.max_retries {
-            if attempt > 0 {
-                log::warn!("xAI API retry attempt {} after {:?}", attempt, retry_delay);
-                tokio::time::sleep(retry_delay).await;
-                retry_delay *= 2;
-            }
-
-            let request = client
-                .post(&endpoint)
-                .header("Content-Type", "application/json")
-                .header("Authorization", format!("Bearer {}", api_key));
-
-            let response = match request.json(&body).send().await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
+        let client = self
+            .state
+            .http_client(std::time::Duration::from_secs_f64(timeout_secs))?;
+
+        // Send with retries as the policy allows
+        let policy = self.state.effective_retry_policy(self.max_retries);
+        let response = policy
+            .send("xAI", || async {
+                #[cfg(feature = "chaos")]
+                if let Some(fault) = crate::chaos::provider_fault("xai").await {
+                    return Err(fault.into());
                 }
-            };
 
-            let status = response.status();
-
-            // Rate limiting
-            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                last_error = Some("Rate limited by xAI API (429)".into());
-                continue;
-            }
-
-            // Server errors
-            if status.is_server_error() {
-                last_error = Some(format!("xAI API server error: {}", status).into());
-                continue;
-            }
-
-            let response_text = match response.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    last_error = Some(Box::new(e));
-                    continue;
-                }
-            };
+                let request = client
+                    .post(&endpoint)
+                    .header("Content-Type", "application/json")
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", api_key.expose_secret()),
+                    );
+                Ok(request.json(&body).send().await?)
+            })
+            .await?;
+
+        let status = response.status();
+        let response_text = response.text().await?;
+
+        // Errors that were not retried
+        if !status.is_success() {
+            return Err(format!("xAI API error ({}): {}", status, response_text).into());
+        }
 
-            // Client errors — don't retry
-            if status.is_client_error() {
-                return Err(format!("xAI API error ({}): {}", status, response_text).into());
+        // Parse JSON
+        let response_json: Value = match serde_json::from_str(&response_text) {
+            Ok(json) => json,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to parse xAI response: {} - Body: {}",
+                    e,
+                    &response_text[..response_text.len().min(500)]
+                )
+                .into());
             }
+        };
+
+        // Check for error in response body
+        if let Some(err) = response_json.get("error") {
+            let msg = err
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown xAI API error");
+            return Err(format!("xAI API error: {}", msg).into());
+        }
 
-            // Parse JSON
-            let response_json: Value = match serde_json::from_str(&response_text) {
-                Ok(json) => json,
-                Err(e) => {
-                    return Err(format!(
-                        "Failed to parse xAI response: {} - Body: {}",
-                        e,
-                        &response_text[..response_text.len().min(500)]
-                    )
-                    .into());
-                }
-            };
-
-            // Check for error in response body
-            if let Some(err) = response_json.get("error") {
-                let msg = err
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or("Unknown xAI API error");
-                return Err(format!("xAI API error: {}", msg).into());
-            }
+        let result = self.parse_response(&response_json)?;
 
-            let result = self.parse_response(&response_json)?;
-            return Ok(result);
-        }
+        // Record token usage
+        self.state
+            .track_token_usage_internal(&Self::extract_token_usage(&response_json));
+        self.state.last_response.set(&response_json);
 
-        Err(last_error.unwrap_or_else(|| "xAI API call failed after all retries".into()))
+        Ok(result)
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
@@ -485,6 +538,14 @@ impl BaseLLM for XAICompletion {
     fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
         self.state.track_token_usage_internal(usage_data);
     }
+
+    fn request_identity(&self, messages: &[LLMMessage], tools: Option<&[Value]>) -> Option<Value> {
+        Some(serde_json::json!({
+            "endpoint": format!("{}/chat/completions", self.api_base_url()),
+            "credential": self.state.credential_hash("XAI_API_KEY"),
+            "body": self.build_request_body(messages, tools),
+        }))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -0,0 +1,411 @@
+//! Retry policy for provider requests.
+//!
+//! Every native provider sends its HTTP requests through
+//! [`RetryPolicy::send`], so the number of attempts, the backoff between
+//! them and the failures worth retrying are configured in one place: on the
+//! [`LLM`](crate::llm::LLM) (`retry_policy`), or per provider on its
+//! [`BaseLLMState`](super::base_llm::BaseLLMState). A `Retry-After` (or
+//! `retry-after-ms`) header on a retried response replaces the computed
+//! backoff.
+
+use std::error::Error;
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// A class of failure a [`RetryPolicy`] can retry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryOn {
+    /// 429 Too Many Requests.
+    RateLimit,
+    /// The provider is overloaded: 503 Service Unavailable, or Anthropic's
+    /// 529.
+    Overloaded,
+    /// Any other 5xx response.
+    ServerError,
+    /// 408 Request Timeout, or no response within the client timeout.
+    Timeout,
+    /// The request could not be sent (connection refused, reset, DNS).
+    Connection,
+}
+
+impl RetryOn {
+    /// All classes.
+    pub const ALL: [RetryOn; 5] = [
+        RetryOn::RateLimit,
+        RetryOn::Overloaded,
+        RetryOn::ServerError,
+        RetryOn::Timeout,
+        RetryOn::Connection,
+    ];
+
+    /// The class of a response status; `None` for statuses never retried.
+    pub fn from_status(status: StatusCode) -> Option<Self> {
+        match status.as_u16() {
+            429 => Some(RetryOn::RateLimit),
+            503 | 529 => Some(RetryOn::Overloaded),
+            408 => Some(RetryOn::Timeout),
+            500..=599 => Some(RetryOn::ServerError),
+            _ => None,
+        }
+    }
+
+    /// The class of a failure to get a response; `None` for errors never
+    /// retried (such as a request that could not be built).
+    pub fn from_error(error: &(dyn Error + 'static)) -> Option<Self> {
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            return if error.is_timeout() {
+                Some(RetryOn::Timeout)
+            } else if error.is_connect() || error.is_request() {
+                Some(RetryOn::Connection)
+            } else {
+                None
+            };
+        }
+        #[cfg(feature = "chaos")]
+        if let Some(fault) = error.downcast_ref::<crate::chaos::ProviderFault>() {
+            return Some(match fault {
+                crate::chaos::ProviderFault::RateLimited => RetryOn::RateLimit,
+                crate::chaos::ProviderFault::ServerError => RetryOn::ServerError,
+            });
+        }
+        None
+    }
+}
+
+/// How often, and after how long, a failed provider request is retried.
+///
+/// The delay before retry `n` is `base_delay * 2^(n-1)`, capped at
+/// `max_delay`, minus a random share of up to `jitter` of it. A
+/// `Retry-After` header on the failed response is used instead when
+/// `respect_retry_after` is set, again capped at `max_delay`.
+///
+/// ```
+/// use std::time::Duration;
+/// use crewai::llms::retry::{RetryOn, RetryPolicy};
+///
+/// let policy = RetryPolicy::default()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_millis(500))
+///     .with_jitter(0.2)
+///     .with_retry_on([RetryOn::RateLimit, RetryOn::Overloaded]);
+/// assert_eq!(policy.max_retries(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first included; 1 disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    #[serde(with = "duration_ms", rename = "base_delay_ms")]
+    pub base_delay: Duration,
+    /// Longest delay between attempts.
+    #[serde(with = "duration_ms", rename = "max_delay_ms")]
+    pub max_delay: Duration,
+    /// Share of each delay (0 to 1) randomly taken off it, so that clients
+    /// failing together do not retry together.
+    pub jitter: f64,
+    /// Failures that are retried; any other failure is returned at once.
+    pub retry_on: Vec<RetryOn>,
+    /// Whether a `Retry-After` header sets the delay.
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 1s then 2s apart, retrying every class.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.0,
+            retry_on: RetryOn::ALL.to_vec(),
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set the number of attempts, the first included.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the number of retries after the first attempt.
+    pub fn with_max_retries(self, max_retries: u32) -> Self {
+        self.with_max_attempts(max_retries.saturating_add(1))
+    }
+
+    /// Set the delay before the first retry.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the longest delay between attempts.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set the share of each delay randomly taken off it (clamped to 0–1).
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the failures that are retried.
+    pub fn with_retry_on(mut self, retry_on: impl IntoIterator<Item = RetryOn>) -> Self {
+        self.retry_on = retry_on.into_iter().collect();
+        self
+    }
+
+    /// Set whether a `Retry-After` header sets the delay.
+    pub fn with_respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Number of retries after the first attempt.
+    pub fn max_retries(&self) -> u32 {
+        self.max_attempts.saturating_sub(1)
+    }
+
+    /// Whether failures of `class` are retried.
+    pub fn retries(&self, class: RetryOn) -> bool {
+        self.retry_on.contains(&class)
+    }
+
+    /// Delay before retry `retry` (1 for the first), given the `Retry-After`
+    /// of the failed response.
+    pub fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after.min(self.max_delay);
+        }
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if self.jitter > 0.0 {
+            delay.mul_f64(1.0 - self.jitter * random_fraction())
+        } else {
+            delay
+        }
+    }
+
+    /// Send a request with `send`, retrying it as the policy allows, and
+    /// return the first response that is not retried. That response may
+    /// still be an error status the caller has to handle. `provider` names
+    /// the provider in logs and errors.
+    pub async fn send<F, Fut>(
+        &self,
+        provider: &str,
+        mut send: F,
+    ) -> Result<reqwest::Response, Box<dyn Error + Send + Sync>>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<reqwest::Response, Box<dyn Error + Send + Sync>>>,
+    {
+        let mut last_error: Option<Box<dyn Error + Send + Sync>> = None;
+        let mut retry_after = None;
+        for attempt in 0..self.max_attempts.max(1) {
+            if attempt > 0 {
+                let delay = self.delay(attempt, retry_after.take());
+                log::warn!(
+                    "{} API retry attempt {} after {:?}",
+                    provider,
+                    attempt,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+
+            let response = match send().await {
+                Ok(response) => response,
+                Err(e) => match RetryOn::from_error(e.as_ref()) {
+                    Some(class) if self.retries(class) => {
+                        last_error = Some(e);
+                        continue;
+                    }
+                    _ => return Err(e),
+                },
+            };
+
+            let status = response.status();
+            match RetryOn::from_status(status) {
+                Some(class) if self.retries(class) => {
+                    retry_after = parse_retry_after(response.headers());
+                    last_error = Some(
+                        match class {
+                            RetryOn::RateLimit => {
+                                format!("Rate limited by {} API ({})", provider, status.as_u16())
+                            }
+                            RetryOn::Overloaded => {
+                                format!("{} API overloaded ({})", provider, status.as_u16())
+                            }
+                            _ => format!("{} API server error: {}", provider, status),
+                        }
+                        .into(),
+                    );
+                }
+                _ => return Ok(response),
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| format!("{} API call failed after all retries", provider).into()))
+    }
+}
+
+/// The delay a `retry-after-ms` or `Retry-After` header asks for: a number
+/// of (milliseconds or) seconds, or an HTTP date.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    if let Some(ms) = header("retry-after-ms").and_then(|v| v.trim().parse::<f64>().ok()) {
+        return Duration::try_from_secs_f64(ms / 1000.0).ok();
+    }
+    let value = header("retry-after")?.trim();
+    if let Ok(secs) = value.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+/// A random number in `[0, 1)`, from the journaled run's random stream
+/// when there is one.
+fn random_fraction() -> f64 {
+    let bits = crate::utilities::journal::next_random()
+        .unwrap_or_else(|| (uuid::Uuid::new_v4().as_u128() >> 64) as u64);
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+mod duration_ms {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use axum::http::StatusCode as AxumStatus;
+    use axum::response::IntoResponse;
+    use axum::routing::post;
+
+    #[test]
+    fn test_delay_backs_off_and_honors_retry_after() {
+        let policy = RetryPolicy::default().with_max_delay(Duration::from_secs(5));
+        assert_eq!(policy.delay(1, None), Duration::from_secs(1));
+        assert_eq!(policy.delay(3, None), Duration::from_secs(4));
+        assert_eq!(policy.delay(4, None), Duration::from_secs(5));
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(3))),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            policy.delay(1, Some(Duration::from_secs(30))),
+            Duration::from_secs(5)
+        );
+        let ignoring = policy.clone().with_respect_retry_after(false);
+        assert_eq!(
+            ignoring.delay(2, Some(Duration::from_secs(3))),
+            Duration::from_secs(2)
+        );
+        let jittered = policy.with_jitter(0.5).delay(2, None);
+        assert!(jittered > Duration::from_secs(1) && jittered <= Duration::from_secs(2));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "2".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert("retry-after-ms", "250".parse().unwrap());
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(250))
+        );
+        let date = (chrono::Utc::now() + chrono::Duration::seconds(90)).to_rfc2822();
+        headers.clear();
+        headers.insert("retry-after", date.parse().unwrap());
+        let delay = parse_retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(80) && delay <= Duration::from_secs(90));
+
+        let json = serde_json::to_value(RetryPolicy::none()).unwrap();
+        assert_eq!(json["max_attempts"], 1);
+        assert_eq!(json["base_delay_ms"], 1000);
+        let policy: RetryPolicy =
+            serde_json::from_str(r#"{"max_attempts": 4, "retry_on": ["rate_limit"]}"#).unwrap();
+        assert_eq!(policy.max_retries(), 3);
+        assert!(policy.retries(RetryOn::RateLimit) && !policy.retries(RetryOn::ServerError));
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_only_configured_classes() {
+        let hits = Arc::new(AtomicU32::new(0));
+        let counter = hits.clone();
+        let router = axum::Router::new().route(
+            "/",
+            post(move || {
+                let hit = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match hit {
+                        0 => (AxumStatus::TOO_MANY_REQUESTS, [("retry-after-ms", "10")])
+                            .into_response(),
+                        1 => AxumStatus::SERVICE_UNAVAILABLE.into_response(),
+                        _ => AxumStatus::INTERNAL_SERVER_ERROR.into_response(),
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = reqwest::Client::new();
+        let send = || {
+            let request = client.post(&url);
+            async move { Ok(request.send().await?) }
+        };
+        let policy = RetryPolicy::default()
+            .with_max_attempts(5)
+            .with_base_delay(Duration::from_millis(10))
+            .with_retry_on([RetryOn::RateLimit, RetryOn::Overloaded]);
+        let response = policy.send("Test", send).await.unwrap();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let err = RetryPolicy::default()
+            .with_max_attempts(2)
+            .with_base_delay(Duration::from_millis(10))
+            .send("Test", send)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Test API server error: 500 Internal Server Error"
+        );
+        assert_eq!(hits.load(Ordering::SeqCst), 5);
+    }
+}
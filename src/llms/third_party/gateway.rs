@@ -0,0 +1,414 @@
+//! OpenAI-compatible gateways.
+//!
+//! LiteLLM's proxy, OpenRouter, Together AI and Fireworks AI all serve the
+//! OpenAI Chat Completions API, so one provider covers them: it sends the
+//! model name through unchanged to the gateway's base URL, with the
+//! gateway's API key and headers. Models are addressed the way LiteLLM does
+//! it, with the gateway as prefix (`openrouter/anthropic/claude-3.5-sonnet`,
+//! `together_ai/meta-llama/Llama-3.3-70B-Instruct-Turbo`); any other
+//! OpenAI-compatible server is reached with the `gateway` provider and an
+//! explicit base URL.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::llm::LLM;
+use crate::llms::base_llm::{BaseLLM, LLMMessage};
+use crate::llms::hooks::{HeaderHook, HeaderInjector};
+use crate::llms::http::HttpConfig;
+use crate::llms::providers::openai::OpenAICompletion;
+use crate::security::secrets::SecretString;
+use crate::types::usage_metrics::UsageMetrics;
+
+/// A known OpenAI-compatible gateway.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Gateway {
+    /// A LiteLLM proxy (`$LITELLM_PROXY_API_BASE`, default
+    /// `http://localhost:4000`).
+    LiteLLM,
+    /// OpenRouter.
+    OpenRouter,
+    /// Together AI.
+    Together,
+    /// Fireworks AI.
+    Fireworks,
+    /// Any other OpenAI-compatible server; needs a base URL.
+    Custom,
+}
+
+impl Gateway {
+    /// All gateways.
+    pub const ALL: [Gateway; 5] = [
+        Gateway::LiteLLM,
+        Gateway::OpenRouter,
+        Gateway::Together,
+        Gateway::Fireworks,
+        Gateway::Custom,
+    ];
+
+    /// Provider name, as returned by [`BaseLLM::provider`].
+    pub fn name(self) -> &'static str {
+        match self {
+            Gateway::LiteLLM => "litellm",
+            Gateway::OpenRouter => "openrouter",
+            Gateway::Together => "together_ai",
+            Gateway::Fireworks => "fireworks_ai",
+            Gateway::Custom => "gateway",
+        }
+    }
+
+    /// Provider names and model prefixes that select the gateway.
+    pub fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Gateway::LiteLLM => &["litellm", "litellm_proxy"],
+            Gateway::OpenRouter => &["openrouter"],
+            Gateway::Together => &["together_ai", "together"],
+            Gateway::Fireworks => &["fireworks_ai", "fireworks"],
+            Gateway::Custom => &["gateway", "openai_compatible"],
+        }
+    }
+
+    /// The gateway a provider name or model prefix selects.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|gateway| gateway.aliases().contains(&name.as_str()))
+    }
+
+    /// The gateway a model is prefixed with, and the model name passed
+    /// through to it: `openrouter/anthropic/claude-3.5-sonnet` is
+    /// `anthropic/claude-3.5-sonnet` on OpenRouter.
+    pub fn split_model(model: &str) -> Option<(Self, &str)> {
+        let (prefix, model) = model.split_once('/')?;
+        Some((Self::from_name(prefix)?, model))
+    }
+
+    /// Base URL used unless one is configured.
+    pub fn default_base_url(self) -> Option<String> {
+        let env = |names: &[&str]| names.iter().find_map(|name| std::env::var(name).ok());
+        match self {
+            Gateway::LiteLLM => Some(
+                env(&["LITELLM_PROXY_API_BASE", "LITELLM_PROXY_URL"])
+                    .unwrap_or_else(|| "http://localhost:4000".to_string()),
+            ),
+            Gateway::OpenRouter => Some(
+                env(&["OPENROUTER_API_BASE"])
+                    .unwrap_or_else(|| "https://openrouter.ai/api/v1".to_string()),
+            ),
+            Gateway::Together => Some("https://api.together.xyz/v1".to_string()),
+            Gateway::Fireworks => Some("https://api.fireworks.ai/inference/v1".to_string()),
+            Gateway::Custom => None,
+        }
+    }
+
+    /// Environment variables holding the API key, in order of precedence.
+    /// The first is also the secret name looked up in a secrets provider.
+    pub fn api_key_env(self) -> &'static [&'static str] {
+        match self {
+            Gateway::LiteLLM => &["LITELLM_PROXY_API_KEY", "LITELLM_API_KEY"],
+            Gateway::OpenRouter => &["OPENROUTER_API_KEY"],
+            Gateway::Together => &["TOGETHER_API_KEY", "TOGETHERAI_API_KEY"],
+            Gateway::Fireworks => &["FIREWORKS_API_KEY", "FIREWORKS_AI_API_KEY"],
+            Gateway::Custom => &["GATEWAY_API_KEY"],
+        }
+    }
+
+    /// Whether calls fail without an API key. A LiteLLM proxy or a custom
+    /// server may run without authentication.
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, Gateway::LiteLLM | Gateway::Custom)
+    }
+
+    /// Headers the gateway expects on every request: OpenRouter's app
+    /// attribution from `$OR_SITE_URL` and `$OR_APP_NAME`.
+    pub fn default_headers(self) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        if self == Gateway::OpenRouter {
+            for (header, env) in [("HTTP-Referer", "OR_SITE_URL"), ("X-Title", "OR_APP_NAME")] {
+                if let Ok(value) = std::env::var(env) {
+                    headers.insert(header.to_string(), value);
+                }
+            }
+        }
+        headers
+    }
+}
+
+impl fmt::Display for Gateway {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A completion provider for an OpenAI-compatible gateway.
+///
+/// Requests go through an [`OpenAICompletion`] pointed at the gateway, so
+/// retries, tools and structured output work as they do against OpenAI.
+///
+/// # Example
+///
+/// ```ignore
+/// let llm = GatewayCompletion::from_model("openrouter/anthropic/claude-3.5-sonnet")
+///     .unwrap()
+///     .with_header_hook(|_| vec![("X-Request-Source".into(), "crew".into())]);
+/// ```
+#[derive(Clone)]
+pub struct GatewayCompletion {
+    /// The gateway called.
+    pub gateway: Gateway,
+    /// The OpenAI client pointed at the gateway.
+    pub completion: OpenAICompletion,
+    header_hooks: Vec<Arc<HeaderHook>>,
+}
+
+impl GatewayCompletion {
+    /// A provider calling `model` on `gateway`, at `base_url` or the
+    /// gateway's default, with the API key from the gateway's environment
+    /// variable.
+    pub fn new(gateway: Gateway, model: impl Into<String>, base_url: Option<String>) -> Self {
+        let api_key = gateway
+            .api_key_env()
+            .iter()
+            .find_map(|name| std::env::var(name).ok());
+        let mut completion =
+            OpenAICompletion::new(model, None, base_url.or_else(|| gateway.default_base_url()));
+        completion.state.api_key = api_key.map(SecretString::from);
+        completion.state.api_key_secret = Some(gateway.api_key_env()[0].to_string());
+        completion.state.provider = gateway.name().to_string();
+        completion.api_key_required = gateway.requires_api_key();
+        // Not OpenAI's, so these must not leak to a gateway.
+        completion.organization = None;
+        let headers = gateway.default_headers();
+        if !headers.is_empty() {
+            completion.default_headers = Some(headers);
+        }
+        Self {
+            gateway,
+            completion,
+            header_hooks: Vec::new(),
+        }
+    }
+
+    /// A provider for a gateway-prefixed model such as
+    /// `together_ai/meta-llama/Llama-3.3-70B-Instruct-Turbo`.
+    pub fn from_model(model: &str) -> Option<Self> {
+        let (gateway, model) = Gateway::split_model(model)?;
+        Some(Self::new(gateway, model, None))
+    }
+
+    /// Set the API key.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.completion.state.api_key = Some(SecretString::new(api_key));
+        self
+    }
+
+    /// Send requests with these proxy and TLS settings.
+    pub fn with_http_config(mut self, config: HttpConfig) -> Self {
+        self.completion.state.http = Some(config);
+        self
+    }
+
+    /// Take the generation parameters set on `llm`, as
+    /// [`OpenAICompletion::with_llm_config`] does.
+    pub fn with_llm_config(mut self, llm: &LLM) -> Self {
+        self.completion = self.completion.with_llm_config(llm);
+        self
+    }
+
+    /// Add a header to every request.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.completion
+            .default_headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Add the headers `hook` computes for each request, e.g. a trace ID
+    /// or a per-tenant key for the gateway.
+    pub fn with_header_hook(
+        mut self,
+        hook: impl Fn(&reqwest::Request) -> Vec<(String, String)> + Send + Sync + 'static,
+    ) -> Self {
+        self.header_hooks.push(Arc::new(hook));
+        let hooks = self.header_hooks.clone();
+        self.completion.interceptor = Some(Arc::new(HeaderInjector::new(move |request| {
+            hooks.iter().flat_map(|hook| hook(request)).collect()
+        })));
+        self
+    }
+
+    fn check_base_url(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.completion.state.base_url.is_none() {
+            return Err(format!(
+                "The {} provider needs a base_url for the OpenAI-compatible server",
+                self.gateway
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for GatewayCompletion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GatewayCompletion")
+            .field("gateway", &self.gateway)
+            .field("completion", &self.completion)
+            .field("header_hooks", &self.header_hooks.len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BaseLLM for GatewayCompletion {
+    fn model(&self) -> &str {
+        self.completion.model()
+    }
+
+    fn temperature(&self) -> Option<f64> {
+        self.completion.temperature()
+    }
+
+    fn stop(&self) -> &[String] {
+        self.completion.stop()
+    }
+
+    fn set_stop(&mut self, stop: Vec<String>) {
+        self.completion.set_stop(stop);
+    }
+
+    fn provider(&self) -> &str {
+        self.gateway.name()
+    }
+
+    fn is_litellm(&self) -> bool {
+        self.gateway == Gateway::LiteLLM
+    }
+
+    fn supports_function_calling(&self) -> bool {
+        true
+    }
+
+    fn supports_stop_words(&self) -> bool {
+        self.completion.supports_stop_words()
+    }
+
+    fn call(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_base_url()?;
+        self.completion.call(messages, tools, available_functions)
+    }
+
+    async fn acall(
+        &self,
+        messages: Vec<LLMMessage>,
+        tools: Option<Vec<Value>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.check_base_url()?;
+        self.completion
+            .acall(messages, tools, available_functions)
+            .await
+    }
+
+    fn get_token_usage_summary(&self) -> UsageMetrics {
+        self.completion.get_token_usage_summary()
+    }
+
+    fn track_token_usage(&mut self, usage_data: &HashMap<String, Value>) {
+        self.completion.track_token_usage(usage_data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::Json;
+
+    #[test]
+    fn test_gateway_models_pass_through() {
+        assert_eq!(
+            Gateway::split_model("openrouter/anthropic/claude-3.5-sonnet"),
+            Some((Gateway::OpenRouter, "anthropic/claude-3.5-sonnet"))
+        );
+        assert_eq!(
+            Gateway::split_model("fireworks_ai/accounts/fireworks/models/llama-v3p1-70b-instruct"),
+            Some((
+                Gateway::Fireworks,
+                "accounts/fireworks/models/llama-v3p1-70b-instruct"
+            ))
+        );
+        assert_eq!(Gateway::from_name("Together"), Some(Gateway::Together));
+        assert_eq!(Gateway::split_model("anthropic/claude-3-5-sonnet"), None);
+
+        let llm = GatewayCompletion::from_model("litellm/gpt-4o").unwrap();
+        assert_eq!(llm.model(), "gpt-4o");
+        assert_eq!(llm.provider(), "litellm");
+        assert!(llm.is_litellm());
+        assert!(!llm.completion.api_key_required);
+        assert!(llm.completion.state.base_url.is_some());
+        let err = GatewayCompletion::new(Gateway::Custom, "local-model", None)
+            .call(Vec::new(), None, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("needs a base_url"));
+    }
+
+    #[tokio::test]
+    async fn test_gateway_sends_model_key_and_headers() {
+        let router = axum::Router::new().route(
+            "/v1/chat/completions",
+            post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                let header = |name: &str| {
+                    headers
+                        .get(name)
+                        .map(|v| v.to_str().unwrap().to_string())
+                        .unwrap_or_default()
+                };
+                let reply = format!(
+                    "{} {} {} {}",
+                    body["model"].as_str().unwrap(),
+                    header("authorization"),
+                    header("x-title"),
+                    header("x-trace-id"),
+                );
+                Json(serde_json::json!({
+                    "choices": [{"message": {"role": "assistant", "content": reply}}]
+                }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let llm =
+            GatewayCompletion::new(Gateway::OpenRouter, "meta-llama/llama-3.3-70b", Some(url))
+                .with_api_key("or-key")
+                .with_header("X-Title", "Crew")
+                .with_header_hook(|request| {
+                    vec![("X-Trace-Id".to_string(), format!("{}-1", request.method()))]
+                });
+        let message: LLMMessage = [
+            ("role".to_string(), Value::from("user")),
+            ("content".to_string(), Value::from("hi")),
+        ]
+        .into_iter()
+        .collect();
+        let reply = llm.acall(vec![message], None, None).await.unwrap();
+        assert_eq!(reply, "meta-llama/llama-3.3-70b Bearer or-key Crew POST-1");
+    }
+}
@@ -16,9 +16,11 @@
 //! - Hugging Face Inference Endpoints
 //! - Custom OpenAI-compatible endpoints
 //!
-//! In the Rust port, the LiteLLM bridge is represented as a struct that
-//! can be configured to call LiteLLM via its OpenAI-compatible proxy API,
-//! or directly via the individual provider APIs.
+//! In the Rust port, the LiteLLM bridge calls a LiteLLM proxy through its
+//! OpenAI-compatible API. [`gateway`] covers the proxy and the hosted
+//! OpenAI-compatible gateways (OpenRouter, Together AI, Fireworks AI).
+
+pub mod gateway;
 
 use std::any::Any;
 use std::collections::HashMap;
@@ -28,17 +30,19 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::llms::base_llm::{BaseLLM, BaseLLMState, LLMMessage};
+use crate::security::secrets::SecretString;
 use crate::types::usage_metrics::UsageMetrics;
 
+pub use gateway::{Gateway, GatewayCompletion};
+
 // ---------------------------------------------------------------------------
 // LiteLLMBridge
 // ---------------------------------------------------------------------------
 
 /// LiteLLM bridge for third-party LLM providers.
 ///
-/// Acts as a universal adapter for LLM providers not natively supported.
-/// In the full implementation, this would call LiteLLM's proxy API or
-/// directly invoke the provider's API via an OpenAI-compatible endpoint.
+/// Acts as a universal adapter for LLM providers not natively supported by
+/// calling a LiteLLM proxy, which routes the model string to its provider.
 ///
 /// # Example
 ///
@@ -84,7 +88,7 @@ impl LiteLLMBridge {
             .or_else(|| std::env::var("LITELLM_PROXY_URL").ok());
 
         let mut state = BaseLLMState::new(&model);
-        state.api_key = api_key;
+        state.api_key = api_key.map(SecretString::from);
         state.base_url = base_url;
         state.provider = "litellm".to_string();
 
@@ -97,6 +101,27 @@ impl LiteLLMBridge {
             max_tokens: None,
         }
     }
+
+    /// The proxy client for the bridge's settings. A `litellm/` prefix is
+    /// dropped; any other model string goes to the proxy as is.
+    fn gateway(&self) -> GatewayCompletion {
+        let model = match Gateway::split_model(&self.original_model) {
+            Some((Gateway::LiteLLM, model)) => model,
+            _ => &self.original_model,
+        };
+        let mut gateway =
+            GatewayCompletion::new(Gateway::LiteLLM, model, self.proxy_base_url.clone());
+        let completion = &mut gateway.completion;
+        if let Some(key) = &self.state.api_key {
+            completion.state.api_key = Some(key.clone());
+        }
+        completion.state.secrets_provider = self.state.secrets_provider.clone();
+        completion.state.temperature = self.state.temperature;
+        completion.state.stop = self.state.stop.clone();
+        completion.timeout = self.timeout;
+        completion.max_tokens = self.max_tokens;
+        gateway
+    }
 }
 
 #[async_trait]
@@ -134,7 +159,7 @@ impl BaseLLM for LiteLLMBridge {
         &self,
         messages: Vec<LLMMessage>,
         tools: Option<Vec<Value>>,
-        _available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         log::debug!(
             "LiteLLMBridge.call: model={}, proxy={:?}, messages={}, tools={:?}",
@@ -143,27 +168,31 @@ impl BaseLLM for LiteLLMBridge {
             messages.len(),
             tools.as_ref().map(|t| t.len()),
         );
-
-        Err("LiteLLMBridge.call is a stub - LiteLLM proxy integration not yet implemented".into())
+        let gateway = self.gateway();
+        let result = gateway.call(messages, tools, available_functions);
+        self.state
+            .token_usage
+            .add(&gateway.get_token_usage_summary());
+        result
     }
 
     async fn acall(
         &self,
         messages: Vec<LLMMessage>,
         tools: Option<Vec<Value>>,
-        _available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
+        available_functions: Option<HashMap<String, Box<dyn Any + Send + Sync>>>,
     ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
         log::debug!(
             "LiteLLMBridge.acall: model={}, messages={}",
             self.original_model,
             messages.len(),
         );
-        let _ = tools;
-
-        Err(
-            "LiteLLMBridge.acall is a stub - async LiteLLM proxy integration not yet implemented"
-                .into(),
-        )
+        let gateway = self.gateway();
+        let result = gateway.acall(messages, tools, available_functions).await;
+        self.state
+            .token_usage
+            .add(&gateway.get_token_usage_summary());
+        result
     }
 
     fn get_token_usage_summary(&self) -> UsageMetrics {
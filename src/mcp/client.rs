@@ -9,6 +9,7 @@
 //! for observability.
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -29,6 +30,8 @@ pub const MCP_TOOL_EXECUTION_TIMEOUT: u64 = 30;
 pub const MCP_DISCOVERY_TIMEOUT: u64 = 30;
 /// Maximum retry attempts.
 pub const MCP_MAX_RETRIES: u32 = 3;
+/// MCP protocol revision requested during initialization.
+pub const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
 
 /// Simple in-memory cache TTL for MCP tool schemas (5 minutes).
 const CACHE_TTL: Duration = Duration::from_secs(300);
@@ -113,11 +116,13 @@ pub struct MCPClient {
     initialized: bool,
     /// Whether the client was previously connected (for reconnection tracking).
     was_connected: bool,
-    /// Opaque MCP session handle.
+    /// Result of the `initialize` handshake (protocol version, server
+    /// info and capabilities) while a session is open.
     ///
     /// In the Python implementation, this is a `ClientSession` from the MCP SDK.
-    /// Stored as `Option<Value>` until an actual MCP SDK binding is integrated.
     session: Option<Value>,
+    /// Next JSON-RPC request id.
+    next_id: AtomicU64,
     /// In-memory schema cache (keyed by resource-type-qualified identifier).
     schema_cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
 }
@@ -139,6 +144,7 @@ impl MCPClient {
             initialized: false,
             was_connected: false,
             session: None,
+            next_id: AtomicU64::new(1),
             schema_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
@@ -282,18 +288,15 @@ impl MCPClient {
         // TODO: Emit MCPConnectionStartedEvent via event bus when integrated.
 
         let timeout = Duration::from_secs(self.connect_timeout);
-        let result = tokio::time::timeout(timeout, self.transport.connect()).await;
+        let result = tokio::time::timeout(timeout, async {
+            self.transport.connect().await?;
+            self.initialize_session().await
+        })
+        .await;
 
         match result {
-            Ok(Ok(())) => {
-                // TODO: Create ClientSession with transport read/write streams.
-                // self.session = Some(ClientSession::new(
-                //     self.transport.read_stream(),
-                //     self.transport.write_stream(),
-                // ));
-                // Initialize the MCP protocol session:
-                // self.session.as_mut().unwrap().initialize().await?;
-
+            Ok(Ok(session)) => {
+                self.session = Some(session);
                 self.initialized = true;
                 self.was_connected = true;
 
@@ -344,6 +347,59 @@ impl MCPClient {
         result.map_err(|e| anyhow::anyhow!("Error during MCP client disconnect: {}", e))
     }
 
+    /// Perform the MCP `initialize` handshake and return its result.
+    async fn initialize_session(&self) -> Result<Value, anyhow::Error> {
+        let session = self
+            .request(
+                "initialize",
+                serde_json::json!({
+                    "protocolVersion": MCP_PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "crewai-rust",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .await?;
+        self.transport
+            .send_notification(serde_json::json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/initialized",
+            }))
+            .await?;
+        Ok(session)
+    }
+
+    /// Send a JSON-RPC request and return its `result`.
+    ///
+    /// A JSON-RPC error response becomes an `Err` carrying the server's
+    /// error code and message.
+    async fn request(&self, method: &str, params: Value) -> Result<Value, anyhow::Error> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .transport
+            .send_request(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "method": method,
+                "params": params,
+            }))
+            .await?;
+        if let Some(error) = response.get("error") {
+            anyhow::bail!(
+                "MCP {} failed ({}): {}",
+                method,
+                error.get("code").unwrap_or(&Value::Null),
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+            );
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
     /// Clean up resources when an error occurs during connection.
     ///
     /// Best-effort cleanup: disconnects transport, clears session, resets
@@ -446,18 +502,53 @@ impl MCPClient {
     /// Calls the MCP session's `list_tools()` method and converts
     /// the response into a list of tool definitions.
     async fn list_tools_impl(&self) -> Result<Vec<HashMap<String, Value>>, anyhow::Error> {
-        // TODO: Integrate with actual MCP session list_tools
-        // let tools_result = self.session.list_tools().await?;
-        // return Ok(tools_result.tools.iter().map(|tool| {
-        //     let mut def = HashMap::new();
-        //     def.insert("name".into(), Value::String(sanitize_tool_name(&tool.name)));
-        //     def.insert("description".into(), Value::String(tool.description.clone().unwrap_or_default()));
-        //     def.insert("inputSchema".into(), tool.input_schema.clone().unwrap_or(Value::Object(Default::default())));
-        //     def
-        // }).collect());
+        let tools = self.list_paginated("tools/list", "tools").await?;
+        Ok(tools
+            .iter()
+            .map(|tool| {
+                let mut def = HashMap::new();
+                def.insert(
+                    "name".into(),
+                    tool.get("name").cloned().unwrap_or(Value::Null),
+                );
+                def.insert(
+                    "description".into(),
+                    tool.get("description")
+                        .cloned()
+                        .unwrap_or_else(|| Value::String(String::new())),
+                );
+                def.insert(
+                    "inputSchema".into(),
+                    tool.get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| serde_json::json!({"type": "object"})),
+                );
+                def
+            })
+            .collect())
+    }
 
-        log::debug!("list_tools_impl called (MCP SDK integration pending)");
-        Ok(Vec::new())
+    /// Collect the `key` items of every page of a paginated list method.
+    async fn list_paginated(&self, method: &str, key: &str) -> Result<Vec<Value>, anyhow::Error> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => serde_json::json!({ "cursor": cursor }),
+                None => serde_json::json!({}),
+            };
+            let page = self.request(method, params).await?;
+            if let Some(page_items) = page.get(key).and_then(Value::as_array) {
+                items.extend(page_items.iter().cloned());
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            if cursor.is_none() {
+                return Ok(items);
+            }
+        }
     }
 
     /// Call a tool on the MCP server.
@@ -538,25 +629,40 @@ impl MCPClient {
         tool_name: &str,
         arguments: &HashMap<String, Value>,
     ) -> Result<String, anyhow::Error> {
-        // TODO: Integrate with actual MCP session call_tool
-        // let result = self.session.call_tool(tool_name, arguments).await?;
-        // if let Some(content) = result.content.first() {
-        //     if let Some(text) = content.text.as_ref() {
-        //         return Ok(text.clone());
-        //     }
-        //     return Ok(format!("{:?}", content));
-        // }
-        // return Ok(format!("{:?}", result));
-
-        log::debug!(
-            "call_tool_impl called: tool='{}', args={:?} (MCP SDK integration pending)",
-            tool_name,
-            arguments
-        );
-        Err(anyhow::anyhow!(
-            "MCP tool execution not yet implemented: {}",
-            tool_name
-        ))
+        let result = self
+            .request(
+                "tools/call",
+                serde_json::json!({ "name": tool_name, "arguments": arguments }),
+            )
+            .await?;
+        let output = Self::content_text(&result);
+        if result.get("isError").and_then(Value::as_bool) == Some(true) {
+            // Tool-level errors are returned to the agent as output rather
+            // than retried.
+            log::warn!("MCP tool '{}' reported an error: {}", tool_name, output);
+        }
+        Ok(output)
+    }
+
+    /// Join the content items of a tool result into text.
+    ///
+    /// Text items are used as-is, other items (images, resources) as JSON.
+    /// Falls back to `structuredContent` when there is no content.
+    fn content_text(result: &Value) -> String {
+        match result.get("content").and_then(Value::as_array) {
+            Some(items) if !items.is_empty() => items
+                .iter()
+                .map(|item| match item.get("text").and_then(Value::as_str) {
+                    Some(text) => text.to_string(),
+                    None => item.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => result
+                .get("structuredContent")
+                .map(Value::to_string)
+                .unwrap_or_default(),
+        }
     }
 
     // -----------------------------------------------------------------------
@@ -581,18 +687,32 @@ impl MCPClient {
 
     /// Internal implementation of list_prompts.
     async fn list_prompts_impl(&self) -> Result<Vec<HashMap<String, Value>>, anyhow::Error> {
-        // TODO: Integrate with actual MCP session list_prompts
-        // let prompts_result = self.session.list_prompts().await?;
-        // return Ok(prompts_result.prompts.iter().map(|prompt| {
-        //     let mut def = HashMap::new();
-        //     def.insert("name".into(), Value::String(prompt.name.clone()));
-        //     def.insert("description".into(), Value::String(prompt.description.clone().unwrap_or_default()));
-        //     def.insert("arguments".into(), serde_json::to_value(&prompt.arguments).unwrap_or(Value::Array(vec![])));
-        //     def
-        // }).collect());
-
-        log::debug!("list_prompts_impl called (MCP SDK integration pending)");
-        Ok(Vec::new())
+        let prompts = self.list_paginated("prompts/list", "prompts").await?;
+        Ok(prompts
+            .iter()
+            .map(|prompt| {
+                let mut def = HashMap::new();
+                def.insert(
+                    "name".into(),
+                    prompt.get("name").cloned().unwrap_or(Value::Null),
+                );
+                def.insert(
+                    "description".into(),
+                    prompt
+                        .get("description")
+                        .cloned()
+                        .unwrap_or_else(|| Value::String(String::new())),
+                );
+                def.insert(
+                    "arguments".into(),
+                    prompt
+                        .get("arguments")
+                        .cloned()
+                        .unwrap_or_else(|| Value::Array(Vec::new())),
+                );
+                def
+            })
+            .collect())
     }
 
     /// Get a prompt from the MCP server.
@@ -630,29 +750,32 @@ impl MCPClient {
         prompt_name: &str,
         arguments: &HashMap<String, Value>,
     ) -> Result<HashMap<String, Value>, anyhow::Error> {
-        // TODO: Integrate with actual MCP session get_prompt
-        // let result = self.session.get_prompt(prompt_name, arguments).await?;
-        // let messages: Vec<Value> = result.messages.iter().map(|msg| {
-        //     serde_json::json!({
-        //         "role": msg.role,
-        //         "content": msg.content,
-        //     })
-        // }).collect();
-        // let mut response = HashMap::new();
-        // response.insert("name".into(), Value::String(prompt_name.to_string()));
-        // response.insert("messages".into(), Value::Array(messages));
-        // response.insert("arguments".into(), serde_json::to_value(arguments)?);
-        // return Ok(response);
-
-        log::debug!(
-            "get_prompt_impl called: prompt='{}', args={:?} (MCP SDK integration pending)",
-            prompt_name,
-            arguments
-        );
-        Err(anyhow::anyhow!(
-            "MCP prompt retrieval not yet implemented: {}",
-            prompt_name
-        ))
+        let result = self
+            .request(
+                "prompts/get",
+                serde_json::json!({ "name": prompt_name, "arguments": arguments }),
+            )
+            .await?;
+        let messages: Vec<Value> = result
+            .get("messages")
+            .and_then(Value::as_array)
+            .map(|messages| {
+                messages
+                    .iter()
+                    .map(|msg| {
+                        serde_json::json!({
+                            "role": msg.get("role"),
+                            "content": msg.get("content"),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut response = HashMap::new();
+        response.insert("name".into(), Value::String(prompt_name.to_string()));
+        response.insert("messages".into(), Value::Array(messages));
+        response.insert("arguments".into(), serde_json::to_value(arguments)?);
+        Ok(response)
     }
 
     // -----------------------------------------------------------------------
@@ -3,8 +3,9 @@
 //! Corresponds to `crewai/mcp/`.
 //!
 //! This module provides the MCP client, server configuration types,
-//! transport layers (Stdio, HTTP, SSE), and tool filtering for connecting
-//! agents to MCP-compatible tool servers.
+//! transport layers (Stdio, HTTP, SSE), tool filtering, toolsets that
+//! register a server's tools for agents, and a server exposing crewAI tools
+//! and crews to MCP hosts.
 //!
 //! MCP allows agents to discover and invoke tools exposed by external
 //! servers using a standardized protocol with different transport mechanisms.
@@ -12,10 +13,14 @@
 pub mod client;
 pub mod config;
 pub mod filters;
+pub mod server;
+pub mod toolset;
 pub mod transports;
 
 // Re-export main types.
 pub use client::MCPClient;
 pub use config::{MCPServerConfig, MCPServerHTTP, MCPServerSSE, MCPServerStdio};
 pub use filters::{StaticToolFilter, ToolFilterContext};
+pub use server::MCPServer;
+pub use toolset::MCPToolset;
 pub use transports::{BaseTransport, TransportType};
@@ -0,0 +1,417 @@
+//! MCP server exposing crewAI tools and crews.
+//!
+//! The counterpart of [`MCPClient`](super::MCPClient): an [`MCPServer`] lets
+//! MCP hosts (Claude Desktop, IDEs, other agents) call the tools of a
+//! [`ToolRegistry`] and run registered crews. Each crew is offered as a tool
+//! named `run_crew_<name>` whose arguments are the crew's kickoff inputs.
+//!
+//! Two transports are provided: newline-delimited JSON-RPC over stdio
+//! ([`MCPServer::serve_stdio`], what desktop hosts spawn) and streamable HTTP
+//! ([`MCPServer::router`], mounted at `/mcp` by the HTTP server). Crew runs
+//! are recorded in the same [`ExecutionStore`] as runs started over HTTP,
+//! and are admitted against tenant quotas when those are configured.
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::Value;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::mcp::client::MCP_PROTOCOL_VERSION;
+use crate::mcp::toolset::tool_args_schema;
+use crate::server::crew_routes::{CrewRegistry, ExecutionStatus, ExecutionStore};
+use crate::server::quotas::QuotaManager;
+use crate::tools::registry::ToolRegistry;
+
+/// Prefix of the tools that run a registered crew.
+pub const CREW_TOOL_PREFIX: &str = "run_crew_";
+
+/// Protocol revisions the server can speak.
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", "2025-06-18"];
+
+/// An MCP server over crewAI tools and crews.
+#[derive(Clone)]
+pub struct MCPServer {
+    /// Server name reported during initialization.
+    pub name: String,
+    /// Server version reported during initialization.
+    pub version: String,
+    tools: Option<ToolRegistry>,
+    crews: Option<(CrewRegistry, ExecutionStore)>,
+    quotas: Option<QuotaManager>,
+}
+
+impl std::fmt::Debug for MCPServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MCPServer")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("tools", &self.tools.as_ref().map(|t| t.names()))
+            .field("crews", &self.crews.as_ref().map(|(c, _)| c.names()))
+            .finish()
+    }
+}
+
+impl MCPServer {
+    /// A server exposing nothing yet.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            tools: None,
+            crews: None,
+            quotas: None,
+        }
+    }
+
+    /// Builder: expose the tools of `registry`.
+    pub fn with_tools(mut self, registry: ToolRegistry) -> Self {
+        self.tools = Some(registry);
+        self
+    }
+
+    /// Builder: expose the crews of `crews`, recording runs in `executions`.
+    pub fn with_crews(mut self, crews: CrewRegistry, executions: ExecutionStore) -> Self {
+        self.crews = Some((crews, executions));
+        self
+    }
+
+    /// Builder: admit crew runs against tenant quotas. Callers then need an
+    /// API key, so this only suits the HTTP transport.
+    pub fn with_quotas(mut self, quotas: QuotaManager) -> Self {
+        self.quotas = Some(quotas);
+        self
+    }
+
+    /// The `tools/list` entries: registry tools, then one per crew.
+    pub fn tool_definitions(&self) -> Vec<Value> {
+        let mut definitions = Vec::new();
+        if let Some(registry) = &self.tools {
+            for name in registry.names() {
+                definitions.push(serde_json::json!({
+                    "name": name,
+                    "description": registry.description(&name).unwrap_or_default(),
+                    "inputSchema": tool_args_schema(
+                        &registry.args_schema(&name).unwrap_or(Value::Null)
+                    ),
+                }));
+            }
+        }
+        if let Some((crews, _)) = &self.crews {
+            for name in crews.names() {
+                let description = crews
+                    .description(&name)
+                    .unwrap_or_else(|| format!("Run the '{}' crew", name));
+                definitions.push(serde_json::json!({
+                    "name": format!("{}{}", CREW_TOOL_PREFIX, name),
+                    "description": format!(
+                        "{}. Arguments are the crew's kickoff inputs.",
+                        description.trim_end_matches('.')
+                    ),
+                    "inputSchema": {
+                        "type": "object",
+                        "additionalProperties": {"type": "string"},
+                    },
+                }));
+            }
+        }
+        definitions
+    }
+
+    /// Run a tool or crew, returning an MCP `CallToolResult`.
+    ///
+    /// Failures are reported in the result (`isError`) so the host's model
+    /// sees them; `Err` means the tool does not exist.
+    pub async fn call_tool(
+        &self,
+        name: &str,
+        arguments: Value,
+        headers: &HeaderMap,
+    ) -> Result<Value, String> {
+        if let Some(crew) = name.strip_prefix(CREW_TOOL_PREFIX) {
+            if let Some((crews, executions)) = &self.crews {
+                if crews.names().iter().any(|n| n == crew) {
+                    return Ok(self
+                        .run_crew(crews, executions, crew, arguments, headers)
+                        .await);
+                }
+            }
+        }
+
+        let registry = self
+            .tools
+            .clone()
+            .filter(|r| r.contains(name))
+            .ok_or_else(|| format!("Unknown tool: {}", name))?;
+        let name = name.to_string();
+        let outcome =
+            tokio::task::spawn_blocking(move || registry.execute(None, &name, &arguments))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|r| r.ok_or_else(|| "Tool was unregistered".to_string()));
+        Ok(match outcome {
+            Ok(Ok(output)) => tool_result(output, false),
+            Ok(Err(e)) => tool_result(e.to_string(), true),
+            Err(e) => tool_result(e, true),
+        })
+    }
+
+    async fn run_crew(
+        &self,
+        crews: &CrewRegistry,
+        executions: &ExecutionStore,
+        name: &str,
+        arguments: Value,
+        headers: &HeaderMap,
+    ) -> Value {
+        let permit = match self
+            .quotas
+            .as_ref()
+            .map(|q| q.admit(headers, name))
+            .transpose()
+        {
+            Ok(permit) => permit,
+            Err(e) => return tool_result(e.to_string(), true),
+        };
+        let crew = match crews.build(name) {
+            Ok(crew) => crew,
+            Err((_, Json(body))) => {
+                return tool_result(body["error"].as_str().unwrap_or("").to_string(), true)
+            }
+        };
+        let inputs: HashMap<String, String> = arguments
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+
+        let tenant = permit.as_ref().map(|p| p.tenant().to_string());
+        let execution_id = executions.insert(name, &crew, inputs, tenant.as_deref());
+        executions.run(execution_id.clone(), crew, permit).await;
+
+        match executions.get(&execution_id) {
+            Some(record) if record.status == ExecutionStatus::Completed => {
+                let raw = record
+                    .result
+                    .as_ref()
+                    .and_then(|r| r.get("raw"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut result = tool_result(raw, false);
+                result["structuredContent"] = serde_json::json!({
+                    "execution_id": execution_id,
+                    "output": record.result,
+                });
+                result
+            }
+            Some(record) => tool_result(
+                record
+                    .error
+                    .unwrap_or_else(|| format!("Crew run ended as {:?}", record.status)),
+                true,
+            ),
+            None => tool_result("Crew run record was dropped".to_string(), true),
+        }
+    }
+
+    /// Handle one JSON-RPC message, returning the response (`None` for
+    /// notifications).
+    pub async fn handle(&self, message: Value, headers: &HeaderMap) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let method = message.get("method").and_then(Value::as_str);
+        let Some(method) = method else {
+            // Responses to server requests are never sent; ignore strays.
+            return id
+                .is_some()
+                .then(|| rpc_error(id.unwrap_or(Value::Null), -32600, "Invalid request"));
+        };
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => {
+                let requested = params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(MCP_PROTOCOL_VERSION);
+                let version = if SUPPORTED_PROTOCOL_VERSIONS.contains(&requested) {
+                    requested
+                } else {
+                    MCP_PROTOCOL_VERSION
+                };
+                Ok(serde_json::json!({
+                    "protocolVersion": version,
+                    "capabilities": {"tools": {"listChanged": false}},
+                    "serverInfo": {"name": self.name, "version": self.version},
+                }))
+            }
+            "ping" => Ok(serde_json::json!({})),
+            "tools/list" => Ok(serde_json::json!({ "tools": self.tool_definitions() })),
+            "tools/call" => match params.get("name").and_then(Value::as_str) {
+                Some(name) => {
+                    let arguments = params
+                        .get("arguments")
+                        .cloned()
+                        .unwrap_or_else(|| serde_json::json!({}));
+                    self.call_tool(name, arguments, headers)
+                        .await
+                        .map_err(|e| (-32602, e))
+                }
+                None => Err((-32602, "Missing tool name".to_string())),
+            },
+            other => Err((-32601, format!("Method not found: {}", other))),
+        };
+        Some(match result {
+            Ok(result) => serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => rpc_error(id, code, &message),
+        })
+    }
+
+    /// Serve newline-delimited JSON-RPC from `reader` to `writer` until the
+    /// input ends.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> std::io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let headers = HeaderMap::new();
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(message, &headers).await,
+                Err(e) => Some(rpc_error(
+                    Value::Null,
+                    -32700,
+                    &format!("Parse error: {}", e),
+                )),
+            };
+            if let Some(response) = response {
+                let mut out = response.to_string();
+                out.push('\n');
+                writer.write_all(out.as_bytes()).await?;
+                writer.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Serve over the process's stdin/stdout. Logs must go to stderr.
+    pub async fn serve_stdio(&self) -> std::io::Result<()> {
+        self.serve(
+            tokio::io::BufReader::new(tokio::io::stdin()),
+            tokio::io::stdout(),
+        )
+        .await
+    }
+
+    /// Streamable HTTP endpoint at `POST /mcp` (JSON responses, no server
+    /// event stream).
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/mcp", post(http_handler))
+            .with_state(self)
+    }
+}
+
+async fn http_handler(
+    State(server): State<MCPServer>,
+    headers: HeaderMap,
+    Json(message): Json<Value>,
+) -> Response {
+    match server.handle(message, &headers).await {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    serde_json::json!({
+        "content": [{"type": "text", "text": text}],
+        "isError": is_error,
+    })
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {"code": code, "message": message},
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::base_tool::Tool;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_stdio_session_lists_and_calls_tools() {
+        let registry = ToolRegistry::new();
+        registry.register(Tool::new(
+            "shout",
+            "Upper-case text",
+            Arc::new(|args: HashMap<String, Value>| {
+                Ok(Value::from(
+                    args["text"].as_str().unwrap_or_default().to_uppercase(),
+                ))
+            }),
+        ));
+        let crews = CrewRegistry::new();
+        crews.register("research", || {
+            crate::crew::Crew::new(Vec::new(), Vec::new())
+        });
+        let server = MCPServer::new("test")
+            .with_tools(registry)
+            .with_crews(crews, ExecutionStore::new());
+
+        let input = [
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05"}}"#,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"shout","arguments":{"text":"hi"}}}"#,
+            r#"{"jsonrpc":"2.0","id":4,"method":"tools/call","params":{"name":"nope"}}"#,
+            r#"{"jsonrpc":"2.0","id":5,"method":"resources/list"}"#,
+        ]
+        .join("\n");
+        let mut output = Vec::new();
+        server
+            .serve(tokio::io::BufReader::new(input.as_bytes()), &mut output)
+            .await
+            .unwrap();
+        let responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(responses.len(), 5, "the notification gets no response");
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2024-11-05");
+        let names: Vec<&str> = responses[1]["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["shout", "run_crew_research"]);
+        assert_eq!(responses[2]["result"]["content"][0]["text"], "HI");
+        assert_eq!(responses[2]["result"]["isError"], false);
+        assert_eq!(responses[3]["error"]["code"], -32602);
+        assert_eq!(responses[4]["error"]["code"], -32601);
+    }
+}
@@ -0,0 +1,340 @@
+//! Exposing an MCP server's tools to agents.
+//!
+//! An [`MCPToolset`] owns the client for one server. Connecting it lists the
+//! server's tools, converts their input schemas into crewAI argument schemas
+//! and wraps each one as an [`MCPNativeTool`]; [`MCPToolset::register`] then
+//! adds them to a [`ToolRegistry`] so agents can call them by name.
+//! Disconnecting removes them again and closes the connection.
+//!
+//! All MCP I/O runs on a dedicated runtime. Transports (child-process pipes,
+//! SSE readers) are bound to the runtime that opened them, so this lets one
+//! connection serve synchronous tool calls from any thread as well as async
+//! callers on other runtimes.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::mcp::client::MCPClient;
+use crate::mcp::config::{ArcToolFilter, MCPServerConfig};
+use crate::mcp::transports::{BaseTransport, HTTPTransport, SSETransport, StdioTransport};
+use crate::tools::mcp_native_tool::MCPNativeTool;
+use crate::tools::registry::ToolRegistry;
+
+/// The runtime all MCP connections live on.
+fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .thread_name("crewai-mcp")
+            .enable_all()
+            .build()
+            .expect("failed to build the MCP runtime")
+    })
+}
+
+/// Run `future` on the MCP runtime and wait for it.
+pub(crate) async fn run<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match runtime().spawn(future).await {
+        Ok(output) => output,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Run `future` on the MCP runtime, blocking the calling thread.
+///
+/// Safe to call from inside another runtime: the future is driven by the
+/// MCP runtime's own threads.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match futures::executor::block_on(runtime().spawn(future)) {
+        Ok(output) => output,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Convert an MCP tool's `inputSchema` into a crewAI argument schema.
+///
+/// The result is always an object schema with `properties` (and `required`
+/// when the server lists any); draft metadata such as `$schema` is dropped.
+pub fn tool_args_schema(input_schema: &Value) -> Value {
+    let mut schema = match input_schema {
+        Value::Object(map) => map.clone(),
+        _ => serde_json::Map::new(),
+    };
+    schema.remove("$schema");
+    schema.insert("type".into(), Value::String("object".into()));
+    if !schema.get("properties").is_some_and(Value::is_object) {
+        schema.insert("properties".into(), Value::Object(serde_json::Map::new()));
+    }
+    if schema
+        .get("required")
+        .is_some_and(|r| r.as_array().is_none_or(|r| r.is_empty()))
+    {
+        schema.remove("required");
+    }
+    Value::Object(schema)
+}
+
+/// Build the transport for a server configuration.
+pub fn transport_for(config: &MCPServerConfig) -> Box<dyn BaseTransport> {
+    match config {
+        MCPServerConfig::Stdio(c) => Box::new(StdioTransport::new(
+            &c.command,
+            Some(c.args.clone()),
+            c.env.clone(),
+        )),
+        MCPServerConfig::Http(c) => Box::new(HTTPTransport::new(
+            &c.url,
+            c.headers.clone(),
+            Some(c.streamable),
+        )),
+        MCPServerConfig::Sse(c) => Box::new(SSETransport::new(&c.url, c.headers.clone())),
+    }
+}
+
+/// Registry a toolset's tools were added to, with their names.
+type Registration = Option<(ToolRegistry, Vec<String>)>;
+
+/// The tools of one MCP server, with the lifecycle of its connection.
+///
+/// Cloning shares the connection.
+#[derive(Clone)]
+pub struct MCPToolset {
+    /// Name prefixed to the server's tool names (`<server>_<tool>`).
+    pub server_name: String,
+    client: Arc<Mutex<MCPClient>>,
+    tool_filter: Option<ArcToolFilter>,
+    registered: Arc<StdMutex<Registration>>,
+}
+
+impl std::fmt::Debug for MCPToolset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MCPToolset")
+            .field("server_name", &self.server_name)
+            .field("tool_filter", &self.tool_filter.is_some())
+            .field("registered", &self.tool_names())
+            .finish()
+    }
+}
+
+impl MCPToolset {
+    /// Wrap a (not yet connected) client.
+    pub fn new(server_name: impl Into<String>, client: MCPClient) -> Self {
+        Self {
+            server_name: server_name.into(),
+            client: Arc::new(Mutex::new(client)),
+            tool_filter: None,
+            registered: Arc::new(StdMutex::new(None)),
+        }
+    }
+
+    /// Toolset for a configured server, honouring its tool filter and
+    /// caching settings.
+    pub fn from_config(server_name: impl Into<String>, config: &MCPServerConfig) -> Self {
+        let client =
+            MCPClient::new(transport_for(config)).with_cache_tools_list(config.cache_tools_list());
+        let mut toolset = Self::new(server_name, client);
+        toolset.tool_filter = config.tool_filter().clone();
+        toolset
+    }
+
+    /// Builder: only expose tools for which `filter` returns `true`.
+    pub fn with_tool_filter(mut self, filter: ArcToolFilter) -> Self {
+        self.tool_filter = Some(filter);
+        self
+    }
+
+    /// The shared client.
+    pub fn client(&self) -> Arc<Mutex<MCPClient>> {
+        self.client.clone()
+    }
+
+    /// Connect (if needed) and wrap the server's tools.
+    pub async fn tools(&self) -> Result<Vec<MCPNativeTool>, anyhow::Error> {
+        let client = self.client.clone();
+        let definitions = run(async move {
+            let mut client = client.lock().await;
+            client.connect().await?;
+            client.list_tools(None).await
+        })
+        .await?;
+
+        Ok(definitions
+            .into_iter()
+            .map(|def| Value::Object(def.into_iter().collect()))
+            .filter(|def| self.tool_filter.as_ref().is_none_or(|filter| filter(def)))
+            .filter_map(|def| {
+                let name = def.get("name")?.as_str()?.to_string();
+                let schema = serde_json::json!({
+                    "description": def.get("description"),
+                    "args_schema": tool_args_schema(def.get("inputSchema").unwrap_or(&Value::Null)),
+                });
+                Some(MCPNativeTool::new(
+                    self.client.clone(),
+                    name,
+                    &schema,
+                    self.server_name.clone(),
+                ))
+            })
+            .collect())
+    }
+
+    /// Connect and add the server's tools to `registry`, returning their
+    /// (prefixed) names.
+    pub async fn register(&self, registry: &ToolRegistry) -> Result<Vec<String>, anyhow::Error> {
+        let tools = self.tools().await?;
+        let names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
+        for tool in tools {
+            registry.register(tool);
+        }
+        log::info!(
+            "Registered {} MCP tool(s) from '{}'",
+            names.len(),
+            self.server_name
+        );
+        *self.registered.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some((registry.clone(), names.clone()));
+        Ok(names)
+    }
+
+    /// [`register`](Self::register) from synchronous code.
+    pub fn register_blocking(&self, registry: &ToolRegistry) -> Result<Vec<String>, anyhow::Error> {
+        let toolset = self.clone();
+        let registry = registry.clone();
+        block_on(async move { toolset.register(&registry).await })
+    }
+
+    /// Names of the tools currently registered by this toolset.
+    pub fn tool_names(&self) -> Vec<String> {
+        self.registered
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|(_, names)| names.clone())
+            .unwrap_or_default()
+    }
+
+    /// Remove the registered tools and close the connection.
+    pub async fn disconnect(&self) -> Result<(), anyhow::Error> {
+        self.unregister();
+        let client = self.client.clone();
+        run(async move { client.lock().await.disconnect().await }).await
+    }
+
+    /// [`disconnect`](Self::disconnect) from synchronous code.
+    pub fn disconnect_blocking(&self) -> Result<(), anyhow::Error> {
+        self.unregister();
+        let client = self.client.clone();
+        block_on(async move { client.lock().await.disconnect().await })
+    }
+
+    fn unregister(&self) {
+        let registered = self
+            .registered
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some((registry, names)) = registered {
+            for name in names {
+                registry.unregister(&name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::config::MCPServerStdio;
+
+    /// A minimal MCP server speaking newline-delimited JSON-RPC.
+    const ECHO_SERVER: &str = r#"
+import json, sys
+for line in sys.stdin:
+    msg = json.loads(line)
+    if "id" not in msg:
+        continue
+    method = msg["method"]
+    if method == "initialize":
+        result = {"protocolVersion": "2025-03-26", "capabilities": {"tools": {}},
+                  "serverInfo": {"name": "echo", "version": "1"}}
+    elif method == "tools/list":
+        if msg["params"].get("cursor") is None:
+            result = {"tools": [{"name": "echo", "description": "Echo text",
+                                 "inputSchema": {"$schema": "x", "type": "object",
+                                                 "properties": {"text": {"type": "string"}},
+                                                 "required": ["text"]}}],
+                      "nextCursor": "2"}
+        else:
+            result = {"tools": [{"name": "hidden", "inputSchema": {}}]}
+    elif method == "tools/call":
+        text = msg["params"]["arguments"]["text"]
+        result = {"content": [{"type": "text", "text": "echo: " + text}]}
+    else:
+        print(json.dumps({"jsonrpc": "2.0", "id": msg["id"],
+                          "error": {"code": -32601, "message": "Method not found"}}), flush=True)
+        continue
+    print(json.dumps({"jsonrpc": "2.0", "id": msg["id"], "result": result}), flush=True)
+"#;
+
+    #[test]
+    fn test_args_schema_conversion() {
+        let schema = tool_args_schema(&serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "properties": {"q": {"type": "string"}},
+            "required": [],
+        }));
+        assert_eq!(
+            schema,
+            serde_json::json!({"type": "object", "properties": {"q": {"type": "string"}}})
+        );
+        assert_eq!(
+            tool_args_schema(&Value::Null),
+            serde_json::json!({"type": "object", "properties": {}})
+        );
+    }
+
+    #[test]
+    fn test_stdio_server_tools_registered_and_called() {
+        if std::process::Command::new("python3")
+            .arg("--version")
+            .output()
+            .is_err()
+        {
+            return;
+        }
+        let config = MCPServerStdio::new("python3")
+            .with_args(vec!["-c".to_string(), ECHO_SERVER.to_string()])
+            .with_tool_filter(Arc::new(|tool: &Value| tool["name"] != "hidden"));
+        let toolset = MCPToolset::from_config("local", &config.into());
+        let registry = ToolRegistry::new();
+
+        let names = toolset.register_blocking(&registry).unwrap();
+        assert_eq!(names, vec!["local_echo".to_string()]);
+        assert_eq!(
+            registry.description("local_echo").as_deref(),
+            Some("Echo text")
+        );
+
+        let output = registry
+            .execute(None, "local_echo", &serde_json::json!({"text": "hi"}))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output, "echo: hi");
+
+        toolset.disconnect_blocking().unwrap();
+        assert!(!registry.contains("local_echo"));
+        assert!(toolset.tool_names().is_empty());
+    }
+}
@@ -5,12 +5,20 @@
 use std::collections::HashMap;
 
 use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
 
-use crate::mcp::transports::{BaseTransport, TransportType};
+use crate::mcp::transports::{is_response_to, sse_events, BaseTransport, TransportType};
+
+/// Header carrying the session assigned by a streamable HTTP server.
+const SESSION_HEADER: &str = "Mcp-Session-Id";
 
 /// HTTP/Streamable HTTP transport for connecting to remote MCP servers.
 ///
 /// Connects to MCP servers over HTTP/HTTPS using the streamable HTTP protocol.
+/// Every message is POSTed to `url`; the server answers with either a JSON
+/// body or an event stream carrying the response. The session id assigned
+/// by the server is sent with later requests and ended on disconnect.
 pub struct HTTPTransport {
     /// Server URL (e.g., "https://api.example.com/mcp").
     pub url: String,
@@ -20,6 +28,10 @@ pub struct HTTPTransport {
     pub streamable: bool,
     /// Whether the transport is currently connected.
     is_connected: bool,
+    /// HTTP client, created on connect.
+    client: Option<reqwest::Client>,
+    /// Session id assigned by the server.
+    session_id: std::sync::Mutex<Option<String>>,
 }
 
 impl HTTPTransport {
@@ -39,7 +51,55 @@ impl HTTPTransport {
             headers: headers.unwrap_or_default(),
             streamable: streamable.unwrap_or(true),
             is_connected: false,
+            client: None,
+            session_id: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn session_id(&self) -> Option<String> {
+        self.session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// POST a message, returning the server's response.
+    async fn post(&self, message: &Value) -> Result<reqwest::Response, anyhow::Error> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("HTTP transport is not connected"))?;
+        let mut request = client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(session_id) = self.session_id() {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "MCP server at {} returned HTTP {}: {}",
+                self.url,
+                status,
+                body
+            );
+        }
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session_id.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some(session_id.to_string());
         }
+        Ok(response)
     }
 }
 
@@ -62,9 +122,7 @@ impl BaseTransport for HTTPTransport {
             return Ok(());
         }
 
-        // TODO: Integrate with actual MCP SDK HTTP client
-        // For now, mark as connected. The actual HTTP connection
-        // will be established when the MCP SDK is integrated.
+        self.client = Some(reqwest::Client::builder().build()?);
         log::info!(
             "HTTP transport connecting to: {} (streamable={})",
             self.url,
@@ -82,6 +140,23 @@ impl BaseTransport for HTTPTransport {
 
         log::info!("HTTP transport disconnecting from: {}", self.url);
 
+        // End the server-side session; failures only leave it to expire.
+        let session_id = self
+            .session_id
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let (Some(client), Some(session_id)) = (&self.client, session_id) {
+            let mut request = client.delete(&self.url).header(SESSION_HEADER, session_id);
+            for (name, value) in &self.headers {
+                request = request.header(name, value);
+            }
+            if let Err(e) = request.send().await {
+                log::debug!("Failed to end MCP session at {}: {}", self.url, e);
+            }
+        }
+
+        self.client = None;
         self.is_connected = false;
         Ok(())
     }
@@ -89,4 +164,43 @@ impl BaseTransport for HTTPTransport {
     fn server_identifier(&self) -> String {
         format!("http:{}", self.url)
     }
+
+    async fn send_request(&self, request: Value) -> Result<Value, anyhow::Error> {
+        let response = self.post(&request).await?;
+        let is_stream = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+
+        if is_stream {
+            let events = sse_events(response);
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                let Ok(message) = serde_json::from_str::<Value>(&event?.data) else {
+                    continue;
+                };
+                if is_response_to(&message, &request) {
+                    return Ok(message);
+                }
+            }
+            anyhow::bail!(
+                "MCP server at {} ended the stream without a response",
+                self.url
+            );
+        }
+
+        match response.json::<Value>().await? {
+            // A batch: pick out the response to this request.
+            Value::Array(messages) => messages
+                .into_iter()
+                .find(|m| is_response_to(m, &request))
+                .ok_or_else(|| anyhow::anyhow!("MCP server at {} sent no response", self.url)),
+            message => Ok(message),
+        }
+    }
+
+    async fn send_notification(&self, notification: Value) -> Result<(), anyhow::Error> {
+        self.post(&notification).await.map(drop)
+    }
 }
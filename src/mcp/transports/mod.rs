@@ -13,14 +13,16 @@
 //!   Events for real-time streaming communication.
 //!
 //! All transports implement the `BaseTransport` trait, which defines the common
-//! interface for connection management. The `TransportType` enum identifies
-//! the type of transport being used.
+//! interface for connection management and JSON-RPC message exchange. The
+//! `TransportType` enum identifies the type of transport being used.
 
 pub mod http;
 pub mod sse;
 pub mod stdio;
 
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use serde_json::Value;
 
 pub use http::HTTPTransport;
 pub use sse::SSETransport;
@@ -157,6 +159,73 @@ pub trait BaseTransport: Send + Sync {
     /// - HTTP: `"http:{url}"`
     /// - SSE: `"sse:{url}"`
     fn server_identifier(&self) -> String;
+
+    /// Send a JSON-RPC request and wait for the response with the same `id`.
+    ///
+    /// Returns the whole response message; the caller inspects its `result`
+    /// or `error` member.
+    async fn send_request(&self, request: Value) -> Result<Value, anyhow::Error>;
+
+    /// Send a JSON-RPC notification, which has no response.
+    async fn send_notification(&self, notification: Value) -> Result<(), anyhow::Error>;
+}
+
+// ---------------------------------------------------------------------------
+// Server-Sent Events decoding
+// ---------------------------------------------------------------------------
+
+/// A single Server-Sent Event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SseEvent {
+    /// Event name (`event:` field); empty for the default `message` event.
+    pub event: String,
+    /// Event payload (`data:` lines joined by newlines).
+    pub data: String,
+}
+
+/// Decode a `text/event-stream` response body into events.
+pub(crate) fn sse_events(
+    response: reqwest::Response,
+) -> impl Stream<Item = Result<SseEvent, anyhow::Error>> + Send {
+    futures::stream::unfold(
+        (response.bytes_stream(), String::new()),
+        |(mut bytes, mut buffer)| async move {
+            loop {
+                if let Some(end) = buffer.find("\n\n") {
+                    let block: String = buffer.drain(..end + 2).collect();
+                    let mut event = SseEvent::default();
+                    let mut data = Vec::new();
+                    for line in block.lines() {
+                        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+                        let value = value.strip_prefix(' ').unwrap_or(value);
+                        match field {
+                            "event" => event.event = value.to_string(),
+                            "data" => data.push(value),
+                            _ => {}
+                        }
+                    }
+                    if data.is_empty() {
+                        continue;
+                    }
+                    event.data = data.join("\n");
+                    return Some((Ok(event), (bytes, buffer)));
+                }
+                match bytes.next().await? {
+                    Ok(chunk) => {
+                        buffer.push_str(&String::from_utf8_lossy(&chunk).replace('\r', ""))
+                    }
+                    Err(e) => return Some((Err(e.into()), (bytes, String::new()))),
+                }
+            }
+        },
+    )
+}
+
+/// Whether `message` is the response to `request` (same `id`, no `method`).
+pub(crate) fn is_response_to(message: &Value, request: &Value) -> bool {
+    message.get("method").is_none()
+        && message.get("id").is_some()
+        && message.get("id") == request.get("id")
 }
 
 // ---------------------------------------------------------------------------
@@ -274,6 +343,103 @@ mod tests {
         assert_eq!(transport_plain.transport_type(), TransportType::Http);
     }
 
+    /// Answer the requests used by the round-trip test.
+    fn mcp_reply(message: &Value) -> Option<Value> {
+        let id = message.get("id")?;
+        let result = match message["method"].as_str()? {
+            "initialize" => {
+                serde_json::json!({"protocolVersion": "2025-03-26", "capabilities": {}})
+            }
+            "tools/call" => serde_json::json!({
+                "content": [{"type": "text", "text": message["params"]["arguments"]["text"]}],
+            }),
+            _ => return None,
+        };
+        Some(serde_json::json!({"jsonrpc": "2.0", "id": id, "result": result}))
+    }
+
+    #[tokio::test]
+    async fn test_http_and_sse_round_trip() {
+        use axum::body::Body;
+        use axum::extract::State;
+        use axum::response::Response;
+        use axum::routing::{get, post};
+        use tokio::sync::mpsc;
+
+        use crate::mcp::client::MCPClient;
+
+        type Events = std::sync::Arc<tokio::sync::Mutex<Option<mpsc::UnboundedSender<String>>>>;
+
+        // Streamable HTTP answers tool calls as an event stream; the legacy
+        // SSE endpoint pushes every response onto the GET stream.
+        async fn streamable(headers: axum::http::HeaderMap, body: String) -> Response {
+            let message: Value = serde_json::from_str(&body).unwrap();
+            let Some(reply) = mcp_reply(&message) else {
+                return Response::builder().status(202).body(Body::empty()).unwrap();
+            };
+            if message["method"] == "initialize" {
+                return Response::builder()
+                    .header("Content-Type", "application/json")
+                    .header("Mcp-Session-Id", "s-1")
+                    .body(Body::from(reply.to_string()))
+                    .unwrap();
+            }
+            assert_eq!(headers["mcp-session-id"], "s-1");
+            Response::builder()
+                .header("Content-Type", "text/event-stream")
+                .body(Body::from(format!("event: message\ndata: {}\n\n", reply)))
+                .unwrap()
+        }
+        async fn stream(State(events): State<Events>) -> Response {
+            let (tx, rx) = mpsc::unbounded_channel();
+            tx.send("event: endpoint\ndata: /messages?session=1\n\n".to_string())
+                .unwrap();
+            *events.lock().await = Some(tx);
+            let body = futures::stream::unfold(rx, |mut rx| async move {
+                rx.recv()
+                    .await
+                    .map(|chunk| (Ok::<_, std::convert::Infallible>(chunk), rx))
+            });
+            Response::builder()
+                .header("Content-Type", "text/event-stream")
+                .body(Body::from_stream(body))
+                .unwrap()
+        }
+        async fn messages(State(events): State<Events>, body: String) -> Response {
+            let message: Value = serde_json::from_str(&body).unwrap();
+            if let Some(reply) = mcp_reply(&message) {
+                let events = events.lock().await;
+                let tx = events.as_ref().unwrap();
+                tx.send(format!("event: message\ndata: {}\n\n", reply))
+                    .unwrap();
+            }
+            Response::builder().status(202).body(Body::empty()).unwrap()
+        }
+
+        let app = axum::Router::new()
+            .route("/mcp", post(streamable))
+            .route("/sse", get(stream))
+            .route("/messages", post(messages))
+            .with_state(Events::default());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let transports: Vec<Box<dyn BaseTransport>> = vec![
+            Box::new(HTTPTransport::new(&format!("{}/mcp", base), None, None)),
+            Box::new(SSETransport::new(&format!("{}/sse", base), None)),
+        ];
+        for transport in transports {
+            let mut client = MCPClient::new(transport).with_max_retries(1);
+            client.connect().await.unwrap();
+            assert!(client.connected());
+            let mut args = std::collections::HashMap::new();
+            args.insert("text".to_string(), serde_json::json!("pong"));
+            assert_eq!(client.call_tool("echo", Some(args)).await.unwrap(), "pong");
+            client.disconnect().await.unwrap();
+        }
+    }
+
     #[test]
     fn test_sse_transport_basic() {
         let transport = SSETransport::new("https://example.com/sse", None);
@@ -3,15 +3,25 @@
 //! Port of crewai/mcp/transports/sse.py
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 
-use crate::mcp::transports::{BaseTransport, TransportType};
+use crate::mcp::transports::{sse_events, BaseTransport, TransportType};
+
+/// Responses awaited by in-flight requests, keyed by JSON-RPC id.
+type Pending = Arc<Mutex<HashMap<String, oneshot::Sender<Value>>>>;
 
 /// SSE transport for connecting to remote MCP servers.
 ///
 /// Connects to MCP servers using Server-Sent Events for
-/// real-time streaming communication.
+/// real-time streaming communication. The server first sends an `endpoint`
+/// event naming the URL messages are POSTed to; responses then arrive as
+/// `message` events on the stream and are matched to requests by id.
 pub struct SSETransport {
     /// Server URL (e.g., "https://api.example.com/mcp/sse").
     pub url: String,
@@ -19,6 +29,14 @@ pub struct SSETransport {
     pub headers: HashMap<String, String>,
     /// Whether the transport is currently connected.
     is_connected: bool,
+    /// HTTP client, created on connect.
+    client: Option<reqwest::Client>,
+    /// URL messages are POSTed to, announced by the server.
+    endpoint: Option<reqwest::Url>,
+    /// Requests awaiting their response.
+    pending: Pending,
+    /// Task reading the event stream.
+    reader: Option<JoinHandle<()>>,
 }
 
 impl SSETransport {
@@ -32,6 +50,51 @@ impl SSETransport {
             url: url.to_string(),
             headers: headers.unwrap_or_default(),
             is_connected: false,
+            client: None,
+            endpoint: None,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            reader: None,
+        }
+    }
+
+    fn with_headers(&self, mut request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request
+    }
+
+    fn post_target(&self) -> Result<(&reqwest::Client, &reqwest::Url), anyhow::Error> {
+        match (&self.client, &self.endpoint) {
+            (Some(client), Some(endpoint)) => Ok((client, endpoint)),
+            _ => anyhow::bail!("SSE transport is not connected"),
+        }
+    }
+
+    async fn post(&self, message: &Value) -> Result<(), anyhow::Error> {
+        let (client, endpoint) = self.post_target()?;
+        let response = self
+            .with_headers(client.post(endpoint.clone()).json(message))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "MCP server at {} returned HTTP {}: {}",
+                endpoint,
+                status,
+                body
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SSETransport {
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
         }
     }
 }
@@ -51,11 +114,64 @@ impl BaseTransport for SSETransport {
             return Ok(());
         }
 
-        // TODO: Integrate with actual MCP SDK SSE client
-        // For now, mark as connected. The actual SSE connection
-        // will be established when the MCP SDK is integrated.
         log::info!("SSE transport connecting to: {}", self.url);
 
+        let client = reqwest::Client::builder().build()?;
+        let response = self
+            .with_headers(client.get(&self.url).header("Accept", "text/event-stream"))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "MCP server at {} returned HTTP {}",
+                self.url,
+                response.status()
+            );
+        }
+        let base = response.url().clone();
+        let mut events = Box::pin(sse_events(response));
+
+        // The first event announces where to POST messages.
+        let endpoint = loop {
+            let event = events.next().await.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "SSE stream from {} closed before the endpoint event",
+                    self.url
+                )
+            })??;
+            if event.event == "endpoint" {
+                break base.join(event.data.trim())?;
+            }
+        };
+
+        let pending = self.pending.clone();
+        self.reader = Some(tokio::spawn(async move {
+            while let Some(Ok(event)) = events.next().await {
+                if !matches!(event.event.as_str(), "" | "message") {
+                    continue;
+                }
+                let Ok(message) = serde_json::from_str::<Value>(&event.data) else {
+                    continue;
+                };
+                let waiter = match (message.get("method"), message.get("id")) {
+                    (None, Some(id)) => pending
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .remove(&id.to_string()),
+                    _ => None,
+                };
+                match waiter {
+                    Some(waiter) => {
+                        let _ = waiter.send(message);
+                    }
+                    None => log::debug!("Unhandled MCP SSE message: {}", message),
+                }
+            }
+            // Fail the requests still waiting on the closed stream.
+            pending.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        }));
+        self.client = Some(client);
+        self.endpoint = Some(endpoint);
         self.is_connected = true;
         Ok(())
     }
@@ -67,6 +183,15 @@ impl BaseTransport for SSETransport {
 
         log::info!("SSE transport disconnecting from: {}", self.url);
 
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        self.client = None;
+        self.endpoint = None;
         self.is_connected = false;
         Ok(())
     }
@@ -74,4 +199,30 @@ impl BaseTransport for SSETransport {
     fn server_identifier(&self) -> String {
         format!("sse:{}", self.url)
     }
+
+    async fn send_request(&self, request: Value) -> Result<Value, anyhow::Error> {
+        let id = request
+            .get("id")
+            .map(Value::to_string)
+            .ok_or_else(|| anyhow::anyhow!("JSON-RPC request without an id"))?;
+        let (tx, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id.clone(), tx);
+
+        if let Err(e) = self.post(&request).await {
+            self.pending
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&id);
+            return Err(e);
+        }
+        rx.await
+            .map_err(|_| anyhow::anyhow!("SSE stream from {} closed before the response", self.url))
+    }
+
+    async fn send_notification(&self, notification: Value) -> Result<(), anyhow::Error> {
+        self.post(&notification).await
+    }
 }
@@ -4,17 +4,35 @@
 
 use std::collections::HashMap;
 use std::process::Stdio;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use tokio::process::{Child, Command};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
 
-use crate::mcp::transports::{BaseTransport, TransportType};
+use crate::mcp::transports::{is_response_to, BaseTransport, TransportType};
+
+/// How long a server may take to exit after its stdin is closed before it
+/// is killed.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+/// The server's stdin/stdout, carrying newline-delimited JSON-RPC messages.
+struct StdioPipes {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
 
 /// Stdio transport for connecting to local MCP servers.
 ///
 /// Connects to MCP servers running as local processes, communicating
 /// via standard input/output streams. Supports Python, Node.js, and
 /// other command-line servers.
+///
+/// Messages are newline-delimited JSON. Requests are serialized: each one
+/// holds the pipes until its response arrives. The server's stderr is
+/// forwarded to the log.
 pub struct StdioTransport {
     /// Command to execute (e.g., "python", "node", "npx").
     pub command: String,
@@ -26,6 +44,8 @@ pub struct StdioTransport {
     is_connected: bool,
     /// The child process handle.
     process: Option<Child>,
+    /// Pipes to the child process while connected.
+    pipes: Option<Mutex<StdioPipes>>,
 }
 
 impl StdioTransport {
@@ -46,8 +66,24 @@ impl StdioTransport {
             env: env.unwrap_or_default(),
             is_connected: false,
             process: None,
+            pipes: None,
         }
     }
+
+    fn pipes(&self) -> Result<&Mutex<StdioPipes>, anyhow::Error> {
+        self.pipes
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Stdio transport is not connected"))
+    }
+}
+
+/// Write one message as a line of JSON.
+async fn write_message(stdin: &mut ChildStdin, message: &Value) -> Result<(), anyhow::Error> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.flush().await?;
+    Ok(())
 }
 
 #[async_trait]
@@ -76,7 +112,7 @@ impl BaseTransport for StdioTransport {
             cmd.env(key, value);
         }
 
-        let child = cmd.spawn().map_err(|e| {
+        let mut child = cmd.spawn().map_err(|e| {
             anyhow::anyhow!(
                 "Failed to start MCP server process '{}': {}",
                 self.command,
@@ -84,6 +120,24 @@ impl BaseTransport for StdioTransport {
             )
         })?;
 
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill().await;
+            anyhow::bail!("MCP server process '{}' has no stdio pipes", self.command);
+        };
+        if let Some(stderr) = child.stderr.take() {
+            let command = self.command.clone();
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    log::debug!("[mcp:{}] {}", command, line);
+                }
+            });
+        }
+
+        self.pipes = Some(Mutex::new(StdioPipes {
+            stdin,
+            stdout: BufReader::new(stdout),
+        }));
         self.process = Some(child);
         self.is_connected = true;
 
@@ -101,12 +155,17 @@ impl BaseTransport for StdioTransport {
             return Ok(());
         }
 
-        if let Some(ref mut process) = self.process {
-            // Try graceful termination first
-            let _ = process.kill().await;
+        // Closing stdin asks the server to exit; kill it if it does not.
+        self.pipes = None;
+        if let Some(mut process) = self.process.take() {
+            if tokio::time::timeout(SHUTDOWN_GRACE, process.wait())
+                .await
+                .is_err()
+            {
+                let _ = process.kill().await;
+            }
         }
 
-        self.process = None;
         self.is_connected = false;
 
         log::info!(
@@ -121,6 +180,54 @@ impl BaseTransport for StdioTransport {
     fn server_identifier(&self) -> String {
         format!("stdio:{}:{}", self.command, self.args.join(":"))
     }
+
+    async fn send_request(&self, request: Value) -> Result<Value, anyhow::Error> {
+        let mut pipes = self.pipes()?.lock().await;
+        let StdioPipes { stdin, stdout } = &mut *pipes;
+        write_message(stdin, &request).await?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if stdout.read_line(&mut line).await? == 0 {
+                anyhow::bail!("MCP server '{}' closed its output", self.command);
+            }
+            let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                if !line.trim().is_empty() {
+                    log::debug!(
+                        "[mcp:{}] ignoring non-JSON output: {}",
+                        self.command,
+                        line.trim()
+                    );
+                }
+                continue;
+            };
+            if is_response_to(&message, &request) {
+                return Ok(message);
+            }
+            match (message.get("method"), message.get("id")) {
+                // A request from the server: answer pings, decline the rest.
+                (Some(method), Some(id)) => {
+                    let reply = if method == "ping" {
+                        serde_json::json!({"jsonrpc": "2.0", "id": id, "result": {}})
+                    } else {
+                        serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {"code": -32601, "message": "Method not found"},
+                        })
+                    };
+                    write_message(stdin, &reply).await?;
+                }
+                _ => log::debug!("[mcp:{}] unhandled message: {}", self.command, message),
+            }
+        }
+    }
+
+    async fn send_notification(&self, notification: Value) -> Result<(), anyhow::Error> {
+        let mut pipes = self.pipes()?.lock().await;
+        write_message(&mut pipes.stdin, &notification).await
+    }
 }
 
 impl Drop for StdioTransport {
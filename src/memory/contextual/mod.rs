@@ -2,7 +2,9 @@
 //!
 //! Port of crewai/memory/contextual/contextual_memory.py
 
+use std::collections::HashMap;
 
+use serde_json::Value;
 
 use crate::memory::entity::EntityMemory;
 use crate::memory::external::ExternalMemory;
@@ -163,34 +165,8 @@ impl ContextualMemory {
         };
 
         let ltm_results = ltm.search(task, 2)?;
-        if ltm_results.is_empty() {
-            return Ok(None);
-        }
-
-        let mut suggestions: Vec<String> = Vec::new();
-        for result in &ltm_results {
-            if let Some(metadata) = result.get("metadata") {
-                if let Some(suggs) = metadata.get("suggestions") {
-                    if let Some(arr) = suggs.as_array() {
-                        for s in arr {
-                            if let Some(text) = s.as_str() {
-                                if !suggestions.contains(&text.to_string()) {
-                                    suggestions.push(text.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        let formatted: Vec<String> = suggestions.iter().map(|s| format!("- {}", s)).collect();
 
-        if formatted.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(format!("Historical Data:\n{}", formatted.join("\n"))))
-        }
+        Ok(format_ltm_results(&ltm_results))
     }
 
     /// Fetch relevant entity information from Entity Memory.
@@ -264,34 +240,8 @@ impl ContextualMemory {
         };
 
         let ltm_results = ltm.asearch(task, 2).await?;
-        if ltm_results.is_empty() {
-            return Ok(None);
-        }
-
-        let mut suggestions: Vec<String> = Vec::new();
-        for result in &ltm_results {
-            if let Some(metadata) = result.get("metadata") {
-                if let Some(suggs) = metadata.get("suggestions") {
-                    if let Some(arr) = suggs.as_array() {
-                        for s in arr {
-                            if let Some(text) = s.as_str() {
-                                if !suggestions.contains(&text.to_string()) {
-                                    suggestions.push(text.to_string());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
 
-        let formatted: Vec<String> = suggestions.iter().map(|s| format!("- {}", s)).collect();
-
-        if formatted.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(format!("Historical Data:\n{}", formatted.join("\n"))))
-        }
+        Ok(format_ltm_results(&ltm_results))
     }
 
     /// Fetch entity context asynchronously.
@@ -336,3 +286,42 @@ impl ContextualMemory {
         Ok(format!("External memories:\n{}", formatted.join("\n")))
     }
 }
+
+/// The suggestions and results of the past tasks in `ltm_results`, for the
+/// prompt; `None` without any.
+fn format_ltm_results(ltm_results: &[HashMap<String, Value>]) -> Option<String> {
+    let mut suggestions: Vec<&str> = Vec::new();
+    for result in ltm_results {
+        let listed = result
+            .get("metadata")
+            .and_then(|m| m.get("suggestions"))
+            .and_then(Value::as_array);
+        for text in listed.into_iter().flatten().filter_map(Value::as_str) {
+            if !suggestions.contains(&text) {
+                suggestions.push(text);
+            }
+        }
+    }
+    let past_results: Vec<String> = ltm_results
+        .iter()
+        .filter_map(|result| {
+            let output = result.get("output").and_then(Value::as_str)?;
+            let task = result.get("task_description").and_then(Value::as_str)?;
+            let score = result.get("score").and_then(Value::as_f64).unwrap_or(0.0);
+            (!output.is_empty()).then(|| format!("- {} (score {}): {}", task, score, output))
+        })
+        .collect();
+
+    let mut parts = Vec::new();
+    if !suggestions.is_empty() {
+        let formatted: Vec<String> = suggestions.iter().map(|s| format!("- {}", s)).collect();
+        parts.push(format!("Historical Data:\n{}", formatted.join("\n")));
+    }
+    if !past_results.is_empty() {
+        parts.push(format!(
+            "Results of similar past tasks:\n{}",
+            past_results.join("\n")
+        ));
+    }
+    (!parts.is_empty()).then(|| parts.join("\n"))
+}
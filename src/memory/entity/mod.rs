@@ -10,6 +10,7 @@ use serde_json::Value;
 use crate::memory::memory::Memory;
 use crate::memory::storage::interface::Storage;
 use crate::memory::storage::rag_storage::RAGStorage;
+use crate::rag::embeddings::EmbedderConfig;
 
 /// An item stored in entity memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,24 +68,19 @@ impl EntityMemory {
     /// * `crew_agent_roles` - Optional list of agent roles.
     /// * `path` - Optional persist directory path.
     pub fn new(
-        embedder_config: Option<Value>,
+        embedder_config: Option<EmbedderConfig>,
         storage: Option<Box<dyn Storage>>,
         crew_agent_roles: Option<Vec<String>>,
         path: Option<String>,
     ) -> Self {
-        let memory_provider = embedder_config
-            .as_ref()
-            .and_then(|c| c.get("provider"))
-            .and_then(|p| p.as_str())
-            .map(|s| s.to_string());
+        let memory_provider = embedder_config.as_ref().map(|c| c.provider.clone());
 
         let storage: Box<dyn Storage> = if let Some(s) = storage {
             s
         } else if memory_provider.as_deref() == Some("mem0") {
             let config = embedder_config
                 .as_ref()
-                .and_then(|c| c.get("config"))
-                .and_then(|c| serde_json::from_value::<HashMap<String, Value>>(c.clone()).ok());
+                .map(|c| c.provider_config().into_iter().collect());
             Box::new(
                 crate::memory::storage::mem0_storage::Mem0Storage::new("entities", None, config)
                     .expect("Failed to create Mem0Storage"),
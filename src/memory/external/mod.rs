@@ -9,6 +9,7 @@ use serde_json::Value;
 
 use crate::memory::memory::Memory;
 use crate::memory::storage::interface::Storage;
+use crate::rag::embeddings::EmbedderConfig;
 
 /// An item stored in external memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,21 +56,16 @@ impl ExternalMemory {
     /// Create a storage backend for external memory based on the embedder config.
     ///
     /// # Arguments
-    /// * `embedder_config` - The embedder configuration with a "provider" key.
+    /// * `embedder_config` - The embedder configuration.
     ///
     /// # Returns
     /// A boxed Storage implementation, or an error if the provider is not supported.
-    pub fn create_storage(embedder_config: &Value) -> Result<Box<dyn Storage>, anyhow::Error> {
-        let provider = embedder_config
-            .get("provider")
-            .and_then(|p| p.as_str())
-            .ok_or_else(|| anyhow::anyhow!("embedder_config must include a 'provider' key"))?;
-
-        match provider {
+    pub fn create_storage(
+        embedder_config: &EmbedderConfig,
+    ) -> Result<Box<dyn Storage>, anyhow::Error> {
+        match embedder_config.provider.as_str() {
             "mem0" => {
-                let config = embedder_config
-                    .get("config")
-                    .and_then(|c| serde_json::from_value::<HashMap<String, Value>>(c.clone()).ok());
+                let config = Some(embedder_config.provider_config().into_iter().collect());
                 let storage = crate::memory::storage::mem0_storage::Mem0Storage::new(
                     "external", None, config,
                 )?;
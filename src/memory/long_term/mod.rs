@@ -18,6 +18,9 @@ pub struct LongTermMemoryItem {
     pub task: String,
     /// The expected output description.
     pub expected_output: String,
+    /// The output of the task.
+    #[serde(default)]
+    pub output: String,
     /// Timestamp of the memory.
     pub datetime: String,
     /// Optional quality score.
@@ -32,6 +35,7 @@ impl LongTermMemoryItem {
         agent: String,
         task: String,
         expected_output: String,
+        output: String,
         datetime: String,
         quality: Option<f64>,
         metadata: Option<HashMap<String, Value>>,
@@ -40,6 +44,7 @@ impl LongTermMemoryItem {
             agent,
             task,
             expected_output,
+            output,
             datetime,
             quality,
             metadata: metadata.unwrap_or_default(),
@@ -76,24 +81,22 @@ impl LongTermMemory {
     /// # Arguments
     /// * `item` - The LongTermMemoryItem to save.
     pub fn save(&self, item: &LongTermMemoryItem) -> Result<(), anyhow::Error> {
-        let mut metadata = item.metadata.clone();
-        metadata.insert("agent".to_string(), Value::String(item.agent.clone()));
-        metadata.insert(
-            "expected_output".to_string(),
-            Value::String(item.expected_output.clone()),
-        );
-
-        let score = metadata
-            .get("quality")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-
+        let (metadata, score) = Self::metadata_and_score(item);
         self.storage
-            .save(&item.task, &metadata, &item.datetime, score)
+            .save_with_output(&item.task, &item.output, &metadata, &item.datetime, score)
     }
 
     /// Save an item to long-term memory asynchronously.
     pub async fn asave(&self, item: &LongTermMemoryItem) -> Result<(), anyhow::Error> {
+        let (metadata, score) = Self::metadata_and_score(item);
+        self.storage
+            .asave_with_output(&item.task, &item.output, &metadata, &item.datetime, score)
+            .await
+    }
+
+    /// The metadata stored for `item`, with its agent and expected output,
+    /// and its score: its quality, else the `quality` in its metadata.
+    fn metadata_and_score(item: &LongTermMemoryItem) -> (HashMap<String, Value>, f64) {
         let mut metadata = item.metadata.clone();
         metadata.insert("agent".to_string(), Value::String(item.agent.clone()));
         metadata.insert(
@@ -101,17 +104,15 @@ impl LongTermMemory {
             Value::String(item.expected_output.clone()),
         );
 
-        let score = metadata
-            .get("quality")
-            .and_then(|v| v.as_f64())
+        let score = item
+            .quality
+            .or_else(|| metadata.get("quality").and_then(|v| v.as_f64()))
             .unwrap_or(0.0);
-
-        self.storage
-            .asave(&item.task, &metadata, &item.datetime, score)
-            .await
+        (metadata, score)
     }
 
-    /// Search long-term memory for relevant entries.
+    /// Search long-term memory for the entries of the tasks most similar
+    /// to `task` (see [`LTMSQLiteStorage::load`]).
     ///
     /// # Arguments
     /// * `task` - The task description to search for.
@@ -8,13 +8,14 @@ use std::collections::HashMap;
 use serde_json::Value;
 
 use crate::memory::storage::interface::Storage;
+use crate::rag::embeddings::EmbedderConfig;
 
 /// Base class for memory, supporting agent tags and generic metadata.
 pub struct Memory {
     /// The storage backend for this memory instance.
     pub storage: Box<dyn Storage>,
-    /// Optional embedder configuration (mirrors Python's EmbedderConfig | dict | None).
-    pub embedder_config: Option<Value>,
+    /// Optional embedder configuration.
+    pub embedder_config: Option<EmbedderConfig>,
     /// Optional reference to the crew that owns this memory.
     pub crew: Option<Box<dyn Any + Send + Sync>>,
     /// Optional reference to the current agent.
@@ -36,7 +37,10 @@ impl Memory {
     }
 
     /// Create a new Memory instance with storage and embedder config.
-    pub fn with_embedder(storage: Box<dyn Storage>, embedder_config: Option<Value>) -> Self {
+    pub fn with_embedder(
+        storage: Box<dyn Storage>,
+        embedder_config: Option<EmbedderConfig>,
+    ) -> Self {
         Self {
             storage,
             embedder_config,
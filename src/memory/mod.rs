@@ -1,13 +1,15 @@
 //! Memory system for crewAI agents.
 //!
 //! This module provides the memory subsystem including short-term, long-term,
-//! entity, contextual, and external memory types, along with their storage backends.
+//! entity, contextual, and external memory types, along with their storage backends,
+//! and namespaces shared between crews.
 
 pub mod contextual;
 pub mod entity;
 pub mod external;
 pub mod long_term;
 pub mod memory;
+pub mod shared;
 pub mod short_term;
 pub mod storage;
 
@@ -16,4 +18,5 @@ pub use entity::{EntityMemory, EntityMemoryItem};
 pub use external::{ExternalMemory, ExternalMemoryItem};
 pub use long_term::{LongTermMemory, LongTermMemoryItem};
 pub use memory::Memory;
+pub use shared::{ConflictStrategy, CrewSharedMemory, NamespaceConfig, SharedMemory};
 pub use short_term::{ShortTermMemory, ShortTermMemoryItem};
@@ -0,0 +1,632 @@
+//! Named memory namespaces shared between crews.
+//!
+//! A [`SharedMemory`] holds namespaces (e.g. `customer_context`) that several
+//! crews read and write over time. Each namespace lists the crews allowed to
+//! read and to write it; these permissions are installed as rules in the
+//! [`PolicyEngine`], which every access is evaluated against, so they can be
+//! audited and combined with other rules.
+//!
+//! Every entry carries a version. A write may name the version it was based
+//! on; when another crew has written since, the namespace's
+//! [`ConflictStrategy`] decides the outcome.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::policy::{
+    ConditionOperator, PolicyAction, PolicyCondition, PolicyEffect, PolicyEngine, PolicyPrincipal,
+    PolicyRequest, PolicyResource, PolicyRule,
+};
+
+/// How concurrent writes to the same key are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// The later write replaces the entry.
+    #[default]
+    LastWriteWins,
+    /// A stale write fails with [`SharedMemoryError::Conflict`]. Writes
+    /// without a base version may only create new keys.
+    Reject,
+    /// A stale write is deep-merged into the current value: objects are
+    /// merged key by key, arrays are concatenated, other values replaced.
+    Merge,
+}
+
+/// Configuration of a namespace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NamespaceConfig {
+    /// Namespace name.
+    pub name: String,
+    /// Crews allowed to read; `"*"` allows every crew.
+    #[serde(default)]
+    pub readers: Vec<String>,
+    /// Crews allowed to write; `"*"` allows every crew. Writers may also read.
+    #[serde(default)]
+    pub writers: Vec<String>,
+    /// Resolution of concurrent writes.
+    #[serde(default)]
+    pub conflict: ConflictStrategy,
+}
+
+impl NamespaceConfig {
+    /// A namespace no crew may access until granted.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            readers: Vec::new(),
+            writers: Vec::new(),
+            conflict: ConflictStrategy::default(),
+        }
+    }
+
+    /// Builder: allow `crew` to read.
+    pub fn with_reader(mut self, crew: impl Into<String>) -> Self {
+        self.readers.push(crew.into());
+        self
+    }
+
+    /// Builder: allow `crew` to read and write.
+    pub fn with_writer(mut self, crew: impl Into<String>) -> Self {
+        self.writers.push(crew.into());
+        self
+    }
+
+    /// Builder: set the conflict strategy.
+    pub fn with_conflict(mut self, conflict: ConflictStrategy) -> Self {
+        self.conflict = conflict;
+        self
+    }
+
+    /// Policy rules denying reads and writes to crews not listed.
+    ///
+    /// Access is checked with the crew name as the request's `agent_id`
+    /// and in its `crew` context key.
+    pub fn policy_rules(&self) -> Vec<PolicyRule> {
+        let mut readers = self.readers.clone();
+        readers.extend(self.writers.iter().cloned());
+        [
+            ("read", PolicyAction::MemoryRead, readers),
+            ("write", PolicyAction::MemoryWrite, self.writers.clone()),
+        ]
+        .into_iter()
+        .filter(|(_, _, crews)| !crews.iter().any(|c| c == "*"))
+        .map(|(access, action, crews)| PolicyRule {
+            name: rule_name(&self.name, access),
+            description: format!(
+                "Only {} may {} shared memory namespace '{}'",
+                if crews.is_empty() {
+                    "no crew".to_string()
+                } else {
+                    crews.join(", ")
+                },
+                access,
+                self.name
+            ),
+            effect: PolicyEffect::Deny,
+            principal: PolicyPrincipal::All,
+            action,
+            resource: PolicyResource::Collection(namespace_resource(&self.name)),
+            conditions: vec![PolicyCondition {
+                key: "crew".to_string(),
+                operator: ConditionOperator::NotIn,
+                value: Value::from(crews),
+            }],
+            priority: 50,
+        })
+        .collect()
+    }
+}
+
+fn rule_name(namespace: &str, access: &str) -> String {
+    format!("shared_memory:{}:{}", namespace, access)
+}
+
+fn namespace_resource(namespace: &str) -> String {
+    format!("shared:{}", namespace)
+}
+
+/// A value in a namespace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharedEntry {
+    /// Stored value.
+    pub value: Value,
+    /// Incremented on every write, starting at 1.
+    pub version: u64,
+    /// Crew that wrote the current value.
+    pub written_by: String,
+    /// When the current value was written.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Errors from shared memory access.
+#[derive(Debug, Error)]
+pub enum SharedMemoryError {
+    #[error("Shared memory namespace '{0}' does not exist")]
+    UnknownNamespace(String),
+    #[error("Crew '{crew}' may not {access} namespace '{namespace}': {reason}")]
+    AccessDenied {
+        crew: String,
+        namespace: String,
+        access: &'static str,
+        reason: String,
+    },
+    #[error("Write conflict on '{namespace}/{key}': based on version {expected:?}, current is {}", current.version)]
+    Conflict {
+        namespace: String,
+        key: String,
+        expected: Option<u64>,
+        current: Box<SharedEntry>,
+    },
+    #[error("Failed to persist shared memory: {0}")]
+    Persistence(#[from] std::io::Error),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Namespace {
+    config: Option<NamespaceConfig>,
+    entries: HashMap<String, SharedEntry>,
+}
+
+/// Namespaced memory shared between crews.
+///
+/// Cloning shares the store.
+#[derive(Debug, Clone)]
+pub struct SharedMemory {
+    namespaces: Arc<RwLock<HashMap<String, Namespace>>>,
+    policy: Arc<Mutex<PolicyEngine>>,
+    path: Option<PathBuf>,
+}
+
+impl Default for SharedMemory {
+    fn default() -> Self {
+        Self::new(Arc::new(Mutex::new(PolicyEngine::new())))
+    }
+}
+
+impl SharedMemory {
+    /// An in-memory store enforcing access through `policy`.
+    pub fn new(policy: Arc<Mutex<PolicyEngine>>) -> Self {
+        Self {
+            namespaces: Arc::new(RwLock::new(HashMap::new())),
+            policy,
+            path: None,
+        }
+    }
+
+    /// A store persisted as JSON at `path`, loading what is already there.
+    ///
+    /// Namespace configurations are persisted too; their policy rules are
+    /// reinstalled on load.
+    pub fn open(
+        path: impl Into<PathBuf>,
+        policy: Arc<Mutex<PolicyEngine>>,
+    ) -> Result<Self, SharedMemoryError> {
+        let path = path.into();
+        let mut memory = Self::new(policy);
+        if path.exists() {
+            let namespaces: HashMap<String, Namespace> =
+                serde_json::from_slice(&std::fs::read(&path)?).map_err(std::io::Error::from)?;
+            for config in namespaces.values().filter_map(|ns| ns.config.as_ref()) {
+                memory.install_rules(config);
+            }
+            *memory.namespaces.write().unwrap_or_else(|e| e.into_inner()) = namespaces;
+        }
+        memory.path = Some(path);
+        Ok(memory)
+    }
+
+    /// The policy engine access is evaluated against.
+    pub fn policy(&self) -> Arc<Mutex<PolicyEngine>> {
+        self.policy.clone()
+    }
+
+    /// Create or reconfigure a namespace, replacing its policy rules.
+    /// Existing entries are kept.
+    pub fn define(&self, config: NamespaceConfig) -> Result<(), SharedMemoryError> {
+        self.install_rules(&config);
+        let name = config.name.clone();
+        self.namespaces
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(name)
+            .or_default()
+            .config = Some(config);
+        self.persist()
+    }
+
+    /// Names of the defined namespaces.
+    pub fn namespaces(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .namespaces
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Read `key` as `crew`.
+    pub fn read(
+        &self,
+        crew: &str,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<SharedEntry>, SharedMemoryError> {
+        self.authorize(crew, namespace, PolicyAction::MemoryRead, key)?;
+        self.with_namespace(namespace, |ns| ns.entries.get(key).cloned())
+    }
+
+    /// All entries of a namespace, as `crew`.
+    pub fn entries(
+        &self,
+        crew: &str,
+        namespace: &str,
+    ) -> Result<HashMap<String, SharedEntry>, SharedMemoryError> {
+        self.authorize(crew, namespace, PolicyAction::MemoryRead, "*")?;
+        self.with_namespace(namespace, |ns| ns.entries.clone())
+    }
+
+    /// Write `key` as `crew`.
+    ///
+    /// `base_version` is the version the new value was derived from (`None`
+    /// for a blind write). Returns the stored entry.
+    pub fn write(
+        &self,
+        crew: &str,
+        namespace: &str,
+        key: &str,
+        value: Value,
+        base_version: Option<u64>,
+    ) -> Result<SharedEntry, SharedMemoryError> {
+        self.authorize(crew, namespace, PolicyAction::MemoryWrite, key)?;
+        let entry = {
+            let mut namespaces = self.namespaces.write().unwrap_or_else(|e| e.into_inner());
+            let ns = namespaces
+                .get_mut(namespace)
+                .ok_or_else(|| SharedMemoryError::UnknownNamespace(namespace.to_string()))?;
+            let strategy = ns.config.as_ref().map(|c| c.conflict).unwrap_or_default();
+            let current = ns.entries.get(key);
+
+            let stale = match (current, base_version) {
+                (Some(current), Some(base)) => current.version != base,
+                (Some(_), None) => strategy == ConflictStrategy::Reject,
+                (None, Some(base)) => base != 0,
+                (None, None) => false,
+            };
+            let value = match (stale, strategy, current) {
+                (true, ConflictStrategy::Reject, Some(current)) => {
+                    return Err(SharedMemoryError::Conflict {
+                        namespace: namespace.to_string(),
+                        key: key.to_string(),
+                        expected: base_version,
+                        current: Box::new(current.clone()),
+                    })
+                }
+                (true, ConflictStrategy::Merge, Some(current)) => {
+                    merge(current.value.clone(), value)
+                }
+                _ => value,
+            };
+
+            let entry = SharedEntry {
+                value,
+                version: current.map_or(0, |c| c.version) + 1,
+                written_by: crew.to_string(),
+                updated_at: Utc::now(),
+            };
+            ns.entries.insert(key.to_string(), entry.clone());
+            entry
+        };
+        self.persist()?;
+        Ok(entry)
+    }
+
+    /// Delete `key` as `crew` (a write). Returns whether it existed.
+    pub fn delete(
+        &self,
+        crew: &str,
+        namespace: &str,
+        key: &str,
+    ) -> Result<bool, SharedMemoryError> {
+        self.authorize(crew, namespace, PolicyAction::MemoryWrite, key)?;
+        let removed = self
+            .namespaces
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_mut(namespace)
+            .ok_or_else(|| SharedMemoryError::UnknownNamespace(namespace.to_string()))?
+            .entries
+            .remove(key)
+            .is_some();
+        self.persist()?;
+        Ok(removed)
+    }
+
+    /// A view of the store acting as `crew`.
+    pub fn for_crew(&self, crew: impl Into<String>) -> CrewSharedMemory {
+        CrewSharedMemory {
+            memory: self.clone(),
+            crew: crew.into(),
+        }
+    }
+
+    fn install_rules(&self, config: &NamespaceConfig) {
+        let mut policy = self.policy.lock().unwrap_or_else(|e| e.into_inner());
+        for access in ["read", "write"] {
+            policy.remove_rule(&rule_name(&config.name, access));
+        }
+        for rule in config.policy_rules() {
+            policy.add_rule(rule);
+        }
+    }
+
+    fn with_namespace<T>(
+        &self,
+        namespace: &str,
+        f: impl FnOnce(&Namespace) -> T,
+    ) -> Result<T, SharedMemoryError> {
+        self.namespaces
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(namespace)
+            .map(f)
+            .ok_or_else(|| SharedMemoryError::UnknownNamespace(namespace.to_string()))
+    }
+
+    fn authorize(
+        &self,
+        crew: &str,
+        namespace: &str,
+        action: PolicyAction,
+        key: &str,
+    ) -> Result<(), SharedMemoryError> {
+        let access = match action {
+            PolicyAction::MemoryWrite => "write",
+            _ => "read",
+        };
+        let request = PolicyRequest {
+            agent_slot: 0,
+            agent_id: crew.to_string(),
+            agent_roles: Vec::new(),
+            action,
+            resource: PolicyResource::Collection(namespace_resource(namespace)),
+            context: HashMap::from([
+                ("crew".to_string(), Value::from(crew)),
+                ("namespace".to_string(), Value::from(namespace)),
+                ("key".to_string(), Value::from(key)),
+            ]),
+        };
+        let decision = self
+            .policy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .evaluate(&request);
+        if decision.effect == PolicyEffect::Deny && decision.enforced {
+            return Err(SharedMemoryError::AccessDenied {
+                crew: crew.to_string(),
+                namespace: namespace.to_string(),
+                access,
+                reason: decision.reason,
+            });
+        }
+        Ok(())
+    }
+
+    fn persist(&self) -> Result<(), SharedMemoryError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let json = {
+            let namespaces = self.namespaces.read().unwrap_or_else(|e| e.into_inner());
+            serde_json::to_vec_pretty(&*namespaces).map_err(std::io::Error::from)?
+        };
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Deep-merge `incoming` into `current`.
+fn merge(current: Value, incoming: Value) -> Value {
+    match (current, incoming) {
+        (Value::Object(mut current), Value::Object(incoming)) => {
+            for (key, value) in incoming {
+                let merged = match current.remove(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => value,
+                };
+                current.insert(key, merged);
+            }
+            Value::Object(current)
+        }
+        (Value::Array(mut current), Value::Array(incoming)) => {
+            current.extend(incoming);
+            Value::Array(current)
+        }
+        (_, incoming) => incoming,
+    }
+}
+
+/// [`SharedMemory`] bound to one crew.
+#[derive(Debug, Clone)]
+pub struct CrewSharedMemory {
+    memory: SharedMemory,
+    crew: String,
+}
+
+impl CrewSharedMemory {
+    /// The crew accesses are made as.
+    pub fn crew(&self) -> &str {
+        &self.crew
+    }
+
+    /// Read `key` from `namespace`.
+    pub fn read(
+        &self,
+        namespace: &str,
+        key: &str,
+    ) -> Result<Option<SharedEntry>, SharedMemoryError> {
+        self.memory.read(&self.crew, namespace, key)
+    }
+
+    /// All entries of `namespace`.
+    pub fn entries(
+        &self,
+        namespace: &str,
+    ) -> Result<HashMap<String, SharedEntry>, SharedMemoryError> {
+        self.memory.entries(&self.crew, namespace)
+    }
+
+    /// Write `key` in `namespace`; see [`SharedMemory::write`].
+    pub fn write(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: Value,
+        base_version: Option<u64>,
+    ) -> Result<SharedEntry, SharedMemoryError> {
+        self.memory
+            .write(&self.crew, namespace, key, value, base_version)
+    }
+
+    /// Delete `key` from `namespace`.
+    pub fn delete(&self, namespace: &str, key: &str) -> Result<bool, SharedMemoryError> {
+        self.memory.delete(&self.crew, namespace, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_permissions_enforced_by_policy() {
+        let memory = SharedMemory::default();
+        memory
+            .define(
+                NamespaceConfig::new("customer_context")
+                    .with_writer("support")
+                    .with_reader("billing"),
+            )
+            .unwrap();
+        let support = memory.for_crew("support");
+        let billing = memory.for_crew("billing");
+        let sales = memory.for_crew("sales");
+
+        support
+            .write("customer_context", "plan", Value::from("pro"), None)
+            .unwrap();
+        assert_eq!(
+            billing
+                .read("customer_context", "plan")
+                .unwrap()
+                .unwrap()
+                .value,
+            "pro"
+        );
+        assert!(matches!(
+            billing.write("customer_context", "plan", Value::from("free"), None),
+            Err(SharedMemoryError::AccessDenied {
+                access: "write",
+                ..
+            })
+        ));
+        assert!(matches!(
+            sales.read("customer_context", "plan"),
+            Err(SharedMemoryError::AccessDenied { access: "read", .. })
+        ));
+        assert!(matches!(
+            support.read("unknown", "plan"),
+            Err(SharedMemoryError::UnknownNamespace(_))
+        ));
+        assert_eq!(memory.policy().lock().unwrap().rule_count(), 2);
+    }
+
+    #[test]
+    fn test_conflict_strategies() {
+        let memory = SharedMemory::default();
+        for (name, conflict) in [
+            ("lww", ConflictStrategy::LastWriteWins),
+            ("reject", ConflictStrategy::Reject),
+            ("merge", ConflictStrategy::Merge),
+        ] {
+            memory
+                .define(
+                    NamespaceConfig::new(name)
+                        .with_writer("*")
+                        .with_conflict(conflict),
+                )
+                .unwrap();
+        }
+        let a = memory.for_crew("a");
+        let b = memory.for_crew("b");
+
+        for ns in ["lww", "reject", "merge"] {
+            let first = a
+                .write(ns, "k", serde_json::json!({"x": 1, "tags": ["a"]}), None)
+                .unwrap();
+            assert_eq!(first.version, 1);
+            // `b` writes based on version 1, then `a` writes based on the
+            // same, now stale, version.
+            b.write(ns, "k", serde_json::json!({"y": 2, "tags": ["b"]}), Some(1))
+                .unwrap();
+            let result = a.write(ns, "k", serde_json::json!({"x": 3, "tags": ["c"]}), Some(1));
+            match ns {
+                "lww" => assert_eq!(
+                    result.unwrap().value,
+                    serde_json::json!({"x": 3, "tags": ["c"]})
+                ),
+                "reject" => match result {
+                    Err(SharedMemoryError::Conflict { current, .. }) => {
+                        assert_eq!(current.version, 2);
+                        assert_eq!(current.written_by, "b");
+                    }
+                    other => panic!("expected a conflict, got {:?}", other),
+                },
+                _ => {
+                    let entry = result.unwrap();
+                    assert_eq!(entry.version, 3);
+                    assert_eq!(
+                        entry.value,
+                        serde_json::json!({"y": 2, "x": 3, "tags": ["b", "c"]})
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_persisted_across_instances() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shared.json");
+        let memory = SharedMemory::open(&path, Default::default()).unwrap();
+        memory
+            .define(NamespaceConfig::new("notes").with_writer("research"))
+            .unwrap();
+        memory
+            .for_crew("research")
+            .write("notes", "summary", Value::from("done"), None)
+            .unwrap();
+
+        let reopened = SharedMemory::open(&path, Default::default()).unwrap();
+        let entry = reopened
+            .read("research", "notes", "summary")
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.value, "done");
+        assert!(reopened.read("writer", "notes", "summary").is_err());
+    }
+}
@@ -10,6 +10,7 @@ use serde_json::Value;
 use crate::memory::memory::Memory;
 use crate::memory::storage::interface::Storage;
 use crate::memory::storage::rag_storage::RAGStorage;
+use crate::rag::embeddings::EmbedderConfig;
 
 /// An item stored in short-term memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,16 +61,12 @@ impl ShortTermMemory {
     /// * `crew_agent_roles` - Optional list of agent roles for collection naming.
     /// * `path` - Optional persist directory path.
     pub fn new(
-        embedder_config: Option<Value>,
+        embedder_config: Option<EmbedderConfig>,
         storage: Option<Box<dyn Storage>>,
         crew_agent_roles: Option<Vec<String>>,
         path: Option<String>,
     ) -> Self {
-        let memory_provider = embedder_config
-            .as_ref()
-            .and_then(|c| c.get("provider"))
-            .and_then(|p| p.as_str())
-            .map(|s| s.to_string());
+        let memory_provider = embedder_config.as_ref().map(|c| c.provider.clone());
 
         let storage: Box<dyn Storage> = if let Some(s) = storage {
             s
@@ -77,8 +74,7 @@ impl ShortTermMemory {
             // Use Mem0Storage for mem0 provider
             let config = embedder_config
                 .as_ref()
-                .and_then(|c| c.get("config"))
-                .and_then(|c| serde_json::from_value::<HashMap<String, Value>>(c.clone()).ok());
+                .map(|c| c.provider_config().into_iter().collect());
             Box::new(
                 crate::memory::storage::mem0_storage::Mem0Storage::new("short_term", None, config)
                     .expect("Failed to create Mem0Storage"),
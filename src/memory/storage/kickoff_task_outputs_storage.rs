@@ -22,11 +22,10 @@ impl KickoffTaskOutputsSQLiteStorage {
     ///
     /// # Arguments
     /// * `db_path` - Optional path to the database file.
-    ///   Defaults to `<db_storage_path>/latest_kickoff_task_outputs.db`.
+    ///   Defaults to `<storage_dir>/latest_kickoff_task_outputs.db`.
     pub fn new(db_path: Option<PathBuf>) -> Result<Self, anyhow::Error> {
         let db_path = db_path.unwrap_or_else(|| {
-            let base = crate::utilities::paths::db_storage_path();
-            PathBuf::from(base).join("latest_kickoff_task_outputs.db")
+            crate::utilities::paths::storage_dir().join("latest_kickoff_task_outputs.db")
         });
 
         // Ensure parent directory exists
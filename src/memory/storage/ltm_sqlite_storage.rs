@@ -1,17 +1,33 @@
 //! SQLite storage class for long-term memory data.
 //!
 //! Port of crewai/memory/storage/ltm_sqlite_storage.py
+//!
+//! Each memory is a task's description, its output, the evaluation score
+//! of the output and metadata (the agent, the evaluator's suggestions, ...).
+//! [`LTMSQLiteStorage::load`] ranks the stored memories by how similar
+//! their task is to the one at hand, so the results of past runs of the
+//! same or a related task can be included in its prompt.
+//!
+//! The keywords of each task description are stored alongside it, so a
+//! query only reads the memories sharing a keyword with its task and ranks
+//! that short list.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
 use serde_json::Value;
 
+use crate::knowledge::storage::keywords;
+
+/// Lowest task similarity, from 0 to 1, of the memories
+/// [`LTMSQLiteStorage::load`] returns.
+pub const MIN_TASK_SIMILARITY: f64 = 0.3;
+
 /// SQLite storage class for long-term memory data.
 ///
-/// Stores task descriptions, metadata, datetime, and quality scores
-/// in a SQLite database for persistent long-term memory.
+/// Stores task descriptions, outputs, metadata, datetime, and quality
+/// scores in a SQLite database for persistent long-term memory.
 pub struct LTMSQLiteStorage {
     /// Path to the SQLite database file.
     pub db_path: PathBuf,
@@ -24,13 +40,10 @@ impl LTMSQLiteStorage {
     ///
     /// # Arguments
     /// * `db_path` - Optional path to the database file.
-    ///   Defaults to `<db_storage_path>/long_term_memory_storage.db`.
+    ///   Defaults to [`LTMSQLiteStorage::default_db_path`].
     /// * `verbose` - Whether to print error messages.
     pub fn new(db_path: Option<PathBuf>, verbose: bool) -> Result<Self, anyhow::Error> {
-        let db_path = db_path.unwrap_or_else(|| {
-            let base = crate::utilities::paths::db_storage_path();
-            PathBuf::from(base).join("long_term_memory_storage.db")
-        });
+        let db_path = db_path.unwrap_or_else(Self::default_db_path);
 
         // Ensure parent directory exists
         if let Some(parent) = db_path.parent() {
@@ -42,20 +55,47 @@ impl LTMSQLiteStorage {
         Ok(storage)
     }
 
-    /// Initialize the SQLite database and create the LTM table.
+    /// `long_term_memory_storage.db` in the project's storage directory
+    /// (see [`storage_dir`](crate::utilities::paths::storage_dir)).
+    pub fn default_db_path() -> PathBuf {
+        crate::utilities::paths::storage_dir().join("long_term_memory_storage.db")
+    }
+
+    /// Initialize the SQLite database and create the LTM and keyword tables.
+    ///
+    /// Databases created before outputs were stored get an `output` column;
+    /// those created before keywords were stored get their memories indexed.
     fn initialize_db(&self) -> Result<(), anyhow::Error> {
         match Connection::open(&self.db_path) {
-            Ok(conn) => {
+            Ok(mut conn) => {
                 conn.execute(
                     "CREATE TABLE IF NOT EXISTS long_term_memories (
                         id INTEGER PRIMARY KEY AUTOINCREMENT,
                         task_description TEXT,
                         metadata TEXT,
                         datetime TEXT,
-                        score REAL
+                        score REAL,
+                        output TEXT
                     )",
                     [],
                 )?;
+                if conn
+                    .prepare("SELECT output FROM long_term_memories LIMIT 0")
+                    .is_err()
+                {
+                    conn.execute("ALTER TABLE long_term_memories ADD COLUMN output TEXT", [])?;
+                }
+                let indexed: Option<String> = conn
+                    .query_row(
+                        "SELECT name FROM sqlite_master
+                         WHERE type = 'table' AND name = 'long_term_memory_keywords'",
+                        [],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if indexed.is_none() {
+                    index_existing(&mut conn)?;
+                }
                 Ok(())
             }
             Err(e) => {
@@ -70,7 +110,7 @@ impl LTMSQLiteStorage {
         }
     }
 
-    /// Save data to the LTM table.
+    /// Save data to the LTM table, without the task's output.
     ///
     /// # Arguments
     /// * `task_description` - Description of the task.
@@ -83,63 +123,90 @@ impl LTMSQLiteStorage {
         metadata: &HashMap<String, Value>,
         datetime: &str,
         score: f64,
+    ) -> Result<(), anyhow::Error> {
+        self.save_with_output(task_description, "", metadata, datetime, score)
+    }
+
+    /// Save data to the LTM table, with the task's output.
+    ///
+    /// # Arguments
+    /// * `task_description` - Description of the task.
+    /// * `output` - Output of the task.
+    /// * `metadata` - Metadata associated with the memory.
+    /// * `datetime` - Timestamp of the memory.
+    /// * `score` - Quality score of the memory.
+    pub fn save_with_output(
+        &self,
+        task_description: &str,
+        output: &str,
+        metadata: &HashMap<String, Value>,
+        datetime: &str,
+        score: f64,
     ) -> Result<(), anyhow::Error> {
         let metadata_json = serde_json::to_string(metadata)?;
-        match Connection::open(&self.db_path) {
-            Ok(conn) => {
-                conn.execute(
-                    "INSERT INTO long_term_memories (task_description, metadata, datetime, score)
-                     VALUES (?1, ?2, ?3, ?4)",
-                    params![task_description, metadata_json, datetime, score],
-                )?;
-                Ok(())
-            }
-            Err(e) => {
-                if self.verbose {
-                    log::error!("MEMORY ERROR: An error occurred while saving to LTM: {}", e);
-                }
-                Err(e.into())
-            }
-        }
+        insert(
+            &self.db_path,
+            self.verbose,
+            task_description,
+            output,
+            &metadata_json,
+            datetime,
+            score,
+        )
+    }
+
+    /// Save data to the LTM table asynchronously, without the task's output.
+    pub async fn asave(
+        &self,
+        task_description: &str,
+        metadata: &HashMap<String, Value>,
+        datetime: &str,
+        score: f64,
+    ) -> Result<(), anyhow::Error> {
+        self.asave_with_output(task_description, "", metadata, datetime, score)
+            .await
     }
 
-    /// Save data to the LTM table asynchronously.
+    /// Save data to the LTM table asynchronously, with the task's output.
     ///
     /// Note: In Rust, rusqlite is synchronous. This method wraps the sync
     /// operation in a tokio blocking task for async compatibility.
-    pub async fn asave(
+    pub async fn asave_with_output(
         &self,
         task_description: &str,
+        output: &str,
         metadata: &HashMap<String, Value>,
         datetime: &str,
         score: f64,
     ) -> Result<(), anyhow::Error> {
         let db_path = self.db_path.clone();
         let task_description = task_description.to_string();
+        let output = output.to_string();
         let metadata_json = serde_json::to_string(metadata)?;
         let datetime = datetime.to_string();
         let verbose = self.verbose;
 
-        tokio::task::spawn_blocking(move || match Connection::open(&db_path) {
-            Ok(conn) => {
-                conn.execute(
-                    "INSERT INTO long_term_memories (task_description, metadata, datetime, score)
-                         VALUES (?1, ?2, ?3, ?4)",
-                    params![task_description, metadata_json, datetime, score],
-                )?;
-                Ok(())
-            }
-            Err(e) => {
-                if verbose {
-                    log::error!("MEMORY ERROR: An error occurred while saving to LTM: {}", e);
-                }
-                Err(anyhow::anyhow!(e))
-            }
+        tokio::task::spawn_blocking(move || {
+            insert(
+                &db_path,
+                verbose,
+                &task_description,
+                &output,
+                &metadata_json,
+                &datetime,
+                score,
+            )
         })
         .await?
     }
 
-    /// Query the LTM table by task description.
+    /// Query the LTM table for the memories of the tasks most similar to
+    /// `task_description`.
+    ///
+    /// Memories are ranked by task similarity, then by score, then most
+    /// recent first; those less similar than [`MIN_TASK_SIMILARITY`] are
+    /// left out. Each entry holds the `task_description`, `output`,
+    /// `metadata`, `datetime`, `score` and `similarity` of a memory.
     ///
     /// # Arguments
     /// * `task_description` - Description of the task to search for.
@@ -152,49 +219,7 @@ impl LTMSQLiteStorage {
         task_description: &str,
         latest_n: usize,
     ) -> Result<Option<Vec<HashMap<String, Value>>>, anyhow::Error> {
-        match Connection::open(&self.db_path) {
-            Ok(conn) => {
-                let mut stmt = conn.prepare(&format!(
-                    "SELECT metadata, datetime, score
-                     FROM long_term_memories
-                     WHERE task_description = ?1
-                     ORDER BY datetime DESC, score ASC
-                     LIMIT {}",
-                    latest_n
-                ))?;
-
-                let rows = stmt.query_map(params![task_description], |row| {
-                    let metadata_str: String = row.get(0)?;
-                    let datetime: String = row.get(1)?;
-                    let score: f64 = row.get(2)?;
-                    Ok((metadata_str, datetime, score))
-                })?;
-
-                let mut results = Vec::new();
-                for row in rows {
-                    let (metadata_str, datetime, score) = row?;
-                    let metadata: Value =
-                        serde_json::from_str(&metadata_str).unwrap_or(Value::Null);
-                    let mut entry = HashMap::new();
-                    entry.insert("metadata".to_string(), metadata);
-                    entry.insert("datetime".to_string(), Value::String(datetime));
-                    entry.insert("score".to_string(), serde_json::to_value(score)?);
-                    results.push(entry);
-                }
-
-                if results.is_empty() {
-                    Ok(None)
-                } else {
-                    Ok(Some(results))
-                }
-            }
-            Err(e) => {
-                if self.verbose {
-                    log::error!("MEMORY ERROR: An error occurred while querying LTM: {}", e);
-                }
-                Ok(None)
-            }
-        }
+        query(&self.db_path, self.verbose, task_description, latest_n)
     }
 
     /// Query the LTM table by task description asynchronously.
@@ -207,60 +232,17 @@ impl LTMSQLiteStorage {
         let task_description = task_description.to_string();
         let verbose = self.verbose;
 
-        tokio::task::spawn_blocking(move || match Connection::open(&db_path) {
-            Ok(conn) => {
-                let mut stmt = conn.prepare(&format!(
-                    "SELECT metadata, datetime, score
-                         FROM long_term_memories
-                         WHERE task_description = ?1
-                         ORDER BY datetime DESC, score ASC
-                         LIMIT {}",
-                    latest_n
-                ))?;
-
-                let rows = stmt.query_map(params![&task_description], |row| {
-                    let metadata_str: String = row.get(0)?;
-                    let datetime: String = row.get(1)?;
-                    let score: f64 = row.get(2)?;
-                    Ok((metadata_str, datetime, score))
-                })?;
-
-                let mut results = Vec::new();
-                for row in rows {
-                    let (metadata_str, datetime, score) = row?;
-                    let metadata: Value =
-                        serde_json::from_str(&metadata_str).unwrap_or(Value::Null);
-                    let mut entry = HashMap::new();
-                    entry.insert("metadata".to_string(), metadata);
-                    entry.insert("datetime".to_string(), Value::String(datetime));
-                    entry.insert(
-                        "score".to_string(),
-                        serde_json::to_value(score).unwrap_or(Value::Null),
-                    );
-                    results.push(entry);
-                }
-
-                if results.is_empty() {
-                    Ok(None)
-                } else {
-                    Ok(Some(results))
-                }
-            }
-            Err(e) => {
-                if verbose {
-                    log::error!("MEMORY ERROR: An error occurred while querying LTM: {}", e);
-                }
-                Ok(None)
-            }
-        })
-        .await?
+        tokio::task::spawn_blocking(move || query(&db_path, verbose, &task_description, latest_n))
+            .await?
     }
 
     /// Reset the LTM table by deleting all rows.
     pub fn reset(&self) -> Result<(), anyhow::Error> {
         match Connection::open(&self.db_path) {
             Ok(conn) => {
-                conn.execute("DELETE FROM long_term_memories", [])?;
+                conn.execute_batch(
+                    "DELETE FROM long_term_memories; DELETE FROM long_term_memory_keywords;",
+                )?;
                 Ok(())
             }
             Err(e) => {
@@ -282,7 +264,9 @@ impl LTMSQLiteStorage {
 
         tokio::task::spawn_blocking(move || match Connection::open(&db_path) {
             Ok(conn) => {
-                conn.execute("DELETE FROM long_term_memories", [])?;
+                conn.execute_batch(
+                    "DELETE FROM long_term_memories; DELETE FROM long_term_memory_keywords;",
+                )?;
                 Ok(())
             }
             Err(e) => {
@@ -298,3 +282,262 @@ impl LTMSQLiteStorage {
         .await?
     }
 }
+
+/// A row of the LTM table.
+struct StoredMemory {
+    task_description: String,
+    output: String,
+    metadata: Value,
+    datetime: String,
+    score: f64,
+}
+
+/// Create the keyword table and index the memories already stored.
+fn index_existing(conn: &mut Connection) -> Result<(), anyhow::Error> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(
+        "CREATE TABLE long_term_memory_keywords (
+            memory_id INTEGER NOT NULL,
+            keyword TEXT NOT NULL
+        );
+        CREATE INDEX long_term_memory_keywords_keyword
+            ON long_term_memory_keywords (keyword);",
+    )?;
+    let memories: Vec<(i64, String)> = tx
+        .prepare("SELECT id, task_description FROM long_term_memories")?
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    for (id, task_description) in memories {
+        insert_keywords(&tx, id, &task_description)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Store the keywords of memory `id`'s task description.
+fn insert_keywords(conn: &Connection, id: i64, task_description: &str) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare_cached(
+        "INSERT INTO long_term_memory_keywords (memory_id, keyword) VALUES (?1, ?2)",
+    )?;
+    for keyword in keywords(task_description) {
+        stmt.execute(params![id, keyword])?;
+    }
+    Ok(())
+}
+
+/// Insert a memory into the database at `db_path`.
+fn insert(
+    db_path: &Path,
+    verbose: bool,
+    task_description: &str,
+    output: &str,
+    metadata_json: &str,
+    datetime: &str,
+    score: f64,
+) -> Result<(), anyhow::Error> {
+    match Connection::open(db_path) {
+        Ok(mut conn) => {
+            let tx = conn.transaction()?;
+            tx.execute(
+                "INSERT INTO long_term_memories (task_description, output, metadata, datetime, score)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![task_description, output, metadata_json, datetime, score],
+            )?;
+            insert_keywords(&tx, tx.last_insert_rowid(), task_description)?;
+            tx.commit()?;
+            Ok(())
+        }
+        Err(e) => {
+            if verbose {
+                log::error!("MEMORY ERROR: An error occurred while saving to LTM: {}", e);
+            }
+            Err(e.into())
+        }
+    }
+}
+
+/// The `latest_n` memories in the database at `db_path` whose task is most
+/// similar to `task_description`.
+///
+/// Only memories of the same task or sharing a keyword with it are read;
+/// any other has a similarity of 0.
+fn query(
+    db_path: &Path,
+    verbose: bool,
+    task_description: &str,
+    latest_n: usize,
+) -> Result<Option<Vec<HashMap<String, Value>>>, anyhow::Error> {
+    let conn = match Connection::open(db_path) {
+        Ok(conn) => conn,
+        Err(e) => {
+            if verbose {
+                log::error!("MEMORY ERROR: An error occurred while querying LTM: {}", e);
+            }
+            return Ok(None);
+        }
+    };
+    let task_keywords: Vec<String> = keywords(task_description).into_iter().collect();
+    let mut stmt = conn.prepare(
+        "SELECT task_description, output, metadata, datetime, score
+         FROM long_term_memories
+         WHERE trim(task_description) = ?1
+            OR id IN (
+                SELECT memory_id FROM long_term_memory_keywords
+                WHERE keyword IN (SELECT value FROM json_each(?2))
+            )",
+    )?;
+    let params = params![
+        task_description.trim(),
+        serde_json::to_string(&task_keywords)?
+    ];
+    let rows = stmt.query_map(params, |row| {
+        let metadata: Option<String> = row.get(2)?;
+        Ok(StoredMemory {
+            task_description: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+            output: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+            metadata: metadata
+                .and_then(|m| serde_json::from_str(&m).ok())
+                .unwrap_or(Value::Null),
+            datetime: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            score: row.get::<_, Option<f64>>(4)?.unwrap_or_default(),
+        })
+    })?;
+
+    let mut ranked = Vec::new();
+    for memory in rows {
+        let memory = memory?;
+        let similarity = task_similarity(task_description, &memory.task_description);
+        if similarity >= MIN_TASK_SIMILARITY {
+            ranked.push((similarity, memory));
+        }
+    }
+    ranked.sort_by(|(a_similarity, a), (b_similarity, b)| {
+        b_similarity
+            .total_cmp(a_similarity)
+            .then(b.score.total_cmp(&a.score))
+            .then_with(|| b.datetime.cmp(&a.datetime))
+    });
+    ranked.truncate(latest_n);
+
+    let results: Vec<HashMap<String, Value>> = ranked
+        .into_iter()
+        .map(|(similarity, memory)| {
+            HashMap::from([
+                (
+                    "task_description".to_string(),
+                    Value::String(memory.task_description),
+                ),
+                ("output".to_string(), Value::String(memory.output)),
+                ("metadata".to_string(), memory.metadata),
+                ("datetime".to_string(), Value::String(memory.datetime)),
+                ("score".to_string(), Value::from(memory.score)),
+                ("similarity".to_string(), Value::from(similarity)),
+            ])
+        })
+        .collect();
+
+    if results.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(results))
+    }
+}
+
+/// Similarity of two task descriptions, from 0 to 1: the keywords they
+/// share over all their keywords.
+fn task_similarity(a: &str, b: &str) -> f64 {
+    if a.trim() == b.trim() {
+        return 1.0;
+    }
+    let (a, b) = (keywords(a), keywords(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::long_term::{LongTermMemory, LongTermMemoryItem};
+    use crate::memory::ContextualMemory;
+
+    #[test]
+    fn test_memories_of_similar_tasks_are_ranked() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("ltm.db");
+        // A database from before outputs and keywords were stored
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE long_term_memories (id INTEGER PRIMARY KEY AUTOINCREMENT,
+                 task_description TEXT, metadata TEXT, datetime TEXT, score REAL);
+                 INSERT INTO long_term_memories (task_description, metadata, datetime, score)
+                 VALUES ('Draft the Q3 sales email', '{}', '0', 7.0);",
+            )
+            .unwrap();
+
+        let ltm = LongTermMemory::new(None, Some(db_path)).unwrap();
+        for (task, output, quality, datetime) in [
+            ("Summarize the Q3 sales report", "Sales grew 4%.", 6.0, "1"),
+            (
+                "Summarize the Q3 sales report",
+                "Sales grew 4% in EMEA.",
+                9.0,
+                "2",
+            ),
+            ("Summarize the Q4 sales report", "Sales fell.", 8.0, "3"),
+            ("Plan the team offsite", "Lisbon in May.", 10.0, "4"),
+        ] {
+            let metadata = HashMap::from([(
+                "suggestions".to_string(),
+                serde_json::json!([format!("Cite figures ({})", datetime)]),
+            )]);
+            let item = LongTermMemoryItem::new(
+                "Analyst".to_string(),
+                task.to_string(),
+                "A summary".to_string(),
+                output.to_string(),
+                datetime.to_string(),
+                Some(quality),
+                Some(metadata),
+            );
+            ltm.save(&item).unwrap();
+        }
+
+        let results = ltm.search("Summarize the Q3 sales report", 3).unwrap();
+        let outputs: Vec<_> = results
+            .iter()
+            .map(|r| r["output"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            outputs,
+            ["Sales grew 4% in EMEA.", "Sales grew 4%.", "Sales fell."]
+        );
+        assert_eq!(results[0]["score"], 9.0);
+        assert_eq!(results[0]["metadata"]["agent"], "Analyst");
+        assert!(ltm.search("Book flights", 3).unwrap().is_empty());
+        // The older memory was indexed when the database was opened.
+        let results = ltm.search("Email the Q3 sales figures", 1).unwrap();
+        assert_eq!(results[0]["task_description"], "Draft the Q3 sales email");
+        assert_eq!(results[0]["output"], "");
+
+        let memory = ContextualMemory::new(None, Some(ltm), None, None);
+        let context = memory
+            .build_context_for_task("Summarize the Q4 sales report", "")
+            .unwrap();
+        assert_eq!(
+            context,
+            "Historical Data:\n- Cite figures (3)\n- Cite figures (2)\n\
+             Results of similar past tasks:\n\
+             - Summarize the Q4 sales report (score 8): Sales fell.\n\
+             - Summarize the Q3 sales report (score 9): Sales grew 4% in EMEA."
+        );
+    }
+}
@@ -13,6 +13,8 @@ use async_trait::async_trait;
 use serde_json::Value;
 
 use crate::memory::storage::interface::Storage;
+use crate::rag::embeddings::EmbedderConfig;
+use crate::utilities::perf::{self, HotPath};
 
 /// Maximum file name length for storage paths.
 const MAX_FILE_NAME_LENGTH: usize = 255;
@@ -39,7 +41,7 @@ pub struct RAGStorage {
     /// Whether reset is allowed.
     pub allow_reset: bool,
     /// Embedder configuration.
-    pub embedder_config: Option<Value>,
+    pub embedder_config: Option<EmbedderConfig>,
     /// Concatenated sanitized agent roles.
     pub agents: String,
     /// The constructed storage file name.
@@ -62,7 +64,7 @@ impl RAGStorage {
     pub fn new(
         storage_type: &str,
         allow_reset: bool,
-        embedder_config: Option<Value>,
+        embedder_config: Option<EmbedderConfig>,
         crew_agent_roles: Option<Vec<String>>,
         path: Option<String>,
     ) -> Self {
@@ -92,11 +94,7 @@ impl RAGStorage {
 
     /// Build the storage file name, ensuring it does not exceed max allowed length.
     fn build_storage_file_name(storage_type: &str, file_name: &str) -> String {
-        let base_path = format!(
-            "{}/{}",
-            crate::utilities::paths::db_storage_path(),
-            storage_type
-        );
+        let base_path = crate::utilities::paths::storage_dir().join(storage_type);
         let trimmed = if file_name.len() > MAX_FILE_NAME_LENGTH {
             log::warn!(
                 "Trimming file name from {} to {} characters.",
@@ -107,7 +105,7 @@ impl RAGStorage {
         } else {
             file_name
         };
-        format!("{}/{}", base_path, trimmed)
+        base_path.join(trimmed).to_string_lossy().to_string()
     }
 
     /// Get the collection name for this storage instance.
@@ -179,6 +177,7 @@ impl Storage for RAGStorage {
         limit: usize,
         score_threshold: f64,
     ) -> Result<Vec<Value>, anyhow::Error> {
+        let _span = perf::span(HotPath::VectorSearch);
         log::debug!(
             "RAGStorage search in '{}': query='{}'",
             self.collection_name(),
@@ -105,6 +105,7 @@ fn skill_descriptor_to_a2a_skill(skill: &SkillDescriptor) -> AgentSkill {
         input_modes: skill.input_modes.clone(),
         output_modes: skill.output_modes.clone(),
         tags: skill.tags.clone(),
+        rate_limits: None,
     }
 }
 
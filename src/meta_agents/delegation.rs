@@ -383,6 +383,18 @@ pub enum OrchestrationEvent {
         skill_count: usize,
         performance: f64,
     },
+    /// A crew was composed for an objective.
+    CrewComposed {
+        objective: String,
+        agents: Vec<String>,
+        tasks: usize,
+    },
+    /// A solve result was evaluated.
+    SolutionEvaluated {
+        iteration: u32,
+        score: f64,
+        accepted: bool,
+    },
     /// Orchestration completed.
     OrchestrationFinished {
         total_tasks: usize,
@@ -20,7 +20,8 @@
 //!   domain savants with skill-aware routing, cross-domain delegation, and
 //!   dynamic A2A card synchronization.
 //! - **Orchestrator** (`orchestrator`): The auto-attended controller that
-//!   spawns agents, distributes tasks, and adjusts skills.
+//!   spawns agents, distributes tasks, and adjusts skills. Its `solve`
+//!   composes crews for an objective, evaluates their output and iterates.
 //!
 //! # Quick Start
 //!
@@ -46,6 +47,20 @@
 //! println!("Completed: {}/{}", result.completed_tasks, result.total_tasks);
 //! ```
 //!
+//! # Solving an Objective
+//!
+//! ```rust,no_run
+//! use crewai::meta_agents::orchestrator::{MetaOrchestrator, OrchestratorConfig};
+//!
+//! let mut orch = MetaOrchestrator::with_default_savants(OrchestratorConfig::default());
+//!
+//! // Compose a crew, run it, evaluate the result and retry with feedback
+//! let solved = orch.solve("Research Rust async patterns and write a summary")?;
+//! println!("Score {:.1} after {} attempts", solved.score, solved.attempts.len());
+//! println!("{}", solved.output.raw);
+//! # Ok::<(), String>(())
+//! ```
+//!
 //! # Savant Coordinator
 //!
 //! ```rust
@@ -90,7 +105,10 @@ pub use delegation::{
 pub use dto_meta::{
     DtoContentType, DtoEnvelope, DtoRegistry, DtoSchema, SchemaVersion, ValidationResult,
 };
-pub use orchestrator::{MetaOrchestrator, OrchestrationResult, OrchestratorConfig, PoolStats};
+pub use orchestrator::{
+    CrewRunner, MetaOrchestrator, OrchestrationResult, OrchestratorConfig, PoolStats,
+    SolutionEvaluator, SolveAttempt, SolveResult,
+};
 pub use savant_meta::{CrossDomainDelegation, RoutingDecision, SavantCoordinator, SavantEntry};
 pub use skill_engine::{SkillEngine, SkillEngineConfig};
 pub use spawner::{DecomposedTask, DecompositionPlan, SpawnerAgent};
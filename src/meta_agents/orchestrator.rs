@@ -19,6 +19,10 @@ use uuid::Uuid;
 
 use crate::a2a::client::AgentCard;
 use crate::agent::Agent;
+use crate::crew::Crew;
+use crate::crews::crew_output::CrewOutput;
+use crate::task::Task;
+use crate::utilities::evaluators::{evaluate_task_output, EvaluationSummary};
 
 use super::card_builder::{build_card_from_blueprint, update_card_skills};
 use super::delegation::{
@@ -27,7 +31,7 @@ use super::delegation::{
 };
 use super::savants;
 use super::skill_engine::SkillEngine;
-use super::spawner::SpawnerAgent;
+use super::spawner::{DecompositionPlan, SpawnerAgent};
 use super::types::{
     AgentBlueprint, OrchestratedTask, OrchestratedTaskStatus, SavantDomain, SkillDescriptor,
     SpawnedAgentState, TaskPriority,
@@ -60,6 +64,15 @@ pub struct OrchestratorConfig {
     /// Minimum skill match score to assign a task (0.0 - 10.0).
     #[serde(default = "default_min_score")]
     pub min_match_score: f64,
+    /// Maximum crews [`MetaOrchestrator::solve`] composes for an objective.
+    #[serde(default = "default_max_solve_iterations")]
+    pub max_solve_iterations: u32,
+    /// Evaluation score (1.0 - 10.0) at which `solve` accepts a result.
+    #[serde(default = "default_acceptance_score")]
+    pub acceptance_score: f64,
+    /// LLM that evaluates `solve` results (defaults to `default_llm`).
+    #[serde(default)]
+    pub eval_llm: Option<String>,
 }
 
 fn default_base_url() -> String {
@@ -77,6 +90,12 @@ fn default_true() -> bool {
 fn default_min_score() -> f64 {
     0.5
 }
+fn default_max_solve_iterations() -> u32 {
+    3
+}
+fn default_acceptance_score() -> f64 {
+    7.0
+}
 
 impl Default for OrchestratorConfig {
     fn default() -> Self {
@@ -88,6 +107,9 @@ impl Default for OrchestratorConfig {
             auto_spawn: true,
             adaptive_skills: true,
             min_match_score: default_min_score(),
+            max_solve_iterations: default_max_solve_iterations(),
+            acceptance_score: default_acceptance_score(),
+            eval_llm: None,
         }
     }
 }
@@ -96,6 +118,13 @@ impl Default for OrchestratorConfig {
 // MetaOrchestrator
 // ---------------------------------------------------------------------------
 
+/// Scores a `solve` result against its objective.
+pub type SolutionEvaluator =
+    Box<dyn Fn(&str, &CrewOutput) -> Result<EvaluationSummary, String> + Send + Sync>;
+
+/// Executes a crew composed by `solve`.
+pub type CrewRunner = Box<dyn Fn(&mut Crew) -> Result<CrewOutput, String> + Send + Sync>;
+
 /// The auto-attended meta-orchestrator.
 ///
 /// Manages a pool of agents, distributes tasks based on skill matching,
@@ -125,6 +154,10 @@ pub struct MetaOrchestrator {
     pub event_log: Vec<OrchestrationEvent>,
     /// Pending delegation requests.
     delegation_queue: Vec<DelegationRequest>,
+    /// Evaluator for `solve` results; an LLM judge when unset.
+    evaluator: Option<SolutionEvaluator>,
+    /// Runner for crews composed by `solve`; `Crew::kickoff` when unset.
+    crew_runner: Option<CrewRunner>,
 }
 
 impl MetaOrchestrator {
@@ -145,6 +178,8 @@ impl MetaOrchestrator {
             skill_engine,
             event_log: Vec::new(),
             delegation_queue: Vec::new(),
+            evaluator: None,
+            crew_runner: None,
         }
     }
 
@@ -799,6 +834,215 @@ impl MetaOrchestrator {
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Objective solving
+    // -----------------------------------------------------------------------
+
+    /// Replace the evaluator that scores `solve` results.
+    pub fn set_evaluator<F>(&mut self, evaluator: F)
+    where
+        F: Fn(&str, &CrewOutput) -> Result<EvaluationSummary, String> + Send + Sync + 'static,
+    {
+        self.evaluator = Some(Box::new(evaluator));
+    }
+
+    /// Replace how crews composed by `solve` are executed.
+    pub fn set_crew_runner<F>(&mut self, runner: F)
+    where
+        F: Fn(&mut Crew) -> Result<CrewOutput, String> + Send + Sync + 'static,
+    {
+        self.crew_runner = Some(Box::new(runner));
+    }
+
+    /// Compose a crew for a high-level objective.
+    ///
+    /// The objective is decomposed by the spawner; each sub-task becomes a
+    /// crew task assigned to a pooled agent of its domain, spawning one
+    /// when none exists. `feedback` on a previous attempt is appended to
+    /// every task description.
+    pub fn compose_crew(&mut self, objective: &str, feedback: Option<&str>) -> Crew {
+        let plan = self.spawner.decompose(objective);
+        let mut tasks = Vec::with_capacity(plan.tasks.len());
+        let mut agents: Vec<Agent> = Vec::new();
+
+        for (i, decomposed) in plan.tasks.iter().enumerate() {
+            let agent_id = self.agent_for_domain(decomposed.domain);
+            let agent = self.agents[&agent_id].clone();
+
+            let description = match feedback {
+                Some(feedback) => format!(
+                    "{}\n\nA previous attempt at this objective was rejected. \
+                     Address this feedback: {}",
+                    decomposed.description, feedback
+                ),
+                None => decomposed.description.clone(),
+            };
+            let mut task = Task::new(description, expected_output(&plan, i));
+            task.agent = Some(agent.role.clone());
+            task.tools = decomposed.suggested_tools.clone();
+            tasks.push(task);
+
+            if !agents.iter().any(|a| a.role == agent.role) {
+                agents.push(agent);
+            }
+        }
+
+        log::info!(
+            "Composed crew of {} agents and {} tasks for '{}'",
+            agents.len(),
+            tasks.len(),
+            objective
+        );
+        self.event_log.push(OrchestrationEvent::CrewComposed {
+            objective: objective.to_string(),
+            agents: agents.iter().map(|a| a.role.clone()).collect(),
+            tasks: tasks.len(),
+        });
+        self.event_log.extend(self.spawner.drain_events());
+
+        let mut crew = Crew::new(tasks, Vec::new());
+        for agent in agents {
+            crew.register_agent(agent);
+        }
+        crew
+    }
+
+    /// Solve a high-level objective with dynamically composed crews.
+    ///
+    /// Composes a crew for the objective, executes it and evaluates the
+    /// result. Results scoring below `acceptance_score` are fed back into
+    /// a new crew, up to `max_solve_iterations` times.
+    ///
+    /// Returns the best-scoring result, accepted or not, or the last error
+    /// when no crew produced output.
+    pub fn solve(&mut self, objective: &str) -> Result<SolveResult, String> {
+        let mut attempts: Vec<SolveAttempt> = Vec::new();
+        let mut best: Option<(CrewOutput, EvaluationSummary)> = None;
+        let mut feedback: Option<String> = None;
+        let mut last_error = format!("No crew was run for '{}'", objective);
+
+        for iteration in 1..=self.config.max_solve_iterations.max(1) {
+            let mut crew = self.compose_crew(objective, feedback.as_deref());
+            let mut attempt = SolveAttempt {
+                iteration,
+                agents: crew.agents.clone(),
+                tasks: crew.tasks.iter().map(|t| t.description.clone()).collect(),
+                output: None,
+                score: None,
+                feedback: String::new(),
+            };
+
+            let evaluated = self
+                .run_crew(&mut crew)
+                .and_then(|output| Ok((self.evaluate_solution(objective, &output)?, output)));
+            let (evaluation, output) = match evaluated {
+                Ok(evaluated) => evaluated,
+                Err(error) => {
+                    log::warn!("Solve iteration {} failed: {}", iteration, error);
+                    attempt.feedback = error.clone();
+                    attempts.push(attempt);
+                    feedback = Some(format!("The crew failed with: {}", error));
+                    last_error = error;
+                    continue;
+                }
+            };
+
+            let accepted = evaluation.score >= self.config.acceptance_score;
+            log::info!(
+                "Solve iteration {} scored {:.1} (accepted: {})",
+                iteration,
+                evaluation.score,
+                accepted
+            );
+            self.event_log.push(OrchestrationEvent::SolutionEvaluated {
+                iteration,
+                score: evaluation.score,
+                accepted,
+            });
+            attempt.output = Some(output.raw.clone());
+            attempt.score = Some(evaluation.score);
+            attempt.feedback = evaluation.feedback.clone();
+            attempts.push(attempt);
+            feedback = Some(evaluation.feedback.clone());
+
+            if best
+                .as_ref()
+                .is_none_or(|(_, best)| evaluation.score > best.score)
+            {
+                best = Some((output, evaluation));
+            }
+            if accepted {
+                break;
+            }
+        }
+
+        let (output, evaluation) = best.ok_or(last_error)?;
+        Ok(SolveResult {
+            objective: objective.to_string(),
+            accepted: evaluation.score >= self.config.acceptance_score,
+            score: evaluation.score,
+            feedback: evaluation.feedback,
+            output,
+            attempts,
+        })
+    }
+
+    /// A pooled agent of `domain`, spawned from a registered blueprint (or
+    /// the domain's savant) when the pool has none.
+    ///
+    /// When the pool is full the best-matching agent is reused instead.
+    fn agent_for_domain(&mut self, domain: SavantDomain) -> String {
+        if let Some((id, _)) = self.agent_pool.iter().find(|(_, s)| s.domain == domain) {
+            return id.clone();
+        }
+        if self.agent_pool.len() >= self.config.max_agents {
+            let probe = OrchestratedTask::new(domain.to_string()).with_domain(domain);
+            if let Some((id, _)) = self.find_best_agent(&probe) {
+                return id;
+            }
+        }
+        match self
+            .blueprints
+            .iter()
+            .find(|bp| bp.domain == domain)
+            .cloned()
+        {
+            Some(bp) => self.spawn_agent(&bp),
+            None => self.spawn_domain_agent(domain),
+        }
+    }
+
+    fn run_crew(&self, crew: &mut Crew) -> Result<CrewOutput, String> {
+        match &self.crew_runner {
+            Some(runner) => runner(crew),
+            None => crew.kickoff(None),
+        }
+    }
+
+    fn evaluate_solution(
+        &self,
+        objective: &str,
+        output: &CrewOutput,
+    ) -> Result<EvaluationSummary, String> {
+        if let Some(evaluator) = &self.evaluator {
+            return evaluator(objective, output);
+        }
+        let model = self
+            .config
+            .eval_llm
+            .as_deref()
+            .unwrap_or(&self.config.default_llm);
+        let task = Task::new(
+            objective.to_string(),
+            "A complete deliverable that fully accomplishes the objective.".to_string(),
+        );
+        let final_output = output
+            .tasks_output
+            .last()
+            .ok_or_else(|| "Crew produced no task outputs".to_string())?;
+        evaluate_task_output(model, &task, final_output)
+    }
+
     // -----------------------------------------------------------------------
     // Skill adjustment
     // -----------------------------------------------------------------------
@@ -1266,6 +1510,40 @@ pub struct OrchestrationResult {
     pub event_log: Vec<OrchestrationEvent>,
 }
 
+/// Result of [`MetaOrchestrator::solve`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolveResult {
+    /// The objective that was solved.
+    pub objective: String,
+    /// Output of the best-scoring crew.
+    pub output: CrewOutput,
+    /// Evaluation score of that output (1.0 - 10.0).
+    pub score: f64,
+    /// The evaluator's feedback on that output.
+    pub feedback: String,
+    /// Whether the score reached `acceptance_score`.
+    pub accepted: bool,
+    /// Every crew composed for the objective, in order.
+    pub attempts: Vec<SolveAttempt>,
+}
+
+/// One crew composed and run by [`MetaOrchestrator::solve`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolveAttempt {
+    /// Iteration number, starting at 1.
+    pub iteration: u32,
+    /// Roles of the crew's agents.
+    pub agents: Vec<String>,
+    /// Descriptions of the crew's tasks.
+    pub tasks: Vec<String>,
+    /// Raw crew output, if the crew completed and was evaluated.
+    pub output: Option<String>,
+    /// Evaluation score, if the crew completed and was evaluated.
+    pub score: Option<f64>,
+    /// The evaluator's feedback, or the error the attempt failed with.
+    pub feedback: String,
+}
+
 /// Agent pool statistics.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolStats {
@@ -1283,6 +1561,19 @@ pub struct PoolStats {
 // Domain inference
 // ---------------------------------------------------------------------------
 
+/// Expected output of the `index`th task of a decomposition plan: the
+/// final task delivers the objective, earlier ones feed later tasks.
+fn expected_output(plan: &DecompositionPlan, index: usize) -> String {
+    if index + 1 == plan.tasks.len() {
+        format!("The final deliverable for: {}", plan.objective)
+    } else {
+        format!(
+            "Findings on the {} aspects of the objective, for the tasks that follow.",
+            plan.tasks[index].domain
+        )
+    }
+}
+
 /// Infer relevant domains from an objective string.
 fn infer_domains(objective: &str) -> Vec<SavantDomain> {
     let lower = objective.to_lowercase();
@@ -1807,4 +2098,92 @@ mod tests {
             result.event_log
         );
     }
+
+    #[test]
+    fn test_compose_crew_assigns_domain_agents() {
+        let mut orch = MetaOrchestrator::with_default_savants(OrchestratorConfig::default());
+        let objective = "Research Rust async patterns and write a summary";
+        let crew = orch.compose_crew(objective, None);
+
+        assert!(crew.tasks.len() > 1);
+        for task in &crew.tasks {
+            let role = task.agent.as_ref().unwrap();
+            assert!(crew.get_agent(role).is_some());
+        }
+        let last = crew.tasks.last().unwrap();
+        assert!(last.expected_output.contains(objective));
+        let pooled = orch.agent_pool.len();
+        assert_eq!(pooled, crew.agents.len());
+
+        // A retry reuses the pooled agents and carries the feedback
+        let retry = orch.compose_crew(objective, Some("Cite sources"));
+        assert_eq!(orch.agent_pool.len(), pooled);
+        assert!(retry
+            .tasks
+            .iter()
+            .all(|t| t.description.contains("Cite sources")));
+        assert!(orch
+            .get_event_log()
+            .iter()
+            .any(|e| matches!(e, OrchestrationEvent::CrewComposed { .. })));
+    }
+
+    #[test]
+    fn test_solve_iterates_until_accepted() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let mut orch = MetaOrchestrator::with_default_savants(OrchestratorConfig::default());
+        orch.set_crew_runner(|crew| {
+            let revised = crew.tasks[0].description.contains("Add benchmarks");
+            let raw = if revised {
+                "summary with benchmarks"
+            } else {
+                "summary"
+            };
+            Ok(CrewOutput::new(
+                raw.to_string(),
+                Vec::new(),
+                Default::default(),
+            ))
+        });
+        orch.set_evaluator(|_, output| {
+            let benchmarked = output.raw.contains("benchmarks");
+            Ok(EvaluationSummary {
+                entity_id: String::new(),
+                label: String::new(),
+                score: if benchmarked { 9.0 } else { 4.0 },
+                feedback: "Add benchmarks".to_string(),
+            })
+        });
+
+        let solved = orch
+            .solve("Write a summary of Rust async runtimes")
+            .unwrap();
+        assert!(solved.accepted);
+        assert_eq!(solved.score, 9.0);
+        assert_eq!(solved.output.raw, "summary with benchmarks");
+        assert_eq!(solved.attempts.len(), 2);
+        assert_eq!(solved.attempts[0].score, Some(4.0));
+
+        // Never accepted: the best attempt is returned after the last iteration
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        orch.set_evaluator(move |_, _| {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            Ok(EvaluationSummary {
+                entity_id: String::new(),
+                label: String::new(),
+                score: [5.0, 6.0, 3.0][call],
+                feedback: "Still shallow".to_string(),
+            })
+        });
+        let solved = orch
+            .solve("Write a summary of Rust async runtimes")
+            .unwrap();
+        assert!(!solved.accepted);
+        assert_eq!(solved.score, 6.0);
+        assert_eq!(solved.attempts.len(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
 }
@@ -0,0 +1,447 @@
+//! Persistent, queryable policy audit log.
+//!
+//! Every decision of a [`PolicyEngine`](super::PolicyEngine) becomes an
+//! [`AuditRecord`] (time, agent, action, resource, effect, rule and reason).
+//! The engine keeps the most recent records in memory and writes each one to
+//! its [`AuditSink`]s, which keep them across restarts:
+//!
+//! - [`JsonlAuditSink`] appends one JSON object per line, for log shippers
+//! - [`SqliteAuditSink`] stores records in an indexed SQLite table
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use crewai::policy::audit::{AuditFilter, SqliteAuditSink};
+//! use crewai::policy::{PolicyEffect, PolicyEngine};
+//!
+//! let mut engine = PolicyEngine::new();
+//! engine.add_audit_sink(Arc::new(SqliteAuditSink::open("audit.db")?));
+//! let denials = engine.query_audit(&AuditFilter {
+//!     effect: Some(PolicyEffect::Deny),
+//!     limit: Some(20),
+//!     ..Default::default()
+//! })?;
+//! # Ok::<(), crewai::policy::audit::AuditError>(())
+//! ```
+//!
+//! Setting `CREWAI_POLICY_AUDIT` to a file path gives every new engine a
+//! sink at that path (JSONL for `.jsonl`/`.json` files, SQLite otherwise);
+//! `crewai policy-audit` reads it back. Writing to a sink never fails an
+//! evaluation: errors are logged under the `crewai::policy_audit` target.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{PolicyDecision, PolicyEffect, PolicyRequest};
+
+/// Environment variable with the path of the process-wide audit sink.
+pub const POLICY_AUDIT_ENV: &str = "CREWAI_POLICY_AUDIT";
+
+/// Error writing or reading an audit sink.
+#[derive(Debug, thiserror::Error)]
+pub enum AuditError {
+    /// Reading or writing the log file failed.
+    #[error("Audit log I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A JSONL record could not be encoded or decoded.
+    #[error("Invalid audit record: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The SQLite database failed.
+    #[error("Audit database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// One policy decision.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the request was evaluated.
+    pub timestamp: DateTime<Utc>,
+    /// Agent that made the request.
+    pub agent_id: String,
+    /// Requested action, e.g. `tool_call:web_search` or `memory_write`.
+    pub action: String,
+    /// Accessed resource, e.g. `tool:web_search` or `any`.
+    pub resource: String,
+    /// Allow or deny.
+    pub effect: PolicyEffect,
+    /// Rule that decided; `None` for the default allow.
+    pub rule_name: Option<String>,
+    /// Human-readable reason.
+    pub reason: String,
+    /// Whether the decision was enforced or only audited.
+    pub enforced: bool,
+}
+
+impl AuditRecord {
+    /// Record of `decision` for `request`, timestamped now.
+    pub fn new(request: &PolicyRequest, decision: &PolicyDecision) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            agent_id: request.agent_id.clone(),
+            action: label(&request.action),
+            resource: label(&request.resource),
+            effect: decision.effect.clone(),
+            rule_name: decision.rule_name.clone(),
+            reason: decision.reason.clone(),
+            enforced: decision.enforced,
+        }
+    }
+}
+
+/// `kind` or `kind:value` for a serialized action or resource.
+fn label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(kind)) => kind,
+        Ok(Value::Object(map)) => match map.into_iter().next() {
+            Some((kind, Value::String(value))) => format!("{}:{}", kind, value),
+            Some((kind, value)) => format!("{}:{}", kind, value),
+            None => String::new(),
+        },
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Which records to return; unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Only this agent.
+    pub agent_id: Option<String>,
+    /// Only allows or only denials.
+    pub effect: Option<PolicyEffect>,
+    /// Only decisions of this rule.
+    pub rule_name: Option<String>,
+    /// Only records at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// At most this many records (the most recent).
+    pub limit: Option<usize>,
+}
+
+impl AuditFilter {
+    /// Whether `record` passes the filter (ignoring the limit).
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.agent_id.as_ref().is_none_or(|a| *a == record.agent_id)
+            && self.effect.as_ref().is_none_or(|e| *e == record.effect)
+            && self
+                .rule_name
+                .as_ref()
+                .is_none_or(|r| record.rule_name.as_ref() == Some(r))
+            && self.since.is_none_or(|since| record.timestamp >= since)
+    }
+
+    /// Records of `records` (oldest first) that pass the filter, most
+    /// recent first.
+    pub fn apply<'a>(
+        &self,
+        records: impl DoubleEndedIterator<Item = &'a AuditRecord>,
+    ) -> Vec<AuditRecord> {
+        records
+            .rev()
+            .filter(|r| self.matches(r))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Destination for audit records.
+pub trait AuditSink: Send + Sync + std::fmt::Debug {
+    /// Store one record.
+    fn record(&self, record: &AuditRecord) -> Result<(), AuditError>;
+
+    /// Stored records matching `filter`, most recent first.
+    fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>, AuditError>;
+}
+
+/// Appends records to a JSON Lines file.
+#[derive(Debug)]
+pub struct JsonlAuditSink {
+    path: PathBuf,
+    /// Serializes appends from engines sharing the sink.
+    lock: Mutex<()>,
+}
+
+impl JsonlAuditSink {
+    /// A sink appending to `path`; the file and its directory are created on
+    /// first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Path of the log file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AuditSink for JsonlAuditSink {
+    fn record(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(&line)?;
+        Ok(())
+    }
+
+    fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>, AuditError> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                records.push(serde_json::from_str::<AuditRecord>(&line)?);
+            }
+        }
+        Ok(filter.apply(records.iter()))
+    }
+}
+
+/// Stores records in a SQLite database.
+#[derive(Debug, Clone)]
+pub struct SqliteAuditSink {
+    /// Path to the database file.
+    pub db_path: PathBuf,
+}
+
+impl SqliteAuditSink {
+    /// Open (creating if needed) the database at `db_path`.
+    pub fn open(db_path: impl Into<PathBuf>) -> Result<Self, AuditError> {
+        let sink = Self {
+            db_path: db_path.into(),
+        };
+        if let Some(parent) = sink.db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        sink.connect()?.execute_batch(
+            "CREATE TABLE IF NOT EXISTS policy_audit (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                agent_id TEXT NOT NULL,
+                action TEXT NOT NULL,
+                resource TEXT NOT NULL,
+                effect TEXT NOT NULL,
+                rule_name TEXT,
+                reason TEXT NOT NULL,
+                enforced INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS policy_audit_effect ON policy_audit (effect, seq);",
+        )?;
+        Ok(sink)
+    }
+
+    fn connect(&self) -> Result<Connection, AuditError> {
+        let conn = Connection::open(&self.db_path)?;
+        // Engines of concurrent crews write to the same file.
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(conn)
+    }
+}
+
+fn effect_str(effect: &PolicyEffect) -> &'static str {
+    match effect {
+        PolicyEffect::Allow => "allow",
+        PolicyEffect::Deny => "deny",
+    }
+}
+
+impl AuditSink for SqliteAuditSink {
+    fn record(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        self.connect()?.execute(
+            "INSERT INTO policy_audit (timestamp, agent_id, action, resource, effect,
+                rule_name, reason, enforced)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                record.timestamp.to_rfc3339(),
+                record.agent_id,
+                record.action,
+                record.resource,
+                effect_str(&record.effect),
+                record.rule_name,
+                record.reason,
+                record.enforced,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>, AuditError> {
+        let conn = self.connect()?;
+        let mut sql = String::from(
+            "SELECT timestamp, agent_id, action, resource, effect, rule_name, reason, enforced
+             FROM policy_audit WHERE 1 = 1",
+        );
+        let mut values: Vec<String> = Vec::new();
+        if let Some(agent_id) = &filter.agent_id {
+            values.push(agent_id.clone());
+            sql.push_str(&format!(" AND agent_id = ?{}", values.len()));
+        }
+        if let Some(effect) = &filter.effect {
+            values.push(effect_str(effect).to_string());
+            sql.push_str(&format!(" AND effect = ?{}", values.len()));
+        }
+        if let Some(rule_name) = &filter.rule_name {
+            values.push(rule_name.clone());
+            sql.push_str(&format!(" AND rule_name = ?{}", values.len()));
+        }
+        if let Some(since) = filter.since {
+            values.push(since.to_rfc3339());
+            sql.push_str(&format!(" AND timestamp >= ?{}", values.len()));
+        }
+        sql.push_str(" ORDER BY seq DESC");
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut statement = conn.prepare(&sql)?;
+        let rows = statement.query_map(rusqlite::params_from_iter(&values), |row| {
+            let timestamp: String = row.get(0)?;
+            let effect: String = row.get(4)?;
+            Ok(AuditRecord {
+                timestamp: DateTime::parse_from_rfc3339(&timestamp)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_default(),
+                agent_id: row.get(1)?,
+                action: row.get(2)?,
+                resource: row.get(3)?,
+                effect: if effect == "deny" {
+                    PolicyEffect::Deny
+                } else {
+                    PolicyEffect::Allow
+                },
+                rule_name: row.get(5)?,
+                reason: row.get(6)?,
+                enforced: row.get(7)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
+
+/// Sink for `path`: JSONL for `.jsonl`/`.json` files, SQLite otherwise.
+pub fn open_sink(path: impl AsRef<Path>) -> Result<Arc<dyn AuditSink>, AuditError> {
+    let path = path.as_ref();
+    let jsonl = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("jsonl" | "json" | "ndjson")
+    );
+    Ok(if jsonl {
+        Arc::new(JsonlAuditSink::new(path))
+    } else {
+        Arc::new(SqliteAuditSink::open(path)?)
+    })
+}
+
+static ENV_SINK: OnceLock<Option<Arc<dyn AuditSink>>> = OnceLock::new();
+
+/// The sink configured by `CREWAI_POLICY_AUDIT`, shared by all engines.
+pub fn env_sink() -> Option<Arc<dyn AuditSink>> {
+    ENV_SINK
+        .get_or_init(|| {
+            let path = std::env::var(POLICY_AUDIT_ENV)
+                .ok()
+                .filter(|p| !p.is_empty())?;
+            open_sink(&path)
+                .map_err(|e| {
+                    log::warn!(
+                        target: "crewai::policy_audit",
+                        "Cannot open policy audit log {}: {}",
+                        path,
+                        e
+                    )
+                })
+                .ok()
+        })
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{PolicyAction, PolicyResource};
+    use std::collections::HashMap;
+
+    fn record(agent_id: &str, effect: PolicyEffect, rule: Option<&str>) -> AuditRecord {
+        let request = PolicyRequest {
+            agent_slot: 0,
+            agent_id: agent_id.to_string(),
+            agent_roles: vec![],
+            action: PolicyAction::ToolCall("shell".to_string()),
+            resource: PolicyResource::Tool("shell".to_string()),
+            context: HashMap::new(),
+        };
+        AuditRecord::new(
+            &request,
+            &PolicyDecision {
+                effect,
+                rule_name: rule.map(str::to_string),
+                reason: "test".to_string(),
+                enforced: true,
+            },
+        )
+    }
+
+    #[test]
+    fn test_sinks_store_and_filter_records() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in ["audit.jsonl", "audit.db"] {
+            let sink = open_sink(dir.path().join(path)).unwrap();
+            sink.record(&record("alice", PolicyEffect::Allow, None))
+                .unwrap();
+            sink.record(&record("bob", PolicyEffect::Deny, Some("no_shell")))
+                .unwrap();
+            sink.record(&record("alice", PolicyEffect::Deny, Some("no_shell")))
+                .unwrap();
+
+            let all = sink.query(&AuditFilter::default()).unwrap();
+            assert_eq!(all.len(), 3, "{}", path);
+            assert_eq!(all[0].agent_id, "alice");
+            assert_eq!(all[0].action, "tool_call:shell");
+            assert_eq!(all[0].resource, "tool:shell");
+
+            let denials = sink
+                .query(&AuditFilter {
+                    effect: Some(PolicyEffect::Deny),
+                    limit: Some(1),
+                    ..Default::default()
+                })
+                .unwrap();
+            assert_eq!(denials, vec![all[0].clone()], "{}", path);
+
+            let bob = sink
+                .query(&AuditFilter {
+                    agent_id: Some("bob".to_string()),
+                    rule_name: Some("no_shell".to_string()),
+                    ..Default::default()
+                })
+                .unwrap();
+            assert_eq!(bob.len(), 1, "{}", path);
+            let future = sink
+                .query(&AuditFilter {
+                    since: Some(Utc::now() + chrono::Duration::hours(1)),
+                    ..Default::default()
+                })
+                .unwrap();
+            assert!(future.is_empty(), "{}", path);
+        }
+    }
+}
@@ -0,0 +1,849 @@
+//! Cedar policy import.
+//!
+//! Parses the subset of Cedar that maps onto [`PolicyRule`]s, so policies
+//! written for Cedar (or exported with
+//! [`PolicyEngine::export_cedar`](super::PolicyEngine::export_cedar)) can be
+//! loaded into the engine:
+//!
+//! ```text
+//! @id("no_shell")
+//! @priority("10")
+//! forbid (
+//!   principal in Role::"intern",
+//!   action in [Action::"tool_call:shell", Action::"tool_call:ssh"],
+//!   resource
+//! )
+//! when { context.command like "rm *" && context.confidence < 90 };
+//! ```
+//!
+//! Supported:
+//!
+//! - `permit` / `forbid`, with `@id`, `@description` and `@priority`
+//!   annotations (a `// name: description` comment, as exported, also names
+//!   the rule);
+//! - principals `Agent::"<id or 0xNN slot>"`, `Role::"<role>"` and sets of
+//!   agent slots;
+//! - actions `Action::"<name>"` using the export's names (`tool_call`,
+//!   `tool_call:<tool>`, `a2a:<kind>`, `memory_write`, ...), or a set of
+//!   them, which yields one rule per action;
+//! - resources `Tool`, `Capability`, `Collection`, `Zone`, `Prefix` and
+//!   `Custom` entities, and `resource like "<glob>"`;
+//! - `when` clauses joining comparisons of `context.<key>` with `&&`
+//!   (`==`, `!=`, `<`, `<=`, `>`, `>=`, `like`, `in`, `.contains(..)`), and
+//!   `unless` clauses with a single comparison.
+//!
+//! Anything else (`||`, `if`, `has`, attribute access on principals or
+//! resources, entity hierarchies other than roles, ...) is rejected with a
+//! [`CedarImportError::Unsupported`] naming the construct and its line.
+//! Cedar `like` globs become anchored regular expressions, which is what
+//! [`ConditionOperator::Matches`] evaluates; exported patterns, already
+//! anchored regexes, are kept as they are.
+
+use serde_json::Value;
+
+use super::{
+    ConditionOperator, PolicyAction, PolicyCondition, PolicyEffect, PolicyPrincipal,
+    PolicyResource, PolicyRule,
+};
+
+/// Error importing Cedar policies.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CedarImportError {
+    /// The text is not valid Cedar.
+    #[error("Cedar syntax error on line {line}: {message}")]
+    Syntax {
+        /// 1-based line of the error.
+        line: usize,
+        /// What was expected.
+        message: String,
+    },
+    /// The text is valid Cedar the policy engine cannot express.
+    #[error("Unsupported Cedar construct on line {line}: {construct}")]
+    Unsupported {
+        /// 1-based line of the construct.
+        line: usize,
+        /// The construct.
+        construct: String,
+    },
+}
+
+/// Parse Cedar policy text into rules, in the order written.
+pub fn parse_policies(text: &str) -> Result<Vec<PolicyRule>, CedarImportError> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+    };
+    let mut rules = Vec::new();
+    while !parser.at_end() {
+        rules.extend(parser.policy(rules.len())?);
+    }
+    Ok(rules)
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(f64),
+    Punct(&'static str),
+    Comment(String),
+}
+
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    line: usize,
+}
+
+/// Punctuation, longest first.
+const PUNCTUATION: &[&str] = &[
+    "::", "==", "!=", "<=", ">=", "&&", "||", "(", ")", "[", "]", "{", "}", ",", ";", "@", ".",
+    "<", ">", "!", "-", "+", "*", "?", ":",
+];
+
+fn tokenize(text: &str) -> Result<Vec<Spanned>, CedarImportError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let end = chars[i..]
+                .iter()
+                .position(|&c| c == '\n')
+                .map_or(chars.len(), |n| i + n);
+            let comment: String = chars[i + 2..end].iter().collect();
+            tokens.push(Spanned {
+                token: Token::Comment(comment.trim().to_string()),
+                line,
+            });
+            i = end;
+        } else if c == '"' {
+            let start_line = line;
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(CedarImportError::Syntax {
+                            line: start_line,
+                            message: "unterminated string".to_string(),
+                        })
+                    }
+                    Some('"') => break,
+                    Some('\\') => {
+                        let escaped = chars.get(i + 1).copied().unwrap_or('\\');
+                        value.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            'r' => '\r',
+                            '0' => '\0',
+                            other => other,
+                        });
+                        i += 2;
+                        continue;
+                    }
+                    Some(&c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        value.push(c);
+                    }
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Spanned {
+                token: Token::Str(value),
+                line: start_line,
+            });
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+            {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let number = literal.parse().map_err(|_| CedarImportError::Syntax {
+                line,
+                message: format!("invalid number '{}'", literal),
+            })?;
+            tokens.push(Spanned {
+                token: Token::Number(number),
+                line,
+            });
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while chars
+                .get(i)
+                .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+            {
+                i += 1;
+            }
+            tokens.push(Spanned {
+                token: Token::Ident(chars[start..i].iter().collect()),
+                line,
+            });
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let punct = PUNCTUATION
+                .iter()
+                .find(|p| rest.starts_with(**p))
+                .ok_or_else(|| CedarImportError::Syntax {
+                    line,
+                    message: format!("unexpected character '{}'", c),
+                })?;
+            tokens.push(Spanned {
+                token: Token::Punct(punct),
+                line,
+            });
+            i += punct.len();
+        }
+    }
+    Ok(tokens)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    pos: usize,
+}
+
+/// Scope constraint on the principal, action or resource.
+enum Scope {
+    Any,
+    Eq(String, String),
+    In(String, String),
+    InSet(Vec<(String, String)>),
+    Like(String),
+}
+
+impl Parser {
+    fn at_end(&self) -> bool {
+        self.tokens[self.pos..]
+            .iter()
+            .all(|t| matches!(t.token, Token::Comment(_)))
+    }
+
+    fn skip_comments(&mut self) -> Option<String> {
+        let mut last = None;
+        while let Some(Spanned {
+            token: Token::Comment(text),
+            ..
+        }) = self.tokens.get(self.pos)
+        {
+            last = Some(text.clone());
+            self.pos += 1;
+        }
+        last
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_comments();
+        self.tokens.get(self.pos).map(|t| &t.token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |t| t.line)
+    }
+
+    fn next(&mut self) -> Result<Token, CedarImportError> {
+        self.skip_comments();
+        let token = self
+            .tokens
+            .get(self.pos)
+            .map(|t| t.token.clone())
+            .ok_or_else(|| self.syntax("unexpected end of input"))?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn syntax(&self, message: impl Into<String>) -> CedarImportError {
+        CedarImportError::Syntax {
+            line: self.line(),
+            message: message.into(),
+        }
+    }
+
+    fn unsupported(&self, construct: impl Into<String>) -> CedarImportError {
+        CedarImportError::Unsupported {
+            line: self.line(),
+            construct: construct.into(),
+        }
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, ident: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(i)) if i == ident) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), CedarImportError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.syntax(format!("expected '{}'", punct)))
+        }
+    }
+
+    fn expect_ident(&mut self, ident: &str) -> Result<(), CedarImportError> {
+        if self.eat_ident(ident) {
+            Ok(())
+        } else {
+            Err(self.syntax(format!("expected '{}'", ident)))
+        }
+    }
+
+    fn string(&mut self) -> Result<String, CedarImportError> {
+        match self.next()? {
+            Token::Str(s) => Ok(s),
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax("expected a string"))
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, CedarImportError> {
+        match self.next()? {
+            Token::Ident(s) => Ok(s),
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax("expected an identifier"))
+            }
+        }
+    }
+
+    /// One policy, as one rule per action. `index` numbers unnamed rules.
+    fn policy(&mut self, index: usize) -> Result<Vec<PolicyRule>, CedarImportError> {
+        let comment = self.skip_comments();
+        let (mut name, mut description) = comment
+            .as_deref()
+            .and_then(|c| c.split_once(':'))
+            .filter(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+            .map_or((None, String::new()), |(n, d)| {
+                (Some(n.to_string()), d.trim().to_string())
+            });
+        let mut priority = super::default_priority();
+
+        while self.eat("@") {
+            let key = self.ident()?;
+            self.expect("(")?;
+            let value = self.string()?;
+            self.expect(")")?;
+            match key.as_str() {
+                "id" => name = Some(value),
+                "description" => description = value,
+                "priority" => {
+                    priority = value
+                        .parse()
+                        .map_err(|_| self.syntax(format!("invalid priority '{}'", value)))?
+                }
+                // Other annotations carry no meaning for the engine.
+                _ => {}
+            }
+        }
+
+        let effect = match self.ident()?.as_str() {
+            "permit" => PolicyEffect::Allow,
+            "forbid" => PolicyEffect::Deny,
+            other => {
+                self.pos -= 1;
+                return Err(
+                    self.syntax(format!("expected 'permit' or 'forbid', found '{}'", other))
+                );
+            }
+        };
+        self.expect("(")?;
+        let principal = self.scope("principal")?;
+        self.expect(",")?;
+        let action = self.scope("action")?;
+        self.expect(",")?;
+        let resource = self.scope("resource")?;
+        self.expect(")")?;
+
+        let mut conditions = Vec::new();
+        loop {
+            if self.eat_ident("when") {
+                self.expect("{")?;
+                conditions.extend(self.conditions()?);
+            } else if self.eat_ident("unless") {
+                self.expect("{")?;
+                let line = self.line();
+                let mut negated = self.conditions()?;
+                if negated.len() != 1 {
+                    return Err(CedarImportError::Unsupported {
+                        line,
+                        construct: "'unless' with more than one condition".to_string(),
+                    });
+                }
+                let mut condition = negated.remove(0);
+                condition.operator =
+                    negate(&condition.operator).ok_or_else(|| CedarImportError::Unsupported {
+                        line,
+                        construct: format!("'unless' with '{:?}'", condition.operator),
+                    })?;
+                conditions.push(condition);
+            } else {
+                break;
+            }
+        }
+        self.expect(";")?;
+
+        let principal = self.principal(principal)?;
+        let actions = self.actions(action)?;
+        let resource = self.resource(resource)?;
+        let name = name.unwrap_or_else(|| format!("cedar_policy_{}", index));
+        let count = actions.len();
+        Ok(actions
+            .into_iter()
+            .enumerate()
+            .map(|(i, action)| PolicyRule {
+                name: if count > 1 {
+                    format!("{}_{}", name, i + 1)
+                } else {
+                    name.clone()
+                },
+                description: description.clone(),
+                effect: effect.clone(),
+                principal: principal.clone(),
+                action,
+                resource: resource.clone(),
+                conditions: conditions.clone(),
+                priority,
+            })
+            .collect())
+    }
+
+    fn entity(&mut self) -> Result<(String, String), CedarImportError> {
+        let kind = self.ident()?;
+        if !self.eat("::") {
+            return Err(self.syntax(format!("expected '::' after '{}'", kind)));
+        }
+        if matches!(self.peek(), Some(Token::Ident(_))) {
+            return Err(self.unsupported("namespaced entity types"));
+        }
+        Ok((kind, self.string()?))
+    }
+
+    fn scope(&mut self, var: &str) -> Result<Scope, CedarImportError> {
+        self.expect_ident(var)?;
+        if self.eat("==") {
+            let (kind, id) = self.entity()?;
+            Ok(Scope::Eq(kind, id))
+        } else if self.eat_ident("in") {
+            if self.eat("[") {
+                let mut entities = Vec::new();
+                while !self.eat("]") {
+                    entities.push(self.entity()?);
+                    if !self.eat(",") {
+                        self.expect("]")?;
+                        break;
+                    }
+                }
+                Ok(Scope::InSet(entities))
+            } else {
+                let (kind, id) = self.entity()?;
+                Ok(Scope::In(kind, id))
+            }
+        } else if self.eat_ident("like") {
+            Ok(Scope::Like(self.string()?))
+        } else if self.eat_ident("is") {
+            Err(self.unsupported(format!("'{} is <type>'", var)))
+        } else {
+            Ok(Scope::Any)
+        }
+    }
+
+    fn principal(&self, scope: Scope) -> Result<PolicyPrincipal, CedarImportError> {
+        let agent = |id: &str| match parse_slot(id) {
+            Some(slot) => PolicyPrincipal::Agent(slot),
+            None => PolicyPrincipal::AgentId(id.to_string()),
+        };
+        match scope {
+            Scope::Any => Ok(PolicyPrincipal::All),
+            Scope::Eq(kind, id) if kind == "Agent" => Ok(agent(&id)),
+            Scope::Eq(kind, id) | Scope::In(kind, id) if kind == "Role" => {
+                Ok(PolicyPrincipal::Role(id))
+            }
+            Scope::InSet(entities) => entities
+                .iter()
+                .map(|(kind, id)| match parse_slot(id) {
+                    Some(slot) if kind == "Agent" => Ok(slot),
+                    _ => Err(self.unsupported(format!(
+                        "principal set member {}::\"{}\" (only agent slots)",
+                        kind, id
+                    ))),
+                })
+                .collect::<Result<_, _>>()
+                .map(PolicyPrincipal::Group),
+            Scope::Eq(kind, id) | Scope::In(kind, id) => Err(self.unsupported(format!(
+                "principal {}::\"{}\" (only Agent and Role)",
+                kind, id
+            ))),
+            Scope::Like(_) => Err(self.unsupported("'principal like'")),
+        }
+    }
+
+    fn actions(&self, scope: Scope) -> Result<Vec<PolicyAction>, CedarImportError> {
+        let action = |kind: &str, id: &str| {
+            if kind == "Action" {
+                Ok(parse_action(id))
+            } else {
+                Err(self.unsupported(format!("action of type '{}'", kind)))
+            }
+        };
+        match scope {
+            Scope::Any => Ok(vec![PolicyAction::Any]),
+            Scope::Eq(kind, id) => Ok(vec![action(&kind, &id)?]),
+            Scope::InSet(entities) if !entities.is_empty() => {
+                entities.iter().map(|(kind, id)| action(kind, id)).collect()
+            }
+            Scope::InSet(_) => Err(self.unsupported("empty action set")),
+            Scope::In(..) => Err(self.unsupported("action groups")),
+            Scope::Like(_) => Err(self.unsupported("'action like'")),
+        }
+    }
+
+    fn resource(&self, scope: Scope) -> Result<PolicyResource, CedarImportError> {
+        match scope {
+            Scope::Any => Ok(PolicyResource::Any),
+            Scope::Like(pattern) => Ok(PolicyResource::Pattern(pattern)),
+            Scope::Eq(kind, id) => match kind.as_str() {
+                "Tool" => Ok(PolicyResource::Tool(id)),
+                "Capability" => Ok(PolicyResource::Capability(id)),
+                "Collection" => Ok(PolicyResource::Collection(id)),
+                "Zone" => Ok(PolicyResource::Zone(id)),
+                "Custom" => Ok(PolicyResource::Custom(id)),
+                "Prefix" => parse_slot(&id)
+                    .map(PolicyResource::Prefix)
+                    .ok_or_else(|| self.syntax(format!("invalid prefix '{}'", id))),
+                _ => Err(self.unsupported(format!("resource of type '{}'", kind))),
+            },
+            Scope::In(..) | Scope::InSet(_) => Err(self.unsupported("'resource in'")),
+        }
+    }
+
+    /// Conditions of a `when`/`unless` body, up to and including its `}`.
+    fn conditions(&mut self) -> Result<Vec<PolicyCondition>, CedarImportError> {
+        let mut conditions = Vec::new();
+        while !self.eat("}") {
+            if !conditions.is_empty() {
+                // Exported policies put one condition per line without `&&`.
+                self.eat("&&");
+            }
+            conditions.push(self.condition()?);
+            if self.eat("||") {
+                self.pos -= 1;
+                return Err(self.unsupported("'||'"));
+            }
+        }
+        Ok(conditions)
+    }
+
+    fn condition(&mut self) -> Result<PolicyCondition, CedarImportError> {
+        match self.peek() {
+            Some(Token::Ident(var)) if var == "context" => {}
+            Some(Token::Ident(word))
+                if matches!(word.as_str(), "if" | "principal" | "resource" | "action") =>
+            {
+                let construct = format!("'{}' in conditions", word);
+                return Err(self.unsupported(construct));
+            }
+            Some(Token::Punct("!")) => return Err(self.unsupported("negated conditions")),
+            Some(Token::Punct("(")) => return Err(self.unsupported("grouped conditions")),
+            _ => return Err(self.syntax("expected a condition on 'context.<key>'")),
+        }
+        self.pos += 1;
+        if self.eat_ident("has") {
+            return Err(self.unsupported("'has'"));
+        }
+        let key = if self.eat(".") {
+            self.ident()?
+        } else if self.eat("[") {
+            let key = self.string()?;
+            self.expect("]")?;
+            key
+        } else {
+            return Err(self.syntax("expected '.' after 'context'"));
+        };
+
+        if self.eat(".") {
+            let method = self.ident()?;
+            let operator = match method.as_str() {
+                "contains" => ConditionOperator::Contains,
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.unsupported(format!(
+                        "'.{}' (nested attributes and methods other than contains)",
+                        method
+                    )));
+                }
+            };
+            self.expect("(")?;
+            let value = self.value()?;
+            self.expect(")")?;
+            return Ok(PolicyCondition {
+                key,
+                operator,
+                value,
+            });
+        }
+
+        let operator = if self.eat("!") {
+            match self.ident()?.as_str() {
+                "contains" => ConditionOperator::NotContains,
+                "in" => ConditionOperator::NotIn,
+                _ => {
+                    self.pos -= 1;
+                    return Err(self.syntax("expected 'contains' or 'in' after '!'"));
+                }
+            }
+        } else {
+            match self.next()? {
+                Token::Punct("==") => ConditionOperator::Equals,
+                Token::Punct("!=") => ConditionOperator::NotEquals,
+                Token::Punct("<") => ConditionOperator::LessThan,
+                Token::Punct("<=") => ConditionOperator::LessThanOrEqual,
+                Token::Punct(">") => ConditionOperator::GreaterThan,
+                Token::Punct(">=") => ConditionOperator::GreaterThanOrEqual,
+                Token::Ident(op) if op == "like" => ConditionOperator::Matches,
+                Token::Ident(op) if op == "in" => ConditionOperator::In,
+                Token::Ident(op) if op == "contains" => ConditionOperator::Contains,
+                Token::Ident(op) if op == "starts_with" => ConditionOperator::StartsWith,
+                Token::Ident(op) if op == "ends_with" => ConditionOperator::EndsWith,
+                Token::Ident(op) if op == "script" => ConditionOperator::Script,
+                other => {
+                    self.pos -= 1;
+                    return Err(self.syntax(format!("expected an operator, found {:?}", other)));
+                }
+            }
+        };
+        let mut value = self.value()?;
+        if let (ConditionOperator::Matches, Value::String(glob)) = (&operator, &value) {
+            value = Value::String(glob_to_regex(glob));
+        }
+        Ok(PolicyCondition {
+            key,
+            operator,
+            value,
+        })
+    }
+
+    fn value(&mut self) -> Result<Value, CedarImportError> {
+        match self.next()? {
+            Token::Str(s) => Ok(Value::String(s)),
+            Token::Number(n) => Ok(number(n)),
+            Token::Punct("-") => match self.next()? {
+                Token::Number(n) => Ok(number(-n)),
+                _ => Err(self.syntax("expected a number after '-'")),
+            },
+            Token::Ident(b) if b == "true" || b == "false" => Ok(Value::Bool(b == "true")),
+            Token::Punct("[") => {
+                let mut items = Vec::new();
+                while !self.eat("]") {
+                    items.push(self.value()?);
+                    if !self.eat(",") {
+                        self.expect("]")?;
+                        break;
+                    }
+                }
+                Ok(Value::Array(items))
+            }
+            Token::Ident(other) => {
+                self.pos -= 1;
+                Err(self.unsupported(format!("'{}' as a value (only literals and sets)", other)))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.syntax("expected a value"))
+            }
+        }
+    }
+}
+
+fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < i64::MAX as f64 {
+        Value::from(n as i64)
+    } else {
+        Value::from(n)
+    }
+}
+
+/// `0x0C` as a slot.
+fn parse_slot(id: &str) -> Option<u8> {
+    u8::from_str_radix(id.strip_prefix("0x").or(id.strip_prefix("0X"))?, 16).ok()
+}
+
+/// Action from its exported name.
+fn parse_action(name: &str) -> PolicyAction {
+    match name {
+        "tool_call" => PolicyAction::AnyToolCall,
+        "memory_write" => PolicyAction::MemoryWrite,
+        "memory_read" => PolicyAction::MemoryRead,
+        "blackboard_commit" => PolicyAction::BlackboardCommit,
+        "handover" => PolicyAction::Handover,
+        _ => {
+            if let Some(tool) = name.strip_prefix("tool_call:") {
+                PolicyAction::ToolCall(tool.to_string())
+            } else if let Some(kind) = name.strip_prefix("a2a:") {
+                PolicyAction::A2aMessage(kind.to_string())
+            } else if let Some(opcode) = name
+                .strip_prefix("cam:0x")
+                .and_then(|op| u16::from_str_radix(op, 16).ok())
+            {
+                PolicyAction::CamOp(opcode)
+            } else {
+                PolicyAction::Custom(name.to_string())
+            }
+        }
+    }
+}
+
+fn negate(operator: &ConditionOperator) -> Option<ConditionOperator> {
+    Some(match operator {
+        ConditionOperator::Equals => ConditionOperator::NotEquals,
+        ConditionOperator::NotEquals => ConditionOperator::Equals,
+        ConditionOperator::LessThan => ConditionOperator::GreaterThanOrEqual,
+        ConditionOperator::GreaterThanOrEqual => ConditionOperator::LessThan,
+        ConditionOperator::GreaterThan => ConditionOperator::LessThanOrEqual,
+        ConditionOperator::LessThanOrEqual => ConditionOperator::GreaterThan,
+        ConditionOperator::Contains => ConditionOperator::NotContains,
+        ConditionOperator::NotContains => ConditionOperator::Contains,
+        ConditionOperator::In => ConditionOperator::NotIn,
+        ConditionOperator::NotIn => ConditionOperator::In,
+        _ => return None,
+    })
+}
+
+/// Cedar `like` glob (`*` matches anything) as an anchored regex. Values
+/// that already are anchored regexes, as exported, are kept.
+fn glob_to_regex(glob: &str) -> String {
+    if glob.starts_with('^') && glob.ends_with('$') {
+        return glob.to_string();
+    }
+    let parts: Vec<String> = glob.split('*').map(regex::escape).collect();
+    format!("^{}$", parts.join(".*"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{PolicyEngine, PolicyRequest};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_import_cedar_policies() {
+        let text = r#"
+            // Imported from the platform team's policy store.
+            @id("no_shell")
+            @priority("10")
+            forbid (
+              principal in Role::"intern",
+              action in [Action::"tool_call:shell", Action::"tool_call:ssh"],
+              resource
+            )
+            when { context.command like "rm *" && context.confidence < 90 };
+
+            permit (principal == Agent::"0x0C", action, resource == Tool::"search")
+            unless { context.query.contains("secret") };
+        "#;
+        let rules = parse_policies(text).unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].name, "no_shell_1");
+        assert_eq!(rules[0].priority, 10);
+        assert_eq!(rules[0].effect, PolicyEffect::Deny);
+        assert!(matches!(&rules[0].principal, PolicyPrincipal::Role(r) if r == "intern"));
+        assert!(matches!(&rules[1].action, PolicyAction::ToolCall(t) if t == "ssh"));
+        assert_eq!(rules[0].conditions[0].value, "^rm .*$");
+        assert_eq!(rules[0].conditions[1].value, 90);
+        assert_eq!(rules[2].name, "cedar_policy_2");
+        assert!(matches!(rules[2].principal, PolicyPrincipal::Agent(0x0C)));
+        assert!(matches!(
+            rules[2].conditions[0].operator,
+            ConditionOperator::NotContains
+        ));
+
+        let mut engine = PolicyEngine::new();
+        assert_eq!(engine.import_cedar(text).unwrap(), 3);
+        let request = PolicyRequest {
+            agent_slot: 1,
+            agent_id: "bob".to_string(),
+            agent_roles: vec!["intern".to_string()],
+            action: PolicyAction::ToolCall("shell".to_string()),
+            resource: PolicyResource::Tool("shell".to_string()),
+            context: HashMap::from([
+                ("command".to_string(), Value::from("rm -rf /")),
+                ("confidence".to_string(), Value::from(50)),
+            ]),
+        };
+        assert_eq!(engine.evaluate(&request).effect, PolicyEffect::Deny);
+
+        // Exported policies import back to the same rules.
+        let exported = engine.export_cedar();
+        let reimported = parse_policies(&exported).unwrap();
+        assert_eq!(
+            serde_json::to_value(&reimported).unwrap(),
+            serde_json::to_value(&engine.rules).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_unsupported_constructs_are_reported() {
+        let err = parse_policies(
+            "permit (principal, action, resource)\nwhen { context.a == 1 || context.b == 2 };",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            CedarImportError::Unsupported {
+                line: 2,
+                construct: "'||'".to_string()
+            }
+        );
+        assert!(matches!(
+            parse_policies("permit (principal is User, action, resource);"),
+            Err(CedarImportError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            parse_policies("permit (principal, action, resource)\nwhen { principal.level > 3 };"),
+            Err(CedarImportError::Unsupported { line: 2, .. })
+        ));
+        assert_eq!(
+            parse_policies("allow (principal, action, resource);")
+                .unwrap_err()
+                .to_string(),
+            "Cedar syntax error on line 1: expected 'permit' or 'forbid', found 'allow'"
+        );
+    }
+}
@@ -37,20 +37,44 @@
 //!   requires_roles: ["server_admin"]
 //! ```
 //!
-//! ## Cedar Export
+//! ## Cedar Export and Import
 //!
-//! Policies can be exported to Cedar language for audit/compliance tools.
+//! Policies can be exported to Cedar language for audit/compliance tools,
+//! and a subset of Cedar can be imported (see [`cedar`]) to reuse existing
+//! Cedar policies.
+//!
+//! ## Rate Limits
+//!
+//! A capability policy's `max_rpm` is enforced per agent with a sliding
+//! one-minute window (see [`rate_limit`]); calls beyond it are denied by the
+//! `<capability>_rate_limit` rule.
+//!
+//! ## Audit Log
+//!
+//! Every decision is recorded; attach an [`audit::AuditSink`] to keep the
+//! records across restarts and query them with
+//! [`PolicyEngine::query_audit`].
 
+pub mod audit;
+pub mod cedar;
+pub mod rate_limit;
 pub mod rbac;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+use crate::events::types::policy_events::PolicyDeniedEvent;
+use crate::events::CREWAI_EVENT_BUS;
+use crate::utilities::perf::{self, HotPath};
 
+pub use audit::{AuditFilter, AuditRecord, AuditSink};
+pub use rate_limit::RateLimiter;
 pub use rbac::RbacManager;
 
 /// The policy engine: evaluates requests against rules.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PolicyEngine {
     /// All rules, evaluated in order (first match wins for deny, all must pass for allow)
     pub rules: Vec<PolicyRule>,
@@ -61,11 +85,17 @@ pub struct PolicyEngine {
     /// RBAC manager
     pub rbac: RbacManager,
 
+    /// Per-agent capability rate limits
+    pub rate_limiter: RateLimiter,
+
     /// Audit log of recent decisions
-    audit_log: Vec<AuditEntry>,
+    audit_log: VecDeque<AuditRecord>,
 
     /// Maximum audit log entries to retain
     max_audit_entries: usize,
+
+    /// Persistent destinations of audit records
+    audit_sinks: Vec<Arc<dyn AuditSink>>,
 }
 
 /// A policy rule
@@ -205,6 +235,10 @@ pub enum ConditionOperator {
     EndsWith,
     In,
     NotIn,
+    /// `value` is a Rhai script returning a boolean, evaluated with `value`
+    /// (the key's context value) and `context` in scope. Requires the
+    /// `scripting` feature; without it the condition never matches.
+    Script,
 }
 
 /// Enforcement mode
@@ -244,6 +278,62 @@ pub struct PolicyRequest {
     pub context: HashMap<String, Value>,
 }
 
+impl PolicyRequest {
+    /// Request for agent `agent_id` calling `tool` with `args`.
+    ///
+    /// The resource is the tool, or its capability for qualified
+    /// `capability::tool` names. The context holds each top-level argument,
+    /// `args` (all of them), `args_string` (their JSON text) and `tool`.
+    pub fn tool_call(agent_id: &str, agent_roles: Vec<String>, tool: &str, args: &Value) -> Self {
+        let resource = match tool.split_once("::") {
+            Some((capability, _)) => PolicyResource::Capability(capability.to_string()),
+            None => PolicyResource::Tool(tool.to_string()),
+        };
+        let mut context: HashMap<String, Value> = args
+            .as_object()
+            .map(|args| args.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+            .unwrap_or_default();
+        context.insert("args".to_string(), args.clone());
+        context.insert("args_string".to_string(), Value::String(args.to_string()));
+        context.insert("tool".to_string(), Value::String(tool.to_string()));
+        Self {
+            agent_slot: 0,
+            agent_id: agent_id.to_string(),
+            agent_roles,
+            action: PolicyAction::ToolCall(tool.to_string()),
+            resource,
+            context,
+        }
+    }
+}
+
+/// A tool call stopped by an enforced deny decision.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PolicyViolation {
+    /// The call was not run ([`EnforcementMode::Strict`]); the agent is told
+    /// why and may continue without it.
+    #[error("Tool '{tool}' was blocked by policy: {reason}")]
+    Blocked {
+        /// Tool that was called.
+        tool: String,
+        /// Matching deny rule.
+        rule_name: Option<String>,
+        /// Why the call was denied.
+        reason: String,
+    },
+    /// The call was not run and the execution stops, leaving the decision
+    /// to the orchestrator ([`EnforcementMode::Escalate`]).
+    #[error("Tool '{tool}' was denied by policy and escalated: {reason}")]
+    Escalated {
+        /// Tool that was called.
+        tool: String,
+        /// Matching deny rule.
+        rule_name: Option<String>,
+        /// Why the call was denied.
+        reason: String,
+    },
+}
+
 /// The result of a policy evaluation
 #[derive(Debug, Clone)]
 pub struct PolicyDecision {
@@ -260,12 +350,10 @@ pub struct PolicyDecision {
     pub enforced: bool,
 }
 
-/// Audit log entry
-#[derive(Debug, Clone)]
-struct AuditEntry {
-    timestamp: std::time::Instant,
-    request_summary: String,
-    decision: PolicyDecision,
+impl Default for PolicyEngine {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PolicyEngine {
@@ -275,8 +363,10 @@ impl PolicyEngine {
             rules: Vec::new(),
             enforcement: EnforcementMode::Strict,
             rbac: RbacManager::new(),
-            audit_log: Vec::new(),
+            rate_limiter: RateLimiter::new(),
+            audit_log: VecDeque::new(),
             max_audit_entries: 10000,
+            audit_sinks: audit::env_sink().into_iter().collect(),
         }
     }
 
@@ -312,9 +402,11 @@ impl PolicyEngine {
     /// Logic:
     /// 1. Check RBAC first (if the action involves a capability)
     /// 2. Evaluate all Deny rules — if any match, deny
-    /// 3. Evaluate all Allow rules — if any match, allow
-    /// 4. Default: deny (deny by default)
+    /// 3. Check the rate limit of the capability, if any — if exceeded, deny
+    /// 4. Evaluate all Allow rules — if any match, allow
+    /// 5. Default: allow
     pub fn evaluate(&mut self, request: &PolicyRequest) -> PolicyDecision {
+        let _span = perf::span(HotPath::PolicyEvaluation);
         // Check deny rules first
         for rule in &self.rules {
             if rule.effect == PolicyEffect::Deny && self.rule_matches(rule, request) {
@@ -326,6 +418,28 @@ impl PolicyEngine {
                         || self.enforcement == EnforcementMode::Escalate,
                 };
                 self.audit(request, &decision);
+                emit_denied(request, &decision);
+                return decision;
+            }
+        }
+
+        // Check rate limits; only calls that get this far are counted
+        if let PolicyResource::Capability(capability) = &request.resource {
+            if let Err(exceeded) = self.rate_limiter.check(&request.agent_id, capability) {
+                let decision = PolicyDecision {
+                    effect: PolicyEffect::Deny,
+                    rule_name: Some(format!("{}_rate_limit", capability)),
+                    reason: format!(
+                        "Rate limit of {} calls per minute to {} exceeded — retry in {}s",
+                        exceeded.max_rpm,
+                        capability,
+                        exceeded.retry_after.as_secs().max(1)
+                    ),
+                    enforced: self.enforcement == EnforcementMode::Strict
+                        || self.enforcement == EnforcementMode::Escalate,
+                };
+                self.audit(request, &decision);
+                emit_denied(request, &decision);
                 return decision;
             }
         }
@@ -356,6 +470,51 @@ impl PolicyEngine {
         decision
     }
 
+    /// Check a call of `tool` with `args` by `agent_id`, whose roles come
+    /// from the RBAC manager.
+    ///
+    /// Allowed calls, and denied ones in audit-only mode, return `Ok`. An
+    /// enforced denial is [`PolicyViolation::Blocked`], or
+    /// [`PolicyViolation::Escalated`] in escalate mode.
+    pub fn check_tool_call(
+        &mut self,
+        agent_id: &str,
+        tool: &str,
+        args: &Value,
+    ) -> Result<PolicyDecision, PolicyViolation> {
+        let roles = self
+            .rbac
+            .get_agent_roles(agent_id)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        let decision = self.evaluate(&PolicyRequest::tool_call(agent_id, roles, tool, args));
+        if decision.effect == PolicyEffect::Allow || !decision.enforced {
+            if decision.effect == PolicyEffect::Deny {
+                log::warn!(
+                    "Policy would deny '{}' for agent '{}' (audit only): {}",
+                    tool,
+                    agent_id,
+                    decision.reason
+                );
+            }
+            return Ok(decision);
+        }
+        let (tool, rule_name, reason) = (tool.to_string(), decision.rule_name, decision.reason);
+        Err(match self.enforcement {
+            EnforcementMode::Escalate => PolicyViolation::Escalated {
+                tool,
+                rule_name,
+                reason,
+            },
+            _ => PolicyViolation::Blocked {
+                tool,
+                rule_name,
+                reason,
+            },
+        })
+    }
+
     /// Check if a rule matches a request
     fn rule_matches(&self, rule: &PolicyRule, request: &PolicyRequest) -> bool {
         // Check principal
@@ -444,6 +603,9 @@ impl PolicyEngine {
         condition: &PolicyCondition,
         context: &HashMap<String, Value>,
     ) -> bool {
+        if let ConditionOperator::Script = condition.operator {
+            return script_condition_matches(condition, context);
+        }
         let actual = match context.get(&condition.key) {
             Some(v) => v,
             None => return false,
@@ -513,22 +675,22 @@ impl PolicyEngine {
                     true
                 }
             }
+            ConditionOperator::Script => unreachable!("handled above"),
         }
     }
 
     /// Add an audit entry
     fn audit(&mut self, request: &PolicyRequest, decision: &PolicyDecision) {
+        let record = AuditRecord::new(request, decision);
+        for sink in &self.audit_sinks {
+            if let Err(e) = sink.record(&record) {
+                log::warn!(target: "crewai::policy_audit", "Failed to write audit record: {}", e);
+            }
+        }
         if self.audit_log.len() >= self.max_audit_entries {
-            self.audit_log.remove(0);
+            self.audit_log.pop_front();
         }
-        self.audit_log.push(AuditEntry {
-            timestamp: std::time::Instant::now(),
-            request_summary: format!(
-                "agent={} action={:?} resource={:?}",
-                request.agent_id, request.action, request.resource
-            ),
-            decision: decision.clone(),
-        });
+        self.audit_log.push_back(record);
     }
 
     /// Get recent audit entries count.
@@ -536,6 +698,22 @@ impl PolicyEngine {
         self.audit_log.len()
     }
 
+    /// Also write every audit record to `sink`.
+    pub fn add_audit_sink(&mut self, sink: Arc<dyn AuditSink>) {
+        self.audit_sinks.push(sink);
+    }
+
+    /// Audit records matching `filter`, most recent first.
+    ///
+    /// Reads the first sink when one is attached (so records of earlier
+    /// runs are included), otherwise the in-memory log.
+    pub fn query_audit(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>, audit::AuditError> {
+        match self.audit_sinks.first() {
+            Some(sink) => sink.query(filter),
+            None => Ok(filter.apply(self.audit_log.iter())),
+        }
+    }
+
     /// Load rules from a capability's policy section.
     pub fn load_capability_policy(
         &mut self,
@@ -547,18 +725,9 @@ impl PolicyEngine {
             self.rbac.grant_capability_to_role(role, capability_id);
         }
 
-        // Add rate limit rule if specified
+        // Enforce the rate limit if specified
         if let Some(max_rpm) = policy.max_rpm {
-            self.add_rule(PolicyRule {
-                name: format!("{}_rate_limit", capability_id),
-                description: format!("Rate limit for {} ({} rpm)", capability_id, max_rpm),
-                effect: PolicyEffect::Allow,
-                principal: PolicyPrincipal::All,
-                action: PolicyAction::AnyToolCall,
-                resource: PolicyResource::Capability(capability_id.to_string()),
-                conditions: vec![],
-                priority: 50,
-            });
+            self.rate_limiter.set_limit(capability_id, max_rpm);
         }
 
         // Add approval rules
@@ -687,9 +856,13 @@ impl PolicyEngine {
                 PolicyResource::Custom(name) => format!("resource == Custom::\"{}\"", name),
             };
 
+            output.push_str(&format!("// {}: {}\n", rule.name, rule.description));
+            if rule.priority != default_priority() {
+                output.push_str(&format!("@priority(\"{}\")\n", rule.priority));
+            }
             output.push_str(&format!(
-                "// {}: {}\n{} (\n  {},\n  {},\n  {}\n)",
-                rule.name, rule.description, effect, principal, action_str, resource_str
+                "{} (\n  {},\n  {},\n  {}\n)",
+                effect, principal, action_str, resource_str
             ));
 
             if !rule.conditions.is_empty() {
@@ -709,6 +882,7 @@ impl PolicyEngine {
                         ConditionOperator::EndsWith => "ends_with",
                         ConditionOperator::In => "in",
                         ConditionOperator::NotIn => "!in",
+                        ConditionOperator::Script => "script",
                     };
                     output.push_str(&format!(
                         "  context.{} {} {}\n",
@@ -726,12 +900,48 @@ impl PolicyEngine {
         output
     }
 
+    /// Import rules from Cedar policy text (see [`cedar`] for the supported
+    /// subset). Either all policies are added or, on error, none; returns
+    /// the number of rules added.
+    pub fn import_cedar(&mut self, text: &str) -> Result<usize, cedar::CedarImportError> {
+        let rules = cedar::parse_policies(text)?;
+        let count = rules.len();
+        for rule in rules {
+            self.add_rule(rule);
+        }
+        Ok(count)
+    }
+
     /// Get the total number of rules.
     pub fn rule_count(&self) -> usize {
         self.rules.len()
     }
 }
 
+/// Evaluate a [`ConditionOperator::Script`] condition.
+#[cfg(feature = "scripting")]
+fn script_condition_matches(condition: &PolicyCondition, context: &HashMap<String, Value>) -> bool {
+    match condition.value.as_str() {
+        Some(source) => {
+            crate::scripting::policy_condition_matches(source, context.get(&condition.key), context)
+        }
+        None => false,
+    }
+}
+
+/// Evaluate a [`ConditionOperator::Script`] condition.
+#[cfg(not(feature = "scripting"))]
+fn script_condition_matches(
+    condition: &PolicyCondition,
+    _context: &HashMap<String, Value>,
+) -> bool {
+    log::warn!(
+        "Policy condition on '{}' uses a script, but scripting is not enabled",
+        condition.key
+    );
+    false
+}
+
 /// Simple glob-like pattern matching (supports * wildcards)
 fn pattern_matches(pattern: &str, text: &str) -> bool {
     if pattern == "*" {
@@ -786,6 +996,27 @@ fn compare_values(a: &Value, b: &Value) -> i8 {
     }
 }
 
+/// Emit a [`PolicyDeniedEvent`] on the global event bus, if it has been
+/// initialised.
+fn emit_denied(request: &PolicyRequest, decision: &PolicyDecision) {
+    if let Some(bus) = CREWAI_EVENT_BUS.get() {
+        let action = match serde_json::to_value(&request.action) {
+            Ok(Value::String(name)) => name,
+            Ok(Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+            _ => format!("{:?}", request.action),
+        };
+        let mut event = PolicyDeniedEvent::new(
+            request.agent_id.clone(),
+            action,
+            format!("{:?}", request.resource),
+            decision.rule_name.clone(),
+            decision.reason.clone(),
+            decision.enforced,
+        );
+        bus.emit(std::sync::Arc::new(request.agent_id.clone()), &mut event);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -838,6 +1069,25 @@ mod tests {
 
         let decision2 = engine.evaluate(&request2);
         assert_eq!(decision2.effect, PolicyEffect::Allow);
+
+        let denials = engine
+            .query_audit(&AuditFilter {
+                effect: Some(PolicyEffect::Deny),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(denials.len(), 1);
+        assert_eq!(denials[0].rule_name.as_deref(), Some("deny_stop"));
+        assert_eq!(denials[0].action, "tool_call:mc_execute");
+
+        let dir = tempfile::tempdir().unwrap();
+        let sink = Arc::new(audit::JsonlAuditSink::new(dir.path().join("audit.jsonl")));
+        engine.add_audit_sink(sink.clone());
+        engine.evaluate(&request);
+        assert_eq!(engine.audit_count(), 3);
+        let persisted = engine.query_audit(&AuditFilter::default()).unwrap();
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted, sink.query(&AuditFilter::default()).unwrap());
     }
 
     #[test]
@@ -901,6 +1151,39 @@ mod tests {
         assert!(pattern_matches("*exec*", "mc_execute_cmd"));
     }
 
+    #[test]
+    fn test_capability_rate_limit_is_enforced() {
+        let mut engine = PolicyEngine::new();
+        engine.load_capability_policy(
+            "search",
+            &crate::capabilities::CapabilityPolicy {
+                max_rpm: Some(2),
+                ..Default::default()
+            },
+        );
+        let args = serde_json::json!({"q": "rust"});
+        for _ in 0..2 {
+            assert!(engine
+                .check_tool_call("alice", "search::web", &args)
+                .is_ok());
+        }
+        match engine.check_tool_call("alice", "search::news", &args) {
+            Err(PolicyViolation::Blocked { rule_name, .. }) => {
+                assert_eq!(rule_name.as_deref(), Some("search_rate_limit"))
+            }
+            other => panic!("expected a rate limit denial, got {:?}", other),
+        }
+        assert!(engine.check_tool_call("bob", "search::web", &args).is_ok());
+        assert!(engine.check_tool_call("alice", "web_fetch", &args).is_ok());
+
+        engine.enforcement = EnforcementMode::AuditOnly;
+        let decision = engine
+            .check_tool_call("alice", "search::web", &args)
+            .unwrap();
+        assert_eq!(decision.effect, PolicyEffect::Deny);
+        assert!(!decision.enforced);
+    }
+
     #[test]
     fn test_cedar_export() {
         let mut engine = PolicyEngine::new();
@@ -920,5 +1203,6 @@ mod tests {
         assert!(cedar.contains("forbid"));
         assert!(cedar.contains("deny_node_writes"));
         assert!(cedar.contains("cam:0x0080"));
+        assert!(cedar.contains("@priority(\"1\")\nforbid ("));
     }
 }
@@ -0,0 +1,130 @@
+//! Per-principal rate limits on capabilities.
+//!
+//! A capability policy's `max_rpm` limits how often each agent may call
+//! the capability's tools. The limiter keeps a sliding one-minute window of
+//! call times per (agent, capability) pair; a call beyond the limit is
+//! denied until the oldest call in the window is a minute old.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Length of the sliding window.
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// A call denied by a rate limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitExceeded {
+    /// Calls allowed per minute.
+    pub max_rpm: u32,
+    /// When the next call will be allowed.
+    pub retry_after: Duration,
+}
+
+/// Sliding-window rate limiter keyed by principal and capability.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// Capability → calls allowed per minute
+    limits: HashMap<String, u32>,
+
+    /// (principal, capability) → times of the calls in the window
+    windows: HashMap<(String, String), VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow each principal `max_rpm` calls per minute to `capability`.
+    pub fn set_limit(&mut self, capability: &str, max_rpm: u32) {
+        self.limits.insert(capability.to_string(), max_rpm);
+    }
+
+    /// Remove the limit of `capability`.
+    pub fn remove_limit(&mut self, capability: &str) -> bool {
+        self.windows.retain(|(_, cap), _| cap != capability);
+        self.limits.remove(capability).is_some()
+    }
+
+    /// Calls per minute allowed to `capability`, if limited.
+    pub fn limit(&self, capability: &str) -> Option<u32> {
+        self.limits.get(capability).copied()
+    }
+
+    /// Count a call by `principal` to `capability` now, unless it exceeds
+    /// the limit.
+    pub fn check(&mut self, principal: &str, capability: &str) -> Result<(), RateLimitExceeded> {
+        self.check_at(principal, capability, Instant::now())
+    }
+
+    /// Count a call by `principal` to `capability` at `now`, unless it
+    /// exceeds the limit. Denied calls are not counted.
+    pub fn check_at(
+        &mut self,
+        principal: &str,
+        capability: &str,
+        now: Instant,
+    ) -> Result<(), RateLimitExceeded> {
+        let Some(&max_rpm) = self.limits.get(capability) else {
+            return Ok(());
+        };
+        let calls = self
+            .windows
+            .entry((principal.to_string(), capability.to_string()))
+            .or_default();
+        while calls
+            .front()
+            .is_some_and(|&t| now.saturating_duration_since(t) >= WINDOW)
+        {
+            calls.pop_front();
+        }
+        if calls.len() >= max_rpm as usize {
+            let retry_after = calls
+                .front()
+                .map_or(WINDOW, |&t| WINDOW - now.saturating_duration_since(t));
+            return Err(RateLimitExceeded {
+                max_rpm,
+                retry_after,
+            });
+        }
+        calls.push_back(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sliding_window_per_principal() {
+        let mut limiter = RateLimiter::new();
+        limiter.set_limit("search", 2);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("alice", "search", start).is_ok());
+        assert!(limiter
+            .check_at("alice", "search", start + Duration::from_secs(20))
+            .is_ok());
+        let exceeded = limiter
+            .check_at("alice", "search", start + Duration::from_secs(30))
+            .unwrap_err();
+        assert_eq!(exceeded.max_rpm, 2);
+        assert_eq!(exceeded.retry_after, Duration::from_secs(30));
+
+        // Other principals and capabilities have their own budgets.
+        assert!(limiter.check_at("bob", "search", start).is_ok());
+        assert!(limiter.check_at("alice", "email", start).is_ok());
+
+        // The first call leaves the window after a minute.
+        assert!(limiter
+            .check_at("alice", "search", start + Duration::from_secs(60))
+            .is_ok());
+        assert!(limiter
+            .check_at("alice", "search", start + Duration::from_secs(61))
+            .is_err());
+
+        assert!(limiter.remove_limit("search"));
+        assert!(limiter.check_at("alice", "search", start).is_ok());
+    }
+}
@@ -2,17 +2,140 @@
 //!
 //! Maps agents → roles → capabilities. Provides the bridge between
 //! agent card role assignments and capability RBAC requirements.
+//!
+//! Roles can inherit from other roles (`admin` → `operator` → `viewer`):
+//! an agent holds its assigned roles and every role they inherit, and may
+//! use the capabilities granted to any of them. Capability grants may be
+//! wildcards (`*`, `minecraft:*`).
+//!
+//! Role definitions and assignments serialize as [`RbacConfig`], so they
+//! can live in the agent card YAML:
+//!
+//! ```yaml
+//! rbac:
+//!   roles:
+//!     viewer:
+//!       capabilities: ["reports:read"]
+//!     operator:
+//!       inherits: [viewer]
+//!       capabilities: ["minecraft:*"]
+//!     admin:
+//!       inherits: [operator]
+//!       capabilities: ["*"]
+//!   agents:
+//!     agent-1: [operator]
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
 
-use std::collections::{HashMap, HashSet};
+use super::pattern_matches;
 
 /// RBAC manager: tracks role assignments and capability grants.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(from = "RbacConfig", into = "RbacConfig")]
 pub struct RbacManager {
     /// Agent → roles mapping
     agent_roles: HashMap<String, HashSet<String>>,
 
-    /// Role → capabilities mapping
+    /// Role → capabilities mapping (exact IDs or wildcard patterns)
     role_capabilities: HashMap<String, HashSet<String>>,
+
+    /// Role → roles it inherits from
+    role_parents: HashMap<String, HashSet<String>>,
+}
+
+/// Serialized form of an [`RbacManager`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RbacConfig {
+    /// Role definitions by name.
+    #[serde(default)]
+    pub roles: BTreeMap<String, RoleDefinition>,
+    /// Roles assigned to each agent.
+    #[serde(default)]
+    pub agents: BTreeMap<String, Vec<String>>,
+}
+
+/// A role's parents and capability grants.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoleDefinition {
+    /// Roles whose permissions this role inherits.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inherits: Vec<String>,
+    /// Capabilities granted to the role; `*` wildcards are allowed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<String>,
+}
+
+/// What an agent may do, with inheritance resolved.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct EffectivePermissions {
+    /// Assigned roles and every role they inherit.
+    pub roles: BTreeSet<String>,
+    /// Capability grants of those roles (exact IDs and wildcard patterns).
+    pub capabilities: BTreeSet<String>,
+}
+
+impl EffectivePermissions {
+    /// Whether the grants cover `capability_id`.
+    pub fn allows(&self, capability_id: &str) -> bool {
+        self.capabilities
+            .iter()
+            .any(|grant| pattern_matches(grant, capability_id))
+    }
+}
+
+impl From<RbacConfig> for RbacManager {
+    fn from(config: RbacConfig) -> Self {
+        let mut rbac = Self::new();
+        for (role, definition) in config.roles {
+            rbac.define_role(&role);
+            for parent in &definition.inherits {
+                rbac.add_role_parent(&role, parent);
+            }
+            for capability in &definition.capabilities {
+                rbac.grant_capability_to_role(&role, capability);
+            }
+        }
+        for (agent, roles) in config.agents {
+            for role in &roles {
+                rbac.assign_role(&agent, role);
+            }
+        }
+        rbac
+    }
+}
+
+impl From<RbacManager> for RbacConfig {
+    fn from(rbac: RbacManager) -> Self {
+        let sorted = |set: &HashSet<String>| {
+            let mut items: Vec<String> = set.iter().cloned().collect();
+            items.sort();
+            items
+        };
+        let roles = rbac
+            .all_roles()
+            .into_iter()
+            .map(|role| {
+                let definition = RoleDefinition {
+                    inherits: rbac.role_parents.get(role).map(sorted).unwrap_or_default(),
+                    capabilities: rbac
+                        .role_capabilities
+                        .get(role)
+                        .map(sorted)
+                        .unwrap_or_default(),
+                };
+                (role.to_string(), definition)
+            })
+            .collect();
+        let agents = rbac
+            .agent_roles
+            .iter()
+            .map(|(agent, roles)| (agent.clone(), sorted(roles)))
+            .collect();
+        Self { roles, agents }
+    }
 }
 
 impl RbacManager {
@@ -20,6 +143,47 @@ impl RbacManager {
         Self::default()
     }
 
+    /// Load role definitions and assignments from YAML (an [`RbacConfig`]).
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str::<RbacConfig>(yaml).map(Self::from)
+    }
+
+    /// Make `role` known without grants or parents.
+    pub fn define_role(&mut self, role: &str) {
+        self.role_capabilities.entry(role.to_string()).or_default();
+    }
+
+    /// Let `role` inherit the permissions of `parent`.
+    pub fn add_role_parent(&mut self, role: &str, parent: &str) {
+        self.define_role(parent);
+        self.role_parents
+            .entry(role.to_string())
+            .or_default()
+            .insert(parent.to_string());
+    }
+
+    /// Stop `role` inheriting from `parent`.
+    pub fn remove_role_parent(&mut self, role: &str, parent: &str) -> bool {
+        self.role_parents
+            .get_mut(role)
+            .is_some_and(|parents| parents.remove(parent))
+    }
+
+    /// `roles` and every role they inherit, transitively. Cycles in the
+    /// hierarchy are tolerated.
+    fn with_ancestors<'a>(&'a self, roles: impl IntoIterator<Item = &'a str>) -> BTreeSet<&'a str> {
+        let mut seen = BTreeSet::new();
+        let mut pending: Vec<&str> = roles.into_iter().collect();
+        while let Some(role) = pending.pop() {
+            if seen.insert(role) {
+                if let Some(parents) = self.role_parents.get(role) {
+                    pending.extend(parents.iter().map(String::as_str));
+                }
+            }
+        }
+        seen
+    }
+
     /// Assign a role to an agent.
     pub fn assign_role(&mut self, agent_id: &str, role: &str) {
         self.agent_roles
@@ -54,44 +218,58 @@ impl RbacManager {
         }
     }
 
-    /// Get all roles assigned to an agent.
-    pub fn get_agent_roles(&self, agent_id: &str) -> Vec<&str> {
+    /// Get the roles assigned to an agent directly.
+    pub fn direct_roles(&self, agent_id: &str) -> Vec<&str> {
         self.agent_roles
             .get(agent_id)
             .map(|roles| roles.iter().map(|r| r.as_str()).collect())
             .unwrap_or_default()
     }
 
-    /// Get all capabilities accessible to an agent (through its roles).
+    /// Get all roles of an agent: assigned ones and those they inherit.
+    pub fn get_agent_roles(&self, agent_id: &str) -> Vec<&str> {
+        self.with_ancestors(self.direct_roles(agent_id))
+            .into_iter()
+            .collect()
+    }
+
+    /// Get all capability grants of an agent (through its roles, including
+    /// inherited ones). Grants may be wildcard patterns.
     pub fn get_agent_capabilities(&self, agent_id: &str) -> HashSet<&str> {
-        let roles = match self.agent_roles.get(agent_id) {
-            Some(r) => r,
-            None => return HashSet::new(),
-        };
+        self.get_agent_roles(agent_id)
+            .into_iter()
+            .filter_map(|role| self.role_capabilities.get(role))
+            .flatten()
+            .map(String::as_str)
+            .collect()
+    }
 
-        let mut caps = HashSet::new();
-        for role in roles {
-            if let Some(role_caps) = self.role_capabilities.get(role) {
-                for cap in role_caps {
-                    caps.insert(cap.as_str());
-                }
-            }
+    /// Roles and capability grants of an agent, with inheritance resolved.
+    pub fn effective_permissions(&self, agent_id: &str) -> EffectivePermissions {
+        EffectivePermissions {
+            roles: self
+                .get_agent_roles(agent_id)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            capabilities: self
+                .get_agent_capabilities(agent_id)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
         }
-        caps
     }
 
     /// Check if an agent can use a specific capability.
     pub fn can_use_capability(&self, agent_id: &str, capability_id: &str) -> bool {
-        let caps = self.get_agent_capabilities(agent_id);
-        caps.contains(capability_id)
+        self.get_agent_capabilities(agent_id)
+            .into_iter()
+            .any(|grant| pattern_matches(grant, capability_id))
     }
 
-    /// Check if an agent has a specific role.
+    /// Check if an agent has a specific role, directly or by inheritance.
     pub fn has_role(&self, agent_id: &str, role: &str) -> bool {
-        self.agent_roles
-            .get(agent_id)
-            .map(|roles| roles.contains(role))
-            .unwrap_or(false)
+        self.get_agent_roles(agent_id).contains(&role)
     }
 
     /// List all known roles.
@@ -105,15 +283,19 @@ impl RbacManager {
         for role in self.role_capabilities.keys() {
             roles.insert(role.as_str());
         }
+        for (role, parents) in &self.role_parents {
+            roles.insert(role.as_str());
+            roles.extend(parents.iter().map(String::as_str));
+        }
         roles.into_iter().collect()
     }
 
-    /// List all agents with a specific role.
+    /// List all agents with a specific role, directly or by inheritance.
     pub fn agents_with_role(&self, role: &str) -> Vec<&str> {
         self.agent_roles
-            .iter()
-            .filter(|(_, roles)| roles.contains(role))
-            .map(|(agent, _)| agent.as_str())
+            .keys()
+            .filter(|agent| self.has_role(agent, role))
+            .map(|agent| agent.as_str())
             .collect()
     }
 
@@ -197,4 +379,55 @@ mod tests {
         assert!(researchers.contains(&"alpha"));
         assert!(researchers.contains(&"beta"));
     }
+
+    #[test]
+    fn test_role_hierarchy_wildcards_and_yaml() {
+        let rbac = RbacManager::from_yaml(
+            r#"
+roles:
+  viewer:
+    capabilities: ["reports:read"]
+  operator:
+    inherits: [viewer]
+    capabilities: ["minecraft:*"]
+  admin:
+    inherits: [operator]
+    capabilities: ["*"]
+  # A cycle does not loop forever.
+  auditor:
+    inherits: [viewer, auditor]
+agents:
+  ops-1: [operator]
+  root: [admin]
+  audit: [auditor]
+"#,
+        )
+        .unwrap();
+
+        assert!(rbac.has_role("ops-1", "viewer"));
+        assert!(!rbac.has_role("ops-1", "admin"));
+        assert_eq!(rbac.direct_roles("ops-1"), vec!["operator"]);
+        assert!(rbac.can_use_capability("ops-1", "reports:read"));
+        assert!(rbac.can_use_capability("ops-1", "minecraft:server_control"));
+        assert!(!rbac.can_use_capability("ops-1", "billing:refund"));
+        assert!(rbac.can_use_capability("root", "billing:refund"));
+        assert!(rbac.can_use_capability("audit", "reports:read"));
+        let mut viewers = rbac.agents_with_role("viewer");
+        viewers.sort();
+        assert_eq!(viewers, vec!["audit", "ops-1", "root"]);
+
+        let permissions = rbac.effective_permissions("ops-1");
+        assert_eq!(
+            permissions.roles.into_iter().collect::<Vec<_>>(),
+            vec!["operator", "viewer"]
+        );
+        assert_eq!(
+            permissions.capabilities.into_iter().collect::<Vec<_>>(),
+            vec!["minecraft:*", "reports:read"]
+        );
+
+        let yaml = serde_yaml::to_string(&rbac).unwrap();
+        let restored: RbacManager = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(RbacConfig::from(restored), RbacConfig::from(rbac));
+    }
 }
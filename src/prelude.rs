@@ -0,0 +1,29 @@
+//! Commonly used types and helpers, importable in one line.
+//!
+//! ```no_run
+//! use crewai::prelude::*;
+//!
+//! let output = run_single_agent(
+//!     "Researcher",
+//!     "Summarize recent work",
+//!     "Summarize the state of Rust async runtimes in three bullets.",
+//!     "openai/gpt-4o-mini",
+//! )
+//! .unwrap();
+//! println!("{}", output.raw);
+//! ```
+
+pub use crate::agent::Agent;
+pub use crate::crew::Crew;
+pub use crate::crews::crew_output::CrewOutput;
+pub use crate::events::{BaseEvent, CREWAI_EVENT_BUS};
+pub use crate::flow::Flow;
+pub use crate::knowledge::Knowledge;
+pub use crate::llm::LLM;
+pub use crate::llms::base_llm::BaseLLM;
+pub use crate::process::Process;
+pub use crate::quickstart::{quick_crew, run_single_agent, single_agent_crew, QuickCrew};
+pub use crate::task::Task;
+pub use crate::tasks::llm_guardrail::LLMGuardrail;
+pub use crate::tasks::task_output::TaskOutput;
+pub use crate::tools::{BaseTool, Tool};
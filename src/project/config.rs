@@ -0,0 +1,647 @@
+//! YAML-configured crews.
+//!
+//! Corresponds to the `agents_config` / `tasks_config` loading of the
+//! Python `CrewBase`. `agents.yaml` maps agent names to their settings and
+//! `tasks.yaml` maps task names to theirs; a task names its agent, the
+//! tasks whose output it uses (`context`) and its tools by name:
+//!
+//! ```yaml
+//! # agents.yaml
+//! researcher:
+//!   role: Senior Researcher
+//!   goal: Uncover developments in {topic}
+//!   backstory: A seasoned researcher.
+//!   tools: [search]
+//!
+//! # tasks.yaml
+//! research_task:
+//!   description: Research {topic}.
+//!   expected_output: Ten bullet points.
+//!   agent: researcher
+//! ```
+//!
+//! With the `scripting` feature, a task's `guardrail` may be a
+//! [`ScriptConfig`] instead of a description, and its `condition` a script
+//! deciding from the previous task's output whether the task runs:
+//!
+//! ```yaml
+//! summary_task:
+//!   description: Summarize the research.
+//!   expected_output: One paragraph.
+//!   agent: researcher
+//!   condition:
+//!     script: output.len() > 200
+//!   guardrail:
+//!     script: |
+//!       if output.len() > 1000 { "Keep it under 1000 characters" } else { true }
+//! ```
+//!
+//! Entries keep their file order, which is the order tasks run in.
+//! `{placeholders}` are filled from the kickoff inputs, or up front by
+//! [`CrewConfig::build_crew`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::agent::Agent;
+use crate::crew::Crew;
+#[cfg(feature = "scripting")]
+use crate::scripting::ScriptConfig;
+use crate::task::Task;
+use crate::tools::registry::ToolRegistry;
+
+/// Errors raised while loading or validating a crew configuration.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// A config file could not be read.
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// A config file is not a YAML mapping of names to entries.
+    #[error("invalid YAML in {file}: {source}")]
+    Yaml {
+        file: String,
+        #[source]
+        source: serde_yaml::Error,
+    },
+    /// An entry has missing, unknown or mistyped fields.
+    #[error("invalid entry '{name}' in {file}: {message}")]
+    InvalidEntry {
+        file: String,
+        name: String,
+        message: String,
+    },
+    /// A task names an agent that is not defined.
+    #[error("task '{task}' references unknown agent '{agent}' (defined agents: {})", available.join(", "))]
+    UnknownAgent {
+        task: String,
+        agent: String,
+        available: Vec<String>,
+    },
+    /// A task's context names a task that is not defined.
+    #[error("task '{task}' has unknown task '{context}' in its context")]
+    UnknownContext { task: String, context: String },
+    /// An agent or task uses a tool that is not registered.
+    #[error("'{owner}' uses unknown tool '{tool}' (registered tools: {})", available.join(", "))]
+    UnknownTool {
+        owner: String,
+        tool: String,
+        available: Vec<String>,
+    },
+    /// A placeholder has no matching input.
+    #[error("cannot interpolate '{name}': {message}")]
+    Interpolation { name: String, message: String },
+    /// A task's guardrail or condition script does not compile.
+    #[error("task '{task}' has an invalid {field} script: {message}")]
+    InvalidScript {
+        task: String,
+        field: &'static str,
+        message: String,
+    },
+}
+
+/// An entry of `agents.yaml`.
+///
+/// Unset options keep the [`Agent`] defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentConfig {
+    /// The agent's role; also the name tasks are assigned by at runtime.
+    pub role: String,
+    /// The agent's goal.
+    pub goal: String,
+    /// The agent's backstory.
+    pub backstory: String,
+    /// Model identifier (e.g. `"gpt-4o-mini"`).
+    #[serde(default)]
+    pub llm: Option<String>,
+    /// Model used for tool calling.
+    #[serde(default)]
+    pub function_calling_llm: Option<String>,
+    /// Names of registered tools the agent may use.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    #[serde(default)]
+    pub allow_delegation: Option<bool>,
+    #[serde(default)]
+    pub verbose: Option<bool>,
+    #[serde(default)]
+    pub cache: Option<bool>,
+    #[serde(default)]
+    pub max_iter: Option<i32>,
+    #[serde(default)]
+    pub max_rpm: Option<i32>,
+    #[serde(default)]
+    pub max_execution_time: Option<i64>,
+    #[serde(default)]
+    pub multimodal: Option<bool>,
+    #[serde(default)]
+    pub reasoning: Option<bool>,
+    #[serde(default)]
+    pub allow_code_execution: Option<bool>,
+    #[serde(default)]
+    pub respect_context_window: Option<bool>,
+    #[serde(default)]
+    pub inject_date: Option<bool>,
+    #[serde(default)]
+    pub system_template: Option<String>,
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    #[serde(default)]
+    pub response_template: Option<String>,
+}
+
+/// An entry of `tasks.yaml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskConfig {
+    /// What the task asks for.
+    pub description: String,
+    /// What a complete answer looks like.
+    pub expected_output: String,
+    /// Name of the agent (its key in `agents.yaml`) running the task.
+    #[serde(default)]
+    pub agent: Option<String>,
+    /// Names of tasks whose output this task uses.
+    #[serde(default)]
+    pub context: Vec<String>,
+    /// Names of registered tools for this task.
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// File the output is written to.
+    #[serde(default)]
+    pub output_file: Option<String>,
+    #[serde(default)]
+    pub create_directory: Option<bool>,
+    #[serde(default)]
+    pub async_execution: Option<bool>,
+    #[serde(default)]
+    pub human_input: Option<bool>,
+    #[serde(default)]
+    pub markdown: Option<bool>,
+    #[serde(default)]
+    pub output_json: Option<String>,
+    /// A guardrail description or, with the `scripting` feature, a script
+    /// checking the output.
+    #[serde(default)]
+    pub guardrail: Option<GuardrailConfig>,
+    /// Script deciding from the previous task's output whether the task
+    /// runs.
+    #[cfg(feature = "scripting")]
+    #[serde(default)]
+    pub condition: Option<ScriptConfig>,
+}
+
+/// The `guardrail` of a `tasks.yaml` entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GuardrailConfig {
+    /// What the output must satisfy, in words.
+    Description(String),
+    /// A script run over the task output.
+    #[cfg(feature = "scripting")]
+    Script(ScriptConfig),
+}
+
+/// Agents and tasks loaded from `agents.yaml` and `tasks.yaml`, in file
+/// order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CrewConfig {
+    /// Agents by name.
+    pub agents: Vec<(String, AgentConfig)>,
+    /// Tasks by name.
+    pub tasks: Vec<(String, TaskConfig)>,
+}
+
+impl CrewConfig {
+    /// Parse the contents of `agents.yaml` and `tasks.yaml`.
+    pub fn from_yaml(agents_yaml: &str, tasks_yaml: &str) -> Result<Self, ConfigError> {
+        Ok(Self {
+            agents: entries("agents.yaml", agents_yaml)?,
+            tasks: entries("tasks.yaml", tasks_yaml)?,
+        })
+    }
+
+    /// Read and parse `agents.yaml` and `tasks.yaml` from the given paths.
+    pub fn load(
+        agents_path: impl AsRef<Path>,
+        tasks_path: impl AsRef<Path>,
+    ) -> Result<Self, ConfigError> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        };
+        let (agents_path, tasks_path) = (agents_path.as_ref(), tasks_path.as_ref());
+        Ok(Self {
+            agents: entries(&agents_path.display().to_string(), &read(agents_path)?)?,
+            tasks: entries(&tasks_path.display().to_string(), &read(tasks_path)?)?,
+        })
+    }
+
+    /// The agent named `name`.
+    pub fn agent(&self, name: &str) -> Option<&AgentConfig> {
+        self.agents.iter().find(|(n, _)| n == name).map(|(_, a)| a)
+    }
+
+    /// The task named `name`.
+    pub fn task(&self, name: &str) -> Option<&TaskConfig> {
+        self.tasks.iter().find(|(n, _)| n == name).map(|(_, t)| t)
+    }
+
+    /// Check that tasks reference defined agents and tasks, and that every
+    /// tool is registered in `registry`.
+    pub fn validate(&self, registry: &ToolRegistry) -> Result<(), ConfigError> {
+        let unknown_tool = |owner: &str, tools: &[String]| {
+            tools
+                .iter()
+                .find(|tool| !registry.contains(tool))
+                .map(|tool| ConfigError::UnknownTool {
+                    owner: owner.to_string(),
+                    tool: tool.clone(),
+                    available: registry.names(),
+                })
+        };
+        for (name, agent) in &self.agents {
+            if let Some(e) = unknown_tool(name, &agent.tools) {
+                return Err(e);
+            }
+        }
+        for (name, task) in &self.tasks {
+            if let Some(agent) = task.agent.as_deref() {
+                if self.agent(agent).is_none() {
+                    return Err(ConfigError::UnknownAgent {
+                        task: name.clone(),
+                        agent: agent.to_string(),
+                        available: self.agents.iter().map(|(n, _)| n.clone()).collect(),
+                    });
+                }
+            }
+            if let Some(context) = task.context.iter().find(|c| self.task(c).is_none()) {
+                return Err(ConfigError::UnknownContext {
+                    task: name.clone(),
+                    context: context.clone(),
+                });
+            }
+            if let Some(e) = unknown_tool(name, &task.tools) {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a crew using tools from the global [`ToolRegistry`].
+    ///
+    /// With `inputs`, placeholders are filled now, so a missing input is
+    /// reported before anything runs; a later kickoff can still
+    /// re-interpolate from the original templates.
+    pub fn build_crew(
+        &self,
+        inputs: Option<&HashMap<String, String>>,
+    ) -> Result<Crew, ConfigError> {
+        self.validate(ToolRegistry::global())?;
+        self.assemble(inputs, None)
+    }
+
+    /// Build a crew whose agents resolve tools from `registry`.
+    pub fn build_crew_with_tools(
+        &self,
+        inputs: Option<&HashMap<String, String>>,
+        registry: &ToolRegistry,
+    ) -> Result<Crew, ConfigError> {
+        self.validate(registry)?;
+        self.assemble(inputs, Some(registry))
+    }
+
+    fn assemble(
+        &self,
+        inputs: Option<&HashMap<String, String>>,
+        registry: Option<&ToolRegistry>,
+    ) -> Result<Crew, ConfigError> {
+        let interpolation = |name: &str, message: String| ConfigError::Interpolation {
+            name: name.to_string(),
+            message,
+        };
+
+        let mut agents = Vec::with_capacity(self.agents.len());
+        for (name, config) in &self.agents {
+            let mut agent = build_agent(config);
+            agent.tool_registry = registry.cloned();
+            if let Some(inputs) = inputs {
+                agent
+                    .interpolate_inputs(inputs)
+                    .map_err(|e| interpolation(name, e))?;
+            }
+            agents.push(agent);
+        }
+
+        let mut tasks: Vec<Task> = Vec::with_capacity(self.tasks.len());
+        for (name, config) in &self.tasks {
+            let mut task = build_task(name, config);
+            #[cfg(feature = "scripting")]
+            compile_scripts(name, config, &mut task)?;
+            // Crews assign tasks by the agent's role as written, before
+            // interpolation.
+            task.agent = config
+                .agent
+                .as_deref()
+                .and_then(|agent| self.agent(agent))
+                .map(|agent| agent.role.trim().to_string());
+            if let Some(inputs) = inputs {
+                task.interpolate_inputs(inputs)
+                    .map_err(|e| interpolation(name, e))?;
+            }
+            tasks.push(task);
+        }
+        let ids: HashMap<&str, uuid::Uuid> = self
+            .tasks
+            .iter()
+            .zip(&tasks)
+            .map(|((name, _), task)| (name.as_str(), task.id))
+            .collect();
+        for ((_, config), task) in self.tasks.iter().zip(&mut tasks) {
+            if !config.context.is_empty() {
+                task.context = Some(config.context.iter().map(|c| ids[c.as_str()]).collect());
+            }
+        }
+
+        Ok(Crew::with_agents(tasks, agents))
+    }
+}
+
+/// An [`Agent`] from its config, without interpolation.
+pub fn build_agent(config: &AgentConfig) -> Agent {
+    let mut agent = Agent::new(
+        config.role.trim().to_string(),
+        config.goal.trim().to_string(),
+        config.backstory.trim().to_string(),
+    );
+    agent.llm = config.llm.clone();
+    agent.function_calling_llm = config.function_calling_llm.clone();
+    agent.tools = config.tools.clone();
+    agent.max_rpm = config.max_rpm;
+    agent.max_execution_time = config.max_execution_time;
+    agent.system_template = config.system_template.clone();
+    agent.prompt_template = config.prompt_template.clone();
+    agent.response_template = config.response_template.clone();
+    let flags = [
+        (&mut agent.allow_delegation, config.allow_delegation),
+        (&mut agent.verbose, config.verbose),
+        (&mut agent.cache, config.cache),
+        (&mut agent.multimodal, config.multimodal),
+        (&mut agent.reasoning, config.reasoning),
+        (&mut agent.allow_code_execution, config.allow_code_execution),
+        (
+            &mut agent.respect_context_window,
+            config.respect_context_window,
+        ),
+        (&mut agent.inject_date, config.inject_date),
+    ];
+    for (flag, value) in flags {
+        if let Some(value) = value {
+            *flag = value;
+        }
+    }
+    if let Some(max_iter) = config.max_iter {
+        agent.max_iter = max_iter;
+    }
+    agent.refresh_fingerprint();
+    agent
+}
+
+/// A [`Task`] named `name` from its config, without its agent, context or
+/// interpolation.
+pub fn build_task(name: &str, config: &TaskConfig) -> Task {
+    let mut task = Task::new(
+        config.description.trim().to_string(),
+        config.expected_output.trim().to_string(),
+    );
+    task.name = Some(name.to_string());
+    task.tools = config.tools.clone();
+    task.output_file = config.output_file.clone();
+    task.output_json = config.output_json.clone();
+    if let Some(GuardrailConfig::Description(guardrail)) = &config.guardrail {
+        task.guardrail = Some(guardrail.clone());
+    }
+    let flags = [
+        (&mut task.create_directory, config.create_directory),
+        (&mut task.async_execution, config.async_execution),
+        (&mut task.human_input, config.human_input),
+        (&mut task.markdown, config.markdown),
+    ];
+    for (flag, value) in flags {
+        if let Some(value) = value {
+            *flag = value;
+        }
+    }
+    task.refresh_fingerprint();
+    task
+}
+
+/// Compile the guardrail and condition scripts of task `name` onto `task`.
+#[cfg(feature = "scripting")]
+fn compile_scripts(name: &str, config: &TaskConfig, task: &mut Task) -> Result<(), ConfigError> {
+    let invalid = |field, e: crate::scripting::ScriptError| ConfigError::InvalidScript {
+        task: name.to_string(),
+        field,
+        message: e.to_string(),
+    };
+    if let Some(GuardrailConfig::Script(script)) = &config.guardrail {
+        task.guardrail_fn = Some(script.guardrail().map_err(|e| invalid("guardrail", e))?);
+    }
+    if let Some(script) = &config.condition {
+        task.condition = Some(script.condition().map_err(|e| invalid("condition", e))?);
+    }
+    Ok(())
+}
+
+/// The entries of a YAML mapping of names to `T`, in file order.
+pub(crate) fn entries<T: DeserializeOwned>(
+    file: &str,
+    yaml: &str,
+) -> Result<Vec<(String, T)>, ConfigError> {
+    let yaml_error = |source| ConfigError::Yaml {
+        file: file.to_string(),
+        source,
+    };
+    let mapping: Option<serde_yaml::Mapping> = serde_yaml::from_str(yaml).map_err(yaml_error)?;
+    mapping
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(key, value)| {
+            let name = match key {
+                serde_yaml::Value::String(name) => name,
+                other => serde_yaml::to_string(&other)
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            };
+            let entry = serde_yaml::from_value(value).map_err(|e| ConfigError::InvalidEntry {
+                file: file.to_string(),
+                name: name.clone(),
+                message: e.to_string(),
+            })?;
+            Ok((name, entry))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGENTS: &str = r#"
+researcher:
+  role: >
+    Senior Researcher
+  goal: Uncover developments in {topic}
+  backstory: A seasoned researcher.
+  tools: [search]
+  max_iter: 5
+writer:
+  role: Writer
+  goal: Write about {topic}
+  backstory: A clear writer.
+"#;
+
+    const TASKS: &str = r#"
+research_task:
+  description: Research {topic}.
+  expected_output: Ten bullet points.
+  agent: researcher
+report_task:
+  description: Write a report. Use {{topic}} as a heading placeholder.
+  expected_output: A report.
+  agent: writer
+  context: [research_task]
+  output_file: "{topic}.md"
+"#;
+
+    fn registry() -> ToolRegistry {
+        let registry = ToolRegistry::new();
+        registry.register(crate::tools::Tool::new(
+            "search",
+            "Search the web",
+            std::sync::Arc::new(|_| Ok(serde_json::Value::Null)),
+        ));
+        registry
+    }
+
+    #[test]
+    fn test_build_crew_from_yaml() {
+        let config = CrewConfig::from_yaml(AGENTS, TASKS).unwrap();
+        let names: Vec<&str> = config.tasks.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["research_task", "report_task"]);
+
+        let inputs = HashMap::from([("topic".to_string(), "bees".to_string())]);
+        let crew = config
+            .build_crew_with_tools(Some(&inputs), &registry())
+            .unwrap();
+        assert_eq!(crew.agents, ["Senior Researcher", "Writer"]);
+        let researcher = crew.get_agent("Senior Researcher").unwrap();
+        let researcher = researcher.read().unwrap();
+        assert_eq!(researcher.goal, "Uncover developments in bees");
+        assert_eq!(researcher.max_iter, 5);
+        assert_eq!(researcher.tools, ["search"]);
+
+        let report = &crew.tasks[1];
+        assert_eq!(report.agent.as_deref(), Some("Writer"));
+        assert_eq!(
+            report.description,
+            "Write a report. Use {topic} as a heading placeholder."
+        );
+        assert_eq!(report.output_file.as_deref(), Some("bees.md"));
+        assert_eq!(report.context, Some(vec![crew.tasks[0].id]));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let config = CrewConfig::from_yaml(AGENTS, TASKS).unwrap();
+        let err = config.build_crew_with_tools(None, &ToolRegistry::new());
+        assert!(matches!(err, Err(ConfigError::UnknownTool { ref tool, .. }) if tool == "search"));
+
+        let inputs = HashMap::from([("subject".to_string(), "bees".to_string())]);
+        let err = config
+            .build_crew_with_tools(Some(&inputs), &registry())
+            .unwrap_err();
+        assert!(err.to_string().contains("'topic' not found"), "{}", err);
+
+        let tasks = "t:\n  description: d\n  expected_output: e\n  agent: editor\n";
+        let err = CrewConfig::from_yaml(AGENTS, tasks)
+            .unwrap()
+            .validate(&registry())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "task 't' references unknown agent 'editor' (defined agents: researcher, writer)"
+        );
+
+        let err = CrewConfig::from_yaml("a:\n  rol: x\n", "").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidEntry { ref name, .. } if name == "a"));
+        assert!(err.to_string().contains("unknown field `rol`"), "{}", err);
+    }
+
+    #[cfg(feature = "scripting")]
+    #[test]
+    fn test_script_guardrail_and_condition() {
+        use crate::tasks::output_format::OutputFormat;
+        use crate::tasks::task_output::TaskOutput;
+
+        let tasks = r#"
+check_task:
+  description: Check the facts.
+  expected_output: A verdict.
+  agent: writer
+  condition:
+    script: output.contains("bees")
+  guardrail:
+    script: |
+      if output.len() > 10 { "Keep it short" } else { true }
+    timeout_ms: 500
+"#;
+        let config = CrewConfig::from_yaml(AGENTS, tasks).unwrap();
+        let (_, check) = &config.tasks[0];
+        assert!(matches!(check.guardrail, Some(GuardrailConfig::Script(_))));
+        let crew = config.build_crew_with_tools(None, &registry()).unwrap();
+        let task = &crew.tasks[0];
+        assert!(task.guardrail.is_none());
+
+        let output =
+            |raw: &str| TaskOutput::new("d".into(), "Writer".into(), raw.into(), OutputFormat::Raw);
+        let guardrail = task.guardrail_fn.as_ref().unwrap();
+        assert_eq!(guardrail(&output("fine")), (true, "fine".to_string()));
+        assert_eq!(
+            guardrail(&output("far too long")),
+            (false, "Keep it short".to_string())
+        );
+        let condition = task.condition.as_ref().unwrap();
+        assert!(condition(&output("about bees")));
+        assert!(!condition(&output("about ants")));
+
+        let tasks =
+            "t:\n  description: d\n  expected_output: e\n  guardrail:\n    script: 'if ('\n";
+        let err = CrewConfig::from_yaml(AGENTS, tasks)
+            .unwrap()
+            .build_crew_with_tools(None, &registry())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ConfigError::InvalidScript {
+                    field: "guardrail",
+                    ..
+                }
+            ),
+            "{}",
+            err
+        );
+    }
+}
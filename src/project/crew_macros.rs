@@ -0,0 +1,317 @@
+//! Macro for assembling a crew from annotated methods.
+//!
+//! Python's `@CrewBase` class decorator collects the methods marked
+//! `@agent`, `@task`, `@before_kickoff` and `@after_kickoff` and builds the
+//! crew from them. [`crew!`](crate::crew) does the same for an `impl`
+//! block: methods carry the markers as attributes, and the macro adds
+//! `agents()`, `tasks()`, `crew_metadata()` and `build_crew()` to the type.
+
+/// Assemble a crew from the annotated methods of an `impl` block.
+///
+/// Methods marked `#[agent]` return an [`Agent`](crate::agent::Agent) and
+/// `#[task]` a [`Task`](crate::task::Task); both are collected in
+/// declaration order. `#[before_kickoff]` methods take and return the
+/// kickoff inputs, `#[after_kickoff]` methods the
+/// [`CrewOutput`](crate::crews::crew_output::CrewOutput). One method may be
+/// marked `#[crew]` to build the crew itself (typically from
+/// `self.agents()` and `self.tasks()`); without it, `build_crew()` makes a
+/// sequential crew of all agents and tasks. Unmarked methods are kept
+/// as they are.
+///
+/// `build_crew()` registers the kickoff hooks on the crew. Hooks keep a
+/// clone of the value, so types with hooks must implement `Clone`.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use crewai::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct ResearchCrew {
+///     model: String,
+/// }
+///
+/// crewai::crew! {
+///     impl ResearchCrew {
+///         /// Finds the facts.
+///         #[agent]
+///         fn researcher(&self) -> Agent {
+///             let mut agent = Agent::new(
+///                 "Researcher".into(),
+///                 "Find facts about {topic}".into(),
+///                 "Thorough".into(),
+///             );
+///             agent.llm = Some(self.model.clone());
+///             agent
+///         }
+///
+///         #[task]
+///         fn research_task(&self) -> Task {
+///             let mut task = Task::new("Research {topic}".into(), "Ten facts".into());
+///             task.agent = Some("Researcher".into());
+///             task
+///         }
+///
+///         #[before_kickoff]
+///         fn default_topic(
+///             &self,
+///             inputs: Option<HashMap<String, String>>,
+///         ) -> Option<HashMap<String, String>> {
+///             let mut inputs = inputs.unwrap_or_default();
+///             inputs.entry("topic".into()).or_insert_with(|| "Rust".into());
+///             Some(inputs)
+///         }
+///     }
+/// }
+///
+/// let crews = ResearchCrew { model: "gpt-4o-mini".into() };
+/// assert_eq!(ResearchCrew::crew_metadata().tasks, ["research_task"]);
+/// let crew = crews.build_crew();
+/// assert_eq!(crew.agents, ["Researcher"]);
+/// assert_eq!(crew.before_kickoff_callbacks.len(), 1);
+/// ```
+#[macro_export]
+macro_rules! crew {
+    (
+        $(#[$meta:meta])*
+        impl $ty:ident { $($items:tt)* }
+    ) => {
+        $crate::__crew_items! {
+            @ty [$ty] [$(#[$meta])*]
+            @methods []
+            @agents [] @tasks [] @before [] @after [] @crew []
+            @rest $($items)*
+        }
+    };
+}
+
+/// Sorts the methods of a [`crew!`](crate::crew) block by their markers.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __crew_items {
+    // `#[agent]`
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents [$($agents:ident)*] @tasks $tasks:tt @before $before:tt @after $after:tt @crew $crew:tt
+        @rest $(#[doc = $doc:literal])* #[agent] $(#[$m:meta])*
+        $vis:vis fn $name:ident ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[doc = $doc])* $(#[$m])* $vis fn $name($($args)*) -> $ret $body]
+            @agents [$($agents)* $name] @tasks $tasks @before $before @after $after @crew $crew
+            @rest $($rest)*
+        }
+    };
+    // `#[task]`
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents $agents:tt @tasks [$($tasks:ident)*] @before $before:tt @after $after:tt @crew $crew:tt
+        @rest $(#[doc = $doc:literal])* #[task] $(#[$m:meta])*
+        $vis:vis fn $name:ident ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[doc = $doc])* $(#[$m])* $vis fn $name($($args)*) -> $ret $body]
+            @agents $agents @tasks [$($tasks)* $name] @before $before @after $after @crew $crew
+            @rest $($rest)*
+        }
+    };
+    // `#[before_kickoff]`
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents $agents:tt @tasks $tasks:tt @before [$($before:ident)*] @after $after:tt @crew $crew:tt
+        @rest $(#[doc = $doc:literal])* #[before_kickoff] $(#[$m:meta])*
+        $vis:vis fn $name:ident ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[doc = $doc])* $(#[$m])* $vis fn $name($($args)*) -> $ret $body]
+            @agents $agents @tasks $tasks @before [$($before)* $name] @after $after @crew $crew
+            @rest $($rest)*
+        }
+    };
+    // `#[after_kickoff]`
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents $agents:tt @tasks $tasks:tt @before $before:tt @after [$($after:ident)*] @crew $crew:tt
+        @rest $(#[doc = $doc:literal])* #[after_kickoff] $(#[$m:meta])*
+        $vis:vis fn $name:ident ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[doc = $doc])* $(#[$m])* $vis fn $name($($args)*) -> $ret $body]
+            @agents $agents @tasks $tasks @before $before @after [$($after)* $name] @crew $crew
+            @rest $($rest)*
+        }
+    };
+    // `#[crew]`
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents $agents:tt @tasks $tasks:tt @before $before:tt @after $after:tt @crew []
+        @rest $(#[doc = $doc:literal])* #[crew] $(#[$m:meta])*
+        $vis:vis fn $name:ident ($($args:tt)*) -> $ret:ty $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[doc = $doc])* $(#[$m])* $vis fn $name($($args)*) -> $ret $body]
+            @agents $agents @tasks $tasks @before $before @after $after @crew [$name]
+            @rest $($rest)*
+        }
+    };
+    (
+        @ty $ty:tt $meta:tt @methods $methods:tt
+        @agents $agents:tt @tasks $tasks:tt @before $before:tt @after $after:tt @crew [$crew:ident]
+        @rest $(#[doc = $doc:literal])* #[crew] $($rest:tt)*
+    ) => {
+        compile_error!(concat!("crew! allows one #[crew] method; `", stringify!($crew), "` is already one"));
+    };
+    // Any other method.
+    (
+        @ty $ty:tt $meta:tt @methods [$($methods:tt)*]
+        @agents $agents:tt @tasks $tasks:tt @before $before:tt @after $after:tt @crew $crew:tt
+        @rest $(#[$m:meta])* $vis:vis fn $name:ident ($($args:tt)*) $(-> $ret:ty)? $body:block
+        $($rest:tt)*
+    ) => {
+        $crate::__crew_items! {
+            @ty $ty $meta
+            @methods [$($methods)* $(#[$m])* $vis fn $name($($args)*) $(-> $ret)? $body]
+            @agents $agents @tasks $tasks @before $before @after $after @crew $crew
+            @rest $($rest)*
+        }
+    };
+    // All methods sorted.
+    (
+        @ty [$ty:ident] [$($meta:tt)*] @methods [$($methods:tt)*]
+        @agents [$($agent:ident)*] @tasks [$($task:ident)*]
+        @before [$($before:ident)*] @after [$($after:ident)*] @crew [$($crew:ident)?]
+        @rest
+    ) => {
+        $($meta)*
+        impl $ty {
+            $($methods)*
+
+            /// Agents of the `#[agent]` methods, in declaration order.
+            pub fn agents(&self) -> Vec<$crate::agent::Agent> {
+                vec![$(self.$agent()),*]
+            }
+
+            /// Tasks of the `#[task]` methods, in declaration order.
+            pub fn tasks(&self) -> Vec<$crate::task::Task> {
+                vec![$(self.$task()),*]
+            }
+
+            /// Names of the annotated methods.
+            pub fn crew_metadata() -> $crate::project::CrewMetadata {
+                $crate::project::CrewMetadata {
+                    agents: vec![$(stringify!($agent).to_string()),*],
+                    tasks: vec![$(stringify!($task).to_string()),*],
+                    before_kickoff: vec![$(stringify!($before).to_string()),*],
+                    after_kickoff: vec![$(stringify!($after).to_string()),*],
+                    ..Default::default()
+                }
+            }
+
+            /// Build the crew, from the `#[crew]` method if there is one,
+            /// and register the kickoff hooks on it.
+            pub fn build_crew(&self) -> $crate::crew::Crew {
+                let mut crew = $crate::__crew_items!(@build self $($crew)?);
+                $(
+                    let this = self.clone();
+                    crew.before_kickoff_callbacks
+                        .push(Box::new(move |inputs| this.$before(inputs)));
+                )*
+                $(
+                    let this = self.clone();
+                    crew.after_kickoff_callbacks
+                        .push(Box::new(move |output| this.$after(output)));
+                )*
+                crew
+            }
+        }
+    };
+    (@build $self:ident $crew:ident) => {
+        $self.$crew()
+    };
+    (@build $self:ident) => {
+        $crate::crew::Crew::with_agents($self.tasks(), $self.agents())
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::agent::Agent;
+    use crate::crew::Crew;
+    use crate::crews::crew_output::CrewOutput;
+    use crate::process::Process;
+    use crate::task::Task;
+
+    #[derive(Clone)]
+    struct ReviewCrew {
+        suffix: String,
+    }
+
+    crate::crew! {
+        impl ReviewCrew {
+            #[agent]
+            fn reviewer(&self) -> Agent {
+                Agent::new("Reviewer".into(), "Review".into(), "Strict".into())
+            }
+
+            #[agent]
+            fn editor(&self) -> Agent {
+                Agent::new("Editor".into(), "Edit".into(), "Precise".into())
+            }
+
+            #[task]
+            fn review_task(&self) -> Task {
+                let mut task = Task::new("Review the draft".into(), "Notes".into());
+                task.agent = Some("Reviewer".into());
+                task
+            }
+
+            #[crew]
+            fn crew(&self) -> Crew {
+                let mut crew = Crew::with_agents(self.tasks(), self.agents());
+                crew.process = Process::Hierarchical;
+                crew
+            }
+
+            #[after_kickoff]
+            fn sign(&self, mut output: CrewOutput) -> CrewOutput {
+                output.raw.push_str(&self.suffix);
+                output
+            }
+
+            fn unmarked(&self) -> usize {
+                self.suffix.len()
+            }
+        }
+    }
+
+    #[test]
+    fn test_crew_macro_collects_annotated_methods() {
+        let metadata = ReviewCrew::crew_metadata();
+        assert_eq!(metadata.agents, ["reviewer", "editor"]);
+        assert_eq!(metadata.tasks, ["review_task"]);
+        assert_eq!(metadata.after_kickoff, ["sign"]);
+
+        let crews = ReviewCrew {
+            suffix: " -- signed".into(),
+        };
+        assert_eq!(crews.unmarked(), 10);
+        let crew = crews.build_crew();
+        assert_eq!(crew.agents, ["Reviewer", "Editor"]);
+        assert_eq!(crew.process, Process::Hierarchical);
+        let output = CrewOutput {
+            raw: "LGTM".into(),
+            ..Default::default()
+        };
+        let output = (crew.after_kickoff_callbacks[0])(output);
+        assert_eq!(output.raw, "LGTM -- signed");
+    }
+}
@@ -3,9 +3,20 @@
 //! Corresponds to `crewai/project/`.
 //!
 //! In Rust, Python decorator patterns are represented as marker types
-//! and builder patterns rather than function wrappers.
+//! and builder patterns rather than function wrappers; the `@tool`
+//! decorator becomes the [`tool!`](crate::tool) and
+//! [`tool_args!`](crate::tool_args) macros and `@CrewBase` into the
+//! [`crew!`](crate::crew) macro. Crews defined in `agents.yaml` /
+//! `tasks.yaml` are loaded by [`CrewConfig`].
+
+pub mod config;
+mod crew_macros;
+mod tool_macros;
 
 use std::collections::HashMap;
+use std::path::Path;
+
+pub use config::{AgentConfig, ConfigError, CrewConfig, TaskConfig};
 
 // ---------------------------------------------------------------------------
 // Marker types for crew component annotations
@@ -148,6 +159,28 @@ impl CrewBase {
     pub fn register_tool(&mut self, name: impl Into<String>) {
         self.metadata.tools.push(name.into());
     }
+
+    /// Load `agents_config` and `tasks_config`, relative to `base_dir`.
+    ///
+    /// A missing path counts as an empty file.
+    pub fn load_config(&self, base_dir: impl AsRef<Path>) -> Result<CrewConfig, ConfigError> {
+        let base_dir = base_dir.as_ref();
+        let read = |path: &Option<String>| match path {
+            Some(path) => {
+                let path = base_dir.join(path);
+                std::fs::read_to_string(&path)
+                    .map(|yaml| (path.display().to_string(), yaml))
+                    .map_err(|source| ConfigError::Io { path, source })
+            }
+            None => Ok((String::new(), String::new())),
+        };
+        let (agents_file, agents) = read(&self.agents_config)?;
+        let (tasks_file, tasks) = read(&self.tasks_config)?;
+        Ok(CrewConfig {
+            agents: config::entries(&agents_file, &agents)?,
+            tasks: config::entries(&tasks_file, &tasks)?,
+        })
+    }
 }
 
 // ---------------------------------------------------------------------------
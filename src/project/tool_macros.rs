@@ -0,0 +1,129 @@
+//! Macros for writing tools as plain Rust functions.
+//!
+//! Python's `@tool` decorator builds a tool from a function and its type
+//! hints. [`tool!`](crate::tool) does the same for a Rust function: the
+//! generated constructor returns a [`Tool`](crate::tools::Tool) whose
+//! argument schema is derived from the parameter types and doc comments.
+//! [`tool_args!`](crate::tool_args) defines an argument struct for use with
+//! [`Tool::typed`](crate::tools::Tool::typed).
+
+/// Define a tool from a function with typed parameters.
+///
+/// Expands to a function of the same name returning a
+/// [`Tool`](crate::tools::Tool). The doc comment becomes the description,
+/// parameter doc comments describe the arguments, and the function must
+/// return a `Result` whose value serializes to JSON.
+///
+/// ```
+/// crewai::tool! {
+///     /// Multiply two numbers.
+///     pub fn multiply(
+///         /// The first factor.
+///         a: f64,
+///         b: f64,
+///     ) -> Result<f64, String> {
+///         Ok(a * b)
+///     }
+/// }
+///
+/// use crewai::tools::BaseTool;
+/// let mut tool = multiply();
+/// let args = [("a".to_string(), 6.into()), ("b".to_string(), 7.into())];
+/// assert_eq!(tool.run(args.into_iter().collect()).unwrap(), 42.0);
+/// ```
+#[macro_export]
+macro_rules! tool {
+    (
+        $(#[doc = $doc:literal])*
+        $vis:vis fn $name:ident (
+            $( $(#[doc = $pdoc:literal])* $param:ident : $ty:ty ),* $(,)?
+        ) -> $ret:ty $body:block
+    ) => {
+        $(#[doc = $doc])*
+        $vis fn $name() -> $crate::tools::base_tool::Tool {
+            fn call($($param: $ty),*) -> $ret $body
+
+            let description = $crate::tools::typed_tool::doc_text(concat!($($doc, "\n",)* ""));
+            let description = if description.is_empty() {
+                stringify!($name).to_string()
+            } else {
+                description
+            };
+            $crate::tools::base_tool::Tool::new(
+                stringify!($name),
+                description,
+                $crate::tools::typed_tool::tool_fn(|args| {
+                    $(
+                        let $param: $ty = $crate::tools::typed_tool::arg(args, stringify!($param))?;
+                    )*
+                    $crate::tools::typed_tool::into_output(call($($param),*))
+                }),
+            )
+            .with_args_schema($crate::tools::typed_tool::object_schema(vec![
+                $(
+                    $crate::tools::typed_tool::field::<$ty>(
+                        stringify!($param),
+                        concat!($($pdoc, "\n",)* ""),
+                    )
+                ),*
+            ]))
+        }
+    };
+}
+
+/// Define a struct of tool arguments implementing
+/// [`ToolArgs`](crate::tools::typed_tool::ToolArgs).
+///
+/// Field doc comments describe the arguments; `Option` fields may be
+/// omitted by the caller.
+///
+/// ```
+/// crewai::tool_args! {
+///     /// Arguments of a search tool.
+///     #[derive(Debug)]
+///     pub struct SearchArgs {
+///         /// What to search for.
+///         pub query: String,
+///         pub limit: Option<u32>,
+///     }
+/// }
+///
+/// let tool = crewai::tools::Tool::typed("search", "Search the docs", |args: SearchArgs| {
+///     Ok::<_, String>(format!("{} (top {})", args.query, args.limit.unwrap_or(5)))
+/// });
+/// ```
+#[macro_export]
+macro_rules! tool_args {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[doc = $fdoc:literal])* $fvis:vis $field:ident : $ty:ty ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[doc = $fdoc])* $fvis $field: $ty ),*
+        }
+
+        impl $crate::tools::typed_tool::ToolArgs for $name {
+            fn args_schema() -> $crate::__private::serde_json::Value {
+                $crate::tools::typed_tool::object_schema(vec![
+                    $(
+                        $crate::tools::typed_tool::field::<$ty>(
+                            stringify!($field),
+                            concat!($($fdoc, "\n",)* ""),
+                        )
+                    ),*
+                ])
+            }
+
+            fn from_args(
+                mut args: ::std::collections::HashMap<String, $crate::__private::serde_json::Value>,
+            ) -> Result<Self, $crate::tools::typed_tool::ToolArgsError> {
+                Ok(Self {
+                    $( $field: $crate::tools::typed_tool::arg(&mut args, stringify!($field))?, )*
+                })
+            }
+        }
+    };
+}
@@ -0,0 +1,192 @@
+//! High-level one-liners for getting a crew running quickly.
+//!
+//! These helpers build a sequential crew with memory disabled, assign every
+//! agent the given model, and kick it off. They are meant for first examples;
+//! anything beyond that should use [`Crew`], [`Agent`] and [`Task`] directly.
+
+use crate::agent::Agent;
+use crate::crew::Crew;
+use crate::crews::crew_output::CrewOutput;
+use crate::process::Process;
+use crate::task::Task;
+use crate::tasks::task_output::TaskOutput;
+
+/// Expected output used for tasks created by the quickstart helpers.
+const DEFAULT_EXPECTED_OUTPUT: &str = "A complete and accurate answer to the task.";
+
+/// Builder behind [`quick_crew`], for callers who want to adjust the crew
+/// before kicking it off.
+#[derive(Debug, Clone)]
+pub struct QuickCrew {
+    /// `(role, task description)` pairs, executed in order.
+    pub steps: Vec<(String, String)>,
+    /// Model identifier given to every agent (e.g. `"openai/gpt-4o-mini"`).
+    pub model: String,
+    /// Expected output applied to every task.
+    pub expected_output: String,
+}
+
+impl QuickCrew {
+    /// Create a builder for the given `(role, task)` pairs and model.
+    pub fn new<R, T>(steps: Vec<(R, T)>, model: impl Into<String>) -> Self
+    where
+        R: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            steps: steps
+                .into_iter()
+                .map(|(role, task)| (role.into(), task.into()))
+                .collect(),
+            model: model.into(),
+            expected_output: DEFAULT_EXPECTED_OUTPUT.to_string(),
+        }
+    }
+
+    /// Override the expected output used for every task.
+    pub fn expected_output(mut self, expected_output: impl Into<String>) -> Self {
+        self.expected_output = expected_output.into();
+        self
+    }
+
+    /// Build the crew without running it.
+    ///
+    /// One agent is created per distinct role; a role listed more than once
+    /// handles each of its tasks.
+    pub fn build(self) -> Crew {
+        let mut agents: Vec<Agent> = Vec::new();
+        let mut tasks = Vec::with_capacity(self.steps.len());
+        for (role, description) in self.steps {
+            if !agents.iter().any(|a| a.role == role) {
+                let goal = format!("Complete the tasks assigned to the {}", role);
+                agents.push(quick_agent(&role, goal, &self.model));
+            }
+            tasks.push(quick_task(description, &self.expected_output, role));
+        }
+        quick_sequential_crew(tasks, agents)
+    }
+
+    /// Build the crew and kick it off with no inputs.
+    pub fn kickoff(self) -> Result<CrewOutput, String> {
+        self.build().kickoff(None)
+    }
+}
+
+/// Build a one-agent, one-task crew without running it.
+pub fn single_agent_crew(
+    role: impl Into<String>,
+    goal: impl Into<String>,
+    task: impl Into<String>,
+    model: impl Into<String>,
+) -> Crew {
+    let role = role.into();
+    let agent = quick_agent(&role, goal.into(), &model.into());
+    let task = quick_task(task.into(), DEFAULT_EXPECTED_OUTPUT, role);
+    quick_sequential_crew(vec![task], vec![agent])
+}
+
+fn quick_agent(role: &str, goal: String, model: &str) -> Agent {
+    let mut agent = Agent::new(
+        role.to_string(),
+        goal,
+        format!("You are an experienced {}.", role),
+    );
+    agent.llm = Some(model.to_string());
+    agent.refresh_fingerprint();
+    agent
+}
+
+fn quick_task(description: String, expected_output: &str, role: String) -> Task {
+    let mut task = Task::new(description, expected_output.to_string());
+    task.agent = Some(role);
+    task.refresh_fingerprint();
+    task
+}
+
+fn quick_sequential_crew(tasks: Vec<Task>, agents: Vec<Agent>) -> Crew {
+    let mut crew = Crew::with_agents(tasks, agents);
+    crew.process = Process::Sequential;
+    crew.memory = false;
+    crew
+}
+
+/// Run a single task with a single agent and return the task's output.
+///
+/// ```no_run
+/// let output = crewai::prelude::run_single_agent(
+///     "Writer",
+///     "Write clear prose",
+///     "Write a haiku about ownership.",
+///     "gpt-4o-mini",
+/// )
+/// .unwrap();
+/// println!("{}", output.raw);
+/// ```
+pub fn run_single_agent(
+    role: impl Into<String>,
+    goal: impl Into<String>,
+    task: impl Into<String>,
+    model: impl Into<String>,
+) -> Result<TaskOutput, String> {
+    let output = single_agent_crew(role, goal, task, model).kickoff(None)?;
+    output
+        .tasks_output
+        .into_iter()
+        .last()
+        .ok_or_else(|| "Crew produced no task output".to_string())
+}
+
+/// Run a sequential crew of `(role, task)` pairs, all using `model`.
+///
+/// ```no_run
+/// let output = crewai::prelude::quick_crew(
+///     vec![
+///         ("Researcher", "List three facts about the borrow checker."),
+///         ("Writer", "Turn the facts into a short paragraph."),
+///     ],
+///     "openai/gpt-4o-mini",
+/// )
+/// .unwrap();
+/// println!("{}", output.raw);
+/// ```
+pub fn quick_crew<R, T>(steps: Vec<(R, T)>, model: impl Into<String>) -> Result<CrewOutput, String>
+where
+    R: Into<String>,
+    T: Into<String>,
+{
+    QuickCrew::new(steps, model).kickoff()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_crew_defaults() {
+        let crew = QuickCrew::new(
+            vec![
+                ("Researcher", "Find facts"),
+                ("Writer", "Write it up"),
+                ("Researcher", "Check the write-up"),
+            ],
+            "openai/gpt-4o-mini",
+        )
+        .build();
+
+        assert_eq!(crew.process, Process::Sequential);
+        assert!(!crew.memory);
+        assert_eq!(crew.agents, vec!["Researcher", "Writer"]);
+        assert_eq!(crew.tasks.len(), 3);
+        assert_eq!(crew.tasks[2].agent.as_deref(), Some("Researcher"));
+        let writer = crew.get_agent("Writer").unwrap();
+        assert_eq!(
+            writer.read().unwrap().llm.as_deref(),
+            Some("openai/gpt-4o-mini")
+        );
+
+        let crew = single_agent_crew("Analyst", "Be precise", "Count to three", "gpt-4o");
+        let analyst = crew.get_agent("Analyst").unwrap();
+        assert_eq!(analyst.read().unwrap().goal, "Be precise");
+        assert_eq!(crew.tasks[0].description, "Count to three");
+    }
+}
@@ -0,0 +1,286 @@
+//! Embedder configuration shared by knowledge, memory and RAG storage.
+//!
+//! Port of `EmbedderConfig` in crewai/rag/embeddings/types.py.
+//!
+//! Crews, agents, [`Knowledge`](crate::knowledge::Knowledge) and the memory
+//! backends all take an [`EmbedderConfig`]. It deserializes from the flat
+//! form
+//!
+//! ```json
+//! {"provider": "openai", "model": "text-embedding-3-small", "dimensions": 512}
+//! ```
+//!
+//! as well as from the provider spec form of crewAI,
+//! `{"provider": "openai", "config": {"model_name": ..., "api_key": ...}}`.
+//! [`build`](EmbedderConfig::build) resolves it to an
+//! [`EmbeddingFunctionTrait`] through the provider registry.
+//!
+//! The API key is a [`SecretString`]: it is redacted from `Debug` output
+//! and left out when the configuration (or a crew holding it) is
+//! serialized.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::rag::core::{EmbeddingFunctionTrait, Embeddings};
+use crate::security::secrets::SecretString;
+
+/// Keys under which provider configs name the embedding model.
+const MODEL_KEYS: [&str; 3] = ["model", "model_name", "model_id"];
+
+/// Configuration of the embedder used to embed documents and queries.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "Value")]
+pub struct EmbedderConfig {
+    /// Provider name, as in the provider registry (e.g. "openai", "ollama").
+    pub provider: String,
+    /// Embedding model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// API key; providers fall back to their environment variables.
+    #[serde(skip_serializing)]
+    pub api_key: Option<SecretString>,
+    /// Dimensions of the embeddings, for models that support several.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<usize>,
+    /// Most texts embedded per provider call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<usize>,
+    /// Further provider-specific settings.
+    #[serde(flatten)]
+    pub options: Map<String, Value>,
+}
+
+impl EmbedderConfig {
+    /// Embed with `provider`'s default model.
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Builder: set the model.
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Builder: set the API key.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(SecretString::new(api_key));
+        self
+    }
+
+    /// Builder: set the embedding dimensions.
+    pub fn dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Builder: set the batch size.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Builder: set a provider-specific option.
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.options.insert(key.into(), value.into());
+        self
+    }
+
+    /// The configuration in `value`, in the flat or the provider spec form.
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| format!("Embedder configuration must be an object: {}", value))?;
+        let provider = object
+            .get("provider")
+            .and_then(Value::as_str)
+            .ok_or("Embedder configuration must include a 'provider' key")?;
+
+        let mut options = object.clone();
+        options.remove("provider");
+        if let Some(Value::Object(config)) = options.remove("config") {
+            options.extend(config);
+        }
+        let mut take_str = |keys: &[&str]| {
+            keys.iter()
+                .filter_map(|key| options.remove(*key))
+                .find_map(|value| value.as_str().map(str::to_string))
+        };
+        let model = take_str(&MODEL_KEYS);
+        let api_key = take_str(&["api_key"]).map(SecretString::from);
+        let mut take_usize = |key: &str| -> Result<Option<usize>, String> {
+            match options.remove(key) {
+                None | Some(Value::Null) => Ok(None),
+                Some(value) => value
+                    .as_u64()
+                    .map(|n| Some(n as usize))
+                    .ok_or_else(|| format!("Embedder '{}' must be a positive integer", key)),
+            }
+        };
+        let dimensions = take_usize("dimensions")?;
+        let batch_size = take_usize("batch_size")?;
+        Ok(Self {
+            provider: provider.to_string(),
+            model,
+            api_key,
+            dimensions,
+            batch_size,
+            options,
+        })
+    }
+
+    /// The provider spec the embedding factory takes:
+    /// `{"provider": ..., "config": {...}}`.
+    pub fn to_spec(&self) -> Value {
+        serde_json::json!({"provider": self.provider, "config": self.provider_config()})
+    }
+
+    /// The provider's settings, the `config` of the provider spec.
+    pub fn provider_config(&self) -> Map<String, Value> {
+        let mut config = self.options.clone();
+        if let Some(model) = &self.model {
+            config.insert("model".to_string(), Value::from(model.clone()));
+        }
+        if let Some(api_key) = &self.api_key {
+            config.insert("api_key".to_string(), Value::from(api_key.expose_secret()));
+        }
+        if let Some(dimensions) = self.dimensions {
+            config.insert("dimensions".to_string(), Value::from(dimensions));
+        }
+        if let Some(batch_size) = self.batch_size {
+            config.insert("batch_size".to_string(), Value::from(batch_size));
+        }
+        config
+    }
+
+    /// The embedding function of the configured provider, embedding at most
+    /// `batch_size` texts per call.
+    pub fn build(&self) -> Result<Arc<dyn EmbeddingFunctionTrait>, anyhow::Error> {
+        let embedder: Arc<dyn EmbeddingFunctionTrait> =
+            Arc::from(super::build_embedder(&self.to_spec())?);
+        Ok(match self.batch_size {
+            Some(batch_size) if batch_size > 0 => Arc::new(Batched {
+                embedder,
+                batch_size,
+            }),
+            _ => embedder,
+        })
+    }
+}
+
+impl TryFrom<Value> for EmbedderConfig {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Self::from_value(&value)
+    }
+}
+
+impl From<&str> for EmbedderConfig {
+    fn from(provider: &str) -> Self {
+        Self::new(provider)
+    }
+}
+
+/// An embedding function called with at most `batch_size` texts at a time.
+struct Batched {
+    embedder: Arc<dyn EmbeddingFunctionTrait>,
+    batch_size: usize,
+}
+
+impl EmbeddingFunctionTrait for Batched {
+    fn call(&self, input: &[String]) -> Result<Embeddings, anyhow::Error> {
+        let mut embeddings = Vec::with_capacity(input.len());
+        for batch in input.chunks(self.batch_size) {
+            embeddings.extend(self.embedder.call(batch)?);
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flat_and_spec_forms_agree() {
+        let flat: EmbedderConfig = serde_json::from_value(json!({
+            "provider": "openai",
+            "model": "text-embedding-3-small",
+            "dimensions": 512,
+            "batch_size": 16,
+        }))
+        .unwrap();
+        let spec = EmbedderConfig::from_value(&json!({
+            "provider": "openai",
+            "config": {"model_name": "text-embedding-3-small", "dimensions": 512, "batch_size": 16},
+        }))
+        .unwrap();
+        assert_eq!(flat, spec);
+        assert_eq!(
+            flat,
+            EmbedderConfig::new("openai")
+                .model("text-embedding-3-small")
+                .dimensions(512)
+                .batch_size(16)
+        );
+
+        let mem0 = EmbedderConfig::from_value(&json!({
+            "provider": "mem0",
+            "config": {"user_id": "alice", "api_key": "m0-key"},
+        }))
+        .unwrap();
+        assert_eq!(
+            mem0.api_key.as_ref().map(SecretString::expose_secret),
+            Some("m0-key")
+        );
+        assert_eq!(
+            mem0.to_spec(),
+            json!({"provider": "mem0", "config": {"user_id": "alice", "api_key": "m0-key"}})
+        );
+        // The key never leaves through logs or serialized configs.
+        assert!(!format!("{:?}", mem0).contains("m0-key"));
+        let serialized = serde_json::to_value(&mem0).unwrap();
+        assert_eq!(serialized, json!({"provider": "mem0", "user_id": "alice"}));
+        let round_trip: EmbedderConfig = serde_json::from_value(serialized).unwrap();
+        assert_eq!(round_trip.api_key, None);
+        assert_eq!(round_trip.options, mem0.options);
+
+        assert!(EmbedderConfig::from_value(&json!({"model": "x"})).is_err());
+        assert!(
+            EmbedderConfig::from_value(&json!({"provider": "openai", "dimensions": "big"}))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_batches_embedding_calls() {
+        struct Lengths(std::sync::Mutex<Vec<usize>>);
+        impl EmbeddingFunctionTrait for Lengths {
+            fn call(&self, input: &[String]) -> Result<Embeddings, anyhow::Error> {
+                self.0.lock().unwrap().push(input.len());
+                Ok(input.iter().map(|text| vec![text.len() as f32]).collect())
+            }
+        }
+        let inner = Arc::new(Lengths(Default::default()));
+        let batched = Batched {
+            embedder: inner.clone(),
+            batch_size: 2,
+        };
+        let input: Vec<String> = ["a", "bb", "ccc", "dddd", "e"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let embeddings = batched.call(&input).unwrap();
+        assert_eq!(embeddings, [[1.0], [2.0], [3.0], [4.0], [1.0]]);
+        assert_eq!(*inner.0.lock().unwrap(), [2, 2, 1]);
+        assert!(EmbedderConfig::new("unknown").build().is_err());
+    }
+}
@@ -3,12 +3,16 @@
 //! Port of crewai/rag/embeddings/
 //!
 //! This module provides:
+//! - [`EmbedderConfig`], the embedder configuration shared across crewAI
 //! - Provider registry mapping provider names to implementations
 //! - Factory functions for building embedding functions from specs
 //! - Submodule `providers` with all supported embedding provider stubs
 
+pub mod config;
 pub mod providers;
 
+pub use config::EmbedderConfig;
+
 use std::collections::HashMap;
 
 use serde_json::Value;
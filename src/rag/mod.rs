@@ -1,17 +1,20 @@
 //! RAG (Retrieval-Augmented Generation) system for crewAI.
 //!
 //! This module provides the RAG subsystem including vector database clients
-//! (ChromaDB, Qdrant), embedding providers, storage abstractions, and factory
-//! functions for client creation.
+//! (ChromaDB, Qdrant), embedding providers, storage abstractions, factory
+//! functions for client creation, and a document ingestion and retrieval
+//! [`pipeline`].
 
 pub mod chromadb;
 pub mod config;
 pub mod core;
 pub mod embeddings;
 pub mod factory;
+pub mod pipeline;
 pub mod qdrant;
 pub mod storage;
 pub mod types;
 
 pub use factory::create_client;
+pub use pipeline::{RagPipeline, Retriever};
 pub use types::{BaseRecord, EmbeddingFunction, Embeddings, SearchResult};
@@ -0,0 +1,542 @@
+//! Document ingestion and retrieval pipeline.
+//!
+//! A [`RagPipeline`] runs documents through the stages of
+//! retrieval-augmented generation:
+//!
+//! [`DocumentLoader`] → [`Chunker`] → embedder → [`VectorStore`] → [`Retriever`]
+//!
+//! Each stage is a trait, with a default implementation for the common
+//! case: [`TextLoader`] and [`FileLoader`], [`TextChunker`] and the
+//! [`InMemoryVectorStore`]. The embedder is any
+//! [`EmbeddingFunctionTrait`], or one built from an [`EmbedderConfig`].
+//! Pipelines are put together with [`RagPipeline::builder`]:
+//!
+//! ```rust,no_run
+//! use crewai::rag::embeddings::EmbedderConfig;
+//! use crewai::rag::pipeline::{FileLoader, RagPipeline, TextChunker};
+//!
+//! let pipeline = RagPipeline::builder()
+//!     .loader(FileLoader::new(["docs/handbook.md"]))
+//!     .chunker(TextChunker::new(1000, 100))
+//!     .embedder_config(EmbedderConfig::new("openai"))
+//!     .build()
+//!     .unwrap();
+//! pipeline.ingest().unwrap();
+//! let results = pipeline.query("How do I request leave?").unwrap();
+//! ```
+//!
+//! [`RagTool`](crate::tools::builtin::RagTool) lets agents query a pipeline.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use serde_json::Value;
+
+use crate::rag::core::EmbeddingFunctionTrait;
+use crate::rag::embeddings::EmbedderConfig;
+use crate::rag::types::{BaseRecord, Embeddings, SearchResult};
+use crate::utilities::perf::{self, HotPath};
+
+// ---------------------------------------------------------------------------
+// Loaders
+// ---------------------------------------------------------------------------
+
+/// Loads the documents a pipeline ingests.
+pub trait DocumentLoader: Send + Sync {
+    /// The documents, with metadata such as their `source`.
+    fn load(&self) -> Result<Vec<BaseRecord>, anyhow::Error>;
+}
+
+/// Loads documents given as text.
+#[derive(Debug, Clone, Default)]
+pub struct TextLoader {
+    /// The documents' text.
+    pub texts: Vec<String>,
+}
+
+impl TextLoader {
+    /// Load `texts`, one document each.
+    pub fn new<S: Into<String>>(texts: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            texts: texts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl DocumentLoader for TextLoader {
+    fn load(&self) -> Result<Vec<BaseRecord>, anyhow::Error> {
+        Ok(self.texts.iter().cloned().map(BaseRecord::new).collect())
+    }
+}
+
+/// Loads text files, one document each, with the file path as `source`.
+#[derive(Debug, Clone, Default)]
+pub struct FileLoader {
+    /// Files to load.
+    pub paths: Vec<PathBuf>,
+}
+
+impl FileLoader {
+    /// Load the files at `paths`.
+    pub fn new<P: Into<PathBuf>>(paths: impl IntoIterator<Item = P>) -> Self {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl DocumentLoader for FileLoader {
+    fn load(&self) -> Result<Vec<BaseRecord>, anyhow::Error> {
+        self.paths
+            .iter()
+            .map(|path| {
+                let content = std::fs::read_to_string(path).map_err(|e| {
+                    anyhow::anyhow!("Failed to load document '{}': {}", path.display(), e)
+                })?;
+                let source = Value::String(path.display().to_string());
+                Ok(BaseRecord::new(content)
+                    .with_metadata(HashMap::from([("source".to_string(), source)])))
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Chunkers
+// ---------------------------------------------------------------------------
+
+/// Splits documents into the chunks that are embedded and retrieved.
+pub trait Chunker: Send + Sync {
+    /// The chunks of `document`, which keep its metadata.
+    fn chunk(&self, document: &BaseRecord) -> Vec<BaseRecord>;
+}
+
+/// Splits text into windows of `chunk_size` characters, consecutive
+/// windows sharing `chunk_overlap` characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextChunker {
+    /// Most characters in a chunk.
+    pub chunk_size: usize,
+    /// Characters a chunk shares with the previous one.
+    pub chunk_overlap: usize,
+}
+
+impl Default for TextChunker {
+    /// The chunk size and overlap of knowledge sources.
+    fn default() -> Self {
+        Self::new(4000, 200)
+    }
+}
+
+impl TextChunker {
+    /// Chunks of `chunk_size` characters overlapping by `chunk_overlap`.
+    pub fn new(chunk_size: usize, chunk_overlap: usize) -> Self {
+        Self {
+            chunk_size,
+            chunk_overlap,
+        }
+    }
+
+    /// The chunks of `text`.
+    pub fn split(&self, text: &str) -> Vec<String> {
+        let _span = perf::span(HotPath::Chunking);
+        let chars: Vec<char> = text.chars().collect();
+        let size = self.chunk_size.max(1);
+        let step = size.saturating_sub(self.chunk_overlap).max(1);
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        loop {
+            let end = (start + size).min(chars.len());
+            chunks.push(chars[start..end].iter().collect());
+            if end == chars.len() {
+                return chunks;
+            }
+            start += step;
+        }
+    }
+}
+
+impl Chunker for TextChunker {
+    fn chunk(&self, document: &BaseRecord) -> Vec<BaseRecord> {
+        self.split(&document.content)
+            .into_iter()
+            .map(|chunk| BaseRecord::new(chunk).with_metadata(document.metadata.clone()))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Vector stores
+// ---------------------------------------------------------------------------
+
+/// Stores embedded chunks and finds those closest to a query.
+pub trait VectorStore: Send + Sync {
+    /// Store `records`, `embeddings[i]` being the embedding of `records[i]`.
+    /// A record with the ID of a stored one replaces it.
+    fn add(&self, records: Vec<BaseRecord>, embeddings: Embeddings) -> Result<(), anyhow::Error>;
+
+    /// The `limit` records most similar to `embedding` scoring at least
+    /// `score_threshold`, best first, whose metadata contains `filter`.
+    fn search(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+        filter: Option<&HashMap<String, Value>>,
+        score_threshold: f64,
+    ) -> Result<Vec<SearchResult>, anyhow::Error>;
+
+    /// Number of stored records.
+    fn len(&self) -> usize;
+
+    /// Whether the store is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove every record.
+    fn reset(&self) -> Result<(), anyhow::Error>;
+}
+
+/// A [`VectorStore`] in memory, scoring records by cosine similarity.
+#[derive(Default)]
+pub struct InMemoryVectorStore {
+    records: RwLock<Vec<(BaseRecord, Vec<f32>)>>,
+}
+
+impl InMemoryVectorStore {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl VectorStore for InMemoryVectorStore {
+    fn add(&self, records: Vec<BaseRecord>, embeddings: Embeddings) -> Result<(), anyhow::Error> {
+        if records.len() != embeddings.len() {
+            return Err(anyhow::anyhow!(
+                "Got {} embeddings for {} records",
+                embeddings.len(),
+                records.len()
+            ));
+        }
+        let mut stored = self.records.write().unwrap();
+        for (record, embedding) in records.into_iter().zip(embeddings) {
+            let id = record.get_or_generate_id();
+            stored.retain(|(existing, _)| existing.get_or_generate_id() != id);
+            stored.push((record, embedding));
+        }
+        Ok(())
+    }
+
+    fn search(
+        &self,
+        embedding: &[f32],
+        limit: usize,
+        filter: Option<&HashMap<String, Value>>,
+        score_threshold: f64,
+    ) -> Result<Vec<SearchResult>, anyhow::Error> {
+        let _span = perf::span(HotPath::VectorSearch);
+        let stored = self.records.read().unwrap();
+        let mut results: Vec<SearchResult> = stored
+            .iter()
+            .filter(|(record, _)| {
+                filter.is_none_or(|filter| {
+                    filter
+                        .iter()
+                        .all(|(key, value)| record.metadata.get(key) == Some(value))
+                })
+            })
+            .map(|(record, stored_embedding)| {
+                SearchResult::new(
+                    record.get_or_generate_id(),
+                    record.content.clone(),
+                    record.metadata.clone(),
+                    cosine_similarity(embedding, stored_embedding),
+                )
+            })
+            .filter(|result| result.score >= score_threshold)
+            .collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    fn len(&self) -> usize {
+        self.records.read().unwrap().len()
+    }
+
+    fn reset(&self) -> Result<(), anyhow::Error> {
+        self.records.write().unwrap().clear();
+        Ok(())
+    }
+}
+
+/// Cosine similarity of two embeddings; 0 when either is all zeros.
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        dot += (*x as f64) * (*y as f64);
+        norm_a += (*x as f64).powi(2);
+        norm_b += (*y as f64).powi(2);
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a.sqrt() * norm_b.sqrt())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Retriever
+// ---------------------------------------------------------------------------
+
+/// Finds the stored chunks relevant to a query.
+#[derive(Clone)]
+pub struct Retriever {
+    /// Embeds queries, with the embedder the chunks were embedded with.
+    pub embedder: Arc<dyn EmbeddingFunctionTrait>,
+    /// Store searched.
+    pub store: Arc<dyn VectorStore>,
+    /// Most results returned.
+    pub limit: usize,
+    /// Lowest score of a result.
+    pub score_threshold: f64,
+}
+
+impl Retriever {
+    /// The chunks most relevant to `query`, best first.
+    pub fn retrieve(&self, query: &str) -> Result<Vec<SearchResult>, anyhow::Error> {
+        self.retrieve_filtered(query, None)
+    }
+
+    /// The chunks most relevant to `query` whose metadata contains
+    /// `filter`, best first.
+    pub fn retrieve_filtered(
+        &self,
+        query: &str,
+        filter: Option<&HashMap<String, Value>>,
+    ) -> Result<Vec<SearchResult>, anyhow::Error> {
+        let embedding = self.embedder.embed_query(query)?;
+        self.store
+            .search(&embedding, self.limit, filter, self.score_threshold)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pipeline
+// ---------------------------------------------------------------------------
+
+/// Loads, chunks, embeds and stores documents, and retrieves them.
+pub struct RagPipeline {
+    /// Sources of the documents ingested.
+    pub loaders: Vec<Box<dyn DocumentLoader>>,
+    /// Splits documents into chunks.
+    pub chunker: Box<dyn Chunker>,
+    /// Searches the ingested chunks.
+    pub retriever: Retriever,
+}
+
+impl RagPipeline {
+    /// A builder for a pipeline.
+    pub fn builder() -> RagPipelineBuilder {
+        RagPipelineBuilder::default()
+    }
+
+    /// Load every document, chunk it, embed the chunks and store them.
+    /// Returns the number of chunks stored.
+    pub fn ingest(&self) -> Result<usize, anyhow::Error> {
+        let mut chunks = Vec::new();
+        for loader in &self.loaders {
+            for document in loader.load()? {
+                chunks.extend(self.chunker.chunk(&document));
+            }
+        }
+        self.add_chunks(chunks)
+    }
+
+    /// Chunk, embed and store `documents`, besides those of the loaders.
+    /// Returns the number of chunks stored.
+    pub fn add_documents(&self, documents: &[BaseRecord]) -> Result<usize, anyhow::Error> {
+        let chunks = documents
+            .iter()
+            .flat_map(|document| self.chunker.chunk(document))
+            .collect();
+        self.add_chunks(chunks)
+    }
+
+    fn add_chunks(&self, chunks: Vec<BaseRecord>) -> Result<usize, anyhow::Error> {
+        if chunks.is_empty() {
+            return Ok(0);
+        }
+        let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+        let embeddings = self.retriever.embedder.call(&texts)?;
+        let count = chunks.len();
+        self.retriever.store.add(chunks, embeddings)?;
+        Ok(count)
+    }
+
+    /// The ingested chunks most relevant to `query`, best first.
+    pub fn query(&self, query: &str) -> Result<Vec<SearchResult>, anyhow::Error> {
+        self.retriever.retrieve(query)
+    }
+}
+
+/// Builder for a [`RagPipeline`].
+///
+/// An embedder is required; the chunker defaults to [`TextChunker`], the
+/// store to an [`InMemoryVectorStore`], and queries return the 5 best
+/// results scoring at least 0.
+#[derive(Default)]
+pub struct RagPipelineBuilder {
+    loaders: Vec<Box<dyn DocumentLoader>>,
+    chunker: Option<Box<dyn Chunker>>,
+    embedder: Option<Arc<dyn EmbeddingFunctionTrait>>,
+    embedder_config: Option<EmbedderConfig>,
+    store: Option<Arc<dyn VectorStore>>,
+    limit: Option<usize>,
+    score_threshold: f64,
+}
+
+impl RagPipelineBuilder {
+    /// Add a source of documents.
+    pub fn loader(mut self, loader: impl DocumentLoader + 'static) -> Self {
+        self.loaders.push(Box::new(loader));
+        self
+    }
+
+    /// Split documents with `chunker`.
+    pub fn chunker(mut self, chunker: impl Chunker + 'static) -> Self {
+        self.chunker = Some(Box::new(chunker));
+        self
+    }
+
+    /// Embed chunks and queries with `embedder`.
+    pub fn embedder(mut self, embedder: Arc<dyn EmbeddingFunctionTrait>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Embed chunks and queries with the embedder `config` describes.
+    pub fn embedder_config(mut self, config: impl Into<EmbedderConfig>) -> Self {
+        self.embedder_config = Some(config.into());
+        self
+    }
+
+    /// Store chunks in `store`, which may be shared with other pipelines.
+    pub fn store(mut self, store: Arc<dyn VectorStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Return at most `limit` results per query.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Leave out results scoring below `score_threshold`.
+    pub fn score_threshold(mut self, score_threshold: f64) -> Self {
+        self.score_threshold = score_threshold;
+        self
+    }
+
+    /// The pipeline; fails without an embedder, or when the configured one
+    /// cannot be built.
+    pub fn build(self) -> Result<RagPipeline, anyhow::Error> {
+        let embedder = match (self.embedder, self.embedder_config) {
+            (Some(embedder), _) => embedder,
+            (None, Some(config)) => config.build()?,
+            (None, None) => return Err(anyhow::anyhow!("A RAG pipeline needs an embedder")),
+        };
+        Ok(RagPipeline {
+            loaders: self.loaders,
+            chunker: self
+                .chunker
+                .unwrap_or_else(|| Box::new(TextChunker::default())),
+            retriever: Retriever {
+                embedder,
+                store: self
+                    .store
+                    .unwrap_or_else(|| Arc::new(InMemoryVectorStore::new())),
+                limit: self.limit.unwrap_or(5),
+                score_threshold: self.score_threshold,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Embeds text as counts of a few keywords.
+    struct KeywordEmbedder;
+
+    impl EmbeddingFunctionTrait for KeywordEmbedder {
+        fn call(&self, input: &[String]) -> Result<Embeddings, anyhow::Error> {
+            Ok(input
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    ["leave", "salary", "laptop"]
+                        .iter()
+                        .map(|word| text.matches(word).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_pipeline_ingests_and_retrieves() {
+        let dir = tempfile::tempdir().unwrap();
+        let handbook = dir.path().join("handbook.md");
+        std::fs::write(&handbook, "Leave is requested from your manager.").unwrap();
+
+        let pipeline = RagPipeline::builder()
+            .loader(FileLoader::new([&handbook]))
+            .loader(TextLoader::new([
+                "Salary is paid monthly.",
+                "IT hands out a laptop.",
+            ]))
+            .chunker(TextChunker::new(24, 4))
+            .embedder(Arc::new(KeywordEmbedder))
+            .limit(2)
+            .score_threshold(0.1)
+            .build()
+            .unwrap();
+        assert_eq!(pipeline.ingest().unwrap(), 4);
+        assert_eq!(pipeline.ingest().unwrap(), 4); // Re-ingested chunks replace their copies
+        assert_eq!(pipeline.retriever.store.len(), 4);
+
+        let results = pipeline.query("When is my salary paid?").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].content, "Salary is paid monthly.");
+        let results = pipeline.query("How do I take leave?").unwrap();
+        assert_eq!(results[0].content, "Leave is requested from ");
+        assert_eq!(
+            results[0].metadata["source"],
+            Value::String(handbook.display().to_string())
+        );
+
+        let filter = HashMap::from([("source".to_string(), Value::from("elsewhere"))]);
+        let filtered = pipeline
+            .retriever
+            .retrieve_filtered("How do I take leave?", Some(&filter))
+            .unwrap();
+        assert!(filtered.is_empty());
+        assert!(RagPipeline::builder().build().is_err());
+        assert!(FileLoader::new([dir.path().join("missing.md")])
+            .load()
+            .is_err());
+    }
+
+    #[test]
+    fn test_text_chunker_splits_on_characters() {
+        let chunker = TextChunker::new(4, 1);
+        assert_eq!(chunker.split("héllo wörld"), ["héll", "lo w", "wörl", "ld"]);
+        assert_eq!(chunker.split("abc"), ["abc"]);
+        assert_eq!(TextChunker::new(2, 5).split("abc"), ["ab", "bc"]);
+    }
+}
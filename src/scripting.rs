@@ -0,0 +1,507 @@
+//! Embedded scripting for user-defined guardrails and conditions.
+//!
+//! With the `scripting` feature, guardrails, conditional-task conditions
+//! and policy conditions can be written as [Rhai](https://rhai.rs)
+//! snippets in YAML configuration instead of Rust code:
+//!
+//! ```yaml
+//! guardrail:
+//!   script: |
+//!     if output.len() > 2000 { "Keep the answer under 2000 characters" } else { true }
+//!   timeout_ms: 500
+//! ```
+//!
+//! Scripts are sandboxed: they cannot import modules, touch the file
+//! system or run `eval`, and every evaluation is bounded by
+//! [`ScriptLimits`] (wall-clock time, operation count, nesting depth and
+//! collection sizes). `print` and `debug` go to the log. Lua is not
+//! supported; Rhai builds without a C toolchain and is sandboxed by
+//! default.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, Scope, AST};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::task::GuardrailFn;
+use crate::tasks::conditional_task::ConditionFn;
+use crate::tasks::task_output::TaskOutput;
+
+/// Token returned by the progress callback when a deadline passes.
+const TIMEOUT_TOKEN: &str = "crewai:script-timeout";
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Error compiling or evaluating a script.
+#[derive(Debug, thiserror::Error)]
+pub enum ScriptError {
+    /// The script does not parse.
+    #[error("Script compile error: {0}")]
+    Compile(String),
+    /// The script failed or exceeded a resource limit.
+    #[error("Script error: {0}")]
+    Runtime(String),
+    /// The script ran past its time limit.
+    #[error("Script exceeded its time limit of {0:?}")]
+    Timeout(Duration),
+    /// The script returned a value of the wrong type.
+    #[error("Script returned {found}, expected {expected}")]
+    Type {
+        /// Expected type.
+        expected: &'static str,
+        /// Returned value.
+        found: String,
+    },
+}
+
+/// Resource limits of one script evaluation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptLimits {
+    /// Wall-clock limit.
+    #[serde(with = "duration_ms", rename = "timeout_ms")]
+    pub timeout: Duration,
+    /// Maximum number of operations.
+    pub max_operations: u64,
+    /// Maximum function call depth.
+    pub max_call_levels: usize,
+    /// Maximum expression nesting depth.
+    pub max_expr_depth: usize,
+    /// Maximum string length.
+    pub max_string_size: usize,
+    /// Maximum array length.
+    pub max_array_size: usize,
+    /// Maximum number of map entries.
+    pub max_map_size: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(1),
+            max_operations: 1_000_000,
+            max_call_levels: 32,
+            max_expr_depth: 64,
+            max_string_size: 1024 * 1024,
+            max_array_size: 10_000,
+            max_map_size: 10_000,
+        }
+    }
+}
+
+mod duration_ms {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// A sandboxed Rhai engine with fixed limits.
+pub struct ScriptEngine {
+    engine: Engine,
+    limits: ScriptLimits,
+}
+
+impl std::fmt::Debug for ScriptEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptEngine")
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new(ScriptLimits::default())
+    }
+}
+
+impl ScriptEngine {
+    /// An engine enforcing `limits`.
+    pub fn new(limits: ScriptLimits) -> Self {
+        let mut engine = Engine::new();
+        engine
+            .set_module_resolver(DummyModuleResolver::new())
+            .set_max_operations(limits.max_operations)
+            .set_max_call_levels(limits.max_call_levels)
+            .set_max_expr_depths(limits.max_expr_depth, limits.max_expr_depth)
+            .set_max_string_size(limits.max_string_size)
+            .set_max_array_size(limits.max_array_size)
+            .set_max_map_size(limits.max_map_size)
+            .on_print(|text| log::info!(target: "crewai::scripting", "{}", text))
+            .on_debug(|text, _, pos| log::debug!(target: "crewai::scripting", "{} {}", pos, text))
+            .on_progress(|_| {
+                let expired = DEADLINE
+                    .with(Cell::get)
+                    .is_some_and(|d| Instant::now() >= d);
+                expired.then(|| Dynamic::from(TIMEOUT_TOKEN))
+            });
+        engine.disable_symbol("eval");
+        Self { engine, limits }
+    }
+
+    /// The engine used by scripts without their own limits.
+    pub fn shared() -> Arc<ScriptEngine> {
+        static SHARED: OnceLock<Arc<ScriptEngine>> = OnceLock::new();
+        SHARED.get_or_init(Default::default).clone()
+    }
+
+    /// This engine's limits.
+    pub fn limits(&self) -> &ScriptLimits {
+        &self.limits
+    }
+
+    fn compile(&self, source: &str) -> Result<AST, ScriptError> {
+        self.engine
+            .compile(source)
+            .map_err(|e| ScriptError::Compile(e.to_string()))
+    }
+
+    fn eval(&self, ast: &AST, vars: Vec<(&str, Value)>) -> Result<Value, ScriptError> {
+        let mut scope = Scope::new();
+        for (name, value) in vars {
+            let value =
+                rhai::serde::to_dynamic(value).map_err(|e| ScriptError::Runtime(e.to_string()))?;
+            scope.push_dynamic(name, value);
+        }
+
+        let previous = DEADLINE.with(|d| d.replace(Some(Instant::now() + self.limits.timeout)));
+        let result = self.engine.eval_ast_with_scope::<Dynamic>(&mut scope, ast);
+        DEADLINE.with(|d| d.set(previous));
+
+        match result {
+            Ok(value) => rhai::serde::from_dynamic(&value).map_err(|e| ScriptError::Type {
+                expected: "a JSON-compatible value",
+                found: e.to_string(),
+            }),
+            Err(err) => match *err {
+                EvalAltResult::ErrorTerminated(ref token, _)
+                    if token.clone().into_string().ok().as_deref() == Some(TIMEOUT_TOKEN) =>
+                {
+                    Err(ScriptError::Timeout(self.limits.timeout))
+                }
+                err => Err(ScriptError::Runtime(err.to_string())),
+            },
+        }
+    }
+}
+
+/// A compiled script.
+#[derive(Clone)]
+pub struct Script {
+    source: Arc<str>,
+    ast: Arc<AST>,
+    engine: Arc<ScriptEngine>,
+}
+
+impl std::fmt::Debug for Script {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Script")
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl Script {
+    /// Compile `source` with the shared engine and default limits.
+    pub fn compile(source: &str) -> Result<Self, ScriptError> {
+        Self::with_engine(ScriptEngine::shared(), source)
+    }
+
+    /// Compile `source` for `engine`.
+    pub fn with_engine(engine: Arc<ScriptEngine>, source: &str) -> Result<Self, ScriptError> {
+        Ok(Self {
+            ast: Arc::new(engine.compile(source)?),
+            source: source.into(),
+            engine,
+        })
+    }
+
+    /// Compile `source` with the shared engine, reusing earlier compilations.
+    pub fn cached(source: &str) -> Result<Self, ScriptError> {
+        static CACHE: OnceLock<Mutex<HashMap<String, Script>>> = OnceLock::new();
+        let cache = CACHE.get_or_init(Default::default);
+        if let Some(script) = cache.lock().ok().and_then(|c| c.get(source).cloned()) {
+            return Ok(script);
+        }
+        let script = Self::compile(source)?;
+        if let Ok(mut cache) = cache.lock() {
+            cache.insert(source.to_string(), script.clone());
+        }
+        Ok(script)
+    }
+
+    /// The script's source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate with `vars` in scope and return the script's value.
+    pub fn eval(&self, vars: Vec<(&str, Value)>) -> Result<Value, ScriptError> {
+        self.engine.eval(&self.ast, vars)
+    }
+
+    /// Evaluate a script that must return a boolean.
+    pub fn eval_bool(&self, vars: Vec<(&str, Value)>) -> Result<bool, ScriptError> {
+        match self.eval(vars)? {
+            Value::Bool(b) => Ok(b),
+            other => Err(ScriptError::Type {
+                expected: "a boolean",
+                found: other.to_string(),
+            }),
+        }
+    }
+}
+
+/// A script as written in YAML configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptConfig {
+    /// Rhai source.
+    pub script: String,
+    /// Wall-clock limit in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Operation limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_operations: Option<u64>,
+}
+
+impl ScriptConfig {
+    /// Compile the script, with its own engine when it overrides a limit.
+    pub fn compile(&self) -> Result<Script, ScriptError> {
+        if self.timeout_ms.is_none() && self.max_operations.is_none() {
+            return Script::compile(&self.script);
+        }
+        let defaults = ScriptLimits::default();
+        let limits = ScriptLimits {
+            timeout: self
+                .timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+            max_operations: self.max_operations.unwrap_or(defaults.max_operations),
+            ..defaults
+        };
+        Script::with_engine(Arc::new(ScriptEngine::new(limits)), &self.script)
+    }
+
+    /// Compile the script as a task guardrail; see [`guardrail`].
+    pub fn guardrail(&self) -> Result<GuardrailFn, ScriptError> {
+        self.compile().map(guardrail)
+    }
+
+    /// Compile the script as a conditional-task condition; see [`condition`].
+    pub fn condition(&self) -> Result<ConditionFn, ScriptError> {
+        self.compile().map(condition)
+    }
+}
+
+/// Variables a task output exposes to scripts: `output` (raw text),
+/// `json` (parsed JSON output or `()`), `agent` and `description`.
+fn task_output_vars(output: &TaskOutput) -> Vec<(&'static str, Value)> {
+    let json = output
+        .json_dict
+        .as_ref()
+        .map(|d| serde_json::to_value(d).unwrap_or_default())
+        .or_else(|| output.pydantic.clone())
+        .unwrap_or(Value::Null);
+    vec![
+        ("output", Value::String(output.raw.clone())),
+        ("json", json),
+        ("agent", Value::String(output.agent.clone())),
+        ("description", Value::String(output.description.clone())),
+    ]
+}
+
+/// A task guardrail running `script` over the task output.
+///
+/// The script returns `true` to accept the output, `false` to reject it,
+/// or a string to reject it with that feedback for the agent's retry.
+/// Script errors reject the output.
+pub fn guardrail(script: Script) -> GuardrailFn {
+    Box::new(
+        move |output: &TaskOutput| match script.eval(task_output_vars(output)) {
+            Ok(Value::Bool(true)) => (true, output.raw.clone()),
+            Ok(Value::Bool(false)) => (false, "Output rejected by guardrail script".to_string()),
+            Ok(Value::String(feedback)) => (false, feedback),
+            Ok(other) => (
+                false,
+                ScriptError::Type {
+                    expected: "a boolean or string",
+                    found: other.to_string(),
+                }
+                .to_string(),
+            ),
+            Err(e) => (false, e.to_string()),
+        },
+    )
+}
+
+/// A conditional-task condition running `script` over the previous task's
+/// output. The script returns a boolean; errors count as `false`.
+pub fn condition(script: Script) -> ConditionFn {
+    Box::new(
+        move |output: &TaskOutput| match script.eval_bool(task_output_vars(output)) {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Task condition script failed: {}", e);
+                false
+            }
+        },
+    )
+}
+
+/// Evaluate a policy condition script with `value` (the condition key's
+/// context value, or `()`) and `context` (the whole request context) in
+/// scope. Errors count as not matching.
+pub fn policy_condition_matches(
+    source: &str,
+    value: Option<&Value>,
+    context: &HashMap<String, Value>,
+) -> bool {
+    let result = Script::cached(source).and_then(|script| {
+        script.eval_bool(vec![
+            ("value", value.cloned().unwrap_or(Value::Null)),
+            ("context", serde_json::to_value(context).unwrap_or_default()),
+        ])
+    });
+    result.unwrap_or_else(|e| {
+        log::warn!("Policy condition script failed: {}", e);
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::output_format::OutputFormat;
+
+    fn output(raw: &str) -> TaskOutput {
+        TaskOutput {
+            description: "Write a haiku".to_string(),
+            name: None,
+            expected_output: None,
+            summary: None,
+            raw: raw.to_string(),
+            pydantic: None,
+            json_dict: Some(HashMap::from([("score".to_string(), Value::from(7))])),
+            agent: "Poet".to_string(),
+            output_format: OutputFormat::Raw,
+            messages: Vec::new(),
+            artifacts: Vec::new(),
+            assertions: Vec::new(),
+            guardrail_reports: Vec::new(),
+            token_usage: None,
+            execution_steps: Vec::new(),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn test_guardrail_and_condition_from_yaml() {
+        let config: ScriptConfig = serde_yaml::from_str(
+            "script: |\n  if output.len() > 10 { \"Too long\" } else { json.score > 5 }\n",
+        )
+        .unwrap();
+        let guardrail = config.guardrail().unwrap();
+        assert_eq!(guardrail(&output("short")), (true, "short".to_string()));
+        assert_eq!(
+            guardrail(&output("far too long")),
+            (false, "Too long".to_string())
+        );
+
+        let condition = ScriptConfig {
+            script: "agent == \"Poet\" && description.contains(\"haiku\")".to_string(),
+            timeout_ms: None,
+            max_operations: None,
+        }
+        .condition()
+        .unwrap();
+        assert!(condition(&output("x")));
+
+        let err = Script::compile("let x = ;").unwrap_err();
+        assert!(matches!(err, ScriptError::Compile(_)));
+    }
+
+    #[test]
+    fn test_scripts_are_sandboxed_and_time_limited() {
+        let config = ScriptConfig {
+            script: "loop {}".to_string(),
+            timeout_ms: Some(50),
+            max_operations: Some(u64::MAX),
+        };
+        let started = Instant::now();
+        let err = config.compile().unwrap().eval(Vec::new()).unwrap_err();
+        assert!(matches!(err, ScriptError::Timeout(_)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let err = Script::compile("let x = 0; loop { x += 1; }")
+            .unwrap()
+            .eval(Vec::new())
+            .unwrap_err();
+        assert!(matches!(err, ScriptError::Runtime(_)));
+
+        assert!(Script::compile("import \"os\" as os; 1")
+            .unwrap()
+            .eval(Vec::new())
+            .is_err());
+        assert!(Script::compile("eval(\"1\")").is_err());
+    }
+
+    #[test]
+    fn test_policy_condition_script() {
+        use crate::policy::{
+            ConditionOperator, EnforcementMode, PolicyAction, PolicyCondition, PolicyEffect,
+            PolicyEngine, PolicyRequest, PolicyResource, PolicyRule,
+        };
+
+        let rules: Vec<PolicyRule> = serde_yaml::from_str(
+            r#"
+- name: large-payments
+  effect: deny
+  principal: all
+  action: any
+  resource: any
+  conditions:
+    - key: amount
+      operator: script
+      value: "value > 1000 && context.currency == \"EUR\""
+"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            rules[0].conditions[0],
+            PolicyCondition {
+                operator: ConditionOperator::Script,
+                ..
+            }
+        ));
+        let mut engine = PolicyEngine::with_rules(rules, EnforcementMode::Strict);
+        let request = |amount: i64| PolicyRequest {
+            agent_slot: 0,
+            agent_id: "payer".to_string(),
+            agent_roles: Vec::new(),
+            action: PolicyAction::Custom("pay".to_string()),
+            resource: PolicyResource::Any,
+            context: HashMap::from([
+                ("amount".to_string(), Value::from(amount)),
+                ("currency".to_string(), Value::from("EUR")),
+            ]),
+        };
+        assert_eq!(engine.evaluate(&request(5000)).effect, PolicyEffect::Deny);
+        assert_eq!(engine.evaluate(&request(10)).effect, PolicyEffect::Allow);
+    }
+}
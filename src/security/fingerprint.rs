@@ -1,13 +1,25 @@
 //! Fingerprint identity tracking.
 //!
 //! Corresponds to `crewai/security/fingerprint.py`.
+//!
+//! Agents, tasks and crews carry a definition fingerprint
+//! ([`Fingerprint::of_definition`]): a SHA-256 hash of what defines them
+//! (an agent's role, goal, backstory and model; a task's description,
+//! expected output and agent; a crew's name, process, agents and tasks) and
+//! a UUID derived from that hash. Identical definitions get the same
+//! fingerprint in every process, so runs can be attributed to a crew
+//! definition and deduplicated. Entities stamp their fingerprint on the
+//! events they emit ([`Fingerprint::stamp`]), and the flight recorder
+//! stores the crew's with each execution.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use uuid::Uuid;
 
 use super::constants::crew_ai_namespace;
+use crate::events::BaseEvent;
 
 /// Maximum metadata size in bytes (10KB).
 const MAX_METADATA_SIZE: usize = 10 * 1024;
@@ -22,6 +34,9 @@ pub struct Fingerprint {
     /// Optional metadata (max 10KB, depth limit 1).
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// SHA-256 of the entity definition, for definition fingerprints.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
 }
 
 impl Fingerprint {
@@ -40,7 +55,62 @@ impl Fingerprint {
             uuid_str,
             created_at: Utc::now(),
             metadata,
+            hash: None,
+        }
+    }
+
+    /// Fingerprint of an entity definition: the SHA-256 `hash` of `kind`
+    /// and `definition`, and a UUID derived from the hash.
+    pub fn of_definition(kind: &str, definition: &serde_json::Value) -> Self {
+        let hash = hex::encode(Sha256::digest(format!("{}:{}", kind, definition)));
+        Self {
+            uuid_str: Self::generate_uuid(&hash),
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+            hash: Some(hash),
+        }
+    }
+
+    /// Hash of the definition, for definition fingerprints.
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// Replace a definition fingerprint whose definition changed, keeping
+    /// its metadata. Assigned fingerprints (without a hash) and unchanged
+    /// ones, with their creation time, are kept.
+    pub fn update_definition(&mut self, fingerprint: Fingerprint) {
+        if self.hash.is_none() || self.hash == fingerprint.hash {
+            return;
+        }
+        let metadata = std::mem::take(&mut self.metadata);
+        *self = Self {
+            metadata,
+            ..fingerprint
+        };
+    }
+
+    /// Metadata attached to stamped events: the hash, creation time and
+    /// user metadata.
+    pub fn event_metadata(&self) -> HashMap<String, serde_json::Value> {
+        let mut metadata = self.metadata.clone();
+        metadata.insert(
+            "created_at".to_string(),
+            serde_json::Value::String(self.created_at.to_rfc3339()),
+        );
+        if let Some(hash) = &self.hash {
+            metadata.insert("hash".to_string(), serde_json::Value::String(hash.clone()));
         }
+        metadata
+    }
+
+    /// Stamp `event` with this fingerprint as its source's, unless it
+    /// already carries one.
+    pub fn stamp<'a, E: BaseEvent + ?Sized>(&self, event: &'a mut E) -> &'a mut E {
+        if event.source_fingerprint().is_none() {
+            event.set_source_fingerprint(Some(self.uuid_str.clone()), Some(self.event_metadata()));
+        }
+        event
     }
 
     /// Generate a deterministic UUID from seed using uuid5.
@@ -100,11 +170,16 @@ impl Fingerprint {
             .get("metadata")
             .and_then(|v| serde_json::from_value(v.clone()).ok())
             .unwrap_or_default();
+        let hash = data
+            .get("hash")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
 
         Ok(Self {
             uuid_str,
             created_at,
             metadata,
+            hash,
         })
     }
 
@@ -123,6 +198,9 @@ impl Fingerprint {
             "metadata".to_string(),
             serde_json::to_value(&self.metadata).unwrap_or_default(),
         );
+        if let Some(hash) = &self.hash {
+            map.insert("hash".to_string(), serde_json::Value::String(hash.clone()));
+        }
         map
     }
 }
@@ -152,3 +230,78 @@ impl std::hash::Hash for Fingerprint {
         self.uuid_str.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::core::Agent;
+    use crate::crew::Crew;
+    use crate::events::types::crew_events::CrewKickoffStartedEvent;
+    use crate::security::SecurityConfig;
+    use crate::task::Task;
+
+    fn crew(goal: &str) -> Crew {
+        let mut task = Task::new("Research {topic}".into(), "Notes".into());
+        task.agent = Some("Researcher".into());
+        let agent = Agent::new("Researcher".into(), goal.into(), "Curious".into());
+        Crew::with_agents(vec![task], vec![agent])
+    }
+
+    #[test]
+    fn test_definition_fingerprints_are_stable() {
+        let (mut a, mut b) = (crew("Find facts"), crew("Find facts"));
+        a.refresh_fingerprints();
+        b.refresh_fingerprints();
+        assert_eq!(a.security_config.fingerprint, b.security_config.fingerprint);
+        assert_eq!(
+            a.tasks[0].security_config.fingerprint,
+            b.tasks[0].security_config.fingerprint
+        );
+        assert!(a.security_config.fingerprint.hash().is_some());
+        assert_ne!(a.id, b.id);
+
+        // Interpolated inputs are not part of the definition.
+        let before = a.tasks[0].security_config.fingerprint.clone();
+        a.tasks[0]
+            .interpolate_inputs(&HashMap::from([("topic".into(), "bees".into())]))
+            .unwrap();
+        a.tasks[0].refresh_fingerprint();
+        assert_eq!(a.tasks[0].security_config.fingerprint, before);
+
+        // A changed definition changes the fingerprint; metadata is kept.
+        let mut c = crew("Find rumours");
+        c.security_config
+            .fingerprint
+            .metadata
+            .insert("team".into(), "ops".into());
+        c.refresh_fingerprints();
+        assert_ne!(c.security_config.fingerprint, a.security_config.fingerprint);
+        assert_eq!(c.security_config.fingerprint.metadata["team"], "ops");
+
+        // Assigned fingerprints are kept.
+        let assigned = Fingerprint::generate(Some("my-crew"), None);
+        c.security_config = SecurityConfig::with_fingerprint(assigned.clone());
+        c.tasks[0].description = "Something else".into();
+        c.refresh_fingerprints();
+        assert_eq!(c.security_config.fingerprint, assigned);
+
+        let restored = Fingerprint::from_dict(&before.to_dict()).unwrap();
+        assert_eq!(restored.hash(), before.hash());
+    }
+
+    #[test]
+    fn test_stamp_sets_event_source_fingerprint() {
+        let fingerprint = crew("Find facts").security_config.fingerprint;
+        let mut event = CrewKickoffStartedEvent::new(None, None);
+        fingerprint.stamp(&mut event);
+        assert_eq!(event.source_fingerprint(), Some(fingerprint.uuid_str()));
+        let metadata = event.fingerprint_metadata().unwrap();
+        assert_eq!(metadata["hash"], fingerprint.hash().unwrap());
+        assert!(metadata.contains_key("created_at"));
+
+        // An event that already names its source keeps it.
+        let other = Fingerprint::default();
+        other.stamp(&mut event);
+        assert_eq!(event.source_fingerprint(), Some(fingerprint.uuid_str()));
+    }
+}
@@ -4,7 +4,11 @@
 
 pub mod constants;
 pub mod fingerprint;
+pub mod provenance;
+pub mod secrets;
 pub mod security_config;
 
 pub use fingerprint::Fingerprint;
+pub use provenance::{ProvenanceConfig, ProvenanceManifest};
+pub use secrets::{SecretString, SecretsError, SecretsProvider};
 pub use security_config::SecurityConfig;
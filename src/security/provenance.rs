@@ -0,0 +1,301 @@
+//! Provenance manifests for generated outputs.
+//!
+//! A C2PA-style claim describing how an output was produced: when, by
+//! which crew (and its fingerprint), with which models and tools, and from
+//! which inputs. Manifests are written as detached JSON sidecars and can be
+//! embedded in Markdown/HTML outputs as a comment block, to support
+//! downstream AI-content disclosure requirements.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::crew::Crew;
+
+/// Manifest format version.
+pub const MANIFEST_VERSION: &str = "1.0";
+
+/// Opening marker of an embedded manifest comment block.
+const EMBED_START: &str = "<!-- crewai-provenance";
+/// Closing marker of an embedded manifest comment block.
+const EMBED_END: &str = "-->";
+
+/// Options controlling how provenance is attached to crew outputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceConfig {
+    /// Write `<output_file>.provenance.json` next to each task output file.
+    #[serde(default = "default_true")]
+    pub sidecar: bool,
+    /// Embed the manifest as a comment block in Markdown/HTML output files.
+    #[serde(default)]
+    pub embed: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ProvenanceConfig {
+    fn default() -> Self {
+        Self {
+            sidecar: true,
+            embed: false,
+        }
+    }
+}
+
+/// Provenance claim for a generated output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceManifest {
+    /// Manifest format version.
+    pub manifest_version: String,
+    /// Software that generated the output.
+    pub claim_generator: String,
+    /// When the output was generated.
+    pub generated_at: DateTime<Utc>,
+    /// Whether the content was produced by an AI system.
+    pub ai_generated: bool,
+    /// ID of the crew that produced the output.
+    pub crew_id: String,
+    /// Name of the crew, if set.
+    pub crew_name: Option<String>,
+    /// The crew's security fingerprint.
+    pub crew_fingerprint: String,
+    /// Hash of the kickoff inputs (`sha256:<hex>`), if any were given.
+    pub input_hash: Option<String>,
+    /// Hash of the output content (`sha256:<hex>`).
+    pub output_hash: String,
+    /// Models used by the crew's agents, manager and function calling.
+    pub models: Vec<String>,
+    /// Tools available to the crew's agents and tasks.
+    pub tools: Vec<String>,
+    /// Roles of the agents in the crew.
+    pub agents: Vec<String>,
+}
+
+impl ProvenanceManifest {
+    /// Build a manifest for `output` produced by `crew` from `inputs`.
+    pub fn for_crew(crew: &Crew, inputs: Option<&HashMap<String, String>>, output: &str) -> Self {
+        let mut models = BTreeSet::new();
+        let mut tools = BTreeSet::new();
+        for agent_lock in crew.agent_objects.values() {
+            if let Ok(agent) = agent_lock.read() {
+                models.extend(agent.llm.clone());
+                models.extend(agent.function_calling_llm.clone());
+                tools.extend(agent.tools.iter().cloned());
+            }
+        }
+        models.extend(crew.manager_llm.clone());
+        models.extend(crew.function_calling_llm.clone());
+        for task in &crew.tasks {
+            tools.extend(task.tools.iter().cloned());
+        }
+
+        Self {
+            manifest_version: MANIFEST_VERSION.to_string(),
+            claim_generator: format!("crewai-rust/{}", env!("CARGO_PKG_VERSION")),
+            generated_at: Utc::now(),
+            ai_generated: true,
+            crew_id: crew.id.to_string(),
+            crew_name: crew.name.clone(),
+            crew_fingerprint: crew.security_config.fingerprint.uuid_str().to_string(),
+            input_hash: inputs.map(hash_inputs),
+            output_hash: hash_content(output),
+            models: models.into_iter().collect(),
+            tools: tools.into_iter().collect(),
+            agents: crew.agents.clone(),
+        }
+    }
+
+    /// The same claim, re-targeted at different content.
+    pub fn for_content(&self, content: &str) -> Self {
+        Self {
+            output_hash: hash_content(content),
+            ..self.clone()
+        }
+    }
+
+    /// Whether `content` is the content this manifest was issued for.
+    pub fn verify(&self, content: &str) -> bool {
+        self.output_hash == hash_content(content)
+    }
+
+    /// Serialize the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Write the manifest as a sidecar next to `output_path`.
+    ///
+    /// Returns the sidecar path (`<output_path>.provenance.json`).
+    pub fn write_sidecar(&self, output_path: &Path) -> Result<PathBuf, anyhow::Error> {
+        let path = sidecar_path(output_path);
+        std::fs::write(&path, self.to_json()?)?;
+        Ok(path)
+    }
+
+    /// Append the manifest to Markdown/HTML content as a comment block.
+    ///
+    /// Any previously embedded manifest is replaced.
+    pub fn embed(&self, content: &str) -> Result<String, serde_json::Error> {
+        let (content, _) = strip_embedded(content);
+        // `--` cannot appear inside an HTML comment; it only occurs within
+        // JSON strings, where `-` is an equivalent escape.
+        let json = self.to_json()?.replace("--", "-\\u002d");
+        Ok(format!(
+            "{}\n\n{}\n{}\n{}\n",
+            content.trim_end(),
+            EMBED_START,
+            json,
+            EMBED_END
+        ))
+    }
+
+    /// Extract an embedded manifest, returning it with the content it covers.
+    pub fn extract(content: &str) -> Option<(Self, String)> {
+        let (stripped, json) = strip_embedded(content);
+        let manifest = serde_json::from_str(json?).ok()?;
+        Some((manifest, stripped))
+    }
+
+    /// Attach the manifest to an output file according to `config`.
+    ///
+    /// Embedding applies to `.md`, `.markdown`, `.html` and `.htm` files.
+    /// The manifests hash the file's own content: the raw file, or, when
+    /// embedding, the content [`ProvenanceManifest::extract`] returns.
+    pub fn attach_to_file(
+        &self,
+        path: &Path,
+        config: &ProvenanceConfig,
+    ) -> Result<(), anyhow::Error> {
+        let content = std::fs::read_to_string(path)?;
+        let manifest = if config.embed && supports_embedding(path) {
+            let (content, _) = strip_embedded(&content);
+            let content = content.trim_end();
+            let manifest = self.for_content(content);
+            std::fs::write(path, manifest.embed(content)?)?;
+            manifest
+        } else {
+            self.for_content(&content)
+        };
+        if config.sidecar {
+            manifest.write_sidecar(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sidecar path for an output file: `<output_path>.provenance.json`.
+pub fn sidecar_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.as_os_str().to_owned();
+    name.push(".provenance.json");
+    PathBuf::from(name)
+}
+
+/// SHA-256 of content, formatted as `sha256:<hex>`.
+pub fn hash_content(content: &str) -> String {
+    format!("sha256:{}", hex::encode(Sha256::digest(content.as_bytes())))
+}
+
+/// Hash inputs independently of map iteration order.
+fn hash_inputs(inputs: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<&String, &String> = inputs.iter().collect();
+    hash_content(&serde_json::to_string(&sorted).unwrap_or_default())
+}
+
+fn supports_embedding(path: &Path) -> bool {
+    matches!(
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref(),
+        Some("md" | "markdown" | "html" | "htm")
+    )
+}
+
+/// Split content into (content without manifest block, manifest JSON).
+fn strip_embedded(content: &str) -> (String, Option<&str>) {
+    let Some(start) = content.rfind(EMBED_START) else {
+        return (content.to_string(), None);
+    };
+    let body = &content[start + EMBED_START.len()..];
+    let Some(end) = body.find(EMBED_END) else {
+        return (content.to_string(), None);
+    };
+    let stripped = content[..start].trim_end().to_string();
+    (stripped, Some(body[..end].trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task;
+
+    fn sample_manifest() -> ProvenanceManifest {
+        let mut crew = Crew::new(
+            vec![Task::new("Write a report".into(), "A report".into())],
+            vec!["writer".into()],
+        );
+        crew.manager_llm = Some("openai/gpt-4o".into());
+        let inputs = HashMap::from([
+            ("topic".to_string(), "rust".to_string()),
+            ("year".to_string(), "2026".to_string()),
+        ]);
+        ProvenanceManifest::for_crew(&crew, Some(&inputs), "# Report -- draft")
+    }
+
+    #[test]
+    fn test_manifest_for_crew() {
+        let manifest = sample_manifest();
+        assert!(manifest.ai_generated);
+        assert_eq!(manifest.models, vec!["openai/gpt-4o".to_string()]);
+        assert_eq!(manifest.agents, vec!["writer".to_string()]);
+        assert!(manifest.verify("# Report -- draft"));
+        assert!(!manifest.verify("# Report"));
+
+        let reordered = HashMap::from([
+            ("year".to_string(), "2026".to_string()),
+            ("topic".to_string(), "rust".to_string()),
+        ]);
+        assert_eq!(manifest.input_hash, Some(hash_inputs(&reordered)));
+    }
+
+    #[test]
+    fn test_embed_roundtrip() {
+        let manifest = sample_manifest();
+        let embedded = manifest.embed("# Report -- draft\n").unwrap();
+        let block = &embedded[embedded.find(EMBED_START).unwrap() + EMBED_START.len()..];
+        assert!(!block.trim_end().trim_end_matches(EMBED_END).contains("--"));
+
+        let (extracted, content) = ProvenanceManifest::extract(&embedded).unwrap();
+        assert_eq!(extracted, manifest);
+        assert_eq!(content, "# Report -- draft");
+        assert!(extracted.verify(&content));
+
+        // Re-embedding replaces the previous block.
+        let again = manifest.embed(&embedded).unwrap();
+        assert_eq!(again.matches(EMBED_START).count(), 1);
+    }
+
+    #[test]
+    fn test_attach_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.md");
+        std::fs::write(&path, "# Final report\n").unwrap();
+
+        let config = ProvenanceConfig {
+            sidecar: true,
+            embed: true,
+        };
+        sample_manifest().attach_to_file(&path, &config).unwrap();
+
+        let (embedded, content) =
+            ProvenanceManifest::extract(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(embedded.verify(&content));
+        let sidecar: ProvenanceManifest =
+            serde_json::from_str(&std::fs::read_to_string(sidecar_path(&path)).unwrap()).unwrap();
+        assert_eq!(sidecar, embedded);
+    }
+}
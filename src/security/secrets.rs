@@ -0,0 +1,475 @@
+//! Secret values and the providers that resolve them.
+//!
+//! API keys and other credentials are held in a [`SecretString`], which
+//! prints and serializes as `[REDACTED]`; the value is only reachable
+//! through [`SecretString::expose_secret`]. When a key is not configured up
+//! front, LLM providers resolve it at call time from a [`SecretsProvider`]:
+//!
+//! - [`EnvSecretsProvider`] reads environment variables,
+//! - [`FileSecretsProvider`] reads one file per secret from a directory
+//!   (the Docker / Kubernetes secrets layout),
+//! - [`AwsSecretsManagerProvider`] calls AWS Secrets Manager,
+//! - [`ChainSecretsProvider`] tries several providers in order.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use crewai::llms::providers::openai::OpenAICompletion;
+//! use crewai::security::secrets::{ChainSecretsProvider, EnvSecretsProvider, FileSecretsProvider};
+//!
+//! let mut llm = OpenAICompletion::new("gpt-4o-mini", None, None);
+//! llm.state.secrets_provider = Some(Arc::new(
+//!     ChainSecretsProvider::new()
+//!         .with(EnvSecretsProvider::new())
+//!         .with(FileSecretsProvider::new("/run/secrets")),
+//! ));
+//! ```
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::llms::providers::bedrock::sigv4;
+
+/// Placeholder printed and serialized in place of a secret value.
+pub const REDACTED: &str = "[REDACTED]";
+
+// ---------------------------------------------------------------------------
+// SecretString
+// ---------------------------------------------------------------------------
+
+/// A string that must not leak through logs or serialized config.
+///
+/// `Debug`, `Display` and `Serialize` all produce [`REDACTED`]; deserializing
+/// accepts the plain value. The buffer is zeroed when the value is dropped.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a secret value.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Borrow the secret value. Call this only where the value is sent to
+    /// the service that needs it.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the secret is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        let mut bytes = std::mem::take(&mut self.0).into_bytes();
+        bytes.fill(0);
+        std::hint::black_box(&bytes);
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SecretString").field(&REDACTED).finish()
+    }
+}
+
+impl fmt::Display for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// SecretsProvider
+// ---------------------------------------------------------------------------
+
+/// Errors raised while resolving a secret.
+#[derive(Debug, Error)]
+pub enum SecretsError {
+    #[error("failed to read secret '{key}': {source}")]
+    Io {
+        key: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid secret name '{0}'")]
+    InvalidName(String),
+    #[error("{provider} request failed: {message}")]
+    Provider { provider: String, message: String },
+}
+
+/// Resolves secrets by name at call time.
+///
+/// `Ok(None)` means the provider does not have the secret, so a chain can
+/// fall through to the next provider; errors are reserved for failures.
+#[async_trait]
+pub trait SecretsProvider: Send + Sync + fmt::Debug {
+    /// Short provider name used in errors and logs.
+    fn name(&self) -> &str;
+
+    /// Look up the secret called `key`.
+    async fn get_secret(&self, key: &str) -> Result<Option<SecretString>, SecretsError>;
+}
+
+/// Reads secrets from environment variables, optionally with a prefix.
+#[derive(Debug, Clone, Default)]
+pub struct EnvSecretsProvider {
+    /// Prepended to every key (e.g. `"CREWAI_"` maps `OPENAI_API_KEY` to
+    /// `CREWAI_OPENAI_API_KEY`).
+    pub prefix: Option<String>,
+}
+
+impl EnvSecretsProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_prefix(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for EnvSecretsProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<SecretString>, SecretsError> {
+        let var = format!("{}{}", self.prefix.as_deref().unwrap_or(""), key);
+        Ok(std::env::var(var)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(SecretString::from))
+    }
+}
+
+/// Reads each secret from a file named after it in a directory, trimming a
+/// trailing newline.
+#[derive(Debug, Clone)]
+pub struct FileSecretsProvider {
+    pub dir: PathBuf,
+}
+
+impl FileSecretsProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for FileSecretsProvider {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<SecretString>, SecretsError> {
+        if key.is_empty() || key.contains(['/', '\\']) || key.starts_with('.') {
+            return Err(SecretsError::InvalidName(key.to_string()));
+        }
+        match tokio::fs::read_to_string(self.dir.join(key)).await {
+            Ok(value) => Ok(Some(SecretString::from(
+                value.trim_end_matches(['\r', '\n']).to_string(),
+            ))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(SecretsError::Io {
+                key: key.to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Fetches secrets from AWS Secrets Manager (`GetSecretValue`).
+///
+/// A key of the form `name#field` selects `field` from a secret stored as a
+/// JSON object. Credentials default to the standard `AWS_*` environment
+/// variables.
+#[derive(Debug, Clone)]
+pub struct AwsSecretsManagerProvider {
+    pub region: String,
+    pub access_key_id: Option<String>,
+    pub secret_access_key: Option<SecretString>,
+    pub session_token: Option<SecretString>,
+    /// Override the endpoint (e.g. for LocalStack); defaults to
+    /// `https://secretsmanager.<region>.amazonaws.com`.
+    pub endpoint: Option<String>,
+}
+
+impl AwsSecretsManagerProvider {
+    /// Create a provider for `region` (defaults to `AWS_REGION`,
+    /// `AWS_DEFAULT_REGION`, then `us-east-1`).
+    pub fn new(region: Option<String>) -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        Self {
+            region: region
+                .or_else(|| env("AWS_REGION"))
+                .or_else(|| env("AWS_DEFAULT_REGION"))
+                .unwrap_or_else(|| "us-east-1".to_string()),
+            access_key_id: env("AWS_ACCESS_KEY_ID"),
+            secret_access_key: env("AWS_SECRET_ACCESS_KEY").map(SecretString::from),
+            session_token: env("AWS_SESSION_TOKEN").map(SecretString::from),
+            endpoint: None,
+        }
+    }
+
+    fn host(&self) -> String {
+        format!("secretsmanager.{}.amazonaws.com", self.region)
+    }
+
+    fn error(&self, message: impl Into<String>) -> SecretsError {
+        SecretsError::Provider {
+            provider: self.name().to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// SigV4 headers for a `GetSecretValue` call with `payload`.
+    fn signed_headers(&self, payload: &[u8]) -> Result<Vec<(String, String)>, SecretsError> {
+        const SERVICE: &str = "secretsmanager";
+        let access_key = self
+            .access_key_id
+            .as_deref()
+            .ok_or_else(|| self.error("AWS_ACCESS_KEY_ID not set"))?;
+        let secret_key = self
+            .secret_access_key
+            .as_ref()
+            .ok_or_else(|| self.error("AWS_SECRET_ACCESS_KEY not set"))?;
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let scope = format!("{}/{}/{}/aws4_request", date_stamp, self.region, SERVICE);
+
+        let mut headers = vec![
+            (
+                "content-type".to_string(),
+                "application/x-amz-json-1.1".to_string(),
+            ),
+            ("host".to_string(), self.host()),
+            ("x-amz-date".to_string(), amz_date.clone()),
+            (
+                "x-amz-target".to_string(),
+                "secretsmanager.GetSecretValue".to_string(),
+            ),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push((
+                "x-amz-security-token".to_string(),
+                token.expose_secret().to_string(),
+            ));
+        }
+        headers.sort_by(|a, b| a.0.cmp(&b.0));
+        let signed: String = headers
+            .iter()
+            .map(|(k, _)| k.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical = sigv4::canonical_request(
+            "POST",
+            "/",
+            "",
+            &headers,
+            &signed,
+            &sigv4::sha256_hex(payload),
+        );
+        let sts =
+            sigv4::string_to_sign(&amz_date, &scope, &sigv4::sha256_hex(canonical.as_bytes()));
+        let key = sigv4::signing_key(
+            secret_key.expose_secret(),
+            &date_stamp,
+            &self.region,
+            SERVICE,
+        );
+        let signature = sigv4::sign_hex(&key, &sts);
+        headers.push((
+            "authorization".to_string(),
+            sigv4::authorization_header(access_key, &scope, &signed, &signature),
+        ));
+        Ok(headers)
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for AwsSecretsManagerProvider {
+    fn name(&self) -> &str {
+        "aws-secrets-manager"
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<SecretString>, SecretsError> {
+        let (secret_id, field) = match key.split_once('#') {
+            Some((id, field)) => (id, Some(field)),
+            None => (key, None),
+        };
+        if secret_id.is_empty() {
+            return Err(SecretsError::InvalidName(key.to_string()));
+        }
+
+        let payload = serde_json::to_vec(&serde_json::json!({ "SecretId": secret_id }))
+            .map_err(|e| self.error(e.to_string()))?;
+        let endpoint = self
+            .endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://{}", self.host()));
+        let mut request = reqwest::Client::new().post(&endpoint);
+        for (name, value) in self.signed_headers(&payload)? {
+            request = request.header(name, value);
+        }
+        let response = request
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| self.error(e.to_string()))?;
+
+        let status = response.status();
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| self.error(e.to_string()))?;
+        if !status.is_success() {
+            let kind = body["__type"].as_str().unwrap_or_default();
+            if kind.ends_with("ResourceNotFoundException") {
+                return Ok(None);
+            }
+            return Err(self.error(format!("{} {}", status, kind)));
+        }
+
+        let Some(value) = body["SecretString"].as_str() else {
+            return Ok(None);
+        };
+        match field {
+            None => Ok(Some(SecretString::from(value))),
+            Some(field) => {
+                let object: serde_json::Value = serde_json::from_str(value).map_err(|_| {
+                    self.error(format!("secret '{}' is not a JSON object", secret_id))
+                })?;
+                Ok(object[field].as_str().map(SecretString::from))
+            }
+        }
+    }
+}
+
+/// Tries each provider in order and returns the first secret found.
+#[derive(Debug, Clone, Default)]
+pub struct ChainSecretsProvider {
+    pub providers: Vec<Arc<dyn SecretsProvider>>,
+}
+
+impl ChainSecretsProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a provider to the chain.
+    pub fn with(mut self, provider: impl SecretsProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+}
+
+#[async_trait]
+impl SecretsProvider for ChainSecretsProvider {
+    fn name(&self) -> &str {
+        "chain"
+    }
+
+    async fn get_secret(&self, key: &str) -> Result<Option<SecretString>, SecretsError> {
+        for provider in &self.providers {
+            if let Some(secret) = provider.get_secret(key).await? {
+                return Ok(Some(secret));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_string_is_redacted() {
+        let secret = SecretString::from("sk-live-123");
+        assert_eq!(format!("{}", secret), REDACTED);
+        assert!(!format!("{:?}", secret).contains("sk-live"));
+        assert_eq!(
+            serde_json::to_string(&secret).unwrap(),
+            format!("\"{}\"", REDACTED)
+        );
+        let parsed: SecretString = serde_json::from_str("\"sk-live-123\"").unwrap();
+        assert_eq!(parsed.expose_secret(), "sk-live-123");
+    }
+
+    #[tokio::test]
+    async fn test_file_and_chain_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("OPENAI_API_KEY"), "sk-file\n").unwrap();
+
+        let chain = ChainSecretsProvider::new()
+            .with(EnvSecretsProvider::with_prefix("CREWAI_SECRETS_TEST_"))
+            .with(FileSecretsProvider::new(dir.path()));
+        let key = chain.get_secret("OPENAI_API_KEY").await.unwrap().unwrap();
+        assert_eq!(key.expose_secret(), "sk-file");
+        assert!(chain.get_secret("MISSING_KEY").await.unwrap().is_none());
+        assert!(matches!(
+            chain.get_secret("../etc/passwd").await,
+            Err(SecretsError::InvalidName(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_llm_state_redacts_and_resolves_key() {
+        use crate::llms::base_llm::BaseLLMState;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("team-key"), "sk-resolved").unwrap();
+
+        let mut state = BaseLLMState::new("gpt-4o-mini");
+        state.secrets_provider = Some(Arc::new(FileSecretsProvider::new(dir.path())));
+        state.api_key_secret = Some("team-key".to_string());
+        let key = state.resolve_api_key("OPENAI_API_KEY").await.unwrap();
+        assert_eq!(key.unwrap().expose_secret(), "sk-resolved");
+
+        state.api_key = Some(SecretString::from("sk-configured"));
+        assert!(!format!("{:?}", state).contains("sk-configured"));
+        assert!(!serde_json::to_string(&state)
+            .unwrap()
+            .contains("sk-configured"));
+        let key = state.resolve_api_key("OPENAI_API_KEY").await.unwrap();
+        assert_eq!(key.unwrap().expose_secret(), "sk-configured");
+    }
+}
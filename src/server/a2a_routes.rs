@@ -7,20 +7,40 @@
 //!
 //! Supported JSON-RPC methods:
 //! - `message/send`   — Send a message, get a task back
+//! - `message/stream` — Send a message, get task updates as server-sent events
 //! - `tasks/get`      — Get task status by ID
 //! - `tasks/cancel`   — Cancel a running task
+//!
+//! Crews registered in [`A2AState::crews`] are exposed as skills in the
+//! agent card, described from their current configuration (see
+//! [`crate::a2a::card`]). A message delegates to the crew named by its `skill_id`
+//! metadata (or the only registered crew): the message text is passed as the
+//! `message` input, `data` parts as further inputs, and the crew's output
+//! comes back as the task's artifact. Without registered crews messages are
+//! acknowledged only.
+//!
+//! With [`A2AState::auth`] set, `POST /a2a` requires credentials (the agent
+//! card stays public and advertises the scheme). With [`A2AState::quotas`]
+//! set, delegated runs count against the caller's tenant quota like kickoffs
+//! over the crew routes.
 
 use std::collections::HashMap;
+use std::convert::Infallible;
 use std::sync::{Arc, RwLock};
 
 use axum::{
     extract::State,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
 };
+use futures::StreamExt;
 use serde_json::Value;
 
+use crate::a2a::auth::ServerAuth;
+use crate::a2a::card;
 use crate::a2a::client::{
     A2AMessage, A2ATask, A2ATaskState, A2ATaskStatus, AgentCapabilities, AgentCard, AgentProvider,
     AgentSkill,
@@ -28,6 +48,12 @@ use crate::a2a::client::{
 use crate::a2a::errors::{create_error_response, A2AErrorCode};
 use crate::a2a::types::PartsDict;
 
+use super::crew_routes::{CrewRegistry, ExecutionRecord, ExecutionStatus, ExecutionStore, Update};
+use super::quotas::{QuotaError, QuotaManager};
+
+/// JSON-RPC endpoint URL advertised in the agent card by default.
+pub const DEFAULT_A2A_URL: &str = "https://crewai-rust.up.railway.app/a2a";
+
 // ---------------------------------------------------------------------------
 // State
 // ---------------------------------------------------------------------------
@@ -37,14 +63,56 @@ use crate::a2a::types::PartsDict;
 pub struct A2AState {
     /// Active tasks keyed by task ID.
     pub tasks: Arc<RwLock<HashMap<String, A2ATask>>>,
+    /// Crews remote agents can delegate to, one skill each.
+    pub crews: CrewRegistry,
+    /// Runs of delegated crews; a task's ID is its execution ID.
+    pub executions: ExecutionStore,
+    /// Credentials required on `POST /a2a`; `None` leaves it open.
+    pub auth: Option<Arc<ServerAuth>>,
+    /// JSON-RPC endpoint URL advertised in the agent card.
+    pub url: String,
+    /// Tenant quotas delegated runs are admitted against.
+    pub quotas: Option<QuotaManager>,
 }
 
 impl A2AState {
     pub fn new() -> Self {
         Self {
             tasks: Arc::new(RwLock::new(HashMap::new())),
+            crews: CrewRegistry::new(),
+            executions: ExecutionStore::new(),
+            auth: None,
+            url: DEFAULT_A2A_URL.to_string(),
+            quotas: None,
         }
     }
+
+    /// Builder: delegate to crews from `crews`, tracking runs in
+    /// `executions` (share both with the crew routes).
+    pub fn with_crews(mut self, crews: CrewRegistry, executions: ExecutionStore) -> Self {
+        self.crews = crews;
+        self.executions = executions;
+        self
+    }
+
+    /// Builder: require credentials on the JSON-RPC endpoint.
+    pub fn with_auth(mut self, auth: ServerAuth) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
+
+    /// Builder: admit delegated runs against tenant quotas (share the
+    /// manager with the crew routes).
+    pub fn with_quotas(mut self, quotas: QuotaManager) -> Self {
+        self.quotas = Some(quotas);
+        self
+    }
+
+    /// Builder: set the endpoint URL advertised in the agent card.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
 }
 
 impl Default for A2AState {
@@ -70,9 +138,28 @@ pub fn a2a_router(state: A2AState) -> Router {
 // ---------------------------------------------------------------------------
 
 /// Serve the agent card for discovery.
-async fn agent_card_handler() -> impl IntoResponse {
-    let card = build_agent_card();
-    Json(serde_json::to_value(card).unwrap_or_default())
+async fn agent_card_handler(State(state): State<A2AState>) -> impl IntoResponse {
+    Json(serde_json::to_value(agent_card(&state)).unwrap_or_default())
+}
+
+/// The agent card served by `state`. Skills of registered crews are
+/// derived from a freshly built crew each time, so the card reflects the
+/// current configuration.
+pub fn agent_card(state: &A2AState) -> AgentCard {
+    let mut card = build_agent_card();
+    card.url = state.url.clone();
+    card.capabilities.streaming = true;
+    for name in state.crews.names() {
+        if let Ok(crew) = state.crews.build(&name) {
+            let description = state.crews.description(&name);
+            card.skills
+                .push(card::crew_skill(&name, &crew, description));
+        }
+    }
+    if let Some(auth) = &state.auth {
+        card.security_schemes.push(auth.security_scheme());
+    }
+    card
 }
 
 fn build_agent_card() -> AgentCard {
@@ -83,7 +170,7 @@ fn build_agent_card() -> AgentCard {
              triune persona, Markov gating, and modular crew execution."
                 .to_string(),
         ),
-        url: "https://crewai-rust.up.railway.app/a2a".to_string(),
+        url: DEFAULT_A2A_URL.to_string(),
         version: Some(crate::VERSION.to_string()),
         capabilities: AgentCapabilities {
             streaming: false,
@@ -105,6 +192,7 @@ fn build_agent_card() -> AgentCard {
                     "agent".to_string(),
                     "execution".to_string(),
                 ],
+                rate_limits: None,
             },
             AgentSkill {
                 id: "barrier.check".to_string(),
@@ -122,6 +210,7 @@ fn build_agent_card() -> AgentCard {
                     "triune".to_string(),
                     "safety".to_string(),
                 ],
+                rate_limits: None,
             },
             AgentSkill {
                 id: "barrier.topology".to_string(),
@@ -136,6 +225,7 @@ fn build_agent_card() -> AgentCard {
                     "triune".to_string(),
                     "topology".to_string(),
                 ],
+                rate_limits: None,
             },
             AgentSkill {
                 id: "chat".to_string(),
@@ -146,6 +236,7 @@ fn build_agent_card() -> AgentCard {
                 input_modes: vec!["text/plain".to_string()],
                 output_modes: vec!["text/plain".to_string()],
                 tags: vec!["chat".to_string(), "conversation".to_string()],
+                rate_limits: None,
             },
         ],
         provider: Some(AgentProvider {
@@ -165,10 +256,32 @@ fn build_agent_card() -> AgentCard {
 
 async fn jsonrpc_handler(
     State(state): State<A2AState>,
+    headers: HeaderMap,
     Json(body): Json<Value>,
-) -> impl IntoResponse {
+) -> Response {
     let request_id = body.get("id").cloned();
 
+    if let Some(auth) = &state.auth {
+        if !auth.verify(&headers) {
+            let mut response = (
+                StatusCode::UNAUTHORIZED,
+                Json(create_error_response(
+                    A2AErrorCode::AuthenticationRequired,
+                    Some("Missing or invalid credentials"),
+                    None,
+                    request_id,
+                )),
+            )
+                .into_response();
+            if let Ok(challenge) = auth.challenge().parse() {
+                response
+                    .headers_mut()
+                    .insert(header::WWW_AUTHENTICATE, challenge);
+            }
+            return response;
+        }
+    }
+
     // Validate JSON-RPC envelope
     let jsonrpc = body.get("jsonrpc").and_then(|v| v.as_str()).unwrap_or("");
     if jsonrpc != "2.0" {
@@ -177,7 +290,8 @@ async fn jsonrpc_handler(
             Some("Missing or invalid jsonrpc version — expected \"2.0\""),
             None,
             request_id,
-        ));
+        ))
+        .into_response();
     }
 
     let method = match body.get("method").and_then(|v| v.as_str()) {
@@ -188,7 +302,8 @@ async fn jsonrpc_handler(
                 Some("Missing \"method\" field"),
                 None,
                 request_id,
-            ));
+            ))
+            .into_response();
         }
     };
 
@@ -198,15 +313,19 @@ async fn jsonrpc_handler(
         .unwrap_or(Value::Object(Default::default()));
 
     match method {
-        "message/send" => handle_message_send(&state, params, request_id),
-        "tasks/get" => handle_tasks_get(&state, params, request_id),
-        "tasks/cancel" => handle_tasks_cancel(&state, params, request_id),
+        "message/send" => handle_message_send(&state, &headers, params, request_id)
+            .await
+            .into_response(),
+        "message/stream" => handle_message_stream(&state, &headers, params, request_id),
+        "tasks/get" => handle_tasks_get(&state, params, request_id).into_response(),
+        "tasks/cancel" => handle_tasks_cancel(&state, params, request_id).into_response(),
         _ => Json(create_error_response(
             A2AErrorCode::MethodNotFound,
             Some(&format!("Unknown method: {}", method)),
             None,
             request_id,
-        )),
+        ))
+        .into_response(),
     }
 }
 
@@ -214,65 +333,102 @@ async fn jsonrpc_handler(
 // method: message/send
 // ---------------------------------------------------------------------------
 
-fn handle_message_send(state: &A2AState, params: Value, request_id: Option<Value>) -> Json<Value> {
-    // Parse the message from params
-    let message = match params.get("message") {
-        Some(msg_val) => {
-            let role = msg_val
-                .get("role")
-                .and_then(|v| v.as_str())
-                .unwrap_or("user")
-                .to_string();
-            let parts = msg_val
-                .get("parts")
-                .and_then(|v| v.as_array())
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|p| {
-                            p.get("text")
-                                .and_then(|t| t.as_str())
-                                .map(|text| PartsDict {
-                                    text: text.to_string(),
-                                    metadata: None,
-                                })
-                        })
-                        .collect::<Vec<_>>()
-                })
-                .unwrap_or_default();
-            let metadata = msg_val
-                .get("metadata")
-                .and_then(|v| serde_json::from_value::<HashMap<String, Value>>(v.clone()).ok());
-
-            A2AMessage {
-                role,
-                parts,
-                metadata,
-            }
-        }
-        None => {
-            return Json(create_error_response(
-                A2AErrorCode::InvalidParams,
-                Some("Missing \"message\" in params"),
-                None,
-                request_id,
-            ));
-        }
+async fn handle_message_send(
+    state: &A2AState,
+    headers: &HeaderMap,
+    params: Value,
+    request_id: Option<Value>,
+) -> Json<Value> {
+    let message = match parse_message(&params) {
+        Ok(message) => message,
+        Err(error) => return invalid_params(error, request_id),
     };
+    if state.crews.names().is_empty() {
+        let task = acknowledge_message(state, &params, message);
+        return rpc_result(request_id, serde_json::to_value(&task).unwrap_or_default());
+    }
 
-    // Generate task ID
-    let task_id = uuid::Uuid::new_v4().to_string();
-    let context_id = params
-        .get("context_id")
+    let blocking = params
+        .pointer("/configuration/blocking")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    let (task, run) = match start_delegation(state, headers, &params, message) {
+        Ok(started) => started,
+        Err((code, error)) => return rpc_error(code, &error, request_id),
+    };
+    let task = if blocking {
+        let _ = run.await;
+        state
+            .tasks
+            .read()
+            .ok()
+            .and_then(|tasks| tasks.get(&task.id).cloned())
+            .unwrap_or(task)
+    } else {
+        task
+    };
+    rpc_result(request_id, serde_json::to_value(&task).unwrap_or_default())
+}
+
+/// Parse `params.message` into an [`A2AMessage`] (text parts only).
+fn parse_message(params: &Value) -> Result<A2AMessage, &'static str> {
+    let msg_val = params
+        .get("message")
+        .ok_or("Missing \"message\" in params")?;
+    let role = msg_val
+        .get("role")
         .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+        .unwrap_or("user")
+        .to_string();
+    let parts = msg_val
+        .get("parts")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| {
+                    p.get("text")
+                        .and_then(|t| t.as_str())
+                        .map(|text| PartsDict {
+                            text: text.to_string(),
+                            metadata: None,
+                        })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let metadata = msg_val
+        .get("metadata")
+        .and_then(|v| serde_json::from_value::<HashMap<String, Value>>(v.clone()).ok());
+
+    Ok(A2AMessage {
+        role,
+        parts,
+        metadata,
+    })
+}
 
-    // Extract the text content for processing
-    let input_text = message
+fn message_text(message: &A2AMessage) -> String {
+    message
         .parts
         .iter()
         .map(|p| p.text.as_str())
         .collect::<Vec<_>>()
-        .join("\n");
+        .join("\n")
+}
+
+fn context_id(params: &Value) -> Option<String> {
+    params
+        .get("context_id")
+        .or_else(|| params.pointer("/message/contextId"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Acknowledge a message when no crew is registered to handle it.
+fn acknowledge_message(state: &A2AState, params: &Value, message: A2AMessage) -> A2ATask {
+    // Generate task ID
+    let task_id = uuid::Uuid::new_v4().to_string();
+    let input_text = message_text(&message);
 
     // Process the message — for now, echo acknowledgment + barrier status hint
     let response_text = format!(
@@ -282,20 +438,13 @@ fn handle_message_send(state: &A2AState, params: Value, request_id: Option<Value
         &task_id[..8],
     );
 
-    let response_message = A2AMessage {
-        role: "agent".to_string(),
-        parts: vec![PartsDict {
-            text: response_text,
-            metadata: None,
-        }],
-        metadata: None,
-    };
+    let response_message = agent_message(response_text);
 
     // Build the task
     let now = chrono::Utc::now().to_rfc3339();
     let task = A2ATask {
         id: task_id.clone(),
-        context_id: context_id.clone(),
+        context_id: context_id(params),
         status: A2ATaskStatus {
             state: A2ATaskState::Completed,
             message: Some(response_message.clone()),
@@ -307,17 +456,266 @@ fn handle_message_send(state: &A2AState, params: Value, request_id: Option<Value
     };
 
     // Store the task
+    if let Ok(mut tasks) = state.tasks.write() {
+        tasks.insert(task_id, task.clone());
+    }
+    task
+}
+
+/// Start the crew a message is addressed to, admitted against the caller's
+/// quota. The returned handle resolves once the run finished and the task
+/// was updated.
+fn start_delegation(
+    state: &A2AState,
+    headers: &HeaderMap,
+    params: &Value,
+    message: A2AMessage,
+) -> Result<(A2ATask, tokio::task::JoinHandle<()>), (A2AErrorCode, String)> {
+    let skill_id = params
+        .pointer("/metadata/skill_id")
+        .or_else(|| params.pointer("/message/metadata/skill_id"))
+        .and_then(|v| v.as_str());
+    let names = state.crews.names();
+    let name = match (skill_id, names.as_slice()) {
+        (Some(skill), _) => skill.to_string(),
+        (None, [only]) => only.clone(),
+        (None, _) => {
+            return Err((
+                A2AErrorCode::InvalidParams,
+                format!(
+                    "Several crews are available; set metadata.skill_id to one of: {}",
+                    names.join(", ")
+                ),
+            ))
+        }
+    };
+    let crew = state.crews.build(&name).map_err(|(_, Json(body))| {
+        let error = body["error"].as_str().unwrap_or("").to_string();
+        (A2AErrorCode::InvalidParams, error)
+    })?;
+    let permit = state
+        .quotas
+        .as_ref()
+        .map(|quotas| quotas.admit(headers, &name))
+        .transpose()
+        .map_err(|e| {
+            let code = match e {
+                QuotaError::UnknownKey => A2AErrorCode::AuthenticationRequired,
+                QuotaError::CrewNotAllowed { .. } => A2AErrorCode::AuthorizationFailed,
+                _ => A2AErrorCode::RateLimitExceeded,
+            };
+            (code, e.to_string())
+        })?;
+
+    // The text is the `message` input; `data` parts add named inputs.
+    let mut inputs = HashMap::from([("message".to_string(), message_text(&message))]);
+    let data_parts = params
+        .pointer("/message/parts")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p.get("data").and_then(|d| d.as_object()));
+    for data in data_parts {
+        for (key, value) in data {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            inputs.insert(key.clone(), value);
+        }
+    }
+
+    let tenant = permit.as_ref().map(|p| p.tenant());
+    let task_id = state.executions.insert(&name, &crew, inputs, tenant);
+    let task = A2ATask {
+        id: task_id.clone(),
+        context_id: context_id(params),
+        status: A2ATaskStatus {
+            state: A2ATaskState::Working,
+            message: None,
+            timestamp: Some(chrono::Utc::now().to_rfc3339()),
+        },
+        history: vec![message],
+        artifacts: vec![],
+        metadata: Some(HashMap::from([("crew".to_string(), Value::String(name))])),
+    };
     if let Ok(mut tasks) = state.tasks.write() {
         tasks.insert(task_id.clone(), task.clone());
     }
 
-    // Return JSON-RPC success
-    let result = serde_json::to_value(&task).unwrap_or_default();
-    Json(serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": request_id,
-        "result": result,
-    }))
+    let state = state.clone();
+    let run = tokio::spawn(async move {
+        state.executions.run(task_id.clone(), crew, permit).await;
+        let Some(record) = state.executions.get(&task_id) else {
+            return;
+        };
+        let (status, artifacts) = task_outcome(&record);
+        if let Ok(mut tasks) = state.tasks.write() {
+            // A canceled task keeps its state; the run's result is dropped.
+            if let Some(task) = tasks
+                .get_mut(&task_id)
+                .filter(|t| t.status.state != A2ATaskState::Canceled)
+            {
+                task.history.extend(status.message.clone());
+                task.status = status;
+                task.artifacts = artifacts;
+            }
+        }
+    });
+    Ok((task, run))
+}
+
+/// Final status and artifacts of a finished crew run.
+fn task_outcome(record: &ExecutionRecord) -> (A2ATaskStatus, Vec<Value>) {
+    let timestamp = Some(chrono::Utc::now().to_rfc3339());
+    if record.status == ExecutionStatus::Completed {
+        let text = record
+            .result
+            .as_ref()
+            .map(|r| match &r["raw"] {
+                Value::String(raw) => raw.clone(),
+                _ => r.to_string(),
+            })
+            .unwrap_or_default();
+        let artifact = serde_json::json!({
+            "artifactId": uuid::Uuid::new_v4().to_string(),
+            "name": "result",
+            "parts": [{"kind": "text", "text": text}],
+        });
+        let status = A2ATaskStatus {
+            state: A2ATaskState::Completed,
+            message: Some(agent_message(text)),
+            timestamp,
+        };
+        (status, vec![artifact])
+    } else {
+        let error = record
+            .error
+            .clone()
+            .unwrap_or_else(|| "Crew run failed".into());
+        let status = A2ATaskStatus {
+            state: A2ATaskState::Failed,
+            message: Some(agent_message(error)),
+            timestamp,
+        };
+        (status, Vec::new())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// method: message/stream
+// ---------------------------------------------------------------------------
+
+/// Delegate like `message/send`, answering with server-sent events: the
+/// task, a `status-update` per crew task started/completed, then the result
+/// `artifact-update` and a final `status-update`.
+fn handle_message_stream(
+    state: &A2AState,
+    headers: &HeaderMap,
+    params: Value,
+    request_id: Option<Value>,
+) -> Response {
+    let message = match parse_message(&params) {
+        Ok(message) => message,
+        Err(error) => return invalid_params(error, request_id).into_response(),
+    };
+    if state.crews.names().is_empty() {
+        let task = acknowledge_message(state, &params, message);
+        let event = rpc_value(request_id, task_event(&task));
+        let events = futures::stream::iter([Ok::<_, Infallible>(
+            Event::default().data(event.to_string()),
+        )]);
+        return Sse::new(events).into_response();
+    }
+    let (task, _run) = match start_delegation(state, headers, &params, message) {
+        Ok(started) => started,
+        Err((code, error)) => return rpc_error(code, &error, request_id).into_response(),
+    };
+    let Some(updates) = state.executions.updates(&task.id) else {
+        return Json(create_error_response(
+            A2AErrorCode::InternalError,
+            Some("Crew run disappeared"),
+            None,
+            request_id,
+        ))
+        .into_response();
+    };
+
+    let first = rpc_value(request_id.clone(), task_event(&task));
+    let executions = state.executions.clone();
+    let events = updates.flat_map(move |update| {
+        let results = match update {
+            Update::Event(event) => {
+                let text = match event["type"].as_str() {
+                    Some("task_started") => Some(format!(
+                        "Started task: {}",
+                        event["task_name"].as_str().unwrap_or("task")
+                    )),
+                    Some("task_completed") => Some(match &event["output"]["raw"] {
+                        Value::String(raw) => raw.clone(),
+                        _ => event["output"].to_string(),
+                    }),
+                    _ => None,
+                };
+                text.map(|text| {
+                    let status = A2ATaskStatus {
+                        state: A2ATaskState::Working,
+                        message: Some(agent_message(text)),
+                        timestamp: Some(chrono::Utc::now().to_rfc3339()),
+                    };
+                    vec![status_event(&task, &status, false)]
+                })
+                .unwrap_or_default()
+            }
+            Update::Finished(_) => {
+                let mut results = Vec::new();
+                if let Some(record) = executions.get(&task.id) {
+                    let (status, artifacts) = task_outcome(&record);
+                    results.extend(artifacts.into_iter().map(|artifact| {
+                        serde_json::json!({
+                            "kind": "artifact-update",
+                            "taskId": task.id,
+                            "contextId": task.context_id,
+                            "artifact": artifact,
+                            "lastChunk": true,
+                        })
+                    }));
+                    results.push(status_event(&task, &status, true));
+                }
+                results
+            }
+        };
+        let request_id = request_id.clone();
+        futures::stream::iter(
+            results
+                .into_iter()
+                .map(move |result| rpc_value(request_id.clone(), result)),
+        )
+    });
+    let events = futures::stream::iter([first])
+        .chain(events)
+        .map(|value| Ok::<_, Infallible>(Event::default().data(value.to_string())));
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn task_event(task: &A2ATask) -> Value {
+    let mut value = serde_json::to_value(task).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.insert("kind".to_string(), Value::String("task".to_string()));
+    }
+    value
+}
+
+fn status_event(task: &A2ATask, status: &A2ATaskStatus, last: bool) -> Value {
+    serde_json::json!({
+        "kind": "status-update",
+        "taskId": task.id,
+        "contextId": task.context_id,
+        "status": status,
+        "final": last,
+    })
 }
 
 // ---------------------------------------------------------------------------
@@ -453,6 +851,37 @@ fn handle_tasks_cancel(state: &A2AState, params: Value, request_id: Option<Value
 // Helpers
 // ---------------------------------------------------------------------------
 
+fn agent_message(text: String) -> A2AMessage {
+    A2AMessage {
+        role: "agent".to_string(),
+        parts: vec![PartsDict {
+            text,
+            metadata: None,
+        }],
+        metadata: None,
+    }
+}
+
+fn rpc_value(request_id: Option<Value>, result: Value) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": request_id,
+        "result": result,
+    })
+}
+
+fn rpc_result(request_id: Option<Value>, result: Value) -> Json<Value> {
+    Json(rpc_value(request_id, result))
+}
+
+fn invalid_params(message: &str, request_id: Option<Value>) -> Json<Value> {
+    rpc_error(A2AErrorCode::InvalidParams, message, request_id)
+}
+
+fn rpc_error(code: A2AErrorCode, message: &str, request_id: Option<Value>) -> Json<Value> {
+    Json(create_error_response(code, Some(message), None, request_id))
+}
+
 fn truncate(s: &str, max: usize) -> &str {
     if s.len() <= max {
         s
@@ -469,7 +898,7 @@ fn truncate(s: &str, max: usize) -> &str {
 mod tests {
     use super::*;
     use axum::body::Body;
-    use axum::http::Request;
+    use axum::http::{Request, StatusCode};
     use tower::ServiceExt;
 
     fn test_state() -> A2AState {
@@ -573,4 +1002,104 @@ mod tests {
         assert!(json.get("error").is_some());
         assert_eq!(json["error"]["code"], -32601); // MethodNotFound
     }
+
+    async fn post_rpc(app: Router, token: Option<&str>, rpc: Value) -> (StatusCode, String) {
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/a2a")
+            .header("Content-Type", "application/json");
+        if let Some(token) = token {
+            req = req.header("Authorization", format!("Bearer {}", token));
+        }
+        let resp = app
+            .oneshot(req.body(Body::from(rpc.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = resp.status();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_crew_delegation_with_auth() {
+        use crate::crew::Crew;
+        use crate::task::Task;
+
+        let state = A2AState::new().with_auth(ServerAuth::bearer("s3cret"));
+        state.crews.register("summarizer", || {
+            let mut task = Task::new("Summarize: {message} ({lang})".into(), "Summary".into());
+            task.agent = Some("writer".to_string());
+            task.set_agent_executor(|prompt: &str, _: Option<&str>, _: &[String]| {
+                Ok((format!("summary of [{}]", prompt), Vec::new()))
+            });
+            Crew::new(vec![task], vec!["writer".to_string()])
+        });
+        state.crews.describe("summarizer", "Summarizes text");
+        let app = a2a_router(state.clone());
+
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/.well-known/agent.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let card: Value = serde_json::from_slice(&body).unwrap();
+        assert!(card["skills"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|s| s["id"] == "summarizer" && s["description"] == "Summarizes text"));
+        assert_eq!(card["security_schemes"][0]["scheme"], "bearer");
+        assert_eq!(card["capabilities"]["streaming"], true);
+
+        let rpc = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "message/send",
+            "params": {"message": {"role": "user", "parts": [
+                {"text": "long text"},
+                {"data": {"lang": "en"}}
+            ]}}
+        });
+        let (status, body) = post_rpc(app.clone(), Some("wrong"), rpc.clone()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body.contains("-32011"));
+
+        let (status, body) = post_rpc(app.clone(), Some("s3cret"), rpc.clone()).await;
+        assert_eq!(status, StatusCode::OK);
+        let json: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["result"]["status"]["state"], "completed", "{}", body);
+        assert!(json["result"]["artifacts"][0]["parts"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("Summarize: long text (en)"));
+
+        let mut stream_rpc = rpc;
+        stream_rpc["method"] = "message/stream".into();
+        let (status, body) = post_rpc(app, Some("s3cret"), stream_rpc).await;
+        assert_eq!(status, StatusCode::OK);
+        let events: Vec<Value> = body
+            .lines()
+            .filter_map(|l| l.strip_prefix("data: "))
+            .map(|d| serde_json::from_str(d).unwrap())
+            .collect();
+        assert_eq!(events[0]["result"]["kind"], "task");
+        assert_eq!(events[0]["result"]["status"]["state"], "working");
+        let last = &events.last().unwrap()["result"];
+        assert_eq!(last["kind"], "status-update");
+        assert_eq!(last["final"], true);
+        assert_eq!(last["status"]["state"], "completed");
+        assert!(events
+            .iter()
+            .any(|e| e["result"]["kind"] == "artifact-update"));
+    }
 }
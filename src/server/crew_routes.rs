@@ -0,0 +1,1319 @@
+//! Crew REST endpoints — kickoff, execution status and live event streams.
+//!
+//! Like flows, crews are built from code, so applications register a named
+//! factory in the [`CrewRegistry`]; each kickoff builds a fresh crew from it
+//! and runs it in the background. Runs are tracked in the
+//! [`ExecutionStore`], which also collects the run's events from the event
+//! bus for the SSE stream.
+//!
+//! # Endpoints
+//!
+//! - `GET  /crews`                  — List registered crew names
+//! - `POST /crews/:name/kickoff`    — Start a run with `{ "inputs": {...} }`
+//!   (add `"wait": true` to block until it finishes)
+//! - `GET  /executions`             — List executions, newest first
+//! - `GET  /executions/:id`         — Status and result of a run, with the
+//!   run state reconstructed from its events (see [`crate::events::state`])
+//! - `POST /executions/:id/resume`  — Start a new run of a finished crew
+//!   run's crew that restores the tasks it completed and runs the rest
+//! - `GET  /executions/:id/events`  — Server-sent events for a run (replays
+//!   what was emitted so far, then streams until the run finishes)
+//! - `GET  /executions/:id/ws`      — The same events over a WebSocket, as
+//!   JSON text messages ending with `{"type": "done", ...}`
+//! - `GET  /executions/:id/artifacts` — Binary artifacts the run's tasks
+//!   produced (see [`crate::utilities::artifacts`])
+//! - `GET  /artifacts/:id`          — Download an artifact, served with its
+//!   MIME type
+//!
+//! Both streams carry task transitions, agent and tool activity, LLM calls
+//! and — for providers that stream — token-level `llm_stream_chunk` events.
+//!
+//! When [`CrewServerState::quotas`] is set, kickoffs are admitted against the
+//! caller's tenant quota (see [`crate::server::quotas`]) and execution
+//! listings and artifact downloads are limited to the caller's own runs.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use axum::{
+    extract::ws::{close_code, CloseFrame, Message, WebSocketUpgrade},
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::crew::Crew;
+use crate::events::event_bus::serialize_event;
+use crate::events::types::agent_events::{
+    AgentExecutionCompletedEvent, AgentExecutionErrorEvent, AgentExecutionStartedEvent,
+};
+use crate::events::types::crew_events::{
+    CrewKickoffCompletedEvent, CrewKickoffFailedEvent, CrewKickoffStartedEvent,
+};
+use crate::events::types::llm_events::{
+    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent, LLMStreamChunkEvent,
+};
+use crate::events::types::task_events::{TaskCompletedEvent, TaskFailedEvent, TaskStartedEvent};
+use crate::events::types::tool_events::{
+    ToolUsageErrorEvent, ToolUsageFinishedEvent, ToolUsageStartedEvent,
+};
+use crate::events::{BaseEvent, CrewAIEventsBus, ExecutionState};
+use crate::utilities::artifacts::{self, Artifact, ArtifactError};
+use crate::utilities::profiles::ActiveProfile;
+
+use super::quotas::{QuotaManager, QuotaPermit};
+
+/// Finished executions kept for status queries before the oldest are dropped.
+pub const MAX_FINISHED_EXECUTIONS: usize = 1000;
+
+/// Largest message accepted from a WebSocket client.
+pub const MAX_WS_MESSAGE_SIZE: usize = 1 << 20;
+
+/// Factory that builds a fully configured crew (agents, tasks, callbacks).
+pub type CrewFactory = Arc<dyn Fn() -> Crew + Send + Sync>;
+
+/// Named crew factories exposed over HTTP.
+#[derive(Clone, Default)]
+pub struct CrewRegistry {
+    factories: Arc<RwLock<HashMap<String, CrewFactory>>>,
+    descriptions: Arc<RwLock<HashMap<String, String>>>,
+    profile: Option<Arc<ActiveProfile>>,
+}
+
+impl CrewRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply `profile` to every crew built from the registry.
+    pub fn with_profile(mut self, profile: ActiveProfile) -> Self {
+        self.profile = Some(Arc::new(profile));
+        self
+    }
+
+    /// The profile applied to built crews, if any.
+    pub fn profile(&self) -> Option<&ActiveProfile> {
+        self.profile.as_deref()
+    }
+
+    /// Register a crew factory under `name`, replacing any previous one.
+    pub fn register<F>(&self, name: impl Into<String>, factory: F)
+    where
+        F: Fn() -> Crew + Send + Sync + 'static,
+    {
+        if let Ok(mut factories) = self.factories.write() {
+            factories.insert(name.into(), Arc::new(factory));
+        }
+    }
+
+    /// Describe a registered crew (shown as its skill in the A2A agent card).
+    pub fn describe(&self, name: impl Into<String>, description: impl Into<String>) {
+        if let Ok(mut descriptions) = self.descriptions.write() {
+            descriptions.insert(name.into(), description.into());
+        }
+    }
+
+    /// Description of a crew, if one was set.
+    pub fn description(&self, name: &str) -> Option<String> {
+        self.descriptions.read().ok()?.get(name).cloned()
+    }
+
+    /// Names of all registered crews, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .factories
+            .read()
+            .map(|f| f.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    pub(crate) fn build(&self, name: &str) -> Result<Crew, (StatusCode, Json<Value>)> {
+        let factory = self
+            .factories
+            .read()
+            .map_err(|_| {
+                error(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Crew registry lock poisoned",
+                )
+            })?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| {
+                error(
+                    StatusCode::NOT_FOUND,
+                    &format!("Crew '{}' not registered", name),
+                )
+            })?;
+        let mut crew = factory();
+        if let Some(profile) = &self.profile {
+            profile.apply(&mut crew);
+        }
+        Ok(crew)
+    }
+}
+
+// ============================================================================
+// Executions
+// ============================================================================
+
+/// Lifecycle state of a crew run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    /// Accepted, not started yet.
+    Queued,
+    /// Running.
+    Running,
+    /// Finished successfully.
+    Completed,
+    /// Finished with an error.
+    Failed,
+}
+
+impl ExecutionStatus {
+    /// Whether the run has finished.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+/// Status and result of one crew run.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionRecord {
+    /// Execution ID.
+    pub execution_id: String,
+    /// Registered crew name.
+    pub crew: String,
+    /// Tenant that started the run, when quotas are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// Current status.
+    pub status: ExecutionStatus,
+    /// Kickoff inputs.
+    pub inputs: HashMap<String, String>,
+    /// Crew output, once completed.
+    pub result: Option<Value>,
+    /// Error message, if failed.
+    pub error: Option<String>,
+    /// When the run was accepted.
+    pub created_at: DateTime<Utc>,
+    /// When the run started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When the run finished.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Number of events collected so far.
+    pub event_count: usize,
+    /// Run state folded from the events collected so far.
+    pub state: ExecutionState,
+}
+
+/// Message on a run's live stream.
+#[derive(Debug, Clone)]
+pub(crate) enum Update {
+    Event(Value),
+    Finished(ExecutionStatus),
+}
+
+struct Execution {
+    record: ExecutionRecord,
+    events: Vec<Value>,
+    updates: broadcast::Sender<Update>,
+    /// Event sources (crew, task and agent IDs, LLM call IDs) belonging to
+    /// this run.
+    sources: Vec<String>,
+}
+
+#[derive(Default)]
+struct StoreInner {
+    executions: HashMap<String, Execution>,
+    /// Event source or LLM call ID → execution ID.
+    sources: HashMap<String, String>,
+    /// Finished execution IDs, oldest first.
+    finished: VecDeque<String>,
+}
+
+/// Crew runs started over HTTP, with their collected events.
+#[derive(Clone, Default)]
+pub struct ExecutionStore {
+    inner: Arc<RwLock<StoreInner>>,
+    shutting_down: Arc<AtomicBool>,
+    subscribed: Arc<AtomicBool>,
+}
+
+impl ExecutionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect run events from the global event bus (once per store).
+    fn ensure_subscribed(&self) {
+        if self.subscribed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let bus = CrewAIEventsBus::global();
+        macro_rules! subscribe {
+            ($($event:ty),* $(,)?) => {
+                $(
+                    let store = self.clone();
+                    bus.on::<$event>(
+                        "crew_execution_store",
+                        move |source, event| store.record_event(source, event),
+                        None,
+                    );
+                )*
+            };
+        }
+        subscribe!(
+            CrewKickoffStartedEvent,
+            CrewKickoffCompletedEvent,
+            CrewKickoffFailedEvent,
+            TaskStartedEvent,
+            TaskCompletedEvent,
+            TaskFailedEvent,
+            AgentExecutionStartedEvent,
+            AgentExecutionCompletedEvent,
+            AgentExecutionErrorEvent,
+            LLMCallStartedEvent,
+            LLMCallCompletedEvent,
+            LLMCallFailedEvent,
+            LLMStreamChunkEvent,
+            ToolUsageStartedEvent,
+            ToolUsageFinishedEvent,
+            ToolUsageErrorEvent,
+        );
+    }
+
+    fn record_event(&self, source: &dyn std::any::Any, event: &dyn BaseEvent) {
+        let Ok(mut inner) = self.inner.write() else {
+            return;
+        };
+        if inner.sources.is_empty() {
+            return;
+        }
+        let mut execution_id = source
+            .downcast_ref::<String>()
+            .and_then(|source| inner.sources.get(source).cloned());
+        // Streaming providers emit chunks without knowing the agent; match
+        // them to the run through the call ID of its LLM calls.
+        let is_chunk = event.event_type() == "llm_stream_chunk";
+        if execution_id.is_none() && !is_chunk {
+            return;
+        }
+        let data = serialize_event(event);
+        let mut value = data
+            .payload
+            .clone()
+            .unwrap_or_else(|| serde_json::to_value(&*data).unwrap_or(Value::Null));
+        let call_id = value["call_id"].as_str().map(str::to_string);
+        if execution_id.is_none() {
+            execution_id = call_id
+                .as_ref()
+                .and_then(|id| inner.sources.get(id).cloned());
+        }
+        let Some(execution_id) = execution_id else {
+            return;
+        };
+        if let Some(call_id) = call_id.filter(|id| !inner.sources.contains_key(id)) {
+            inner.sources.insert(call_id.clone(), execution_id.clone());
+            if let Some(execution) = inner.executions.get_mut(&execution_id) {
+                execution.sources.push(call_id);
+            }
+        }
+        if let Some(execution) = inner.executions.get_mut(&execution_id) {
+            if let Value::Object(map) = &mut value {
+                map.insert("type".to_string(), Value::String(data.event_type.clone()));
+            }
+            execution.record.state.apply(&value);
+            execution.events.push(value.clone());
+            execution.record.event_count = execution.events.len();
+            let _ = execution.updates.send(Update::Event(value));
+        }
+    }
+
+    /// Status and result of an execution.
+    pub fn get(&self, execution_id: &str) -> Option<ExecutionRecord> {
+        let inner = self.inner.read().ok()?;
+        inner.executions.get(execution_id).map(|e| e.record.clone())
+    }
+
+    /// All executions, newest first.
+    pub fn list(&self) -> Vec<ExecutionRecord> {
+        let mut records: Vec<ExecutionRecord> = self
+            .inner
+            .read()
+            .map(|inner| {
+                inner
+                    .executions
+                    .values()
+                    .map(|e| e.record.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        records.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        records
+    }
+
+    /// Number of executions that have not finished.
+    pub fn active_count(&self) -> usize {
+        self.inner
+            .read()
+            .map(|inner| {
+                inner
+                    .executions
+                    .values()
+                    .filter(|e| !e.record.status.is_finished())
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Stop accepting kickoffs and wait up to `timeout` for running
+    /// executions to finish. Returns whether all of them finished.
+    pub async fn shutdown(&self, timeout: Duration) -> bool {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        let deadline = tokio::time::Instant::now() + timeout;
+        while self.active_count() > 0 {
+            if tokio::time::Instant::now() >= deadline {
+                return false;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        true
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called.
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn insert(
+        &self,
+        name: &str,
+        crew: &Crew,
+        inputs: HashMap<String, String>,
+        tenant: Option<&str>,
+    ) -> String {
+        self.ensure_subscribed();
+        let execution_id = Uuid::new_v4().to_string();
+        let mut sources = vec![crew.id.to_string()];
+        sources.extend(crew.tasks.iter().map(|t| t.id.to_string()));
+        sources.extend(
+            crew.agent_objects
+                .values()
+                .filter_map(|agent| agent.read().ok().map(|a| a.id.to_string())),
+        );
+        let (updates, _) = broadcast::channel(256);
+        let execution = Execution {
+            record: ExecutionRecord {
+                execution_id: execution_id.clone(),
+                crew: name.to_string(),
+                tenant: tenant.map(str::to_string),
+                status: ExecutionStatus::Queued,
+                inputs,
+                result: None,
+                error: None,
+                created_at: Utc::now(),
+                started_at: None,
+                finished_at: None,
+                event_count: 0,
+                state: ExecutionState::new(),
+            },
+            events: Vec::new(),
+            updates,
+            sources: sources.clone(),
+        };
+        if let Ok(mut inner) = self.inner.write() {
+            for source in sources {
+                inner.sources.insert(source, execution_id.clone());
+            }
+            inner.executions.insert(execution_id.clone(), execution);
+        }
+        execution_id
+    }
+
+    fn update(&self, execution_id: &str, f: impl FnOnce(&mut ExecutionRecord)) {
+        let Ok(mut inner) = self.inner.write() else {
+            return;
+        };
+        let Some(execution) = inner.executions.get_mut(execution_id) else {
+            return;
+        };
+        f(&mut execution.record);
+        let status = execution.record.status;
+        if !status.is_finished() {
+            return;
+        }
+        let _ = execution.updates.send(Update::Finished(status));
+        let sources = std::mem::take(&mut execution.sources);
+        for source in sources {
+            inner.sources.remove(&source);
+        }
+        inner.finished.push_back(execution_id.to_string());
+        while inner.finished.len() > MAX_FINISHED_EXECUTIONS {
+            if let Some(oldest) = inner.finished.pop_front() {
+                inner.executions.remove(&oldest);
+            }
+        }
+    }
+
+    /// Run a crew to completion, updating its execution record and charging
+    /// its token usage to the quota `permit`, if any.
+    pub(crate) async fn run(
+        &self,
+        execution_id: String,
+        mut crew: Crew,
+        permit: Option<QuotaPermit>,
+    ) {
+        self.update(&execution_id, |r| {
+            r.status = ExecutionStatus::Running;
+            r.started_at = Some(Utc::now());
+        });
+        let inputs = self
+            .get(&execution_id)
+            .map(|r| r.inputs)
+            .unwrap_or_default();
+        let inputs = (!inputs.is_empty()).then_some(inputs);
+        let outcome = tokio::task::spawn_blocking(move || crew.kickoff(inputs))
+            .await
+            .unwrap_or_else(|e| Err(format!("Crew execution panicked: {}", e)));
+
+        // Deliver the run's last events before it is marked finished.
+        if let Some(bus) = crate::events::CREWAI_EVENT_BUS.get() {
+            tokio::task::spawn_blocking(move || bus.flush()).await.ok();
+        }
+
+        self.update(&execution_id, |r| {
+            r.finished_at = Some(Utc::now());
+            match outcome {
+                Ok(output) => {
+                    if let Some(permit) = &permit {
+                        permit.record(&output.token_usage);
+                    }
+                    r.status = ExecutionStatus::Completed;
+                    r.result = serde_json::to_value(&output).ok();
+                }
+                Err(e) => {
+                    r.status = ExecutionStatus::Failed;
+                    r.error = Some(e);
+                }
+            }
+        });
+    }
+
+    /// Events so far plus a receiver for the rest, or `None` when unknown.
+    fn subscribe(
+        &self,
+        execution_id: &str,
+    ) -> Option<(
+        Vec<Value>,
+        Option<broadcast::Receiver<Update>>,
+        ExecutionStatus,
+    )> {
+        let inner = self.inner.read().ok()?;
+        let execution = inner.executions.get(execution_id)?;
+        let status = execution.record.status;
+        let receiver = (!status.is_finished()).then(|| execution.updates.subscribe());
+        Some((execution.events.clone(), receiver, status))
+    }
+
+    /// A run's events from the start, ending with [`Update::Finished`].
+    pub(crate) fn updates(&self, execution_id: &str) -> Option<impl Stream<Item = Update> + Send> {
+        let (history, receiver, status) = self.subscribe(execution_id)?;
+        let replay = stream::iter(history.into_iter().map(Update::Event));
+        let live = stream::unfold(receiver, |receiver| async move {
+            let mut receiver = receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok(update) => {
+                        let done = matches!(update, Update::Finished(_));
+                        return Some((update, (!done).then_some(receiver)));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("Event stream client lagged, skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        let finished = status.is_finished().then_some(Update::Finished(status));
+        Some(replay.chain(live).chain(stream::iter(finished)))
+    }
+}
+
+/// State shared by the crew routes.
+#[derive(Clone, Default)]
+pub struct CrewServerState {
+    /// Registered crews.
+    pub crews: CrewRegistry,
+    /// Runs started over HTTP.
+    pub executions: ExecutionStore,
+    /// Tenant quotas enforced at kickoff; unrestricted when `None`.
+    pub quotas: Option<QuotaManager>,
+}
+
+impl CrewServerState {
+    /// Admit a run of `crew` for the caller, if quotas are enabled.
+    pub(crate) fn admit(
+        &self,
+        headers: &HeaderMap,
+        crew: &str,
+    ) -> Result<Option<QuotaPermit>, (StatusCode, Json<Value>)> {
+        self.quotas
+            .as_ref()
+            .map(|quotas| quotas.admit(headers, crew))
+            .transpose()
+            .map_err(|e| e.response())
+    }
+
+    /// The caller's tenant, when quotas are enabled.
+    fn caller_tenant(
+        &self,
+        headers: &HeaderMap,
+    ) -> Result<Option<String>, (StatusCode, Json<Value>)> {
+        self.quotas
+            .as_ref()
+            .map(|quotas| quotas.tenant(headers).map(|t| t.id.clone()))
+            .transpose()
+            .map_err(|e| e.response())
+    }
+}
+
+/// Build the crew router.
+pub fn crew_router(state: CrewServerState) -> Router {
+    Router::new()
+        .route("/crews", get(list_crews_handler))
+        .route("/crews/:name/kickoff", post(kickoff_handler))
+        .route("/executions", get(list_executions_handler))
+        .route("/executions/:id", get(execution_handler))
+        .route("/executions/:id/resume", post(resume_handler))
+        .route("/executions/:id/events", get(events_handler))
+        .route("/executions/:id/ws", get(ws_handler))
+        .route(
+            "/executions/:id/artifacts",
+            get(execution_artifacts_handler),
+        )
+        .route("/artifacts/:id", get(artifact_handler))
+        .with_state(state)
+}
+
+// ============================================================================
+// Request types
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct CrewKickoffRequest {
+    /// Inputs interpolated into the crew's tasks and agents.
+    #[serde(default)]
+    pub inputs: HashMap<String, Value>,
+    /// Block until the run finishes and return its final record.
+    #[serde(default)]
+    pub wait: bool,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET /crews — list registered crews.
+async fn list_crews_handler(State(state): State<CrewServerState>) -> Json<Value> {
+    Json(serde_json::json!({ "crews": state.crews.names() }))
+}
+
+/// POST /crews/:name/kickoff — start a crew run.
+async fn kickoff_handler(
+    State(state): State<CrewServerState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    Json(request): Json<CrewKickoffRequest>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    if state.executions.is_shutting_down() {
+        return Err(error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Server is shutting down",
+        ));
+    }
+    let crew = state.crews.build(&name)?;
+    let permit = state.admit(&headers, &name)?;
+    let inputs = request
+        .inputs
+        .into_iter()
+        .map(|(k, v)| match v {
+            Value::String(s) => (k, s),
+            other => (k, other.to_string()),
+        })
+        .collect();
+    start(&state, &name, crew, inputs, permit, request.wait).await
+}
+
+/// POST /executions/:id/resume — re-run a finished run's crew, restoring the
+/// outputs of the tasks it completed.
+async fn resume_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    body: Option<Json<CrewKickoffRequest>>,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    if state.executions.is_shutting_down() {
+        return Err(error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Server is shutting down",
+        ));
+    }
+    let tenant = state.caller_tenant(&headers)?;
+    let record = state
+        .executions
+        .get(&id)
+        .filter(|r| tenant.is_none() || r.tenant == tenant)
+        .ok_or_else(|| execution_not_found(&id))?;
+    if !record.status.is_finished() {
+        return Err(error(
+            StatusCode::CONFLICT,
+            &format!("Execution '{}' has not finished", id),
+        ));
+    }
+    let crew = state.crews.build(&record.crew)?.resume_from(record.state);
+    let permit = state.admit(&headers, &record.crew)?;
+    let wait = body.is_some_and(|Json(request)| request.wait);
+    start(&state, &record.crew, crew, record.inputs, permit, wait).await
+}
+
+/// Record and spawn a run of `crew`, waiting for it to finish if `wait`.
+async fn start(
+    state: &CrewServerState,
+    name: &str,
+    crew: Crew,
+    inputs: HashMap<String, String>,
+    permit: Option<QuotaPermit>,
+    wait: bool,
+) -> Result<(StatusCode, Json<Value>), (StatusCode, Json<Value>)> {
+    let tenant = permit.as_ref().map(|p| p.tenant());
+    let execution_id = state.executions.insert(name, &crew, inputs, tenant);
+
+    let executions = state.executions.clone();
+    let run_id = execution_id.clone();
+    let handle = tokio::spawn(async move { executions.run(run_id, crew, permit).await });
+
+    if wait {
+        handle
+            .await
+            .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()))?;
+        let record = state.executions.get(&execution_id);
+        return Ok((
+            StatusCode::OK,
+            Json(serde_json::to_value(record).unwrap_or_default()),
+        ));
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(serde_json::json!({
+            "execution_id": execution_id,
+            "status": ExecutionStatus::Queued,
+            "links": {
+                "status": format!("/executions/{}", execution_id),
+                "events": format!("/executions/{}/events", execution_id),
+                "websocket": format!("/executions/{}/ws", execution_id),
+            },
+        })),
+    ))
+}
+
+/// GET /executions — list executions, newest first.
+///
+/// With quotas enabled, only the caller's executions are listed.
+async fn list_executions_handler(
+    State(state): State<CrewServerState>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let tenant = state.caller_tenant(&headers)?;
+    let executions: Vec<_> = state
+        .executions
+        .list()
+        .into_iter()
+        .filter(|r| tenant.is_none() || r.tenant == tenant)
+        .collect();
+    Ok(Json(serde_json::json!({ "executions": executions })))
+}
+
+/// GET /executions/:id — status and result of a run.
+async fn execution_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<ExecutionRecord>, (StatusCode, Json<Value>)> {
+    let tenant = state.caller_tenant(&headers)?;
+    state
+        .executions
+        .get(&id)
+        .filter(|r| tenant.is_none() || r.tenant == tenant)
+        .map(Json)
+        .ok_or_else(|| execution_not_found(&id))
+}
+
+/// GET /executions/:id/artifacts — artifacts produced by a run's tasks.
+async fn execution_artifacts_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let tenant = state.caller_tenant(&headers)?;
+    let record = state
+        .executions
+        .get(&id)
+        .filter(|r| tenant.is_none() || r.tenant == tenant)
+        .ok_or_else(|| execution_not_found(&id))?;
+    let artifacts: Vec<Value> = execution_artifacts(&record)
+        .into_iter()
+        .map(|artifact| {
+            let url = artifact.url_path();
+            let mut value = serde_json::to_value(artifact).unwrap_or_default();
+            value["url"] = Value::String(url);
+            value
+        })
+        .collect();
+    Ok(Json(serde_json::json!({ "artifacts": artifacts })))
+}
+
+/// GET /artifacts/:id — download an artifact.
+///
+/// With quotas enabled, only artifacts of the caller's executions are
+/// served.
+async fn artifact_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let not_found = || {
+        error(
+            StatusCode::NOT_FOUND,
+            &format!("Artifact '{}' not found", id),
+        )
+    };
+    if let Some(tenant) = state.caller_tenant(&headers)? {
+        let owned = state
+            .executions
+            .list()
+            .iter()
+            .filter(|r| r.tenant.as_deref() == Some(tenant.as_str()))
+            .any(|r| execution_artifacts(r).iter().any(|a| a.id == id));
+        if !owned {
+            return Err(not_found());
+        }
+    }
+    let store = artifacts::store();
+    let lookup = id.clone();
+    let loaded = tokio::task::spawn_blocking(move || store.get(&lookup))
+        .await
+        .map_err(|e| error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()))?;
+    let (artifact, bytes) = match loaded {
+        Ok(found) => found,
+        Err(ArtifactError::NotFound(_)) => return Err(not_found()),
+        Err(e) => return Err(error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string())),
+    };
+    let disposition = format!(
+        "attachment; filename=\"{}\"",
+        artifact.name.replace(['"', '\\', '\r', '\n'], "_")
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, artifact.mime_type),
+            (header::CONTENT_DISPOSITION, disposition),
+            (header::ETAG, format!("\"{}\"", artifact.sha256)),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// GET /executions/:id/events — stream a run's events as SSE.
+///
+/// Each bus event is sent as an SSE event named after its type; a final
+/// `done` event carries the run's status.
+async fn events_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<Value>)> {
+    let updates = state
+        .executions
+        .updates(&id)
+        .ok_or_else(|| execution_not_found(&id))?;
+    let events = updates.map(|update| {
+        Ok(match update {
+            Update::Event(value) => {
+                let name = value["type"].as_str().unwrap_or("event").to_string();
+                Event::default().event(name).data(value.to_string())
+            }
+            Update::Finished(status) => Event::default()
+                .event("done")
+                .data(serde_json::json!({ "status": status }).to_string()),
+        })
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// GET /executions/:id/ws — stream a run's events over a WebSocket.
+///
+/// Each event is a JSON text message with a `type` field; the last one is
+/// `{"type": "done", "status": ...}`, after which the server closes the
+/// connection. Pings are answered; other client messages are ignored.
+async fn ws_handler(
+    State(state): State<CrewServerState>,
+    Path(id): Path<String>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let Some(updates) = state.executions.updates(&id) else {
+        return execution_not_found(&id).into_response();
+    };
+    let executions = state.executions.clone();
+    let upgrade = upgrade.max_message_size(MAX_WS_MESSAGE_SIZE);
+    upgrade.on_upgrade(move |mut socket| async move {
+        let mut updates = std::pin::pin!(updates);
+        let mut shutdown_check = tokio::time::interval(Duration::from_secs(1));
+        let code = loop {
+            tokio::select! {
+                update = updates.next() => {
+                    let text = match update {
+                        Some(Update::Event(value)) => value.to_string(),
+                        Some(Update::Finished(status)) => {
+                            serde_json::json!({ "type": "done", "status": status }).to_string()
+                        }
+                        None => break close_code::NORMAL,
+                    };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break close_code::AWAY;
+                    }
+                }
+                // Pongs are sent by the socket as it reads pings.
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_))) | None => break close_code::NORMAL,
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) => break close_code::PROTOCOL,
+                },
+                _ = shutdown_check.tick() => {
+                    if executions.is_shutting_down() && executions.active_count() == 0 {
+                        break close_code::AWAY;
+                    }
+                }
+            }
+        };
+        let close = CloseFrame {
+            code,
+            reason: "".into(),
+        };
+        let _ = socket.send(Message::Close(Some(close))).await;
+    })
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Artifacts listed in the task outputs of a run's result.
+fn execution_artifacts(record: &ExecutionRecord) -> Vec<Artifact> {
+    record
+        .result
+        .as_ref()
+        .and_then(|result| result["tasks_output"].as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|task| task.get("artifacts"))
+        .filter_map(|artifacts| Vec::<Artifact>::deserialize(artifacts).ok())
+        .flatten()
+        .collect()
+}
+
+fn error(status: StatusCode, message: &str) -> (StatusCode, Json<Value>) {
+    (status, Json(serde_json::json!({ "error": message })))
+}
+
+fn execution_not_found(id: &str) -> (StatusCode, Json<Value>) {
+    error(
+        StatusCode::NOT_FOUND,
+        &format!("Execution '{}' not found", id),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn echo_crew() -> Crew {
+        let mut task = Task::new("Greet {name}".to_string(), "A greeting".to_string());
+        task.agent = Some("greeter".to_string());
+        task.set_agent_executor(|prompt: &str, _: Option<&str>, _: &[String]| {
+            Ok((format!("done: {}", prompt), Vec::new()))
+        });
+        Crew::new(vec![task], vec!["greeter".to_string()])
+    }
+
+    async fn send(app: Router, method: &str, uri: &str, body: Value) -> (StatusCode, String) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_state_is_reconstructed_from_events_and_resumed() {
+        use crate::events::{RunStatus, TaskStatus};
+        use std::sync::atomic::AtomicUsize;
+
+        // The second task fails on its first attempt.
+        let calls = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+        let state = CrewServerState::default();
+        let counters = calls.clone();
+        state.crews.register("pipeline", move || {
+            let tasks = ["Research {topic}", "Write up {topic}"]
+                .iter()
+                .enumerate()
+                .map(|(i, description)| {
+                    let mut task = Task::new(description.to_string(), "Notes".to_string());
+                    task.agent = Some("writer".to_string());
+                    let counters = counters.clone();
+                    task.set_agent_executor(move |prompt: &str, _: Option<&str>, _: &[String]| {
+                        if counters[i].fetch_add(1, Ordering::SeqCst) == 0 && i == 1 {
+                            return Err("model overloaded".to_string());
+                        }
+                        Ok((format!("done: {}", prompt), Vec::new()))
+                    });
+                    task
+                })
+                .collect();
+            Crew::new(tasks, vec!["writer".to_string()])
+        });
+        let app = crew_router(state.clone());
+        // A run's record and events, once its state has caught up with the
+        // events still being delivered by the bus.
+        let settled = |id: String| {
+            let executions = state.executions.clone();
+            async move {
+                for _ in 0..200 {
+                    {
+                        let inner = executions.inner.read().unwrap();
+                        let execution = &inner.executions[&id];
+                        let state = &execution.record.state;
+                        if state.is_finished() && state.tasks.iter().all(|t| t.is_finished()) {
+                            return (execution.record.clone(), execution.events.clone());
+                        }
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                panic!("state of execution {} did not settle", id);
+            }
+        };
+
+        let (_, body) = send(
+            app.clone(),
+            "POST",
+            "/crews/pipeline/kickoff",
+            serde_json::json!({"inputs": {"topic": "bees"}, "wait": true}),
+        )
+        .await;
+        let id = serde_json::from_str::<Value>(&body).unwrap()["execution_id"].clone();
+        let (failed, events) = settled(id.as_str().unwrap().to_string()).await;
+        assert_eq!(failed.status, ExecutionStatus::Failed);
+        assert_eq!(failed.state.status, RunStatus::Failed);
+        let statuses: Vec<TaskStatus> = failed.state.tasks.iter().map(|t| t.status).collect();
+        assert_eq!(statuses, vec![TaskStatus::Completed, TaskStatus::Failed]);
+        assert_eq!(
+            failed.state.tasks[1].error.as_deref(),
+            Some("model overloaded")
+        );
+        // Live state matches the state rebuilt from the stored events.
+        assert_eq!(ExecutionState::from_events(&events), failed.state);
+
+        let (status, body) = send(
+            app.clone(),
+            "POST",
+            &format!("/executions/{}/resume", failed.execution_id),
+            serde_json::json!({"wait": true}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        let resumed: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(resumed["status"], "completed");
+        assert_eq!(calls[0].load(Ordering::SeqCst), 1);
+        assert_eq!(calls[1].load(Ordering::SeqCst), 2);
+
+        let (record, events) = settled(resumed["execution_id"].as_str().unwrap().to_string()).await;
+        assert_eq!(ExecutionState::from_events(&events), record.state);
+        assert_eq!(record.state.status, RunStatus::Completed);
+        assert_eq!(record.inputs.get("topic").map(String::as_str), Some("bees"));
+        let outputs: Vec<&str> = record.result.as_ref().unwrap()["tasks_output"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["raw"].as_str().unwrap())
+            .collect();
+        let rebuilt: Vec<&str> = record
+            .state
+            .tasks
+            .iter()
+            .map(|t| t.output.as_deref().unwrap())
+            .collect();
+        assert_eq!(rebuilt, outputs);
+        assert_eq!(outputs[0], failed.state.tasks[0].output.as_deref().unwrap());
+
+        let (status, _) = send(app, "POST", "/executions/missing/resume", Value::Null).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_kickoff_status_and_events() {
+        let state = CrewServerState::default();
+        state.crews.register("greeter", echo_crew);
+        let app = crew_router(state.clone());
+
+        let (status, body) = send(
+            app.clone(),
+            "POST",
+            "/crews/greeter/kickoff",
+            serde_json::json!({"inputs": {"name": "Ada"}, "wait": true}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let record: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(record["status"], "completed");
+        assert!(record["result"]["raw"]
+            .as_str()
+            .unwrap()
+            .contains("Greet Ada"));
+        let id = record["execution_id"].as_str().unwrap().to_string();
+
+        let (status, body) = send(
+            app.clone(),
+            "GET",
+            &format!("/executions/{}", id),
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("\"status\":\"completed\""));
+
+        // Finished run: the stream replays its events and closes with `done`.
+        let (status, body) = send(
+            app.clone(),
+            "GET",
+            &format!("/executions/{}/events", id),
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("event: crew_kickoff_started"));
+        assert!(body.contains("event: task_completed"));
+        assert!(body
+            .trim_end()
+            .ends_with("data: {\"status\":\"completed\"}"));
+
+        let (status, _) = send(
+            app.clone(),
+            "POST",
+            "/crews/missing/kickoff",
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        assert!(state.executions.shutdown(Duration::from_secs(1)).await);
+        let (status, _) = send(app, "POST", "/crews/greeter/kickoff", serde_json::json!({})).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_task_artifacts_are_listed_and_downloadable() {
+        let state = CrewServerState::default();
+        state.crews.register("charts", || {
+            let mut task = Task::new("Plot revenue".to_string(), "A chart".to_string());
+            task.agent = Some("analyst".to_string());
+            task.set_agent_executor(|_: &str, _: Option<&str>, _: &[String]| {
+                artifacts::attach("revenue.csv", None, b"quarter,revenue\nQ1,10\n")
+                    .map_err(|e| e.to_string())?;
+                Ok(("Chart attached".to_string(), Vec::new()))
+            });
+            Crew::new(vec![task], vec!["analyst".to_string()])
+        });
+        let app = crew_router(state);
+
+        let (_, body) = send(
+            app.clone(),
+            "POST",
+            "/crews/charts/kickoff",
+            serde_json::json!({"wait": true}),
+        )
+        .await;
+        let record: Value = serde_json::from_str(&body).unwrap();
+        let artifact = &record["result"]["tasks_output"][0]["artifacts"][0];
+        assert_eq!(artifact["mime_type"], "text/csv");
+        assert_eq!(artifact["size"], 22);
+
+        let id = record["execution_id"].as_str().unwrap();
+        let (status, body) = send(
+            app.clone(),
+            "GET",
+            &format!("/executions/{}/artifacts", id),
+            Value::Null,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let listed: Value = serde_json::from_str(&body).unwrap();
+        let url = listed["artifacts"][0]["url"].as_str().unwrap().to_string();
+        assert_eq!(
+            url,
+            format!("/artifacts/{}", artifact["id"].as_str().unwrap())
+        );
+
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(&url).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/csv");
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"revenue.csv\""
+        );
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], b"quarter,revenue\nQ1,10\n");
+
+        let (status, _) = send(app, "GET", "/artifacts/not-an-id", Value::Null).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        artifacts::store()
+            .delete(artifact["id"].as_str().unwrap())
+            .unwrap();
+    }
+
+    /// A crew whose LLM "streams" two chunks, matched to the run by call ID.
+    fn streaming_crew() -> Crew {
+        use crate::events::types::llm_events::{LLMCallStartedEvent, LLMStreamChunkEvent};
+
+        let mut task = Task::new("Write".to_string(), "Text".to_string());
+        task.agent = Some("writer".to_string());
+        let task_id = task.id.to_string();
+        task.set_agent_executor(move |_: &str, _: Option<&str>, _: &[String]| {
+            let bus = CrewAIEventsBus::global();
+            let call_id = Uuid::new_v4().to_string();
+            bus.emit(
+                Arc::new(task_id.clone()),
+                &mut LLMCallStartedEvent::new(call_id.clone(), None),
+            );
+            bus.flush();
+            for token in ["Hel", "lo"] {
+                bus.emit(
+                    Arc::new("provider".to_string()),
+                    &mut LLMStreamChunkEvent::new(call_id.clone(), None, token.to_string()),
+                );
+            }
+            std::thread::sleep(Duration::from_millis(100));
+            Ok(("Hello".to_string(), Vec::new()))
+        });
+        Crew::new(vec![task], vec!["writer".to_string()])
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_websocket_streams_run() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let state = CrewServerState::default();
+        state.crews.register("writer", streaming_crew);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = crew_router(state.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let (status, body) = send(
+            crew_router(state.clone()),
+            "POST",
+            "/crews/writer/kickoff",
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let id = serde_json::from_str::<Value>(&body).unwrap()["execution_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let handshake = format!(
+            "GET /executions/{}/ws HTTP/1.1\r\nHost: {}\r\nConnection: Upgrade\r\n\
+             Upgrade: websocket\r\nSec-WebSocket-Version: 13\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+            id, addr
+        );
+        stream.write_all(handshake.as_bytes()).await.unwrap();
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{}", head);
+        assert!(head.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        // Read server frames (unmasked, short) until the close frame.
+        let mut messages = Vec::new();
+        loop {
+            let opcode = stream.read_u8().await.unwrap() & 0x0F;
+            let len = match stream.read_u8().await.unwrap() {
+                126 => stream.read_u16().await.unwrap() as usize,
+                len => len as usize,
+            };
+            let mut payload = vec![0; len];
+            stream.read_exact(&mut payload).await.unwrap();
+            if opcode == 0x8 {
+                assert_eq!(payload, 1000u16.to_be_bytes());
+                break;
+            }
+            messages.push(serde_json::from_slice::<Value>(&payload).unwrap());
+        }
+        let types: Vec<&str> = messages.iter().filter_map(|m| m["type"].as_str()).collect();
+        assert!(types.contains(&"task_started"), "{:?}", types);
+        let tokens: Vec<&str> = messages
+            .iter()
+            .filter(|m| m["type"] == "llm_stream_chunk")
+            .filter_map(|m| m["chunk"].as_str())
+            .collect();
+        assert_eq!(tokens, vec!["Hel", "lo"]);
+        assert_eq!(
+            messages.last().unwrap(),
+            &serde_json::json!({"type": "done", "status": "completed"})
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Flow REST endpoints — kickoff and human-in-the-loop resume.
+//!
+//! Flows are code (registered callbacks), so the server cannot rebuild one
+//! from persisted state alone. Applications register a named factory in the
+//! [`FlowRegistry`]; each request builds a fresh flow from it. A flow that
+//! pauses for human input is persisted by its own backend and can be resumed
+//! hours or days later by ID.
+//!
+//! # Endpoints
+//!
+//! - `GET  /flows`                        — List registered flow names
+//! - `POST /flows/:name/kickoff`          — Run a flow with `{ "inputs": {...} }`
+//! - `GET  /flows/:name/pending/:flow_id` — Inspect a paused flow's context
+//! - `POST /flows/:name/resume/:flow_id`  — Resume with `{ "feedback": "..." }`
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::flow::Flow;
+
+/// Factory that builds a fully registered flow (methods, callbacks, persistence).
+pub type FlowFactory = Arc<dyn Fn() -> Flow + Send + Sync>;
+
+/// Named flow factories exposed over HTTP.
+#[derive(Clone, Default)]
+pub struct FlowRegistry {
+    factories: Arc<RwLock<HashMap<String, FlowFactory>>>,
+}
+
+impl FlowRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a flow factory under `name`, replacing any previous one.
+    pub fn register<F>(&self, name: impl Into<String>, factory: F)
+    where
+        F: Fn() -> Flow + Send + Sync + 'static,
+    {
+        if let Ok(mut factories) = self.factories.write() {
+            factories.insert(name.into(), Arc::new(factory));
+        }
+    }
+
+    /// Names of all registered flows, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .factories
+            .read()
+            .map(|f| f.keys().cloned().collect())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    fn build(&self, name: &str) -> Result<Flow, (StatusCode, Json<Value>)> {
+        let factory = self
+            .factories
+            .read()
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({"error": "Flow registry lock poisoned"})),
+                )
+            })?
+            .get(name)
+            .cloned()
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(serde_json::json!({"error": format!("Flow '{}' not registered", name)})),
+                )
+            })?;
+        Ok(factory())
+    }
+}
+
+/// Build the flow router.
+pub fn flow_router(registry: FlowRegistry) -> Router {
+    Router::new()
+        .route("/flows", get(list_flows_handler))
+        .route("/flows/:name/kickoff", post(kickoff_handler))
+        .route("/flows/:name/pending/:flow_id", get(pending_handler))
+        .route("/flows/:name/resume/:flow_id", post(resume_handler))
+        .with_state(registry)
+}
+
+// ============================================================================
+// Request types
+// ============================================================================
+
+#[derive(Debug, Default, Deserialize)]
+pub struct KickoffRequest {
+    /// Inputs merged into the flow state before execution.
+    #[serde(default)]
+    pub inputs: HashMap<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResumeRequest {
+    /// The human response (free text or one of the emit options).
+    #[serde(default)]
+    pub feedback: String,
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET /flows — list registered flows.
+async fn list_flows_handler(State(registry): State<FlowRegistry>) -> Json<Value> {
+    Json(serde_json::json!({ "flows": registry.names() }))
+}
+
+/// POST /flows/:name/kickoff — run a flow until it completes or pauses.
+async fn kickoff_handler(
+    State(registry): State<FlowRegistry>,
+    Path(name): Path<String>,
+    Json(request): Json<KickoffRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mut flow = registry.build(&name)?;
+    flow.initialize_state(request.inputs).map_err(|e| {
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })?;
+
+    let result = flow.kickoff_async().await.map_err(internal_error)?;
+    Ok(Json(run_response(&flow, result)))
+}
+
+/// GET /flows/:name/pending/:flow_id — return the pending feedback context.
+async fn pending_handler(
+    State(registry): State<FlowRegistry>,
+    Path((name, flow_id)): Path<(String, String)>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mut flow = registry.build(&name)?;
+    flow.load_pending(&flow_id).map_err(not_found)?;
+
+    let context = flow.pending_feedback().map(|c| c.to_dict());
+    Ok(Json(serde_json::json!({
+        "flow_id": flow_id,
+        "pending": context,
+    })))
+}
+
+/// POST /flows/:name/resume/:flow_id — resume a paused flow with a human response.
+async fn resume_handler(
+    State(registry): State<FlowRegistry>,
+    Path((name, flow_id)): Path<(String, String)>,
+    Json(request): Json<ResumeRequest>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let mut flow = registry.build(&name)?;
+    flow.load_pending(&flow_id).map_err(not_found)?;
+
+    let result = flow
+        .resume_async(&request.feedback)
+        .await
+        .map_err(internal_error)?;
+    Ok(Json(run_response(&flow, result)))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn run_response(flow: &Flow, result: Value) -> Value {
+    let status = if flow.is_paused() {
+        "pending_human_input"
+    } else {
+        "completed"
+    };
+    serde_json::json!({
+        "status": status,
+        "flow_id": flow.flow_id(),
+        "result": result,
+        "state": flow.state.to_dict(),
+    })
+}
+
+fn not_found(e: anyhow::Error) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": e.to_string()})),
+    )
+}
+
+fn internal_error(e: anyhow::Error) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({"error": e.to_string()})),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::async_feedback::HumanFeedbackPending;
+    use crate::flow::flow::FlowMethodFn;
+    use crate::flow::persistence::SQLiteFlowPersistence;
+    use crate::flow::{FlowMethodMeta, FlowMethodName, FlowState};
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn callback<F>(f: F) -> FlowMethodFn
+    where
+        F: for<'a> Fn(
+                &'a mut FlowState,
+                Option<Value>,
+            ) -> futures::future::BoxFuture<'a, Result<Value, anyhow::Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Box::new(f)
+    }
+
+    fn approval_flow(db_path: &str) -> Flow {
+        let mut flow = Flow::with_name("Approval").with_persistence(Box::new(
+            SQLiteFlowPersistence::new(Some(db_path.to_string())),
+        ));
+        flow.register_method_meta(
+            "review",
+            &FlowMethodMeta {
+                is_start_method: true,
+                ..Default::default()
+            },
+        );
+        flow.register_method_meta(
+            "ship",
+            &FlowMethodMeta {
+                trigger_methods: Some(vec![FlowMethodName::new("approve")]),
+                ..Default::default()
+            },
+        );
+        flow.register_callback(
+            "review",
+            callback(|_, _| {
+                Box::pin(async {
+                    Err(HumanFeedbackPending::request("Ship it?", Value::Null)
+                        .with_emit(vec!["approve".to_string(), "reject".to_string()])
+                        .into())
+                })
+            }),
+        );
+        flow.register_callback(
+            "ship",
+            callback(|state, _| {
+                Box::pin(async move {
+                    state.set("shipped".to_string(), Value::Bool(true));
+                    Ok(Value::String("shipped".to_string()))
+                })
+            }),
+        );
+        flow
+    }
+
+    async fn post_json(app: Router, uri: &str, body: Value) -> (StatusCode, Value) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_kickoff_pause_and_resume() {
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        let db_path = tmp.path().to_string_lossy().to_string();
+        let registry = FlowRegistry::new();
+        registry.register("approval", move || approval_flow(&db_path));
+
+        let (status, body) = post_json(
+            flow_router(registry.clone()),
+            "/flows/approval/kickoff",
+            serde_json::json!({"inputs": {"ticket": 7}}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "pending_human_input");
+        let flow_id = body["flow_id"].as_str().unwrap().to_string();
+
+        let (status, body) = post_json(
+            flow_router(registry.clone()),
+            &format!("/flows/approval/resume/{}", flow_id),
+            serde_json::json!({"feedback": "approve"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "completed");
+        assert_eq!(body["state"]["shipped"], true);
+        assert_eq!(body["state"]["ticket"], 7);
+
+        // Already resumed: nothing pending anymore.
+        let (status, _) = post_json(
+            flow_router(registry),
+            &format!("/flows/approval/resume/{}", flow_id),
+            serde_json::json!({"feedback": "approve"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_flow_returns_404() {
+        let (status, _) = post_json(
+            flow_router(FlowRegistry::new()),
+            "/flows/missing/kickoff",
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,606 @@
+//! Prometheus metrics endpoint, fed from the event bus.
+//!
+//! [`MetricsCollector`] subscribes to the global event bus and aggregates
+//! counters and histograms; `GET /metrics` renders them in the Prometheus
+//! text exposition format.
+//!
+//! # Metrics
+//!
+//! - `crewai_llm_requests_total{provider,model,status}`
+//! - `crewai_llm_request_duration_seconds{provider,model}` (histogram)
+//! - `crewai_llm_tokens_total{provider,model,type}` (`prompt`/`completion`)
+//! - `crewai_tasks_total{status}`
+//! - `crewai_task_duration_seconds{status}` (histogram)
+//! - `crewai_tool_calls_total{tool,status}` (error rate = `status="error"` / all)
+//! - `crewai_tool_duration_seconds{tool}` (histogram)
+//! - `crewai_policy_denials_total{action,rule}`
+//! - `crewai_crew_kickoffs_total{status}`
+//! - `crewai_hot_path_calls_total{path}` and `crewai_hot_path_seconds_total{path}`,
+//!   when hot-path instrumentation is on (see [`crate::utilities::perf`])
+//!
+//! Start and end events are handled concurrently by the bus, so durations
+//! are computed from event timestamps and pairs are matched whichever side
+//! arrives first.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::events::base_event::BaseEventData;
+use crate::events::event_bus::serialize_event;
+use crate::events::types::crew_events::{CrewKickoffCompletedEvent, CrewKickoffFailedEvent};
+use crate::events::types::llm_events::{
+    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent,
+};
+use crate::events::types::policy_events::PolicyDeniedEvent;
+use crate::events::types::task_events::{TaskCompletedEvent, TaskFailedEvent, TaskStartedEvent};
+use crate::events::types::tool_events::{ToolUsageErrorEvent, ToolUsageFinishedEvent};
+use crate::events::CrewAIEventsBus;
+use crate::llms::base_llm::BaseLLMState;
+use crate::utilities::perf;
+
+/// Histogram buckets (seconds) for LLM, task and tool durations.
+pub const DURATION_BUCKETS: &[f64] = &[
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Unmatched start/end events kept before the oldest are discarded.
+const MAX_PENDING: usize = 10_000;
+
+/// Content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const METRICS: &[(&str, &str, &str)] = &[
+    (
+        "crewai_llm_requests_total",
+        "counter",
+        "LLM calls by provider, model and outcome.",
+    ),
+    (
+        "crewai_llm_request_duration_seconds",
+        "histogram",
+        "LLM call latency.",
+    ),
+    (
+        "crewai_llm_tokens_total",
+        "counter",
+        "Tokens used by LLM calls, by type.",
+    ),
+    (
+        "crewai_tasks_total",
+        "counter",
+        "Task executions by outcome.",
+    ),
+    (
+        "crewai_task_duration_seconds",
+        "histogram",
+        "Task execution time.",
+    ),
+    (
+        "crewai_tool_calls_total",
+        "counter",
+        "Tool calls by tool and outcome.",
+    ),
+    (
+        "crewai_tool_duration_seconds",
+        "histogram",
+        "Tool execution time.",
+    ),
+    (
+        "crewai_policy_denials_total",
+        "counter",
+        "Requests denied by the policy engine.",
+    ),
+    (
+        "crewai_crew_kickoffs_total",
+        "counter",
+        "Crew kickoffs by outcome.",
+    ),
+];
+
+type Labels = Vec<(&'static str, String)>;
+
+#[derive(Debug, Clone)]
+struct Histogram {
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            counts: vec![0; DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (count, bound) in self.counts.iter_mut().zip(DURATION_BUCKETS) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Counters and histograms keyed by metric name and label set.
+#[derive(Debug, Default)]
+struct Registry {
+    counters: BTreeMap<&'static str, BTreeMap<Labels, f64>>,
+    histograms: BTreeMap<&'static str, BTreeMap<Labels, Histogram>>,
+}
+
+impl Registry {
+    fn inc(&mut self, name: &'static str, labels: Labels, by: f64) {
+        *self
+            .counters
+            .entry(name)
+            .or_default()
+            .entry(labels)
+            .or_insert(0.0) += by;
+    }
+
+    fn observe(&mut self, name: &'static str, labels: Labels, value: f64) {
+        self.histograms
+            .entry(name)
+            .or_default()
+            .entry(labels)
+            .or_insert_with(Histogram::new)
+            .observe(value);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (name, kind, help) in METRICS {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            if let Some(series) = self.counters.get(name) {
+                for (labels, value) in series {
+                    let _ = writeln!(out, "{}{} {}", name, label_set(labels, None), value);
+                }
+            }
+            if let Some(series) = self.histograms.get(name) {
+                for (labels, histogram) in series {
+                    for (bound, count) in DURATION_BUCKETS.iter().zip(&histogram.counts) {
+                        let le = bound.to_string();
+                        let _ = writeln!(
+                            out,
+                            "{}_bucket{} {}",
+                            name,
+                            label_set(labels, Some(&le)),
+                            count
+                        );
+                    }
+                    let _ = writeln!(
+                        out,
+                        "{}_bucket{} {}",
+                        name,
+                        label_set(labels, Some("+Inf")),
+                        histogram.count
+                    );
+                    let _ = writeln!(
+                        out,
+                        "{}_sum{} {}",
+                        name,
+                        label_set(labels, None),
+                        histogram.sum
+                    );
+                    let _ = writeln!(
+                        out,
+                        "{}_count{} {}",
+                        name,
+                        label_set(labels, None),
+                        histogram.count
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+fn label_set(labels: &Labels, le: Option<&str>) -> String {
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{}\"", le));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", parts.join(","))
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// One side of a start/end pair waiting for the other.
+#[derive(Debug)]
+enum Pending {
+    Started(DateTime<Utc>),
+    Finished(DateTime<Utc>, &'static str, Labels),
+}
+
+#[derive(Debug, Default)]
+struct CollectorState {
+    registry: Registry,
+    pending: HashMap<String, Pending>,
+    pending_order: Vec<String>,
+}
+
+impl CollectorState {
+    fn started(&mut self, key: String, at: DateTime<Utc>) {
+        match self.pending.remove(&key) {
+            Some(Pending::Finished(end, name, labels)) => {
+                self.registry.observe(name, labels, seconds(at, end));
+            }
+            _ => self.insert_pending(key, Pending::Started(at)),
+        }
+    }
+
+    fn finished(&mut self, key: String, at: DateTime<Utc>, name: &'static str, labels: Labels) {
+        match self.pending.remove(&key) {
+            Some(Pending::Started(start)) => {
+                self.registry.observe(name, labels, seconds(start, at));
+            }
+            _ => self.insert_pending(key, Pending::Finished(at, name, labels)),
+        }
+    }
+
+    fn insert_pending(&mut self, key: String, pending: Pending) {
+        if self.pending.len() >= MAX_PENDING {
+            self.pending_order.retain(|k| self.pending.contains_key(k));
+            let excess = self.pending_order.len().saturating_sub(MAX_PENDING / 2);
+            for stale in self.pending_order.drain(..excess) {
+                self.pending.remove(&stale);
+            }
+        }
+        self.pending_order.push(key.clone());
+        self.pending.insert(key, pending);
+    }
+}
+
+fn seconds(start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    ((end - start).num_microseconds().unwrap_or(0) as f64 / 1_000_000.0).max(0.0)
+}
+
+fn payload_str<'a>(event: &'a BaseEventData, key: &str) -> Option<&'a str> {
+    event.payload.as_ref()?.get(key)?.as_str()
+}
+
+fn payload_time(event: &BaseEventData, key: &str) -> Option<DateTime<Utc>> {
+    payload_str(event, key)?.parse().ok()
+}
+
+/// Aggregates event-bus events into Prometheus metrics.
+#[derive(Debug, Default)]
+pub struct MetricsCollector {
+    state: Mutex<CollectorState>,
+}
+
+static GLOBAL: OnceLock<Arc<MetricsCollector>> = OnceLock::new();
+
+impl MetricsCollector {
+    /// An empty collector that is not subscribed to any events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide collector, subscribed to the global event bus on
+    /// first use.
+    pub fn global() -> Arc<MetricsCollector> {
+        GLOBAL
+            .get_or_init(|| {
+                let collector = Arc::new(MetricsCollector::new());
+                collector.subscribe(CrewAIEventsBus::global());
+                collector
+            })
+            .clone()
+    }
+
+    /// Subscribe to the events the metrics are derived from.
+    pub fn subscribe(self: &Arc<Self>, bus: &CrewAIEventsBus) {
+        macro_rules! subscribe {
+            ($($event:ty),* $(,)?) => {
+                $(
+                    let sink = self.clone();
+                    bus.on::<$event>(
+                        "prometheus_metrics",
+                        move |_, event| sink.observe(&serialize_event(event)),
+                        None,
+                    );
+                )*
+            };
+        }
+        subscribe!(
+            LLMCallStartedEvent,
+            LLMCallCompletedEvent,
+            LLMCallFailedEvent,
+            TaskStartedEvent,
+            TaskCompletedEvent,
+            TaskFailedEvent,
+            ToolUsageFinishedEvent,
+            ToolUsageErrorEvent,
+            PolicyDeniedEvent,
+            CrewKickoffCompletedEvent,
+            CrewKickoffFailedEvent,
+        );
+    }
+
+    /// Update the metrics from one (serialized) event.
+    pub fn observe(&self, event: &BaseEventData) {
+        let mut state = self.state.lock().unwrap();
+        let at = event.timestamp;
+        match event.event_type.as_str() {
+            "llm_call_started" => {
+                if let Some(call_id) = payload_str(event, "call_id") {
+                    state.started(format!("llm:{}", call_id), at);
+                }
+            }
+            "llm_call_completed" | "llm_call_failed" => {
+                let model = payload_str(event, "model").unwrap_or("unknown").to_string();
+                let provider = BaseLLMState::extract_provider(&model);
+                let status = if event.event_type == "llm_call_completed" {
+                    "success"
+                } else {
+                    "error"
+                };
+                let labels: Labels = vec![("provider", provider), ("model", model)];
+                let mut with_status = labels.clone();
+                with_status.push(("status", status.to_string()));
+                state
+                    .registry
+                    .inc("crewai_llm_requests_total", with_status, 1.0);
+
+                let usage = event.payload.as_ref().and_then(|p| p.get("usage"));
+                for (kind, key) in [
+                    ("prompt", "prompt_tokens"),
+                    ("completion", "completion_tokens"),
+                ] {
+                    let tokens = usage.and_then(|u| u.get(key)).and_then(Value::as_f64);
+                    if let Some(tokens) = tokens.filter(|t| *t > 0.0) {
+                        let mut with_type = labels.clone();
+                        with_type.push(("type", kind.to_string()));
+                        state
+                            .registry
+                            .inc("crewai_llm_tokens_total", with_type, tokens);
+                    }
+                }
+
+                if let Some(call_id) = payload_str(event, "call_id") {
+                    state.finished(
+                        format!("llm:{}", call_id),
+                        at,
+                        "crewai_llm_request_duration_seconds",
+                        labels,
+                    );
+                }
+            }
+            "task_started" => {
+                if let Some(task_id) = &event.task_id {
+                    state.started(format!("task:{}", task_id), at);
+                }
+            }
+            "task_completed" | "task_failed" => {
+                let status = if event.event_type == "task_completed" {
+                    "success"
+                } else {
+                    "error"
+                };
+                let labels: Labels = vec![("status", status.to_string())];
+                state
+                    .registry
+                    .inc("crewai_tasks_total", labels.clone(), 1.0);
+                if let Some(task_id) = &event.task_id {
+                    state.finished(
+                        format!("task:{}", task_id),
+                        at,
+                        "crewai_task_duration_seconds",
+                        labels,
+                    );
+                }
+            }
+            "tool_usage_finished" | "tool_usage_error" => {
+                let tool = payload_str(event, "tool_name")
+                    .unwrap_or("unknown")
+                    .to_string();
+                let status = if event.event_type == "tool_usage_finished" {
+                    "success"
+                } else {
+                    "error"
+                };
+                state.registry.inc(
+                    "crewai_tool_calls_total",
+                    vec![("tool", tool.clone()), ("status", status.to_string())],
+                    1.0,
+                );
+                if let (Some(start), Some(end)) = (
+                    payload_time(event, "started_at"),
+                    payload_time(event, "finished_at"),
+                ) {
+                    state.registry.observe(
+                        "crewai_tool_duration_seconds",
+                        vec![("tool", tool)],
+                        seconds(start, end),
+                    );
+                }
+            }
+            "policy_denied" => {
+                let labels = vec![
+                    (
+                        "action",
+                        payload_str(event, "action")
+                            .unwrap_or("unknown")
+                            .to_string(),
+                    ),
+                    (
+                        "rule",
+                        payload_str(event, "rule_name").unwrap_or("").to_string(),
+                    ),
+                ];
+                state
+                    .registry
+                    .inc("crewai_policy_denials_total", labels, 1.0);
+            }
+            "crew_kickoff_completed" | "crew_kickoff_failed" => {
+                let status = if event.event_type == "crew_kickoff_completed" {
+                    "success"
+                } else {
+                    "error"
+                };
+                state.registry.inc(
+                    "crewai_crew_kickoffs_total",
+                    vec![("status", status.to_string())],
+                    1.0,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// The metrics in Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = self.state.lock().unwrap().registry.render();
+        if perf::is_enabled() {
+            render_hot_paths(&mut out);
+        }
+        out
+    }
+}
+
+fn render_hot_paths(out: &mut String) {
+    let stats = perf::snapshot();
+    let _ = writeln!(
+        out,
+        "# HELP crewai_hot_path_calls_total Instrumented hot-path calls"
+    );
+    let _ = writeln!(out, "# TYPE crewai_hot_path_calls_total counter");
+    for s in &stats {
+        let _ = writeln!(
+            out,
+            "crewai_hot_path_calls_total{{path=\"{}\"}} {}",
+            s.path, s.calls
+        );
+    }
+    let _ = writeln!(
+        out,
+        "# HELP crewai_hot_path_seconds_total Time spent in instrumented hot paths"
+    );
+    let _ = writeln!(out, "# TYPE crewai_hot_path_seconds_total counter");
+    for s in &stats {
+        let _ = writeln!(
+            out,
+            "crewai_hot_path_seconds_total{{path=\"{}\"}} {}",
+            s.path,
+            s.total_ns as f64 / 1e9
+        );
+    }
+}
+
+/// Build the `/metrics` router.
+pub fn metrics_router(collector: Arc<MetricsCollector>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(collector)
+}
+
+/// GET /metrics — Prometheus scrape endpoint.
+async fn metrics_handler(State(collector): State<Arc<MetricsCollector>>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], collector.render())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::types::llm_events::LLMCallType;
+    use crate::events::BaseEvent;
+    use crate::types::usage_metrics::UsageMetrics;
+
+    fn at(event: &mut dyn BaseEvent, millis: i64) -> BaseEventData {
+        let mut data = (*serialize_event(event)).clone();
+        data.timestamp = DateTime::<Utc>::from_timestamp_millis(millis).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_metrics_from_events() {
+        let collector = MetricsCollector::new();
+        let model = Some("anthropic/claude-sonnet".to_string());
+        let completed = LLMCallCompletedEvent::new(
+            "c1".into(),
+            model.clone(),
+            Value::Null,
+            LLMCallType::LlmCall,
+        )
+        .with_usage(UsageMetrics {
+            prompt_tokens: 100,
+            completion_tokens: 20,
+            total_tokens: 120,
+            ..Default::default()
+        });
+        // The end event may be handled before its start.
+        collector.observe(&at(&mut completed.clone(), 1_700));
+        collector.observe(&at(
+            &mut LLMCallStartedEvent::new("c1".into(), model),
+            1_000,
+        ));
+
+        collector.observe(&at(
+            &mut TaskStartedEvent::new(Some("t1".into()), None, None),
+            0,
+        ));
+        collector.observe(&at(
+            &mut TaskFailedEvent::new(Some("t1".into()), None, "boom".into()),
+            40_000,
+        ));
+        collector.observe(&at(
+            &mut ToolUsageErrorEvent::new("search".into(), Value::Null, 1, Value::Null),
+            0,
+        ));
+        collector.observe(&at(
+            &mut PolicyDeniedEvent::new(
+                "a1".into(),
+                "tool_call".into(),
+                "Tool(\"rm\")".into(),
+                Some("no_rm".into()),
+                "denied".into(),
+                true,
+            ),
+            0,
+        ));
+
+        let text = collector.render();
+        for line in [
+            "# TYPE crewai_llm_request_duration_seconds histogram",
+            "crewai_llm_requests_total{provider=\"anthropic\",model=\"anthropic/claude-sonnet\",status=\"success\"} 1",
+            "crewai_llm_tokens_total{provider=\"anthropic\",model=\"anthropic/claude-sonnet\",type=\"prompt\"} 100",
+            "crewai_llm_request_duration_seconds_bucket{provider=\"anthropic\",model=\"anthropic/claude-sonnet\",le=\"0.5\"} 0",
+            "crewai_llm_request_duration_seconds_bucket{provider=\"anthropic\",model=\"anthropic/claude-sonnet\",le=\"1\"} 1",
+            "crewai_llm_request_duration_seconds_sum{provider=\"anthropic\",model=\"anthropic/claude-sonnet\"} 0.7",
+            "crewai_tasks_total{status=\"error\"} 1",
+            "crewai_task_duration_seconds_bucket{status=\"error\",le=\"60\"} 1",
+            "crewai_task_duration_seconds_count{status=\"error\"} 1",
+            "crewai_tool_calls_total{tool=\"search\",status=\"error\"} 1",
+            "crewai_policy_denials_total{action=\"tool_call\",rule=\"no_rm\"} 1",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {:?} in\n{}", line, text);
+        }
+        assert!(collector.state.lock().unwrap().pending.is_empty());
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
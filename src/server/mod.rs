@@ -6,6 +6,7 @@
 //! # Endpoints
 //!
 //! - `GET  /health`  — Liveness probe
+//! - `GET  /ready`   — Readiness probe (503 while shutting down)
 //! - `POST /execute` — Execute a `crew.*` step delegation
 //! - `POST /barrier/check-outbound` — 4-layer barrier check (outbound)
 //! - `POST /barrier/check-inbound`  — 4-layer barrier check (inbound)
@@ -14,11 +15,69 @@
 //! - `GET  /barrier/stats`          — Markov barrier statistics
 //! - `GET  /.well-known/agent.json` — A2A agent card discovery
 //! - `POST /a2a`                    — A2A JSON-RPC 2.0 dispatcher
+//! - `POST /flows/:name/kickoff`    — Run a registered flow
+//! - `POST /flows/:name/resume/:id` — Resume a flow paused for human input
+//! - `GET  /metrics`                — Prometheus metrics (see [`metrics`])
+//! - `POST /crews/:name/kickoff`    — Start a registered crew run
+//! - `GET  /executions/:id`         — Crew run status and result
+//! - `GET  /executions/:id/events`  — Crew run events as server-sent events
+//! - `GET  /executions/:id/ws`      — Crew run events over a WebSocket
+//! - `GET  /quota`                  — Caller's tenant quota and usage (see [`quotas`])
+//!
+//! [`service`] integrates the server binary with systemd and Windows
+//! service managers.
+//!
+//! With the `playground` feature, [`playground`] provides the interactive
+//! UI served by the `crewai-playground` binary.
 
 pub mod a2a_routes;
 pub mod barrier_routes;
+pub mod crew_routes;
+pub mod flow_routes;
+pub mod metrics;
+#[cfg(feature = "playground")]
+pub mod playground;
+pub mod quotas;
 pub mod routes;
+pub mod service;
 
 pub use a2a_routes::{a2a_router, A2AState};
 pub use barrier_routes::{barrier_router, BarrierState};
+pub use crew_routes::{crew_router, CrewFactory, CrewRegistry, CrewServerState, ExecutionStore};
+pub use flow_routes::{flow_router, FlowFactory, FlowRegistry};
+pub use metrics::{metrics_router, MetricsCollector};
+pub use quotas::{quota_router, QuotaConfig, QuotaError, QuotaManager, QuotaPermit};
 pub use routes::{app_router, AppState};
+
+/// Resolve when the process receives Ctrl-C or (on Unix) SIGTERM.
+///
+/// Pass to `axum::serve(...).with_graceful_shutdown(...)` so in-flight
+/// requests finish before the server exits.
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            log::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                log::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
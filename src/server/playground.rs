@@ -0,0 +1,543 @@
+//! Interactive playground — compose an agent and task, run it, inspect it.
+//!
+//! Backs the `crewai-playground` binary (cargo feature `playground`). The
+//! UI is a single embedded HTML page; everything else is a small JSON API
+//! so the same endpoints can be scripted.
+//!
+//! # Endpoints
+//!
+//! - `GET  /`                     — Playground UI
+//! - `GET  /api/providers`        — Providers with credentials configured
+//! - `POST /api/runs`             — Start a run from a [`RunSpec`]
+//! - `GET  /api/runs/:id`         — Run snapshot (status, output, transcript, events)
+//! - `GET  /api/runs/:id/events`  — Live run events (Server-Sent Events)
+//! - `POST /api/export`           — Render a [`RunSpec`] as `agents.yaml` / `tasks.yaml`
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast;
+
+use crate::agent::Agent;
+
+const PLAYGROUND_HTML: &str = include_str!("playground.html");
+
+/// Providers the playground can run against: (name, API key env var, default model).
+const PROVIDERS: &[(&str, &str, &str)] = &[
+    ("openai", "OPENAI_API_KEY", "openai/gpt-4o-mini"),
+    (
+        "anthropic",
+        "ANTHROPIC_API_KEY",
+        "anthropic/claude-3-5-haiku-latest",
+    ),
+    ("xai", "XAI_API_KEY", "xai/grok-3-mini"),
+];
+
+// ============================================================================
+// Run specification
+// ============================================================================
+
+/// Agent half of a playground run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSpec {
+    /// Key used for the agent in exported YAML.
+    #[serde(default = "default_agent_key")]
+    pub key: String,
+    /// Agent role.
+    pub role: String,
+    /// Agent goal.
+    pub goal: String,
+    /// Agent backstory.
+    #[serde(default)]
+    pub backstory: String,
+    /// Model string, e.g. "openai/gpt-4o-mini".
+    #[serde(default)]
+    pub llm: Option<String>,
+}
+
+/// Task half of a playground run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSpec {
+    /// Key used for the task in exported YAML.
+    #[serde(default = "default_task_key")]
+    pub key: String,
+    /// What the agent should do.
+    pub description: String,
+    /// Description of the desired result.
+    #[serde(default)]
+    pub expected_output: String,
+}
+
+/// An agent/task pair composed in the playground.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSpec {
+    /// The agent to run.
+    pub agent: AgentSpec,
+    /// The task given to the agent.
+    pub task: TaskSpec,
+}
+
+fn default_agent_key() -> String {
+    "agent".to_string()
+}
+
+fn default_task_key() -> String {
+    "task".to_string()
+}
+
+impl RunSpec {
+    /// Render as crewAI project config: `(agents.yaml, tasks.yaml)`.
+    pub fn to_yaml(&self) -> Result<(String, String), serde_yaml::Error> {
+        let mut agent = BTreeMap::new();
+        agent.insert("role", self.agent.role.clone());
+        agent.insert("goal", self.agent.goal.clone());
+        agent.insert("backstory", self.agent.backstory.clone());
+        if let Some(llm) = &self.agent.llm {
+            agent.insert("llm", llm.clone());
+        }
+
+        let mut task = BTreeMap::new();
+        task.insert("description", self.task.description.clone());
+        task.insert("expected_output", self.task.expected_output.clone());
+        task.insert("agent", self.agent.key.clone());
+
+        let agents = serde_yaml::to_string(&BTreeMap::from([(self.agent.key.clone(), agent)]))?;
+        let tasks = serde_yaml::to_string(&BTreeMap::from([(self.task.key.clone(), task)]))?;
+        Ok((agents, tasks))
+    }
+}
+
+// ============================================================================
+// Runs and events
+// ============================================================================
+
+/// One entry in a run's live event log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaygroundEvent {
+    /// Position in the run's event log, starting at 0.
+    pub seq: usize,
+    /// Event kind: "run_started", "message", "run_completed", "run_failed".
+    pub kind: String,
+    /// When the event was recorded.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Event payload.
+    pub data: Value,
+}
+
+/// State of a playground run.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlaygroundRun {
+    /// Run identifier.
+    pub id: String,
+    /// The agent/task pair that was run.
+    pub spec: RunSpec,
+    /// "running", "completed" or "failed".
+    pub status: String,
+    /// Final answer, once completed.
+    pub output: Option<String>,
+    /// Error message, if the run failed.
+    pub error: Option<String>,
+    /// Messages exchanged with the LLM, in order.
+    pub transcript: Vec<HashMap<String, String>>,
+    /// Every event emitted so far.
+    pub events: Vec<PlaygroundEvent>,
+    #[serde(skip)]
+    sender: broadcast::Sender<PlaygroundEvent>,
+}
+
+/// What an agent run produced: the final output and the message transcript.
+pub type RunOutcome =
+    Result<(String, Vec<HashMap<String, String>>), (String, Vec<HashMap<String, String>>)>;
+
+/// Executes a [`RunSpec`]. Swappable so the API can be exercised without an LLM.
+pub type AgentRunner = Arc<dyn Fn(&RunSpec) -> RunOutcome + Send + Sync>;
+
+/// Run the spec with a real [`Agent`] against its configured provider.
+pub fn agent_runner() -> AgentRunner {
+    Arc::new(|spec: &RunSpec| {
+        let mut agent = Agent::new(
+            spec.agent.role.clone(),
+            spec.agent.goal.clone(),
+            spec.agent.backstory.clone(),
+        );
+        agent.llm = spec.agent.llm.clone();
+        agent.verbose = false;
+
+        let description = if spec.task.expected_output.is_empty() {
+            spec.task.description.clone()
+        } else {
+            format!(
+                "{}\n\nExpected output: {}",
+                spec.task.description, spec.task.expected_output
+            )
+        };
+        match agent.execute_task(&description, None, None) {
+            Ok(output) => Ok((output, agent.last_messages.clone())),
+            Err(error) => Err((error, agent.last_messages.clone())),
+        }
+    })
+}
+
+/// Shared playground state.
+#[derive(Clone)]
+pub struct PlaygroundState {
+    runs: Arc<RwLock<HashMap<String, PlaygroundRun>>>,
+    runner: AgentRunner,
+}
+
+impl Default for PlaygroundState {
+    fn default() -> Self {
+        Self::new(agent_runner())
+    }
+}
+
+impl PlaygroundState {
+    /// Create a playground that executes runs with `runner`.
+    pub fn new(runner: AgentRunner) -> Self {
+        Self {
+            runs: Arc::new(RwLock::new(HashMap::new())),
+            runner,
+        }
+    }
+
+    /// Snapshot of a run.
+    pub fn run(&self, id: &str) -> Option<PlaygroundRun> {
+        self.runs.read().ok()?.get(id).cloned()
+    }
+
+    /// Append an event to a run and broadcast it to live subscribers.
+    fn push_event(&self, id: &str, kind: &str, data: Value) {
+        let Ok(mut runs) = self.runs.write() else {
+            return;
+        };
+        if let Some(run) = runs.get_mut(id) {
+            let event = PlaygroundEvent {
+                seq: run.events.len(),
+                kind: kind.to_string(),
+                timestamp: chrono::Utc::now(),
+                data,
+            };
+            run.events.push(event.clone());
+            // No subscribers is fine; the event stays in the log.
+            let _ = run.sender.send(event);
+        }
+    }
+
+    /// Register a run and execute it in the background. Returns the run ID.
+    pub fn start(&self, spec: RunSpec) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let (sender, _) = broadcast::channel(256);
+        if let Ok(mut runs) = self.runs.write() {
+            runs.insert(
+                id.clone(),
+                PlaygroundRun {
+                    id: id.clone(),
+                    spec: spec.clone(),
+                    status: "running".to_string(),
+                    output: None,
+                    error: None,
+                    transcript: Vec::new(),
+                    events: Vec::new(),
+                    sender,
+                },
+            );
+        }
+        self.push_event(
+            &id,
+            "run_started",
+            serde_json::json!({"agent": spec.agent.role, "llm": spec.agent.llm}),
+        );
+
+        let state = self.clone();
+        let run_id = id.clone();
+        tokio::spawn(async move {
+            let runner = state.runner.clone();
+            let outcome = tokio::task::spawn_blocking(move || runner(&spec))
+                .await
+                .unwrap_or_else(|e| Err((format!("Run panicked: {}", e), Vec::new())));
+            state.finish(&run_id, outcome);
+        });
+        id
+    }
+
+    fn finish(&self, id: &str, outcome: RunOutcome) {
+        let (result, transcript) = match outcome {
+            Ok((output, transcript)) => (Ok(output), transcript),
+            Err((error, transcript)) => (Err(error), transcript),
+        };
+        for message in &transcript {
+            self.push_event(id, "message", serde_json::json!(message));
+        }
+
+        if let Ok(mut runs) = self.runs.write() {
+            if let Some(run) = runs.get_mut(id) {
+                run.transcript = transcript;
+                match &result {
+                    Ok(output) => {
+                        run.status = "completed".to_string();
+                        run.output = Some(output.clone());
+                    }
+                    Err(error) => {
+                        run.status = "failed".to_string();
+                        run.error = Some(error.clone());
+                    }
+                }
+            }
+        }
+        match result {
+            Ok(output) => {
+                self.push_event(id, "run_completed", serde_json::json!({"output": output}))
+            }
+            Err(error) => self.push_event(id, "run_failed", serde_json::json!({"error": error})),
+        }
+    }
+}
+
+/// Build the playground router.
+pub fn playground_router(state: PlaygroundState) -> Router {
+    Router::new()
+        .route("/", get(index_handler))
+        .route("/api/providers", get(providers_handler))
+        .route("/api/runs", post(start_run_handler))
+        .route("/api/runs/:id", get(get_run_handler))
+        .route("/api/runs/:id/events", get(run_events_handler))
+        .route("/api/export", post(export_handler))
+        .with_state(state)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// GET / — the playground UI.
+async fn index_handler() -> Html<&'static str> {
+    Html(PLAYGROUND_HTML)
+}
+
+/// GET /api/providers — providers whose API key is set in the environment.
+async fn providers_handler() -> Json<Value> {
+    let providers: Vec<Value> = PROVIDERS
+        .iter()
+        .map(|(name, env_var, default_model)| {
+            serde_json::json!({
+                "name": name,
+                "configured": std::env::var(env_var).map(|v| !v.is_empty()).unwrap_or(false),
+                "default_model": default_model,
+            })
+        })
+        .collect();
+    Json(serde_json::json!({ "providers": providers }))
+}
+
+/// POST /api/runs — start a run.
+async fn start_run_handler(
+    State(state): State<PlaygroundState>,
+    Json(spec): Json<RunSpec>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    if spec.agent.role.trim().is_empty() || spec.task.description.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "agent.role and task.description are required"})),
+        ));
+    }
+    let run_id = state.start(spec);
+    Ok(Json(serde_json::json!({ "run_id": run_id })))
+}
+
+/// GET /api/runs/:id — run snapshot.
+async fn get_run_handler(
+    State(state): State<PlaygroundState>,
+    Path(id): Path<String>,
+) -> Result<Json<PlaygroundRun>, (StatusCode, Json<Value>)> {
+    state.run(&id).map(Json).ok_or_else(|| run_not_found(&id))
+}
+
+/// GET /api/runs/:id/events — replay the event log, then stream new events.
+async fn run_events_handler(
+    State(state): State<PlaygroundState>,
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<Value>)> {
+    let (backlog, receiver) = {
+        let runs = state.runs.read().map_err(|_| run_not_found(&id))?;
+        let run = runs.get(&id).ok_or_else(|| run_not_found(&id))?;
+        (run.events.clone(), run.sender.subscribe())
+    };
+    let finished = backlog.iter().any(|e| is_final(&e.kind));
+    let next_seq = backlog.len();
+
+    let live = stream::unfold(
+        (receiver, next_seq, finished),
+        |(mut receiver, next_seq, done)| async move {
+            if done {
+                return None;
+            }
+            loop {
+                match receiver.recv().await {
+                    // Skip events already sent as part of the backlog.
+                    Ok(event) if event.seq < next_seq => continue,
+                    Ok(event) => {
+                        let done = is_final(&event.kind);
+                        return Some((event, (receiver, next_seq, done)));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    let events = stream::iter(backlog).chain(live).map(|event| {
+        Ok(Event::default()
+            .event(event.kind.clone())
+            .json_data(&event)
+            .unwrap_or_default())
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// POST /api/export — render a spec as YAML config.
+async fn export_handler(
+    Json(spec): Json<RunSpec>,
+) -> Result<Json<Value>, (StatusCode, Json<Value>)> {
+    let (agents, tasks) = spec.to_yaml().map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })?;
+    Ok(Json(serde_json::json!({
+        "agents_yaml": agents,
+        "tasks_yaml": tasks,
+    })))
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn is_final(kind: &str) -> bool {
+    kind == "run_completed" || kind == "run_failed"
+}
+
+fn run_not_found(id: &str) -> (StatusCode, Json<Value>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(serde_json::json!({"error": format!("Run '{}' not found", id)})),
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn spec() -> RunSpec {
+        RunSpec {
+            agent: AgentSpec {
+                key: "researcher".to_string(),
+                role: "Researcher".to_string(),
+                goal: "Find facts".to_string(),
+                backstory: "Curious".to_string(),
+                llm: Some("openai/gpt-4o-mini".to_string()),
+            },
+            task: TaskSpec {
+                key: "research_task".to_string(),
+                description: "Summarize Rust".to_string(),
+                expected_output: "One paragraph".to_string(),
+            },
+        }
+    }
+
+    fn echo_runner() -> AgentRunner {
+        Arc::new(|spec: &RunSpec| {
+            let message = HashMap::from([
+                ("role".to_string(), "user".to_string()),
+                ("content".to_string(), spec.task.description.clone()),
+            ]);
+            Ok((format!("done: {}", spec.task.description), vec![message]))
+        })
+    }
+
+    #[test]
+    fn test_export_yaml() {
+        let (agents, tasks) = spec().to_yaml().unwrap();
+        assert!(agents.starts_with("researcher:"));
+        assert!(agents.contains("role: Researcher"));
+        assert!(agents.contains("llm: openai/gpt-4o-mini"));
+        assert!(tasks.contains("research_task:"));
+        assert!(tasks.contains("agent: researcher"));
+    }
+
+    #[tokio::test]
+    async fn test_run_lifecycle() {
+        let state = PlaygroundState::new(echo_runner());
+        let app = playground_router(state.clone());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/runs")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_string(&spec()).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        let run_id = body["run_id"].as_str().unwrap().to_string();
+
+        for _ in 0..100 {
+            if state.run(&run_id).unwrap().status != "running" {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let run = state.run(&run_id).unwrap();
+        assert_eq!(run.status, "completed");
+        assert_eq!(run.output.as_deref(), Some("done: Summarize Rust"));
+        assert_eq!(run.transcript.len(), 1);
+        let kinds: Vec<&str> = run.events.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["run_started", "message", "run_completed"]);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_run_returns_404() {
+        let app = playground_router(PlaygroundState::new(echo_runner()));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/runs/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,512 @@
+//! Per-tenant execution quotas for hosted crews.
+//!
+//! Each tenant is identified by one or more API keys (sent as `X-API-Key` or
+//! `Authorization: Bearer`) and may be limited to:
+//!
+//! - a number of concurrent executions,
+//! - a daily token budget and a daily cost budget (UTC days; cost is derived
+//!   from token usage with the configured [`Pricing`]),
+//! - a list of crews it may run.
+//!
+//! Limits are enforced when a kickoff is admitted; a run that is already
+//! going is never interrupted. Daily usage is persisted to a JSON file so
+//! restarts do not reset budgets.
+//!
+//! # Configuration
+//!
+//! ```yaml
+//! usage_file: /var/lib/crewai/usage.json
+//! pricing:
+//!   prompt_per_million: 3.0
+//!   completion_per_million: 15.0
+//! tenants:
+//!   - id: search-team
+//!     api_keys: [sk-search-1]
+//!     max_concurrent: 2
+//!     daily_token_budget: 1000000
+//!     daily_cost_budget: 25.0
+//!     allowed_crews: [researcher]
+//! ```
+//!
+//! # Endpoints
+//!
+//! - `GET /quota` — The caller's limits, today's usage and what remains
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::security::secrets::REDACTED;
+use crate::types::usage_metrics::UsageMetrics;
+
+/// Environment variable pointing at the quota configuration (YAML or JSON).
+pub const QUOTAS_ENV: &str = "CREWAI_QUOTAS";
+
+/// Token prices used to turn usage into cost.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pricing {
+    /// Cost per million prompt tokens.
+    #[serde(default)]
+    pub prompt_per_million: f64,
+    /// Cost per million completion tokens.
+    #[serde(default)]
+    pub completion_per_million: f64,
+}
+
+impl Pricing {
+    /// Cost of `usage`.
+    pub fn cost(&self, usage: &UsageMetrics) -> f64 {
+        (usage.prompt_tokens.max(0) as f64 * self.prompt_per_million
+            + usage.completion_tokens.max(0) as f64 * self.completion_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Limits for one tenant. Unset limits are not enforced.
+///
+/// The API keys are neither serialized nor shown by `Debug`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TenantQuota {
+    /// Tenant ID.
+    pub id: String,
+    /// API keys identifying the tenant.
+    #[serde(default, skip_serializing)]
+    pub api_keys: Vec<String>,
+    /// Maximum executions running at once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<usize>,
+    /// Maximum tokens per UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_token_budget: Option<u64>,
+    /// Maximum cost per UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_cost_budget: Option<f64>,
+    /// Crews the tenant may run; `None` allows all.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_crews: Option<Vec<String>>,
+}
+
+impl std::fmt::Debug for TenantQuota {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let api_keys: Vec<&str> = self.api_keys.iter().map(|_| REDACTED).collect();
+        f.debug_struct("TenantQuota")
+            .field("id", &self.id)
+            .field("api_keys", &api_keys)
+            .field("max_concurrent", &self.max_concurrent)
+            .field("daily_token_budget", &self.daily_token_budget)
+            .field("daily_cost_budget", &self.daily_cost_budget)
+            .field("allowed_crews", &self.allowed_crews)
+            .finish()
+    }
+}
+
+/// Quota configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Tenants and their limits.
+    #[serde(default)]
+    pub tenants: Vec<TenantQuota>,
+    /// Token prices for cost budgets.
+    #[serde(default)]
+    pub pricing: Pricing,
+    /// Where daily usage is persisted; in memory only when unset.
+    #[serde(default)]
+    pub usage_file: Option<PathBuf>,
+}
+
+impl QuotaConfig {
+    /// Load from a YAML or JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+        if is_json {
+            serde_json::from_str(&text).map_err(|e| format!("Invalid quota config: {}", e))
+        } else {
+            serde_yaml::from_str(&text).map_err(|e| format!("Invalid quota config: {}", e))
+        }
+    }
+}
+
+/// A tenant's usage for one UTC day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DailyUsage {
+    /// The day the counters belong to.
+    pub date: NaiveDate,
+    /// Executions admitted.
+    pub executions: u64,
+    /// Prompt tokens used.
+    pub prompt_tokens: u64,
+    /// Completion tokens used.
+    pub completion_tokens: u64,
+    /// Total tokens used.
+    pub total_tokens: u64,
+    /// Cost of the tokens used.
+    pub cost: f64,
+}
+
+impl DailyUsage {
+    fn today() -> Self {
+        Self {
+            date: Utc::now().date_naive(),
+            executions: 0,
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+            cost: 0.0,
+        }
+    }
+}
+
+/// Why a request was refused.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum QuotaError {
+    #[error("Missing or unknown API key")]
+    UnknownKey,
+    #[error("Tenant '{tenant}' may not run crew '{crew}'")]
+    CrewNotAllowed { tenant: String, crew: String },
+    #[error("Tenant '{tenant}' already has {limit} execution(s) running")]
+    ConcurrencyExceeded { tenant: String, limit: usize },
+    #[error("Tenant '{tenant}' used its daily token budget of {budget}")]
+    TokenBudgetExhausted { tenant: String, budget: u64 },
+    #[error("Tenant '{tenant}' used its daily cost budget of {budget:.2}")]
+    CostBudgetExhausted { tenant: String, budget: f64 },
+}
+
+impl QuotaError {
+    /// HTTP status for the refusal.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::UnknownKey => StatusCode::UNAUTHORIZED,
+            Self::CrewNotAllowed { .. } => StatusCode::FORBIDDEN,
+            _ => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    /// JSON error response for the refusal.
+    pub fn response(&self) -> (StatusCode, Json<Value>) {
+        (
+            self.status_code(),
+            Json(serde_json::json!({ "error": self.to_string() })),
+        )
+    }
+}
+
+/// A tenant's limits, usage and remaining budget.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuotaStatus {
+    /// Tenant limits.
+    pub quota: TenantQuota,
+    /// Executions running now.
+    pub active: usize,
+    /// Today's usage.
+    pub usage: DailyUsage,
+    /// Tokens left today, if budgeted.
+    pub remaining_tokens: Option<u64>,
+    /// Cost left today, if budgeted.
+    pub remaining_cost: Option<f64>,
+}
+
+#[derive(Default)]
+struct QuotaState {
+    usage: HashMap<String, DailyUsage>,
+    active: HashMap<String, usize>,
+}
+
+/// Admits executions against tenant quotas and tracks their usage.
+#[derive(Clone)]
+pub struct QuotaManager {
+    config: Arc<QuotaConfig>,
+    /// API key → index into `config.tenants`.
+    keys: Arc<HashMap<String, usize>>,
+    state: Arc<Mutex<QuotaState>>,
+}
+
+impl QuotaManager {
+    /// Create a manager, loading persisted usage from `config.usage_file`.
+    pub fn new(config: QuotaConfig) -> Self {
+        let keys = config
+            .tenants
+            .iter()
+            .enumerate()
+            .flat_map(|(i, t)| t.api_keys.iter().map(move |k| (k.clone(), i)))
+            .collect();
+        let usage = config
+            .usage_file
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            config: Arc::new(config),
+            keys: Arc::new(keys),
+            state: Arc::new(Mutex::new(QuotaState {
+                usage,
+                active: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Load the configuration named by [`QUOTAS_ENV`], if set.
+    pub fn from_env() -> Option<Result<Self, String>> {
+        let path = std::env::var(QUOTAS_ENV).ok().filter(|p| !p.is_empty())?;
+        Some(QuotaConfig::from_file(path).map(Self::new))
+    }
+
+    /// The tenant owning the API key in `headers`.
+    pub fn tenant(&self, headers: &HeaderMap) -> Result<&TenantQuota, QuotaError> {
+        api_key(headers)
+            .and_then(|key| self.keys.get(key))
+            .map(|&i| &self.config.tenants[i])
+            .ok_or(QuotaError::UnknownKey)
+    }
+
+    /// Admit an execution of `crew` for the caller, reserving a concurrency
+    /// slot until the returned permit is dropped.
+    pub fn admit(&self, headers: &HeaderMap, crew: &str) -> Result<QuotaPermit, QuotaError> {
+        let tenant = self.tenant(headers)?;
+        let id = tenant.id.clone();
+        if let Some(allowed) = &tenant.allowed_crews {
+            if !allowed.iter().any(|c| c == crew) {
+                return Err(QuotaError::CrewNotAllowed {
+                    tenant: id,
+                    crew: crew.to_string(),
+                });
+            }
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let usage = current_usage(&mut state.usage, &id);
+        if let Some(budget) = tenant.daily_token_budget {
+            if usage.total_tokens >= budget {
+                return Err(QuotaError::TokenBudgetExhausted { tenant: id, budget });
+            }
+        }
+        if let Some(budget) = tenant.daily_cost_budget {
+            if usage.cost >= budget {
+                return Err(QuotaError::CostBudgetExhausted { tenant: id, budget });
+            }
+        }
+        let active = state.active.get(&id).copied().unwrap_or(0);
+        if let Some(limit) = tenant.max_concurrent {
+            if active >= limit {
+                return Err(QuotaError::ConcurrencyExceeded { tenant: id, limit });
+            }
+        }
+
+        state.active.insert(id.clone(), active + 1);
+        current_usage(&mut state.usage, &id).executions += 1;
+        self.persist(&state);
+        Ok(QuotaPermit {
+            manager: self.clone(),
+            tenant: id,
+        })
+    }
+
+    /// The caller's quota status.
+    pub fn status(&self, headers: &HeaderMap) -> Result<QuotaStatus, QuotaError> {
+        let tenant = self.tenant(headers)?;
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let usage = current_usage(&mut state.usage, &tenant.id).clone();
+        Ok(QuotaStatus {
+            quota: tenant.clone(),
+            active: state.active.get(&tenant.id).copied().unwrap_or(0),
+            remaining_tokens: tenant
+                .daily_token_budget
+                .map(|b| b.saturating_sub(usage.total_tokens)),
+            remaining_cost: tenant.daily_cost_budget.map(|b| (b - usage.cost).max(0.0)),
+            usage,
+        })
+    }
+
+    fn record(&self, tenant: &str, usage: &UsageMetrics) {
+        let cost = self.config.pricing.cost(usage);
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let day = current_usage(&mut state.usage, tenant);
+        day.prompt_tokens += usage.prompt_tokens.max(0) as u64;
+        day.completion_tokens += usage.completion_tokens.max(0) as u64;
+        day.total_tokens += usage.total_tokens.max(0) as u64;
+        day.cost += cost;
+        self.persist(&state);
+    }
+
+    fn release(&self, tenant: &str) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(active) = state.active.get_mut(tenant) {
+            *active = active.saturating_sub(1);
+        }
+    }
+
+    /// Write usage counters to the usage file (atomically, via rename).
+    fn persist(&self, state: &QuotaState) {
+        let Some(path) = &self.config.usage_file else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&state.usage)
+            .map_err(std::io::Error::other)
+            .and_then(|json| {
+                let tmp = path.with_extension("tmp");
+                std::fs::write(&tmp, json)?;
+                std::fs::rename(&tmp, path)
+            });
+        if let Err(e) = result {
+            log::warn!("Failed to persist quota usage to {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Today's counters for `tenant`, reset when the day changed.
+fn current_usage<'a>(
+    usage: &'a mut HashMap<String, DailyUsage>,
+    tenant: &str,
+) -> &'a mut DailyUsage {
+    let day = usage
+        .entry(tenant.to_string())
+        .or_insert_with(DailyUsage::today);
+    if day.date != Utc::now().date_naive() {
+        *day = DailyUsage::today();
+    }
+    day
+}
+
+/// API key from `X-API-Key` or `Authorization: Bearer`.
+fn api_key(headers: &HeaderMap) -> Option<&str> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    header("x-api-key").or_else(|| {
+        header("authorization")
+            .and_then(|v| v.split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, token)| token.trim())
+    })
+}
+
+/// An admitted execution. Holds a concurrency slot until dropped.
+pub struct QuotaPermit {
+    manager: QuotaManager,
+    tenant: String,
+}
+
+impl QuotaPermit {
+    /// The admitted tenant.
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// Charge the execution's token usage to the tenant.
+    pub fn record(&self, usage: &UsageMetrics) {
+        self.manager.record(&self.tenant, usage);
+    }
+}
+
+impl std::fmt::Debug for QuotaPermit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuotaPermit")
+            .field("tenant", &self.tenant)
+            .finish()
+    }
+}
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        self.manager.release(&self.tenant);
+    }
+}
+
+/// Build the quota status router.
+pub fn quota_router(manager: QuotaManager) -> Router {
+    Router::new()
+        .route("/quota", get(status_handler))
+        .with_state(manager)
+}
+
+/// GET /quota — the caller's quota status.
+async fn status_handler(
+    State(manager): State<QuotaManager>,
+    headers: HeaderMap,
+) -> Result<Json<QuotaStatus>, (StatusCode, Json<Value>)> {
+    manager.status(&headers).map(Json).map_err(|e| e.response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", key.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_admission_and_persisted_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: QuotaConfig = serde_yaml::from_str(&format!(
+            "usage_file: {}\n\
+             pricing: {{prompt_per_million: 1.0, completion_per_million: 2.0}}\n\
+             tenants:\n\
+             - id: team-a\n  api_keys: [key-a]\n  max_concurrent: 1\n  \
+               daily_token_budget: 1000\n  allowed_crews: [research]\n",
+            dir.path().join("usage.json").display()
+        ))
+        .unwrap();
+        let quotas = QuotaManager::new(config.clone());
+        let debug = format!("{:?}", config);
+        assert!(
+            !debug.contains("key-a") && debug.contains(REDACTED),
+            "{}",
+            debug
+        );
+
+        assert_eq!(
+            quotas.admit(&headers("nope"), "research").err(),
+            Some(QuotaError::UnknownKey)
+        );
+        assert_eq!(
+            quotas
+                .admit(&headers("key-a"), "other")
+                .unwrap_err()
+                .status_code(),
+            StatusCode::FORBIDDEN
+        );
+
+        let permit = quotas.admit(&headers("key-a"), "research").unwrap();
+        assert!(matches!(
+            quotas.admit(&headers("key-a"), "research"),
+            Err(QuotaError::ConcurrencyExceeded { limit: 1, .. })
+        ));
+        permit.record(&UsageMetrics {
+            total_tokens: 1500,
+            prompt_tokens: 1000,
+            completion_tokens: 500,
+            ..Default::default()
+        });
+        drop(permit);
+
+        // A restart keeps today's usage, so the token budget stays spent.
+        let quotas = QuotaManager::new(config);
+        let status = quotas.status(&headers("key-a")).unwrap();
+        assert_eq!(status.active, 0);
+        assert_eq!(status.usage.executions, 1);
+        assert_eq!(status.remaining_tokens, Some(0));
+        assert!((status.usage.cost - 0.002).abs() < 1e-9);
+        assert_eq!(
+            quotas
+                .admit(&headers("key-a"), "research")
+                .unwrap_err()
+                .status_code(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+}
@@ -3,12 +3,18 @@
 //! # Routes
 //!
 //! - `GET  /health`            — Returns `{"status": "ok", "version": "1.9.3"}`
+//! - `GET  /ready`             — Readiness probe; 503 while shutting down
 //! - `POST /execute`           — Accepts `StepDelegationRequest`, runs crew task
 //! - `GET  /modules`           — List active modules
 //! - `GET  /modules/:id`       — Get module details
 //! - `POST /modules/:id/activate`   — Activate a loaded module
 //! - `POST /modules/:id/deactivate` — Deactivate a module
 //! - `POST /modules/:id/gate-check` — Check cognitive gate
+//! - `/flows/*`                — Flow kickoff and HITL resume (see [`super::flow_routes`])
+//! - `GET  /metrics`           — Prometheus metrics (see [`super::metrics`])
+//! - `/crews/*`, `/executions/*` — Crew kickoff, run status and SSE events (see [`super::crew_routes`])
+//! - `GET  /quota`             — Caller's tenant quota and usage, when quotas are configured (see [`super::quotas`])
+//! - `POST /mcp`               — MCP endpoint exposing global tools and crews (see [`crate::mcp::server`])
 
 use std::sync::{Arc, RwLock};
 
@@ -30,6 +36,7 @@ use crate::contract::types::{
     DataEnvelope, EnvelopeMetadata, StepDelegationRequest, StepDelegationResponse,
 };
 use crate::modules::runtime::ModuleRuntime;
+use crate::utilities::profiles::{self, ActiveProfile, ProfileSet};
 
 /// Shared application state for the HTTP server.
 #[derive(Clone)]
@@ -40,20 +47,64 @@ pub struct AppState {
     pub module_runtime: Arc<RwLock<ModuleRuntime>>,
     /// Chat configuration (XAI keys, URLs, identity seed).
     pub chat_config: Arc<ChatConfig>,
+    /// Flows exposed over HTTP for kickoff and human-in-the-loop resume.
+    pub flows: super::flow_routes::FlowRegistry,
+    /// Event-bus metrics served on `/metrics`.
+    pub metrics: Arc<super::metrics::MetricsCollector>,
+    /// Crews exposed over HTTP and the runs started from them.
+    pub crews: super::crew_routes::CrewServerState,
+    /// A2A endpoint state; delegates to the same crews.
+    pub a2a: super::a2a_routes::A2AState,
 }
 
 impl AppState {
     pub fn new() -> Self {
+        let mut crews = super::crew_routes::CrewServerState::default();
+        if let Some(profile) = active_profile() {
+            tracing::info!("Using run profile '{}'", profile.name);
+            profile.apply_env();
+            crews.crews = crews.crews.with_profile(profile);
+        }
+        let mut a2a = super::a2a_routes::A2AState::new()
+            .with_crews(crews.crews.clone(), crews.executions.clone());
+        // A broken quota file must not silently leave the server unrestricted.
+        if let Some(quotas) = super::quotas::QuotaManager::from_env() {
+            let quotas = quotas.unwrap_or_else(|e| panic!("{}: {}", super::quotas::QUOTAS_ENV, e));
+            crews.quotas = Some(quotas.clone());
+            a2a = a2a.with_quotas(quotas);
+        }
+        if let Some(auth) = crate::a2a::auth::ServerAuth::from_env() {
+            a2a = a2a.with_auth(auth);
+        }
+        if let Ok(url) = std::env::var("CREWAI_A2A_URL") {
+            a2a = a2a.with_url(url);
+        }
         Self {
             recorder: Arc::new(RwLock::new(ContractRecorder::new())),
             module_runtime: Arc::new(RwLock::new(ModuleRuntime::new(
                 "anthropic/claude-opus-4-5-20251101",
             ))),
             chat_config: Arc::new(ChatConfig::from_env()),
+            flows: super::flow_routes::FlowRegistry::new(),
+            metrics: super::metrics::MetricsCollector::global(),
+            crews,
+            a2a,
         }
     }
 }
 
+/// The run profile selected by `CREWAI_PROFILES` and `CREWAI_PROFILE`.
+///
+/// Panics on a broken profile file or an unknown profile: running with the
+/// wrong settings (e.g. dev models in prod) must not go unnoticed.
+fn active_profile() -> Option<ActiveProfile> {
+    let profiles =
+        ProfileSet::from_env()?.unwrap_or_else(|e| panic!("{}: {}", profiles::PROFILES_ENV, e));
+    profiles
+        .select(None)
+        .unwrap_or_else(|e| panic!("{}: {}", profiles::PROFILE_ENV, e))
+}
+
 impl Default for AppState {
     fn default() -> Self {
         Self::new()
@@ -65,6 +116,33 @@ pub fn app_router(state: AppState) -> Router {
     // Chat routes use Arc<ChatConfig> as state
     let chat_config = state.chat_config.clone();
 
+    // Flow routes (own state: registered flow factories)
+    let flow_routes = super::flow_routes::flow_router(state.flows.clone());
+
+    // Metrics route (own state: event-bus collector)
+    let metrics_routes = super::metrics::metrics_router(state.metrics.clone());
+
+    // Crew routes (own state: registered crews and their executions)
+    let crew_routes = super::crew_routes::crew_router(state.crews.clone());
+
+    // A2A protocol routes (own state: task store, shared crews)
+    let a2a_routes = super::a2a_routes::a2a_router(state.a2a.clone());
+
+    // MCP endpoint (own state: global tools, shared crews and quotas)
+    let mut mcp_server = crate::mcp::MCPServer::new("crewai")
+        .with_tools(crate::tools::registry::ToolRegistry::global().clone())
+        .with_crews(state.crews.crews.clone(), state.crews.executions.clone());
+    if let Some(quotas) = &state.crews.quotas {
+        mcp_server = mcp_server.with_quotas(quotas.clone());
+    }
+    let mcp_routes = mcp_server.router();
+
+    // Quota status route, only when quotas are configured
+    let quota_routes = match &state.crews.quotas {
+        Some(quotas) => super::quotas::quota_router(quotas.clone()),
+        None => Router::new(),
+    };
+
     // Barrier stack routes (separate state: Arc<RwLock<BarrierStack>>)
     let barrier_state = super::barrier_routes::BarrierState::new(std::sync::RwLock::new(
         crate::drivers::barrier_stack::BarrierStack::new(),
@@ -73,6 +151,7 @@ pub fn app_router(state: AppState) -> Router {
 
     let main_routes = Router::new()
         .route("/health", get(health_handler))
+        .route("/ready", get(ready_handler))
         .route("/execute", post(execute_handler))
         .route("/modules", get(list_modules_handler))
         .route("/modules/{id}", get(get_module_handler))
@@ -84,12 +163,15 @@ pub fn app_router(state: AppState) -> Router {
         .layer(CorsLayer::permissive())
         .with_state(state);
 
-    // A2A protocol routes (own state: task store)
-    let a2a_state = super::a2a_routes::A2AState::new();
-    let a2a_routes = super::a2a_routes::a2a_router(a2a_state);
-
     // Merge barrier routes (own state) after main routes are finalized
-    main_routes.merge(barrier_routes).merge(a2a_routes)
+    main_routes
+        .merge(barrier_routes)
+        .merge(a2a_routes)
+        .merge(flow_routes)
+        .merge(metrics_routes)
+        .merge(crew_routes)
+        .merge(quota_routes)
+        .merge(mcp_routes)
 }
 
 /// GET /health — liveness probe.
@@ -101,6 +183,22 @@ async fn health_handler() -> impl IntoResponse {
     }))
 }
 
+/// GET /ready — readiness probe; 503 once shutdown began, matching the
+/// `STOPPING=1` sent to systemd.
+async fn ready_handler(State(state): State<AppState>) -> impl IntoResponse {
+    if state.crews.executions.is_shutting_down() {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({ "status": "stopping" })),
+        )
+    } else {
+        (
+            StatusCode::OK,
+            Json(serde_json::json!({ "status": "ready" })),
+        )
+    }
+}
+
 /// POST /execute — execute a crew.* step delegation.
 ///
 /// Request:  `StepDelegationRequest` = `{ "step": UnifiedStep, "input": DataEnvelope }`
@@ -108,6 +206,7 @@ async fn health_handler() -> impl IntoResponse {
 ///
 /// The handler:
 /// 1. Maps incoming step parameters to an Agent + Task configuration
+///    (steps that cannot move to `running` are rejected with 409 Conflict)
 /// 2. Runs the agent via `execute_task()` (sync, wrapped in spawn_blocking)
 /// 3. Populates decision trail fields (reasoning, confidence, alternatives) from output
 /// 4. Returns DataEnvelope with result
@@ -156,6 +255,14 @@ async fn execute_handler(
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
 
+    // Mark step as running
+    step.mark_running().map_err(|e| {
+        (
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+    })?;
+
     // Record step start
     let crew_name = format!("delegation-{}", &step.execution_id);
     {
@@ -171,9 +278,6 @@ async fn execute_handler(
         recorder.on_task_started(&step.step_id, &step.name, &crew_name, Some(&role));
     }
 
-    // Mark step as running
-    step.mark_running();
-
     // Execute via Agent (synchronous, so use spawn_blocking)
     let task_description = if task_input.is_empty() {
         step.name.clone()
@@ -213,7 +317,9 @@ async fn execute_handler(
             };
 
             // Update step with completion + decision trail
-            step.mark_completed(serde_json::json!({"result": &output}));
+            if let Err(e) = step.mark_completed(serde_json::json!({"result": &output})) {
+                log::warn!("execute: {}", e);
+            }
             step.confidence = Some(confidence);
             // Reasoning is extracted from agent's last messages if available
             step.reasoning = Some(format!("Executed as {} agent", step.step_type));
@@ -237,7 +343,9 @@ async fn execute_handler(
             }))
         }
         Ok(Err(error)) => {
-            step.mark_failed(&error);
+            if let Err(e) = step.mark_failed(&error) {
+                log::warn!("execute: {}", e);
+            }
 
             // Record failure
             {
@@ -267,7 +375,9 @@ async fn execute_handler(
         }
         Err(join_error) => {
             let error_msg = format!("Agent execution panicked: {}", join_error);
-            step.mark_failed(&error_msg);
+            if let Err(e) = step.mark_failed(&error_msg) {
+                log::warn!("execute: {}", e);
+            }
 
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -500,6 +610,7 @@ async fn gate_check_handler(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::contract::types::UnifiedStep;
     use axum::body::Body;
     use axum::http::Request;
     use tower::ServiceExt;
@@ -552,6 +663,28 @@ mod tests {
         assert!(json["error"].as_str().unwrap().contains("n8n.set"));
     }
 
+    #[tokio::test]
+    async fn test_execute_rejects_finished_step() {
+        let state = AppState::new();
+        let app = app_router(state);
+
+        let mut step = UnifiedStep::new("e1", "crew.agent", "Research", 0);
+        step.mark_running().unwrap();
+        step.mark_completed(serde_json::json!({})).unwrap();
+        let input = DataEnvelope::new(serde_json::json!({}), "trigger");
+        let req_body = StepDelegationRequest { step, input };
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/execute")
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_string(&req_body).unwrap()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+    }
+
     #[tokio::test]
     async fn test_execute_crew_agent_step() {
         let state = AppState::new();
@@ -0,0 +1,313 @@
+//! Running the server as a systemd or Windows service.
+//!
+//! Under systemd (`Type=notify`), the server reports readiness, status and
+//! shutdown through `sd_notify` and pings the watchdog when `WatchdogSec` is
+//! set. The notifications are plain datagrams to `$NOTIFY_SOCKET`; outside
+//! systemd every call is a no-op.
+//!
+//! [`ServiceDefinition`] renders the files needed to install the server:
+//! a systemd unit, or on Windows a [WinSW](https://github.com/winsw/winsw)
+//! wrapper configuration. WinSW forwards a service stop as Ctrl-C, which
+//! [`shutdown_signal`](super::shutdown_signal) already handles, so both
+//! platforms get the same graceful shutdown.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Send a raw `sd_notify` message (e.g. `"READY=1"`).
+///
+/// Returns `Ok(false)` when not running under systemd.
+pub fn sd_notify(state: &str) -> std::io::Result<bool> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::net::UnixDatagram;
+
+        let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+            return Ok(false);
+        };
+        let socket = UnixDatagram::unbound()?;
+        let path = path.to_string_lossy();
+        match path.strip_prefix('@') {
+            #[cfg(target_os = "linux")]
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+                socket.send_to_addr(state.as_bytes(), &addr)?;
+            }
+            _ => {
+                socket.send_to(state.as_bytes(), path.as_ref())?;
+            }
+        }
+        Ok(true)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = state;
+        Ok(false)
+    }
+}
+
+/// Tell systemd the server accepts connections, with a status line.
+pub fn notify_ready(status: &str) {
+    notify(&format!(
+        "READY=1\nSTATUS={}\nMAINPID={}",
+        status,
+        std::process::id()
+    ));
+}
+
+/// Tell systemd the server is shutting down.
+pub fn notify_stopping(status: &str) {
+    notify(&format!("STOPPING=1\nSTATUS={}", status));
+}
+
+/// Update the status line shown by `systemctl status`.
+pub fn notify_status(status: &str) {
+    notify(&format!("STATUS={}", status));
+}
+
+fn notify(state: &str) {
+    if let Err(e) = sd_notify(state) {
+        log::warn!("sd_notify failed: {}", e);
+    }
+}
+
+/// The watchdog interval requested by systemd (`WatchdogSec`), if any.
+pub fn watchdog_interval() -> Option<Duration> {
+    let usec: u64 = std::env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+    let for_us = std::env::var("WATCHDOG_PID")
+        .ok()
+        .and_then(|pid| pid.parse::<u32>().ok())
+        .is_none_or(|pid| pid == std::process::id());
+    (for_us && usec > 0).then(|| Duration::from_micros(usec))
+}
+
+/// Ping the systemd watchdog at half its interval from the runtime.
+///
+/// A stalled runtime misses its pings, so systemd restarts the server.
+/// Does nothing without a watchdog.
+pub fn spawn_watchdog() -> Option<tokio::task::JoinHandle<()>> {
+    let interval = watchdog_interval()? / 2;
+    Some(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            notify("WATCHDOG=1");
+        }
+    }))
+}
+
+/// Where a service definition is installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceTarget {
+    /// A systemd unit (`<name>.service`).
+    Systemd,
+    /// A WinSW configuration (`<name>.xml`) for a Windows service.
+    Windows,
+}
+
+impl ServiceTarget {
+    /// The target for the current platform.
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            Self::Windows
+        } else {
+            Self::Systemd
+        }
+    }
+}
+
+impl std::str::FromStr for ServiceTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "systemd" => Ok(Self::Systemd),
+            "windows" => Ok(Self::Windows),
+            other => Err(format!(
+                "Unknown service target '{}' (expected systemd or windows)",
+                other
+            )),
+        }
+    }
+}
+
+/// Description of the server as an installable service.
+#[derive(Debug, Clone)]
+pub struct ServiceDefinition {
+    /// Service name.
+    pub name: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Path of the server binary.
+    pub executable: PathBuf,
+    /// Working directory of the service.
+    pub working_dir: PathBuf,
+    /// Account the systemd service runs as; root when `None`.
+    pub user: Option<String>,
+    /// HTTP port.
+    pub port: u16,
+    /// Grace period for running executions on stop (`SHUTDOWN_TIMEOUT_SECS`).
+    pub shutdown_timeout: Duration,
+    /// systemd watchdog interval; disabled when `None`.
+    pub watchdog: Option<Duration>,
+}
+
+impl ServiceDefinition {
+    /// Describe the running binary as service `name`.
+    pub fn new(name: impl Into<String>) -> std::io::Result<Self> {
+        Ok(Self {
+            name: name.into(),
+            description: "crewai-rust server".to_string(),
+            executable: std::env::current_exe()?,
+            working_dir: std::env::current_dir()?,
+            user: None,
+            port: 8080,
+            shutdown_timeout: Duration::from_secs(30),
+            watchdog: Some(Duration::from_secs(60)),
+        })
+    }
+
+    /// File name of the definition for `target`.
+    pub fn file_name(&self, target: ServiceTarget) -> String {
+        match target {
+            ServiceTarget::Systemd => format!("{}.service", self.name),
+            ServiceTarget::Windows => format!("{}.xml", self.name),
+        }
+    }
+
+    /// Render the definition for `target`.
+    pub fn render(&self, target: ServiceTarget) -> String {
+        match target {
+            ServiceTarget::Systemd => self.systemd_unit(),
+            ServiceTarget::Windows => self.winsw_config(),
+        }
+    }
+
+    /// A `Type=notify` systemd unit. Secrets such as API keys belong in the
+    /// optional `/etc/crewai/<name>.env` environment file.
+    pub fn systemd_unit(&self) -> String {
+        let mut unit = format!(
+            "[Unit]\n\
+             Description={description}\n\
+             After=network-online.target\n\
+             Wants=network-online.target\n\
+             \n\
+             [Service]\n\
+             Type=notify\n\
+             NotifyAccess=main\n\
+             ExecStart={exec}\n\
+             WorkingDirectory={dir}\n\
+             Environment=PORT={port}\n\
+             Environment=SHUTDOWN_TIMEOUT_SECS={shutdown}\n\
+             EnvironmentFile=-/etc/crewai/{name}.env\n\
+             KillSignal=SIGTERM\n\
+             TimeoutStopSec={stop}\n\
+             Restart=on-failure\n\
+             RestartSec=5\n",
+            description = self.description,
+            exec = self.executable.display(),
+            dir = self.working_dir.display(),
+            port = self.port,
+            shutdown = self.shutdown_timeout.as_secs(),
+            name = self.name,
+            // Leave room past the executions' grace period for the
+            // server itself to exit.
+            stop = self.shutdown_timeout.as_secs() + 10,
+        );
+        if let Some(watchdog) = self.watchdog {
+            unit.push_str(&format!("WatchdogSec={}\n", watchdog.as_secs()));
+        }
+        if let Some(user) = &self.user {
+            unit.push_str(&format!("User={}\n", user));
+        }
+        unit.push_str("\n[Install]\nWantedBy=multi-user.target\n");
+        unit
+    }
+
+    /// A WinSW configuration; place it next to `WinSW.exe` renamed to
+    /// `<name>.exe` and run `<name>.exe install`.
+    pub fn winsw_config(&self) -> String {
+        format!(
+            "<service>\n\
+             \x20 <id>{name}</id>\n\
+             \x20 <name>{name}</name>\n\
+             \x20 <description>{description}</description>\n\
+             \x20 <executable>{exec}</executable>\n\
+             \x20 <workingdirectory>{dir}</workingdirectory>\n\
+             \x20 <env name=\"PORT\" value=\"{port}\"/>\n\
+             \x20 <env name=\"SHUTDOWN_TIMEOUT_SECS\" value=\"{shutdown}\"/>\n\
+             \x20 <stoptimeout>{stop} sec</stoptimeout>\n\
+             \x20 <startmode>Automatic</startmode>\n\
+             \x20 <onfailure action=\"restart\" delay=\"5 sec\"/>\n\
+             \x20 <log mode=\"roll\"/>\n\
+             </service>\n",
+            name = xml_escape(&self.name),
+            description = xml_escape(&self.description),
+            exec = xml_escape(&self.executable.display().to_string()),
+            dir = xml_escape(&self.working_dir.display().to_string()),
+            port = self.port,
+            shutdown = self.shutdown_timeout.as_secs(),
+            stop = self.shutdown_timeout.as_secs() + 10,
+        )
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_definitions() {
+        let definition = ServiceDefinition {
+            name: "crewai".to_string(),
+            description: "Crews & flows".to_string(),
+            executable: PathBuf::from("/opt/crewai/server"),
+            working_dir: PathBuf::from("/opt/crewai"),
+            user: Some("crewai".to_string()),
+            port: 9000,
+            shutdown_timeout: Duration::from_secs(20),
+            watchdog: Some(Duration::from_secs(30)),
+        };
+
+        let unit = definition.render(ServiceTarget::Systemd);
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains("ExecStart=/opt/crewai/server\n"));
+        assert!(unit.contains("Environment=PORT=9000\n"));
+        assert!(unit.contains("TimeoutStopSec=30\n"));
+        assert!(unit.contains("WatchdogSec=30\n"));
+        assert!(unit.contains("User=crewai\n"));
+        assert!(unit.ends_with("WantedBy=multi-user.target\n"));
+
+        let xml = definition.render(ServiceTarget::Windows);
+        assert!(xml.contains("<description>Crews &amp; flows</description>"));
+        assert!(xml.contains("<stoptimeout>30 sec</stoptimeout>"));
+        assert_eq!(definition.file_name(ServiceTarget::Windows), "crewai.xml");
+        assert!("launchd".parse::<ServiceTarget>().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sd_notify_sends_datagram() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let listener = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+
+        std::env::set_var("NOTIFY_SOCKET", &path);
+        let sent = sd_notify("READY=1");
+        std::env::remove_var("NOTIFY_SOCKET");
+        assert!(sent.unwrap());
+
+        let mut buf = [0u8; 64];
+        let n = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1");
+        assert!(!sd_notify("READY=1").unwrap());
+    }
+}
@@ -8,9 +8,22 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+use crate::events::types::task_events::{TaskCompletedEvent, TaskFailedEvent, TaskStartedEvent};
+use crate::events::{BaseEvent, CREWAI_EVENT_BUS};
+use crate::security::fingerprint::Fingerprint;
 use crate::security::security_config::SecurityConfig;
+use crate::tasks::assertions::{self, TaskAssertion};
+use crate::tasks::conditional_task::ConditionFn;
+use crate::tasks::content_guardrails::{self, ContentGuardrail};
+use crate::tasks::execution_trace::TraceScope;
 use crate::tasks::output_format::OutputFormat;
-use crate::tasks::task_output::TaskOutput;
+use crate::tasks::task_output::{LLMMessage, TaskOutput};
+use crate::types::usage_metrics::UsageScope;
+use crate::utilities::artifacts::ArtifactScope;
+use crate::utilities::i18n::get_i18n;
+use crate::utilities::normalize::{strip_code_fence, Normalizer};
+use crate::utilities::run_log;
+use crate::utilities::string_utils::interpolate_only;
 
 /// Type alias for a guardrail callback.
 ///
@@ -73,7 +86,8 @@ pub struct Task {
     pub agent: Option<String>,
 
     // ---- Context tasks (stored as task IDs) ----
-    /// IDs of other tasks providing context or input data.
+    /// IDs of earlier tasks of the crew whose outputs are the task's
+    /// context. Without it, the outputs of all earlier tasks are.
     pub context: Option<Vec<Uuid>>,
 
     // ---- Execution mode ----
@@ -87,6 +101,14 @@ pub struct Task {
     pub output_pydantic: Option<String>,
     /// Schema name for structured LLM output using native provider features.
     pub response_model: Option<String>,
+    /// Normalization of dates, numbers and units in the JSON output.
+    pub normalize: Option<Normalizer>,
+    /// Checks of the output evaluated after execution.
+    #[serde(default)]
+    pub assertions: Vec<TaskAssertion>,
+    /// PII, profanity and prompt-injection checks of the input and output.
+    #[serde(default)]
+    pub content_guardrails: Vec<ContentGuardrail>,
 
     // ---- File output ----
     /// File path for storing task output.
@@ -133,6 +155,10 @@ pub struct Task {
     pub retry_count: i32,
 
     // ---- Timing ----
+    /// Maximum execution time in seconds. When run by a crew, a task still
+    /// running after this long is stopped and its output marked as timed out.
+    #[serde(default)]
+    pub max_execution_time: Option<u64>,
     /// Start time of the task execution.
     pub start_time: Option<DateTime<Utc>>,
     /// End time of the task execution.
@@ -154,6 +180,11 @@ pub struct Task {
     #[serde(skip)]
     pub guardrails_fns: Vec<GuardrailFn>,
 
+    /// Condition on the previous task's output deciding whether a crew runs
+    /// this task; when it returns false the task is skipped (not serialized).
+    #[serde(skip)]
+    pub condition: Option<ConditionFn>,
+
     /// Task completion callback (not serialized).
     #[serde(skip)]
     pub callback: Option<TaskCallback>,
@@ -203,6 +234,9 @@ impl Clone for Task {
             output_json: self.output_json.clone(),
             output_pydantic: self.output_pydantic.clone(),
             response_model: self.response_model.clone(),
+            normalize: self.normalize.clone(),
+            assertions: self.assertions.clone(),
+            content_guardrails: self.content_guardrails.clone(),
             output_file: self.output_file.clone(),
             create_directory: self.create_directory,
             output: self.output.clone(),
@@ -216,6 +250,7 @@ impl Clone for Task {
             guardrails: self.guardrails.clone(),
             guardrail_max_retries: self.guardrail_max_retries,
             retry_count: 0,
+            max_execution_time: self.max_execution_time,
             start_time: None,
             end_time: None,
             processed_by_agents: HashSet::new(),
@@ -223,6 +258,7 @@ impl Clone for Task {
             // Non-cloneable fields
             guardrail_fn: None,
             guardrails_fns: Vec::new(),
+            condition: None,
             callback: None,
             agent_executor: None,
             original_description: self.original_description.clone(),
@@ -235,7 +271,7 @@ impl Clone for Task {
 impl Task {
     /// Create a new Task with required fields.
     pub fn new(description: String, expected_output: String) -> Self {
-        Self {
+        let mut task = Self {
             used_tools: 0,
             tools_errors: 0,
             delegations: 0,
@@ -251,6 +287,9 @@ impl Task {
             output_json: None,
             output_pydantic: None,
             response_model: None,
+            normalize: None,
+            assertions: Vec::new(),
+            content_guardrails: Vec::new(),
             output_file: None,
             create_directory: true,
             output: None,
@@ -264,17 +303,47 @@ impl Task {
             guardrails: None,
             guardrail_max_retries: 3,
             retry_count: 0,
+            max_execution_time: None,
             start_time: None,
             end_time: None,
             processed_by_agents: HashSet::new(),
             allow_crewai_trigger_context: None,
             guardrail_fn: None,
             guardrails_fns: Vec::new(),
+            condition: None,
             agent_executor: None,
             original_description: None,
             original_expected_output: None,
             original_output_file: None,
-        }
+        };
+        task.security_config = SecurityConfig::with_fingerprint(task.definition_fingerprint());
+        task
+    }
+
+    /// Fingerprint of the task's definition: its name, description and
+    /// expected output (before input interpolation) and agent.
+    pub fn definition_fingerprint(&self) -> Fingerprint {
+        Fingerprint::of_definition(
+            "task",
+            &serde_json::json!({
+                "name": self.name,
+                "description": self.original_description.as_ref().unwrap_or(&self.description),
+                "expected_output": self
+                    .original_expected_output
+                    .as_ref()
+                    .unwrap_or(&self.expected_output),
+                "agent": self.agent,
+            }),
+        )
+    }
+
+    /// Bring a definition fingerprint up to date with the task's
+    /// definition (see [`Fingerprint::update_definition`]).
+    pub fn refresh_fingerprint(&mut self) {
+        let fingerprint = self.definition_fingerprint();
+        self.security_config
+            .fingerprint
+            .update_definition(fingerprint);
     }
 
     /// Set the agent executor callback.
@@ -297,12 +366,116 @@ impl Task {
     /// In the full implementation this would delegate to an agent executor.
     /// Currently a stub that sets start_time and returns a placeholder.
     pub fn execute_sync(
+        &mut self,
+        agent: Option<&str>,
+        context: Option<&str>,
+        tools: Option<&[String]>,
+    ) -> Result<TaskOutput, String> {
+        self.refresh_fingerprint();
+        let task_id = Some(self.id.to_string());
+        let task_name = self.name.clone().or_else(|| Some(self.description.clone()));
+        self.emit_event(&mut TaskStartedEvent::new(
+            task_id.clone(),
+            task_name.clone(),
+            context.map(str::to_string),
+        ));
+        let run_log = run_log::current();
+        if let Some(log) = &run_log {
+            log.begin_task(
+                task_id.clone(),
+                task_name.clone(),
+                agent.map(str::to_string).or_else(|| self.agent.clone()),
+            );
+        }
+
+        let result = self.execute_core(agent, context, tools);
+        if let Some(log) = &run_log {
+            log.end_task(
+                result
+                    .as_ref()
+                    .map(|o| o.raw.as_str())
+                    .map_err(String::as_str),
+            );
+        }
+        match result {
+            Ok(output) => {
+                self.emit_event(&mut TaskCompletedEvent::new(
+                    task_id,
+                    task_name,
+                    serde_json::Value::String(output.raw.clone()),
+                ));
+                Ok(output)
+            }
+            Err(e) => {
+                self.emit_event(&mut TaskFailedEvent::new(task_id, task_name, e.clone()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Complete the task with the output of an earlier run instead of
+    /// executing it, as when a crew is resumed.
+    ///
+    /// Only the raw output is recorded, so a JSON or structured output is
+    /// parsed from it again.
+    pub(crate) fn restore_output(&mut self, raw: &str) -> TaskOutput {
+        let name = self.name.clone().or_else(|| Some(self.description.clone()));
+        let mut output = TaskOutput::new(
+            self.description.clone(),
+            self.agent.clone().unwrap_or_default(),
+            raw.to_string(),
+            self.get_output_format(),
+        );
+        output.name = name.clone();
+        output.expected_output = Some(self.expected_output.clone());
+        let json = strip_code_fence(raw);
+        match output.output_format {
+            OutputFormat::JSON => output.json_dict = serde_json::from_str(json).ok(),
+            OutputFormat::Pydantic => output.pydantic = serde_json::from_str(json).ok(),
+            OutputFormat::Raw => {}
+        }
+        self.output = Some(output.clone());
+        self.emit_event(&mut TaskCompletedEvent::new(
+            Some(self.id.to_string()),
+            name,
+            serde_json::Value::String(output.raw.clone()),
+        ));
+        output
+    }
+
+    /// The output of a task stopped for exceeding its `max_execution_time`.
+    pub(crate) fn timed_out_output(&self) -> TaskOutput {
+        let mut output = TaskOutput::new(
+            self.description.clone(),
+            self.agent.clone().unwrap_or_default(),
+            String::new(),
+            self.get_output_format(),
+        );
+        output.name = self.name.clone().or_else(|| Some(self.description.clone()));
+        output.expected_output = Some(self.expected_output.clone());
+        output.timed_out = true;
+        output
+    }
+
+    /// Emit an event on the global event bus, if it has been initialised.
+    fn emit_event<E: BaseEvent + 'static>(&self, event: &mut E) {
+        if let Some(bus) = CREWAI_EVENT_BUS.get() {
+            self.security_config.fingerprint.stamp(event);
+            bus.emit(std::sync::Arc::new(self.id.to_string()), event);
+        }
+    }
+
+    fn execute_core(
         &mut self,
         agent: Option<&str>,
         context: Option<&str>,
         _tools: Option<&[String]>,
     ) -> Result<TaskOutput, String> {
         self.start_time = Some(Utc::now());
+        self.retry_count = 0;
+        let artifacts = ArtifactScope::enter();
+        let usage = UsageScope::enter();
+        let trace = TraceScope::enter();
 
         let agent_role = agent
             .or(self.agent.as_deref())
@@ -316,72 +489,107 @@ impl Task {
             })?
             .to_string();
 
+        self.processed_by_agents.insert(agent_role.clone());
+
+        // Build the task prompt and screen it with the content guardrails
+        let mut task_prompt = self.prompt();
+        let mut context = context.map(str::to_string);
+        let input_reports = match content_guardrails::screen_input(
+            &self.content_guardrails,
+            &mut task_prompt,
+            &mut context,
+        ) {
+            Ok(reports) => reports,
+            Err(e) => {
+                self.end_time = Some(Utc::now());
+                return Err(e);
+            }
+        };
+        let context = context.as_deref();
+
         if let Some(ctx) = context {
             self.prompt_context = Some(ctx.to_string());
         }
 
-        self.processed_by_agents.insert(agent_role.clone());
-
-        // Build the task prompt
-        let task_prompt = self.prompt();
-
         // Collect tool names
         let tool_names: Vec<String> = self.tools.clone();
 
-        // Execute via the agent executor callback if set
-        let (result, messages) = if let Some(ref executor) = self.agent_executor {
-            executor(&task_prompt, context, &tool_names)?
-        } else {
-            // Fallback: use LLM directly when no executor is configured
-            log::warn!("No agent_executor configured for task, using direct LLM call");
-            let llm = crate::llm::LLM::new("openai/gpt-4o-mini".to_string());
-            let mut messages = Vec::new();
-            let mut sys_msg = HashMap::new();
-            sys_msg.insert("role".to_string(), "system".to_string());
-            sys_msg.insert(
-                "content".to_string(),
-                format!(
-                    "You are an AI assistant working as {}. Complete the following task.",
-                    agent_role
+        // Run the agent, retrying with the validation error as context while
+        // a guardrail rejects the answer
+        let mut retry_context: Option<String> = None;
+        let mut task_output = loop {
+            let attempt_context = retry_context.as_deref().or(context);
+            let (result, messages) =
+                self.run_agent(&agent_role, &task_prompt, attempt_context, &tool_names)?;
+            let mut task_output = TaskOutput {
+                description: self.description.clone(),
+                name: self.name.clone().or_else(|| Some(self.description.clone())),
+                expected_output: Some(self.expected_output.clone()),
+                summary: Some(
+                    self.description
+                        .split_whitespace()
+                        .take(10)
+                        .collect::<Vec<&str>>()
+                        .join(" ")
+                        + "...",
                 ),
-            );
-            messages.push(sys_msg);
-            let mut user_msg = HashMap::new();
-            user_msg.insert("role".to_string(), "user".to_string());
-            user_msg.insert("content".to_string(), task_prompt.clone());
-            messages.push(user_msg);
-            match llm.call(&messages, None) {
-                Ok(response) => (response, Vec::new()),
-                Err(e) => {
-                    log::error!("Direct LLM call failed: {}", e);
-                    (format!("[LLM call failed: {}]", e), Vec::new())
-                }
+                raw: result,
+                pydantic: None,
+                json_dict: None,
+                agent: agent_role.clone(),
+                output_format: self.get_output_format(),
+                messages,
+                artifacts: Vec::new(),
+                assertions: Vec::new(),
+                guardrail_reports: input_reports.clone(),
+                token_usage: None,
+                execution_steps: Vec::new(),
+                timed_out: false,
+            };
+            if let Some(normalizer) = &self.normalize {
+                normalizer.process(&mut task_output);
             }
+            let Some(error) = self.guardrail_error(&task_output) else {
+                break task_output;
+            };
+            if self.retry_count >= self.guardrail_max_retries {
+                self.end_time = Some(Utc::now());
+                return Err(format!(
+                    "Task failed guardrail validation after {} retries. Last error: {}",
+                    self.guardrail_max_retries, error
+                ));
+            }
+            self.retry_count += 1;
+            retry_context = Some(
+                get_i18n()
+                    .errors("validation_error")
+                    .replace("{guardrail_result_error}", &error)
+                    .replace("{task_output}", &task_output.raw),
+            );
         };
-
-        let task_output = TaskOutput {
-            description: self.description.clone(),
-            name: self.name.clone().or_else(|| Some(self.description.clone())),
-            expected_output: Some(self.expected_output.clone()),
-            summary: Some(
-                self.description
-                    .split_whitespace()
-                    .take(10)
-                    .collect::<Vec<&str>>()
-                    .join(" ")
-                    + "...",
-            ),
-            raw: result,
-            pydantic: None,
-            json_dict: None,
-            agent: agent_role,
-            output_format: self.get_output_format(),
-            messages,
-        };
+        // Every attempt's artifacts and steps belong to the task
+        task_output.artifacts = artifacts.take();
+        task_output.execution_steps = trace.take();
+        if let Err(e) =
+            content_guardrails::screen_output(&self.content_guardrails, &mut task_output)
+        {
+            self.end_time = Some(Utc::now());
+            return Err(e);
+        }
+        if let Err(e) = assertions::check(&self.assertions, &mut task_output) {
+            self.end_time = Some(Utc::now());
+            return Err(e);
+        }
+        // Including what the output guardrails used
+        task_output.token_usage = Some(usage.usage());
 
         self.output = Some(task_output.clone());
         self.end_time = Some(Utc::now());
 
+        if self.output_file.is_some() {
+            self.save_file(&task_output.raw)?;
+        }
+
         if let Some(ref cb) = self.callback {
             cb(&task_output);
         }
@@ -389,6 +597,77 @@ impl Task {
         Ok(task_output)
     }
 
+    /// Run the task prompt through the agent executor, or straight through an
+    /// LLM when no executor is configured.
+    fn run_agent(
+        &self,
+        agent_role: &str,
+        task_prompt: &str,
+        context: Option<&str>,
+        tool_names: &[String],
+    ) -> Result<(String, Vec<LLMMessage>), String> {
+        if let Some(ref executor) = self.agent_executor {
+            return executor(task_prompt, context, tool_names);
+        }
+        log::warn!("No agent_executor configured for task, using direct LLM call");
+        let llm = crate::llm::LLM::new("openai/gpt-4o-mini".to_string());
+        let mut messages = Vec::new();
+        let mut sys_msg = HashMap::new();
+        sys_msg.insert("role".to_string(), "system".to_string());
+        sys_msg.insert(
+            "content".to_string(),
+            format!(
+                "You are an AI assistant working as {}. Complete the following task.",
+                agent_role
+            ),
+        );
+        messages.push(sys_msg);
+        let mut user_msg = HashMap::new();
+        user_msg.insert("role".to_string(), "user".to_string());
+        user_msg.insert("content".to_string(), task_prompt.to_string());
+        messages.push(user_msg);
+        match llm.call(&messages, None) {
+            Ok(response) => Ok((response, Vec::new())),
+            Err(e) => {
+                log::error!("Direct LLM call failed: {}", e);
+                Ok((format!("[LLM call failed: {}]", e), Vec::new()))
+            }
+        }
+    }
+
+    /// The first error reported by the task's guardrail callbacks, if any
+    /// rejects the output.
+    fn guardrail_error(&self, output: &TaskOutput) -> Option<String> {
+        self.guardrail_fn
+            .iter()
+            .chain(self.guardrails_fns.iter())
+            .map(|guardrail| guardrail(output))
+            .find(|(passed, _)| !passed)
+            .map(|(_, error)| error)
+    }
+
+    /// Output recorded for the task when its condition skips it.
+    pub fn skipped_output(&self) -> TaskOutput {
+        TaskOutput {
+            description: self.description.clone(),
+            name: self.name.clone(),
+            expected_output: Some(self.expected_output.clone()),
+            summary: None,
+            raw: String::new(),
+            pydantic: None,
+            json_dict: None,
+            agent: self.agent.clone().unwrap_or_default(),
+            output_format: OutputFormat::Raw,
+            messages: Vec::new(),
+            artifacts: Vec::new(),
+            assertions: Vec::new(),
+            guardrail_reports: Vec::new(),
+            token_usage: None,
+            execution_steps: Vec::new(),
+            timed_out: false,
+        }
+    }
+
     /// Execute the task asynchronously (spawns a background tokio task).
     ///
     /// Returns a JoinHandle that resolves to the TaskOutput.
@@ -456,7 +735,14 @@ Follow these guidelines:\n\
     }
 
     /// Interpolate inputs into the task description, expected output, and output file path.
-    pub fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) {
+    ///
+    /// Placeholders are always filled from the original templates, so a task
+    /// can be re-interpolated for another kickoff. `{{name}}` escapes a
+    /// literal `{name}`.
+    ///
+    /// # Errors
+    /// Returns an error naming the first placeholder missing from `inputs`.
+    pub fn interpolate_inputs(&mut self, inputs: &HashMap<String, String>) -> Result<(), String> {
         if self.original_description.is_none() {
             self.original_description = Some(self.description.clone());
         }
@@ -468,19 +754,24 @@ Follow these guidelines:\n\
         }
 
         if inputs.is_empty() {
-            return;
+            return Ok(());
         }
 
-        // Simple interpolation: replace {key} with value
-        if let Some(ref orig_desc) = self.original_description {
-            self.description = interpolate_string(orig_desc, inputs);
-        }
-        if let Some(ref orig_expected) = self.original_expected_output {
-            self.expected_output = interpolate_string(orig_expected, inputs);
-        }
-        if let Some(ref orig_file) = self.original_output_file {
-            self.output_file = Some(interpolate_string(orig_file, inputs));
-        }
+        let description = interpolate_only(self.original_description.as_deref(), inputs)
+            .map_err(|e| format!("Task description: {}", e))?;
+        let expected_output = interpolate_only(self.original_expected_output.as_deref(), inputs)
+            .map_err(|e| format!("Task expected_output: {}", e))?;
+        let output_file = match self.original_output_file.as_deref() {
+            Some(file) => Some(
+                interpolate_only(Some(file), inputs)
+                    .map_err(|e| format!("Task output_file: {}", e))?,
+            ),
+            None => self.output_file.take(),
+        };
+        self.description = description;
+        self.expected_output = expected_output;
+        self.output_file = output_file;
+        Ok(())
     }
 
     /// Increment the tools errors counter.
@@ -533,13 +824,3 @@ impl std::fmt::Display for Task {
         )
     }
 }
-
-/// Simple string interpolation: replace `{key}` with corresponding value.
-fn interpolate_string(template: &str, inputs: &HashMap<String, String>) -> String {
-    let mut result = template.to_string();
-    for (key, value) in inputs {
-        let pattern = format!("{{{}}}", key);
-        result = result.replace(&pattern, value);
-    }
-    result
-}
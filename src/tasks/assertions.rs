@@ -0,0 +1,321 @@
+//! Inline output assertions for tasks.
+//!
+//! Lightweight, deterministic checks evaluated after a task runs: required
+//! keywords, a regex, numeric ranges on fields of the JSON output and a
+//! maximum length. Every result is recorded on
+//! [`TaskOutput::assertions`]; an assertion with `fail_task` set fails the
+//! task instead. Cheaper than an LLM guardrail for simple checks.
+//!
+//! ```yaml
+//! assertions:
+//!   - type: contains
+//!     keywords: [revenue, outlook]
+//!   - type: matches
+//!     pattern: "Q[1-4] \\d{4}"
+//!   - type: range
+//!     field: summary.confidence
+//!     min: 0.5
+//!     max: 1.0
+//!     fail_task: true
+//!   - type: max_length
+//!     max: 2000
+//! ```
+//!
+//! [`TaskOutput::assertions`]: super::task_output::TaskOutput::assertions
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::task_output::TaskOutput;
+use crate::utilities::normalize::strip_code_fence;
+
+/// What an assertion checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AssertionCheck {
+    /// The raw output contains every keyword.
+    Contains {
+        /// Required keywords.
+        keywords: Vec<String>,
+        /// Match case exactly (default: case-insensitive).
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    /// The raw output matches a regular expression.
+    Matches {
+        /// Regular expression searched for in the output.
+        pattern: String,
+    },
+    /// A numeric field of the JSON output lies within bounds (inclusive).
+    Range {
+        /// Dotted path of the field, e.g. `summary.score` or `items.0.price`.
+        field: String,
+        /// Lower bound.
+        #[serde(default)]
+        min: Option<f64>,
+        /// Upper bound.
+        #[serde(default)]
+        max: Option<f64>,
+    },
+    /// The raw output has at most this many characters.
+    MaxLength {
+        /// Maximum number of characters.
+        max: usize,
+    },
+}
+
+/// An assertion declared on a task.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskAssertion {
+    /// Name in results and errors; derived from the check when unset.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The check.
+    #[serde(flatten)]
+    pub check: AssertionCheck,
+    /// Fail the task when the assertion does not hold.
+    #[serde(default)]
+    pub fail_task: bool,
+}
+
+impl TaskAssertion {
+    /// A recorded (non-failing) assertion.
+    pub fn new(check: AssertionCheck) -> Self {
+        Self {
+            name: None,
+            check,
+            fail_task: false,
+        }
+    }
+
+    /// Builder: name the assertion.
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Builder: fail the task when the assertion does not hold.
+    pub fn failing_task(mut self) -> Self {
+        self.fail_task = true;
+        self
+    }
+
+    /// The assertion's name.
+    pub fn name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        match &self.check {
+            AssertionCheck::Contains { keywords, .. } => {
+                format!("contains {}", keywords.join(", "))
+            }
+            AssertionCheck::Matches { pattern } => format!("matches /{}/", pattern),
+            AssertionCheck::Range { field, min, max } => {
+                let bound = |b: &Option<f64>| b.map_or("..".to_string(), |b| b.to_string());
+                format!("{} in [{}, {}]", field, bound(min), bound(max))
+            }
+            AssertionCheck::MaxLength { max } => format!("at most {} characters", max),
+        }
+    }
+
+    /// Check `output`.
+    pub fn evaluate(&self, output: &TaskOutput) -> AssertionResult {
+        let failure = match &self.check {
+            AssertionCheck::Contains {
+                keywords,
+                case_sensitive,
+            } => {
+                let haystack = if *case_sensitive {
+                    output.raw.clone()
+                } else {
+                    output.raw.to_lowercase()
+                };
+                let missing: Vec<&str> = keywords
+                    .iter()
+                    .filter(|k| {
+                        let needle = if *case_sensitive {
+                            k.to_string()
+                        } else {
+                            k.to_lowercase()
+                        };
+                        !haystack.contains(&needle)
+                    })
+                    .map(String::as_str)
+                    .collect();
+                (!missing.is_empty()).then(|| format!("missing {}", missing.join(", ")))
+            }
+            AssertionCheck::Matches { pattern } => match regex::Regex::new(pattern) {
+                Ok(re) if re.is_match(&output.raw) => None,
+                Ok(_) => Some("no match".to_string()),
+                Err(e) => Some(format!("invalid pattern: {}", e)),
+            },
+            AssertionCheck::Range { field, min, max } => match field_number(output, field) {
+                Ok(n) if min.is_some_and(|min| n < min) || max.is_some_and(|max| n > max) => {
+                    Some(format!("{} is out of range", n))
+                }
+                Ok(_) => None,
+                Err(e) => Some(e),
+            },
+            AssertionCheck::MaxLength { max } => {
+                let length = output.raw.chars().count();
+                (length > *max).then(|| format!("{} characters", length))
+            }
+        };
+        AssertionResult {
+            name: self.name(),
+            passed: failure.is_none(),
+            message: failure,
+        }
+    }
+}
+
+/// Outcome of one assertion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssertionResult {
+    /// Name of the assertion.
+    pub name: String,
+    /// Whether it held.
+    pub passed: bool,
+    /// Why it did not hold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The number at dotted `path` in the JSON output (`json_dict`, or the raw
+/// text when it is JSON). Numeric strings count as numbers.
+fn field_number(output: &TaskOutput, path: &str) -> Result<f64, String> {
+    let root = match &output.json_dict {
+        Some(dict) => Value::Object(dict.clone().into_iter().collect()),
+        None => serde_json::from_str(strip_code_fence(&output.raw))
+            .map_err(|_| "output is not JSON".to_string())?,
+    };
+    let mut value = &root;
+    for key in path.split('.') {
+        value = match value {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            other => other.get(key),
+        }
+        .ok_or_else(|| format!("field '{}' not found", path))?;
+    }
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| format!("field '{}' is not a number", path))
+}
+
+/// Evaluate `assertions` against `output`, recording the results on it.
+///
+/// Returns an error naming the first failed assertion with `fail_task` set.
+pub fn check(assertions: &[TaskAssertion], output: &mut TaskOutput) -> Result<(), String> {
+    let mut error = None;
+    for assertion in assertions {
+        let result = assertion.evaluate(output);
+        if !result.passed && assertion.fail_task && error.is_none() {
+            error = Some(format!(
+                "Task assertion '{}' failed: {}",
+                result.name,
+                result.message.as_deref().unwrap_or_default()
+            ));
+        }
+        output.assertions.push(result);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::output_format::OutputFormat;
+
+    #[test]
+    fn test_assertions_from_yaml() {
+        let assertions: Vec<TaskAssertion> = serde_yaml::from_str(
+            r#"
+- type: contains
+  keywords: [Revenue, outlook]
+- type: matches
+  pattern: "Q[1-4] \\d{4}"
+- name: confident
+  type: range
+  field: summary.confidence
+  min: 0.5
+  fail_task: true
+- type: range
+  field: items.1
+  max: 10
+- type: max_length
+  max: 20
+"#,
+        )
+        .unwrap();
+        let mut output = TaskOutput::new(
+            "Report".into(),
+            "Analyst".into(),
+            r#"{"summary": {"confidence": "0.8", "text": "revenue up in Q3 2024"}, "items": [1, 12]}"#
+                .into(),
+            OutputFormat::Raw,
+        );
+        check(&assertions, &mut output).unwrap();
+        let results: Vec<(String, bool)> = output
+            .assertions
+            .iter()
+            .map(|r| (r.name.clone(), r.passed))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                ("contains Revenue, outlook".to_string(), false),
+                ("matches /Q[1-4] \\d{4}/".to_string(), true),
+                ("confident".to_string(), true),
+                ("items.1 in [.., 10]".to_string(), false),
+                ("at most 20 characters".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            output.assertions[0].message.as_deref(),
+            Some("missing outlook")
+        );
+
+        let mut low = TaskOutput::new(
+            "Report".into(),
+            "Analyst".into(),
+            "```json\n{\"summary\": {\"confidence\": 0.2}}\n```".into(),
+            OutputFormat::Raw,
+        );
+        let err = check(&assertions[2..3], &mut low).unwrap_err();
+        assert_eq!(
+            err,
+            "Task assertion 'confident' failed: 0.2 is out of range"
+        );
+        assert!(!low.assertions[0].passed);
+    }
+
+    #[test]
+    fn test_task_records_assertions_and_fails_on_required_ones() {
+        let mut task = crate::task::Task::new("Summarize".into(), "A summary".into());
+        task.agent = Some("writer".to_string());
+        task.set_agent_executor(|_: &str, _: Option<&str>, _: &[String]| {
+            Ok(("A short summary".to_string(), Vec::new()))
+        });
+        task.assertions = vec![
+            TaskAssertion::new(AssertionCheck::MaxLength { max: 100 }),
+            TaskAssertion::new(AssertionCheck::Contains {
+                keywords: vec!["Summary".to_string()],
+                case_sensitive: true,
+            }),
+        ];
+        let output = task.execute_sync(None, None, None).unwrap();
+        assert_eq!(output.assertions.len(), 2);
+        assert!(!output.assertions_passed());
+
+        task.assertions[1].fail_task = true;
+        let err = task.execute_sync(None, None, None).unwrap_err();
+        assert!(
+            err.contains("Task assertion 'contains Summary' failed"),
+            "{}",
+            err
+        );
+    }
+}
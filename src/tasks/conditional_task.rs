@@ -102,6 +102,12 @@ impl ConditionalTask {
             agent: self.agent_role.clone().unwrap_or_default(),
             output_format: OutputFormat::Raw,
             messages: Vec::new(),
+            artifacts: Vec::new(),
+            assertions: Vec::new(),
+            guardrail_reports: Vec::new(),
+            token_usage: None,
+            execution_steps: Vec::new(),
+            timed_out: false,
         }
     }
 }
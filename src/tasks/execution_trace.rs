@@ -0,0 +1,177 @@
+//! Step-by-step trace of a task's execution.
+//!
+//! The agent executor [`record`]s an [`ExecutionStep`] for every LLM call
+//! of its loop: the thought, the tool calls made with their arguments and
+//! observations, the final answer, and the tokens the call used. A
+//! [`TraceScope`] collects the steps recorded on its thread while a task
+//! executes, and they end up in the task's
+//! [`TaskOutput::execution_steps`](super::task_output::TaskOutput::execution_steps)
+//! for audit and replay.
+
+use std::cell::RefCell;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::usage_metrics::UsageMetrics;
+
+/// A tool call made in a step.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallTrace {
+    /// Name of the tool.
+    pub tool: String,
+    /// Arguments the tool was called with, as the LLM wrote them.
+    pub arguments: String,
+    /// What the tool returned to the agent.
+    pub observation: String,
+}
+
+/// One LLM call of an agent's loop and what came of it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionStep {
+    /// Iteration of the agent's loop, from 0.
+    pub iteration: usize,
+    /// The agent's reasoning, or the raw response when it could not be
+    /// parsed.
+    pub thought: String,
+    /// Tool calls made, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCallTrace>,
+    /// The final answer, for the step that ended the loop.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_answer: Option<String>,
+    /// Tokens the step's LLM call used.
+    pub token_usage: UsageMetrics,
+}
+
+thread_local! {
+    /// Steps recorded by the task running on this thread.
+    static COLLECTED: RefCell<Option<Vec<ExecutionStep>>> = const { RefCell::new(None) };
+}
+
+/// Add `step` to the trace of the task running on this thread, if any.
+pub fn record(step: ExecutionStep) {
+    COLLECTED.with(|collected| {
+        if let Some(steps) = collected.borrow_mut().as_mut() {
+            steps.push(step);
+        }
+    });
+}
+
+/// Whether a task on this thread is collecting a trace.
+pub(crate) fn is_collecting() -> bool {
+    COLLECTED.with(|collected| collected.borrow().is_some())
+}
+
+/// Add `steps`, recorded by another thread working for the task running on
+/// this one.
+pub(crate) fn extend(steps: Vec<ExecutionStep>) {
+    COLLECTED.with(|collected| {
+        if let Some(collected) = collected.borrow_mut().as_mut() {
+            collected.extend(steps);
+        }
+    });
+}
+
+/// RAII guard collecting the steps recorded on this thread, used by a task
+/// around its execution.
+///
+/// When dropped, restores the previous collection (a task run from inside
+/// another task's tool keeps its own trace).
+pub struct TraceScope {
+    previous: Option<Vec<ExecutionStep>>,
+}
+
+impl TraceScope {
+    /// Start collecting steps on this thread.
+    pub fn enter() -> Self {
+        let previous = COLLECTED.with(|collected| collected.borrow_mut().replace(Vec::new()));
+        Self { previous }
+    }
+
+    /// Steps recorded so far.
+    pub fn take(&self) -> Vec<ExecutionStep> {
+        COLLECTED.with(|collected| {
+            collected
+                .borrow_mut()
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for TraceScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        COLLECTED.with(|collected| *collected.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::crew_agent_executor::CrewAgentExecutor;
+    use crate::agents::tools_handler::ToolsHandler;
+    use crate::types::usage_metrics;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_executor_steps_are_traced() {
+        let calls = AtomicUsize::new(0);
+        let mut executor = CrewAgentExecutor::new(
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            Box::new(()),
+            HashMap::from([("prompt".to_string(), "{input}".to_string())]),
+            10,
+            Vec::new(),
+            "search".to_string(),
+            Vec::new(),
+            "- search: Search the web".to_string(),
+            ToolsHandler::new(None),
+        );
+        executor.set_llm_call(move |_, _| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            usage_metrics::record(&UsageMetrics {
+                total_tokens: 10 * (call as i64 + 1),
+                successful_requests: 1,
+                ..Default::default()
+            });
+            Ok(match call {
+                0 => "Thought: look it up\nAction: search\nAction Input: {\"q\": \"rust\"}",
+                1 => "I think I know",
+                _ => "Thought: done\nFinal Answer: Rust is a language",
+            }
+            .to_string())
+        });
+        executor.set_tool_executor(|_, _| Ok("Rust: a systems language".to_string()));
+
+        let trace = TraceScope::enter();
+        let inputs = HashMap::from([("input".to_string(), "What is Rust?".to_string())]);
+        executor.invoke(inputs).unwrap();
+        let steps = trace.take();
+        drop(trace);
+        record(ExecutionStep::default()); // No scope, dropped
+
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].thought, "look it up");
+        assert_eq!(
+            steps[0].tool_calls,
+            [ToolCallTrace {
+                tool: "search".to_string(),
+                arguments: "{\"q\": \"rust\"}".to_string(),
+                observation: "Rust: a systems language".to_string(),
+            }]
+        );
+        assert_eq!(steps[1].thought, "I think I know");
+        assert_eq!(steps[2].final_answer.as_deref(), Some("Rust is a language"));
+        let tokens: Vec<_> = steps.iter().map(|s| s.token_usage.total_tokens).collect();
+        assert_eq!(tokens, [10, 20, 30]);
+        assert_eq!(
+            steps.iter().map(|s| s.iteration).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+    }
+}
@@ -1,8 +1,12 @@
-//! Task sub-modules for output format, task output, conditional tasks, and guardrails.
+//! Task sub-modules for output format, task output, conditional tasks,
+//! assertions, and guardrails (LLM-based and built-in content checks).
 //!
 //! Corresponds to `crewai/tasks/`.
 
+pub mod assertions;
 pub mod conditional_task;
+pub mod content_guardrails;
+pub mod execution_trace;
 pub mod hallucination_guardrail;
 pub mod llm_guardrail;
 pub mod output_format;
@@ -6,7 +6,12 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
 
+use super::assertions::AssertionResult;
+use super::content_guardrails::GuardrailReport;
+use super::execution_trace::ExecutionStep;
 use super::output_format::OutputFormat;
+use crate::types::usage_metrics::UsageMetrics;
+use crate::utilities::artifacts::Artifact;
 
 /// Represents a message from the LLM during task execution.
 ///
@@ -33,6 +38,11 @@ pub struct LLMMessage {
 /// * `agent` - Agent that executed the task
 /// * `output_format` - Output format of the task (JSON, Pydantic, or Raw)
 /// * `messages` - Messages exchanged during the task
+/// * `artifacts` - Binary artifacts produced by the task
+/// * `assertions` - Results of the task's inline assertions
+/// * `guardrail_reports` - Reports of the task's content guardrails
+/// * `token_usage` - Tokens the LLM calls of the task's execution used
+/// * `execution_steps` - The agent's steps while executing the task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskOutput {
     /// Description of the task.
@@ -56,6 +66,26 @@ pub struct TaskOutput {
     /// Messages of the task.
     #[serde(default)]
     pub messages: Vec<LLMMessage>,
+    /// Binary artifacts (charts, spreadsheets, PDFs) produced by the task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<Artifact>,
+    /// Results of the task's inline assertions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<AssertionResult>,
+    /// Reports of the task's content guardrails, input first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guardrail_reports: Vec<GuardrailReport>,
+    /// Tokens used by the LLM calls made while executing the task; `None`
+    /// for outputs that were not executed (restored or skipped).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<UsageMetrics>,
+    /// The agent's thoughts, tool calls and answer, one step per LLM call,
+    /// with the tokens each used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub execution_steps: Vec<ExecutionStep>,
+    /// Whether the task was stopped for exceeding its `max_execution_time`.
+    #[serde(default)]
+    pub timed_out: bool,
 }
 
 impl TaskOutput {
@@ -78,9 +108,20 @@ impl TaskOutput {
             agent,
             output_format,
             messages: Vec::new(),
+            artifacts: Vec::new(),
+            assertions: Vec::new(),
+            guardrail_reports: Vec::new(),
+            token_usage: None,
+            execution_steps: Vec::new(),
+            timed_out: false,
         }
     }
 
+    /// Whether every assertion of the task held.
+    pub fn assertions_passed(&self) -> bool {
+        self.assertions.iter().all(|a| a.passed)
+    }
+
     /// Generate a summary from the description (first 10 words + "...").
     fn generate_summary(description: &str) -> String {
         let excerpt: String = description
@@ -0,0 +1,667 @@
+//! Flight recorder: a bounded SQLite history of crew executions.
+//!
+//! Every crew kickoff records its key facts (crew and its definition
+//! fingerprint, profile, start time, duration, outcome and error, models,
+//! token usage and cost, and per-task agent, model, duration and outcome)
+//! into a small SQLite database that
+//! keeps only the last [`DEFAULT_MAX_EXECUTIONS`] executions. Operators can
+//! then answer "what ran, what failed, what did it cost" with
+//! `crewai history` instead of a telemetry stack.
+//!
+//! # Configuration
+//!
+//! - `CREWAI_FLIGHT_RECORDER` — database path, or `off` to disable
+//!   recording; defaults to `flight_recorder.db` in the storage directory
+//!   ([`db_storage_path`](crate::utilities::paths::db_storage_path))
+//! - `CREWAI_FLIGHT_RECORDER_MAX` — number of executions kept (default 1000)
+//! - `CREWAI_FLIGHT_RECORDER_PRICING` — `<prompt>,<completion>` cost per
+//!   million tokens; costs are not recorded without it
+//!
+//! Recording never fails a kickoff: database errors are logged under the
+//! `crewai::flight_recorder` target.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::server::quotas::Pricing;
+use crate::types::usage_metrics::UsageMetrics;
+
+/// Environment variable with the database path, or `off`.
+pub const FLIGHT_RECORDER_ENV: &str = "CREWAI_FLIGHT_RECORDER";
+/// Environment variable with the number of executions kept.
+pub const FLIGHT_RECORDER_MAX_ENV: &str = "CREWAI_FLIGHT_RECORDER_MAX";
+/// Environment variable with token prices (`<prompt>,<completion>` per
+/// million tokens).
+pub const FLIGHT_RECORDER_PRICING_ENV: &str = "CREWAI_FLIGHT_RECORDER_PRICING";
+
+/// Executions kept when no limit is configured.
+pub const DEFAULT_MAX_EXECUTIONS: usize = 1000;
+
+/// Outcome of an execution or task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordStatus {
+    /// Finished successfully.
+    Completed,
+    /// Failed with an error.
+    Failed,
+    /// Never ran (a task after a failure, or a skipped conditional task).
+    Skipped,
+}
+
+impl RecordStatus {
+    /// Name stored in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+        }
+    }
+}
+
+impl std::fmt::Display for RecordStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RecordStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "completed" | "ok" => Ok(Self::Completed),
+            "failed" | "error" => Ok(Self::Failed),
+            "skipped" => Ok(Self::Skipped),
+            other => Err(anyhow::anyhow!("Unknown status: {}", other)),
+        }
+    }
+}
+
+/// Key facts of one task of an execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskRecord {
+    /// Task name, or its description when unnamed.
+    pub name: String,
+    /// Role of the agent that ran it.
+    pub agent: Option<String>,
+    /// Model of that agent.
+    pub model: Option<String>,
+    /// How long it ran.
+    pub duration_ms: Option<u64>,
+    /// Outcome.
+    pub status: RecordStatus,
+}
+
+/// Key facts of one crew execution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionRecord {
+    /// Crew ID.
+    pub id: String,
+    /// Crew name.
+    pub crew: Option<String>,
+    /// Definition fingerprint of the crew (see
+    /// [`Fingerprint::of_definition`](crate::security::Fingerprint::of_definition)),
+    /// shared by all runs of the same crew definition.
+    #[serde(default)]
+    pub fingerprint: Option<String>,
+    /// Run profile the crew was built with.
+    pub profile: Option<String>,
+    /// When the kickoff started.
+    pub started_at: DateTime<Utc>,
+    /// How long the kickoff took.
+    pub duration_ms: u64,
+    /// Outcome.
+    pub status: RecordStatus,
+    /// Error of a failed execution.
+    pub error: Option<String>,
+    /// Models used by the crew's agents, sorted.
+    pub models: Vec<String>,
+    /// Token usage.
+    pub usage: UsageMetrics,
+    /// Cost of the token usage, when pricing is configured.
+    pub cost: Option<f64>,
+    /// Tasks, in execution order.
+    pub tasks: Vec<TaskRecord>,
+}
+
+/// Which executions to list or aggregate. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Crew name.
+    pub crew: Option<String>,
+    /// Crew definition fingerprint.
+    pub fingerprint: Option<String>,
+    /// Outcome.
+    pub status: Option<RecordStatus>,
+    /// Model used by any agent.
+    pub model: Option<String>,
+    /// Executions started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Most recent executions to return.
+    pub limit: Option<usize>,
+}
+
+/// Aggregates over matching executions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistoryStats {
+    /// Executions matched.
+    pub executions: usize,
+    /// Completed executions.
+    pub completed: usize,
+    /// Failed executions.
+    pub failed: usize,
+    /// Mean duration.
+    pub avg_duration_ms: u64,
+    /// 95th percentile duration.
+    pub p95_duration_ms: u64,
+    /// Total tokens used.
+    pub total_tokens: i64,
+    /// Total recorded cost.
+    pub total_cost: f64,
+    /// Executions per model.
+    pub models: BTreeMap<String, usize>,
+    /// Most frequent errors with their counts, most frequent first.
+    pub top_errors: Vec<(String, usize)>,
+}
+
+/// A bounded SQLite history of crew executions.
+#[derive(Debug, Clone)]
+pub struct FlightRecorder {
+    /// Path to the database file.
+    pub db_path: PathBuf,
+    /// Executions kept; older ones are dropped when a new one is recorded.
+    pub max_executions: usize,
+    /// Token prices for recorded costs.
+    pub pricing: Option<Pricing>,
+}
+
+impl FlightRecorder {
+    /// Open (creating if needed) the database at `db_path`, keeping the last
+    /// `max_executions` executions.
+    pub fn open(db_path: impl Into<PathBuf>, max_executions: usize) -> Result<Self, anyhow::Error> {
+        let recorder = Self {
+            db_path: db_path.into(),
+            max_executions: max_executions.max(1),
+            pricing: None,
+        };
+        if let Some(parent) = recorder.db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        recorder.connect()?.execute_batch(
+            "CREATE TABLE IF NOT EXISTS executions (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                id TEXT NOT NULL,
+                crew TEXT,
+                profile TEXT,
+                started_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                error TEXT,
+                models TEXT NOT NULL,
+                prompt_tokens INTEGER NOT NULL,
+                completion_tokens INTEGER NOT NULL,
+                total_tokens INTEGER NOT NULL,
+                requests INTEGER NOT NULL,
+                cost REAL,
+                fingerprint TEXT
+            );
+            CREATE TABLE IF NOT EXISTS tasks (
+                execution_seq INTEGER NOT NULL,
+                position INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                agent TEXT,
+                model TEXT,
+                duration_ms INTEGER,
+                status TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS tasks_execution ON tasks (execution_seq);",
+        )?;
+        recorder.migrate()?;
+        Ok(recorder)
+    }
+
+    /// Add columns missing from databases created by earlier versions.
+    fn migrate(&self) -> Result<(), anyhow::Error> {
+        let conn = self.connect()?;
+        let has_fingerprint = conn
+            .prepare("SELECT 1 FROM pragma_table_info('executions') WHERE name = 'fingerprint'")?
+            .exists([])?;
+        if !has_fingerprint {
+            conn.execute("ALTER TABLE executions ADD COLUMN fingerprint TEXT", [])?;
+        }
+        Ok(())
+    }
+
+    /// Set the token prices used for costs.
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = Some(pricing);
+        self
+    }
+
+    /// Default database location in the storage directory.
+    pub fn default_path() -> PathBuf {
+        crate::utilities::paths::storage_dir().join("flight_recorder.db")
+    }
+
+    /// Recorder configured from the environment; `None` when disabled.
+    pub fn from_env() -> Result<Option<Self>, anyhow::Error> {
+        let path = match std::env::var(FLIGHT_RECORDER_ENV) {
+            Ok(v) if matches!(v.as_str(), "off" | "0" | "false" | "") => return Ok(None),
+            Ok(v) => PathBuf::from(v),
+            Err(_) => Self::default_path(),
+        };
+        let max = match std::env::var(FLIGHT_RECORDER_MAX_ENV) {
+            Ok(v) => v
+                .parse()
+                .map_err(|_| anyhow::anyhow!("{}: not a number: {}", FLIGHT_RECORDER_MAX_ENV, v))?,
+            Err(_) => DEFAULT_MAX_EXECUTIONS,
+        };
+        let mut recorder = Self::open(path, max)?;
+        if let Ok(prices) = std::env::var(FLIGHT_RECORDER_PRICING_ENV) {
+            let parse = |s: Option<&str>| -> Result<f64, anyhow::Error> {
+                s.and_then(|s| s.trim().parse().ok()).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{}: expected '<prompt>,<completion>': {}",
+                        FLIGHT_RECORDER_PRICING_ENV,
+                        prices
+                    )
+                })
+            };
+            let mut parts = prices.split(',');
+            recorder.pricing = Some(Pricing {
+                prompt_per_million: parse(parts.next())?,
+                completion_per_million: parse(parts.next())?,
+            });
+        }
+        Ok(Some(recorder))
+    }
+
+    fn connect(&self) -> Result<Connection, anyhow::Error> {
+        let conn = Connection::open(&self.db_path)?;
+        // Concurrent kickoffs write to the same file.
+        conn.busy_timeout(Duration::from_secs(5))?;
+        Ok(conn)
+    }
+
+    /// Record an execution, dropping the oldest beyond `max_executions`.
+    /// The record's cost is filled in from the pricing when unset.
+    pub fn record(&self, record: &ExecutionRecord) -> Result<(), anyhow::Error> {
+        let cost = record
+            .cost
+            .or_else(|| self.pricing.as_ref().map(|p| p.cost(&record.usage)));
+        let mut conn = self.connect()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO executions (id, crew, profile, started_at, duration_ms, status, error,
+                models, prompt_tokens, completion_tokens, total_tokens, requests, cost,
+                fingerprint)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                record.id,
+                record.crew,
+                record.profile,
+                record.started_at.to_rfc3339(),
+                record.duration_ms as i64,
+                record.status.as_str(),
+                record.error,
+                record.models.join(","),
+                record.usage.prompt_tokens,
+                record.usage.completion_tokens,
+                record.usage.total_tokens,
+                record.usage.successful_requests,
+                cost,
+                record.fingerprint,
+            ],
+        )?;
+        let seq = tx.last_insert_rowid();
+        for (position, task) in record.tasks.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO tasks (execution_seq, position, name, agent, model, duration_ms, status)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    seq,
+                    position as i64,
+                    task.name,
+                    task.agent,
+                    task.model,
+                    task.duration_ms.map(|d| d as i64),
+                    task.status.as_str(),
+                ],
+            )?;
+        }
+        let cutoff: Option<i64> = tx
+            .query_row(
+                "SELECT seq FROM executions ORDER BY seq DESC LIMIT 1 OFFSET ?1",
+                params![self.max_executions as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(cutoff) = cutoff {
+            tx.execute("DELETE FROM executions WHERE seq <= ?1", params![cutoff])?;
+            tx.execute(
+                "DELETE FROM tasks WHERE execution_seq <= ?1",
+                params![cutoff],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Executions matching `filter`, most recent first.
+    pub fn query(&self, filter: &HistoryFilter) -> Result<Vec<ExecutionRecord>, anyhow::Error> {
+        let conn = self.connect()?;
+        let mut sql = String::from(
+            "SELECT seq, id, crew, profile, started_at, duration_ms, status, error, models,
+                prompt_tokens, completion_tokens, total_tokens, requests, cost, fingerprint
+             FROM executions WHERE 1 = 1",
+        );
+        let mut values: Vec<String> = Vec::new();
+        if let Some(crew) = &filter.crew {
+            values.push(crew.clone());
+            sql.push_str(&format!(" AND crew = ?{}", values.len()));
+        }
+        if let Some(fingerprint) = &filter.fingerprint {
+            values.push(fingerprint.clone());
+            sql.push_str(&format!(" AND fingerprint = ?{}", values.len()));
+        }
+        if let Some(status) = filter.status {
+            values.push(status.as_str().to_string());
+            sql.push_str(&format!(" AND status = ?{}", values.len()));
+        }
+        if let Some(model) = &filter.model {
+            values.push(model.clone());
+            sql.push_str(&format!(
+                " AND ',' || models || ',' LIKE '%,' || ?{} || ',%'",
+                values.len()
+            ));
+        }
+        if let Some(since) = filter.since {
+            values.push(since.to_rfc3339());
+            sql.push_str(&format!(" AND started_at >= ?{}", values.len()));
+        }
+        sql.push_str(" ORDER BY seq DESC");
+        if let Some(limit) = filter.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut statement = conn.prepare(&sql)?;
+        let rows = statement.query_map(rusqlite::params_from_iter(&values), |row| {
+            let started_at: String = row.get(4)?;
+            let status: String = row.get(6)?;
+            let models: String = row.get(8)?;
+            Ok((
+                row.get::<_, i64>(0)?,
+                ExecutionRecord {
+                    id: row.get(1)?,
+                    crew: row.get(2)?,
+                    fingerprint: row.get(14)?,
+                    profile: row.get(3)?,
+                    started_at: DateTime::parse_from_rfc3339(&started_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_default(),
+                    duration_ms: row.get::<_, i64>(5)?.max(0) as u64,
+                    status: status.parse().unwrap_or(RecordStatus::Failed),
+                    error: row.get(7)?,
+                    models: models
+                        .split(',')
+                        .filter(|m| !m.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    usage: UsageMetrics {
+                        prompt_tokens: row.get(9)?,
+                        completion_tokens: row.get(10)?,
+                        total_tokens: row.get(11)?,
+                        successful_requests: row.get(12)?,
+                        ..Default::default()
+                    },
+                    cost: row.get(13)?,
+                    tasks: Vec::new(),
+                },
+            ))
+        })?;
+
+        let mut task_statement = conn.prepare(
+            "SELECT name, agent, model, duration_ms, status FROM tasks
+             WHERE execution_seq = ?1 ORDER BY position",
+        )?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (seq, mut record) = row?;
+            record.tasks = task_statement
+                .query_map(params![seq], |row| {
+                    let status: String = row.get(4)?;
+                    Ok(TaskRecord {
+                        name: row.get(0)?,
+                        agent: row.get(1)?,
+                        model: row.get(2)?,
+                        duration_ms: row.get::<_, Option<i64>>(3)?.map(|d| d.max(0) as u64),
+                        status: status.parse().unwrap_or(RecordStatus::Failed),
+                    })
+                })?
+                .collect::<Result<_, _>>()?;
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Aggregates over the executions matching `filter`.
+    pub fn stats(&self, filter: &HistoryFilter) -> Result<HistoryStats, anyhow::Error> {
+        Ok(HistoryStats::from_records(&self.query(filter)?))
+    }
+}
+
+impl HistoryStats {
+    /// Aggregate `records`.
+    pub fn from_records(records: &[ExecutionRecord]) -> Self {
+        let mut stats = Self {
+            executions: records.len(),
+            ..Self::default()
+        };
+        if records.is_empty() {
+            return stats;
+        }
+        let mut durations: Vec<u64> = records.iter().map(|r| r.duration_ms).collect();
+        durations.sort_unstable();
+        stats.avg_duration_ms = durations.iter().sum::<u64>() / durations.len() as u64;
+        stats.p95_duration_ms = durations[(durations.len() * 95).div_ceil(100) - 1];
+
+        let mut errors: BTreeMap<&str, usize> = BTreeMap::new();
+        for record in records {
+            match record.status {
+                RecordStatus::Completed => stats.completed += 1,
+                RecordStatus::Failed => stats.failed += 1,
+                RecordStatus::Skipped => {}
+            }
+            stats.total_tokens += record.usage.total_tokens;
+            stats.total_cost += record.cost.unwrap_or_default();
+            for model in &record.models {
+                *stats.models.entry(model.clone()).or_default() += 1;
+            }
+            if let Some(error) = &record.error {
+                *errors.entry(error.as_str()).or_default() += 1;
+            }
+        }
+        let mut errors: Vec<(String, usize)> = errors
+            .into_iter()
+            .map(|(error, count)| (error.to_string(), count))
+            .collect();
+        errors.sort_by(|a, b| b.1.cmp(&a.1));
+        errors.truncate(5);
+        stats.top_errors = errors;
+        stats
+    }
+}
+
+static RECORDER: RwLock<Option<Arc<FlightRecorder>>> = RwLock::new(None);
+static FROM_ENV: OnceLock<()> = OnceLock::new();
+
+/// The process-wide recorder crews record into, configured from the
+/// environment on first use.
+///
+/// Unit tests of this crate do not record unless a recorder is installed, so
+/// they leave the user's storage directory alone.
+pub fn global() -> Option<Arc<FlightRecorder>> {
+    FROM_ENV.get_or_init(|| {
+        if cfg!(test) {
+            return;
+        }
+        match FlightRecorder::from_env() {
+            Ok(recorder) => {
+                *RECORDER.write().unwrap_or_else(|e| e.into_inner()) = recorder.map(Arc::new)
+            }
+            Err(e) => log::error!(target: "crewai::flight_recorder", "{}", e),
+        }
+    });
+    RECORDER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Replace the process-wide recorder; `None` stops recording.
+pub fn install(recorder: Option<FlightRecorder>) {
+    FROM_ENV.get_or_init(|| ());
+    *RECORDER.write().unwrap_or_else(|e| e.into_inner()) = recorder.map(Arc::new);
+}
+
+/// Record `record` with the process-wide recorder, if any, logging failures.
+pub fn record(record: &ExecutionRecord) {
+    if let Some(recorder) = global() {
+        if let Err(e) = recorder.record(record) {
+            log::warn!(
+                target: "crewai::flight_recorder",
+                "Failed to record execution in {}: {}",
+                recorder.db_path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution(crew: &str, status: RecordStatus, duration_ms: u64) -> ExecutionRecord {
+        ExecutionRecord {
+            id: format!("{}-{}", crew, duration_ms),
+            crew: Some(crew.to_string()),
+            fingerprint: Some(format!("{}-definition", crew)),
+            profile: None,
+            started_at: Utc::now(),
+            duration_ms,
+            status,
+            error: (status == RecordStatus::Failed).then(|| "LLM timeout".to_string()),
+            models: vec!["gpt-4o".to_string()],
+            usage: UsageMetrics {
+                prompt_tokens: 800,
+                completion_tokens: 200,
+                total_tokens: 1000,
+                ..Default::default()
+            },
+            cost: None,
+            tasks: vec![TaskRecord {
+                name: "Research".to_string(),
+                agent: Some("Researcher".to_string()),
+                model: Some("gpt-4o".to_string()),
+                duration_ms: Some(duration_ms),
+                status,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_records_are_bounded_and_queryable() {
+        let dir = tempfile::tempdir().unwrap();
+        let recorder = FlightRecorder::open(dir.path().join("history.db"), 3)
+            .unwrap()
+            .with_pricing(Pricing {
+                prompt_per_million: 1.0,
+                completion_per_million: 5.0,
+            });
+        recorder
+            .record(&execution("research", RecordStatus::Completed, 100))
+            .unwrap();
+        recorder
+            .record(&execution("research", RecordStatus::Failed, 200))
+            .unwrap();
+        recorder
+            .record(&execution("writing", RecordStatus::Completed, 300))
+            .unwrap();
+        recorder
+            .record(&execution("research", RecordStatus::Completed, 400))
+            .unwrap();
+
+        let all = recorder.query(&HistoryFilter::default()).unwrap();
+        let durations: Vec<u64> = all.iter().map(|r| r.duration_ms).collect();
+        assert_eq!(durations, [400, 300, 200]);
+        assert_eq!(
+            all[2].tasks,
+            execution("research", RecordStatus::Failed, 200).tasks
+        );
+        assert!((all[0].cost.unwrap() - 0.0018).abs() < 1e-12);
+
+        let writing = HistoryFilter {
+            fingerprint: Some("writing-definition".to_string()),
+            ..Default::default()
+        };
+        let runs = recorder.query(&writing).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].fingerprint.as_deref(), Some("writing-definition"));
+
+        let research = HistoryFilter {
+            crew: Some("research".to_string()),
+            ..Default::default()
+        };
+        let stats = recorder.stats(&research).unwrap();
+        assert_eq!((stats.executions, stats.completed, stats.failed), (2, 1, 1));
+        assert_eq!(stats.avg_duration_ms, 300);
+        assert_eq!(stats.p95_duration_ms, 400);
+        assert_eq!(stats.top_errors, [("LLM timeout".to_string(), 1)]);
+        assert_eq!(stats.models["gpt-4o"], 2);
+
+        let failed = HistoryFilter {
+            status: Some(RecordStatus::Failed),
+            model: Some("gpt-4o".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(recorder.query(&failed).unwrap().len(), 1);
+        let other_model = HistoryFilter {
+            model: Some("gpt-4".to_string()),
+            ..Default::default()
+        };
+        assert!(recorder.query(&other_model).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_databases_without_fingerprints_are_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.db");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE executions (
+                    seq INTEGER PRIMARY KEY AUTOINCREMENT, id TEXT NOT NULL, crew TEXT,
+                    profile TEXT, started_at TEXT NOT NULL, duration_ms INTEGER NOT NULL,
+                    status TEXT NOT NULL, error TEXT, models TEXT NOT NULL,
+                    prompt_tokens INTEGER NOT NULL, completion_tokens INTEGER NOT NULL,
+                    total_tokens INTEGER NOT NULL, requests INTEGER NOT NULL, cost REAL
+                );",
+            )
+            .unwrap();
+        let recorder = FlightRecorder::open(&path, 10).unwrap();
+        recorder
+            .record(&execution("research", RecordStatus::Completed, 100))
+            .unwrap();
+        let records = recorder.query(&HistoryFilter::default()).unwrap();
+        assert_eq!(
+            records[0].fingerprint.as_deref(),
+            Some("research-definition")
+        );
+    }
+}
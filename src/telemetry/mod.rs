@@ -6,11 +6,20 @@
 //! No prompts, task descriptions, agent backstories/goals, responses, or
 //! sensitive data is collected. Users can opt-in to share more complete data
 //! using the `share_crew` attribute.
+//!
+//! Each crew kickoff and flow run reports [`run_stats::RunStatistics`] to
+//! the registered [`run_stats::TelemetryClient`]s.
+
+pub mod flight_recorder;
+pub mod otel;
+pub mod run_stats;
 
 use std::collections::HashMap;
 use std::env;
 use std::sync::{Arc, Mutex, OnceLock};
 
+use run_stats::{HttpTelemetryClient, RunStatistics, TelemetryClient};
+
 // opentelemetry trace types available for future use when full OTEL SDK
 // initialization is wired up.
 
@@ -32,42 +41,80 @@ pub fn telemetry() -> Arc<Mutex<Telemetry>> {
 // ---------------------------------------------------------------------------
 
 /// Handle anonymous telemetry for the CrewAI package.
-#[derive(Debug)]
 pub struct Telemetry {
     /// Whether telemetry is initialized and ready.
     pub ready: bool,
     /// Whether the tracer provider has been set.
     pub trace_set: bool,
+    /// Sinks for run statistics.
+    clients: Vec<Arc<dyn TelemetryClient>>,
+}
+
+impl std::fmt::Debug for Telemetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Telemetry")
+            .field("ready", &self.ready)
+            .field("trace_set", &self.trace_set)
+            .field("clients", &self.clients.len())
+            .finish()
+    }
 }
 
 impl Telemetry {
     /// Create a new `Telemetry` instance.
+    ///
+    /// Posts run statistics to `CREWAI_TELEMETRY_ENDPOINT` when it is set.
     fn new() -> Self {
         let mut t = Self {
             ready: false,
             trace_set: false,
+            clients: Vec::new(),
         };
 
         if t.is_telemetry_disabled() {
             return t;
         }
 
+        if let Some(client) = HttpTelemetryClient::from_env() {
+            t.clients.push(Arc::new(client));
+        }
         t.set_tracer();
         t
     }
 
     /// Check whether telemetry is disabled via environment variables.
     ///
-    /// Checks `CREWAI_TELEMETRY_OPT_OUT` and `OTEL_SDK_DISABLED`.
+    /// Checks `CREWAI_DISABLE_TELEMETRY`, `CREWAI_TELEMETRY_OPT_OUT` and
+    /// `OTEL_SDK_DISABLED`.
     pub fn is_telemetry_disabled(&self) -> bool {
-        let opt_out = env::var("CREWAI_TELEMETRY_OPT_OUT")
-            .unwrap_or_default()
-            .to_lowercase();
-        let otel_disabled = env::var("OTEL_SDK_DISABLED")
-            .unwrap_or_default()
-            .to_lowercase();
-
-        opt_out == "true" || opt_out == "1" || otel_disabled == "true" || otel_disabled == "1"
+        [
+            "CREWAI_DISABLE_TELEMETRY",
+            "CREWAI_TELEMETRY_OPT_OUT",
+            "OTEL_SDK_DISABLED",
+        ]
+        .iter()
+        .any(|var| {
+            matches!(
+                env::var(var).unwrap_or_default().to_lowercase().as_str(),
+                "true" | "1"
+            )
+        })
+    }
+
+    /// Register a sink for run statistics.
+    pub fn add_client(&mut self, client: Arc<dyn TelemetryClient>) {
+        self.clients.push(client);
+    }
+
+    /// Send the statistics of a finished run to every client, unless
+    /// telemetry is disabled.
+    pub fn record_run(&self, stats: &RunStatistics) {
+        if self.is_telemetry_disabled() {
+            return;
+        }
+        for client in &self.clients {
+            client.send(stats);
+        }
     }
 
     /// Set up the OpenTelemetry tracer provider.
@@ -119,6 +166,22 @@ impl Telemetry {
         attrs.insert("agent_id".to_string(), agent_id.to_string());
         self.create_span("tool_usage", attrs)
     }
+
+    /// Record a regional endpoint latency probe.
+    pub fn region_probe(
+        &self,
+        provider: &str,
+        result: &crate::llms::providers::regions::ProbeResult,
+    ) -> SpanHandle {
+        let mut attrs = HashMap::new();
+        attrs.insert("provider".to_string(), provider.to_string());
+        attrs.insert("region".to_string(), result.region.clone());
+        attrs.insert("healthy".to_string(), result.healthy.to_string());
+        if let Some(ms) = result.latency_ms {
+            attrs.insert("latency_ms".to_string(), ms.to_string());
+        }
+        self.create_span("region_probe", attrs)
+    }
 }
 
 /// Handle to a telemetry span.
@@ -0,0 +1,963 @@
+//! OpenTelemetry (OTLP) export of the event stream.
+//!
+//! [`OtelTracing::install`] subscribes to the scope events emitted during a
+//! run (crew kickoff, task, agent execution, LLM call, tool usage, knowledge
+//! query, flow and flow method) and turns each start/end pair into a span.
+//! Spans are nested following the event bus's scope tracking, so a crew run
+//! shows up as a `crew → task → agent → llm-call / tool-call` tree in
+//! Jaeger, Tempo, Langfuse or any other OTLP/HTTP (JSON) collector, sent by
+//! the `opentelemetry-otlp` exporter.
+//! Knowledge query spans carry the query, the number of chunks returned,
+//! their scores and the search latency.
+//!
+//! Configuration follows the standard OpenTelemetry environment variables
+//! (see [`OtelConfig::from_env`]); set `CREWAI_OTEL_OPT_OUT=true` or
+//! `OTEL_SDK_DISABLED=true` to turn the exporter off.
+//!
+//! ```ignore
+//! use crewai::telemetry::otel::OtelTracing;
+//!
+//! let _tracing = OtelTracing::install_from_env();
+//! crew.kickoff(None)?;
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use opentelemetry::trace::{
+    self, SpanContext, SpanId, Status, TraceError, TraceFlags, TraceId, TraceState,
+};
+use opentelemetry::{InstrumentationScope, KeyValue};
+use opentelemetry_otlp::{Protocol, WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::export::trace::{SpanData, SpanExporter};
+use opentelemetry_sdk::trace::{SpanEvents, SpanLinks};
+use opentelemetry_sdk::Resource;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::events::base_event::BaseEventData;
+use crate::events::event_bus::serialize_event;
+use crate::events::event_context::{SCOPE_STARTING_EVENTS, VALID_EVENT_PAIRS};
+use crate::events::types::agent_events::{
+    AgentExecutionCompletedEvent, AgentExecutionErrorEvent, AgentExecutionStartedEvent,
+};
+use crate::events::types::crew_events::{
+    CrewKickoffCompletedEvent, CrewKickoffFailedEvent, CrewKickoffStartedEvent,
+};
+use crate::events::types::flow_events::{
+    FlowFinishedEvent, FlowStartedEvent, MethodExecutionFailedEvent, MethodExecutionFinishedEvent,
+    MethodExecutionStartedEvent,
+};
+use crate::events::types::knowledge_events::{
+    KnowledgeQueryCompletedEvent, KnowledgeQueryFailedEvent, KnowledgeQueryStartedEvent,
+};
+use crate::events::types::llm_events::{
+    LLMCallCompletedEvent, LLMCallFailedEvent, LLMCallStartedEvent,
+};
+use crate::events::types::task_events::{TaskCompletedEvent, TaskFailedEvent, TaskStartedEvent};
+use crate::events::types::tool_events::{
+    ToolUsageErrorEvent, ToolUsageFinishedEvent, ToolUsageStartedEvent,
+};
+use crate::events::{BaseEvent, CrewAIEventsBus};
+
+/// Environment variable that disables the exporter when set to `true`/`1`.
+pub const OPT_OUT_ENV: &str = "CREWAI_OTEL_OPT_OUT";
+
+/// Collector endpoint used when no endpoint is configured.
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:4318/v1/traces";
+
+/// Instrumentation scope name reported with every span.
+const SCOPE_NAME: &str = "crewai";
+
+/// Event types that close a run; the buffer is exported right away.
+const ROOT_END_EVENTS: &[&str] = &[
+    "crew_kickoff_completed",
+    "crew_kickoff_failed",
+    "flow_finished",
+];
+
+/// Batches an unmatched end event is retried for before it is dropped.
+const MAX_ORPHAN_RETRIES: u8 = 3;
+
+// ---------------------------------------------------------------------------
+// Configuration
+// ---------------------------------------------------------------------------
+
+/// Settings for the OTLP exporter.
+#[derive(Debug, Clone)]
+pub struct OtelConfig {
+    /// OTLP/HTTP traces endpoint (e.g. `http://localhost:4318/v1/traces`).
+    pub endpoint: String,
+    /// Extra request headers (e.g. authentication for a hosted collector).
+    pub headers: HashMap<String, String>,
+    /// `service.name` resource attribute.
+    pub service_name: String,
+    /// How often buffered spans are exported.
+    pub export_interval: Duration,
+    /// Request timeout for each export.
+    pub timeout: Duration,
+}
+
+impl OtelConfig {
+    /// Create a config exporting to `endpoint`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            headers: HashMap::new(),
+            service_name: "crewai".to_string(),
+            export_interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Builder: add a request header.
+    pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builder: set the `service.name` resource attribute.
+    pub fn with_service_name(mut self, name: impl Into<String>) -> Self {
+        self.service_name = name.into();
+        self
+    }
+
+    /// Builder: set the export interval.
+    pub fn with_export_interval(mut self, interval: Duration) -> Self {
+        self.export_interval = interval;
+        self
+    }
+
+    /// Builder: set the export request timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Read the config from the standard OpenTelemetry environment variables.
+    ///
+    /// * `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT` - full traces endpoint, or
+    /// * `OTEL_EXPORTER_OTLP_ENDPOINT` - base endpoint (`/v1/traces` is appended)
+    /// * `OTEL_EXPORTER_OTLP_HEADERS` - `key=value` pairs separated by commas
+    /// * `OTEL_SERVICE_NAME` - service name
+    ///
+    /// Returns `None` when the exporter is disabled (see [`is_disabled`]).
+    pub fn from_env() -> Option<Self> {
+        if is_disabled() {
+            return None;
+        }
+        let endpoint = non_empty_var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT")
+            .or_else(|| {
+                non_empty_var("OTEL_EXPORTER_OTLP_ENDPOINT")
+                    .map(|base| format!("{}/v1/traces", base.trim_end_matches('/')))
+            })
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        let mut config = Self::new(endpoint);
+        if let Some(headers) = non_empty_var("OTEL_EXPORTER_OTLP_HEADERS") {
+            config.headers = parse_headers(&headers);
+        }
+        if let Some(name) = non_empty_var("OTEL_SERVICE_NAME") {
+            config.service_name = name;
+        }
+        Some(config)
+    }
+}
+
+/// Whether OTLP export is disabled via `CREWAI_OTEL_OPT_OUT` or `OTEL_SDK_DISABLED`.
+pub fn is_disabled() -> bool {
+    [OPT_OUT_ENV, "OTEL_SDK_DISABLED"].iter().any(|var| {
+        matches!(
+            env::var(var)
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+                .as_str(),
+            "true" | "1"
+        )
+    })
+}
+
+fn non_empty_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Parse an `OTEL_EXPORTER_OTLP_HEADERS` value (`k1=v1,k2=v2`, values
+/// percent-encoded).
+pub fn parse_headers(value: &str) -> HashMap<String, String> {
+    value
+        .split(',')
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            Some((key.to_string(), percent_decode(value.trim())))
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                out.push(byte);
+                i += 3;
+            }
+            None => {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+// ---------------------------------------------------------------------------
+// Spans
+// ---------------------------------------------------------------------------
+
+/// OTLP span kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    /// Work inside the process (crew, task, agent, flow).
+    Internal,
+    /// A call out to another system (LLM provider, tool).
+    Client,
+}
+
+/// A finished span, ready for export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanRecord {
+    /// 32 hex character trace ID.
+    pub trace_id: String,
+    /// 16 hex character span ID.
+    pub span_id: String,
+    /// Span ID of the enclosing span, if any.
+    pub parent_span_id: Option<String>,
+    /// Span name (e.g. `task Research`).
+    pub name: String,
+    /// Span kind.
+    pub kind: SpanKind,
+    /// Start time (from the start event).
+    pub start: DateTime<Utc>,
+    /// End time (from the end event).
+    pub end: DateTime<Utc>,
+    /// Span attributes.
+    pub attributes: BTreeMap<String, Value>,
+    /// Error message, if the scope failed.
+    pub error: Option<String>,
+}
+
+struct OpenSpan {
+    event: BaseEventData,
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+}
+
+/// Pairs scope start/end events into spans.
+///
+/// Events may arrive out of order and across batches; they are processed in
+/// emission order, and end events whose start has not been seen yet are kept
+/// for a few batches before being dropped.
+#[derive(Default)]
+pub struct SpanBuilder {
+    /// Open spans by start event ID.
+    open: HashMap<String, OpenSpan>,
+    /// End events without a matching open span, with their retry count.
+    orphans: Vec<(BaseEventData, u8)>,
+}
+
+impl SpanBuilder {
+    /// Create an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of spans started but not yet ended.
+    pub fn open_spans(&self) -> usize {
+        self.open.len()
+    }
+
+    /// Feed a batch of events, returning the spans they completed.
+    pub fn push(&mut self, events: Vec<BaseEventData>) -> Vec<SpanRecord> {
+        let mut batch: Vec<(BaseEventData, u8)> = std::mem::take(&mut self.orphans);
+        batch.extend(events.into_iter().map(|e| (e, 0)));
+        batch.sort_by_key(|(e, _)| e.emission_sequence.unwrap_or(u64::MAX));
+
+        let mut spans = Vec::new();
+        for (event, retries) in batch {
+            if SCOPE_STARTING_EVENTS.contains(event.event_type.as_str()) {
+                self.start(event);
+            } else if let Some(start_type) = VALID_EVENT_PAIRS.get(event.event_type.as_str()) {
+                match self.finish(start_type, &event) {
+                    Some(span) => spans.push(span),
+                    None if retries < MAX_ORPHAN_RETRIES => self.orphans.push((event, retries + 1)),
+                    None => log::debug!(
+                        "Dropping {} event {} without a matching start",
+                        event.event_type,
+                        event.event_id
+                    ),
+                }
+            }
+        }
+        spans
+    }
+
+    fn start(&mut self, event: BaseEventData) {
+        let parent = event
+            .parent_event_id
+            .as_deref()
+            .and_then(|id| self.open.get(id));
+        let (trace_id, parent_span_id) = match parent {
+            Some(parent) => (parent.trace_id.clone(), Some(parent.span_id.clone())),
+            None => (hex_id(&event.event_id, 32), None),
+        };
+        let span_id = hex_id(&event.event_id, 16);
+        self.open.insert(
+            event.event_id.clone(),
+            OpenSpan {
+                event,
+                trace_id,
+                span_id,
+                parent_span_id,
+            },
+        );
+    }
+
+    /// Close the most recent open span of `start_type` in the end event's scope.
+    fn finish(&mut self, start_type: &str, end: &BaseEventData) -> Option<SpanRecord> {
+        let id = self
+            .open
+            .values()
+            .filter(|s| {
+                s.event.event_type == start_type && s.event.parent_event_id == end.parent_event_id
+            })
+            .max_by_key(|s| s.event.emission_sequence)
+            .map(|s| s.event.event_id.clone())?;
+        let open = self.open.remove(&id)?;
+        Some(build_span(open, end))
+    }
+}
+
+/// A fixed-width lowercase hex ID derived from an event UUID.
+fn hex_id(event_id: &str, len: usize) -> String {
+    let mut hex: String = event_id
+        .chars()
+        .filter(char::is_ascii_hexdigit)
+        .map(|c| c.to_ascii_lowercase())
+        .take(len)
+        .collect();
+    while hex.len() < len {
+        hex.push('0');
+    }
+    hex
+}
+
+fn payload_str<'a>(event: &'a BaseEventData, key: &str) -> Option<&'a str> {
+    event.payload.as_ref()?.get(key)?.as_str()
+}
+
+fn build_span(open: OpenSpan, end: &BaseEventData) -> SpanRecord {
+    let start = &open.event;
+    let field = |key: &str| payload_str(start, key).map(str::to_string);
+    let label = |prefix: &str, value: Option<String>| match value {
+        Some(v) => format!("{} {}", prefix, v),
+        None => prefix.to_string(),
+    };
+
+    let mut attributes = BTreeMap::new();
+    attributes.insert("crewai.event_type".to_string(), json!(start.event_type));
+    attributes.insert("crewai.event_id".to_string(), json!(start.event_id));
+    let mut put = |key: &str, value: Option<String>| {
+        if let Some(v) = value {
+            attributes.insert(key.to_string(), Value::String(v));
+        }
+    };
+    put("crewai.task.id", start.task_id.clone());
+    put("crewai.task.name", start.task_name.clone());
+    put("crewai.agent.id", start.agent_id.clone());
+    put("crewai.agent.role", start.agent_role.clone());
+    put("crewai.source.type", start.source_type.clone());
+    put(
+        "crewai.source.fingerprint",
+        start.source_fingerprint.clone(),
+    );
+
+    let (name, kind) = match start.event_type.as_str() {
+        "crew_kickoff_started" => {
+            put("crewai.crew.name", field("crew_name"));
+            (
+                label("crew.kickoff", field("crew_name")),
+                SpanKind::Internal,
+            )
+        }
+        "task_started" => (label("task", start.task_name.clone()), SpanKind::Internal),
+        "agent_execution_started" => (label("agent", start.agent_role.clone()), SpanKind::Internal),
+        "llm_call_started" => {
+            put("gen_ai.request.model", field("model"));
+            put("gen_ai.call_id", field("call_id"));
+            (label("llm.call", field("model")), SpanKind::Client)
+        }
+        "tool_usage_started" => {
+            put("tool.name", field("tool_name"));
+            (label("tool", field("tool_name")), SpanKind::Client)
+        }
+        "knowledge_query_started" => {
+            put("crewai.knowledge.query", field("task_prompt"));
+            ("knowledge.query".to_string(), SpanKind::Client)
+        }
+        "flow_started" => {
+            put("crewai.flow.name", field("flow_name"));
+            (label("flow", field("flow_name")), SpanKind::Internal)
+        }
+        "method_execution_started" => {
+            put("crewai.flow.name", field("flow_name"));
+            put("crewai.flow.method", field("method_name"));
+            (
+                label("flow.method", field("method_name")),
+                SpanKind::Internal,
+            )
+        }
+        other => (
+            other.trim_end_matches("_started").replace('_', "."),
+            SpanKind::Internal,
+        ),
+    };
+    if let Some(tokens) = end
+        .payload
+        .as_ref()
+        .and_then(|p| p.get("total_tokens"))
+        .and_then(Value::as_i64)
+    {
+        attributes.insert("gen_ai.usage.total_tokens".to_string(), json!(tokens));
+    }
+
+    if end.event_type == "knowledge_query_completed" {
+        for key in ["chunks", "scores", "latency_ms"] {
+            if let Some(value) = end.payload.as_ref().and_then(|p| p.get(key)) {
+                attributes.insert(format!("crewai.knowledge.{}", key), value.clone());
+            }
+        }
+    }
+
+    let error = end.payload.as_ref().and_then(|p| match p.get("error")? {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    });
+
+    SpanRecord {
+        trace_id: open.trace_id,
+        span_id: open.span_id,
+        parent_span_id: open.parent_span_id,
+        name,
+        kind,
+        start: start.timestamp,
+        end: end.timestamp.max(start.timestamp),
+        attributes,
+        error,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// OTLP export
+// ---------------------------------------------------------------------------
+
+/// Convert a span to the OpenTelemetry SDK's export format.
+pub fn to_span_data(span: &SpanRecord) -> SpanData {
+    let span_id = |id: &str| SpanId::from_hex(id).unwrap_or(SpanId::INVALID);
+    let context = SpanContext::new(
+        TraceId::from_hex(&span.trace_id).unwrap_or(TraceId::INVALID),
+        span_id(&span.span_id),
+        TraceFlags::SAMPLED,
+        false,
+        TraceState::default(),
+    );
+    SpanData {
+        span_context: context,
+        parent_span_id: span
+            .parent_span_id
+            .as_deref()
+            .map_or(SpanId::INVALID, span_id),
+        span_kind: match span.kind {
+            SpanKind::Internal => trace::SpanKind::Internal,
+            SpanKind::Client => trace::SpanKind::Client,
+        },
+        name: span.name.clone().into(),
+        start_time: span.start.into(),
+        end_time: span.end.into(),
+        attributes: span
+            .attributes
+            .iter()
+            .map(|(k, v)| key_value(k, v))
+            .collect(),
+        dropped_attributes_count: 0,
+        events: SpanEvents::default(),
+        links: SpanLinks::default(),
+        status: match &span.error {
+            Some(message) => Status::error(message.clone()),
+            None => Status::Ok,
+        },
+        instrumentation_scope: InstrumentationScope::builder(SCOPE_NAME)
+            .with_version(env!("CARGO_PKG_VERSION"))
+            .build(),
+    }
+}
+
+fn key_value(key: &str, value: &Value) -> KeyValue {
+    let key = key.to_string();
+    match value {
+        Value::Bool(b) => KeyValue::new(key, *b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => KeyValue::new(key, i),
+            None => KeyValue::new(key, n.as_f64().unwrap_or_default()),
+        },
+        Value::String(s) => KeyValue::new(key, s.clone()),
+        other => KeyValue::new(key, other.to_string()),
+    }
+}
+
+/// Errors from exporting spans.
+#[derive(Debug, Error)]
+pub enum OtelError {
+    /// The exporter could not be built, or the collector did not accept
+    /// the spans.
+    #[error("OTLP export failed: {0}")]
+    Export(#[from] TraceError),
+}
+
+/// Sends spans to an OTLP/HTTP collector, encoded as JSON.
+#[derive(Debug)]
+pub struct OtlpExporter {
+    exporter: opentelemetry_otlp::SpanExporter,
+}
+
+impl OtlpExporter {
+    /// Create an exporter for `config`.
+    pub fn new(config: OtelConfig) -> Result<Self, OtelError> {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_default();
+        let mut exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpJson)
+            .with_endpoint(config.endpoint)
+            .with_headers(config.headers)
+            .with_timeout(config.timeout)
+            .with_http_client(client)
+            .build()?;
+        exporter.set_resource(&Resource::new([KeyValue::new(
+            "service.name",
+            config.service_name,
+        )]));
+        Ok(Self { exporter })
+    }
+
+    /// Export a batch of spans.
+    pub async fn export(&mut self, spans: &[SpanRecord]) -> Result<(), OtelError> {
+        if spans.is_empty() {
+            return Ok(());
+        }
+        let batch = spans.iter().map(to_span_data).collect();
+        self.exporter.export(batch).await?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Event bus integration
+// ---------------------------------------------------------------------------
+
+#[derive(Default)]
+struct WorkerState {
+    buffer: Vec<BaseEventData>,
+    flush_requested: bool,
+    stopped: bool,
+    /// Completed export rounds.
+    rounds: u64,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<WorkerState>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn record(&self, event: &dyn BaseEvent) {
+        let mut state = self.state.lock().unwrap();
+        if state.stopped {
+            return;
+        }
+        let root_end = ROOT_END_EVENTS.contains(&event.event_type());
+        state.buffer.push((*serialize_event(event)).clone());
+        if root_end {
+            state.flush_requested = true;
+            self.changed.notify_all();
+        }
+    }
+}
+
+/// Exports the event stream to an OTLP collector while installed.
+///
+/// Spans are exported periodically, whenever a crew kickoff or flow ends,
+/// and on [`flush`](Self::flush). Dropping the handle flushes and stops the
+/// exporter.
+pub struct OtelTracing {
+    shared: Arc<Shared>,
+    worker: Option<std::thread::JoinHandle<()>>,
+}
+
+impl OtelTracing {
+    /// Subscribe to the global event bus and start exporting to `config`.
+    pub fn install(config: OtelConfig) -> std::io::Result<Self> {
+        let shared = Arc::new(Shared::default());
+        let bus = CrewAIEventsBus::global();
+
+        macro_rules! subscribe {
+            ($($event:ty),* $(,)?) => {
+                $(
+                    let sink = shared.clone();
+                    bus.on::<$event>(
+                        "otel_exporter",
+                        move |_, event| sink.record(event),
+                        None,
+                    );
+                )*
+            };
+        }
+        subscribe!(
+            CrewKickoffStartedEvent,
+            CrewKickoffCompletedEvent,
+            CrewKickoffFailedEvent,
+            TaskStartedEvent,
+            TaskCompletedEvent,
+            TaskFailedEvent,
+            AgentExecutionStartedEvent,
+            AgentExecutionCompletedEvent,
+            AgentExecutionErrorEvent,
+            LLMCallStartedEvent,
+            LLMCallCompletedEvent,
+            LLMCallFailedEvent,
+            ToolUsageStartedEvent,
+            ToolUsageFinishedEvent,
+            ToolUsageErrorEvent,
+            KnowledgeQueryStartedEvent,
+            KnowledgeQueryCompletedEvent,
+            KnowledgeQueryFailedEvent,
+            FlowStartedEvent,
+            FlowFinishedEvent,
+            MethodExecutionStartedEvent,
+            MethodExecutionFinishedEvent,
+            MethodExecutionFailedEvent,
+        );
+
+        let worker_shared = shared.clone();
+        let worker = std::thread::Builder::new()
+            .name("crewai-otel-exporter".to_string())
+            .spawn(move || run_worker(worker_shared, config))?;
+        Ok(Self {
+            shared,
+            worker: Some(worker),
+        })
+    }
+
+    /// Install with [`OtelConfig::from_env`], unless the exporter is disabled.
+    pub fn install_from_env() -> Option<Self> {
+        let config = OtelConfig::from_env()?;
+        match Self::install(config) {
+            Ok(tracing) => Some(tracing),
+            Err(e) => {
+                log::warn!("Failed to start OTLP exporter: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Export everything emitted so far and wait for the export to finish.
+    pub fn flush(&self) {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.stopped {
+            return;
+        }
+        let target = state.rounds + 1;
+        state.flush_requested = true;
+        self.shared.changed.notify_all();
+        while state.rounds < target && !state.stopped {
+            state = self.shared.changed.wait(state).unwrap();
+        }
+    }
+
+    /// Flush and stop exporting.
+    pub fn shutdown(&mut self) {
+        self.flush();
+        self.shared.state.lock().unwrap().stopped = true;
+        self.shared.changed.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for OtelTracing {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run_worker(shared: Arc<Shared>, config: OtelConfig) {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            log::warn!("OTLP exporter could not start a runtime: {}", e);
+            shared.state.lock().unwrap().stopped = true;
+            shared.changed.notify_all();
+            return;
+        }
+    };
+    let interval = config.export_interval;
+    let service_name = config.service_name.clone();
+    let mut exporter = match OtlpExporter::new(config) {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            log::warn!("{}", e);
+            shared.state.lock().unwrap().stopped = true;
+            shared.changed.notify_all();
+            return;
+        }
+    };
+    let mut builder = SpanBuilder::new();
+
+    loop {
+        {
+            let state = shared.state.lock().unwrap();
+            let (state, _) = shared
+                .changed
+                .wait_timeout_while(state, interval, |s| !s.flush_requested && !s.stopped)
+                .unwrap();
+            if state.stopped {
+                return;
+            }
+        }
+
+        // Let in-flight handlers deliver their events before draining.
+        CrewAIEventsBus::global().flush();
+        let events = {
+            let mut state = shared.state.lock().unwrap();
+            state.flush_requested = false;
+            std::mem::take(&mut state.buffer)
+        };
+        let spans = builder.push(events);
+        if !spans.is_empty() {
+            log::debug!("Exporting {} spans for {}", spans.len(), service_name);
+            if let Err(e) = runtime.block_on(exporter.export(&spans)) {
+                log::warn!("{}", e);
+            }
+        }
+
+        let mut state = shared.state.lock().unwrap();
+        state.rounds += 1;
+        shared.changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        seq: u64,
+        event_type: &str,
+        parent: Option<&BaseEventData>,
+        payload: Value,
+    ) -> BaseEventData {
+        let mut data = BaseEventData::new(event_type);
+        data.emission_sequence = Some(seq);
+        data.parent_event_id = parent.map(|p| p.event_id.clone());
+        data.payload = Some(payload);
+        data
+    }
+
+    #[test]
+    fn test_span_hierarchy() {
+        let crew = event(
+            1,
+            "crew_kickoff_started",
+            None,
+            json!({"crew_name": "research"}),
+        );
+        let mut task = event(2, "task_started", Some(&crew), json!({}));
+        task.task_name = Some("Summarize".into());
+        let agent = event(3, "agent_execution_started", Some(&task), json!({}));
+        let llm = event(
+            4,
+            "llm_call_started",
+            Some(&agent),
+            json!({"model": "gpt-4o"}),
+        );
+        let llm_end = event(5, "llm_call_completed", Some(&agent), json!({}));
+        let tool = event(
+            6,
+            "tool_usage_started",
+            Some(&agent),
+            json!({"tool_name": "search"}),
+        );
+        let tool_end = event(
+            7,
+            "tool_usage_error",
+            Some(&agent),
+            json!({"error": "timeout"}),
+        );
+        let agent_end = event(8, "agent_execution_completed", Some(&task), json!({}));
+        let task_end = event(9, "task_completed", Some(&crew), json!({}));
+        let crew_end = event(
+            10,
+            "crew_kickoff_completed",
+            None,
+            json!({"total_tokens": 42}),
+        );
+
+        let mut builder = SpanBuilder::new();
+        // The tool's end arrives in a batch before its start.
+        let first = builder.push(vec![
+            tool_end,
+            crew.clone(),
+            task,
+            agent.clone(),
+            llm_end,
+            llm,
+        ]);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].name, "llm.call gpt-4o");
+        assert_eq!(first[0].kind, SpanKind::Client);
+
+        let rest = builder.push(vec![crew_end, task_end, agent_end, tool]);
+        assert_eq!(builder.open_spans(), 0);
+        let names: Vec<&str> = rest.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "tool search",
+                "agent",
+                "task Summarize",
+                "crew.kickoff research"
+            ]
+        );
+
+        let trace_id = hex_id(&crew.event_id, 32);
+        assert!(first.iter().chain(&rest).all(|s| s.trace_id == trace_id));
+        assert_eq!(rest[0].error.as_deref(), Some("timeout"));
+        assert_eq!(rest[0].parent_span_id, Some(hex_id(&agent.event_id, 16)));
+        assert_eq!(rest[3].parent_span_id, None);
+        assert_eq!(rest[3].attributes["gen_ai.usage.total_tokens"], json!(42));
+    }
+
+    #[test]
+    fn test_knowledge_query_span() {
+        let task = event(1, "task_started", None, json!({}));
+        let query = event(
+            2,
+            "knowledge_query_started",
+            Some(&task),
+            json!({"task_prompt": "refund policy"}),
+        );
+        let done = event(
+            3,
+            "knowledge_query_completed",
+            Some(&task),
+            json!({"chunks": 2, "scores": [0.82, 0.41], "latency_ms": 12}),
+        );
+
+        let spans = SpanBuilder::new().push(vec![task, query, done]);
+        assert_eq!(spans.len(), 1);
+        let span = &spans[0];
+        assert_eq!(span.name, "knowledge.query");
+        assert_eq!(span.attributes["crewai.knowledge.query"], "refund policy");
+        assert_eq!(span.attributes["crewai.knowledge.chunks"], json!(2));
+        assert_eq!(
+            span.attributes["crewai.knowledge.scores"],
+            json!([0.82, 0.41])
+        );
+        assert_eq!(span.attributes["crewai.knowledge.latency_ms"], json!(12));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_export_and_headers() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let router = axum::Router::new().route(
+            "/v1/traces",
+            axum::routing::post(
+                move |headers: axum::http::HeaderMap, axum::Json(body): axum::Json<Value>| {
+                    let tenant = headers["x-tenant"].to_str().unwrap().to_string();
+                    sink.lock().unwrap().push((tenant, body));
+                    async { "{}" }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let start = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let span = SpanRecord {
+            trace_id: "0af7651916cd43dd8448eb211c80319c".into(),
+            span_id: "b7ad6b7169203331".into(),
+            parent_span_id: None,
+            name: "tool search".into(),
+            kind: SpanKind::Client,
+            start,
+            end: start + chrono::Duration::milliseconds(250),
+            attributes: BTreeMap::from([
+                ("tool.name".to_string(), json!("search")),
+                ("attempts".to_string(), json!(2)),
+            ]),
+            error: Some("timeout".into()),
+        };
+        let config = OtelConfig::new(endpoint)
+            .with_header("x-tenant", "t1")
+            .with_service_name("svc");
+        let mut exporter = OtlpExporter::new(config).unwrap();
+        exporter.export(&[span]).await.unwrap();
+
+        let received = received.lock().unwrap();
+        let (tenant, body) = &received[0];
+        assert_eq!(tenant, "t1");
+        let resource = &body["resourceSpans"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][0],
+            json!({"key": "service.name", "value": {"stringValue": "svc"}})
+        );
+        let scope = &resource["scopeSpans"][0];
+        assert_eq!(scope["scope"]["name"], SCOPE_NAME);
+        let encoded = &scope["spans"][0];
+        assert_eq!(encoded["traceId"], "0af7651916cd43dd8448eb211c80319c");
+        assert_eq!(encoded["name"], "tool search");
+        assert_eq!(encoded["kind"], 3);
+        assert_eq!(encoded["startTimeUnixNano"], "1700000000000000000");
+        assert_eq!(encoded["endTimeUnixNano"], "1700000000250000000");
+        assert_eq!(encoded["status"]["message"], "timeout");
+        assert_eq!(encoded["status"]["code"], 2);
+
+        let headers = parse_headers("Authorization=Bearer%20abc, x-tenant = t1,bad");
+        assert_eq!(headers["Authorization"], "Bearer abc");
+        assert_eq!(headers["x-tenant"], "t1");
+        assert_eq!(headers.len(), 2);
+    }
+}
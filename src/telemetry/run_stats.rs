@@ -0,0 +1,243 @@
+//! Anonymized run statistics.
+//!
+//! At the end of every crew kickoff and flow run a [`RunStatistics`] is
+//! handed to the [`TelemetryClient`]s registered on the
+//! [`Telemetry`](super::Telemetry) singleton. Statistics only hold counts,
+//! durations, the outcome and model *families* (`gpt`, `claude`, ...): no
+//! names, IDs, prompts, outputs or full model names.
+//!
+//! # Configuration
+//!
+//! - `CREWAI_TELEMETRY_ENDPOINT` — URL the statistics are posted to as
+//!   JSON; nothing is posted without it
+//! - `CREWAI_DISABLE_TELEMETRY`, `CREWAI_TELEMETRY_OPT_OUT` or
+//!   `OTEL_SDK_DISABLED` set to `true`/`1` — disable telemetry entirely,
+//!   including custom clients
+//!
+//! ```ignore
+//! use crewai::telemetry::{telemetry, run_stats::{RunStatistics, TelemetryClient}};
+//!
+//! #[derive(Debug)]
+//! struct LogClient;
+//!
+//! impl TelemetryClient for LogClient {
+//!     fn send(&self, stats: &RunStatistics) {
+//!         log::info!("{:?} run took {} ms", stats.kind, stats.duration_ms);
+//!     }
+//! }
+//!
+//! telemetry().lock().unwrap().add_client(std::sync::Arc::new(LogClient));
+//! ```
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Environment variable with the URL statistics are posted to.
+pub const TELEMETRY_ENDPOINT_ENV: &str = "CREWAI_TELEMETRY_ENDPOINT";
+
+/// Model families reported as is; any other model is reported as `other`.
+const MODEL_FAMILIES: &[&str] = &[
+    "gpt", "o1", "o3", "o4", "claude", "gemini", "grok", "llama", "mistral", "mixtral", "command",
+    "deepseek", "qwen", "phi",
+];
+
+/// What kind of run the statistics describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunKind {
+    /// A crew kickoff.
+    Crew,
+    /// A flow run.
+    Flow,
+}
+
+/// Anonymized statistics of one crew kickoff or flow run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStatistics {
+    /// Random ID of this report, unrelated to the crew or flow ID.
+    pub run_id: String,
+    /// Crew or flow.
+    pub kind: RunKind,
+    /// Version of this crate.
+    pub crewai_version: String,
+    /// Operating system (`linux`, `macos`, `windows`, ...).
+    pub os: String,
+    /// Whether the run succeeded.
+    pub success: bool,
+    /// Wall-clock duration of the run.
+    pub duration_ms: u64,
+    /// Process of a crew (`sequential` or `hierarchical`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+    /// Agents in the crew.
+    pub agent_count: usize,
+    /// Tasks in the crew.
+    pub task_count: usize,
+    /// Tasks that completed.
+    pub tasks_completed: usize,
+    /// Flow methods executed.
+    pub method_count: usize,
+    /// Families of the models used (see [`model_family`]).
+    pub model_families: BTreeSet<String>,
+    /// Tokens used by the run.
+    pub total_tokens: i64,
+}
+
+impl RunStatistics {
+    /// Empty statistics for a run of `kind`.
+    pub fn new(kind: RunKind, success: bool, duration: Duration) -> Self {
+        Self {
+            run_id: Uuid::new_v4().to_string(),
+            kind,
+            crewai_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            success,
+            duration_ms: duration.as_millis() as u64,
+            process: None,
+            agent_count: 0,
+            task_count: 0,
+            tasks_completed: 0,
+            method_count: 0,
+            model_families: BTreeSet::new(),
+            total_tokens: 0,
+        }
+    }
+}
+
+/// The family of `model`: `gpt-4o-mini` and `openai/gpt-4o` are `gpt`,
+/// `anthropic/claude-3-5-sonnet` is `claude`. Unknown models (including
+/// custom and fine-tuned names, which may be identifying) are `other`.
+pub fn model_family(model: &str) -> String {
+    let name = model.rsplit('/').next().unwrap_or(model).to_lowercase();
+    let name = name.strip_prefix("ft:").unwrap_or(&name);
+    MODEL_FAMILIES
+        .iter()
+        .find(|family| name.starts_with(*family))
+        .map_or_else(|| "other".to_string(), |family| family.to_string())
+}
+
+/// A sink for run statistics.
+///
+/// Called on the thread that finished the run; implementations should
+/// return quickly and do any I/O in the background.
+pub trait TelemetryClient: Send + Sync {
+    /// Receive the statistics of a finished run.
+    fn send(&self, stats: &RunStatistics);
+}
+
+/// Posts statistics as JSON to an HTTP endpoint, in the background.
+///
+/// Failures are logged at debug level and never affect the run.
+#[derive(Debug, Clone)]
+pub struct HttpTelemetryClient {
+    /// URL the statistics are posted to.
+    pub endpoint: String,
+    /// Request timeout.
+    pub timeout: Duration,
+}
+
+impl HttpTelemetryClient {
+    /// A client posting to `endpoint`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// A client posting to `CREWAI_TELEMETRY_ENDPOINT`, if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(TELEMETRY_ENDPOINT_ENV)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(Self::new)
+    }
+
+    async fn post(&self, stats: &RunStatistics) -> Result<(), reqwest::Error> {
+        reqwest::Client::builder()
+            .timeout(self.timeout)
+            .build()?
+            .post(&self.endpoint)
+            .json(stats)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+impl TelemetryClient for HttpTelemetryClient {
+    fn send(&self, stats: &RunStatistics) {
+        let client = self.clone();
+        let stats = stats.clone();
+        let spawned = std::thread::Builder::new()
+            .name("crewai-telemetry".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => return log::debug!("Telemetry runtime failed: {}", e),
+                };
+                if let Err(e) = runtime.block_on(client.post(&stats)) {
+                    log::debug!("Telemetry post to {} failed: {}", client.endpoint, e);
+                }
+            });
+        if let Err(e) = spawned {
+            log::debug!("Telemetry thread failed to start: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_model_family() {
+        assert_eq!(model_family("gpt-4o-mini"), "gpt");
+        assert_eq!(model_family("openai/gpt-4o"), "gpt");
+        assert_eq!(model_family("anthropic/claude-3-5-sonnet"), "claude");
+        assert_eq!(model_family("ft:gpt-4o:acme:support"), "gpt");
+        assert_eq!(model_family("together_ai/meta-llama/Llama-3-70b"), "llama");
+        assert_eq!(model_family("acme-internal-7b"), "other");
+    }
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<RunStatistics>>);
+
+    impl TelemetryClient for Recorder {
+        fn send(&self, stats: &RunStatistics) {
+            self.0.lock().unwrap().push(stats.clone());
+        }
+    }
+
+    #[test]
+    fn test_clients_receive_statistics() {
+        let recorder = Arc::new(Recorder::default());
+        let mut telemetry = super::super::Telemetry::new();
+        telemetry.add_client(recorder.clone());
+
+        let mut stats = RunStatistics::new(RunKind::Crew, true, Duration::from_millis(1500));
+        stats.task_count = 2;
+        stats.model_families.insert(model_family("gpt-4o"));
+        telemetry.record_run(&stats);
+
+        let sent = recorder.0.lock().unwrap();
+        if telemetry.is_telemetry_disabled() {
+            assert!(sent.is_empty());
+        } else {
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].duration_ms, 1500);
+            assert_eq!(sent[0].kind, RunKind::Crew);
+        }
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["kind"], "crew");
+        assert_eq!(json["model_families"], serde_json::json!(["gpt"]));
+    }
+}
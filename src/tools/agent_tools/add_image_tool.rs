@@ -7,6 +7,11 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::llms::images::{image_block, image_url};
+
+/// Name under which multimodal agents are given the tool.
+pub const ADD_IMAGE_TOOL_NAME: &str = "add_image";
+
 /// Schema for add image tool arguments.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AddImageToolSchema {
@@ -82,13 +87,14 @@ impl AddImageTool {
                     "type": "text",
                     "text": action_text
                 },
-                {
-                    "type": "image_url",
-                    "image_url": {
-                        "url": image_url
-                    }
-                }
+                image_block(image_url)
             ]
         })
     }
+
+    /// Execute the tool for an image URL or local file, which is inlined
+    /// as a `data:` URL.
+    pub fn load(&self, location: &str, action: Option<&str>) -> Result<Value, String> {
+        Ok(self.run(&image_url(location)?, action))
+    }
 }
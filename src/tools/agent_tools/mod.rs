@@ -3,16 +3,18 @@
 //! Corresponds to `crewai/tools/agent_tools/` Python package.
 //!
 //! Provides tools that enable agents to delegate work, ask questions,
-//! read files, and add images.
+//! read files, add images, and keep private scratchpad notes.
 
 pub mod add_image_tool;
 pub mod agent_tools;
 pub mod ask_question_tool;
 pub mod delegate_work_tool;
 pub mod read_file_tool;
+pub mod scratchpad_tool;
 
 pub use add_image_tool::AddImageTool;
 pub use agent_tools::AgentTools;
 pub use ask_question_tool::AskQuestionTool;
 pub use delegate_work_tool::DelegateWorkTool;
 pub use read_file_tool::ReadFileTool;
+pub use scratchpad_tool::{Scratchpad, ScratchpadTool};
@@ -0,0 +1,421 @@
+//! Scratchpad tool.
+//!
+//! Gives an agent a private key-value working memory for notes,
+//! intermediate lists and counters. Entries stay out of the conversation
+//! until the agent explicitly recalls them with `get`, which keeps the
+//! context small in long multi-step tasks.
+//!
+//! Each [`ScratchpadTool`] owns its own [`Scratchpad`], so giving every
+//! agent its own tool keeps scratchpads private. The scratchpad lives as
+//! long as the tool: reuse the tool across tasks to keep notes for the
+//! whole crew run, or call [`Scratchpad::clear`] between tasks to scope them
+//! to a single task.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::tools::base_tool::BaseTool;
+
+/// Default maximum number of keys in a scratchpad.
+pub const DEFAULT_MAX_ENTRIES: usize = 100;
+
+/// Default maximum size of a single value, in characters of its JSON form.
+pub const DEFAULT_MAX_VALUE_CHARS: usize = 10_000;
+
+/// A thread-safe key-value store backing a [`ScratchpadTool`].
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct Scratchpad {
+    entries: Arc<Mutex<BTreeMap<String, Value>>>,
+    max_entries: usize,
+    max_value_chars: usize,
+}
+
+impl Default for Scratchpad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scratchpad {
+    /// An empty scratchpad with the default limits.
+    pub fn new() -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(BTreeMap::new())),
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_value_chars: DEFAULT_MAX_VALUE_CHARS,
+        }
+    }
+
+    /// Builder: set the maximum number of keys and value size.
+    pub fn with_limits(mut self, max_entries: usize, max_value_chars: usize) -> Self {
+        self.max_entries = max_entries;
+        self.max_value_chars = max_value_chars;
+        self
+    }
+
+    /// Store `value` under `key`, replacing any previous value.
+    pub fn set(&self, key: &str, value: Value) -> Result<(), String> {
+        self.check_size(key, &value)?;
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(key) && entries.len() >= self.max_entries {
+            return Err(format!(
+                "Scratchpad is full ({} keys). Delete a key before adding '{}'.",
+                self.max_entries, key
+            ));
+        }
+        entries.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// The value stored under `key`.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.entries.lock().unwrap().get(key).cloned()
+    }
+
+    /// Append `value` to the list under `key`, creating it if needed. A
+    /// non-list value already stored under `key` becomes the first item.
+    pub fn append(&self, key: &str, value: Value) -> Result<usize, String> {
+        let list = match self.get(key) {
+            None => vec![value],
+            Some(Value::Array(mut items)) => {
+                items.push(value);
+                items
+            }
+            Some(other) => vec![other, value],
+        };
+        let len = list.len();
+        self.set(key, Value::Array(list))?;
+        Ok(len)
+    }
+
+    /// Add `amount` to the counter under `key` (starting from 0) and return
+    /// the new value.
+    pub fn increment(&self, key: &str, amount: f64) -> Result<Value, String> {
+        let current = match self.get(key) {
+            None => 0.0,
+            Some(value) => value
+                .as_f64()
+                .ok_or_else(|| format!("'{}' is not a number and cannot be incremented.", key))?,
+        };
+        let total = current + amount;
+        let value = if total.fract() == 0.0 && total.abs() < i64::MAX as f64 {
+            json!(total as i64)
+        } else {
+            json!(total)
+        };
+        self.set(key, value.clone())?;
+        Ok(value)
+    }
+
+    /// Remove `key`, returning whether it existed.
+    pub fn delete(&self, key: &str) -> bool {
+        self.entries.lock().unwrap().remove(key).is_some()
+    }
+
+    /// Remove every entry.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Number of keys.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Whether the scratchpad is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// A copy of every entry.
+    pub fn snapshot(&self) -> BTreeMap<String, Value> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// One line per key describing its type and size, without the contents.
+    pub fn summary(&self) -> String {
+        let entries = self.entries.lock().unwrap();
+        if entries.is_empty() {
+            return "The scratchpad is empty.".to_string();
+        }
+        let lines: Vec<String> = entries
+            .iter()
+            .map(|(key, value)| format!("- {} ({})", key, describe(value)))
+            .collect();
+        format!("Scratchpad keys:\n{}", lines.join("\n"))
+    }
+
+    fn check_size(&self, key: &str, value: &Value) -> Result<(), String> {
+        let size = match value {
+            Value::String(s) => s.chars().count(),
+            other => other.to_string().chars().count(),
+        };
+        if size > self.max_value_chars {
+            return Err(format!(
+                "Value for '{}' is too large ({} characters, limit {}).",
+                key, size, self.max_value_chars
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "empty".to_string(),
+        Value::Bool(b) => format!("flag: {}", b),
+        Value::Number(n) => format!("number: {}", n),
+        Value::String(s) => format!("text, {} chars", s.chars().count()),
+        Value::Array(items) => format!("list, {} items", items.len()),
+        Value::Object(map) => format!("object, {} fields", map.len()),
+    }
+}
+
+/// Tool giving an agent a private key-value scratchpad.
+///
+/// Arguments: `action` (one of `set`, `get`, `append`, `increment`,
+/// `delete`, `list`, `clear`), `key` (all actions except `list` and
+/// `clear`), `value` (for `set` and `append`) and `amount` (for `increment`,
+/// default 1).
+#[derive(Debug, Clone, Default)]
+pub struct ScratchpadTool {
+    scratchpad: Scratchpad,
+    usage_count: u32,
+}
+
+impl ScratchpadTool {
+    /// A tool with its own empty scratchpad.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tool backed by an existing scratchpad.
+    pub fn with_scratchpad(scratchpad: Scratchpad) -> Self {
+        Self {
+            scratchpad,
+            usage_count: 0,
+        }
+    }
+
+    /// The scratchpad backing this tool.
+    pub fn scratchpad(&self) -> &Scratchpad {
+        &self.scratchpad
+    }
+
+    /// Perform one scratchpad action and describe the result for the agent.
+    pub fn execute(&self, args: &HashMap<String, Value>) -> Result<String, String> {
+        let action = args
+            .get("action")
+            .and_then(Value::as_str)
+            .ok_or("scratchpad requires an `action` argument")?;
+        let key = || {
+            args.get("key")
+                .and_then(Value::as_str)
+                .filter(|k| !k.is_empty())
+                .ok_or_else(|| format!("The `{}` action requires a `key` argument", action))
+        };
+        let value = || {
+            args.get("value")
+                .cloned()
+                .ok_or_else(|| format!("The `{}` action requires a `value` argument", action))
+        };
+
+        match action {
+            "set" => {
+                let key = key()?;
+                self.scratchpad.set(key, value()?)?;
+                Ok(format!("Saved '{}'.", key))
+            }
+            "get" => {
+                let key = key()?;
+                match self.scratchpad.get(key) {
+                    Some(Value::String(s)) => Ok(s),
+                    Some(other) => Ok(other.to_string()),
+                    None => Ok(format!("No entry named '{}'.", key)),
+                }
+            }
+            "append" => {
+                let key = key()?;
+                let len = self.scratchpad.append(key, value()?)?;
+                Ok(format!("Appended to '{}' ({} items).", key, len))
+            }
+            "increment" => {
+                let key = key()?;
+                let amount = args.get("amount").and_then(Value::as_f64).unwrap_or(1.0);
+                let total = self.scratchpad.increment(key, amount)?;
+                Ok(format!("'{}' is now {}.", key, total))
+            }
+            "delete" => {
+                let key = key()?;
+                Ok(if self.scratchpad.delete(key) {
+                    format!("Deleted '{}'.", key)
+                } else {
+                    format!("No entry named '{}'.", key)
+                })
+            }
+            "list" => Ok(self.scratchpad.summary()),
+            "clear" => {
+                self.scratchpad.clear();
+                Ok("Cleared the scratchpad.".to_string())
+            }
+            other => Err(format!(
+                "Unknown scratchpad action '{}'. Use one of: set, get, append, increment, \
+                 delete, list, clear.",
+                other
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTool for ScratchpadTool {
+    fn name(&self) -> &str {
+        "scratchpad"
+    }
+
+    fn description(&self) -> &str {
+        "Your private working memory. Store notes, intermediate lists and counters here instead \
+         of repeating them in your reasoning, and recall them when needed. Actions: set, get, \
+         append (add to a list), increment (counter), delete, list (keys only), clear."
+    }
+
+    fn args_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "get", "append", "increment", "delete", "list", "clear"],
+                    "description": "The operation to perform"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Name of the entry"
+                },
+                "value": {
+                    "description": "Value to store (set) or add to the list (append)"
+                },
+                "amount": {
+                    "type": "number",
+                    "description": "Amount to add for increment (default 1)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn should_cache(&self, _args: &Value, _result: &Value) -> bool {
+        // Results depend on the scratchpad's state, not only the arguments.
+        false
+    }
+
+    fn run(
+        &mut self,
+        args: HashMap<String, Value>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.usage_count += 1;
+        Ok(Value::String(self.execute(&args)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(tool: &mut ScratchpadTool, args: Value) -> String {
+        let args: HashMap<String, Value> = serde_json::from_value(args).unwrap();
+        match tool.run(args) {
+            Ok(value) => value.as_str().unwrap().to_string(),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_scratchpad_actions() {
+        let mut tool = ScratchpadTool::new();
+        assert_eq!(
+            call(
+                &mut tool,
+                json!({"action": "set", "key": "plan", "value": "check sources"})
+            ),
+            "Saved 'plan'."
+        );
+        assert_eq!(
+            call(
+                &mut tool,
+                json!({"action": "append", "key": "urls", "value": "a.com"})
+            ),
+            "Appended to 'urls' (1 items)."
+        );
+        call(
+            &mut tool,
+            json!({"action": "append", "key": "urls", "value": "b.com"}),
+        );
+        call(&mut tool, json!({"action": "increment", "key": "visited"}));
+        assert_eq!(
+            call(
+                &mut tool,
+                json!({"action": "increment", "key": "visited", "amount": 2})
+            ),
+            "'visited' is now 3."
+        );
+        assert_eq!(
+            call(&mut tool, json!({"action": "list"})),
+            "Scratchpad keys:\n- plan (text, 13 chars)\n- urls (list, 2 items)\n- visited (number: 3)"
+        );
+        assert_eq!(
+            call(&mut tool, json!({"action": "get", "key": "plan"})),
+            "check sources"
+        );
+        assert_eq!(
+            call(&mut tool, json!({"action": "get", "key": "urls"})),
+            r#"["a.com","b.com"]"#
+        );
+        assert_eq!(
+            call(&mut tool, json!({"action": "increment", "key": "plan"})),
+            "'plan' is not a number and cannot be incremented."
+        );
+        assert_eq!(
+            call(&mut tool, json!({"action": "get"})),
+            "The `get` action requires a `key` argument"
+        );
+        assert_eq!(tool.current_usage_count(), 10);
+
+        // Each tool is private; a shared scratchpad must be passed explicitly.
+        let other = ScratchpadTool::new();
+        assert!(other.scratchpad().is_empty());
+        let shared = ScratchpadTool::with_scratchpad(tool.scratchpad().clone());
+        assert_eq!(shared.scratchpad().len(), 3);
+        call(&mut tool, json!({"action": "clear"}));
+        assert!(shared.scratchpad().is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        let pad = Scratchpad::new().with_limits(1, 5);
+        assert!(pad.set("a", json!("12345")).is_ok());
+        assert!(pad
+            .set("a", json!("123456"))
+            .unwrap_err()
+            .contains("too large"));
+        assert!(pad.set("b", json!(1)).unwrap_err().contains("full"));
+    }
+}
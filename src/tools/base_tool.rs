@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use super::structured_tool::CrewStructuredTool;
+use super::typed_tool::{into_output, ToolArgs};
 
 // ---------------------------------------------------------------------------
 // EnvVar
@@ -193,6 +194,10 @@ pub type ToolFn = Arc<
         + Sync,
 >;
 
+/// Predicate over a call's arguments and result deciding whether the
+/// result may be cached.
+pub type CacheFunction = Arc<dyn Fn(&Value, &Value) -> bool + Send + Sync>;
+
 /// Concrete tool that wraps a callable function.
 ///
 /// This corresponds to Python's `Tool` class which is generic over `P` (params)
@@ -215,6 +220,8 @@ pub struct Tool {
     tool_max_usage_count: Option<u32>,
     /// Current usage count.
     tool_current_usage_count: u32,
+    /// Decides whether a result may be cached (always, when `None`).
+    tool_cache_function: Option<CacheFunction>,
 }
 
 impl fmt::Debug for Tool {
@@ -241,9 +248,29 @@ impl Tool {
             tool_result_as_answer: false,
             tool_max_usage_count: None,
             tool_current_usage_count: 0,
+            tool_cache_function: None,
         }
     }
 
+    /// Create a tool from a function over typed arguments.
+    ///
+    /// The args schema is taken from `A`; calls with arguments that do not
+    /// convert to `A` fail without invoking `func`.
+    pub fn typed<A, R, E, F>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        func: F,
+    ) -> Self
+    where
+        A: ToolArgs,
+        R: Serialize,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+        F: Fn(A) -> Result<R, E> + Send + Sync + 'static,
+    {
+        let func: ToolFn = Arc::new(move |args| into_output(func(A::from_args(args)?)));
+        Self::new(name, description, func).with_args_schema(A::args_schema())
+    }
+
     /// Builder method to set the args schema.
     pub fn with_args_schema(mut self, schema: Value) -> Self {
         self.tool_args_schema = schema;
@@ -262,6 +289,16 @@ impl Tool {
         self
     }
 
+    /// Builder method to set the predicate deciding, from the arguments and
+    /// the result of a call, whether the result may be cached.
+    pub fn with_cache_function(
+        mut self,
+        cache_function: impl Fn(&Value, &Value) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.tool_cache_function = Some(Arc::new(cache_function));
+        self
+    }
+
     /// Builder method to set the maximum usage count.
     pub fn with_max_usage_count(mut self, max_usage_count: Option<u32>) -> Self {
         if let Some(count) = max_usage_count {
@@ -310,6 +347,12 @@ impl BaseTool for Tool {
         self.tool_current_usage_count = 0;
     }
 
+    fn should_cache(&self, args: &Value, result: &Value) -> bool {
+        self.tool_cache_function
+            .as_ref()
+            .is_none_or(|cache_function| cache_function(args, result))
+    }
+
     fn run(
         &mut self,
         args: HashMap<String, Value>,
@@ -0,0 +1,545 @@
+//! Running code in a sandboxed interpreter.
+//!
+//! Corresponds to `crewai_tools.CodeInterpreterTool`. The code is written
+//! to a scratch directory and run in a [`CodeSandbox`]:
+//!
+//! - [`Docker`](CodeSandbox::Docker), the default: a throwaway container
+//!   with the scratch directory mounted, no network and capped memory and
+//!   processes (the agent's `safe` code execution mode);
+//! - [`Firejail`](CodeSandbox::Firejail): a firejail jail with no network
+//!   and the scratch directory as home;
+//! - [`Process`](CodeSandbox::Process): a plain child process.
+//!
+//! Firejail and plain processes run the code on the host, so a tool only
+//! uses them after an explicit opt-in with
+//! [`with_unsafe_sandbox`](CodeInterpreterTool::with_unsafe_sandbox) (the
+//! agent's `unsafe` mode); otherwise every run fails.
+//!
+//! In every sandbox the interpreter gets a cleared environment, no stdin, a
+//! timeout and capped output. Every run is first checked against an
+//! optional [`PolicyEngine`].
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::policy::{PolicyAction, PolicyEffect, PolicyEngine, PolicyRequest, PolicyResource};
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+
+/// Action name checked against the policy engine.
+pub const CODE_INTERPRETER_ACTION: &str = "code_interpreter";
+
+crate::tool_args! {
+    /// Arguments of [`CodeInterpreterTool`].
+    pub struct CodeInterpreterArgs {
+        /// Python3 code used to be interpreted in the Docker container. ALWAYS
+        /// PRINT the final result and the output of the code.
+        pub code: String,
+    }
+}
+
+/// Default image of the Docker sandbox.
+pub const DEFAULT_DOCKER_IMAGE: &str = "python:3.12-slim";
+
+/// Where [`CodeInterpreterTool`] runs code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeSandbox {
+    /// A Docker container of `image`, removed after the run.
+    Docker {
+        /// Image providing the interpreter.
+        image: String,
+    },
+    /// A firejail jail.
+    Firejail,
+    /// A child process of this one.
+    Process,
+}
+
+impl Default for CodeSandbox {
+    fn default() -> Self {
+        Self::docker()
+    }
+}
+
+impl CodeSandbox {
+    /// The Docker sandbox with [`DEFAULT_DOCKER_IMAGE`].
+    pub fn docker() -> Self {
+        Self::Docker {
+            image: DEFAULT_DOCKER_IMAGE.to_string(),
+        }
+    }
+
+    /// Firejail when it is installed, else a plain process.
+    pub fn unsafe_default() -> Self {
+        if program_runs("firejail", "--version") {
+            Self::Firejail
+        } else {
+            Self::Process
+        }
+    }
+
+    /// Whether code runs on the host rather than in a container.
+    pub fn is_unsafe(&self) -> bool {
+        !matches!(self, Self::Docker { .. })
+    }
+}
+
+/// Whether Docker is installed and its daemon reachable.
+pub fn docker_available() -> bool {
+    program_runs("docker", "info")
+}
+
+fn program_runs(program: &str, arg: &str) -> bool {
+    Command::new(program)
+        .arg(arg)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Runs Python code (or another interpreter's) and returns its output.
+#[derive(Debug, Clone)]
+pub struct CodeInterpreterTool {
+    /// Interpreter program, given the script path as its only argument.
+    pub interpreter: String,
+    /// Script file extension.
+    pub extension: String,
+    /// Wall-clock limit of one run.
+    pub timeout: Duration,
+    /// Output beyond this many bytes is cut off.
+    pub max_output_bytes: usize,
+    /// Where the code runs.
+    pub sandbox: CodeSandbox,
+    allow_unsafe_sandbox: bool,
+    policy: Option<Arc<Mutex<PolicyEngine>>>,
+    agent_id: String,
+    agent_roles: Vec<String>,
+    usage_count: u32,
+}
+
+impl Default for CodeInterpreterTool {
+    fn default() -> Self {
+        Self {
+            interpreter: "python3".to_string(),
+            extension: "py".to_string(),
+            timeout: Duration::from_secs(30),
+            max_output_bytes: 64 * 1024,
+            sandbox: CodeSandbox::default(),
+            allow_unsafe_sandbox: false,
+            policy: None,
+            agent_id: String::new(),
+            agent_roles: Vec::new(),
+            usage_count: 0,
+        }
+    }
+}
+
+/// Scratch directory removed on drop.
+struct Scratch(PathBuf);
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+impl CodeInterpreterTool {
+    /// A tool running Python 3 code in the Docker sandbox.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: run code with `interpreter`, writing scripts with `extension`.
+    pub fn with_interpreter(
+        mut self,
+        interpreter: impl Into<String>,
+        extension: impl Into<String>,
+    ) -> Self {
+        self.interpreter = interpreter.into();
+        self.extension = extension.into();
+        self
+    }
+
+    /// Builder: limit each run to `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builder: run code in `sandbox`. Sandboxes running code on the host
+    /// need [`with_unsafe_sandbox`](Self::with_unsafe_sandbox).
+    pub fn with_sandbox(mut self, sandbox: CodeSandbox) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Builder: run code in `sandbox`, even one running it on the host
+    /// (Firejail or a plain process). Only for trusted code.
+    pub fn with_unsafe_sandbox(mut self, sandbox: CodeSandbox) -> Self {
+        self.sandbox = sandbox;
+        self.allow_unsafe_sandbox = true;
+        self
+    }
+
+    /// Builder: check every run against `policy`.
+    pub fn with_policy(mut self, policy: Arc<Mutex<PolicyEngine>>) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Builder: identify the agent using the tool in policy requests.
+    pub fn with_agent(mut self, agent_id: impl Into<String>, roles: Vec<String>) -> Self {
+        self.agent_id = agent_id.into();
+        self.agent_roles = roles;
+        self
+    }
+
+    /// Ask the policy engine whether `code` may run.
+    fn check_policy(&self, code: &str) -> Result<(), ToolRunError> {
+        let Some(policy) = &self.policy else {
+            return Ok(());
+        };
+        let request = PolicyRequest {
+            agent_slot: 0,
+            agent_id: self.agent_id.clone(),
+            agent_roles: self.agent_roles.clone(),
+            action: PolicyAction::ToolCall(CODE_INTERPRETER_ACTION.to_string()),
+            resource: PolicyResource::Custom(self.interpreter.clone()),
+            context: HashMap::from([
+                ("code".to_string(), Value::String(code.to_string())),
+                (
+                    "interpreter".to_string(),
+                    Value::String(self.interpreter.clone()),
+                ),
+            ]),
+        };
+        let decision = policy
+            .lock()
+            .map_err(|_| "policy engine lock poisoned")?
+            .evaluate(&request);
+        if decision.effect == PolicyEffect::Deny && decision.enforced {
+            return Err(format!("Code execution denied by policy: {}", decision.reason).into());
+        }
+        Ok(())
+    }
+
+    /// Run `code` and return its output.
+    pub fn execute(&self, code: &str) -> Result<String, ToolRunError> {
+        if self.sandbox.is_unsafe() && !self.allow_unsafe_sandbox {
+            return Err(format!(
+                "The {:?} sandbox runs code on the host; opt in with `with_unsafe_sandbox`",
+                self.sandbox
+            )
+            .into());
+        }
+        self.check_policy(code)?;
+
+        let name = format!("crewai-code-{}", uuid::Uuid::new_v4());
+        let scratch = Scratch(std::env::temp_dir().join(&name));
+        std::fs::create_dir_all(&scratch.0)?;
+        let script = scratch.0.join(format!("main.{}", self.extension));
+        std::fs::write(&script, code)?;
+
+        let mut command = self.command(&scratch.0, &script, &name);
+        let mut child = command.spawn().map_err(|e| {
+            format!(
+                "Failed to start '{}': {}",
+                command.get_program().to_string_lossy(),
+                e
+            )
+        })?;
+
+        // Drain the pipes on their own threads so a chatty child never blocks.
+        let limit = self.max_output_bytes as u64;
+        let drain = |pipe: Option<Box<dyn Read + Send>>| {
+            std::thread::spawn(move || {
+                let mut buf = Vec::new();
+                if let Some(pipe) = pipe {
+                    let mut pipe = pipe.take(limit + 1);
+                    let _ = pipe.read_to_end(&mut buf);
+                    let _ = std::io::copy(pipe.get_mut(), &mut std::io::sink());
+                }
+                buf
+            })
+        };
+        let stdout = drain(child.stdout.take().map(|p| Box::new(p) as _));
+        let stderr = drain(child.stderr.take().map(|p| Box::new(p) as _));
+
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                if matches!(self.sandbox, CodeSandbox::Docker { .. }) {
+                    // Killing the client leaves the container running.
+                    let _ = Command::new("docker")
+                        .args(["rm", "-f", &name])
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .status();
+                }
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        // On timeout the drain threads are left behind: processes the code
+        // spawned may still hold the pipes open.
+        let Some(status) = status else {
+            return Err(format!(
+                "Code execution timed out after {} seconds",
+                self.timeout.as_secs_f64()
+            )
+            .into());
+        };
+        let stdout = self.output_text(stdout.join().unwrap_or_default());
+        let stderr = self.output_text(stderr.join().unwrap_or_default());
+        if status.success() {
+            Ok(stdout)
+        } else {
+            Ok(format!(
+                "Something went wrong while running the code ({}): \n{}",
+                status, stderr
+            ))
+        }
+    }
+
+    /// The command running `script` in the scratch directory `dir`, in a
+    /// container named `container` for Docker.
+    fn command(&self, dir: &Path, script: &Path, container: &str) -> Command {
+        let mut command = match &self.sandbox {
+            CodeSandbox::Docker { image } => {
+                let mut command = Command::new("docker");
+                command
+                    .args(["run", "--rm", "--name", container])
+                    .args([
+                        "--network",
+                        "none",
+                        "--memory",
+                        "512m",
+                        "--pids-limit",
+                        "64",
+                    ])
+                    .arg("-v")
+                    .arg(format!("{}:/workspace", dir.display()))
+                    .args(["-w", "/workspace", image, &self.interpreter])
+                    .arg(Path::new(script.file_name().unwrap_or_default()));
+                command
+            }
+            CodeSandbox::Firejail => {
+                let mut command = Command::new("firejail");
+                command
+                    .args(["--quiet", "--net=none"])
+                    .arg(format!("--private={}", dir.display()))
+                    .arg(&self.interpreter)
+                    .arg(script);
+                command
+            }
+            CodeSandbox::Process => {
+                let mut command = Command::new(&self.interpreter);
+                command.arg(script);
+                command
+            }
+        };
+        command
+            .current_dir(dir)
+            .env_clear()
+            .env("HOME", dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for var in ["PATH", "DOCKER_HOST"] {
+            if let Some(value) = std::env::var_os(var) {
+                command.env(var, value);
+            }
+        }
+        command
+    }
+
+    fn output_text(&self, mut bytes: Vec<u8>) -> String {
+        let truncated = bytes.len() > self.max_output_bytes;
+        bytes.truncate(self.max_output_bytes);
+        let mut text = String::from_utf8_lossy(&bytes).into_owned();
+        if truncated {
+            text.push_str("\n[output truncated]");
+        }
+        text
+    }
+}
+
+#[async_trait]
+impl BaseTool for CodeInterpreterTool {
+    fn name(&self) -> &str {
+        "Code Interpreter"
+    }
+
+    fn description(&self) -> &str {
+        "Interprets Python3 code strings with a final print statement."
+    }
+
+    fn args_schema(&self) -> Value {
+        CodeInterpreterArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = CodeInterpreterArgs::from_args(args)?;
+        let output = self.execute(&args.code)?;
+        self.usage_count += 1;
+        Ok(Value::String(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{
+        ConditionOperator, EnforcementMode, PolicyCondition, PolicyPrincipal, PolicyRule,
+    };
+
+    fn has(program: &str) -> bool {
+        Command::new(program)
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    }
+
+    fn code(code: &str) -> HashMap<String, Value> {
+        HashMap::from([("code".to_string(), Value::from(code))])
+    }
+
+    #[test]
+    fn test_runs_code_in_scratch_directory() {
+        if !has("python3") {
+            return;
+        }
+        let mut tool = CodeInterpreterTool::new().with_unsafe_sandbox(CodeSandbox::Process);
+        let output = tool
+            .run(code(
+                "import os\nprint(sum(range(10)), os.environ.get('SECRET'), os.listdir('.'))",
+            ))
+            .unwrap();
+        assert_eq!(output, "45 None ['main.py']\n");
+
+        let output = tool.run(code("raise ValueError('boom')")).unwrap();
+        assert!(output.as_str().unwrap().contains("ValueError: boom"));
+        assert_eq!(tool.current_usage_count(), 2);
+    }
+
+    #[test]
+    fn test_policy_denies_code() {
+        let rule = PolicyRule {
+            name: "no-subprocess".to_string(),
+            description: String::new(),
+            effect: PolicyEffect::Deny,
+            principal: PolicyPrincipal::All,
+            action: PolicyAction::ToolCall(CODE_INTERPRETER_ACTION.to_string()),
+            resource: PolicyResource::Any,
+            conditions: vec![PolicyCondition {
+                key: "code".to_string(),
+                operator: ConditionOperator::Contains,
+                value: Value::from("subprocess"),
+            }],
+            priority: 10,
+        };
+        let policy = PolicyEngine::with_rules(vec![rule], EnforcementMode::Strict);
+        // `sh` keeps the allowed run independent of python3 being installed.
+        let mut tool = CodeInterpreterTool::new()
+            .with_interpreter("sh", "sh")
+            .with_unsafe_sandbox(CodeSandbox::Process)
+            .with_policy(Arc::new(Mutex::new(policy)));
+
+        let err = tool.run(code("import subprocess")).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Code execution denied by policy"));
+        assert_eq!(tool.run(code("echo ok")).unwrap(), "ok\n");
+    }
+
+    #[test]
+    fn test_sandbox_commands() {
+        let dir = Path::new("/tmp/crewai-code-1");
+        let script = dir.join("main.py");
+        let args = |tool: &CodeInterpreterTool| -> Vec<String> {
+            let command = tool.command(dir, &script, "crewai-code-1");
+            std::iter::once(command.get_program())
+                .chain(command.get_args())
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let docker = CodeInterpreterTool::new().with_sandbox(CodeSandbox::docker());
+        let docker = args(&docker).join(" ");
+        assert!(docker.starts_with("docker run --rm --name crewai-code-1 --network none"));
+        assert!(docker.ends_with(
+            "-v /tmp/crewai-code-1:/workspace -w /workspace python:3.12-slim python3 main.py"
+        ));
+
+        let firejail = CodeInterpreterTool::new().with_unsafe_sandbox(CodeSandbox::Firejail);
+        assert_eq!(
+            args(&firejail),
+            [
+                "firejail",
+                "--quiet",
+                "--net=none",
+                "--private=/tmp/crewai-code-1",
+                "python3",
+                "/tmp/crewai-code-1/main.py"
+            ]
+        );
+
+        let mut agent = crate::agent::Agent::new("Analyst".into(), "Analyze".into(), "".into());
+        assert!(agent.get_code_execution_tools().is_empty());
+        agent.allow_code_execution = true;
+        assert_eq!(agent.get_code_execution_tools(), [CODE_INTERPRETER_ACTION]);
+        assert_eq!(agent.code_interpreter_tool().sandbox, CodeSandbox::docker());
+        agent.code_execution_mode = crate::agent::core::CodeExecutionMode::Unsafe;
+        let tool = agent.code_interpreter_tool();
+        assert!(tool.sandbox.is_unsafe() && tool.allow_unsafe_sandbox);
+
+        // Running on the host takes an explicit opt-in.
+        assert_eq!(CodeInterpreterTool::new().sandbox, CodeSandbox::docker());
+        let err = CodeInterpreterTool::new()
+            .with_sandbox(CodeSandbox::Process)
+            .execute("print(1)")
+            .unwrap_err();
+        assert!(err.to_string().contains("with_unsafe_sandbox"), "{}", err);
+    }
+
+    #[test]
+    fn test_timeout_kills_process() {
+        let mut tool = CodeInterpreterTool::new()
+            .with_interpreter("sh", "sh")
+            .with_unsafe_sandbox(CodeSandbox::Process)
+            .with_timeout(Duration::from_millis(200));
+        let started = Instant::now();
+        let err = tool.run(code("sleep 5")).unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(tool.current_usage_count(), 0);
+    }
+}
@@ -0,0 +1,175 @@
+//! Listing directories.
+//!
+//! Corresponds to `crewai_tools.DirectoryReadTool`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+
+crate::tool_args! {
+    /// Arguments of [`DirectoryReadTool`].
+    pub struct DirectoryReadArgs {
+        /// Directory to list.
+        pub directory: Option<String>,
+    }
+}
+
+/// Recursively lists the files in a directory.
+#[derive(Debug, Clone)]
+pub struct DirectoryReadTool {
+    /// Directory listed when the call does not name one.
+    pub directory: Option<PathBuf>,
+    /// Maximum number of files listed.
+    pub max_entries: usize,
+    description: String,
+    usage_count: u32,
+}
+
+impl Default for DirectoryReadTool {
+    fn default() -> Self {
+        Self {
+            directory: None,
+            max_entries: 1000,
+            description: "A tool that can be used to recursively list a directory's content."
+                .to_string(),
+            usage_count: 0,
+        }
+    }
+}
+
+impl DirectoryReadTool {
+    /// A tool listing any directory it is given.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tool listing `directory` unless the call names another.
+    pub fn with_directory(directory: impl Into<PathBuf>) -> Self {
+        let directory = directory.into();
+        Self {
+            description: format!(
+                "A tool that can be used to list {}'s content.",
+                directory.display()
+            ),
+            directory: Some(directory),
+            ..Self::default()
+        }
+    }
+
+    fn list(&self, args: DirectoryReadArgs) -> Result<String, ToolRunError> {
+        let directory = args
+            .directory
+            .map(PathBuf::from)
+            .or_else(|| self.directory.clone())
+            .ok_or("No directory provided.")?;
+        if !directory.is_dir() {
+            return Err(format!("Directory not found: {}", directory.display()).into());
+        }
+        let mut files = Vec::new();
+        collect_files(&directory, &directory, &mut files)?;
+        files.sort();
+        let total = files.len();
+        files.truncate(self.max_entries);
+
+        let mut listing = format!(
+            "File paths: \n- {}",
+            files
+                .iter()
+                .map(|f| directory.join(f).display().to_string())
+                .collect::<Vec<_>>()
+                .join("\n- ")
+        );
+        if total > files.len() {
+            listing.push_str(&format!("\n({} more files not shown)", total - files.len()));
+        }
+        Ok(listing)
+    }
+}
+
+/// Collect file paths under `dir`, relative to `root`. Symlinked
+/// directories are not followed.
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl BaseTool for DirectoryReadTool {
+    fn name(&self) -> &str {
+        "List files in directory"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn args_schema(&self) -> Value {
+        DirectoryReadArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let listing = self.list(DirectoryReadArgs::from_args(args)?)?;
+        self.usage_count += 1;
+        Ok(Value::String(listing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_files_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/nested")).unwrap();
+        std::fs::write(dir.path().join("README.md"), "").unwrap();
+        std::fs::write(dir.path().join("src/nested/lib.rs"), "").unwrap();
+
+        let mut tool = DirectoryReadTool::with_directory(dir.path());
+        let listing = tool.run(HashMap::new()).unwrap();
+        let root = dir.path().display();
+        assert_eq!(
+            listing,
+            format!(
+                "File paths: \n- {}/README.md\n- {}/src/nested/lib.rs",
+                root, root
+            )
+        );
+
+        tool.max_entries = 1;
+        let listing = tool.run(HashMap::new()).unwrap();
+        assert!(listing
+            .as_str()
+            .unwrap()
+            .ends_with("(1 more files not shown)"));
+
+        let args = [("directory".to_string(), Value::from("/definitely/not/here"))];
+        assert!(tool.run(args.into_iter().collect()).is_err());
+    }
+}
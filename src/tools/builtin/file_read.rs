@@ -0,0 +1,169 @@
+//! Reading files.
+//!
+//! Corresponds to `crewai_tools.FileReadTool`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+
+crate::tool_args! {
+    /// Arguments of [`FileReadTool`].
+    pub struct FileReadArgs {
+        /// Path of the file to read.
+        pub file_path: Option<String>,
+        /// Line to start reading from (1-based).
+        pub start_line: Option<usize>,
+        /// Number of lines to read; the rest of the file when omitted.
+        pub line_count: Option<usize>,
+    }
+}
+
+/// Reads the content of a file, optionally a range of its lines.
+#[derive(Debug, Clone)]
+pub struct FileReadTool {
+    /// File read when the call does not name one.
+    pub file_path: Option<PathBuf>,
+    description: String,
+    usage_count: u32,
+}
+
+impl Default for FileReadTool {
+    fn default() -> Self {
+        Self {
+            file_path: None,
+            description: "A tool that reads the content of a file. To use this tool, provide a \
+                          'file_path' parameter with the path to the file you want to read. \
+                          Optionally, provide 'start_line' to start reading from a specific \
+                          line and 'line_count' to limit the number of lines read."
+                .to_string(),
+            usage_count: 0,
+        }
+    }
+}
+
+impl FileReadTool {
+    /// A tool reading any file it is given.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tool reading `path` unless the call names another file.
+    pub fn with_file(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            description: format!(
+                "A tool that reads file content. The default file is {}, but you can provide \
+                 a different 'file_path' parameter to read another file.",
+                path.display()
+            ),
+            file_path: Some(path),
+            usage_count: 0,
+        }
+    }
+
+    fn read(&self, args: FileReadArgs) -> Result<String, ToolRunError> {
+        let path = args
+            .file_path
+            .map(PathBuf::from)
+            .or_else(|| self.file_path.clone())
+            .ok_or("No file path provided. Please provide a file path either in the constructor or as an argument.")?;
+        let content = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!("File not found at path: {}", path.display()),
+            std::io::ErrorKind::PermissionDenied => {
+                format!(
+                    "Permission denied when trying to read file: {}",
+                    path.display()
+                )
+            }
+            _ => format!("Failed to read file {}: {}", path.display(), e),
+        })?;
+
+        if args.start_line.is_none() && args.line_count.is_none() {
+            return Ok(content);
+        }
+        let start = args.start_line.unwrap_or(1).max(1) - 1;
+        let lines: Vec<&str> = content.lines().collect();
+        if start >= lines.len() && !lines.is_empty() {
+            return Err(format!(
+                "Start line {} exceeds the number of lines in the file ({})",
+                start + 1,
+                lines.len()
+            )
+            .into());
+        }
+        let end = args
+            .line_count
+            .map_or(lines.len(), |count| (start + count).min(lines.len()));
+        Ok(lines[start..end].join("\n"))
+    }
+}
+
+#[async_trait]
+impl BaseTool for FileReadTool {
+    fn name(&self) -> &str {
+        "Read a file's content"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn args_schema(&self) -> Value {
+        FileReadArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let content = self.read(FileReadArgs::from_args(args)?)?;
+        self.usage_count += 1;
+        Ok(Value::String(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_whole_file_and_line_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "one\ntwo\nthree\nfour\n").unwrap();
+
+        let mut tool = FileReadTool::with_file(&path);
+        assert!(tool.description().contains("notes.txt"));
+        assert_eq!(tool.run(HashMap::new()).unwrap(), "one\ntwo\nthree\nfour\n");
+
+        let args = serde_json::json!({"start_line": 2, "line_count": 2});
+        let args = serde_json::from_value(args).unwrap();
+        assert_eq!(tool.run(args).unwrap(), "two\nthree");
+
+        let mut tool = FileReadTool::new();
+        let missing = dir.path().join("missing.txt");
+        let args = [(
+            "file_path".to_string(),
+            Value::from(missing.to_str().unwrap()),
+        )];
+        let err = tool.run(args.into_iter().collect()).unwrap_err();
+        assert!(err.to_string().starts_with("File not found at path"));
+        assert!(tool.run(HashMap::new()).is_err());
+        assert_eq!(tool.current_usage_count(), 0);
+    }
+}
@@ -0,0 +1,173 @@
+//! Writing files.
+//!
+//! Corresponds to `crewai_tools.FileWriterTool`.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+
+crate::tool_args! {
+    /// Arguments of [`FileWriterTool`].
+    pub struct FileWriterArgs {
+        /// Name of the file to write.
+        pub filename: String,
+        /// Directory to write the file in; created if missing.
+        pub directory: Option<String>,
+        /// Content to write.
+        pub content: String,
+        /// Whether to replace an existing file.
+        pub overwrite: Option<bool>,
+    }
+}
+
+/// Writes content to a file, creating its directory when needed.
+#[derive(Debug, Clone, Default)]
+pub struct FileWriterTool {
+    /// When set, files may only be written inside this directory and
+    /// relative directories are resolved against it.
+    pub base_dir: Option<PathBuf>,
+    usage_count: u32,
+}
+
+impl FileWriterTool {
+    /// A tool writing anywhere the process may.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tool confined to `base_dir`.
+    pub fn within(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: Some(base_dir.into()),
+            usage_count: 0,
+        }
+    }
+
+    fn target(&self, args: &FileWriterArgs) -> Result<PathBuf, ToolRunError> {
+        let relative = Path::new(args.directory.as_deref().unwrap_or("./")).join(&args.filename);
+        let Some(base) = &self.base_dir else {
+            return Ok(relative);
+        };
+        let escapes = relative.components().any(|c| {
+            matches!(
+                c,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_)
+            )
+        });
+        if escapes {
+            return Err(format!(
+                "Refusing to write {} outside {}",
+                relative.display(),
+                base.display()
+            )
+            .into());
+        }
+        Ok(base.join(relative))
+    }
+
+    fn write(&self, args: FileWriterArgs) -> Result<String, ToolRunError> {
+        let path = self.target(&args)?;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        if path.exists() && !args.overwrite.unwrap_or(false) {
+            return Err(format!(
+                "File {} already exists and overwrite option was not passed.",
+                path.display()
+            )
+            .into());
+        }
+        std::fs::write(&path, &args.content)
+            .map_err(|e| format!("An error occurred while writing to the file: {}", e))?;
+        Ok(format!(
+            "Content successfully written to {}",
+            path.display()
+        ))
+    }
+}
+
+#[async_trait]
+impl BaseTool for FileWriterTool {
+    fn name(&self) -> &str {
+        "File Writer Tool"
+    }
+
+    fn description(&self) -> &str {
+        "A tool to write content to a specified file. Accepts filename, content, and optionally \
+         a directory path and overwrite flag as input."
+    }
+
+    fn args_schema(&self) -> Value {
+        FileWriterArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let message = self.write(FileWriterArgs::from_args(args)?)?;
+        self.usage_count += 1;
+        Ok(Value::String(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_writes_files_within_base_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tool = FileWriterTool::within(dir.path());
+
+        let output = tool
+            .run(args(serde_json::json!({
+                "filename": "report.md",
+                "directory": "out/drafts",
+                "content": "# Draft",
+            })))
+            .unwrap();
+        let path = dir.path().join("out/drafts/report.md");
+        assert!(output
+            .as_str()
+            .unwrap()
+            .starts_with("Content successfully written"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Draft");
+
+        let again = serde_json::json!({
+            "filename": "report.md",
+            "directory": "out/drafts",
+            "content": "# Final",
+        });
+        let err = tool.run(args(again.clone())).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        let mut again = again;
+        again["overwrite"] = Value::Bool(true);
+        tool.run(args(again)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "# Final");
+
+        let escape =
+            serde_json::json!({"filename": "x", "directory": "../elsewhere", "content": ""});
+        assert!(tool.run(args(escape)).is_err());
+        assert_eq!(tool.current_usage_count(), 2);
+    }
+}
@@ -0,0 +1,52 @@
+//! Built-in tools.
+//!
+//! Ports of the core `crewai-tools` package: reading and writing files,
+//! listing directories, web search through Serper, scraping websites,
+//! running code in a sandboxed interpreter and querying a
+//! [RAG pipeline](crate::rag::pipeline), plus tools normalizing dates,
+//! numbers and units. Each implements
+//! [`BaseTool`](super::BaseTool) and can be registered in a
+//! [`ToolRegistry`](super::ToolRegistry) or given to an agent directly.
+
+pub mod code_interpreter;
+pub mod directory_read;
+pub mod file_read;
+pub mod file_write;
+pub mod normalize;
+pub mod rag;
+pub mod scrape_website;
+pub mod serper_dev;
+
+pub use code_interpreter::{CodeInterpreterTool, CodeSandbox};
+pub use directory_read::DirectoryReadTool;
+pub use file_read::FileReadTool;
+pub use file_write::FileWriterTool;
+pub use normalize::{DateNormalizeTool, NumberNormalizeTool, UnitConvertTool};
+pub use rag::RagTool;
+pub use scrape_website::ScrapeWebsiteTool;
+pub use serper_dev::SerperDevTool;
+
+/// Error type returned by tool runs.
+pub(crate) type ToolRunError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Run an HTTP future from a synchronous `run`.
+///
+/// The future gets its own thread and runtime, so this also works when
+/// the caller is already on an async runtime.
+pub(crate) fn block_on<F>(future: F) -> Result<F::Output, ToolRunError>
+where
+    F: std::future::Future + Send,
+    F::Output: Send,
+{
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+                Ok(runtime.block_on(future))
+            })
+            .join()
+            .unwrap_or_else(|e| std::panic::resume_unwind(e))
+    })
+}
@@ -0,0 +1,254 @@
+//! Normalizing dates, numbers and units.
+//!
+//! Tool wrappers around [`crate::utilities::normalize`], for agents that
+//! should report values in a canonical form themselves rather than leave it
+//! to a task's post-processor.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+use crate::utilities::normalize::{self, Locale};
+
+crate::tool_args! {
+    /// Arguments of [`DateNormalizeTool`].
+    pub struct DateNormalizeArgs {
+        /// Date or date-time as written, e.g. "March 5th, 2024".
+        pub text: String,
+        /// Locale the date is written in, e.g. "de-DE"; defaults to the tool's.
+        pub locale: Option<String>,
+    }
+}
+
+crate::tool_args! {
+    /// Arguments of [`NumberNormalizeTool`].
+    pub struct NumberNormalizeArgs {
+        /// Number as written, e.g. "1.234,5 €" or "2.5 million".
+        pub text: String,
+        /// Locale the number is written in, e.g. "de-DE"; defaults to the tool's.
+        pub locale: Option<String>,
+    }
+}
+
+crate::tool_args! {
+    /// Arguments of [`UnitConvertTool`].
+    pub struct UnitConvertArgs {
+        /// Quantity with its unit, e.g. "5 mi" or "12,5 kg".
+        pub quantity: String,
+        /// Unit to convert to, e.g. "km".
+        pub to: String,
+        /// Locale the quantity is written in, e.g. "de-DE"; defaults to the tool's.
+        pub locale: Option<String>,
+    }
+}
+
+fn locale(requested: Option<String>, default: Locale) -> Locale {
+    requested.as_deref().map_or(default, Locale::parse)
+}
+
+/// Normalizes a written date to an RFC 3339 timestamp in UTC.
+#[derive(Debug, Clone, Default)]
+pub struct DateNormalizeTool {
+    /// Locale used when the call does not name one.
+    pub locale: Locale,
+    usage_count: u32,
+}
+
+impl DateNormalizeTool {
+    /// A tool reading dates in `locale`.
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            usage_count: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTool for DateNormalizeTool {
+    fn name(&self) -> &str {
+        "Normalize date"
+    }
+
+    fn description(&self) -> &str {
+        "Converts a date or date-time written in any common format to an RFC 3339 timestamp in UTC."
+    }
+
+    fn args_schema(&self) -> Value {
+        DateNormalizeArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = DateNormalizeArgs::from_args(args)?;
+        let date = normalize::normalize_date(&args.text, &locale(args.locale, self.locale))?;
+        self.usage_count += 1;
+        Ok(Value::String(date))
+    }
+}
+
+/// Parses a written number, honouring the locale's decimal separator.
+#[derive(Debug, Clone, Default)]
+pub struct NumberNormalizeTool {
+    /// Locale used when the call does not name one.
+    pub locale: Locale,
+    usage_count: u32,
+}
+
+impl NumberNormalizeTool {
+    /// A tool reading numbers in `locale`.
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            usage_count: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTool for NumberNormalizeTool {
+    fn name(&self) -> &str {
+        "Normalize number"
+    }
+
+    fn description(&self) -> &str {
+        "Converts a number written with grouping, currency symbols, percentages or magnitude words to a plain number."
+    }
+
+    fn args_schema(&self) -> Value {
+        NumberNormalizeArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = NumberNormalizeArgs::from_args(args)?;
+        let number = normalize::parse_number(&args.text, &locale(args.locale, self.locale))?;
+        self.usage_count += 1;
+        Ok(Value::from(number))
+    }
+}
+
+/// Converts a quantity to another unit of the same dimension.
+#[derive(Debug, Clone, Default)]
+pub struct UnitConvertTool {
+    /// Locale used when the call does not name one.
+    pub locale: Locale,
+    usage_count: u32,
+}
+
+impl UnitConvertTool {
+    /// A tool reading quantities in `locale`.
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            usage_count: 0,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTool for UnitConvertTool {
+    fn name(&self) -> &str {
+        "Convert units"
+    }
+
+    fn description(&self) -> &str {
+        "Converts a quantity such as '5 mi' to another unit of length, mass, time, volume, area, speed, data size or temperature."
+    }
+
+    fn args_schema(&self) -> Value {
+        UnitConvertArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = UnitConvertArgs::from_args(args)?;
+        let locale = locale(args.locale, self.locale);
+        let value = normalize::normalize_quantity(&args.quantity, &args.to, &locale)?;
+        self.usage_count += 1;
+        Ok(Value::from(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> HashMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), Value::from(*v)))
+            .collect()
+    }
+
+    #[test]
+    fn test_tools_normalize_with_call_or_default_locale() {
+        let mut dates = DateNormalizeTool::new(Locale::parse("en-GB"));
+        assert_eq!(
+            dates.run(args(&[("text", "05/03/2024")])).unwrap(),
+            "2024-03-05T00:00:00Z"
+        );
+        assert_eq!(
+            dates
+                .run(args(&[("text", "05/03/2024"), ("locale", "en-US")]))
+                .unwrap(),
+            "2024-05-03T00:00:00Z"
+        );
+
+        let mut numbers = NumberNormalizeTool::default();
+        assert_eq!(
+            numbers
+                .run(args(&[("text", "1.234,5 €"), ("locale", "de")]))
+                .unwrap(),
+            1234.5
+        );
+        assert!(numbers.run(args(&[("text", "lots")])).is_err());
+        assert_eq!(numbers.current_usage_count(), 1);
+
+        let mut units = UnitConvertTool::default();
+        let km = units
+            .run(args(&[("quantity", "5 mi"), ("to", "km")]))
+            .unwrap();
+        assert!((km.as_f64().unwrap() - 8.04672).abs() < 1e-9);
+        assert!(units
+            .run(args(&[("quantity", "5 kg"), ("to", "km")]))
+            .is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Querying a RAG pipeline.
+//!
+//! Corresponds to `crewai_tools.RagTool`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::ToolRunError;
+use crate::rag::pipeline::RagPipeline;
+use crate::tools::base_tool::BaseTool;
+use crate::tools::typed_tool::ToolArgs;
+
+crate::tool_args! {
+    /// Arguments of [`RagTool`].
+    pub struct RagArgs {
+        /// Question to look up in the knowledge base.
+        pub query: String,
+    }
+}
+
+/// Answers questions from the documents ingested by a [`RagPipeline`].
+///
+/// Returns the relevant chunks, each followed by its `source` when the
+/// loader recorded one.
+#[derive(Clone)]
+pub struct RagTool {
+    /// Pipeline queried.
+    pub pipeline: Arc<RagPipeline>,
+    description: String,
+    usage_count: u32,
+}
+
+impl RagTool {
+    /// A tool querying `pipeline`, whose documents should already be
+    /// ingested.
+    pub fn new(pipeline: Arc<RagPipeline>) -> Self {
+        Self {
+            pipeline,
+            description: "A knowledge base that can be used to answer questions.".to_string(),
+            usage_count: 0,
+        }
+    }
+
+    /// Builder: describe what the knowledge base holds, for the agent to
+    /// know when to query it.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    fn query(&self, args: RagArgs) -> Result<String, ToolRunError> {
+        let results = self
+            .pipeline
+            .query(&args.query)
+            .map_err(|e| format!("Failed to query the knowledge base: {}", e))?;
+        if results.is_empty() {
+            return Ok("No relevant content found.".to_string());
+        }
+        let chunks: Vec<String> = results
+            .iter()
+            .map(|result| {
+                let source = result.metadata.get("source").and_then(Value::as_str);
+                match source {
+                    Some(source) => format!("{}\n[Source: {}]", result.content, source),
+                    None => result.content.clone(),
+                }
+            })
+            .collect();
+        Ok(format!("Relevant Content:\n{}", chunks.join("\n\n")))
+    }
+}
+
+impl fmt::Debug for RagTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RagTool")
+            .field("description", &self.description)
+            .field("usage_count", &self.usage_count)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl BaseTool for RagTool {
+    fn name(&self) -> &str {
+        "Knowledge base"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn args_schema(&self) -> Value {
+        RagArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let content = self.query(RagArgs::from_args(args)?)?;
+        self.usage_count += 1;
+        Ok(Value::String(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rag::core::{EmbeddingFunctionTrait, Embeddings};
+    use crate::rag::pipeline::FileLoader;
+
+    /// Embeds text by whether it mentions invoices.
+    struct InvoiceEmbedder;
+
+    impl EmbeddingFunctionTrait for InvoiceEmbedder {
+        fn call(&self, input: &[String]) -> Result<Embeddings, anyhow::Error> {
+            Ok(input
+                .iter()
+                .map(|text| vec![text.contains("invoice") as u8 as f32, 1.0])
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_agent_queries_the_pipeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = dir.path().join("billing.md");
+        std::fs::write(&policy, "Every invoice is due in 30 days.").unwrap();
+        let pipeline = RagPipeline::builder()
+            .loader(FileLoader::new([&policy]))
+            .embedder(Arc::new(InvoiceEmbedder))
+            .score_threshold(0.9)
+            .build()
+            .unwrap();
+        pipeline.ingest().unwrap();
+
+        let mut tool = RagTool::new(Arc::new(pipeline)).with_description("Billing policies");
+        let args = |query: &str| HashMap::from([("query".to_string(), Value::from(query))]);
+        let answer = tool.run(args("When is an invoice due?")).unwrap();
+        assert_eq!(
+            answer,
+            format!(
+                "Relevant Content:\nEvery invoice is due in 30 days.\n[Source: {}]",
+                policy.display()
+            )
+        );
+        assert_eq!(
+            tool.run(args("Who is the CEO?")).unwrap(),
+            "No relevant content found."
+        );
+        assert_eq!(tool.current_usage_count(), 2);
+        assert!(tool.run(HashMap::new()).is_err());
+    }
+}
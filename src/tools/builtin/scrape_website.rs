@@ -0,0 +1,175 @@
+//! Reading web pages.
+//!
+//! Corresponds to `crewai_tools.ScrapeWebsiteTool`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{block_on, ToolRunError};
+use crate::tools::base_tool::BaseTool;
+use crate::tools::registry::transformers::html_to_markdown;
+use crate::tools::typed_tool::ToolArgs;
+
+crate::tool_args! {
+    /// Arguments of [`ScrapeWebsiteTool`].
+    pub struct ScrapeWebsiteArgs {
+        /// Mandatory website url to read the file.
+        pub website_url: Option<String>,
+    }
+}
+
+/// Fetches a web page and returns its text as Markdown.
+#[derive(Debug, Clone)]
+pub struct ScrapeWebsiteTool {
+    /// Page read when the call does not name one.
+    pub website_url: Option<String>,
+    /// Request headers.
+    pub headers: HashMap<String, String>,
+    /// Request timeout.
+    pub timeout: Duration,
+    description: String,
+    usage_count: u32,
+}
+
+impl Default for ScrapeWebsiteTool {
+    fn default() -> Self {
+        let headers = [
+            (
+                "User-Agent",
+                "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/96.0.4664.110 Safari/537.36",
+            ),
+            (
+                "Accept",
+                "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8",
+            ),
+            ("Accept-Language", "en-US,en;q=0.9"),
+        ];
+        Self {
+            website_url: None,
+            headers: headers
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            timeout: Duration::from_secs(15),
+            description: "A tool that can be used to read a website content.".to_string(),
+            usage_count: 0,
+        }
+    }
+}
+
+impl ScrapeWebsiteTool {
+    /// A tool reading any page it is given.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tool reading `url` unless the call names another page.
+    pub fn with_url(url: impl Into<String>) -> Self {
+        let url = url.into();
+        Self {
+            description: format!("A tool that can be used to read {}'s content.", url),
+            website_url: Some(url),
+            ..Self::default()
+        }
+    }
+
+    /// Fetch a page and convert it to Markdown.
+    pub async fn scrape(&self, args: ScrapeWebsiteArgs) -> Result<String, ToolRunError> {
+        let url = args
+            .website_url
+            .or_else(|| self.website_url.clone())
+            .ok_or("No website URL provided.")?;
+        let client = reqwest::Client::builder().timeout(self.timeout).build()?;
+        let mut request = client.get(&url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Failed to fetch {} ({})", url, status).into());
+        }
+        Ok(html_to_markdown(&response.text().await?))
+    }
+}
+
+#[async_trait]
+impl BaseTool for ScrapeWebsiteTool {
+    fn name(&self) -> &str {
+        "Read website content"
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn args_schema(&self) -> Value {
+        ScrapeWebsiteArgs::args_schema()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = ScrapeWebsiteArgs::from_args(args)?;
+        let text = block_on(self.scrape(args))??;
+        self.usage_count += 1;
+        Ok(Value::String(text))
+    }
+
+    async fn arun(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let text = self.scrape(ScrapeWebsiteArgs::from_args(args)?).await?;
+        self.usage_count += 1;
+        Ok(Value::String(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::Html;
+    use axum::routing::get;
+    use axum::Router;
+
+    #[test]
+    fn test_scrapes_page_as_markdown() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let base_url = runtime.block_on(async {
+            let router = Router::new().route(
+                "/page",
+                get(|| async {
+                    Html("<html><head><title>x</title></head><body><h1>Crews</h1><p>Agents &amp; tasks</p></body></html>")
+                }),
+            );
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+            format!("http://{}", addr)
+        });
+
+        let mut tool = ScrapeWebsiteTool::with_url(format!("{}/page", base_url));
+        assert_eq!(
+            tool.run(HashMap::new()).unwrap(),
+            "# Crews\n\nAgents & tasks"
+        );
+
+        let args = [(
+            "website_url".to_string(),
+            Value::from(format!("{}/missing", base_url)),
+        )];
+        let err = tool.run(args.into_iter().collect()).unwrap_err();
+        assert!(err.to_string().contains("404"));
+    }
+}
@@ -0,0 +1,291 @@
+//! Web search through the Serper API.
+//!
+//! Corresponds to `crewai_tools.SerperDevTool`.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+use super::{block_on, ToolRunError};
+use crate::tools::base_tool::{BaseTool, EnvVar};
+use crate::tools::typed_tool::ToolArgs;
+
+/// Environment variable holding the Serper API key.
+pub const SERPER_API_KEY_ENV: &str = "SERPER_API_KEY";
+
+crate::tool_args! {
+    /// Arguments of [`SerperDevTool`].
+    pub struct SerperDevArgs {
+        /// Mandatory search query you want to use to search the internet.
+        pub search_query: String,
+        /// `search` (default) or `news`.
+        pub search_type: Option<String>,
+    }
+}
+
+/// Searches the internet with [Serper](https://serper.dev).
+#[derive(Debug, Clone)]
+pub struct SerperDevTool {
+    /// API base URL.
+    pub base_url: String,
+    /// Number of results requested.
+    pub n_results: u32,
+    /// Default search type: `search` or `news`.
+    pub search_type: String,
+    /// Country code of the search (`gl`).
+    pub country: Option<String>,
+    /// Location of the search.
+    pub location: Option<String>,
+    /// Interface language (`hl`).
+    pub locale: Option<String>,
+    api_key: Option<String>,
+    env_vars: Vec<EnvVar>,
+    usage_count: u32,
+}
+
+impl Default for SerperDevTool {
+    fn default() -> Self {
+        Self {
+            base_url: "https://google.serper.dev".to_string(),
+            n_results: 10,
+            search_type: "search".to_string(),
+            country: None,
+            location: None,
+            locale: None,
+            api_key: None,
+            env_vars: vec![EnvVar::new(
+                SERPER_API_KEY_ENV,
+                "API key for Serper search service",
+            )],
+            usage_count: 0,
+        }
+    }
+}
+
+impl SerperDevTool {
+    /// A tool using the key from `SERPER_API_KEY`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder: use `api_key` instead of the environment.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Builder: request `n_results` results.
+    pub fn with_n_results(mut self, n_results: u32) -> Self {
+        self.n_results = n_results;
+        self
+    }
+
+    /// Run a search and return the processed results.
+    pub async fn search(&self, args: SerperDevArgs) -> Result<Value, ToolRunError> {
+        let search_type = args.search_type.as_deref().unwrap_or(&self.search_type);
+        if !matches!(search_type, "search" | "news") {
+            return Err(format!(
+                "Invalid search type: {}. Must be 'search' or 'news'.",
+                search_type
+            )
+            .into());
+        }
+        let api_key = self
+            .api_key
+            .clone()
+            .or_else(|| std::env::var(SERPER_API_KEY_ENV).ok())
+            .ok_or_else(|| format!("{} is not set", SERPER_API_KEY_ENV))?;
+
+        let mut payload = serde_json::json!({"q": args.search_query, "num": self.n_results});
+        for (key, value) in [
+            ("gl", &self.country),
+            ("location", &self.location),
+            ("hl", &self.locale),
+        ] {
+            if let Some(value) = value {
+                payload[key] = Value::String(value.clone());
+            }
+        }
+
+        let url = format!("{}/{}", self.base_url.trim_end_matches('/'), search_type);
+        let response = reqwest::Client::new()
+            .post(&url)
+            .header("X-API-KEY", api_key)
+            .json(&payload)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Serper request failed ({}): {}",
+                status,
+                response.text().await.unwrap_or_default()
+            )
+            .into());
+        }
+        let body: Value = response.json().await?;
+        Ok(self.process(search_type, &body))
+    }
+
+    /// Keep the useful parts of a Serper response.
+    fn process(&self, search_type: &str, body: &Value) -> Value {
+        let limit = self.n_results as usize;
+        let pick = |item: &Value, keys: &[&str]| -> Value {
+            Value::Object(
+                keys.iter()
+                    .filter_map(|k| item.get(*k).map(|v| (k.to_string(), v.clone())))
+                    .collect(),
+            )
+        };
+        let list = |key: &str, keys: &[&str]| -> Vec<Value> {
+            body.get(key)
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .take(limit)
+                        .map(|item| pick(item, keys))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let mut results = serde_json::Map::new();
+        if search_type == "news" {
+            results.insert(
+                "news".into(),
+                list(
+                    "news",
+                    &["title", "link", "snippet", "date", "source", "imageUrl"],
+                )
+                .into(),
+            );
+        } else {
+            for key in ["knowledgeGraph", "answerBox"] {
+                if let Some(value) = body.get(key) {
+                    results.insert(key.into(), value.clone());
+                }
+            }
+            results.insert(
+                "organic".into(),
+                list("organic", &["title", "link", "snippet", "position"]).into(),
+            );
+            results.insert(
+                "peopleAlsoAsk".into(),
+                list("peopleAlsoAsk", &["question", "snippet", "title", "link"]).into(),
+            );
+            results.insert(
+                "relatedSearches".into(),
+                list("relatedSearches", &["query"]).into(),
+            );
+        }
+        if let Some(credits) = body.get("credits") {
+            results.insert("credits".into(), credits.clone());
+        }
+        Value::Object(results)
+    }
+}
+
+#[async_trait]
+impl BaseTool for SerperDevTool {
+    fn name(&self) -> &str {
+        "Search the internet with Serper"
+    }
+
+    fn description(&self) -> &str {
+        "A tool that can be used to search the internet with a search_query. Supports \
+         different search types: 'search' (default), 'news'"
+    }
+
+    fn args_schema(&self) -> Value {
+        SerperDevArgs::args_schema()
+    }
+
+    fn env_vars(&self) -> &[EnvVar] {
+        &self.env_vars
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.usage_count = 0;
+    }
+
+    fn run(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let args = SerperDevArgs::from_args(args)?;
+        let results = block_on(self.search(args))??;
+        self.usage_count += 1;
+        Ok(results)
+    }
+
+    async fn arun(&mut self, args: HashMap<String, Value>) -> Result<Value, ToolRunError> {
+        let results = self.search(SerperDevArgs::from_args(args)?).await?;
+        self.usage_count += 1;
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use axum::{Json, Router};
+
+    async fn serve(router: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_search_posts_query_and_processes_results() {
+        let router = Router::new().route(
+            "/search",
+            post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                assert_eq!(headers["x-api-key"], "secret");
+                assert_eq!(body["gl"], "de");
+                Json(serde_json::json!({
+                    "organic": [
+                        {"title": "Rust", "link": "https://rust-lang.org", "snippet": "A language", "position": 1, "sitelinks": []},
+                        {"title": "Crates", "link": "https://crates.io", "snippet": "Packages", "position": 2},
+                    ],
+                    "answerBox": {"answer": format!("{} results", body["num"])},
+                    "credits": 1,
+                }))
+            }),
+        );
+        let base_url = serve(router).await;
+
+        let mut tool = SerperDevTool::new()
+            .with_api_key("secret")
+            .with_n_results(1);
+        tool.base_url = base_url;
+        tool.country = Some("de".to_string());
+
+        let args = [("search_query".to_string(), Value::from("rust"))];
+        let results = tool.arun(args.into_iter().collect()).await.unwrap();
+        assert_eq!(
+            results["organic"],
+            serde_json::json!([{"title": "Rust", "link": "https://rust-lang.org", "snippet": "A language", "position": 1}])
+        );
+        assert_eq!(results["answerBox"]["answer"], "1 results");
+
+        // The synchronous path works from inside a runtime too.
+        let args = [
+            ("search_query".to_string(), Value::from("rust")),
+            ("search_type".to_string(), Value::from("images")),
+        ];
+        let err = tool.run(args.into_iter().collect()).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid search type"));
+        assert_eq!(tool.current_usage_count(), 1);
+    }
+}
@@ -7,14 +7,18 @@
 //! `MCPToolWrapper` which connects on-demand, this tool uses a shared
 //! MCP client instance that maintains a persistent connection.
 
-use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
+use async_trait::async_trait;
 use serde_json::Value;
 use tokio::sync::Mutex;
 
+use crate::mcp::client::MCPClient;
+use crate::mcp::toolset;
+use crate::tools::base_tool::BaseTool;
+
 /// Native MCP tool that reuses client sessions.
 ///
 /// Used when agents connect to MCP servers using structured configurations.
@@ -28,13 +32,14 @@ pub struct MCPNativeTool {
     pub description: String,
     /// JSON Schema for the tool's arguments.
     pub args_schema: Value,
-    /// Shared MCP client instance (opaque, type-erased).
-    /// In a full implementation, this would be a concrete MCP client type.
-    mcp_client: Arc<Mutex<Box<dyn Any + Send + Sync>>>,
+    /// Shared MCP client instance.
+    mcp_client: Arc<Mutex<MCPClient>>,
     /// Original tool name on the MCP server (without prefix).
     pub original_tool_name: String,
     /// Name of the MCP server.
     pub server_name: String,
+    /// Number of times the tool has been run.
+    current_usage_count: u32,
 }
 
 impl fmt::Debug for MCPNativeTool {
@@ -53,12 +58,13 @@ impl MCPNativeTool {
     ///
     /// # Arguments
     ///
-    /// * `mcp_client` - MCPClient instance with active session (type-erased).
+    /// * `mcp_client` - Shared MCPClient; connected on first use if needed.
     /// * `tool_name` - Original name of the tool on the MCP server.
-    /// * `tool_schema` - Schema information for the tool.
+    /// * `tool_schema` - Schema information for the tool (`description`,
+    ///   `args_schema`).
     /// * `server_name` - Name of the MCP server for prefixing.
     pub fn new(
-        mcp_client: Box<dyn Any + Send + Sync>,
+        mcp_client: Arc<Mutex<MCPClient>>,
         tool_name: impl Into<String>,
         tool_schema: &Value,
         server_name: impl Into<String>,
@@ -70,6 +76,7 @@ impl MCPNativeTool {
         let description = tool_schema
             .get("description")
             .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
             .map(|s| s.to_string())
             .unwrap_or_else(|| format!("Tool {} from {}", tool_name, server_name));
 
@@ -82,77 +89,92 @@ impl MCPNativeTool {
             name: prefixed_name,
             description,
             args_schema,
-            mcp_client: Arc::new(Mutex::new(mcp_client)),
+            mcp_client,
             original_tool_name: tool_name,
             server_name,
+            current_usage_count: 0,
         }
     }
 
     /// Execute tool using the MCP client session (synchronous wrapper).
     ///
-    /// Handles the complexity of running async code from a sync context,
-    /// similar to the Python implementation which uses `asyncio.run()`.
+    /// Blocks until the call completes on the MCP runtime, like the Python
+    /// implementation's `asyncio.run()`.
     pub fn run(
         &self,
-        _args: HashMap<String, Value>,
+        args: HashMap<String, Value>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // In a full implementation, this would:
-        // 1. Lock the MCP client
-        // 2. Reconnect if needed (since each sync call may be on a different tokio runtime)
-        // 3. Call the tool
-        // 4. Disconnect after the call
-        // 5. Extract and return the result
-        Err(format!(
-            "MCP native tool execution for '{}' not yet implemented in Rust port. Server: {}",
-            self.original_tool_name, self.server_name,
-        )
-        .into())
+        toolset::block_on(Self::call(
+            self.mcp_client.clone(),
+            self.original_tool_name.clone(),
+            args,
+        ))
     }
 
     /// Execute tool asynchronously using the MCP client session.
     ///
-    /// Reconnects on-demand because async runtimes may differ between calls.
-    /// Always disconnects after the call to ensure clean context manager lifecycle.
+    /// The session stays open between calls and is connected on first use.
     pub async fn run_async(
         &self,
-        _args: HashMap<String, Value>,
+        args: HashMap<String, Value>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // TODO: Implement actual MCP client connection and tool call.
-        // The implementation should:
-        // 1. Lock the MCP client
-        // 2. Disconnect if already connected (new event loop context)
-        // 3. Connect
-        // 4. Call the tool
-        // 5. Always disconnect in the finally block
-        // 6. Handle reconnection on connection errors
-        Err(format!(
-            "MCP native tool async execution for '{}' not yet implemented in Rust port. Server: {}",
-            self.original_tool_name, self.server_name,
-        )
-        .into())
+        toolset::run(Self::call(
+            self.mcp_client.clone(),
+            self.original_tool_name.clone(),
+            args,
+        ))
+        .await
     }
 
-    /// Extract result content from an MCP tool call response.
-    ///
-    /// Handles various result formats including string results,
-    /// content lists, and text content items.
-    fn extract_result_content(result: &Value) -> String {
-        if let Some(s) = result.as_str() {
-            return s.to_string();
-        }
+    async fn call(
+        client: Arc<Mutex<MCPClient>>,
+        tool_name: String,
+        args: HashMap<String, Value>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut client = client.lock().await;
+        Ok(client.call_tool(&tool_name, Some(args)).await?)
+    }
+}
 
-        if let Some(content) = result.get("content") {
-            if let Some(arr) = content.as_array() {
-                if let Some(first) = arr.first() {
-                    if let Some(text) = first.get("text").and_then(|t| t.as_str()) {
-                        return text.to_string();
-                    }
-                    return first.to_string();
-                }
-            }
-            return content.to_string();
-        }
+#[async_trait]
+impl BaseTool for MCPNativeTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn args_schema(&self) -> Value {
+        self.args_schema.clone()
+    }
+
+    fn current_usage_count(&self) -> u32 {
+        self.current_usage_count
+    }
+
+    fn increment_usage_count(&mut self) {
+        self.current_usage_count += 1;
+    }
+
+    fn reset_usage_count(&mut self) {
+        self.current_usage_count = 0;
+    }
+
+    fn run(
+        &mut self,
+        args: HashMap<String, Value>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.increment_usage_count();
+        MCPNativeTool::run(self, args).map(Value::String)
+    }
 
-        result.to_string()
+    async fn arun(
+        &mut self,
+        args: HashMap<String, Value>,
+    ) -> Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+        self.increment_usage_count();
+        self.run_async(args).await.map(Value::String)
     }
 }
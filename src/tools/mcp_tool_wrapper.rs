@@ -12,6 +12,10 @@ use std::time::Duration;
 
 use serde_json::Value;
 
+use crate::mcp::client::MCPClient;
+use crate::mcp::toolset;
+use crate::mcp::transports::{BaseTransport, HTTPTransport, SSETransport};
+
 /// Connection timeout in seconds for MCP server.
 pub const MCP_CONNECTION_TIMEOUT: u64 = 15;
 
@@ -38,7 +42,8 @@ pub struct MCPToolWrapper {
     pub description: String,
     /// JSON Schema for the tool's arguments.
     pub args_schema: Value,
-    /// Parameters for connecting to the MCP server (e.g., URL).
+    /// Parameters for connecting to the MCP server: `url`, and optionally
+    /// `transport` (`"sse"` or `"http"`, default streamable HTTP).
     pub mcp_server_params: HashMap<String, String>,
     /// Original tool name on the MCP server (without prefix).
     pub original_tool_name: String,
@@ -103,25 +108,10 @@ impl MCPToolWrapper {
     /// Uses retry logic with exponential backoff for transient failures.
     pub fn run(
         &self,
-        _args: HashMap<String, Value>,
+        args: HashMap<String, Value>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // In a full implementation, this would:
-        // 1. Connect to the MCP server via the configured transport
-        // 2. Initialize the session
-        // 3. Call the tool with the given arguments
-        // 4. Extract and return the result
-        //
-        // For now, return an error indicating the MCP client is not yet implemented.
-        Err(format!(
-            "MCP tool execution for '{}' not yet implemented in Rust port. \
-             Server: {}, URL: {}",
-            self.original_tool_name,
-            self.server_name,
-            self.mcp_server_params
-                .get("url")
-                .unwrap_or(&"<not set>".to_string()),
-        )
-        .into())
+        let wrapper = self.clone();
+        toolset::block_on(async move { wrapper.run_async(args).await })
     }
 
     /// Execute the MCP tool asynchronously with retry logic.
@@ -191,19 +181,33 @@ impl MCPToolWrapper {
         }
     }
 
-    /// Execute the actual MCP tool call.
-    ///
-    /// This is a stub that will be implemented when MCP client support is added.
+    /// Execute the actual MCP tool call over a fresh connection.
     async fn execute_tool(
         &self,
-        _args: &HashMap<String, Value>,
+        args: &HashMap<String, Value>,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // TODO: Implement actual MCP client connection and tool call.
-        // This requires an MCP client library for Rust.
-        Err(format!(
-            "MCP tool execution for '{}' not yet implemented in Rust port",
-            self.original_tool_name
-        )
-        .into())
+        let url = self
+            .mcp_server_params
+            .get("url")
+            .ok_or("MCP server parameters have no 'url'")?;
+        let transport: Box<dyn BaseTransport> =
+            match self.mcp_server_params.get("transport").map(String::as_str) {
+                Some("sse") => Box::new(SSETransport::new(url, None)),
+                Some("http") => Box::new(HTTPTransport::new(url, None, Some(false))),
+                _ => Box::new(HTTPTransport::new(url, None, None)),
+            };
+        let mut client = MCPClient::new(transport)
+            .with_connect_timeout(MCP_CONNECTION_TIMEOUT)
+            .with_execution_timeout(MCP_TOOL_EXECUTION_TIMEOUT)
+            .with_max_retries(1);
+
+        client.connect().await?;
+        let result = client
+            .call_tool(&self.original_tool_name, Some(args.clone()))
+            .await;
+        if let Err(e) = client.disconnect().await {
+            log::debug!("Error disconnecting from MCP server {}: {}", url, e);
+        }
+        Ok(result?)
     }
 }
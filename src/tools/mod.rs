@@ -4,22 +4,28 @@
 //!
 //! This module provides the tools infrastructure including base tool traits,
 //! structured tools, tool calling, tool usage lifecycle, cache tools,
-//! agent tools, and MCP tool wrappers.
+//! agent tools, MCP tool wrappers, typed tool arguments, the tool
+//! registry with per-agent result transformers, and the built-in tools.
 
 pub mod agent_tools;
 pub mod base_tool;
+pub mod builtin;
 pub mod cache_tools;
 pub mod mcp_native_tool;
 pub mod mcp_tool_wrapper;
+pub mod registry;
 pub mod structured_tool;
 pub mod tool_calling;
 pub mod tool_types;
 pub mod tool_usage;
+pub mod typed_tool;
 
 // Re-exports for convenience
 pub use base_tool::{BaseTool, EnvVar, Tool};
 pub use cache_tools::CacheTools;
+pub use registry::{ResultTransformer, ToolRegistry};
 pub use structured_tool::CrewStructuredTool;
 pub use tool_calling::ToolCalling;
 pub use tool_types::ToolResult;
 pub use tool_usage::{ToolUsage, ToolUsageError};
+pub use typed_tool::{ToolArgSchema, ToolArgs, ToolArgsError};
@@ -0,0 +1,434 @@
+//! Tool registry with per-agent result transformers.
+//!
+//! Agents refer to their tools by name; the [`ToolRegistry`] maps those
+//! names to implementations. It also holds result transformers: closures
+//! that post-process a tool's output before it becomes the agent's
+//! observation, e.g. keeping only relevant columns, converting HTML to
+//! Markdown or stripping base64 blobs. Transformers are attached per
+//! (agent role, tool) pair, or per tool for every agent, so shared tool
+//! implementations stay untouched.
+//!
+//! Ready-made transformers live in [`transformers`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+use serde_json::Value;
+
+use super::base_tool::{BaseTool, ToolUsageLimitExceededError};
+
+/// Post-processes a tool's output before the agent sees it.
+pub type ResultTransformer = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+type SharedTool = Arc<Mutex<Box<dyn BaseTool>>>;
+
+#[derive(Default)]
+struct RegistryInner {
+    tools: HashMap<String, SharedTool>,
+    /// (agent role or `None` for all agents, tool name) → transformers in
+    /// the order they were added.
+    transformers: HashMap<(Option<String>, String), Vec<ResultTransformer>>,
+}
+
+/// Named tools and the result transformers applied to their output.
+///
+/// Cloning is cheap and clones share state.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    inner: Arc<RwLock<RegistryInner>>,
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .finish_non_exhaustive()
+    }
+}
+
+static GLOBAL: OnceLock<ToolRegistry> = OnceLock::new();
+
+impl ToolRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide registry used by agents without their own.
+    pub fn global() -> &'static ToolRegistry {
+        GLOBAL.get_or_init(ToolRegistry::new)
+    }
+
+    /// Register a tool under its name, replacing any tool of that name.
+    pub fn register(&self, tool: impl BaseTool + 'static) {
+        self.register_boxed(Box::new(tool));
+    }
+
+    /// Register an already boxed tool.
+    pub fn register_boxed(&self, tool: Box<dyn BaseTool>) {
+        let name = tool.name().to_string();
+        self.write().tools.insert(name, Arc::new(Mutex::new(tool)));
+    }
+
+    /// Remove the tool registered under `name`; returns whether there was one.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.write().tools.remove(name).is_some()
+    }
+
+    /// Whether a tool is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.read().tools.contains_key(name)
+    }
+
+    /// Registered tool names, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.read().tools.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Description of the tool registered under `name`.
+    pub fn description(&self, name: &str) -> Option<String> {
+        let tool = self.read().tools.get(name).cloned()?;
+        let tool = tool.lock().unwrap_or_else(|e| e.into_inner());
+        Some(tool.description().to_string())
+    }
+
+    /// Argument schema of the tool registered under `name`.
+    pub fn args_schema(&self, name: &str) -> Option<Value> {
+        let tool = self.read().tools.get(name).cloned()?;
+        let tool = tool.lock().unwrap_or_else(|e| e.into_inner());
+        Some(tool.args_schema())
+    }
+
+    /// Transform `tool`'s output for the agent with role `agent_role`.
+    pub fn add_transformer(
+        &self,
+        agent_role: impl Into<String>,
+        tool: impl Into<String>,
+        transformer: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) {
+        self.push_transformer(Some(agent_role.into()), tool.into(), Arc::new(transformer));
+    }
+
+    /// Transform `tool`'s output for every agent.
+    pub fn add_tool_transformer(
+        &self,
+        tool: impl Into<String>,
+        transformer: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) {
+        self.push_transformer(None, tool.into(), Arc::new(transformer));
+    }
+
+    /// Remove the transformers for `tool` scoped to `agent_role` (or the
+    /// tool-wide ones when `None`).
+    pub fn clear_transformers(&self, agent_role: Option<&str>, tool: &str) {
+        self.write()
+            .transformers
+            .remove(&(agent_role.map(str::to_string), tool.to_string()));
+    }
+
+    fn push_transformer(
+        &self,
+        agent: Option<String>,
+        tool: String,
+        transformer: ResultTransformer,
+    ) {
+        self.write()
+            .transformers
+            .entry((agent, tool))
+            .or_default()
+            .push(transformer);
+    }
+
+    /// Apply the transformers for `tool`: first the tool-wide ones, then
+    /// those for `agent_role`.
+    pub fn transform(&self, agent_role: Option<&str>, tool: &str, output: String) -> String {
+        let transformers: Vec<ResultTransformer> = {
+            let inner = self.read();
+            let tool = tool.to_string();
+            let tool_wide = inner.transformers.get(&(None, tool.clone()));
+            let for_agent =
+                agent_role.and_then(|role| inner.transformers.get(&(Some(role.to_string()), tool)));
+            tool_wide
+                .into_iter()
+                .chain(for_agent)
+                .flatten()
+                .cloned()
+                .collect()
+        };
+        transformers.iter().fold(output, |output, f| f(&output))
+    }
+
+    /// Whether the result of calling `name` with `args` may be cached, as
+    /// decided by the tool. Unregistered tools are never cached.
+    pub fn should_cache(&self, name: &str, args: &Value, result: &Value) -> bool {
+        let Some(tool) = self.read().tools.get(name).cloned() else {
+            return false;
+        };
+        let should_cache = tool
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .should_cache(args, result);
+        should_cache
+    }
+
+    /// Uses left before the tool registered under `name` reaches its
+    /// `max_usage_count`; `None` when it is unlimited or not registered.
+    pub fn remaining_uses(&self, name: &str) -> Option<u32> {
+        let tool = self.read().tools.get(name).cloned()?;
+        let tool = tool.lock().unwrap_or_else(|e| e.into_inner());
+        let max = tool.max_usage_count()?;
+        Some(max.saturating_sub(tool.current_usage_count()))
+    }
+
+    /// Run the tool registered under `name` and transform its output for
+    /// `agent_role`. Returns `None` when no such tool is registered; a tool
+    /// that reached its `max_usage_count` fails without running.
+    pub fn execute(
+        &self,
+        agent_role: Option<&str>,
+        name: &str,
+        args: &Value,
+    ) -> Option<Result<String, Box<dyn std::error::Error + Send + Sync>>> {
+        let tool = self.read().tools.get(name).cloned()?;
+        #[cfg(feature = "chaos")]
+        if let Err(timeout) = crate::chaos::tool_fault(name) {
+            return Some(Err(Box::new(timeout)));
+        }
+        let args = match args {
+            Value::Object(map) => map.clone().into_iter().collect(),
+            _ => HashMap::new(),
+        };
+        let result = {
+            let mut tool = tool.lock().unwrap_or_else(|e| e.into_inner());
+            if tool.has_reached_max_usage_count() {
+                return Some(Err(Box::new(ToolUsageLimitExceededError {
+                    message: format!(
+                        "Tool '{}' has reached its maximum usage limit of {}. You should not use the {} tool again.",
+                        name,
+                        tool.max_usage_count().unwrap_or(0),
+                        name,
+                    ),
+                })));
+            }
+            tool.run(args)
+        };
+        Some(result.map(|value| {
+            let output = match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            };
+            self.transform(agent_role, name, output)
+        }))
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, RegistryInner> {
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, RegistryInner> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Common result transformers.
+pub mod transformers {
+    use std::sync::OnceLock;
+
+    use regex::Regex;
+    use serde_json::Value;
+
+    /// Replace base64 blobs (data URIs and long base64 runs) with a short
+    /// placeholder.
+    pub fn strip_base64(output: &str) -> String {
+        static BLOB: OnceLock<Regex> = OnceLock::new();
+        let blob = BLOB.get_or_init(|| {
+            Regex::new(r"data:[\w/+.-]+;base64,[A-Za-z0-9+/=]+|[A-Za-z0-9+/]{200,}={0,2}")
+                .expect("valid regex")
+        });
+        blob.replace_all(output, |caps: &regex::Captures| {
+            format!("[base64 data, {} bytes]", caps[0].len())
+        })
+        .into_owned()
+    }
+
+    /// Convert HTML to rough Markdown: headings, links, list items and
+    /// paragraphs are kept, scripts, styles and other tags are dropped.
+    pub fn html_to_markdown(output: &str) -> String {
+        static RULES: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
+        let rules = RULES.get_or_init(|| {
+            [
+                (r"(?is)<(script|style|head)\b.*?</(script|style|head)>", ""),
+                (r"(?is)<h([1-6])\b[^>]*>(.*?)</h[1-6]>", "\n\n#$1 $2\n\n"),
+                (r#"(?is)<a\b[^>]*href="([^"]*)"[^>]*>(.*?)</a>"#, "[$2]($1)"),
+                (r"(?is)<(strong|b)\b[^>]*>(.*?)</(strong|b)>", "**$2**"),
+                (r"(?is)<(em|i)\b[^>]*>(.*?)</(em|i)>", "*$2*"),
+                (r"(?i)<li\b[^>]*>", "\n- "),
+                (r"(?i)<br\s*/?>", "\n"),
+                (
+                    r"(?i)</?(p|div|ul|ol|tr|table|section|article)\b[^>]*>",
+                    "\n\n",
+                ),
+                (r"(?s)<[^>]+>", ""),
+                (r"[ \t]+", " "),
+                (r"\n[ \t]+", "\n"),
+                (r"\n{3,}", "\n\n"),
+            ]
+            .into_iter()
+            .map(|(pattern, replacement)| (Regex::new(pattern).expect("valid regex"), replacement))
+            .collect()
+        });
+        let mut text = output.to_string();
+        for (pattern, replacement) in rules {
+            text = pattern.replace_all(&text, *replacement).into_owned();
+        }
+        // `#1` from the heading rule becomes the right number of `#`s.
+        static HEADING: OnceLock<Regex> = OnceLock::new();
+        let heading = HEADING.get_or_init(|| Regex::new(r"(?m)^#([1-6]) ").expect("valid regex"));
+        let text = heading.replace_all(&text, |caps: &regex::Captures| {
+            format!("{} ", "#".repeat(caps[1].parse().unwrap_or(1)))
+        });
+        text.replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&nbsp;", " ")
+            .trim()
+            .to_string()
+    }
+
+    /// Keep only `columns` of JSON output: applied to every object of an
+    /// array (or a single object). Non-JSON output is returned unchanged.
+    pub fn select_columns(columns: &[&str]) -> impl Fn(&str) -> String + Send + Sync + 'static {
+        let columns: Vec<String> = columns.iter().map(|c| c.to_string()).collect();
+        move |output| {
+            let Ok(value) = serde_json::from_str::<Value>(output) else {
+                return output.to_string();
+            };
+            let select = |value: &Value| match value {
+                Value::Object(map) => Value::Object(
+                    map.iter()
+                        .filter(|(key, _)| columns.contains(key))
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                ),
+                other => other.clone(),
+            };
+            let selected = match &value {
+                Value::Array(items) => Value::Array(items.iter().map(select).collect()),
+                other => select(other),
+            };
+            selected.to_string()
+        }
+    }
+
+    /// Truncate output to `max_chars` characters, noting what was cut.
+    pub fn truncate(max_chars: usize) -> impl Fn(&str) -> String + Send + Sync + 'static {
+        move |output| {
+            let total = output.chars().count();
+            if total <= max_chars {
+                return output.to_string();
+            }
+            let kept: String = output.chars().take(max_chars).collect();
+            format!(
+                "{}\n[truncated {} of {} characters]",
+                kept,
+                total - max_chars,
+                total
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::transformers::*;
+    use super::*;
+    use crate::tools::base_tool::Tool;
+
+    #[test]
+    fn test_transformers_per_agent_and_tool() {
+        let registry = ToolRegistry::new();
+        registry.register(Tool::new(
+            "search",
+            "Search the web",
+            Arc::new(|_| {
+                Ok(serde_json::json!([
+                    {"title": "Rust", "url": "https://rust-lang.org", "html": "<p>big</p>"},
+                    {"title": "Cargo", "url": "https://crates.io", "html": "<p>bigger</p>"},
+                ]))
+            }),
+        ));
+        registry.add_transformer("Researcher", "search", select_columns(&["title"]));
+        registry.add_tool_transformer("search", |out: &str| out.replace("Cargo", "cargo"));
+
+        let args = serde_json::json!({});
+        let researcher = registry
+            .execute(Some("Researcher"), "search", &args)
+            .unwrap();
+        assert_eq!(
+            researcher.unwrap(),
+            r#"[{"title":"Rust"},{"title":"cargo"}]"#
+        );
+        let writer = registry
+            .execute(Some("Writer"), "search", &args)
+            .unwrap()
+            .unwrap();
+        assert!(writer.contains("\"html\"") && writer.contains("cargo"));
+        assert!(registry.execute(None, "missing", &args).is_none());
+
+        registry.clear_transformers(Some("Researcher"), "search");
+        let researcher = registry
+            .execute(Some("Researcher"), "search", &args)
+            .unwrap();
+        assert!(researcher.unwrap().contains("\"url\""));
+    }
+
+    #[test]
+    fn test_builtin_transformers() {
+        let html = "<html><head><title>x</title></head><body><h2>Intro</h2>\
+                    <p>Read <a href=\"https://a.io\">the <b>docs</b></a> &amp; more.</p>\
+                    <ul><li>one</li><li>two</li></ul><script>alert(1)</script></body></html>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## Intro\n\nRead [the **docs**](https://a.io) & more.\n\n- one\n- two"
+        );
+
+        let blob = format!("image: data:image/png;base64,{}= done", "QUJD".repeat(10));
+        assert_eq!(strip_base64(&blob), "image: [base64 data, 63 bytes] done");
+        assert_eq!(truncate(3)("abcdef"), "abc\n[truncated 3 of 6 characters]");
+    }
+    #[test]
+    fn test_max_usage_count_enforced() {
+        let registry = ToolRegistry::new();
+        registry.register(
+            Tool::new("lookup", "Look up", Arc::new(|_| Ok(Value::from("found"))))
+                .with_max_usage_count(Some(2)),
+        );
+        let args = serde_json::json!({});
+        assert_eq!(registry.remaining_uses("lookup"), Some(2));
+        for _ in 0..2 {
+            assert_eq!(
+                registry.execute(None, "lookup", &args).unwrap().unwrap(),
+                "found"
+            );
+        }
+        assert_eq!(registry.remaining_uses("lookup"), Some(0));
+        let err = registry
+            .execute(None, "lookup", &args)
+            .unwrap()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Tool 'lookup' has reached its maximum usage limit of 2."));
+
+        let mut agent = crate::agent::Agent::new("Clerk".into(), "File".into(), "Tidy".into());
+        agent.tools = vec!["lookup".to_string(), "notes".to_string()];
+        agent.tool_usage_limits = Some(HashMap::from([("notes".to_string(), 3)]));
+        assert_eq!(
+            agent.effective_tool_usage_limits(&registry),
+            HashMap::from([("lookup".to_string(), 0), ("notes".to_string(), 3)])
+        );
+    }
+}
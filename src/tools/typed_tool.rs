@@ -0,0 +1,307 @@
+//! Typed tool arguments.
+//!
+//! Tools receive their arguments as a JSON object. [`ToolArgs`] turns that
+//! object into a typed value and describes it as a JSON Schema, so tools can
+//! be written as plain Rust functions: [`Tool::typed`](super::Tool::typed)
+//! wraps a closure over a [`ToolArgs`] type, and the [`tool!`](crate::tool)
+//! and [`tool_args!`](crate::tool_args) macros generate the implementation
+//! from a function signature or struct definition.
+//!
+//! Argument schemas come from [`ToolArgSchema`], implemented for strings,
+//! numbers, booleans, `Option`, `Vec`, maps and `serde_json::Value`.
+//! `Option` arguments may be omitted; everything else is required.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::base_tool::ToolFn;
+
+/// Error converting a tool's JSON arguments into typed values.
+#[derive(Debug, thiserror::Error)]
+pub enum ToolArgsError {
+    /// A required argument was not given.
+    #[error("Missing required argument '{0}'")]
+    Missing(String),
+    /// An argument did not match its type.
+    #[error("Invalid argument '{name}': {source}")]
+    Invalid {
+        /// Argument name.
+        name: String,
+        /// Deserialization error.
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A type usable as a tool argument.
+pub trait ToolArgSchema: DeserializeOwned {
+    /// JSON Schema of the argument.
+    fn schema() -> Value;
+
+    /// Whether the argument must be given.
+    fn required() -> bool {
+        true
+    }
+}
+
+/// Typed arguments of a tool.
+pub trait ToolArgs: Sized {
+    /// JSON Schema (an object schema) of the arguments.
+    fn args_schema() -> Value;
+
+    /// Convert the JSON arguments a tool was called with.
+    fn from_args(args: HashMap<String, Value>) -> Result<Self, ToolArgsError>;
+}
+
+macro_rules! impl_schema {
+    ($schema:literal: $($ty:ty),+) => {
+        $(
+            impl ToolArgSchema for $ty {
+                fn schema() -> Value {
+                    serde_json::json!({ "type": $schema })
+                }
+            }
+        )+
+    };
+}
+
+impl_schema!("string": String, char);
+impl_schema!("boolean": bool);
+impl_schema!("integer": i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+impl_schema!("number": f32, f64);
+
+impl ToolArgSchema for Value {
+    fn schema() -> Value {
+        Value::Object(Map::new())
+    }
+}
+
+impl<T: ToolArgSchema> ToolArgSchema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+impl<T: ToolArgSchema> ToolArgSchema for Vec<T> {
+    fn schema() -> Value {
+        serde_json::json!({ "type": "array", "items": T::schema() })
+    }
+}
+
+impl<T: ToolArgSchema> ToolArgSchema for HashMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+impl<T: ToolArgSchema> ToolArgSchema for BTreeMap<String, T> {
+    fn schema() -> Value {
+        serde_json::json!({ "type": "object", "additionalProperties": T::schema() })
+    }
+}
+
+/// One property of an argument object schema.
+#[derive(Debug, Clone)]
+pub struct ArgField {
+    /// Argument name.
+    pub name: &'static str,
+    /// Schema of the argument's type.
+    pub schema: Value,
+    /// Whether the argument must be given.
+    pub required: bool,
+    /// Description, from the argument's doc comment.
+    pub description: String,
+}
+
+/// Describe argument `name` of type `T`; `doc` is its (possibly
+/// multi-line) doc comment.
+pub fn field<T: ToolArgSchema>(name: &'static str, doc: &str) -> ArgField {
+    ArgField {
+        name,
+        schema: T::schema(),
+        required: T::required(),
+        description: doc_text(doc),
+    }
+}
+
+/// Object schema over `fields`.
+pub fn object_schema(fields: Vec<ArgField>) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in fields {
+        let mut schema = field.schema;
+        if !field.description.is_empty() {
+            if let Value::Object(map) = &mut schema {
+                map.insert("description".into(), Value::String(field.description));
+            }
+        }
+        if field.required {
+            required.push(Value::String(field.name.to_string()));
+        }
+        properties.insert(field.name.to_string(), schema);
+    }
+    let mut schema = serde_json::json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Take argument `name` from `args` as a `T`. A missing (or `null`)
+/// argument is `None` for optional types and an error otherwise.
+pub fn arg<T: ToolArgSchema>(
+    args: &mut HashMap<String, Value>,
+    name: &str,
+) -> Result<T, ToolArgsError> {
+    let value = args.remove(name).unwrap_or(Value::Null);
+    if value.is_null() && T::required() {
+        return Err(ToolArgsError::Missing(name.to_string()));
+    }
+    serde_json::from_value(value).map_err(|source| ToolArgsError::Invalid {
+        name: name.to_string(),
+        source,
+    })
+}
+
+/// Wrap a function over a tool's argument map as a [`ToolFn`].
+pub fn tool_fn<F>(f: F) -> ToolFn
+where
+    F: Fn(&mut HashMap<String, Value>) -> Result<Value, Box<dyn std::error::Error + Send + Sync>>
+        + Send
+        + Sync
+        + 'static,
+{
+    Arc::new(move |mut args| f(&mut args))
+}
+
+/// Convert a typed tool function's result into a tool output.
+pub fn into_output<R, E>(
+    result: Result<R, E>,
+) -> Result<Value, Box<dyn std::error::Error + Send + Sync>>
+where
+    R: Serialize,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let output = result.map_err(Into::into)?;
+    Ok(serde_json::to_value(output)?)
+}
+
+/// Join doc comment lines into a description.
+pub fn doc_text(doc: &str) -> String {
+    doc.lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::base_tool::{BaseTool, Tool};
+
+    crate::tool_args! {
+        /// Arguments of the search tool.
+        pub struct SearchArgs {
+            /// What to search for.
+            pub query: String,
+            /// Maximum number of results.
+            pub limit: Option<u32>,
+            pub tags: Vec<String>,
+        }
+    }
+
+    crate::tool! {
+        /// Multiply two numbers.
+        ///
+        /// Both factors may be fractional.
+        fn multiply(
+            /// The first factor.
+            a: f64,
+            b: f64,
+        ) -> Result<f64, String> {
+            Ok(a * b)
+        }
+    }
+
+    fn args(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_tool_macro_schema_and_run() {
+        let mut tool = multiply();
+        assert_eq!(tool.name(), "multiply");
+        assert_eq!(
+            tool.description(),
+            "Multiply two numbers.\n\nBoth factors may be fractional."
+        );
+        assert_eq!(
+            tool.args_schema(),
+            serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "The first factor."},
+                    "b": {"type": "number"},
+                },
+                "required": ["a", "b"],
+            })
+        );
+        assert_eq!(
+            tool.run(args(serde_json::json!({"a": 3, "b": 2.5})))
+                .unwrap(),
+            serde_json::json!(7.5)
+        );
+        let err = tool.run(args(serde_json::json!({"a": 3}))).unwrap_err();
+        assert_eq!(err.to_string(), "Missing required argument 'b'");
+        let err = tool
+            .run(args(serde_json::json!({"a": "x", "b": 1})))
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Invalid argument 'a'"));
+    }
+
+    #[test]
+    fn test_typed_tool_from_args_struct() {
+        let schema = SearchArgs::args_schema();
+        assert_eq!(schema["required"], serde_json::json!(["query", "tags"]));
+        assert_eq!(
+            schema["properties"]["tags"],
+            serde_json::json!({"type": "array", "items": {"type": "string"}})
+        );
+        assert_eq!(
+            schema["properties"]["limit"]["description"],
+            "Maximum number of results."
+        );
+
+        let mut tool = Tool::typed("search", "Search the docs", |a: SearchArgs| {
+            if a.query.is_empty() {
+                return Err("empty query");
+            }
+            Ok(serde_json::json!({
+                "query": a.query,
+                "limit": a.limit.unwrap_or(10),
+                "tags": a.tags,
+            }))
+        });
+        assert_eq!(tool.args_schema(), schema);
+        let output = tool
+            .run(args(
+                serde_json::json!({"query": "agents", "tags": ["rust"]}),
+            ))
+            .unwrap();
+        assert_eq!(output["limit"], 10);
+        let err = tool
+            .run(args(serde_json::json!({"query": "", "tags": []})))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "empty query");
+    }
+}
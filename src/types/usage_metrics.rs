@@ -1,6 +1,12 @@
 //! Usage metrics tracking for CrewAI execution.
 //!
 //! Corresponds to `crewai/types/usage_metrics.py`.
+//!
+//! Providers [`record`] the usage of every call; a [`UsageScope`] sums
+//! what was recorded on its thread while it is active, which is how a task
+//! learns what its execution cost.
+
+use std::cell::RefCell;
 
 use serde::{Deserialize, Serialize};
 
@@ -34,3 +40,99 @@ impl UsageMetrics {
         self.successful_requests += other.successful_requests;
     }
 }
+
+thread_local! {
+    /// Usage of the scopes active on this thread, innermost last.
+    static SCOPES: RefCell<Vec<UsageMetrics>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Add `usage` to every [`UsageScope`] active on this thread.
+pub fn record(usage: &UsageMetrics) {
+    SCOPES.with(|scopes| {
+        for scope in scopes.borrow_mut().iter_mut() {
+            scope.add_usage_metrics(usage);
+        }
+    });
+}
+
+/// RAII guard summing the usage recorded on this thread, used by a task
+/// around its execution.
+///
+/// Scopes nest: usage recorded inside an inner scope (a task run from
+/// another task's tool) also counts towards the outer one.
+pub struct UsageScope {
+    depth: usize,
+}
+
+impl UsageScope {
+    /// Start summing usage on this thread.
+    pub fn enter() -> Self {
+        let depth = SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            scopes.push(UsageMetrics::new());
+            scopes.len()
+        });
+        Self { depth }
+    }
+
+    /// Usage recorded since the scope was entered.
+    pub fn usage(&self) -> UsageMetrics {
+        SCOPES.with(|scopes| {
+            scopes
+                .borrow()
+                .get(self.depth - 1)
+                .cloned()
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for UsageScope {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().truncate(self.depth - 1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(total: i64) -> UsageMetrics {
+        UsageMetrics {
+            total_tokens: total,
+            successful_requests: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_usage_scopes_nest() {
+        record(&tokens(1)); // No scope, dropped
+        let outer = UsageScope::enter();
+        record(&tokens(10));
+        {
+            let inner = UsageScope::enter();
+            record(&tokens(5));
+            assert_eq!(inner.usage().total_tokens, 5);
+        }
+        record(&tokens(2));
+        let usage = outer.usage();
+        assert_eq!(usage.total_tokens, 17);
+        assert_eq!(usage.successful_requests, 3);
+    }
+
+    #[test]
+    fn test_task_output_carries_executor_usage() {
+        let mut task = crate::task::Task::new("Summarize".into(), "A summary".into());
+        task.agent = Some("writer".to_string());
+        task.set_agent_executor(|_: &str, _: Option<&str>, _: &[String]| {
+            record(&tokens(40));
+            record(&tokens(2));
+            Ok(("A short summary".to_string(), Vec::new()))
+        });
+        let output = task.execute_sync(None, None, None).unwrap();
+        let usage = output.token_usage.unwrap();
+        assert_eq!(usage.total_tokens, 42);
+        assert_eq!(usage.successful_requests, 2);
+    }
+}
@@ -0,0 +1,330 @@
+//! Binary artifacts produced by tools and tasks.
+//!
+//! Crews that deliver charts, spreadsheets or PDFs register the generated
+//! bytes with [`attach`]: the bytes go to the [`ArtifactStore`] and the
+//! returned [`Artifact`] (name, MIME type, SHA-256 hash, size) is added to
+//! the running task's [`TaskOutput::artifacts`]. The server serves stored
+//! artifacts at `GET /artifacts/:id`.
+//!
+//! ```no_run
+//! use crewai::utilities::artifacts;
+//!
+//! // Inside a tool's `run`, a task guardrail or a task callback:
+//! let png: Vec<u8> = render_chart();
+//! let artifact = artifacts::attach("revenue.png", None, &png)?;
+//! # fn render_chart() -> Vec<u8> { Vec::new() }
+//! # Ok::<(), artifacts::ArtifactError>(())
+//! ```
+//!
+//! A task collects the artifacts attached on its thread while it runs; the
+//! store lives in `CREWAI_ARTIFACTS_DIR`, by default `artifacts` in the
+//! storage directory.
+//!
+//! [`TaskOutput::artifacts`]: crate::tasks::task_output::TaskOutput::artifacts
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Environment variable naming the artifact store directory.
+pub const ARTIFACTS_DIR_ENV: &str = "CREWAI_ARTIFACTS_DIR";
+
+/// Error storing or loading an artifact.
+#[derive(Debug, thiserror::Error)]
+pub enum ArtifactError {
+    /// No artifact with this ID.
+    #[error("Artifact '{0}' not found")]
+    NotFound(String),
+    /// Reading or writing the store failed.
+    #[error("Artifact store error: {0}")]
+    Io(#[from] std::io::Error),
+    /// The metadata file is corrupt.
+    #[error("Invalid artifact metadata: {0}")]
+    Metadata(#[from] serde_json::Error),
+}
+
+/// A stored binary artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Artifact ID.
+    pub id: String,
+    /// File name, e.g. `revenue.png`.
+    pub name: String,
+    /// MIME type, e.g. `image/png`.
+    pub mime_type: String,
+    /// Hex SHA-256 of the contents.
+    pub sha256: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// When the artifact was stored.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Artifact {
+    /// Path of the artifact on the server.
+    pub fn url_path(&self) -> String {
+        format!("/artifacts/{}", self.id)
+    }
+}
+
+/// MIME types by file extension.
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("pdf", "application/pdf"),
+    ("csv", "text/csv"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("html", "text/html"),
+    ("json", "application/json"),
+    ("zip", "application/zip"),
+    (
+        "xlsx",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    ),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+];
+
+/// MIME type for a file name, from its extension; `application/octet-stream`
+/// when unknown.
+pub fn mime_type_for(name: &str) -> &'static str {
+    let extension = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    MIME_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map_or("application/octet-stream", |(_, mime)| mime)
+}
+
+/// Directory-backed artifact storage: `<id>.bin` holds the contents and
+/// `<id>.json` the [`Artifact`] metadata.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    /// Store directory.
+    pub root: PathBuf,
+}
+
+impl ArtifactStore {
+    /// A store in `root`, created on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Store `bytes` as `name`. The MIME type is guessed from the name when
+    /// not given.
+    pub fn put(
+        &self,
+        name: &str,
+        mime_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Result<Artifact, ArtifactError> {
+        std::fs::create_dir_all(&self.root)?;
+        let artifact = Artifact {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            mime_type: mime_type.unwrap_or_else(|| mime_type_for(name)).to_string(),
+            sha256: hex::encode(Sha256::digest(bytes)),
+            size: bytes.len() as u64,
+            created_at: Utc::now(),
+        };
+        std::fs::write(self.root.join(format!("{}.bin", artifact.id)), bytes)?;
+        std::fs::write(
+            self.root.join(format!("{}.json", artifact.id)),
+            serde_json::to_vec_pretty(&artifact)?,
+        )?;
+        Ok(artifact)
+    }
+
+    /// Metadata of artifact `id`.
+    pub fn metadata(&self, id: &str) -> Result<Artifact, ArtifactError> {
+        let path = self.path(id, "json")?;
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ArtifactError::NotFound(id.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Metadata and contents of artifact `id`.
+    pub fn get(&self, id: &str) -> Result<(Artifact, Vec<u8>), ArtifactError> {
+        let artifact = self.metadata(id)?;
+        let bytes = std::fs::read(self.path(id, "bin")?)?;
+        Ok((artifact, bytes))
+    }
+
+    /// Delete artifact `id`; returns whether it existed.
+    pub fn delete(&self, id: &str) -> Result<bool, ArtifactError> {
+        let existed = self.path(id, "json")?.exists();
+        for extension in ["bin", "json"] {
+            match std::fs::remove_file(self.path(id, extension)?) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(existed)
+    }
+
+    /// File of artifact `id`; IDs other than UUIDs are never found, so they
+    /// cannot escape the store directory.
+    fn path(&self, id: &str, extension: &str) -> Result<PathBuf, ArtifactError> {
+        let id = Uuid::parse_str(id).map_err(|_| ArtifactError::NotFound(id.to_string()))?;
+        Ok(self.root.join(format!("{}.{}", id, extension)))
+    }
+}
+
+static STORE: OnceLock<ArtifactStore> = OnceLock::new();
+
+/// The process-wide artifact store, in `CREWAI_ARTIFACTS_DIR` or else
+/// `artifacts` in the storage directory.
+pub fn store() -> &'static ArtifactStore {
+    STORE.get_or_init(|| {
+        let root = std::env::var(ARTIFACTS_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                if cfg!(test) {
+                    // Unit tests leave the user's storage directory alone.
+                    return std::env::temp_dir().join("crewai-test-artifacts");
+                }
+                crate::utilities::paths::storage_dir().join("artifacts")
+            });
+        ArtifactStore::new(root)
+    })
+}
+
+thread_local! {
+    /// Artifacts attached by the task running on this thread.
+    static COLLECTED: RefCell<Option<Vec<Artifact>>> = const { RefCell::new(None) };
+}
+
+/// Store `bytes` as `name` in the process-wide store and attach the
+/// artifact to the task running on this thread, if any.
+pub fn attach(
+    name: &str,
+    mime_type: Option<&str>,
+    bytes: &[u8],
+) -> Result<Artifact, ArtifactError> {
+    let artifact = store().put(name, mime_type, bytes)?;
+    COLLECTED.with(|collected| {
+        if let Some(artifacts) = collected.borrow_mut().as_mut() {
+            artifacts.push(artifact.clone());
+        }
+    });
+    Ok(artifact)
+}
+
+/// Whether a task on this thread is collecting attached artifacts.
+pub(crate) fn is_collecting() -> bool {
+    COLLECTED.with(|collected| collected.borrow().is_some())
+}
+
+/// Attach `artifacts`, stored by another thread working for the task
+/// running on this one.
+pub(crate) fn extend(artifacts: Vec<Artifact>) {
+    COLLECTED.with(|collected| {
+        if let Some(collected) = collected.borrow_mut().as_mut() {
+            collected.extend(artifacts);
+        }
+    });
+}
+
+/// RAII guard collecting the artifacts attached on this thread, used by a
+/// task around its execution.
+///
+/// When dropped, restores the previous collection (a task run from inside
+/// another task's tool keeps its own artifacts).
+pub struct ArtifactScope {
+    previous: Option<Vec<Artifact>>,
+}
+
+impl ArtifactScope {
+    /// Start collecting artifacts on this thread.
+    pub fn enter() -> Self {
+        let previous = COLLECTED.with(|collected| collected.borrow_mut().replace(Vec::new()));
+        Self { previous }
+    }
+
+    /// Artifacts attached so far.
+    pub fn take(&self) -> Vec<Artifact> {
+        COLLECTED.with(|collected| {
+            collected
+                .borrow_mut()
+                .as_mut()
+                .map(std::mem::take)
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for ArtifactScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        COLLECTED.with(|collected| *collected.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_and_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = ArtifactStore::new(dir.path());
+        let artifact = local.put("report.xlsx", None, b"PK\x03\x04").unwrap();
+        assert_eq!(
+            artifact.mime_type,
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        assert_eq!(artifact.size, 4);
+        assert_eq!(artifact.sha256, hex::encode(Sha256::digest(b"PK\x03\x04")));
+        let (loaded, bytes) = local.get(&artifact.id).unwrap();
+        assert_eq!((loaded, bytes), (artifact.clone(), b"PK\x03\x04".to_vec()));
+        assert!(matches!(
+            local.get("../../etc/passwd"),
+            Err(ArtifactError::NotFound(_))
+        ));
+        assert!(local.delete(&artifact.id).unwrap());
+        assert!(matches!(
+            local.metadata(&artifact.id),
+            Err(ArtifactError::NotFound(_))
+        ));
+
+        let outer = ArtifactScope::enter();
+        let chart = attach("chart.svg", None, b"<svg/>").unwrap();
+        {
+            let inner = ArtifactScope::enter();
+            let notes = attach("notes.bin", Some("application/x-notes"), b"..").unwrap();
+            assert_eq!(inner.take(), vec![notes.clone()]);
+            store().delete(&notes.id).unwrap();
+        }
+        assert_eq!(outer.take(), vec![chart.clone()]);
+        drop(outer);
+        let orphan = attach("orphan.txt", None, b"no task").unwrap();
+        assert_eq!(orphan.mime_type, "text/plain");
+        assert_eq!(mime_type_for("A.PNG"), "image/png");
+        for id in [chart.id, orphan.id] {
+            store().delete(&id).unwrap();
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! Cooperative cancellation and execution deadlines.
+//!
+//! A [`CancellationScope`] makes a [`CancellationToken`] and an optional
+//! deadline current on this thread for the duration of a crew kickoff, a
+//! task or an agent execution. Scopes nest: an execution is interrupted
+//! when any enclosing token is cancelled or any enclosing deadline passes.
+//!
+//! Agent executors [`check`] between iterations, and LLM and tool calls go
+//! through [`run_interruptible`], which stops waiting as soon as the
+//! execution is interrupted.
+//!
+//! An interrupted call is abandoned, not aborted: threads cannot be
+//! preempted, so it keeps running on its background thread until it
+//! returns, and whatever it does meanwhile (files written, requests sent,
+//! a code interpreter process) still happens. Only its result, and the
+//! artifacts and usage it records, are discarded. Calls that should stop
+//! early can [`check`] themselves: their thread inherits the scopes.
+
+use std::cell::RefCell;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub use tokio_util::sync::CancellationToken;
+
+use crate::events::base_event;
+use crate::events::event_context::{self, EventContextSnapshot};
+use crate::flow::flow_trackable;
+use crate::tasks::execution_trace::{self, ExecutionStep, TraceScope};
+use crate::types::usage_metrics::{self, UsageMetrics, UsageScope};
+use crate::utilities::artifacts::{self, Artifact, ArtifactScope};
+use crate::utilities::journal::{self, ExecutionJournal, JournalScope};
+
+/// How often an interruptible call checks for cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Why an execution was stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Interrupted {
+    /// Its cancellation token was cancelled.
+    #[error("Execution was cancelled")]
+    Cancelled,
+    /// It ran longer than its `max_execution_time`.
+    #[error("Execution timed out after {}s", .0.as_secs_f64())]
+    TimedOut(Duration),
+}
+
+/// A token and deadline made current by a [`CancellationScope`].
+#[derive(Clone)]
+struct Entry {
+    token: CancellationToken,
+    /// When the scope times out, and its timeout.
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl Entry {
+    fn interrupted(&self) -> Option<Interrupted> {
+        if self.token.is_cancelled() {
+            return Some(Interrupted::Cancelled);
+        }
+        match self.deadline {
+            Some((deadline, timeout)) if Instant::now() >= deadline => {
+                Some(Interrupted::TimedOut(timeout))
+            }
+            _ => None,
+        }
+    }
+}
+
+thread_local! {
+    /// Scopes active on this thread, innermost last.
+    static SCOPES: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+}
+
+/// RAII guard making a cancellation token and deadline current on this
+/// thread.
+pub struct CancellationScope {
+    /// Scopes active before this one was entered.
+    base: usize,
+    depth: usize,
+}
+
+impl CancellationScope {
+    /// Make `token` current, with a deadline `timeout` from now if given.
+    pub fn enter(token: CancellationToken, timeout: Option<Duration>) -> Self {
+        let entry = Entry {
+            token,
+            deadline: timeout.map(|timeout| (Instant::now() + timeout, timeout)),
+        };
+        Self::push(vec![entry])
+    }
+
+    fn push(entries: Vec<Entry>) -> Self {
+        SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            let base = scopes.len();
+            scopes.extend(entries);
+            Self {
+                base,
+                depth: scopes.len(),
+            }
+        })
+    }
+
+    /// Whether this scope, or one it is nested in, was interrupted.
+    pub fn interrupted(&self) -> Option<Interrupted> {
+        SCOPES.with(|scopes| {
+            scopes.borrow()[..self.depth]
+                .iter()
+                .find_map(Entry::interrupted)
+        })
+    }
+}
+
+impl Drop for CancellationScope {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().truncate(self.base));
+    }
+}
+
+/// Fail if the current execution was cancelled or timed out.
+pub fn check() -> Result<(), Interrupted> {
+    SCOPES.with(
+        |scopes| match scopes.borrow().iter().find_map(Entry::interrupted) {
+            Some(interrupted) => Err(interrupted),
+            None => Ok(()),
+        },
+    )
+}
+
+/// The thread-local context of a thread calling [`run_interruptible`] or
+/// [`spawn_blocking`], continued on the call's background thread.
+struct Inherited {
+    scopes: Vec<Entry>,
+    events: EventContextSnapshot,
+    emission_sequence: u64,
+    flow_id: Option<String>,
+    flow_request_id: Option<String>,
+    journal: Option<ExecutionJournal>,
+    collects_artifacts: bool,
+    collects_trace: bool,
+}
+
+/// What a background thread recorded for the calling thread.
+struct Recorded {
+    usage: UsageMetrics,
+    artifacts: Vec<Artifact>,
+    steps: Vec<ExecutionStep>,
+    last_event_id: Option<String>,
+    emission_sequence: u64,
+}
+
+impl Inherited {
+    fn capture(scopes: Vec<Entry>) -> Self {
+        Self {
+            scopes,
+            events: event_context::snapshot_context(),
+            emission_sequence: base_event::peek_emission_sequence(),
+            flow_id: flow_trackable::current_flow_id(),
+            flow_request_id: flow_trackable::current_flow_request_id(),
+            journal: journal::current(),
+            collects_artifacts: artifacts::is_collecting(),
+            collects_trace: execution_trace::is_collecting(),
+        }
+    }
+
+    /// Run `call` on this (background) thread in the inherited context.
+    fn run<T>(self, call: impl FnOnce() -> T) -> (T, Recorded) {
+        let _scope = CancellationScope::push(self.scopes);
+        event_context::restore_context(self.events);
+        base_event::set_emission_counter(self.emission_sequence);
+        flow_trackable::set_current_flow_id(self.flow_id);
+        flow_trackable::set_current_flow_request_id(self.flow_request_id);
+        let _journal_scope = self.journal.map(JournalScope::enter);
+        let artifact_scope = self.collects_artifacts.then(ArtifactScope::enter);
+        let trace_scope = self.collects_trace.then(TraceScope::enter);
+        let usage = UsageScope::enter();
+        let result = call();
+        let recorded = Recorded {
+            usage: usage.usage(),
+            artifacts: artifact_scope.map(|s| s.take()).unwrap_or_default(),
+            steps: trace_scope.map(|s| s.take()).unwrap_or_default(),
+            last_event_id: event_context::get_last_event_id(),
+            emission_sequence: base_event::peek_emission_sequence(),
+        };
+        (result, recorded)
+    }
+}
+
+impl Recorded {
+    /// Record on the calling thread what the background thread recorded.
+    fn apply(self) {
+        usage_metrics::record(&self.usage);
+        artifacts::extend(self.artifacts);
+        execution_trace::extend(self.steps);
+        if let Some(id) = self.last_event_id {
+            event_context::set_last_event_id(id);
+        }
+        base_event::set_emission_counter(self.emission_sequence);
+    }
+}
+
+/// Run `call`, giving up as soon as the current execution is interrupted.
+///
+/// Outside any [`CancellationScope`] `call` simply runs on this thread.
+/// Inside one it runs on a background thread that continues this thread's
+/// scopes: cancellation, event parents, the flow, the execution journal
+/// and the running task's artifact and trace collection. Its token usage, artifacts and trace
+/// steps are recorded on this thread once it returns. When the execution
+/// is interrupted first, the call is abandoned (see the module docs).
+pub fn run_interruptible<T, F>(call: F) -> Result<T, Interrupted>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let entries = SCOPES.with(|scopes| scopes.borrow().clone());
+    if entries.is_empty() {
+        return Ok(call());
+    }
+    check()?;
+
+    let inherited = Inherited::capture(entries);
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let _ = tx.send(inherited.run(call));
+    });
+    loop {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok((result, recorded)) => {
+                recorded.apply();
+                return Ok(result);
+            }
+            Err(RecvTimeoutError::Timeout) => check()?,
+            Err(RecvTimeoutError::Disconnected) => match handle.join() {
+                Err(panic) => std::panic::resume_unwind(panic),
+                Ok(()) => unreachable!("interruptible call returned without a result"),
+            },
+        }
+    }
+}
+
+/// Run blocking `call` on tokio's blocking thread pool, so async code
+/// can await synchronous work (such as a kickoff making blocking LLM
+/// calls) without stalling or nesting runtimes.
+///
+/// Like [`run_interruptible`], the call continues this thread's scopes and
+/// what it records comes back to this thread. A panic in `call` resumes
+/// here.
+pub(crate) async fn spawn_blocking<T, F>(call: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let entries = SCOPES.with(|scopes| scopes.borrow().clone());
+    let inherited = Inherited::capture(entries);
+    match tokio::task::spawn_blocking(move || inherited.run(call)).await {
+        Ok((result, recorded)) => {
+            recorded.apply();
+            result
+        }
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_scopes_nest() {
+        let token = CancellationToken::new();
+        let outer = CancellationScope::enter(token.clone(), None);
+        {
+            let inner = CancellationScope::enter(CancellationToken::new(), None);
+            assert_eq!(inner.interrupted(), None);
+            token.cancel();
+            assert_eq!(inner.interrupted(), Some(Interrupted::Cancelled));
+        }
+        assert_eq!(check(), Err(Interrupted::Cancelled));
+        drop(outer);
+        assert_eq!(check(), Ok(()));
+    }
+
+    #[test]
+    fn test_run_interruptible_gives_up_at_deadline() {
+        assert_eq!(run_interruptible(|| 1), Ok(1));
+
+        let timeout = Duration::from_millis(50);
+        let scope = CancellationScope::enter(CancellationToken::new(), Some(timeout));
+        let started = Instant::now();
+        let result = run_interruptible(|| thread::sleep(Duration::from_secs(5)));
+        assert_eq!(result, Err(Interrupted::TimedOut(timeout)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(scope.interrupted(), Some(Interrupted::TimedOut(timeout)));
+    }
+
+    #[test]
+    fn test_run_interruptible_continues_thread_context() {
+        let _scope = CancellationScope::enter(CancellationToken::new(), None);
+        let collected = ArtifactScope::enter();
+        event_context::push_event_scope("task-1".to_string(), "task_started".to_string());
+        let parent = run_interruptible(|| {
+            artifacts::attach("notes.txt", None, b"notes").unwrap();
+            event_context::get_current_parent_id()
+        });
+        event_context::pop_event_scope();
+        assert_eq!(parent, Ok(Some("task-1".to_string())));
+        let attached = collected.take();
+        assert_eq!(attached.len(), 1);
+        assert_eq!(attached[0].name, "notes.txt");
+    }
+
+    /// A task whose agent works until interrupted, or answers `answer`.
+    fn task(description: &str, answer: Option<&'static str>) -> crate::task::Task {
+        let mut task = crate::task::Task::new(description.into(), "An answer".into());
+        task.agent = Some("Researcher".to_string());
+        task.set_agent_executor(move |_, _, _| match answer {
+            Some(answer) => Ok((answer.to_string(), Vec::new())),
+            None => run_interruptible(|| thread::sleep(Duration::from_secs(10)))
+                .map(|()| (String::new(), Vec::new()))
+                .map_err(|e| e.to_string()),
+        });
+        task
+    }
+
+    fn crew(tasks: Vec<crate::task::Task>) -> (crate::crew::Crew, std::sync::Arc<AtomicUsize>) {
+        let cleanups = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = cleanups.clone();
+        let mut crew = crate::crew::Crew::new(tasks, Vec::new());
+        crew.add_cleanup_hook(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        (crew, cleanups)
+    }
+
+    #[test]
+    fn test_timed_out_task_is_marked_in_crew_output() {
+        let mut slow = task("Research everything", None);
+        slow.max_execution_time = Some(1);
+        let (mut crew, cleanups) = crew(vec![slow, task("Summarize", Some("done"))]);
+
+        let started = Instant::now();
+        let output = crew.kickoff(None).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(output.tasks_output[0].timed_out);
+        assert!(!output.tasks_output[1].timed_out);
+        assert_eq!(output.raw, "done");
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_kickoff_with_cancel() {
+        let (mut crew, cleanups) = crew(vec![task("Research everything", None)]);
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+
+        let started = Instant::now();
+        let err = crew.kickoff_with_cancel(None, token).unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(err, Interrupted::Cancelled.to_string());
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+        assert!(crew.cancellation.is_none());
+    }
+}
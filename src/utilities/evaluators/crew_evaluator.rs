@@ -0,0 +1,327 @@
+//! Scores a crew's task outputs across test runs.
+//!
+//! Corresponds to `crewai/utilities/evaluators/crew_evaluator_handler.py`.
+//!
+//! [`Crew::test`](crate::crew::Crew::test) hands every run's task outputs
+//! to a [`CrewEvaluator`], which has an evaluator LLM score them from 1 to
+//! 10 against the tasks' expected output. The [`EvaluationReport`]
+//! aggregates the scores per task, per agent and per run, and prints as a
+//! table or serializes as JSON.
+
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::evaluate_task_output;
+use crate::task::Task;
+use crate::tasks::task_output::TaskOutput;
+
+/// Score of one task output in one test run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskEvaluation {
+    /// Name (or description) of the task.
+    pub task: String,
+    /// Role of the agent that produced the output.
+    pub agent: String,
+    /// Score from 1 to 10.
+    pub score: f64,
+    /// The evaluator's reasoning.
+    pub feedback: String,
+}
+
+/// Evaluations of one test run, in task order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestRun {
+    /// One evaluation per task output.
+    pub evaluations: Vec<TaskEvaluation>,
+    /// How long the crew took, in seconds.
+    pub execution_time: f64,
+}
+
+impl TestRun {
+    /// Mean score of the run's task outputs.
+    pub fn quality(&self) -> f64 {
+        mean(self.evaluations.iter().map(|e| e.score))
+    }
+}
+
+/// Collects the evaluations of a crew's test runs.
+#[derive(Debug, Clone)]
+pub struct CrewEvaluator {
+    eval_llm: String,
+    runs: Vec<TestRun>,
+}
+
+impl CrewEvaluator {
+    /// Create an evaluator that scores outputs with `eval_llm`.
+    pub fn new(eval_llm: impl Into<String>) -> Self {
+        Self {
+            eval_llm: eval_llm.into(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Model the outputs are scored with.
+    pub fn eval_llm(&self) -> &str {
+        &self.eval_llm
+    }
+
+    /// Runs recorded so far.
+    pub fn runs(&self) -> &[TestRun] {
+        &self.runs
+    }
+
+    /// Score the task outputs of one run against the tasks they came from
+    /// and record the run.
+    pub fn evaluate(
+        &mut self,
+        tasks: &[Task],
+        outputs: &[TaskOutput],
+        execution_time: Duration,
+    ) -> Result<&TestRun, String> {
+        let evaluations = outputs
+            .iter()
+            .enumerate()
+            .map(|(i, output)| {
+                let task = tasks
+                    .iter()
+                    .find(|task| task.description == output.description)
+                    .or_else(|| tasks.get(i))
+                    .ok_or_else(|| format!("No task for output '{}'", output.description))?;
+                let summary = evaluate_task_output(&self.eval_llm, task, output)?;
+                Ok(TaskEvaluation {
+                    task: summary.label,
+                    agent: output.agent.clone(),
+                    score: summary.score,
+                    feedback: summary.feedback,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(self.record(TestRun {
+            evaluations,
+            execution_time: execution_time.as_secs_f64(),
+        }))
+    }
+
+    /// Record a run scored elsewhere.
+    pub fn record(&mut self, run: TestRun) -> &TestRun {
+        self.runs.push(run);
+        &self.runs[self.runs.len() - 1]
+    }
+
+    /// Scores aggregated over the recorded runs.
+    pub fn report(&self) -> EvaluationReport {
+        let n_tasks = self
+            .runs
+            .iter()
+            .map(|run| run.evaluations.len())
+            .max()
+            .unwrap_or(0);
+        let tasks = (0..n_tasks)
+            .map(|i| {
+                let evaluations: Vec<&TaskEvaluation> = self
+                    .runs
+                    .iter()
+                    .filter_map(|run| run.evaluations.get(i))
+                    .collect();
+                TaskScores {
+                    task: evaluations[0].task.clone(),
+                    agent: evaluations[0].agent.clone(),
+                    scores: evaluations.iter().map(|e| e.score).collect(),
+                    average: mean(evaluations.iter().map(|e| e.score)),
+                    feedback: evaluations.iter().map(|e| e.feedback.clone()).collect(),
+                }
+            })
+            .collect();
+
+        let mut agents: Vec<AgentScore> = Vec::new();
+        for evaluation in self.runs.iter().flat_map(|run| &run.evaluations) {
+            if !agents.iter().any(|a| a.agent == evaluation.agent) {
+                let scores = self
+                    .runs
+                    .iter()
+                    .flat_map(|run| &run.evaluations)
+                    .filter(|e| e.agent == evaluation.agent)
+                    .map(|e| e.score);
+                agents.push(AgentScore {
+                    agent: evaluation.agent.clone(),
+                    outputs: scores.clone().count(),
+                    average: mean(scores),
+                });
+            }
+        }
+
+        let crew_scores: Vec<f64> = self.runs.iter().map(TestRun::quality).collect();
+        EvaluationReport {
+            eval_llm: self.eval_llm.clone(),
+            runs: self.runs.len(),
+            crew_average: mean(crew_scores.iter().copied()),
+            crew_scores,
+            execution_times: self.runs.iter().map(|run| run.execution_time).collect(),
+            tasks,
+            agents,
+        }
+    }
+}
+
+/// Scores of one task across the test runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskScores {
+    /// Name (or description) of the task.
+    pub task: String,
+    /// Role of the agent that ran the task.
+    pub agent: String,
+    /// Score of each run.
+    pub scores: Vec<f64>,
+    /// Mean of `scores`.
+    pub average: f64,
+    /// The evaluator's feedback of each run.
+    pub feedback: Vec<String>,
+}
+
+/// Mean score of an agent's outputs across the test runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentScore {
+    /// Role of the agent.
+    pub agent: String,
+    /// Number of outputs scored.
+    pub outputs: usize,
+    /// Mean score of the outputs.
+    pub average: f64,
+}
+
+/// Scores of a crew's test runs, per task, per agent and per run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationReport {
+    /// Model the outputs were scored with.
+    pub eval_llm: String,
+    /// Number of test runs.
+    pub runs: usize,
+    /// Scores per task, in task order.
+    pub tasks: Vec<TaskScores>,
+    /// Scores per agent, in order of their first task.
+    pub agents: Vec<AgentScore>,
+    /// Mean task score of each run.
+    pub crew_scores: Vec<f64>,
+    /// Mean of `crew_scores`.
+    pub crew_average: f64,
+    /// Execution time of each run, in seconds.
+    pub execution_times: Vec<f64>,
+}
+
+impl fmt::Display for EvaluationReport {
+    /// A table of the task scores per run, followed by the agent averages.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<40}", "Task")?;
+        for run in 1..=self.runs {
+            write!(f, " {:>6}", format!("Run {}", run))?;
+        }
+        writeln!(f, " {:>6}  Agent", "Avg.")?;
+        for task in &self.tasks {
+            write!(f, "{:<40}", truncate(&task.task, 40))?;
+            for score in &task.scores {
+                write!(f, " {:>6.1}", score)?;
+            }
+            writeln!(f, " {:>6.1}  {}", task.average, task.agent)?;
+        }
+        write!(f, "{:<40}", "Crew")?;
+        for score in &self.crew_scores {
+            write!(f, " {:>6.1}", score)?;
+        }
+        writeln!(f, " {:>6.1}", self.crew_average)?;
+        write!(f, "{:<40}", "Execution Time (s)")?;
+        for secs in &self.execution_times {
+            write!(f, " {:>6.1}", secs)?;
+        }
+        writeln!(f, " {:>6.1}", mean(self.execution_times.iter().copied()))?;
+
+        writeln!(f, "\n{:<40} {:>6} {:>6}", "Agent", "Scored", "Avg.")?;
+        for agent in &self.agents {
+            writeln!(
+                f,
+                "{:<40} {:>6} {:>6.1}",
+                truncate(&agent.agent, 40),
+                agent.outputs,
+                agent.average
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Mean of `values`, or 0 without any.
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// `text` cut to at most `max` characters.
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max - 3).collect();
+    cut.push_str("...");
+    cut
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluation(task: &str, agent: &str, score: f64) -> TaskEvaluation {
+        TaskEvaluation {
+            task: task.to_string(),
+            agent: agent.to_string(),
+            score,
+            feedback: format!("{} scored {}", task, score),
+        }
+    }
+
+    #[test]
+    fn test_report_aggregates_runs_per_task_and_agent() {
+        let mut evaluator = CrewEvaluator::new("gpt-4o-mini");
+        evaluator.record(TestRun {
+            evaluations: vec![
+                evaluation("Research", "Researcher", 8.0),
+                evaluation("Report", "Writer", 6.0),
+            ],
+            execution_time: 2.0,
+        });
+        let run = evaluator.record(TestRun {
+            evaluations: vec![
+                evaluation("Research", "Researcher", 9.0),
+                evaluation("Report", "Writer", 9.0),
+            ],
+            execution_time: 4.0,
+        });
+        assert_eq!(run.quality(), 9.0);
+
+        let report = evaluator.report();
+        assert_eq!(report.runs, 2);
+        assert_eq!(report.tasks[0].scores, [8.0, 9.0]);
+        assert_eq!(report.tasks[1].average, 7.5);
+        assert_eq!(report.tasks[1].feedback[0], "Report scored 6");
+        assert_eq!(report.agents[0].agent, "Researcher");
+        assert_eq!(report.agents[0].average, 8.5);
+        assert_eq!(report.crew_scores, [7.0, 9.0]);
+        assert_eq!(report.crew_average, 8.0);
+
+        let table = report.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("Task") && lines[0].contains("Run 2"));
+        assert!(lines[1].starts_with("Research") && lines[1].ends_with("8.5  Researcher"));
+        assert!(lines[3].starts_with("Crew") && lines[3].ends_with("8.0"));
+        assert!(lines[4].starts_with("Execution Time (s)") && lines[4].ends_with("3.0"));
+        assert!(lines[8].starts_with("Writer") && lines[8].ends_with("2    7.5"));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["tasks"][0]["agent"], "Researcher");
+        assert_eq!(json["eval_llm"], "gpt-4o-mini");
+    }
+}
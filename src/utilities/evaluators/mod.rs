@@ -2,8 +2,21 @@
 //!
 //! Corresponds to `crewai/utilities/evaluators/`.
 
+pub mod crew_evaluator;
+pub mod task_evaluator;
+
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::task::Task;
+use crate::tasks::task_output::TaskOutput;
+
+pub use crew_evaluator::{
+    AgentScore, CrewEvaluator, EvaluationReport, TaskEvaluation, TaskScores, TestRun,
+};
+pub use task_evaluator::{evaluate_training_data, TrainingTaskEvaluation};
+
 /// Summary of an evaluation run.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluationSummary {
@@ -22,3 +35,73 @@ pub trait Evaluator {
     /// Run the evaluation and return a summary.
     fn evaluate(&self) -> EvaluationSummary;
 }
+
+/// Have `model` score `output` of `task` from 1 to 10 on completion,
+/// quality and overall performance against the task's expected output.
+///
+/// The summary is labelled with the task's name (or description) and
+/// identified by the task id.
+pub fn evaluate_task_output(
+    model: &str,
+    task: &Task,
+    output: &TaskOutput,
+) -> Result<EvaluationSummary, String> {
+    let prompt = format!(
+        "Based on the task description and the expected output, evaluate the \
+         agent's output with a score from 1 to 10 on completion, quality and overall \
+         performance. Answer with only a JSON object with \"score\" (a number) and \
+         \"feedback\" (one or two sentences).\n\n\
+         Task description:\n{}\n\nExpected output:\n{}\n\nAgent output:\n{}",
+        task.description, task.expected_output, output.raw
+    );
+    let message = HashMap::from([
+        ("role".to_string(), "user".to_string()),
+        ("content".to_string(), prompt),
+    ]);
+    let response = crate::llm::LLM::new(model).call(&[message], None)?;
+    let (score, feedback) = parse_score(&response)
+        .ok_or_else(|| format!("Evaluator returned no score: {}", response.trim()))?;
+    Ok(EvaluationSummary {
+        entity_id: task.id.to_string(),
+        label: task
+            .name
+            .clone()
+            .unwrap_or_else(|| task.description.clone()),
+        score,
+        feedback,
+    })
+}
+
+/// Score (clamped to 1-10) and feedback from an evaluator's answer: a JSON
+/// object, or else the first number in the text.
+fn parse_score(response: &str) -> Option<(f64, String)> {
+    let json = crate::utilities::normalize::strip_code_fence(response);
+    let (score, feedback) = match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(value) => (
+            value["score"].as_f64()?,
+            value["feedback"].as_str().unwrap_or_default().to_string(),
+        ),
+        Err(_) => {
+            let number = response
+                .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .find_map(|word| word.trim_matches('.').parse::<f64>().ok())?;
+            (number, response.trim().to_string())
+        }
+    };
+    Some((score.clamp(1.0, 10.0), feedback))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_score() {
+        let (score, feedback) =
+            parse_score("```json\n{\"score\": 8.5, \"feedback\": \"Thorough.\"}\n```").unwrap();
+        assert_eq!(score, 8.5);
+        assert_eq!(feedback, "Thorough.");
+        assert_eq!(parse_score("I'd give it 12/10.").unwrap().0, 10.0);
+        assert!(parse_score("No idea.").is_none());
+    }
+}
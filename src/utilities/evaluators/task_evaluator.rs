@@ -0,0 +1,161 @@
+//! Distills an agent's training data into suggestions.
+//!
+//! Corresponds to `TaskEvaluator.evaluate_training_data` in
+//! `crewai/utilities/evaluators/task_evaluator.py`.
+//!
+//! The agent's LLM reads every output recorded for the agent during
+//! training, the human feedback on it and, from the next iteration of the
+//! same task, the output produced with that feedback applied. It answers
+//! with instructions for future tasks and a quality score.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::llms::base_llm::LLMMessage;
+
+/// Suggestions distilled from an agent's training.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingTaskEvaluation {
+    /// Instructions the agent follows in future tasks.
+    pub suggestions: Vec<String>,
+    /// Score from 0 to 10 of the improvement the feedback brought.
+    pub quality: f64,
+    /// Summary of the training.
+    pub final_summary: String,
+}
+
+/// Evaluate the training `entries` recorded for the agent `role` (see
+/// [`CrewTrainingHandler`](crate::utilities::training_handler::CrewTrainingHandler)),
+/// calling the LLM with `call`.
+pub fn evaluate_training_data(
+    role: &str,
+    entries: &[Value],
+    call: &mut dyn FnMut(Vec<LLMMessage>) -> Result<String, String>,
+) -> Result<TrainingTaskEvaluation, String> {
+    if entries.is_empty() {
+        return Err(format!("No training data for agent '{}'", role));
+    }
+    let prompt = format!(
+        "Assess the quality of the training data of the agent '{}' based on its \
+         initial outputs, the human feedback on them and its improved outputs.\n\n\
+         {}\
+         Please provide:\n\
+         - A list of clear, actionable instructions derived from the human feedback \
+         to enhance the agent's performance. Analyze the differences between initial \
+         outputs and improved outputs to generate specific action items for future \
+         tasks. Ensure all key and specific points from the human feedback are \
+         incorporated into these instructions.\n\
+         - A score from 0 to 10 evaluating on completion, quality, and overall \
+         performance from the improved output to the initial output based on the \
+         human feedback.\n\
+         - A final summary of the training.\n\n\
+         Answer with only a JSON object with \"suggestions\" (a list of strings), \
+         \"quality\" (a number) and \"final_summary\" (a string).",
+        role,
+        aggregate(entries)
+    );
+    let message = HashMap::from([
+        ("role".to_string(), Value::String("user".to_string())),
+        ("content".to_string(), Value::String(prompt)),
+    ]);
+    let response = call(vec![message])?;
+    let json = crate::utilities::normalize::strip_code_fence(&response);
+    serde_json::from_str(json).map_err(|e| {
+        format!(
+            "Failed to parse training evaluation ({}): {}",
+            e,
+            response.trim()
+        )
+    })
+}
+
+/// The entries as text, each with the output of the same task in the next
+/// iteration as its improved output.
+fn aggregate(entries: &[Value]) -> String {
+    let mut text = String::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let improved = entries[i + 1..].iter().find(|later| {
+            later["task"] == entry["task"]
+                && later["iteration"].as_u64() > entry["iteration"].as_u64()
+        });
+        text.push_str(&format!(
+            "Iteration: {}\nTask: {}\nInitial Output:\n{}\n\nHuman Feedback:\n{}\n\n",
+            entry["iteration"],
+            entry["task"].as_str().unwrap_or_default(),
+            entry["initial_output"].as_str().unwrap_or_default(),
+            entry["human_feedback"].as_str().unwrap_or_default(),
+        ));
+        if let Some(improved) = improved {
+            text.push_str(&format!(
+                "Improved Output:\n{}\n\n",
+                improved["initial_output"].as_str().unwrap_or_default()
+            ));
+        }
+        text.push_str("------------------------------------------------\n\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::training_handler::CrewTrainingHandler;
+
+    #[test]
+    fn test_training_feeds_future_prompts() {
+        let dir = tempfile::tempdir().unwrap();
+        let training = CrewTrainingHandler::for_file(dir.path().join("training.json"));
+        for (iteration, output, feedback) in [(0, "Short", "Add sources"), (1, "Long [1]", "")] {
+            let entry = serde_json::json!({
+                "iteration": iteration,
+                "task": "Research AI",
+                "initial_output": output,
+                "human_feedback": feedback,
+            });
+            training.append("Researcher", entry).unwrap();
+        }
+        assert_eq!(training.human_feedbacks("Researcher"), ["Add sources"]);
+
+        let mut prompt = String::new();
+        let reply = r#"```json
+{"suggestions": ["Cite sources"], "quality": 8, "final_summary": "Better sourced."}
+```"#;
+        let mut call = |messages: Vec<LLMMessage>| {
+            prompt = messages[0]["content"].as_str().unwrap().to_string();
+            Ok(reply.to_string())
+        };
+        let entries = training.entries("Researcher");
+        let evaluation = evaluate_training_data("Researcher", &entries, &mut call).unwrap();
+        assert!(evaluate_training_data("Writer", &[], &mut call).is_err());
+        assert!(prompt.contains("Human Feedback:\nAdd sources\n\nImproved Output:\nLong [1]"));
+        assert_eq!(evaluation.suggestions, ["Cite sources"]);
+
+        let trained_file = dir.path().join("trained.json");
+        let trained = CrewTrainingHandler::for_file(&trained_file);
+        trained
+            .save_trained_data("Researcher", serde_json::to_value(&evaluation).unwrap())
+            .unwrap();
+        assert_eq!(trained.suggestions("Researcher"), ["Cite sources"]);
+
+        let prompt = crate::agent::utils::apply_training_data(
+            "Researcher",
+            "Research AI",
+            None,
+            trained_file.to_str().unwrap(),
+        );
+        assert_eq!(
+            prompt,
+            "Research AI\n\nYou MUST follow these instructions: \n - Cite sources"
+        );
+        let training_file = dir.path().join("training.json");
+        let prompt = crate::agent::utils::apply_training_data(
+            "Researcher",
+            "Research AI",
+            training_file.to_str(),
+            trained_file.to_str().unwrap(),
+        );
+        assert!(prompt.ends_with("instructions: \n - Add sources"));
+    }
+}
@@ -0,0 +1,298 @@
+//! Per-execution feature flags.
+//!
+//! Flags are resolved through an ordered list of [`FlagProvider`]s (static
+//! maps, environment variables, or a remote flag service) and every flag
+//! that is checked during an execution is recorded, so output differences
+//! can be correlated with the flag states that produced them.
+//!
+//! A crew installs its flags for the duration of `kickoff()`; hooks, tools
+//! and prompt construction read them through [`current`] or [`enabled`]:
+//!
+//! ```ignore
+//! use crewai::utilities::feature_flags;
+//!
+//! let prompt = if feature_flags::enabled("use_new_prompt_v2") {
+//!     build_prompt_v2()
+//! } else {
+//!     build_prompt()
+//! };
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::sync::{Arc, Mutex};
+
+/// Environment variable prefix read by [`EnvFlagProvider::default`].
+pub const DEFAULT_ENV_PREFIX: &str = "CREWAI_FLAG_";
+
+/// A source of feature flag values.
+///
+/// Returning `None` defers to the next provider. Remote providers should
+/// cache or poll in the background; `is_enabled` is called synchronously
+/// on the execution path.
+pub trait FlagProvider: Send + Sync {
+    /// Provider name (for logging).
+    fn name(&self) -> &str;
+
+    /// Look up a flag.
+    fn is_enabled(&self, flag: &str) -> Option<bool>;
+}
+
+/// Flags from a fixed in-memory map.
+#[derive(Debug, Clone, Default)]
+pub struct StaticFlagProvider {
+    flags: HashMap<String, bool>,
+}
+
+impl StaticFlagProvider {
+    /// Create a provider from a map of flag name to state.
+    pub fn new(flags: HashMap<String, bool>) -> Self {
+        Self { flags }
+    }
+}
+
+impl FlagProvider for StaticFlagProvider {
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    fn is_enabled(&self, flag: &str) -> Option<bool> {
+        self.flags.get(flag).copied()
+    }
+}
+
+/// Flags from environment variables.
+///
+/// `use_new_prompt_v2` is read from `CREWAI_FLAG_USE_NEW_PROMPT_V2`.
+/// `1`/`true`/`on`/`yes` enable a flag and `0`/`false`/`off`/`no` disable
+/// it; any other value is ignored.
+#[derive(Debug, Clone)]
+pub struct EnvFlagProvider {
+    prefix: String,
+}
+
+impl EnvFlagProvider {
+    /// Create a provider reading variables with the given prefix.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// The environment variable a flag is read from.
+    pub fn var_name(&self, flag: &str) -> String {
+        let normalized: String = flag
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        format!("{}{}", self.prefix, normalized)
+    }
+}
+
+impl Default for EnvFlagProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENV_PREFIX)
+    }
+}
+
+impl FlagProvider for EnvFlagProvider {
+    fn name(&self) -> &str {
+        "env"
+    }
+
+    fn is_enabled(&self, flag: &str) -> Option<bool> {
+        let value = env::var(self.var_name(flag)).ok()?;
+        match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "on" | "yes" => Some(true),
+            "0" | "false" | "off" | "no" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A layered set of flag providers plus a record of evaluated flags.
+///
+/// Providers are consulted in order and the first answer wins; unknown
+/// flags are disabled. Clones share the evaluation record.
+#[derive(Clone)]
+pub struct FeatureFlags {
+    providers: Vec<Arc<dyn FlagProvider>>,
+    evaluated: Arc<Mutex<BTreeMap<String, bool>>>,
+}
+
+impl FeatureFlags {
+    /// Create flags with no providers (every flag disabled).
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            evaluated: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Builder: add a provider, consulted after the existing ones.
+    pub fn with_provider(mut self, provider: impl FlagProvider + 'static) -> Self {
+        self.providers.push(Arc::new(provider));
+        self
+    }
+
+    /// Builder: add a static map of flags, consulted after the existing providers.
+    pub fn with_static(self, flags: HashMap<String, bool>) -> Self {
+        self.with_provider(StaticFlagProvider::new(flags))
+    }
+
+    /// Builder: force a flag on or off, ahead of every provider.
+    pub fn with_override(mut self, flag: impl Into<String>, enabled: bool) -> Self {
+        let provider = StaticFlagProvider::new(HashMap::from([(flag.into(), enabled)]));
+        self.providers.insert(0, Arc::new(provider));
+        self
+    }
+
+    /// Check whether a flag is enabled, recording the result.
+    pub fn enabled(&self, flag: &str) -> bool {
+        let (enabled, source) = self
+            .providers
+            .iter()
+            .find_map(|p| p.is_enabled(flag).map(|on| (on, p.name())))
+            .unwrap_or((false, "default"));
+        log::trace!("Feature flag {}={} (from {})", flag, enabled, source);
+        self.evaluated
+            .lock()
+            .unwrap()
+            .insert(flag.to_string(), enabled);
+        enabled
+    }
+
+    /// Flags checked so far, with the state each resolved to.
+    pub fn evaluated(&self) -> BTreeMap<String, bool> {
+        self.evaluated.lock().unwrap().clone()
+    }
+
+    /// Same providers with an empty evaluation record, for a new execution.
+    pub fn for_execution(&self) -> Self {
+        Self {
+            providers: self.providers.clone(),
+            evaluated: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+}
+
+impl Default for FeatureFlags {
+    /// Flags read from `CREWAI_FLAG_*` environment variables.
+    fn default() -> Self {
+        Self::new().with_provider(EnvFlagProvider::default())
+    }
+}
+
+impl std::fmt::Debug for FeatureFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let providers: Vec<&str> = self.providers.iter().map(|p| p.name()).collect();
+        f.debug_struct("FeatureFlags")
+            .field("providers", &providers)
+            .field("evaluated", &self.evaluated())
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Current execution
+// ---------------------------------------------------------------------------
+
+/// Flags of the execution currently in progress.
+static CURRENT_FLAGS: Mutex<Option<FeatureFlags>> = Mutex::new(None);
+
+/// The flags of the current execution, or environment flags outside one.
+pub fn current() -> FeatureFlags {
+    CURRENT_FLAGS.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Check a flag against the current execution's flags.
+pub fn enabled(flag: &str) -> bool {
+    current().enabled(flag)
+}
+
+/// RAII guard installing flags as the current execution's flags.
+///
+/// When dropped, restores the previously installed flags.
+pub struct FlagScope {
+    previous: Option<FeatureFlags>,
+}
+
+impl FlagScope {
+    /// Install `flags` until the returned guard is dropped.
+    pub fn enter(flags: FeatureFlags) -> Self {
+        let mut current = CURRENT_FLAGS.lock().unwrap();
+        let previous = current.replace(flags);
+        FlagScope { previous }
+    }
+}
+
+impl Drop for FlagScope {
+    fn drop(&mut self) {
+        let mut current = CURRENT_FLAGS.lock().unwrap();
+        *current = self.previous.take();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_providers_layer_in_order() {
+        let flags = FeatureFlags::new()
+            .with_static(HashMap::from([
+                ("a".to_string(), true),
+                ("b".to_string(), false),
+            ]))
+            .with_static(HashMap::from([("b".to_string(), true)]))
+            .with_override("a", false);
+
+        assert!(!flags.enabled("a"));
+        assert!(!flags.enabled("b"));
+        assert!(!flags.enabled("unknown"));
+        assert_eq!(
+            flags.evaluated(),
+            BTreeMap::from([
+                ("a".to_string(), false),
+                ("b".to_string(), false),
+                ("unknown".to_string(), false),
+            ])
+        );
+        assert!(flags.for_execution().evaluated().is_empty());
+    }
+
+    #[test]
+    fn test_env_provider() {
+        let provider = EnvFlagProvider::new("CREWAI_TEST_FLAG_");
+        assert_eq!(
+            provider.var_name("use-new.prompt_v2"),
+            "CREWAI_TEST_FLAG_USE_NEW_PROMPT_V2"
+        );
+        env::set_var("CREWAI_TEST_FLAG_ON", "true");
+        env::set_var("CREWAI_TEST_FLAG_OFF", "0");
+        env::set_var("CREWAI_TEST_FLAG_JUNK", "maybe");
+        assert_eq!(provider.is_enabled("on"), Some(true));
+        assert_eq!(provider.is_enabled("off"), Some(false));
+        assert_eq!(provider.is_enabled("junk"), None);
+        assert_eq!(provider.is_enabled("missing"), None);
+    }
+
+    #[test]
+    fn test_scope_records_into_installed_flags() {
+        let flags = FeatureFlags::new().with_override("scoped_flag", true);
+        {
+            let _scope = FlagScope::enter(flags.clone());
+            assert!(enabled("scoped_flag"));
+        }
+        assert_eq!(
+            flags.evaluated(),
+            BTreeMap::from([("scoped_flag".to_string(), true)])
+        );
+    }
+}
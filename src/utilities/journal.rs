@@ -0,0 +1,503 @@
+//! Execution journal for deterministic replay of crew runs.
+//!
+//! While recording, every LLM response, tool output and the run's random
+//! seed is appended to a JSON Lines file as it happens, so the journal of a
+//! run that crashed is complete up to the crash. Replaying a journal
+//! re-executes the crew with the journaled responses and tool outputs in
+//! place of live calls: no provider is contacted and no tool runs, which
+//! makes a production incident reproducible on a laptop and a test
+//! deterministic.
+//!
+//! The seed starts the run's random stream ([`next_random`]), which chaos
+//! fault injection without a seed of its own and the jitter of LLM retries
+//! draw from. A run recorded with
+//! [`record_with_seed`](ExecutionJournal::record_with_seed) and the same seed
+//! thus sees the same faults and backoff when run live again.
+//!
+//! The journal is current on the thread running the kickoff and on the
+//! threads it starts for asynchronous tasks and interruptible calls, so
+//! crews running side by side keep their own journals.
+//!
+//! LLM calls are matched to journal entries by their request key (see
+//! [`content_key`](crate::llms::coalescing::content_key)), so tasks running
+//! concurrently replay correctly whatever order they call in. A request
+//! without a matching entry (e.g. after the prompt was edited) takes the
+//! next unused response in journal order. Tool calls are matched by tool
+//! name and input.
+//!
+//! ```no_run
+//! use crewai::Crew;
+//! # let (tasks, agents) = (Vec::new(), Vec::new());
+//! // In production:
+//! let crew = Crew::new(tasks, agents).journal_file("runs/incident.jsonl");
+//! # let (tasks, agents) = (Vec::new(), Vec::new());
+//! // Later, without network access:
+//! let crew = Crew::new(tasks, agents).replay_journal("runs/incident.jsonl");
+//! ```
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A journaled event of a crew run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEntry {
+    /// The run's random seed, the first entry of every journal.
+    Seed { seed: u64 },
+    /// An LLM call and its response or error.
+    LlmCall {
+        request_key: String,
+        agent: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        response: Option<Value>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A tool call and its output.
+    ToolCall {
+        agent: String,
+        tool: String,
+        input: Value,
+        output: String,
+    },
+}
+
+#[derive(Debug)]
+enum Mode {
+    /// Appending entries to a file, if one is configured.
+    Record(Option<File>),
+    /// Serving entries; `used[i]` is set once entry `i` was replayed.
+    Replay { used: Vec<bool> },
+}
+
+#[derive(Debug)]
+struct JournalState {
+    entries: Vec<JournalEntry>,
+    mode: Mode,
+    /// State of the run's random stream.
+    rng: u64,
+}
+
+/// The journal of a crew run, recording or replaying.
+///
+/// Clones share the same entries.
+#[derive(Debug, Clone)]
+pub struct ExecutionJournal {
+    path: Option<PathBuf>,
+    seed: u64,
+    state: Arc<Mutex<JournalState>>,
+}
+
+impl ExecutionJournal {
+    /// A journal recording to `path`, which is truncated, with a random
+    /// seed.
+    pub fn record(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        Self::record_with_seed(path, random_seed())
+    }
+
+    /// A journal recording to `path` with the given seed.
+    pub fn record_with_seed(path: impl Into<PathBuf>, seed: u64) -> std::io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let journal = Self::new(
+            Some(path.clone()),
+            seed,
+            Mode::Record(Some(File::create(&path)?)),
+        );
+        journal.append(JournalEntry::Seed { seed })?;
+        Ok(journal)
+    }
+
+    /// A journal recording in memory only.
+    pub fn in_memory(seed: u64) -> Self {
+        let journal = Self::new(None, seed, Mode::Record(None));
+        let _ = journal.append(JournalEntry::Seed { seed });
+        journal
+    }
+
+    /// A journal replaying the one recorded at `path`.
+    pub fn replay(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        let mut entries = Vec::new();
+        for (number, line) in BufReader::new(File::open(&path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line).map_err(|e| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", path.display(), number + 1, e),
+                )
+            })?;
+            entries.push(entry);
+        }
+        let mut journal = Self::from_entries(entries);
+        journal.path = Some(path);
+        Ok(journal)
+    }
+
+    /// A journal replaying `entries`.
+    pub fn from_entries(entries: Vec<JournalEntry>) -> Self {
+        let seed = entries
+            .iter()
+            .find_map(|entry| match entry {
+                JournalEntry::Seed { seed } => Some(*seed),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let used = vec![false; entries.len()];
+        let journal = Self::new(None, seed, Mode::Replay { used });
+        journal.state.lock().unwrap().entries = entries;
+        journal
+    }
+
+    fn new(path: Option<PathBuf>, seed: u64, mode: Mode) -> Self {
+        Self {
+            path,
+            seed,
+            state: Arc::new(Mutex::new(JournalState {
+                entries: Vec::new(),
+                mode,
+                rng: seed,
+            })),
+        }
+    }
+
+    /// The journal file, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The run's random seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The next number of the run's random stream, which starts from the
+    /// seed.
+    pub fn next_random(&self) -> u64 {
+        splitmix64(&mut self.state.lock().unwrap().rng)
+    }
+
+    /// Whether responses are served from the journal instead of live calls.
+    pub fn is_replaying(&self) -> bool {
+        matches!(self.state.lock().unwrap().mode, Mode::Replay { .. })
+    }
+
+    /// All entries recorded or loaded.
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.state.lock().unwrap().entries.clone()
+    }
+
+    /// The response to the LLM request `request_key`.
+    ///
+    /// While recording, makes the request with `call` and journals its
+    /// result; while replaying, returns the journaled result without
+    /// calling.
+    pub fn llm_call<F>(
+        &self,
+        request_key: &str,
+        agent: &str,
+        model: Option<&str>,
+        call: F,
+    ) -> Result<Value, String>
+    where
+        F: FnOnce() -> Result<Value, String>,
+    {
+        if let Some(entry) = self.take(|entry, exact| match entry {
+            JournalEntry::LlmCall {
+                request_key: key, ..
+            } => !exact || key == request_key,
+            _ => false,
+        }) {
+            let JournalEntry::LlmCall {
+                request_key: key,
+                response,
+                error,
+                ..
+            } = entry
+            else {
+                unreachable!("only LLM calls are taken");
+            };
+            if key != request_key {
+                log::warn!(
+                    "Replayed LLM request differs from the journaled one; using the next journaled response"
+                );
+            }
+            return match error {
+                Some(error) => Err(error),
+                None => Ok(response.unwrap_or(Value::Null)),
+            };
+        }
+        if self.is_replaying() {
+            return Err(format!(
+                "Journal has no recorded response for LLM request {}",
+                request_key
+            ));
+        }
+
+        let result = call();
+        let (response, error) = match &result {
+            Ok(response) => (Some(response.clone()), None),
+            Err(error) => (None, Some(error.clone())),
+        };
+        self.record_entry(JournalEntry::LlmCall {
+            request_key: request_key.to_string(),
+            agent: agent.to_string(),
+            model: model.map(str::to_string),
+            response,
+            error,
+        });
+        result
+    }
+
+    /// The output of running `tool` with `input`.
+    ///
+    /// While recording, runs the tool with `run` and journals its output;
+    /// while replaying, returns the journaled output without running it.
+    pub fn tool_call<F, E>(
+        &self,
+        agent: &str,
+        tool: &str,
+        input: &Value,
+        run: F,
+    ) -> Result<String, E>
+    where
+        F: FnOnce() -> Result<String, E>,
+        E: From<String>,
+    {
+        let matches = |entry: &JournalEntry, _exact: bool| {
+            matches!(entry, JournalEntry::ToolCall { tool: t, input: i, .. }
+                if t == tool && i == input)
+        };
+        if let Some(JournalEntry::ToolCall { output, .. }) = self.take(matches) {
+            return Ok(output);
+        }
+        if self.is_replaying() {
+            return Err(E::from(format!(
+                "Journal has no recorded output for tool '{}' with input {}",
+                tool, input
+            )));
+        }
+
+        let output = run()?;
+        self.record_entry(JournalEntry::ToolCall {
+            agent: agent.to_string(),
+            tool: tool.to_string(),
+            input: input.clone(),
+            output: output.clone(),
+        });
+        Ok(output)
+    }
+
+    /// While replaying, the first unused entry `matches` accepts exactly
+    /// (`true`), or failing that loosely (`false`), marked as used.
+    fn take(&self, matches: impl Fn(&JournalEntry, bool) -> bool) -> Option<JournalEntry> {
+        let mut state = self.state.lock().unwrap();
+        let JournalState { entries, mode, .. } = &mut *state;
+        let Mode::Replay { used } = mode else {
+            return None;
+        };
+        let index = [true, false].into_iter().find_map(|exact| {
+            entries
+                .iter()
+                .zip(used.iter())
+                .position(|(entry, used)| !used && matches(entry, exact))
+        })?;
+        used[index] = true;
+        Some(entries[index].clone())
+    }
+
+    fn record_entry(&self, entry: JournalEntry) {
+        if let Err(e) = self.append(entry) {
+            log::warn!("Failed to write execution journal: {}", e);
+        }
+    }
+
+    /// Append `entry`, writing it to the file at once.
+    fn append(&self, entry: JournalEntry) -> std::io::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if let Mode::Record(Some(file)) = &mut state.mode {
+            let line = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+        state.entries.push(entry);
+        Ok(())
+    }
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Advance `state` and return the next number of its splitmix64 stream.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// ---------------------------------------------------------------------------
+// Current run
+// ---------------------------------------------------------------------------
+
+thread_local! {
+    /// Journal of the run in progress on this thread.
+    static CURRENT_JOURNAL: RefCell<Option<ExecutionJournal>> = const { RefCell::new(None) };
+}
+
+/// The journal of the run on this thread, if one is installed.
+pub fn current() -> Option<ExecutionJournal> {
+    CURRENT_JOURNAL.with(|journal| journal.borrow().clone())
+}
+
+/// The random seed of the run on this thread. `None` outside a journaled
+/// run.
+pub fn seed() -> Option<u64> {
+    current().map(|journal| journal.seed())
+}
+
+/// The next number of the random stream of the run on this thread. `None`
+/// outside a journaled run.
+pub fn next_random() -> Option<u64> {
+    current().map(|journal| journal.next_random())
+}
+
+/// RAII guard installing a journal as the journal of the run on this
+/// thread.
+///
+/// When dropped, restores the previously installed journal.
+pub struct JournalScope {
+    previous: Option<ExecutionJournal>,
+}
+
+impl JournalScope {
+    /// Install `journal` until the returned guard is dropped.
+    pub fn enter(journal: ExecutionJournal) -> Self {
+        let previous = CURRENT_JOURNAL.with(|current| current.borrow_mut().replace(journal));
+        JournalScope { previous }
+    }
+}
+
+impl Drop for JournalScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_JOURNAL.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_replay_serves_journaled_calls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("runs").join("run.jsonl");
+
+        let journal = ExecutionJournal::record_with_seed(&path, 42).unwrap();
+        let first = journal.llm_call("k1", "Researcher", Some("gpt-4o"), || {
+            Ok(json!("Action: search"))
+        });
+        let searched =
+            journal.tool_call::<_, String>("Researcher", "search", &json!({"q": "rust"}), || {
+                Ok("Rust is a language".to_string())
+            });
+        let failed = journal.llm_call("k2", "Researcher", None, || Err("429".to_string()));
+        assert_eq!(first, Ok(json!("Action: search")));
+        assert_eq!(journal.entries().len(), 4);
+
+        let replay = ExecutionJournal::replay(&path).unwrap();
+        assert!(replay.is_replaying());
+        assert_eq!(replay.seed(), 42);
+        assert_eq!(replay.entries(), journal.entries());
+        let live = |_: &str| -> Result<Value, String> { panic!("no live calls on replay") };
+        // Matched by request key, whatever the order.
+        assert_eq!(
+            replay.llm_call("k2", "Researcher", None, || live("k2")),
+            failed
+        );
+        assert_eq!(
+            replay.tool_call::<_, String>("Researcher", "search", &json!({"q": "rust"}), || {
+                panic!("no tool runs on replay")
+            }),
+            searched
+        );
+        // An edited prompt takes the next unused response.
+        assert_eq!(
+            replay.llm_call("edited", "Researcher", None, || live("edited")),
+            first
+        );
+        assert!(replay
+            .llm_call("k3", "Researcher", None, || live("k3"))
+            .unwrap_err()
+            .contains("no recorded response"));
+
+        assert_eq!(seed(), None);
+        {
+            let _scope = JournalScope::enter(replay);
+            assert_eq!(seed(), Some(42));
+            assert_eq!(next_random(), Some(journal.next_random()));
+            std::thread::spawn(|| assert_eq!(seed(), None))
+                .join()
+                .unwrap();
+        }
+        assert_eq!(seed(), None);
+    }
+
+    #[test]
+    fn test_crew_replays_journal() {
+        use crate::agent::Agent;
+        use crate::crew::Crew;
+        use crate::task::Task;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("incident.jsonl");
+        let entries = [
+            json!({"type": "seed", "seed": 7}),
+            json!({
+                "type": "llm_call",
+                "request_key": "recorded",
+                "agent": "Researcher",
+                "response": "Thought: I know this.\nFinal Answer: Rust was first released in 2015.",
+            }),
+        ];
+        let lines: Vec<String> = entries.iter().map(Value::to_string).collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let researcher = Agent::new(
+            "Researcher".to_string(),
+            "Find release dates".to_string(),
+            "A historian of programming languages".to_string(),
+        );
+        let mut task = Task::new(
+            "When was Rust first released?".to_string(),
+            "A year".to_string(),
+        );
+        task.agent = Some("Researcher".to_string());
+        // No provider is reachable: every LLM call must come from the journal.
+        let mut crew = Crew::with_agents(vec![task], vec![researcher])
+            .replay_journal(path.display().to_string());
+        let output = crew.kickoff(None).unwrap();
+        assert_eq!(output.raw, "Rust was first released in 2015.");
+        assert_eq!(current().map(|j| j.seed()), None);
+    }
+}
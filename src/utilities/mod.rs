@@ -2,22 +2,32 @@
 //!
 //! Corresponds to `crewai/utilities/`.
 
+pub mod artifacts;
+pub mod cancellation;
 pub mod config;
 pub mod converter;
 pub mod crew;
 pub mod errors;
 pub mod evaluators;
 pub mod exceptions;
+pub mod feature_flags;
 pub mod file_handler;
 pub mod formatter;
 pub mod guardrail_types;
 pub mod i18n;
+pub mod journal;
 pub mod logger;
+pub mod normalize;
 pub mod paths;
+pub mod perf;
 pub mod printer;
+pub mod profiles;
+pub mod prompt_templates;
 pub mod prompts;
 pub mod pydantic_schema_utils;
+pub mod reasoning_handler;
 pub mod rpm_controller;
+pub mod run_log;
 pub mod string_utils;
 pub mod task_output_storage_handler;
 pub mod token_counter_callback;
@@ -0,0 +1,831 @@
+//! Normalization of dates, numbers and units in agent outputs.
+//!
+//! Agents extracting facts write the same value many ways ("March 5th,
+//! 2024", "05/03/2024", "1.234,5 €", "5 mi"), which breaks downstream joins.
+//! This module turns them into one canonical form:
+//!
+//! - [`normalize_date`]: RFC 3339 timestamps in UTC;
+//! - [`parse_number`]: plain numbers, honouring the locale's decimal
+//!   separator and grouping, signs, percentages and magnitude words;
+//! - [`convert_unit`] and [`normalize_quantity`]: quantities converted to a
+//!   target unit.
+//!
+//! The same functions are available to agents as tools
+//! ([`tools::builtin::normalize`](crate::tools::builtin::normalize)) and to
+//! tasks as an output post-processor: a task's [`Normalizer`] rewrites the
+//! named fields of its JSON output.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::tasks::task_output::TaskOutput;
+
+/// Error normalizing a value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NormalizeError {
+    /// The text is not a recognized date.
+    #[error("Unrecognized date: '{0}'")]
+    Date(String),
+    /// The text is not a number.
+    #[error("Not a number: '{0}'")]
+    Number(String),
+    /// The unit is not known.
+    #[error("Unknown unit: '{0}'")]
+    UnknownUnit(String),
+    /// The units measure different things.
+    #[error("Cannot convert {from} to {to}")]
+    Incompatible {
+        /// Source unit.
+        from: String,
+        /// Target unit.
+        to: String,
+    },
+}
+
+/// Conventions for reading numbers and numeric dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    /// Decimal separator (`.` or `,`).
+    pub decimal_separator: char,
+    /// Whether numeric dates put the day first (`05/03/2024` is 5 March).
+    pub day_first: bool,
+}
+
+impl Default for Locale {
+    /// `en-US`: decimal point, month first.
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            day_first: false,
+        }
+    }
+}
+
+/// Languages writing a decimal comma.
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "bg", "ca", "cs", "da", "de", "el", "es", "et", "fi", "fr", "hr", "hu", "id", "is", "it", "lt",
+    "lv", "nb", "nl", "nn", "no", "pl", "pt", "ro", "ru", "sk", "sl", "sr", "sv", "tr", "uk", "vi",
+];
+
+impl Locale {
+    /// Conventions of a BCP 47 tag such as `de-DE` or `en_GB`. English
+    /// (other than `en-US` and plain `en`) and most other languages put the
+    /// day first; unknown tags get the defaults.
+    pub fn parse(tag: &str) -> Self {
+        let tag = tag.replace('_', "-").to_ascii_lowercase();
+        let (language, region) = tag.split_once('-').unwrap_or((&tag, ""));
+        let decimal_separator = if DECIMAL_COMMA_LANGUAGES.contains(&language) {
+            ','
+        } else {
+            '.'
+        };
+        let day_first = match language {
+            "en" => !matches!(region, "" | "us" | "ph"),
+            "ja" | "zh" | "ko" | "" => false,
+            _ => true,
+        };
+        Self {
+            decimal_separator,
+            day_first,
+        }
+    }
+}
+
+// ============================================================================
+// Dates
+// ============================================================================
+
+/// Date-time layouts tried in order (read as UTC).
+const DATE_TIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Date layouts tried in order (midnight UTC).
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%Y.%m.%d",
+    "%Y%m%d",
+    "%B %d, %Y",
+    "%B %d %Y",
+    "%b %d, %Y",
+    "%b %d %Y",
+    "%d %B %Y",
+    "%d %b %Y",
+    "%d %B, %Y",
+    "%d %b, %Y",
+    "%d.%m.%Y",
+    "%B %Y",
+];
+
+/// Normalize a date or date-time to RFC 3339 in UTC
+/// (`2024-03-05T00:00:00Z`).
+///
+/// Accepts RFC 3339 and RFC 2822 timestamps, ISO dates and date-times,
+/// written-out months ("March 5th, 2024", "5 Mar 2024") and numeric dates,
+/// read day-first or month-first per `locale`. Dates without a time are
+/// midnight UTC; a month without a day is its first day.
+pub fn normalize_date(text: &str, locale: &Locale) -> Result<String, NormalizeError> {
+    parse_date(text, locale).map(|dt| dt.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// Parse a date as [`normalize_date`] does.
+pub fn parse_date(text: &str, locale: &Locale) -> Result<DateTime<Utc>, NormalizeError> {
+    let trimmed = text.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(trimmed) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    for format in DATE_TIME_FORMATS {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(trimmed, format) {
+            return Ok(dt.and_utc());
+        }
+    }
+
+    // "March 5th, 2024" → "March 5, 2024"; "Tuesday, 5 March" → "5 March".
+    let cleaned = strip_ordinals(trimmed);
+    let cleaned = strip_weekday(&cleaned);
+    let numeric = if locale.day_first {
+        ["%d/%m/%Y", "%d-%m-%Y", "%d/%m/%y"]
+    } else {
+        ["%m/%d/%Y", "%m-%d-%Y", "%m/%d/%y"]
+    };
+    let date = DATE_FORMATS
+        .iter()
+        .chain(&numeric)
+        .find_map(|format| parse_naive_date(cleaned, format))
+        .ok_or_else(|| NormalizeError::Date(text.to_string()))?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+fn parse_naive_date(text: &str, format: &str) -> Option<NaiveDate> {
+    if format == "%B %Y" {
+        // chrono needs a day; take the first of the month.
+        return NaiveDate::parse_from_str(&format!("1 {}", text), "%d %B %Y").ok();
+    }
+    NaiveDate::parse_from_str(text, format).ok()
+}
+
+fn strip_ordinals(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        out.push(chars[i]);
+        let is_digit = chars[i].is_ascii_digit();
+        let suffix: String = chars.get(i + 1..i + 3).unwrap_or_default().iter().collect();
+        let boundary = chars.get(i + 3).is_none_or(|c| !c.is_alphanumeric());
+        if is_digit
+            && boundary
+            && matches!(
+                suffix.to_ascii_lowercase().as_str(),
+                "st" | "nd" | "rd" | "th"
+            )
+        {
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+fn strip_weekday(text: &str) -> &str {
+    let lower = text.to_ascii_lowercase();
+    for day in [
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+        "sunday",
+    ] {
+        for name in [day, &day[..3]] {
+            if lower.starts_with(name) {
+                return text[name.len()..].trim_start_matches([',', ' ', '.']);
+            }
+        }
+    }
+    text
+}
+
+// ============================================================================
+// Numbers
+// ============================================================================
+
+/// Magnitude words and suffixes, longest first.
+const MAGNITUDES: &[(&str, f64)] = &[
+    ("trillion", 1e12),
+    ("billion", 1e9),
+    ("million", 1e6),
+    ("thousand", 1e3),
+    ("bn", 1e9),
+    ("mn", 1e6),
+    ("tn", 1e12),
+    ("k", 1e3),
+    ("m", 1e6),
+    ("b", 1e9),
+];
+
+/// Parse a number as written in `locale`.
+///
+/// Handles grouping (`1,234,567`, `1.234.567`, `1 234 567`, `1'234`),
+/// either decimal separator, currency symbols, a leading sign or
+/// accounting parentheses (`(12.5)` is negative), a trailing `%` (divided
+/// by 100) and magnitude words or suffixes (`3.2k`, `1.5 million`,
+/// `2bn`). A lone separator followed by exactly three digits that is not
+/// the locale's decimal separator is read as grouping (`1,500` is 1500 in
+/// `en-US`, 1.5 in `de-DE`).
+pub fn parse_number(text: &str, locale: &Locale) -> Result<f64, NormalizeError> {
+    let invalid = || NormalizeError::Number(text.to_string());
+    let mut s = text.trim().to_string();
+    let mut negative = false;
+    if s.starts_with('(') && s.ends_with(')') {
+        negative = true;
+        s = s[1..s.len() - 1].trim().to_string();
+    }
+    s.retain(|c| !matches!(c, '$' | '€' | '£' | '¥' | '₹' | '₩' | '₽' | '¢'));
+    let mut s = s.trim().to_string();
+    for sign in ['-', '−', '–'] {
+        if let Some(rest) = s.strip_prefix(sign) {
+            negative = !negative;
+            s = rest.trim_start().to_string();
+            break;
+        }
+    }
+    if let Some(rest) = s.strip_prefix('+') {
+        s = rest.trim_start().to_string();
+    }
+
+    let mut scale = 1.0;
+    if let Some(rest) = s.strip_suffix('%') {
+        scale = 0.01;
+        s = rest.trim_end().to_string();
+    } else {
+        let lower = s.to_lowercase();
+        if let Some((suffix, factor)) = MAGNITUDES.iter().find(|(suffix, _)| {
+            lower.ends_with(suffix)
+                && lower[..lower.len() - suffix.len()]
+                    .trim_end()
+                    .ends_with(|c: char| c.is_ascii_digit())
+        }) {
+            scale = *factor;
+            s.truncate(s.len() - suffix.len());
+            s = s.trim_end().to_string();
+        }
+    }
+
+    let digits: String = s
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '\u{a0}' | '\u{202f}' | '\'' | '’' | '_'))
+        .collect();
+    if digits.is_empty()
+        || !digits
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+    {
+        return Err(invalid());
+    }
+    let decimal = decimal_separator(&digits, locale);
+    let plain: String = digits
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_ascii_digit() => Some(c),
+            c if Some(c) == decimal => Some('.'),
+            _ => None,
+        })
+        .collect();
+    let value: f64 = plain.parse().map_err(|_| invalid())?;
+    let value = value * scale;
+    Ok(if negative { -value } else { value })
+}
+
+/// The decimal separator used in `digits`, if any.
+fn decimal_separator(digits: &str, locale: &Locale) -> Option<char> {
+    let dots = digits.matches('.').count();
+    let commas = digits.matches(',').count();
+    match (dots, commas) {
+        (0, 0) => None,
+        // Both present: the last one is the decimal separator.
+        (_, c) if c > 0 && dots > 0 => digits
+            .rfind(['.', ','])
+            .and_then(|i| digits[i..].chars().next()),
+        (1, 0) | (0, 1) => {
+            let sep = if dots == 1 { '.' } else { ',' };
+            let after = digits.len() - digits.find(sep).unwrap_or_default() - 1;
+            if sep != locale.decimal_separator && after == 3 {
+                None
+            } else {
+                Some(sep)
+            }
+        }
+        // Repeated: grouping.
+        _ => None,
+    }
+}
+
+// ============================================================================
+// Units
+// ============================================================================
+
+/// What a unit measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Volume,
+    Area,
+    Speed,
+    Data,
+    Temperature,
+}
+
+/// Units: aliases, dimension and size in the dimension's base unit (metre,
+/// kilogram, second, litre, square metre, metre per second, byte).
+/// Temperatures are converted separately.
+const UNITS: &[(&[&str], Dimension, f64)] = &[
+    (
+        &[
+            "mm",
+            "millimeter",
+            "millimetre",
+            "millimeters",
+            "millimetres",
+        ],
+        Dimension::Length,
+        1e-3,
+    ),
+    (
+        &[
+            "cm",
+            "centimeter",
+            "centimetre",
+            "centimeters",
+            "centimetres",
+        ],
+        Dimension::Length,
+        1e-2,
+    ),
+    (
+        &["m", "meter", "metre", "meters", "metres"],
+        Dimension::Length,
+        1.0,
+    ),
+    (
+        &["km", "kilometer", "kilometre", "kilometers", "kilometres"],
+        Dimension::Length,
+        1e3,
+    ),
+    (&["in", "inch", "inches", "\""], Dimension::Length, 0.0254),
+    (&["ft", "foot", "feet", "'"], Dimension::Length, 0.3048),
+    (&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    (&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    (
+        &["nmi", "nautical mile", "nautical miles"],
+        Dimension::Length,
+        1852.0,
+    ),
+    (&["mg", "milligram", "milligrams"], Dimension::Mass, 1e-6),
+    (
+        &["g", "gram", "grams", "gramme", "grammes"],
+        Dimension::Mass,
+        1e-3,
+    ),
+    (
+        &["kg", "kilogram", "kilograms", "kilo", "kilos"],
+        Dimension::Mass,
+        1.0,
+    ),
+    (
+        &["t", "tonne", "tonnes", "metric ton", "metric tons"],
+        Dimension::Mass,
+        1e3,
+    ),
+    (
+        &["oz", "ounce", "ounces"],
+        Dimension::Mass,
+        0.028_349_523_125,
+    ),
+    (
+        &["lb", "lbs", "pound", "pounds"],
+        Dimension::Mass,
+        0.453_592_37,
+    ),
+    (
+        &["ms", "millisecond", "milliseconds"],
+        Dimension::Time,
+        1e-3,
+    ),
+    (
+        &["s", "sec", "secs", "second", "seconds"],
+        Dimension::Time,
+        1.0,
+    ),
+    (&["min", "mins", "minute", "minutes"], Dimension::Time, 60.0),
+    (
+        &["h", "hr", "hrs", "hour", "hours"],
+        Dimension::Time,
+        3600.0,
+    ),
+    (&["d", "day", "days"], Dimension::Time, 86_400.0),
+    (&["wk", "week", "weeks"], Dimension::Time, 604_800.0),
+    (
+        &[
+            "ml",
+            "milliliter",
+            "millilitre",
+            "milliliters",
+            "millilitres",
+        ],
+        Dimension::Volume,
+        1e-3,
+    ),
+    (
+        &["l", "liter", "litre", "liters", "litres"],
+        Dimension::Volume,
+        1.0,
+    ),
+    (
+        &["m3", "m³", "cubic meter", "cubic metre"],
+        Dimension::Volume,
+        1e3,
+    ),
+    (
+        &["gal", "gallon", "gallons"],
+        Dimension::Volume,
+        3.785_411_784,
+    ),
+    (&["qt", "quart", "quarts"], Dimension::Volume, 0.946_352_946),
+    (
+        &["floz", "fl oz", "fluid ounce", "fluid ounces"],
+        Dimension::Volume,
+        0.029_573_529_562_5,
+    ),
+    (
+        &["m2", "m²", "sqm", "square meter", "square metre"],
+        Dimension::Area,
+        1.0,
+    ),
+    (
+        &["km2", "km²", "square kilometer", "square kilometre"],
+        Dimension::Area,
+        1e6,
+    ),
+    (
+        &["ft2", "ft²", "sqft", "sq ft", "square foot", "square feet"],
+        Dimension::Area,
+        0.092_903_04,
+    ),
+    (&["ha", "hectare", "hectares"], Dimension::Area, 1e4),
+    (&["acre", "acres"], Dimension::Area, 4_046.856_422_4),
+    (&["m/s", "mps"], Dimension::Speed, 1.0),
+    (&["km/h", "kph", "kmh"], Dimension::Speed, 1.0 / 3.6),
+    (&["mph", "mi/h"], Dimension::Speed, 0.447_04),
+    (
+        &["kn", "kt", "knot", "knots"],
+        Dimension::Speed,
+        1852.0 / 3600.0,
+    ),
+    (&["b", "byte", "bytes"], Dimension::Data, 1.0),
+    (&["kb", "kilobyte", "kilobytes"], Dimension::Data, 1e3),
+    (&["mb", "megabyte", "megabytes"], Dimension::Data, 1e6),
+    (&["gb", "gigabyte", "gigabytes"], Dimension::Data, 1e9),
+    (&["tb", "terabyte", "terabytes"], Dimension::Data, 1e12),
+    (&["kib", "kibibyte", "kibibytes"], Dimension::Data, 1024.0),
+    (
+        &["mib", "mebibyte", "mebibytes"],
+        Dimension::Data,
+        1_048_576.0,
+    ),
+    (
+        &["gib", "gibibyte", "gibibytes"],
+        Dimension::Data,
+        1_073_741_824.0,
+    ),
+    (&["c", "°c", "celsius", "degc"], Dimension::Temperature, 0.0),
+    (
+        &["f", "°f", "fahrenheit", "degf"],
+        Dimension::Temperature,
+        0.0,
+    ),
+    (&["k", "kelvin"], Dimension::Temperature, 0.0),
+];
+
+fn lookup_unit(unit: &str) -> Result<(Dimension, f64, &'static str), NormalizeError> {
+    let key = unit.trim().to_lowercase();
+    let key = key.trim_end_matches('.');
+    UNITS
+        .iter()
+        .find(|(aliases, _, _)| aliases.contains(&key))
+        .map(|(aliases, dimension, factor)| (*dimension, *factor, aliases[0]))
+        .ok_or_else(|| NormalizeError::UnknownUnit(unit.to_string()))
+}
+
+/// Convert `value` from unit `from` to unit `to` (e.g. `"mi"` to `"km"`).
+///
+/// Units are matched case-insensitively by symbol or name, singular or
+/// plural: length, mass, time, volume, area, speed, data size and
+/// temperature (`c`, `f`, `k`).
+pub fn convert_unit(value: f64, from: &str, to: &str) -> Result<f64, NormalizeError> {
+    let (from_dim, from_factor, from_name) = lookup_unit(from)?;
+    let (to_dim, to_factor, to_name) = lookup_unit(to)?;
+    if from_dim != to_dim {
+        return Err(NormalizeError::Incompatible {
+            from: from.to_string(),
+            to: to.to_string(),
+        });
+    }
+    if from_dim == Dimension::Temperature {
+        let kelvin = match from_name {
+            "c" => value + 273.15,
+            "f" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+            _ => value,
+        };
+        return Ok(match to_name {
+            "c" => kelvin - 273.15,
+            "f" => (kelvin - 273.15) * 9.0 / 5.0 + 32.0,
+            _ => kelvin,
+        });
+    }
+    Ok(value * from_factor / to_factor)
+}
+
+/// Split a quantity such as `"5.2 km"` or `"1,5kg"` into its value (read
+/// per `locale`) and unit.
+pub fn parse_quantity(text: &str, locale: &Locale) -> Result<(f64, String), NormalizeError> {
+    let trimmed = text.trim();
+    let split = trimmed
+        .char_indices()
+        .rfind(|(_, c)| c.is_ascii_digit())
+        .map(|(i, _)| i + 1)
+        .ok_or_else(|| NormalizeError::Number(text.to_string()))?;
+    let (number, unit) = trimmed.split_at(split);
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return Err(NormalizeError::UnknownUnit(String::new()));
+    }
+    Ok((parse_number(number, locale)?, unit.to_string()))
+}
+
+/// Read a quantity and convert it to `to` (`"3 mi"` → 4.828 for `"km"`).
+pub fn normalize_quantity(text: &str, to: &str, locale: &Locale) -> Result<f64, NormalizeError> {
+    let (value, unit) = parse_quantity(text, locale)?;
+    convert_unit(value, &unit, to)
+}
+
+// ============================================================================
+// Output post-processing
+// ============================================================================
+
+/// How a field of a task's JSON output is normalized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldNormalization {
+    /// To an RFC 3339 string.
+    Date,
+    /// To a JSON number.
+    Number,
+    /// To a JSON number in the given unit; bare numbers are kept.
+    Unit(String),
+}
+
+/// Normalizes fields of JSON task output.
+///
+/// Fields are matched by name at any depth, so a rule for `price` also
+/// covers every item of a `products` list. Values that cannot be
+/// normalized are kept as they are and logged.
+///
+/// ```yaml
+/// normalize:
+///   locale: de-DE
+///   fields:
+///     signed_on: date
+///     revenue: number
+///     distance: { unit: km }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Normalizer {
+    /// Locale of the source text (BCP 47); `en-US` when unset.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Normalization of each field, by field name.
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldNormalization>,
+}
+
+impl Normalizer {
+    /// A normalizer reading text as written in `locale`.
+    pub fn new(locale: Option<&str>) -> Self {
+        Self {
+            locale: locale.map(str::to_string),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Builder: normalize `field` as `normalization`.
+    pub fn field(mut self, field: impl Into<String>, normalization: FieldNormalization) -> Self {
+        self.fields.insert(field.into(), normalization);
+        self
+    }
+
+    fn locale(&self) -> Locale {
+        self.locale
+            .as_deref()
+            .map(Locale::parse)
+            .unwrap_or_default()
+    }
+
+    /// Normalize the configured fields in `value`, returning the errors of
+    /// the values left unchanged.
+    pub fn apply(&self, value: &mut Value) -> Vec<NormalizeError> {
+        let mut errors = Vec::new();
+        self.visit(value, &self.locale(), &mut errors);
+        errors
+    }
+
+    fn visit(&self, value: &mut Value, locale: &Locale, errors: &mut Vec<NormalizeError>) {
+        match value {
+            Value::Object(map) => {
+                for (key, field) in map.iter_mut() {
+                    match self.fields.get(key) {
+                        Some(normalization) => {
+                            if let Err(e) = normalize_value(field, normalization, locale) {
+                                errors.push(e);
+                            }
+                        }
+                        None => self.visit(field, locale, errors),
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.visit(item, locale, errors);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Post-process `output`: normalize its JSON (from `json_dict`, or the
+    /// raw text when it is JSON) and store the result in both `raw` and
+    /// `json_dict`. Non-JSON output is left alone.
+    pub fn process(&self, output: &mut TaskOutput) {
+        let mut value = match &output.json_dict {
+            Some(dict) => Value::Object(dict.clone().into_iter().collect()),
+            None => match serde_json::from_str::<Value>(strip_code_fence(&output.raw)) {
+                Ok(value) if value.is_object() || value.is_array() => value,
+                _ => return,
+            },
+        };
+        for error in self.apply(&mut value) {
+            log::warn!(
+                "Output normalization of '{}': {}",
+                output.description,
+                error
+            );
+        }
+        output.raw = value.to_string();
+        if let Value::Object(map) = value {
+            output.json_dict = Some(map.into_iter().collect());
+        }
+    }
+}
+
+fn normalize_value(
+    value: &mut Value,
+    normalization: &FieldNormalization,
+    locale: &Locale,
+) -> Result<(), NormalizeError> {
+    match value {
+        Value::Array(items) => {
+            let mut result = Ok(());
+            for item in items {
+                if let Err(e) = normalize_value(item, normalization, locale) {
+                    result = Err(e);
+                }
+            }
+            return result;
+        }
+        Value::Null => return Ok(()),
+        _ => {}
+    }
+    let normalized = match (normalization, &*value) {
+        (FieldNormalization::Date, Value::String(s)) => Value::String(normalize_date(s, locale)?),
+        (FieldNormalization::Number, Value::String(s)) => number(parse_number(s, locale)?),
+        (FieldNormalization::Unit(to), Value::String(s)) => {
+            number(normalize_quantity(s, to, locale)?)
+        }
+        _ => return Ok(()),
+    };
+    *value = normalized;
+    Ok(())
+}
+
+fn number(value: f64) -> Value {
+    // Whole numbers stay integers in the JSON.
+    if value.fract() == 0.0 && value.abs() < 9e15 {
+        Value::from(value as i64)
+    } else {
+        serde_json::Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// The content of a Markdown code fence, or the text itself.
+pub(crate) fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map_or(trimmed, str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::output_format::OutputFormat;
+
+    #[test]
+    fn test_dates_numbers_and_units() {
+        let us = Locale::default();
+        let de = Locale::parse("de-DE");
+        assert_eq!(
+            normalize_date("March 5th, 2024", &us).unwrap(),
+            "2024-03-05T00:00:00Z"
+        );
+        assert_eq!(
+            normalize_date("Tuesday, 5 March 2024", &us).unwrap(),
+            "2024-03-05T00:00:00Z"
+        );
+        assert_eq!(
+            normalize_date("03/05/2024", &us).unwrap(),
+            "2024-03-05T00:00:00Z"
+        );
+        assert_eq!(
+            normalize_date("05/03/2024", &de).unwrap(),
+            "2024-03-05T00:00:00Z"
+        );
+        assert_eq!(
+            normalize_date("2024-03-05T10:30:00+02:00", &us).unwrap(),
+            "2024-03-05T08:30:00Z"
+        );
+        assert!(normalize_date("next week", &us).is_err());
+
+        assert_eq!(parse_number("1,234.5", &us).unwrap(), 1234.5);
+        assert_eq!(parse_number("1.234,5 €", &de).unwrap(), 1234.5);
+        assert_eq!(parse_number("1,500", &us).unwrap(), 1500.0);
+        assert_eq!(parse_number("1,500", &de).unwrap(), 1.5);
+        assert_eq!(parse_number("(12.5)", &us).unwrap(), -12.5);
+        assert_eq!(parse_number("$3.2k", &us).unwrap(), 3200.0);
+        assert_eq!(parse_number("1.5 million", &us).unwrap(), 1_500_000.0);
+        assert_eq!(parse_number("12%", &us).unwrap(), 0.12);
+        assert!(parse_number("twelve", &us).is_err());
+
+        assert!((convert_unit(3.0, "miles", "km").unwrap() - 4.828_032).abs() < 1e-9);
+        assert!((convert_unit(212.0, "°F", "C").unwrap() - 100.0).abs() < 1e-9);
+        assert_eq!(normalize_quantity("1,5 kg", "g", &de).unwrap(), 1500.0);
+        assert_eq!(
+            convert_unit(1.0, "kg", "km").unwrap_err().to_string(),
+            "Cannot convert kg to km"
+        );
+    }
+
+    #[test]
+    fn test_normalizer_post_processes_json_output() {
+        let normalizer = Normalizer::new(Some("de-DE"))
+            .field("signed_on", FieldNormalization::Date)
+            .field("price", FieldNormalization::Number)
+            .field("distance", FieldNormalization::Unit("km".into()));
+        let mut output = TaskOutput::new(
+            "Extract the contract".to_string(),
+            "Analyst".to_string(),
+            "```json\n{\"signed_on\": \"5. März 2024\", \"products\": [\
+             {\"price\": \"1.299,00 €\", \"distance\": \"2500 m\"}, {\"price\": 7}]}\n```"
+                .to_string(),
+            OutputFormat::JSON,
+        );
+        normalizer.process(&mut output);
+        let dict = output.json_dict.unwrap();
+        // Unrecognized values are kept.
+        assert_eq!(dict["signed_on"], "5. März 2024");
+        assert_eq!(
+            dict["products"],
+            serde_json::json!([{"price": 1299, "distance": 2.5}, {"price": 7}])
+        );
+        assert!(output.raw.starts_with('{'));
+
+        let mut value = serde_json::json!({"signed_on": "2024-03-05"});
+        assert!(normalizer.apply(&mut value).is_empty());
+        assert_eq!(value["signed_on"], "2024-03-05T00:00:00Z");
+    }
+}
@@ -1,64 +1,214 @@
 //! Path management utilities for CrewAI storage and configuration.
 //!
 //! Port of crewai/utilities/paths.py
+//!
+//! Everything crewAI persists lives under the project's storage directory,
+//! [`storage_dir`]: memory databases at its root (where crewAI keeps
+//! them), checkpoints in a subdirectory. Caches, which can be deleted at
+//! any time, go to [`cache_dir`].
+//!
+//! `CREWAI_STORAGE_DIR` picks the storage directory. A path (absolute, or
+//! relative to the working directory, like `./.crewai`) is used as is; a
+//! bare name names the project inside the platform data directory:
+//!
+//! | Platform | Data directory                          | Cache directory                |
+//! |----------|-----------------------------------------|--------------------------------|
+//! | Linux    | `$XDG_DATA_HOME` or `~/.local/share`    | `$XDG_CACHE_HOME` or `~/.cache` |
+//! | macOS    | `~/Library/Application Support`         | `~/Library/Caches`             |
+//! | Windows  | `%LOCALAPPDATA%`                        | `%LOCALAPPDATA%`               |
+//!
+//! followed by `CrewAI/<project>`, the project defaulting to the name of
+//! the working directory. With a storage path, caches go to its `cache`
+//! subdirectory, so a packaged binary keeps all its files in one place.
 
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the storage directory or the project.
+pub const STORAGE_DIR_ENV: &str = "CREWAI_STORAGE_DIR";
+
+/// Directory under the platform directories holding crewAI's projects.
+const APP_AUTHOR: &str = "CrewAI";
 
 /// Returns the path for SQLite database storage.
 ///
-/// Uses the platform-specific data directory for the current project,
-/// creating it if necessary.
+/// Same as [`storage_dir`], as a string.
 ///
 /// # Returns
 /// String path to the data directory.
 pub fn db_storage_path() -> String {
-    let app_name = get_project_directory_name();
-    let app_author = "CrewAI";
-
-    // Use a platform-appropriate data directory.
-    // On Linux: ~/.local/share/<app_author>/<app_name>
-    // On macOS: ~/Library/Application Support/<app_author>/<app_name>
-    // On Windows: C:\Users\<user>\AppData\Local\<app_author>\<app_name>
-    let data_dir = if cfg!(target_os = "linux") {
-        let home = env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-        PathBuf::from(home)
-            .join(".local")
-            .join("share")
-            .join(app_author)
-            .join(&app_name)
+    storage_dir().to_string_lossy().to_string()
+}
+
+/// The project's storage directory, created if necessary.
+pub fn storage_dir() -> PathBuf {
+    ensure_dir(resolve_storage_dir(&lookup_env, &current_dir_name()))
+}
+
+/// Directory of checkpoints (e.g. flow states), created if necessary.
+pub fn checkpoints_dir() -> PathBuf {
+    ensure_dir(storage_dir().join("checkpoints"))
+}
+
+/// The project's cache directory, created if necessary.
+pub fn cache_dir() -> PathBuf {
+    ensure_dir(resolve_cache_dir(&lookup_env, &current_dir_name()))
+}
+
+/// Returns the current project directory name.
+///
+/// The project named by the `CREWAI_STORAGE_DIR` environment variable, or
+/// the last component of the storage path it gives, otherwise the current
+/// working directory name. Always a name, never a path.
+pub fn get_project_directory_name() -> String {
+    resolve_project_name(&lookup_env, &current_dir_name())
+}
+
+/// The project name, reading environment variables with `var`.
+fn resolve_project_name(var: &dyn Fn(&str) -> Option<String>, cwd_name: &str) -> String {
+    match var(STORAGE_DIR_ENV) {
+        Some(dir) if is_path(&dir) => Path::new(&dir)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| cwd_name.to_string()),
+        Some(project) => project,
+        None => cwd_name.to_string(),
+    }
+}
+
+/// The storage directory, reading environment variables with `var`.
+fn resolve_storage_dir(var: &dyn Fn(&str) -> Option<String>, cwd_name: &str) -> PathBuf {
+    match var(STORAGE_DIR_ENV) {
+        Some(dir) if is_path(&dir) => PathBuf::from(dir),
+        project => platform_dir(var, Platform::Data)
+            .join(APP_AUTHOR)
+            .join(project.as_deref().unwrap_or(cwd_name)),
+    }
+}
+
+/// The cache directory, reading environment variables with `var`.
+fn resolve_cache_dir(var: &dyn Fn(&str) -> Option<String>, cwd_name: &str) -> PathBuf {
+    match var(STORAGE_DIR_ENV) {
+        Some(dir) if is_path(&dir) => PathBuf::from(dir).join("cache"),
+        project => platform_dir(var, Platform::Cache)
+            .join(APP_AUTHOR)
+            .join(project.as_deref().unwrap_or(cwd_name)),
+    }
+}
+
+/// Kinds of platform directories.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    Data,
+    Cache,
+}
+
+/// The platform's data or cache directory.
+fn platform_dir(var: &dyn Fn(&str) -> Option<String>, kind: Platform) -> PathBuf {
+    let home = || PathBuf::from(var("HOME").unwrap_or_else(|| "/tmp".to_string()));
+    if cfg!(target_os = "linux") {
+        let (xdg, default) = match kind {
+            Platform::Data => ("XDG_DATA_HOME", home().join(".local").join("share")),
+            Platform::Cache => ("XDG_CACHE_HOME", home().join(".cache")),
+        };
+        var(xdg).map(PathBuf::from).unwrap_or(default)
     } else if cfg!(target_os = "macos") {
-        let home = env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-        PathBuf::from(home)
-            .join("Library")
-            .join("Application Support")
-            .join(app_author)
-            .join(&app_name)
+        match kind {
+            Platform::Data => home().join("Library").join("Application Support"),
+            Platform::Cache => home().join("Library").join("Caches"),
+        }
     } else if cfg!(target_os = "windows") {
-        let local_app_data = env::var("LOCALAPPDATA")
-            .unwrap_or_else(|_| env::var("APPDATA").unwrap_or_else(|_| "C:\\tmp".to_string()));
+        let local_app_data = var("LOCALAPPDATA")
+            .or_else(|| var("APPDATA"))
+            .unwrap_or_else(|| "C:\\tmp".to_string());
         PathBuf::from(local_app_data)
-            .join(app_author)
-            .join(&app_name)
     } else {
-        PathBuf::from("/tmp").join(app_author).join(&app_name)
-    };
+        PathBuf::from("/tmp")
+    }
+}
 
-    // Create the directory if it doesn't exist
-    let _ = std::fs::create_dir_all(&data_dir);
+/// Whether a `CREWAI_STORAGE_DIR` value is a path rather than a project
+/// name.
+fn is_path(value: &str) -> bool {
+    let path = Path::new(value);
+    path.is_absolute() || path.components().count() > 1 || value.starts_with('.')
+}
 
-    data_dir.to_string_lossy().to_string()
+/// The environment variable `key`, unless unset or empty.
+fn lookup_env(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
 }
 
-/// Returns the current project directory name.
-///
-/// Uses the `CREWAI_STORAGE_DIR` environment variable if set,
-/// otherwise defaults to the current working directory name.
-pub fn get_project_directory_name() -> String {
-    env::var("CREWAI_STORAGE_DIR").unwrap_or_else(|_| {
-        env::current_dir()
-            .ok()
-            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
-            .unwrap_or_else(|| "crewai_default".to_string())
-    })
+fn current_dir_name() -> String {
+    env::current_dir()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "crewai_default".to_string())
+}
+
+fn ensure_dir(dir: PathBuf) -> PathBuf {
+    let _ = std::fs::create_dir_all(&dir);
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_storage_dir_resolution() {
+        let env = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            move |key: &str| vars.get(key).cloned()
+        };
+
+        let packaged = env(&[(STORAGE_DIR_ENV, "/var/lib/crewai"), ("HOME", "/home/ada")]);
+        assert_eq!(
+            resolve_storage_dir(&packaged, "app"),
+            PathBuf::from("/var/lib/crewai")
+        );
+        assert_eq!(
+            resolve_cache_dir(&packaged, "app"),
+            PathBuf::from("/var/lib/crewai/cache")
+        );
+        let relative = env(&[(STORAGE_DIR_ENV, "./.crewai-dev")]);
+        assert_eq!(
+            resolve_storage_dir(&relative, "app"),
+            PathBuf::from("./.crewai-dev")
+        );
+        assert_eq!(resolve_project_name(&packaged, "app"), "crewai");
+        assert_eq!(resolve_project_name(&relative, "app"), ".crewai-dev");
+        let named = env(&[(STORAGE_DIR_ENV, "research")]);
+        assert_eq!(resolve_project_name(&named, "app"), "research");
+        assert_eq!(resolve_project_name(&env(&[]), "app"), "app");
+
+        if cfg!(target_os = "linux") {
+            let home = env(&[("HOME", "/home/ada")]);
+            assert_eq!(
+                resolve_storage_dir(&home, "app"),
+                PathBuf::from("/home/ada/.local/share/CrewAI/app")
+            );
+            assert_eq!(
+                resolve_cache_dir(&home, "app"),
+                PathBuf::from("/home/ada/.cache/CrewAI/app")
+            );
+            let xdg = env(&[
+                (STORAGE_DIR_ENV, "research"),
+                ("XDG_DATA_HOME", "/data"),
+                ("XDG_CACHE_HOME", "/cache"),
+            ]);
+            assert_eq!(
+                resolve_storage_dir(&xdg, "app"),
+                PathBuf::from("/data/CrewAI/research")
+            );
+            assert_eq!(
+                resolve_cache_dir(&xdg, "app"),
+                PathBuf::from("/cache/CrewAI/research")
+            );
+        }
+    }
 }
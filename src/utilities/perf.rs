@@ -0,0 +1,199 @@
+//! Lightweight timing of orchestration hot paths.
+//!
+//! Hot paths (prompt formatting, knowledge chunking, memory search, event
+//! dispatch and policy evaluation) open a [`span`] that adds its duration
+//! to per-path atomic counters when dropped. Instrumentation is off by
+//! default and costs one relaxed atomic load per call; enable it with
+//! `CREWAI_PERF=1` or [`set_enabled`]. The totals are exported on the
+//! server's `/metrics` endpoint and by `crewai bench`.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+/// Environment variable enabling hot-path instrumentation at startup.
+pub const PERF_ENV: &str = "CREWAI_PERF";
+
+/// An instrumented hot path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotPath {
+    /// Building agent prompts ([`Prompts::task_execution`](super::prompts::Prompts::task_execution)).
+    MessageFormatting,
+    /// Splitting knowledge sources into chunks.
+    Chunking,
+    /// Searching memory storage.
+    VectorSearch,
+    /// Emitting an event on the event bus.
+    EventDispatch,
+    /// Evaluating a request against the policy engine.
+    PolicyEvaluation,
+}
+
+impl HotPath {
+    /// Every hot path, in reporting order.
+    pub const ALL: [HotPath; 5] = [
+        HotPath::MessageFormatting,
+        HotPath::Chunking,
+        HotPath::VectorSearch,
+        HotPath::EventDispatch,
+        HotPath::PolicyEvaluation,
+    ];
+
+    /// Snake-case name used in metrics and reports.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MessageFormatting => "message_formatting",
+            Self::Chunking => "chunking",
+            Self::VectorSearch => "vector_search",
+            Self::EventDispatch => "event_dispatch",
+            Self::PolicyEvaluation => "policy_evaluation",
+        }
+    }
+}
+
+impl std::fmt::Display for HotPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+struct Counters {
+    calls: AtomicU64,
+    total_ns: AtomicU64,
+    max_ns: AtomicU64,
+}
+
+const PATHS: usize = HotPath::ALL.len();
+
+static COUNTERS: [Counters; PATHS] = [const {
+    Counters {
+        calls: AtomicU64::new(0),
+        total_ns: AtomicU64::new(0),
+        max_ns: AtomicU64::new(0),
+    }
+}; PATHS];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static FROM_ENV: OnceLock<()> = OnceLock::new();
+
+/// Whether instrumentation is on.
+pub fn is_enabled() -> bool {
+    FROM_ENV.get_or_init(|| {
+        if std::env::var(PERF_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")) {
+            ENABLED.store(true, Ordering::Relaxed);
+        }
+    });
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turn instrumentation on or off, overriding `CREWAI_PERF`.
+pub fn set_enabled(enabled: bool) {
+    FROM_ENV.get_or_init(|| ());
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Time the rest of the enclosing scope as `path`.
+#[must_use = "the span records when dropped"]
+pub fn span(path: HotPath) -> Span {
+    Span {
+        path,
+        start: is_enabled().then(Instant::now),
+    }
+}
+
+/// An open timing span; see [`span`].
+pub struct Span {
+    path: HotPath,
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.path, start.elapsed().as_nanos() as u64);
+        }
+    }
+}
+
+/// Add one call of `nanos` to `path`.
+pub fn record(path: HotPath, nanos: u64) {
+    let counters = &COUNTERS[path as usize];
+    counters.calls.fetch_add(1, Ordering::Relaxed);
+    counters.total_ns.fetch_add(nanos, Ordering::Relaxed);
+    counters.max_ns.fetch_max(nanos, Ordering::Relaxed);
+}
+
+/// Accumulated timings of one hot path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HotPathStats {
+    /// The hot path.
+    pub path: HotPath,
+    /// Number of recorded calls.
+    pub calls: u64,
+    /// Total time spent, in nanoseconds.
+    pub total_ns: u64,
+    /// Slowest call, in nanoseconds.
+    pub max_ns: u64,
+}
+
+impl HotPathStats {
+    /// Mean call time in nanoseconds (0 without calls).
+    pub fn mean_ns(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.total_ns as f64 / self.calls as f64
+        }
+    }
+}
+
+/// Current totals for every hot path.
+pub fn snapshot() -> Vec<HotPathStats> {
+    HotPath::ALL
+        .iter()
+        .map(|&path| {
+            let counters = &COUNTERS[path as usize];
+            HotPathStats {
+                path,
+                calls: counters.calls.load(Ordering::Relaxed),
+                total_ns: counters.total_ns.load(Ordering::Relaxed),
+                max_ns: counters.max_ns.load(Ordering::Relaxed),
+            }
+        })
+        .collect()
+}
+
+/// Zero every counter.
+pub fn reset() {
+    for counters in &COUNTERS {
+        counters.calls.store(0, Ordering::Relaxed);
+        counters.total_ns.store(0, Ordering::Relaxed);
+        counters.max_ns.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_record_when_enabled() {
+        // Counters are global, so only assert on growth.
+        let before = snapshot()[HotPath::Chunking as usize].calls;
+        set_enabled(false);
+        drop(span(HotPath::Chunking));
+        assert_eq!(snapshot()[HotPath::Chunking as usize].calls, before);
+
+        set_enabled(true);
+        drop(span(HotPath::Chunking));
+        record(HotPath::Chunking, 5_000);
+        set_enabled(false);
+        let stats = &snapshot()[HotPath::Chunking as usize];
+        assert!(stats.calls >= before + 2);
+        assert!(stats.max_ns >= 5_000);
+        assert_eq!(HotPath::Chunking.to_string(), "chunking");
+    }
+}
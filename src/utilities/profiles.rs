@@ -0,0 +1,404 @@
+//! Named run profiles (e.g. `dev`, `staging`, `prod`).
+//!
+//! A crew is defined once; a profile file lists what changes between
+//! environments: models, request and iteration budgets, verbosity, memory
+//! and cache, the telemetry endpoint and where logs and storage go. A
+//! profile may `extends` another, so `prod` only states how it differs from
+//! `staging`.
+//!
+//! The active profile is chosen with `--profile <name>`, else
+//! [`PROFILE_ENV`], else the file's `default`. Applying it to a crew records
+//! its name, which is reported in the crew's output.
+//!
+//! ```yaml
+//! # CREWAI_PROFILES=profiles.yaml
+//! default: dev
+//! profiles:
+//!   dev:
+//!     llm: openai/gpt-4o-mini
+//!     verbose: true
+//!     storage_dir: ./.crewai-dev
+//!   staging:
+//!     llm: openai/gpt-4o
+//!     max_rpm: 60
+//!     telemetry_endpoint: http://otel.staging:4318/v1/traces
+//!   prod:
+//!     extends: staging
+//!     max_rpm: 300
+//!     agents:
+//!       Senior Researcher:
+//!         llm: anthropic/claude-sonnet-4-5
+//!         max_iter: 40
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::Agent;
+use crate::crew::Crew;
+
+/// Environment variable naming the active profile.
+pub const PROFILE_ENV: &str = "CREWAI_PROFILE";
+/// Environment variable pointing at the profile file (YAML or JSON).
+pub const PROFILES_ENV: &str = "CREWAI_PROFILES";
+
+/// Error selecting a profile.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProfileError {
+    /// The profile file could not be read or parsed.
+    #[error("{0}")]
+    Load(String),
+    /// No profile has the requested name.
+    #[error("Unknown profile '{name}' (available: {available})")]
+    Unknown {
+        /// Requested name.
+        name: String,
+        /// Defined profiles, comma-separated.
+        available: String,
+    },
+    /// A profile extends itself, directly or through others.
+    #[error("Profile '{0}' extends itself")]
+    Cycle(String),
+}
+
+/// Settings a profile overrides for one agent, or for all of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentOverrides {
+    /// Model string, e.g. `openai/gpt-4o`.
+    pub llm: Option<String>,
+    /// Maximum iterations per task.
+    pub max_iter: Option<i32>,
+    /// Maximum requests per minute.
+    pub max_rpm: Option<i32>,
+    /// Maximum execution time per task, in seconds.
+    pub max_execution_time: Option<i64>,
+    /// Verbose logging.
+    pub verbose: Option<bool>,
+}
+
+impl AgentOverrides {
+    /// `self`, with unset settings taken from `base`.
+    fn or(self, base: &Self) -> Self {
+        Self {
+            llm: self.llm.or_else(|| base.llm.clone()),
+            max_iter: self.max_iter.or(base.max_iter),
+            max_rpm: self.max_rpm.or(base.max_rpm),
+            max_execution_time: self.max_execution_time.or(base.max_execution_time),
+            verbose: self.verbose.or(base.verbose),
+        }
+    }
+
+    /// Apply the settings that are set to `agent`.
+    pub fn apply(&self, agent: &mut Agent) {
+        if let Some(llm) = &self.llm {
+            agent.llm = Some(llm.clone());
+        }
+        if let Some(max_iter) = self.max_iter {
+            agent.max_iter = max_iter;
+        }
+        if let Some(max_rpm) = self.max_rpm {
+            agent.max_rpm = Some(max_rpm);
+        }
+        if let Some(max_execution_time) = self.max_execution_time {
+            agent.max_execution_time = Some(max_execution_time);
+        }
+        if let Some(verbose) = self.verbose {
+            agent.verbose = verbose;
+        }
+    }
+}
+
+/// What a profile changes. Unset settings keep the crew's own values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrewProfile {
+    /// Profile whose settings this one starts from.
+    pub extends: Option<String>,
+    /// Model for every agent.
+    pub llm: Option<String>,
+    /// Model of the manager agent (hierarchical process).
+    pub manager_llm: Option<String>,
+    /// Maximum iterations per task for every agent.
+    pub max_iter: Option<i32>,
+    /// Crew-wide maximum requests per minute.
+    pub max_rpm: Option<i32>,
+    /// Verbose logging for the crew and its agents.
+    pub verbose: Option<bool>,
+    /// Crew memory.
+    pub memory: Option<bool>,
+    /// Tool result cache.
+    pub cache: Option<bool>,
+    /// Overrides for single agents, by role; they win over the settings
+    /// for every agent.
+    pub agents: BTreeMap<String, AgentOverrides>,
+    /// OTLP/HTTP traces endpoint.
+    pub telemetry_endpoint: Option<String>,
+    /// Storage directory for memory and other persisted data, exported as
+    /// `CREWAI_STORAGE_DIR` (see [`paths`](super::paths)).
+    pub storage_dir: Option<String>,
+    /// Crew log file.
+    pub output_log_file: Option<String>,
+    /// Directory of per-task log files.
+    pub task_log_dir: Option<String>,
+    /// Further environment variables to set.
+    pub env: BTreeMap<String, String>,
+}
+
+impl CrewProfile {
+    /// `self`, with unset settings taken from `base`.
+    fn or(self, base: &Self) -> Self {
+        let mut agents = base.agents.clone();
+        for (role, overrides) in self.agents {
+            let merged = match agents.get(&role) {
+                Some(inherited) => overrides.or(inherited),
+                None => overrides,
+            };
+            agents.insert(role, merged);
+        }
+        let mut env = base.env.clone();
+        env.extend(self.env);
+        Self {
+            extends: None,
+            llm: self.llm.or_else(|| base.llm.clone()),
+            manager_llm: self.manager_llm.or_else(|| base.manager_llm.clone()),
+            max_iter: self.max_iter.or(base.max_iter),
+            max_rpm: self.max_rpm.or(base.max_rpm),
+            verbose: self.verbose.or(base.verbose),
+            memory: self.memory.or(base.memory),
+            cache: self.cache.or(base.cache),
+            agents,
+            telemetry_endpoint: self
+                .telemetry_endpoint
+                .or_else(|| base.telemetry_endpoint.clone()),
+            storage_dir: self.storage_dir.or_else(|| base.storage_dir.clone()),
+            output_log_file: self
+                .output_log_file
+                .or_else(|| base.output_log_file.clone()),
+            task_log_dir: self.task_log_dir.or_else(|| base.task_log_dir.clone()),
+            env,
+        }
+    }
+
+    /// Overrides for the agent with `role`.
+    pub fn agent_overrides(&self, role: &str) -> AgentOverrides {
+        let all = AgentOverrides {
+            llm: self.llm.clone(),
+            max_iter: self.max_iter,
+            verbose: self.verbose,
+            ..Default::default()
+        };
+        match self.agents.get(role) {
+            Some(overrides) => overrides.clone().or(&all),
+            None => all,
+        }
+    }
+}
+
+/// The profiles of a crew definition.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSet {
+    /// Profile used when none is requested.
+    pub default: Option<String>,
+    /// Profiles by name.
+    pub profiles: BTreeMap<String, CrewProfile>,
+}
+
+impl ProfileSet {
+    /// Load from a YAML or JSON file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| ProfileError::Load(format!("Failed to read {}: {}", path.display(), e)))?;
+        serde_yaml::from_str(&text)
+            .map_err(|e| ProfileError::Load(format!("Invalid profile file: {}", e)))
+    }
+
+    /// Load the file named by [`PROFILES_ENV`], if set.
+    pub fn from_env() -> Option<Result<Self, ProfileError>> {
+        let path = std::env::var(PROFILES_ENV).ok().filter(|p| !p.is_empty())?;
+        Some(Self::from_file(path))
+    }
+
+    /// Profile `name` with the profiles it extends folded in.
+    pub fn resolve(&self, name: &str) -> Result<ActiveProfile, ProfileError> {
+        let mut chain = vec![name.to_string()];
+        let mut profile = self.get(name)?.clone();
+        while let Some(parent) = profile.extends.clone() {
+            if chain.contains(&parent) {
+                return Err(ProfileError::Cycle(name.to_string()));
+            }
+            profile = profile.or(self.get(&parent)?);
+            profile.extends = self.get(&parent)?.extends.clone();
+            chain.push(parent);
+        }
+        Ok(ActiveProfile {
+            name: name.to_string(),
+            profile,
+        })
+    }
+
+    /// The active profile: `requested` (from `--profile`), else
+    /// [`PROFILE_ENV`], else the default. `None` when nothing selects one.
+    pub fn select(&self, requested: Option<&str>) -> Result<Option<ActiveProfile>, ProfileError> {
+        let from_env = std::env::var(PROFILE_ENV).ok().filter(|p| !p.is_empty());
+        requested
+            .map(str::to_string)
+            .or(from_env)
+            .or_else(|| self.default.clone())
+            .map(|name| self.resolve(&name))
+            .transpose()
+    }
+
+    fn get(&self, name: &str) -> Result<&CrewProfile, ProfileError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| ProfileError::Unknown {
+                name: name.to_string(),
+                available: self.profiles.keys().cloned().collect::<Vec<_>>().join(", "),
+            })
+    }
+}
+
+/// A selected profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveProfile {
+    /// Profile name.
+    pub name: String,
+    /// Settings, including those of the profiles it extends.
+    pub profile: CrewProfile,
+}
+
+impl ActiveProfile {
+    /// Apply the profile to `crew` and its agents, and record its name.
+    pub fn apply(&self, crew: &mut Crew) {
+        let profile = &self.profile;
+        if let Some(max_rpm) = profile.max_rpm {
+            crew.max_rpm = Some(max_rpm);
+        }
+        if let Some(verbose) = profile.verbose {
+            crew.verbose = verbose;
+        }
+        if let Some(memory) = profile.memory {
+            crew.memory = memory;
+        }
+        if let Some(cache) = profile.cache {
+            crew.cache = cache;
+        }
+        if let Some(manager_llm) = &profile.manager_llm {
+            crew.manager_llm = Some(manager_llm.clone());
+        }
+        if let Some(file) = &profile.output_log_file {
+            crew.output_log_file = Some(file.clone());
+        }
+        if let Some(dir) = &profile.task_log_dir {
+            crew.task_log_dir = Some(dir.clone());
+        }
+        for agent in crew.agent_objects.values() {
+            if let Ok(mut agent) = agent.write() {
+                let overrides = profile.agent_overrides(&agent.role);
+                overrides.apply(&mut agent);
+            }
+        }
+        crew.profile = Some(self.name.clone());
+    }
+
+    /// Export the profile's process-wide settings (telemetry endpoint,
+    /// storage directory and `env`) as environment variables. Call once at
+    /// startup, before telemetry and storage are initialized.
+    pub fn apply_env(&self) {
+        let profile = &self.profile;
+        if let Some(endpoint) = &profile.telemetry_endpoint {
+            std::env::set_var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT", endpoint);
+        }
+        if let Some(dir) = &profile.storage_dir {
+            std::env::set_var("CREWAI_STORAGE_DIR", dir);
+        }
+        for (key, value) in &profile.env {
+            std::env::set_var(key, value);
+        }
+    }
+}
+
+/// Take `--profile <name>` (or `--profile=<name>`) out of `args`.
+pub fn take_profile_arg(args: &mut Vec<String>) -> Option<String> {
+    let index = args
+        .iter()
+        .position(|a| a == "--profile" || a.starts_with("--profile="))?;
+    let arg = args.remove(index);
+    match arg.strip_prefix("--profile=") {
+        Some(name) => Some(name.to_string()),
+        None => (index < args.len()).then(|| args.remove(index)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROFILES: &str = r#"
+default: dev
+profiles:
+  dev:
+    llm: openai/gpt-4o-mini
+    verbose: true
+  staging:
+    llm: openai/gpt-4o
+    max_rpm: 60
+    agents:
+      Writer:
+        max_iter: 5
+  prod:
+    extends: staging
+    max_rpm: 300
+    agents:
+      Writer:
+        llm: anthropic/claude-sonnet-4-5
+"#;
+
+    #[test]
+    fn test_profile_extends_and_applies_to_crew() {
+        let set: ProfileSet = serde_yaml::from_str(PROFILES).unwrap();
+        let prod = set.resolve("prod").unwrap();
+        assert_eq!(prod.profile.max_rpm, Some(300));
+        assert_eq!(prod.profile.llm.as_deref(), Some("openai/gpt-4o"));
+        let writer = prod.profile.agent_overrides("Writer");
+        assert_eq!(writer.llm.as_deref(), Some("anthropic/claude-sonnet-4-5"));
+        assert_eq!(writer.max_iter, Some(5));
+
+        let writer = Agent::new("Writer".into(), "Write".into(), "Writes".into());
+        let editor = Agent::new("Editor".into(), "Edit".into(), "Edits".into());
+        let mut crew = Crew::with_agents(Vec::new(), vec![writer, editor]);
+        prod.apply(&mut crew);
+        assert_eq!(crew.max_rpm, Some(300));
+        assert_eq!(crew.profile.as_deref(), Some("prod"));
+        let llm = |role: &str| crew.get_agent(role).unwrap().read().unwrap().llm.clone();
+        assert_eq!(
+            llm("Writer").as_deref(),
+            Some("anthropic/claude-sonnet-4-5")
+        );
+        assert_eq!(llm("Editor").as_deref(), Some("openai/gpt-4o"));
+
+        assert_eq!(set.select(Some("dev")).unwrap().unwrap().name, "dev");
+        assert_eq!(
+            set.resolve("qa").unwrap_err().to_string(),
+            "Unknown profile 'qa' (available: dev, prod, staging)"
+        );
+        let mut looped = set.clone();
+        looped.profiles.get_mut("staging").unwrap().extends = Some("prod".into());
+        assert_eq!(
+            looped.resolve("prod").unwrap_err(),
+            ProfileError::Cycle("prod".into())
+        );
+
+        let mut args: Vec<String> = ["run", "--profile", "prod", "-v"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(take_profile_arg(&mut args).as_deref(), Some("prod"));
+        assert_eq!(args, ["run", "-v"]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Per-language prompt templates for agent execution.
+//!
+//! An agent's system prompt, the user message carrying its task and the
+//! output reported to the LLM when a tool fails are rendered from a
+//! [`PromptTemplate`]. [`PromptTemplates`] holds one per language: the
+//! embedded defaults (`translations/prompt_templates.json`, English,
+//! Spanish, French and German), optionally overridden or extended from a
+//! user JSON file of the same shape:
+//!
+//! ```json
+//! {
+//!   "es": {"tool_error": "La herramienta falló: {error}"},
+//!   "it": {"system": "Sei {role}. {backstory}\nIl tuo obiettivo: {goal} ..."}
+//! }
+//! ```
+//!
+//! Templates left out of a language fall back to English. Translated
+//! system prompts keep the ReAct keywords (`Thought:`, `Action:`,
+//! `Final Answer:`, ...) in English, as the executor parses them.
+//!
+//! Placeholders:
+//! - `system`: `{role}`, `{goal}`, `{backstory}`, `{tools}`, `{tool_names}`
+//! - `user`: `{input}`
+//! - `tool_error`: `{tool}`, `{error}`
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// Embedded default templates, keyed by language.
+const EMBEDDED_TEMPLATES_JSON: &str = include_str!("../translations/prompt_templates.json");
+
+/// Language used when none is selected or the selected one is unknown.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// The prompt templates of one language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    /// System prompt describing the agent, its tools and the ReAct format.
+    pub system: String,
+    /// User message carrying the task prompt.
+    pub user: String,
+    /// Output reported to the LLM when a tool fails.
+    pub tool_error: String,
+}
+
+impl PromptTemplate {
+    /// The system prompt of an agent with `tools`.
+    pub fn system_prompt(
+        &self,
+        role: &str,
+        goal: &str,
+        backstory: &str,
+        tools: &[String],
+    ) -> String {
+        let tools = tools.join(", ");
+        self.system
+            .replace("{role}", role)
+            .replace("{goal}", goal)
+            .replace("{backstory}", backstory)
+            .replace("{tool_names}", &tools)
+            .replace("{tools}", &tools)
+    }
+
+    /// The user message for `input`, the task prompt.
+    pub fn user_prompt(&self, input: &str) -> String {
+        self.user.replace("{input}", input)
+    }
+
+    /// The output reported when `tool` fails with `error`.
+    pub fn tool_error(&self, tool: &str, error: &str) -> String {
+        self.tool_error
+            .replace("{tool}", tool)
+            .replace("{error}", error)
+    }
+
+    /// Whether `output` of `tool` was rendered by [`tool_error`](Self::tool_error).
+    pub fn is_tool_error(&self, tool: &str, output: &str) -> bool {
+        let rendered = self.tool_error.replace("{tool}", tool);
+        match rendered.split_once("{error}") {
+            Some((prefix, suffix)) => {
+                output.len() >= prefix.len() + suffix.len()
+                    && output.starts_with(prefix)
+                    && output.ends_with(suffix)
+            }
+            None => output == rendered,
+        }
+    }
+}
+
+/// A template of a user file, whose missing parts fall back to English.
+#[derive(Debug, Default, Deserialize)]
+struct PartialTemplate {
+    system: Option<String>,
+    user: Option<String>,
+    tool_error: Option<String>,
+}
+
+/// Prompt templates keyed by language.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PromptTemplates {
+    templates: BTreeMap<String, PromptTemplate>,
+}
+
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        Self::embedded()
+    }
+}
+
+impl PromptTemplates {
+    /// The embedded default templates.
+    pub fn embedded() -> Self {
+        let templates = serde_json::from_str(EMBEDDED_TEMPLATES_JSON)
+            .expect("Error decoding embedded prompt_templates.json.");
+        Self { templates }
+    }
+
+    /// The embedded templates, overridden and extended by `json`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let overrides: BTreeMap<String, PartialTemplate> = serde_json::from_str(json)
+            .map_err(|e| format!("Failed to parse prompt templates JSON: {}", e))?;
+        let mut templates = Self::embedded();
+        for (language, partial) in overrides {
+            let mut template = templates
+                .templates
+                .get(&language)
+                .unwrap_or_else(|| templates.get(DEFAULT_LANGUAGE))
+                .clone();
+            if let Some(system) = partial.system {
+                template.system = system;
+            }
+            if let Some(user) = partial.user {
+                template.user = user;
+            }
+            if let Some(tool_error) = partial.tool_error {
+                template.tool_error = tool_error;
+            }
+            templates.insert(language, template);
+        }
+        Ok(templates)
+    }
+
+    /// The embedded templates, overridden and extended by the JSON file at
+    /// `path`.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read prompt templates '{}': {}", path, e))?;
+        Self::from_json(&json)
+    }
+
+    /// Languages with templates, in order.
+    pub fn languages(&self) -> Vec<&str> {
+        self.templates.keys().map(String::as_str).collect()
+    }
+
+    /// Add or replace the templates of `language`.
+    pub fn insert(&mut self, language: impl Into<String>, template: PromptTemplate) {
+        self.templates
+            .insert(language.into().to_lowercase(), template);
+    }
+
+    /// The templates of `language` (case-insensitive). A regional variant
+    /// such as `es-MX` or `pt_BR` falls back to its base language, and an
+    /// unknown language to English.
+    pub fn get(&self, language: &str) -> &PromptTemplate {
+        let language = language.to_lowercase();
+        let base = language.split(['-', '_']).next().unwrap_or_default();
+        self.templates
+            .get(&language)
+            .or_else(|| self.templates.get(base))
+            .or_else(|| self.templates.get(DEFAULT_LANGUAGE))
+            .expect("Prompt templates have no English entry.")
+    }
+}
+
+/// Global cached embedded templates.
+static DEFAULT_TEMPLATES: OnceLock<PromptTemplates> = OnceLock::new();
+
+/// The embedded templates, parsed once.
+pub fn default_prompt_templates() -> &'static PromptTemplates {
+    DEFAULT_TEMPLATES.get_or_init(PromptTemplates::embedded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_languages_fall_back_to_english() {
+        let templates = PromptTemplates::embedded();
+        assert_eq!(templates.languages(), vec!["de", "en", "es", "fr"]);
+        assert!(templates
+            .get("ES-mx")
+            .system
+            .contains("Responde siempre en español."));
+        assert_eq!(templates.get("ja"), templates.get("en"));
+
+        let tools = ["search".to_string(), "read".to_string()];
+        let system = templates
+            .get("es")
+            .system_prompt("Analista", "Analizar", "Experta", &tools);
+        assert!(system.starts_with("Eres Analista.\nExperta\n\nTu objetivo: Analizar"));
+        assert!(system.contains("Action: la acción a realizar, una de [search, read]"));
+        assert_eq!(templates.get("en").user_prompt("Summarize"), "Summarize");
+    }
+
+    #[test]
+    fn test_user_json_overrides_and_extends_defaults() {
+        let templates = PromptTemplates::from_json(
+            r#"{"es": {"tool_error": "{tool} falló: {error}."},
+                "it": {"user": "Compito: {input}"}}"#,
+        )
+        .unwrap();
+        let es = templates.get("es");
+        assert_eq!(es.tool_error("search", "timeout"), "search falló: timeout.");
+        assert!(es.is_tool_error("search", "search falló: timeout."));
+        assert!(!es.is_tool_error("read", "search falló: timeout."));
+        assert!(es.system.starts_with("Eres {role}."));
+
+        let it = templates.get("it");
+        assert_eq!(it.user_prompt("Riassumi"), "Compito: Riassumi");
+        assert_eq!(it.system, templates.get("en").system);
+        assert!(PromptTemplates::from_json("[]").is_err());
+
+        let agent = crate::agent::Agent::new("Analyst".into(), "Analyze".into(), "".into())
+            .i18n("it")
+            .with_prompt_templates(templates.clone());
+        assert_eq!(agent.prompt_template(), it);
+    }
+
+    #[test]
+    fn test_agent_custom_templates() {
+        let mut agent = crate::agent::Agent::new("Analyst".into(), "Find trends".into(), "".into());
+        agent.system_template = Some("<|system|>{{ .System }}\nStay on {goal}.<|end|>".to_string());
+        agent.prompt_template = Some("<|user|>{task} with [{tools}]<|end|>".to_string());
+        agent.response_template = Some("<|assistant|>{{ .Response }}<|end|>".to_string());
+
+        let tools = ["search".to_string()];
+        let prompt = agent.build_prompt("Summarize Q3", &tools);
+        let system = &prompt["system"];
+        assert!(system.starts_with("<|system|>You are Analyst."));
+        assert!(system.contains("Action: the action to take, one of [search]"));
+        assert!(system.ends_with("Stay on Find trends.<|end|>"));
+        assert_eq!(
+            prompt["user"],
+            "<|user|>Summarize Q3 with [search]<|end|>\n<|assistant|>"
+        );
+
+        agent.use_system_prompt = false;
+        agent.system_template = None;
+        agent.prompt_template = Some("{{ .Prompt }}".to_string());
+        agent.response_template = None;
+        let prompt = agent.build_prompt("Summarize Q3", &tools);
+        assert_eq!(prompt.len(), 1);
+        assert!(prompt["prompt"].ends_with("original input question\nSummarize Q3"));
+    }
+}
@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::utilities::i18n::I18N;
+use crate::utilities::perf::{self, HotPath};
 
 /// Result with only prompt field for standard mode.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,6 +89,7 @@ pub struct Prompts {
 impl Prompts {
     /// Generate a prompt result for task execution.
     pub fn task_execution(&self, agent: &AgentInfo) -> PromptResult {
+        let _span = perf::span(HotPath::MessageFormatting);
         let mut slices: Vec<PromptComponent> = vec![PromptComponent::RolePlaying];
 
         if self.has_tools {
@@ -0,0 +1,272 @@
+//! Planning by an agent before it executes a task.
+//!
+//! Corresponds to `crewai/utilities/reasoning_handler.py`.
+//!
+//! With `reasoning` enabled, an agent first asks its LLM for a plan for the
+//! task (the `reasoning.*` prompts of the translations) and whether it is
+//! ready to execute it. While it is not, the plan is refined, up to the
+//! agent's `max_reasoning_attempts`. The final plan is added to the task
+//! prompt.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::events::base_event::BaseEventData;
+use crate::events::types::reasoning_events::{
+    AgentReasoningCompletedEvent, AgentReasoningFailedEvent, AgentReasoningStartedEvent,
+};
+use crate::events::{BaseEvent, CREWAI_EVENT_BUS};
+use crate::llms::base_llm::LLMMessage;
+use crate::utilities::i18n::get_i18n;
+
+/// Statement with which the LLM declares a plan ready.
+pub const READY_MARKER: &str = "READY: I am ready to execute the task.";
+
+/// Reasoning attempts when the agent sets no `max_reasoning_attempts`.
+pub const DEFAULT_MAX_REASONING_ATTEMPTS: usize = 3;
+
+/// A plan for a task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReasoningPlan {
+    /// The plan, as the LLM wrote it.
+    pub plan: String,
+    /// Whether the agent declared itself ready to execute the task.
+    pub ready: bool,
+}
+
+/// Result of an agent's reasoning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentReasoningOutput {
+    /// The final plan.
+    pub plan: ReasoningPlan,
+    /// Plans created, the first included.
+    pub attempts: usize,
+}
+
+impl AgentReasoningOutput {
+    /// `task_prompt` with the plan appended.
+    pub fn apply_to(&self, task_prompt: &str) -> String {
+        format!("{}\n\nReasoning Plan:\n{}", task_prompt, self.plan.plan)
+    }
+}
+
+/// Plans a task for an agent.
+#[derive(Debug, Clone)]
+pub struct AgentReasoning {
+    /// Role of the agent.
+    pub role: String,
+    /// Goal of the agent.
+    pub goal: String,
+    /// Backstory of the agent.
+    pub backstory: String,
+    /// Tools available to the agent.
+    pub tools: Vec<String>,
+    /// What the task asks for.
+    pub description: String,
+    /// What the task should produce.
+    pub expected_output: String,
+    /// Most plans to create.
+    pub max_attempts: usize,
+    /// ID of the agent, for events.
+    pub agent_id: Option<String>,
+    /// ID of the task, for events.
+    pub task_id: Option<String>,
+}
+
+impl AgentReasoning {
+    /// Plan the task `description` for an agent.
+    pub fn new(
+        role: impl Into<String>,
+        goal: impl Into<String>,
+        backstory: impl Into<String>,
+        description: impl Into<String>,
+        expected_output: impl Into<String>,
+    ) -> Self {
+        Self {
+            role: role.into(),
+            goal: goal.into(),
+            backstory: backstory.into(),
+            tools: Vec::new(),
+            description: description.into(),
+            expected_output: expected_output.into(),
+            max_attempts: DEFAULT_MAX_REASONING_ATTEMPTS,
+            agent_id: None,
+            task_id: None,
+        }
+    }
+
+    /// Create a plan and refine it until the agent is ready or
+    /// `max_attempts` plans were made, calling the LLM with `call`.
+    ///
+    /// Emits `AgentReasoningStarted` for each attempt, then
+    /// `AgentReasoningCompleted`, or `AgentReasoningFailed` when a call
+    /// fails.
+    pub fn handle_agent_reasoning(
+        &self,
+        call: &mut dyn FnMut(Vec<LLMMessage>) -> Result<String, String>,
+    ) -> Result<AgentReasoningOutput, String> {
+        let mut plan = self.attempt(1, call, |reasoning| {
+            (
+                reasoning.prompt("initial_plan"),
+                reasoning.prompt("create_plan_prompt"),
+            )
+        })?;
+        let mut attempts = 1;
+        while !plan.ready && attempts < self.max_attempts.max(1) {
+            attempts += 1;
+            let current_plan = plan.plan;
+            plan = self.attempt(attempts, call, |reasoning| {
+                (
+                    reasoning.prompt("refine_plan"),
+                    reasoning
+                        .prompt("refine_plan_prompt")
+                        .replace("{current_plan}", &current_plan),
+                )
+            })?;
+        }
+
+        let mut completed = AgentReasoningCompletedEvent::new(
+            self.role.clone(),
+            String::new(),
+            attempts as i64,
+            plan.plan.clone(),
+            plan.ready,
+        );
+        self.tag(&mut completed.base);
+        self.emit(&mut completed);
+        Ok(AgentReasoningOutput { plan, attempts })
+    }
+
+    /// Create plan number `attempt` from the system and user prompts built
+    /// by `prompts`.
+    fn attempt(
+        &self,
+        attempt: usize,
+        call: &mut dyn FnMut(Vec<LLMMessage>) -> Result<String, String>,
+        prompts: impl FnOnce(&Self) -> (String, String),
+    ) -> Result<ReasoningPlan, String> {
+        let mut started =
+            AgentReasoningStartedEvent::new(self.role.clone(), String::new(), attempt as i64);
+        self.tag(&mut started.base);
+        self.emit(&mut started);
+
+        let (system, user) = prompts(self);
+        match call(vec![message("system", system), message("user", user)]) {
+            Ok(response) => Ok(parse_plan(&response)),
+            Err(e) => {
+                let mut failed = AgentReasoningFailedEvent::new(
+                    self.role.clone(),
+                    String::new(),
+                    attempt as i64,
+                    e.clone(),
+                );
+                self.tag(&mut failed.base);
+                self.emit(&mut failed);
+                Err(e)
+            }
+        }
+    }
+
+    /// The `reasoning.<key>` prompt, filled in for this agent and task.
+    fn prompt(&self, key: &str) -> String {
+        let tools = match self.tools.len() {
+            0 => "No tools available".to_string(),
+            _ => self.tools.join(", "),
+        };
+        get_i18n()
+            .retrieve("reasoning", key)
+            .replace("{role}", &self.role)
+            .replace("{goal}", &self.goal)
+            .replace("{backstory}", &self.backstory)
+            .replace("{tools}", &tools)
+            .replace("{expected_output}", &self.expected_output)
+            .replace("{description}", &self.description)
+    }
+
+    /// Attribute a reasoning event to the agent and task.
+    fn tag(&self, base: &mut BaseEventData) {
+        base.agent_id = self.agent_id.clone();
+        base.task_id = self.task_id.clone();
+        base.source_type = Some("agent".to_string());
+    }
+
+    fn emit<E: BaseEvent + 'static>(&self, event: &mut E) {
+        if let Some(bus) = CREWAI_EVENT_BUS.get() {
+            let source = self.agent_id.clone().unwrap_or_default();
+            bus.emit(std::sync::Arc::new(source), event);
+        }
+    }
+}
+
+/// The plan in an LLM response, ready if it contains [`READY_MARKER`].
+fn parse_plan(response: &str) -> ReasoningPlan {
+    let plan = response.trim();
+    if plan.is_empty() {
+        return ReasoningPlan {
+            plan: "No plan was generated.".to_string(),
+            ready: false,
+        };
+    }
+    ReasoningPlan {
+        plan: plan.to_string(),
+        ready: plan.contains(READY_MARKER),
+    }
+}
+
+fn message(role: &str, content: String) -> LLMMessage {
+    HashMap::from([
+        ("role".to_string(), Value::String(role.to_string())),
+        ("content".to_string(), Value::String(content)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_is_refined_until_ready() {
+        let mut reasoning = AgentReasoning::new(
+            "Analyst",
+            "Find trends",
+            "Ten years in retail",
+            "Analyze Q3 sales",
+            "A report",
+        );
+        reasoning.tools = vec!["search".to_string()];
+
+        let mut prompts = Vec::new();
+        let mut replies = vec![
+            format!("1. Gather data\n{}", READY_MARKER),
+            "1. Read\nNOT READY: I need to refine my plan because data is missing.".to_string(),
+        ];
+        let mut call = |messages: Vec<LLMMessage>| {
+            prompts.push(messages[1]["content"].as_str().unwrap().to_string());
+            Ok(replies.pop().unwrap())
+        };
+        let output = reasoning.handle_agent_reasoning(&mut call).unwrap();
+
+        assert_eq!(output.attempts, 2);
+        assert!(output.plan.ready);
+        assert!(prompts[0].contains("following task:\nAnalyze Q3 sales"));
+        assert!(prompts[0].contains("Available tools: search"));
+        assert!(prompts[1].contains("following plan for this task:\n1. Read\nNOT READY"));
+        assert_eq!(
+            output.apply_to("Analyze Q3 sales"),
+            format!(
+                "Analyze Q3 sales\n\nReasoning Plan:\n1. Gather data\n{}",
+                READY_MARKER
+            )
+        );
+
+        reasoning.max_attempts = 1;
+        let mut call = |_: Vec<LLMMessage>| Ok("NOT READY: unsure.".to_string());
+        let output = reasoning.handle_agent_reasoning(&mut call).unwrap();
+        assert_eq!((output.attempts, output.plan.ready), (1, false));
+
+        let mut call = |_: Vec<LLMMessage>| Err("rate limited".to_string());
+        assert!(reasoning.handle_agent_reasoning(&mut call).is_err());
+    }
+}
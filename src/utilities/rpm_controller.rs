@@ -3,21 +3,96 @@
 //! Corresponds to `crewai/utilities/rpm_controller.py`.
 //!
 //! Manages requests-per-minute (RPM) limiting to respect API rate limits.
+//! The limit is enforced with a token bucket holding up to `max_rpm`
+//! requests and refilling at `max_rpm` per minute, so short bursts are
+//! allowed while the sustained rate stays within the limit. Clones share
+//! the bucket: one controller handed to several agents (or to concurrent
+//! tasks of one agent) enforces a single budget across all of them.
 
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::Arc;
-use std::thread;
-use std::time::Duration;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
 use crate::utilities::logger::Logger;
 
+/// Length of the window counted by [`RPMController::current_rpm`].
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Token bucket state shared by the clones of a controller.
+#[derive(Debug)]
+struct Bucket {
+    /// Requests that may be made right away.
+    tokens: f64,
+    /// When `tokens` was last refilled.
+    refilled_at: Instant,
+    /// When the current minute window started.
+    window_started: Instant,
+    /// Requests made in the current minute window.
+    window_requests: i32,
+    /// Requests that had to wait for the budget.
+    throttled: u64,
+    /// Set by [`RPMController::stop_rpm_counter`]: requests no longer wait.
+    stopped: bool,
+}
+
+impl Bucket {
+    fn new(capacity: f64) -> Self {
+        let now = Instant::now();
+        Self {
+            tokens: capacity,
+            refilled_at: now,
+            window_started: now,
+            window_requests: 0,
+            throttled: 0,
+            stopped: false,
+        }
+    }
+
+    /// Start a new minute window if the current one is over.
+    fn roll_window(&mut self, now: Instant) {
+        if now.duration_since(self.window_started) >= WINDOW {
+            self.window_started = now;
+            self.window_requests = 0;
+        }
+    }
+
+    /// Count a request made at `now`.
+    fn count(&mut self, now: Instant) {
+        self.roll_window(now);
+        self.window_requests = self.window_requests.saturating_add(1);
+    }
+}
+
+/// State shared by the clones of a controller.
+#[derive(Debug)]
+struct Shared {
+    bucket: Mutex<Bucket>,
+    /// Notified when the controller is stopped, waking waiting requests.
+    stopped: Condvar,
+}
+
+impl Default for Shared {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl Shared {
+    fn new(capacity: f64) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(capacity)),
+            stopped: Condvar::new(),
+        }
+    }
+}
+
 /// Manages requests per minute limiting.
 ///
-/// When `max_rpm` is set, the controller tracks the number of requests
-/// made in the current minute and blocks when the limit is reached.
-/// A background timer resets the counter every 60 seconds.
+/// When `max_rpm` is set, each request takes a token from a bucket of
+/// `max_rpm` tokens refilled continuously over a minute; a request finding
+/// the bucket empty waits until a token is available. Without a limit,
+/// requests never wait.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RPMController {
     /// Maximum requests per minute. If `None`, no limit is applied.
@@ -27,109 +102,176 @@ pub struct RPMController {
     pub logger: Logger,
 
     // ---- Internal state (not serialized) ----
-    /// Current request count in this minute window.
+    /// Token bucket and stop flag, shared between clones.
     #[serde(skip)]
-    current_rpm: Arc<AtomicI32>,
-    /// Flag to signal shutdown of the background timer.
-    #[serde(skip)]
-    shutdown_flag: Arc<AtomicBool>,
+    shared: Arc<Shared>,
 }
 
 impl Default for RPMController {
     fn default() -> Self {
-        Self {
-            max_rpm: None,
-            logger: Logger::new(false),
-            current_rpm: Arc::new(AtomicI32::new(0)),
-            shutdown_flag: Arc::new(AtomicBool::new(false)),
-        }
+        Self::new(None)
     }
 }
 
 impl RPMController {
     /// Create a new `RPMController` with the given RPM limit.
     ///
-    /// If `max_rpm` is `Some`, a background timer is started to reset
-    /// the request counter every 60 seconds.
+    /// A limit of zero or less is treated as no limit. The bucket starts
+    /// full.
     pub fn new(max_rpm: Option<i32>) -> Self {
-        let controller = Self {
+        let max_rpm = max_rpm.filter(|&rpm| rpm > 0);
+        Self {
             max_rpm,
             logger: Logger::new(false),
-            current_rpm: Arc::new(AtomicI32::new(0)),
-            shutdown_flag: Arc::new(AtomicBool::new(false)),
-        };
-
-        if max_rpm.is_some() {
-            controller.start_reset_timer();
+            shared: Arc::new(Shared::new(max_rpm.unwrap_or(0) as f64)),
         }
+    }
 
-        controller
+    fn bucket(&self) -> MutexGuard<'_, Bucket> {
+        self.shared.bucket.lock().unwrap_or_else(|e| e.into_inner())
     }
 
-    /// Check if a new request can be made, waiting if the RPM limit is reached.
+    /// Take a request from the budget without waiting.
     ///
-    /// Returns `true` if the request was counted successfully.
-    /// If the limit is reached, this method blocks for 60 seconds until the
-    /// next minute window, then resets the counter and allows the request.
-    pub fn check_or_wait(&self) -> bool {
+    /// Returns how long to wait before retrying when the budget is
+    /// exhausted.
+    pub fn try_acquire(&self) -> Result<(), Duration> {
         let max = match self.max_rpm {
-            Some(max) => max,
-            None => return true,
+            Some(max) if max > 0 => max as f64,
+            _ => return Ok(()),
         };
+        let mut bucket = self.bucket();
+        let now = Instant::now();
+        if bucket.stopped {
+            bucket.count(now);
+            return Ok(());
+        }
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * max / 60.0).min(max);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.count(now);
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) * 60.0 / max))
+        }
+    }
 
-        let current = self.current_rpm.fetch_add(1, Ordering::SeqCst);
-        if current < max {
-            return true;
+    /// Take a request from the budget, waiting as long as needed.
+    ///
+    /// `on_throttle` is called once, with the first expected wait, when the
+    /// request has to wait. Returns the total time waited.
+    pub fn acquire(&self, on_throttle: impl FnOnce(Duration)) -> Duration {
+        let Err(mut wait) = self.try_acquire() else {
+            return Duration::ZERO;
+        };
+        let started = Instant::now();
+        let mut on_throttle = Some(on_throttle);
+        loop {
+            if let Some(on_throttle) = on_throttle.take() {
+                self.bucket().throttled += 1;
+                log::info!(
+                    target: "crewai::rpm",
+                    "Max RPM ({}) reached, waiting {:.1}s for the next request",
+                    self.max_rpm.unwrap_or_default(),
+                    wait.as_secs_f64()
+                );
+                self.logger.log(
+                    "info",
+                    "Max RPM reached, waiting for the request budget to refill.",
+                    None,
+                );
+                on_throttle(wait);
+            }
+            // Sleep until the budget refills, or until the controller is
+            // stopped.
+            let bucket = self.bucket();
+            if !bucket.stopped {
+                drop(
+                    self.shared
+                        .stopped
+                        .wait_timeout_while(bucket, wait, |bucket| !bucket.stopped)
+                        .unwrap_or_else(|e| e.into_inner()),
+                );
+            }
+            match self.try_acquire() {
+                Ok(()) => return started.elapsed(),
+                Err(next) => wait = next,
+            }
         }
+    }
 
-        // Max RPM reached, wait for next minute
-        self.logger.log(
-            "info",
-            "Max RPM reached, waiting for next minute to start.",
-            None,
-        );
-        self.wait_for_next_minute();
-        self.current_rpm.store(1, Ordering::SeqCst);
+    /// Check if a new request can be made, waiting if the RPM limit is reached.
+    ///
+    /// Returns `true` once the request was counted.
+    pub fn check_or_wait(&self) -> bool {
+        self.acquire(|_| {});
         true
     }
 
-    /// Stop the RPM counter and signal background timer to shut down.
+    /// Stop limiting: waiting and later requests of this controller and
+    /// its clones go ahead right away.
     pub fn stop_rpm_counter(&self) {
-        self.shutdown_flag.store(true, Ordering::SeqCst);
+        self.bucket().stopped = true;
+        self.shared.stopped.notify_all();
     }
 
-    /// Get the current request count.
+    /// Requests made through this controller (and its clones) in the
+    /// current minute window.
     pub fn current_rpm(&self) -> i32 {
-        self.current_rpm.load(Ordering::SeqCst)
+        let mut bucket = self.bucket();
+        bucket.roll_window(Instant::now());
+        bucket.window_requests
     }
 
-    /// Wait for the next minute window (blocks for 60 seconds).
-    fn wait_for_next_minute(&self) {
-        thread::sleep(Duration::from_secs(60));
-        self.current_rpm.store(0, Ordering::SeqCst);
+    /// Requests that had to wait for the budget.
+    pub fn throttled(&self) -> u64 {
+        self.bucket().throttled
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    /// Start a background daemon thread that resets the request counter
-    /// every 60 seconds.
-    fn start_reset_timer(&self) {
-        let current_rpm = Arc::clone(&self.current_rpm);
-        let shutdown_flag = Arc::clone(&self.shutdown_flag);
+    #[test]
+    fn test_bucket_shared_between_clones() {
+        let controller = RPMController::new(Some(600));
+        let shared = controller.clone();
+        for _ in 0..600 {
+            assert!(shared.try_acquire().is_ok());
+        }
+        // 600 rpm refills one request every 100ms.
+        let wait = controller.try_acquire().unwrap_err();
+        assert!(wait <= Duration::from_millis(100), "{:?}", wait);
+
+        let mut throttled = None;
+        let waited = controller.acquire(|wait| throttled = Some(wait));
+        assert!(throttled.is_some());
+        assert!(waited > Duration::ZERO);
+        assert_eq!(shared.current_rpm(), 601);
+        assert_eq!(shared.throttled(), 1);
 
-        thread::Builder::new()
-            .name("rpm-controller-timer".to_string())
-            .spawn(move || loop {
-                thread::sleep(Duration::from_secs(60));
-                if shutdown_flag.load(Ordering::SeqCst) {
-                    break;
-                }
-                current_rpm.store(0, Ordering::SeqCst);
-            })
-            .expect("Failed to spawn RPM controller timer thread");
+        let unlimited = RPMController::new(Some(0));
+        assert_eq!(unlimited.max_rpm, None);
+        assert_eq!(unlimited.acquire(|_| panic!("throttled")), Duration::ZERO);
     }
-}
 
-impl Drop for RPMController {
-    fn drop(&mut self) {
-        self.stop_rpm_counter();
+    #[test]
+    fn test_stop_wakes_waiting_requests() {
+        let controller = RPMController::new(Some(1));
+        controller.try_acquire().unwrap();
+        let waiting = std::thread::spawn({
+            let controller = controller.clone();
+            move || controller.acquire(|_| {})
+        });
+        std::thread::sleep(Duration::from_millis(50));
+        controller.stop_rpm_counter();
+        // Woken long before the minute a 1 rpm budget takes to refill.
+        assert!(waiting.join().unwrap() < Duration::from_secs(10));
+        assert!(controller.try_acquire().is_ok());
+        assert_eq!(controller.current_rpm(), 3);
+        assert_eq!(controller.max_rpm, Some(1));
     }
 }
@@ -7,8 +7,9 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static VARIABLE_PATTERN: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_\-]*)\}").unwrap());
+static VARIABLE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\{\{([A-Za-z_][A-Za-z0-9_\-]*)\}\}|\{([A-Za-z_][A-Za-z0-9_\-]*)\}").unwrap()
+});
 static QUOTE_PATTERN: Lazy<Regex> = Lazy::new(|| Regex::new(r#"['"]+"#).unwrap());
 static CAMEL_LOWER_UPPER: Lazy<Regex> = Lazy::new(|| Regex::new(r"([a-z])([A-Z])").unwrap());
 static CAMEL_UPPER_LOWER: Lazy<Regex> = Lazy::new(|| Regex::new(r"([A-Z]+)([A-Z][a-z])").unwrap());
@@ -63,7 +64,8 @@ pub fn sanitize_tool_name(name: &str, max_length: Option<usize>) -> String {
 ///
 /// Only interpolates placeholders that follow the pattern `{variable_name}` where
 /// `variable_name` starts with a letter/underscore and contains only alphanumeric chars,
-/// underscores, and hyphens.
+/// underscores, and hyphens. A doubled placeholder (`{{variable_name}}`) is an
+/// escape and becomes the literal text `{variable_name}`.
 ///
 /// # Arguments
 /// * `input_string` - The string containing template variables.
@@ -80,40 +82,45 @@ pub fn interpolate_only(
         _ => return Ok(String::new()),
     };
 
-    if !input.contains('{') && !input.contains('}') {
+    if !input.contains('{') {
         return Ok(input.to_string());
     }
 
-    if inputs.is_empty() {
-        return Err("Inputs dictionary cannot be empty when interpolating variables".to_string());
+    let mut result = String::with_capacity(input.len());
+    let mut last = 0;
+    for cap in VARIABLE_PATTERN.captures_iter(input) {
+        let whole = cap.get(0).unwrap();
+        result.push_str(&input[last..whole.start()]);
+        last = whole.end();
+        if let Some(escaped) = cap.get(1) {
+            result.push('{');
+            result.push_str(escaped.as_str());
+            result.push('}');
+            continue;
+        }
+        let var = &cap[2];
+        let value = inputs
+            .get(var)
+            .ok_or_else(|| format!("Template variable '{}' not found in inputs dictionary", var))?;
+        result.push_str(value);
     }
+    result.push_str(&input[last..]);
 
-    let variables: Vec<String> = VARIABLE_PATTERN
-        .captures_iter(input)
-        .map(|cap| cap[1].to_string())
-        .collect();
-
-    // Check for missing variables
-    let missing: Vec<&String> = variables
-        .iter()
-        .filter(|v| !inputs.contains_key(*v))
-        .collect();
-    if !missing.is_empty() {
-        return Err(format!(
-            "Template variable '{}' not found in inputs dictionary",
-            missing[0]
-        ));
-    }
+    Ok(result)
+}
 
-    let mut result = input.to_string();
-    for var in &variables {
-        if let Some(value) = inputs.get(var) {
-            let placeholder = format!("{{{}}}", var);
-            result = result.replace(&placeholder, value);
+/// Names of the template variables (`{key}`) in a string, in order of first
+/// appearance. Escaped placeholders (`{{key}}`) are not variables.
+pub fn template_variables(input: &str) -> Vec<String> {
+    let mut variables: Vec<String> = Vec::new();
+    for cap in VARIABLE_PATTERN.captures_iter(input) {
+        if let Some(var) = cap.get(2) {
+            if !variables.iter().any(|v| v == var.as_str()) {
+                variables.push(var.as_str().to_string());
+            }
         }
     }
-
-    Ok(result)
+    variables
 }
 
 #[cfg(test)]
@@ -138,10 +145,33 @@ mod tests {
         assert_eq!(result, "Hello Alice!");
     }
 
+    #[test]
+    fn test_template_variables() {
+        assert_eq!(
+            template_variables("Write about {topic} for {audience}, not {{raw}} {topic}"),
+            vec!["topic", "audience"]
+        );
+        assert!(template_variables("{\"json\": true}").is_empty());
+    }
+
     #[test]
     fn test_interpolate_only_missing_var() {
         let inputs = HashMap::new();
         let result = interpolate_only(Some("Hello {name}!"), &inputs);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_interpolate_only_escapes_and_json() {
+        let inputs = HashMap::from([("name".to_string(), "Alice".to_string())]);
+        let result = interpolate_only(
+            Some(r#"Hi {name}, write {{name}} literally. Reply as {"name": "x"}"#),
+            &inputs,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            r#"Hi Alice, write {name} literally. Reply as {"name": "x"}"#
+        );
+    }
 }
@@ -1,15 +1,32 @@
 //! Training data handler.
 //!
 //! Corresponds to `crewai/utilities/training_handler.py`.
+//!
+//! [`Crew::train`](crate::crew::Crew::train) records every task output and
+//! the human feedback on it in a training data file, keyed by agent role.
+//! While an agent is trained, that feedback is added to its task prompts.
+//! Once training ends, the feedback is distilled into suggestions saved in
+//! the trained agents file ([`TRAINED_AGENTS_DATA_FILE`] by default), which
+//! agents follow from then on. Both files are JSON, where Python pickles.
+
+use std::path::Path;
 
 use serde_json::Value;
 
 use crate::utilities::file_handler::FileHandler;
 
+/// Default training data file.
+pub const TRAINING_DATA_FILE: &str = "training_data.json";
+
+/// Default file of the suggestions distilled from training, keyed by agent
+/// role.
+pub const TRAINED_AGENTS_DATA_FILE: &str = "trained_agents_data.json";
+
 /// Handles loading and saving crew training data.
 #[derive(Debug, Clone)]
 pub struct CrewTrainingHandler {
     file_handler: FileHandler,
+    filename: String,
 }
 
 impl CrewTrainingHandler {
@@ -17,17 +34,34 @@ impl CrewTrainingHandler {
     pub fn new(directory: impl Into<String>) -> Self {
         Self {
             file_handler: FileHandler::new(directory),
+            filename: TRAINING_DATA_FILE.to_string(),
+        }
+    }
+
+    /// Create a handler for the training data file at `path`.
+    pub fn for_file(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let directory = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map_or_else(|| ".".to_string(), |dir| dir.to_string_lossy().to_string());
+        Self {
+            file_handler: FileHandler::new(directory),
+            filename: path.file_name().map_or_else(
+                || TRAINING_DATA_FILE.into(),
+                |name| name.to_string_lossy().to_string(),
+            ),
         }
     }
 
     /// Load training data from disk.
     pub fn load(&self) -> Option<Value> {
-        self.file_handler.load("training_data.json")
+        self.file_handler.load(&self.filename)
     }
 
     /// Save training data to disk.
     pub fn save(&self, data: &Value) -> std::io::Result<()> {
-        self.file_handler.save("training_data.json", data)
+        self.file_handler.save(&self.filename, data)
     }
 
     /// Append a new training entry.
@@ -47,6 +81,55 @@ impl CrewTrainingHandler {
 
         self.save(&training_data)
     }
+
+    /// Training entries recorded for `agent_id`, oldest first.
+    pub fn entries(&self, agent_id: &str) -> Vec<Value> {
+        match self.load() {
+            Some(Value::Object(mut map)) => match map.remove(agent_id) {
+                Some(Value::Array(entries)) => entries,
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    /// Human feedback recorded for `agent_id`, skipping empty feedback.
+    pub fn human_feedbacks(&self, agent_id: &str) -> Vec<String> {
+        self.entries(agent_id)
+            .iter()
+            .filter_map(|entry| entry["human_feedback"].as_str())
+            .map(str::trim)
+            .filter(|feedback| !feedback.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Save the trained data of `agent_id`, replacing any it had.
+    pub fn save_trained_data(&self, agent_id: &str, trained_data: Value) -> std::io::Result<()> {
+        let mut data = match self.load() {
+            Some(Value::Object(map)) => map,
+            _ => Default::default(),
+        };
+        data.insert(agent_id.to_string(), trained_data);
+        self.save(&Value::Object(data))
+    }
+
+    /// Suggestions in the trained data of `agent_id`.
+    pub fn suggestions(&self, agent_id: &str) -> Vec<String> {
+        let Some(data) = self.load() else {
+            return Vec::new();
+        };
+        data[agent_id]["suggestions"]
+            .as_array()
+            .map(|suggestions| {
+                suggestions
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
 }
 
 impl Default for CrewTrainingHandler {